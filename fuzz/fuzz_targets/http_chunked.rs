@@ -0,0 +1,33 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tokio::io::AsyncReadExt;
+
+use g3_http::HttpBodyDecodeReader;
+
+fuzz_target!(|data: &[u8]| {
+    g3_fuzz::block_on(async {
+        let mut reader = data;
+        let mut body_reader = HttpBodyDecodeReader::new_chunked(&mut reader, 1024);
+        let mut body = Vec::new();
+        if body_reader.read_to_end(&mut body).await.is_ok() {
+            let _ = body_reader.trailer(4096).await;
+        }
+    })
+});
@@ -0,0 +1,28 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use g3_http::{HttpChunkedLine, HttpHeaderLine, HttpMethodLine, HttpStatusLine};
+
+fuzz_target!(|data: &[u8]| {
+    let _ = HttpMethodLine::parse(data);
+    let _ = HttpStatusLine::parse(data);
+    let _ = HttpHeaderLine::parse(data);
+    let _ = HttpChunkedLine::parse(data);
+});
@@ -0,0 +1,47 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tokio::io::AsyncReadExt;
+
+use g3_http::server::HttpProxyClientRequest;
+use g3_http::{HttpBodyDecodeReader, HttpBodyType};
+
+fuzz_target!(|data: &[u8]| {
+    g3_fuzz::block_on(async {
+        let mut reader = data;
+        let mut version = http::Version::HTTP_11;
+        let Ok(req) = HttpProxyClientRequest::parse_basic(&mut reader, 4096, &mut version).await
+        else {
+            return;
+        };
+
+        let mut body_reader = match req.body_type() {
+            Some(HttpBodyType::ContentLength(len)) => {
+                HttpBodyDecodeReader::new_fixed_length(&mut reader, len)
+            }
+            Some(HttpBodyType::Chunked) => HttpBodyDecodeReader::new_chunked(&mut reader, 1024),
+            Some(HttpBodyType::ReadUntilEnd) => {
+                HttpBodyDecodeReader::new_read_until_end(&mut reader)
+            }
+            None => return,
+        };
+        let mut body = Vec::new();
+        let _ = body_reader.read_to_end(&mut body).await;
+    })
+});
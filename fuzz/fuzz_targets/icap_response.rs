@@ -0,0 +1,36 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use g3_icap_client::fuzz;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((method, data)) = data.split_first() else {
+        return;
+    };
+
+    g3_fuzz::block_on(async {
+        let mut reader = data;
+        let _ = match method % 3 {
+            0 => fuzz::parse_options_response(&mut reader, 4096).await,
+            1 => fuzz::parse_reqmod_response(&mut reader, 4096).await,
+            _ => fuzz::parse_respmod_response(&mut reader, 4096).await,
+        };
+    })
+});
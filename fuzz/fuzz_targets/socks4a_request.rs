@@ -0,0 +1,29 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use g3_socks::v4a::SocksV4aRequest;
+
+fuzz_target!(|data: &[u8]| {
+    g3_fuzz::block_on(async {
+        // the version code should have already been read
+        let mut reader = data;
+        let _ = SocksV4aRequest::recv(&mut reader).await;
+    })
+});
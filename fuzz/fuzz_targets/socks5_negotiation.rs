@@ -0,0 +1,49 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tokio::io::AsyncReadExt;
+
+use g3_socks::v5::{auth, Socks5Request};
+use g3_socks::SocksAuthMethod;
+
+// the same negotiation steps as in the g3proxy socks server
+fuzz_target!(|data: &[u8]| {
+    g3_fuzz::block_on(async {
+        let mut reader = data;
+        let Ok(version) = reader.read_u8().await else {
+            return;
+        };
+        if version != 0x05 {
+            return;
+        }
+
+        let Ok(methods) = auth::recv_methods_from_client(&mut reader).await else {
+            return;
+        };
+        if methods.contains(&SocksAuthMethod::User)
+            && auth::recv_user_from_client(&mut reader).await.is_err()
+        {
+            return;
+        }
+
+        if let Ok(req) = Socks5Request::recv(&mut reader).await {
+            let _ = req.udp_peer_addr();
+        }
+    })
+});
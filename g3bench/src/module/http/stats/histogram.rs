@@ -23,6 +23,9 @@ use g3_types::ext::DurationExt;
 use crate::target::BenchHistogram;
 
 pub(crate) struct HttpHistogram {
+    dns_time: Option<Duration>,
+    tcp_connect_time: KeepingHistogram<u64>,
+    tls_handshake_time: KeepingHistogram<u64>,
     send_hdr_time: KeepingHistogram<u64>,
     recv_hdr_time: KeepingHistogram<u64>,
     total_time: KeepingHistogram<u64>,
@@ -31,17 +34,24 @@ pub(crate) struct HttpHistogram {
 
 impl HttpHistogram {
     pub(crate) fn new() -> (Self, HttpHistogramRecorder) {
+        let (tcp_connect_time_h, tcp_connect_time_r) = KeepingHistogram::new();
+        let (tls_handshake_time_h, tls_handshake_time_r) = KeepingHistogram::new();
         let (send_hdr_time_h, send_hdr_time_r) = KeepingHistogram::new();
         let (recv_hdr_time_h, recv_hdr_time_r) = KeepingHistogram::new();
         let (total_time_h, total_time_r) = KeepingHistogram::new();
         let (conn_reuse_count_h, conn_reuse_count_r) = KeepingHistogram::new();
         let h = HttpHistogram {
+            dns_time: None,
+            tcp_connect_time: tcp_connect_time_h,
+            tls_handshake_time: tls_handshake_time_h,
             send_hdr_time: send_hdr_time_h,
             recv_hdr_time: recv_hdr_time_h,
             total_time: total_time_h,
             conn_reuse_count: conn_reuse_count_h,
         };
         let r = HttpHistogramRecorder {
+            tcp_connect_time: tcp_connect_time_r,
+            tls_handshake_time: tls_handshake_time_r,
             send_hdr_time: send_hdr_time_r,
             recv_hdr_time: recv_hdr_time_r,
             total_time: total_time_r,
@@ -49,10 +59,16 @@ impl HttpHistogram {
         };
         (h, r)
     }
+
+    pub(crate) fn set_dns_time(&mut self, dur: Duration) {
+        self.dns_time = Some(dur);
+    }
 }
 
 impl BenchHistogram for HttpHistogram {
     fn refresh(&mut self) {
+        self.tcp_connect_time.refresh().unwrap();
+        self.tls_handshake_time.refresh().unwrap();
         self.send_hdr_time.refresh().unwrap();
         self.recv_hdr_time.refresh().unwrap();
         self.total_time.refresh().unwrap();
@@ -60,6 +76,15 @@ impl BenchHistogram for HttpHistogram {
     }
 
     fn emit(&self, client: &mut StatsdClient) {
+        if let Some(dur) = self.dns_time {
+            client.gauge("http.time.dns", dur.as_nanos_u64()).send();
+        }
+        self.emit_phase_histogram(client, self.tcp_connect_time.inner(), "http.time.connect");
+        self.emit_phase_histogram(
+            client,
+            self.tls_handshake_time.inner(),
+            "http.time.tls_handshake",
+        );
         self.emit_histogram(client, self.send_hdr_time.inner(), "http.time.send_hdr");
         self.emit_histogram(client, self.recv_hdr_time.inner(), "http.time.recv_hdr");
         self.emit_histogram(client, self.total_time.inner(), "http.time.total");
@@ -68,7 +93,10 @@ impl BenchHistogram for HttpHistogram {
     fn summary(&self) {
         Self::summary_histogram_title("# Connection Re-Usage:");
         Self::summary_data_line("Req/Conn:", self.conn_reuse_count.inner());
+        Self::summary_resolve_time(self.dns_time);
         Self::summary_histogram_title("# Duration Times");
+        Self::summary_phase_duration_line("Connect:", self.tcp_connect_time.inner());
+        Self::summary_phase_duration_line("TlsHs:", self.tls_handshake_time.inner());
         Self::summary_duration_line("SendHdr:", self.send_hdr_time.inner());
         Self::summary_duration_line("RecvHdr:", self.recv_hdr_time.inner());
         Self::summary_duration_line("Total:", self.total_time.inner());
@@ -79,6 +107,8 @@ impl BenchHistogram for HttpHistogram {
 
 #[derive(Clone)]
 pub(crate) struct HttpHistogramRecorder {
+    tcp_connect_time: HistogramRecorder<u64>,
+    tls_handshake_time: HistogramRecorder<u64>,
     send_hdr_time: HistogramRecorder<u64>,
     recv_hdr_time: HistogramRecorder<u64>,
     total_time: HistogramRecorder<u64>,
@@ -86,6 +116,14 @@ pub(crate) struct HttpHistogramRecorder {
 }
 
 impl HttpHistogramRecorder {
+    pub(crate) fn record_tcp_connect_time(&mut self, dur: Duration) {
+        let _ = self.tcp_connect_time.record(dur.as_nanos_u64());
+    }
+
+    pub(crate) fn record_tls_handshake_time(&mut self, dur: Duration) {
+        let _ = self.tls_handshake_time.record(dur.as_nanos_u64());
+    }
+
     pub(crate) fn record_send_hdr_time(&mut self, dur: Duration) {
         let _ = self.send_hdr_time.record(dur.as_nanos_u64());
     }
@@ -22,5 +22,6 @@ pub(crate) mod http;
 
 pub(crate) mod openssl;
 pub(crate) mod rustls;
+pub(crate) mod tls_identity;
 
 pub(crate) mod ssl;
@@ -16,6 +16,7 @@
 
 use std::fs::File;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use anyhow::{anyhow, Context};
 use clap::{value_parser, Arg, ArgAction, ArgMatches, Command, ValueHint};
@@ -30,9 +31,15 @@ use g3_types::net::{
     RustlsClientConfigBuilder, UpstreamAddr,
 };
 
+use super::tls_identity::ClientIdentity;
+
 const TLS_ARG_CA_CERT: &str = "tls-ca-cert";
 const TLS_ARG_CERT: &str = "tls-cert";
 const TLS_ARG_KEY: &str = "tls-key";
+const TLS_ARG_CERT_CHAIN: &str = "tls-cert-chain";
+const TLS_ARG_PKCS12: &str = "tls-pkcs12";
+const TLS_ARG_PKCS12_PASSWORD: &str = "tls-pkcs12-password";
+const TLS_ARG_CLIENT_IDENTITY_DIR: &str = "tls-client-identity-dir";
 const TLS_ARG_NAME: &str = "tls-name";
 const TLS_ARG_NO_SESSION_CACHE: &str = "tls-no-session-cache";
 const TLS_ARG_NO_SNI: &str = "tls-no-sni";
@@ -40,6 +47,9 @@ const TLS_ARG_NO_SNI: &str = "tls-no-sni";
 const PROXY_TLS_ARG_CA_CERT: &str = "proxy-tls-ca-cert";
 const PROXY_TLS_ARG_CERT: &str = "proxy-tls-cert";
 const PROXY_TLS_ARG_KEY: &str = "proxy-tls-key";
+const PROXY_TLS_ARG_CERT_CHAIN: &str = "proxy-tls-cert-chain";
+const PROXY_TLS_ARG_PKCS12: &str = "proxy-tls-pkcs12";
+const PROXY_TLS_ARG_PKCS12_PASSWORD: &str = "proxy-tls-pkcs12-password";
 const PROXY_TLS_ARG_NAME: &str = "proxy-tls-name";
 const PROXY_TLS_ARG_NO_SESSION_CACHE: &str = "proxy-tls-no-session-cache";
 const PROXY_TLS_ARG_NO_SNI: &str = "proxy-tls-no-sni";
@@ -57,9 +67,25 @@ pub(crate) struct RustlsTlsClientArgs {
     pub(crate) tls_name: Option<ServerName<'static>>,
     pub(crate) cert_pair: Option<RustlsCertificatePair>,
     pub(crate) alpn_protocol: Option<AlpnProtocol>,
+    pub(crate) identity_cert_pairs: Vec<RustlsCertificatePair>,
+    pub(crate) identity_clients: Vec<RustlsClientConfig>,
+    pub(crate) next_identity: AtomicUsize,
 }
 
 impl RustlsTlsClientArgs {
+    /// Get the tls client config for a new task context.
+    ///
+    /// If client identities are loaded from a directory, each call will get the next one in turn.
+    pub(crate) fn fetch_client(&self) -> Option<RustlsClientConfig> {
+        if self.identity_clients.is_empty() {
+            return self.client.clone();
+        }
+        let i = self.next_identity.fetch_add(1, Ordering::Relaxed);
+        self.identity_clients
+            .get(i % self.identity_clients.len())
+            .cloned()
+    }
+
     pub(crate) async fn connect_target<S>(
         &self,
         tls_client: &RustlsClientConfig,
@@ -111,15 +137,23 @@ impl RustlsTlsClientArgs {
         &mut self,
         args: &ArgMatches,
         cert_id: &str,
+        chain_id: &str,
         key_id: &str,
     ) -> anyhow::Result<()> {
         let mut set_cert_pair = false;
         let mut cert_pair_builder = RustlsCertificatePairBuilder::default();
         if let Some(file) = args.get_one::<PathBuf>(cert_id) {
-            let certs = load_certs(file).context(format!(
+            let mut certs = load_certs(file).context(format!(
                 "failed to load client certificate from file {}",
                 file.display()
             ))?;
+            if let Some(file) = args.get_one::<PathBuf>(chain_id) {
+                let chain = load_certs(file).context(format!(
+                    "failed to load client certificate chain from file {}",
+                    file.display()
+                ))?;
+                certs.extend(chain);
+            }
             cert_pair_builder.set_certs(certs);
             set_cert_pair = true;
         }
@@ -140,6 +174,42 @@ impl RustlsTlsClientArgs {
         Ok(())
     }
 
+    fn parse_client_pkcs12(
+        &mut self,
+        args: &ArgMatches,
+        pkcs12_id: &str,
+        password_id: &str,
+    ) -> anyhow::Result<()> {
+        if let Some(file) = args.get_one::<PathBuf>(pkcs12_id) {
+            let password = args
+                .get_one::<String>(password_id)
+                .map(|s| s.as_str())
+                .unwrap_or_default();
+            let identity = ClientIdentity::load_pkcs12(file, password)?;
+            self.cert_pair = Some(identity.rustls_cert_pair()?);
+        }
+        Ok(())
+    }
+
+    fn parse_client_identity_dir(
+        &mut self,
+        args: &ArgMatches,
+        dir_id: &str,
+        password_id: &str,
+    ) -> anyhow::Result<()> {
+        if let Some(dir) = args.get_one::<PathBuf>(dir_id) {
+            let password = args
+                .get_one::<String>(password_id)
+                .map(|s| s.as_str())
+                .unwrap_or_default();
+            let identities = ClientIdentity::load_dir(dir, password)?;
+            for identity in identities {
+                self.identity_cert_pairs.push(identity.rustls_cert_pair()?);
+            }
+        }
+        Ok(())
+    }
+
     fn parse_no_session_cache(&mut self, args: &ArgMatches, id: &str) -> anyhow::Result<()> {
         let tls_config = self
             .config
@@ -167,19 +237,22 @@ impl RustlsTlsClientArgs {
             .config
             .as_mut()
             .ok_or_else(|| anyhow!("no tls config found"))?;
-        if let Some(cert_pair) = &self.cert_pair {
+
+        for (i, cert_pair) in self.identity_cert_pairs.iter().enumerate() {
             tls_config.set_cert_pair(cert_pair.clone());
+            let tls_client = build_client_config(tls_config, self.alpn_protocol)
+                .context(format!("failed to build tls client #{i}"))?;
+            self.identity_clients.push(tls_client);
         }
 
-        tls_config.check().context("invalid tls config")?;
-        let tls_client = if let Some(p) = self.alpn_protocol {
-            tls_config
-                .build_with_alpn_protocols(Some(vec![p]))
-                .context(format!("failed to build tls client with alpn protocol {p}"))?
+        if let Some(cert_pair) = &self.cert_pair {
+            tls_config.set_cert_pair(cert_pair.clone());
+            self.client = Some(build_client_config(tls_config, self.alpn_protocol)?);
+        } else if let Some(tls_client) = self.identity_clients.first() {
+            self.client = Some(tls_client.clone());
         } else {
-            tls_config.build().context("failed to build tls client")?
-        };
-        self.client = Some(tls_client);
+            self.client = Some(build_client_config(tls_config, self.alpn_protocol)?);
+        }
         Ok(())
     }
 
@@ -190,7 +263,9 @@ impl RustlsTlsClientArgs {
 
         self.parse_tls_name(args, TLS_ARG_NAME)?;
         self.parse_ca_cert(args, TLS_ARG_CA_CERT)?;
-        self.parse_client_auth(args, TLS_ARG_CERT, TLS_ARG_KEY)?;
+        self.parse_client_auth(args, TLS_ARG_CERT, TLS_ARG_CERT_CHAIN, TLS_ARG_KEY)?;
+        self.parse_client_pkcs12(args, TLS_ARG_PKCS12, TLS_ARG_PKCS12_PASSWORD)?;
+        self.parse_client_identity_dir(args, TLS_ARG_CLIENT_IDENTITY_DIR, TLS_ARG_PKCS12_PASSWORD)?;
         self.parse_no_session_cache(args, TLS_ARG_NO_SESSION_CACHE)?;
         self.parse_no_sni(args, TLS_ARG_NO_SNI)?;
         self.build_client()
@@ -204,13 +279,33 @@ impl RustlsTlsClientArgs {
 
         self.parse_tls_name(args, PROXY_TLS_ARG_NAME)?;
         self.parse_ca_cert(args, PROXY_TLS_ARG_CA_CERT)?;
-        self.parse_client_auth(args, PROXY_TLS_ARG_CERT, PROXY_TLS_ARG_KEY)?;
+        self.parse_client_auth(
+            args,
+            PROXY_TLS_ARG_CERT,
+            PROXY_TLS_ARG_CERT_CHAIN,
+            PROXY_TLS_ARG_KEY,
+        )?;
+        self.parse_client_pkcs12(args, PROXY_TLS_ARG_PKCS12, PROXY_TLS_ARG_PKCS12_PASSWORD)?;
         self.parse_no_session_cache(args, PROXY_TLS_ARG_NO_SESSION_CACHE)?;
         self.parse_no_sni(args, PROXY_TLS_ARG_NO_SNI)?;
         self.build_client()
     }
 }
 
+fn build_client_config(
+    tls_config: &mut RustlsClientConfigBuilder,
+    alpn_protocol: Option<AlpnProtocol>,
+) -> anyhow::Result<RustlsClientConfig> {
+    tls_config.check().context("invalid tls config")?;
+    if let Some(p) = alpn_protocol {
+        tls_config
+            .build_with_alpn_protocols(Some(vec![p]))
+            .context(format!("failed to build tls client with alpn protocol {p}"))
+    } else {
+        tls_config.build().context("failed to build tls client")
+    }
+}
+
 pub(crate) fn load_certs(path: &Path) -> anyhow::Result<Vec<CertificateDer<'static>>> {
     let file =
         File::open(path).map_err(|e| anyhow!("unable to open file {}: {e}", path.display()))?;
@@ -281,6 +376,46 @@ pub(crate) fn append_tls_args(cmd: Command) -> Command {
             .value_parser(value_parser!(PathBuf))
             .requires(TLS_ARG_CERT),
     )
+    .arg(
+        Arg::new(TLS_ARG_CERT_CHAIN)
+            .help("TLS client intermediate certificate chain file for target site")
+            .value_name("CHAIN FILE")
+            .long(TLS_ARG_CERT_CHAIN)
+            .num_args(1)
+            .value_hint(ValueHint::FilePath)
+            .value_parser(value_parser!(PathBuf))
+            .requires(TLS_ARG_CERT),
+    )
+    .arg(
+        Arg::new(TLS_ARG_PKCS12)
+            .help("TLS client PKCS#12 file for target site")
+            .value_name("PKCS12 FILE")
+            .long(TLS_ARG_PKCS12)
+            .num_args(1)
+            .value_hint(ValueHint::FilePath)
+            .value_parser(value_parser!(PathBuf))
+            .conflicts_with_all([TLS_ARG_CERT, TLS_ARG_KEY]),
+    )
+    .arg(
+        Arg::new(TLS_ARG_PKCS12_PASSWORD)
+            .help("Password for the TLS client PKCS#12 files for target site")
+            .value_name("PASSWORD")
+            .long(TLS_ARG_PKCS12_PASSWORD)
+            .num_args(1),
+    )
+    .arg(
+        Arg::new(TLS_ARG_CLIENT_IDENTITY_DIR)
+            .help(
+                "Directory of TLS client identities for target site, \
+                each concurrency will use a distinct one in turn",
+            )
+            .value_name("DIRECTORY")
+            .long(TLS_ARG_CLIENT_IDENTITY_DIR)
+            .num_args(1)
+            .value_hint(ValueHint::DirPath)
+            .value_parser(value_parser!(PathBuf))
+            .conflicts_with_all([TLS_ARG_CERT, TLS_ARG_KEY, TLS_ARG_PKCS12]),
+    )
     .arg(
         Arg::new(TLS_ARG_NO_SESSION_CACHE)
             .help("Disable TLS session cache for target site")
@@ -333,6 +468,34 @@ pub(crate) fn append_proxy_tls_args(cmd: Command) -> Command {
             .value_parser(value_parser!(PathBuf))
             .requires(PROXY_TLS_ARG_CERT),
     )
+    .arg(
+        Arg::new(PROXY_TLS_ARG_CERT_CHAIN)
+            .help("TLS client intermediate certificate chain file for proxy")
+            .value_name("CHAIN FILE")
+            .long(PROXY_TLS_ARG_CERT_CHAIN)
+            .num_args(1)
+            .value_hint(ValueHint::FilePath)
+            .value_parser(value_parser!(PathBuf))
+            .requires(PROXY_TLS_ARG_CERT),
+    )
+    .arg(
+        Arg::new(PROXY_TLS_ARG_PKCS12)
+            .help("TLS client PKCS#12 file for proxy")
+            .value_name("PKCS12 FILE")
+            .long(PROXY_TLS_ARG_PKCS12)
+            .num_args(1)
+            .value_hint(ValueHint::FilePath)
+            .value_parser(value_parser!(PathBuf))
+            .conflicts_with_all([PROXY_TLS_ARG_CERT, PROXY_TLS_ARG_KEY]),
+    )
+    .arg(
+        Arg::new(PROXY_TLS_ARG_PKCS12_PASSWORD)
+            .help("Password for the TLS client PKCS#12 file for proxy")
+            .value_name("PASSWORD")
+            .long(PROXY_TLS_ARG_PKCS12_PASSWORD)
+            .num_args(1)
+            .requires(PROXY_TLS_ARG_PKCS12),
+    )
     .arg(
         Arg::new(PROXY_TLS_ARG_NO_SESSION_CACHE)
             .help("Disable TLS session cache for proxy")
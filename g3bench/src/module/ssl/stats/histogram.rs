@@ -23,30 +23,61 @@ use g3_types::ext::DurationExt;
 use crate::target::BenchHistogram;
 
 pub(crate) struct SslHistogram {
+    dns_time: Option<Duration>,
+    tcp_connect_time: KeepingHistogram<u64>,
+    tls_handshake_time: KeepingHistogram<u64>,
     total_time: KeepingHistogram<u64>,
 }
 
 impl SslHistogram {
     pub(crate) fn new() -> (Self, SslHistogramRecorder) {
-        let (h, r) = KeepingHistogram::new();
-        (
-            SslHistogram { total_time: h },
-            SslHistogramRecorder { total_time: r },
-        )
+        let (tcp_connect_time_h, tcp_connect_time_r) = KeepingHistogram::new();
+        let (tls_handshake_time_h, tls_handshake_time_r) = KeepingHistogram::new();
+        let (total_time_h, total_time_r) = KeepingHistogram::new();
+        let h = SslHistogram {
+            dns_time: None,
+            tcp_connect_time: tcp_connect_time_h,
+            tls_handshake_time: tls_handshake_time_h,
+            total_time: total_time_h,
+        };
+        let r = SslHistogramRecorder {
+            tcp_connect_time: tcp_connect_time_r,
+            tls_handshake_time: tls_handshake_time_r,
+            total_time: total_time_r,
+        };
+        (h, r)
+    }
+
+    pub(crate) fn set_dns_time(&mut self, dur: Duration) {
+        self.dns_time = Some(dur);
     }
 }
 
 impl BenchHistogram for SslHistogram {
     fn refresh(&mut self) {
+        self.tcp_connect_time.refresh().unwrap();
+        self.tls_handshake_time.refresh().unwrap();
         self.total_time.refresh().unwrap();
     }
 
     fn emit(&self, client: &mut StatsdClient) {
+        if let Some(dur) = self.dns_time {
+            client.gauge("ssl.time.dns", dur.as_nanos_u64()).send();
+        }
+        self.emit_phase_histogram(client, self.tcp_connect_time.inner(), "ssl.time.connect");
+        self.emit_phase_histogram(
+            client,
+            self.tls_handshake_time.inner(),
+            "ssl.time.tls_handshake",
+        );
         self.emit_histogram(client, self.total_time.inner(), "ssl.time.total");
     }
 
     fn summary(&self) {
+        Self::summary_resolve_time(self.dns_time);
         Self::summary_histogram_title("# Duration Times");
+        Self::summary_phase_duration_line("Connect:", self.tcp_connect_time.inner());
+        Self::summary_phase_duration_line("TlsHs:", self.tls_handshake_time.inner());
         let total_time = self.total_time.inner();
         Self::summary_duration_line("Total:", total_time);
         Self::summary_newline();
@@ -56,10 +87,20 @@ impl BenchHistogram for SslHistogram {
 
 #[derive(Clone)]
 pub(crate) struct SslHistogramRecorder {
+    tcp_connect_time: HistogramRecorder<u64>,
+    tls_handshake_time: HistogramRecorder<u64>,
     total_time: HistogramRecorder<u64>,
 }
 
 impl SslHistogramRecorder {
+    pub(crate) fn record_tcp_connect_time(&mut self, dur: Duration) {
+        let _ = self.tcp_connect_time.record(dur.as_nanos_u64());
+    }
+
+    pub(crate) fn record_tls_handshake_time(&mut self, dur: Duration) {
+        let _ = self.tls_handshake_time.record(dur.as_nanos_u64());
+    }
+
     pub(crate) fn record_total_time(&mut self, dur: Duration) {
         let _ = self.total_time.record(dur.as_nanos_u64());
     }
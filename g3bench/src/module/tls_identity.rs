@@ -0,0 +1,171 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use openssl::pkcs12::Pkcs12;
+use openssl::pkey::{PKey, Private};
+use openssl::x509::X509;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+
+use g3_types::net::{OpensslCertificatePair, RustlsCertificatePair, RustlsCertificatePairBuilder};
+
+/// A client certificate chain and its private key, loaded from either PEM or PKCS#12 files
+pub(crate) struct ClientIdentity {
+    certs: Vec<X509>,
+    key: PKey<Private>,
+}
+
+impl ClientIdentity {
+    pub(crate) fn load_pem(
+        cert_file: &Path,
+        chain_file: Option<&Path>,
+        key_file: &Path,
+    ) -> anyhow::Result<Self> {
+        let mut certs = super::openssl::load_certs(cert_file).context(format!(
+            "failed to load client certificate from file {}",
+            cert_file.display()
+        ))?;
+        if let Some(file) = chain_file {
+            let chain = super::openssl::load_certs(file).context(format!(
+                "failed to load client certificate chain from file {}",
+                file.display()
+            ))?;
+            certs.extend(chain);
+        }
+        let key = super::openssl::load_key(key_file).context(format!(
+            "failed to load client private key from file {}",
+            key_file.display()
+        ))?;
+        Ok(ClientIdentity { certs, key })
+    }
+
+    pub(crate) fn load_pkcs12(path: &Path, password: &str) -> anyhow::Result<Self> {
+        const MAX_FILE_SIZE: usize = 4_000_000; // 4MB
+        let mut contents = Vec::with_capacity(MAX_FILE_SIZE);
+        let file =
+            File::open(path).map_err(|e| anyhow!("unable to open file {}: {e}", path.display()))?;
+        file.take(MAX_FILE_SIZE as u64)
+            .read_to_end(&mut contents)
+            .map_err(|e| anyhow!("failed to read contents of file {}: {e}", path.display()))?;
+
+        let pkcs12 = Pkcs12::from_der(&contents)
+            .map_err(|e| anyhow!("invalid pkcs12 file({}): {e}", path.display()))?;
+        let parsed = pkcs12
+            .parse2(password)
+            .map_err(|e| anyhow!("failed to decrypt pkcs12 file({}): {e}", path.display()))?;
+        let key = parsed
+            .pkey
+            .ok_or_else(|| anyhow!("no private key found in pkcs12 file {}", path.display()))?;
+        let cert = parsed
+            .cert
+            .ok_or_else(|| anyhow!("no certificate found in pkcs12 file {}", path.display()))?;
+        let mut certs = vec![cert];
+        if let Some(chain) = parsed.ca {
+            certs.extend(chain);
+        }
+        Ok(ClientIdentity { certs, key })
+    }
+
+    /// Load all identities in the directory, sorted by file name.
+    ///
+    /// Files with extension `p12` or `pfx` are loaded as PKCS#12 files, and files with
+    /// extension `crt` or `pem` are loaded as PEM certificates, the private key of which
+    /// should be placed in the file with the same stem and extension `key`.
+    pub(crate) fn load_dir(dir: &Path, pkcs12_password: &str) -> anyhow::Result<Vec<Self>> {
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| anyhow!("failed to read directory {}: {e}", dir.display()))?;
+        let mut files = Vec::new();
+        for entry in entries {
+            let entry =
+                entry.map_err(|e| anyhow!("failed to read directory {}: {e}", dir.display()))?;
+            let path = entry.path();
+            if path.is_file() {
+                files.push(path);
+            }
+        }
+        files.sort();
+
+        let mut identities = Vec::with_capacity(files.len());
+        for path in files {
+            let Some(ext) = path.extension().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            match ext.to_ascii_lowercase().as_str() {
+                "p12" | "pfx" => {
+                    let identity = Self::load_pkcs12(&path, pkcs12_password)?;
+                    identities.push(identity);
+                }
+                "crt" | "pem" => {
+                    let key_file = path.with_extension("key");
+                    if !key_file.is_file() {
+                        return Err(anyhow!(
+                            "no private key file found for certificate file {}",
+                            path.display()
+                        ));
+                    }
+                    let identity = Self::load_pem(&path, None, &key_file)?;
+                    identities.push(identity);
+                }
+                _ => {}
+            }
+        }
+
+        if identities.is_empty() {
+            Err(anyhow!(
+                "no client identity found in directory {}",
+                dir.display()
+            ))
+        } else {
+            Ok(identities)
+        }
+    }
+
+    pub(crate) fn openssl_cert_pair(&self) -> anyhow::Result<OpensslCertificatePair> {
+        let mut cert_pair = OpensslCertificatePair::default();
+        cert_pair
+            .set_certificates(self.certs.clone())
+            .context("failed to set client certificate")?;
+        cert_pair
+            .set_private_key(self.key.clone())
+            .context("failed to set client private key")?;
+        Ok(cert_pair)
+    }
+
+    pub(crate) fn rustls_cert_pair(&self) -> anyhow::Result<RustlsCertificatePair> {
+        let mut certs = Vec::with_capacity(self.certs.len());
+        for cert in &self.certs {
+            let der = cert
+                .to_der()
+                .map_err(|e| anyhow!("failed to encode client certificate: {e}"))?;
+            certs.push(CertificateDer::from(der));
+        }
+        let key = self
+            .key
+            .private_key_to_pkcs8()
+            .map_err(|e| anyhow!("failed to encode client private key: {e}"))?;
+
+        let mut builder = RustlsCertificatePairBuilder::default();
+        builder.set_certs(certs);
+        builder.set_key(PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key)));
+        builder
+            .build()
+            .context("failed to build client auth cert pair")
+    }
+}
@@ -26,19 +26,22 @@ use openssl::x509::X509;
 use tokio::runtime::Handle;
 
 use g3_cert_agent::Request;
-use g3_tls_cert::builder::{MimicCertBuilder, ServerCertBuilder, TlsServerCertBuilder};
+use g3_tls_cert::builder::{
+    MimicCertBuilder, OcspResponseBuilder, ServerCertBuilder, TlsServerCertBuilder,
+};
 use g3_types::net::{Host, TlsCertUsage};
 
 mod stats;
 pub(crate) use stats::BackendStats;
 
 use super::{BackendRequest, BackendResponse};
-use crate::config::OpensslBackendConfig;
+use crate::config::{OpensslBackendConfig, OpensslCaCert};
 use crate::frontend::GeneratedData;
 
 pub(crate) struct OpensslBackend {
     config: Arc<OpensslBackendConfig>,
     builder: ServerCertBuilder,
+    ocsp_builder: OcspResponseBuilder,
     stats: Arc<BackendStats>,
 }
 
@@ -51,6 +54,7 @@ impl OpensslBackend {
         Ok(OpensslBackend {
             config: Arc::clone(config),
             builder,
+            ocsp_builder: OcspResponseBuilder::default(),
             stats: Arc::clone(stats),
         })
     }
@@ -70,11 +74,11 @@ impl OpensslBackend {
         } else {
             let host = Host::from_str(req.host_str())?;
             self.builder.refresh_serial()?;
-            let cert =
-                self.builder
-                    .build_fake(&host, &self.config.ca_cert, &self.config.ca_key, None)?;
+            let ca_set = self.config.ca_set.load();
+            let ca = ca_set.default_ca();
+            let cert = self.builder.build_fake(&host, &ca.cert, &ca.key, None)?;
             let ttl = self.builder.valid_seconds()?;
-            self.pack_data(cert, self.builder.pkey(), ttl)
+            self.pack_data(cert, self.builder.pkey(), ttl, ca)
         }
     }
 
@@ -86,30 +90,24 @@ impl OpensslBackend {
         let mut mimic_builder = MimicCertBuilder::new(mimic_cert)?;
         mimic_builder.set_keep_serial(self.config.keep_serial);
 
+        let ca_set = self.config.ca_set.load();
+        let ca = ca_set.select(mimic_builder.pkey().id());
         let cert = match cert_usage {
-            TlsCertUsage::TlsServer => {
-                mimic_builder.build_tls_cert(&self.config.ca_cert, &self.config.ca_key, None)?
+            TlsCertUsage::TlsServer => mimic_builder.build_tls_cert(&ca.cert, &ca.key, None)?,
+            TlsCertUsage::TLsServerTongsuo => {
+                mimic_builder.build_tls_cert_with_new_usage(&ca.cert, &ca.key, None)?
+            }
+            TlsCertUsage::TlcpServerEncryption => {
+                mimic_builder.build_tlcp_enc_cert(&ca.cert, &ca.key, None)?
+            }
+            TlsCertUsage::TlcpServerSignature => {
+                mimic_builder.build_tlcp_sign_cert(&ca.cert, &ca.key, None)?
             }
-            TlsCertUsage::TLsServerTongsuo => mimic_builder.build_tls_cert_with_new_usage(
-                &self.config.ca_cert,
-                &self.config.ca_key,
-                None,
-            )?,
-            TlsCertUsage::TlcpServerEncryption => mimic_builder.build_tlcp_enc_cert(
-                &self.config.ca_cert,
-                &self.config.ca_key,
-                None,
-            )?,
-            TlsCertUsage::TlcpServerSignature => mimic_builder.build_tlcp_sign_cert(
-                &self.config.ca_cert,
-                &self.config.ca_key,
-                None,
-            )?,
         };
 
         let ttl = mimic_builder.valid_seconds()?;
 
-        self.pack_data(cert, mimic_builder.pkey(), ttl)
+        self.pack_data(cert, mimic_builder.pkey(), ttl, ca)
     }
 
     fn pack_data(
@@ -117,13 +115,24 @@ impl OpensslBackend {
         cert: X509,
         pkey: &PKey<Private>,
         ttl: i32,
+        ca: &OpensslCaCert,
     ) -> anyhow::Result<GeneratedData> {
         let ttl = ttl.clamp(0, self.config.max_ttl) as u32;
+        let ocsp = if self.config.ocsp_stapling {
+            // keep it valid a bit longer than the cert ttl, as the expired cert may still be used
+            // by g3proxy before the cache is refreshed
+            let ocsp =
+                self.ocsp_builder
+                    .build_good(&cert, &ca.cert, &ca.key, ttl.saturating_add(3600))?;
+            Some(ocsp)
+        } else {
+            None
+        };
         let mut cert_pem = cert
             .to_pem()
             .map_err(|e| anyhow!("failed to encode cert to PEM format: {e}"))?;
-        if !self.config.ca_cert_pem.is_empty() {
-            cert_pem.extend_from_slice(&self.config.ca_cert_pem);
+        if !ca.cert_pem.is_empty() {
+            cert_pem.extend_from_slice(&ca.cert_pem);
         }
         let key = pkey
             .private_key_to_der()
@@ -132,6 +141,7 @@ impl OpensslBackend {
         let data = GeneratedData {
             cert: unsafe { String::from_utf8_unchecked(cert_pem) },
             key,
+            ocsp,
             ttl,
         };
         self.stats.add_request_ok();
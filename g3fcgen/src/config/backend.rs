@@ -14,11 +14,13 @@
  * limitations under the License.
  */
 
+use std::path::Path;
 use std::sync::Arc;
 use std::sync::OnceLock;
 
 use anyhow::{anyhow, Context};
-use openssl::pkey::{PKey, Private};
+use arc_swap::ArcSwap;
+use openssl::pkey::{Id, PKey, Private};
 use openssl::x509::X509;
 use yaml_rust::Yaml;
 
@@ -30,58 +32,184 @@ pub(crate) fn get_config() -> Option<Arc<OpensslBackendConfig>> {
     BACKEND_CONFIG_LOCK.get().cloned()
 }
 
+pub(crate) struct OpensslCaCert {
+    pub(crate) cert: X509,
+    pub(crate) key: PKey<Private>,
+    pub(crate) cert_pem: Vec<u8>,
+}
+
+impl OpensslCaCert {
+    fn parse_yaml(
+        cert: &Yaml,
+        key: &Yaml,
+        no_append_ca_cert: bool,
+        lookup_dir: &Path,
+    ) -> anyhow::Result<Self> {
+        let certs = g3_yaml::value::as_openssl_certificates(cert, Some(lookup_dir))
+            .context("invalid openssl certificate value for ca certificate")?;
+        let mut cert_pem = Vec::new();
+        if !no_append_ca_cert {
+            for (i, cert) in certs.iter().enumerate() {
+                let pem = cert
+                    .to_pem()
+                    .map_err(|e| anyhow!("failed to convert cert {i} back to pem format: {e}"))?;
+                cert_pem.extend(pem);
+            }
+        }
+        let cert = certs
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("no valid openssl certificate key found"))?;
+
+        let key = g3_yaml::value::as_openssl_private_key(key, Some(lookup_dir))
+            .context("invalid openssl private key value for ca private key")?;
+        let cert_pkey = cert
+            .public_key()
+            .map_err(|e| anyhow!("failed to get public key of the ca certificate: {e}"))?;
+        if !cert_pkey.public_eq(&key) {
+            return Err(anyhow!(
+                "the ca private key does not match the ca certificate"
+            ));
+        }
+
+        Ok(OpensslCaCert {
+            cert,
+            key,
+            cert_pem,
+        })
+    }
+}
+
+/// All the CAs that can be used to sign the generated certificates.
+pub(crate) struct OpensslCaSet {
+    default: OpensslCaCert,
+    extra: Vec<OpensslCaCert>,
+}
+
+impl OpensslCaSet {
+    #[inline]
+    pub(crate) fn default_ca(&self) -> &OpensslCaCert {
+        &self.default
+    }
+
+    /// Select the CA which has the same key type as the mimic certificate,
+    /// the default one will be used if no match found.
+    pub(crate) fn select(&self, key_type: Id) -> &OpensslCaCert {
+        if self.default.key.id() == key_type {
+            return &self.default;
+        }
+        self.extra
+            .iter()
+            .find(|ca| ca.key.id() == key_type)
+            .unwrap_or(&self.default)
+    }
+}
+
+/// The raw CA config, kept to reload the CA files at runtime.
+#[derive(Default)]
+struct CaSetConfig {
+    ca_certificate: Option<Yaml>,
+    ca_private_key: Option<Yaml>,
+    extra_ca: Vec<(Yaml, Yaml)>,
+    no_append_ca_cert: bool,
+}
+
+impl CaSetConfig {
+    fn add_extra_ca(&mut self, value: &Yaml) -> anyhow::Result<()> {
+        match value {
+            Yaml::Hash(map) => {
+                let cert = g3_yaml::hash_get_required(map, "ca_certificate")?;
+                let key = g3_yaml::hash_get_required(map, "ca_private_key")?;
+                self.extra_ca.push((cert.clone(), key.clone()));
+                Ok(())
+            }
+            Yaml::Array(seq) => {
+                for (i, v) in seq.iter().enumerate() {
+                    self.add_extra_ca(v)
+                        .context(format!("invalid extra ca value for element #{i}"))?;
+                }
+                Ok(())
+            }
+            _ => Err(anyhow!(
+                "yaml value type for 'extra ca' should be 'map' or 'seq'"
+            )),
+        }
+    }
+
+    fn build(&self) -> anyhow::Result<OpensslCaSet> {
+        let lookup_dir = g3_daemon::config::get_lookup_dir(None)?;
+
+        let Some(ca_cert) = &self.ca_certificate else {
+            return Err(anyhow!("no ca certificate set"));
+        };
+        let Some(ca_key) = &self.ca_private_key else {
+            return Err(anyhow!("no ca private key set"));
+        };
+        let default =
+            OpensslCaCert::parse_yaml(ca_cert, ca_key, self.no_append_ca_cert, lookup_dir)?;
+
+        let mut extra = Vec::with_capacity(self.extra_ca.len());
+        for (i, (cert, key)) in self.extra_ca.iter().enumerate() {
+            let ca = OpensslCaCert::parse_yaml(cert, key, self.no_append_ca_cert, lookup_dir)
+                .context(format!("invalid extra ca #{i}"))?;
+            extra.push(ca);
+        }
+
+        Ok(OpensslCaSet { default, extra })
+    }
+}
+
 pub(crate) struct OpensslBackendConfig {
-    pub(crate) ca_cert: X509,
-    pub(crate) ca_key: PKey<Private>,
-    pub(crate) ca_cert_pem: Vec<u8>,
+    ca_set_config: CaSetConfig,
+    pub(crate) ca_set: ArcSwap<OpensslCaSet>,
     pub(crate) keep_serial: bool,
+    pub(crate) ocsp_stapling: bool,
     pub(crate) max_ttl: i32,
     pub(crate) duration_stats: HistogramMetricsConfig,
 }
 
+pub(crate) fn reload_ca() -> anyhow::Result<()> {
+    let config = get_config().ok_or_else(|| anyhow!("no backend config loaded"))?;
+    let ca_set = config.ca_set_config.build()?;
+    config.ca_set.store(Arc::new(ca_set));
+    Ok(())
+}
+
 pub(super) fn load_config(value: &Yaml) -> anyhow::Result<()> {
     if let Yaml::Hash(map) = value {
-        let mut no_append_ca_cert = false;
-        let mut ca_cert_pem = Vec::new();
-        let mut ca_cert: Option<X509> = None;
-        let mut ca_key: Option<PKey<Private>> = None;
+        let mut ca_set_config = CaSetConfig::default();
         let mut keep_serial = false;
+        let mut ocsp_stapling = false;
         let mut max_ttl = 24 * 3600; // 1 day
         let mut duration_stats = HistogramMetricsConfig::default();
-        let lookup_dir = g3_daemon::config::get_lookup_dir(None)?;
 
         g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
             "ca_certificate" => {
-                let certs = g3_yaml::value::as_openssl_certificates(v, Some(lookup_dir))
-                    .context(format!("invalid openssl certificate value for key {k}"))?;
-                for (i, cert) in certs.iter().enumerate() {
-                    let pem = cert.to_pem().map_err(|e| {
-                        anyhow!("failed to convert cert {i} back to pem format: {e}")
-                    })?;
-                    ca_cert_pem.extend(pem);
-                }
-
-                let cert = certs
-                    .into_iter()
-                    .next()
-                    .ok_or_else(|| anyhow!("no valid openssl certificate key found"))?;
-                ca_cert = Some(cert);
+                ca_set_config.ca_certificate = Some(v.clone());
                 Ok(())
             }
             "ca_private_key" => {
-                let key = g3_yaml::value::as_openssl_private_key(v, Some(lookup_dir))
-                    .context(format!("invalid openssl private key value for key {k}"))?;
-                ca_key = Some(key);
+                ca_set_config.ca_private_key = Some(v.clone());
                 Ok(())
             }
+            "extra_ca" => ca_set_config
+                .add_extra_ca(v)
+                .context(format!("invalid extra ca value for key {k}")),
             "no_append_ca_cert" => {
-                no_append_ca_cert = g3_yaml::value::as_bool(v)?;
+                ca_set_config.no_append_ca_cert = g3_yaml::value::as_bool(v)?;
                 Ok(())
             }
             "keep_serial" => {
                 keep_serial = g3_yaml::value::as_bool(v)?;
                 Ok(())
             }
+            "ocsp_stapling" => {
+                ocsp_stapling = g3_yaml::value::as_bool(v)?;
+                if ocsp_stapling && cfg!(feature = "vendored-boringssl") {
+                    return Err(anyhow!("ocsp stapling is not supported with BoringSSL"));
+                }
+                Ok(())
+            }
             "max_ttl" => {
                 let v = g3_yaml::value::as_i32(v)?;
                 max_ttl = v.max(300); // at least for 5 minutes
@@ -96,22 +224,13 @@ pub(super) fn load_config(value: &Yaml) -> anyhow::Result<()> {
             _ => Err(anyhow!("invalid key {k}")),
         })?;
 
-        let Some(ca_cert) = ca_cert else {
-            return Err(anyhow!("no ca certificate set"));
-        };
-        let Some(ca_key) = ca_key else {
-            return Err(anyhow!("no ca private key set"));
-        };
-
-        if no_append_ca_cert {
-            ca_cert_pem.clear();
-        }
+        let ca_set = ca_set_config.build()?;
         BACKEND_CONFIG_LOCK
             .set(Arc::new(OpensslBackendConfig {
-                ca_cert,
-                ca_key,
-                ca_cert_pem,
+                ca_set_config,
+                ca_set: ArcSwap::new(Arc::new(ca_set)),
                 keep_serial,
+                ocsp_stapling,
                 max_ttl,
                 duration_stats,
             }))
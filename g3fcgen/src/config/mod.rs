@@ -20,7 +20,9 @@ use anyhow::anyhow;
 use yaml_rust::{yaml, Yaml};
 
 mod backend;
-pub(crate) use backend::{get_config as get_backend_config, OpensslBackendConfig};
+pub(crate) use backend::{
+    get_config as get_backend_config, reload_ca, OpensslBackendConfig, OpensslCaCert,
+};
 
 pub fn load() -> anyhow::Result<&'static Path> {
     let config_file =
@@ -41,6 +43,7 @@ fn load_doc(map: &yaml::Hash) -> anyhow::Result<()> {
         "worker" => g3_daemon::runtime::config::load_worker(v),
         "stat" => g3_daemon::stat::config::load(v, crate::build::PKG_NAME),
         "backend" => backend::load_config(v),
+        "tls_frontend" => g3_daemon::frontend::TlsFrontendConfig::load(v),
         _ => Err(anyhow!("invalid key {k} in main conf")),
     })?;
     Ok(())
@@ -14,6 +14,7 @@
  * limitations under the License.
  */
 
+use std::io;
 use std::sync::Arc;
 
 use anyhow::anyhow;
@@ -24,7 +25,7 @@ use g3_cert_agent::Request;
 use g3_histogram::HistogramRecorder;
 use g3_types::net::UdpListenConfig;
 
-use crate::{BackendRequest, BackendResponse};
+use crate::{BackendRequest, BackendResponse, ResponsePeer};
 
 mod stats;
 pub(crate) use stats::FrontendStats;
@@ -32,10 +33,14 @@ pub(crate) use stats::FrontendStats;
 mod udp_dgram;
 use udp_dgram::UdpDgramIo;
 
+mod tls_stream;
+pub(crate) use tls_stream::TlsStreamFrontend;
+
 #[derive(Debug)]
 pub(crate) struct GeneratedData {
     pub(crate) cert: String,
     pub(crate) key: Vec<u8>,
+    pub(crate) ocsp: Option<Vec<u8>>,
     pub(crate) ttl: u32,
 }
 
@@ -78,7 +83,7 @@ impl Frontend {
                         Ok((len, peer)) => match Request::parse_req(&rcv_buf[0..len]) {
                             Ok(user_req) => {
                                 debug!("{} - request received", user_req.host());
-                                let req = BackendRequest {user_req, peer, recv_time};
+                                let req = BackendRequest {user_req, peer: ResponsePeer::Udp(peer), recv_time};
                                 if let Err(e) = req_sender.send_async(req).await {
                                     return Err(anyhow!("failed to send request to backend: {e}"));
                                 }
@@ -118,20 +123,29 @@ impl Frontend {
     }
 
     async fn handle_rsp(&self, rsp: BackendResponse) {
-        match rsp
-            .user_req
-            .encode_rsp(&rsp.generated.cert, &rsp.generated.key, rsp.generated.ttl)
-        {
+        match rsp.user_req.encode_rsp(
+            &rsp.generated.cert,
+            &rsp.generated.key,
+            rsp.generated.ocsp.as_deref(),
+            rsp.generated.ttl,
+        ) {
             Ok(buf) => {
                 self.stats.add_response_total();
-                match self.io.send_rsp(buf.as_slice(), rsp.peer).await {
+                let rsp_size = buf.len();
+                let r = match &rsp.peer {
+                    ResponsePeer::Udp(addr) => self.io.send_rsp(buf.as_slice(), *addr).await,
+                    ResponsePeer::Stream(sender) => sender
+                        .try_send(buf)
+                        .map_err(|e| io::Error::other(format!("tls connection unavailable: {e}"))),
+                };
+                match r {
                     Ok(_) => {
                         let duration_nanos = rsp.duration();
                         debug!(
                             "{} - duration: {}ns, rsp size: {}",
                             rsp.user_req.host(),
                             duration_nanos,
-                            buf.len()
+                            rsp_size
                         );
                         let _ = self.duration_recorder.record(duration_nanos);
                     }
@@ -0,0 +1,128 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use log::{debug, warn};
+use tokio::net::TcpStream;
+use tokio::time::Instant;
+
+use g3_cert_agent::Request;
+use g3_daemon::frontend::{TlsFrontendConfig, TlsFrontendListener, TlsFrontendStream};
+
+use super::FrontendStats;
+use crate::{BackendRequest, ResponsePeer};
+
+const CONNECTION_RSP_QUEUE_SIZE: usize = 128;
+
+pub(crate) struct TlsStreamFrontend {
+    listener: TlsFrontendListener,
+    stats: Arc<FrontendStats>,
+}
+
+impl TlsStreamFrontend {
+    pub(crate) fn new(
+        config: &TlsFrontendConfig,
+        stats: Arc<FrontendStats>,
+    ) -> anyhow::Result<Self> {
+        let listener = TlsFrontendListener::new(config)?;
+        Ok(TlsStreamFrontend { listener, stats })
+    }
+
+    /// The weak sender is used so that the backend could quit after the udp frontend
+    pub(crate) fn spawn(self, req_sender: flume::WeakSender<BackendRequest>) {
+        tokio::spawn(async move {
+            loop {
+                match self.listener.accept().await {
+                    Ok((stream, peer)) => self.spawn_connection(stream, peer, req_sender.clone()),
+                    Err(e) => warn!("failed to accept tls frontend connection: {e}"),
+                }
+            }
+        });
+    }
+
+    fn spawn_connection(
+        &self,
+        stream: TcpStream,
+        peer: SocketAddr,
+        req_sender: flume::WeakSender<BackendRequest>,
+    ) {
+        let handshake = self.listener.handshake(stream);
+        let stats = self.stats.clone();
+        tokio::spawn(async move {
+            let r = match handshake.await {
+                Ok(io) => serve_connection(io, &stats, req_sender).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = r {
+                debug!("tls frontend connection from {peer} closed: {e:?}");
+            }
+        });
+    }
+}
+
+async fn serve_connection(
+    mut io: TlsFrontendStream,
+    stats: &FrontendStats,
+    req_sender: flume::WeakSender<BackendRequest>,
+) -> anyhow::Result<()> {
+    let (rsp_sender, rsp_receiver) = flume::bounded::<Vec<u8>>(CONNECTION_RSP_QUEUE_SIZE);
+
+    let mut rcv_buf = [0u8; 16384];
+    loop {
+        tokio::select! {
+            r = io.recv_msg(&mut rcv_buf) => {
+                let len = match r {
+                    Ok(len) => len,
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+                    Err(e) => return Err(anyhow!("recv error: {e}")),
+                };
+                stats.add_request_total();
+                let recv_time = Instant::now();
+                match Request::parse_req(&rcv_buf[0..len]) {
+                    Ok(user_req) => {
+                        debug!("{} - request received", user_req.host());
+                        let Some(req_sender) = req_sender.upgrade() else {
+                            // the frontend is quitting
+                            return Ok(());
+                        };
+                        let req = BackendRequest {
+                            user_req,
+                            peer: ResponsePeer::Stream(rsp_sender.clone()),
+                            recv_time,
+                        };
+                        if let Err(e) = req_sender.send_async(req).await {
+                            return Err(anyhow!("failed to send request to backend: {e}"));
+                        }
+                    }
+                    Err(e) => {
+                        stats.add_request_invalid();
+                        warn!("invalid request: {e:?}");
+                    }
+                }
+            }
+            r = rsp_receiver.recv_async() => {
+                // we always hold a sender here, so it won't fail
+                if let Ok(buf) = r {
+                    io.send_msg(&buf).await.map_err(|e| anyhow!("send error: {e}"))?;
+                }
+            }
+        }
+    }
+}
@@ -22,6 +22,7 @@ use tokio::runtime::Handle;
 use tokio::time::Instant;
 
 use g3_cert_agent::Request;
+use g3_daemon::control::LocalController;
 use g3_types::ext::DurationExt;
 
 pub mod config;
@@ -31,24 +32,30 @@ mod build;
 pub mod opts;
 use opts::ProcArgs;
 
+mod signal;
 mod stat;
 
 mod backend;
 use backend::{BackendStats, OpensslBackend};
 
 mod frontend;
-use frontend::{Frontend, FrontendStats, GeneratedData};
+use frontend::{Frontend, FrontendStats, GeneratedData, TlsStreamFrontend};
+
+enum ResponsePeer {
+    Udp(SocketAddr),
+    Stream(flume::Sender<Vec<u8>>),
+}
 
 struct BackendRequest {
     user_req: Request,
-    peer: SocketAddr,
+    peer: ResponsePeer,
     recv_time: Instant,
 }
 
 struct BackendResponse {
     user_req: Request,
     generated: GeneratedData,
-    peer: SocketAddr,
+    peer: ResponsePeer,
     recv_time: Instant,
 }
 
@@ -77,6 +84,14 @@ pub async fn run(proc_args: &ProcArgs) -> anyhow::Result<()> {
         config::get_backend_config().ok_or_else(|| anyhow!("no backend config available"))?;
     let backend_stats = Arc::new(BackendStats::default());
 
+    signal::register_reload().context("failed to setup signal handler")?;
+    g3_daemon::control::set_reload_action(config::reload_ca);
+    let unique_ctl = LocalController::start_unique(build::PKG_NAME, opts::daemon_group())
+        .context("failed to start unique controller")?;
+    tokio::spawn(async move {
+        unique_ctl.await;
+    });
+
     let (duration_recorder, duration_stats) = backend_config.duration_stats.build_spawned(None);
 
     let workers = g3_daemon::runtime::worker::foreach(|h| {
@@ -94,6 +109,11 @@ pub async fn run(proc_args: &ProcArgs) -> anyhow::Result<()> {
     }
 
     let frontend = Frontend::new(proc_args.listen_config(), duration_recorder, rsp_receiver)?;
+    if let Some(tls_config) = g3_daemon::frontend::TlsFrontendConfig::get() {
+        let tls_frontend = TlsStreamFrontend::new(&tls_config, frontend.stats())
+            .context("failed to setup tls frontend")?;
+        tls_frontend.spawn(req_sender.downgrade());
+    }
 
     if let Some(stats_config) = g3_daemon::stat::config::get_global_stat_config() {
         stat::spawn_working_thread(
@@ -104,5 +124,7 @@ pub async fn run(proc_args: &ProcArgs) -> anyhow::Result<()> {
         )?;
     }
 
-    frontend.run(req_sender).await
+    let r = frontend.run(req_sender).await;
+    LocalController::abort_unique().await;
+    r
 }
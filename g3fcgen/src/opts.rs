@@ -157,3 +157,7 @@ pub fn parse_clap() -> anyhow::Result<Option<ProcArgs>> {
 
     Ok(Some(proc_args))
 }
+
+pub(crate) fn daemon_group() -> &'static str {
+    DAEMON_GROUP.get().map(|s| s.as_str()).unwrap_or_default()
+}
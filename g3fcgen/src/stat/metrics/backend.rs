@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use g3_daemon::metrics::TAG_KEY_QUANTILE;
+use g3_daemon::metrics::{TAG_KEY_BUCKET_LE, TAG_KEY_QUANTILE};
 use g3_histogram::HistogramStats;
 use g3_statsd_client::StatsdClient;
 
@@ -41,4 +41,10 @@ pub(crate) fn emit_duration_stats(client: &mut StatsdClient, s: &HistogramStats)
             .with_tag(TAG_KEY_QUANTILE, qs)
             .send();
     });
+    s.foreach_bucket(|le, count| {
+        client
+            .gauge("backend.request_duration.bucket", count)
+            .with_tag(TAG_KEY_BUCKET_LE, le)
+            .send();
+    });
 }
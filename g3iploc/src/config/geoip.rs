@@ -14,12 +14,17 @@
  * limitations under the License.
  */
 
-use std::path::Path;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
+use g3_geoip_db::MaxmindDb;
+
+static MMDB_CITY_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+static MMDB_ASN_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
 pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
     if let Yaml::Hash(map) = v {
         g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
@@ -35,9 +40,60 @@ pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
                 g3_geoip_db::store::store_asn(Arc::new(db));
                 Ok(())
             }
+            "mmdb_city" | "mmdb_country" => {
+                let path = g3_yaml::value::as_file_path(v, conf_dir, false)?;
+                load_mmdb_city(&path)?;
+                *MMDB_CITY_FILE.lock().unwrap() = Some(path);
+                Ok(())
+            }
+            "mmdb_asn" => {
+                let path = g3_yaml::value::as_file_path(v, conf_dir, false)?;
+                load_mmdb_asn(&path)?;
+                *MMDB_ASN_FILE.lock().unwrap() = Some(path);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         })
     } else {
         Err(anyhow!("invalid value type"))
     }
 }
+
+fn load_mmdb_city(path: &Path) -> anyhow::Result<()> {
+    let db = MaxmindDb::open(path)?;
+    let db_type = db.database_type();
+    if !(db_type.contains("City") || db_type.contains("Country")) {
+        return Err(anyhow!(
+            "mmdb file {} has unsupported database type {db_type}",
+            path.display()
+        ));
+    }
+    g3_geoip_db::store::store_mmdb_city(Arc::new(db));
+    Ok(())
+}
+
+fn load_mmdb_asn(path: &Path) -> anyhow::Result<()> {
+    let db = MaxmindDb::open(path)?;
+    let db_type = db.database_type();
+    if !db_type.contains("ASN") {
+        return Err(anyhow!(
+            "mmdb file {} has unsupported database type {db_type}",
+            path.display()
+        ));
+    }
+    g3_geoip_db::store::store_mmdb_asn(Arc::new(db));
+    Ok(())
+}
+
+/// Reopen all configured mmdb files, the old mappings will be dropped after all in-flight lookups
+pub(crate) fn reload_mmdb() -> anyhow::Result<()> {
+    let city_file = MMDB_CITY_FILE.lock().unwrap().clone();
+    if let Some(path) = city_file {
+        load_mmdb_city(&path).context("failed to reload mmdb city database")?;
+    }
+    let asn_file = MMDB_ASN_FILE.lock().unwrap().clone();
+    if let Some(path) = asn_file {
+        load_mmdb_asn(&path).context("failed to reload mmdb asn database")?;
+    }
+    Ok(())
+}
@@ -20,6 +20,10 @@ use anyhow::anyhow;
 use yaml_rust::{yaml, Yaml};
 
 mod geoip;
+pub(crate) use geoip::reload_mmdb;
+
+mod tcp_frontend;
+pub(crate) use tcp_frontend::{get_config as get_tcp_frontend_config, TcpFrontendConfig};
 
 pub fn load() -> anyhow::Result<&'static Path> {
     let config_file =
@@ -42,6 +46,8 @@ fn load_doc(map: &yaml::Hash) -> anyhow::Result<()> {
         "worker" => g3_daemon::runtime::config::load_worker(v),
         "stat" => g3_daemon::stat::config::load(v, crate::build::PKG_NAME),
         "geoip_db" => geoip::load(v, conf_dir),
+        "tcp_frontend" => tcp_frontend::load_config(v),
+        "tls_frontend" => g3_daemon::frontend::TlsFrontendConfig::load(v),
         _ => Err(anyhow!("invalid key {k} in main conf")),
     })?;
     Ok(())
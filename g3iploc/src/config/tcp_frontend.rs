@@ -0,0 +1,76 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use std::sync::OnceLock;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use g3_types::net::TcpListenConfig;
+
+static TCP_FRONTEND_CONFIG_LOCK: OnceLock<Arc<TcpFrontendConfig>> = OnceLock::new();
+
+pub(crate) fn get_config() -> Option<Arc<TcpFrontendConfig>> {
+    TCP_FRONTEND_CONFIG_LOCK.get().cloned()
+}
+
+pub(crate) struct TcpFrontendConfig {
+    pub(crate) listen: TcpListenConfig,
+    pub(crate) max_batch_size: usize,
+}
+
+pub(super) fn load_config(value: &Yaml) -> anyhow::Result<()> {
+    if let Yaml::Hash(map) = value {
+        let mut listen: Option<TcpListenConfig> = None;
+        let mut max_batch_size = 64;
+
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "listen" => {
+                let config = g3_yaml::value::as_tcp_listen_config(v)
+                    .context(format!("invalid tcp listen config value for key {k}"))?;
+                listen = Some(config);
+                Ok(())
+            }
+            "max_batch_size" => {
+                max_batch_size = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        let Some(listen) = listen else {
+            return Err(anyhow!("no listen address set"));
+        };
+        listen.check().context("invalid listen address")?;
+        if max_batch_size == 0 {
+            return Err(anyhow!("max batch size should not be 0"));
+        }
+
+        TCP_FRONTEND_CONFIG_LOCK
+            .set(Arc::new(TcpFrontendConfig {
+                listen,
+                max_batch_size,
+            }))
+            .map_err(|_| anyhow!("duplicate tcp frontend config"))?;
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "yaml value type for the tcp frontend config should be 'map'"
+        ))
+    }
+}
@@ -30,6 +30,12 @@ pub(crate) use stats::FrontendStats;
 mod udp_dgram;
 use udp_dgram::UdpDgramFrontend;
 
+mod tcp_stream;
+pub(crate) use tcp_stream::TcpStreamFrontend;
+
+mod tls_stream;
+pub(crate) use tls_stream::TlsStreamFrontend;
+
 pub(super) struct Frontend {
     io: UdpDgramFrontend,
     stats: Arc<FrontendStats>,
@@ -72,7 +78,7 @@ impl Frontend {
                                 continue;
                             };
 
-                            let Some(location) = self.fetch(ip) else {
+                            let Some(location) = fetch(ip) else {
                                 continue;
                             };
 
@@ -97,31 +103,59 @@ impl Frontend {
             }
         }
     }
+}
 
-    fn fetch(&self, ip: IpAddr) -> Option<IpLocation> {
-        let mut builder = IpLocationBuilder::default();
+fn fetch(ip: IpAddr) -> Option<IpLocation> {
+    let mut builder = IpLocationBuilder::default();
 
-        if let Some(db) = g3_geoip_db::store::load_country() {
-            if let Some((net, v)) = db.longest_match(ip) {
+    let mut country_found = false;
+    if let Some(db) = g3_geoip_db::store::load_country() {
+        if let Some((net, v)) = db.longest_match(ip) {
+            builder.set_network(net);
+            builder.set_country(v.country);
+            builder.set_continent(v.continent);
+            country_found = true;
+        }
+    }
+    if !country_found {
+        if let Some(db) = g3_geoip_db::store::load_mmdb_city() {
+            if let Some((net, v)) = db.lookup_country(ip) {
                 builder.set_network(net);
-                builder.set_country(v.country);
-                builder.set_continent(v.continent);
+                if let Some(country) = v.country {
+                    builder.set_country(country);
+                }
+                if let Some(continent) = v.continent {
+                    builder.set_continent(continent);
+                }
             }
         }
+    }
 
-        if let Some(asn_db) = g3_geoip_db::store::load_asn() {
-            if let Some((net, v)) = asn_db.longest_match(ip) {
+    let mut asn_found = false;
+    if let Some(asn_db) = g3_geoip_db::store::load_asn() {
+        if let Some((net, v)) = asn_db.longest_match(ip) {
+            builder.set_network(net);
+            builder.set_as_number(v.number);
+            if let Some(name) = v.isp_name() {
+                builder.set_isp_name(name.to_string());
+            }
+            if let Some(domain) = v.isp_domain() {
+                builder.set_isp_domain(domain.to_string());
+            }
+            asn_found = true;
+        }
+    }
+    if !asn_found {
+        if let Some(db) = g3_geoip_db::store::load_mmdb_asn() {
+            if let Some((net, v)) = db.lookup_asn(ip) {
                 builder.set_network(net);
                 builder.set_as_number(v.number);
-                if let Some(name) = v.isp_name() {
+                if let Some(name) = v.name {
                     builder.set_isp_name(name.to_string());
                 }
-                if let Some(domain) = v.isp_domain() {
-                    builder.set_isp_domain(domain.to_string());
-                }
             }
         }
-
-        builder.build().ok()
     }
+
+    builder.build().ok()
 }
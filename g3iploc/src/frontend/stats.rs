@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
 
 #[derive(Default)]
 pub(crate) struct FrontendStats {
@@ -22,6 +22,8 @@ pub(crate) struct FrontendStats {
     request_invalid: AtomicU64,
     response_total: AtomicU64,
     response_fail: AtomicU64,
+    connection_total: AtomicU64,
+    connection_alive: AtomicI64,
 }
 
 macro_rules! impl_for_field {
@@ -41,4 +43,21 @@ impl FrontendStats {
     impl_for_field!(add_request_invalid, take_request_invalid, request_invalid);
     impl_for_field!(add_response_total, take_response_total, response_total);
     impl_for_field!(add_response_fail, take_response_fail, response_fail);
+    impl_for_field!(
+        add_connection_total,
+        take_connection_total,
+        connection_total
+    );
+
+    pub(crate) fn inc_connection_alive(&self) {
+        self.connection_alive.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn dec_connection_alive(&self) {
+        self.connection_alive.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn get_connection_alive(&self) -> i64 {
+        self.connection_alive.load(Ordering::Relaxed)
+    }
 }
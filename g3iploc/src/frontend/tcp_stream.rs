@@ -0,0 +1,258 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use log::{debug, warn};
+use tokio::io::{AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader, BufWriter};
+use tokio::net::tcp::OwnedReadHalf;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+
+use g3_ip_locate::{Request, Response};
+
+use super::FrontendStats;
+use crate::config::TcpFrontendConfig;
+
+const LENGTH_PREFIX_SIZE: usize = 2;
+const MAX_MESSAGE_SIZE: usize = 1024;
+
+pub(crate) struct TcpStreamFrontend {
+    listener: TcpListener,
+    max_batch_size: usize,
+    stats: Arc<FrontendStats>,
+}
+
+impl TcpStreamFrontend {
+    pub(crate) fn new(
+        config: &TcpFrontendConfig,
+        stats: Arc<FrontendStats>,
+    ) -> anyhow::Result<Self> {
+        let listener = g3_socket::tcp::new_listen_to(&config.listen).map_err(|e| {
+            anyhow!(
+                "failed to listen on tcp address {}: {e}",
+                config.listen.address()
+            )
+        })?;
+        Ok(TcpStreamFrontend {
+            listener,
+            max_batch_size: config.max_batch_size,
+            stats,
+        })
+    }
+
+    pub(crate) async fn run(self, mut quit_receiver: broadcast::Receiver<()>) {
+        loop {
+            tokio::select! {
+                biased;
+
+                r = self.listener.accept() => {
+                    match r {
+                        Ok((stream, peer)) => self.spawn_connection(stream, peer, quit_receiver.resubscribe()),
+                        Err(e) => warn!("failed to accept tcp frontend connection: {e}"),
+                    }
+                }
+                _ = quit_receiver.recv() => return,
+            }
+        }
+    }
+
+    fn spawn_connection(
+        &self,
+        stream: TcpStream,
+        peer: SocketAddr,
+        quit_receiver: broadcast::Receiver<()>,
+    ) {
+        let max_batch_size = self.max_batch_size;
+        let stats = self.stats.clone();
+        stats.add_connection_total();
+        stats.inc_connection_alive();
+        tokio::spawn(async move {
+            let mut conn_stats = ConnectionStats::default();
+            let r = serve_connection(
+                stream,
+                max_batch_size,
+                &stats,
+                &mut conn_stats,
+                quit_receiver,
+            )
+            .await;
+            stats.dec_connection_alive();
+            match r {
+                Ok(_) => debug!("tcp frontend connection from {peer} closed, {conn_stats}"),
+                Err(e) => debug!("tcp frontend connection from {peer} closed: {e:?}, {conn_stats}"),
+            }
+        });
+    }
+}
+
+#[derive(Default)]
+struct ConnectionStats {
+    request_total: u64,
+    request_invalid: u64,
+    response_total: u64,
+    batch_total: u64,
+    batch_max_size: usize,
+}
+
+impl std::fmt::Display for ConnectionStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "request total: {}, request invalid: {}, response total: {}, batch total: {}, max batch size: {}",
+            self.request_total,
+            self.request_invalid,
+            self.response_total,
+            self.batch_total,
+            self.batch_max_size
+        )
+    }
+}
+
+/// get the length of the next message if it has been fully buffered
+fn buffered_msg_len(reader: &BufReader<OwnedReadHalf>) -> Option<usize> {
+    let buf = reader.buffer();
+    if buf.len() < LENGTH_PREFIX_SIZE {
+        return None;
+    }
+    let len = u16::from_be_bytes([buf[0], buf[1]]) as usize;
+    if buf.len() - LENGTH_PREFIX_SIZE >= len {
+        Some(len)
+    } else {
+        None
+    }
+}
+
+async fn recv_msg(reader: &mut BufReader<OwnedReadHalf>, buf: &mut [u8]) -> io::Result<usize> {
+    let len = reader.read_u16().await? as usize;
+    if len > buf.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("too large message size {len}"),
+        ));
+    }
+    reader.read_exact(&mut buf[..len]).await?;
+    Ok(len)
+}
+
+async fn serve_connection(
+    stream: TcpStream,
+    max_batch_size: usize,
+    stats: &FrontendStats,
+    conn_stats: &mut ConnectionStats,
+    mut quit_receiver: broadcast::Receiver<()>,
+) -> anyhow::Result<()> {
+    let (r, w) = stream.into_split();
+    let mut reader = BufReader::new(r);
+    let mut writer = BufWriter::new(w);
+
+    let mut recv_buf = [0u8; MAX_MESSAGE_SIZE];
+    loop {
+        let len = tokio::select! {
+            biased;
+
+            r = recv_msg(&mut reader, &mut recv_buf) => {
+                match r {
+                    Ok(len) => len,
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+                    Err(e) => return Err(anyhow!("recv error: {e}")),
+                }
+            }
+            _ = quit_receiver.recv() => return Ok(()),
+        };
+
+        // handle all pipelined requests that are already received, and send the responses at once
+        let mut batch_size = 1;
+        handle_msg(&recv_buf[..len], &mut writer, stats, conn_stats).await?;
+        while batch_size < max_batch_size {
+            if buffered_msg_len(&reader).is_none() {
+                break;
+            }
+            // the message has been fully buffered, so this won't block
+            let len = recv_msg(&mut reader, &mut recv_buf)
+                .await
+                .map_err(|e| anyhow!("recv error: {e}"))?;
+            handle_msg(&recv_buf[..len], &mut writer, stats, conn_stats).await?;
+            batch_size += 1;
+        }
+        conn_stats.batch_total += 1;
+        conn_stats.batch_max_size = conn_stats.batch_max_size.max(batch_size);
+
+        writer
+            .flush()
+            .await
+            .map_err(|e| anyhow!("send error: {e}"))?;
+    }
+}
+
+async fn handle_msg<W>(
+    msg: &[u8],
+    writer: &mut W,
+    stats: &FrontendStats,
+    conn_stats: &mut ConnectionStats,
+) -> anyhow::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    stats.add_request_total();
+    conn_stats.request_total += 1;
+
+    let req = match Request::parse_req(msg) {
+        Ok(req) => req,
+        Err(e) => {
+            stats.add_request_invalid();
+            conn_stats.request_invalid += 1;
+            warn!("invalid request: {e:?}");
+            return Ok(());
+        }
+    };
+    let Some(ip) = req.ip() else {
+        stats.add_request_invalid();
+        conn_stats.request_invalid += 1;
+        return Ok(());
+    };
+
+    let Some(location) = super::fetch(ip) else {
+        return Ok(());
+    };
+
+    match Response::encode_new(ip, location, 300) {
+        Ok(buf) => {
+            let Ok(len) = u16::try_from(buf.len()) else {
+                warn!("too large response for ip {ip}");
+                return Ok(());
+            };
+            stats.add_response_total();
+            conn_stats.response_total += 1;
+            // the data will be buffered, and all of them will be flushed at the end of the batch
+            if let Err(e) = writer.write_all(&len.to_be_bytes()).await {
+                stats.add_response_fail();
+                return Err(anyhow!("send error: {e}"));
+            }
+            if let Err(e) = writer.write_all(&buf).await {
+                stats.add_response_fail();
+                return Err(anyhow!("send error: {e}"));
+            }
+        }
+        Err(e) => {
+            warn!("failed to encode response for ip {ip}: {e}");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,135 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use log::{debug, warn};
+use tokio::net::TcpStream;
+use tokio::sync::broadcast;
+
+use g3_daemon::frontend::{TlsFrontendConfig, TlsFrontendListener, TlsFrontendStream};
+use g3_ip_locate::{Request, Response};
+
+use super::FrontendStats;
+
+pub(crate) struct TlsStreamFrontend {
+    listener: TlsFrontendListener,
+    stats: Arc<FrontendStats>,
+}
+
+impl TlsStreamFrontend {
+    pub(crate) fn new(
+        config: &TlsFrontendConfig,
+        stats: Arc<FrontendStats>,
+    ) -> anyhow::Result<Self> {
+        let listener = TlsFrontendListener::new(config)?;
+        Ok(TlsStreamFrontend { listener, stats })
+    }
+
+    pub(crate) async fn run(self, mut quit_receiver: broadcast::Receiver<()>) {
+        loop {
+            tokio::select! {
+                biased;
+
+                r = self.listener.accept() => {
+                    match r {
+                        Ok((stream, peer)) => self.spawn_connection(stream, peer, quit_receiver.resubscribe()),
+                        Err(e) => warn!("failed to accept tls frontend connection: {e}"),
+                    }
+                }
+                _ = quit_receiver.recv() => return,
+            }
+        }
+    }
+
+    fn spawn_connection(
+        &self,
+        stream: TcpStream,
+        peer: SocketAddr,
+        quit_receiver: broadcast::Receiver<()>,
+    ) {
+        let handshake = self.listener.handshake(stream);
+        let stats = self.stats.clone();
+        stats.add_connection_total();
+        stats.inc_connection_alive();
+        tokio::spawn(async move {
+            let r = match handshake.await {
+                Ok(io) => serve_connection(io, &stats, quit_receiver).await,
+                Err(e) => Err(e),
+            };
+            stats.dec_connection_alive();
+            if let Err(e) = r {
+                debug!("tls frontend connection from {peer} closed: {e:?}");
+            }
+        });
+    }
+}
+
+async fn serve_connection(
+    mut io: TlsFrontendStream,
+    stats: &FrontendStats,
+    mut quit_receiver: broadcast::Receiver<()>,
+) -> anyhow::Result<()> {
+    let mut recv_buf = [0u8; 1024];
+    loop {
+        let len = tokio::select! {
+            biased;
+
+            r = io.recv_msg(&mut recv_buf) => {
+                match r {
+                    Ok(len) => len,
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+                    Err(e) => return Err(anyhow!("recv error: {e}")),
+                }
+            }
+            _ = quit_receiver.recv() => return Ok(()),
+        };
+        stats.add_request_total();
+
+        let req = match Request::parse_req(&recv_buf[..len]) {
+            Ok(req) => req,
+            Err(e) => {
+                stats.add_request_invalid();
+                warn!("invalid request: {e:?}");
+                continue;
+            }
+        };
+        let Some(ip) = req.ip() else {
+            stats.add_request_invalid();
+            continue;
+        };
+
+        let Some(location) = super::fetch(ip) else {
+            continue;
+        };
+
+        match Response::encode_new(ip, location, 300) {
+            Ok(buf) => {
+                stats.add_response_total();
+                if let Err(e) = io.send_msg(&buf).await {
+                    stats.add_response_fail();
+                    return Err(anyhow!("send error: {e}"));
+                }
+            }
+            Err(e) => {
+                warn!("failed to encode response for ip {ip}: {e}");
+            }
+        }
+    }
+}
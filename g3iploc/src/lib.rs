@@ -16,6 +16,7 @@
 
 use std::sync::Arc;
 
+use anyhow::Context;
 use log::{debug, warn};
 use tokio::sync::{broadcast, mpsc};
 
@@ -26,10 +27,11 @@ mod build;
 pub mod opts;
 use opts::ProcArgs;
 
+mod signal;
 mod stat;
 
 mod frontend;
-use frontend::{Frontend, FrontendStats};
+use frontend::{Frontend, FrontendStats, TcpStreamFrontend, TlsStreamFrontend};
 
 pub async fn run(proc_args: &ProcArgs) -> anyhow::Result<()> {
     let frontend_stats = Arc::new(FrontendStats::default());
@@ -37,6 +39,8 @@ pub async fn run(proc_args: &ProcArgs) -> anyhow::Result<()> {
     let (wait_sender, mut wait_receiver) =
         mpsc::channel(g3_daemon::runtime::worker::worker_count().max(1));
 
+    signal::register_reload().context("failed to setup signal handler")?;
+
     if let Some(stats_config) = g3_daemon::stat::config::get_global_stat_config() {
         stat::spawn_working_thread(stats_config, frontend_stats.clone())?;
     }
@@ -62,6 +66,18 @@ pub async fn run(proc_args: &ProcArgs) -> anyhow::Result<()> {
         });
     }
 
+    if let Some(tcp_config) = config::get_tcp_frontend_config() {
+        let tcp_frontend = TcpStreamFrontend::new(&tcp_config, frontend_stats.clone())
+            .context("failed to setup tcp frontend")?;
+        tokio::spawn(tcp_frontend.run(quit_sender.subscribe()));
+    }
+
+    if let Some(tls_config) = g3_daemon::frontend::TlsFrontendConfig::get() {
+        let tls_frontend = TlsStreamFrontend::new(&tls_config, frontend_stats.clone())
+            .context("failed to setup tls frontend")?;
+        tokio::spawn(tls_frontend.run(quit_sender.subscribe()));
+    }
+
     if let Err(e) = tokio::signal::ctrl_c().await {
         warn!("failed to recv Ctrl-C signal: {e}");
     }
@@ -0,0 +1,36 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use log::{info, warn};
+
+use g3_daemon::signal::AsyncSignalAction;
+
+#[derive(Clone, Copy)]
+struct ReloadAction {}
+
+impl AsyncSignalAction for ReloadAction {
+    async fn run(&self) {
+        info!("reloading geoip databases");
+        match crate::config::reload_mmdb() {
+            Ok(_) => info!("reload finished"),
+            Err(e) => warn!("reload aborted: {e:?}"),
+        }
+    }
+}
+
+pub(crate) fn register_reload() -> anyhow::Result<()> {
+    g3_daemon::signal::register_reload(ReloadAction {})
+}
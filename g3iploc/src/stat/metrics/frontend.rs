@@ -30,4 +30,9 @@ pub(crate) fn emit_stats(client: &mut StatsdClient, s: &FrontendStats) {
     emit_count!(take_request_invalid, "request_invalid");
     emit_count!(take_response_total, "response_total");
     emit_count!(take_response_fail, "response_fail");
+    emit_count!(take_connection_total, "connection_total");
+
+    client
+        .gauge("frontend.connection_alive", s.get_connection_alive())
+        .send();
 }
@@ -14,12 +14,14 @@
  * limitations under the License.
  */
 
+use std::sync::Arc;
+
 use openssl::pkey::{PKey, Private};
 use tokio::sync::mpsc;
 
 use g3_openssl::async_job::{SyncOperation, TokioAsyncOperation};
 
-use super::{Backend, DispatchedKeylessRequest};
+use super::{Backend, BackendHealth, DispatchedKeylessRequest};
 use crate::config::backend::AsyncJobBackendConfig;
 use crate::protocol::{KeylessErrorResponse, KeylessResponse};
 use crate::serve::{WrappedKeylessRequest, WrappedKeylessResponse};
@@ -33,8 +35,13 @@ impl AsyncJobBackend {
         AsyncJobBackend { config }
     }
 
-    async fn loop_run(self, mut receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
+    async fn loop_run(
+        self,
+        mut receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
         while let Some(req) = receiver.recv().await {
+            health.record_queue_latency(req.inner.queue_latency());
             let DispatchedKeylessRequest {
                 inner,
                 key,
@@ -53,6 +60,7 @@ impl AsyncJobBackend {
             };
 
             let async_op_timeout = self.config.async_op_timeout;
+            let task_health = health.clone();
             tokio::spawn(async move {
                 let rsp = match tokio::time::timeout(async_op_timeout, task).await {
                     Ok(Ok(r)) => {
@@ -64,6 +72,8 @@ impl AsyncJobBackend {
                         rsp
                     }
                     Err(_) => {
+                        // the stuck async job will be cancelled when dropped
+                        task_health.add_timed_out();
                         req_server_stats.add_crypto_fail();
                         rsp
                     }
@@ -75,28 +85,52 @@ impl AsyncJobBackend {
 }
 
 impl Backend for AsyncJobBackend {
-    async fn run_rsa_2048(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
-        self.loop_run(receiver).await
+    async fn run_rsa_2048(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
+        self.loop_run(receiver, health).await
     }
 
-    async fn run_rsa_3072(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
-        self.loop_run(receiver).await
+    async fn run_rsa_3072(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
+        self.loop_run(receiver, health).await
     }
 
-    async fn run_rsa_4096(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
-        self.loop_run(receiver).await
+    async fn run_rsa_4096(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
+        self.loop_run(receiver, health).await
     }
 
-    async fn run_ecdsa_p256(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
-        self.loop_run(receiver).await
+    async fn run_ecdsa_p256(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
+        self.loop_run(receiver, health).await
     }
 
-    async fn run_ecdsa_p384(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
-        self.loop_run(receiver).await
+    async fn run_ecdsa_p384(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
+        self.loop_run(receiver, health).await
     }
 
-    async fn run_ecdsa_p521(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
-        self.loop_run(receiver).await
+    async fn run_ecdsa_p521(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
+        self.loop_run(receiver, health).await
     }
 }
 
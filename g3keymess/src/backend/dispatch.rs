@@ -15,14 +15,16 @@
  */
 
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
+use arc_swap::ArcSwap;
 use openssl::nid::Nid;
 use openssl::pkey::Id;
 use tokio::sync::mpsc;
 
 use g3_types::sync::GlobalInit;
 
-use super::DispatchedKeylessRequest;
+use super::{BackendHealth, DispatchedKeylessRequest};
 
 const DEFAULT_COUNTER_SHIFT: u8 = 3;
 
@@ -30,10 +32,27 @@ static DISPATCH_CONTAINER: GlobalInit<DispatcherContainer> = GlobalInit::new(
     DispatcherContainer::with_counter_shift(DEFAULT_COUNTER_SHIFT),
 );
 
+pub(super) struct DispatchWorker {
+    pub(super) sender: ArcSwap<mpsc::Sender<DispatchedKeylessRequest>>,
+    pub(super) health: Arc<BackendHealth>,
+}
+
+impl DispatchWorker {
+    pub(super) fn new(
+        sender: mpsc::Sender<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) -> Self {
+        DispatchWorker {
+            sender: ArcSwap::new(Arc::new(sender)),
+            health,
+        }
+    }
+}
+
 struct Dispatcher {
     counter: AtomicUsize,
     counter_shift: u8,
-    workers: Vec<mpsc::Sender<DispatchedKeylessRequest>>,
+    workers: Vec<Arc<DispatchWorker>>,
 }
 
 impl Default for Dispatcher {
@@ -52,9 +71,21 @@ impl Dispatcher {
     }
 
     fn dispatch(&self, req: DispatchedKeylessRequest) -> Result<(), DispatchedKeylessRequest> {
+        let len = self.workers.len();
         let cur = self.counter.fetch_add(1, Ordering::Relaxed);
-        let id = (cur >> self.counter_shift) % self.workers.len();
-        self.workers[id].try_send(req).map_err(|e| e.into_inner())
+        let id = (cur >> self.counter_shift) % len;
+        // skip the unhealthy workers, fallback to local processing if none available
+        for i in 0..len {
+            let worker = &self.workers[(id + i) % len];
+            if worker.health.is_healthy() {
+                return worker
+                    .sender
+                    .load()
+                    .try_send(req)
+                    .map_err(|e| e.into_inner());
+            }
+        }
+        Err(req)
     }
 }
 
@@ -109,13 +140,10 @@ pub(crate) fn dispatch(req: DispatchedKeylessRequest) -> Result<(), DispatchedKe
 
 macro_rules! define_register {
     ($method:ident, $field:ident) => {
-        pub(super) fn $method(
-            rsp_sender: mpsc::Sender<DispatchedKeylessRequest>,
-            counter_shift: u8,
-        ) {
+        pub(super) fn $method(worker: Arc<DispatchWorker>, counter_shift: u8) {
             DISPATCH_CONTAINER.with_mut(|c| {
                 c.$field.counter_shift = counter_shift;
-                c.$field.workers.push(rsp_sender);
+                c.$field.workers.push(worker);
             });
         }
     };
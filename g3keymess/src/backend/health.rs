@@ -0,0 +1,96 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+static HEALTH_EPOCH: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+pub(crate) struct BackendHealth {
+    /// the time (in microseconds since epoch) when the current operation started, 0 if idle
+    busy_since: AtomicU64,
+    /// the queue latency (in microseconds) of the last dequeued request
+    queue_latency: AtomicU64,
+    timed_out: AtomicU64,
+    healthy: AtomicBool,
+}
+
+impl Default for BackendHealth {
+    fn default() -> Self {
+        BackendHealth {
+            busy_since: AtomicU64::new(0),
+            queue_latency: AtomicU64::new(0),
+            timed_out: AtomicU64::new(0),
+            healthy: AtomicBool::new(true),
+        }
+    }
+}
+
+impl BackendHealth {
+    fn now() -> u64 {
+        // add 1 to make sure it won't be 0
+        HEALTH_EPOCH.elapsed().as_micros() as u64 + 1
+    }
+
+    pub(super) fn set_busy(&self) {
+        self.busy_since.store(Self::now(), Ordering::Relaxed);
+    }
+
+    pub(super) fn set_idle(&self) {
+        self.busy_since.store(0, Ordering::Relaxed);
+    }
+
+    pub(super) fn busy_duration(&self) -> Option<Duration> {
+        let since = self.busy_since.load(Ordering::Relaxed);
+        if since == 0 {
+            return None;
+        }
+        Some(Duration::from_micros(Self::now().saturating_sub(since)))
+    }
+
+    pub(super) fn record_queue_latency(&self, latency: Duration) {
+        self.queue_latency
+            .store(latency.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    pub(super) fn reset_queue_latency(&self) {
+        self.queue_latency.store(0, Ordering::Relaxed);
+    }
+
+    pub(super) fn queue_latency(&self) -> Duration {
+        Duration::from_micros(self.queue_latency.load(Ordering::Relaxed))
+    }
+
+    #[cfg(feature = "openssl-async-job")]
+    pub(super) fn add_timed_out(&self) {
+        self.timed_out.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn timed_out(&self) -> u64 {
+        self.timed_out.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(super) fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
+
+    /// return true if the state changed
+    pub(super) fn set_healthy(&self, healthy: bool) -> bool {
+        self.healthy.swap(healthy, Ordering::Relaxed) != healthy
+    }
+}
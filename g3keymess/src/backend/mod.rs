@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+use std::sync::Arc;
+
 use openssl::pkey::{PKey, Private};
 use tokio::runtime::Handle;
 use tokio::sync::mpsc;
@@ -23,6 +25,12 @@ use crate::serve::{WrappedKeylessRequest, WrappedKeylessResponse};
 
 mod dispatch;
 pub(crate) use dispatch::dispatch;
+use dispatch::DispatchWorker;
+
+mod health;
+use health::BackendHealth;
+
+mod watchdog;
 
 #[cfg(feature = "openssl-async-job")]
 mod async_job;
@@ -38,32 +46,76 @@ pub(crate) struct DispatchedKeylessRequest {
 }
 
 trait Backend {
-    async fn run_rsa_2048(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>);
-    async fn run_rsa_3072(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>);
-    async fn run_rsa_4096(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>);
-    async fn run_ecdsa_p256(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>);
-    async fn run_ecdsa_p384(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>);
-    async fn run_ecdsa_p521(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>);
+    async fn run_rsa_2048(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    );
+    async fn run_rsa_3072(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    );
+    async fn run_rsa_4096(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    );
+    async fn run_ecdsa_p256(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    );
+    async fn run_ecdsa_p384(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    );
+    async fn run_ecdsa_p521(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    );
 }
 
-pub fn create(_id: usize, handle: &Handle) -> anyhow::Result<()> {
+pub fn create(id: usize, handle: &Handle) -> anyhow::Result<()> {
     let config = crate::config::backend::get_config();
 
     macro_rules! setup {
         ($run:ident, $register:ident) => {
             let (sender, receiver) = mpsc::channel(config.dispatch_channel_size);
-            match config.driver {
+            let health = Arc::new(BackendHealth::default());
+            let driver = config.driver;
+            let spawn_handle = handle.clone();
+            let spawn_health = health.clone();
+            let spawn = move |receiver: mpsc::Receiver<DispatchedKeylessRequest>| match driver {
                 BackendDriverConfig::Simple => {
                     let backend = simple::SimpleBackend::new();
-                    handle.spawn(backend.$run(receiver));
+                    spawn_handle.spawn(backend.$run(receiver, spawn_health.clone()));
                 }
                 #[cfg(feature = "openssl-async-job")]
                 BackendDriverConfig::AsyncJob(config) => {
                     let backend = async_job::AsyncJobBackend::new(config);
-                    handle.spawn(backend.$run(receiver));
+                    spawn_handle.spawn(backend.$run(receiver, spawn_health.clone()));
                 }
+            };
+            spawn(receiver);
+
+            let worker = Arc::new(DispatchWorker::new(sender, health));
+            if let Some(watchdog_config) = config.watchdog {
+                let name = stringify!($run)
+                    .strip_prefix("run_")
+                    .unwrap_or(stringify!($run));
+                watchdog::spawn(
+                    id,
+                    name,
+                    watchdog_config,
+                    config.dispatch_channel_size,
+                    worker.clone(),
+                    spawn,
+                );
             }
-            dispatch::$register(sender, config.dispatch_counter_shift);
+            dispatch::$register(worker, config.dispatch_counter_shift);
         };
     }
 
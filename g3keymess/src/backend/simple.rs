@@ -14,9 +14,11 @@
  * limitations under the License.
  */
 
+use std::sync::Arc;
+
 use tokio::sync::mpsc;
 
-use super::{Backend, DispatchedKeylessRequest};
+use super::{Backend, BackendHealth, DispatchedKeylessRequest};
 
 pub(super) struct SimpleBackend {}
 
@@ -25,36 +27,67 @@ impl SimpleBackend {
         SimpleBackend {}
     }
 
-    async fn run(self, mut receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
+    async fn run(
+        self,
+        mut receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
         while let Some(req) = receiver.recv().await {
+            health.record_queue_latency(req.inner.queue_latency());
+            health.set_busy();
             let rsp = req.inner.process_by_openssl(&req.key);
+            health.set_idle();
             let _ = req.rsp_sender.send(req.inner.build_response(rsp)).await;
         }
     }
 }
 
 impl Backend for SimpleBackend {
-    async fn run_rsa_2048(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
-        self.run(receiver).await
+    async fn run_rsa_2048(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
+        self.run(receiver, health).await
     }
 
-    async fn run_rsa_3072(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
-        self.run(receiver).await
+    async fn run_rsa_3072(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
+        self.run(receiver, health).await
     }
 
-    async fn run_rsa_4096(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
-        self.run(receiver).await
+    async fn run_rsa_4096(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
+        self.run(receiver, health).await
     }
 
-    async fn run_ecdsa_p256(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
-        self.run(receiver).await
+    async fn run_ecdsa_p256(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
+        self.run(receiver, health).await
     }
 
-    async fn run_ecdsa_p384(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
-        self.run(receiver).await
+    async fn run_ecdsa_p384(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
+        self.run(receiver, health).await
     }
 
-    async fn run_ecdsa_p521(self, receiver: mpsc::Receiver<DispatchedKeylessRequest>) {
-        self.run(receiver).await
+    async fn run_ecdsa_p521(
+        self,
+        receiver: mpsc::Receiver<DispatchedKeylessRequest>,
+        health: Arc<BackendHealth>,
+    ) {
+        self.run(receiver, health).await
     }
 }
@@ -0,0 +1,97 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use log::{info, warn};
+use tokio::sync::mpsc;
+
+use super::{DispatchWorker, DispatchedKeylessRequest};
+use crate::config::backend::BackendWatchdogConfig;
+
+pub(super) fn spawn<F>(
+    worker_id: usize,
+    name: &'static str,
+    config: BackendWatchdogConfig,
+    channel_size: usize,
+    worker: Arc<DispatchWorker>,
+    respawn: F,
+) where
+    F: Fn(mpsc::Receiver<DispatchedKeylessRequest>) + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.check_interval);
+        let mut last_timed_out = 0;
+
+        loop {
+            interval.tick().await;
+
+            let health = &worker.health;
+            let sender = worker.sender.load_full();
+            if sender.is_closed() {
+                warn!("backend {name} in worker #{worker_id} exited unexpectedly, respawn it");
+                let (sender, receiver) = mpsc::channel(channel_size);
+                respawn(receiver);
+                worker.sender.store(Arc::new(sender));
+                health.set_idle();
+                health.reset_queue_latency();
+                if health.set_healthy(true) {
+                    info!("backend {name} in worker #{worker_id} is healthy again after respawn");
+                }
+                continue;
+            }
+
+            let timed_out = health.timed_out();
+            if timed_out > last_timed_out {
+                warn!(
+                    "backend {name} in worker #{worker_id} cancelled {} stuck async jobs",
+                    timed_out - last_timed_out
+                );
+                last_timed_out = timed_out;
+            }
+
+            if let Some(busy) = health.busy_duration() {
+                if busy > config.stuck_timeout {
+                    if health.set_healthy(false) {
+                        warn!(
+                            "backend {name} in worker #{worker_id} stuck for {busy:?}, mark it unhealthy"
+                        );
+                    }
+                    continue;
+                }
+            }
+
+            let queue_latency = health.queue_latency();
+            if queue_latency > config.max_queue_latency {
+                if sender.capacity() < sender.max_capacity() {
+                    // still has pending requests
+                    if health.set_healthy(false) {
+                        warn!(
+                            "backend {name} in worker #{worker_id} has queue latency {queue_latency:?}, mark it unhealthy"
+                        );
+                    }
+                    continue;
+                }
+                // all pending requests have been drained
+                health.reset_queue_latency();
+            }
+
+            if health.set_healthy(true) {
+                info!("backend {name} in worker #{worker_id} is healthy again");
+            }
+        }
+    });
+}
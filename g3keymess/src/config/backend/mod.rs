@@ -24,6 +24,9 @@ mod async_job;
 #[cfg(feature = "openssl-async-job")]
 pub(crate) use async_job::AsyncJobBackendConfig;
 
+mod watchdog;
+pub(crate) use watchdog::BackendWatchdogConfig;
+
 static BACKEND_CONFIG: GlobalInit<BackendConfig> =
     GlobalInit::new(BackendConfig::with_driver(BackendDriverConfig::Simple));
 
@@ -31,6 +34,7 @@ pub(crate) struct BackendConfig {
     pub(crate) dispatch_channel_size: usize,
     pub(crate) dispatch_counter_shift: u8,
     pub(crate) driver: BackendDriverConfig,
+    pub(crate) watchdog: Option<BackendWatchdogConfig>,
 }
 
 impl Default for BackendConfig {
@@ -45,10 +49,12 @@ impl BackendConfig {
             dispatch_channel_size: 1024,
             dispatch_counter_shift: 3,
             driver,
+            watchdog: None,
         }
     }
 }
 
+#[derive(Clone, Copy)]
 pub(crate) enum BackendDriverConfig {
     Simple,
     #[cfg(feature = "openssl-async-job")]
@@ -74,6 +80,15 @@ pub(super) fn load(value: &Yaml) -> anyhow::Result<()> {
                     config.driver = BackendDriverConfig::AsyncJob(driver);
                     Ok(())
                 }
+                "watchdog" => {
+                    if let Yaml::Boolean(false) = v {
+                        config.watchdog = None;
+                    } else {
+                        let watchdog = BackendWatchdogConfig::parse_yaml(v)?;
+                        config.watchdog = Some(watchdog);
+                    }
+                    Ok(())
+                }
                 _ => Err(anyhow!("invalid key {k}")),
             })?;
         }
@@ -0,0 +1,70 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use yaml_rust::Yaml;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BackendWatchdogConfig {
+    pub(crate) check_interval: Duration,
+    pub(crate) stuck_timeout: Duration,
+    pub(crate) max_queue_latency: Duration,
+}
+
+impl Default for BackendWatchdogConfig {
+    fn default() -> Self {
+        BackendWatchdogConfig {
+            check_interval: Duration::from_secs(1),
+            stuck_timeout: Duration::from_secs(5),
+            max_queue_latency: Duration::from_millis(500),
+        }
+    }
+}
+
+impl BackendWatchdogConfig {
+    pub(super) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => {
+                let mut config = BackendWatchdogConfig::default();
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "check_interval" => {
+                        config.check_interval = g3_yaml::humanize::as_duration(v)?;
+                        Ok(())
+                    }
+                    "stuck_timeout" => {
+                        config.stuck_timeout = g3_yaml::humanize::as_duration(v)?;
+                        Ok(())
+                    }
+                    "max_queue_latency" => {
+                        config.max_queue_latency = g3_yaml::humanize::as_duration(v)?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                if config.check_interval.is_zero() {
+                    return Err(anyhow!("check interval should not be zero"));
+                }
+                Ok(config)
+            }
+            Yaml::Boolean(true) => Ok(BackendWatchdogConfig::default()),
+            _ => Err(anyhow!(
+                "yaml value type for `backend watchdog` should be `map` or `bool`"
+            )),
+        }
+    }
+}
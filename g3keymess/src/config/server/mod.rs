@@ -24,6 +24,7 @@ use slog::Logger;
 use yaml_rust::{yaml, Yaml};
 
 use g3_histogram::HistogramMetricsConfig;
+use g3_types::limit::RateLimitQuotaConfig;
 use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::net::TcpListenConfig;
 use g3_yaml::{HybridParser, YamlDocPosition};
@@ -44,6 +45,9 @@ pub(crate) struct KeyServerConfig {
     #[cfg(feature = "openssl-async-job")]
     pub(crate) async_op_timeout: Duration,
     pub(crate) concurrency_limit: usize,
+    pub(crate) request_rate_limit: Option<RateLimitQuotaConfig>,
+    pub(crate) client_ip_request_rate_limit: Option<RateLimitQuotaConfig>,
+    pub(crate) key_request_rate_limit: Option<RateLimitQuotaConfig>,
     pub(crate) extra_metrics_tags: Option<Arc<StaticMetricsTags>>,
 }
 
@@ -60,6 +64,9 @@ impl KeyServerConfig {
             #[cfg(feature = "openssl-async-job")]
             async_op_timeout: Duration::from_secs(1),
             concurrency_limit: 0,
+            request_rate_limit: None,
+            client_ip_request_rate_limit: None,
+            key_request_rate_limit: None,
             extra_metrics_tags: None,
         }
     }
@@ -131,6 +138,24 @@ impl KeyServerConfig {
                 self.concurrency_limit = g3_yaml::value::as_usize(v)?;
                 Ok(())
             }
+            "request_rate_limit" | "connection_request_rate_limit" => {
+                let quota = g3_yaml::value::as_rate_limit_quota(v)
+                    .context(format!("invalid request quota value for key {k}"))?;
+                self.request_rate_limit = Some(quota);
+                Ok(())
+            }
+            "client_ip_request_rate_limit" | "ip_request_rate_limit" => {
+                let quota = g3_yaml::value::as_rate_limit_quota(v)
+                    .context(format!("invalid request quota value for key {k}"))?;
+                self.client_ip_request_rate_limit = Some(quota);
+                Ok(())
+            }
+            "key_request_rate_limit" => {
+                let quota = g3_yaml::value::as_rate_limit_quota(v)
+                    .context(format!("invalid request quota value for key {k}"))?;
+                self.key_request_rate_limit = Some(quota);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
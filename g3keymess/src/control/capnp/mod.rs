@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+use g3_daemon::control::CtlAccessLevel;
+
 use g3keymess_proto::proc_capnp::proc_control;
 
 mod common;
@@ -26,8 +28,9 @@ pub fn stop_working_thread() {
     g3_daemon::control::capnp::stop_working_thread();
 }
 
-fn build_capnp_client() -> capnp::capability::Client {
-    let control_client: proc_control::Client = capnp_rpc::new_client(proc::ProcControlImpl);
+fn build_capnp_client(access: CtlAccessLevel) -> capnp::capability::Client {
+    let control_client: proc_control::Client =
+        capnp_rpc::new_client(proc::ProcControlImpl::new(access));
     control_client.client
 }
 
@@ -20,6 +20,7 @@ use anyhow::anyhow;
 use capnp::capability::Promise;
 use capnp_rpc::pry;
 
+use g3_daemon::control::CtlAccessLevel;
 use g3_types::metrics::{MetricTagName, MetricTagValue};
 
 use g3keymess_proto::proc_capnp::proc_control;
@@ -28,7 +29,15 @@ use g3keymess_proto::types_capnp::fetch_result;
 
 use super::set_operation_result;
 
-pub(super) struct ProcControlImpl;
+pub(super) struct ProcControlImpl {
+    access: CtlAccessLevel,
+}
+
+impl ProcControlImpl {
+    pub(super) fn new(access: CtlAccessLevel) -> Self {
+        ProcControlImpl { access }
+    }
+}
 
 impl proc_control::Server for ProcControlImpl {
     fn version(
@@ -45,6 +54,7 @@ impl proc_control::Server for ProcControlImpl {
         _params: proc_control::OfflineParams,
         mut results: proc_control::OfflineResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         Promise::from_future(async move {
             g3_daemon::control::quit::start_graceful_shutdown().await;
             set_operation_result(results.get().init_result(), Ok(()));
@@ -57,6 +67,7 @@ impl proc_control::Server for ProcControlImpl {
         _params: proc_control::CancelShutdownParams,
         mut results: proc_control::CancelShutdownResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         Promise::from_future(async move {
             let r = g3_daemon::control::quit::cancel_graceful_shutdown().await;
             set_operation_result(results.get().init_result(), r);
@@ -69,6 +80,7 @@ impl proc_control::Server for ProcControlImpl {
         _params: proc_control::ReleaseControllerParams,
         mut results: proc_control::ReleaseControllerResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         Promise::from_future(async move {
             let r = g3_daemon::control::quit::release_controller().await;
             set_operation_result(results.get().init_result(), r);
@@ -97,7 +109,7 @@ impl proc_control::Server for ProcControlImpl {
         let server = pry!(pry!(pry!(params.get()).get_name()).to_str());
         pry!(set_fetch_result::<server_control::Owned>(
             results.get().init_server(),
-            super::server::ServerControlImpl::new_client(server),
+            super::server::ServerControlImpl::new_client(server, self.access),
         ));
         Promise::ok(())
     }
@@ -107,6 +119,7 @@ impl proc_control::Server for ProcControlImpl {
         params: proc_control::PublishKeyParams,
         mut results: proc_control::PublishKeyResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let pem = pry!(pry!(pry!(params.get()).get_pem()).to_string());
         Promise::from_future(async move {
             let r = crate::control::bridge::add_key(&pem).await;
@@ -150,6 +163,7 @@ impl proc_control::Server for ProcControlImpl {
         params: proc_control::AddMetricsTagParams,
         mut results: proc_control::AddMetricsTagResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let name = pry!(pry!(pry!(params.get()).get_name()).to_str());
         let value = pry!(pry!(pry!(params.get()).get_value()).to_str());
 
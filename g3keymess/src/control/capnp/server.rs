@@ -21,6 +21,7 @@ use anyhow::anyhow;
 use capnp::capability::Promise;
 use capnp_rpc::pry;
 
+use g3_daemon::control::CtlAccessLevel;
 use g3_types::metrics::{MetricTagName, MetricTagValue, NodeName};
 
 use g3keymess_proto::server_capnp::server_control;
@@ -30,13 +31,17 @@ use crate::serve::KeyServer;
 
 pub(super) struct ServerControlImpl {
     server: Arc<KeyServer>,
+    access: CtlAccessLevel,
 }
 
 impl ServerControlImpl {
-    pub(super) fn new_client(name: &str) -> anyhow::Result<server_control::Client> {
+    pub(super) fn new_client(
+        name: &str,
+        access: CtlAccessLevel,
+    ) -> anyhow::Result<server_control::Client> {
         let name = unsafe { NodeName::new_unchecked(name) };
         let server = crate::serve::get_server(&name)?;
-        Ok(capnp_rpc::new_client(ServerControlImpl { server }))
+        Ok(capnp_rpc::new_client(ServerControlImpl { server, access }))
     }
 
     fn do_add_metrics_tag(&self, name: &str, value: &str) -> anyhow::Result<()> {
@@ -68,6 +73,7 @@ impl server_control::Server for ServerControlImpl {
         params: server_control::AddMetricsTagParams,
         mut results: server_control::AddMetricsTagResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let name = pry!(pry!(pry!(params.get()).get_name()).to_str());
         let value = pry!(pry!(pry!(params.get()).get_value()).to_str());
 
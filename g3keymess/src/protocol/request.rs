@@ -16,13 +16,17 @@
 
 use std::io;
 
+use openssl::bn::BigNumContext;
+use openssl::derive::Deriver;
+use openssl::ec::{EcKey, EcPoint};
+use openssl::error::ErrorStack;
 use openssl::hash::MessageDigest;
 use openssl::md::Md;
 use openssl::nid::Nid;
-use openssl::pkey::{PKey, Private};
+use openssl::pkey::{Id, PKey, Private, Public};
 use openssl::pkey_ctx::PkeyCtx;
 use openssl::rsa::Padding;
-use openssl::sign::RsaPssSaltlen;
+use openssl::sign::{RsaPssSaltlen, Signer};
 use thiserror::Error;
 use tokio::io::{AsyncRead, AsyncReadExt};
 
@@ -39,6 +43,10 @@ pub(crate) enum KeylessAction {
     RsaPssSign(Nid),
     EcdsaSign(Nid),
     Ed25519Sign,
+    /// opcode 0xA0, see [`KeylessRequest::verify_opcode`] for the payload
+    EcdhDerive,
+    /// opcode 0xA3 - 0xA7, see [`KeylessRequest::verify_opcode`] for the payload
+    EcdhHmac(MessageDigest),
 }
 
 #[derive(Debug, Error)]
@@ -174,6 +182,17 @@ impl KeylessRequest {
         Ok(request)
     }
 
+    /// Check the opcode and the payload length, and set the action.
+    ///
+    /// Besides the opcodes defined in the upstream keyless protocol, the following are added:
+    ///
+    /// - 0xA0 ECDH derive: the payload is the peer public key, which is the EC point octet
+    ///   string for EC keys, or the raw public key for X25519 / X448 keys. The response payload
+    ///   is the shared secret.
+    /// - 0xA3 - 0xA7 ECDH then HMAC with SHA1 / SHA224 / SHA256 / SHA384 / SHA512: the payload
+    ///   is a 2 bytes big endian peer public key length, the peer public key encoded as for 0xA0,
+    ///   and then the message. The response payload is the HMAC of the message, keyed by the
+    ///   shared secret.
     pub(crate) fn verify_opcode(&mut self) -> Result<(), KeylessErrorResponse> {
         let action = match self.opcode {
             0x01 => KeylessAction::RsaDecrypt(Padding::PKCS1),
@@ -243,6 +262,27 @@ impl KeylessRequest {
                 self.check_payload_for_message_digest(MessageDigest::sha512())?;
                 KeylessAction::RsaPssSign(Nid::SHA512)
             }
+            0xA0 => KeylessAction::EcdhDerive,
+            0xA3 => {
+                self.check_payload_for_ecdh_hmac()?;
+                KeylessAction::EcdhHmac(MessageDigest::sha1())
+            }
+            0xA4 => {
+                self.check_payload_for_ecdh_hmac()?;
+                KeylessAction::EcdhHmac(MessageDigest::sha224())
+            }
+            0xA5 => {
+                self.check_payload_for_ecdh_hmac()?;
+                KeylessAction::EcdhHmac(MessageDigest::sha256())
+            }
+            0xA6 => {
+                self.check_payload_for_ecdh_hmac()?;
+                KeylessAction::EcdhHmac(MessageDigest::sha384())
+            }
+            0xA7 => {
+                self.check_payload_for_ecdh_hmac()?;
+                KeylessAction::EcdhHmac(MessageDigest::sha512())
+            }
             0xF1 => KeylessAction::Ping,
             _ => return Err(KeylessErrorResponse::new(self.id).bad_op_code()),
         };
@@ -260,6 +300,24 @@ impl KeylessRequest {
         Ok(())
     }
 
+    /// the payload for ecdh hmac should be:
+    ///   2 bytes peer public key length + peer public key + message
+    fn check_payload_for_ecdh_hmac(&self) -> Result<(), KeylessErrorResponse> {
+        if self.payload.len() < 2 {
+            return Err(KeylessErrorResponse::new(self.id).format_error());
+        }
+        let peer_len = ((self.payload[0] as usize) << 8) + self.payload[1] as usize;
+        if peer_len == 0 || self.payload.len() < 2 + peer_len {
+            return Err(KeylessErrorResponse::new(self.id).format_error());
+        }
+        Ok(())
+    }
+
+    fn split_ecdh_hmac_payload(&self) -> (&[u8], &[u8]) {
+        let peer_len = ((self.payload[0] as usize) << 8) + self.payload[1] as usize;
+        self.payload[2..].split_at(peer_len)
+    }
+
     fn check_payload_for_key_size(&self, key_size: usize) -> Result<(), KeylessErrorResponse> {
         match self.opcode {
             0x01 | 0x08 => {
@@ -294,7 +352,10 @@ impl KeylessRequest {
         &self,
         key: &PKey<Private>,
     ) -> Result<KeylessDataResponse, KeylessErrorResponse> {
-        let key_size = key.size();
+        let key_size = match self.action {
+            KeylessAction::EcdhHmac(h) => h.size(),
+            _ => key.size(),
+        };
         let err_rsp = KeylessErrorResponse::new(self.id);
         let mut data_rsp = KeylessDataResponse::new(self.id, key_size);
         match self.action {
@@ -361,7 +422,190 @@ impl KeylessRequest {
                 data_rsp.finalize_payload(len);
                 Ok(data_rsp)
             }
+            KeylessAction::EcdhDerive => {
+                let peer_key =
+                    ecdh_peer_key(key, &self.payload).map_err(|_| err_rsp.format_error())?;
+                let mut deriver = Deriver::new(key).map_err(|_| err_rsp.crypto_fail())?;
+                deriver
+                    .set_peer(&peer_key)
+                    .map_err(|_| err_rsp.crypto_fail())?;
+
+                let len = deriver
+                    .derive(data_rsp.payload_data_mut())
+                    .map_err(|_| err_rsp.crypto_fail())?;
+                data_rsp.finalize_payload(len);
+                Ok(data_rsp)
+            }
+            KeylessAction::EcdhHmac(h) => {
+                let (peer, message) = self.split_ecdh_hmac_payload();
+                let peer_key = ecdh_peer_key(key, peer).map_err(|_| err_rsp.format_error())?;
+                let mut deriver = Deriver::new(key).map_err(|_| err_rsp.crypto_fail())?;
+                deriver
+                    .set_peer(&peer_key)
+                    .map_err(|_| err_rsp.crypto_fail())?;
+                let secret = deriver.derive_to_vec().map_err(|_| err_rsp.crypto_fail())?;
+
+                let hmac_key = PKey::hmac(&secret).map_err(|_| err_rsp.crypto_fail())?;
+                let mut signer = Signer::new(h, &hmac_key).map_err(|_| err_rsp.crypto_fail())?;
+                signer.update(message).map_err(|_| err_rsp.crypto_fail())?;
+
+                let len = signer
+                    .sign(data_rsp.payload_data_mut())
+                    .map_err(|_| err_rsp.crypto_fail())?;
+                data_rsp.finalize_payload(len);
+                Ok(data_rsp)
+            }
             KeylessAction::NotSet | KeylessAction::Ping => Err(err_rsp.unexpected_op_code()),
         }
     }
 }
+
+/// build the peer public key for ECDH, the encoding should be:
+///  - EC point octet string for EC keys
+///  - raw public key for X25519 and X448 keys
+fn ecdh_peer_key(key: &PKey<Private>, peer: &[u8]) -> Result<PKey<Public>, ErrorStack> {
+    match key.id() {
+        Id::EC => {
+            let ec_key = key.ec_key()?;
+            let group = ec_key.group();
+            let mut ctx = BigNumContext::new()?;
+            let point = EcPoint::from_bytes(group, peer, &mut ctx)?;
+            let peer_key = EcKey::from_public_key(group, &point)?;
+            PKey::from_ec_key(peer_key)
+        }
+        Id::X25519 | Id::X448 => PKey::public_key_from_raw_bytes(peer, key.id()),
+        _ => Err(ErrorStack::get()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+    use openssl::ec::{EcGroup, PointConversionForm};
+
+    use crate::protocol::KeylessResponseErrorCode;
+
+    // RFC 7748, Section 6.1
+    const X25519_ALICE_PRIVATE: [u8; 32] =
+        hex!("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+    const X25519_BOB_PUBLIC: [u8; 32] =
+        hex!("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f");
+    const X25519_SHARED_SECRET: [u8; 32] =
+        hex!("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+
+    fn build_message(id: u32, opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let payload_len = payload.len() as u16;
+        let mut items = vec![0x11, 0x00, 0x01, opcode, 0x12];
+        items.extend_from_slice(&payload_len.to_be_bytes());
+        items.extend_from_slice(payload);
+
+        let mut msg = vec![0x01, 0x00];
+        msg.extend_from_slice(&(items.len() as u16).to_be_bytes());
+        msg.extend_from_slice(&id.to_be_bytes());
+        msg.extend_from_slice(&items);
+        msg
+    }
+
+    async fn round_trip(
+        key: &PKey<Private>,
+        opcode: u8,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, KeylessResponseErrorCode> {
+        let msg = build_message(1, opcode, payload);
+        let mut buf = Vec::new();
+        let mut req = KeylessRequest::read(&mut msg.as_slice(), &mut buf, 0)
+            .await
+            .unwrap();
+        assert_eq!(req.id, 1);
+        req.verify_opcode().map_err(|e| e.error_code())?;
+        let rsp = req.process(key).map_err(|e| e.error_code())?;
+        let len = ((rsp.buf[13] as usize) << 8) + rsp.buf[14] as usize;
+        Ok(rsp.buf[15..15 + len].to_vec())
+    }
+
+    fn ecdh_hmac_payload(peer: &[u8], message: &[u8]) -> Vec<u8> {
+        let mut payload = (peer.len() as u16).to_be_bytes().to_vec();
+        payload.extend_from_slice(peer);
+        payload.extend_from_slice(message);
+        payload
+    }
+
+    fn x25519_key() -> PKey<Private> {
+        PKey::private_key_from_raw_bytes(&X25519_ALICE_PRIVATE, Id::X25519).unwrap()
+    }
+
+    #[tokio::test]
+    async fn ecdh_derive_p256() {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+        let peer = EcKey::generate(&group).unwrap();
+        let mut ctx = BigNumContext::new().unwrap();
+        let peer_public = peer
+            .public_key()
+            .to_bytes(&group, PointConversionForm::UNCOMPRESSED, &mut ctx)
+            .unwrap();
+
+        let secret = round_trip(&key, 0xA0, &peer_public).await.unwrap();
+
+        let peer = PKey::from_ec_key(peer).unwrap();
+        let mut deriver = Deriver::new(&peer).unwrap();
+        deriver.set_peer(&key).unwrap();
+        assert_eq!(secret, deriver.derive_to_vec().unwrap());
+    }
+
+    #[tokio::test]
+    async fn ecdh_derive_x25519() {
+        let key = x25519_key();
+        let secret = round_trip(&key, 0xA0, &X25519_BOB_PUBLIC).await.unwrap();
+        assert_eq!(secret, X25519_SHARED_SECRET);
+    }
+
+    #[tokio::test]
+    async fn ecdh_hmac_sha256() {
+        let key = x25519_key();
+        let payload = ecdh_hmac_payload(&X25519_BOB_PUBLIC, b"g3keymess ecdh hmac");
+        let mac = round_trip(&key, 0xA5, &payload).await.unwrap();
+        assert_eq!(
+            mac,
+            hex!("6867e4689250c375d31ca424751fd92af6ebc98771fdf6057e06b0e558758d3d")
+        );
+    }
+
+    #[tokio::test]
+    async fn ecdh_malformed_peer() {
+        let key = x25519_key();
+        let r = round_trip(&key, 0xA0, &X25519_BOB_PUBLIC[..31]).await;
+        assert!(matches!(r, Err(KeylessResponseErrorCode::FormatError)));
+
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+        let mut point = [0x01u8; 65];
+        point[0] = 0x04;
+        let r = round_trip(&key, 0xA0, &point).await;
+        assert!(matches!(r, Err(KeylessResponseErrorCode::FormatError)));
+        let r = round_trip(&key, 0xA0, &point[..33]).await;
+        assert!(matches!(r, Err(KeylessResponseErrorCode::FormatError)));
+    }
+
+    #[tokio::test]
+    async fn ecdh_hmac_short_payload() {
+        let key = x25519_key();
+
+        let r = round_trip(&key, 0xA5, &[0x00]).await;
+        assert!(matches!(r, Err(KeylessResponseErrorCode::FormatError)));
+
+        let r = round_trip(&key, 0xA5, &ecdh_hmac_payload(&[], b"message")).await;
+        assert!(matches!(r, Err(KeylessResponseErrorCode::FormatError)));
+
+        // the declared peer key length is larger than the payload
+        let mut payload = ecdh_hmac_payload(&X25519_BOB_PUBLIC, b"");
+        payload.truncate(20);
+        let r = round_trip(&key, 0xA5, &payload).await;
+        assert!(matches!(r, Err(KeylessResponseErrorCode::FormatError)));
+
+        let payload = ecdh_hmac_payload(&X25519_BOB_PUBLIC[..16], b"message");
+        let r = round_trip(&key, 0xA5, &payload).await;
+        assert!(matches!(r, Err(KeylessResponseErrorCode::FormatError)));
+    }
+}
@@ -117,6 +117,9 @@ pub(crate) enum KeylessResponseErrorCode {
     Expired = 10,
     #[error("the remote keyserver was not configured correctly")]
     RemoteConfiguration = 11,
+    /// not defined in the upstream protocol, old clients will see it as an unknown error
+    #[error("request rate limited")]
+    RateLimited = 0x80,
 }
 
 #[derive(Clone, Copy)]
@@ -154,6 +157,7 @@ impl KeylessErrorResponse {
             9 => KeylessResponseErrorCode::CertNotFound,
             10 => KeylessResponseErrorCode::Expired,
             11 => KeylessResponseErrorCode::RemoteConfiguration,
+            0x80 => KeylessResponseErrorCode::RateLimited,
             _ => unreachable!(),
         }
     }
@@ -187,6 +191,11 @@ impl KeylessErrorResponse {
     pub(crate) fn format_error(self) -> Self {
         self.set_error_code(KeylessResponseErrorCode::FormatError)
     }
+
+    #[inline]
+    pub(crate) fn rate_limited(self) -> Self {
+        self.set_error_code(KeylessResponseErrorCode::RateLimited)
+    }
 }
 
 pub(crate) enum KeylessResponse {
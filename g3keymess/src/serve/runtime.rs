@@ -16,6 +16,7 @@
 
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use log::{info, warn};
 use tokio::net::TcpStream;
@@ -62,6 +63,8 @@ impl KeyServerRuntime {
     ) {
         use broadcast::error::RecvError;
 
+        let mut rate_limit_retain_interval = tokio::time::interval(Duration::from_secs(60));
+
         loop {
             tokio::select! {
                 biased;
@@ -113,6 +116,9 @@ impl KeyServerRuntime {
                         break;
                     }
                 }
+                _ = rate_limit_retain_interval.tick() => {
+                    self.server.retain_recent_rate_limit();
+                }
             }
         }
         self.post_stop();
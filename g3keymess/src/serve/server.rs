@@ -14,10 +14,11 @@
  * limitations under the License.
  */
 
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 
 use arc_swap::ArcSwap;
+use governor::{DefaultKeyedRateLimiter, RateLimiter};
 use slog::Logger;
 use tokio::net::TcpStream;
 #[cfg(feature = "openssl-async-job")]
@@ -26,6 +27,7 @@ use tokio::sync::{broadcast, Semaphore};
 
 use g3_daemon::listen::ListenStats;
 use g3_daemon::server::ServerQuitPolicy;
+use g3_types::limit::RateLimitQuotaConfig;
 use g3_types::metrics::{MetricTagName, MetricTagValue, NodeName, StaticMetricsTags};
 
 use super::{
@@ -43,6 +45,8 @@ pub(crate) struct KeyServer {
     quit_policy: Arc<ServerQuitPolicy>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
     concurrency_limit: Option<Arc<Semaphore>>,
+    client_ip_rate_limit: Option<Arc<DefaultKeyedRateLimiter<IpAddr>>>,
+    key_rate_limit: Option<Arc<DefaultKeyedRateLimiter<Vec<u8>>>>,
     task_logger: Logger,
     request_logger: Logger,
     dynamic_metrics_tags: Arc<ArcSwap<StaticMetricsTags>>,
@@ -56,6 +60,8 @@ impl KeyServer {
         duration_recorder: KeyServerDurationRecorder,
         duration_stats: Arc<KeyServerDurationStats>,
         concurrency_limit: Option<Arc<Semaphore>>,
+        client_ip_rate_limit: Option<Arc<DefaultKeyedRateLimiter<IpAddr>>>,
+        key_rate_limit: Option<Arc<DefaultKeyedRateLimiter<Vec<u8>>>>,
         dynamic_metrics_tags: Arc<ArcSwap<StaticMetricsTags>>,
     ) -> Self {
         let reload_sender = broadcast::Sender::new(16);
@@ -87,6 +93,8 @@ impl KeyServer {
             quit_policy: Arc::new(ServerQuitPolicy::default()),
             reload_sender,
             concurrency_limit,
+            client_ip_rate_limit,
+            key_rate_limit,
             task_logger,
             request_logger,
             dynamic_metrics_tags,
@@ -103,6 +111,14 @@ impl KeyServer {
         } else {
             None
         };
+        let client_ip_rate_limit = config
+            .client_ip_request_rate_limit
+            .as_ref()
+            .map(|quota| Arc::new(RateLimiter::keyed(quota.get_inner())));
+        let key_rate_limit = config
+            .key_request_rate_limit
+            .as_ref()
+            .map(|quota| Arc::new(RateLimiter::keyed(quota.get_inner())));
         KeyServer::new(
             config,
             Arc::new(server_stats),
@@ -110,6 +126,8 @@ impl KeyServer {
             duration_recorder,
             duration_stats,
             concurrency_limit,
+            client_ip_rate_limit,
+            key_rate_limit,
             Arc::new(ArcSwap::new(Default::default())),
         )
     }
@@ -126,6 +144,16 @@ impl KeyServer {
             } else {
                 (self.duration_recorder.clone(), self.duration_stats.clone())
             };
+        let client_ip_rate_limit = reload_keyed_rate_limit(
+            &self.config.client_ip_request_rate_limit,
+            &self.client_ip_rate_limit,
+            &config.client_ip_request_rate_limit,
+        );
+        let key_rate_limit = reload_keyed_rate_limit(
+            &self.config.key_request_rate_limit,
+            &self.key_rate_limit,
+            &config.key_request_rate_limit,
+        );
         KeyServer::new(
             config,
             self.server_stats.clone(),
@@ -133,6 +161,8 @@ impl KeyServer {
             duration_recorder,
             duration_stats,
             concurrency_limit,
+            client_ip_rate_limit,
+            key_rate_limit,
             self.dynamic_metrics_tags.clone(),
         )
     }
@@ -198,6 +228,18 @@ impl KeyServer {
         self.duration_stats.clone()
     }
 
+    /// drop the rate limit states that have been fully replenished
+    pub(super) fn retain_recent_rate_limit(&self) {
+        if let Some(limiter) = &self.client_ip_rate_limit {
+            limiter.retain_recent();
+            limiter.shrink_to_fit();
+        }
+        if let Some(limiter) = &self.key_rate_limit {
+            limiter.retain_recent();
+            limiter.shrink_to_fit();
+        }
+    }
+
     pub(super) fn start_runtime(&self, server: &Arc<KeyServer>) -> anyhow::Result<()> {
         KeyServerRuntime::new(server)
             .into_running(&self.config.listen, &self.reload_sender)
@@ -229,6 +271,8 @@ impl KeyServer {
             request_logger: self.request_logger.clone(),
             reload_notifier: self.reload_sender.subscribe(),
             concurrency_limit: self.concurrency_limit.clone(),
+            client_ip_rate_limit: self.client_ip_rate_limit.clone(),
+            key_rate_limit: self.key_rate_limit.clone(),
         };
 
         let (r, w) = stream.into_split();
@@ -253,3 +297,21 @@ impl KeyServer {
         }
     }
 }
+
+fn reload_keyed_rate_limit<K>(
+    old_quota: &Option<RateLimitQuotaConfig>,
+    old_limiter: &Option<Arc<DefaultKeyedRateLimiter<K>>>,
+    new_quota: &Option<RateLimitQuotaConfig>,
+) -> Option<Arc<DefaultKeyedRateLimiter<K>>>
+where
+    K: Clone + Eq + std::hash::Hash,
+{
+    let quota = new_quota.as_ref()?;
+    if let Some(limiter) = old_limiter {
+        if old_quota.as_ref().is_some_and(|old| old.eq(quota)) {
+            // always use the old rate limiter when possible
+            return Some(Arc::clone(limiter));
+        }
+    }
+    Some(Arc::new(RateLimiter::keyed(quota.get_inner())))
+}
@@ -20,37 +20,56 @@ use std::sync::Arc;
 use arc_swap::ArcSwapOption;
 
 use g3_histogram::{HistogramMetricsConfig, HistogramRecorder, HistogramStats, RotatingHistogram};
+use g3_macros::StatsSnapshot;
 use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::stats::StatId;
 
 use crate::protocol::KeylessResponseErrorCode;
 
-#[derive(Default)]
+#[derive(Default, StatsSnapshot)]
+#[stats_snapshot(KeyServerRequestSnapshot)]
 pub(crate) struct KeyServerRequestStats {
+    #[stats(count = "server.request.total")]
     total: AtomicU64,
+    #[stats(gauge = "server.request.alive")]
     alive_count: AtomicI32,
 
+    #[stats(count = "server.request.passed")]
     passed: AtomicU64,
+    #[stats(
+        count = "server.request.failed",
+        tag(reason = "key_not_found"),
+        skip_zero
+    )]
     key_not_found: AtomicU64,
+    #[stats(
+        count = "server.request.failed",
+        tag(reason = "crypto_fail"),
+        skip_zero
+    )]
     crypto_fail: AtomicU64,
+    #[stats(
+        count = "server.request.failed",
+        tag(reason = "bad_op_code"),
+        skip_zero
+    )]
     bad_op_code: AtomicU64,
+    #[stats(
+        count = "server.request.failed",
+        tag(reason = "format_error"),
+        skip_zero
+    )]
     format_error: AtomicU64,
+    #[stats(
+        count = "server.request.failed",
+        tag(reason = "rate_limited"),
+        skip_zero
+    )]
+    rate_limited: AtomicU64,
+    #[stats(count = "server.request.failed", tag(reason = "other_fail"), skip_zero)]
     other_fail: AtomicU64,
 }
 
-#[derive(Default)]
-pub(crate) struct KeyServerRequestSnapshot {
-    pub(crate) total: u64,
-    pub(crate) alive_count: i32,
-
-    pub(crate) passed: u64,
-    pub(crate) key_not_found: u64,
-    pub(crate) crypto_fail: u64,
-    pub(crate) bad_op_code: u64,
-    pub(crate) format_error: u64,
-    pub(crate) other_fail: u64,
-}
-
 impl KeyServerRequestStats {
     pub(crate) fn add_total(&self) {
         self.total.fetch_add(1, Ordering::Relaxed);
@@ -84,6 +103,10 @@ impl KeyServerRequestStats {
         self.format_error.fetch_add(1, Ordering::Relaxed);
     }
 
+    pub(crate) fn add_rate_limited(&self) {
+        self.rate_limited.fetch_add(1, Ordering::Relaxed);
+    }
+
     fn add_other_fail(&self) {
         self.other_fail.fetch_add(1, Ordering::Relaxed);
     }
@@ -95,22 +118,10 @@ impl KeyServerRequestStats {
             KeylessResponseErrorCode::CryptographyFailure => self.add_crypto_fail(),
             KeylessResponseErrorCode::BadOpCode => self.add_bad_op_code(),
             KeylessResponseErrorCode::FormatError => self.add_format_error(),
+            KeylessResponseErrorCode::RateLimited => self.add_rate_limited(),
             _ => self.add_other_fail(),
         }
     }
-
-    pub(crate) fn snapshot(&self) -> KeyServerRequestSnapshot {
-        KeyServerRequestSnapshot {
-            total: self.total.load(Ordering::Relaxed),
-            alive_count: self.alive_count.load(Ordering::Relaxed),
-            passed: self.passed.load(Ordering::Relaxed),
-            key_not_found: self.key_not_found.load(Ordering::Relaxed),
-            crypto_fail: self.crypto_fail.load(Ordering::Relaxed),
-            bad_op_code: self.bad_op_code.load(Ordering::Relaxed),
-            format_error: self.format_error.load(Ordering::Relaxed),
-            other_fail: self.other_fail.load(Ordering::Relaxed),
-        }
-    }
 }
 
 pub(crate) struct KeyServerStats {
@@ -130,6 +141,8 @@ pub(crate) struct KeyServerStats {
     pub(crate) rsa_pss_sign: Arc<KeyServerRequestStats>,
     pub(crate) ecdsa_sign: Arc<KeyServerRequestStats>,
     pub(crate) ed25519_sign: Arc<KeyServerRequestStats>,
+    pub(crate) ecdh_derive: Arc<KeyServerRequestStats>,
+    pub(crate) ecdh_hmac: Arc<KeyServerRequestStats>,
     pub(crate) noop: Arc<KeyServerRequestStats>,
 }
 
@@ -143,6 +156,8 @@ pub(crate) struct KeyServerSnapshot {
     pub(crate) rsa_pss_sign: KeyServerRequestSnapshot,
     pub(crate) ecdsa_sign: KeyServerRequestSnapshot,
     pub(crate) ed25519_sign: KeyServerRequestSnapshot,
+    pub(crate) ecdh_derive: KeyServerRequestSnapshot,
+    pub(crate) ecdh_hmac: KeyServerRequestSnapshot,
     pub(crate) noop: KeyServerRequestSnapshot,
 }
 
@@ -161,6 +176,8 @@ impl KeyServerStats {
             rsa_pss_sign: Arc::new(KeyServerRequestStats::default()),
             ecdsa_sign: Arc::new(KeyServerRequestStats::default()),
             ed25519_sign: Arc::new(KeyServerRequestStats::default()),
+            ecdh_derive: Arc::new(KeyServerRequestStats::default()),
+            ecdh_hmac: Arc::new(KeyServerRequestStats::default()),
             noop: Arc::new(KeyServerRequestStats::default()),
         }
     }
@@ -231,6 +248,8 @@ pub(crate) struct KeyServerDurationStats {
     pub(crate) rsa_pss_sign: Arc<HistogramStats>,
     pub(crate) ecdsa_sign: Arc<HistogramStats>,
     pub(crate) ed25519_sign: Arc<HistogramStats>,
+    pub(crate) ecdh_derive: Arc<HistogramStats>,
+    pub(crate) ecdh_hmac: Arc<HistogramStats>,
 }
 
 impl KeyServerDurationStats {
@@ -274,6 +293,8 @@ pub(crate) struct KeyServerDurationRecorder {
     pub(crate) rsa_pss_sign: Arc<HistogramRecorder<u64>>,
     pub(crate) ecdsa_sign: Arc<HistogramRecorder<u64>>,
     pub(crate) ed25519_sign: Arc<HistogramRecorder<u64>>,
+    pub(crate) ecdh_derive: Arc<HistogramRecorder<u64>>,
+    pub(crate) ecdh_hmac: Arc<HistogramRecorder<u64>>,
     pub(crate) noop: Arc<HistogramRecorder<u64>>,
 }
 
@@ -288,6 +309,8 @@ impl KeyServerDurationRecorder {
         let (rsa_pss_sign_r, rsa_pss_sign_s) = config.build_spawned(None);
         let (ecdsa_sign_r, ecdsa_sign_s) = config.build_spawned(None);
         let (ed25519_sign_r, ed25519_sign_s) = config.build_spawned(None);
+        let (ecdh_derive_r, ecdh_derive_s) = config.build_spawned(None);
+        let (ecdh_hmac_r, ecdh_hmac_s) = config.build_spawned(None);
         let (_, noop_r) = RotatingHistogram::new(config.rotate_interval());
 
         let r = KeyServerDurationRecorder {
@@ -297,6 +320,8 @@ impl KeyServerDurationRecorder {
             rsa_pss_sign: Arc::new(rsa_pss_sign_r),
             ecdsa_sign: Arc::new(ecdsa_sign_r),
             ed25519_sign: Arc::new(ed25519_sign_r),
+            ecdh_derive: Arc::new(ecdh_derive_r),
+            ecdh_hmac: Arc::new(ecdh_hmac_r),
             noop: Arc::new(noop_r),
         };
         let s = KeyServerDurationStats {
@@ -310,6 +335,8 @@ impl KeyServerDurationRecorder {
             rsa_pss_sign: rsa_pss_sign_s,
             ecdsa_sign: ecdsa_sign_s,
             ed25519_sign: ed25519_sign_s,
+            ecdh_derive: ecdh_derive_s,
+            ecdh_hmac: ecdh_hmac_s,
         };
         (r, Arc::new(s))
     }
@@ -14,10 +14,15 @@
  * limitations under the License.
  */
 
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use governor::{
+    clock::DefaultClock, state::InMemoryState, state::NotKeyed, DefaultKeyedRateLimiter,
+    RateLimiter,
+};
 use openssl::pkey::{PKey, Private};
 use slog::{slog_info, Logger};
 use tokio::io::AsyncRead;
@@ -102,6 +107,14 @@ impl WrappedKeylessRequest {
                 server_stats.ed25519_sign.clone(),
                 duration_recorder.ed25519_sign.clone(),
             ),
+            KeylessAction::EcdhDerive => (
+                server_stats.ecdh_derive.clone(),
+                duration_recorder.ecdh_derive.clone(),
+            ),
+            KeylessAction::EcdhHmac(_) => (
+                server_stats.ecdh_hmac.clone(),
+                duration_recorder.ecdh_hmac.clone(),
+            ),
             KeylessAction::NotSet => (server_stats.noop.clone(), duration_recorder.noop.clone()),
         };
         stats.add_total();
@@ -133,6 +146,11 @@ impl WrappedKeylessRequest {
         }
     }
 
+    /// the time elapsed since the request was read
+    pub(crate) fn queue_latency(&self) -> Duration {
+        self.create_time.elapsed()
+    }
+
     pub(crate) fn build_response(&self, rsp: KeylessResponse) -> WrappedKeylessResponse {
         WrappedKeylessResponse::new(rsp, self.create_time, self.duration_recorder.clone())
     }
@@ -154,6 +172,8 @@ pub(crate) struct KeylessTaskContext {
     pub(crate) request_logger: Logger,
     pub(crate) reload_notifier: broadcast::Receiver<ServerReloadCommand>,
     pub(crate) concurrency_limit: Option<Arc<Semaphore>>,
+    pub(crate) client_ip_rate_limit: Option<Arc<DefaultKeyedRateLimiter<IpAddr>>>,
+    pub(crate) key_rate_limit: Option<Arc<DefaultKeyedRateLimiter<Vec<u8>>>>,
 }
 
 pub(crate) struct KeylessTask {
@@ -161,6 +181,7 @@ pub(crate) struct KeylessTask {
     ctx: KeylessTaskContext,
     started: DateTime<Utc>,
     buf: Vec<u8>,
+    request_rate_limit: Option<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>,
     #[cfg(feature = "openssl-async-job")]
     allow_openssl_async_job: bool,
     allow_dispatch: bool,
@@ -178,12 +199,18 @@ impl KeylessTask {
         ctx.server_stats.inc_alive_task();
 
         let started = Utc::now();
+        let request_rate_limit = ctx
+            .server_config
+            .request_rate_limit
+            .as_ref()
+            .map(|quota| RateLimiter::direct(quota.get_inner()));
 
         KeylessTask {
             id: g3_daemon::server::task::generate_uuid(&started),
             ctx,
             started,
             buf: Vec::with_capacity(crate::protocol::MESSAGE_PADDED_LENGTH + 2),
+            request_rate_limit,
             #[cfg(feature = "openssl-async-job")]
             allow_openssl_async_job: false,
             allow_dispatch: false,
@@ -223,6 +250,37 @@ impl KeylessTask {
         }
     }
 
+    /// check the per connection and per client ip request rate limit
+    fn check_request_rate_limit(
+        &self,
+        req: &WrappedKeylessRequest,
+    ) -> Result<(), KeylessErrorResponse> {
+        if let Some(limiter) = &self.request_rate_limit {
+            if limiter.check().is_err() {
+                return Err(KeylessErrorResponse::new(req.inner.id).rate_limited());
+            }
+        }
+        if let Some(limiter) = &self.ctx.client_ip_rate_limit {
+            if limiter.check_key(&self.ctx.peer_addr.ip()).is_err() {
+                return Err(KeylessErrorResponse::new(req.inner.id).rate_limited());
+            }
+        }
+        Ok(())
+    }
+
+    /// check the per key request rate limit, should be called after the key is found
+    fn check_key_rate_limit(
+        &self,
+        req: &WrappedKeylessRequest,
+    ) -> Result<(), KeylessErrorResponse> {
+        if let Some(limiter) = &self.ctx.key_rate_limit {
+            if limiter.check_key(&req.inner.ski).is_err() {
+                return Err(KeylessErrorResponse::new(req.inner.id).rate_limited());
+            }
+        }
+        Ok(())
+    }
+
     fn log_task_err(&self, e: ServerTaskError) {
         if e.ignore_log() {
             return;
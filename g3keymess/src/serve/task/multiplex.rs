@@ -168,6 +168,14 @@ impl KeylessTask {
             return Ok(());
         }
 
+        if let Err(rsp) = self.check_request_rate_limit(&req) {
+            req.stats.add_by_error_code(rsp.error_code());
+            let _ = msg_sender
+                .send(req.build_response(KeylessResponse::Error(rsp)))
+                .await;
+            return Ok(());
+        }
+
         let key = match req.inner.find_key() {
             Ok(key) => key,
             Err(rsp) => {
@@ -179,6 +187,14 @@ impl KeylessTask {
             }
         };
 
+        if let Err(rsp) = self.check_key_rate_limit(&req) {
+            req.stats.add_by_error_code(rsp.error_code());
+            let _ = msg_sender
+                .send(req.build_response(KeylessResponse::Error(rsp)))
+                .await;
+            return Ok(());
+        }
+
         if self.allow_dispatch {
             self.async_process_by_dispatch(req, key, msg_sender).await;
             return Ok(());
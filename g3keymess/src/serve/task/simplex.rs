@@ -105,6 +105,13 @@ impl KeylessTask {
                 .await;
         }
 
+        if let Err(rsp) = self.check_request_rate_limit(&req) {
+            req.stats.add_by_error_code(rsp.error_code());
+            return self
+                .send_response(writer, KeylessResponse::Error(rsp))
+                .await;
+        }
+
         let key = match req.inner.find_key() {
             Ok(key) => key,
             Err(rsp) => {
@@ -115,6 +122,13 @@ impl KeylessTask {
             }
         };
 
+        if let Err(rsp) = self.check_key_rate_limit(&req) {
+            req.stats.add_by_error_code(rsp.error_code());
+            return self
+                .send_response(writer, KeylessResponse::Error(rsp))
+                .await;
+        }
+
         let server_sem = if let Some(sem) = self.ctx.concurrency_limit.clone() {
             sem.acquire_owned().await.ok()
         } else {
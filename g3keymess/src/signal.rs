@@ -49,7 +49,6 @@ impl AsyncSignalAction for QuitAction {
     }
 }
 
-#[allow(unused)]
 #[derive(Clone, Copy)]
 struct OfflineAction {}
 
@@ -76,5 +75,5 @@ pub fn register() -> anyhow::Result<()> {
 
 #[cfg(windows)]
 pub fn register() -> anyhow::Result<()> {
-    g3_daemon::signal::register(QuitAction {})
+    g3_daemon::signal::register(QuitAction {}, OfflineAction {})
 }
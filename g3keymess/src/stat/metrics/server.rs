@@ -19,7 +19,7 @@ use std::sync::{Arc, LazyLock, Mutex};
 use ahash::AHashMap;
 
 use g3_daemon::listen::{ListenSnapshot, ListenStats};
-use g3_daemon::metrics::{ServerMetricExt, TAG_KEY_QUANTILE, TAG_KEY_REQUEST};
+use g3_daemon::metrics::{ServerMetricExt, TAG_KEY_BUCKET_LE, TAG_KEY_QUANTILE, TAG_KEY_REQUEST};
 use g3_histogram::HistogramStats;
 use g3_statsd_client::{StatsdClient, StatsdTagGroup};
 use g3_types::stats::StatId;
@@ -28,16 +28,11 @@ use crate::serve::{
     KeyServerDurationStats, KeyServerRequestSnapshot, KeyServerSnapshot, KeyServerStats,
 };
 
-const TAG_KEY_REASON: &str = "reason";
-
 const METRIC_NAME_SERVER_TASK_TOTAL: &str = "server.task.total";
 const METRIC_NAME_SERVER_TASK_ALIVE: &str = "server.task.alive";
 
-const METRIC_NAME_SERVER_REQUEST_TOTAL: &str = "server.request.total";
-const METRIC_NAME_SERVER_REQUEST_ALIVE: &str = "server.request.alive";
-const METRIC_NAME_SERVER_REQUEST_PASSED: &str = "server.request.passed";
-const METRIC_NAME_SERVER_REQUEST_FAILED: &str = "server.request.failed";
 const METRIC_NAME_SERVER_REQUEST_DURATION: &str = "server.request.duration";
+const METRIC_NAME_SERVER_REQUEST_DURATION_BUCKET: &str = "server.request.duration.bucket";
 
 const REQUEST_TYPE_NO_OP: &str = "no_op";
 const REQUEST_TYPE_PING_PONG: &str = "ping_pong";
@@ -46,12 +41,8 @@ const REQUEST_TYPE_RSA_SIGN: &str = "rsa_sign";
 const REQUEST_TYPE_RSA_PSS_SIGN: &str = "rsa_pss_sign";
 const REQUEST_TYPE_ECDSA_SIGN: &str = "ecdsa_sign";
 const REQUEST_TYPE_ED25519_SIGN: &str = "ed25519_sign";
-
-const FAIL_REASON_KEY_NOT_FOUND: &str = "key_not_found";
-const FAIL_REASON_CRYPTO_FAIL: &str = "crypto_fail";
-const FAIL_REASON_BAD_OP_CODE: &str = "bad_op_code";
-const FAIL_REASON_FORMAT_ERROR: &str = "format_error";
-const FAIL_REASON_OTHER_FAIL: &str = "other_fail";
+const REQUEST_TYPE_ECDH_DERIVE: &str = "ecdh_derive";
+const REQUEST_TYPE_ECDH_HMAC: &str = "ecdh_hmac";
 
 type ServerStatsValue = (Arc<KeyServerStats>, KeyServerSnapshot);
 type ListenStatsValue = (Arc<ListenStats>, ListenSnapshot);
@@ -163,6 +154,8 @@ fn emit_server_stats(
     emit_request_stats_u64!(rsa_pss_sign, REQUEST_TYPE_RSA_PSS_SIGN);
     emit_request_stats_u64!(ecdsa_sign, REQUEST_TYPE_ECDSA_SIGN);
     emit_request_stats_u64!(ed25519_sign, REQUEST_TYPE_ED25519_SIGN);
+    emit_request_stats_u64!(ecdh_derive, REQUEST_TYPE_ECDH_DERIVE);
+    emit_request_stats_u64!(ecdh_hmac, REQUEST_TYPE_ECDH_HMAC);
 }
 
 fn emit_server_request_stats(
@@ -172,53 +165,13 @@ fn emit_server_request_stats(
     snap: &mut KeyServerRequestSnapshot,
     common_tags: &StatsdTagGroup,
 ) {
-    let new_value = stats.total;
-    if new_value == 0 && snap.total == 0 {
+    if stats.total == 0 && snap.total == 0 {
         return;
     }
-    let diff_value = new_value.wrapping_sub(snap.total);
-    client
-        .count_with_tags(METRIC_NAME_SERVER_REQUEST_TOTAL, diff_value, common_tags)
-        .with_tag(TAG_KEY_REQUEST, request)
-        .send();
-    snap.total = new_value;
-
-    client
-        .gauge_with_tags(
-            METRIC_NAME_SERVER_REQUEST_ALIVE,
-            stats.alive_count,
-            common_tags,
-        )
-        .with_tag(TAG_KEY_REQUEST, request)
-        .send();
-
-    let new_value = stats.passed;
-    let diff_value = new_value.wrapping_sub(snap.passed);
-    client
-        .count_with_tags(METRIC_NAME_SERVER_REQUEST_PASSED, diff_value, common_tags)
-        .with_tag(TAG_KEY_REQUEST, request)
-        .send();
-    snap.passed = new_value;
 
-    macro_rules! emit_failed_stats_u64 {
-        ($id:ident, $reason:expr) => {
-            let new_value = stats.$id;
-            if new_value != 0 || snap.$id != 0 {
-                let diff_value = new_value.wrapping_sub(snap.$id);
-                client
-                    .count_with_tags(METRIC_NAME_SERVER_REQUEST_FAILED, diff_value, common_tags)
-                    .with_tag(TAG_KEY_REQUEST, request)
-                    .with_tag(TAG_KEY_REASON, $reason)
-                    .send();
-                snap.$id = new_value;
-            }
-        };
-    }
-    emit_failed_stats_u64!(key_not_found, FAIL_REASON_KEY_NOT_FOUND);
-    emit_failed_stats_u64!(crypto_fail, FAIL_REASON_CRYPTO_FAIL);
-    emit_failed_stats_u64!(bad_op_code, FAIL_REASON_BAD_OP_CODE);
-    emit_failed_stats_u64!(format_error, FAIL_REASON_FORMAT_ERROR);
-    emit_failed_stats_u64!(other_fail, FAIL_REASON_OTHER_FAIL);
+    let mut tags = common_tags.clone();
+    tags.add_tag(TAG_KEY_REQUEST, request);
+    stats.emit_diff(client, &tags, snap);
 }
 
 fn emit_server_duration_stats(client: &mut StatsdClient, stats: &Arc<KeyServerDurationStats>) {
@@ -239,6 +192,8 @@ fn emit_server_duration_stats(client: &mut StatsdClient, stats: &Arc<KeyServerDu
     emit_request_stats_u64!(rsa_pss_sign, REQUEST_TYPE_RSA_PSS_SIGN);
     emit_request_stats_u64!(ecdsa_sign, REQUEST_TYPE_ECDSA_SIGN);
     emit_request_stats_u64!(ed25519_sign, REQUEST_TYPE_ED25519_SIGN);
+    emit_request_stats_u64!(ecdh_derive, REQUEST_TYPE_ECDH_DERIVE);
+    emit_request_stats_u64!(ecdh_hmac, REQUEST_TYPE_ECDH_HMAC);
 }
 
 fn emit_server_request_duration_stats(
@@ -255,5 +210,16 @@ fn emit_server_request_duration_stats(
                 .with_tag(TAG_KEY_QUANTILE, qs)
                 .send();
         }
-    })
+    });
+    stats.foreach_bucket(|le, count| {
+        client
+            .gauge_with_tags(
+                METRIC_NAME_SERVER_REQUEST_DURATION_BUCKET,
+                count,
+                common_tags,
+            )
+            .with_tag(TAG_KEY_REQUEST, request)
+            .with_tag(TAG_KEY_BUCKET_LE, le)
+            .send();
+    });
 }
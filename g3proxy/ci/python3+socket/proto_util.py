@@ -0,0 +1,120 @@
+#!/usr/bin/env python3
+
+import socket
+from urllib.parse import urlparse
+
+
+def parse_addr(s: str, default_port: int):
+    url = urlparse(s if '://' in s else f"tcp://{s}")
+    return url.hostname, url.port if url.port is not None else default_port
+
+
+def connect(addr, timeout=5.0):
+    sock = socket.create_connection(addr, timeout=timeout)
+    sock.setsockopt(socket.IPPROTO_TCP, socket.TCP_NODELAY, 1)
+    return sock
+
+
+def send_in_pieces(sock, data: bytes, size=1):
+    for i in range(0, len(data), size):
+        sock.sendall(data[i:i + size])
+
+
+def recv_exact(sock, n: int) -> bytes:
+    buf = b''
+    while len(buf) < n:
+        d = sock.recv(n - len(buf))
+        if not d:
+            raise ConnectionError(f"connection closed after {len(buf)} of {n} bytes")
+        buf += d
+    return buf
+
+
+def recv_until_closed(sock) -> bytes:
+    """Read until closed by peer, return the data read, which may be empty"""
+    buf = b''
+    try:
+        while True:
+            d = sock.recv(4096)
+            if not d:
+                break
+            buf += d
+    except (ConnectionResetError, socket.timeout):
+        pass
+    return buf
+
+
+class HttpResponse:
+    def __init__(self, version: str, status: int, headers: dict, body: bytes):
+        self.version = version
+        self.status = status
+        self.headers = headers
+        self.body = body
+
+
+class HttpReader:
+    def __init__(self, sock):
+        self.sock = sock
+        self.buf = b''
+
+    def _fill(self):
+        d = self.sock.recv(4096)
+        if not d:
+            raise ConnectionError("connection closed")
+        self.buf += d
+
+    def read_line(self) -> bytes:
+        while True:
+            p = self.buf.find(b'\r\n')
+            if p >= 0:
+                line = self.buf[:p]
+                self.buf = self.buf[p + 2:]
+                return line
+            self._fill()
+
+    def read_exact(self, n: int) -> bytes:
+        while len(self.buf) < n:
+            self._fill()
+        data = self.buf[:n]
+        self.buf = self.buf[n:]
+        return data
+
+    def read_head(self):
+        status_line = self.read_line().decode('latin-1')
+        version, status = status_line.split(' ', 2)[:2]
+        headers = {}
+        while True:
+            line = self.read_line()
+            if not line:
+                break
+            name, value = line.decode('latin-1').split(':', 1)
+            headers[name.strip().lower()] = value.strip()
+        return version, int(status), headers
+
+    def read_response(self, method='GET', tunnel=False) -> HttpResponse:
+        version, status, headers = self.read_head()
+        body = b''
+        if method == 'HEAD' or status in (204, 304) or 100 <= status < 200 or (tunnel and 200 <= status < 300):
+            pass
+        elif headers.get('transfer-encoding', '').lower() == 'chunked':
+            while True:
+                size = int(self.read_line().split(b';')[0], 16)
+                if size == 0:
+                    while self.read_line():
+                        pass
+                    break
+                body += self.read_exact(size)
+                self.read_line()
+        elif 'content-length' in headers:
+            body = self.read_exact(int(headers['content-length']))
+        else:
+            body = self.buf + recv_until_closed(self.sock)
+            self.buf = b''
+        return HttpResponse(version, status, headers, body)
+
+    def try_read_response(self, method='GET'):
+        """Read a response, None will be returned if the connection is closed before the response head"""
+        try:
+            return self.read_response(method)
+        except (ConnectionError, socket.timeout):
+            return None
@@ -0,0 +1,125 @@
+#!/usr/bin/env python3
+
+import argparse
+import json
+import sys
+import unittest
+
+from proto_util import connect, parse_addr, send_in_pieces, HttpReader
+
+proxy_addr = ('127.0.0.1', 8080)
+target_url = 'http://httpbin.local/post'
+
+
+def request_head(extra_headers: str = 'Transfer-Encoding: chunked\r\n') -> bytes:
+    host = target_url.split('/')[2]
+    return (f"POST {target_url} HTTP/1.1\r\n"
+            f"Host: {host}\r\n"
+            f"Connection: close\r\n"
+            f"Content-Type: application/octet-stream\r\n"
+            f"{extra_headers}\r\n").encode('ascii')
+
+
+class TestChunkedRequest(unittest.TestCase):
+    def setUp(self):
+        self.sock = connect(proxy_addr)
+        self.reader = HttpReader(self.sock)
+
+    def tearDown(self):
+        self.sock.close()
+
+    def assert_echoed(self, expected: bytes):
+        rsp = self.reader.read_response()
+        self.assertEqual(rsp.status, 200)
+        try:
+            data = json.loads(rsp.body)['data']
+        except (ValueError, KeyError):
+            # the target is not an echo server, status check only
+            return
+        self.assertEqual(data.encode('latin-1'), expected)
+
+    def assert_not_ok(self):
+        rsp = self.reader.try_read_response()
+        if rsp is not None:
+            self.assertNotEqual(rsp.status, 200)
+
+    def test_basic(self):
+        self.sock.sendall(request_head() + b"5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n")
+        self.assert_echoed(b"hello world")
+
+    def test_chunk_extension(self):
+        self.sock.sendall(request_head() + b"5;name=value\r\nhello\r\n6;a;b=\"c d\"\r\n world\r\n0;end\r\n\r\n")
+        self.assert_echoed(b"hello world")
+
+    def test_uppercase_hex_size(self):
+        body = b'x' * 0xAB
+        self.sock.sendall(request_head() + b"AB\r\n" + body + b"\r\n0\r\n\r\n")
+        self.assert_echoed(body)
+
+    def test_leading_zero_size(self):
+        self.sock.sendall(request_head() + b"0005\r\nhello\r\n000\r\n\r\n")
+        self.assert_echoed(b"hello")
+
+    def test_trailer(self):
+        self.sock.sendall(request_head('Transfer-Encoding: chunked\r\nTrailer: X-Checksum\r\n') +
+                          b"5\r\nhello\r\n0\r\nX-Checksum: 1234\r\n\r\n")
+        self.assert_echoed(b"hello")
+
+    def test_byte_by_byte(self):
+        send_in_pieces(self.sock, request_head() + b"5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n", size=1)
+        self.assert_echoed(b"hello world")
+
+    def test_many_small_chunks(self):
+        payload = bytes(range(0x61, 0x61 + 26)) * 40
+        body = b''.join(b"1\r\n" + payload[i:i + 1] + b"\r\n" for i in range(len(payload)))
+        self.sock.sendall(request_head() + body + b"0\r\n\r\n")
+        self.assert_echoed(payload)
+
+    def test_large_chunk(self):
+        payload = b'z' * 65536
+        self.sock.sendall(request_head() + b"10000\r\n" + payload + b"\r\n0\r\n\r\n")
+        self.assert_echoed(payload)
+
+    def test_invalid_size(self):
+        self.sock.sendall(request_head() + b"xyz\r\nhello\r\n0\r\n\r\n")
+        self.assert_not_ok()
+
+    def test_overflow_size(self):
+        self.sock.sendall(request_head() + b"fffffffffffffffff1\r\nhello\r\n0\r\n\r\n")
+        self.assert_not_ok()
+
+    def test_missing_chunk_crlf(self):
+        self.sock.sendall(request_head() + b"5\r\nhelloXX6\r\n world\r\n0\r\n\r\n")
+        self.assert_not_ok()
+
+    def test_content_length_and_chunked(self):
+        # RFC 9112 6.3: chunked overrides Content-Length, or the request should be rejected
+        self.sock.sendall(request_head('Transfer-Encoding: chunked\r\nContent-Length: 3\r\n') +
+                          b"5\r\nhello\r\n0\r\n\r\n")
+        rsp = self.reader.try_read_response()
+        if rsp is not None and rsp.status == 200:
+            try:
+                data = json.loads(rsp.body)['data']
+            except (ValueError, KeyError):
+                return
+            self.assertEqual(data, 'hello')
+
+    def test_unknown_transfer_coding(self):
+        self.sock.sendall(request_head('Transfer-Encoding: gzip\r\n') + b"5\r\nhello\r\n0\r\n\r\n")
+        self.assert_not_ok()
+
+
+if __name__ == '__main__':
+    parser = argparse.ArgumentParser(description='HTTP chunked encoding conformance tests')
+    parser.add_argument('--proxy', '-x', nargs='?', help='Proxy address', default='127.0.0.1:8080')
+    parser.add_argument('--target', '-T', nargs='?', help='Target POST url, should echo the request body',
+                        default='http://httpbin.local/post')
+
+    (args, left_args) = parser.parse_known_args()
+
+    proxy_addr = parse_addr(args.proxy, 8080)
+    target_url = args.target
+
+    left_args.insert(0, sys.argv[0])
+
+    unittest.main(argv=left_args)
@@ -0,0 +1,111 @@
+#!/usr/bin/env python3
+
+import argparse
+import sys
+import unittest
+
+from proto_util import connect, parse_addr, send_in_pieces, HttpReader
+
+proxy_addr = ('127.0.0.1', 8080)
+target_host = 'httpbin.local'
+target_port = 80
+
+
+class TestHttpConnect(unittest.TestCase):
+    def setUp(self):
+        self.sock = connect(proxy_addr)
+        self.reader = HttpReader(self.sock)
+
+    def tearDown(self):
+        self.sock.close()
+
+    def get_through_tunnel(self):
+        self.sock.sendall(f"GET /get HTTP/1.1\r\nHost: {target_host}\r\nConnection: close\r\n\r\n".encode('ascii'))
+        return self.reader.read_response()
+
+    def assert_rejected(self, request: bytes):
+        self.sock.sendall(request)
+        rsp = self.reader.try_read_response(method='CONNECT')
+        if rsp is not None:
+            self.assertGreaterEqual(rsp.status, 400)
+            self.assertLess(rsp.status, 500)
+
+    def test_connect(self):
+        self.sock.sendall(f"CONNECT {target_host}:{target_port} HTTP/1.1\r\n"
+                          f"Host: {target_host}:{target_port}\r\n\r\n".encode('ascii'))
+        rsp = self.reader.read_response(method='CONNECT', tunnel=True)
+        self.assertEqual(rsp.status, 200)
+        self.assertNotIn('content-length', rsp.headers)
+        self.assertNotIn('transfer-encoding', rsp.headers)
+        self.assertEqual(self.get_through_tunnel().status, 200)
+
+    def test_connect_http10(self):
+        self.sock.sendall(f"CONNECT {target_host}:{target_port} HTTP/1.0\r\n\r\n".encode('ascii'))
+        rsp = self.reader.read_response(method='CONNECT', tunnel=True)
+        self.assertEqual(rsp.status, 200)
+        self.assertEqual(self.get_through_tunnel().status, 200)
+
+    def test_connect_without_host_header(self):
+        self.sock.sendall(f"CONNECT {target_host}:{target_port} HTTP/1.1\r\n\r\n".encode('ascii'))
+        rsp = self.reader.read_response(method='CONNECT', tunnel=True)
+        self.assertEqual(rsp.status, 200)
+        self.assertEqual(self.get_through_tunnel().status, 200)
+
+    def test_fragmented_request(self):
+        send_in_pieces(self.sock, f"CONNECT {target_host}:{target_port} HTTP/1.1\r\n"
+                                  f"Host: {target_host}:{target_port}\r\n\r\n".encode('ascii'), size=3)
+        rsp = self.reader.read_response(method='CONNECT', tunnel=True)
+        self.assertEqual(rsp.status, 200)
+        self.assertEqual(self.get_through_tunnel().status, 200)
+
+    def test_pipelined_tunnel_data(self):
+        # client data sent before the 200 response should be forwarded to the target
+        self.sock.sendall(f"CONNECT {target_host}:{target_port} HTTP/1.1\r\n"
+                          f"Host: {target_host}:{target_port}\r\n\r\n"
+                          f"GET /get HTTP/1.1\r\nHost: {target_host}\r\nConnection: close\r\n\r\n".encode('ascii'))
+        rsp = self.reader.read_response(method='CONNECT', tunnel=True)
+        self.assertEqual(rsp.status, 200)
+        rsp = self.reader.read_response()
+        self.assertEqual(rsp.status, 200)
+
+    def test_header_folding_rejected(self):
+        self.assert_rejected(f"CONNECT {target_host}:{target_port} HTTP/1.1\r\n"
+                             f"Host: {target_host}:{target_port}\r\n"
+                             f"X-Folded: a\r\n b\r\n\r\n".encode('ascii'))
+
+    def test_missing_port(self):
+        self.assert_rejected(f"CONNECT {target_host} HTTP/1.1\r\nHost: {target_host}\r\n\r\n".encode('ascii'))
+
+    def test_invalid_port(self):
+        self.assert_rejected(f"CONNECT {target_host}:65536 HTTP/1.1\r\n\r\n".encode('ascii'))
+
+    def test_origin_form_target(self):
+        self.assert_rejected(b"CONNECT /get HTTP/1.1\r\nHost: httpbin.local:80\r\n\r\n")
+
+    def test_absolute_form_target(self):
+        self.assert_rejected(f"CONNECT http://{target_host}:{target_port}/ HTTP/1.1\r\n\r\n".encode('ascii'))
+
+    def test_unsupported_version(self):
+        self.assert_rejected(f"CONNECT {target_host}:{target_port} HTTP/2.0\r\n\r\n".encode('ascii'))
+
+    def test_bare_lf_line_ending(self):
+        # bare LF is allowed by RFC 9112 for robustness, but it should never be a success with a broken tunnel
+        self.sock.sendall(f"CONNECT {target_host}:{target_port} HTTP/1.1\n\n".encode('ascii'))
+        rsp = self.reader.try_read_response(method='CONNECT')
+        if rsp is not None and rsp.status == 200:
+            self.assertEqual(self.get_through_tunnel().status, 200)
+
+
+if __name__ == '__main__':
+    parser = argparse.ArgumentParser(description='HTTP CONNECT conformance tests')
+    parser.add_argument('--proxy', '-x', nargs='?', help='Proxy address', default='127.0.0.1:8080')
+    parser.add_argument('--target', '-T', nargs='?', help='Target HTTP server domain:port', default='httpbin.local:80')
+
+    (args, left_args) = parser.parse_known_args()
+
+    proxy_addr = parse_addr(args.proxy, 8080)
+    (target_host, target_port) = parse_addr(args.target, 80)
+
+    left_args.insert(0, sys.argv[0])
+
+    unittest.main(argv=left_args)
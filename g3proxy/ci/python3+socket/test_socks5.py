@@ -0,0 +1,192 @@
+#!/usr/bin/env python3
+
+import argparse
+import socket
+import struct
+import sys
+import unittest
+
+from proto_util import connect, parse_addr, recv_exact, recv_until_closed, send_in_pieces, HttpReader
+
+proxy_addr = ('127.0.0.1', 1080)
+target_host = 'httpbin.local'
+target_port = 80
+target_ip = '127.0.0.1'
+
+REPLY_SUCCEEDED = 0x00
+REPLY_COMMAND_NOT_SUPPORTED = 0x07
+REPLY_ADDRESS_TYPE_NOT_SUPPORTED = 0x08
+
+
+def domain_request(cmd: int, host: str, port: int) -> bytes:
+    h = host.encode('ascii')
+    return bytes([0x05, cmd, 0x00, 0x03, len(h)]) + h + struct.pack('!H', port)
+
+
+def ipv4_request(cmd: int, ip: str, port: int) -> bytes:
+    return bytes([0x05, cmd, 0x00, 0x01]) + socket.inet_aton(ip) + struct.pack('!H', port)
+
+
+def recv_reply(sock):
+    """Receive a reply, return (code, bind addr bytes), or None if closed by the proxy"""
+    try:
+        head = recv_exact(sock, 4)
+    except (ConnectionError, socket.timeout):
+        return None
+    if head[0] != 0x05:
+        raise AssertionError(f"invalid reply version {head[0]}")
+    atyp = head[3]
+    if atyp == 0x01:
+        addr = recv_exact(sock, 4 + 2)
+    elif atyp == 0x04:
+        addr = recv_exact(sock, 16 + 2)
+    elif atyp == 0x03:
+        n = recv_exact(sock, 1)[0]
+        addr = recv_exact(sock, n + 2)
+    else:
+        raise AssertionError(f"invalid reply address type {atyp}")
+    return head[1], addr
+
+
+class TestSocks5(unittest.TestCase):
+    def setUp(self):
+        self.sock = connect(proxy_addr)
+
+    def tearDown(self):
+        self.sock.close()
+
+    def negotiate(self):
+        self.sock.sendall(b'\x05\x01\x00')
+        self.assertEqual(recv_exact(self.sock, 2), b'\x05\x00')
+
+    def assert_tunnel_works(self):
+        self.sock.sendall(f"GET /get HTTP/1.1\r\nHost: {target_host}\r\nConnection: close\r\n\r\n".encode('ascii'))
+        rsp = HttpReader(self.sock).read_response()
+        self.assertEqual(rsp.status, 200)
+
+    def assert_not_succeeded(self, expected_code=None):
+        # RFC 1928 requires a reply with the failure code, but closing the connection is also tolerated
+        r = recv_reply(self.sock)
+        if r is not None:
+            if expected_code is None:
+                self.assertNotEqual(r[0], REPLY_SUCCEEDED)
+            else:
+                self.assertEqual(r[0], expected_code)
+            self.assertEqual(recv_until_closed(self.sock), b'')
+
+    def test_connect_domain(self):
+        self.negotiate()
+        self.sock.sendall(domain_request(0x01, target_host, target_port))
+        code, _ = recv_reply(self.sock)
+        self.assertEqual(code, REPLY_SUCCEEDED)
+        self.assert_tunnel_works()
+
+    def test_connect_ipv4(self):
+        self.negotiate()
+        self.sock.sendall(ipv4_request(0x01, target_ip, target_port))
+        code, _ = recv_reply(self.sock)
+        self.assertEqual(code, REPLY_SUCCEEDED)
+        self.assert_tunnel_works()
+
+    def test_fragmented_handshake(self):
+        send_in_pieces(self.sock, b'\x05\x01\x00')
+        self.assertEqual(recv_exact(self.sock, 2), b'\x05\x00')
+        send_in_pieces(self.sock, domain_request(0x01, target_host, target_port))
+        code, _ = recv_reply(self.sock)
+        self.assertEqual(code, REPLY_SUCCEEDED)
+        self.assert_tunnel_works()
+
+    def test_pipelined_handshake(self):
+        self.sock.sendall(b'\x05\x01\x00' + domain_request(0x01, target_host, target_port))
+        self.assertEqual(recv_exact(self.sock, 2), b'\x05\x00')
+        code, _ = recv_reply(self.sock)
+        self.assertEqual(code, REPLY_SUCCEEDED)
+        self.assert_tunnel_works()
+
+    def test_duplicated_methods(self):
+        self.sock.sendall(b'\x05\x03\x00\x00\x00')
+        self.assertEqual(recv_exact(self.sock, 2), b'\x05\x00')
+
+    def test_no_acceptable_method(self):
+        # only GSSAPI is offered
+        self.sock.sendall(b'\x05\x01\x01')
+        self.assertEqual(recv_exact(self.sock, 2), b'\x05\xff')
+        self.assertEqual(recv_until_closed(self.sock), b'')
+
+    def test_zero_methods(self):
+        self.sock.sendall(b'\x05\x00')
+        data = recv_until_closed(self.sock)
+        self.assertIn(data, (b'', b'\x05\xff'))
+
+    def test_invalid_version(self):
+        self.sock.sendall(b'\x06\x01\x00')
+        self.assertEqual(recv_until_closed(self.sock), b'')
+
+    def test_invalid_request_version(self):
+        self.negotiate()
+        self.sock.sendall(b'\x04' + domain_request(0x01, target_host, target_port)[1:])
+        self.assert_not_succeeded()
+
+    def test_bind_not_supported(self):
+        self.negotiate()
+        self.sock.sendall(ipv4_request(0x02, target_ip, target_port))
+        code, _ = recv_reply(self.sock)
+        self.assertEqual(code, REPLY_COMMAND_NOT_SUPPORTED)
+
+    def test_unknown_command(self):
+        self.negotiate()
+        self.sock.sendall(ipv4_request(0x09, target_ip, target_port))
+        self.assert_not_succeeded(REPLY_COMMAND_NOT_SUPPORTED)
+
+    def test_unknown_address_type(self):
+        self.negotiate()
+        self.sock.sendall(b'\x05\x01\x00\x05' + socket.inet_aton(target_ip) + struct.pack('!H', target_port))
+        self.assert_not_succeeded(REPLY_ADDRESS_TYPE_NOT_SUPPORTED)
+
+    def test_empty_domain(self):
+        self.negotiate()
+        self.sock.sendall(b'\x05\x01\x00\x03\x00' + struct.pack('!H', target_port))
+        self.assert_not_succeeded()
+
+    def test_truncated_request(self):
+        self.negotiate()
+        self.sock.sendall(domain_request(0x01, target_host, target_port)[:-1])
+        self.sock.shutdown(socket.SHUT_WR)
+        self.assertEqual(recv_until_closed(self.sock), b'')
+
+
+class TestSocks4a(unittest.TestCase):
+    def setUp(self):
+        self.sock = connect(proxy_addr)
+
+    def tearDown(self):
+        self.sock.close()
+
+    def test_connect_domain(self):
+        self.sock.sendall(b'\x04\x01' + struct.pack('!H', target_port) + b'\x00\x00\x00\x01'
+                          + b'\x00' + target_host.encode('ascii') + b'\x00')
+        rsp = recv_exact(self.sock, 8)
+        self.assertEqual(rsp[0], 0x00)
+        self.assertEqual(rsp[1], 0x5a)
+
+    def test_bind_rejected(self):
+        self.sock.sendall(b'\x04\x02' + struct.pack('!H', target_port) + socket.inet_aton(target_ip) + b'\x00')
+        rsp = recv_exact(self.sock, 8)
+        self.assertEqual(rsp[1], 0x5b)
+
+
+if __name__ == '__main__':
+    parser = argparse.ArgumentParser(description='SOCKS protocol conformance tests')
+    parser.add_argument('--proxy', '-x', nargs='?', help='Proxy address', default='127.0.0.1:1080')
+    parser.add_argument('--target', '-T', nargs='?', help='Target HTTP server domain:port', default='httpbin.local:80')
+    parser.add_argument('--target-ip', nargs='?', help='Target HTTP server IPv4 address', default=target_ip)
+
+    (args, left_args) = parser.parse_known_args()
+
+    proxy_addr = parse_addr(args.proxy, 1080)
+    (target_host, target_port) = parse_addr(args.target, 80)
+    target_ip = args.target_ip
+
+    left_args.insert(0, sys.argv[0])
+
+    unittest.main(argv=left_args)
@@ -21,8 +21,10 @@ fn main() {
         .file("schema/proc.capnp")
         .file("schema/user_group.capnp")
         .file("schema/resolver.capnp")
+        .file("schema/auditor.capnp")
         .file("schema/escaper.capnp")
         .file("schema/server.capnp")
+        .file("schema/tenant.capnp")
         .run()
         .unwrap();
 }
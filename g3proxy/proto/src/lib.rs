@@ -33,6 +33,10 @@ pub mod resolver_capnp {
     include!(concat!(env!("OUT_DIR"), "/resolver_capnp.rs"));
 }
 
+pub mod auditor_capnp {
+    include!(concat!(env!("OUT_DIR"), "/auditor_capnp.rs"));
+}
+
 pub mod escaper_capnp {
     include!(concat!(env!("OUT_DIR"), "/escaper_capnp.rs"));
 }
@@ -40,3 +44,7 @@ pub mod escaper_capnp {
 pub mod server_capnp {
     include!(concat!(env!("OUT_DIR"), "/server_capnp.rs"));
 }
+
+pub mod tenant_capnp {
+    include!(concat!(env!("OUT_DIR"), "/tenant_capnp.rs"));
+}
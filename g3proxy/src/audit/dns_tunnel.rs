@@ -0,0 +1,73 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ahash::AHashMap;
+
+type DomainQueryKey = (Option<Arc<str>>, String);
+
+struct DomainQueryStats {
+    window_start: Instant,
+    queries: usize,
+    txt_queries: usize,
+}
+
+/// Track the DNS queries for each user and registered domain within a fixed time window
+#[derive(Default)]
+pub(crate) struct DnsTunnelTracker {
+    inner: Mutex<AHashMap<DomainQueryKey, DomainQueryStats>>,
+}
+
+impl DnsTunnelTracker {
+    // stale entries will be pruned when the map grows larger than this
+    const PRUNE_THRESHOLD: usize = 16384;
+
+    /// Record a new query, return the query count and the TXT query count in current window
+    pub(crate) fn record(
+        &self,
+        user: Option<&Arc<str>>,
+        domain: &str,
+        is_txt: bool,
+        window: Duration,
+    ) -> (usize, usize) {
+        let now = Instant::now();
+        let mut map = self.inner.lock().unwrap();
+
+        if map.len() > Self::PRUNE_THRESHOLD {
+            map.retain(|_, stats| now.duration_since(stats.window_start) < window);
+        }
+
+        let stats = map
+            .entry((user.cloned(), domain.to_string()))
+            .or_insert(DomainQueryStats {
+                window_start: now,
+                queries: 0,
+                txt_queries: 0,
+            });
+        if now.duration_since(stats.window_start) >= window {
+            stats.window_start = now;
+            stats.queries = 0;
+            stats.txt_queries = 0;
+        }
+        stats.queries += 1;
+        if is_txt {
+            stats.txt_queries += 1;
+        }
+        (stats.queries, stats.txt_queries)
+    }
+}
@@ -19,15 +19,17 @@ use std::sync::Arc;
 use slog::Logger;
 
 use g3_dpi::{
-    H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig, ProtocolInspectPolicy,
-    ProtocolInspectionConfig, ProtocolPortMap, SmtpInterceptionConfig,
+    DnsInterceptionConfig, H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig,
+    KafkaInterceptionConfig, MysqlInterceptionConfig, PostgresInterceptionConfig,
+    ProtocolInspectPolicy, ProtocolInspectionConfig, ProtocolPortMap, SmtpInterceptionConfig,
+    WebsocketInterceptionConfig,
 };
 use g3_icap_client::reqmod::IcapReqmodClient;
 use g3_icap_client::respmod::IcapRespmodClient;
 
-use super::Auditor;
 #[cfg(feature = "quic")]
 use super::StreamDetourClient;
+use super::{Auditor, AuditorStatsRecorder, DnsTunnelTracker};
 use crate::config::audit::AuditorConfig;
 use crate::inspect::tls::TlsInterceptionContext;
 
@@ -38,14 +40,18 @@ pub(crate) struct AuditHandle {
     tls_interception: Option<TlsInterceptionContext>,
     inspect_logger: Logger,
     intercept_logger: Logger,
+    flow_logger: Option<Logger>,
     icap_reqmod_client: Option<IcapReqmodClient>,
     icap_respmod_client: Option<IcapRespmodClient>,
     #[cfg(feature = "quic")]
     stream_detour_client: Option<Arc<StreamDetourClient>>,
+    dns_tunnel_tracker: Arc<DnsTunnelTracker>,
+    stats_recorder: Arc<AuditorStatsRecorder>,
     pub(crate) h2_inspect_policy: ProtocolInspectPolicy,
     pub(crate) websocket_inspect_policy: ProtocolInspectPolicy,
     pub(crate) smtp_inspect_policy: ProtocolInspectPolicy,
     pub(crate) imap_inspect_policy: ProtocolInspectPolicy,
+    pub(crate) mysql_inspect_policy: ProtocolInspectPolicy,
 }
 
 impl AuditHandle {
@@ -65,14 +71,18 @@ impl AuditHandle {
             tls_interception: None,
             inspect_logger: crate::log::inspect::get_logger(auditor.config.name()),
             intercept_logger: crate::log::intercept::get_logger(auditor.config.name()),
+            flow_logger: crate::log::flow::get_logger(auditor.config.name()),
             icap_reqmod_client: icap_reqmod_service,
             icap_respmod_client: icap_respmod_service,
             #[cfg(feature = "quic")]
             stream_detour_client: auditor.stream_detour_service.clone(),
+            dns_tunnel_tracker: auditor.dns_tunnel_tracker.clone(),
+            stats_recorder: auditor.stats_recorder.clone(),
             h2_inspect_policy: auditor.config.h2_inspect_policy.build(),
             websocket_inspect_policy: auditor.config.websocket_inspect_policy.build(),
             smtp_inspect_policy: auditor.config.smtp_inspect_policy.build(),
             imap_inspect_policy: auditor.config.imap_inspect_policy.build(),
+            mysql_inspect_policy: auditor.config.mysql_inspect_policy.build(),
         }
     }
 
@@ -90,6 +100,11 @@ impl AuditHandle {
         &self.intercept_logger
     }
 
+    #[inline]
+    pub(crate) fn flow_logger(&self) -> Option<&Logger> {
+        self.flow_logger.as_ref()
+    }
+
     #[inline]
     pub(crate) fn protocol_inspection(&self) -> &ProtocolInspectionConfig {
         &self.auditor_config.protocol_inspection
@@ -125,6 +140,11 @@ impl AuditHandle {
         &self.auditor_config.h2_interception
     }
 
+    #[inline]
+    pub(crate) fn websocket_interception(&self) -> &WebsocketInterceptionConfig {
+        &self.auditor_config.websocket_interception
+    }
+
     #[inline]
     pub(crate) fn smtp_interception(&self) -> &SmtpInterceptionConfig {
         &self.auditor_config.smtp_interception
@@ -135,6 +155,36 @@ impl AuditHandle {
         &self.auditor_config.imap_interception
     }
 
+    #[inline]
+    pub(crate) fn kafka_interception(&self) -> &KafkaInterceptionConfig {
+        &self.auditor_config.kafka_interception
+    }
+
+    #[inline]
+    pub(crate) fn postgres_interception(&self) -> &PostgresInterceptionConfig {
+        &self.auditor_config.postgres_interception
+    }
+
+    #[inline]
+    pub(crate) fn mysql_interception(&self) -> &MysqlInterceptionConfig {
+        &self.auditor_config.mysql_interception
+    }
+
+    #[inline]
+    pub(crate) fn dns_interception(&self) -> &DnsInterceptionConfig {
+        &self.auditor_config.dns_interception
+    }
+
+    #[inline]
+    pub(crate) fn dns_tunnel_tracker(&self) -> &DnsTunnelTracker {
+        &self.dns_tunnel_tracker
+    }
+
+    #[inline]
+    pub(crate) fn stats_recorder(&self) -> &Arc<AuditorStatsRecorder> {
+        &self.stats_recorder
+    }
+
     #[inline]
     pub(crate) fn icap_reqmod_client(&self) -> Option<&IcapReqmodClient> {
         self.icap_reqmod_client.as_ref()
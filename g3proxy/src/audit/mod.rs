@@ -16,12 +16,13 @@
 
 use std::sync::Arc;
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 
 use g3_dpi::ProtocolPortMap;
-use g3_icap_client::IcapServiceClient;
+use g3_icap_client::respmod::{IcapVerdictCache, IcapVerdictCacheSnapshot};
+use g3_icap_client::{IcapConnectionPoolSnapshot, IcapMethod, IcapServiceClient};
 use g3_types::metrics::NodeName;
-use g3_types::net::{OpensslTicketKey, RollingTicketer};
+use g3_types::net::{OpensslSharedSessionCache, OpensslTicketKey, RollingTicketer};
 
 use crate::config::audit::AuditorConfig;
 use crate::inspect::tls::TlsInterceptionContext;
@@ -31,11 +32,20 @@ pub use ops::load_all;
 pub(crate) use ops::reload;
 
 mod registry;
-pub(crate) use registry::{get_names, get_or_insert_default};
+pub(crate) use registry::{foreach, get_names, get_or_insert_default};
 
 mod handle;
 pub(crate) use handle::AuditHandle;
 
+mod preflight;
+pub(crate) use preflight::preflight_check;
+
+mod dns_tunnel;
+use dns_tunnel::DnsTunnelTracker;
+
+mod stats;
+pub(crate) use stats::{AuditorSnapshot, AuditorStats, AuditorStatsRecorder};
+
 #[cfg(feature = "quic")]
 mod detour;
 #[cfg(feature = "quic")]
@@ -48,15 +58,19 @@ pub(crate) struct Auditor {
     server_tcp_portmap: Arc<ProtocolPortMap>,
     client_tcp_portmap: Arc<ProtocolPortMap>,
     tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+    tls_client_session_cache: Option<OpensslSharedSessionCache>,
     icap_reqmod_service: Option<Arc<IcapServiceClient>>,
     icap_respmod_service: Option<Arc<IcapServiceClient>>,
     #[cfg(feature = "quic")]
     stream_detour_service: Option<Arc<StreamDetourClient>>,
+    dns_tunnel_tracker: Arc<DnsTunnelTracker>,
+    stats_recorder: Arc<AuditorStatsRecorder>,
 }
 
 impl Auditor {
     fn new_no_config(name: &NodeName) -> Arc<Self> {
         let config = AuditorConfig::empty(name);
+        let stats_recorder = AuditorStatsRecorder::new(name, &config.inspect_cost_stats);
         let server_tcp_portmap = Arc::new(config.server_tcp_portmap.clone());
         let client_tcp_portmap = Arc::new(config.client_tcp_portmap.clone());
         let auditor = Auditor {
@@ -64,10 +78,13 @@ impl Auditor {
             server_tcp_portmap,
             client_tcp_portmap,
             tls_rolling_ticketer: None,
+            tls_client_session_cache: None,
             icap_reqmod_service: None,
             icap_respmod_service: None,
             #[cfg(feature = "quic")]
             stream_detour_service: None,
+            dns_tunnel_tracker: Arc::new(DnsTunnelTracker::default()),
+            stats_recorder: Arc::new(stats_recorder),
         };
         Arc::new(auditor)
     }
@@ -77,23 +94,28 @@ impl Auditor {
         let client_tcp_portmap = Arc::new(config.client_tcp_portmap.clone());
         let tls_rolling_ticketer = if let Some(c) = &config.tls_ticketer {
             let ticketer = c
-                .build_and_spawn_updater()
+                .build_and_spawn_updater(config.name())
                 .context("failed to create tls rolling ticketer")?;
             Some(ticketer)
         } else {
             None
         };
+        let tls_client_session_cache = config.tls_interception_client.new_shared_session_cache();
+        let stats_recorder = AuditorStatsRecorder::new(config.name(), &config.inspect_cost_stats);
         let mut auditor = Auditor {
             config: Arc::new(config),
             server_tcp_portmap,
             client_tcp_portmap,
             tls_rolling_ticketer,
+            tls_client_session_cache,
             icap_reqmod_service: None,
             icap_respmod_service: None,
             #[cfg(feature = "quic")]
             stream_detour_service: None,
+            dns_tunnel_tracker: Arc::new(DnsTunnelTracker::default()),
+            stats_recorder: Arc::new(stats_recorder),
         };
-        auditor.set_agent_clients()?;
+        auditor.set_agent_clients(None)?;
         Ok(Arc::new(auditor))
     }
 
@@ -104,36 +126,60 @@ impl Auditor {
             self.tls_rolling_ticketer.clone()
         } else if let Some(c) = &config.tls_ticketer {
             let ticketer = c
-                .build_and_spawn_updater()
+                .build_and_spawn_updater(config.name())
                 .context("failed to create tls rolling ticketer")?;
             Some(ticketer)
         } else {
             None
         };
+        // only keep the old sessions if the client config is not changed
+        let tls_client_session_cache = if self
+            .config
+            .tls_interception_client
+            .eq(&config.tls_interception_client)
+        {
+            self.tls_client_session_cache.clone()
+        } else {
+            config.tls_interception_client.new_shared_session_cache()
+        };
+        let stats_recorder = self.stats_recorder.reload(&config.inspect_cost_stats);
         let mut auditor = Auditor {
             config: Arc::new(config),
             server_tcp_portmap,
             client_tcp_portmap,
             tls_rolling_ticketer,
+            tls_client_session_cache,
             icap_reqmod_service: None,
             icap_respmod_service: None,
             #[cfg(feature = "quic")]
             stream_detour_service: None,
+            dns_tunnel_tracker: self.dns_tunnel_tracker.clone(),
+            stats_recorder,
         };
-        auditor.set_agent_clients()?;
+        auditor.set_agent_clients(Some(self))?;
         Ok(Arc::new(auditor))
     }
 
-    fn set_agent_clients(&mut self) -> anyhow::Result<()> {
+    pub(crate) fn get_stats(&self) -> Arc<AuditorStats> {
+        self.stats_recorder.stats().clone()
+    }
+
+    fn set_agent_clients(&mut self, old: Option<&Auditor>) -> anyhow::Result<()> {
         if let Some(c) = self.config.icap_reqmod_service.clone() {
             self.icap_reqmod_service = Some(Arc::new(
                 IcapServiceClient::new(c).context("failed to create ICAP REQMOD client")?,
             ));
         }
         if let Some(c) = self.config.icap_respmod_service.clone() {
-            self.icap_respmod_service = Some(Arc::new(
-                IcapServiceClient::new(c).context("failed to create ICAP RESPMOD client")?,
-            ));
+            let client = match &self.icap_reqmod_service {
+                Some(reqmod) => IcapServiceClient::new_sharing_pool(c, reqmod),
+                None => IcapServiceClient::new(c),
+            };
+            let mut client = client.context("failed to create ICAP RESPMOD client")?;
+            if let Some(old_client) = old.and_then(|a| a.icap_respmod_service.as_ref()) {
+                client.inherit_verdict_cache(old_client);
+            }
+            self.icap_respmod_service = Some(Arc::new(client));
         }
         #[cfg(feature = "quic")]
         if let Some(c) = self.config.stream_detour_service.clone() {
@@ -153,7 +199,7 @@ impl Auditor {
             let client_config = self
                 .config
                 .tls_interception_client
-                .build()
+                .build_with_shared_session_cache(self.tls_client_session_cache.as_ref())
                 .context("failed to build tls client config")?;
             let server_config = self
                 .config
@@ -165,12 +211,60 @@ impl Auditor {
                 client_config,
                 server_config,
                 self.config.tls_stream_dump,
+                self.config.tls_interception_fallback,
             )?;
             handle.set_tls_interception(ctx);
         }
 
         Ok(Arc::new(handle))
     }
+
+    fn icap_verdict_cache(&self) -> anyhow::Result<&Arc<IcapVerdictCache>> {
+        self.icap_respmod_service
+            .as_ref()
+            .and_then(|c| c.verdict_cache())
+            .ok_or_else(|| anyhow!("no ICAP verdict cache enabled in this auditor"))
+    }
+}
+
+pub(crate) fn flush_icap_verdict_cache(name: &NodeName) -> anyhow::Result<usize> {
+    let auditor =
+        registry::get(name).ok_or_else(|| anyhow!("no auditor with name {name} found"))?;
+    let cache = auditor.icap_verdict_cache()?;
+    Ok(cache.flush())
+}
+
+pub(crate) fn icap_verdict_cache_snapshot(
+    name: &NodeName,
+) -> anyhow::Result<IcapVerdictCacheSnapshot> {
+    let auditor =
+        registry::get(name).ok_or_else(|| anyhow!("no auditor with name {name} found"))?;
+    let cache = auditor.icap_verdict_cache()?;
+    Ok(cache.snapshot())
+}
+
+pub(crate) fn icap_connection_pool_snapshot(
+    name: &NodeName,
+    method: IcapMethod,
+) -> anyhow::Result<(IcapConnectionPoolSnapshot, bool)> {
+    let auditor =
+        registry::get(name).ok_or_else(|| anyhow!("no auditor with name {name} found"))?;
+    let (client, other) = match method {
+        IcapMethod::Reqmod => (&auditor.icap_reqmod_service, &auditor.icap_respmod_service),
+        IcapMethod::Respmod => (&auditor.icap_respmod_service, &auditor.icap_reqmod_service),
+        IcapMethod::Options => return Err(anyhow!("no ICAP OPTIONS service")),
+    };
+    let client = client.as_ref().ok_or_else(|| {
+        anyhow!(
+            "no ICAP {} service enabled in this auditor",
+            method.as_str()
+        )
+    })?;
+    let shared = other
+        .as_ref()
+        .map(|o| client.pool_shared_with(o))
+        .unwrap_or(false);
+    Ok((client.pool_snapshot(), shared))
 }
 
 #[derive(Clone, Default)]
@@ -0,0 +1,75 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use anyhow::{anyhow, Context};
+
+use g3_icap_client::IcapServiceClient;
+
+use crate::config::preflight::{PreflightConfig, PreflightPolicy};
+use crate::preflight::{PreflightComponent, PreflightReport};
+
+async fn check_icap(config: &PreflightConfig, client: &IcapServiceClient) -> anyhow::Result<()> {
+    match tokio::time::timeout(config.check_timeout, client.probe_options()).await {
+        Ok(Ok(_)) => Ok(()),
+        Ok(Err(e)) => Err(e),
+        Err(_) => Err(anyhow!("timed out to get icap service options")),
+    }
+}
+
+pub(crate) async fn preflight_check(config: &PreflightConfig, report: &mut PreflightReport) {
+    for name in super::registry::get_names() {
+        let Some(auditor) = super::registry::get(&name) else {
+            continue;
+        };
+
+        if config.icap != PreflightPolicy::Skip {
+            if let Some(client) = &auditor.icap_reqmod_service {
+                let r = check_icap(config, client).await;
+                report.add(PreflightComponent::IcapReqmod, &name, config.icap, r);
+            }
+            if let Some(client) = &auditor.icap_respmod_service {
+                let r = check_icap(config, client).await;
+                report.add(PreflightComponent::IcapRespmod, &name, config.icap, r);
+            }
+        }
+
+        if config.cert_agent != PreflightPolicy::Skip {
+            if let Some(cert_agent_config) = &auditor.config.tls_cert_agent {
+                let r = match cert_agent_config
+                    .spawn_cert_agent()
+                    .context("failed to spawn cert agent")
+                {
+                    Ok(cert_agent) => {
+                        match tokio::time::timeout(
+                            config.check_timeout,
+                            cert_agent.probe(config.cert_test_host.clone()),
+                        )
+                        .await
+                        {
+                            Ok(Some(_)) => Ok(()),
+                            Ok(None) => Err(anyhow!(
+                                "no cert returned for host {}",
+                                config.cert_test_host
+                            )),
+                            Err(_) => Err(anyhow!("timed out to get cert from cert generator")),
+                        }
+                    }
+                    Err(e) => Err(e),
+                };
+                report.add(PreflightComponent::CertAgent, &name, config.cert_agent, r);
+            }
+        }
+    }
+}
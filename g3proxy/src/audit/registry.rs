@@ -40,6 +40,16 @@ pub(super) fn del(name: &NodeName) {
     if let Some(_old_auditor) = ht.remove(name) {}
 }
 
+pub(crate) fn foreach<F>(mut f: F)
+where
+    F: FnMut(&NodeName, &Auditor),
+{
+    let ht = RUNTIME_AUDITOR_REGISTRY.lock().unwrap();
+    for (name, auditor) in ht.iter() {
+        f(name, auditor)
+    }
+}
+
 pub(crate) fn get_names() -> HashSet<NodeName> {
     let mut names = HashSet::new();
     let ht = RUNTIME_AUDITOR_REGISTRY.lock().unwrap();
@@ -0,0 +1,120 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ahash::AHashMap;
+
+use g3_dpi::{Protocol, ProtocolInspectAction};
+use g3_histogram::{HistogramMetricsConfig, HistogramRecorder, HistogramStats};
+use g3_types::ext::DurationExt;
+use g3_types::metrics::NodeName;
+use g3_types::stats::StatId;
+
+pub(crate) struct AuditorStats {
+    id: StatId,
+    name: NodeName,
+    protocol: Mutex<AHashMap<&'static str, u64>>,
+    inspect_action: Mutex<AHashMap<(&'static str, &'static str), u64>>,
+    pub(crate) inspect_duration: Arc<HistogramStats>,
+    pub(crate) inspect_bytes: Arc<HistogramStats>,
+}
+
+impl AuditorStats {
+    #[inline]
+    pub(crate) fn stat_id(&self) -> StatId {
+        self.id
+    }
+
+    #[inline]
+    pub(crate) fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    pub(crate) fn protocol_snapshot(&self) -> AHashMap<&'static str, u64> {
+        self.protocol.lock().unwrap().clone()
+    }
+
+    pub(crate) fn inspect_action_snapshot(&self) -> AHashMap<(&'static str, &'static str), u64> {
+        self.inspect_action.lock().unwrap().clone()
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct AuditorSnapshot {
+    pub(crate) protocol: AHashMap<&'static str, u64>,
+    pub(crate) inspect_action: AHashMap<(&'static str, &'static str), u64>,
+}
+
+pub(crate) struct AuditorStatsRecorder {
+    config: HistogramMetricsConfig,
+    stats: Arc<AuditorStats>,
+    inspect_duration: HistogramRecorder<u64>,
+    inspect_bytes: HistogramRecorder<u64>,
+}
+
+impl AuditorStatsRecorder {
+    pub(super) fn new(name: &NodeName, config: &HistogramMetricsConfig) -> Self {
+        let (inspect_duration_r, inspect_duration_s) =
+            config.build_spawned(g3_daemon::runtime::main_handle().cloned());
+        let (inspect_bytes_r, inspect_bytes_s) =
+            config.build_spawned(g3_daemon::runtime::main_handle().cloned());
+
+        let stats = AuditorStats {
+            id: StatId::new(),
+            name: name.clone(),
+            protocol: Mutex::new(AHashMap::new()),
+            inspect_action: Mutex::new(AHashMap::new()),
+            inspect_duration: inspect_duration_s,
+            inspect_bytes: inspect_bytes_s,
+        };
+        AuditorStatsRecorder {
+            config: config.clone(),
+            stats: Arc::new(stats),
+            inspect_duration: inspect_duration_r,
+            inspect_bytes: inspect_bytes_r,
+        }
+    }
+
+    /// Reuse the old recorder if the histogram config is not changed, so the metrics won't be reset
+    pub(super) fn reload(self: &Arc<Self>, config: &HistogramMetricsConfig) -> Arc<Self> {
+        if self.config.eq(config) {
+            self.clone()
+        } else {
+            Arc::new(AuditorStatsRecorder::new(&self.stats.name, config))
+        }
+    }
+
+    #[inline]
+    pub(crate) fn stats(&self) -> &Arc<AuditorStats> {
+        &self.stats
+    }
+
+    pub(crate) fn add_protocol(&self, protocol: Protocol) {
+        let mut map = self.stats.protocol.lock().unwrap();
+        *map.entry(protocol.as_str()).or_default() += 1;
+    }
+
+    pub(crate) fn add_inspect_action(&self, protocol: Protocol, action: ProtocolInspectAction) {
+        let mut map = self.stats.inspect_action.lock().unwrap();
+        *map.entry((protocol.as_str(), action.as_str())).or_default() += 1;
+    }
+
+    pub(crate) fn record_inspect_cost(&self, dur: Duration, bytes: u64) {
+        let _ = self.inspect_duration.record(dur.as_nanos_u64());
+        let _ = self.inspect_bytes.record(bytes);
+    }
+}
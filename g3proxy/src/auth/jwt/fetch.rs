@@ -0,0 +1,104 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::anyhow;
+use http::{Method, StatusCode};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, BufStream};
+use tokio::net::TcpStream;
+use url::{Position, Url};
+
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_http::HttpBodyReader;
+use g3_io_ext::LimitedWriteExt;
+use g3_openssl::SslConnector;
+use g3_types::net::{Host, OpensslClientConfig};
+
+const RSP_HEADER_MAX_SIZE: usize = 4096;
+const RSP_BODY_MAX_SIZE: u64 = 1 << 20;
+
+pub(super) async fn fetch_url(
+    url: &Url,
+    tls_client: Option<&OpensslClientConfig>,
+    tls_name: Option<&Host>,
+) -> anyhow::Result<Vec<u8>> {
+    let Some(host) = url.host() else {
+        return Err(anyhow!("no host found in url"));
+    };
+    let host = Host::from(host.to_owned());
+    let Some(port) = url.port_or_known_default() else {
+        return Err(anyhow!("no port found in url"));
+    };
+
+    let stream = TcpStream::connect((host.to_string().as_str(), port))
+        .await
+        .map_err(|e| anyhow!("failed to connect to {host}: {e}"))?;
+
+    match tls_client {
+        Some(tls_client) if url.scheme() == "https" => {
+            let tls_name = tls_name.unwrap_or(&host);
+            let ssl = tls_client.build_ssl(tls_name, port)?;
+            let connector = SslConnector::new(ssl, stream)
+                .map_err(|e| anyhow!("failed to get ssl stream: {e}"))?;
+            let stream = tokio::time::timeout(tls_client.handshake_timeout, connector.connect())
+                .await
+                .map_err(|_| anyhow!("tls handshake timed out"))?
+                .map_err(|e| anyhow!("tls handshake failed: {e}"))?;
+            fetch_on(stream, url).await
+        }
+        _ => fetch_on(stream, url).await,
+    }
+}
+
+async fn fetch_on<S>(stream: S, url: &Url) -> anyhow::Result<Vec<u8>>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut stream = BufStream::new(stream);
+
+    let req = format!(
+        "GET {} HTTP/1.1\r\n\
+         Host: {}\r\n\
+         Accept: application/json\r\n\
+         Connection: close\r\n\
+         \r\n",
+        &url[Position::BeforePath..Position::AfterQuery],
+        &url[Position::BeforeHost..Position::AfterPort],
+    );
+    stream
+        .write_all_flush(req.as_bytes())
+        .await
+        .map_err(|e| anyhow!("failed to write request: {e:?}"))?;
+
+    let rsp =
+        HttpForwardRemoteResponse::parse(&mut stream, &Method::GET, false, RSP_HEADER_MAX_SIZE)
+            .await
+            .map_err(|e| anyhow!("failed to recv response: {e}"))?;
+    if rsp.code != StatusCode::OK {
+        return Err(anyhow!("unexpected response: {} {}", rsp.code, rsp.reason));
+    }
+    let Some(body_type) = rsp.body_type(&Method::GET) else {
+        return Err(anyhow!("no body found in response"));
+    };
+
+    let body_reader = HttpBodyReader::new(&mut stream, body_type, 1024);
+    let mut data = Vec::new();
+    body_reader
+        .take(RSP_BODY_MAX_SIZE)
+        .read_to_end(&mut data)
+        .await
+        .map_err(|e| anyhow!("failed to read response body: {e:?}"))?;
+    Ok(data)
+}
@@ -0,0 +1,201 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::{anyhow, Context};
+use base64::prelude::*;
+use log::debug;
+use openssl::bn::BigNum;
+use openssl::ec::{EcGroup, EcKey};
+use openssl::ecdsa::EcdsaSig;
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Public};
+use openssl::rsa::Rsa;
+use openssl::sign::Verifier;
+use serde_json::Value;
+
+use crate::config::auth::JwtAlgorithm;
+
+const ES256_SIGNATURE_LEN: usize = 64;
+
+pub(super) enum JwkPublicKey {
+    Rsa(PKey<Public>),
+    Ec(EcKey<Public>),
+}
+
+impl JwkPublicKey {
+    fn parse_rsa(map: &serde_json::Map<String, Value>) -> anyhow::Result<Self> {
+        let n = get_b64_bignum(map, "n")?;
+        let e = get_b64_bignum(map, "e")?;
+        let rsa = Rsa::from_public_components(n, e)
+            .map_err(|e| anyhow!("invalid rsa public key: {e}"))?;
+        let pkey = PKey::from_rsa(rsa).map_err(|e| anyhow!("failed to build rsa pkey: {e}"))?;
+        Ok(JwkPublicKey::Rsa(pkey))
+    }
+
+    fn parse_ec(map: &serde_json::Map<String, Value>) -> anyhow::Result<Self> {
+        match map.get("crv").and_then(|v| v.as_str()) {
+            Some("P-256") => {}
+            Some(crv) => return Err(anyhow!("unsupported ec curve {crv}")),
+            None => return Err(anyhow!("no crv found")),
+        }
+        let x = get_b64_bignum(map, "x")?;
+        let y = get_b64_bignum(map, "y")?;
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)
+            .map_err(|e| anyhow!("failed to get P-256 group: {e}"))?;
+        let key = EcKey::from_public_key_affine_coordinates(&group, &x, &y)
+            .map_err(|e| anyhow!("invalid ec public key: {e}"))?;
+        key.check_key()
+            .map_err(|e| anyhow!("invalid ec public key: {e}"))?;
+        Ok(JwkPublicKey::Ec(key))
+    }
+
+    fn support(&self, alg: JwtAlgorithm) -> bool {
+        matches!(
+            (self, alg),
+            (JwkPublicKey::Rsa(_), JwtAlgorithm::RS256)
+                | (JwkPublicKey::Ec(_), JwtAlgorithm::ES256)
+        )
+    }
+
+    fn verify(&self, input: &[u8], signature: &[u8]) -> anyhow::Result<bool> {
+        match self {
+            JwkPublicKey::Rsa(pkey) => {
+                let mut verifier = Verifier::new(MessageDigest::sha256(), pkey)
+                    .map_err(|e| anyhow!("failed to create rsa verifier: {e}"))?;
+                verifier
+                    .update(input)
+                    .map_err(|e| anyhow!("failed to update rsa verifier: {e}"))?;
+                verifier
+                    .verify(signature)
+                    .map_err(|e| anyhow!("rsa verify failed: {e}"))
+            }
+            JwkPublicKey::Ec(key) => {
+                // the signature is the concatenation of R and S, see RFC 7518 Section 3.4
+                if signature.len() != ES256_SIGNATURE_LEN {
+                    return Ok(false);
+                }
+                let r = BigNum::from_slice(&signature[..32])
+                    .map_err(|e| anyhow!("invalid ecdsa r value: {e}"))?;
+                let s = BigNum::from_slice(&signature[32..])
+                    .map_err(|e| anyhow!("invalid ecdsa s value: {e}"))?;
+                let sig = EcdsaSig::from_private_components(r, s)
+                    .map_err(|e| anyhow!("invalid ecdsa signature: {e}"))?;
+                let digest = openssl::sha::sha256(input);
+                sig.verify(&digest, key)
+                    .map_err(|e| anyhow!("ecdsa verify failed: {e}"))
+            }
+        }
+    }
+}
+
+struct JwkKey {
+    kid: Option<String>,
+    alg: Option<JwtAlgorithm>,
+    key: JwkPublicKey,
+}
+
+#[derive(Default)]
+pub(super) struct JwkSet {
+    keys: Vec<JwkKey>,
+}
+
+impl JwkSet {
+    pub(super) fn parse_json(data: &[u8]) -> anyhow::Result<Self> {
+        let doc: Value =
+            serde_json::from_slice(data).map_err(|e| anyhow!("invalid json document: {e}"))?;
+        let Some(keys) = doc.get("keys").and_then(|v| v.as_array()) else {
+            return Err(anyhow!("no keys array found in jwk set"));
+        };
+
+        let mut set = JwkSet::default();
+        for (i, v) in keys.iter().enumerate() {
+            let Value::Object(map) = v else {
+                return Err(anyhow!("jwk #{i} is not a json object"));
+            };
+            if let Some(usage) = map.get("use").and_then(|v| v.as_str()) {
+                if usage != "sig" {
+                    continue;
+                }
+            }
+            let alg = match map.get("alg").and_then(|v| v.as_str()) {
+                Some(s) => match s.parse() {
+                    Ok(alg) => Some(alg),
+                    Err(_) => {
+                        debug!("jwk #{i}: unsupported alg {s}");
+                        continue;
+                    }
+                },
+                None => None,
+            };
+            let key = match map.get("kty").and_then(|v| v.as_str()) {
+                Some("RSA") => JwkPublicKey::parse_rsa(map),
+                Some("EC") => JwkPublicKey::parse_ec(map),
+                Some(kty) => {
+                    debug!("jwk #{i}: unsupported key type {kty}");
+                    continue;
+                }
+                None => Err(anyhow!("no kty found")),
+            }
+            .context(format!("invalid jwk #{i}"))?;
+            let kid = map
+                .get("kid")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+            set.keys.push(JwkKey { kid, alg, key });
+        }
+        if set.keys.is_empty() {
+            return Err(anyhow!("no usable key found in jwk set"));
+        }
+        Ok(set)
+    }
+
+    pub(super) fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// verify the signature with all matched keys, the kid will be checked if set in the token
+    pub(super) fn verify(
+        &self,
+        kid: Option<&str>,
+        alg: JwtAlgorithm,
+        input: &[u8],
+        signature: &[u8],
+    ) -> bool {
+        self.keys
+            .iter()
+            .filter(|k| kid.is_none() || k.kid.as_deref() == kid)
+            .filter(|k| k.alg.map(|a| a == alg).unwrap_or(true) && k.key.support(alg))
+            .any(|k| match k.key.verify(input, signature) {
+                Ok(true) => true,
+                Ok(false) => false,
+                Err(e) => {
+                    debug!("jwt signature verify error: {e}");
+                    false
+                }
+            })
+    }
+}
+
+fn get_b64_bignum(map: &serde_json::Map<String, Value>, key: &str) -> anyhow::Result<BigNum> {
+    let Some(s) = map.get(key).and_then(|v| v.as_str()) else {
+        return Err(anyhow!("no {key} found"));
+    };
+    let data = BASE64_URL_SAFE_NO_PAD
+        .decode(s)
+        .map_err(|e| anyhow!("invalid base64url value for {key}: {e}"))?;
+    BigNum::from_slice(&data).map_err(|e| anyhow!("invalid bignum value for {key}: {e}"))
+}
@@ -0,0 +1,349 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Context};
+use arc_swap::ArcSwap;
+use base64::prelude::*;
+use log::{debug, warn};
+use serde_json::{Map, Value};
+use url::Url;
+
+use g3_types::net::OpensslClientConfig;
+
+use crate::config::auth::{JwksSource, JwtAlgorithm, UserJwtConfig};
+
+mod fetch;
+
+mod jwks;
+use jwks::JwkSet;
+
+pub(crate) struct JwtAuthenticator {
+    config: UserJwtConfig,
+    tls_client: Option<OpensslClientConfig>,
+    jwks: ArcSwap<JwkSet>,
+}
+
+impl JwtAuthenticator {
+    pub(super) fn new(config: &UserJwtConfig) -> anyhow::Result<Arc<Self>> {
+        let tls_client = match &config.tls_client {
+            Some(builder) => Some(
+                builder
+                    .build()
+                    .context("failed to build tls client config")?,
+            ),
+            None => None,
+        };
+
+        let (jwks, fetch_now) = match &config.jwks {
+            Some(JwksSource::File(path)) => (load_file(path)?, false),
+            // the keys will be fetched in the background
+            _ => (JwkSet::default(), true),
+        };
+
+        let authenticator = Arc::new(JwtAuthenticator {
+            config: config.clone(),
+            tls_client,
+            jwks: ArcSwap::new(Arc::new(jwks)),
+        });
+
+        tokio::spawn(refresh_jwks(
+            Arc::downgrade(&authenticator),
+            config.refresh_interval,
+            fetch_now,
+        ));
+        Ok(authenticator)
+    }
+
+    /// verify the bearer token and return the mapped username
+    pub(super) fn verify(&self, token: &str) -> Option<Arc<str>> {
+        match self.verify_token(token) {
+            Ok(username) => Some(username),
+            Err(e) => {
+                debug!("jwt verify failed: {e:?}");
+                None
+            }
+        }
+    }
+
+    fn verify_token(&self, token: &str) -> anyhow::Result<Arc<str>> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(anyhow!("invalid jws compact serialization"));
+        };
+
+        let header = decode_json_object(header).context("invalid jose header")?;
+        let Some(alg) = header.get("alg").and_then(|v| v.as_str()) else {
+            return Err(anyhow!("no alg found in jose header"));
+        };
+        let alg = alg
+            .parse::<JwtAlgorithm>()
+            .map_err(|_| anyhow!("unsupported alg {alg}"))?;
+        if !self.config.algorithms.contains(&alg) {
+            return Err(anyhow!("alg {} is not allowed", alg.as_str()));
+        }
+        let kid = header.get("kid").and_then(|v| v.as_str());
+
+        let signature = BASE64_URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|e| anyhow!("invalid base64url encoded signature: {e}"))?;
+        let signing_input = &token[..header_payload_len(token)];
+        if !self
+            .jwks
+            .load()
+            .verify(kid, alg, signing_input.as_bytes(), &signature)
+        {
+            return Err(anyhow!("signature not match"));
+        }
+
+        let claims = decode_json_object(payload).context("invalid jwt claims")?;
+        self.check_claims(&claims)?;
+
+        let Some(value) = claims.get(&self.config.user_claim).and_then(|v| v.as_str()) else {
+            return Err(anyhow!(
+                "no string value found for claim {}",
+                self.config.user_claim
+            ));
+        };
+        Ok(self.config.map_claim(value))
+    }
+
+    fn check_claims(&self, claims: &Map<String, Value>) -> anyhow::Result<()> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        let leeway = self.config.leeway.as_secs();
+
+        match claims.get("exp") {
+            Some(v) => {
+                let exp = v.as_u64().ok_or_else(|| anyhow!("invalid exp claim"))?;
+                if exp.saturating_add(leeway) <= now {
+                    return Err(anyhow!("token expired"));
+                }
+            }
+            None => {
+                if self.config.require_exp {
+                    return Err(anyhow!("no exp claim found"));
+                }
+            }
+        }
+        if let Some(v) = claims.get("nbf") {
+            let nbf = v.as_u64().ok_or_else(|| anyhow!("invalid nbf claim"))?;
+            if nbf > now.saturating_add(leeway) {
+                return Err(anyhow!("token not valid yet"));
+            }
+        }
+
+        if let Some(issuer) = &self.config.issuer {
+            match claims.get("iss").and_then(|v| v.as_str()) {
+                Some(iss) if iss == issuer => {}
+                _ => return Err(anyhow!("issuer not match")),
+            }
+        }
+
+        if !self.config.audience.is_empty() {
+            let matched = match claims.get("aud") {
+                Some(Value::String(aud)) => self.config.audience.iter().any(|a| a == aud),
+                Some(Value::Array(list)) => list.iter().any(|v| {
+                    v.as_str()
+                        .map(|aud| self.config.audience.iter().any(|a| a == aud))
+                        .unwrap_or(false)
+                }),
+                _ => false,
+            };
+            if !matched {
+                return Err(anyhow!("audience not match"));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn reload_jwks(&self) -> anyhow::Result<()> {
+        let jwks = match &self.config.jwks {
+            Some(JwksSource::File(path)) => load_file(path)?,
+            Some(JwksSource::Url(url)) => self.load_url(url).await?,
+            None => return Ok(()),
+        };
+        debug!("loaded {} jwt verification keys", jwks.len());
+        self.jwks.store(Arc::new(jwks));
+        Ok(())
+    }
+
+    async fn load_url(&self, url: &Url) -> anyhow::Result<JwkSet> {
+        let data = tokio::time::timeout(
+            self.config.fetch_timeout,
+            fetch::fetch_url(url, self.tls_client.as_ref(), self.config.tls_name.as_ref()),
+        )
+        .await
+        .map_err(|_| anyhow!("timed out to fetch jwk set from {url}"))?
+        .context(format!("failed to fetch jwk set from {url}"))?;
+        JwkSet::parse_json(&data).context(format!("invalid jwk set fetched from {url}"))
+    }
+}
+
+async fn refresh_jwks(authenticator: Weak<JwtAuthenticator>, interval: Duration, fetch_now: bool) {
+    let mut interval = tokio::time::interval(interval);
+    if !fetch_now {
+        // skip the first tick, as the keys have already been loaded
+        interval.tick().await;
+    }
+
+    loop {
+        interval.tick().await;
+        let Some(authenticator) = authenticator.upgrade() else {
+            break;
+        };
+        if let Err(e) = authenticator.reload_jwks().await {
+            warn!("failed to refresh jwk set: {e:?}");
+        }
+    }
+}
+
+fn load_file(path: &std::path::Path) -> anyhow::Result<JwkSet> {
+    let data = std::fs::read(path)
+        .map_err(|e| anyhow!("failed to read jwks file {}: {e}", path.display()))?;
+    JwkSet::parse_json(&data).context(format!("invalid jwks file {}", path.display()))
+}
+
+fn decode_json_object(s: &str) -> anyhow::Result<Map<String, Value>> {
+    let data = BASE64_URL_SAFE_NO_PAD
+        .decode(s)
+        .map_err(|e| anyhow!("invalid base64url encoding: {e}"))?;
+    match serde_json::from_slice(&data) {
+        Ok(Value::Object(map)) => Ok(map),
+        Ok(_) => Err(anyhow!("not a json object")),
+        Err(e) => Err(anyhow!("invalid json: {e}")),
+    }
+}
+
+fn header_payload_len(token: &str) -> usize {
+    token.rfind('.').unwrap_or(token.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::bn::BigNumContext;
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::hash::MessageDigest;
+    use openssl::nid::Nid;
+    use openssl::pkey::{PKey, Private};
+    use openssl::rsa::Rsa;
+    use openssl::sign::Signer;
+
+    fn b64(data: &[u8]) -> String {
+        BASE64_URL_SAFE_NO_PAD.encode(data)
+    }
+
+    fn build_authenticator(jwks: &str) -> JwtAuthenticator {
+        let config = UserJwtConfig {
+            issuer: Some("https://idp.example.net".to_string()),
+            audience: vec!["g3proxy".to_string()],
+            ..Default::default()
+        };
+        JwtAuthenticator {
+            config,
+            tls_client: None,
+            jwks: ArcSwap::new(Arc::new(JwkSet::parse_json(jwks.as_bytes()).unwrap())),
+        }
+    }
+
+    fn claims(exp_offset: i64) -> String {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        format!(
+            r#"{{"sub":"alice","iss":"https://idp.example.net","aud":["g3proxy"],"exp":{}}}"#,
+            now + exp_offset
+        )
+    }
+
+    fn sign_rs256(key: &PKey<Private>, kid: &str, claims: &str) -> String {
+        let header = format!(r#"{{"alg":"RS256","typ":"JWT","kid":"{kid}"}}"#);
+        let input = format!("{}.{}", b64(header.as_bytes()), b64(claims.as_bytes()));
+        let mut signer = Signer::new(MessageDigest::sha256(), key).unwrap();
+        signer.update(input.as_bytes()).unwrap();
+        let sig = signer.sign_to_vec().unwrap();
+        format!("{input}.{}", b64(&sig))
+    }
+
+    fn sign_es256(key: &EcKey<Private>, claims: &str) -> String {
+        let header = r#"{"alg":"ES256","typ":"JWT"}"#;
+        let input = format!("{}.{}", b64(header.as_bytes()), b64(claims.as_bytes()));
+        let digest = openssl::sha::sha256(input.as_bytes());
+        let sig = openssl::ecdsa::EcdsaSig::sign(&digest, key).unwrap();
+        let mut raw = sig.r().to_vec_padded(32).unwrap();
+        raw.extend_from_slice(&sig.s().to_vec_padded(32).unwrap());
+        format!("{input}.{}", b64(&raw))
+    }
+
+    #[test]
+    fn verify_rs256() {
+        let rsa = Rsa::generate(2048).unwrap();
+        let jwks = format!(
+            r#"{{"keys":[{{"kty":"RSA","kid":"k1","use":"sig","n":"{}","e":"{}"}}]}}"#,
+            b64(&rsa.n().to_vec()),
+            b64(&rsa.e().to_vec())
+        );
+        let authenticator = build_authenticator(&jwks);
+        let key = PKey::from_rsa(rsa).unwrap();
+
+        let token = sign_rs256(&key, "k1", &claims(300));
+        assert_eq!(authenticator.verify(&token).as_deref(), Some("alice"));
+
+        let token = sign_rs256(&key, "k2", &claims(300));
+        assert!(authenticator.verify(&token).is_none());
+
+        let token = sign_rs256(&key, "k1", &claims(-300));
+        assert!(authenticator.verify(&token).is_none());
+
+        let mut token = sign_rs256(&key, "k1", &claims(300));
+        token.push('A');
+        assert!(authenticator.verify(&token).is_none());
+    }
+
+    #[test]
+    fn verify_es256() {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = EcKey::generate(&group).unwrap();
+        let mut ctx = BigNumContext::new().unwrap();
+        let mut x = openssl::bn::BigNum::new().unwrap();
+        let mut y = openssl::bn::BigNum::new().unwrap();
+        key.public_key()
+            .affine_coordinates(&group, &mut x, &mut y, &mut ctx)
+            .unwrap();
+        let jwks = format!(
+            r#"{{"keys":[{{"kty":"EC","crv":"P-256","x":"{}","y":"{}"}}]}}"#,
+            b64(&x.to_vec_padded(32).unwrap()),
+            b64(&y.to_vec_padded(32).unwrap())
+        );
+        let authenticator = build_authenticator(&jwks);
+
+        let token = sign_es256(&key, &claims(300));
+        assert_eq!(authenticator.verify(&token).as_deref(), Some("alice"));
+
+        let claims = claims(300).replace("g3proxy", "other");
+        let token = sign_es256(&key, &claims);
+        assert!(authenticator.verify(&token).is_none());
+    }
+}
@@ -0,0 +1,104 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use libgssapi::context::{SecurityContext, ServerCtx};
+use libgssapi::credential::{Cred, CredUsage};
+use libgssapi::name::Name;
+use libgssapi::oid::{OidSet, GSS_MECH_KRB5, GSS_MECH_SPNEGO, GSS_NT_HOSTBASED_SERVICE};
+use log::{debug, warn};
+
+use crate::config::auth::UserKerberosConfig;
+
+const KEYTAB_ENV_NAME: &str = "KRB5_KTNAME";
+
+pub(crate) struct KerberosAuthenticator {
+    config: UserKerberosConfig,
+}
+
+impl KerberosAuthenticator {
+    pub(super) fn new(config: &UserKerberosConfig) -> anyhow::Result<Self> {
+        if let Some(keytab) = &config.keytab {
+            // the keytab used by the gssapi acceptor can only be set process wide
+            std::env::set_var(KEYTAB_ENV_NAME, keytab);
+        }
+        if let Some(principal) = &config.service_principal {
+            // make sure the credential of the service principal is available in the keytab
+            acquire_cred(principal)
+                .context(format!("failed to acquire credential for {principal}"))?;
+        }
+        Ok(KerberosAuthenticator {
+            config: config.clone(),
+        })
+    }
+
+    /// accept the SPNEGO token and return the mapped username
+    ///
+    /// only single round trip kerberos exchange is supported
+    pub(super) async fn verify(self: &Arc<Self>, token: Vec<u8>) -> Option<Arc<str>> {
+        let authenticator = self.clone();
+        let principal =
+            match tokio::task::spawn_blocking(move || authenticator.accept(&token)).await {
+                Ok(Ok(principal)) => principal,
+                Ok(Err(e)) => {
+                    debug!("failed to accept negotiate token: {e:?}");
+                    return None;
+                }
+                Err(e) => {
+                    warn!("failed to run negotiate token accept task: {e}");
+                    return None;
+                }
+            };
+        let username = self.config.map_principal(&principal);
+        if username.is_none() {
+            debug!("no user mapped for kerberos principal {principal}");
+        }
+        username
+    }
+
+    fn accept(&self, token: &[u8]) -> anyhow::Result<String> {
+        let cred = match &self.config.service_principal {
+            Some(principal) => Some(acquire_cred(principal)?),
+            None => None,
+        };
+        let mut ctx = ServerCtx::new(cred);
+        ctx.step(token)
+            .map_err(|e| anyhow!("gssapi accept failed: {e}"))?;
+        if !ctx.is_complete() {
+            return Err(anyhow!("multiple round trip negotiation is not supported"));
+        }
+        let name = ctx
+            .source_name()
+            .map_err(|e| anyhow!("failed to get source name: {e}"))?;
+        Ok(name.to_string())
+    }
+}
+
+fn acquire_cred(principal: &str) -> anyhow::Result<Cred> {
+    let name = Name::new(principal.as_bytes(), Some(&GSS_NT_HOSTBASED_SERVICE))
+        .map_err(|e| anyhow!("invalid service principal: {e}"))?;
+    let mut mechs = OidSet::new().map_err(|e| anyhow!("failed to create oid set: {e}"))?;
+    mechs
+        .add(&GSS_MECH_SPNEGO)
+        .map_err(|e| anyhow!("failed to add spnego mech: {e}"))?;
+    mechs
+        .add(&GSS_MECH_KRB5)
+        .map_err(|e| anyhow!("failed to add krb5 mech: {e}"))?;
+    Cred::acquire(Some(&name), None, CredUsage::Accept, Some(&mechs))
+        .map_err(|e| anyhow!("failed to acquire acceptor credential: {e}"))
+}
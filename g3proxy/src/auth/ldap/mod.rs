@@ -0,0 +1,172 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::{anyhow, Context};
+use log::{debug, warn};
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use g3_openssl::SslConnector;
+use g3_types::net::{Host, OpensslClientConfig};
+
+use super::verify_cache::PasswordVerifyCache;
+use crate::config::auth::UserLdapConfig;
+
+mod protocol;
+
+const BIND_MESSAGE_ID: u32 = 1;
+
+pub(crate) struct LdapAuthenticator {
+    config: UserLdapConfig,
+    tls_client: Option<OpensslClientConfig>,
+    cache: PasswordVerifyCache,
+}
+
+impl LdapAuthenticator {
+    pub(super) fn new(config: &UserLdapConfig) -> anyhow::Result<Self> {
+        let tls_client = match &config.tls_client {
+            Some(builder) => Some(
+                builder
+                    .build()
+                    .context("failed to build tls client config")?,
+            ),
+            None => None,
+        };
+        Ok(LdapAuthenticator {
+            config: config.clone(),
+            tls_client,
+            cache: PasswordVerifyCache::new(config.cache_ttl, config.negative_cache_ttl),
+        })
+    }
+
+    /// verify the username and password by simple bind, the result will be cached
+    pub(super) async fn verify(&self, username: &str, password: &str) -> bool {
+        if username.is_empty() || password.is_empty() {
+            // an empty password will result in an unauthenticated bind, which always success
+            return false;
+        }
+
+        let digest = PasswordVerifyCache::digest(password);
+        if let Some(success) = self.cache.check(username, &digest) {
+            return success;
+        }
+
+        let success = match self.bind(username, password).await {
+            Ok(success) => success,
+            Err(e) => {
+                warn!(
+                    "failed to verify user {username} with ldap server {}: {e:?}",
+                    self.config.server
+                );
+                return false;
+            }
+        };
+        self.cache.add(username, digest, success);
+        success
+    }
+
+    async fn bind(&self, username: &str, password: &str) -> anyhow::Result<bool> {
+        let dn = self.config.user_dn(username);
+        let server = &self.config.server;
+
+        let stream = tokio::time::timeout(
+            self.config.connect_timeout,
+            TcpStream::connect((server.host_str().as_ref(), server.port())),
+        )
+        .await
+        .map_err(|_| anyhow!("timed out to connect"))?
+        .map_err(|e| anyhow!("failed to connect: {e}"))?;
+
+        match &self.tls_client {
+            Some(tls_client) => {
+                let tls_name = self
+                    .config
+                    .tls_name
+                    .clone()
+                    .unwrap_or_else(|| server.host().clone());
+                let stream = self
+                    .tls_connect(stream, tls_client, &tls_name, server.port())
+                    .await?;
+                self.bind_on(stream, &dn, password).await
+            }
+            None => self.bind_on(stream, &dn, password).await,
+        }
+    }
+
+    async fn tls_connect(
+        &self,
+        stream: TcpStream,
+        tls_client: &OpensslClientConfig,
+        tls_name: &Host,
+        port: u16,
+    ) -> anyhow::Result<g3_openssl::SslStream<TcpStream>> {
+        let ssl = tls_client.build_ssl(tls_name, port)?;
+        let connector =
+            SslConnector::new(ssl, stream).map_err(|e| anyhow!("failed to get ssl stream: {e}"))?;
+        tokio::time::timeout(tls_client.handshake_timeout, connector.connect())
+            .await
+            .map_err(|_| anyhow!("tls handshake timed out"))?
+            .map_err(|e| anyhow!("tls handshake failed: {e}"))
+    }
+
+    async fn bind_on<S>(&self, mut stream: S, dn: &str, password: &str) -> anyhow::Result<bool>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let req = protocol::encode_simple_bind(BIND_MESSAGE_ID, dn, password);
+        stream
+            .write_all(&req)
+            .await
+            .map_err(|e| anyhow!("failed to send bind request: {e}"))?;
+
+        let rsp = tokio::time::timeout(
+            self.config.response_timeout,
+            protocol::read_message(&mut stream),
+        )
+        .await
+        .map_err(|_| anyhow!("timed out to read bind response"))?
+        .context("failed to read bind response")?;
+        let rsp = protocol::BindResponse::parse(&rsp)?;
+        if rsp.message_id != BIND_MESSAGE_ID {
+            return Err(anyhow!(
+                "unexpected message id {} in bind response",
+                rsp.message_id
+            ));
+        }
+
+        let _ = stream
+            .write_all(&protocol::encode_unbind(BIND_MESSAGE_ID + 1))
+            .await;
+        let _ = stream.shutdown().await;
+
+        match rsp.result_code {
+            protocol::RESULT_SUCCESS => Ok(true),
+            protocol::RESULT_INVALID_CREDENTIALS
+            | protocol::RESULT_INSUFFICIENT_ACCESS_RIGHTS
+            | protocol::RESULT_UNWILLING_TO_PERFORM => {
+                debug!(
+                    "ldap bind as {dn} failed with result code {}: {}",
+                    rsp.result_code, rsp.diagnostic_message
+                );
+                Ok(false)
+            }
+            code => Err(anyhow!(
+                "bind failed with result code {code}: {}",
+                rsp.diagnostic_message
+            )),
+        }
+    }
+}
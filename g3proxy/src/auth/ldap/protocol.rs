@@ -0,0 +1,246 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Minimal BER encoding for the LDAP simple bind operation, see RFC 4511
+
+use anyhow::anyhow;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+const TAG_INTEGER: u8 = 0x02;
+const TAG_OCTET_STRING: u8 = 0x04;
+const TAG_ENUMERATED: u8 = 0x0a;
+const TAG_SEQUENCE: u8 = 0x30;
+const TAG_BIND_REQUEST: u8 = 0x60;
+const TAG_BIND_RESPONSE: u8 = 0x61;
+const TAG_UNBIND_REQUEST: u8 = 0x42;
+const TAG_AUTH_SIMPLE: u8 = 0x80;
+
+const LDAP_VERSION: u32 = 3;
+const MAX_MESSAGE_SIZE: usize = 16384;
+
+pub(super) const RESULT_SUCCESS: u32 = 0;
+pub(super) const RESULT_INVALID_CREDENTIALS: u32 = 49;
+pub(super) const RESULT_INSUFFICIENT_ACCESS_RIGHTS: u32 = 50;
+pub(super) const RESULT_UNWILLING_TO_PERFORM: u32 = 53;
+
+fn push_length(buf: &mut Vec<u8>, len: usize) {
+    if len < 0x80 {
+        buf.push(len as u8);
+    } else {
+        let bytes = (len as u32).to_be_bytes();
+        let skip = bytes.iter().take_while(|b| **b == 0).count();
+        buf.push(0x80 | (4 - skip) as u8);
+        buf.extend_from_slice(&bytes[skip..]);
+    }
+}
+
+fn push_tlv(buf: &mut Vec<u8>, tag: u8, value: &[u8]) {
+    buf.push(tag);
+    push_length(buf, value.len());
+    buf.extend_from_slice(value);
+}
+
+fn push_integer(buf: &mut Vec<u8>, tag: u8, v: u32) {
+    let bytes = v.to_be_bytes();
+    let skip = bytes.iter().take_while(|b| **b == 0).count().min(3);
+    let mut value = Vec::with_capacity(5);
+    if bytes[skip] & 0x80 != 0 {
+        value.push(0);
+    }
+    value.extend_from_slice(&bytes[skip..]);
+    push_tlv(buf, tag, &value);
+}
+
+fn encode_message(message_id: u32, op: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(op.len() + 8);
+    push_integer(&mut body, TAG_INTEGER, message_id);
+    body.extend_from_slice(op);
+
+    let mut buf = Vec::with_capacity(body.len() + 8);
+    push_tlv(&mut buf, TAG_SEQUENCE, &body);
+    buf
+}
+
+pub(super) fn encode_simple_bind(message_id: u32, dn: &str, password: &str) -> Vec<u8> {
+    let mut req = Vec::with_capacity(dn.len() + password.len() + 16);
+    push_integer(&mut req, TAG_INTEGER, LDAP_VERSION);
+    push_tlv(&mut req, TAG_OCTET_STRING, dn.as_bytes());
+    push_tlv(&mut req, TAG_AUTH_SIMPLE, password.as_bytes());
+
+    let mut op = Vec::with_capacity(req.len() + 8);
+    push_tlv(&mut op, TAG_BIND_REQUEST, &req);
+    encode_message(message_id, &op)
+}
+
+pub(super) fn encode_unbind(message_id: u32) -> Vec<u8> {
+    encode_message(message_id, &[TAG_UNBIND_REQUEST, 0x00])
+}
+
+struct BerReader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> BerReader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        BerReader { buf }
+    }
+
+    fn read_tlv(&mut self, expected_tag: u8) -> anyhow::Result<&'a [u8]> {
+        let Some((&tag, left)) = self.buf.split_first() else {
+            return Err(anyhow!("no enough data"));
+        };
+        if tag != expected_tag {
+            return Err(anyhow!(
+                "unexpected tag {tag:#04x}, expected {expected_tag:#04x}"
+            ));
+        }
+        let Some((&b, mut left)) = left.split_first() else {
+            return Err(anyhow!("no length found"));
+        };
+        let len = if b < 0x80 {
+            b as usize
+        } else {
+            let n = (b & 0x7f) as usize;
+            if n == 0 || n > 4 || left.len() < n {
+                return Err(anyhow!("invalid length encoding"));
+            }
+            let len = left[..n]
+                .iter()
+                .fold(0usize, |acc, v| (acc << 8) | (*v as usize));
+            left = &left[n..];
+            len
+        };
+        if left.len() < len {
+            return Err(anyhow!("no enough data for value of length {len}"));
+        }
+        let (value, left) = left.split_at(len);
+        self.buf = left;
+        Ok(value)
+    }
+
+    fn read_integer(&mut self, tag: u8) -> anyhow::Result<u32> {
+        let value = self.read_tlv(tag)?;
+        if value.is_empty() || value.len() > 5 || (value.len() == 5 && value[0] != 0) {
+            return Err(anyhow!("unsupported integer value"));
+        }
+        if value[0] & 0x80 != 0 {
+            return Err(anyhow!("negative integer value"));
+        }
+        Ok(value.iter().fold(0u32, |acc, v| (acc << 8) | (*v as u32)))
+    }
+}
+
+pub(super) struct BindResponse {
+    pub(super) message_id: u32,
+    pub(super) result_code: u32,
+    pub(super) diagnostic_message: String,
+}
+
+impl BindResponse {
+    /// parse the value of the outer LDAPMessage sequence
+    pub(super) fn parse(buf: &[u8]) -> anyhow::Result<Self> {
+        let mut reader = BerReader::new(buf);
+        let message_id = reader
+            .read_integer(TAG_INTEGER)
+            .map_err(|e| anyhow!("invalid message id: {e}"))?;
+        let op = reader
+            .read_tlv(TAG_BIND_RESPONSE)
+            .map_err(|e| anyhow!("invalid bind response: {e}"))?;
+
+        let mut reader = BerReader::new(op);
+        let result_code = reader
+            .read_integer(TAG_ENUMERATED)
+            .map_err(|e| anyhow!("invalid result code: {e}"))?;
+        let _matched_dn = reader
+            .read_tlv(TAG_OCTET_STRING)
+            .map_err(|e| anyhow!("invalid matched dn: {e}"))?;
+        let diagnostic_message = reader
+            .read_tlv(TAG_OCTET_STRING)
+            .map(|v| String::from_utf8_lossy(v).to_string())
+            .map_err(|e| anyhow!("invalid diagnostic message: {e}"))?;
+
+        Ok(BindResponse {
+            message_id,
+            result_code,
+            diagnostic_message,
+        })
+    }
+}
+
+/// read a full LDAPMessage and return the value of the outer sequence
+pub(super) async fn read_message<R>(reader: &mut R) -> anyhow::Result<Vec<u8>>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut hdr = [0u8; 2];
+    reader.read_exact(&mut hdr).await?;
+    if hdr[0] != TAG_SEQUENCE {
+        return Err(anyhow!("invalid message tag {:#04x}", hdr[0]));
+    }
+    let len = if hdr[1] < 0x80 {
+        hdr[1] as usize
+    } else {
+        let n = (hdr[1] & 0x7f) as usize;
+        if n == 0 || n > 4 {
+            return Err(anyhow!("invalid message length encoding"));
+        }
+        let mut len_buf = [0u8; 4];
+        reader.read_exact(&mut len_buf[4 - n..]).await?;
+        u32::from_be_bytes(len_buf) as usize
+    };
+    if len > MAX_MESSAGE_SIZE {
+        return Err(anyhow!("too large message size {len}"));
+    }
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf).await?;
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simple_bind() {
+        let req = encode_simple_bind(1, "uid=a,dc=b", "p");
+        assert_eq!(
+            req,
+            [
+                0x30, 0x17, 0x02, 0x01, 0x01, 0x60, 0x12, 0x02, 0x01, 0x03, 0x04, 0x0a, b'u', b'i',
+                b'd', b'=', b'a', b',', b'd', b'c', b'=', b'b', 0x80, 0x01, b'p'
+            ]
+        );
+
+        let long_password = "p".repeat(200);
+        let req = encode_simple_bind(128, "", &long_password);
+        assert_eq!(&req[..3], &[0x30, 0x81, 0xd7]);
+        assert_eq!(&req[3..7], &[0x02, 0x02, 0x00, 0x80]);
+        assert_eq!(&req[7..10], &[0x60, 0x81, 0xd0]);
+    }
+
+    #[test]
+    fn bind_response() {
+        let data = [
+            0x02, 0x01, 0x01, 0x61, 0x07, 0x0a, 0x01, 0x31, 0x04, 0x00, 0x04, 0x00,
+        ];
+        let rsp = BindResponse::parse(&data).unwrap();
+        assert_eq!(rsp.message_id, 1);
+        assert_eq!(rsp.result_code, RESULT_INVALID_CREDENTIALS);
+        assert!(rsp.diagnostic_message.is_empty());
+
+        assert!(BindResponse::parse(&data[..8]).is_err());
+    }
+}
@@ -0,0 +1,175 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use ahash::AHashMap;
+
+use g3_types::metrics::NodeName;
+
+use super::{User, UserGroup, UserSiteStats, UserTrafficStats};
+
+/// Total bytes of all kinds of client side traffic
+#[derive(Default, Clone, Copy)]
+pub(crate) struct UserLiveTraffic {
+    pub(crate) in_bytes: u64,
+    pub(crate) out_bytes: u64,
+}
+
+impl UserLiveTraffic {
+    fn add_stats(&mut self, stats: &UserTrafficStats) {
+        let io = &stats.io;
+        for tcp in [
+            &io.http_forward,
+            &io.https_forward,
+            &io.http_connect,
+            &io.ftp_over_http,
+            &io.socks_tcp_connect,
+        ] {
+            let s = tcp.snapshot();
+            self.in_bytes = self.in_bytes.wrapping_add(s.in_bytes);
+            self.out_bytes = self.out_bytes.wrapping_add(s.out_bytes);
+        }
+        for udp in [&io.socks_udp_connect, &io.socks_udp_associate] {
+            let s = udp.snapshot();
+            self.in_bytes = self.in_bytes.wrapping_add(s.in_bytes);
+            self.out_bytes = self.out_bytes.wrapping_add(s.out_bytes);
+        }
+    }
+
+    fn sub(&self, prev: &Self) -> Self {
+        UserLiveTraffic {
+            in_bytes: self.in_bytes.wrapping_sub(prev.in_bytes),
+            out_bytes: self.out_bytes.wrapping_sub(prev.out_bytes),
+        }
+    }
+
+    pub(super) fn total(&self) -> u64 {
+        self.in_bytes.saturating_add(self.out_bytes)
+    }
+}
+
+pub(crate) struct UserLiveSummary {
+    pub(crate) user: String,
+    pub(crate) alive_task_count: i32,
+    pub(crate) traffic: UserLiveTraffic,
+    pub(crate) top_sites: Vec<(NodeName, UserLiveTraffic)>,
+}
+
+struct UserLiveSample {
+    user: String,
+    handle: Arc<User>,
+    traffic: UserLiveTraffic,
+    sites: AHashMap<NodeName, UserLiveTraffic>,
+}
+
+impl UserLiveSample {
+    fn new(name: &str, user: &Arc<User>) -> Self {
+        UserLiveSample {
+            user: name.to_string(),
+            handle: user.clone(),
+            traffic: user.live_traffic(),
+            sites: user.live_site_traffic(),
+        }
+    }
+
+    fn summary(self, count: usize) -> UserLiveSummary {
+        let traffic = self.handle.live_traffic().sub(&self.traffic);
+        let mut top_sites: Vec<(NodeName, UserLiveTraffic)> = self
+            .handle
+            .live_site_traffic()
+            .into_iter()
+            .map(|(site, t)| {
+                let t = match self.sites.get(&site) {
+                    Some(prev) => t.sub(prev),
+                    None => t,
+                };
+                (site, t)
+            })
+            .filter(|(_, t)| t.total() > 0)
+            .collect();
+        top_sites.sort_by(|a, b| b.1.total().cmp(&a.1.total()));
+        top_sites.truncate(count);
+
+        UserLiveSummary {
+            user: self.user,
+            alive_task_count: self.handle.alive_task_count(),
+            traffic,
+            top_sites,
+        }
+    }
+}
+
+pub(super) fn site_live_traffic(stats: &UserSiteStats) -> UserLiveTraffic {
+    let mut traffic = UserLiveTraffic::default();
+    let map = stats.client_io.lock().unwrap();
+    for s in map.values() {
+        traffic.add_stats(s);
+    }
+    traffic
+}
+
+pub(super) fn user_live_traffic<'a, T>(all_stats: T) -> UserLiveTraffic
+where
+    T: Iterator<Item = &'a Arc<UserTrafficStats>>,
+{
+    let mut traffic = UserLiveTraffic::default();
+    for s in all_stats {
+        traffic.add_stats(s);
+    }
+    traffic
+}
+
+impl UserGroup {
+    /// Sample the traffic of users in this group for `interval`, and return the busiest ones.
+    ///
+    /// All values are computed from the stats already kept in memory for metrics.
+    pub(crate) async fn live_traffic_top(
+        &self,
+        user: Option<&str>,
+        interval: Duration,
+        count: usize,
+    ) -> Vec<UserLiveSummary> {
+        let mut samples = Vec::new();
+        match user {
+            Some(name) => {
+                if let Some((user, _)) = self.get_named_user(name) {
+                    samples.push(UserLiveSample::new(name, &user));
+                }
+            }
+            None => self.foreach_user(|name, user| {
+                samples.push(UserLiveSample::new(name, user));
+            }),
+        }
+        if samples.is_empty() {
+            return Vec::new();
+        }
+
+        tokio::time::sleep(interval).await;
+
+        let mut summaries: Vec<UserLiveSummary> =
+            samples.into_iter().map(|s| s.summary(count)).collect();
+        summaries.sort_by(|a, b| {
+            b.traffic
+                .total()
+                .cmp(&a.traffic.total())
+                .then(b.alive_task_count.cmp(&a.alive_task_count))
+        });
+        summaries.truncate(count);
+        summaries
+    }
+}
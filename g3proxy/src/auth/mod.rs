@@ -17,17 +17,27 @@
 use std::net::SocketAddr;
 use std::str::FromStr;
 use std::sync::Arc;
+#[cfg(target_os = "linux")]
+use std::time::Duration;
 
 use ahash::AHashMap;
 use anyhow::anyhow;
 use arc_swap::ArcSwap;
 use chrono::Utc;
+#[cfg(target_os = "linux")]
+use log::debug;
 use log::{info, warn};
 use tokio::sync::{mpsc, oneshot};
 
+use g3_daemon::server::ClientConnectionInfo;
+use g3_types::auth::UserAuthError;
 use g3_types::metrics::NodeName;
 
 use crate::config::auth::UserGroupConfig;
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
+
+#[cfg(target_os = "linux")]
+const LOCAL_PEER_LOOKUP_TIMEOUT: Duration = Duration::from_secs(2);
 
 mod ops;
 pub use ops::load_all;
@@ -43,6 +53,31 @@ use site::UserSites;
 mod user;
 pub(crate) use user::{User, UserContext};
 
+mod live;
+pub(crate) use live::{UserLiveSummary, UserLiveTraffic};
+
+mod verify_cache;
+
+mod ldap;
+use ldap::LdapAuthenticator;
+
+mod jwt;
+use jwt::JwtAuthenticator;
+
+#[cfg(feature = "pam")]
+mod pam;
+#[cfg(feature = "pam")]
+use pam::PamAuthenticator;
+
+#[cfg(feature = "gssapi")]
+mod kerberos;
+#[cfg(feature = "gssapi")]
+use kerberos::KerberosAuthenticator;
+
+mod quota;
+pub(crate) use quota::UserQuotaSnapshot;
+use quota::UserQuotaState;
+
 mod stats;
 pub(crate) use stats::{
     UserForbiddenSnapshot, UserForbiddenStats, UserRequestSnapshot, UserRequestStats,
@@ -52,10 +87,14 @@ pub(crate) use stats::{
 
 mod source;
 
+mod preflight;
+pub(crate) use preflight::preflight_check;
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub(crate) enum UserType {
     Static,
     Dynamic,
+    Unmanaged,
     Anonymous,
 }
 
@@ -64,6 +103,7 @@ impl UserType {
         match self {
             UserType::Static => "Static",
             UserType::Dynamic => "Dynamic",
+            UserType::Unmanaged => "Unmanaged",
             UserType::Anonymous => "Anonymous",
         }
     }
@@ -81,7 +121,16 @@ pub(crate) struct UserGroup {
     fetch_quit_sender: Option<mpsc::Sender<()>>,
     // the job for user expire check
     check_quit_sender: Option<oneshot::Sender<()>>,
+    // the job for quota sync
+    quota_quit_sender: Option<oneshot::Sender<()>>,
     anonymous_user: Option<Arc<User>>,
+    ldap: Option<Arc<LdapAuthenticator>>,
+    #[cfg(feature = "pam")]
+    pam: Option<Arc<PamAuthenticator>>,
+    unmanaged_user: Option<Arc<User>>,
+    #[cfg(feature = "gssapi")]
+    kerberos: Option<Arc<KerberosAuthenticator>>,
+    jwt: Option<Arc<JwtAuthenticator>>,
 }
 
 impl Drop for UserGroup {
@@ -89,6 +138,9 @@ impl Drop for UserGroup {
         if let Some(sender) = self.check_quit_sender.take() {
             let _ = sender.send(());
         }
+        if let Some(sender) = self.quota_quit_sender.take() {
+            let _ = sender.send(());
+        }
     }
 }
 
@@ -100,7 +152,15 @@ impl UserGroup {
             dynamic_users: Arc::new(ArcSwap::from_pointee(AHashMap::new())),
             fetch_quit_sender: None,
             check_quit_sender: None,
+            quota_quit_sender: None,
             anonymous_user: None,
+            ldap: None,
+            #[cfg(feature = "pam")]
+            pam: None,
+            unmanaged_user: None,
+            #[cfg(feature = "gssapi")]
+            kerberos: None,
+            jwt: None,
         }
     }
 
@@ -125,6 +185,33 @@ impl UserGroup {
             None => None,
         };
 
+        let ldap = match &config.ldap {
+            Some(ldap_config) => Some(Arc::new(LdapAuthenticator::new(ldap_config)?)),
+            None => None,
+        };
+        #[cfg(feature = "pam")]
+        let pam = match &config.pam {
+            Some(pam_config) => Some(Arc::new(PamAuthenticator::new(pam_config)?)),
+            None => None,
+        };
+        let unmanaged_user = match config.unmanaged_user() {
+            Some(user_config) => {
+                let user = User::new(config.name(), user_config, &datetime_now)?;
+                Some(Arc::new(user))
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "gssapi")]
+        let kerberos = match &config.kerberos {
+            Some(kerberos_config) => Some(Arc::new(KerberosAuthenticator::new(kerberos_config)?)),
+            None => None,
+        };
+        let jwt = match &config.jwt {
+            Some(jwt_config) => Some(JwtAuthenticator::new(jwt_config)?),
+            None => None,
+        };
+
         let mut group = Self::new_without_users(config);
         group.static_users = Arc::new(users);
         if let Some(source) = &group.config.dynamic_source {
@@ -147,6 +234,17 @@ impl UserGroup {
         }
 
         group.anonymous_user = anonymous_user;
+        group.ldap = ldap;
+        #[cfg(feature = "pam")]
+        {
+            group.pam = pam;
+        }
+        group.unmanaged_user = unmanaged_user;
+        #[cfg(feature = "gssapi")]
+        {
+            group.kerberos = kerberos;
+        }
+        group.jwt = jwt;
 
         group.fetch_quit_sender = Some(source::new_fetch_job(
             group.config.clone(),
@@ -157,6 +255,11 @@ impl UserGroup {
             group.static_users.clone(),
             group.dynamic_users.clone(),
         ));
+        group.quota_quit_sender = quota::new_sync_job(
+            &group.config,
+            group.static_users.clone(),
+            group.dynamic_users.clone(),
+        );
 
         Ok(Arc::new(group))
     }
@@ -185,6 +288,37 @@ impl UserGroup {
             None => None,
         };
 
+        let ldap = match &config.ldap {
+            Some(ldap_config) => Some(Arc::new(LdapAuthenticator::new(ldap_config)?)),
+            None => None,
+        };
+        #[cfg(feature = "pam")]
+        let pam = match &config.pam {
+            Some(pam_config) => Some(Arc::new(PamAuthenticator::new(pam_config)?)),
+            None => None,
+        };
+        let unmanaged_user = match config.unmanaged_user() {
+            Some(user_config) => {
+                let user = if let Some(old) = &self.unmanaged_user {
+                    old.new_for_reload(user_config, &datetime_now)?
+                } else {
+                    User::new(config.name(), user_config, &datetime_now)?
+                };
+                Some(Arc::new(user))
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "gssapi")]
+        let kerberos = match &config.kerberos {
+            Some(kerberos_config) => Some(Arc::new(KerberosAuthenticator::new(kerberos_config)?)),
+            None => None,
+        };
+        let jwt = match &config.jwt {
+            Some(jwt_config) => Some(JwtAuthenticator::new(jwt_config)?),
+            None => None,
+        };
+
         let mut dynamic_users = AHashMap::new();
         if self.config.dynamic_source.is_some() && config.dynamic_source.is_some() {
             // keep old dynamic users, even if the source may change
@@ -201,6 +335,17 @@ impl UserGroup {
         }
 
         group.anonymous_user = anonymous_user;
+        group.ldap = ldap;
+        #[cfg(feature = "pam")]
+        {
+            group.pam = pam;
+        }
+        group.unmanaged_user = unmanaged_user;
+        #[cfg(feature = "gssapi")]
+        {
+            group.kerberos = kerberos;
+        }
+        group.jwt = jwt;
 
         group.fetch_quit_sender = Some(source::new_fetch_job(
             group.config.clone(),
@@ -211,10 +356,25 @@ impl UserGroup {
             group.static_users.clone(),
             group.dynamic_users.clone(),
         ));
+        group.quota_quit_sender = quota::new_sync_job(
+            &group.config,
+            group.static_users.clone(),
+            group.dynamic_users.clone(),
+        );
 
         Ok(Arc::new(group))
     }
 
+    #[inline]
+    pub(crate) fn server_timing_header(&self) -> bool {
+        self.config.server_timing_header
+    }
+
+    #[inline]
+    pub(crate) fn http_header_rewrite(&self) -> Option<&Arc<HttpHeaderRewriteConfig>> {
+        self.config.http_header_rewrite.as_ref()
+    }
+
     #[inline]
     pub(crate) fn allow_anonymous(&self, client_addr: SocketAddr) -> bool {
         let Some(user) = &self.anonymous_user else {
@@ -230,6 +390,94 @@ impl UserGroup {
     }
 
     pub(crate) fn get_user(&self, username: &str) -> Option<(Arc<User>, UserType)> {
+        self.get_named_user(username)
+            .or_else(|| self.get_unmanaged_user())
+            .or_else(|| self.get_anonymous_user())
+    }
+
+    fn get_unmanaged_user(&self) -> Option<(Arc<User>, UserType)> {
+        self.unmanaged_user
+            .as_ref()
+            .map(|user| (user.clone(), UserType::Unmanaged))
+    }
+
+    /// check the password of the user, which will be verified by the ldap server or pam service if set
+    pub(crate) async fn check_user_password(
+        &self,
+        user_ctx: &UserContext,
+        password: &str,
+    ) -> Result<(), UserAuthError> {
+        if let Some(ldap) = &self.ldap {
+            return user_ctx.check_ldap_password(ldap, password).await;
+        }
+        #[cfg(feature = "pam")]
+        if let Some(pam) = &self.pam {
+            return user_ctx.check_pam_password(pam, password).await;
+        }
+        user_ctx.check_password(password)
+    }
+
+    #[cfg(feature = "gssapi")]
+    #[inline]
+    pub(crate) fn negotiate_enabled(&self) -> bool {
+        self.kerberos.is_some()
+    }
+
+    #[cfg(not(feature = "gssapi"))]
+    #[inline]
+    pub(crate) fn negotiate_enabled(&self) -> bool {
+        false
+    }
+
+    /// get the existing user mapped from the kerberos principal in the SPNEGO token
+    #[cfg(feature = "gssapi")]
+    pub(crate) async fn get_negotiate_user(
+        &self,
+        token: &[u8],
+    ) -> Result<(Arc<str>, Arc<User>, UserType), UserAuthError> {
+        let Some(kerberos) = &self.kerberos else {
+            return Err(UserAuthError::NoUserSupplied);
+        };
+        let Some(username) = kerberos.verify(token.to_vec()).await else {
+            return Err(UserAuthError::TokenNotMatch);
+        };
+        match self.get_named_user(&username) {
+            Some((user, user_type)) => Ok((username, user, user_type)),
+            None => Err(UserAuthError::NoSuchUser),
+        }
+    }
+
+    #[cfg(not(feature = "gssapi"))]
+    pub(crate) async fn get_negotiate_user(
+        &self,
+        _token: &[u8],
+    ) -> Result<(Arc<str>, Arc<User>, UserType), UserAuthError> {
+        Err(UserAuthError::NoUserSupplied)
+    }
+
+    #[inline]
+    pub(crate) fn bearer_enabled(&self) -> bool {
+        self.jwt.is_some()
+    }
+
+    /// get the existing user mapped from the claims in the JWT bearer token
+    pub(crate) fn get_bearer_user(
+        &self,
+        token: &str,
+    ) -> Result<(Arc<str>, Arc<User>, UserType), UserAuthError> {
+        let Some(jwt) = &self.jwt else {
+            return Err(UserAuthError::NoUserSupplied);
+        };
+        let Some(username) = jwt.verify(token) else {
+            return Err(UserAuthError::TokenNotMatch);
+        };
+        match self.get_named_user(&username) {
+            Some((user, user_type)) => Ok((username, user, user_type)),
+            None => Err(UserAuthError::NoSuchUser),
+        }
+    }
+
+    fn get_named_user(&self, username: &str) -> Option<(Arc<User>, UserType)> {
         if let Some(user) = self.static_users.get(username) {
             return Some((Arc::clone(user), UserType::Static));
         }
@@ -239,7 +487,88 @@ impl UserGroup {
             return Some((Arc::clone(user), UserType::Dynamic));
         }
 
-        self.get_anonymous_user()
+        None
+    }
+
+    /// map the client process of a unix socket or loopback tcp connection to a named user
+    pub(crate) async fn get_local_peer_user(
+        &self,
+        cc_info: &ClientConnectionInfo,
+    ) -> Option<(Arc<str>, Arc<User>, UserType)> {
+        #[cfg(target_os = "linux")]
+        {
+            let username = self.find_local_peer_username(cc_info).await?;
+            let (user, user_type) = self.get_named_user(&username)?;
+            Some((username, user, user_type))
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = cc_info;
+            None
+        }
+    }
+
+    /// the peer process is searched in /proc, which may be slow on a busy host,
+    /// so the lookup will be run in a blocking thread with a timeout
+    #[cfg(target_os = "linux")]
+    async fn find_local_peer_username(&self, cc_info: &ClientConnectionInfo) -> Option<Arc<str>> {
+        let config = self.config.local_peer_users.as_ref()?;
+
+        let peer_cred = cc_info.local_peer_cred().cloned();
+        if let Some(cred) = &peer_cred {
+            // unix socket connections, which has the credentials from SO_PEERCRED
+            if let Some(username) = config.match_uid(cred.uid()) {
+                return Some(username.clone());
+            }
+            if !config.has_cgroup_rules() {
+                return None;
+            }
+        } else if !cc_info.client_addr().ip().to_canonical().is_loopback() {
+            return None;
+        }
+
+        let client_addr = cc_info.client_addr();
+        let server_addr = cc_info.server_addr();
+        let group_config = self.config.clone();
+        let lookup = tokio::task::spawn_blocking(move || {
+            let Some(config) = &group_config.local_peer_users else {
+                return Ok(None);
+            };
+            let mut cred = match peer_cred {
+                Some(cred) => cred,
+                None => match g3_socket::local_peer::find_tcp_peer(client_addr, server_addr)? {
+                    Some(cred) => cred,
+                    None => return Ok(None),
+                },
+            };
+            config.match_peer(&mut cred)
+        });
+
+        match tokio::time::timeout(LOCAL_PEER_LOOKUP_TIMEOUT, lookup).await {
+            Ok(Ok(Ok(username))) => username,
+            Ok(Ok(Err(e))) => {
+                debug!(
+                    "user-group {}: failed to find local peer user for {client_addr}: {e}",
+                    self.config.name()
+                );
+                None
+            }
+            Ok(Err(e)) => {
+                debug!(
+                    "user-group {}: failed to join local peer lookup task for {client_addr}: {e}",
+                    self.config.name()
+                );
+                None
+            }
+            Err(_) => {
+                debug!(
+                    "user-group {}: timeout to find local peer user for {client_addr}",
+                    self.config.name()
+                );
+                None
+            }
+        }
     }
 
     fn stop_fetch_job(&self) {
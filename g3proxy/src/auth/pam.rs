@@ -0,0 +1,134 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::anyhow;
+use log::{debug, warn};
+use tokio::sync::oneshot;
+
+use super::verify_cache::PasswordVerifyCache;
+use crate::config::auth::UserPamConfig;
+
+const PAM_QUEUE_SIZE_PER_THREAD: usize = 64;
+
+struct PamRequest {
+    username: String,
+    password: String,
+    rsp_sender: oneshot::Sender<bool>,
+}
+
+pub(crate) struct PamAuthenticator {
+    config: UserPamConfig,
+    cache: PasswordVerifyCache,
+    req_sender: flume::Sender<PamRequest>,
+}
+
+impl PamAuthenticator {
+    pub(super) fn new(config: &UserPamConfig) -> anyhow::Result<Self> {
+        let (req_sender, req_receiver) =
+            flume::bounded::<PamRequest>(config.threads * PAM_QUEUE_SIZE_PER_THREAD);
+        for i in 0..config.threads {
+            let receiver = req_receiver.clone();
+            let service = config.service.clone();
+            // the threads will quit when the sender is dropped
+            let _detached_thread = std::thread::Builder::new()
+                .name(format!("pam#{i}"))
+                .spawn(move || {
+                    while let Ok(req) = receiver.recv() {
+                        if req.rsp_sender.is_closed() {
+                            // the waiting task has timed out or been cancelled
+                            continue;
+                        }
+                        let success = authenticate(&service, &req.username, &req.password);
+                        let _ = req.rsp_sender.send(success);
+                    }
+                })
+                .map_err(|e| anyhow!("failed to spawn pam thread: {e}"))?;
+        }
+
+        Ok(PamAuthenticator {
+            config: config.clone(),
+            cache: PasswordVerifyCache::new(config.cache_ttl, config.negative_cache_ttl),
+            req_sender,
+        })
+    }
+
+    /// verify the username and password in the pam threads, the result will be cached
+    pub(super) async fn verify(&self, username: &str, password: &str) -> bool {
+        if username.is_empty() || password.is_empty() {
+            return false;
+        }
+
+        let digest = PasswordVerifyCache::digest(password);
+        if let Some(success) = self.cache.check(username, &digest) {
+            return success;
+        }
+
+        let (rsp_sender, rsp_receiver) = oneshot::channel();
+        let req = PamRequest {
+            username: username.to_string(),
+            password: password.to_string(),
+            rsp_sender,
+        };
+        match self.req_sender.try_send(req) {
+            Ok(_) => {}
+            Err(flume::TrySendError::Full(_)) => {
+                warn!("too many pending pam requests, drop the one for user {username}");
+                return false;
+            }
+            Err(flume::TrySendError::Disconnected(_)) => {
+                warn!("no pam thread available to verify user {username}");
+                return false;
+            }
+        }
+
+        let success = match tokio::time::timeout(self.config.response_timeout, rsp_receiver).await {
+            Ok(Ok(success)) => success,
+            Ok(Err(_)) => {
+                warn!("pam thread quit when verifying user {username}");
+                return false;
+            }
+            Err(_) => {
+                warn!(
+                    "timed out to verify user {username} with pam service {}",
+                    self.config.service
+                );
+                return false;
+            }
+        };
+        self.cache.add(username, digest, success);
+        success
+    }
+}
+
+fn authenticate(service: &str, username: &str, password: &str) -> bool {
+    let mut client = match pam::Client::with_password(service) {
+        Ok(client) => client,
+        Err(e) => {
+            warn!("failed to start pam service {service}: {e}");
+            return false;
+        }
+    };
+    client
+        .conversation_mut()
+        .set_credentials(username, password);
+    match client.authenticate() {
+        Ok(_) => true,
+        Err(e) => {
+            debug!("pam auth failed for user {username}: {e}");
+            false
+        }
+    }
+}
@@ -0,0 +1,44 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use anyhow::anyhow;
+
+use crate::config::preflight::PreflightConfig;
+use crate::preflight::{PreflightComponent, PreflightReport};
+
+pub(crate) async fn preflight_check(config: &PreflightConfig, report: &mut PreflightReport) {
+    for group in super::get_all_groups() {
+        let Some(source) = &group.config.dynamic_source else {
+            continue;
+        };
+
+        let r = match tokio::time::timeout(
+            config.check_timeout,
+            super::source::probe_source(&group.config, source),
+        )
+        .await
+        {
+            Ok(Ok(_)) => Ok(()),
+            Ok(Err(e)) => Err(e),
+            Err(_) => Err(anyhow!("timed out to fetch users from the dynamic source")),
+        };
+        report.add(
+            PreflightComponent::UserSource,
+            group.config.name(),
+            config.user_source,
+            r,
+        );
+    }
+}
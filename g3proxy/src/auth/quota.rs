@@ -0,0 +1,269 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use arc_swap::ArcSwap;
+use chrono::{DateTime, Local};
+use log::warn;
+use redis::AsyncCommands;
+use tokio::sync::oneshot;
+
+use g3_redis_client::RedisClientConfig;
+use g3_types::metrics::NodeName;
+
+use super::{User, UserGroup};
+use crate::config::auth::{UserGroupConfig, UserQuotaConfig, UserQuotaStoreConfig};
+
+/// The quota state of a user, which will be kept across reload
+#[derive(Default)]
+pub(crate) struct UserQuotaState {
+    initialized: AtomicBool,
+    period_id: AtomicU64,
+    used_bytes: AtomicU64,
+    unsynced_bytes: AtomicU64,
+    last_total_bytes: AtomicU64,
+    traffic_exceeded: AtomicBool,
+}
+
+impl UserQuotaState {
+    /// account the traffic since last collection into the current period
+    pub(super) fn collect(&self, config: &UserQuotaConfig, now: &DateTime<Local>, total: u64) {
+        let period_id = config.traffic_period.id(now);
+        let last_total = self.last_total_bytes.swap(total, Ordering::Relaxed);
+        if !self.initialized.swap(true, Ordering::Relaxed) {
+            // the traffic before the quota is enabled should not be counted
+            self.period_id.store(period_id, Ordering::Relaxed);
+            return;
+        }
+
+        if self.period_id.swap(period_id, Ordering::Relaxed) != period_id {
+            self.used_bytes.store(0, Ordering::Relaxed);
+            self.unsynced_bytes.store(0, Ordering::Relaxed);
+        }
+        let delta = total.wrapping_sub(last_total);
+        let used = self.used_bytes.fetch_add(delta, Ordering::Relaxed) + delta;
+        self.unsynced_bytes.fetch_add(delta, Ordering::Relaxed);
+        self.update_exceeded(config, used);
+    }
+
+    fn update_exceeded(&self, config: &UserQuotaConfig, used: u64) {
+        let exceeded = config
+            .traffic_limit
+            .map(|limit| used >= limit)
+            .unwrap_or(false);
+        self.traffic_exceeded.store(exceeded, Ordering::Relaxed);
+    }
+
+    pub(super) fn check(&self, config: &UserQuotaConfig) -> bool {
+        if self.traffic_exceeded.load(Ordering::Relaxed) {
+            return false;
+        }
+        config.in_time_window(&Local::now())
+    }
+
+    pub(super) fn reset(&self) {
+        self.used_bytes.store(0, Ordering::Relaxed);
+        self.unsynced_bytes.store(0, Ordering::Relaxed);
+        self.traffic_exceeded.store(false, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(super) fn period_id(&self) -> u64 {
+        self.period_id.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(super) fn used_bytes(&self) -> u64 {
+        self.used_bytes.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    pub(super) fn traffic_exceeded(&self) -> bool {
+        self.traffic_exceeded.load(Ordering::Relaxed)
+    }
+}
+
+pub(crate) struct UserQuotaSnapshot {
+    pub(crate) user: String,
+    pub(crate) period: &'static str,
+    pub(crate) used_bytes: u64,
+    pub(crate) traffic_limit: u64,
+    pub(crate) traffic_exceeded: bool,
+    pub(crate) in_time_window: bool,
+}
+
+impl UserQuotaSnapshot {
+    fn new(username: &str, user: &User, config: &UserQuotaConfig) -> Self {
+        let state = user.quota_state();
+        UserQuotaSnapshot {
+            user: username.to_string(),
+            period: config.traffic_period.as_str(),
+            used_bytes: state.used_bytes(),
+            traffic_limit: config.traffic_limit.unwrap_or_default(),
+            traffic_exceeded: state.traffic_exceeded(),
+            in_time_window: config.in_time_window(&Local::now()),
+        }
+    }
+}
+
+struct UserQuotaStore {
+    group: NodeName,
+    config: UserQuotaStoreConfig,
+    client: RedisClientConfig,
+}
+
+impl UserQuotaStore {
+    fn new(group: &NodeName, config: &UserQuotaStoreConfig) -> anyhow::Result<Self> {
+        let client = config.client_builder.build()?;
+        Ok(UserQuotaStore {
+            group: group.clone(),
+            config: config.clone(),
+            client,
+        })
+    }
+
+    async fn sync_users(&self, users: Vec<(Arc<str>, Arc<User>)>) -> anyhow::Result<()> {
+        let mut con = self.client.connect().await?;
+
+        for (name, user) in users {
+            let Some(config) = user.quota_config() else {
+                continue;
+            };
+            let state = user.quota_state();
+            let period_id = state.period_id();
+            let key = self
+                .config
+                .counter_key(self.group.as_str(), &name, period_id);
+            let delta = state.unsynced_bytes.swap(0, Ordering::Relaxed);
+            let total: u64 = match con.incr(&key, delta).await {
+                Ok(v) => v,
+                Err(e) => {
+                    state.unsynced_bytes.fetch_add(delta, Ordering::Relaxed);
+                    return Err(anyhow!("failed to incr key {key}: {e}"));
+                }
+            };
+            let ttl = config.traffic_period.max_duration().as_secs() as i64 * 2;
+            let _: () = con
+                .expire(&key, ttl)
+                .await
+                .map_err(|e| anyhow!("failed to set expire for key {key}: {e}"))?;
+
+            if state.period_id() == period_id {
+                // the remote value contains usage from other instances
+                let unsynced = state.unsynced_bytes.load(Ordering::Relaxed);
+                let used = total.saturating_add(unsynced);
+                state.used_bytes.store(used, Ordering::Relaxed);
+                state.update_exceeded(config, used);
+            }
+        }
+        Ok(())
+    }
+
+    async fn reset_user(&self, name: &str, user: &User) -> anyhow::Result<()> {
+        let mut con = self.client.connect().await?;
+        let key =
+            self.config
+                .counter_key(self.group.as_str(), name, user.quota_state().period_id());
+        let _: () = con
+            .del(&key)
+            .await
+            .map_err(|e| anyhow!("failed to delete key {key}: {e}"))?;
+        Ok(())
+    }
+}
+
+pub(super) fn new_sync_job(
+    group_config: &UserGroupConfig,
+    static_users: Arc<AHashMap<Arc<str>, Arc<User>>>,
+    dynamic_users_container: Arc<ArcSwap<AHashMap<Arc<str>, Arc<User>>>>,
+) -> Option<oneshot::Sender<()>> {
+    use oneshot::error::TryRecvError;
+
+    let config = group_config.quota_store.as_ref()?;
+    let store = match UserQuotaStore::new(group_config.name(), config) {
+        Ok(store) => store,
+        Err(e) => {
+            warn!(
+                "user-group {}: failed to create quota store: {e:?}",
+                group_config.name()
+            );
+            return None;
+        }
+    };
+    let sync_interval = config.sync_interval;
+
+    let (quit_sender, mut quit_receiver) = oneshot::channel();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(sync_interval);
+        interval.tick().await; // will tick immediately
+        loop {
+            interval.tick().await;
+
+            match quit_receiver.try_recv() {
+                Ok(_) => break,
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Closed) => break,
+            }
+
+            let mut users = Vec::new();
+            let dynamic_users = dynamic_users_container.load();
+            for (name, user) in static_users.iter().chain(dynamic_users.iter()) {
+                if user.quota_config().is_some() {
+                    users.push((name.clone(), user.clone()));
+                }
+            }
+            drop(dynamic_users);
+            if let Err(e) = store.sync_users(users).await {
+                warn!("user-group {}: quota sync failed: {e:?}", store.group);
+            }
+        }
+    });
+    Some(quit_sender)
+}
+
+impl UserGroup {
+    pub(crate) fn quota_snapshot(&self, user: Option<&str>) -> Vec<UserQuotaSnapshot> {
+        let mut all = Vec::new();
+        self.foreach_user(|name, u| {
+            if user.map(|v| v != name).unwrap_or(false) {
+                return;
+            }
+            if let Some(config) = u.quota_config() {
+                all.push(UserQuotaSnapshot::new(name, u, config));
+            }
+        });
+        all
+    }
+
+    pub(crate) async fn reset_quota(&self, user: &str) -> anyhow::Result<()> {
+        let Some((u, _)) = self.get_named_user(user) else {
+            return Err(anyhow!("no user {user} found"));
+        };
+        if u.quota_config().is_none() {
+            return Err(anyhow!("no quota set for user {user}"));
+        }
+        if let Some(config) = &self.config.quota_store {
+            let store = UserQuotaStore::new(self.config.name(), config)?;
+            store.reset_user(user, &u).await?;
+        }
+        u.quota_state().reset();
+        Ok(())
+    }
+}
@@ -24,13 +24,16 @@ use arc_swap::ArcSwapOption;
 use ip_network_table::IpNetworkTable;
 use radix_trie::Trie;
 
+use g3_resolver::{ResolveError, ResolveLocalError};
 use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::net::{Host, OpensslClientConfig, UpstreamAddr};
 use g3_types::resolve::ResolveStrategy;
+use g3_types::route::HostPatternMatch;
 
 use super::stats::{UserSiteDurationRecorder, UserSiteStats};
-use super::{UserSiteDurationStats, UserType};
+use super::{UserLiveTraffic, UserSiteDurationStats, UserType};
 use crate::config::auth::UserSiteConfig;
+use crate::resolve::ArcIntegratedResolverHandle;
 
 struct DurationValue {
     recorder: Arc<UserSiteDurationRecorder>,
@@ -43,6 +46,7 @@ pub(crate) struct UserSite {
     stats: Arc<UserSiteStats>,
     duration_recorder: Arc<Mutex<AHashMap<String, DurationValue>>>,
     tls_client: Option<OpensslClientConfig>,
+    resolver_handle: Mutex<Option<ArcIntegratedResolverHandle>>,
 }
 
 impl UserSite {
@@ -62,9 +66,15 @@ impl UserSite {
         };
         Ok(UserSite {
             config: Arc::clone(config),
-            stats: Arc::new(UserSiteStats::new(user, user_group, &config.id)),
+            stats: Arc::new(UserSiteStats::new(
+                user,
+                user_group,
+                &config.id,
+                config.request_tags.clone(),
+            )),
             duration_recorder: Arc::new(Mutex::new(AHashMap::new())),
             tls_client,
+            resolver_handle: Mutex::new(None),
         })
     }
 
@@ -78,12 +88,14 @@ impl UserSite {
             }
             None => None,
         };
+        self.stats.set_request_tags(config.request_tags.clone());
         let site = if self.config.duration_stats != config.duration_stats {
             UserSite {
                 config: Arc::clone(config),
                 stats: self.stats.clone(),
                 duration_recorder: Arc::new(Mutex::new(AHashMap::new())),
                 tls_client,
+                resolver_handle: Mutex::new(None),
             }
         } else {
             UserSite {
@@ -91,6 +103,7 @@ impl UserSite {
                 stats: self.stats.clone(),
                 duration_recorder: self.duration_recorder.clone(),
                 tls_client,
+                resolver_handle: Mutex::new(None),
             }
         };
         Ok(site)
@@ -111,11 +124,41 @@ impl UserSite {
         self.config.resolve_strategy
     }
 
+    /// get the handle of the resolver set for this site, `None` means no resolver is set
+    pub(super) fn resolver_handle(
+        &self,
+    ) -> Option<Result<ArcIntegratedResolverHandle, ResolveError>> {
+        let name = self.config.resolver.as_ref()?;
+
+        let mut cache = self.resolver_handle.lock().unwrap();
+        if let Some(handle) = cache.as_ref() {
+            if !handle.is_closed() {
+                return Some(Ok(handle.clone()));
+            }
+        }
+        // the resolver may be respawned or not yet loaded, always fetch the latest one
+        match crate::resolve::get_handle(name) {
+            Ok(handle) => {
+                *cache = Some(handle.clone());
+                Some(Ok(handle))
+            }
+            Err(_) => {
+                *cache = None;
+                Some(Err(ResolveLocalError::NoResolverRunning.into()))
+            }
+        }
+    }
+
     #[inline]
     pub(crate) fn tls_client(&self) -> Option<&OpensslClientConfig> {
         self.tls_client.as_ref()
     }
 
+    #[inline]
+    pub(crate) fn request_tags(&self) -> Option<&Arc<StaticMetricsTags>> {
+        self.config.request_tags.as_ref()
+    }
+
     #[inline]
     pub(crate) fn http_rsp_hdr_recv_timeout(&self) -> Option<Duration> {
         self.config.http_rsp_hdr_recv_timeout
@@ -152,7 +195,11 @@ impl UserSite {
         drop(map);
 
         if let Some(stats) = new_stats {
-            crate::stat::user_site::push_duration_stats(stats, &self.config.id);
+            crate::stat::user_site::push_duration_stats(
+                stats,
+                &self.config.id,
+                self.stats.request_tags(),
+            );
         }
 
         recorder
@@ -165,6 +212,7 @@ pub(super) struct UserSites {
     exact_match_ipaddr: Option<AHashMap<IpAddr, Arc<UserSite>>>,
     exact_match_domain: Option<AHashMap<Arc<str>, Arc<UserSite>>>,
     child_match_domain: Option<Trie<String, Arc<UserSite>>>,
+    pattern_match_domain: Option<HostPatternMatch<Arc<UserSite>>>,
     subnet_match_ipaddr: Option<IpNetworkTable<Arc<UserSite>>>,
 }
 
@@ -181,6 +229,7 @@ impl UserSites {
         let mut exact_match_domain = AHashMap::new();
         let mut child_match_domain = Trie::new();
         let mut child_match_domain_count = 0usize;
+        let mut pattern_match_domain = HostPatternMatch::default();
         let mut subnet_match_ipaddr = IpNetworkTable::new();
 
         for site_config in sites {
@@ -201,6 +250,9 @@ impl UserSites {
                     child_match_domain_count += 1;
                 }
             }
+            for pattern in &site_config.pattern_match_domain {
+                pattern_match_domain.add(pattern.clone(), site.clone());
+            }
             for net in &site_config.subnet_match_ipaddr {
                 subnet_match_ipaddr.insert(*net, site.clone());
             }
@@ -221,6 +273,11 @@ impl UserSites {
         } else {
             None
         };
+        let pattern_match_domain = if pattern_match_domain.is_empty() {
+            None
+        } else {
+            Some(pattern_match_domain)
+        };
         let subnet_match_ipaddr = if subnet_match_ipaddr.is_empty() {
             None
         } else {
@@ -232,6 +289,7 @@ impl UserSites {
             exact_match_ipaddr,
             exact_match_domain,
             child_match_domain,
+            pattern_match_domain,
             subnet_match_ipaddr,
         })
     }
@@ -261,6 +319,14 @@ impl UserSites {
         })
     }
 
+    pub(super) fn live_traffic(&self) -> AHashMap<NodeName, UserLiveTraffic> {
+        self.all_sites
+            .iter()
+            .filter(|(_, site)| site.emit_stats())
+            .map(|(id, site)| (id.clone(), super::live::site_live_traffic(&site.stats)))
+            .collect()
+    }
+
     pub(super) fn fetch_site(&self, ups: &UpstreamAddr) -> Option<Arc<UserSite>> {
         match ups.host() {
             Host::Ip(ip) => {
@@ -289,6 +355,12 @@ impl UserSites {
                         return Some(r.clone());
                     }
                 }
+
+                if let Some(pm) = &self.pattern_match_domain {
+                    if let Some(r) = pm.get(domain) {
+                        return Some(r.clone());
+                    }
+                }
             }
         }
 
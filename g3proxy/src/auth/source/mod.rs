@@ -19,7 +19,7 @@ use std::time::Duration;
 
 use ahash::AHashMap;
 use arc_swap::ArcSwap;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, Utc};
 use log::warn;
 use tokio::sync::{mpsc, oneshot};
 
@@ -64,6 +64,25 @@ pub(super) async fn load_initial_users(
     Ok(dynamic_users)
 }
 
+/// Fetch from the source directly, without publishing the records
+pub(super) async fn probe_source(
+    group_config: &UserGroupConfig,
+    source: &UserDynamicSource,
+) -> anyhow::Result<usize> {
+    let r = match source {
+        UserDynamicSource::File(config) => config.fetch_records().await?,
+        #[cfg(feature = "lua")]
+        UserDynamicSource::Lua(config) => {
+            lua::fetch_records(config, &group_config.dynamic_cache).await?
+        }
+        #[cfg(feature = "python")]
+        UserDynamicSource::Python(config) => {
+            python::fetch_records(config, &group_config.dynamic_cache).await?
+        }
+    };
+    Ok(r.len())
+}
+
 pub(super) fn new_fetch_job(
     group_config: Arc<UserGroupConfig>,
     dynamic_users_container: Arc<ArcSwap<AHashMap<Arc<str>, Arc<User>>>>,
@@ -179,9 +198,11 @@ fn check_dynamic_users(
     datetime_now: &DateTime<Utc>,
     dynamic_users_container: &Arc<ArcSwap<AHashMap<Arc<str>, Arc<User>>>>,
 ) {
+    let local_now = datetime_now.with_timezone(&Local);
     let old_dynamic_users = dynamic_users_container.load();
     for (_, user) in old_dynamic_users.iter() {
         user.check_expired(datetime_now);
+        user.collect_quota(&local_now);
     }
 }
 
@@ -189,7 +210,9 @@ fn check_static_users(
     datetime_now: &DateTime<Utc>,
     static_users: &Arc<AHashMap<Arc<str>, Arc<User>>>,
 ) {
+    let local_now = datetime_now.with_timezone(&Local);
     for (_, user) in static_users.iter() {
         user.check_expired(datetime_now);
+        user.collect_quota(&local_now);
     }
 }
@@ -28,17 +28,24 @@ pub(crate) struct UserSiteStats {
     user: Arc<str>,
     user_group: NodeName,
     site_id: NodeName,
+    request_tags: Arc<ArcSwapOption<StaticMetricsTags>>,
     pub(crate) request: Mutex<AHashMap<String, Arc<UserRequestStats>>>,
     pub(crate) client_io: Mutex<AHashMap<String, Arc<UserTrafficStats>>>,
     pub(crate) remote_io: Mutex<AHashMap<String, Arc<UserUpstreamTrafficStats>>>,
 }
 
 impl UserSiteStats {
-    pub(crate) fn new(user: Arc<str>, user_group: &NodeName, site_id: &NodeName) -> Self {
+    pub(crate) fn new(
+        user: Arc<str>,
+        user_group: &NodeName,
+        site_id: &NodeName,
+        request_tags: Option<Arc<StaticMetricsTags>>,
+    ) -> Self {
         UserSiteStats {
             user,
             user_group: user_group.clone(),
             site_id: site_id.clone(),
+            request_tags: Arc::new(ArcSwapOption::new(request_tags)),
             request: Mutex::new(AHashMap::new()),
             client_io: Mutex::new(AHashMap::new()),
             remote_io: Mutex::new(AHashMap::new()),
@@ -55,6 +62,15 @@ impl UserSiteStats {
         &self.user
     }
 
+    #[inline]
+    pub(crate) fn request_tags(&self) -> &Arc<ArcSwapOption<StaticMetricsTags>> {
+        &self.request_tags
+    }
+
+    pub(crate) fn set_request_tags(&self, request_tags: Option<Arc<StaticMetricsTags>>) {
+        self.request_tags.store(request_tags);
+    }
+
     pub(crate) fn fetch_request_stats(
         &self,
         user_type: UserType,
@@ -81,7 +97,7 @@ impl UserSiteStats {
         drop(map);
 
         if let Some(stats) = new_stats {
-            crate::stat::user_site::push_request_stats(stats, &self.site_id);
+            crate::stat::user_site::push_request_stats(stats, &self.site_id, &self.request_tags);
         }
 
         stats
@@ -113,7 +129,7 @@ impl UserSiteStats {
         drop(map);
 
         if let Some(stats) = new_stats {
-            crate::stat::user_site::push_traffic_stats(stats, &self.site_id);
+            crate::stat::user_site::push_traffic_stats(stats, &self.site_id, &self.request_tags);
         }
 
         stats
@@ -145,7 +161,11 @@ impl UserSiteStats {
         drop(map);
 
         if let Some(stats) = new_stats {
-            crate::stat::user_site::push_upstream_traffic_stats(stats, &self.site_id);
+            crate::stat::user_site::push_upstream_traffic_stats(
+                stats,
+                &self.site_id,
+                &self.request_tags,
+            );
         }
 
         stats
@@ -22,12 +22,13 @@ use std::time::Duration;
 use ahash::AHashMap;
 use anyhow::Context;
 use arc_swap::ArcSwapOption;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, Utc};
 use governor::{clock::DefaultClock, state::InMemoryState, state::NotKeyed, RateLimiter};
 use tokio::time::Instant;
 
 use g3_io_ext::{GlobalDatagramLimiter, GlobalLimitGroup, GlobalStreamLimiter};
-use g3_types::acl::{AclAction, AclNetworkRule};
+use g3_resolver::ResolveError;
+use g3_types::acl::{AclAction, AclChildDomainRule, AclNetworkRule};
 use g3_types::acl_set::AclDstHostRuleSet;
 use g3_types::auth::UserAuthError;
 use g3_types::limit::{GaugeSemaphore, GaugeSemaphorePermit};
@@ -35,11 +36,16 @@ use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::net::{HttpHeaderMap, ProxyRequestType, UpstreamAddr};
 use g3_types::resolve::{ResolveRedirection, ResolveStrategy};
 
+#[cfg(feature = "pam")]
+use super::PamAuthenticator;
 use super::{
-    UserForbiddenStats, UserRequestStats, UserSite, UserSiteDurationRecorder, UserSiteStats,
-    UserSites, UserTrafficStats, UserType, UserUpstreamTrafficStats,
+    LdapAuthenticator, UserForbiddenStats, UserLiveTraffic, UserQuotaState, UserRequestStats,
+    UserSite, UserSiteDurationRecorder, UserSiteStats, UserSites, UserTrafficStats, UserType,
+    UserUpstreamTrafficStats,
 };
-use crate::config::auth::{UserAuditConfig, UserConfig};
+use crate::config::auth::{UserAuditConfig, UserConfig, UserQuotaConfig};
+use crate::config::idle::TaskIdlePolicy;
+use crate::resolve::ArcIntegratedResolverHandle;
 
 pub(crate) struct User {
     config: Arc<UserConfig>,
@@ -56,12 +62,14 @@ pub(crate) struct User {
     ingress_net_filter: Option<Arc<AclNetworkRule>>,
     dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
     resolve_redirection: Option<ResolveRedirection>,
+    resolve_rebind_allow: Option<AclChildDomainRule>,
     log_rate_limit: Option<Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>>,
     forbid_stats: Arc<Mutex<AHashMap<String, Arc<UserForbiddenStats>>>>,
     req_stats: Arc<Mutex<AHashMap<String, Arc<UserRequestStats>>>>,
     io_stats: Arc<Mutex<AHashMap<String, Arc<UserTrafficStats>>>>,
     upstream_io_stats: Arc<Mutex<AHashMap<String, Arc<UserUpstreamTrafficStats>>>>,
     req_alive_sem: GaugeSemaphore,
+    quota_state: Arc<UserQuotaState>,
     explicit_sites: UserSites,
 }
 
@@ -71,6 +79,11 @@ impl User {
         self.config.task_idle_max_count
     }
 
+    #[inline]
+    pub(crate) fn task_idle_policy(&self) -> &TaskIdlePolicy {
+        &self.config.task_idle_policy
+    }
+
     fn update_ingress_net_filter(&mut self) {
         self.ingress_net_filter = self
             .config
@@ -95,6 +108,14 @@ impl User {
             .map(|builder| builder.build());
     }
 
+    fn update_resolve_rebind_allow(&mut self) {
+        self.resolve_rebind_allow = self
+            .config
+            .resolve_rebind_allow
+            .as_ref()
+            .map(|builder| builder.build());
+    }
+
     pub(super) fn new(
         group: &NodeName,
         config: &Arc<UserConfig>,
@@ -165,17 +186,20 @@ impl User {
             ingress_net_filter: None,
             dst_host_filter: None,
             resolve_redirection: None,
+            resolve_rebind_allow: None,
             log_rate_limit,
             forbid_stats: Arc::new(Mutex::new(AHashMap::new())),
             req_stats: Arc::new(Mutex::new(AHashMap::new())),
             io_stats: Arc::new(Mutex::new(AHashMap::new())),
             upstream_io_stats: Arc::new(Mutex::new(AHashMap::new())),
             req_alive_sem: GaugeSemaphore::new(config.request_alive_max),
+            quota_state: Arc::new(UserQuotaState::default()),
             explicit_sites,
         };
         user.update_ingress_net_filter();
         user.update_dst_host_filter();
         user.update_resolve_redirection();
+        user.update_resolve_rebind_allow();
         Ok(user)
     }
 
@@ -323,12 +347,14 @@ impl User {
             ingress_net_filter: None,
             dst_host_filter: None,
             resolve_redirection: None,
+            resolve_rebind_allow: None,
             log_rate_limit,
             forbid_stats: Arc::clone(&self.forbid_stats),
             req_stats: Arc::clone(&self.req_stats),
             io_stats: Arc::clone(&self.io_stats),
             upstream_io_stats: Arc::clone(&self.upstream_io_stats),
             req_alive_sem: self.req_alive_sem.new_updated(config.request_alive_max),
+            quota_state: Arc::clone(&self.quota_state),
             explicit_sites,
         };
         if self
@@ -346,6 +372,7 @@ impl User {
             user.dst_host_filter.clone_from(&self.dst_host_filter);
         }
         user.update_resolve_redirection();
+        user.update_resolve_rebind_allow();
         Ok(user)
     }
 
@@ -407,10 +434,22 @@ impl User {
         password: &str,
         forbid_stats: &Arc<UserForbiddenStats>,
     ) -> Result<(), UserAuthError> {
-        if !self.config.check_password(password) {
+        self.check_verified(self.config.check_password(password), forbid_stats)
+    }
+
+    fn check_verified(
+        &self,
+        verified: bool,
+        forbid_stats: &Arc<UserForbiddenStats>,
+    ) -> Result<(), UserAuthError> {
+        if !verified {
             forbid_stats.add_auth_failed();
             return Err(UserAuthError::TokenNotMatch);
         }
+        self.check_usable(forbid_stats)
+    }
+
+    fn check_usable(&self, forbid_stats: &Arc<UserForbiddenStats>) -> Result<(), UserAuthError> {
         if self.is_expired() {
             forbid_stats.add_user_expired();
             return Err(UserAuthError::ExpiredUser);
@@ -419,9 +458,32 @@ impl User {
             forbid_stats.add_user_blocked();
             return Err(UserAuthError::BlockedUser(duration));
         }
+        if let Some(quota) = &self.config.quota {
+            if !self.quota_state.check(quota) {
+                forbid_stats.add_user_blocked();
+                return Err(UserAuthError::BlockedUser(quota.block_delay));
+            }
+        }
         Ok(())
     }
 
+    #[inline]
+    pub(super) fn quota_config(&self) -> Option<&UserQuotaConfig> {
+        self.config.quota.as_ref()
+    }
+
+    #[inline]
+    pub(super) fn quota_state(&self) -> &Arc<UserQuotaState> {
+        &self.quota_state
+    }
+
+    pub(super) fn collect_quota(&self, datetime_now: &DateTime<Local>) {
+        if let Some(quota) = &self.config.quota {
+            let total = self.live_traffic().total();
+            self.quota_state.collect(quota, datetime_now, total);
+        }
+    }
+
     fn fetch_forbidden_stats(
         &self,
         user_type: UserType,
@@ -525,6 +587,20 @@ impl User {
         Arc::clone(stats)
     }
 
+    pub(super) fn alive_task_count(&self) -> i32 {
+        let map = self.req_stats.lock().unwrap();
+        map.values().map(|s| s.req_alive.total()).sum()
+    }
+
+    pub(super) fn live_traffic(&self) -> UserLiveTraffic {
+        let map = self.io_stats.lock().unwrap();
+        super::live::user_live_traffic(map.values())
+    }
+
+    pub(super) fn live_site_traffic(&self) -> AHashMap<NodeName, UserLiveTraffic> {
+        self.explicit_sites.live_traffic()
+    }
+
     pub(crate) fn all_upstream_traffic_stats(&self) -> Vec<Arc<UserUpstreamTrafficStats>> {
         let map = self.upstream_io_stats.lock().unwrap();
         let mut all_stats = Vec::with_capacity(map.len());
@@ -650,11 +726,23 @@ impl User {
         self.resolve_redirection.as_ref()
     }
 
+    pub(crate) fn resolve_rebind_allowed(&self, domain: &str) -> bool {
+        self.resolve_rebind_allow
+            .as_ref()
+            .map(|rule| matches!(rule.check(domain), (true, AclAction::Permit)))
+            .unwrap_or(false)
+    }
+
     #[inline]
     pub(crate) fn http_rsp_hdr_recv_timeout(&self) -> Option<Duration> {
         self.config.http_rsp_hdr_recv_timeout
     }
 
+    #[inline]
+    pub(crate) fn name(&self) -> &Arc<str> {
+        self.config.name()
+    }
+
     pub(crate) fn audit(&self) -> &UserAuditConfig {
         &self.config.audit
     }
@@ -722,6 +810,43 @@ impl UserContext {
         }
     }
 
+    /// Create a user context with stats that are not registered to the user,
+    /// so checks done through it will not show up in metrics.
+    pub(crate) fn new_detached(
+        raw_user_name: Option<Arc<str>>,
+        user: Arc<User>,
+        user_type: UserType,
+        server: &NodeName,
+        server_extra_tags: &Arc<ArcSwapOption<StaticMetricsTags>>,
+    ) -> Self {
+        let forbid_stats = Arc::new(UserForbiddenStats::new(
+            &user.group,
+            user.config.name().clone(),
+            user_type,
+            server,
+            server_extra_tags,
+        ));
+        let req_stats = Arc::new(UserRequestStats::new(
+            &user.group,
+            user.config.name().clone(),
+            user_type,
+            server,
+            server_extra_tags,
+        ));
+        UserContext {
+            raw_user_name,
+            user,
+            user_type,
+            user_site: None,
+            forbid_stats,
+            req_stats,
+            site_stats: None,
+            site_req_stats: None,
+            site_duration_recorder: None,
+            reused_client_connection: false,
+        }
+    }
+
     pub(crate) fn mark_reused_client_connection(&mut self) {
         self.reused_client_connection = true;
     }
@@ -777,6 +902,11 @@ impl UserContext {
         self.user_site.as_ref()
     }
 
+    #[inline]
+    pub(crate) fn request_tags(&self) -> Option<&Arc<StaticMetricsTags>> {
+        self.user_site.as_ref().and_then(|s| s.request_tags())
+    }
+
     pub(crate) fn resolve_strategy(&self) -> Option<ResolveStrategy> {
         self.user_site
             .as_ref()
@@ -784,6 +914,12 @@ impl UserContext {
             .or(self.user.config.resolve_strategy)
     }
 
+    pub(crate) fn resolver_handle(
+        &self,
+    ) -> Option<Result<ArcIntegratedResolverHandle, ResolveError>> {
+        self.user_site.as_ref().and_then(|s| s.resolver_handle())
+    }
+
     #[inline]
     pub(crate) fn forbidden_stats(&self) -> &Arc<UserForbiddenStats> {
         &self.forbid_stats
@@ -873,6 +1009,53 @@ impl UserContext {
         self.user.check_password(password, &self.forbid_stats)
     }
 
+    /// check the password by bind to the ldap server, the anonymous user will still be checked locally
+    pub(super) async fn check_ldap_password(
+        &self,
+        ldap: &LdapAuthenticator,
+        password: &str,
+    ) -> Result<(), UserAuthError> {
+        let Some(username) = self
+            .raw_user_name
+            .as_ref()
+            .filter(|_| !self.user_type.is_anonymous())
+        else {
+            return self.check_password(password);
+        };
+        let verified = ldap.verify(username, password).await;
+        self.user.check_verified(verified, &self.forbid_stats)
+    }
+
+    /// check the password by the pam service, the anonymous user will still be checked locally
+    #[cfg(feature = "pam")]
+    pub(super) async fn check_pam_password(
+        &self,
+        pam: &PamAuthenticator,
+        password: &str,
+    ) -> Result<(), UserAuthError> {
+        let Some(username) = self
+            .raw_user_name
+            .as_ref()
+            .filter(|_| !self.user_type.is_anonymous())
+        else {
+            return self.check_password(password);
+        };
+        let verified = pam.verify(username, password).await;
+        self.user.check_verified(verified, &self.forbid_stats)
+    }
+
+    /// check for users that are classified by network metadata, no password is needed
+    #[inline]
+    pub(crate) fn check_usable(&self) -> Result<(), UserAuthError> {
+        self.user.check_usable(&self.forbid_stats)
+    }
+
+    /// check for users that are mapped from local peer credentials, no password is needed
+    #[inline]
+    pub(crate) fn check_local_peer(&self) -> Result<(), UserAuthError> {
+        self.user.check_usable(&self.forbid_stats)
+    }
+
     #[inline]
     pub(crate) fn skip_log(&self) -> bool {
         self.user.skip_log(&self.forbid_stats)
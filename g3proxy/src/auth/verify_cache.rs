@@ -0,0 +1,85 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use ahash::AHashMap;
+
+const CACHE_PRUNE_SIZE: usize = 4096;
+
+struct VerifyCacheEntry {
+    digest: [u8; 32],
+    success: bool,
+    expire: Instant,
+}
+
+/// cache for password verify results of external authenticators,
+/// only the digest of the password will be saved
+pub(super) struct PasswordVerifyCache {
+    cache_ttl: Duration,
+    negative_cache_ttl: Duration,
+    inner: Mutex<AHashMap<String, VerifyCacheEntry>>,
+}
+
+impl PasswordVerifyCache {
+    pub(super) fn new(cache_ttl: Duration, negative_cache_ttl: Duration) -> Self {
+        PasswordVerifyCache {
+            cache_ttl,
+            negative_cache_ttl,
+            inner: Mutex::new(AHashMap::new()),
+        }
+    }
+
+    pub(super) fn digest(password: &str) -> [u8; 32] {
+        openssl::sha::sha256(password.as_bytes())
+    }
+
+    pub(super) fn check(&self, username: &str, digest: &[u8; 32]) -> Option<bool> {
+        let cache = self.inner.lock().unwrap();
+        let entry = cache.get(username)?;
+        if entry.expire > Instant::now() && entry.digest.eq(digest) {
+            Some(entry.success)
+        } else {
+            None
+        }
+    }
+
+    pub(super) fn add(&self, username: &str, digest: [u8; 32], success: bool) {
+        let ttl = if success {
+            self.cache_ttl
+        } else {
+            self.negative_cache_ttl
+        };
+        if ttl.is_zero() {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut cache = self.inner.lock().unwrap();
+        if cache.len() >= CACHE_PRUNE_SIZE {
+            cache.retain(|_, entry| entry.expire > now);
+        }
+        cache.insert(
+            username.to_string(),
+            VerifyCacheEntry {
+                digest,
+                success,
+                expire: now + ttl,
+            },
+        );
+    }
+}
@@ -0,0 +1,110 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use rustix::process::Resource;
+
+use super::CapacitySnapshot;
+
+const TCP_STATE_LISTEN: &str = "0A";
+
+pub(super) fn sample() -> CapacitySnapshot {
+    let mut snapshot = CapacitySnapshot::default();
+
+    // the fd used to read the dir itself is also counted
+    if let Ok(dir) = fs::read_dir("/proc/self/fd") {
+        snapshot.fd_used = dir.count().saturating_sub(1) as u64;
+    }
+    snapshot.fd_limit = rustix::process::getrlimit(Resource::Nofile)
+        .current
+        .unwrap_or_default();
+
+    if let Some((start, end)) = read_port_range() {
+        snapshot.port_range_size = (end - start + 1) as u64;
+        let mut port_used = HashMap::new();
+        for file in ["/proc/net/tcp", "/proc/net/tcp6"] {
+            let Ok(content) = fs::read_to_string(file) else {
+                continue;
+            };
+            for line in content.lines().skip(1) {
+                let Some((ip, port)) = parse_tcp_entry(line) else {
+                    continue;
+                };
+                if (start..=end).contains(&port) {
+                    *port_used.entry(ip).or_insert(0u64) += 1;
+                }
+            }
+        }
+        let mut port_used: Vec<(IpAddr, u64)> = port_used.into_iter().collect();
+        port_used.sort_unstable();
+        snapshot.port_used = port_used;
+    }
+
+    snapshot.flow_count = read_u64("/proc/sys/net/netfilter/nf_conntrack_count");
+    snapshot.flow_max = read_u64("/proc/sys/net/netfilter/nf_conntrack_max");
+
+    snapshot
+}
+
+fn read_u64(path: &str) -> Option<u64> {
+    let content = fs::read_to_string(path).ok()?;
+    content.trim().parse().ok()
+}
+
+fn read_port_range() -> Option<(u16, u16)> {
+    let content = fs::read_to_string("/proc/sys/net/ipv4/ip_local_port_range").ok()?;
+    let mut iter = content.split_whitespace();
+    let start = iter.next()?.parse().ok()?;
+    let end = iter.next()?.parse().ok()?;
+    if start > end {
+        return None;
+    }
+    Some((start, end))
+}
+
+/// Get the local address of the non-listening sockets in /proc/net/tcp{,6}
+fn parse_tcp_entry(line: &str) -> Option<(IpAddr, u16)> {
+    let mut iter = line.split_whitespace();
+    let _sl = iter.next()?;
+    let local = iter.next()?;
+    let _remote = iter.next()?;
+    if iter.next()? == TCP_STATE_LISTEN {
+        return None;
+    }
+
+    let (ip, port) = local.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    // the address is printed as 32-bit words in host byte order
+    let ip = match ip.len() {
+        8 => {
+            let word = u32::from_str_radix(ip, 16).ok()?;
+            IpAddr::V4(Ipv4Addr::from(word.to_ne_bytes()))
+        }
+        32 => {
+            let mut octets = [0u8; 16];
+            for (i, chunk) in octets.chunks_mut(4).enumerate() {
+                let word = u32::from_str_radix(&ip[i * 8..i * 8 + 8], 16).ok()?;
+                chunk.copy_from_slice(&word.to_ne_bytes());
+            }
+            Ipv6Addr::from(octets).to_canonical()
+        }
+        _ => return None,
+    };
+    Some((ip, port))
+}
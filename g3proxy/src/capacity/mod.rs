@@ -0,0 +1,141 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use arc_swap::ArcSwapOption;
+use log::{info, warn};
+
+use crate::config::capacity::CapacityConfig;
+
+#[cfg(target_os = "linux")]
+mod linux;
+
+static CAPACITY_SNAPSHOT: ArcSwapOption<CapacitySnapshot> = ArcSwapOption::const_empty();
+static SHED_NEW_CONNECTION: AtomicBool = AtomicBool::new(false);
+static SHED_CONNECTION_TOTAL: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Default)]
+pub(crate) struct CapacitySnapshot {
+    pub(crate) fd_used: u64,
+    pub(crate) fd_limit: u64,
+    /// the size of the local ephemeral port range
+    pub(crate) port_range_size: u64,
+    /// the number of sockets that use an ephemeral port, for each local ip
+    pub(crate) port_used: Vec<(IpAddr, u64)>,
+    pub(crate) flow_count: Option<u64>,
+    pub(crate) flow_max: Option<u64>,
+}
+
+impl CapacitySnapshot {
+    #[cfg(target_os = "linux")]
+    fn sample() -> Self {
+        linux::sample()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn sample() -> Self {
+        CapacitySnapshot::default()
+    }
+
+    /// Get the max usage ratio and the name of the corresponding resource
+    fn max_usage(&self) -> (f64, String) {
+        let mut max = (0.0, String::new());
+        let mut update = |used: u64, limit: u64, name: &dyn Fn() -> String| {
+            if limit == 0 {
+                return;
+            }
+            let ratio = used as f64 / limit as f64;
+            if ratio > max.0 {
+                max = (ratio, name());
+            }
+        };
+
+        update(self.fd_used, self.fd_limit, &|| "fd".to_string());
+        for (ip, used) in &self.port_used {
+            update(*used, self.port_range_size, &|| format!("ports of {ip}"));
+        }
+        if let (Some(count), Some(limit)) = (self.flow_count, self.flow_max) {
+            update(count, limit, &|| "flows".to_string());
+        }
+        max
+    }
+}
+
+/// Get the latest snapshot, `None` if the monitor is not running
+pub(crate) fn snapshot() -> Option<Arc<CapacitySnapshot>> {
+    CAPACITY_SNAPSHOT.load_full()
+}
+
+pub(crate) fn shed_connection_total() -> u64 {
+    SHED_CONNECTION_TOTAL.load(Ordering::Relaxed)
+}
+
+/// Check if the new client connection should be shed, as some resources are near exhaustion
+pub(crate) fn check_shed_connection() -> bool {
+    if SHED_NEW_CONNECTION.load(Ordering::Relaxed) {
+        SHED_CONNECTION_TOTAL.fetch_add(1, Ordering::Relaxed);
+        true
+    } else {
+        false
+    }
+}
+
+pub fn spawn_monitor() {
+    if let Some(config) = crate::config::capacity::get_config() {
+        tokio::spawn(run_monitor(config));
+    }
+}
+
+async fn run_monitor(config: &'static CapacityConfig) {
+    let mut interval = tokio::time::interval(config.check_interval);
+    let mut warned = false;
+    loop {
+        interval.tick().await;
+
+        let Ok(snapshot) = tokio::task::spawn_blocking(CapacitySnapshot::sample).await else {
+            break;
+        };
+        let (ratio, resource) = snapshot.max_usage();
+        CAPACITY_SNAPSHOT.store(Some(Arc::new(snapshot)));
+
+        if ratio >= config.warn_ratio {
+            if !warned {
+                warn!(
+                    "capacity: usage of {resource} reached {:.2}%",
+                    ratio * 100.0
+                );
+                warned = true;
+            }
+        } else if warned {
+            info!("capacity: all resource usage are back to normal");
+            warned = false;
+        }
+
+        if let Some(shed_ratio) = config.shed_ratio {
+            let shed = ratio >= shed_ratio;
+            if SHED_NEW_CONNECTION.swap(shed, Ordering::Relaxed) != shed {
+                if shed {
+                    warn!("capacity: start shedding new connections as usage of {resource} is too high");
+                } else {
+                    info!("capacity: stop shedding new connections");
+                }
+            }
+        }
+    }
+}
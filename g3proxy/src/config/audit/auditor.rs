@@ -22,10 +22,12 @@ use yaml_rust::{yaml, Yaml};
 
 use g3_cert_agent::CertAgentConfig;
 use g3_dpi::{
-    H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig,
+    DnsInterceptionConfig, H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig,
+    KafkaInterceptionConfig, MysqlInterceptionConfig, PostgresInterceptionConfig,
     ProtocolInspectPolicyBuilder, ProtocolInspectionConfig, ProtocolPortMap,
-    SmtpInterceptionConfig,
+    SmtpInterceptionConfig, WebsocketInterceptionConfig,
 };
+use g3_histogram::HistogramMetricsConfig;
 use g3_icap_client::IcapServiceConfig;
 use g3_tls_ticket::TlsTicketConfig;
 use g3_types::metrics::NodeName;
@@ -37,6 +39,7 @@ use g3_yaml::YamlDocPosition;
 
 #[cfg(feature = "quic")]
 use super::AuditStreamDetourConfig;
+use super::TlsInterceptionFallbackConfig;
 
 #[derive(Clone)]
 pub(crate) struct AuditorConfig {
@@ -50,20 +53,28 @@ pub(crate) struct AuditorConfig {
     pub(crate) tls_interception_client: OpensslInterceptionClientConfigBuilder,
     pub(crate) tls_interception_server: OpensslInterceptionServerConfigBuilder,
     pub(crate) tls_stream_dump: Option<StreamDumpConfig>,
+    pub(crate) tls_interception_fallback: Option<TlsInterceptionFallbackConfig>,
     pub(crate) log_uri_max_chars: usize,
     pub(crate) h1_interception: H1InterceptionConfig,
     pub(crate) h2_inspect_policy: ProtocolInspectPolicyBuilder,
     pub(crate) h2_interception: H2InterceptionConfig,
     pub(crate) websocket_inspect_policy: ProtocolInspectPolicyBuilder,
+    pub(crate) websocket_interception: WebsocketInterceptionConfig,
     pub(crate) smtp_inspect_policy: ProtocolInspectPolicyBuilder,
     pub(crate) smtp_interception: SmtpInterceptionConfig,
     pub(crate) imap_inspect_policy: ProtocolInspectPolicyBuilder,
     pub(crate) imap_interception: ImapInterceptionConfig,
+    pub(crate) kafka_interception: KafkaInterceptionConfig,
+    pub(crate) postgres_interception: PostgresInterceptionConfig,
+    pub(crate) mysql_inspect_policy: ProtocolInspectPolicyBuilder,
+    pub(crate) mysql_interception: MysqlInterceptionConfig,
+    pub(crate) dns_interception: DnsInterceptionConfig,
     pub(crate) icap_reqmod_service: Option<Arc<IcapServiceConfig>>,
     pub(crate) icap_respmod_service: Option<Arc<IcapServiceConfig>>,
     #[cfg(feature = "quic")]
     pub(crate) stream_detour_service: Option<Arc<AuditStreamDetourConfig>>,
     pub(crate) task_audit_ratio: Bernoulli,
+    pub(crate) inspect_cost_stats: HistogramMetricsConfig,
 }
 
 impl AuditorConfig {
@@ -87,20 +98,28 @@ impl AuditorConfig {
             tls_interception_client: Default::default(),
             tls_interception_server: Default::default(),
             tls_stream_dump: None,
+            tls_interception_fallback: None,
             log_uri_max_chars: 1024,
             h1_interception: Default::default(),
             h2_inspect_policy: Default::default(),
             h2_interception: Default::default(),
             websocket_inspect_policy: Default::default(),
+            websocket_interception: Default::default(),
             smtp_inspect_policy: Default::default(),
             smtp_interception: Default::default(),
             imap_inspect_policy: Default::default(),
             imap_interception: Default::default(),
+            kafka_interception: Default::default(),
+            postgres_interception: Default::default(),
+            mysql_inspect_policy: Default::default(),
+            mysql_interception: Default::default(),
+            dns_interception: Default::default(),
             icap_reqmod_service: None,
             icap_respmod_service: None,
             #[cfg(feature = "quic")]
             stream_detour_service: None,
             task_audit_ratio: Bernoulli::new(1.0).unwrap(),
+            inspect_cost_stats: HistogramMetricsConfig::default(),
         }
     }
 
@@ -149,7 +168,8 @@ impl AuditorConfig {
                     .context(format!("invalid protocol portmap value for key {k}"))
             }
             "tls_cert_agent" | "tls_cert_generator" => {
-                let agent = CertAgentConfig::parse_yaml(v).context(format!(
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let agent = CertAgentConfig::parse_yaml(v, Some(lookup_dir)).context(format!(
                     "invalid tls cert generator config value for key {k}"
                 ))?;
                 self.tls_cert_agent = Some(agent);
@@ -186,6 +206,12 @@ impl AuditorConfig {
                 self.tls_stream_dump = Some(dump);
                 Ok(())
             }
+            "tls_interception_fallback" => {
+                self.tls_interception_fallback = TlsInterceptionFallbackConfig::parse(v).context(
+                    format!("invalid tls interception fallback value for key {k}"),
+                )?;
+                Ok(())
+            }
             "log_uri_max_chars" | "uri_log_max_chars" => {
                 self.log_uri_max_chars = g3_yaml::value::as_usize(v)
                     .context(format!("invalid usize value for key {k}"))?;
@@ -212,6 +238,12 @@ impl AuditorConfig {
                         .context(format!("invalid protocol inspect policy value for key {k}"))?;
                 Ok(())
             }
+            "websocket_interception" => {
+                self.websocket_interception =
+                    g3_yaml::value::as_websocket_interception_config(v)
+                        .context(format!("invalid websocket interception value for key {k}"))?;
+                Ok(())
+            }
             "smtp_inspect_policy" => {
                 self.smtp_inspect_policy = g3_yaml::value::as_protocol_inspect_policy_builder(v)
                     .context(format!("invalid protocol inspect policy value for key {k}"))?;
@@ -232,6 +264,32 @@ impl AuditorConfig {
                     .context(format!("invalid imap interception value for key {k}"))?;
                 Ok(())
             }
+            "kafka_interception" => {
+                self.kafka_interception = g3_yaml::value::as_kafka_interception_config(v)
+                    .context(format!("invalid kafka interception value for key {k}"))?;
+                Ok(())
+            }
+            "postgres_interception" => {
+                self.postgres_interception = g3_yaml::value::as_postgres_interception_config(v)
+                    .context(format!("invalid postgres interception value for key {k}"))?;
+                Ok(())
+            }
+            "mysql_inspect_policy" => {
+                self.mysql_inspect_policy =
+                    g3_yaml::value::as_protocol_inspect_policy_builder(v)
+                        .context(format!("invalid protocol inspect policy value for key {k}"))?;
+                Ok(())
+            }
+            "mysql_interception" => {
+                self.mysql_interception = g3_yaml::value::as_mysql_interception_config(v)
+                    .context(format!("invalid mysql interception value for key {k}"))?;
+                Ok(())
+            }
+            "dns_interception" => {
+                self.dns_interception = g3_yaml::value::as_dns_interception_config(v)
+                    .context(format!("invalid dns interception value for key {k}"))?;
+                Ok(())
+            }
             "icap_reqmod_service" => {
                 let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
                 let service = IcapServiceConfig::parse_reqmod_service_yaml(v, Some(lookup_dir))
@@ -263,6 +321,12 @@ impl AuditorConfig {
                     .context(format!("invalid random ratio value for key {k}"))?;
                 Ok(())
             }
+            "inspect_cost_stats" | "inspect_cost_metrics" => {
+                self.inspect_cost_stats = g3_yaml::value::as_histogram_metrics_config(v).context(
+                    format!("invalid histogram metrics config value for key {k}"),
+                )?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
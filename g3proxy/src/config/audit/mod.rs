@@ -22,11 +22,14 @@ use yaml_rust::{yaml, Yaml};
 use g3_yaml::{HybridParser, YamlDocPosition};
 
 mod registry;
-pub(crate) use registry::{clear, get_all};
+pub(crate) use registry::{clear, del, get_all, get_all_names, restore, snapshot};
 
 mod auditor;
 pub(crate) use auditor::AuditorConfig;
 
+mod tls_fallback;
+pub(crate) use tls_fallback::TlsInterceptionFallbackConfig;
+
 #[cfg(feature = "quic")]
 mod detour;
 #[cfg(feature = "quic")]
@@ -0,0 +1,109 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+#[derive(Clone, Copy)]
+pub(crate) struct TlsInterceptionFallbackConfig {
+    pub(crate) on_cert_agent_failure: bool,
+    pub(crate) on_client_reject: bool,
+    pub(crate) cert_agent_failure_hold: Duration,
+    pub(crate) bypass_cache_ttl: Duration,
+    pub(crate) bypass_cache_max_entries: usize,
+    pub(crate) bypass_failure_threshold: usize,
+    pub(crate) max_client_hello_size: u32,
+}
+
+impl Default for TlsInterceptionFallbackConfig {
+    fn default() -> Self {
+        TlsInterceptionFallbackConfig {
+            on_cert_agent_failure: false,
+            on_client_reject: false,
+            cert_agent_failure_hold: Duration::from_secs(30),
+            bypass_cache_ttl: Duration::from_secs(600),
+            bypass_cache_max_entries: 4096,
+            bypass_failure_threshold: 3,
+            max_client_hello_size: 1 << 16,
+        }
+    }
+}
+
+impl TlsInterceptionFallbackConfig {
+    pub(super) fn parse(value: &Yaml) -> anyhow::Result<Option<Self>> {
+        let mut config = TlsInterceptionFallbackConfig::default();
+
+        match value {
+            Yaml::Boolean(enable) => {
+                if !*enable {
+                    return Ok(None);
+                }
+            }
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "on_cert_agent_failure" | "cert_agent_failure" => {
+                        config.on_cert_agent_failure = g3_yaml::value::as_bool(v)?;
+                        Ok(())
+                    }
+                    "on_client_reject" | "client_reject" => {
+                        config.on_client_reject = g3_yaml::value::as_bool(v)?;
+                        Ok(())
+                    }
+                    "cert_agent_failure_hold" => {
+                        config.cert_agent_failure_hold = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "bypass_cache_ttl" => {
+                        config.bypass_cache_ttl = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "bypass_cache_max_entries" => {
+                        config.bypass_cache_max_entries = g3_yaml::value::as_usize(v)?;
+                        Ok(())
+                    }
+                    "bypass_failure_threshold" | "failure_threshold" => {
+                        let threshold = g3_yaml::value::as_usize(v)?;
+                        if threshold == 0 {
+                            return Err(anyhow!("{k} should not be 0"));
+                        }
+                        config.bypass_failure_threshold = threshold;
+                        Ok(())
+                    }
+                    "max_client_hello_size" => {
+                        config.max_client_hello_size = g3_yaml::value::as_u32(v)?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            _ => {
+                return Err(anyhow!(
+                    "invalid yaml value type for tls interception fallback config"
+                ));
+            }
+        }
+
+        Ok(Some(config))
+    }
+
+    #[inline]
+    pub(crate) fn bypass_cache_enabled(&self) -> bool {
+        !self.bypass_cache_ttl.is_zero() && self.bypass_cache_max_entries > 0
+    }
+}
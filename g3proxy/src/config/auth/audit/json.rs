@@ -36,6 +36,11 @@ impl UserAuditConfig {
                         self.prohibit_timeout_protocol = g3_json::value::as_bool(v)
                             .context(format!("invalid bool value for key {k}"))?;
                     }
+                    "dns_tunnel_block_score" => {
+                        let score = g3_json::value::as_u8(v)
+                            .context(format!("invalid u8 value for key {k}"))?;
+                        self.dns_tunnel_block_score = Some(score);
+                    }
                     "task_audit_ratio" | "application_audit_ratio" => {
                         let ratio = g3_json::value::as_random_ratio(v)
                             .context(format!("invalid random ratio value for key {k}"))?;
@@ -24,6 +24,7 @@ pub(crate) struct UserAuditConfig {
     pub(crate) enable_protocol_inspection: bool,
     pub(crate) prohibit_unknown_protocol: bool,
     pub(crate) prohibit_timeout_protocol: bool,
+    pub(crate) dns_tunnel_block_score: Option<u8>,
     task_audit_ratio: Option<Bernoulli>,
 }
 
@@ -33,6 +34,7 @@ impl Default for UserAuditConfig {
             enable_protocol_inspection: false,
             prohibit_unknown_protocol: false,
             prohibit_timeout_protocol: true,
+            dns_tunnel_block_score: None,
             task_audit_ratio: None,
         }
     }
@@ -35,6 +35,10 @@ impl UserAuditConfig {
                     self.prohibit_timeout_protocol = g3_yaml::value::as_bool(v)?;
                     Ok(())
                 }
+                "dns_tunnel_block_score" => {
+                    self.dns_tunnel_block_score = Some(g3_yaml::value::as_u8(v)?);
+                    Ok(())
+                }
                 "task_audit_ratio" | "application_audit_ratio" => {
                     let ratio = g3_yaml::value::as_random_ratio(v)
                         .context(format!("invalid random ratio value for key {k}"))?;
@@ -25,7 +25,15 @@ use yaml_rust::{yaml, Yaml};
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
 
-use super::{UserConfig, UserDynamicSource};
+#[cfg(feature = "gssapi")]
+use super::UserKerberosConfig;
+#[cfg(feature = "pam")]
+use super::UserPamConfig;
+use super::{
+    LocalPeerUserConfig, UserConfig, UserDynamicSource, UserJwtConfig, UserLdapConfig,
+    UserQuotaStoreConfig,
+};
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
 
 const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
 
@@ -38,6 +46,16 @@ pub(crate) struct UserGroupConfig {
     pub(crate) dynamic_cache: PathBuf,
     pub(crate) refresh_interval: Duration,
     pub(crate) anonymous_user: Option<Arc<UserConfig>>,
+    pub(crate) local_peer_users: Option<LocalPeerUserConfig>,
+    pub(crate) server_timing_header: bool,
+    pub(crate) http_header_rewrite: Option<Arc<HttpHeaderRewriteConfig>>,
+    pub(crate) quota_store: Option<UserQuotaStoreConfig>,
+    pub(crate) ldap: Option<UserLdapConfig>,
+    #[cfg(feature = "pam")]
+    pub(crate) pam: Option<UserPamConfig>,
+    #[cfg(feature = "gssapi")]
+    pub(crate) kerberos: Option<UserKerberosConfig>,
+    pub(crate) jwt: Option<UserJwtConfig>,
 }
 
 impl UserGroupConfig {
@@ -58,6 +76,16 @@ impl UserGroupConfig {
             dynamic_cache: PathBuf::default(),
             refresh_interval: DEFAULT_REFRESH_INTERVAL,
             anonymous_user: None,
+            local_peer_users: None,
+            server_timing_header: false,
+            http_header_rewrite: None,
+            quota_store: None,
+            ldap: None,
+            #[cfg(feature = "pam")]
+            pam: None,
+            #[cfg(feature = "gssapi")]
+            kerberos: None,
+            jwt: None,
         }
     }
 
@@ -70,6 +98,16 @@ impl UserGroupConfig {
             dynamic_cache: PathBuf::default(),
             refresh_interval: DEFAULT_REFRESH_INTERVAL,
             anonymous_user: None,
+            local_peer_users: None,
+            server_timing_header: false,
+            http_header_rewrite: None,
+            quota_store: None,
+            ldap: None,
+            #[cfg(feature = "pam")]
+            pam: None,
+            #[cfg(feature = "gssapi")]
+            kerberos: None,
+            jwt: None,
         }
     }
 
@@ -79,10 +117,27 @@ impl UserGroupConfig {
         Ok(())
     }
 
+    /// the user config for users that are not found in static or dynamic users,
+    /// which is only available if the password will be verified externally
+    pub(crate) fn unmanaged_user(&self) -> Option<&Arc<UserConfig>> {
+        if let Some(ldap) = &self.ldap {
+            return ldap.unmanaged_user.as_ref();
+        }
+        #[cfg(feature = "pam")]
+        if let Some(pam) = &self.pam {
+            return pam.unmanaged_user.as_ref();
+        }
+        None
+    }
+
     fn check(&self) -> anyhow::Result<()> {
         if self.name.is_empty() {
             return Err(anyhow!("name is not set"));
         }
+        #[cfg(feature = "pam")]
+        if self.ldap.is_some() && self.pam.is_some() {
+            return Err(anyhow!("ldap and pam can not be set at the same time"));
+        }
 
         Ok(())
     }
@@ -145,6 +200,62 @@ impl UserGroupConfig {
                     Err(anyhow!("invalid hash value for key {k}"))
                 }
             }
+            "local_peer_users" | "local_peer_user_map" => {
+                let config = LocalPeerUserConfig::parse_yaml(v)
+                    .context(format!("invalid local peer user map value for key {k}"))?;
+                if !config.is_empty() {
+                    self.local_peer_users = Some(config);
+                }
+                Ok(())
+            }
+            "server_timing_header" => {
+                self.server_timing_header = g3_yaml::value::as_bool(v)
+                    .context(format!("invalid bool value for key {k}"))?;
+                Ok(())
+            }
+            "http_header_rewrite" | "header_rewrite" => {
+                let config = HttpHeaderRewriteConfig::parse_yaml(v).context(format!(
+                    "invalid http header rewrite config value for key {k}"
+                ))?;
+                if config.is_empty() {
+                    self.http_header_rewrite = None;
+                } else {
+                    self.http_header_rewrite = Some(Arc::new(config));
+                }
+                Ok(())
+            }
+            "quota_store" | "quota_redis" => {
+                let config = UserQuotaStoreConfig::parse_yaml(v, self.position.as_ref())
+                    .context(format!("invalid user quota store config value for key {k}"))?;
+                self.quota_store = Some(config);
+                Ok(())
+            }
+            "ldap" | "ldap_auth" => {
+                let config = UserLdapConfig::parse_yaml(v, self.position.as_ref())
+                    .context(format!("invalid user ldap config value for key {k}"))?;
+                self.ldap = Some(config);
+                Ok(())
+            }
+            #[cfg(feature = "pam")]
+            "pam" | "pam_auth" => {
+                let config = UserPamConfig::parse_yaml(v, self.position.as_ref())
+                    .context(format!("invalid user pam config value for key {k}"))?;
+                self.pam = Some(config);
+                Ok(())
+            }
+            #[cfg(feature = "gssapi")]
+            "kerberos" | "negotiate" => {
+                let config = UserKerberosConfig::parse_yaml(v, self.position.as_ref())
+                    .context(format!("invalid user kerberos config value for key {k}"))?;
+                self.kerberos = Some(config);
+                Ok(())
+            }
+            "jwt" | "jwt_auth" | "bearer" => {
+                let config = UserJwtConfig::parse_yaml(v, self.position.as_ref())
+                    .context(format!("invalid user jwt config value for key {k}"))?;
+                self.jwt = Some(config);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -0,0 +1,238 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use url::Url;
+use yaml_rust::Yaml;
+
+use g3_types::net::{Host, OpensslClientConfigBuilder};
+use g3_yaml::YamlDocPosition;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum JwtAlgorithm {
+    RS256,
+    ES256,
+}
+
+impl JwtAlgorithm {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            JwtAlgorithm::RS256 => "RS256",
+            JwtAlgorithm::ES256 => "ES256",
+        }
+    }
+}
+
+impl FromStr for JwtAlgorithm {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "RS256" => Ok(JwtAlgorithm::RS256),
+            "ES256" => Ok(JwtAlgorithm::ES256),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum JwksSource {
+    File(PathBuf),
+    Url(Url),
+}
+
+#[derive(Clone)]
+pub(crate) struct UserJwtConfig {
+    pub(crate) jwks: Option<JwksSource>,
+    pub(crate) tls_client: Option<OpensslClientConfigBuilder>,
+    pub(crate) tls_name: Option<Host>,
+    pub(crate) refresh_interval: Duration,
+    pub(crate) fetch_timeout: Duration,
+    pub(crate) algorithms: Vec<JwtAlgorithm>,
+    pub(crate) issuer: Option<String>,
+    pub(crate) audience: Vec<String>,
+    pub(crate) leeway: Duration,
+    pub(crate) require_exp: bool,
+    pub(crate) user_claim: String,
+    pub(crate) user_map: BTreeMap<String, Arc<str>>,
+}
+
+impl Default for UserJwtConfig {
+    fn default() -> Self {
+        UserJwtConfig {
+            jwks: None,
+            tls_client: None,
+            tls_name: None,
+            refresh_interval: Duration::from_secs(600),
+            fetch_timeout: Duration::from_secs(10),
+            algorithms: vec![JwtAlgorithm::RS256, JwtAlgorithm::ES256],
+            issuer: None,
+            audience: Vec::new(),
+            leeway: Duration::from_secs(60),
+            require_exp: true,
+            user_claim: "sub".to_string(),
+            user_map: BTreeMap::new(),
+        }
+    }
+}
+
+impl UserJwtConfig {
+    pub(crate) fn parse_yaml(v: &Yaml, position: Option<&YamlDocPosition>) -> anyhow::Result<Self> {
+        if let Yaml::Hash(map) = v {
+            let mut config = UserJwtConfig::default();
+            g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                "jwks_file" => {
+                    let lookup_dir = g3_daemon::config::get_lookup_dir(position)?;
+                    let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                        .context(format!("invalid jwks file path value for key {k}"))?;
+                    config.jwks = Some(JwksSource::File(path));
+                    Ok(())
+                }
+                "jwks_url" => {
+                    let url = g3_yaml::value::as_url(v)
+                        .context(format!("invalid url value for key {k}"))?;
+                    config.jwks = Some(JwksSource::Url(url));
+                    Ok(())
+                }
+                "tls_client" => {
+                    let lookup_dir = g3_daemon::config::get_lookup_dir(position)?;
+                    let builder = g3_yaml::value::as_to_one_openssl_tls_client_config_builder(
+                        v,
+                        Some(lookup_dir),
+                    )
+                    .context(format!("invalid tls client config value for key {k}"))?;
+                    config.tls_client = Some(builder);
+                    Ok(())
+                }
+                "tls_name" => {
+                    let name = g3_yaml::value::as_host(v)
+                        .context(format!("invalid tls server name value for key {k}"))?;
+                    config.tls_name = Some(name);
+                    Ok(())
+                }
+                "refresh_interval" => {
+                    config.refresh_interval = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    Ok(())
+                }
+                "fetch_timeout" => {
+                    config.fetch_timeout = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    Ok(())
+                }
+                "algorithms" | "algorithm" => {
+                    config.algorithms = g3_yaml::value::as_list(v, |v| {
+                        let s = g3_yaml::value::as_string(v)?;
+                        JwtAlgorithm::from_str(&s.to_ascii_uppercase())
+                            .map_err(|_| anyhow!("unsupported jwt algorithm {s}"))
+                    })
+                    .context(format!("invalid jwt algorithm list value for key {k}"))?;
+                    Ok(())
+                }
+                "issuer" | "iss" => {
+                    let issuer = g3_yaml::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                    config.issuer = Some(issuer);
+                    Ok(())
+                }
+                "audience" | "aud" => {
+                    config.audience = g3_yaml::value::as_list(v, g3_yaml::value::as_string)
+                        .context(format!("invalid string list value for key {k}"))?;
+                    Ok(())
+                }
+                "leeway" => {
+                    config.leeway = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    Ok(())
+                }
+                "require_exp" => {
+                    config.require_exp = g3_yaml::value::as_bool(v)
+                        .context(format!("invalid bool value for key {k}"))?;
+                    Ok(())
+                }
+                "user_claim" | "username_claim" => {
+                    config.user_claim = g3_yaml::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                    Ok(())
+                }
+                "user_map" | "claim_map" => {
+                    if let Yaml::Hash(map) = v {
+                        g3_yaml::foreach_kv(map, |claim, v| {
+                            let username = g3_yaml::value::as_string(v).context(format!(
+                                "invalid username value for claim value {claim}"
+                            ))?;
+                            config
+                                .user_map
+                                .insert(claim.to_string(), Arc::from(username));
+                            Ok(())
+                        })
+                        .context(format!("invalid user map value for key {k}"))
+                    } else {
+                        Err(anyhow!("invalid hash value for key {k}"))
+                    }
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+            config.check()?;
+            Ok(config)
+        } else {
+            Err(anyhow!(
+                "yaml value type for 'user jwt config' should be 'map'"
+            ))
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        match &self.jwks {
+            Some(JwksSource::Url(url)) => match url.scheme() {
+                "http" => {}
+                "https" => {
+                    if self.tls_client.is_none() {
+                        self.tls_client =
+                            Some(OpensslClientConfigBuilder::with_cache_for_one_site());
+                    }
+                }
+                s => return Err(anyhow!("unsupported jwks url scheme {s}")),
+            },
+            Some(JwksSource::File(_)) => {}
+            None => return Err(anyhow!("no jwks file or url set")),
+        }
+        if self.refresh_interval.is_zero() {
+            return Err(anyhow!("refresh interval should not be zero"));
+        }
+        if self.algorithms.is_empty() {
+            return Err(anyhow!("no jwt algorithm allowed"));
+        }
+        if self.user_claim.is_empty() {
+            return Err(anyhow!("user claim should not be empty"));
+        }
+        Ok(())
+    }
+
+    /// map the value of the user claim to the name of an existing user
+    pub(crate) fn map_claim(&self, value: &str) -> Arc<str> {
+        match self.user_map.get(value) {
+            Some(username) => username.clone(),
+            None => Arc::from(value),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use g3_yaml::YamlDocPosition;
+
+#[derive(Clone, Default)]
+pub(crate) struct UserKerberosConfig {
+    pub(crate) keytab: Option<PathBuf>,
+    pub(crate) service_principal: Option<String>,
+    pub(crate) realms: Vec<String>,
+    pub(crate) keep_realm: bool,
+    pub(crate) user_map: BTreeMap<String, Arc<str>>,
+}
+
+impl UserKerberosConfig {
+    pub(crate) fn parse_yaml(v: &Yaml, position: Option<&YamlDocPosition>) -> anyhow::Result<Self> {
+        if let Yaml::Hash(map) = v {
+            let mut config = UserKerberosConfig::default();
+            g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                "keytab" | "keytab_file" => {
+                    let lookup_dir = g3_daemon::config::get_lookup_dir(position)?;
+                    let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                        .context(format!("invalid keytab file path value for key {k}"))?;
+                    config.keytab = Some(path);
+                    Ok(())
+                }
+                "service_principal" | "service_name" => {
+                    let name = g3_yaml::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                    config.service_principal = Some(name);
+                    Ok(())
+                }
+                "realms" | "realm" => {
+                    config.realms = g3_yaml::value::as_list(v, g3_yaml::value::as_string)
+                        .context(format!("invalid string list value for key {k}"))?;
+                    Ok(())
+                }
+                "keep_realm" => {
+                    config.keep_realm = g3_yaml::value::as_bool(v)
+                        .context(format!("invalid bool value for key {k}"))?;
+                    Ok(())
+                }
+                "user_map" | "principal_map" => {
+                    if let Yaml::Hash(map) = v {
+                        g3_yaml::foreach_kv(map, |principal, v| {
+                            let username = g3_yaml::value::as_string(v).context(format!(
+                                "invalid username value for principal {principal}"
+                            ))?;
+                            config
+                                .user_map
+                                .insert(principal.to_string(), Arc::from(username));
+                            Ok(())
+                        })
+                        .context(format!("invalid user map value for key {k}"))
+                    } else {
+                        Err(anyhow!("invalid hash value for key {k}"))
+                    }
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+            Ok(config)
+        } else {
+            Err(anyhow!(
+                "yaml value type for 'user kerberos config' should be 'map'"
+            ))
+        }
+    }
+
+    /// map the authenticated client principal to the name of an existing user
+    pub(crate) fn map_principal(&self, principal: &str) -> Option<Arc<str>> {
+        if let Some(username) = self.user_map.get(principal) {
+            return Some(username.clone());
+        }
+
+        let (name, realm) = principal.rsplit_once('@')?;
+        if !self.realms.is_empty() && !self.realms.iter().any(|r| r.eq(realm)) {
+            return None;
+        }
+        if self.keep_realm {
+            Some(Arc::from(principal))
+        } else {
+            Some(Arc::from(name))
+        }
+    }
+}
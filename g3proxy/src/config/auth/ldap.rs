@@ -0,0 +1,185 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use g3_types::net::{Host, OpensslClientConfigBuilder, UpstreamAddr};
+use g3_yaml::YamlDocPosition;
+
+use super::UserConfig;
+
+const LDAP_DEFAULT_PORT: u16 = 389;
+const LDAPS_DEFAULT_PORT: u16 = 636;
+
+#[derive(Clone)]
+pub(crate) struct UserLdapConfig {
+    pub(crate) server: UpstreamAddr,
+    pub(crate) tls_client: Option<OpensslClientConfigBuilder>,
+    pub(crate) tls_name: Option<Host>,
+    pub(crate) base_dn: String,
+    pub(crate) username_attribute: String,
+    pub(crate) connect_timeout: Duration,
+    pub(crate) response_timeout: Duration,
+    pub(crate) cache_ttl: Duration,
+    pub(crate) negative_cache_ttl: Duration,
+    pub(crate) unmanaged_user: Option<Arc<UserConfig>>,
+}
+
+impl Default for UserLdapConfig {
+    fn default() -> Self {
+        UserLdapConfig {
+            server: UpstreamAddr::empty(),
+            tls_client: None,
+            tls_name: None,
+            base_dn: String::new(),
+            username_attribute: "uid".to_string(),
+            connect_timeout: Duration::from_secs(4),
+            response_timeout: Duration::from_secs(4),
+            cache_ttl: Duration::from_secs(300),
+            negative_cache_ttl: Duration::from_secs(30),
+            unmanaged_user: None,
+        }
+    }
+}
+
+impl UserLdapConfig {
+    pub(crate) fn parse_yaml(v: &Yaml, position: Option<&YamlDocPosition>) -> anyhow::Result<Self> {
+        if let Yaml::Hash(map) = v {
+            let mut config = UserLdapConfig::default();
+            g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                "server" | "address" | "addr" => {
+                    config.server = g3_yaml::value::as_upstream_addr(v, 0)
+                        .context(format!("invalid upstream address value for key {k}"))?;
+                    Ok(())
+                }
+                "tls_client" => {
+                    let lookup_dir = g3_daemon::config::get_lookup_dir(position)?;
+                    let builder = g3_yaml::value::as_to_one_openssl_tls_client_config_builder(
+                        v,
+                        Some(lookup_dir),
+                    )
+                    .context(format!("invalid tls client config value for key {k}"))?;
+                    config.tls_client = Some(builder);
+                    Ok(())
+                }
+                "tls_name" => {
+                    let name = g3_yaml::value::as_host(v)
+                        .context(format!("invalid tls server name value for key {k}"))?;
+                    config.tls_name = Some(name);
+                    Ok(())
+                }
+                "base_dn" => {
+                    config.base_dn = g3_yaml::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                    Ok(())
+                }
+                "username_attribute" | "username_attr" => {
+                    config.username_attribute = g3_yaml::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                    Ok(())
+                }
+                "connect_timeout" => {
+                    config.connect_timeout = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    Ok(())
+                }
+                "response_timeout" => {
+                    config.response_timeout = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    Ok(())
+                }
+                "cache_ttl" => {
+                    config.cache_ttl = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    Ok(())
+                }
+                "negative_cache_ttl" => {
+                    config.negative_cache_ttl = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    Ok(())
+                }
+                "unmanaged_user" => {
+                    if let Yaml::Hash(map) = v {
+                        let mut user = UserConfig::parse_yaml(map, position)
+                            .context(format!("invalid user config value for key {k}"))?;
+                        user.set_no_password();
+                        config.unmanaged_user = Some(Arc::new(user));
+                        Ok(())
+                    } else {
+                        Err(anyhow!("invalid hash value for key {k}"))
+                    }
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+            config.check()?;
+            Ok(config)
+        } else {
+            Err(anyhow!(
+                "yaml value type for 'user ldap config' should be 'map'"
+            ))
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.server.is_empty() {
+            return Err(anyhow!("ldap server address is not set"));
+        }
+        if self.server.port() == 0 {
+            if self.tls_client.is_some() {
+                self.server.set_port(LDAPS_DEFAULT_PORT);
+            } else {
+                self.server.set_port(LDAP_DEFAULT_PORT);
+            }
+        }
+        if self.base_dn.is_empty() {
+            return Err(anyhow!("base dn is not set"));
+        }
+        if self.username_attribute.is_empty() {
+            return Err(anyhow!("username attribute should not be empty"));
+        }
+        Ok(())
+    }
+
+    /// the DN used to bind as the user
+    pub(crate) fn user_dn(&self, username: &str) -> String {
+        let mut dn = String::with_capacity(
+            self.username_attribute.len() + username.len() + self.base_dn.len() + 2,
+        );
+        dn.push_str(&self.username_attribute);
+        dn.push('=');
+        // escape special characters as described in RFC 4514
+        let last = username.chars().count().saturating_sub(1);
+        for (i, c) in username.chars().enumerate() {
+            match c {
+                '"' | '+' | ',' | ';' | '<' | '>' | '\\' | '=' => {
+                    dn.push('\\');
+                    dn.push(c);
+                }
+                '#' if i == 0 => dn.push_str("\\#"),
+                ' ' if i == 0 || i == last => dn.push_str("\\ "),
+                '\0' => dn.push_str("\\00"),
+                _ => dn.push(c),
+            }
+        }
+        dn.push(',');
+        dn.push_str(&self.base_dn);
+        dn
+    }
+}
@@ -0,0 +1,225 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+#[cfg(target_os = "linux")]
+use std::io;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::{yaml, Yaml};
+
+#[cfg(target_os = "linux")]
+use g3_socket::local_peer::LocalPeerCred;
+
+#[derive(Clone, Default)]
+pub(crate) struct LocalPeerUserConfig {
+    uid_map: HashMap<u32, Arc<str>>,
+    cgroup_rules: Vec<(String, Arc<str>)>,
+}
+
+impl LocalPeerUserConfig {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.uid_map.is_empty() && self.cgroup_rules.is_empty()
+    }
+
+    #[inline]
+    pub(crate) fn has_cgroup_rules(&self) -> bool {
+        !self.cgroup_rules.is_empty()
+    }
+
+    pub(crate) fn match_uid(&self, uid: u32) -> Option<&Arc<str>> {
+        self.uid_map.get(&uid)
+    }
+
+    /// the cgroup rules will be matched in order, the first one that is
+    /// the same or a parent of the peer cgroup path will be used
+    pub(crate) fn match_cgroup(&self, cgroup: &str) -> Option<&Arc<str>> {
+        self.cgroup_rules
+            .iter()
+            .find(|(prefix, _)| {
+                cgroup
+                    .strip_prefix(prefix.as_str())
+                    .map(|left| left.is_empty() || left.starts_with('/') || prefix.ends_with('/'))
+                    .unwrap_or(false)
+            })
+            .map(|(_, user)| user)
+    }
+
+    /// match the uid first, then the cgroup of the peer process if there are cgroup rules.
+    ///
+    /// the peer process may be searched in /proc, so this should be called in a blocking thread
+    #[cfg(target_os = "linux")]
+    pub(crate) fn match_peer(&self, cred: &mut LocalPeerCred) -> io::Result<Option<Arc<str>>> {
+        if let Some(user) = self.match_uid(cred.uid()) {
+            return Ok(Some(user.clone()));
+        }
+        if !self.has_cgroup_rules() {
+            return Ok(None);
+        }
+        match cred.cgroup()? {
+            Some(cgroup) => Ok(self.match_cgroup(&cgroup).cloned()),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let mut config = LocalPeerUserConfig::default();
+        if let Yaml::Array(seq) = v {
+            for (i, obj) in seq.iter().enumerate() {
+                if let Yaml::Hash(map) = obj {
+                    config
+                        .add_rule(map)
+                        .context(format!("invalid local peer user rule #{i}"))?;
+                } else {
+                    return Err(anyhow!("invalid hash value for #{i}"));
+                }
+            }
+            Ok(config)
+        } else {
+            Err(anyhow!("invalid sequence value"))
+        }
+    }
+
+    fn add_rule(&mut self, map: &yaml::Hash) -> anyhow::Result<()> {
+        let mut user: Option<Arc<str>> = None;
+        let mut uid: Option<u32> = None;
+        let mut cgroup: Option<String> = None;
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "user" | "username" => {
+                let name = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                user = Some(Arc::from(name));
+                Ok(())
+            }
+            "uid" => {
+                let id =
+                    g3_yaml::value::as_u32(v).context(format!("invalid u32 value for key {k}"))?;
+                uid = Some(id);
+                Ok(())
+            }
+            "cgroup" => {
+                let path = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                if !path.starts_with('/') {
+                    return Err(anyhow!("the cgroup path should be absolute"));
+                }
+                cgroup = Some(path);
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        let Some(user) = user else {
+            return Err(anyhow!("no user set"));
+        };
+        match (uid, cgroup) {
+            (Some(uid), None) => {
+                if let Some(old) = self.uid_map.insert(uid, user) {
+                    return Err(anyhow!("uid {uid} has already been mapped to user {old}"));
+                }
+                Ok(())
+            }
+            (None, Some(cgroup)) => {
+                self.cgroup_rules.push((cgroup, user));
+                Ok(())
+            }
+            (Some(_), Some(_)) => Err(anyhow!("only one of uid and cgroup can be set")),
+            (None, None) => Err(anyhow!("no uid or cgroup set")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    fn load(s: &str) -> anyhow::Result<LocalPeerUserConfig> {
+        let docs = YamlLoader::load_from_str(s).unwrap();
+        LocalPeerUserConfig::parse_yaml(&docs[0])
+    }
+
+    #[test]
+    fn parse() {
+        let config = load(
+            r#"
+            - user: a
+              uid: 1000
+            - user: b
+              cgroup: /system.slice/app.service
+            "#,
+        )
+        .unwrap();
+        assert!(!config.is_empty());
+        assert!(config.has_cgroup_rules());
+
+        assert!(load("- user: a").is_err());
+        assert!(load("- uid: 1000").is_err());
+        assert!(load("- {user: a, uid: 1000, cgroup: /a}").is_err());
+        assert!(load("- {user: a, cgroup: a}").is_err());
+        assert!(load("- {user: a, uid: 1000}\n- {user: b, uid: 1000}").is_err());
+        assert!(load("user: a").is_err());
+    }
+
+    #[test]
+    fn match_rules() {
+        let config = load(
+            r#"
+            - {user: a, uid: 1000}
+            - {user: b, cgroup: /system.slice/app.service}
+            - {user: c, cgroup: /user.slice/}
+            - {user: d, cgroup: /}
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.match_uid(1000).unwrap().as_ref(), "a");
+        assert!(config.match_uid(1001).is_none());
+
+        let m = |cgroup: &str| config.match_cgroup(cgroup).map(|u| u.to_string());
+        assert_eq!(m("/system.slice/app.service").unwrap(), "b");
+        assert_eq!(m("/system.slice/app.service/sub").unwrap(), "b");
+        // not a child cgroup, fallback to the root rule
+        assert_eq!(m("/system.slice/app.service2").unwrap(), "d");
+        assert_eq!(m("/user.slice/user-1000.slice").unwrap(), "c");
+        assert_eq!(m("/").unwrap(), "d");
+
+        let config = load("- {user: b, cgroup: /system.slice/app.service}").unwrap();
+        assert!(config.match_cgroup("/system.slice").is_none());
+        assert!(config.match_cgroup("/system.slice/app.service2").is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn match_peer() {
+        let pid = std::process::id() as i32;
+
+        let config = load("- {user: a, uid: 1000}").unwrap();
+        let mut cred = LocalPeerCred::new(1000, None);
+        assert_eq!(config.match_peer(&mut cred).unwrap().unwrap().as_ref(), "a");
+        let mut cred = LocalPeerCred::new(1001, Some(pid));
+        assert!(config.match_peer(&mut cred).unwrap().is_none());
+
+        // all cgroups are children of the root one
+        let config = load("- {user: a, uid: 1000}\n- {user: b, cgroup: /}").unwrap();
+        let mut cred = LocalPeerCred::new(1001, Some(pid));
+        assert_eq!(config.match_peer(&mut cred).unwrap().unwrap().as_ref(), "b");
+        // no pid and no inode to find the pid
+        let mut cred = LocalPeerCred::new(1001, None);
+        assert!(config.match_peer(&mut cred).unwrap().is_none());
+    }
+}
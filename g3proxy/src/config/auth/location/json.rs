@@ -0,0 +1,94 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use serde_json::Value;
+
+use g3_geoip_types::{ContinentCode, IsoCountryCode};
+use g3_types::acl::AclAction;
+
+use super::UserEgressLocationFilter;
+
+fn as_iso_country_code(v: &Value) -> anyhow::Result<IsoCountryCode> {
+    let s = g3_json::value::as_string(v)?;
+    IsoCountryCode::from_str(&s).map_err(|_| anyhow!("invalid iso country code {s}"))
+}
+
+fn as_continent_code(v: &Value) -> anyhow::Result<ContinentCode> {
+    let s = g3_json::value::as_string(v)?;
+    ContinentCode::from_str(&s).map_err(|_| anyhow!("invalid continent code {s}"))
+}
+
+impl UserEgressLocationFilter {
+    pub(crate) fn parse_json(v: &Value) -> anyhow::Result<Self> {
+        let Value::Object(map) = v else {
+            return Err(anyhow!(
+                "json value type for 'user egress location filter' should be 'map'"
+            ));
+        };
+
+        let mut filter = UserEgressLocationFilter::default();
+        for (k, v) in map {
+            match g3_json::key::normalize(k).as_str() {
+                "default" => {
+                    let s = g3_json::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                    let action = AclAction::from_str(&s)
+                        .map_err(|_| anyhow!("invalid AclAction value for key {k}"))?;
+                    filter.set_missed_action(action);
+                }
+                _ => {
+                    let action = AclAction::from_str(k)
+                        .map_err(|_| anyhow!("the key {k} is not a valid AclAction"))?;
+                    filter
+                        .add_json_rules(action, v)
+                        .context(format!("invalid location rules for key {k}"))?;
+                }
+            }
+        }
+
+        Ok(filter)
+    }
+
+    fn add_json_rules(&mut self, action: AclAction, v: &Value) -> anyhow::Result<()> {
+        let Value::Object(map) = v else {
+            return Err(anyhow!("json value type should be 'map'"));
+        };
+
+        for (k, v) in map {
+            match g3_json::key::normalize(k).as_str() {
+                "country" | "countries" => {
+                    let countries = g3_json::value::as_list(v, as_iso_country_code)
+                        .context(format!("invalid iso country code list value for key {k}"))?;
+                    for country in countries {
+                        self.add_country(country, action);
+                    }
+                }
+                "continent" | "continents" => {
+                    let continents = g3_json::value::as_list(v, as_continent_code)
+                        .context(format!("invalid continent code list value for key {k}"))?;
+                    for continent in continents {
+                        self.add_continent(continent, action);
+                    }
+                }
+                _ => return Err(anyhow!("invalid key {k}")),
+            }
+        }
+        Ok(())
+    }
+}
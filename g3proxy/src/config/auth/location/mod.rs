@@ -0,0 +1,123 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use fnv::FnvHashMap;
+
+use g3_geoip_types::{ContinentCode, IpLocation, IsoCountryCode};
+use g3_types::acl::AclAction;
+
+mod json;
+mod yaml;
+
+#[derive(Clone)]
+pub(crate) struct UserEgressLocationFilter {
+    country: FnvHashMap<u16, AclAction>,
+    continent: FnvHashMap<u8, AclAction>,
+    missed_action: AclAction,
+}
+
+impl Default for UserEgressLocationFilter {
+    fn default() -> Self {
+        UserEgressLocationFilter {
+            country: FnvHashMap::default(),
+            continent: FnvHashMap::default(),
+            missed_action: AclAction::Permit,
+        }
+    }
+}
+
+impl UserEgressLocationFilter {
+    fn set_missed_action(&mut self, action: AclAction) {
+        self.missed_action = action;
+    }
+
+    fn add_country(&mut self, country: IsoCountryCode, action: AclAction) {
+        self.country.insert(country as u16, action);
+    }
+
+    fn add_continent(&mut self, continent: ContinentCode, action: AclAction) {
+        self.continent.insert(continent as u8, action);
+    }
+
+    #[inline]
+    pub(crate) fn missed_action(&self) -> AclAction {
+        self.missed_action
+    }
+
+    /// Country rules take precedence over continent rules
+    pub(crate) fn check(&self, location: &IpLocation) -> (bool, AclAction) {
+        let country = location.country();
+        if let Some(country) = country {
+            if let Some(action) = self.country.get(&(country as u16)) {
+                return (true, *action);
+            }
+        }
+
+        if let Some(continent) = location.continent().or(country.map(|c| c.continent())) {
+            if let Some(action) = self.continent.get(&(continent as u8)) {
+                return (true, *action);
+            }
+        }
+
+        (false, self.missed_action)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_geoip_types::IpLocationBuilder;
+    use ip_network::IpNetwork;
+
+    fn build_location(country: Option<IsoCountryCode>) -> IpLocation {
+        let mut builder = IpLocationBuilder::default();
+        builder.set_network(IpNetwork::from_str_truncate("192.0.2.0/24").unwrap());
+        if let Some(country) = country {
+            builder.set_country(country);
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn check_precedence() {
+        let mut filter = UserEgressLocationFilter::default();
+        filter.add_continent(ContinentCode::EU, AclAction::Forbid);
+        filter.add_country(IsoCountryCode::DE, AclAction::PermitAndLog);
+
+        let location = build_location(Some(IsoCountryCode::DE));
+        assert_eq!(filter.check(&location), (true, AclAction::PermitAndLog));
+
+        let location = build_location(Some(IsoCountryCode::FR));
+        assert_eq!(filter.check(&location), (true, AclAction::Forbid));
+
+        let location = build_location(Some(IsoCountryCode::JP));
+        assert_eq!(filter.check(&location), (false, AclAction::Permit));
+    }
+
+    #[test]
+    fn check_missed() {
+        let mut filter = UserEgressLocationFilter::default();
+        filter.set_missed_action(AclAction::ForbidAndLog);
+        filter.add_country(IsoCountryCode::US, AclAction::Permit);
+
+        let location = build_location(None);
+        assert_eq!(filter.check(&location), (false, AclAction::ForbidAndLog));
+        assert_eq!(filter.missed_action(), AclAction::ForbidAndLog);
+
+        let location = build_location(Some(IsoCountryCode::US));
+        assert_eq!(filter.check(&location), (true, AclAction::Permit));
+    }
+}
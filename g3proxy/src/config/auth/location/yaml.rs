@@ -0,0 +1,127 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use g3_types::acl::AclAction;
+
+use super::UserEgressLocationFilter;
+
+impl UserEgressLocationFilter {
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!(
+                "yaml value type for 'user egress location filter' should be 'map'"
+            ));
+        };
+
+        let mut filter = UserEgressLocationFilter::default();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "default" => {
+                let s = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                let action = AclAction::from_str(&s)
+                    .map_err(|_| anyhow!("invalid AclAction value for key {k}"))?;
+                filter.set_missed_action(action);
+                Ok(())
+            }
+            _ => {
+                let action = AclAction::from_str(k)
+                    .map_err(|_| anyhow!("the key {k} is not a valid AclAction"))?;
+                filter
+                    .add_yaml_rules(action, v)
+                    .context(format!("invalid location rules for key {k}"))
+            }
+        })?;
+
+        Ok(filter)
+    }
+
+    fn add_yaml_rules(&mut self, action: AclAction, v: &Yaml) -> anyhow::Result<()> {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!("yaml value type should be 'map'"));
+        };
+
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "country" | "countries" => {
+                let countries = g3_yaml::value::as_list(v, g3_yaml::value::as_iso_country_code)
+                    .context(format!("invalid iso country code list value for key {k}"))?;
+                for country in countries {
+                    self.add_country(country, action);
+                }
+                Ok(())
+            }
+            "continent" | "continents" => {
+                let continents = g3_yaml::value::as_list(v, g3_yaml::value::as_continent_code)
+                    .context(format!("invalid continent code list value for key {k}"))?;
+                for continent in continents {
+                    self.add_continent(continent, action);
+                }
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use g3_geoip_types::{ContinentCode, IpLocationBuilder, IsoCountryCode};
+    use ip_network::IpNetwork;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_map() {
+        let yaml = r#"
+            default: forbid
+            permit:
+              countries: [CN, JP]
+            forbid_log:
+              continent: EU
+        "#;
+        let docs = YamlLoader::load_from_str(yaml).unwrap();
+        let filter = UserEgressLocationFilter::parse_yaml(&docs[0]).unwrap();
+        assert_eq!(filter.missed_action(), AclAction::Forbid);
+
+        let mut builder = IpLocationBuilder::default();
+        builder.set_network(IpNetwork::from_str_truncate("192.0.2.0/24").unwrap());
+        builder.set_continent(ContinentCode::EU);
+        let location = builder.build().unwrap();
+        assert_eq!(filter.check(&location), (true, AclAction::ForbidAndLog));
+
+        let mut builder = IpLocationBuilder::default();
+        builder.set_network(IpNetwork::from_str_truncate("192.0.2.0/24").unwrap());
+        builder.set_country(IsoCountryCode::JP);
+        let location = builder.build().unwrap();
+        assert_eq!(filter.check(&location), (true, AclAction::Permit));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let docs = YamlLoader::load_from_str("whitelist: {country: CN}").unwrap();
+        assert!(UserEgressLocationFilter::parse_yaml(&docs[0]).is_err());
+
+        let docs = YamlLoader::load_from_str("permit: {city: Paris}").unwrap();
+        assert!(UserEgressLocationFilter::parse_yaml(&docs[0]).is_err());
+
+        let docs = YamlLoader::load_from_str("permit: [CN]").unwrap();
+        assert!(UserEgressLocationFilter::parse_yaml(&docs[0]).is_err());
+    }
+}
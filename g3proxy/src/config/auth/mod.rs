@@ -30,9 +30,34 @@ pub(crate) use site::UserSiteConfig;
 mod audit;
 pub(crate) use audit::UserAuditConfig;
 
+mod location;
+pub(crate) use location::UserEgressLocationFilter;
+
+mod quota;
+pub(crate) use quota::{UserQuotaConfig, UserQuotaStoreConfig};
+
 mod user;
 pub(crate) use user::UserConfig;
 
+mod local_peer;
+pub(crate) use local_peer::LocalPeerUserConfig;
+
+mod ldap;
+pub(crate) use ldap::UserLdapConfig;
+
+mod jwt;
+pub(crate) use jwt::{JwksSource, JwtAlgorithm, UserJwtConfig};
+
+#[cfg(feature = "pam")]
+mod pam;
+#[cfg(feature = "pam")]
+pub(crate) use pam::UserPamConfig;
+
+#[cfg(feature = "gssapi")]
+mod kerberos;
+#[cfg(feature = "gssapi")]
+pub(crate) use kerberos::UserKerberosConfig;
+
 mod group;
 pub(crate) use group::UserGroupConfig;
 
@@ -40,7 +65,7 @@ pub(crate) mod source;
 pub(crate) use source::UserDynamicSource;
 
 mod registry;
-pub(crate) use registry::{clear, get_all};
+pub(crate) use registry::{clear, del, get_all, get_all_names, restore, snapshot};
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
     let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
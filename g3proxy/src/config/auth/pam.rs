@@ -0,0 +1,120 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use g3_yaml::YamlDocPosition;
+
+use super::UserConfig;
+
+#[derive(Clone)]
+pub(crate) struct UserPamConfig {
+    pub(crate) service: String,
+    pub(crate) threads: usize,
+    pub(crate) response_timeout: Duration,
+    pub(crate) cache_ttl: Duration,
+    pub(crate) negative_cache_ttl: Duration,
+    pub(crate) unmanaged_user: Option<Arc<UserConfig>>,
+}
+
+impl Default for UserPamConfig {
+    fn default() -> Self {
+        UserPamConfig {
+            service: "g3proxy".to_string(),
+            threads: 2,
+            response_timeout: Duration::from_secs(10),
+            cache_ttl: Duration::from_secs(300),
+            negative_cache_ttl: Duration::from_secs(30),
+            unmanaged_user: None,
+        }
+    }
+}
+
+impl UserPamConfig {
+    pub(crate) fn parse_yaml(v: &Yaml, position: Option<&YamlDocPosition>) -> anyhow::Result<Self> {
+        match v {
+            Yaml::Hash(map) => {
+                let mut config = UserPamConfig::default();
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "service" | "service_name" => {
+                        config.service = g3_yaml::value::as_string(v)
+                            .context(format!("invalid string value for key {k}"))?;
+                        Ok(())
+                    }
+                    "threads" | "thread_number" => {
+                        config.threads = g3_yaml::value::as_usize(v)
+                            .context(format!("invalid usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "response_timeout" => {
+                        config.response_timeout = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "cache_ttl" => {
+                        config.cache_ttl = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "negative_cache_ttl" => {
+                        config.negative_cache_ttl = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "unmanaged_user" => {
+                        if let Yaml::Hash(map) = v {
+                            let mut user = UserConfig::parse_yaml(map, position)
+                                .context(format!("invalid user config value for key {k}"))?;
+                            user.set_no_password();
+                            config.unmanaged_user = Some(Arc::new(user));
+                            Ok(())
+                        } else {
+                            Err(anyhow!("invalid hash value for key {k}"))
+                        }
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                config.check()?;
+                Ok(config)
+            }
+            Yaml::String(_) => {
+                let config = UserPamConfig {
+                    service: g3_yaml::value::as_string(v)?,
+                    ..Default::default()
+                };
+                config.check()?;
+                Ok(config)
+            }
+            _ => Err(anyhow!(
+                "yaml value type for 'user pam config' should be 'map' or 'string'"
+            )),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.service.is_empty() {
+            return Err(anyhow!("pam service name should not be empty"));
+        }
+        if self.threads == 0 {
+            return Err(anyhow!("pam thread number should not be zero"));
+        }
+        Ok(())
+    }
+}
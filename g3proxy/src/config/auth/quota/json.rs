@@ -0,0 +1,72 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use serde_json::Value;
+
+use super::{UserQuotaConfig, UserQuotaPeriod, UserQuotaTimeWindow};
+
+fn as_time_window(v: &Value) -> anyhow::Result<UserQuotaTimeWindow> {
+    if let Value::String(s) = v {
+        UserQuotaTimeWindow::from_str(s)
+    } else {
+        Err(anyhow!(
+            "json value type for 'time window' should be 'string'"
+        ))
+    }
+}
+
+impl UserQuotaConfig {
+    pub(crate) fn parse_json(v: &Value) -> anyhow::Result<Self> {
+        if let Value::Object(map) = v {
+            let mut config = UserQuotaConfig::default();
+            for (k, v) in map {
+                match g3_json::key::normalize(k).as_str() {
+                    "traffic_limit" | "traffic" => {
+                        let limit = g3_json::humanize::as_u64(v)
+                            .context(format!("invalid humanize u64 value for key {k}"))?;
+                        config.traffic_limit = Some(limit);
+                    }
+                    "traffic_period" | "period" => {
+                        if let Value::String(s) = v {
+                            config.traffic_period = UserQuotaPeriod::from_str(s)
+                                .map_err(|_| anyhow!("invalid quota period value for key {k}"))?;
+                        } else {
+                            return Err(anyhow!("invalid string value for key {k}"));
+                        }
+                    }
+                    "time_window" | "time_windows" => {
+                        config.time_windows = g3_json::value::as_list(v, as_time_window)
+                            .context(format!("invalid time window list value for key {k}"))?;
+                    }
+                    "block_delay" => {
+                        config.block_delay = g3_json::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                    }
+                    _ => return Err(anyhow!("invalid key {k}")),
+                }
+            }
+            config.check()?;
+            Ok(config)
+        } else {
+            Err(anyhow!(
+                "json value type for 'user quota config' should be 'map'"
+            ))
+        }
+    }
+}
@@ -0,0 +1,154 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use chrono::{DateTime, Datelike, Local, NaiveTime, Timelike};
+
+mod json;
+mod yaml;
+
+mod store;
+pub(crate) use store::UserQuotaStoreConfig;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum UserQuotaPeriod {
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl UserQuotaPeriod {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            UserQuotaPeriod::Hourly => "hourly",
+            UserQuotaPeriod::Daily => "daily",
+            UserQuotaPeriod::Weekly => "weekly",
+            UserQuotaPeriod::Monthly => "monthly",
+        }
+    }
+
+    /// get the id of the period that the given time is in, in local timezone
+    pub(crate) fn id(&self, dt: &DateTime<Local>) -> u64 {
+        let days = dt.date_naive().num_days_from_ce() as u64;
+        match self {
+            UserQuotaPeriod::Hourly => days * 24 + dt.hour() as u64,
+            UserQuotaPeriod::Daily => days,
+            UserQuotaPeriod::Weekly => {
+                let week = dt.iso_week();
+                week.year() as u64 * 100 + week.week() as u64
+            }
+            UserQuotaPeriod::Monthly => dt.year() as u64 * 100 + dt.month() as u64,
+        }
+    }
+
+    /// the max length of the period
+    pub(crate) fn max_duration(&self) -> Duration {
+        match self {
+            UserQuotaPeriod::Hourly => Duration::from_secs(3600),
+            UserQuotaPeriod::Daily => Duration::from_secs(86400),
+            UserQuotaPeriod::Weekly => Duration::from_secs(86400 * 7),
+            UserQuotaPeriod::Monthly => Duration::from_secs(86400 * 31),
+        }
+    }
+}
+
+impl FromStr for UserQuotaPeriod {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "hourly" | "hour" => Ok(UserQuotaPeriod::Hourly),
+            "daily" | "day" => Ok(UserQuotaPeriod::Daily),
+            "weekly" | "week" => Ok(UserQuotaPeriod::Weekly),
+            "monthly" | "month" => Ok(UserQuotaPeriod::Monthly),
+            _ => Err(()),
+        }
+    }
+}
+
+/// A local time range in format `HH:MM-HH:MM`, the end time is exclusive.
+///
+/// The range will cross midnight if the end time is not greater than the start time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct UserQuotaTimeWindow {
+    start: NaiveTime,
+    end: NaiveTime,
+}
+
+impl UserQuotaTimeWindow {
+    fn contains(&self, t: NaiveTime) -> bool {
+        if self.start < self.end {
+            self.start <= t && t < self.end
+        } else {
+            self.start <= t || t < self.end
+        }
+    }
+}
+
+impl FromStr for UserQuotaTimeWindow {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((start, end)) = s.split_once('-') else {
+            return Err(anyhow!("no '-' delimiter found"));
+        };
+        let start = NaiveTime::parse_from_str(start.trim(), "%H:%M")
+            .map_err(|e| anyhow!("invalid start time: {e}"))?;
+        let end = NaiveTime::parse_from_str(end.trim(), "%H:%M")
+            .map_err(|e| anyhow!("invalid end time: {e}"))?;
+        Ok(UserQuotaTimeWindow { start, end })
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct UserQuotaConfig {
+    pub(crate) traffic_limit: Option<u64>,
+    pub(crate) traffic_period: UserQuotaPeriod,
+    time_windows: Vec<UserQuotaTimeWindow>,
+    pub(crate) block_delay: Duration,
+}
+
+impl Default for UserQuotaConfig {
+    fn default() -> Self {
+        UserQuotaConfig {
+            traffic_limit: None,
+            traffic_period: UserQuotaPeriod::Daily,
+            time_windows: Vec::new(),
+            block_delay: Duration::ZERO,
+        }
+    }
+}
+
+impl UserQuotaConfig {
+    pub(crate) fn in_time_window(&self, dt: &DateTime<Local>) -> bool {
+        if self.time_windows.is_empty() {
+            return true;
+        }
+        let t = dt.time();
+        self.time_windows.iter().any(|w| w.contains(t))
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.traffic_limit.is_none() && self.time_windows.is_empty() {
+            return Err(anyhow!("neither traffic limit nor time window is set"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use g3_redis_client::RedisClientConfigBuilder;
+use g3_yaml::YamlDocPosition;
+
+/// Persist the quota counters of all users in a user group to redis,
+/// so they can be shared between instances and survive restart.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct UserQuotaStoreConfig {
+    pub(crate) client_builder: RedisClientConfigBuilder,
+    pub(crate) key_prefix: String,
+    pub(crate) sync_interval: Duration,
+}
+
+impl Default for UserQuotaStoreConfig {
+    fn default() -> Self {
+        UserQuotaStoreConfig {
+            client_builder: RedisClientConfigBuilder::default(),
+            key_prefix: "g3proxy:quota:".to_string(),
+            sync_interval: Duration::from_secs(10),
+        }
+    }
+}
+
+impl UserQuotaStoreConfig {
+    pub(crate) fn parse_yaml(v: &Yaml, position: Option<&YamlDocPosition>) -> anyhow::Result<Self> {
+        if let Yaml::Hash(map) = v {
+            let mut config = UserQuotaStoreConfig::default();
+            g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                "key_prefix" => {
+                    config.key_prefix = g3_yaml::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                    Ok(())
+                }
+                "sync_interval" => {
+                    config.sync_interval = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    Ok(())
+                }
+                normalized_key => {
+                    let lookup_dir = g3_daemon::config::get_lookup_dir(position)?;
+                    config
+                        .client_builder
+                        .set_yaml_kv(normalized_key, v, Some(lookup_dir))
+                        .context(format!("failed to parse key {k}"))
+                }
+            })?;
+            if config.sync_interval.is_zero() {
+                return Err(anyhow!("sync interval should not be zero"));
+            }
+            Ok(config)
+        } else {
+            Err(anyhow!(
+                "yaml value type for 'user quota store config' should be 'map'"
+            ))
+        }
+    }
+
+    /// the redis key for the counter of a user in a specific period
+    pub(crate) fn counter_key(&self, group: &str, user: &str, period_id: u64) -> String {
+        format!("{}{group}:{user}:{period_id}", self.key_prefix)
+    }
+}
@@ -19,6 +19,8 @@ use std::sync::{Arc, LazyLock, Mutex};
 
 use anyhow::anyhow;
 
+use g3_types::metrics::NodeName;
+
 use super::UserGroupConfig;
 
 static INITIAL_USER_GROUP_CONFIG_REGISTRY: LazyLock<Mutex<HashMap<String, Arc<UserGroupConfig>>>> =
@@ -55,3 +57,23 @@ pub(crate) fn get_all() -> Vec<Arc<UserGroupConfig>> {
     }
     vec
 }
+
+pub(crate) fn del(name: &NodeName) {
+    let mut ht = INITIAL_USER_GROUP_CONFIG_REGISTRY.lock().unwrap();
+    ht.remove(name.as_str());
+}
+
+pub(crate) fn get_all_names() -> Vec<NodeName> {
+    let ht = INITIAL_USER_GROUP_CONFIG_REGISTRY.lock().unwrap();
+    ht.values().map(|v| v.name().clone()).collect()
+}
+
+pub(crate) fn snapshot() -> HashMap<String, Arc<UserGroupConfig>> {
+    let ht = INITIAL_USER_GROUP_CONFIG_REGISTRY.lock().unwrap();
+    ht.clone()
+}
+
+pub(crate) fn restore(snapshot: HashMap<String, Arc<UserGroupConfig>>) {
+    let mut ht = INITIAL_USER_GROUP_CONFIG_REGISTRY.lock().unwrap();
+    *ht = snapshot;
+}
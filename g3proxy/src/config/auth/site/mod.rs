@@ -23,9 +23,10 @@ use anyhow::anyhow;
 use ip_network::IpNetwork;
 
 use g3_histogram::HistogramMetricsConfig;
-use g3_types::metrics::NodeName;
+use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::net::{Host, OpensslClientConfigBuilder};
 use g3_types::resolve::ResolveStrategy;
+use g3_types::route::HostPattern;
 
 mod json;
 mod yaml;
@@ -37,11 +38,14 @@ pub(crate) struct UserSiteConfig {
     pub(crate) exact_match_ipaddr: BTreeSet<IpAddr>,
     pub(crate) subnet_match_ipaddr: BTreeSet<IpNetwork>,
     pub(crate) child_match_domain: BTreeSet<String>,
+    pub(crate) pattern_match_domain: BTreeSet<HostPattern>,
     pub(crate) emit_stats: bool,
+    pub(crate) resolver: Option<NodeName>,
     pub(crate) resolve_strategy: Option<ResolveStrategy>,
     pub(crate) duration_stats: HistogramMetricsConfig,
     pub(crate) tls_client: Option<OpensslClientConfigBuilder>,
     pub(crate) http_rsp_hdr_recv_timeout: Option<Duration>,
+    pub(crate) request_tags: Option<Arc<StaticMetricsTags>>,
 }
 
 impl UserSiteConfig {
@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+use std::sync::Arc;
+
 use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
@@ -69,6 +71,22 @@ impl UserSiteConfig {
                 }
                 Ok(())
             }
+            "glob_match" => {
+                let patterns = g3_yaml::value::as_list(v, g3_yaml::value::as_host_glob_pattern)
+                    .context(format!("invalid host glob pattern list value for key {k}"))?;
+                for p in patterns {
+                    self.pattern_match_domain.insert(p);
+                }
+                Ok(())
+            }
+            "regex_match" => {
+                let patterns = g3_yaml::value::as_list(v, g3_yaml::value::as_host_regex_pattern)
+                    .context(format!("invalid host regex pattern list value for key {k}"))?;
+                for p in patterns {
+                    self.pattern_match_domain.insert(p);
+                }
+                Ok(())
+            }
             "emit_stats" | "emit_metrics" => {
                 self.emit_stats = g3_yaml::value::as_bool(v)
                     .context(format!("invalid bool value for key {k}"))?;
@@ -80,6 +98,12 @@ impl UserSiteConfig {
                 )?;
                 Ok(())
             }
+            "resolver" => {
+                let name = g3_yaml::value::as_metrics_name(v)
+                    .context(format!("invalid metrics name value for key {k}"))?;
+                self.resolver = Some(name);
+                Ok(())
+            }
             "resolve_strategy" => {
                 let strategy = g3_yaml::value::as_resolve_strategy(v)
                     .context(format!("invalid resolve strategy value for key {k}"))?;
@@ -102,6 +126,14 @@ impl UserSiteConfig {
                 self.http_rsp_hdr_recv_timeout = Some(timeout);
                 Ok(())
             }
+            "request_tags" => {
+                let tags = g3_yaml::value::as_static_metrics_tags(v)
+                    .context(format!("invalid static metrics tags value for key {k}"))?;
+                if !tags.is_empty() {
+                    self.request_tags = Some(Arc::new(tags));
+                }
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -20,9 +20,11 @@ use ahash::AHashMap;
 use anyhow::{anyhow, Context};
 use serde_json::{Map, Value};
 
+use g3_types::acl::{AclAction, AclChildDomainRuleBuilder};
 use g3_types::metrics::NodeName;
 
-use super::{PasswordToken, UserConfig, UserSiteConfig};
+use super::{PasswordToken, UserConfig, UserEgressLocationFilter, UserQuotaConfig, UserSiteConfig};
+use crate::config::idle::TaskIdlePolicy;
 use crate::escape::EgressPathSelection;
 
 impl UserConfig {
@@ -60,12 +62,24 @@ impl UserConfig {
                 self.block_and_delay = Some(delay);
                 Ok(())
             }
+            "quota" => {
+                let quota = UserQuotaConfig::parse_json(v)
+                    .context(format!("invalid user quota config value for key {k}"))?;
+                self.quota = Some(quota);
+                Ok(())
+            }
             "tcp_connect" => {
                 let config = g3_json::value::as_tcp_connect_config(v)
                     .context(format!("invalid tcp connect config value for key {k}"))?;
                 self.tcp_connect = Some(config);
                 Ok(())
             }
+            "tcp_connect_race" => {
+                let config = g3_json::value::as_tcp_connect_race_config(v)
+                    .context(format!("invalid tcp connect race config value for key {k}"))?;
+                self.tcp_connect_race = Some(config);
+                Ok(())
+            }
             "tcp_sock_speed_limit" | "tcp_conn_speed_limit" | "tcp_conn_limit" => {
                 self.tcp_sock_speed_limit = g3_json::value::as_tcp_sock_speed_limit(v)
                     .context(format!("invalid tcp socket speed limit value for key {k}"))?;
@@ -192,6 +206,12 @@ impl UserConfig {
                 self.http_user_agent_filter = Some(filter);
                 Ok(())
             }
+            "egress_location_filter" => {
+                let filter = UserEgressLocationFilter::parse_json(v)
+                    .context(format!("invalid egress location filter value for key {k}"))?;
+                self.egress_location_filter = Some(filter);
+                Ok(())
+            }
             "resolve_strategy" => {
                 let strategy = g3_json::value::as_resolve_strategy(v)
                     .context(format!("invalid resolve strategy value for key {k}"))?;
@@ -204,6 +224,16 @@ impl UserConfig {
                 self.resolve_redirection = Some(builder);
                 Ok(())
             }
+            "resolve_rebind_allow" => {
+                let mut builder = AclChildDomainRuleBuilder::new(AclAction::Forbid);
+                let domains = g3_json::value::as_list(v, g3_json::value::as_domain)
+                    .context(format!("invalid domain list value for key {k}"))?;
+                for domain in domains {
+                    builder.add_node(&domain, AclAction::Permit);
+                }
+                self.resolve_rebind_allow = Some(builder);
+                Ok(())
+            }
             "log_rate_limit" | "log_limit_quota" => {
                 let quota = g3_json::value::as_rate_limit_quota(v)
                     .context(format!("invalid request quota value for key {k}"))?;
@@ -221,6 +251,11 @@ impl UserConfig {
                     g3_json::value::as_i32(v).context(format!("invalid i32 value for key {k}"))?;
                 Ok(())
             }
+            "task_idle_policy" => {
+                self.task_idle_policy = TaskIdlePolicy::parse_json(v)
+                    .context(format!("invalid task idle policy value for key {k}"))?;
+                Ok(())
+            }
             "socks_use_udp_associate" => {
                 self.socks_use_udp_associate = g3_json::value::as_bool(v)
                     .context(format!("invalid bool value for key {k}"))?;
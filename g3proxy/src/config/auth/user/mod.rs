@@ -22,7 +22,8 @@ use anyhow::anyhow;
 use chrono::{DateTime, Utc};
 
 use g3_types::acl::{
-    AclExactPortRule, AclNetworkRuleBuilder, AclProxyRequestRule, AclUserAgentRule,
+    AclChildDomainRuleBuilder, AclExactPortRule, AclNetworkRuleBuilder, AclProxyRequestRule,
+    AclUserAgentRule,
 };
 use g3_types::acl_set::AclDstHostRuleSetBuilder;
 use g3_types::limit::{
@@ -30,12 +31,15 @@ use g3_types::limit::{
 };
 use g3_types::metrics::NodeName;
 use g3_types::net::{
-    HttpKeepAliveConfig, TcpConnectConfig, TcpKeepAliveConfig, TcpMiscSockOpts,
-    TcpSockSpeedLimitConfig, UdpMiscSockOpts, UdpSockSpeedLimitConfig,
+    HttpKeepAliveConfig, TcpConnectConfig, TcpConnectRaceConfig, TcpKeepAliveConfig,
+    TcpMiscSockOpts, TcpSockSpeedLimitConfig, UdpMiscSockOpts, UdpSockSpeedLimitConfig,
 };
 use g3_types::resolve::{ResolveRedirectionBuilder, ResolveStrategy};
 
-use super::{PasswordToken, UserAuditConfig, UserSiteConfig};
+use super::{
+    PasswordToken, UserAuditConfig, UserEgressLocationFilter, UserQuotaConfig, UserSiteConfig,
+};
+use crate::config::idle::TaskIdlePolicy;
 use crate::escape::EgressPathSelection;
 
 mod json;
@@ -48,7 +52,9 @@ pub(crate) struct UserConfig {
     expire_datetime: Option<DateTime<Utc>>,
     pub(crate) audit: UserAuditConfig,
     pub(crate) block_and_delay: Option<Duration>,
+    pub(crate) quota: Option<UserQuotaConfig>,
     pub(crate) tcp_connect: Option<TcpConnectConfig>,
+    pub(crate) tcp_connect_race: Option<TcpConnectRaceConfig>,
     pub(crate) tcp_remote_keepalive: TcpKeepAliveConfig,
     tcp_remote_misc_opts: Option<TcpMiscSockOpts>,
     udp_remote_misc_opts: Option<UdpMiscSockOpts>,
@@ -72,9 +78,12 @@ pub(crate) struct UserConfig {
     pub(crate) dst_host_filter: Option<AclDstHostRuleSetBuilder>,
     pub(crate) dst_port_filter: Option<AclExactPortRule>,
     pub(crate) http_user_agent_filter: Option<AclUserAgentRule>,
+    pub(crate) egress_location_filter: Option<UserEgressLocationFilter>,
     pub(crate) resolve_strategy: Option<ResolveStrategy>,
     pub(crate) resolve_redirection: Option<ResolveRedirectionBuilder>,
+    pub(crate) resolve_rebind_allow: Option<AclChildDomainRuleBuilder>,
     pub(crate) task_idle_max_count: i32,
+    pub(crate) task_idle_policy: TaskIdlePolicy,
     pub(crate) socks_use_udp_associate: bool,
     pub(crate) egress_path_selection: Option<EgressPathSelection>,
     pub(crate) explicit_sites: BTreeMap<NodeName, Arc<UserSiteConfig>>,
@@ -88,7 +97,9 @@ impl Default for UserConfig {
             expire_datetime: None,
             audit: UserAuditConfig::default(),
             block_and_delay: None,
+            quota: None,
             tcp_connect: None,
+            tcp_connect_race: None,
             tcp_remote_keepalive: Default::default(),
             tcp_remote_misc_opts: None,
             udp_remote_misc_opts: None,
@@ -112,9 +123,12 @@ impl Default for UserConfig {
             dst_host_filter: None,
             dst_port_filter: None,
             http_user_agent_filter: None,
+            egress_location_filter: None,
             resolve_strategy: None,
             resolve_redirection: None,
+            resolve_rebind_allow: None,
             task_idle_max_count: 1,
+            task_idle_policy: TaskIdlePolicy::default(),
             socks_use_udp_associate: false,
             egress_path_selection: None,
             explicit_sites: BTreeMap::new(),
@@ -160,6 +174,8 @@ impl UserConfig {
         }
     }
 
+    // the regex inside HostPattern is not used for ordering
+    #[allow(clippy::mutable_key_type)]
     fn check(&self) -> anyhow::Result<()> {
         if self.name.is_empty() {
             return Err(anyhow!("name is not set"));
@@ -169,6 +185,7 @@ impl UserConfig {
         let mut check_exact_domain = BTreeSet::new();
         let mut check_child_domain = BTreeSet::new();
         let mut check_subnet = BTreeSet::new();
+        let mut check_pattern = BTreeSet::new();
         for config in self.explicit_sites.values() {
             for ip in &config.exact_match_ipaddr {
                 if !check_exact_ip.insert(*ip) {
@@ -202,6 +219,14 @@ impl UserConfig {
                     ));
                 }
             }
+            for pattern in &config.pattern_match_domain {
+                if !check_pattern.insert(pattern) {
+                    return Err(anyhow!(
+                        "Host pattern {pattern} in site group {} has already been added by others",
+                        config.id
+                    ));
+                }
+            }
         }
 
         Ok(())
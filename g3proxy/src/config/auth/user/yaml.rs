@@ -22,7 +22,8 @@ use yaml_rust::{yaml, Yaml};
 
 use g3_yaml::YamlDocPosition;
 
-use super::{PasswordToken, UserConfig, UserSiteConfig};
+use super::{PasswordToken, UserConfig, UserEgressLocationFilter, UserQuotaConfig, UserSiteConfig};
+use crate::config::idle::TaskIdlePolicy;
 use crate::escape::EgressPathSelection;
 
 impl UserConfig {
@@ -66,12 +67,24 @@ impl UserConfig {
                 self.block_and_delay = Some(delay);
                 Ok(())
             }
+            "quota" => {
+                let quota = UserQuotaConfig::parse_yaml(v)
+                    .context(format!("invalid user quota config value for key {k}"))?;
+                self.quota = Some(quota);
+                Ok(())
+            }
             "tcp_connect" => {
                 let config = g3_yaml::value::as_tcp_connect_config(v)
                     .context(format!("invalid tcp connect config value for key {k}"))?;
                 self.tcp_connect = Some(config);
                 Ok(())
             }
+            "tcp_connect_race" => {
+                let config = g3_yaml::value::as_tcp_connect_race_config(v)
+                    .context(format!("invalid tcp connect race config value for key {k}"))?;
+                self.tcp_connect_race = Some(config);
+                Ok(())
+            }
             "tcp_sock_speed_limit" | "tcp_conn_speed_limit" | "tcp_conn_limit" => {
                 self.tcp_sock_speed_limit = g3_yaml::value::as_tcp_sock_speed_limit(v)
                     .context(format!("invalid tcp socket speed limit value for key {k}"))?;
@@ -198,6 +211,12 @@ impl UserConfig {
                 self.http_user_agent_filter = Some(filter);
                 Ok(())
             }
+            "egress_location_filter" => {
+                let filter = UserEgressLocationFilter::parse_yaml(v)
+                    .context(format!("invalid egress location filter value for key {k}"))?;
+                self.egress_location_filter = Some(filter);
+                Ok(())
+            }
             "resolve_strategy" => {
                 let strategy = g3_yaml::value::as_resolve_strategy(v)
                     .context(format!("invalid resolve strategy value for key {k}"))?;
@@ -210,6 +229,12 @@ impl UserConfig {
                 self.resolve_redirection = Some(builder);
                 Ok(())
             }
+            "resolve_rebind_allow" => {
+                let builder = crate::config::resolver::as_rebind_allow_domains(v)
+                    .context(format!("invalid domain list value for key {k}"))?;
+                self.resolve_rebind_allow = Some(builder);
+                Ok(())
+            }
             "log_rate_limit" | "log_limit_quota" => {
                 let quota = g3_yaml::value::as_rate_limit_quota(v)
                     .context(format!("invalid request quota value for key {k}"))?;
@@ -227,6 +252,11 @@ impl UserConfig {
                     g3_yaml::value::as_i32(v).context(format!("invalid i32 value for key {k}"))?;
                 Ok(())
             }
+            "task_idle_policy" => {
+                self.task_idle_policy = TaskIdlePolicy::parse_yaml(v)
+                    .context(format!("invalid task idle policy value for key {k}"))?;
+                Ok(())
+            }
             "socks_use_udp_associate" => {
                 self.socks_use_udp_associate = g3_yaml::value::as_bool(v)
                     .context(format!("invalid bool value for key {k}"))?;
@@ -0,0 +1,92 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+static CANARY_CONFIG: OnceLock<CanaryConfig> = OnceLock::new();
+
+/// Config for the canary check after each reload
+#[derive(Clone, Debug)]
+pub(crate) struct CanaryConfig {
+    /// the canary check will be skipped if set to zero
+    pub(crate) window: Duration,
+    /// max ratio of failed escaper connections
+    pub(crate) max_error_ratio: f64,
+    /// the ratio will not be checked if there are too few connection attempts
+    pub(crate) min_attempts: u64,
+}
+
+impl Default for CanaryConfig {
+    fn default() -> Self {
+        CanaryConfig {
+            window: Duration::ZERO,
+            max_error_ratio: 0.5,
+            min_attempts: 100,
+        }
+    }
+}
+
+impl CanaryConfig {
+    fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let mut config = CanaryConfig::default();
+        match v {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "window" | "canary_window" => {
+                        config.window = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_error_ratio" => {
+                        let ratio = g3_yaml::value::as_f64(v)
+                            .context(format!("invalid f64 value for key {k}"))?;
+                        if !(0.0..=1.0).contains(&ratio) {
+                            return Err(anyhow!("the value for key {k} should be in [0.0, 1.0]"));
+                        }
+                        config.max_error_ratio = ratio;
+                        Ok(())
+                    }
+                    "min_attempts" => {
+                        config.min_attempts = g3_yaml::value::as_u64(v)
+                            .context(format!("invalid u64 value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            _ => {
+                config.window = g3_yaml::humanize::as_duration(v)
+                    .context("invalid humanize duration value for canary window")?;
+            }
+        }
+        Ok(config)
+    }
+}
+
+pub(crate) fn load(v: &Yaml) -> anyhow::Result<()> {
+    let config = CanaryConfig::parse_yaml(v)?;
+    CANARY_CONFIG
+        .set(config)
+        .map_err(|_| anyhow!("canary config has already been set"))
+}
+
+pub(crate) fn get_config() -> Option<&'static CanaryConfig> {
+    CANARY_CONFIG.get()
+}
@@ -0,0 +1,105 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+static CAPACITY_CONFIG: OnceLock<CapacityConfig> = OnceLock::new();
+
+/// Config for the monitor of system resources that limit the number of connections
+#[derive(Clone, Debug)]
+pub(crate) struct CapacityConfig {
+    pub(crate) check_interval: Duration,
+    /// warn if the usage ratio of any resource reaches this value
+    pub(crate) warn_ratio: f64,
+    /// shed new client connections if the usage ratio of any resource reaches this value
+    pub(crate) shed_ratio: Option<f64>,
+}
+
+impl Default for CapacityConfig {
+    fn default() -> Self {
+        CapacityConfig {
+            check_interval: Duration::from_secs(10),
+            warn_ratio: 0.8,
+            shed_ratio: None,
+        }
+    }
+}
+
+impl CapacityConfig {
+    fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let mut config = CapacityConfig::default();
+        match v {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "check_interval" | "interval" => {
+                        config.check_interval = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "warn_ratio" => {
+                        config.warn_ratio =
+                            as_ratio(v).context(format!("invalid value for key {k}"))?;
+                        Ok(())
+                    }
+                    "shed_ratio" => {
+                        let ratio = as_ratio(v).context(format!("invalid value for key {k}"))?;
+                        config.shed_ratio = Some(ratio);
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            Yaml::Boolean(true) => {}
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for capacity config should be 'map'"
+                ))
+            }
+        }
+        if config.check_interval < Duration::from_secs(1) {
+            config.check_interval = Duration::from_secs(1);
+        }
+        if let Some(shed_ratio) = config.shed_ratio {
+            if shed_ratio < config.warn_ratio {
+                return Err(anyhow!("shed ratio should not be less than warn ratio"));
+            }
+        }
+        Ok(config)
+    }
+}
+
+fn as_ratio(v: &Yaml) -> anyhow::Result<f64> {
+    let ratio = g3_yaml::value::as_f64(v)?;
+    if !(0.0..=1.0).contains(&ratio) {
+        return Err(anyhow!("the ratio should be in [0.0, 1.0]"));
+    }
+    Ok(ratio)
+}
+
+pub(crate) fn load(v: &Yaml) -> anyhow::Result<()> {
+    let config = CapacityConfig::parse_yaml(v)?;
+    CAPACITY_CONFIG
+        .set(config)
+        .map_err(|_| anyhow!("capacity config has already been set"))
+}
+
+pub(crate) fn get_config() -> Option<&'static CapacityConfig> {
+    CAPACITY_CONFIG.get()
+}
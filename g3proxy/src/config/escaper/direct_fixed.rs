@@ -21,14 +21,21 @@ use anyhow::{anyhow, Context};
 use ascii::AsciiString;
 use yaml_rust::{yaml, Yaml};
 
-use g3_types::acl::{AclAction, AclNetworkRuleBuilder};
+use g3_ip_locate::IpLocateServiceConfig;
+use g3_types::acl::{AclAction, AclChildDomainRuleBuilder, AclNetworkRuleBuilder};
 use g3_types::metrics::{NodeName, StaticMetricsTags};
 #[cfg(any(target_os = "linux", target_os = "android"))]
 use g3_types::net::InterfaceName;
-use g3_types::net::{HappyEyeballsConfig, TcpKeepAliveConfig, TcpMiscSockOpts, UdpMiscSockOpts};
+use g3_types::net::{
+    HappyEyeballsConfig, TcpConnectRaceConfig, TcpKeepAliveConfig, TcpMiscSockOpts, UdpMiscSockOpts,
+};
 use g3_types::resolve::{QueryStrategy, ResolveRedirectionBuilder, ResolveStrategy};
 use g3_yaml::YamlDocPosition;
 
+use super::egress_nat::EgressNatConfig;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use super::ipv6_rotate::Ipv6RotateConfig;
+use super::timeout_override::ConnectTimeoutOverrides;
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, GeneralEscaperConfig};
 
 const ESCAPER_CONFIG_TYPE: &str = "DirectFixed";
@@ -47,9 +54,16 @@ pub(crate) struct DirectFixedEscaperConfig {
     pub(crate) resolver: NodeName,
     pub(crate) resolve_strategy: ResolveStrategy,
     pub(crate) resolve_redirection: Option<ResolveRedirectionBuilder>,
+    pub(crate) resolve_rebind_allow: Option<AclChildDomainRuleBuilder>,
+    pub(crate) timeout_overrides: ConnectTimeoutOverrides,
     pub(crate) egress_net_filter: AclNetworkRuleBuilder,
+    pub(crate) ip_locate_service: Option<IpLocateServiceConfig>,
+    pub(crate) egress_nat: Option<EgressNatConfig>,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) ipv6_rotate: Option<Ipv6RotateConfig>,
     pub(crate) general: GeneralEscaperConfig,
     pub(crate) happy_eyeballs: HappyEyeballsConfig,
+    pub(crate) tcp_connect_race: TcpConnectRaceConfig,
     pub(crate) tcp_keepalive: TcpKeepAliveConfig,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) udp_misc_opts: UdpMiscSockOpts,
@@ -72,9 +86,16 @@ impl DirectFixedEscaperConfig {
             resolver: NodeName::default(),
             resolve_strategy: Default::default(),
             resolve_redirection: None,
+            resolve_rebind_allow: None,
+            timeout_overrides: ConnectTimeoutOverrides::default(),
             egress_net_filter: AclNetworkRuleBuilder::new_egress(AclAction::Permit),
+            ip_locate_service: None,
+            egress_nat: None,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            ipv6_rotate: None,
             general: Default::default(),
             happy_eyeballs: Default::default(),
+            tcp_connect_race: Default::default(),
             tcp_keepalive: Default::default(),
             tcp_misc_opts: Default::default(),
             udp_misc_opts: Default::default(),
@@ -143,6 +164,18 @@ impl DirectFixedEscaperConfig {
                 self.resolve_redirection = Some(redirect);
                 Ok(())
             }
+            "resolve_rebind_allow" => {
+                let builder = crate::config::resolver::as_rebind_allow_domains(v)
+                    .context(format!("invalid domain list value for key {k}"))?;
+                self.resolve_rebind_allow = Some(builder);
+                Ok(())
+            }
+            "connect_timeout_overrides" | "timeout_overrides" => {
+                self.timeout_overrides = ConnectTimeoutOverrides::parse_yaml(v).context(
+                    format!("invalid connect timeout overrides value for key {k}"),
+                )?;
+                Ok(())
+            }
             "enable_path_selection" => {
                 self.enable_path_selection = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -152,11 +185,46 @@ impl DirectFixedEscaperConfig {
                     .context(format!("invalid network acl rule value for key {k}"))?;
                 Ok(())
             }
+            "ip_locate_service" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let config = IpLocateServiceConfig::parse_yaml(v, Some(lookup_dir)).context(
+                    format!("invalid ip locate service config value for key {k}"),
+                )?;
+                self.ip_locate_service = Some(config);
+                Ok(())
+            }
+            "egress_nat" => {
+                let config = EgressNatConfig::parse_yaml(v)
+                    .context(format!("invalid egress nat config value for key {k}"))?;
+                self.egress_nat = Some(config);
+                Ok(())
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            "ipv6_rotate" | "bind_ipv6_prefix" => {
+                let config = Ipv6RotateConfig::parse_yaml(v)
+                    .context(format!("invalid ipv6 rotate config value for key {k}"))?;
+                self.ipv6_rotate = Some(config);
+                Ok(())
+            }
             "tcp_sock_speed_limit" | "tcp_conn_speed_limit" | "tcp_conn_limit" => {
                 self.general.tcp_sock_speed_limit = g3_yaml::value::as_tcp_sock_speed_limit(v)
                     .context(format!("invalid tcp conn socket limit value for key {k}"))?;
                 Ok(())
             }
+            "tcp_all_upload_speed_limit" => {
+                let limit = g3_yaml::value::as_global_stream_speed_limit(v).context(format!(
+                    "invalid global stream speed limit config value for key {k}"
+                ))?;
+                self.general.tcp_all_upload_speed_limit = Some(limit);
+                Ok(())
+            }
+            "tcp_all_download_speed_limit" => {
+                let limit = g3_yaml::value::as_global_stream_speed_limit(v).context(format!(
+                    "invalid global stream speed limit config value for key {k}"
+                ))?;
+                self.general.tcp_all_download_speed_limit = Some(limit);
+                Ok(())
+            }
             "udp_sock_speed_limit" | "udp_relay_speed_limit" | "udp_relay_limit" => {
                 self.general.udp_sock_speed_limit = g3_yaml::value::as_udp_sock_speed_limit(v)
                     .context(format!("invalid udp socket speed limit value for key {k}"))?;
@@ -195,6 +263,11 @@ impl DirectFixedEscaperConfig {
                     .context(format!("invalid happy eyeballs config value for key {k}"))?;
                 Ok(())
             }
+            "tcp_connect_race" => {
+                self.tcp_connect_race = g3_yaml::value::as_tcp_connect_race_config(v)
+                    .context(format!("invalid tcp connect race config value for key {k}"))?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -221,6 +294,18 @@ impl DirectFixedEscaperConfig {
             }
         }
 
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if self.ipv6_rotate.is_some() {
+            if !self.bind6.is_empty() {
+                return Err(anyhow!(
+                    "ipv6 bind ip should not be set if ipv6 rotate is enabled"
+                ));
+            }
+            if self.no_ipv6 {
+                return Err(anyhow!("ipv6 rotate is enabled but ipv6 is disabled"));
+            }
+        }
+
         Ok(())
     }
 
@@ -38,12 +38,16 @@ use ascii::AsciiString;
 use log::warn;
 use yaml_rust::{yaml, Yaml};
 
-use g3_types::acl::{AclAction, AclNetworkRuleBuilder};
+use g3_ip_locate::IpLocateServiceConfig;
+use g3_types::acl::{AclAction, AclChildDomainRuleBuilder, AclNetworkRuleBuilder};
 use g3_types::metrics::{NodeName, StaticMetricsTags};
-use g3_types::net::{HappyEyeballsConfig, TcpKeepAliveConfig, TcpMiscSockOpts, UdpMiscSockOpts};
+use g3_types::net::{
+    HappyEyeballsConfig, TcpConnectRaceConfig, TcpKeepAliveConfig, TcpMiscSockOpts, UdpMiscSockOpts,
+};
 use g3_types::resolve::{QueryStrategy, ResolveRedirectionBuilder, ResolveStrategy};
 use g3_yaml::YamlDocPosition;
 
+use super::timeout_override::ConnectTimeoutOverrides;
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, GeneralEscaperConfig};
 
 mod bind;
@@ -63,9 +67,13 @@ pub(crate) struct DirectFloatEscaperConfig {
     pub(crate) resolver: NodeName,
     pub(crate) resolve_strategy: ResolveStrategy,
     pub(crate) resolve_redirection: Option<ResolveRedirectionBuilder>,
+    pub(crate) resolve_rebind_allow: Option<AclChildDomainRuleBuilder>,
+    pub(crate) timeout_overrides: ConnectTimeoutOverrides,
     pub(crate) egress_net_filter: AclNetworkRuleBuilder,
+    pub(crate) ip_locate_service: Option<IpLocateServiceConfig>,
     pub(crate) general: GeneralEscaperConfig,
     pub(crate) happy_eyeballs: HappyEyeballsConfig,
+    pub(crate) tcp_connect_race: TcpConnectRaceConfig,
     pub(crate) tcp_keepalive: TcpKeepAliveConfig,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) udp_misc_opts: UdpMiscSockOpts,
@@ -85,9 +93,13 @@ impl DirectFloatEscaperConfig {
             resolver: NodeName::default(),
             resolve_strategy: Default::default(),
             resolve_redirection: None,
+            resolve_rebind_allow: None,
+            timeout_overrides: ConnectTimeoutOverrides::default(),
             egress_net_filter: AclNetworkRuleBuilder::new_egress(AclAction::Permit),
+            ip_locate_service: None,
             general: Default::default(),
             happy_eyeballs: Default::default(),
+            tcp_connect_race: Default::default(),
             tcp_keepalive: TcpKeepAliveConfig::default_enabled(),
             tcp_misc_opts: Default::default(),
             udp_misc_opts: Default::default(),
@@ -139,16 +151,50 @@ impl DirectFloatEscaperConfig {
                 self.resolve_redirection = Some(redirect);
                 Ok(())
             }
+            "resolve_rebind_allow" => {
+                let builder = crate::config::resolver::as_rebind_allow_domains(v)
+                    .context(format!("invalid domain list value for key {k}"))?;
+                self.resolve_rebind_allow = Some(builder);
+                Ok(())
+            }
+            "connect_timeout_overrides" | "timeout_overrides" => {
+                self.timeout_overrides = ConnectTimeoutOverrides::parse_yaml(v).context(
+                    format!("invalid connect timeout overrides value for key {k}"),
+                )?;
+                Ok(())
+            }
             "egress_network_filter" | "egress_net_filter" => {
                 self.egress_net_filter = g3_yaml::value::acl::as_egress_network_rule_builder(v)
                     .context(format!("invalid network acl rule value for key {k}"))?;
                 Ok(())
             }
+            "ip_locate_service" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let config = IpLocateServiceConfig::parse_yaml(v, Some(lookup_dir)).context(
+                    format!("invalid ip locate service config value for key {k}"),
+                )?;
+                self.ip_locate_service = Some(config);
+                Ok(())
+            }
             "tcp_sock_speed_limit" | "tcp_conn_speed_limit" | "tcp_conn_limit" => {
                 self.general.tcp_sock_speed_limit = g3_yaml::value::as_tcp_sock_speed_limit(v)
                     .context(format!("invalid tcp socket speed limit value for key {k}"))?;
                 Ok(())
             }
+            "tcp_all_upload_speed_limit" => {
+                let limit = g3_yaml::value::as_global_stream_speed_limit(v).context(format!(
+                    "invalid global stream speed limit config value for key {k}"
+                ))?;
+                self.general.tcp_all_upload_speed_limit = Some(limit);
+                Ok(())
+            }
+            "tcp_all_download_speed_limit" => {
+                let limit = g3_yaml::value::as_global_stream_speed_limit(v).context(format!(
+                    "invalid global stream speed limit config value for key {k}"
+                ))?;
+                self.general.tcp_all_download_speed_limit = Some(limit);
+                Ok(())
+            }
             "udp_sock_speed_limit" | "udp_relay_speed_limit" | "udp_relay_limit" => {
                 self.general.udp_sock_speed_limit = g3_yaml::value::as_udp_sock_speed_limit(v)
                     .context(format!("invalid udp socket speed limit value for key {k}"))?;
@@ -188,6 +234,11 @@ impl DirectFloatEscaperConfig {
                     .context(format!("invalid happy eyeballs config value for key {k}"))?;
                 Ok(())
             }
+            "tcp_connect_race" => {
+                self.tcp_connect_race = g3_yaml::value::as_tcp_connect_race_config(v)
+                    .context(format!("invalid tcp connect race config value for key {k}"))?;
+                Ok(())
+            }
             "tcp_keepalive" => {
                 self.tcp_keepalive = g3_yaml::value::as_tcp_keepalive_config(v)
                     .context(format!("invalid tcp keepalive config value for key {k}"))?;
@@ -26,7 +26,8 @@ use g3_types::metrics::{NodeName, StaticMetricsTags};
 #[cfg(any(target_os = "linux", target_os = "android"))]
 use g3_types::net::InterfaceName;
 use g3_types::net::{
-    HappyEyeballsConfig, Host, TcpKeepAliveConfig, TcpMiscSockOpts, WeightedUpstreamAddr,
+    HappyEyeballsConfig, Host, ProxyProtocolTlvConfig, TcpKeepAliveConfig, TcpMiscSockOpts,
+    WeightedUpstreamAddr,
 };
 use g3_types::resolve::{QueryStrategy, ResolveStrategy};
 use g3_yaml::YamlDocPosition;
@@ -55,6 +56,7 @@ pub(crate) struct DivertTcpEscaperConfig {
     pub(crate) tcp_keepalive: TcpKeepAliveConfig,
     pub(crate) tcp_misc_opts: TcpMiscSockOpts,
     pub(crate) extra_metrics_tags: Option<Arc<StaticMetricsTags>>,
+    pub(crate) proxy_protocol_tlv: ProxyProtocolTlvConfig,
 }
 
 impl DivertTcpEscaperConfig {
@@ -78,6 +80,7 @@ impl DivertTcpEscaperConfig {
             tcp_keepalive: Default::default(),
             tcp_misc_opts: Default::default(),
             extra_metrics_tags: None,
+            proxy_protocol_tlv: Default::default(),
         }
     }
 
@@ -111,6 +114,12 @@ impl DivertTcpEscaperConfig {
                 self.extra_metrics_tags = Some(Arc::new(tags));
                 Ok(())
             }
+            "proxy_protocol_tlv" => {
+                self.proxy_protocol_tlv = g3_yaml::value::as_proxy_protocol_tlv_config(v).context(
+                    format!("invalid PROXY protocol TLV config value for key {k}"),
+                )?;
+                Ok(())
+            }
             "proxy_addr" => {
                 self.proxy_nodes = g3_yaml::value::as_list(v, |v| {
                     g3_yaml::value::as_weighted_upstream_addr(v, 3128)
@@ -0,0 +1,96 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+/// Map the local bound ip to the public egress ip, which will be reported in logs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct EgressNatConfig {
+    pub(crate) static_map: BTreeMap<IpAddr, IpAddr>,
+    pub(crate) stun_server: Option<SocketAddr>,
+    pub(crate) probe_interval: Duration,
+    pub(crate) probe_timeout: Duration,
+}
+
+impl Default for EgressNatConfig {
+    fn default() -> Self {
+        EgressNatConfig {
+            static_map: BTreeMap::new(),
+            stun_server: None,
+            probe_interval: Duration::from_secs(300),
+            probe_timeout: Duration::from_secs(4),
+        }
+    }
+}
+
+impl EgressNatConfig {
+    fn parse_static_map(&mut self, v: &Yaml) -> anyhow::Result<()> {
+        if let Yaml::Hash(map) = v {
+            for (k, v) in map.iter() {
+                let local = g3_yaml::value::as_ipaddr(k).context("invalid local ip address")?;
+                let public = g3_yaml::value::as_ipaddr(v).context(format!(
+                    "invalid public ip address value for local ip {local}"
+                ))?;
+                self.static_map.insert(local, public);
+            }
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "yaml value type for 'egress nat static map' should be 'map'"
+            ))
+        }
+    }
+
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let mut config = EgressNatConfig::default();
+        if let Yaml::Hash(map) = v {
+            g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                "static_map" | "static" => config
+                    .parse_static_map(v)
+                    .context(format!("invalid egress nat static map value for key {k}")),
+                "stun_server" => {
+                    let addr = g3_yaml::value::as_sockaddr(v)
+                        .context(format!("invalid socket address value for key {k}"))?;
+                    config.stun_server = Some(addr);
+                    Ok(())
+                }
+                "probe_interval" => {
+                    config.probe_interval = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    Ok(())
+                }
+                "probe_timeout" => {
+                    config.probe_timeout = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    Ok(())
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+            if config.probe_interval.is_zero() {
+                return Err(anyhow!("probe interval should not be zero"));
+            }
+            Ok(config)
+        } else {
+            Err(anyhow!(
+                "yaml value type for 'egress nat config' should be 'map'"
+            ))
+        }
+    }
+}
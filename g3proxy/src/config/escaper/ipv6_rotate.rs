@@ -0,0 +1,200 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeSet;
+use std::net::{IpAddr, Ipv6Addr};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use ip_network::{IpNetwork, Ipv6Network};
+use yaml_rust::Yaml;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Ipv6RotateMode {
+    /// select a new source address for each connection
+    #[default]
+    PerConnection,
+    /// keep the same source address for the same user (or client ip) until the session expires
+    PerSession,
+}
+
+impl Ipv6RotateMode {
+    fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let s = g3_yaml::value::as_string(v)?;
+        match g3_yaml::key::normalize(&s).as_str() {
+            "per_connection" | "connection" => Ok(Ipv6RotateMode::PerConnection),
+            "per_session" | "session" | "per_user" | "user" => Ok(Ipv6RotateMode::PerSession),
+            _ => Err(anyhow!("invalid ipv6 rotate mode {s}")),
+        }
+    }
+}
+
+/// Rotate the ipv6 source address inside a routed prefix, by using IP_FREEBIND
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Ipv6RotateConfig {
+    pub(crate) prefix: Ipv6Network,
+    pub(crate) mode: Ipv6RotateMode,
+    pub(crate) session_ttl: Duration,
+    pub(crate) max_sessions: usize,
+    pub(crate) recent_size: usize,
+    pub(crate) exclude: BTreeSet<Ipv6Addr>,
+}
+
+impl Ipv6RotateConfig {
+    fn new(prefix: Ipv6Network) -> Self {
+        Ipv6RotateConfig {
+            prefix,
+            mode: Ipv6RotateMode::default(),
+            session_ttl: Duration::from_secs(600),
+            max_sessions: 65536,
+            recent_size: 4096,
+            exclude: BTreeSet::new(),
+        }
+    }
+
+    fn parse_prefix(v: &Yaml) -> anyhow::Result<Ipv6Network> {
+        match g3_yaml::value::as_ip_network(v)? {
+            IpNetwork::V6(net) => Ok(net),
+            IpNetwork::V4(_) => Err(anyhow!("the prefix should be an ipv6 network")),
+        }
+    }
+
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        match v {
+            Yaml::Hash(map) => {
+                let mut prefix = None;
+                let mut config = Ipv6RotateConfig::new(Ipv6Network::from(Ipv6Addr::UNSPECIFIED));
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "prefix" | "network" => {
+                        let net = Self::parse_prefix(v)
+                            .context(format!("invalid ipv6 network value for key {k}"))?;
+                        prefix = Some(net);
+                        Ok(())
+                    }
+                    "mode" => {
+                        config.mode = Ipv6RotateMode::parse_yaml(v)
+                            .context(format!("invalid ipv6 rotate mode value for key {k}"))?;
+                        Ok(())
+                    }
+                    "session_ttl" => {
+                        config.session_ttl = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_sessions" => {
+                        config.max_sessions = g3_yaml::value::as_usize(v)?;
+                        Ok(())
+                    }
+                    "recent_size" | "collision_window" => {
+                        config.recent_size = g3_yaml::value::as_usize(v)?;
+                        Ok(())
+                    }
+                    "exclude" => {
+                        let ips = g3_yaml::value::as_list(v, g3_yaml::value::as_ipaddr)
+                            .context(format!("invalid ip address list value for key {k}"))?;
+                        for ip in ips {
+                            let IpAddr::V6(ip6) = ip else {
+                                return Err(anyhow!("{ip} is not an ipv6 address"));
+                            };
+                            config.exclude.insert(ip6);
+                        }
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                let Some(prefix) = prefix else {
+                    return Err(anyhow!("no prefix set"));
+                };
+                config.prefix = prefix;
+                config.check()?;
+                Ok(config)
+            }
+            Yaml::String(_) => {
+                let prefix = Self::parse_prefix(v)?;
+                let config = Ipv6RotateConfig::new(prefix);
+                config.check()?;
+                Ok(config)
+            }
+            _ => Err(anyhow!(
+                "yaml value type for 'ipv6 rotate config' should be 'map' or 'string'"
+            )),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.prefix.netmask() > 120 {
+            return Err(anyhow!(
+                "the prefix {} is too small for address rotation",
+                self.prefix
+            ));
+        }
+        if self.prefix.is_multicast() || self.prefix.is_unicast_link_local() {
+            return Err(anyhow!(
+                "the prefix {} is not usable as source address",
+                self.prefix
+            ));
+        }
+        if self.mode == Ipv6RotateMode::PerSession && self.session_ttl.is_zero() {
+            return Err(anyhow!("session ttl should not be zero"));
+        }
+        Ok(())
+    }
+
+    /// the number of host bits that can be randomized
+    pub(crate) fn host_bits(&self) -> u32 {
+        128 - self.prefix.netmask() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_string() {
+        let v = Yaml::String("2001:db8:1:2::/64".to_string());
+        let config = Ipv6RotateConfig::parse_yaml(&v).unwrap();
+        assert_eq!(config.prefix.netmask(), 64);
+        assert_eq!(config.mode, Ipv6RotateMode::PerConnection);
+        assert_eq!(config.host_bits(), 64);
+
+        let v = Yaml::String("192.168.0.0/16".to_string());
+        assert!(Ipv6RotateConfig::parse_yaml(&v).is_err());
+
+        let v = Yaml::String("2001:db8::/124".to_string());
+        assert!(Ipv6RotateConfig::parse_yaml(&v).is_err());
+    }
+
+    #[test]
+    fn parse_map() {
+        let yaml = r#"
+            prefix: 2001:db8:1:2::/64
+            mode: per_session
+            session_ttl: 30s
+            exclude:
+              - 2001:db8:1:2::1
+        "#;
+        let docs = YamlLoader::load_from_str(yaml).unwrap();
+        let config = Ipv6RotateConfig::parse_yaml(&docs[0]).unwrap();
+        assert_eq!(config.mode, Ipv6RotateMode::PerSession);
+        assert_eq!(config.session_ttl, Duration::from_secs(30));
+        assert!(config.exclude.contains(&"2001:db8:1:2::1".parse().unwrap()));
+
+        let docs = YamlLoader::load_from_str("mode: per_session").unwrap();
+        assert!(Ipv6RotateConfig::parse_yaml(&docs[0]).is_err());
+    }
+}
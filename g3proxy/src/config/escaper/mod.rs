@@ -23,6 +23,7 @@ use slog::Logger;
 use yaml_rust::{yaml, Yaml};
 
 use g3_daemon::config::TopoMap;
+use g3_types::limit::GlobalStreamSpeedLimitConfig;
 use g3_types::metrics::NodeName;
 use g3_types::net::{TcpConnectConfig, TcpSockSpeedLimitConfig, UdpSockSpeedLimitConfig};
 use g3_yaml::{HybridParser, YamlDocPosition};
@@ -32,6 +33,9 @@ pub(crate) mod direct_fixed;
 pub(crate) mod direct_float;
 pub(crate) mod divert_tcp;
 pub(crate) mod dummy_deny;
+pub(crate) mod egress_nat;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) mod ipv6_rotate;
 pub(crate) mod proxy_float;
 pub(crate) mod proxy_http;
 pub(crate) mod proxy_https;
@@ -44,11 +48,13 @@ pub(crate) mod route_mapping;
 pub(crate) mod route_query;
 pub(crate) mod route_resolved;
 pub(crate) mod route_select;
+pub(crate) mod route_sni;
 pub(crate) mod route_upstream;
+pub(crate) mod timeout_override;
 pub(crate) mod trick_float;
 
 mod registry;
-pub(crate) use registry::clear;
+pub(crate) use registry::{clear, del, get_all_names, restore, snapshot};
 
 mod verify;
 use verify::EscaperConfigVerifier;
@@ -92,6 +98,8 @@ pub(crate) struct GeneralEscaperConfig {
     pub(crate) tcp_sock_speed_limit: TcpSockSpeedLimitConfig,
     pub(crate) udp_sock_speed_limit: UdpSockSpeedLimitConfig,
     pub(crate) tcp_connect: TcpConnectConfig,
+    pub(crate) tcp_all_upload_speed_limit: Option<GlobalStreamSpeedLimitConfig>,
+    pub(crate) tcp_all_download_speed_limit: Option<GlobalStreamSpeedLimitConfig>,
 }
 
 #[derive(Clone)]
@@ -112,6 +120,7 @@ pub(crate) enum AnyEscaperConfig {
     RouteMapping(route_mapping::RouteMappingEscaperConfig),
     RouteQuery(route_query::RouteQueryEscaperConfig),
     RouteSelect(route_select::RouteSelectEscaperConfig),
+    RouteSni(route_sni::RouteSniEscaperConfig),
     RouteUpstream(route_upstream::RouteUpstreamEscaperConfig),
     RouteClient(route_client::RouteClientEscaperConfig),
     TrickFloat(trick_float::TrickFloatEscaperConfig),
@@ -137,6 +146,7 @@ macro_rules! impl_transparent0 {
                 AnyEscaperConfig::RouteMapping(s) => s.$f(),
                 AnyEscaperConfig::RouteQuery(s) => s.$f(),
                 AnyEscaperConfig::RouteSelect(s) => s.$f(),
+                AnyEscaperConfig::RouteSni(s) => s.$f(),
                 AnyEscaperConfig::RouteUpstream(s) => s.$f(),
                 AnyEscaperConfig::RouteClient(s) => s.$f(),
                 AnyEscaperConfig::TrickFloat(s) => s.$f(),
@@ -165,6 +175,7 @@ macro_rules! impl_transparent1 {
                 AnyEscaperConfig::RouteMapping(s) => s.$f(p),
                 AnyEscaperConfig::RouteQuery(s) => s.$f(p),
                 AnyEscaperConfig::RouteSelect(s) => s.$f(p),
+                AnyEscaperConfig::RouteSni(s) => s.$f(p),
                 AnyEscaperConfig::RouteUpstream(s) => s.$f(p),
                 AnyEscaperConfig::RouteClient(s) => s.$f(p),
                 AnyEscaperConfig::TrickFloat(s) => s.$f(p),
@@ -291,6 +302,10 @@ fn load_escaper(
             let config = route_select::RouteSelectEscaperConfig::parse(map, position)?;
             Ok(AnyEscaperConfig::RouteSelect(config))
         }
+        "route_sni" | "routesni" | "route_server_name" => {
+            let config = route_sni::RouteSniEscaperConfig::parse(map, position)?;
+            Ok(AnyEscaperConfig::RouteSni(config))
+        }
         "route_upstream" | "routeupstream" => {
             let config = route_upstream::RouteUpstreamEscaperConfig::parse(map, position)?;
             Ok(AnyEscaperConfig::RouteUpstream(config))
@@ -28,8 +28,8 @@ use g3_types::metrics::{NodeName, StaticMetricsTags};
 #[cfg(any(target_os = "linux", target_os = "android"))]
 use g3_types::net::InterfaceName;
 use g3_types::net::{
-    HappyEyeballsConfig, Host, HttpForwardCapability, ProxyProtocolVersion, TcpKeepAliveConfig,
-    TcpMiscSockOpts, WeightedUpstreamAddr,
+    HappyEyeballsConfig, Host, HttpForwardCapability, ProxyProtocolTlvConfig, ProxyProtocolVersion,
+    TcpKeepAliveConfig, TcpMiscSockOpts, WeightedUpstreamAddr,
 };
 use g3_types::resolve::{QueryStrategy, ResolveStrategy};
 use g3_yaml::YamlDocPosition;
@@ -64,6 +64,7 @@ pub(crate) struct ProxyHttpEscaperConfig {
     pub(crate) append_http_headers: Vec<String>,
     pub(crate) pass_proxy_userid: bool,
     pub(crate) use_proxy_protocol: Option<ProxyProtocolVersion>,
+    pub(crate) proxy_protocol_tlv: ProxyProtocolTlvConfig,
     pub(crate) peer_negotiation_timeout: Duration,
     pub(crate) extra_metrics_tags: Option<Arc<StaticMetricsTags>>,
 }
@@ -95,6 +96,7 @@ impl ProxyHttpEscaperConfig {
             append_http_headers: Vec::new(),
             pass_proxy_userid: false,
             use_proxy_protocol: None,
+            proxy_protocol_tlv: Default::default(),
             peer_negotiation_timeout: Duration::from_secs(10),
             extra_metrics_tags: None,
         }
@@ -232,6 +234,12 @@ impl ProxyHttpEscaperConfig {
                 self.use_proxy_protocol = Some(version);
                 Ok(())
             }
+            "proxy_protocol_tlv" => {
+                self.proxy_protocol_tlv = g3_yaml::value::as_proxy_protocol_tlv_config(v).context(
+                    format!("invalid PROXY protocol TLV config value for key {k}"),
+                )?;
+                Ok(())
+            }
             "peer_negotiation_timeout" => {
                 self.peer_negotiation_timeout = g3_yaml::humanize::as_duration(v)
                     .context(format!("invalid humanize duration value for key {k}"))?;
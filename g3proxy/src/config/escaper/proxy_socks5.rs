@@ -65,6 +65,7 @@ pub(crate) struct ProxySocks5EscaperConfig {
     pub(crate) peer_negotiation_timeout: Duration,
     transmute_udp_peer_ip: Option<AHashMap<IpAddr, IpAddr>>,
     pub(crate) end_on_control_closed: bool,
+    pub(crate) udp_over_tcp: bool,
     pub(crate) extra_metrics_tags: Option<Arc<StaticMetricsTags>>,
 }
 
@@ -95,6 +96,7 @@ impl ProxySocks5EscaperConfig {
             peer_negotiation_timeout: Duration::from_secs(10),
             transmute_udp_peer_ip: None,
             end_on_control_closed: false,
+            udp_over_tcp: false,
             extra_metrics_tags: None,
         }
     }
@@ -249,6 +251,10 @@ impl ProxySocks5EscaperConfig {
                 self.end_on_control_closed = g3_yaml::value::as_bool(v)?;
                 Ok(())
             }
+            "udp_over_tcp" => {
+                self.udp_over_tcp = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
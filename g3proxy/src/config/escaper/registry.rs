@@ -36,7 +36,7 @@ pub(super) fn add(escaper: AnyEscaperConfig) -> Option<AnyEscaperConfig> {
     ht.insert(name, escaper).map(|old| old.as_ref().clone())
 }
 
-pub(super) fn del(name: &NodeName) {
+pub(crate) fn del(name: &NodeName) {
     let mut ht = INITIAL_ESCAPER_CONFIG_REGISTRY.lock().unwrap();
     ht.remove(name);
 }
@@ -46,7 +46,17 @@ pub(super) fn get(name: &NodeName) -> Option<Arc<AnyEscaperConfig>> {
     ht.get(name).cloned()
 }
 
-pub(super) fn get_all_names() -> Vec<NodeName> {
+pub(crate) fn get_all_names() -> Vec<NodeName> {
     let ht = INITIAL_ESCAPER_CONFIG_REGISTRY.lock().unwrap();
     ht.keys().cloned().collect()
 }
+
+pub(crate) fn snapshot() -> HashMap<NodeName, Arc<AnyEscaperConfig>> {
+    let ht = INITIAL_ESCAPER_CONFIG_REGISTRY.lock().unwrap();
+    ht.clone()
+}
+
+pub(crate) fn restore(snapshot: HashMap<NodeName, Arc<AnyEscaperConfig>>) {
+    let mut ht = INITIAL_ESCAPER_CONFIG_REGISTRY.lock().unwrap();
+    *ht = snapshot;
+}
@@ -96,9 +96,11 @@ impl RouteGeoIpEscaperConfig {
                 Ok(())
             }
             "ip_locate_service" => {
-                self.ip_locate_service = IpLocateServiceConfig::parse_yaml(v).context(format!(
-                    "invalid ip locate service config value for key {k}"
-                ))?;
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                self.ip_locate_service = IpLocateServiceConfig::parse_yaml(v, Some(lookup_dir))
+                    .context(format!(
+                        "invalid ip locate service config value for key {k}"
+                    ))?;
                 Ok(())
             }
             "geo_rules" | "geo_match" => {
@@ -0,0 +1,275 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::{yaml, Yaml};
+
+use g3_types::metrics::NodeName;
+use g3_types::route::HostPattern;
+use g3_yaml::YamlDocPosition;
+
+use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, EscaperConfigVerifier};
+
+const ESCAPER_CONFIG_TYPE: &str = "RouteSni";
+
+#[derive(Clone, Eq, PartialEq)]
+pub(crate) struct RouteSniEscaperConfig {
+    pub(crate) name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) exact_match_domain: BTreeMap<NodeName, BTreeSet<Arc<str>>>,
+    pub(crate) suffix_match_domain: BTreeMap<NodeName, BTreeSet<String>>,
+    pub(crate) regex_match_domain: Vec<(NodeName, Vec<HostPattern>)>,
+    pub(crate) default_next: NodeName,
+}
+
+impl RouteSniEscaperConfig {
+    pub(crate) fn new(position: Option<YamlDocPosition>) -> Self {
+        RouteSniEscaperConfig {
+            name: NodeName::default(),
+            position,
+            exact_match_domain: BTreeMap::new(),
+            suffix_match_domain: BTreeMap::new(),
+            regex_match_domain: Vec::new(),
+            default_next: NodeName::default(),
+        }
+    }
+
+    fn foreach_rule<F>(k: &str, v: &Yaml, mut f: F) -> anyhow::Result<()>
+    where
+        F: FnMut(&yaml::Hash) -> anyhow::Result<()>,
+    {
+        if let Yaml::Array(seq) = v {
+            for (i, rule) in seq.iter().enumerate() {
+                if let Yaml::Hash(map) = rule {
+                    f(map).context(format!("failed to parse rule {k}#{i}"))?;
+                } else {
+                    return Err(anyhow!("invalid value type for {k}#{i}"));
+                }
+            }
+            Ok(())
+        } else {
+            Err(anyhow!("invalid array value for key {k}"))
+        }
+    }
+
+    pub(super) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut config = Self::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| config.set(k, v))?;
+
+        config.check()?;
+        Ok(config)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_ESCAPER_TYPE => Ok(()),
+            super::CONFIG_KEY_ESCAPER_NAME => {
+                self.name = g3_yaml::value::as_metrics_name(v)?;
+                Ok(())
+            }
+            "exact_match" | "exact_rules" => {
+                RouteSniEscaperConfig::foreach_rule(k, v, |map| self.add_exact_match(map))
+            }
+            "suffix_match" | "suffix_rules" | "child_match" | "child_rules" => {
+                RouteSniEscaperConfig::foreach_rule(k, v, |map| self.add_suffix_match(map))
+            }
+            "regex_match" | "regex_rules" => {
+                RouteSniEscaperConfig::foreach_rule(k, v, |map| self.add_regex_match(map))
+            }
+            "default_next" => {
+                self.default_next = g3_yaml::value::as_metrics_name(v)?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.default_next.is_empty() {
+            return Err(anyhow!("no default next escaper is set"));
+        }
+        if !self.exact_match_domain.is_empty() {
+            EscaperConfigVerifier::check_duplicated_rule(&self.exact_match_domain)
+                .context("found duplicated domain for exact match")?;
+        }
+        if !self.suffix_match_domain.is_empty() {
+            EscaperConfigVerifier::check_duplicated_rule(&self.suffix_match_domain)
+                .context("found duplicated domain suffix for suffix match")?;
+        }
+        if !self.regex_match_domain.is_empty() {
+            self.check_duplicated_pattern()
+                .context("found duplicated pattern for regex match")?;
+        }
+        Ok(())
+    }
+
+    // the regex inside HostPattern is not used for ordering
+    #[allow(clippy::mutable_key_type)]
+    fn check_duplicated_pattern(&self) -> anyhow::Result<()> {
+        let mut table = BTreeMap::<&HostPattern, &NodeName>::new();
+        for (escaper, patterns) in &self.regex_match_domain {
+            for pattern in patterns {
+                if let Some(old_escaper) = table.insert(pattern, escaper) {
+                    return Err(anyhow!(
+                        "rule {pattern} is added both for escaper {escaper} and {old_escaper}"
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_domain_rule(
+        map: &yaml::Hash,
+        escaper: &mut NodeName,
+        all_domain: &mut BTreeSet<String>,
+    ) -> anyhow::Result<()> {
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "next" | "escaper" => {
+                *escaper = g3_yaml::value::as_metrics_name(v)?;
+                Ok(())
+            }
+            "domains" | "domain" => {
+                if let Yaml::Array(seq) = v {
+                    for (i, v) in seq.iter().enumerate() {
+                        let domain = g3_yaml::value::as_domain(v)
+                            .context(format!("invalid domain value for {k}:{i}"))?;
+                        all_domain.insert(domain);
+                    }
+                    Ok(())
+                } else {
+                    Err(anyhow!("invalid array value for key {k}"))
+                }
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+        if escaper.is_empty() {
+            return Err(anyhow!("no next escaper set"));
+        }
+        Ok(())
+    }
+
+    fn add_exact_match(&mut self, map: &yaml::Hash) -> anyhow::Result<()> {
+        let mut escaper = NodeName::default();
+        let mut all_domain = BTreeSet::<String>::new();
+        RouteSniEscaperConfig::parse_domain_rule(map, &mut escaper, &mut all_domain)?;
+        if !all_domain.is_empty() {
+            let all_domain = all_domain.into_iter().map(Arc::from).collect();
+            if let Some(_old) = self.exact_match_domain.insert(escaper.clone(), all_domain) {
+                return Err(anyhow!("found multiple entries for next escaper {escaper}"));
+            }
+        }
+        Ok(())
+    }
+
+    fn add_suffix_match(&mut self, map: &yaml::Hash) -> anyhow::Result<()> {
+        let mut escaper = NodeName::default();
+        let mut all_domain = BTreeSet::<String>::new();
+        RouteSniEscaperConfig::parse_domain_rule(map, &mut escaper, &mut all_domain)?;
+        if !all_domain.is_empty() {
+            if let Some(_old) = self.suffix_match_domain.insert(escaper.clone(), all_domain) {
+                return Err(anyhow!("found multiple entries for next escaper {escaper}"));
+            }
+        }
+        Ok(())
+    }
+
+    fn add_regex_match(&mut self, map: &yaml::Hash) -> anyhow::Result<()> {
+        let mut escaper = NodeName::default();
+        let mut all_pattern = Vec::<HostPattern>::new();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "next" | "escaper" => {
+                escaper = g3_yaml::value::as_metrics_name(v)?;
+                Ok(())
+            }
+            "patterns" | "pattern" => {
+                if let Yaml::Array(seq) = v {
+                    for (i, v) in seq.iter().enumerate() {
+                        let pattern = g3_yaml::value::as_host_regex_pattern(v)
+                            .context(format!("invalid host pattern value for {k}:{i}"))?;
+                        all_pattern.push(pattern);
+                    }
+                    Ok(())
+                } else {
+                    Err(anyhow!("invalid array value for key {k}"))
+                }
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+        if escaper.is_empty() {
+            return Err(anyhow!("no next escaper set"));
+        }
+        if !all_pattern.is_empty() {
+            self.regex_match_domain.push((escaper, all_pattern));
+        }
+        Ok(())
+    }
+}
+
+impl EscaperConfig for RouteSniEscaperConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn escaper_type(&self) -> &str {
+        ESCAPER_CONFIG_TYPE
+    }
+
+    fn resolver(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn diff_action(&self, new: &AnyEscaperConfig) -> EscaperConfigDiffAction {
+        let AnyEscaperConfig::RouteSni(new) = new else {
+            return EscaperConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return EscaperConfigDiffAction::NoAction;
+        }
+
+        EscaperConfigDiffAction::Reload
+    }
+
+    fn dependent_escaper(&self) -> Option<BTreeSet<NodeName>> {
+        let mut set = BTreeSet::new();
+        set.insert(self.default_next.clone());
+        for key in self.exact_match_domain.keys() {
+            set.insert(key.clone());
+        }
+        for key in self.suffix_match_domain.keys() {
+            set.insert(key.clone());
+        }
+        for (key, _) in &self.regex_match_domain {
+            set.insert(key.clone());
+        }
+        Some(set)
+    }
+}
@@ -24,6 +24,7 @@ use yaml_rust::{yaml, Yaml};
 
 use g3_types::metrics::NodeName;
 use g3_types::net::Host;
+use g3_types::route::HostPattern;
 use g3_yaml::YamlDocPosition;
 
 use super::{AnyEscaperConfig, EscaperConfig, EscaperConfigDiffAction, EscaperConfigVerifier};
@@ -39,6 +40,7 @@ pub(crate) struct RouteUpstreamEscaperConfig {
     pub(crate) subnet_match_ipaddr: BTreeMap<NodeName, BTreeSet<IpNetwork>>,
     pub(crate) radix_match_domain: BTreeMap<NodeName, BTreeSet<String>>,
     pub(crate) child_match_domain: BTreeMap<NodeName, BTreeSet<String>>,
+    pub(crate) pattern_match_domain: Vec<(NodeName, Vec<HostPattern>)>,
     pub(crate) default_next: NodeName,
 }
 
@@ -52,6 +54,7 @@ impl RouteUpstreamEscaperConfig {
             subnet_match_ipaddr: BTreeMap::new(),
             radix_match_domain: BTreeMap::new(),
             child_match_domain: BTreeMap::new(),
+            pattern_match_domain: Vec::new(),
             default_next: NodeName::default(),
         }
     }
@@ -105,6 +108,14 @@ impl RouteUpstreamEscaperConfig {
             "child_match" | "child_rules" => {
                 RouteUpstreamEscaperConfig::foreach_rule(k, v, |map| self.add_child_match(map))
             }
+            "glob_match" | "glob_rules" => RouteUpstreamEscaperConfig::foreach_rule(k, v, |map| {
+                self.add_pattern_match(map, g3_yaml::value::as_host_glob_pattern)
+            }),
+            "regex_match" | "regex_rules" => {
+                RouteUpstreamEscaperConfig::foreach_rule(k, v, |map| {
+                    self.add_pattern_match(map, g3_yaml::value::as_host_regex_pattern)
+                })
+            }
             "default_next" => {
                 self.default_next = g3_yaml::value::as_metrics_name(v)?;
                 Ok(())
@@ -140,6 +151,26 @@ impl RouteUpstreamEscaperConfig {
             EscaperConfigVerifier::check_duplicated_rule(&self.child_match_domain)
                 .context("found duplicated domain suffix for child match")?;
         }
+        if !self.pattern_match_domain.is_empty() {
+            self.check_duplicated_pattern()
+                .context("found duplicated pattern for glob / regex match")?;
+        }
+        Ok(())
+    }
+
+    // the regex inside HostPattern is not used for ordering
+    #[allow(clippy::mutable_key_type)]
+    fn check_duplicated_pattern(&self) -> anyhow::Result<()> {
+        let mut table = BTreeMap::<&HostPattern, &NodeName>::new();
+        for (escaper, patterns) in &self.pattern_match_domain {
+            for pattern in patterns {
+                if let Some(old_escaper) = table.insert(pattern, escaper) {
+                    return Err(anyhow!(
+                        "rule {pattern} is added both for escaper {escaper} and {old_escaper}"
+                    ));
+                }
+            }
+        }
         Ok(())
     }
 
@@ -290,6 +321,40 @@ impl RouteUpstreamEscaperConfig {
         }
         Ok(())
     }
+
+    fn add_pattern_match<F>(&mut self, map: &yaml::Hash, parse_pattern: F) -> anyhow::Result<()>
+    where
+        F: Fn(&Yaml) -> anyhow::Result<HostPattern>,
+    {
+        let mut escaper = NodeName::default();
+        let mut all_pattern = Vec::<HostPattern>::new();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "next" | "escaper" => {
+                escaper = g3_yaml::value::as_metrics_name(v)?;
+                Ok(())
+            }
+            "patterns" | "pattern" => {
+                if let Yaml::Array(seq) = v {
+                    for (i, v) in seq.iter().enumerate() {
+                        let pattern = parse_pattern(v)
+                            .context(format!("invalid host pattern value for {k}:{i}"))?;
+                        all_pattern.push(pattern);
+                    }
+                    Ok(())
+                } else {
+                    Err(anyhow!("invalid array value for key {k}"))
+                }
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+        if escaper.is_empty() {
+            return Err(anyhow!("no next escaper set"));
+        }
+        if !all_pattern.is_empty() {
+            self.pattern_match_domain.push((escaper, all_pattern));
+        }
+        Ok(())
+    }
 }
 
 impl EscaperConfig for RouteUpstreamEscaperConfig {
@@ -339,6 +404,9 @@ impl EscaperConfig for RouteUpstreamEscaperConfig {
         for key in self.child_match_domain.keys() {
             set.insert(key.clone());
         }
+        for (key, _) in &self.pattern_match_domain {
+            set.insert(key.clone());
+        }
         Some(set)
     }
 }
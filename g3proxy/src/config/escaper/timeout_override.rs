@@ -0,0 +1,133 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use ip_network::IpNetwork;
+use yaml_rust::Yaml;
+
+use g3_types::net::{Host, Ports, UpstreamAddr};
+
+/// Override the connect timeout and tls handshake timeout for matched upstream
+#[derive(Clone, Default, Eq, PartialEq)]
+pub(crate) struct ConnectTimeoutOverrideRule {
+    domains: Vec<String>,
+    networks: Vec<IpNetwork>,
+    ports: Option<Ports>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) tls_handshake_timeout: Option<Duration>,
+}
+
+impl ConnectTimeoutOverrideRule {
+    fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!(
+                "yaml value type for 'connect timeout override rule' should be 'map'"
+            ));
+        };
+
+        let mut rule = ConnectTimeoutOverrideRule::default();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "domain" | "domains" => {
+                rule.domains = g3_yaml::value::as_list(v, g3_yaml::value::as_domain)
+                    .context(format!("invalid domain list value for key {k}"))?;
+                Ok(())
+            }
+            "network" | "networks" => {
+                rule.networks = g3_yaml::value::as_list(v, g3_yaml::value::as_ip_network)
+                    .context(format!("invalid ip network list value for key {k}"))?;
+                Ok(())
+            }
+            "port" | "ports" => {
+                let ports = g3_yaml::value::as_ports(v)
+                    .context(format!("invalid ports value for key {k}"))?;
+                rule.ports = Some(ports);
+                Ok(())
+            }
+            "connect_timeout" | "tcp_connect_timeout" => {
+                let timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                rule.connect_timeout = Some(timeout);
+                Ok(())
+            }
+            "tls_handshake_timeout" | "handshake_timeout" => {
+                let timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                rule.tls_handshake_timeout = Some(timeout);
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        if rule.domains.is_empty() && rule.networks.is_empty() && rule.ports.is_none() {
+            return Err(anyhow!("no domain / network / port set in this rule"));
+        }
+        if rule.connect_timeout.is_none() && rule.tls_handshake_timeout.is_none() {
+            return Err(anyhow!("no timeout value set in this rule"));
+        }
+        Ok(rule)
+    }
+
+    fn match_host(&self, host: &Host) -> bool {
+        if self.domains.is_empty() && self.networks.is_empty() {
+            return true;
+        }
+        match host {
+            Host::Domain(domain) => self.domains.iter().any(|d| is_child_domain(domain, d)),
+            Host::Ip(ip) => self.networks.iter().any(|net| net.contains(*ip)),
+        }
+    }
+
+    fn match_upstream(&self, upstream: &UpstreamAddr) -> bool {
+        if let Some(ports) = &self.ports {
+            if !ports.contains(upstream.port()) {
+                return false;
+            }
+        }
+        self.match_host(upstream.host())
+    }
+}
+
+fn is_child_domain(domain: &str, parent: &str) -> bool {
+    let domain = domain.as_bytes();
+    let parent = parent.as_bytes();
+    if domain.len() == parent.len() {
+        return domain.eq_ignore_ascii_case(parent);
+    }
+    if domain.len() < parent.len() {
+        return false;
+    }
+    let offset = domain.len() - parent.len();
+    domain[offset - 1] == b'.' && domain[offset..].eq_ignore_ascii_case(parent)
+}
+
+#[derive(Clone, Default, Eq, PartialEq)]
+pub(crate) struct ConnectTimeoutOverrides {
+    rules: Vec<ConnectTimeoutOverrideRule>,
+}
+
+impl ConnectTimeoutOverrides {
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let rules = g3_yaml::value::as_list(v, ConnectTimeoutOverrideRule::parse_yaml)?;
+        Ok(ConnectTimeoutOverrides { rules })
+    }
+
+    /// Get the first matched rule for the upstream
+    pub(crate) fn get(&self, upstream: &UpstreamAddr) -> Option<&ConnectTimeoutOverrideRule> {
+        self.rules.iter().find(|r| r.match_upstream(upstream))
+    }
+}
@@ -0,0 +1,209 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use http::{header, HeaderName};
+use regex::Regex;
+use yaml_rust::Yaml;
+
+use g3_types::net::HttpHeaderValue;
+
+#[derive(Clone, Debug)]
+pub(crate) enum HttpHeaderRewriteAction {
+    /// append a new value
+    Add(String),
+    /// replace all existing values
+    Set(String),
+    Remove,
+    /// regex replace on each existing value
+    Replace(Regex, String),
+}
+
+impl PartialEq for HttpHeaderRewriteAction {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (HttpHeaderRewriteAction::Add(a), HttpHeaderRewriteAction::Add(b)) => a == b,
+            (HttpHeaderRewriteAction::Set(a), HttpHeaderRewriteAction::Set(b)) => a == b,
+            (HttpHeaderRewriteAction::Remove, HttpHeaderRewriteAction::Remove) => true,
+            (
+                HttpHeaderRewriteAction::Replace(r1, s1),
+                HttpHeaderRewriteAction::Replace(r2, s2),
+            ) => r1.as_str() == r2.as_str() && s1 == s2,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for HttpHeaderRewriteAction {}
+
+impl HttpHeaderRewriteAction {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            HttpHeaderRewriteAction::Add(_) => "add",
+            HttpHeaderRewriteAction::Set(_) => "set",
+            HttpHeaderRewriteAction::Remove => "remove",
+            HttpHeaderRewriteAction::Replace(_, _) => "replace",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct HttpHeaderRewriteRule {
+    pub(crate) id: String,
+    pub(crate) header: HeaderName,
+    pub(crate) action: HttpHeaderRewriteAction,
+}
+
+impl HttpHeaderRewriteRule {
+    fn parse_yaml(value: &Yaml, default_id: String) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!(
+                "yaml value type for 'http header rewrite rule' should be 'map'"
+            ));
+        };
+
+        let mut id = default_id;
+        let mut header: Option<HeaderName> = None;
+        let mut action: Option<String> = None;
+        let mut value: Option<String> = None;
+        let mut regex: Option<Regex> = None;
+        let mut replacement = String::new();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "id" => {
+                id = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                Ok(())
+            }
+            "header" | "name" => {
+                let name = g3_yaml::value::as_http_header_name(v)
+                    .context(format!("invalid http header name value for key {k}"))?;
+                header = Some(name);
+                Ok(())
+            }
+            "action" => {
+                let s = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                action = Some(s.to_lowercase());
+                Ok(())
+            }
+            "value" => {
+                let s = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                HttpHeaderValue::from_str(&s)
+                    .map_err(|_| anyhow!("invalid http header value for key {k}"))?;
+                value = Some(s);
+                Ok(())
+            }
+            "regex" => {
+                let s = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                let r =
+                    Regex::new(&s).map_err(|e| anyhow!("invalid regex value for key {k}: {e}"))?;
+                regex = Some(r);
+                Ok(())
+            }
+            "replacement" => {
+                replacement = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        let Some(header) = header else {
+            return Err(anyhow!("no header set"));
+        };
+        if header == header::CONTENT_LENGTH
+            || header == header::TRANSFER_ENCODING
+            || header == header::CONNECTION
+        {
+            return Err(anyhow!("header {header} is not allowed to be rewritten"));
+        }
+        let action = match action.as_deref() {
+            Some("add") | Some("append") => {
+                let value = value.ok_or_else(|| anyhow!("no value set for add action"))?;
+                HttpHeaderRewriteAction::Add(value)
+            }
+            Some("set") => {
+                let value = value.ok_or_else(|| anyhow!("no value set for set action"))?;
+                HttpHeaderRewriteAction::Set(value)
+            }
+            Some("remove") | Some("delete") => HttpHeaderRewriteAction::Remove,
+            Some("replace") => {
+                let regex = regex.ok_or_else(|| anyhow!("no regex set for replace action"))?;
+                HttpHeaderRewriteAction::Replace(regex, replacement)
+            }
+            Some(s) => return Err(anyhow!("unsupported action {s}")),
+            None => return Err(anyhow!("no action set")),
+        };
+
+        Ok(HttpHeaderRewriteRule { id, header, action })
+    }
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub(crate) struct HttpHeaderRewriteConfig {
+    pub(crate) request: Vec<HttpHeaderRewriteRule>,
+    pub(crate) response: Vec<HttpHeaderRewriteRule>,
+}
+
+impl HttpHeaderRewriteConfig {
+    pub(crate) fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        if let Yaml::Hash(map) = value {
+            let mut config = HttpHeaderRewriteConfig::default();
+            g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                "request" => {
+                    config.request = parse_rules(v, "request")
+                        .context(format!("invalid http header rewrite rules for key {k}"))?;
+                    Ok(())
+                }
+                "response" => {
+                    config.response = parse_rules(v, "response")
+                        .context(format!("invalid http header rewrite rules for key {k}"))?;
+                    Ok(())
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+            Ok(config)
+        } else {
+            Err(anyhow!(
+                "yaml value type for 'http header rewrite config' should be 'map'"
+            ))
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.request.is_empty() && self.response.is_empty()
+    }
+}
+
+fn parse_rules(value: &Yaml, direction: &str) -> anyhow::Result<Vec<HttpHeaderRewriteRule>> {
+    if let Yaml::Array(seq) = value {
+        let mut rules = Vec::with_capacity(seq.len());
+        for (i, v) in seq.iter().enumerate() {
+            let rule = HttpHeaderRewriteRule::parse_yaml(v, format!("{direction}#{i}"))
+                .context(format!("invalid http header rewrite rule value for #{i}"))?;
+            rules.push(rule);
+        }
+        Ok(rules)
+    } else {
+        Err(anyhow!(
+            "yaml value type for 'http header rewrite rules' should be 'seq'"
+        ))
+    }
+}
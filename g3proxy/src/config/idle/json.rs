@@ -0,0 +1,71 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::{anyhow, Context};
+use serde_json::Value;
+
+use super::TaskIdlePolicy;
+
+impl TaskIdlePolicy {
+    pub(crate) fn parse_json(v: &Value) -> anyhow::Result<Self> {
+        if let Value::Object(map) = v {
+            let mut policy = TaskIdlePolicy::default();
+            for (k, v) in map {
+                let count =
+                    g3_json::value::as_i32(v).context(format!("invalid i32 value for key {k}"))?;
+                match g3_json::key::normalize(k).as_str() {
+                    "tunnel" | "connect" => policy.tunnel = Some(count),
+                    "websocket" => policy.websocket = Some(count),
+                    "keepalive" | "keep_alive" | "h1_keepalive" => policy.keepalive = Some(count),
+                    "read" | "client_read" => policy.read = Some(count),
+                    "write" | "client_write" => policy.write = Some(count),
+                    _ => return Err(anyhow!("invalid key {k}")),
+                }
+            }
+            Ok(policy)
+        } else {
+            Err(anyhow!(
+                "json value type for 'task idle policy' should be 'map'"
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parse_object() {
+        let v = json!({"connect": 3, "h1_keepalive": 1, "write": 4});
+        let policy = TaskIdlePolicy::parse_json(&v).unwrap();
+        assert_eq!(policy.tunnel, Some(3));
+        assert_eq!(policy.websocket, None);
+        assert_eq!(policy.keepalive, Some(1));
+        assert_eq!(policy.read, None);
+        assert_eq!(policy.write, Some(4));
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let v = json!({"udp": 1});
+        assert!(TaskIdlePolicy::parse_json(&v).is_err());
+
+        let v = json!([1, 2]);
+        assert!(TaskIdlePolicy::parse_json(&v).is_err());
+    }
+}
@@ -0,0 +1,120 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+mod json;
+mod yaml;
+
+/// The kind of relay, which determines which idle budget will be used
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum TaskIdleProtocol {
+    /// plain stream tunnels, such as http CONNECT and socks tcp connect
+    Tunnel,
+    Websocket,
+    /// idle client side HTTP/1.x connections waiting for the next request
+    KeepAlive,
+}
+
+/// Protocol aware idle policy.
+///
+/// All values are the max idle count of the task idle check duration,
+/// `None` means to use the common task idle max count.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct TaskIdlePolicy {
+    pub(crate) tunnel: Option<i32>,
+    pub(crate) websocket: Option<i32>,
+    pub(crate) keepalive: Option<i32>,
+    /// idle if no data received from the client, even if there is data sent to the client
+    pub(crate) read: Option<i32>,
+    /// idle if no data sent to the client, even if there is data received from the client
+    pub(crate) write: Option<i32>,
+}
+
+impl TaskIdlePolicy {
+    pub(crate) fn protocol_max_idle_count(&self, protocol: TaskIdleProtocol) -> Option<i32> {
+        match protocol {
+            TaskIdleProtocol::Tunnel => self.tunnel,
+            TaskIdleProtocol::Websocket => self.websocket,
+            TaskIdleProtocol::KeepAlive => self.keepalive,
+        }
+    }
+
+    /// Get the idle timeout for client side HTTP/1.x keep-alive connections.
+    ///
+    /// `None` means to use the server level pipeline read idle timeout.
+    pub(crate) fn keepalive_idle_timeout(&self, check_duration: Duration) -> Option<Duration> {
+        self.protocol_max_idle_count(TaskIdleProtocol::KeepAlive)
+            .filter(|count| *count > 0)
+            .map(|count| check_duration.saturating_mul(count as u32))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn protocol_select() {
+        let policy = TaskIdlePolicy {
+            tunnel: Some(1),
+            websocket: Some(2),
+            keepalive: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.protocol_max_idle_count(TaskIdleProtocol::Tunnel),
+            Some(1)
+        );
+        assert_eq!(
+            policy.protocol_max_idle_count(TaskIdleProtocol::Websocket),
+            Some(2)
+        );
+        assert_eq!(
+            policy.protocol_max_idle_count(TaskIdleProtocol::KeepAlive),
+            Some(3)
+        );
+
+        let policy = TaskIdlePolicy::default();
+        assert_eq!(
+            policy.protocol_max_idle_count(TaskIdleProtocol::Tunnel),
+            None
+        );
+    }
+
+    #[test]
+    fn keepalive_timeout() {
+        let check_duration = Duration::from_secs(60);
+
+        let policy = TaskIdlePolicy::default();
+        assert_eq!(policy.keepalive_idle_timeout(check_duration), None);
+
+        let policy = TaskIdlePolicy {
+            keepalive: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(policy.keepalive_idle_timeout(check_duration), None);
+
+        let policy = TaskIdlePolicy {
+            keepalive: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.keepalive_idle_timeout(check_duration),
+            Some(Duration::from_secs(120))
+        );
+    }
+}
@@ -0,0 +1,81 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use super::TaskIdlePolicy;
+
+impl TaskIdlePolicy {
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        if let Yaml::Hash(map) = v {
+            let mut policy = TaskIdlePolicy::default();
+            g3_yaml::foreach_kv(map, |k, v| {
+                let count =
+                    g3_yaml::value::as_i32(v).context(format!("invalid i32 value for key {k}"))?;
+                match g3_yaml::key::normalize(k).as_str() {
+                    "tunnel" | "connect" => policy.tunnel = Some(count),
+                    "websocket" => policy.websocket = Some(count),
+                    "keepalive" | "keep_alive" | "h1_keepalive" => policy.keepalive = Some(count),
+                    "read" | "client_read" => policy.read = Some(count),
+                    "write" | "client_write" => policy.write = Some(count),
+                    _ => return Err(anyhow!("invalid key {k}")),
+                }
+                Ok(())
+            })?;
+            Ok(policy)
+        } else {
+            Err(anyhow!(
+                "yaml value type for 'task idle policy' should be 'map'"
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn parse_map() {
+        let yaml = r#"
+            tunnel: 10
+            websocket: 20
+            keep_alive: 2
+            client_read: 5
+        "#;
+        let docs = YamlLoader::load_from_str(yaml).unwrap();
+        let policy = TaskIdlePolicy::parse_yaml(&docs[0]).unwrap();
+        assert_eq!(policy.tunnel, Some(10));
+        assert_eq!(policy.websocket, Some(20));
+        assert_eq!(policy.keepalive, Some(2));
+        assert_eq!(policy.read, Some(5));
+        assert_eq!(policy.write, None);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        let docs = YamlLoader::load_from_str("ftp: 1").unwrap();
+        assert!(TaskIdlePolicy::parse_yaml(&docs[0]).is_err());
+
+        let docs = YamlLoader::load_from_str("tunnel: abc").unwrap();
+        assert!(TaskIdlePolicy::parse_yaml(&docs[0]).is_err());
+
+        let v = Yaml::Integer(1);
+        assert!(TaskIdlePolicy::parse_yaml(&v).is_err());
+    }
+}
@@ -20,6 +20,7 @@ use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
 use g3_daemon::log::{LogConfig, LogConfigContainer};
+use g3_types::limit::RateLimitQuotaConfig;
 use g3_types::sync::GlobalInit;
 
 static RESOLVE_DEFAULT_LOG_CONFIG_CONTAINER: GlobalInit<LogConfigContainer> =
@@ -30,6 +31,9 @@ static AUDIT_DEFAULT_LOG_CONFIG_CONTAINER: GlobalInit<LogConfigContainer> =
     GlobalInit::new(LogConfigContainer::new());
 static TASK_DEFAULT_LOG_CONFIG_CONTAINER: GlobalInit<LogConfigContainer> =
     GlobalInit::new(LogConfigContainer::new());
+// the flow log channel won't inherit the default config, it should be set explicitly
+static FLOW_LOG_CONFIG: GlobalInit<Option<LogConfig>> = GlobalInit::new(None);
+static FLOW_LOG_RATE_LIMIT: GlobalInit<Option<RateLimitQuotaConfig>> = GlobalInit::new(None);
 
 pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
     let mut default_log_config: Option<LogConfig> = None;
@@ -58,6 +62,12 @@ pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
                     default_log_config = Some(config);
                     Ok(())
                 }
+                "kafka" => {
+                    let config = LogConfig::parse_kafka_yaml(v, crate::build::PKG_NAME)
+                        .context(format!("invalid kafka config value for key {k}"))?;
+                    default_log_config = Some(config);
+                    Ok(())
+                }
                 "resolve" => {
                     let config = LogConfig::parse_yaml(v, conf_dir, crate::build::PKG_NAME)
                         .context(format!("invalid value for key {k}"))?;
@@ -82,6 +92,18 @@ pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
                     TASK_DEFAULT_LOG_CONFIG_CONTAINER.with_mut(|l| l.set(config));
                     Ok(())
                 }
+                "flow" => {
+                    let config = LogConfig::parse_yaml(v, conf_dir, crate::build::PKG_NAME)
+                        .context(format!("invalid value for key {k}"))?;
+                    FLOW_LOG_CONFIG.set(Some(config));
+                    Ok(())
+                }
+                "flow_rate_limit" => {
+                    let quota = g3_yaml::value::as_rate_limit_quota(v)
+                        .context(format!("invalid request quota value for key {k}"))?;
+                    FLOW_LOG_RATE_LIMIT.set(Some(quota));
+                    Ok(())
+                }
                 _ => Err(anyhow!("invalid key {k}")),
             })?;
         }
@@ -120,3 +142,11 @@ pub(crate) fn get_task_default_config() -> LogConfig {
         .as_ref()
         .get(crate::build::PKG_NAME)
 }
+
+pub(crate) fn get_flow_config() -> Option<LogConfig> {
+    FLOW_LOG_CONFIG.as_ref().clone()
+}
+
+pub(crate) fn get_flow_rate_limit() -> Option<RateLimitQuotaConfig> {
+    FLOW_LOG_RATE_LIMIT.as_ref().clone()
+}
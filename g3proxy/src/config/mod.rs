@@ -14,11 +14,15 @@
  * limitations under the License.
  */
 
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::sync::Arc;
 
 use anyhow::anyhow;
 use yaml_rust::{yaml, Yaml};
 
+use g3_types::metrics::NodeName;
+
 mod graphviz;
 pub use graphviz::graphviz_graph;
 
@@ -30,10 +34,16 @@ pub use plantuml::plantuml_graph;
 
 pub(crate) mod audit;
 pub(crate) mod auth;
+pub(crate) mod canary;
+pub(crate) mod capacity;
 pub(crate) mod escaper;
+pub(crate) mod http_header_rewrite;
+pub(crate) mod idle;
 pub(crate) mod log;
+pub(crate) mod preflight;
 pub(crate) mod resolver;
 pub(crate) mod server;
+pub(crate) mod tenant;
 
 pub fn load() -> anyhow::Result<&'static Path> {
     let config_file =
@@ -54,16 +64,93 @@ fn clear_all() {
     auth::clear();
     server::clear();
     resolver::clear();
+    tenant::clear();
+}
+
+/// All the reloadable configs in the registries
+pub(crate) struct ConfigGeneration {
+    resolver: HashMap<NodeName, Arc<resolver::AnyResolverConfig>>,
+    escaper: HashMap<NodeName, Arc<escaper::AnyEscaperConfig>>,
+    user_group: HashMap<String, Arc<auth::UserGroupConfig>>,
+    auditor: HashMap<String, Arc<audit::AuditorConfig>>,
+    server: HashMap<NodeName, Arc<server::AnyServerConfig>>,
+    tenant: HashMap<NodeName, Arc<tenant::TenantConfig>>,
+}
+
+impl ConfigGeneration {
+    pub(crate) fn take() -> Self {
+        ConfigGeneration {
+            resolver: resolver::snapshot(),
+            escaper: escaper::snapshot(),
+            user_group: auth::snapshot(),
+            auditor: audit::snapshot(),
+            server: server::snapshot(),
+            tenant: tenant::snapshot(),
+        }
+    }
+
+    pub(crate) fn restore(self) {
+        resolver::restore(self.resolver);
+        escaper::restore(self.escaper);
+        auth::restore(self.user_group);
+        audit::restore(self.auditor);
+        server::restore(self.server);
+        tenant::restore(self.tenant);
+    }
+
+    /// Get the escapers that are new or changed in the current generation
+    pub(crate) fn changed_escapers(&self) -> HashSet<NodeName> {
+        use escaper::{EscaperConfig, EscaperConfigDiffAction};
+
+        escaper::snapshot()
+            .into_iter()
+            .filter(|(name, new)| match self.escaper.get(name) {
+                Some(old) => !matches!(old.diff_action(new), EscaperConfigDiffAction::NoAction),
+                None => true,
+            })
+            .map(|(name, _)| name)
+            .collect()
+    }
 }
 
-pub(crate) async fn reload() -> anyhow::Result<()> {
+/// make sure there is no broken dependency
+fn check_dependency() -> anyhow::Result<()> {
+    resolver::get_all_sorted()?;
+    escaper::get_all_sorted()?;
+    server::get_all_sorted()?;
+    Ok(())
+}
+
+/// Reload all the reloadable configs.
+///
+/// The registries will be kept unchanged if there is any error in the new config, or the previous
+/// generation will be returned so it can be used for rollback.
+pub(crate) async fn reload() -> anyhow::Result<ConfigGeneration> {
     tokio::task::spawn_blocking(reload_blocking)
         .await
         .map_err(|e| anyhow!("failed to join reload task: {e}"))?
 }
 
-fn reload_blocking() -> anyhow::Result<()> {
+fn reload_blocking() -> anyhow::Result<ConfigGeneration> {
+    reload_with(load_all_reloadable)
+}
+
+fn reload_with<F>(load: F) -> anyhow::Result<ConfigGeneration>
+where
+    F: FnOnce() -> anyhow::Result<()>,
+{
+    let generation = ConfigGeneration::take();
     clear_all();
+    match load() {
+        Ok(_) => Ok(generation),
+        Err(e) => {
+            generation.restore();
+            Err(e)
+        }
+    }
+}
+
+fn load_all_reloadable() -> anyhow::Result<()> {
     if let Some(conf_file) = g3_daemon::opts::config_file() {
         // allow multiple docs, and treat them as the same
         g3_yaml::foreach_doc(conf_file, |_, doc| match doc {
@@ -71,24 +158,45 @@ fn reload_blocking() -> anyhow::Result<()> {
             _ => Err(anyhow!("yaml doc root should be hash")),
         })?;
     }
-    Ok(())
+    check_dependency()
 }
 
 fn reload_doc(map: &yaml::Hash) -> anyhow::Result<()> {
     let conf_dir =
         g3_daemon::opts::config_dir().ok_or_else(|| anyhow!("no valid config dir has been set"))?;
     g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
-        "runtime" | "worker" | "log" | "stat" | "controller" => Ok(()),
+        "runtime" | "worker" | "log" | "stat" | "controller" | "schedule" | "preflight"
+        | "canary" | "capacity" => Ok(()),
         "escaper" => escaper::load_all(v, conf_dir),
         "server" => server::load_all(v, conf_dir),
         "resolver" => resolver::load_all(v, conf_dir),
         "user" | "user_group" => auth::load_all(v, conf_dir),
         "auditor" => audit::load_all(v, conf_dir),
+        "tenant" => tenant::load_all(v, conf_dir),
         _ => Ok(()),
     })?;
     Ok(())
 }
 
+const MAIN_CONF_KEYS: &[&str] = &[
+    "runtime",
+    "worker",
+    "log",
+    "stat",
+    "controller",
+    "schedule",
+    "preflight",
+    "canary",
+    "capacity",
+    "escaper",
+    "server",
+    "resolver",
+    "user",
+    "user_group",
+    "auditor",
+    "tenant",
+];
+
 fn load_doc(map: &yaml::Hash) -> anyhow::Result<()> {
     let conf_dir =
         g3_daemon::opts::config_dir().ok_or_else(|| anyhow!("no valid config dir has been set"))?;
@@ -98,12 +206,169 @@ fn load_doc(map: &yaml::Hash) -> anyhow::Result<()> {
         "log" => log::load(v, conf_dir),
         "stat" => g3_daemon::stat::config::load(v, crate::build::PKG_NAME),
         "controller" => g3_daemon::control::config::load(v),
+        "schedule" => g3_daemon::schedule::load(v, crate::control::SCHEDULE_ACTIONS),
+        "preflight" => preflight::load(v),
+        "canary" => canary::load(v),
+        "capacity" => capacity::load(v),
         "escaper" => escaper::load_all(v, conf_dir),
         "server" => server::load_all(v, conf_dir),
         "resolver" => resolver::load_all(v, conf_dir),
         "user" | "user_group" => auth::load_all(v, conf_dir),
         "auditor" => audit::load_all(v, conf_dir),
-        _ => Err(anyhow!("invalid key {k} in main conf")),
+        "tenant" => tenant::load_all(v, conf_dir),
+        _ => match g3_yaml::key::suggest(k, MAIN_CONF_KEYS) {
+            Some(s) => Err(anyhow!("invalid key {k} in main conf, did you mean {s}?")),
+            None => Err(anyhow!("invalid key {k} in main conf")),
+        },
     })?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use yaml_rust::YamlLoader;
+
+    // the registries are global, so tests touching them should not run concurrently
+    static REGISTRY_LOCK: Mutex<()> = Mutex::new(());
+
+    fn load_escapers(yaml: &str) -> anyhow::Result<()> {
+        let docs = YamlLoader::load_from_str(yaml).unwrap();
+        escaper::load_all(&docs[0], Path::new("."))
+    }
+
+    fn escaper_names() -> Vec<String> {
+        let mut names: Vec<String> = escaper::get_all_names()
+            .into_iter()
+            .map(|n| n.to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn take_restore() {
+        let _guard = REGISTRY_LOCK.lock().unwrap();
+        clear_all();
+
+        load_escapers(
+            r#"
+            - name: deny1
+              type: dummy_deny
+            - name: deny2
+              type: dummy_deny
+            "#,
+        )
+        .unwrap();
+        let generation = ConfigGeneration::take();
+        assert_eq!(generation.escaper.len(), 2);
+
+        clear_all();
+        assert!(escaper_names().is_empty());
+
+        generation.restore();
+        assert_eq!(escaper_names(), ["deny1", "deny2"]);
+        clear_all();
+    }
+
+    #[test]
+    fn reload_rollback() {
+        let _guard = REGISTRY_LOCK.lock().unwrap();
+        clear_all();
+
+        load_escapers(
+            r#"
+            - name: deny1
+              type: dummy_deny
+            "#,
+        )
+        .unwrap();
+
+        // error after partially loaded
+        let r = reload_with(|| {
+            load_escapers(
+                r#"
+                - name: deny2
+                  type: dummy_deny
+                "#,
+            )?;
+            Err(anyhow!("load failed"))
+        });
+        assert!(r.is_err());
+        assert_eq!(escaper_names(), ["deny1"]);
+
+        // duplicated escaper name
+        let r = reload_with(|| {
+            load_escapers(
+                r#"
+                - name: deny2
+                  type: dummy_deny
+                - name: deny2
+                  type: dummy_deny
+                "#,
+            )
+        });
+        assert!(r.is_err());
+        assert_eq!(escaper_names(), ["deny1"]);
+
+        // the returned generation can be used to rollback
+        let generation = reload_with(|| {
+            load_escapers(
+                r#"
+                - name: deny2
+                  type: dummy_deny
+                "#,
+            )
+        })
+        .unwrap();
+        assert_eq!(escaper_names(), ["deny2"]);
+        generation.restore();
+        assert_eq!(escaper_names(), ["deny1"]);
+        clear_all();
+    }
+
+    #[test]
+    fn changed_escapers() {
+        let _guard = REGISTRY_LOCK.lock().unwrap();
+        clear_all();
+
+        load_escapers(
+            r#"
+            - name: deny
+              type: dummy_deny
+            - name: direct
+              type: direct_fixed
+              resolver: default
+            - name: removed
+              type: dummy_deny
+            "#,
+        )
+        .unwrap();
+
+        let generation = reload_with(|| {
+            load_escapers(
+                r#"
+                - name: deny
+                  type: dummy_deny
+                - name: direct
+                  type: direct_fixed
+                  resolver: default
+                  no_ipv6: true
+                - name: added
+                  type: dummy_deny
+                "#,
+            )
+        })
+        .unwrap();
+
+        let mut changed: Vec<String> = generation
+            .changed_escapers()
+            .into_iter()
+            .map(|n| n.to_string())
+            .collect();
+        changed.sort();
+        assert_eq!(changed, ["added", "direct"]);
+        clear_all();
+    }
+}
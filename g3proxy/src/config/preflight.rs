@@ -0,0 +1,172 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+static PREFLIGHT_CONFIG: OnceLock<PreflightConfig> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum PreflightPolicy {
+    /// fail the startup
+    Fail,
+    /// log a warning and continue, the dependency will be treated as degraded
+    #[default]
+    Warn,
+    /// do not run the check
+    Skip,
+}
+
+impl PreflightPolicy {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            PreflightPolicy::Fail => "fail",
+            PreflightPolicy::Warn => "warn",
+            PreflightPolicy::Skip => "skip",
+        }
+    }
+
+    fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        match v {
+            Yaml::String(s) => PreflightPolicy::from_str(s),
+            Yaml::Boolean(true) => Ok(PreflightPolicy::Fail),
+            Yaml::Boolean(false) => Ok(PreflightPolicy::Skip),
+            _ => Err(anyhow!(
+                "yaml value type for 'preflight policy' should be 'string' or 'bool'"
+            )),
+        }
+    }
+}
+
+impl FromStr for PreflightPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match g3_yaml::key::normalize(s).as_str() {
+            "fail" | "required" => Ok(PreflightPolicy::Fail),
+            "warn" | "degrade" => Ok(PreflightPolicy::Warn),
+            "skip" | "none" => Ok(PreflightPolicy::Skip),
+            _ => Err(anyhow!("invalid preflight policy {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct PreflightConfig {
+    pub(crate) check_timeout: Duration,
+    pub(crate) resolver: PreflightPolicy,
+    pub(crate) resolve_test_domain: Arc<str>,
+    pub(crate) cert_agent: PreflightPolicy,
+    pub(crate) cert_test_host: Arc<str>,
+    pub(crate) icap: PreflightPolicy,
+    pub(crate) user_source: PreflightPolicy,
+}
+
+impl Default for PreflightConfig {
+    fn default() -> Self {
+        PreflightConfig {
+            check_timeout: Duration::from_secs(5),
+            resolver: PreflightPolicy::Warn,
+            resolve_test_domain: Arc::from("www.example.com"),
+            cert_agent: PreflightPolicy::Warn,
+            cert_test_host: Arc::from("preflight.g3proxy.test"),
+            icap: PreflightPolicy::Warn,
+            user_source: PreflightPolicy::Warn,
+        }
+    }
+}
+
+impl PreflightConfig {
+    fn set_all_policy(&mut self, policy: PreflightPolicy) {
+        self.resolver = policy;
+        self.cert_agent = policy;
+        self.icap = policy;
+        self.user_source = policy;
+    }
+
+    fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let mut config = PreflightConfig::default();
+        match v {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "check_timeout" | "timeout" => {
+                        config.check_timeout = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "default_policy" | "policy" => {
+                        let policy = PreflightPolicy::parse_yaml(v)
+                            .context(format!("invalid preflight policy value for key {k}"))?;
+                        config.set_all_policy(policy);
+                        Ok(())
+                    }
+                    "resolver" => {
+                        config.resolver = PreflightPolicy::parse_yaml(v)
+                            .context(format!("invalid preflight policy value for key {k}"))?;
+                        Ok(())
+                    }
+                    "resolve_test_domain" => {
+                        let domain = g3_yaml::value::as_domain(v)
+                            .context(format!("invalid domain value for key {k}"))?;
+                        config.resolve_test_domain = Arc::from(domain);
+                        Ok(())
+                    }
+                    "cert_agent" => {
+                        config.cert_agent = PreflightPolicy::parse_yaml(v)
+                            .context(format!("invalid preflight policy value for key {k}"))?;
+                        Ok(())
+                    }
+                    "cert_test_host" => {
+                        let domain = g3_yaml::value::as_domain(v)
+                            .context(format!("invalid domain value for key {k}"))?;
+                        config.cert_test_host = Arc::from(domain);
+                        Ok(())
+                    }
+                    "icap" | "icap_service" => {
+                        config.icap = PreflightPolicy::parse_yaml(v)
+                            .context(format!("invalid preflight policy value for key {k}"))?;
+                        Ok(())
+                    }
+                    "user_source" | "user_group" => {
+                        config.user_source = PreflightPolicy::parse_yaml(v)
+                            .context(format!("invalid preflight policy value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            _ => {
+                let policy = PreflightPolicy::parse_yaml(v)?;
+                config.set_all_policy(policy);
+            }
+        }
+        Ok(config)
+    }
+}
+
+pub(crate) fn load(v: &Yaml) -> anyhow::Result<()> {
+    let config = PreflightConfig::parse_yaml(v)?;
+    PREFLIGHT_CONFIG
+        .set(config)
+        .map_err(|_| anyhow!("preflight config has already been set"))
+}
+
+pub(crate) fn get_config() -> Option<&'static PreflightConfig> {
+    PREFLIGHT_CONFIG.get()
+}
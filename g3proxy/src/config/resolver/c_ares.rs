@@ -26,7 +26,7 @@ use g3_resolver::{AnyResolveDriverConfig, ResolverRuntimeConfig};
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
 
-use super::{AnyResolverConfig, ResolverConfigDiffAction};
+use super::{AnyResolverConfig, ResolveRebindProtectionConfig, ResolverConfigDiffAction};
 
 const RESOLVER_CONFIG_TYPE: &str = "c-ares";
 
@@ -36,6 +36,7 @@ pub(crate) struct CAresResolverConfig {
     position: Option<YamlDocPosition>,
     runtime: ResolverRuntimeConfig,
     driver: CAresDriverConfig,
+    pub(crate) rebind_protection: Option<ResolveRebindProtectionConfig>,
 }
 
 impl From<&CAresResolverConfig> for g3_resolver::ResolverConfig {
@@ -55,6 +56,7 @@ impl CAresResolverConfig {
             position,
             runtime: Default::default(),
             driver: Default::default(),
+            rebind_protection: None,
         }
     }
 
@@ -155,6 +157,12 @@ impl CAresResolverConfig {
                 self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
                 Ok(())
             }
+            "rebind_protection" => {
+                self.rebind_protection = ResolveRebindProtectionConfig::parse_yaml(v).context(
+                    format!("invalid rebind protection config value for key {k}"),
+                )?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -26,6 +26,8 @@ use super::hickory;
 
 use super::deny_all;
 use super::fail_over;
+#[cfg(feature = "hickory")]
+use super::zone;
 
 pub(super) const CONFIG_KEY_RESOLVER_TYPE: &str = "type";
 pub(super) const CONFIG_KEY_RESOLVER_NAME: &str = "name";
@@ -53,6 +55,8 @@ pub(crate) enum AnyResolverConfig {
     Hickory(Box<hickory::HickoryResolverConfig>),
     DenyAll(deny_all::DenyAllResolverConfig),
     FailOver(fail_over::FailOverResolverConfig),
+    #[cfg(feature = "hickory")]
+    Zone(Box<zone::ZoneResolverConfig>),
 }
 
 macro_rules! impl_transparent0 {
@@ -65,6 +69,8 @@ macro_rules! impl_transparent0 {
                 AnyResolverConfig::Hickory(r) => r.$f(),
                 AnyResolverConfig::DenyAll(r) => r.$f(),
                 AnyResolverConfig::FailOver(r) => r.$f(),
+                #[cfg(feature = "hickory")]
+                AnyResolverConfig::Zone(r) => r.$f(),
             }
         }
     };
@@ -80,6 +86,8 @@ macro_rules! impl_transparent1 {
                 AnyResolverConfig::Hickory(r) => r.$f(p),
                 AnyResolverConfig::DenyAll(r) => r.$f(p),
                 AnyResolverConfig::FailOver(r) => r.$f(p),
+                #[cfg(feature = "hickory")]
+                AnyResolverConfig::Zone(r) => r.$f(p),
             }
         }
     };
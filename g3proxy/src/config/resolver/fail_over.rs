@@ -16,7 +16,7 @@
 
 use std::collections::BTreeSet;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
 use yaml_rust::{yaml, Yaml};
 
 use g3_resolver::driver::fail_over::FailOverDriverStaticConfig;
@@ -24,7 +24,9 @@ use g3_resolver::ResolverRuntimeConfig;
 use g3_types::metrics::NodeName;
 use g3_yaml::YamlDocPosition;
 
-use super::{AnyResolverConfig, ResolverConfig, ResolverConfigDiffAction};
+use super::{
+    AnyResolverConfig, ResolveRebindProtectionConfig, ResolverConfig, ResolverConfigDiffAction,
+};
 
 const RESOLVER_CONFIG_TYPE: &str = "fail-over";
 
@@ -36,6 +38,7 @@ pub(crate) struct FailOverResolverConfig {
     pub(crate) primary: NodeName,
     pub(crate) standby: NodeName,
     pub(crate) static_conf: FailOverDriverStaticConfig,
+    pub(crate) rebind_protection: Option<ResolveRebindProtectionConfig>,
 }
 
 impl FailOverResolverConfig {
@@ -47,6 +50,7 @@ impl FailOverResolverConfig {
             primary: NodeName::default(),
             standby: NodeName::default(),
             static_conf: FailOverDriverStaticConfig::default(),
+            rebind_protection: None,
         }
     }
 
@@ -100,6 +104,12 @@ impl FailOverResolverConfig {
                 self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
                 Ok(())
             }
+            "rebind_protection" => {
+                self.rebind_protection = ResolveRebindProtectionConfig::parse_yaml(v).context(
+                    format!("invalid rebind protection config value for key {k}"),
+                )?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
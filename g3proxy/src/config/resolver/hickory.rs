@@ -19,14 +19,16 @@ use std::net::IpAddr;
 use std::str::FromStr;
 
 use anyhow::{anyhow, Context};
+use url::{Host, Url};
 use yaml_rust::{yaml, Yaml};
 
 use g3_resolver::driver::hickory::HickoryDriverConfig;
 use g3_resolver::{AnyResolveDriverConfig, ResolverRuntimeConfig};
 use g3_types::metrics::NodeName;
+use g3_types::net::DnsEncryptionConfigBuilder;
 use g3_yaml::YamlDocPosition;
 
-use super::{AnyResolverConfig, ResolverConfigDiffAction};
+use super::{AnyResolverConfig, ResolveRebindProtectionConfig, ResolverConfigDiffAction};
 
 const RESOLVER_CONFIG_TYPE: &str = "hickory";
 
@@ -36,6 +38,7 @@ pub(crate) struct HickoryResolverConfig {
     position: Option<YamlDocPosition>,
     runtime: ResolverRuntimeConfig,
     driver: HickoryDriverConfig,
+    pub(crate) rebind_protection: Option<ResolveRebindProtectionConfig>,
 }
 
 impl From<&HickoryResolverConfig> for g3_resolver::ResolverConfig {
@@ -55,6 +58,7 @@ impl HickoryResolverConfig {
             position,
             runtime: Default::default(),
             driver: Default::default(),
+            rebind_protection: None,
         }
     }
 
@@ -114,6 +118,12 @@ impl HickoryResolverConfig {
                 self.driver.set_encryption(config);
                 Ok(())
             }
+            "server_url" | "url" => {
+                let url =
+                    g3_yaml::value::as_url(v).context(format!("invalid url value for key {k}"))?;
+                self.parse_server_url(&url)
+                    .context(format!("unsupported server url value for key {k}"))
+            }
             "connect_timeout" => {
                 let timeout = g3_yaml::humanize::as_duration(v)
                     .context(format!("invalid humanize duration value for key {k}"))?;
@@ -166,10 +176,36 @@ impl HickoryResolverConfig {
                 self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
                 Ok(())
             }
+            "rebind_protection" => {
+                self.rebind_protection = ResolveRebindProtectionConfig::parse_yaml(v).context(
+                    format!("invalid rebind protection config value for key {k}"),
+                )?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
 
+    fn parse_server_url(&mut self, url: &Url) -> anyhow::Result<()> {
+        let ip = match url.host() {
+            Some(Host::Ipv4(ip)) => IpAddr::V4(ip),
+            Some(Host::Ipv6(ip)) => IpAddr::V6(ip),
+            Some(Host::Domain(_)) => {
+                return Err(anyhow!(
+                    "domain host is not supported, use ip address and set tls name in encryption"
+                ));
+            }
+            None => return Err(anyhow!("no host found in url")),
+        };
+        let (config, port) = DnsEncryptionConfigBuilder::from_url(url)?;
+        self.driver.add_server(ip);
+        if let Some(port) = port {
+            self.driver.set_server_port(port);
+        }
+        self.driver.set_encryption(config);
+        Ok(())
+    }
+
     fn parse_server_str(&mut self, addrs: &str) -> anyhow::Result<()> {
         let addrs = addrs.split_whitespace();
         for (i, addr) in addrs.enumerate() {
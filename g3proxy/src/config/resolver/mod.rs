@@ -30,15 +30,19 @@ pub(crate) mod hickory;
 
 pub(crate) mod deny_all;
 pub(crate) mod fail_over;
+#[cfg(feature = "hickory")]
+pub(crate) mod zone;
 
 mod config;
+mod rebind;
 
 pub(crate) use config::{AnyResolverConfig, ResolverConfig, ResolverConfigDiffAction};
+pub(crate) use rebind::{as_rebind_allow_domains, ResolveRebindProtectionConfig};
 
 use config::{CONFIG_KEY_RESOLVER_NAME, CONFIG_KEY_RESOLVER_TYPE};
 
 mod registry;
-pub(crate) use registry::clear;
+pub(crate) use registry::{clear, del, get_all_names, restore, snapshot};
 
 pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
     let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
@@ -106,6 +110,12 @@ fn load_resolver(
                 .context("failed to load this FailOver resolver")?;
             Ok(AnyResolverConfig::FailOver(resolver))
         }
+        #[cfg(feature = "hickory")]
+        "zone" | "zone_file" => {
+            let resolver = zone::ZoneResolverConfig::parse(map, position)
+                .context("failed to load this Zone resolver")?;
+            Ok(AnyResolverConfig::Zone(Box::new(resolver)))
+        }
         _ => Err(anyhow!("unsupported resolver type {resolver_type}")),
     }
 }
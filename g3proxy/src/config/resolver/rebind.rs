@@ -0,0 +1,95 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use g3_types::acl::{AclAction, AclChildDomainRuleBuilder};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ResolveRebindProtectionConfig {
+    pub(crate) filter_loopback: bool,
+    pub(crate) filter_private: bool,
+    pub(crate) filter_link_local: bool,
+    pub(crate) allow_domains: Option<AclChildDomainRuleBuilder>,
+}
+
+impl Default for ResolveRebindProtectionConfig {
+    fn default() -> Self {
+        ResolveRebindProtectionConfig {
+            filter_loopback: true,
+            filter_private: true,
+            filter_link_local: true,
+            allow_domains: None,
+        }
+    }
+}
+
+impl ResolveRebindProtectionConfig {
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Option<Self>> {
+        match v {
+            Yaml::Boolean(enable) => {
+                if *enable {
+                    Ok(Some(ResolveRebindProtectionConfig::default()))
+                } else {
+                    Ok(None)
+                }
+            }
+            Yaml::Hash(map) => {
+                let mut config = ResolveRebindProtectionConfig::default();
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "loopback" | "filter_loopback" => {
+                        config.filter_loopback = g3_yaml::value::as_bool(v)
+                            .context(format!("invalid bool value for key {k}"))?;
+                        Ok(())
+                    }
+                    "private" | "filter_private" => {
+                        config.filter_private = g3_yaml::value::as_bool(v)
+                            .context(format!("invalid bool value for key {k}"))?;
+                        Ok(())
+                    }
+                    "link_local" | "filter_link_local" => {
+                        config.filter_link_local = g3_yaml::value::as_bool(v)
+                            .context(format!("invalid bool value for key {k}"))?;
+                        Ok(())
+                    }
+                    "allow_domains" | "allow_domain" | "allow" => {
+                        let builder = as_rebind_allow_domains(v)
+                            .context(format!("invalid domain list value for key {k}"))?;
+                        config.allow_domains = Some(builder);
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                Ok(Some(config))
+            }
+            Yaml::Null => Ok(None),
+            _ => Err(anyhow!(
+                "yaml value type for 'rebind protection config' should be 'boolean' or 'map'"
+            )),
+        }
+    }
+}
+
+/// Parse a single domain or a list of domains, all child domains of which
+/// will be permitted.
+pub(crate) fn as_rebind_allow_domains(v: &Yaml) -> anyhow::Result<AclChildDomainRuleBuilder> {
+    let mut builder = AclChildDomainRuleBuilder::new(AclAction::Forbid);
+    for domain in g3_yaml::value::as_list(v, g3_yaml::value::as_domain)? {
+        builder.add_node(&domain, AclAction::Permit);
+    }
+    Ok(builder)
+}
@@ -37,7 +37,7 @@ pub(super) fn add(resolver: AnyResolverConfig) -> Option<AnyResolverConfig> {
     ht.insert(name, resolver).map(|old| old.as_ref().clone())
 }
 
-pub(super) fn del(name: &NodeName) {
+pub(crate) fn del(name: &NodeName) {
     let mut ht = INITIAL_RESOLVER_CONFIG_REGISTRY.lock().unwrap();
     ht.remove(name);
 }
@@ -47,7 +47,17 @@ pub(super) fn get(name: &NodeName) -> Option<Arc<AnyResolverConfig>> {
     ht.get(name).cloned()
 }
 
-pub(super) fn get_all_names() -> Vec<NodeName> {
+pub(crate) fn get_all_names() -> Vec<NodeName> {
     let ht = INITIAL_RESOLVER_CONFIG_REGISTRY.lock().unwrap();
     ht.keys().cloned().collect()
 }
+
+pub(crate) fn snapshot() -> HashMap<NodeName, Arc<AnyResolverConfig>> {
+    let ht = INITIAL_RESOLVER_CONFIG_REGISTRY.lock().unwrap();
+    ht.clone()
+}
+
+pub(crate) fn restore(snapshot: HashMap<NodeName, Arc<AnyResolverConfig>>) {
+    let mut ht = INITIAL_RESOLVER_CONFIG_REGISTRY.lock().unwrap();
+    *ht = snapshot;
+}
@@ -0,0 +1,204 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeSet;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::{yaml, Yaml};
+
+use g3_resolver::driver::zone::{ZoneDriverConfig, ZoneTransferConfig};
+use g3_resolver::ResolverRuntimeConfig;
+use g3_types::metrics::NodeName;
+use g3_yaml::YamlDocPosition;
+
+use super::{
+    AnyResolverConfig, ResolveRebindProtectionConfig, ResolverConfig, ResolverConfigDiffAction,
+};
+
+const RESOLVER_CONFIG_TYPE: &str = "zone";
+
+#[derive(Clone, PartialEq)]
+pub(crate) struct ZoneResolverConfig {
+    position: Option<YamlDocPosition>,
+    name: NodeName,
+    pub(crate) runtime: ResolverRuntimeConfig,
+    pub(crate) driver: ZoneDriverConfig,
+    pub(crate) fallback: NodeName,
+    pub(crate) rebind_protection: Option<ResolveRebindProtectionConfig>,
+}
+
+impl ZoneResolverConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        ZoneResolverConfig {
+            position,
+            name: NodeName::default(),
+            runtime: Default::default(),
+            driver: ZoneDriverConfig::default(),
+            fallback: NodeName::default(),
+            rebind_protection: None,
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut resolver = Self::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| resolver.set(k, v))?;
+
+        resolver.check()?;
+        Ok(resolver)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_RESOLVER_TYPE => Ok(()),
+            super::CONFIG_KEY_RESOLVER_NAME => {
+                self.name = g3_yaml::value::as_metrics_name(v)?;
+                Ok(())
+            }
+            "origin" | "zone" => {
+                let origin = g3_yaml::value::as_domain(v)
+                    .context(format!("invalid domain value for key {k}"))?;
+                self.driver.set_origin(&origin)
+            }
+            "zone_file" | "file" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let path = g3_yaml::value::as_file_path(v, lookup_dir, false)
+                    .context(format!("invalid file path value for key {k}"))?;
+                self.driver.set_zone_file(path);
+                Ok(())
+            }
+            "zone_transfer" | "axfr" => {
+                let config = parse_zone_transfer(v)
+                    .context(format!("invalid zone transfer config value for key {k}"))?;
+                self.driver.set_transfer(config);
+                Ok(())
+            }
+            "fallback" | "upstream" => {
+                self.fallback = g3_yaml::value::as_metrics_name(v)?;
+                Ok(())
+            }
+            "negative_ttl" | "protective_cache_ttl" => {
+                let ttl = g3_yaml::value::as_u32(v)?;
+                self.driver.set_negative_ttl(ttl);
+                Ok(())
+            }
+            "graceful_stop_wait" => {
+                self.runtime.graceful_stop_wait = g3_yaml::humanize::as_duration(v)?;
+                Ok(())
+            }
+            "protective_query_timeout" => {
+                self.runtime.protective_query_timeout = g3_yaml::humanize::as_duration(v)?;
+                Ok(())
+            }
+            "rebind_protection" => {
+                self.rebind_protection = ResolveRebindProtectionConfig::parse_yaml(v).context(
+                    format!("invalid rebind protection config value for key {k}"),
+                )?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.fallback.eq(&self.name) {
+            return Err(anyhow!("the fallback resolver should not be itself"));
+        }
+        self.driver.check()
+    }
+}
+
+fn parse_zone_transfer(v: &Yaml) -> anyhow::Result<ZoneTransferConfig> {
+    match v {
+        Yaml::Hash(map) => {
+            let v = g3_yaml::hash_get_required(map, "master")?;
+            let master = g3_yaml::value::as_sockaddr(v)
+                .context("invalid socket address value for key master")?;
+            let mut config = ZoneTransferConfig::new(master);
+            g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                "master" => Ok(()),
+                "bind" | "bind_addr" => {
+                    let addr = g3_yaml::value::as_sockaddr(v)
+                        .context(format!("invalid socket address value for key {k}"))?;
+                    config.set_bind(addr);
+                    Ok(())
+                }
+                "refresh_interval" | "refresh" => {
+                    let interval = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    config.set_refresh_interval(interval);
+                    Ok(())
+                }
+                "retry_interval" | "retry" => {
+                    let interval = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    config.set_retry_interval(interval);
+                    Ok(())
+                }
+                "timeout" => {
+                    let timeout = g3_yaml::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    config.set_timeout(timeout);
+                    Ok(())
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+            Ok(config)
+        }
+        _ => {
+            let master = g3_yaml::value::as_sockaddr(v)?;
+            Ok(ZoneTransferConfig::new(master))
+        }
+    }
+}
+
+impl ResolverConfig for ZoneResolverConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn resolver_type(&self) -> &'static str {
+        RESOLVER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &AnyResolverConfig) -> ResolverConfigDiffAction {
+        let AnyResolverConfig::Zone(_) = new else {
+            return ResolverConfigDiffAction::SpawnNew;
+        };
+
+        // always update, as the content of the zone file may have changed
+        ResolverConfigDiffAction::Update
+    }
+
+    fn dependent_resolver(&self) -> Option<BTreeSet<NodeName>> {
+        if self.fallback.is_empty() {
+            return None;
+        }
+        let mut set = BTreeSet::new();
+        set.insert(self.fallback.clone());
+        Some(set)
+    }
+}
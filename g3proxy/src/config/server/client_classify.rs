@@ -0,0 +1,146 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use g3_daemon::server::ClientConnectionInfo;
+
+/// the first custom TLV type defined in the PROXY protocol v2 spec
+const DEFAULT_PPV2_TLV_TYPE: u8 = 0xE0;
+const DSCP_MAX: u8 = 63;
+
+/// map unauthenticated client connections to users by network metadata
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ClientUserClassifyConfig {
+    dscp_map: BTreeMap<u8, Arc<str>>,
+    ppv2_tlv_type: u8,
+    ppv2_tlv_map: BTreeMap<Vec<u8>, Arc<str>>,
+    default_user: Option<Arc<str>>,
+}
+
+impl Default for ClientUserClassifyConfig {
+    fn default() -> Self {
+        ClientUserClassifyConfig {
+            dscp_map: BTreeMap::new(),
+            ppv2_tlv_type: DEFAULT_PPV2_TLV_TYPE,
+            ppv2_tlv_map: BTreeMap::new(),
+            default_user: None,
+        }
+    }
+}
+
+impl ClientUserClassifyConfig {
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = v else {
+            return Err(anyhow!(
+                "yaml value type for 'client user classify config' should be 'map'"
+            ));
+        };
+
+        let mut config = ClientUserClassifyConfig::default();
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "dscp_map" | "dscp" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("invalid map value for key {k}"));
+                };
+                g3_yaml::foreach_kv(map, |user, v| {
+                    let user = Arc::<str>::from(user);
+                    let values = g3_yaml::value::as_list(v, g3_yaml::value::as_u8)
+                        .context(format!("invalid dscp value list for user {user}"))?;
+                    for dscp in values {
+                        if dscp > DSCP_MAX {
+                            return Err(anyhow!("invalid dscp value {dscp} for user {user}"));
+                        }
+                        if let Some(old) = config.dscp_map.insert(dscp, user.clone()) {
+                            return Err(anyhow!(
+                                "dscp value {dscp} is already mapped to user {old}"
+                            ));
+                        }
+                    }
+                    Ok(())
+                })
+            }
+            "ppv2_tlv_type" | "proxy_protocol_tlv_type" => {
+                config.ppv2_tlv_type =
+                    g3_yaml::value::as_u8(v).context(format!("invalid u8 value for key {k}"))?;
+                Ok(())
+            }
+            "ppv2_tlv_map" | "proxy_protocol_tlv_map" => {
+                let Yaml::Hash(map) = v else {
+                    return Err(anyhow!("invalid map value for key {k}"));
+                };
+                g3_yaml::foreach_kv(map, |user, v| {
+                    let user = Arc::<str>::from(user);
+                    let values = g3_yaml::value::as_list(v, g3_yaml::value::as_string)
+                        .context(format!("invalid tlv value list for user {user}"))?;
+                    for value in values {
+                        if let Some(old) = config
+                            .ppv2_tlv_map
+                            .insert(value.clone().into_bytes(), user.clone())
+                        {
+                            return Err(anyhow!(
+                                "tlv value {value} is already mapped to user {old}"
+                            ));
+                        }
+                    }
+                    Ok(())
+                })
+            }
+            "default_user" => {
+                let user = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                config.default_user = Some(Arc::from(user));
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+        Ok(config)
+    }
+
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.dscp_map.is_empty() && self.ppv2_tlv_map.is_empty() && self.default_user.is_none()
+    }
+
+    /// get the mapped username, PROXY protocol TLV takes precedence over DSCP
+    pub(crate) fn classify(&self, cc_info: &ClientConnectionInfo) -> Option<&Arc<str>> {
+        if !self.ppv2_tlv_map.is_empty() {
+            let user = cc_info
+                .proxy_protocol_tlvs()
+                .iter()
+                .filter(|tlv| tlv.kind() == self.ppv2_tlv_type)
+                .find_map(|tlv| self.ppv2_tlv_map.get(tlv.value()));
+            if user.is_some() {
+                return user;
+            }
+        }
+
+        if !self.dscp_map.is_empty() {
+            if let Some(user) = cc_info
+                .tcp_sock_dscp()
+                .and_then(|dscp| self.dscp_map.get(&dscp))
+            {
+                return Some(user);
+            }
+        }
+
+        self.default_user.as_ref()
+    }
+}
@@ -0,0 +1,105 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::num::NonZeroUsize;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+/// limit of concurrent connections from the same client ip or subnet
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ClientConnLimitConfig {
+    /// max alive connections per client ip, 0 means no limit
+    pub(crate) max_per_ip: usize,
+    /// max alive connections per client subnet, 0 means no limit
+    pub(crate) max_per_subnet: usize,
+    pub(crate) ipv4_prefix: u8,
+    pub(crate) ipv6_prefix: u8,
+    /// max number of ip / subnet entries to track, new clients will be refused if full
+    pub(crate) max_tracked: NonZeroUsize,
+}
+
+impl Default for ClientConnLimitConfig {
+    fn default() -> Self {
+        ClientConnLimitConfig {
+            max_per_ip: 0,
+            max_per_subnet: 0,
+            ipv4_prefix: 24,
+            ipv6_prefix: 64,
+            max_tracked: NonZeroUsize::new(65536).unwrap(),
+        }
+    }
+}
+
+impl ClientConnLimitConfig {
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let mut config = ClientConnLimitConfig::default();
+        match v {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "max_per_ip" | "per_ip" => {
+                        config.max_per_ip = g3_yaml::value::as_usize(v)
+                            .context(format!("invalid usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_per_subnet" | "per_subnet" => {
+                        config.max_per_subnet = g3_yaml::value::as_usize(v)
+                            .context(format!("invalid usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "ipv4_prefix" => {
+                        let prefix = g3_yaml::value::as_u8(v)
+                            .context(format!("invalid u8 value for key {k}"))?;
+                        if prefix > 32 {
+                            return Err(anyhow!("too large ipv4 prefix length {prefix}"));
+                        }
+                        config.ipv4_prefix = prefix;
+                        Ok(())
+                    }
+                    "ipv6_prefix" => {
+                        let prefix = g3_yaml::value::as_u8(v)
+                            .context(format!("invalid u8 value for key {k}"))?;
+                        if prefix > 128 {
+                            return Err(anyhow!("too large ipv6 prefix length {prefix}"));
+                        }
+                        config.ipv6_prefix = prefix;
+                        Ok(())
+                    }
+                    "max_tracked" | "track_size" => {
+                        config.max_tracked = g3_yaml::value::as_nonzero_usize(v)
+                            .context(format!("invalid nonzero usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            Yaml::Integer(_) => {
+                config.max_per_ip = g3_yaml::value::as_usize(v)?;
+            }
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for 'client conn limit' should be 'map' or 'usize'"
+                ))
+            }
+        }
+        Ok(config)
+    }
+
+    #[inline]
+    pub(crate) fn is_unlimited(&self) -> bool {
+        self.max_per_ip == 0 && self.max_per_subnet == 0
+    }
+}
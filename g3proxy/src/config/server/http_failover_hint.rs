@@ -0,0 +1,96 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use http::HeaderName;
+use yaml_rust::Yaml;
+
+use g3_types::net::UpstreamAddr;
+
+const DEFAULT_SECONDARY_PROXY_HEADER: &str = "x-secondary-proxy";
+
+/// hints sent to managed clients when the proxy is shedding load or draining
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct HttpFailoverHintConfig {
+    /// value of the Retry-After header in 429 / 503 responses
+    pub(crate) retry_after: Duration,
+    /// reply 503 to new requests on existing connections while the server is offline
+    pub(crate) reject_when_offline: bool,
+    pub(crate) secondary_proxy_header: HeaderName,
+    pub(crate) secondary_proxy: Option<UpstreamAddr>,
+}
+
+impl Default for HttpFailoverHintConfig {
+    fn default() -> Self {
+        HttpFailoverHintConfig {
+            retry_after: Duration::from_secs(10),
+            reject_when_offline: false,
+            secondary_proxy_header: HeaderName::from_static(DEFAULT_SECONDARY_PROXY_HEADER),
+            secondary_proxy: None,
+        }
+    }
+}
+
+impl HttpFailoverHintConfig {
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let mut config = HttpFailoverHintConfig::default();
+        match v {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "retry_after" => {
+                        config.retry_after = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "reject_when_offline" | "reject_when_draining" => {
+                        config.reject_when_offline = g3_yaml::value::as_bool(v)
+                            .context(format!("invalid bool value for key {k}"))?;
+                        Ok(())
+                    }
+                    "secondary_proxy_header" => {
+                        config.secondary_proxy_header = g3_yaml::value::as_http_header_name(v)
+                            .context(format!("invalid http header name value for key {k}"))?;
+                        Ok(())
+                    }
+                    "secondary_proxy" => {
+                        let addr = g3_yaml::value::as_upstream_addr(v, 0)
+                            .context(format!("invalid upstream addr value for key {k}"))?;
+                        if addr.port() == 0 {
+                            return Err(anyhow!("port is required for key {k}"));
+                        }
+                        config.secondary_proxy = Some(addr);
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            Yaml::Boolean(true) => {}
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for 'http failover hint' should be 'map' or 'true'"
+                ))
+            }
+        }
+        Ok(config)
+    }
+
+    #[inline]
+    pub(crate) fn retry_after_secs(&self) -> u64 {
+        self.retry_after.as_secs()
+    }
+}
@@ -0,0 +1,233 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+/// pooled h2 connections to upstream for https forward requests from h1 clients
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct HttpForwardUpstreamH2Config {
+    /// open a new connection if all pooled ones have reached this number of active streams
+    pub(crate) max_concurrent_streams: usize,
+    pub(crate) idle_timeout: Duration,
+    pub(crate) handshake_timeout: Duration,
+    pub(crate) stream_window_size: u32,
+    pub(crate) connection_window_size: u32,
+    pub(crate) max_frame_size: u32,
+    pub(crate) max_send_buffer_size: usize,
+    /// how long to remember that an upstream has no h2 support
+    pub(crate) h1_only_cache_ttl: Duration,
+}
+
+impl Default for HttpForwardUpstreamH2Config {
+    fn default() -> Self {
+        HttpForwardUpstreamH2Config {
+            max_concurrent_streams: 64,
+            idle_timeout: Duration::from_secs(60),
+            handshake_timeout: Duration::from_secs(10),
+            stream_window_size: 1 << 20,
+            connection_window_size: 4 << 20,
+            max_frame_size: 1 << 14,
+            max_send_buffer_size: 16 << 10,
+            h1_only_cache_ttl: Duration::from_secs(600),
+        }
+    }
+}
+
+impl HttpForwardUpstreamH2Config {
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let mut config = HttpForwardUpstreamH2Config::default();
+        match v {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "max_concurrent_streams" => {
+                        config.max_concurrent_streams = g3_yaml::value::as_usize(v)
+                            .context(format!("invalid usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "idle_timeout" => {
+                        config.idle_timeout = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "handshake_timeout" => {
+                        config.handshake_timeout = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "stream_window_size" => {
+                        config.stream_window_size = g3_yaml::humanize::as_u32(v)
+                            .context(format!("invalid humanize u32 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "connection_window_size" => {
+                        config.connection_window_size = g3_yaml::humanize::as_u32(v)
+                            .context(format!("invalid humanize u32 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_frame_size" => {
+                        config.max_frame_size = g3_yaml::humanize::as_u32(v)
+                            .context(format!("invalid humanize u32 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_send_buffer_size" => {
+                        config.max_send_buffer_size = g3_yaml::humanize::as_usize(v)
+                            .context(format!("invalid humanize usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "h1_only_cache_ttl" => {
+                        config.h1_only_cache_ttl = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            Yaml::Boolean(true) => {}
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for 'http forward upstream h2' should be 'map' or 'true'"
+                ))
+            }
+        }
+        config.check()?;
+        Ok(config)
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.max_concurrent_streams == 0 {
+            return Err(anyhow!("max_concurrent_streams should not be 0"));
+        }
+        // see https://www.rfc-editor.org/rfc/rfc9113.html#name-defined-settings
+        self.max_frame_size = self.max_frame_size.clamp(1 << 14, (1 << 24) - 1);
+        if self.stream_window_size > (1 << 31) - 1 {
+            return Err(anyhow!("stream_window_size should be less than 2^31"));
+        }
+        if self.connection_window_size > (1 << 31) - 1 {
+            return Err(anyhow!("connection_window_size should be less than 2^31"));
+        }
+        Ok(())
+    }
+}
+
+/// accept h2 clients, each stream will be bridged to the HTTP/1.x proxy path
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct HttpForwardClientH2Config {
+    pub(crate) max_concurrent_streams: u32,
+    pub(crate) handshake_timeout: Duration,
+    pub(crate) stream_window_size: u32,
+    pub(crate) connection_window_size: u32,
+    pub(crate) max_frame_size: u32,
+    pub(crate) max_header_list_size: u32,
+    pub(crate) max_send_buffer_size: usize,
+    /// buffer size of the in-memory HTTP/1.x stream for each h2 stream
+    pub(crate) bridge_buffer_size: usize,
+}
+
+impl Default for HttpForwardClientH2Config {
+    fn default() -> Self {
+        HttpForwardClientH2Config {
+            max_concurrent_streams: 128,
+            handshake_timeout: Duration::from_secs(10),
+            stream_window_size: 1 << 20,
+            connection_window_size: 4 << 20,
+            max_frame_size: 1 << 14,
+            max_header_list_size: 64 << 10,
+            max_send_buffer_size: 16 << 10,
+            bridge_buffer_size: 16 << 10,
+        }
+    }
+}
+
+impl HttpForwardClientH2Config {
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let mut config = HttpForwardClientH2Config::default();
+        match v {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "max_concurrent_streams" => {
+                        config.max_concurrent_streams = g3_yaml::value::as_u32(v)
+                            .context(format!("invalid u32 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "handshake_timeout" => {
+                        config.handshake_timeout = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "stream_window_size" => {
+                        config.stream_window_size = g3_yaml::humanize::as_u32(v)
+                            .context(format!("invalid humanize u32 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "connection_window_size" => {
+                        config.connection_window_size = g3_yaml::humanize::as_u32(v)
+                            .context(format!("invalid humanize u32 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_frame_size" => {
+                        config.max_frame_size = g3_yaml::humanize::as_u32(v)
+                            .context(format!("invalid humanize u32 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_header_list_size" => {
+                        config.max_header_list_size = g3_yaml::humanize::as_u32(v)
+                            .context(format!("invalid humanize u32 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_send_buffer_size" => {
+                        config.max_send_buffer_size = g3_yaml::humanize::as_usize(v)
+                            .context(format!("invalid humanize usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "bridge_buffer_size" => {
+                        config.bridge_buffer_size = g3_yaml::humanize::as_usize(v)
+                            .context(format!("invalid humanize usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            Yaml::Boolean(true) => {}
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for 'http forward client h2' should be 'map' or 'true'"
+                ))
+            }
+        }
+        config.check()?;
+        Ok(config)
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.max_concurrent_streams == 0 {
+            return Err(anyhow!("max_concurrent_streams should not be 0"));
+        }
+        if self.bridge_buffer_size == 0 {
+            return Err(anyhow!("bridge_buffer_size should not be 0"));
+        }
+        self.max_frame_size = self.max_frame_size.clamp(1 << 14, (1 << 24) - 1);
+        if self.stream_window_size > (1 << 31) - 1 {
+            return Err(anyhow!("stream_window_size should be less than 2^31"));
+        }
+        if self.connection_window_size > (1 << 31) - 1 {
+            return Err(anyhow!("connection_window_size should be less than 2^31"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+/// retry policy for idempotent http forward requests on new upstream connections
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct HttpForwardRetryConfig {
+    /// max retry attempts after the first try
+    pub(crate) max_retries: usize,
+    pub(crate) initial_backoff: Duration,
+    pub(crate) max_backoff: Duration,
+}
+
+impl Default for HttpForwardRetryConfig {
+    fn default() -> Self {
+        HttpForwardRetryConfig {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+impl HttpForwardRetryConfig {
+    pub(crate) fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let mut config = HttpForwardRetryConfig::default();
+        match v {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "max_retries" | "max_attempts" | "attempts" => {
+                        config.max_retries = g3_yaml::value::as_usize(v)
+                            .context(format!("invalid usize value for key {k}"))?;
+                        Ok(())
+                    }
+                    "initial_backoff" => {
+                        config.initial_backoff = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    "max_backoff" => {
+                        config.max_backoff = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+            }
+            Yaml::Integer(_) => {
+                config.max_retries = g3_yaml::value::as_usize(v)?;
+            }
+            Yaml::Boolean(true) => {}
+            _ => {
+                return Err(anyhow!(
+                    "yaml value type for 'http forward retry' should be 'map', 'int' or 'true'"
+                ))
+            }
+        }
+        if config.max_backoff < config.initial_backoff {
+            config.max_backoff = config.initial_backoff;
+        }
+        Ok(config)
+    }
+
+    /// get the backoff time before the retry attempt `n` (starts from 1)
+    pub(crate) fn backoff(&self, n: usize) -> Option<Duration> {
+        if n == 0 || n > self.max_retries {
+            return None;
+        }
+        let shift = (n - 1).min(16) as u32;
+        let backoff = self.initial_backoff.saturating_mul(1 << shift);
+        Some(backoff.min(self.max_backoff))
+    }
+}
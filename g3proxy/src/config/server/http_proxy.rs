@@ -30,16 +30,24 @@ use g3_tls_ticket::TlsTicketConfig;
 use g3_types::acl::{AclExactPortRule, AclNetworkRuleBuilder};
 use g3_types::acl_set::AclDstHostRuleSetBuilder;
 use g3_types::metrics::{NodeName, StaticMetricsTags};
+#[cfg(unix)]
+use g3_types::net::UnixListenConfig;
 use g3_types::net::{
-    HttpKeepAliveConfig, HttpServerId, OpensslClientConfigBuilder, RustlsServerConfigBuilder,
-    TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig,
+    HttpForwardClientCertMode, HttpKeepAliveConfig, HttpServerId, OpensslClientConfigBuilder,
+    RustlsServerConfigBuilder, TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig,
 };
 use g3_yaml::YamlDocPosition;
 
+use super::client_conn_limit::ClientConnLimitConfig;
+use super::http_failover_hint::HttpFailoverHintConfig;
+use super::http_forward_h2::{HttpForwardClientH2Config, HttpForwardUpstreamH2Config};
+use super::http_forward_retry::HttpForwardRetryConfig;
 use super::{
     AnyServerConfig, ServerConfig, ServerConfigDiffAction, IDLE_CHECK_DEFAULT_DURATION,
     IDLE_CHECK_MAXIMUM_DURATION,
 };
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
+use crate::config::idle::TaskIdlePolicy;
 
 const SERVER_CONFIG_TYPE: &str = "HttpProxy";
 
@@ -71,11 +79,14 @@ pub(crate) struct HttpProxyServerConfig {
     pub(crate) shared_logger: Option<AsciiString>,
     pub(crate) listen: Option<TcpListenConfig>,
     pub(crate) listen_in_worker: bool,
+    #[cfg(unix)]
+    pub(crate) listen_unix: Option<UnixListenConfig>,
     pub(crate) server_tls_config: Option<RustlsServerConfigBuilder>,
     pub(crate) tls_ticketer: Option<TlsTicketConfig>,
     pub(crate) client_tls_config: OpensslClientConfigBuilder,
     pub(crate) ftp_client_config: Arc<FtpClientConfig>,
     pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
+    pub(crate) client_conn_limit: Option<ClientConnLimitConfig>,
     pub(crate) dst_host_filter: Option<AclDstHostRuleSetBuilder>,
     pub(crate) dst_port_filter: Option<AclExactPortRule>,
     pub(crate) server_id: Option<HttpServerId>,
@@ -84,6 +95,7 @@ pub(crate) struct HttpProxyServerConfig {
     pub(crate) timeout: HttpProxyServerTimeoutConfig,
     pub(crate) task_idle_check_duration: Duration,
     pub(crate) task_idle_max_count: i32,
+    pub(crate) task_idle_policy: TaskIdlePolicy,
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
@@ -98,11 +110,17 @@ pub(crate) struct HttpProxyServerConfig {
     pub(crate) allow_custom_host: bool,
     pub(crate) body_line_max_len: usize,
     pub(crate) http_forward_upstream_keepalive: HttpKeepAliveConfig,
+    pub(crate) http_forward_upstream_h2: Option<HttpForwardUpstreamH2Config>,
+    pub(crate) http_forward_client_h2: Option<HttpForwardClientH2Config>,
     pub(crate) http_forward_mark_upstream: bool,
+    pub(crate) http_forward_retry: Option<HttpForwardRetryConfig>,
     pub(crate) echo_chained_info: bool,
     pub(crate) untrusted_read_limit: Option<TcpSockSpeedLimitConfig>,
     pub(crate) egress_path_selection_header: Option<HeaderName>,
     pub(crate) steal_forwarded_for: bool,
+    pub(crate) forward_client_cert: HttpForwardClientCertMode,
+    pub(crate) failover_hint: Option<HttpFailoverHintConfig>,
+    pub(crate) http_header_rewrite: Option<Arc<HttpHeaderRewriteConfig>>,
     pub(crate) extra_metrics_tags: Option<Arc<StaticMetricsTags>>,
 }
 
@@ -117,11 +135,14 @@ impl HttpProxyServerConfig {
             shared_logger: None,
             listen: None,
             listen_in_worker: false,
+            #[cfg(unix)]
+            listen_unix: None,
             server_tls_config: None,
             tls_ticketer: None,
             client_tls_config: OpensslClientConfigBuilder::with_cache_for_many_sites(),
             ftp_client_config: Arc::new(Default::default()),
             ingress_net_filter: None,
+            client_conn_limit: None,
             dst_host_filter: None,
             dst_port_filter: None,
             server_id: None,
@@ -130,6 +151,7 @@ impl HttpProxyServerConfig {
             timeout: HttpProxyServerTimeoutConfig::default(),
             task_idle_check_duration: IDLE_CHECK_DEFAULT_DURATION,
             task_idle_max_count: 1,
+            task_idle_policy: TaskIdlePolicy::default(),
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
@@ -144,11 +166,17 @@ impl HttpProxyServerConfig {
             allow_custom_host: true,
             body_line_max_len: 8192,
             http_forward_upstream_keepalive: Default::default(),
+            http_forward_upstream_h2: None,
+            http_forward_client_h2: None,
             http_forward_mark_upstream: false,
+            http_forward_retry: None,
             echo_chained_info: false,
             untrusted_read_limit: None,
             egress_path_selection_header: None,
             steal_forwarded_for: false,
+            forward_client_cert: HttpForwardClientCertMode::default(),
+            failover_hint: None,
+            http_header_rewrite: None,
             extra_metrics_tags: None,
         }
     }
@@ -165,6 +193,13 @@ impl HttpProxyServerConfig {
         Ok(server)
     }
 
+    /// the idle timeout for client side keep-alive connections with no running task
+    pub(crate) fn keepalive_idle_timeout(&self) -> Duration {
+        self.task_idle_policy
+            .keepalive_idle_timeout(self.task_idle_check_duration)
+            .unwrap_or(self.pipeline_read_idle_timeout)
+    }
+
     fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
         match g3_yaml::key::normalize(k).as_str() {
             super::CONFIG_KEY_SERVER_TYPE => Ok(()),
@@ -201,6 +236,13 @@ impl HttpProxyServerConfig {
                 self.listen = Some(config);
                 Ok(())
             }
+            #[cfg(unix)]
+            "listen_unix" | "unix_listen" => {
+                let config = g3_yaml::value::as_unix_listen_config(v)
+                    .context(format!("invalid unix listen config value for key {k}"))?;
+                self.listen_unix = Some(config);
+                Ok(())
+            }
             "listen_in_worker" => {
                 self.listen_in_worker = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -244,6 +286,17 @@ impl HttpProxyServerConfig {
                 self.ingress_net_filter = Some(filter);
                 Ok(())
             }
+            "client_conn_limit" => {
+                let limit = ClientConnLimitConfig::parse_yaml(v).context(format!(
+                    "invalid client conn limit config value for key {k}"
+                ))?;
+                if limit.is_unlimited() {
+                    self.client_conn_limit = None;
+                } else {
+                    self.client_conn_limit = Some(limit);
+                }
+                Ok(())
+            }
             "dst_host_filter_set" => {
                 let filter_set = g3_yaml::value::acl_set::as_dst_host_rule_set_builder(v)
                     .context(format!("invalid dst host acl rule set value for key {k}"))?;
@@ -299,6 +352,11 @@ impl HttpProxyServerConfig {
                     g3_yaml::value::as_i32(v).context(format!("invalid i32 value for key {k}"))?;
                 Ok(())
             }
+            "task_idle_policy" => {
+                self.task_idle_policy = TaskIdlePolicy::parse_yaml(v)
+                    .context(format!("invalid task idle policy value for key {k}"))?;
+                Ok(())
+            }
             "flush_task_log_on_created" => {
                 self.flush_task_log_on_created = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -368,10 +426,41 @@ impl HttpProxyServerConfig {
                     .context(format!("invalid http keepalive config value for key {k}"))?;
                 Ok(())
             }
+            "http_forward_upstream_h2" => {
+                if let Yaml::Boolean(false) = v {
+                    self.http_forward_upstream_h2 = None;
+                } else {
+                    let h2_config = HttpForwardUpstreamH2Config::parse_yaml(v).context(format!(
+                        "invalid http forward upstream h2 value for key {k}"
+                    ))?;
+                    self.http_forward_upstream_h2 = Some(h2_config);
+                }
+                Ok(())
+            }
+            "http_forward_client_h2" => {
+                if let Yaml::Boolean(false) = v {
+                    self.http_forward_client_h2 = None;
+                } else {
+                    let h2_config = HttpForwardClientH2Config::parse_yaml(v)
+                        .context(format!("invalid http forward client h2 value for key {k}"))?;
+                    self.http_forward_client_h2 = Some(h2_config);
+                }
+                Ok(())
+            }
             "http_forward_mark_upstream" => {
                 self.http_forward_mark_upstream = g3_yaml::value::as_bool(v)?;
                 Ok(())
             }
+            "http_forward_retry" => {
+                if let Yaml::Boolean(false) = v {
+                    self.http_forward_retry = None;
+                } else {
+                    let retry = HttpForwardRetryConfig::parse_yaml(v)
+                        .context(format!("invalid http forward retry value for key {k}"))?;
+                    self.http_forward_retry = Some(retry);
+                }
+                Ok(())
+            }
             "echo_chained_info" => {
                 self.echo_chained_info = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -397,6 +486,34 @@ impl HttpProxyServerConfig {
                     .context(format!("invalid boolean value for key {k}"))?;
                 Ok(())
             }
+            "forward_client_cert" => {
+                self.forward_client_cert = g3_yaml::value::as_http_forward_client_cert_mode(v)
+                    .context(format!(
+                        "invalid forward client cert mode value for key {k}"
+                    ))?;
+                Ok(())
+            }
+            "failover_hint" => {
+                if let Yaml::Boolean(false) = v {
+                    self.failover_hint = None;
+                } else {
+                    let hint = HttpFailoverHintConfig::parse_yaml(v)
+                        .context(format!("invalid http failover hint value for key {k}"))?;
+                    self.failover_hint = Some(hint);
+                }
+                Ok(())
+            }
+            "http_header_rewrite" | "header_rewrite" => {
+                let config = HttpHeaderRewriteConfig::parse_yaml(v).context(format!(
+                    "invalid http header rewrite config value for key {k}"
+                ))?;
+                if config.is_empty() {
+                    self.http_header_rewrite = None;
+                } else {
+                    self.http_header_rewrite = Some(Arc::new(config));
+                }
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -417,6 +534,11 @@ impl HttpProxyServerConfig {
                 "server_id is required as http_forward_mark_upstream is on"
             ));
         }
+        if self.http_forward_client_h2.is_some() && self.server_tls_config.is_none() {
+            return Err(anyhow!(
+                "tls_server is required as http_forward_client_h2 is set"
+            ));
+        }
         if self.task_idle_check_duration > IDLE_CHECK_MAXIMUM_DURATION {
             self.task_idle_check_duration = IDLE_CHECK_MAXIMUM_DURATION;
         }
@@ -462,6 +584,10 @@ impl ServerConfig for HttpProxyServerConfig {
         if self.listen != new.listen {
             return ServerConfigDiffAction::ReloadAndRespawn;
         }
+        #[cfg(unix)]
+        if self.listen_unix != new.listen_unix {
+            return ServerConfigDiffAction::ReloadAndRespawn;
+        }
 
         ServerConfigDiffAction::ReloadOnlyConfig
     }
@@ -482,4 +608,12 @@ impl ServerConfig for HttpProxyServerConfig {
     fn task_max_idle_count(&self) -> i32 {
         self.task_idle_max_count
     }
+    #[inline]
+    fn task_idle_policy(&self) -> TaskIdlePolicy {
+        self.task_idle_policy
+    }
+    #[inline]
+    fn http_header_rewrite(&self) -> Option<&Arc<HttpHeaderRewriteConfig>> {
+        self.http_header_rewrite.as_ref()
+    }
 }
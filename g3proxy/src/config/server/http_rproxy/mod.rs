@@ -27,8 +27,8 @@ use g3_tls_ticket::TlsTicketConfig;
 use g3_types::acl::AclNetworkRuleBuilder;
 use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::net::{
-    HttpForwardedHeaderType, HttpKeepAliveConfig, HttpServerId, RustlsServerConfigBuilder,
-    TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig,
+    HttpForwardClientCertMode, HttpForwardedHeaderType, HttpKeepAliveConfig, HttpServerId,
+    RustlsServerConfigBuilder, TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig,
 };
 use g3_types::route::HostMatch;
 use g3_yaml::YamlDocPosition;
@@ -37,6 +37,7 @@ use super::{
     AnyServerConfig, ServerConfig, ServerConfigDiffAction, IDLE_CHECK_DEFAULT_DURATION,
     IDLE_CHECK_MAXIMUM_DURATION,
 };
+use crate::config::idle::TaskIdlePolicy;
 
 mod host;
 pub(crate) use host::HttpHostConfig;
@@ -77,6 +78,7 @@ pub(crate) struct HttpRProxyServerConfig {
     pub(crate) timeout: HttpRProxyServerTimeoutConfig,
     pub(crate) task_idle_check_duration: Duration,
     pub(crate) task_idle_max_count: i32,
+    pub(crate) task_idle_policy: TaskIdlePolicy,
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
@@ -92,6 +94,7 @@ pub(crate) struct HttpRProxyServerConfig {
     pub(crate) http_forward_upstream_keepalive: HttpKeepAliveConfig,
     pub(crate) untrusted_read_limit: Option<TcpSockSpeedLimitConfig>,
     pub(crate) append_forwarded_for: HttpForwardedHeaderType,
+    pub(crate) forward_client_cert: HttpForwardClientCertMode,
     pub(crate) extra_metrics_tags: Option<Arc<StaticMetricsTags>>,
     pub(crate) hosts: HostMatch<Arc<HttpHostConfig>>,
     pub(crate) enable_tls_server: bool,
@@ -117,6 +120,7 @@ impl HttpRProxyServerConfig {
             timeout: HttpRProxyServerTimeoutConfig::default(),
             task_idle_check_duration: IDLE_CHECK_DEFAULT_DURATION,
             task_idle_max_count: 1,
+            task_idle_policy: TaskIdlePolicy::default(),
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
@@ -132,6 +136,7 @@ impl HttpRProxyServerConfig {
             http_forward_upstream_keepalive: Default::default(),
             untrusted_read_limit: None,
             append_forwarded_for: HttpForwardedHeaderType::default(),
+            forward_client_cert: HttpForwardClientCertMode::default(),
             extra_metrics_tags: None,
             hosts: Default::default(),
             enable_tls_server: false,
@@ -153,6 +158,13 @@ impl HttpRProxyServerConfig {
         Ok(server)
     }
 
+    /// the idle timeout for client side keep-alive connections with no running task
+    pub(crate) fn keepalive_idle_timeout(&self) -> Duration {
+        self.task_idle_policy
+            .keepalive_idle_timeout(self.task_idle_check_duration)
+            .unwrap_or(self.pipeline_read_idle_timeout)
+    }
+
     fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
         match g3_yaml::key::normalize(k).as_str() {
             super::CONFIG_KEY_SERVER_TYPE => Ok(()),
@@ -239,6 +251,11 @@ impl HttpRProxyServerConfig {
                     g3_yaml::value::as_i32(v).context(format!("invalid i32 value for key {k}"))?;
                 Ok(())
             }
+            "task_idle_policy" => {
+                self.task_idle_policy = TaskIdlePolicy::parse_yaml(v)
+                    .context(format!("invalid task idle policy value for key {k}"))?;
+                Ok(())
+            }
             "flush_task_log_on_created" => {
                 self.flush_task_log_on_created = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -316,6 +333,13 @@ impl HttpRProxyServerConfig {
                     ))?;
                 Ok(())
             }
+            "forward_client_cert" => {
+                self.forward_client_cert = g3_yaml::value::as_http_forward_client_cert_mode(v)
+                    .context(format!(
+                        "invalid forward client cert mode value for key {k}"
+                    ))?;
+                Ok(())
+            }
             "hosts" | "sites" => {
                 self.hosts = g3_yaml::value::as_host_matched_obj(v, self.position.as_ref())
                     .context(format!(
@@ -429,4 +453,8 @@ impl ServerConfig for HttpRProxyServerConfig {
     fn task_max_idle_count(&self) -> i32 {
         self.task_idle_max_count
     }
+    #[inline]
+    fn task_idle_policy(&self) -> TaskIdlePolicy {
+        self.task_idle_policy
+    }
 }
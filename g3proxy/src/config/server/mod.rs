@@ -30,6 +30,14 @@ use g3_yaml::{HybridParser, YamlDocPosition};
 
 use crate::audit::AuditHandle;
 use crate::auth::UserGroup;
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
+use crate::config::idle::TaskIdlePolicy;
+
+pub(crate) mod client_classify;
+pub(crate) mod client_conn_limit;
+pub(crate) mod http_failover_hint;
+pub(crate) mod http_forward_h2;
+pub(crate) mod http_forward_retry;
 
 pub(crate) mod dummy_close;
 pub(crate) mod intelli_proxy;
@@ -43,6 +51,7 @@ pub(crate) mod http_proxy;
 pub(crate) mod http_rproxy;
 pub(crate) mod sni_proxy;
 pub(crate) mod socks_proxy;
+pub(crate) mod speed_test;
 pub(crate) mod tcp_stream;
 #[cfg(any(
     target_os = "linux",
@@ -52,9 +61,11 @@ pub(crate) mod tcp_stream;
 ))]
 pub(crate) mod tcp_tproxy;
 pub(crate) mod tls_stream;
+#[cfg(target_os = "linux")]
+pub(crate) mod udp_tproxy;
 
 mod registry;
-pub(crate) use registry::clear;
+pub(crate) use registry::{clear, del, get_all_names, restore, snapshot};
 
 const CONFIG_KEY_SERVER_TYPE: &str = "type";
 const CONFIG_KEY_SERVER_NAME: &str = "name";
@@ -105,6 +116,12 @@ pub(crate) trait ServerConfig {
     fn task_max_idle_count(&self) -> i32 {
         1
     }
+    fn task_idle_policy(&self) -> TaskIdlePolicy {
+        TaskIdlePolicy::default()
+    }
+    fn http_header_rewrite(&self) -> Option<&Arc<HttpHeaderRewriteConfig>> {
+        None
+    }
 
     fn get_user_group(&self) -> Option<Arc<UserGroup>> {
         if self.user_group().is_empty() {
@@ -144,11 +161,14 @@ pub(crate) enum AnyServerConfig {
         target_os = "openbsd"
     ))]
     TcpTProxy(tcp_tproxy::TcpTProxyServerConfig),
+    #[cfg(target_os = "linux")]
+    UdpTProxy(udp_tproxy::UdpTProxyServerConfig),
     TlsStream(Box<tls_stream::TlsStreamServerConfig>),
     SniProxy(Box<sni_proxy::SniProxyServerConfig>),
     SocksProxy(Box<socks_proxy::SocksProxyServerConfig>),
     HttpProxy(Box<http_proxy::HttpProxyServerConfig>),
     HttpRProxy(Box<http_rproxy::HttpRProxyServerConfig>),
+    SpeedTest(speed_test::SpeedTestServerConfig),
 }
 
 macro_rules! impl_transparent0 {
@@ -170,11 +190,14 @@ macro_rules! impl_transparent0 {
                     target_os = "openbsd"
                 ))]
                 AnyServerConfig::TcpTProxy(s) => s.$f(),
+                #[cfg(target_os = "linux")]
+                AnyServerConfig::UdpTProxy(s) => s.$f(),
                 AnyServerConfig::TlsStream(s) => s.$f(),
                 AnyServerConfig::SniProxy(s) => s.$f(),
                 AnyServerConfig::SocksProxy(s) => s.$f(),
                 AnyServerConfig::HttpProxy(s) => s.$f(),
                 AnyServerConfig::HttpRProxy(s) => s.$f(),
+                AnyServerConfig::SpeedTest(s) => s.$f(),
             }
         }
     };
@@ -199,11 +222,14 @@ macro_rules! impl_transparent1 {
                     target_os = "openbsd"
                 ))]
                 AnyServerConfig::TcpTProxy(s) => s.$f(p),
+                #[cfg(target_os = "linux")]
+                AnyServerConfig::UdpTProxy(s) => s.$f(p),
                 AnyServerConfig::TlsStream(s) => s.$f(p),
                 AnyServerConfig::SniProxy(s) => s.$f(p),
                 AnyServerConfig::SocksProxy(s) => s.$f(p),
                 AnyServerConfig::HttpProxy(s) => s.$f(p),
                 AnyServerConfig::HttpRProxy(s) => s.$f(p),
+                AnyServerConfig::SpeedTest(s) => s.$f(p),
             }
         }
     };
@@ -314,6 +340,12 @@ fn load_server(
                 .context("failed to load this TcpTProxy server")?;
             Ok(AnyServerConfig::TcpTProxy(server))
         }
+        #[cfg(target_os = "linux")]
+        "udp_tproxy" | "udptproxy" => {
+            let server = udp_tproxy::UdpTProxyServerConfig::parse(map, position)
+                .context("failed to load this UdpTProxy server")?;
+            Ok(AnyServerConfig::UdpTProxy(server))
+        }
         "tls_stream" | "tlsstream" => {
             let server = tls_stream::TlsStreamServerConfig::parse(map, position)
                 .context("failed to load this TLsStream server")?;
@@ -340,6 +372,11 @@ fn load_server(
                 .context("failed to load this HttpRProxy server")?;
             Ok(AnyServerConfig::HttpRProxy(Box::new(server)))
         }
+        "speed_test" | "speedtest" => {
+            let server = speed_test::SpeedTestServerConfig::parse(map, position)
+                .context("failed to load this SpeedTest server")?;
+            Ok(AnyServerConfig::SpeedTest(server))
+        }
         _ => Err(anyhow!("unsupported server type {}", server_type)),
     }
 }
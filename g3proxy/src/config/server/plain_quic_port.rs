@@ -23,7 +23,7 @@ use yaml_rust::{yaml, Yaml};
 use g3_tls_ticket::TlsTicketConfig;
 use g3_types::acl::AclNetworkRuleBuilder;
 use g3_types::metrics::NodeName;
-use g3_types::net::{RustlsServerConfigBuilder, UdpListenConfig};
+use g3_types::net::{QuinnMigrationConfig, RustlsServerConfigBuilder, UdpListenConfig};
 use g3_yaml::YamlDocPosition;
 
 use super::ServerConfig;
@@ -50,6 +50,7 @@ pub(crate) struct PlainQuicPortConfig {
     pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
     pub(crate) server: NodeName,
     pub(crate) offline_rebind_port: Option<u16>,
+    pub(crate) migration: QuinnMigrationConfig,
 }
 
 impl PlainQuicPortConfig {
@@ -64,6 +65,7 @@ impl PlainQuicPortConfig {
             ingress_net_filter: None,
             server: NodeName::default(),
             offline_rebind_port: None,
+            migration: QuinnMigrationConfig::default(),
         }
     }
 
@@ -106,6 +108,11 @@ impl PlainQuicPortConfig {
                     g3_yaml::value::as_rustls_server_config_builder(v, Some(lookup_dir))?;
                 Ok(())
             }
+            "connection_migration" | "migration" => {
+                self.migration = g3_yaml::value::as_quinn_migration_config(v)
+                    .context(format!("invalid quinn migration config value for key {k}"))?;
+                Ok(())
+            }
             "tls_ticketer" => {
                 let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
                 let ticketer = TlsTicketConfig::parse_yaml(v, Some(lookup_dir))
@@ -185,7 +192,7 @@ impl ServerConfig for PlainQuicPortConfig {
         if self.listen != new.listen {
             flags.set(PlainQuicPortUpdateFlags::LISTEN, true);
         }
-        if self.tls_server != new.tls_server {
+        if self.tls_server != new.tls_server || self.migration != new.migration {
             flags.set(PlainQuicPortUpdateFlags::QUINN, true);
         }
         if self.server != new.server {
@@ -27,16 +27,20 @@ use g3_io_ext::{LimitedCopyConfig, LimitedUdpRelayConfig};
 use g3_types::acl::{AclExactPortRule, AclNetworkRuleBuilder};
 use g3_types::acl_set::AclDstHostRuleSetBuilder;
 use g3_types::metrics::{NodeName, StaticMetricsTags};
+#[cfg(unix)]
+use g3_types::net::UnixListenConfig;
 use g3_types::net::{
     PortRange, SocketBufferConfig, TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig,
     UdpMiscSockOpts, UdpSockSpeedLimitConfig,
 };
 use g3_yaml::YamlDocPosition;
 
+use super::client_conn_limit::ClientConnLimitConfig;
 use super::{
     AnyServerConfig, ServerConfig, ServerConfigDiffAction, IDLE_CHECK_DEFAULT_DURATION,
     IDLE_CHECK_MAXIMUM_DURATION,
 };
+use crate::config::idle::TaskIdlePolicy;
 
 const SERVER_CONFIG_TYPE: &str = "SocksProxy";
 
@@ -69,12 +73,15 @@ pub(crate) struct SocksProxyServerConfig {
     pub(crate) shared_logger: Option<AsciiString>,
     pub(crate) listen: Option<TcpListenConfig>,
     pub(crate) listen_in_worker: bool,
+    #[cfg(unix)]
+    pub(crate) listen_unix: Option<UnixListenConfig>,
     pub(crate) use_udp_associate: bool,
     pub(crate) udp_bind4: Vec<IpAddr>,
     pub(crate) udp_bind6: Vec<IpAddr>,
     pub(crate) udp_bind_port_range: Option<PortRange>,
     pub(crate) udp_socket_buffer: SocketBufferConfig,
     pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
+    pub(crate) client_conn_limit: Option<ClientConnLimitConfig>,
     pub(crate) dst_host_filter: Option<AclDstHostRuleSetBuilder>,
     pub(crate) dst_port_filter: Option<AclExactPortRule>,
     pub(crate) tcp_sock_speed_limit: TcpSockSpeedLimitConfig,
@@ -82,6 +89,7 @@ pub(crate) struct SocksProxyServerConfig {
     pub(crate) timeout: SocksProxyServerTimeoutConfig,
     pub(crate) task_idle_check_duration: Duration,
     pub(crate) task_idle_max_count: i32,
+    pub(crate) task_idle_policy: TaskIdlePolicy,
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
@@ -104,12 +112,15 @@ impl SocksProxyServerConfig {
             shared_logger: None,
             listen: None,
             listen_in_worker: false,
+            #[cfg(unix)]
+            listen_unix: None,
             use_udp_associate: false,
             udp_bind4: Vec::new(),
             udp_bind6: Vec::new(),
             udp_bind_port_range: None,
             udp_socket_buffer: SocketBufferConfig::default(),
             ingress_net_filter: None,
+            client_conn_limit: None,
             dst_host_filter: None,
             dst_port_filter: None,
             tcp_sock_speed_limit: TcpSockSpeedLimitConfig::default(),
@@ -117,6 +128,7 @@ impl SocksProxyServerConfig {
             timeout: SocksProxyServerTimeoutConfig::default(),
             task_idle_check_duration: IDLE_CHECK_DEFAULT_DURATION,
             task_idle_max_count: 1,
+            task_idle_policy: TaskIdlePolicy::default(),
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
@@ -177,6 +189,13 @@ impl SocksProxyServerConfig {
                 self.listen = Some(config);
                 Ok(())
             }
+            #[cfg(unix)]
+            "listen_unix" | "unix_listen" => {
+                let config = g3_yaml::value::as_unix_listen_config(v)
+                    .context(format!("invalid unix listen config value for key {k}"))?;
+                self.listen_unix = Some(config);
+                Ok(())
+            }
             "listen_in_worker" => {
                 self.listen_in_worker = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -217,6 +236,17 @@ impl SocksProxyServerConfig {
                 self.ingress_net_filter = Some(filter);
                 Ok(())
             }
+            "client_conn_limit" => {
+                let limit = ClientConnLimitConfig::parse_yaml(v).context(format!(
+                    "invalid client conn limit config value for key {k}"
+                ))?;
+                if limit.is_unlimited() {
+                    self.client_conn_limit = None;
+                } else {
+                    self.client_conn_limit = Some(limit);
+                }
+                Ok(())
+            }
             "dst_host_filter_set" => {
                 let filter_set = g3_yaml::value::acl_set::as_dst_host_rule_set_builder(v)
                     .context(format!("invalid dst host acl rule set value for key {k}"))?;
@@ -301,6 +331,11 @@ impl SocksProxyServerConfig {
                     g3_yaml::value::as_i32(v).context(format!("invalid i32 value for key {k}"))?;
                 Ok(())
             }
+            "task_idle_policy" => {
+                self.task_idle_policy = TaskIdlePolicy::parse_yaml(v)
+                    .context(format!("invalid task idle policy value for key {k}"))?;
+                Ok(())
+            }
             "flush_task_log_on_created" => {
                 self.flush_task_log_on_created = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -402,6 +437,10 @@ impl ServerConfig for SocksProxyServerConfig {
         if self.listen != new.listen {
             return ServerConfigDiffAction::ReloadAndRespawn;
         }
+        #[cfg(unix)]
+        if self.listen_unix != new.listen_unix {
+            return ServerConfigDiffAction::ReloadAndRespawn;
+        }
 
         ServerConfigDiffAction::ReloadOnlyConfig
     }
@@ -422,4 +461,8 @@ impl ServerConfig for SocksProxyServerConfig {
     fn task_max_idle_count(&self) -> i32 {
         self.task_idle_max_count
     }
+    #[inline]
+    fn task_idle_policy(&self) -> TaskIdlePolicy {
+        self.task_idle_policy
+    }
 }
@@ -0,0 +1,183 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::{yaml, Yaml};
+
+use g3_types::acl::AclNetworkRuleBuilder;
+use g3_types::metrics::NodeName;
+use g3_types::net::TcpListenConfig;
+use g3_yaml::YamlDocPosition;
+
+use super::{AnyServerConfig, ServerConfig, ServerConfigDiffAction};
+
+const SERVER_CONFIG_TYPE: &str = "SpeedTest";
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct SpeedTestServerConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) listen: Option<TcpListenConfig>,
+    pub(crate) listen_in_worker: bool,
+    pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
+    pub(crate) req_hdr_max_size: usize,
+    pub(crate) req_hdr_recv_timeout: Duration,
+    pub(crate) download_default_size: u64,
+    pub(crate) download_max_size: u64,
+    pub(crate) upload_max_size: u64,
+    pub(crate) body_line_max_len: usize,
+}
+
+impl SpeedTestServerConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        SpeedTestServerConfig {
+            name: NodeName::default(),
+            position,
+            listen: None,
+            listen_in_worker: false,
+            ingress_net_filter: None,
+            req_hdr_max_size: 64 * 1024, // 64KiB
+            req_hdr_recv_timeout: Duration::from_secs(30),
+            download_default_size: 10 * 1024 * 1024, // 10MiB
+            download_max_size: 1024 * 1024 * 1024,   // 1GiB
+            upload_max_size: 1024 * 1024 * 1024,     // 1GiB
+            body_line_max_len: 8192,
+        }
+    }
+
+    pub(crate) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut server = SpeedTestServerConfig::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| server.set(k, v))?;
+
+        server.check()?;
+        Ok(server)
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_SERVER_TYPE => Ok(()),
+            super::CONFIG_KEY_SERVER_NAME => {
+                self.name = g3_yaml::value::as_metrics_name(v)?;
+                Ok(())
+            }
+            "listen" => {
+                let config = g3_yaml::value::as_tcp_listen_config(v)
+                    .context(format!("invalid tcp listen config value for key {k}"))?;
+                self.listen = Some(config);
+                Ok(())
+            }
+            "listen_in_worker" => {
+                self.listen_in_worker = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "ingress_network_filter" | "ingress_net_filter" => {
+                let filter = g3_yaml::value::acl::as_ingress_network_rule_builder(v).context(
+                    format!("invalid ingress network acl rule value for key {k}"),
+                )?;
+                self.ingress_net_filter = Some(filter);
+                Ok(())
+            }
+            "req_header_max_size" | "req_hdr_max_size" => {
+                self.req_hdr_max_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            "req_header_recv_timeout" | "req_hdr_recv_timeout" => {
+                self.req_hdr_recv_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "download_default_size" => {
+                self.download_default_size = g3_yaml::humanize::as_u64(v)
+                    .context(format!("invalid humanize u64 value for key {k}"))?;
+                Ok(())
+            }
+            "download_max_size" => {
+                self.download_max_size = g3_yaml::humanize::as_u64(v)
+                    .context(format!("invalid humanize u64 value for key {k}"))?;
+                Ok(())
+            }
+            "upload_max_size" => {
+                self.upload_max_size = g3_yaml::humanize::as_u64(v)
+                    .context(format!("invalid humanize u64 value for key {k}"))?;
+                Ok(())
+            }
+            "body_line_max_length" | "body_line_max_len" => {
+                self.body_line_max_len = g3_yaml::value::as_usize(v)?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.download_default_size > self.download_max_size {
+            self.download_default_size = self.download_max_size;
+        }
+
+        Ok(())
+    }
+}
+
+impl ServerConfig for SpeedTestServerConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn server_type(&self) -> &'static str {
+        SERVER_CONFIG_TYPE
+    }
+
+    fn escaper(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn user_group(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn auditor(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn diff_action(&self, new: &AnyServerConfig) -> ServerConfigDiffAction {
+        let AnyServerConfig::SpeedTest(new) = new else {
+            return ServerConfigDiffAction::SpawnNew;
+        };
+
+        if self.eq(new) {
+            return ServerConfigDiffAction::NoAction;
+        }
+
+        if self.listen != new.listen {
+            return ServerConfigDiffAction::ReloadAndRespawn;
+        }
+
+        ServerConfigDiffAction::ReloadOnlyConfig
+    }
+}
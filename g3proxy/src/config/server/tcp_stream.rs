@@ -32,6 +32,7 @@ use g3_types::net::{
 use g3_yaml::YamlDocPosition;
 
 use super::{AnyServerConfig, ServerConfig, ServerConfigDiffAction, IDLE_CHECK_MAXIMUM_DURATION};
+use crate::config::idle::TaskIdlePolicy;
 
 const SERVER_CONFIG_TYPE: &str = "TcpStream";
 
@@ -52,6 +53,7 @@ pub(crate) struct TcpStreamServerConfig {
     pub(crate) tcp_sock_speed_limit: TcpSockSpeedLimitConfig,
     pub(crate) task_idle_check_duration: Duration,
     pub(crate) task_idle_max_count: i32,
+    pub(crate) task_idle_policy: TaskIdlePolicy,
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
@@ -78,6 +80,7 @@ impl TcpStreamServerConfig {
             tcp_sock_speed_limit: TcpSockSpeedLimitConfig::default(),
             task_idle_check_duration: Duration::from_secs(300),
             task_idle_max_count: 1,
+            task_idle_policy: TaskIdlePolicy::default(),
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
@@ -211,6 +214,11 @@ impl TcpStreamServerConfig {
                     g3_yaml::value::as_i32(v).context(format!("invalid i32 value for key {k}"))?;
                 Ok(())
             }
+            "task_idle_policy" => {
+                self.task_idle_policy = TaskIdlePolicy::parse_yaml(v)
+                    .context(format!("invalid task idle policy value for key {k}"))?;
+                Ok(())
+            }
             "flush_task_log_on_created" => {
                 self.flush_task_log_on_created = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -309,4 +317,8 @@ impl ServerConfig for TcpStreamServerConfig {
     fn task_max_idle_count(&self) -> i32 {
         self.task_idle_max_count
     }
+    #[inline]
+    fn task_idle_policy(&self) -> TaskIdlePolicy {
+        self.task_idle_policy
+    }
 }
@@ -27,7 +27,9 @@ use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::net::{TcpListenConfig, TcpMiscSockOpts, TcpSockSpeedLimitConfig};
 use g3_yaml::YamlDocPosition;
 
+use super::client_classify::ClientUserClassifyConfig;
 use super::{AnyServerConfig, ServerConfig, ServerConfigDiffAction, IDLE_CHECK_MAXIMUM_DURATION};
+use crate::config::idle::TaskIdlePolicy;
 
 const SERVER_CONFIG_TYPE: &str = "TcpTProxy";
 
@@ -36,6 +38,8 @@ pub(crate) struct TcpTProxyServerConfig {
     name: NodeName,
     position: Option<YamlDocPosition>,
     pub(crate) escaper: NodeName,
+    pub(crate) user_group: NodeName,
+    pub(crate) user_classify: Option<ClientUserClassifyConfig>,
     pub(crate) auditor: NodeName,
     pub(crate) shared_logger: Option<AsciiString>,
     pub(crate) listen: TcpListenConfig,
@@ -44,6 +48,7 @@ pub(crate) struct TcpTProxyServerConfig {
     pub(crate) tcp_sock_speed_limit: TcpSockSpeedLimitConfig,
     pub(crate) task_idle_check_duration: Duration,
     pub(crate) task_idle_max_count: i32,
+    pub(crate) task_idle_policy: TaskIdlePolicy,
     pub(crate) flush_task_log_on_created: bool,
     pub(crate) flush_task_log_on_connected: bool,
     pub(crate) task_log_flush_interval: Option<Duration>,
@@ -58,6 +63,8 @@ impl TcpTProxyServerConfig {
             name: NodeName::default(),
             position,
             escaper: NodeName::default(),
+            user_group: NodeName::default(),
+            user_classify: None,
             auditor: NodeName::default(),
             shared_logger: None,
             listen: TcpListenConfig::default(),
@@ -66,6 +73,7 @@ impl TcpTProxyServerConfig {
             tcp_sock_speed_limit: TcpSockSpeedLimitConfig::default(),
             task_idle_check_duration: Duration::from_secs(300),
             task_idle_max_count: 1,
+            task_idle_policy: TaskIdlePolicy::default(),
             flush_task_log_on_created: false,
             flush_task_log_on_connected: false,
             task_log_flush_interval: None,
@@ -98,6 +106,21 @@ impl TcpTProxyServerConfig {
                 self.escaper = g3_yaml::value::as_metrics_name(v)?;
                 Ok(())
             }
+            "user_group" => {
+                self.user_group = g3_yaml::value::as_metrics_name(v)?;
+                Ok(())
+            }
+            "user_classify" | "client_user_classify" => {
+                let config = ClientUserClassifyConfig::parse_yaml(v).context(format!(
+                    "invalid client user classify config value for key {k}"
+                ))?;
+                if config.is_empty() {
+                    self.user_classify = None;
+                } else {
+                    self.user_classify = Some(config);
+                }
+                Ok(())
+            }
             "auditor" => {
                 self.auditor = g3_yaml::value::as_metrics_name(v)?;
                 Ok(())
@@ -161,6 +184,11 @@ impl TcpTProxyServerConfig {
                     g3_yaml::value::as_i32(v).context(format!("invalid i32 value for key {k}"))?;
                 Ok(())
             }
+            "task_idle_policy" => {
+                self.task_idle_policy = TaskIdlePolicy::parse_yaml(v)
+                    .context(format!("invalid task idle policy value for key {k}"))?;
+                Ok(())
+            }
             "flush_task_log_on_created" => {
                 self.flush_task_log_on_created = g3_yaml::value::as_bool(v)?;
                 Ok(())
@@ -186,6 +214,9 @@ impl TcpTProxyServerConfig {
         if self.escaper.is_empty() {
             return Err(anyhow!("escaper is not set"));
         }
+        if self.user_classify.is_some() && self.user_group.is_empty() {
+            return Err(anyhow!("user group is required if user classify is set"));
+        }
         if self.task_idle_check_duration > IDLE_CHECK_MAXIMUM_DURATION {
             self.task_idle_check_duration = IDLE_CHECK_MAXIMUM_DURATION;
         }
@@ -216,7 +247,7 @@ impl ServerConfig for TcpTProxyServerConfig {
     }
 
     fn user_group(&self) -> &NodeName {
-        Default::default()
+        &self.user_group
     }
 
     fn auditor(&self) -> &NodeName {
@@ -255,4 +286,8 @@ impl ServerConfig for TcpTProxyServerConfig {
     fn task_max_idle_count(&self) -> i32 {
         self.task_idle_max_count
     }
+    #[inline]
+    fn task_idle_policy(&self) -> TaskIdlePolicy {
+        self.task_idle_policy
+    }
 }
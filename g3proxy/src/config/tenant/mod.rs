@@ -0,0 +1,234 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::{yaml, Yaml};
+
+use g3_types::metrics::NodeName;
+use g3_yaml::{HybridParser, YamlDocPosition};
+
+use super::ConfigGeneration;
+
+mod registry;
+pub(crate) use registry::{clear, get, get_all_names, get_tenant_of, restore, snapshot};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TenantMemberType {
+    Resolver,
+    Escaper,
+    UserGroup,
+    Auditor,
+    Server,
+}
+
+#[derive(Clone)]
+pub(crate) struct TenantConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) resolver: BTreeSet<NodeName>,
+    pub(crate) escaper: BTreeSet<NodeName>,
+    pub(crate) user_group: BTreeSet<NodeName>,
+    pub(crate) auditor: BTreeSet<NodeName>,
+    pub(crate) server: BTreeSet<NodeName>,
+}
+
+impl TenantConfig {
+    fn new(position: Option<YamlDocPosition>) -> Self {
+        TenantConfig {
+            name: NodeName::default(),
+            position,
+            resolver: BTreeSet::new(),
+            escaper: BTreeSet::new(),
+            user_group: BTreeSet::new(),
+            auditor: BTreeSet::new(),
+            server: BTreeSet::new(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    #[inline]
+    pub(crate) fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    pub(crate) fn members(&self, member_type: TenantMemberType) -> &BTreeSet<NodeName> {
+        match member_type {
+            TenantMemberType::Resolver => &self.resolver,
+            TenantMemberType::Escaper => &self.escaper,
+            TenantMemberType::UserGroup => &self.user_group,
+            TenantMemberType::Auditor => &self.auditor,
+            TenantMemberType::Server => &self.server,
+        }
+    }
+
+    fn parse(&mut self, map: &yaml::Hash, conf_dir: &Path) -> anyhow::Result<()> {
+        // all relative paths in the tenant doc are relative to the tenant conf file
+        let conf_dir = self
+            .position
+            .as_ref()
+            .and_then(|p| p.path.parent())
+            .unwrap_or(conf_dir)
+            .to_path_buf();
+
+        // the name should be set first, as it will be used in error messages
+        let name = g3_yaml::hash_get_required(map, "name")?;
+        self.name = g3_yaml::value::as_metrics_name(name)
+            .context("invalid metrics name value for key name")?;
+
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "name" => Ok(()),
+            "resolver" => {
+                self.resolver = load_members(super::resolver::get_all_names, || {
+                    super::resolver::load_all(v, &conf_dir)
+                })
+                .context(format!("failed to load resolvers in key {k}"))?;
+                Ok(())
+            }
+            "escaper" => {
+                self.escaper = load_members(super::escaper::get_all_names, || {
+                    super::escaper::load_all(v, &conf_dir)
+                })
+                .context(format!("failed to load escapers in key {k}"))?;
+                Ok(())
+            }
+            "user" | "user_group" => {
+                self.user_group = load_members(super::auth::get_all_names, || {
+                    super::auth::load_all(v, &conf_dir)
+                })
+                .context(format!("failed to load user groups in key {k}"))?;
+                Ok(())
+            }
+            "auditor" => {
+                self.auditor = load_members(super::audit::get_all_names, || {
+                    super::audit::load_all(v, &conf_dir)
+                })
+                .context(format!("failed to load auditors in key {k}"))?;
+                Ok(())
+            }
+            "server" => {
+                self.server = load_members(super::server::get_all_names, || {
+                    super::server::load_all(v, &conf_dir)
+                })
+                .context(format!("failed to load servers in key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })
+        .context(format!("failed to load tenant {}", self.name))
+    }
+
+    /// Remove all the member configs from the registries
+    fn unload_members(&self) {
+        self.resolver.iter().for_each(super::resolver::del);
+        self.escaper.iter().for_each(super::escaper::del);
+        self.user_group.iter().for_each(super::auth::del);
+        self.auditor.iter().for_each(super::audit::del);
+        self.server.iter().for_each(super::server::del);
+    }
+}
+
+fn load_members<N, L>(get_names: N, load: L) -> anyhow::Result<BTreeSet<NodeName>>
+where
+    N: Fn() -> Vec<NodeName>,
+    L: FnOnce() -> anyhow::Result<()>,
+{
+    let old_names: BTreeSet<NodeName> = get_names().into_iter().collect();
+    load()?;
+    let members = get_names()
+        .into_iter()
+        .filter(|name| !old_names.contains(name))
+        .collect();
+    Ok(members)
+}
+
+pub(crate) fn load_all(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
+    let parser = HybridParser::new(conf_dir, g3_daemon::opts::config_file_extension());
+    parser.foreach_map(v, |map, position| {
+        let tenant = load_tenant(map, position, conf_dir)?;
+        if let Some(old_tenant) = registry::add(tenant) {
+            Err(anyhow!(
+                "tenant with name {} already exists",
+                old_tenant.name()
+            ))
+        } else {
+            Ok(())
+        }
+    })
+}
+
+fn load_tenant(
+    map: &yaml::Hash,
+    position: Option<YamlDocPosition>,
+    conf_dir: &Path,
+) -> anyhow::Result<TenantConfig> {
+    let mut tenant = TenantConfig::new(position);
+    tenant.parse(map, conf_dir)?;
+    Ok(tenant)
+}
+
+/// Reload the config of the tenant, only members of this tenant will be changed.
+///
+/// The member configs will be restored if any error occurs, or the previous generation will be
+/// returned so it can be used for rollback.
+pub(crate) fn reload(name: &NodeName) -> anyhow::Result<ConfigGeneration> {
+    let Some(old_tenant) = registry::get(name) else {
+        return Err(anyhow!("no tenant with name {name} found"));
+    };
+    let Some(position) = old_tenant.position() else {
+        return Err(anyhow!(
+            "no config position for tenant {name} found, reload is not supported"
+        ));
+    };
+
+    let generation = ConfigGeneration::take();
+    old_tenant.unload_members();
+    match load_at_position(&position) {
+        Ok(tenant) if tenant.name() == name => {
+            registry::add(tenant);
+            Ok(generation)
+        }
+        Ok(tenant) => {
+            generation.restore();
+            Err(anyhow!(
+                "tenant at position {position} has name {}, while we expect {name}",
+                tenant.name()
+            ))
+        }
+        Err(e) => {
+            generation.restore();
+            Err(e)
+        }
+    }
+}
+
+fn load_at_position(position: &YamlDocPosition) -> anyhow::Result<TenantConfig> {
+    let doc = g3_yaml::load_doc(position)?;
+    let Yaml::Hash(map) = doc else {
+        return Err(anyhow!("yaml doc {position} is not a map"));
+    };
+    let conf_dir = g3_daemon::config::get_lookup_dir(Some(position))?;
+    let tenant = load_tenant(&map, Some(position.clone()), conf_dir)?;
+
+    super::check_dependency()?;
+    Ok(tenant)
+}
@@ -0,0 +1,64 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use g3_types::metrics::NodeName;
+
+use super::{TenantConfig, TenantMemberType};
+
+static TENANT_CONFIG_REGISTRY: LazyLock<Mutex<HashMap<NodeName, Arc<TenantConfig>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub(crate) fn clear() {
+    let mut ht = TENANT_CONFIG_REGISTRY.lock().unwrap();
+    ht.clear();
+}
+
+pub(crate) fn snapshot() -> HashMap<NodeName, Arc<TenantConfig>> {
+    let ht = TENANT_CONFIG_REGISTRY.lock().unwrap();
+    ht.clone()
+}
+
+pub(crate) fn restore(snapshot: HashMap<NodeName, Arc<TenantConfig>>) {
+    let mut ht = TENANT_CONFIG_REGISTRY.lock().unwrap();
+    *ht = snapshot;
+}
+
+pub(super) fn add(tenant: TenantConfig) -> Option<Arc<TenantConfig>> {
+    let name = tenant.name().clone();
+    let mut ht = TENANT_CONFIG_REGISTRY.lock().unwrap();
+    ht.insert(name, Arc::new(tenant))
+}
+
+pub(crate) fn get(name: &NodeName) -> Option<Arc<TenantConfig>> {
+    let ht = TENANT_CONFIG_REGISTRY.lock().unwrap();
+    ht.get(name).cloned()
+}
+
+pub(crate) fn get_all_names() -> Vec<NodeName> {
+    let ht = TENANT_CONFIG_REGISTRY.lock().unwrap();
+    ht.keys().cloned().collect()
+}
+
+/// Get the name of the tenant that the member belongs to
+pub(crate) fn get_tenant_of(member_type: TenantMemberType, name: &NodeName) -> Option<NodeName> {
+    let ht = TENANT_CONFIG_REGISTRY.lock().unwrap();
+    ht.values()
+        .find(|t| t.members(member_type).contains(name))
+        .map(|t| t.name().clone())
+}
@@ -16,5 +16,6 @@
 
 mod reload;
 pub(super) use reload::{
-    reload_auditor, reload_escaper, reload_resolver, reload_server, reload_user_group,
+    rebuild_escaper, reload_auditor, reload_config, reload_escaper, reload_resolver, reload_server,
+    reload_tenant, reload_user_group,
 };
@@ -40,3 +40,29 @@ impl_reload!(reload_auditor, audit);
 impl_reload!(reload_resolver, resolve);
 impl_reload!(reload_escaper, escape);
 impl_reload!(reload_server, serve);
+
+pub(in crate::control) async fn reload_config() -> anyhow::Result<()> {
+    g3_daemon::runtime::main_handle()
+        .ok_or(anyhow!("unable to get main runtime handle"))?
+        .spawn(crate::reload::reload_all())
+        .await
+        .map_err(|e| anyhow!("failed to spawn reload task: {e}"))
+}
+
+pub(in crate::control) async fn rebuild_escaper(name: String) -> anyhow::Result<()> {
+    let name = unsafe { NodeName::new_unchecked(name) };
+    g3_daemon::runtime::main_handle()
+        .ok_or(anyhow!("unable to get main runtime handle"))?
+        .spawn(async move { crate::escape::rebuild(&name).await })
+        .await
+        .map_err(|e| anyhow!("failed to spawn rebuild task: {e}"))?
+}
+
+pub(in crate::control) async fn reload_tenant(name: String) -> anyhow::Result<()> {
+    let name = unsafe { NodeName::new_unchecked(name) };
+    g3_daemon::runtime::main_handle()
+        .ok_or(anyhow!("unable to get main runtime handle"))?
+        .spawn(crate::reload::reload_tenant(name))
+        .await
+        .map_err(|e| anyhow!("failed to spawn reload task: {e}"))?
+}
@@ -0,0 +1,120 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use capnp::capability::Promise;
+use capnp_rpc::pry;
+
+use g3_daemon::control::CtlAccessLevel;
+use g3_icap_client::IcapMethod;
+use g3_types::metrics::NodeName;
+
+use g3proxy_proto::auditor_capnp::auditor_control;
+
+pub(super) struct AuditorControlImpl {
+    name: NodeName,
+    access: CtlAccessLevel,
+}
+
+impl AuditorControlImpl {
+    pub(super) fn new_client(
+        name: &str,
+        access: CtlAccessLevel,
+    ) -> anyhow::Result<auditor_control::Client> {
+        let name = unsafe { NodeName::new_unchecked(name) };
+        if !crate::audit::get_names().contains(&name) {
+            return Err(anyhow::anyhow!("no auditor named {name} found"));
+        }
+        Ok(capnp_rpc::new_client(AuditorControlImpl { name, access }))
+    }
+}
+
+impl auditor_control::Server for AuditorControlImpl {
+    fn flush_icap_verdict_cache(
+        &mut self,
+        _params: auditor_control::FlushIcapVerdictCacheParams,
+        mut results: auditor_control::FlushIcapVerdictCacheResults,
+    ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
+        let mut builder = results.get().init_result();
+        match crate::audit::flush_icap_verdict_cache(&self.name) {
+            Ok(n) => builder.set_ok(format!("flushed {n} entries").as_str()),
+            Err(e) => {
+                let mut ev = builder.init_err();
+                ev.set_code(-1);
+                ev.set_reason(format!("{e:?}").as_str());
+            }
+        }
+        Promise::ok(())
+    }
+
+    fn get_icap_verdict_cache_stats(
+        &mut self,
+        _params: auditor_control::GetIcapVerdictCacheStatsParams,
+        mut results: auditor_control::GetIcapVerdictCacheStatsResults,
+    ) -> Promise<(), capnp::Error> {
+        let builder = results.get().init_stats();
+        match crate::audit::icap_verdict_cache_snapshot(&self.name) {
+            Ok(snap) => {
+                let mut data = builder.init_data();
+                data.set_entries(snap.entries as u64);
+                data.set_hit_clean(snap.hit_clean);
+                data.set_hit_blocked(snap.hit_blocked);
+                data.set_miss(snap.miss);
+                data.set_insert(snap.insert);
+                data.set_expire(snap.expire);
+            }
+            Err(e) => {
+                let mut ev = builder.init_err();
+                ev.set_code(-1);
+                ev.set_reason(format!("{e:?}").as_str());
+            }
+        }
+        Promise::ok(())
+    }
+
+    fn get_icap_connection_pool_stats(
+        &mut self,
+        params: auditor_control::GetIcapConnectionPoolStatsParams,
+        mut results: auditor_control::GetIcapConnectionPoolStatsResults,
+    ) -> Promise<(), capnp::Error> {
+        let service = pry!(pry!(pry!(params.get()).get_service()).to_str());
+        let builder = results.get().init_stats();
+        let r = match service.to_ascii_lowercase().as_str() {
+            "reqmod" => crate::audit::icap_connection_pool_snapshot(&self.name, IcapMethod::Reqmod),
+            "respmod" => {
+                crate::audit::icap_connection_pool_snapshot(&self.name, IcapMethod::Respmod)
+            }
+            _ => Err(anyhow::anyhow!("unsupported ICAP service {service}")),
+        };
+        match r {
+            Ok((snap, shared)) => {
+                let mut data = builder.init_data();
+                data.set_idle(snap.idle as u64);
+                data.set_created(snap.created);
+                data.set_reused(snap.reused);
+                data.set_lifetime_expired(snap.lifetime_expired);
+                data.set_health_check_passed(snap.health_check_passed);
+                data.set_health_check_failed(snap.health_check_failed);
+                data.set_shared(shared);
+            }
+            Err(e) => {
+                let mut ev = builder.init_err();
+                ev.set_code(-1);
+                ev.set_reason(format!("{e:?}").as_str());
+            }
+        }
+        Promise::ok(())
+    }
+}
@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use g3proxy_proto::types_capnp::operation_result;
+use g3proxy_proto::types_capnp::{fetch_result, operation_result};
 
 pub(super) fn set_operation_result(
     mut builder: operation_result::Builder<'_>,
@@ -29,3 +29,21 @@ pub(super) fn set_operation_result(
         }
     }
 }
+
+pub(super) fn set_fetch_result<'a, T>(
+    mut builder: fetch_result::Builder<'a, T>,
+    r: anyhow::Result<<T as capnp::traits::Owned>::Reader<'a>>,
+) -> capnp::Result<()>
+where
+    T: capnp::traits::Owned,
+{
+    match r {
+        Ok(data) => builder.set_data(data),
+        Err(e) => {
+            let mut ev = builder.init_err();
+            ev.set_code(-1);
+            ev.set_reason(format!("{e:?}").as_str());
+            Ok(())
+        }
+    }
+}
@@ -19,6 +19,7 @@ use std::sync::Arc;
 use capnp::capability::Promise;
 use capnp_rpc::pry;
 
+use g3_daemon::control::CtlAccessLevel;
 use g3_types::metrics::NodeName;
 
 use g3proxy_proto::escaper_capnp::escaper_control;
@@ -28,13 +29,20 @@ use crate::escape::ArcEscaper;
 
 pub(super) struct EscaperControlImpl {
     escaper: ArcEscaper,
+    access: CtlAccessLevel,
 }
 
 impl EscaperControlImpl {
-    pub(super) fn new_client(name: &str) -> anyhow::Result<escaper_control::Client> {
+    pub(super) fn new_client(
+        name: &str,
+        access: CtlAccessLevel,
+    ) -> anyhow::Result<escaper_control::Client> {
         let name = unsafe { NodeName::new_unchecked(name) };
         let escaper = crate::escape::get_escaper(&name)?;
-        Ok(capnp_rpc::new_client(EscaperControlImpl { escaper }))
+        Ok(capnp_rpc::new_client(EscaperControlImpl {
+            escaper,
+            access,
+        }))
     }
 }
 
@@ -44,6 +52,7 @@ impl escaper_control::Server for EscaperControlImpl {
         params: escaper_control::PublishParams,
         mut results: escaper_control::PublishResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let data = pry!(pry!(pry!(params.get()).get_data()).to_string());
         let escaper = Arc::clone(&self.escaper);
         Promise::from_future(async move {
@@ -51,4 +60,17 @@ impl escaper_control::Server for EscaperControlImpl {
             Ok(())
         })
     }
+
+    fn list_egress_nat(
+        &mut self,
+        _params: escaper_control::ListEgressNatParams,
+        mut results: escaper_control::ListEgressNatResults,
+    ) -> Promise<(), capnp::Error> {
+        let v = self.escaper.egress_nat_snapshot().unwrap_or_default();
+        let mut builder = results.get().init_result(v.len() as u32);
+        for (i, (local, public)) in v.into_iter().enumerate() {
+            builder.set(i as u32, format!("{local} -> {public}").as_str());
+        }
+        Promise::ok(())
+    }
 }
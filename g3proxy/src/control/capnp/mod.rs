@@ -14,23 +14,28 @@
  * limitations under the License.
  */
 
+use g3_daemon::control::CtlAccessLevel;
+
 use g3proxy_proto::proc_capnp::proc_control;
 
 mod common;
-use common::set_operation_result;
+use common::{set_fetch_result, set_operation_result};
 mod proc;
 
+mod auditor;
 mod escaper;
 mod resolver;
 mod server;
+mod tenant;
 mod user_group;
 
 pub fn stop_working_thread() {
     g3_daemon::control::capnp::stop_working_thread();
 }
 
-fn build_capnp_client() -> capnp::capability::Client {
-    let control_client: proc_control::Client = capnp_rpc::new_client(proc::ProcControlImpl);
+fn build_capnp_client(access: CtlAccessLevel) -> capnp::capability::Client {
+    let control_client: proc_control::Client =
+        capnp_rpc::new_client(proc::ProcControlImpl::new(access));
     control_client.client
 }
 
@@ -17,18 +17,28 @@
 use capnp::capability::Promise;
 use capnp_rpc::pry;
 
+use g3_daemon::control::CtlAccessLevel;
 use g3_types::metrics::NodeName;
 
+use g3proxy_proto::auditor_capnp::auditor_control;
 use g3proxy_proto::escaper_capnp::escaper_control;
 use g3proxy_proto::proc_capnp::proc_control;
 use g3proxy_proto::resolver_capnp::resolver_control;
 use g3proxy_proto::server_capnp::server_control;
-use g3proxy_proto::types_capnp::fetch_result;
+use g3proxy_proto::tenant_capnp::tenant_control;
 use g3proxy_proto::user_group_capnp::user_group_control;
 
-use super::set_operation_result;
+use super::{set_fetch_result, set_operation_result};
 
-pub(super) struct ProcControlImpl;
+pub(super) struct ProcControlImpl {
+    access: CtlAccessLevel,
+}
+
+impl ProcControlImpl {
+    pub(super) fn new(access: CtlAccessLevel) -> Self {
+        ProcControlImpl { access }
+    }
+}
 
 impl proc_control::Server for ProcControlImpl {
     fn version(
@@ -45,6 +55,7 @@ impl proc_control::Server for ProcControlImpl {
         _params: proc_control::OfflineParams,
         mut results: proc_control::OfflineResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         Promise::from_future(async move {
             g3_daemon::control::quit::start_graceful_shutdown().await;
             set_operation_result(results.get().init_result(), Ok(()));
@@ -57,6 +68,7 @@ impl proc_control::Server for ProcControlImpl {
         _params: proc_control::CancelShutdownParams,
         mut results: proc_control::CancelShutdownResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         Promise::from_future(async move {
             let r = g3_daemon::control::quit::cancel_graceful_shutdown().await;
             set_operation_result(results.get().init_result(), r);
@@ -69,6 +81,7 @@ impl proc_control::Server for ProcControlImpl {
         _params: proc_control::ReleaseControllerParams,
         mut results: proc_control::ReleaseControllerResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         Promise::from_future(async move {
             let r = g3_daemon::control::quit::release_controller().await;
             set_operation_result(results.get().init_result(), r);
@@ -146,6 +159,7 @@ impl proc_control::Server for ProcControlImpl {
         params: proc_control::ReloadUserGroupParams,
         mut results: proc_control::ReloadUserGroupResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let user_group = pry!(pry!(pry!(params.get()).get_name()).to_string());
         Promise::from_future(async move {
             let r = crate::control::bridge::reload_user_group(user_group, None).await;
@@ -159,6 +173,7 @@ impl proc_control::Server for ProcControlImpl {
         params: proc_control::ReloadResolverParams,
         mut results: proc_control::ReloadResolverResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let resolver = pry!(pry!(pry!(params.get()).get_name()).to_string());
         Promise::from_future(async move {
             let r = crate::control::bridge::reload_resolver(resolver, None).await;
@@ -172,6 +187,7 @@ impl proc_control::Server for ProcControlImpl {
         params: proc_control::ReloadAuditorParams,
         mut results: proc_control::ReloadAuditorResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let auditor = pry!(pry!(pry!(params.get()).get_name()).to_string());
         Promise::from_future(async move {
             let r = crate::control::bridge::reload_auditor(auditor, None).await;
@@ -185,6 +201,7 @@ impl proc_control::Server for ProcControlImpl {
         params: proc_control::ReloadEscaperParams,
         mut results: proc_control::ReloadEscaperResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let escaper = pry!(pry!(pry!(params.get()).get_name()).to_string());
         Promise::from_future(async move {
             let r = crate::control::bridge::reload_escaper(escaper, None).await;
@@ -198,6 +215,7 @@ impl proc_control::Server for ProcControlImpl {
         params: proc_control::ReloadServerParams,
         mut results: proc_control::ReloadServerResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let server = pry!(pry!(pry!(params.get()).get_name()).to_string());
         Promise::from_future(async move {
             let r = crate::control::bridge::reload_server(server, None).await;
@@ -212,7 +230,7 @@ impl proc_control::Server for ProcControlImpl {
         mut results: proc_control::GetUserGroupResults,
     ) -> Promise<(), capnp::Error> {
         let user_group = pry!(pry!(pry!(params.get()).get_name()).to_str());
-        let ug = super::user_group::UserGroupControlImpl::new_client(user_group);
+        let ug = super::user_group::UserGroupControlImpl::new_client(user_group, self.access);
         pry!(set_fetch_result::<user_group_control::Owned>(
             results.get().init_user_group(),
             Ok(ug),
@@ -233,6 +251,19 @@ impl proc_control::Server for ProcControlImpl {
         Promise::ok(())
     }
 
+    fn get_auditor(
+        &mut self,
+        params: proc_control::GetAuditorParams,
+        mut results: proc_control::GetAuditorResults,
+    ) -> Promise<(), capnp::Error> {
+        let auditor = pry!(pry!(pry!(params.get()).get_name()).to_str());
+        pry!(set_fetch_result::<auditor_control::Owned>(
+            results.get().init_auditor(),
+            super::auditor::AuditorControlImpl::new_client(auditor, self.access),
+        ));
+        Promise::ok(())
+    }
+
     fn get_escaper(
         &mut self,
         params: proc_control::GetEscaperParams,
@@ -241,7 +272,7 @@ impl proc_control::Server for ProcControlImpl {
         let escaper = pry!(pry!(pry!(params.get()).get_name()).to_str());
         pry!(set_fetch_result::<escaper_control::Owned>(
             results.get().init_escaper(),
-            super::escaper::EscaperControlImpl::new_client(escaper),
+            super::escaper::EscaperControlImpl::new_client(escaper, self.access),
         ));
         Promise::ok(())
     }
@@ -264,6 +295,7 @@ impl proc_control::Server for ProcControlImpl {
         _params: proc_control::ForceQuitOfflineServersParams,
         mut results: proc_control::ForceQuitOfflineServersResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         crate::serve::force_quit_offline_servers();
         results.get().init_result().set_ok("success");
         Promise::ok(())
@@ -274,28 +306,83 @@ impl proc_control::Server for ProcControlImpl {
         params: proc_control::ForceQuitOfflineServerParams,
         mut results: proc_control::ForceQuitOfflineServerResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let server = pry!(pry!(pry!(params.get()).get_name()).to_str());
         let server = unsafe { NodeName::new_unchecked(server) };
         crate::serve::force_quit_offline_server(&server);
         results.get().init_result().set_ok("success");
         Promise::ok(())
     }
-}
 
-fn set_fetch_result<'a, T>(
-    mut builder: fetch_result::Builder<'a, T>,
-    r: anyhow::Result<<T as capnp::traits::Owned>::Reader<'a>>,
-) -> capnp::Result<()>
-where
-    T: capnp::traits::Owned,
-{
-    match r {
-        Ok(data) => builder.set_data(data),
-        Err(e) => {
-            let mut ev = builder.init_err();
-            ev.set_code(-1);
-            ev.set_reason(format!("{e:?}").as_str());
+    fn list_tenant(
+        &mut self,
+        _params: proc_control::ListTenantParams,
+        mut results: proc_control::ListTenantResults,
+    ) -> Promise<(), capnp::Error> {
+        let set = crate::config::tenant::get_all_names();
+        let mut builder = results.get().init_result(set.len() as u32);
+        for (i, name) in set.iter().enumerate() {
+            builder.set(i as u32, name.as_str());
+        }
+        Promise::ok(())
+    }
+
+    fn reload_tenant(
+        &mut self,
+        params: proc_control::ReloadTenantParams,
+        mut results: proc_control::ReloadTenantResults,
+    ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
+        let tenant = pry!(pry!(pry!(params.get()).get_name()).to_string());
+        Promise::from_future(async move {
+            let r = crate::control::bridge::reload_tenant(tenant).await;
+            set_operation_result(results.get().init_result(), r);
             Ok(())
+        })
+    }
+
+    fn get_tenant(
+        &mut self,
+        params: proc_control::GetTenantParams,
+        mut results: proc_control::GetTenantResults,
+    ) -> Promise<(), capnp::Error> {
+        let tenant = pry!(pry!(pry!(params.get()).get_name()).to_str());
+        pry!(set_fetch_result::<tenant_control::Owned>(
+            results.get().init_tenant(),
+            super::tenant::TenantControlImpl::new_client(tenant, self.access),
+        ));
+        Promise::ok(())
+    }
+
+    fn get_preflight_report(
+        &mut self,
+        _params: proc_control::GetPreflightReportParams,
+        mut results: proc_control::GetPreflightReportResults,
+    ) -> Promise<(), capnp::Error> {
+        let builder = results.get().init_report();
+        match crate::preflight::last_report() {
+            Some(report) => {
+                let mut data = builder.init_data();
+                data.set_ready(report.is_ready());
+                let checks = report.checks();
+                let mut list = data.init_checks(checks.len() as u32);
+                for (i, check) in checks.iter().enumerate() {
+                    let mut c = list.reborrow().get(i as u32);
+                    c.set_component(check.component.as_str());
+                    c.set_name(check.name.as_str());
+                    c.set_policy(check.policy.as_str());
+                    c.set_passed(check.passed());
+                    if let Some(e) = &check.error {
+                        c.set_error(e.as_str());
+                    }
+                }
+            }
+            None => {
+                let mut ev = builder.init_err();
+                ev.set_code(-1);
+                ev.set_reason("no preflight check has been run");
+            }
         }
+        Promise::ok(())
     }
 }
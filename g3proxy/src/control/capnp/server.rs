@@ -14,13 +14,18 @@
  * limitations under the License.
  */
 
+use std::net::SocketAddr;
+use std::str::FromStr;
+
 use capnp::capability::Promise;
+use capnp_rpc::pry;
 
 use g3_types::metrics::NodeName;
+use g3_types::net::{ProxyRequestType, UpstreamAddr};
 
 use g3proxy_proto::server_capnp::server_control;
 
-use crate::serve::ArcServer;
+use crate::serve::{ArcServer, RouteTestRequest};
 
 pub(super) struct ServerControlImpl {
     server: ArcServer,
@@ -53,4 +58,85 @@ impl server_control::Server for ServerControlImpl {
             ))
         }
     }
+
+    fn client_conn_top(
+        &mut self,
+        params: server_control::ClientConnTopParams,
+        mut results: server_control::ClientConnTopResults,
+    ) -> Promise<(), capnp::Error> {
+        let Some(limiter) = self.server.client_conn_limiter() else {
+            return Promise::err(capnp::Error::failed(
+                "client conn limit is not enabled on this server".to_string(),
+            ));
+        };
+        let count = pry!(params.get()).get_count() as usize;
+
+        let top_ip = limiter.top_ip(count);
+        let top_subnet = limiter.top_subnet(count);
+
+        let mut builder = results.get().init_top();
+        let mut ip_builder = builder.reborrow().init_ip(top_ip.len() as u32);
+        for (i, (ip, n)) in top_ip.into_iter().enumerate() {
+            let mut b = ip_builder.reborrow().get(i as u32);
+            b.set_addr(ip.to_string().as_str());
+            b.set_count(n as u64);
+        }
+        let mut subnet_builder = builder.init_subnet(top_subnet.len() as u32);
+        for (i, (ip, prefix, n)) in top_subnet.into_iter().enumerate() {
+            let mut b = subnet_builder.reborrow().get(i as u32);
+            b.set_addr(format!("{ip}/{prefix}").as_str());
+            b.set_count(n as u64);
+        }
+        Promise::ok(())
+    }
+
+    fn test_route(
+        &mut self,
+        params: server_control::TestRouteParams,
+        mut results: server_control::TestRouteResults,
+    ) -> Promise<(), capnp::Error> {
+        let params = pry!(params.get());
+        let upstream = pry!(pry!(params.get_upstream()).to_str());
+        let upstream = pry!(UpstreamAddr::from_str(upstream)
+            .map_err(|e| capnp::Error::failed(format!("invalid upstream {upstream}: {e}"))));
+        let request_type = pry!(pry!(params.get_request_type()).to_str());
+        let request_type = pry!(ProxyRequestType::from_str(request_type)
+            .map_err(|_| capnp::Error::failed(format!("invalid request type {request_type}"))));
+        let client_addr = pry!(pry!(params.get_client_addr()).to_str());
+        let client_addr = pry!(SocketAddr::from_str(client_addr)
+            .map_err(|e| capnp::Error::failed(format!("invalid client addr {client_addr}: {e}"))));
+        let user = pry!(pry!(params.get_user()).to_str());
+        let user = if user.is_empty() {
+            None
+        } else {
+            Some(user.to_string())
+        };
+
+        let req = RouteTestRequest {
+            user,
+            upstream,
+            client_addr,
+            request_type,
+        };
+        let server = self.server.clone();
+        Promise::from_future(async move {
+            let Some(report) = server.test_route(&req).await else {
+                return Err(capnp::Error::failed(
+                    "route test is not supported on this server".to_string(),
+                ));
+            };
+
+            let mut builder = results.get().init_result();
+            builder.set_permitted(report.permitted);
+            let mut steps_builder = builder.reborrow().init_steps(report.steps.len() as u32);
+            for (i, step) in report.steps.iter().enumerate() {
+                steps_builder.set(i as u32, step.as_str());
+            }
+            let mut escapers_builder = builder.init_escapers(report.escapers.len() as u32);
+            for (i, escaper) in report.escapers.iter().enumerate() {
+                escapers_builder.set(i as u32, escaper.as_str());
+            }
+            Ok(())
+        })
+    }
 }
@@ -0,0 +1,226 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use capnp::capability::Promise;
+use capnp_rpc::pry;
+
+use g3_daemon::control::CtlAccessLevel;
+use g3_types::metrics::NodeName;
+
+use g3proxy_proto::auditor_capnp::auditor_control;
+use g3proxy_proto::escaper_capnp::escaper_control;
+use g3proxy_proto::resolver_capnp::resolver_control;
+use g3proxy_proto::server_capnp::server_control;
+use g3proxy_proto::tenant_capnp::tenant_control;
+use g3proxy_proto::user_group_capnp::user_group_control;
+
+use super::{set_fetch_result, set_operation_result};
+use crate::config::tenant::{TenantConfig, TenantMemberType};
+
+pub(super) struct TenantControlImpl {
+    name: NodeName,
+    access: CtlAccessLevel,
+}
+
+impl TenantControlImpl {
+    pub(super) fn new_client(
+        name: &str,
+        access: CtlAccessLevel,
+    ) -> anyhow::Result<tenant_control::Client> {
+        let name = unsafe { NodeName::new_unchecked(name) };
+        if crate::config::tenant::get(&name).is_none() {
+            return Err(anyhow!("no tenant named {name} found"));
+        }
+        Ok(capnp_rpc::new_client(TenantControlImpl { name, access }))
+    }
+
+    fn get_config(&self) -> capnp::Result<Arc<TenantConfig>> {
+        // the tenant config may be changed by reload, so always fetch the latest one
+        crate::config::tenant::get(&self.name)
+            .ok_or_else(|| capnp::Error::failed(format!("tenant {} has been deleted", self.name)))
+    }
+
+    fn get_members(&self, member_type: TenantMemberType) -> capnp::Result<BTreeSet<NodeName>> {
+        let config = self.get_config()?;
+        Ok(config.members(member_type).clone())
+    }
+
+    fn check_member(&self, member_type: TenantMemberType, name: &str) -> anyhow::Result<()> {
+        let name = unsafe { NodeName::new_unchecked(name) };
+        let config = self.get_config()?;
+        if config.members(member_type).contains(&name) {
+            Ok(())
+        } else {
+            Err(anyhow!("{name} is not a member of tenant {}", self.name))
+        }
+    }
+}
+
+fn set_name_list(mut builder: capnp::text_list::Builder<'_>, set: &BTreeSet<NodeName>) {
+    for (i, name) in set.iter().enumerate() {
+        builder.set(i as u32, name.as_str());
+    }
+}
+
+impl tenant_control::Server for TenantControlImpl {
+    fn reload(
+        &mut self,
+        _params: tenant_control::ReloadParams,
+        mut results: tenant_control::ReloadResults,
+    ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
+        let tenant = self.name.to_string();
+        Promise::from_future(async move {
+            let r = crate::control::bridge::reload_tenant(tenant).await;
+            set_operation_result(results.get().init_result(), r);
+            Ok(())
+        })
+    }
+
+    fn list_user_group(
+        &mut self,
+        _params: tenant_control::ListUserGroupParams,
+        mut results: tenant_control::ListUserGroupResults,
+    ) -> Promise<(), capnp::Error> {
+        let set = pry!(self.get_members(TenantMemberType::UserGroup));
+        set_name_list(results.get().init_result(set.len() as u32), &set);
+        Promise::ok(())
+    }
+
+    fn list_resolver(
+        &mut self,
+        _params: tenant_control::ListResolverParams,
+        mut results: tenant_control::ListResolverResults,
+    ) -> Promise<(), capnp::Error> {
+        let set = pry!(self.get_members(TenantMemberType::Resolver));
+        set_name_list(results.get().init_result(set.len() as u32), &set);
+        Promise::ok(())
+    }
+
+    fn list_auditor(
+        &mut self,
+        _params: tenant_control::ListAuditorParams,
+        mut results: tenant_control::ListAuditorResults,
+    ) -> Promise<(), capnp::Error> {
+        let set = pry!(self.get_members(TenantMemberType::Auditor));
+        set_name_list(results.get().init_result(set.len() as u32), &set);
+        Promise::ok(())
+    }
+
+    fn list_escaper(
+        &mut self,
+        _params: tenant_control::ListEscaperParams,
+        mut results: tenant_control::ListEscaperResults,
+    ) -> Promise<(), capnp::Error> {
+        let set = pry!(self.get_members(TenantMemberType::Escaper));
+        set_name_list(results.get().init_result(set.len() as u32), &set);
+        Promise::ok(())
+    }
+
+    fn list_server(
+        &mut self,
+        _params: tenant_control::ListServerParams,
+        mut results: tenant_control::ListServerResults,
+    ) -> Promise<(), capnp::Error> {
+        let set = pry!(self.get_members(TenantMemberType::Server));
+        set_name_list(results.get().init_result(set.len() as u32), &set);
+        Promise::ok(())
+    }
+
+    fn get_user_group(
+        &mut self,
+        params: tenant_control::GetUserGroupParams,
+        mut results: tenant_control::GetUserGroupResults,
+    ) -> Promise<(), capnp::Error> {
+        let user_group = pry!(pry!(pry!(params.get()).get_name()).to_str());
+        let r = self
+            .check_member(TenantMemberType::UserGroup, user_group)
+            .map(|_| super::user_group::UserGroupControlImpl::new_client(user_group, self.access));
+        pry!(set_fetch_result::<user_group_control::Owned>(
+            results.get().init_user_group(),
+            r,
+        ));
+        Promise::ok(())
+    }
+
+    fn get_resolver(
+        &mut self,
+        params: tenant_control::GetResolverParams,
+        mut results: tenant_control::GetResolverResults,
+    ) -> Promise<(), capnp::Error> {
+        let resolver = pry!(pry!(pry!(params.get()).get_name()).to_str());
+        let r = self
+            .check_member(TenantMemberType::Resolver, resolver)
+            .and_then(|_| super::resolver::ResolverControlImpl::new_client(resolver));
+        pry!(set_fetch_result::<resolver_control::Owned>(
+            results.get().init_resolver(),
+            r,
+        ));
+        Promise::ok(())
+    }
+
+    fn get_auditor(
+        &mut self,
+        params: tenant_control::GetAuditorParams,
+        mut results: tenant_control::GetAuditorResults,
+    ) -> Promise<(), capnp::Error> {
+        let auditor = pry!(pry!(pry!(params.get()).get_name()).to_str());
+        let r = self
+            .check_member(TenantMemberType::Auditor, auditor)
+            .and_then(|_| super::auditor::AuditorControlImpl::new_client(auditor, self.access));
+        pry!(set_fetch_result::<auditor_control::Owned>(
+            results.get().init_auditor(),
+            r,
+        ));
+        Promise::ok(())
+    }
+
+    fn get_escaper(
+        &mut self,
+        params: tenant_control::GetEscaperParams,
+        mut results: tenant_control::GetEscaperResults,
+    ) -> Promise<(), capnp::Error> {
+        let escaper = pry!(pry!(pry!(params.get()).get_name()).to_str());
+        let r = self
+            .check_member(TenantMemberType::Escaper, escaper)
+            .and_then(|_| super::escaper::EscaperControlImpl::new_client(escaper, self.access));
+        pry!(set_fetch_result::<escaper_control::Owned>(
+            results.get().init_escaper(),
+            r,
+        ));
+        Promise::ok(())
+    }
+
+    fn get_server(
+        &mut self,
+        params: tenant_control::GetServerParams,
+        mut results: tenant_control::GetServerResults,
+    ) -> Promise<(), capnp::Error> {
+        let server = pry!(pry!(pry!(params.get()).get_name()).to_str());
+        let r = self
+            .check_member(TenantMemberType::Server, server)
+            .and_then(|_| super::server::ServerControlImpl::new_client(server));
+        pry!(set_fetch_result::<server_control::Owned>(
+            results.get().init_server(),
+            r,
+        ));
+        Promise::ok(())
+    }
+}
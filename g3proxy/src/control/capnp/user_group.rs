@@ -15,10 +15,12 @@
  */
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use capnp::capability::Promise;
 use capnp_rpc::pry;
 
+use g3_daemon::control::CtlAccessLevel;
 use g3_types::metrics::NodeName;
 
 use g3proxy_proto::user_group_capnp::user_group_control;
@@ -26,15 +28,18 @@ use g3proxy_proto::user_group_capnp::user_group_control;
 use super::set_operation_result;
 use crate::auth::UserGroup;
 
+const TRAFFIC_TOP_MAX_INTERVAL_SECS: u32 = 60;
+
 pub(super) struct UserGroupControlImpl {
     user_group: Arc<UserGroup>,
+    access: CtlAccessLevel,
 }
 
 impl UserGroupControlImpl {
-    pub(super) fn new_client(name: &str) -> user_group_control::Client {
+    pub(super) fn new_client(name: &str, access: CtlAccessLevel) -> user_group_control::Client {
         let name = unsafe { NodeName::new_unchecked(name) };
         let user_group = crate::auth::get_or_insert_default(&name);
-        capnp_rpc::new_client(UserGroupControlImpl { user_group })
+        capnp_rpc::new_client(UserGroupControlImpl { user_group, access })
     }
 }
 
@@ -70,6 +75,7 @@ impl user_group_control::Server for UserGroupControlImpl {
         params: user_group_control::PublishDynamicUserParams,
         mut results: user_group_control::PublishDynamicUserResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let user_group = self.user_group.clone();
         let contents = pry!(pry!(pry!(params.get()).get_contents()).to_string());
         Promise::from_future(async move {
@@ -78,4 +84,82 @@ impl user_group_control::Server for UserGroupControlImpl {
             Ok(())
         })
     }
+
+    fn traffic_top(
+        &mut self,
+        params: user_group_control::TrafficTopParams,
+        mut results: user_group_control::TrafficTopResults,
+    ) -> Promise<(), capnp::Error> {
+        let params = pry!(params.get());
+        let user = pry!(pry!(params.get_user()).to_string());
+        let interval = params
+            .get_interval()
+            .clamp(1, TRAFFIC_TOP_MAX_INTERVAL_SECS);
+        let count = params.get_count() as usize;
+        let user_group = self.user_group.clone();
+        Promise::from_future(async move {
+            let user = if user.is_empty() {
+                None
+            } else {
+                Some(user.as_str())
+            };
+            let top = user_group
+                .live_traffic_top(user, Duration::from_secs(interval as u64), count)
+                .await;
+
+            let mut builder = results.get().init_result(top.len() as u32);
+            for (i, summary) in top.into_iter().enumerate() {
+                let mut b = builder.reborrow().get(i as u32);
+                b.set_user(summary.user.as_str());
+                b.set_alive_task_count(summary.alive_task_count);
+                b.set_in_bytes(summary.traffic.in_bytes);
+                b.set_out_bytes(summary.traffic.out_bytes);
+                let mut sites_builder = b.init_top_sites(summary.top_sites.len() as u32);
+                for (j, (site, traffic)) in summary.top_sites.into_iter().enumerate() {
+                    let mut sb = sites_builder.reborrow().get(j as u32);
+                    sb.set_site(site.as_str());
+                    sb.set_in_bytes(traffic.in_bytes);
+                    sb.set_out_bytes(traffic.out_bytes);
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn query_quota(
+        &mut self,
+        params: user_group_control::QueryQuotaParams,
+        mut results: user_group_control::QueryQuotaResults,
+    ) -> Promise<(), capnp::Error> {
+        let user = pry!(pry!(pry!(params.get()).get_user()).to_str());
+        let user = if user.is_empty() { None } else { Some(user) };
+        let all = self.user_group.quota_snapshot(user);
+
+        let mut builder = results.get().init_result(all.len() as u32);
+        for (i, s) in all.into_iter().enumerate() {
+            let mut b = builder.reborrow().get(i as u32);
+            b.set_user(s.user.as_str());
+            b.set_period(s.period);
+            b.set_used_bytes(s.used_bytes);
+            b.set_limit_bytes(s.traffic_limit);
+            b.set_traffic_exceeded(s.traffic_exceeded);
+            b.set_in_time_window(s.in_time_window);
+        }
+        Promise::ok(())
+    }
+
+    fn reset_quota(
+        &mut self,
+        params: user_group_control::ResetQuotaParams,
+        mut results: user_group_control::ResetQuotaResults,
+    ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
+        let user_group = self.user_group.clone();
+        let user = pry!(pry!(pry!(params.get()).get_user()).to_string());
+        Promise::from_future(async move {
+            let r = user_group.reset_quota(&user).await;
+            set_operation_result(results.get().init_result(), r);
+            Ok(())
+        })
+    }
 }
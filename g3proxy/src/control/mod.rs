@@ -20,6 +20,10 @@ use tokio::sync::Mutex;
 
 mod bridge;
 
+mod schedule;
+pub use schedule::run_scheduled_task;
+pub(crate) use schedule::SCHEDULE_ACTIONS;
+
 mod quit;
 pub use quit::QuitActor;
 
@@ -0,0 +1,63 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use anyhow::anyhow;
+
+use g3_daemon::schedule::{ScheduleAction, ScheduledTaskConfig};
+
+use super::bridge;
+
+pub(crate) const SCHEDULE_ACTIONS: &[ScheduleAction] = &[
+    ScheduleAction::without_target("reload_config"),
+    ScheduleAction::with_target("reload_user_group"),
+    ScheduleAction::with_target("reload_auditor"),
+    ScheduleAction::with_target("reload_resolver"),
+    ScheduleAction::with_target("reload_escaper"),
+    ScheduleAction::with_target("reload_server"),
+    ScheduleAction::with_target("reresolve_escaper"),
+    ScheduleAction::with_target("rotate_ticket_key"),
+];
+
+fn get_target(task: &ScheduledTaskConfig) -> anyhow::Result<String> {
+    // the target has been checked when parsing the config
+    task.target()
+        .map(|t| t.to_string())
+        .ok_or_else(|| anyhow!("no target set for action {}", task.action()))
+}
+
+pub async fn run_scheduled_task(task: Arc<ScheduledTaskConfig>) -> anyhow::Result<()> {
+    match task.action() {
+        "reload_config" => bridge::reload_config().await,
+        "reload_user_group" => bridge::reload_user_group(get_target(&task)?, None).await,
+        "reload_auditor" => bridge::reload_auditor(get_target(&task)?, None).await,
+        "reload_resolver" => bridge::reload_resolver(get_target(&task)?, None).await,
+        "reload_escaper" => bridge::reload_escaper(get_target(&task)?, None).await,
+        "reload_server" => bridge::reload_server(get_target(&task)?, None).await,
+        "reresolve_escaper" => bridge::rebuild_escaper(get_target(&task)?).await,
+        "rotate_ticket_key" => {
+            let target = task
+                .target()
+                .ok_or_else(|| anyhow!("no target set for action {}", task.action()))?;
+            if g3_tls_ticket::rotate(target) == 0 {
+                return Err(anyhow!("no tls ticketer found for {target}"));
+            }
+            Ok(())
+        }
+        action => Err(anyhow!("unsupported action {action}")),
+    }
+}
@@ -0,0 +1,141 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use arc_swap::ArcSwapOption;
+
+use g3_io_ext::{GlobalLimitGroup, GlobalStreamLimit, GlobalStreamLimiter, StreamLimitAction};
+use g3_types::limit::GlobalStreamSpeedLimitConfig;
+
+/// the stream limiter shared by all connections of an escaper
+pub(crate) struct EscaperStreamLimiter {
+    inner: Arc<GlobalStreamLimiter>,
+    passed_bytes: AtomicU64,
+}
+
+impl EscaperStreamLimiter {
+    fn new(config: GlobalStreamSpeedLimitConfig) -> Self {
+        let inner = Arc::new(GlobalStreamLimiter::new(GlobalLimitGroup::Escaper, config));
+        inner.clone().tokio_spawn_replenish();
+        EscaperStreamLimiter {
+            inner,
+            passed_bytes: AtomicU64::new(0),
+        }
+    }
+
+    fn snapshot(&self) -> EscaperStreamLimitSnapshot {
+        let config = self.inner.config();
+        let interval = config.replenish_interval().as_secs_f64();
+        let bytes_per_second = if interval > 0.0 {
+            config.replenish_bytes() as f64 / interval
+        } else {
+            0.0
+        };
+        EscaperStreamLimitSnapshot {
+            passed_bytes: self.passed_bytes.load(Ordering::Relaxed),
+            bytes_per_second,
+        }
+    }
+}
+
+impl GlobalStreamLimit for EscaperStreamLimiter {
+    fn group(&self) -> GlobalLimitGroup {
+        GlobalLimitGroup::Escaper
+    }
+
+    fn check(&self, to_advance: usize) -> StreamLimitAction {
+        let action = self.inner.check(to_advance);
+        if let StreamLimitAction::AdvanceBy(n) = action {
+            self.passed_bytes.fetch_add(n as u64, Ordering::Relaxed);
+        }
+        action
+    }
+
+    fn release(&self, size: usize) {
+        // the released size is always less than the size advanced before
+        self.passed_bytes.fetch_sub(size as u64, Ordering::Relaxed);
+        self.inner.release(size);
+    }
+}
+
+#[derive(Clone, Copy)]
+pub(crate) struct EscaperStreamLimitSnapshot {
+    pub(crate) passed_bytes: u64,
+    pub(crate) bytes_per_second: f64,
+}
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct EscaperBandwidthSnapshot {
+    pub(crate) upload: Option<EscaperStreamLimitSnapshot>,
+    pub(crate) download: Option<EscaperStreamLimitSnapshot>,
+}
+
+/// aggregated bandwidth limiters for all tcp connections of an escaper,
+/// which should be kept in the escaper stats so they won't be reset at reload
+#[derive(Default)]
+pub(crate) struct EscaperBandwidthLimiter {
+    upload: ArcSwapOption<EscaperStreamLimiter>,
+    download: ArcSwapOption<EscaperStreamLimiter>,
+}
+
+impl EscaperBandwidthLimiter {
+    pub(crate) fn update(
+        &self,
+        upload: Option<GlobalStreamSpeedLimitConfig>,
+        download: Option<GlobalStreamSpeedLimitConfig>,
+    ) {
+        Self::update_limiter(&self.upload, upload);
+        Self::update_limiter(&self.download, download);
+    }
+
+    fn update_limiter(
+        limiter: &ArcSwapOption<EscaperStreamLimiter>,
+        config: Option<GlobalStreamSpeedLimitConfig>,
+    ) {
+        match config {
+            Some(config) => {
+                if let Some(old) = limiter.load_full() {
+                    old.inner.update(config);
+                } else {
+                    limiter.store(Some(Arc::new(EscaperStreamLimiter::new(config))));
+                }
+            }
+            None => limiter.store(None),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn upload(&self) -> Option<Arc<EscaperStreamLimiter>> {
+        self.upload.load_full()
+    }
+
+    #[inline]
+    pub(crate) fn download(&self) -> Option<Arc<EscaperStreamLimiter>> {
+        self.download.load_full()
+    }
+
+    pub(crate) fn snapshot(&self) -> Option<EscaperBandwidthSnapshot> {
+        let upload = self.upload.load().as_ref().map(|l| l.snapshot());
+        let download = self.download.load().as_ref().map(|l| l.snapshot());
+        if upload.is_none() && download.is_none() {
+            None
+        } else {
+            Some(EscaperBandwidthSnapshot { upload, download })
+        }
+    }
+}
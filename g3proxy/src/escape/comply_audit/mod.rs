@@ -117,7 +117,7 @@ impl Escaper for ComplyAuditEscaper {
         task_stats: ArcTcpConnectionTaskRemoteStats,
         audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.stats.add_request_passed();
         self._update_audit_context(audit_ctx);
         self.next
@@ -133,7 +133,7 @@ impl Escaper for ComplyAuditEscaper {
         task_stats: ArcTcpConnectionTaskRemoteStats,
         audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.stats.add_request_passed();
         self._update_audit_context(audit_ctx);
         self.next
@@ -148,7 +148,7 @@ impl Escaper for ComplyAuditEscaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         self.stats.add_request_passed();
         self.next
             .udp_setup_connection(task_conf, udp_notes, task_notes, task_stats)
@@ -162,7 +162,7 @@ impl Escaper for ComplyAuditEscaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcUdpRelayTaskRemoteStats,
     ) -> UdpRelaySetupResult {
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         self.stats.add_request_passed();
         self.next
             .udp_setup_relay(task_conf, udp_notes, task_notes, task_stats)
@@ -240,7 +240,7 @@ impl EscaperInternal for ComplyAuditEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -251,7 +251,7 @@ impl EscaperInternal for ComplyAuditEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -262,7 +262,7 @@ impl EscaperInternal for ComplyAuditEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcFtpTaskRemoteControlStats,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -275,7 +275,7 @@ impl EscaperInternal for ComplyAuditEscaper {
         _task_stats: ArcFtpTaskRemoteTransferStats,
         _ftp_server: &UpstreamAddr,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
-        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        transfer_tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 }
@@ -0,0 +1,190 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use slog::Logger;
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+use g3_geoip_types::IpLocation;
+use g3_io_ext::UdpRelayRemoteError;
+use g3_ip_locate::IpLocationServiceHandle;
+use g3_types::acl::AclAction;
+use g3_types::net::UpstreamAddr;
+
+use super::DirectFixedEscaperStats;
+use crate::auth::UserContext;
+use crate::log::escape::egress_location::EscapeLogForEgressLocation;
+use crate::serve::ServerTaskNotes;
+
+struct EgressLocationCheck<'a> {
+    escaper_stats: &'a DirectFixedEscaperStats,
+    escape_logger: &'a Logger,
+    task_id: &'a Uuid,
+    user_ctx: &'a UserContext,
+    upstream: &'a UpstreamAddr,
+    peer_ip: IpAddr,
+}
+
+impl EgressLocationCheck<'_> {
+    /// return true if the peer ip should be forbidden
+    fn forbid(&self, location: Option<&IpLocation>) -> bool {
+        let Some(filter) = &self.user_ctx.user_config().egress_location_filter else {
+            return false;
+        };
+
+        let action = match location {
+            Some(location) => {
+                let (_, action) = filter.check(location);
+                if matches!(action, AclAction::PermitAndLog | AclAction::ForbidAndLog) {
+                    EscapeLogForEgressLocation {
+                        task_id: self.task_id,
+                        user: self.user_ctx.user_name(),
+                        upstream: self.upstream,
+                        peer_ip: self.peer_ip,
+                        location,
+                    }
+                    .log(self.escape_logger, action);
+                }
+                action
+            }
+            None => filter.missed_action(),
+        };
+        if action.forbid_early() {
+            self.escaper_stats.forbidden.add_ip_blocked();
+            self.user_ctx.add_ip_blocked();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Check the peer ip against the egress location filter of the user.
+/// Return true if the peer ip should be forbidden.
+pub(crate) async fn forbid_user_egress_location(
+    ip_locate_handle: Option<&IpLocationServiceHandle>,
+    escaper_stats: &DirectFixedEscaperStats,
+    escape_logger: &Logger,
+    task_notes: &ServerTaskNotes,
+    upstream: &UpstreamAddr,
+    peer_ip: IpAddr,
+) -> bool {
+    let Some(ip_locate_handle) = ip_locate_handle else {
+        return false;
+    };
+    let Some(user_ctx) = task_notes.user_ctx() else {
+        return false;
+    };
+    if user_ctx.user_config().egress_location_filter.is_none() {
+        return false;
+    }
+
+    let location = ip_locate_handle.fetch(peer_ip).await;
+    EgressLocationCheck {
+        escaper_stats,
+        escape_logger,
+        task_id: &task_notes.id,
+        user_ctx,
+        upstream,
+        peer_ip,
+    }
+    .forbid(location.as_deref())
+}
+
+/// The poll version of the egress location check, used by udp relay
+pub(crate) struct UdpRelayEgressLocationCheck {
+    ip_locate_handle: Arc<IpLocationServiceHandle>,
+    escape_logger: Logger,
+    task_id: Uuid,
+    user_ctx: UserContext,
+    permitted_ip: Option<IpAddr>,
+    fetch_job: Option<(IpAddr, oneshot::Receiver<Option<Arc<IpLocation>>>)>,
+}
+
+impl UdpRelayEgressLocationCheck {
+    pub(crate) fn new(
+        ip_locate_handle: Option<&Arc<IpLocationServiceHandle>>,
+        escape_logger: &Logger,
+        task_notes: &ServerTaskNotes,
+    ) -> Option<Self> {
+        let ip_locate_handle = ip_locate_handle?;
+        let user_ctx = task_notes.user_ctx()?;
+        user_ctx.user_config().egress_location_filter.as_ref()?;
+        Some(UdpRelayEgressLocationCheck {
+            ip_locate_handle: Arc::clone(ip_locate_handle),
+            escape_logger: escape_logger.clone(),
+            task_id: task_notes.id,
+            user_ctx: user_ctx.clone(),
+            permitted_ip: None,
+            fetch_job: None,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn is_permitted(&self, ip: IpAddr) -> bool {
+        self.permitted_ip == Some(ip)
+    }
+
+    pub(crate) fn poll_check(
+        &mut self,
+        cx: &mut Context<'_>,
+        escaper_stats: &DirectFixedEscaperStats,
+        to_addr: SocketAddr,
+    ) -> Poll<Result<(), UdpRelayRemoteError>> {
+        let to_ip = to_addr.ip();
+        if self.is_permitted(to_ip) {
+            return Poll::Ready(Ok(()));
+        }
+
+        if !matches!(&self.fetch_job, Some((ip, _)) if *ip == to_ip) {
+            let (sender, receiver) = oneshot::channel();
+            let ip_locate_handle = self.ip_locate_handle.clone();
+            tokio::spawn(async move {
+                let location = ip_locate_handle.fetch(to_ip).await;
+                let _ = sender.send(location);
+            });
+            self.fetch_job = Some((to_ip, receiver));
+        }
+        let Some((_, receiver)) = &mut self.fetch_job else {
+            unreachable!()
+        };
+        let location = ready!(Pin::new(receiver).poll(cx)).ok().flatten();
+        self.fetch_job = None;
+
+        let upstream = UpstreamAddr::from(to_addr);
+        let forbid = EgressLocationCheck {
+            escaper_stats,
+            escape_logger: &self.escape_logger,
+            task_id: &self.task_id,
+            user_ctx: &self.user_ctx,
+            upstream: &upstream,
+            peer_ip: to_ip,
+        }
+        .forbid(location.as_deref());
+        if forbid {
+            Poll::Ready(Err(UdpRelayRemoteError::ForbiddenTargetIpAddress(to_addr)))
+        } else {
+            self.permitted_ip = Some(to_ip);
+            Poll::Ready(Ok(()))
+        }
+    }
+}
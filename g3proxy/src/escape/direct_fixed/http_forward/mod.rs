@@ -56,19 +56,25 @@ impl DirectFixedEscaper {
         r_wrapper_stats.push_user_io_stats(user_stats);
 
         let limit_config = &self.config.general.tcp_sock_speed_limit;
-        let ups_r = LimitedBufReader::new(
+        let mut ups_r = LimitedBufReader::new(
             ups_r,
             limit_config.shift_millis,
             limit_config.max_south,
             self.stats.clone(),
             Arc::new(r_wrapper_stats),
         );
-        let ups_w = LimitedWriter::local_limited(
+        if let Some(limiter) = self.stats.bandwidth.download() {
+            ups_r.add_global_limiter(limiter);
+        }
+        let mut ups_w = LimitedWriter::local_limited(
             ups_w,
             limit_config.shift_millis,
             limit_config.max_north,
             Arc::new(w_wrapper_stats),
         );
+        if let Some(limiter) = self.stats.bandwidth.upload() {
+            ups_w.add_global_limiter(limiter);
+        }
 
         let writer = DirectHttpForwardWriter::new(ups_w, Some(Arc::clone(&self.stats)));
         let reader = DirectHttpForwardReader::new(ups_r);
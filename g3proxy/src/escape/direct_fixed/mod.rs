@@ -23,17 +23,19 @@ use async_trait::async_trait;
 use slog::Logger;
 
 use g3_daemon::stat::remote::ArcTcpConnectionTaskRemoteStats;
+use g3_ip_locate::IpLocationServiceHandle;
 use g3_resolver::ResolveError;
 use g3_socket::util::AddressFamily;
 use g3_socket::BindAddr;
-use g3_types::acl::AclNetworkRule;
+use g3_types::acl::{AclAction, AclChildDomainRule, AclNetworkRule};
 use g3_types::metrics::NodeName;
-use g3_types::net::{Host, UpstreamAddr};
+use g3_types::net::{EgressInfo, Host, UpstreamAddr};
 use g3_types::resolve::{ResolveRedirection, ResolveStrategy};
 
-use super::{
-    ArcEscaper, ArcEscaperStats, EgressPathSelection, Escaper, EscaperInternal, EscaperStats,
-};
+use super::egress_nat::EgressNatTable;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use super::ipv6_rotate::Ipv6RotatePool;
+use super::{ArcEscaper, ArcEscaperStats, Escaper, EscaperInternal, EscaperStats};
 use crate::audit::AuditContext;
 use crate::auth::UserUpstreamTrafficStats;
 use crate::config::escaper::direct_fixed::DirectFixedEscaperConfig;
@@ -61,6 +63,7 @@ use crate::serve::ServerTaskNotes;
 mod stats;
 pub(crate) use stats::DirectFixedEscaperStats;
 
+pub(crate) mod egress_location;
 mod ftp_connect;
 pub(crate) mod http_forward;
 pub(crate) mod tcp_connect;
@@ -73,7 +76,12 @@ pub(super) struct DirectFixedEscaper {
     stats: Arc<DirectFixedEscaperStats>,
     resolver_handle: ArcIntegratedResolverHandle,
     egress_net_filter: Arc<AclNetworkRule>,
+    ip_locate_handle: Option<Arc<IpLocationServiceHandle>>,
     resolve_redirection: Option<ResolveRedirection>,
+    resolve_rebind_allow: Option<AclChildDomainRule>,
+    egress_nat: Option<Arc<EgressNatTable>>,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    ipv6_rotate: Option<Ipv6RotatePool>,
     escape_logger: Logger,
 }
 
@@ -84,22 +92,47 @@ impl DirectFixedEscaper {
     ) -> anyhow::Result<ArcEscaper> {
         let resolver_handle = crate::resolve::get_handle(config.resolver())?;
         let egress_net_filter = Arc::new(config.egress_net_filter.build());
+        let ip_locate_handle = match &config.ip_locate_service {
+            Some(c) => Some(Arc::new(c.spawn_ip_locate_agent()?)),
+            None => None,
+        };
 
         let resolve_redirection = config
             .resolve_redirection
             .as_ref()
             .map(|builder| builder.build());
+        let resolve_rebind_allow = config
+            .resolve_rebind_allow
+            .as_ref()
+            .map(|builder| builder.build());
+
+        let egress_nat = config.egress_nat.as_ref().map(|c| {
+            let local_ips = config.bind4.iter().chain(&config.bind6).copied().collect();
+            EgressNatTable::spawn(c, local_ips)
+        });
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        let ipv6_rotate = config.ipv6_rotate.as_ref().map(Ipv6RotatePool::new);
 
         let escape_logger = config.get_escape_logger();
 
         stats.set_extra_tags(config.extra_metrics_tags.clone());
+        stats.bandwidth.update(
+            config.general.tcp_all_upload_speed_limit,
+            config.general.tcp_all_download_speed_limit,
+        );
 
         let escaper = DirectFixedEscaper {
             config: Arc::new(config),
             stats,
             resolver_handle,
             egress_net_filter,
+            ip_locate_handle,
             resolve_redirection,
+            resolve_rebind_allow,
+            egress_nat,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            ipv6_rotate,
             escape_logger,
         };
 
@@ -122,11 +155,21 @@ impl DirectFixedEscaper {
         }
     }
 
-    fn get_bind_random(
-        &self,
-        family: AddressFamily,
-        path_selection: Option<&EgressPathSelection>,
-    ) -> BindAddr {
+    fn egress_info(&self, local_ip: IpAddr) -> Option<EgressInfo> {
+        let public_ip = self.egress_nat.as_ref()?.public_ip(local_ip)?;
+        let mut egress_info = EgressInfo::default();
+        egress_info.set_ip(public_ip);
+        Some(egress_info)
+    }
+
+    fn get_bind_random(&self, family: AddressFamily, task_notes: &ServerTaskNotes) -> BindAddr {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        if family == AddressFamily::Ipv6 {
+            if let Some(pool) = &self.ipv6_rotate {
+                return BindAddr::FreeIp(IpAddr::V6(pool.select(task_notes)));
+            }
+        }
+
         let vec = match family {
             AddressFamily::Ipv4 => &self.config.bind4,
             AddressFamily::Ipv6 => &self.config.bind6,
@@ -143,7 +186,7 @@ impl DirectFixedEscaper {
             1 => BindAddr::Ip(vec[0]),
             n => {
                 if self.config.enable_path_selection {
-                    if let Some(path_selection) = path_selection {
+                    if let Some(path_selection) = task_notes.egress_path() {
                         if let Some(i) = path_selection.select_by_index(n) {
                             return BindAddr::Ip(vec[i]);
                         }
@@ -167,40 +210,75 @@ impl DirectFixedEscaper {
         }
     }
 
+    fn get_resolver_handle(
+        &self,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<ArcIntegratedResolverHandle, ResolveError> {
+        if let Some(user_ctx) = task_notes.user_ctx() {
+            if let Some(r) = user_ctx.resolver_handle() {
+                return r;
+            }
+        }
+        Ok(self.resolver_handle.clone())
+    }
+
     fn resolve_happy(
         &self,
         domain: Arc<str>,
         strategy: ResolveStrategy,
         task_notes: &ServerTaskNotes,
     ) -> Result<HappyEyeballsResolveJob, ResolveError> {
+        let resolver_handle = self.get_resolver_handle(task_notes)?;
+
         if let Some(user_ctx) = task_notes.user_ctx() {
             if let Some(redirect) = user_ctx.user().resolve_redirection() {
                 if let Some(v) = redirect.query_value(&domain) {
-                    return HappyEyeballsResolveJob::new_redirected(
-                        strategy,
-                        &self.resolver_handle,
-                        v,
-                    );
+                    return HappyEyeballsResolveJob::new_redirected(strategy, &resolver_handle, v);
                 }
             }
         }
 
         if let Some(redirect) = &self.resolve_redirection {
             if let Some(v) = redirect.query_value(&domain) {
-                return HappyEyeballsResolveJob::new_redirected(strategy, &self.resolver_handle, v);
+                return HappyEyeballsResolveJob::new_redirected(strategy, &resolver_handle, v);
             }
         }
 
-        HappyEyeballsResolveJob::new_dyn(strategy, &self.resolver_handle, domain)
+        if self.need_rebind_protection(&domain, task_notes) {
+            HappyEyeballsResolveJob::new_dyn_rebind_protected(strategy, &resolver_handle, domain)
+        } else {
+            HappyEyeballsResolveJob::new_dyn(strategy, &resolver_handle, domain)
+        }
+    }
+
+    fn need_rebind_protection(&self, domain: &str, task_notes: &ServerTaskNotes) -> bool {
+        if let Some(user_ctx) = task_notes.user_ctx() {
+            if user_ctx.user().resolve_rebind_allowed(domain) {
+                return false;
+            }
+        }
+
+        if let Some(rule) = &self.resolve_rebind_allow {
+            if matches!(rule.check(domain), (true, AclAction::Permit)) {
+                return false;
+            }
+        }
+
+        true
     }
 
     async fn resolve_best(
         &self,
         domain: Arc<str>,
         strategy: ResolveStrategy,
+        resolver_handle: &ArcIntegratedResolverHandle,
+        rebind_protection: bool,
     ) -> Result<IpAddr, ResolveError> {
-        let mut resolver_job =
-            HappyEyeballsResolveJob::new_dyn(strategy, &self.resolver_handle, domain)?;
+        let mut resolver_job = if rebind_protection {
+            HappyEyeballsResolveJob::new_dyn_rebind_protected(strategy, resolver_handle, domain)?
+        } else {
+            HappyEyeballsResolveJob::new_dyn(strategy, resolver_handle, domain)?
+        };
         let ips = resolver_job
             .get_r1_or_first(self.config.happy_eyeballs.resolution_delay(), usize::MAX)
             .await?;
@@ -213,10 +291,14 @@ impl DirectFixedEscaper {
         &self,
         redirect_result: Host,
         resolve_strategy: ResolveStrategy,
+        resolver_handle: &ArcIntegratedResolverHandle,
     ) -> Result<IpAddr, ResolveError> {
         match redirect_result {
             Host::Ip(ip) => Ok(ip),
-            Host::Domain(new) => self.resolve_best(new, resolve_strategy).await,
+            Host::Domain(new) => {
+                self.resolve_best(new, resolve_strategy, resolver_handle, false)
+                    .await
+            }
         }
     }
 
@@ -229,11 +311,13 @@ impl DirectFixedEscaper {
         match ups.host() {
             Host::Ip(ip) => Ok(SocketAddr::new(*ip, ups.port())),
             Host::Domain(domain) => {
+                let resolver_handle = self.get_resolver_handle(task_notes)?;
+
                 if let Some(user_ctx) = task_notes.user_ctx() {
                     if let Some(redirect) = user_ctx.user().resolve_redirection() {
                         if let Some(v) = redirect.query_first(domain, resolve_strategy.query) {
                             return self
-                                .redirect_get_best(v, resolve_strategy)
+                                .redirect_get_best(v, resolve_strategy, &resolver_handle)
                                 .await
                                 .map(|ip| SocketAddr::new(ip, ups.port()));
                         }
@@ -243,13 +327,20 @@ impl DirectFixedEscaper {
                 if let Some(redirect) = &self.resolve_redirection {
                     if let Some(v) = redirect.query_first(domain, resolve_strategy.query) {
                         return self
-                            .redirect_get_best(v, resolve_strategy)
+                            .redirect_get_best(v, resolve_strategy, &resolver_handle)
                             .await
                             .map(|ip| SocketAddr::new(ip, ups.port()));
                     }
                 }
 
-                let ip = self.resolve_best(domain.clone(), resolve_strategy).await?;
+                let ip = self
+                    .resolve_best(
+                        domain.clone(),
+                        resolve_strategy,
+                        &resolver_handle,
+                        self.need_rebind_protection(domain, task_notes),
+                    )
+                    .await?;
                 Ok(SocketAddr::new(ip, ups.port()))
             }
         }
@@ -280,6 +371,10 @@ impl Escaper for DirectFixedEscaper {
         Some(self.stats.clone())
     }
 
+    fn egress_nat_snapshot(&self) -> Option<Vec<(IpAddr, IpAddr)>> {
+        self.egress_nat.as_ref().map(|t| t.snapshot())
+    }
+
     async fn publish(&self, _data: String) -> anyhow::Result<()> {
         Err(anyhow!("not implemented"))
     }
@@ -293,7 +388,7 @@ impl Escaper for DirectFixedEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tcp_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.tcp_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -307,7 +402,7 @@ impl Escaper for DirectFixedEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tls_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.tls_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -320,7 +415,7 @@ impl Escaper for DirectFixedEscaper {
         task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
         self.stats.interface.add_udp_connect_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         self.udp_connect_to(task_conf, udp_notes, task_notes, task_stats)
             .await
     }
@@ -333,7 +428,7 @@ impl Escaper for DirectFixedEscaper {
         task_stats: ArcUdpRelayTaskRemoteStats,
     ) -> UdpRelaySetupResult {
         self.stats.interface.add_udp_relay_session_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         self.udp_setup_relay(task_conf, task_notes, task_stats)
             .await
     }
@@ -392,7 +487,7 @@ impl EscaperInternal for DirectFixedEscaper {
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.stats.interface.add_http_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.http_forward_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -407,7 +502,7 @@ impl EscaperInternal for DirectFixedEscaper {
         self.stats
             .interface
             .add_https_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.https_forward_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -421,7 +516,7 @@ impl EscaperInternal for DirectFixedEscaper {
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_over_http_request_attempted();
         self.stats.interface.add_ftp_control_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.new_ftp_control_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -436,7 +531,7 @@ impl EscaperInternal for DirectFixedEscaper {
         ftp_server: &UpstreamAddr,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_transfer_connection_attempted();
-        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        transfer_tcp_notes.set_escaper(&self.config.name);
 
         self.new_ftp_transfer_connection(
             task_conf,
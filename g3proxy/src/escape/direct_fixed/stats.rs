@@ -24,7 +24,8 @@ use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::stats::{StatId, TcpIoSnapshot, UdpIoSnapshot};
 
 use crate::escape::{
-    EscaperForbiddenSnapshot, EscaperForbiddenStats, EscaperInterfaceStats, EscaperInternalStats,
+    EscaperBandwidthLimiter, EscaperBandwidthSnapshot, EscaperForbiddenSnapshot,
+    EscaperForbiddenStats, EscaperInterfaceStats, EscaperInternalStats, EscaperKeepaliveStats,
     EscaperStats, EscaperTcpConnectSnapshot, EscaperTcpStats, EscaperUdpStats,
 };
 use crate::module::ftp_over_http::{FtpTaskRemoteControlStats, FtpTaskRemoteTransferStats};
@@ -40,6 +41,7 @@ pub(crate) struct DirectFixedEscaperStats {
     pub(crate) interface: EscaperInterfaceStats,
     pub(crate) udp: EscaperUdpStats,
     pub(crate) tcp: EscaperTcpStats,
+    pub(crate) bandwidth: EscaperBandwidthLimiter,
 }
 
 impl DirectFixedEscaperStats {
@@ -52,6 +54,7 @@ impl DirectFixedEscaperStats {
             interface: Default::default(),
             udp: Default::default(),
             tcp: Default::default(),
+            bandwidth: Default::default(),
         }
     }
 
@@ -70,6 +73,11 @@ impl EscaperInternalStats for DirectFixedEscaperStats {
     fn add_https_forward_request_attempted(&self) {
         self.interface.add_https_forward_request_attempted();
     }
+
+    #[inline]
+    fn http_forward_keepalive(&self) -> &Arc<EscaperKeepaliveStats> {
+        &self.interface.http_forward_keepalive
+    }
 }
 
 impl EscaperStats for DirectFixedEscaperStats {
@@ -119,6 +127,11 @@ impl EscaperStats for DirectFixedEscaperStats {
     fn forbidden_snapshot(&self) -> Option<EscaperForbiddenSnapshot> {
         Some(self.forbidden.snapshot())
     }
+
+    #[inline]
+    fn bandwidth_snapshot(&self) -> Option<EscaperBandwidthSnapshot> {
+        self.bandwidth.snapshot()
+    }
 }
 
 impl LimitedReaderStats for DirectFixedEscaperStats {
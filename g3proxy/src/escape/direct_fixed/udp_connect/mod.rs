@@ -22,7 +22,7 @@ use g3_io_ext::{LimitedUdpRecv, LimitedUdpSend};
 use g3_socket::util::AddressFamily;
 use g3_types::acl::AclAction;
 
-use super::DirectFixedEscaper;
+use super::{egress_location, DirectFixedEscaper};
 use crate::module::udp_connect::{
     ArcUdpConnectTaskRemoteStats, UdpConnectError, UdpConnectRemoteWrapperStats, UdpConnectResult,
     UdpConnectTaskConf, UdpConnectTaskNotes,
@@ -82,9 +82,21 @@ impl DirectFixedEscaper {
 
         let (_, action) = self.egress_net_filter.check(peer_addr.ip());
         self.handle_udp_target_ip_acl_action(action, task_notes)?;
+        if egress_location::forbid_user_egress_location(
+            self.ip_locate_handle.as_deref(),
+            &self.stats,
+            &self.escape_logger,
+            task_notes,
+            task_conf.upstream,
+            peer_addr.ip(),
+        )
+        .await
+        {
+            return Err(UdpConnectError::ForbiddenRemoteAddress);
+        }
 
         let family = AddressFamily::from(&peer_addr);
-        let bind = self.get_bind_random(family, task_notes.egress_path());
+        let bind = self.get_bind_random(family, task_notes);
         udp_notes.bind = bind;
 
         let misc_opts = if let Some(user_ctx) = task_notes.user_ctx() {
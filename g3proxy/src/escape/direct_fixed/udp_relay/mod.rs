@@ -22,6 +22,7 @@ use g3_socket::util::AddressFamily;
 
 use tokio::net::UdpSocket;
 
+use super::egress_location::UdpRelayEgressLocationCheck;
 use super::{DirectFixedEscaper, DirectFixedEscaperStats};
 use crate::module::udp_relay::{
     ArcUdpRelayTaskRemoteStats, UdpRelayRemoteWrapperStats, UdpRelaySetupError,
@@ -54,6 +55,13 @@ impl DirectFixedEscaper {
             &self.resolver_handle,
             self.config.resolve_strategy,
         );
+        if let Some(check) = UdpRelayEgressLocationCheck::new(
+            self.ip_locate_handle.as_ref(),
+            &self.escape_logger,
+            task_notes,
+        ) {
+            send.set_egress_location_check(check);
+        }
 
         if !self.config.no_ipv4 {
             let (bind, r, w) =
@@ -86,7 +94,7 @@ impl DirectFixedEscaper {
         ),
         UdpRelaySetupError,
     > {
-        let bind = self.get_bind_random(family, task_notes.egress_path());
+        let bind = self.get_bind_random(family, task_notes);
 
         let misc_opts = if let Some(user_ctx) = task_notes.user_ctx() {
             user_ctx
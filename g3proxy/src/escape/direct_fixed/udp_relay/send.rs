@@ -36,7 +36,7 @@ use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::net::{Host, UpstreamAddr};
 use g3_types::resolve::ResolveStrategy;
 
-use super::DirectFixedEscaperStats;
+use super::{DirectFixedEscaperStats, UdpRelayEgressLocationCheck};
 use crate::auth::UserContext;
 use crate::resolve::{ArcIntegratedResolverHandle, ArriveFirstResolveJob};
 
@@ -51,6 +51,7 @@ pub(crate) struct DirectUdpRelayRemoteSend<T> {
     bind_v6: SocketAddr,
     egress_net_filter: Arc<AclNetworkRule>,
     checked_egress_ip: Option<IpAddr>,
+    egress_location_check: Option<UdpRelayEgressLocationCheck>,
     resolver_handle: ArcIntegratedResolverHandle,
     resolve_strategy: ResolveStrategy,
     resolver_job: Option<ArriveFirstResolveJob>,
@@ -75,6 +76,7 @@ impl<T> DirectUdpRelayRemoteSend<T> {
             bind_v6: SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0),
             egress_net_filter: Arc::clone(egress_net_filter),
             checked_egress_ip: None,
+            egress_location_check: None,
             resolver_handle: Arc::clone(resolver_handle),
             resolve_strategy,
             resolver_job: None,
@@ -98,6 +100,10 @@ where
         self.bind_v6 = bind;
     }
 
+    pub(crate) fn set_egress_location_check(&mut self, check: UdpRelayEgressLocationCheck) {
+        self.egress_location_check = Some(check);
+    }
+
     pub(crate) fn usable(&self) -> bool {
         self.inner_v4.is_some() || self.inner_v6.is_some()
     }
@@ -238,6 +244,9 @@ where
         to: SocketAddr,
     ) -> Poll<Result<usize, UdpRelayRemoteError>> {
         self.check_egress_ip(to)?;
+        if let Some(check) = &mut self.egress_location_check {
+            ready!(check.poll_check(cx, &self.escaper_stats, to))?;
+        }
         if let Some(inner) = &mut self.inner_v4 {
             let nw = ready!(inner.poll_send_to(cx, buf, to))
                 .map_err(|e| UdpRelayRemoteError::SendFailed(self.bind_v4, to, e))?;
@@ -262,6 +271,9 @@ where
         to: SocketAddr,
     ) -> Poll<Result<usize, UdpRelayRemoteError>> {
         self.check_egress_ip(to)?;
+        if let Some(check) = &mut self.egress_location_check {
+            ready!(check.poll_check(cx, &self.escaper_stats, to))?;
+        }
         if let Some(inner) = &mut self.inner_v6 {
             let nw = ready!(inner.poll_send_to(cx, buf, to))
                 .map_err(|e| UdpRelayRemoteError::SendFailed(self.bind_v6, to, e))?;
@@ -383,6 +395,17 @@ where
                             break;
                         }
                     }
+                    if let Some(check) = &self.egress_location_check {
+                        if !check.is_permitted(ip) {
+                            if count == 0 {
+                                let _ =
+                                    ready!(self.poll_send_packet(cx, p.payload(), p.upstream()))?;
+                                return Poll::Ready(Ok(1));
+                            } else {
+                                break;
+                            }
+                        }
+                    }
 
                     count += 1;
                 }
@@ -419,6 +442,17 @@ where
                             break;
                         }
                     }
+                    if let Some(check) = &self.egress_location_check {
+                        if !check.is_permitted(ip) {
+                            if count == 0 {
+                                let _ =
+                                    ready!(self.poll_send_packet(cx, p.payload(), p.upstream()))?;
+                                return Poll::Ready(Ok(1));
+                            } else {
+                                break;
+                            }
+                        }
+                    }
 
                     count += 1;
                 }
@@ -44,13 +44,19 @@ impl DirectFloatEscaper {
         let wrapper_stats = Arc::new(wrapper_stats);
 
         let limit_config = &self.config.general.tcp_sock_speed_limit;
-        let stream = LimitedStream::local_limited(
+        let mut stream = LimitedStream::local_limited(
             stream,
             limit_config.shift_millis,
             limit_config.max_south,
             limit_config.max_north,
             wrapper_stats,
         );
+        if let Some(limiter) = self.stats.bandwidth.download() {
+            stream.add_global_read_limiter(limiter);
+        }
+        if let Some(limiter) = self.stats.bandwidth.upload() {
+            stream.add_global_write_limiter(limiter);
+        }
 
         Ok(Box::new(stream))
     }
@@ -79,13 +85,19 @@ impl DirectFloatEscaper {
         let wrapper_stats = Arc::new(wrapper_stats);
 
         let limit_config = &self.config.general.tcp_sock_speed_limit;
-        let stream = LimitedStream::local_limited(
+        let mut stream = LimitedStream::local_limited(
             stream,
             limit_config.shift_millis,
             limit_config.max_south,
             limit_config.max_north,
             wrapper_stats,
         );
+        if let Some(limiter) = self.stats.bandwidth.download() {
+            stream.add_global_read_limiter(limiter);
+        }
+        if let Some(limiter) = self.stats.bandwidth.upload() {
+            stream.add_global_write_limiter(limiter);
+        }
 
         Ok(Box::new(stream))
     }
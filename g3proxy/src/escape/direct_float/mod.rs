@@ -27,9 +27,10 @@ use slog::Logger;
 use tokio::time::Instant;
 
 use g3_daemon::stat::remote::ArcTcpConnectionTaskRemoteStats;
+use g3_ip_locate::IpLocationServiceHandle;
 use g3_resolver::ResolveError;
 use g3_socket::util::AddressFamily;
-use g3_types::acl::AclNetworkRule;
+use g3_types::acl::{AclAction, AclChildDomainRule, AclNetworkRule};
 use g3_types::metrics::NodeName;
 use g3_types::net::{Host, UpstreamAddr};
 use g3_types::resolve::{ResolveRedirection, ResolveStrategy};
@@ -76,7 +77,9 @@ pub(super) struct DirectFloatEscaper {
     stats: Arc<DirectFixedEscaperStats>,
     resolver_handle: ArcIntegratedResolverHandle,
     egress_net_filter: Arc<AclNetworkRule>,
+    ip_locate_handle: Option<Arc<IpLocationServiceHandle>>,
     resolve_redirection: Option<ResolveRedirection>,
+    resolve_rebind_allow: Option<AclChildDomainRule>,
     bind_v4: ArcSwap<BindSet>,
     bind_v6: ArcSwap<BindSet>,
     escape_logger: Logger,
@@ -91,11 +94,19 @@ impl DirectFloatEscaper {
     ) -> anyhow::Result<ArcEscaper> {
         let resolver_handle = crate::resolve::get_handle(config.resolver())?;
         let egress_net_filter = Arc::new(config.egress_net_filter.build());
+        let ip_locate_handle = match &config.ip_locate_service {
+            Some(c) => Some(Arc::new(c.spawn_ip_locate_agent()?)),
+            None => None,
+        };
 
         let resolve_redirection = config
             .resolve_redirection
             .as_ref()
             .map(|builder| builder.build());
+        let resolve_rebind_allow = config
+            .resolve_rebind_allow
+            .as_ref()
+            .map(|builder| builder.build());
 
         let escape_logger = config.get_escape_logger();
 
@@ -133,13 +144,19 @@ impl DirectFloatEscaper {
         };
 
         stats.set_extra_tags(config.extra_metrics_tags.clone());
+        stats.bandwidth.update(
+            config.general.tcp_all_upload_speed_limit,
+            config.general.tcp_all_download_speed_limit,
+        );
 
         let escaper = DirectFloatEscaper {
             config,
             stats,
             resolver_handle,
             egress_net_filter,
+            ip_locate_handle,
             resolve_redirection,
+            resolve_rebind_allow,
             bind_v4: ArcSwap::new(bind_v4),
             bind_v6: ArcSwap::new(bind_v6),
             escape_logger,
@@ -255,40 +272,75 @@ impl DirectFloatEscaper {
         }
     }
 
+    fn get_resolver_handle(
+        &self,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<ArcIntegratedResolverHandle, ResolveError> {
+        if let Some(user_ctx) = task_notes.user_ctx() {
+            if let Some(r) = user_ctx.resolver_handle() {
+                return r;
+            }
+        }
+        Ok(self.resolver_handle.clone())
+    }
+
     fn resolve_happy(
         &self,
         domain: Arc<str>,
         strategy: ResolveStrategy,
         task_notes: &ServerTaskNotes,
     ) -> Result<HappyEyeballsResolveJob, ResolveError> {
+        let resolver_handle = self.get_resolver_handle(task_notes)?;
+
         if let Some(user_ctx) = task_notes.user_ctx() {
             if let Some(redirect) = user_ctx.user().resolve_redirection() {
                 if let Some(v) = redirect.query_value(&domain) {
-                    return HappyEyeballsResolveJob::new_redirected(
-                        strategy,
-                        &self.resolver_handle,
-                        v,
-                    );
+                    return HappyEyeballsResolveJob::new_redirected(strategy, &resolver_handle, v);
                 }
             }
         }
 
         if let Some(redirect) = &self.resolve_redirection {
             if let Some(v) = redirect.query_value(&domain) {
-                return HappyEyeballsResolveJob::new_redirected(strategy, &self.resolver_handle, v);
+                return HappyEyeballsResolveJob::new_redirected(strategy, &resolver_handle, v);
+            }
+        }
+
+        if self.need_rebind_protection(&domain, task_notes) {
+            HappyEyeballsResolveJob::new_dyn_rebind_protected(strategy, &resolver_handle, domain)
+        } else {
+            HappyEyeballsResolveJob::new_dyn(strategy, &resolver_handle, domain)
+        }
+    }
+
+    fn need_rebind_protection(&self, domain: &str, task_notes: &ServerTaskNotes) -> bool {
+        if let Some(user_ctx) = task_notes.user_ctx() {
+            if user_ctx.user().resolve_rebind_allowed(domain) {
+                return false;
+            }
+        }
+
+        if let Some(rule) = &self.resolve_rebind_allow {
+            if matches!(rule.check(domain), (true, AclAction::Permit)) {
+                return false;
             }
         }
 
-        HappyEyeballsResolveJob::new_dyn(strategy, &self.resolver_handle, domain)
+        true
     }
 
     async fn resolve_best(
         &self,
         domain: Arc<str>,
         strategy: ResolveStrategy,
+        resolver_handle: &ArcIntegratedResolverHandle,
+        rebind_protection: bool,
     ) -> Result<IpAddr, ResolveError> {
-        let mut resolver_job =
-            HappyEyeballsResolveJob::new_dyn(strategy, &self.resolver_handle, domain)?;
+        let mut resolver_job = if rebind_protection {
+            HappyEyeballsResolveJob::new_dyn_rebind_protected(strategy, resolver_handle, domain)?
+        } else {
+            HappyEyeballsResolveJob::new_dyn(strategy, resolver_handle, domain)?
+        };
         let ips = resolver_job
             .get_r1_or_first(self.config.happy_eyeballs.resolution_delay(), usize::MAX)
             .await?;
@@ -301,10 +353,14 @@ impl DirectFloatEscaper {
         &self,
         redirect_result: Host,
         resolve_strategy: ResolveStrategy,
+        resolver_handle: &ArcIntegratedResolverHandle,
     ) -> Result<IpAddr, ResolveError> {
         match redirect_result {
             Host::Ip(ip) => Ok(ip),
-            Host::Domain(new) => self.resolve_best(new, resolve_strategy).await,
+            Host::Domain(new) => {
+                self.resolve_best(new, resolve_strategy, resolver_handle, false)
+                    .await
+            }
         }
     }
 
@@ -317,11 +373,13 @@ impl DirectFloatEscaper {
         match ups.host() {
             Host::Ip(ip) => Ok(SocketAddr::new(*ip, ups.port())),
             Host::Domain(domain) => {
+                let resolver_handle = self.get_resolver_handle(task_notes)?;
+
                 if let Some(user_ctx) = task_notes.user_ctx() {
                     if let Some(redirect) = user_ctx.user().resolve_redirection() {
                         if let Some(v) = redirect.query_first(domain, resolve_strategy.query) {
                             return self
-                                .redirect_get_best(v, resolve_strategy)
+                                .redirect_get_best(v, resolve_strategy, &resolver_handle)
                                 .await
                                 .map(|ip| SocketAddr::new(ip, ups.port()));
                         }
@@ -331,13 +389,20 @@ impl DirectFloatEscaper {
                 if let Some(redirect) = &self.resolve_redirection {
                     if let Some(v) = redirect.query_first(domain, resolve_strategy.query) {
                         return self
-                            .redirect_get_best(v, resolve_strategy)
+                            .redirect_get_best(v, resolve_strategy, &resolver_handle)
                             .await
                             .map(|ip| SocketAddr::new(ip, ups.port()));
                     }
                 }
 
-                let ip = self.resolve_best(domain.clone(), resolve_strategy).await?;
+                let ip = self
+                    .resolve_best(
+                        domain.clone(),
+                        resolve_strategy,
+                        &resolver_handle,
+                        self.need_rebind_protection(domain, task_notes),
+                    )
+                    .await?;
                 Ok(SocketAddr::new(ip, ups.port()))
             }
         }
@@ -381,7 +446,7 @@ impl Escaper for DirectFloatEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tcp_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.tcp_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -395,7 +460,7 @@ impl Escaper for DirectFloatEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tls_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.tls_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -408,7 +473,7 @@ impl Escaper for DirectFloatEscaper {
         task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
         self.stats.interface.add_udp_connect_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         self.udp_connect_to(task_conf, udp_notes, task_notes, task_stats)
             .await
     }
@@ -421,7 +486,7 @@ impl Escaper for DirectFloatEscaper {
         task_stats: ArcUdpRelayTaskRemoteStats,
     ) -> UdpRelaySetupResult {
         self.stats.interface.add_udp_relay_session_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         self.udp_setup_relay(task_conf, task_notes, task_stats)
             .await
     }
@@ -486,7 +551,7 @@ impl EscaperInternal for DirectFloatEscaper {
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.stats.interface.add_http_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.http_forward_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -501,7 +566,7 @@ impl EscaperInternal for DirectFloatEscaper {
         self.stats
             .interface
             .add_https_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.https_forward_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -515,7 +580,7 @@ impl EscaperInternal for DirectFloatEscaper {
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_over_http_request_attempted();
         self.stats.interface.add_ftp_control_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.new_ftp_control_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -530,7 +595,7 @@ impl EscaperInternal for DirectFloatEscaper {
         ftp_server: &UpstreamAddr,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_transfer_connection_attempted();
-        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        transfer_tcp_notes.set_escaper(&self.config.name);
 
         self.new_ftp_transfer_connection(
             task_conf,
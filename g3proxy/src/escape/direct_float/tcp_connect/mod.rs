@@ -17,6 +17,7 @@
 use std::io;
 use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
+use std::time::Duration;
 
 use tokio::net::{TcpSocket, TcpStream};
 use tokio::task::JoinSet;
@@ -27,9 +28,10 @@ use g3_io_ext::{LimitedReader, LimitedWriter};
 use g3_socket::util::AddressFamily;
 use g3_socket::BindAddr;
 use g3_types::acl::AclAction;
-use g3_types::net::{ConnectError, Host, TcpKeepAliveConfig, UpstreamAddr};
+use g3_types::net::{ConnectError, Host, TcpConnectRaceConfig, TcpKeepAliveConfig, UpstreamAddr};
 
 use super::{DirectFloatBindIp, DirectFloatEscaper};
+use crate::escape::direct_fixed::egress_location;
 use crate::escape::direct_fixed::tcp_connect::DirectTcpConnectConfig;
 use crate::log::escape::tcp_connect::EscapeLogForTcpConnect;
 use crate::module::tcp_connect::{
@@ -68,6 +70,28 @@ impl DirectFloatEscaper {
         }
     }
 
+    async fn check_user_egress_location(
+        &self,
+        peer_ip: IpAddr,
+        task_conf: &TcpConnectTaskConf<'_>,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(), TcpConnectError> {
+        if egress_location::forbid_user_egress_location(
+            self.ip_locate_handle.as_deref(),
+            &self.stats,
+            &self.escape_logger,
+            task_notes,
+            task_conf.upstream,
+            peer_ip,
+        )
+        .await
+        {
+            Err(TcpConnectError::ForbiddenRemoteAddress)
+        } else {
+            Ok(())
+        }
+    }
+
     fn prepare_connect_socket(
         &self,
         peer_ip: IpAddr,
@@ -118,6 +142,8 @@ impl DirectFloatEscaper {
         tcp_notes: &mut TcpConnectTaskNotes,
         task_notes: &ServerTaskNotes,
     ) -> Result<(TcpStream, DirectFloatBindIp), TcpConnectError> {
+        self.check_user_egress_location(peer_ip, task_conf, task_notes)
+            .await?;
         let (sock, bind) =
             self.prepare_connect_socket(peer_ip, tcp_notes.bind, task_notes, &config)?;
         let peer = SocketAddr::new(peer_ip, task_conf.upstream.port());
@@ -186,12 +212,14 @@ impl DirectFloatEscaper {
         task_notes: &ServerTaskNotes,
     ) -> Result<(TcpStream, DirectFloatBindIp), TcpConnectError> {
         let max_tries_each_family = config.connect.max_tries();
+        let resolve_instant = Instant::now();
         let mut ips = resolver_job
             .get_r1_or_first(
                 self.config.happy_eyeballs.resolution_delay(),
                 max_tries_each_family,
             )
             .await?;
+        tcp_notes.resolve_duration = resolve_instant.elapsed();
 
         let mut c_set = JoinSet::new();
 
@@ -212,7 +240,15 @@ impl DirectFloatEscaper {
 
         loop {
             if spawn_new_connection {
-                if let Some(ip) = ips.pop() {
+                while let Some(ip) = ips.pop() {
+                    if let Err(e) = self
+                        .check_user_egress_location(ip, task_conf, task_notes)
+                        .await
+                    {
+                        // skip the forbidden address and try the next one
+                        returned_err = e;
+                        continue;
+                    }
                     let (sock, bind) =
                         self.prepare_connect_socket(ip, tcp_notes.bind, task_notes, &config)?;
                     let peer = SocketAddr::new(ip, task_conf.upstream.port());
@@ -242,6 +278,7 @@ impl DirectFloatEscaper {
                         }
                     });
                     connect_interval.reset();
+                    break;
                 }
             }
 
@@ -336,6 +373,118 @@ impl DirectFloatEscaper {
         }
     }
 
+    async fn race_try_connect(
+        &self,
+        mut resolver_job: HappyEyeballsResolveJob,
+        race_config: TcpConnectRaceConfig,
+        config: DirectTcpConnectConfig,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(TcpStream, DirectFloatBindIp), TcpConnectError> {
+        let resolve_instant = Instant::now();
+        let ips = resolver_job
+            .get_race_pair(
+                self.config.happy_eyeballs.resolution_delay(),
+                race_config.resolution_delay(),
+            )
+            .await?;
+        tcp_notes.resolve_duration = resolve_instant.elapsed();
+        let port = task_conf.upstream.port();
+        let each_timeout = config.connect.each_timeout();
+
+        let mut c_set = JoinSet::new();
+
+        tcp_notes.tries = 0;
+        let instant_now = Instant::now();
+        let mut returned_err = TcpConnectError::NoAddressConnected;
+
+        for ip in ips {
+            if let Err(e) = self
+                .check_user_egress_location(ip, task_conf, task_notes)
+                .await
+            {
+                returned_err = e;
+                continue;
+            }
+            let (sock, bind) =
+                self.prepare_connect_socket(ip, tcp_notes.bind, task_notes, &config)?;
+            let peer = SocketAddr::new(ip, port);
+            // the first one starts at once, and the second one after the stagger delay
+            let delay = if tcp_notes.tries == 0 {
+                Duration::ZERO
+            } else {
+                race_config.stagger()
+            };
+            tcp_notes.tries += 1;
+            let stats = self.stats.clone();
+            c_set.spawn(async move {
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                stats.tcp.connect.add_attempted();
+                match tokio::time::timeout(each_timeout, sock.connect(peer)).await {
+                    Ok(Ok(stream)) => {
+                        stats.tcp.connect.add_success();
+                        (Ok(stream), peer, bind)
+                    }
+                    Ok(Err(e)) => {
+                        stats.tcp.connect.add_error();
+                        (
+                            Err(TcpConnectError::ConnectFailed(ConnectError::from(e))),
+                            peer,
+                            bind,
+                        )
+                    }
+                    Err(_) => {
+                        stats.tcp.connect.add_timeout();
+                        (Err(TcpConnectError::TimeoutByRule), peer, bind)
+                    }
+                }
+            });
+        }
+
+        // the loser will be cancelled when the join set is dropped
+        while let Some(r) = c_set.join_next().await {
+            tcp_notes.duration = instant_now.elapsed();
+            match r {
+                Ok((r, peer_addr, bind)) => {
+                    tcp_notes.next = Some(peer_addr);
+                    tcp_notes.bind = BindAddr::Ip(bind.ip);
+                    tcp_notes.expire = bind.expire_datetime;
+                    tcp_notes.egress = Some(bind.egress_info.clone());
+                    match r {
+                        Ok(ups_stream) => {
+                            let local_addr = ups_stream
+                                .local_addr()
+                                .map_err(TcpConnectError::SetupSocketFailed)?;
+                            self.stats.tcp.connect.add_established();
+                            tcp_notes.local = Some(local_addr);
+                            tcp_notes.chained.target_addr = Some(peer_addr);
+                            tcp_notes.chained.outgoing_addr = Some(local_addr);
+                            return Ok((ups_stream, bind));
+                        }
+                        Err(e) => {
+                            EscapeLogForTcpConnect {
+                                upstream: task_conf.upstream,
+                                tcp_notes,
+                                task_id: &task_notes.id,
+                            }
+                            .log(&self.escape_logger, &e);
+                            returned_err = e;
+                        }
+                    }
+                }
+                Err(e) => {
+                    if e.is_panic() {
+                        return Err(TcpConnectError::InternalServerError("connect task panic"));
+                    }
+                }
+            }
+        }
+        Err(returned_err)
+    }
+
     pub(super) async fn tcp_connect_to(
         &self,
         task_conf: &TcpConnectTaskConf<'_>,
@@ -347,17 +496,27 @@ impl DirectFloatEscaper {
             keepalive: self.config.tcp_keepalive,
             misc_opts: self.config.tcp_misc_opts,
         };
+        if let Some(rule) = self.config.timeout_overrides.get(task_conf.upstream) {
+            if let Some(timeout) = rule.connect_timeout {
+                config.connect.set_each_timeout(timeout);
+            }
+        }
 
+        let mut race_config = self.config.tcp_connect_race;
         if let Some(user_ctx) = task_notes.user_ctx() {
             let user_config = user_ctx.user_config();
 
             if let Some(user_config) = &user_config.tcp_connect {
                 config.connect.limit_to(user_config);
             }
+            if let Some(user_race_config) = user_config.tcp_connect_race {
+                race_config = user_race_config;
+            }
 
             config.keepalive = config.keepalive.adjust_to(user_config.tcp_remote_keepalive);
             config.misc_opts = user_config.tcp_remote_misc_opts(&config.misc_opts);
         }
+        tcp_notes.connect_timeout = Some(config.connect.each_timeout());
 
         match task_conf.upstream.host() {
             Host::Ip(ip) => {
@@ -371,8 +530,20 @@ impl DirectFloatEscaper {
                     task_notes,
                 )?;
 
-                self.happy_try_connect(resolver_job, config, task_conf, tcp_notes, task_notes)
+                if race_config.is_enabled() {
+                    self.race_try_connect(
+                        resolver_job,
+                        race_config,
+                        config,
+                        task_conf,
+                        tcp_notes,
+                        task_notes,
+                    )
                     .await
+                } else {
+                    self.happy_try_connect(resolver_job, config, task_conf, tcp_notes, task_notes)
+                        .await
+                }
             }
         }
     }
@@ -393,6 +564,11 @@ impl DirectFloatEscaper {
             keepalive: TcpKeepAliveConfig::default(),
             misc_opts: self.config.tcp_misc_opts,
         };
+        if let Some(rule) = self.config.timeout_overrides.get(task_conf.upstream) {
+            if let Some(timeout) = rule.connect_timeout {
+                config.connect.set_each_timeout(timeout);
+            }
+        }
 
         if let Some(user_ctx) = task_notes.user_ctx() {
             if let Some(user_config) = &user_ctx.user_config().tcp_connect {
@@ -403,6 +579,7 @@ impl DirectFloatEscaper {
                 .user_config()
                 .tcp_remote_misc_opts(&config.misc_opts)
         }
+        new_tcp_notes.connect_timeout = Some(config.connect.each_timeout());
 
         if task_conf.upstream.host_eq(old_upstream) {
             let control_addr = old_tcp_notes.next.ok_or_else(|| {
@@ -466,18 +643,24 @@ impl DirectFloatEscaper {
         let wrapper_stats = Arc::new(wrapper_stats);
 
         let limit_config = &self.config.general.tcp_sock_speed_limit;
-        let r = LimitedReader::local_limited(
+        let mut r = LimitedReader::local_limited(
             r,
             limit_config.shift_millis,
             limit_config.max_south,
             wrapper_stats.clone(),
         );
-        let w = LimitedWriter::local_limited(
+        if let Some(limiter) = self.stats.bandwidth.download() {
+            r.add_global_limiter(limiter);
+        }
+        let mut w = LimitedWriter::local_limited(
             w,
             limit_config.shift_millis,
             limit_config.max_north,
             wrapper_stats,
         );
+        if let Some(limiter) = self.stats.bandwidth.upload() {
+            w.add_global_limiter(limiter);
+        }
 
         Ok((Box::new(r), Box::new(w)))
     }
@@ -15,6 +15,7 @@
  */
 
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::anyhow;
 use tokio::io::{AsyncRead, AsyncWrite};
@@ -46,20 +47,39 @@ impl DirectFloatEscaper {
 
         // set limit config and add escaper stats, do not count in task stats
         let limit_config = &self.config.general.tcp_sock_speed_limit;
-        let stream = LimitedStream::local_limited(
+        let mut stream = LimitedStream::local_limited(
             stream,
             limit_config.shift_millis,
             limit_config.max_south,
             limit_config.max_north,
             self.stats.clone(),
         );
+        if let Some(limiter) = self.stats.bandwidth.download() {
+            stream.add_global_read_limiter(limiter);
+        }
+        if let Some(limiter) = self.stats.bandwidth.upload() {
+            stream.add_global_write_limiter(limiter);
+        }
 
         let ssl = task_conf.build_ssl()?;
         let connector = SslConnector::new(ssl, stream)
             .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
 
-        match tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await {
-            Ok(Ok(stream)) => Ok((stream, bind)),
+        let handshake_timeout = self
+            .config
+            .timeout_overrides
+            .get(task_conf.tcp.upstream)
+            .and_then(|rule| rule.tls_handshake_timeout)
+            .unwrap_or_else(|| task_conf.handshake_timeout());
+        tcp_notes.tls_handshake_timeout = Some(handshake_timeout);
+
+        let instant_now = Instant::now();
+        match tokio::time::timeout(handshake_timeout, connector.connect()).await {
+            Ok(Ok(stream)) => {
+                tcp_notes.tls_duration = instant_now.elapsed();
+                tcp_notes.set_tls_alpn(stream.ssl());
+                Ok((stream, bind))
+            }
             Ok(Err(e)) => {
                 let e = anyhow::Error::new(e);
                 EscapeLogForTlsHandshake {
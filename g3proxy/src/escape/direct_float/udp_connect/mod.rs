@@ -24,6 +24,7 @@ use g3_socket::BindAddr;
 use g3_types::acl::AclAction;
 
 use super::DirectFloatEscaper;
+use crate::escape::direct_fixed::egress_location;
 use crate::escape::direct_fixed::udp_connect::{
     DirectUdpConnectRemoteRecv, DirectUdpConnectRemoteSend,
 };
@@ -80,6 +81,18 @@ impl DirectFloatEscaper {
 
         let (_, action) = self.egress_net_filter.check(peer_addr.ip());
         self.handle_udp_target_ip_acl_action(action, task_notes)?;
+        if egress_location::forbid_user_egress_location(
+            self.ip_locate_handle.as_deref(),
+            &self.stats,
+            &self.escape_logger,
+            task_notes,
+            task_conf.upstream,
+            peer_addr.ip(),
+        )
+        .await
+        {
+            return Err(UdpConnectError::ForbiddenRemoteAddress);
+        }
 
         let family = AddressFamily::from(&peer_addr);
         let bind = self
@@ -25,6 +25,7 @@ use g3_socket::util::AddressFamily;
 use g3_socket::BindAddr;
 
 use super::DirectFloatEscaper;
+use crate::escape::direct_fixed::egress_location::UdpRelayEgressLocationCheck;
 use crate::escape::direct_fixed::udp_relay::{DirectUdpRelayRemoteRecv, DirectUdpRelayRemoteSend};
 use crate::escape::direct_fixed::DirectFixedEscaperStats;
 use crate::module::udp_relay::{
@@ -52,6 +53,13 @@ impl DirectFloatEscaper {
             &self.resolver_handle,
             self.config.resolve_strategy,
         );
+        if let Some(check) = UdpRelayEgressLocationCheck::new(
+            self.ip_locate_handle.as_ref(),
+            &self.escape_logger,
+            task_notes,
+        ) {
+            send.set_egress_location_check(check);
+        }
 
         if !self.config.no_ipv4 {
             if let Ok((bind, r, w)) =
@@ -167,6 +167,16 @@ impl DivertTcpEscaper {
             pp2_encoder.push_username(user_ctx.user_name())?;
         }
         pp2_encoder.push_task_id(task_notes.id.as_bytes())?;
+
+        let tlv_config = &self.config.proxy_protocol_tlv;
+        if !tlv_config.is_empty() {
+            let unique_id = tlv_config
+                .unique_id()
+                .then(|| task_notes.proxy_protocol_unique_id());
+            for tlv in tlv_config.tlvs(task_notes.proxy_protocol_tlvs(), unique_id.as_ref()) {
+                pp2_encoder.push_tlv(tlv.kind(), tlv.value())?;
+            }
+        }
         Ok(())
     }
 
@@ -222,7 +232,7 @@ impl Escaper for DivertTcpEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tcp_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.tcp_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -236,7 +246,7 @@ impl Escaper for DivertTcpEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tls_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.tls_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -249,7 +259,7 @@ impl Escaper for DivertTcpEscaper {
         _task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
         self.stats.interface.add_udp_connect_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         Err(UdpConnectError::MethodUnavailable)
     }
 
@@ -261,7 +271,7 @@ impl Escaper for DivertTcpEscaper {
         _task_stats: ArcUdpRelayTaskRemoteStats,
     ) -> UdpRelaySetupResult {
         self.stats.interface.add_udp_relay_session_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         Err(UdpRelaySetupError::MethodUnavailable)
     }
 
@@ -318,7 +328,7 @@ impl EscaperInternal for DivertTcpEscaper {
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.stats.interface.add_http_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.http_forward_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -333,7 +343,7 @@ impl EscaperInternal for DivertTcpEscaper {
         self.stats
             .interface
             .add_https_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.https_forward_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -347,7 +357,7 @@ impl EscaperInternal for DivertTcpEscaper {
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_over_http_request_attempted();
         self.stats.interface.add_ftp_control_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -361,7 +371,7 @@ impl EscaperInternal for DivertTcpEscaper {
         _ftp_server: &UpstreamAddr,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_transfer_connection_attempted();
-        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        transfer_tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 }
@@ -15,6 +15,7 @@
  */
 
 use std::sync::Arc;
+use std::time::Instant;
 
 use anyhow::anyhow;
 use tokio::io::{AsyncRead, AsyncWrite};
@@ -66,8 +67,13 @@ impl DivertTcpEscaper {
         let connector = SslConnector::new(ssl, stream)
             .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
 
+        let instant_now = Instant::now();
         match tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await {
-            Ok(Ok(stream)) => Ok(stream),
+            Ok(Ok(stream)) => {
+                tcp_notes.tls_duration = instant_now.elapsed();
+                tcp_notes.set_tls_alpn(stream.ssl());
+                Ok(stream)
+            }
             Ok(Err(e)) => {
                 let e = anyhow::Error::new(e);
                 EscapeLogForTlsHandshake {
@@ -115,7 +115,7 @@ impl Escaper for DummyDenyEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tcp_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -128,7 +128,7 @@ impl Escaper for DummyDenyEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tls_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -140,7 +140,7 @@ impl Escaper for DummyDenyEscaper {
         _task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
         self.stats.interface.add_udp_connect_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         Err(UdpConnectError::MethodUnavailable)
     }
 
@@ -152,7 +152,7 @@ impl Escaper for DummyDenyEscaper {
         _task_stats: ArcUdpRelayTaskRemoteStats,
     ) -> UdpRelaySetupResult {
         self.stats.interface.add_udp_relay_session_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         Err(UdpRelaySetupError::MethodUnavailable)
     }
 
@@ -206,7 +206,7 @@ impl EscaperInternal for DummyDenyEscaper {
         _task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.stats.interface.add_http_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -220,7 +220,7 @@ impl EscaperInternal for DummyDenyEscaper {
         self.stats
             .interface
             .add_https_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -233,7 +233,7 @@ impl EscaperInternal for DummyDenyEscaper {
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_over_http_request_attempted();
         self.stats.interface.add_ftp_control_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -247,7 +247,7 @@ impl EscaperInternal for DummyDenyEscaper {
         _ftp_server: &UpstreamAddr,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_transfer_connection_attempted();
-        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        transfer_tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 }
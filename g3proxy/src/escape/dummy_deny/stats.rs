@@ -21,7 +21,9 @@ use arc_swap::ArcSwapOption;
 use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::stats::StatId;
 
-use crate::escape::{EscaperInterfaceStats, EscaperInternalStats, EscaperStats};
+use crate::escape::{
+    EscaperInterfaceStats, EscaperInternalStats, EscaperKeepaliveStats, EscaperStats,
+};
 
 pub(super) struct DummyDenyEscaperStats {
     name: NodeName,
@@ -55,6 +57,11 @@ impl EscaperInternalStats for DummyDenyEscaperStats {
     fn add_https_forward_request_attempted(&self) {
         self.interface.add_https_forward_request_attempted();
     }
+
+    #[inline]
+    fn http_forward_keepalive(&self) -> &Arc<EscaperKeepaliveStats> {
+        &self.interface.http_forward_keepalive
+    }
 }
 
 impl EscaperStats for DummyDenyEscaperStats {
@@ -0,0 +1,244 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Weak};
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use arc_swap::ArcSwap;
+use log::{debug, warn};
+use tokio::net::UdpSocket;
+
+use crate::config::escaper::egress_nat::EgressNatConfig;
+
+const STUN_BINDING_REQUEST: u16 = 0x0001;
+const STUN_BINDING_SUCCESS: u16 = 0x0101;
+const STUN_MAGIC_COOKIE: u32 = 0x2112_A442;
+const STUN_ATTR_MAPPED_ADDRESS: u16 = 0x0001;
+const STUN_ATTR_XOR_MAPPED_ADDRESS: u16 = 0x0020;
+const STUN_HEADER_LEN: usize = 20;
+
+pub(crate) struct EgressNatTable {
+    static_map: BTreeMap<IpAddr, IpAddr>,
+    probed_map: ArcSwap<AHashMap<IpAddr, IpAddr>>,
+}
+
+impl EgressNatTable {
+    /// create the table, and spawn the probe task if a stun server is set
+    pub(crate) fn spawn(config: &EgressNatConfig, local_ips: Vec<IpAddr>) -> Arc<Self> {
+        let table = Arc::new(EgressNatTable {
+            static_map: config.static_map.clone(),
+            probed_map: ArcSwap::new(Arc::new(AHashMap::new())),
+        });
+
+        if let Some(stun_server) = config.stun_server {
+            let probe = NatProbe {
+                table: Arc::downgrade(&table),
+                config: config.clone(),
+                stun_server,
+                local_ips,
+            };
+            tokio::spawn(probe.into_running());
+        }
+
+        table
+    }
+
+    /// get the public ip for the local bound ip, static entries take precedence
+    pub(crate) fn public_ip(&self, local: IpAddr) -> Option<IpAddr> {
+        if let Some(ip) = self.static_map.get(&local) {
+            return Some(*ip);
+        }
+        self.probed_map.load().get(&local).copied()
+    }
+
+    pub(crate) fn snapshot(&self) -> Vec<(IpAddr, IpAddr)> {
+        let mut map = self.static_map.clone();
+        for (local, public) in self.probed_map.load().iter() {
+            map.entry(*local).or_insert(*public);
+        }
+        map.into_iter().collect()
+    }
+}
+
+struct NatProbe {
+    table: Weak<EgressNatTable>,
+    config: EgressNatConfig,
+    stun_server: SocketAddr,
+    local_ips: Vec<IpAddr>,
+}
+
+impl NatProbe {
+    fn probe_local_ips(&self) -> Vec<IpAddr> {
+        let mut ips: Vec<IpAddr> = self
+            .local_ips
+            .iter()
+            .filter(|ip| ip.is_ipv4() == self.stun_server.is_ipv4())
+            .copied()
+            .collect();
+        if ips.is_empty() {
+            // no bind ip set, probe with the default route
+            let ip = match self.stun_server {
+                SocketAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+                SocketAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+            };
+            ips.push(ip);
+        }
+        ips
+    }
+
+    async fn into_running(self) {
+        let local_ips = self.probe_local_ips();
+        let mut interval = tokio::time::interval(self.config.probe_interval);
+
+        loop {
+            interval.tick().await;
+
+            let Some(table) = self.table.upgrade() else {
+                break;
+            };
+
+            let mut probed_map = AHashMap::with_capacity(local_ips.len());
+            for ip in &local_ips {
+                match tokio::time::timeout(self.config.probe_timeout, self.probe(*ip)).await {
+                    Ok(Ok((local, public))) => {
+                        debug!("egress nat probe: {local} -> {public}");
+                        probed_map.insert(local, public);
+                    }
+                    Ok(Err(e)) => {
+                        warn!(
+                            "egress nat probe from {ip} to {} failed: {e:?}",
+                            self.stun_server
+                        )
+                    }
+                    Err(_) => warn!(
+                        "egress nat probe from {ip} to {} timed out",
+                        self.stun_server
+                    ),
+                }
+            }
+            if !probed_map.is_empty() {
+                table.probed_map.store(Arc::new(probed_map));
+            }
+        }
+    }
+
+    async fn probe(&self, ip: IpAddr) -> anyhow::Result<(IpAddr, IpAddr)> {
+        let socket = UdpSocket::bind(SocketAddr::new(ip, 0)).await?;
+        socket.connect(self.stun_server).await?;
+        let local = socket.local_addr()?.ip();
+
+        let transaction_id: [u8; 12] = rand::random();
+        let mut req = [0u8; STUN_HEADER_LEN];
+        req[0..2].copy_from_slice(&STUN_BINDING_REQUEST.to_be_bytes());
+        req[4..8].copy_from_slice(&STUN_MAGIC_COOKIE.to_be_bytes());
+        req[8..20].copy_from_slice(&transaction_id);
+        socket.send(&req).await?;
+
+        let mut buf = [0u8; 512];
+        loop {
+            let len = socket.recv(&mut buf).await?;
+            if let Some(public) = parse_binding_response(&buf[..len], &transaction_id)? {
+                return Ok((local, public));
+            }
+        }
+    }
+}
+
+/// parse the stun binding response, `None` will be returned if it's not for us
+fn parse_binding_response(buf: &[u8], transaction_id: &[u8; 12]) -> anyhow::Result<Option<IpAddr>> {
+    if buf.len() < STUN_HEADER_LEN || buf[8..20] != transaction_id[..] {
+        return Ok(None);
+    }
+    let msg_type = u16::from_be_bytes([buf[0], buf[1]]);
+    if msg_type != STUN_BINDING_SUCCESS {
+        return Err(anyhow!("unexpected stun message type {msg_type:#06x}"));
+    }
+    let msg_len = u16::from_be_bytes([buf[2], buf[3]]) as usize;
+    let Some(mut attrs) = buf.get(STUN_HEADER_LEN..STUN_HEADER_LEN + msg_len) else {
+        return Err(anyhow!("truncated stun message"));
+    };
+
+    let mut mapped = None;
+    while attrs.len() >= 4 {
+        let attr_type = u16::from_be_bytes([attrs[0], attrs[1]]);
+        let attr_len = u16::from_be_bytes([attrs[2], attrs[3]]) as usize;
+        let Some(value) = attrs.get(4..4 + attr_len) else {
+            return Err(anyhow!("truncated stun attribute"));
+        };
+        match attr_type {
+            STUN_ATTR_XOR_MAPPED_ADDRESS => {
+                return parse_address(value, Some(&buf[4..20])).map(Some);
+            }
+            STUN_ATTR_MAPPED_ADDRESS => mapped = Some(parse_address(value, None)?),
+            _ => {}
+        }
+        // attributes are padded to 4 bytes
+        let skip = 4 + ((attr_len + 3) & !3);
+        attrs = attrs.get(skip..).unwrap_or_default();
+    }
+    mapped
+        .map(Some)
+        .ok_or_else(|| anyhow!("no mapped address found in stun response"))
+}
+
+fn parse_address(value: &[u8], xor_key: Option<&[u8]>) -> anyhow::Result<IpAddr> {
+    if value.len() < 4 {
+        return Err(anyhow!("invalid stun address attribute"));
+    }
+    let xor = |i: usize, b: u8| match xor_key {
+        Some(key) => b ^ key[i],
+        None => b,
+    };
+    match value[1] {
+        0x01 if value.len() >= 8 => {
+            let mut octets = [0u8; 4];
+            for (i, o) in octets.iter_mut().enumerate() {
+                *o = xor(i, value[4 + i]);
+            }
+            Ok(IpAddr::V4(Ipv4Addr::from(octets)))
+        }
+        0x02 if value.len() >= 20 => {
+            let mut octets = [0u8; 16];
+            for (i, o) in octets.iter_mut().enumerate() {
+                *o = xor(i, value[4 + i]);
+            }
+            Ok(IpAddr::V6(Ipv6Addr::from(octets)))
+        }
+        family => Err(anyhow!("invalid stun address family {family}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xor_mapped_v4() {
+        let tid = [1u8; 12];
+        let mut buf = vec![0x01, 0x01, 0x00, 0x0c, 0x21, 0x12, 0xa4, 0x42];
+        buf.extend_from_slice(&tid);
+        // XOR-MAPPED-ADDRESS 203.0.113.10:4660
+        buf.extend_from_slice(&[0x00, 0x20, 0x00, 0x08, 0x00, 0x01, 0x32, 0x26]);
+        buf.extend_from_slice(&[203 ^ 0x21, 0x12, 113 ^ 0xa4, 10 ^ 0x42]);
+        let ip = parse_binding_response(&buf, &tid).unwrap().unwrap();
+        assert_eq!(ip, IpAddr::V4(Ipv4Addr::new(203, 0, 113, 10)));
+
+        let other_tid = [2u8; 12];
+        assert!(parse_binding_response(&buf, &other_tid).unwrap().is_none());
+    }
+}
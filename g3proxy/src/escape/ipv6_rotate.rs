@@ -0,0 +1,179 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::VecDeque;
+use std::net::{IpAddr, Ipv6Addr};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use ahash::{AHashMap, AHashSet};
+
+use crate::config::escaper::ipv6_rotate::{Ipv6RotateConfig, Ipv6RotateMode};
+use crate::serve::ServerTaskNotes;
+
+const ALLOC_MAX_TRIES: usize = 8;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum SessionKey {
+    User(Arc<str>),
+    Client(IpAddr),
+}
+
+impl SessionKey {
+    fn new(task_notes: &ServerTaskNotes) -> Self {
+        match task_notes.user_ctx() {
+            Some(user_ctx) => SessionKey::User(user_ctx.user_name().clone()),
+            None => SessionKey::Client(task_notes.client_ip()),
+        }
+    }
+}
+
+#[derive(Default)]
+struct PoolState {
+    sessions: AHashMap<SessionKey, (Ipv6Addr, Instant)>,
+    recent: VecDeque<Ipv6Addr>,
+    recent_set: AHashSet<Ipv6Addr>,
+}
+
+pub(crate) struct Ipv6RotatePool {
+    config: Ipv6RotateConfig,
+    network: u128,
+    host_mask: u128,
+    state: Mutex<PoolState>,
+}
+
+impl Ipv6RotatePool {
+    pub(crate) fn new(config: &Ipv6RotateConfig) -> Self {
+        let host_bits = config.host_bits();
+        let host_mask = if host_bits >= 128 {
+            u128::MAX
+        } else {
+            (1u128 << host_bits) - 1
+        };
+        Ipv6RotatePool {
+            config: config.clone(),
+            network: u128::from(config.prefix.network_address()) & !host_mask,
+            host_mask,
+            state: Mutex::new(PoolState::default()),
+        }
+    }
+
+    /// select the source address for a new connection
+    pub(crate) fn select(&self, task_notes: &ServerTaskNotes) -> Ipv6Addr {
+        let mut state = self.state.lock().unwrap();
+        match self.config.mode {
+            Ipv6RotateMode::PerConnection => self.alloc(&mut state),
+            Ipv6RotateMode::PerSession => {
+                let key = SessionKey::new(task_notes);
+                let now = Instant::now();
+                if let Some((ip, expire)) = state.sessions.get(&key) {
+                    if *expire > now {
+                        return *ip;
+                    }
+                }
+
+                let ip = self.alloc(&mut state);
+                if state.sessions.len() >= self.config.max_sessions {
+                    state.sessions.retain(|_, (_, expire)| *expire > now);
+                }
+                if state.sessions.len() < self.config.max_sessions {
+                    state
+                        .sessions
+                        .insert(key, (ip, now + self.config.session_ttl));
+                }
+                ip
+            }
+        }
+    }
+
+    fn alloc(&self, state: &mut PoolState) -> Ipv6Addr {
+        let mut ip = self.random_ip();
+        for _ in 1..ALLOC_MAX_TRIES {
+            if self.is_usable(state, &ip) {
+                break;
+            }
+            ip = self.random_ip();
+        }
+
+        if self.config.recent_size > 0 {
+            if state.recent.len() >= self.config.recent_size {
+                if let Some(old) = state.recent.pop_front() {
+                    state.recent_set.remove(&old);
+                }
+            }
+            if state.recent_set.insert(ip) {
+                state.recent.push_back(ip);
+            }
+        }
+        ip
+    }
+
+    fn random_ip(&self) -> Ipv6Addr {
+        let host = fastrand::u128(..) & self.host_mask;
+        Ipv6Addr::from(self.network | host)
+    }
+
+    fn is_usable(&self, state: &PoolState, ip: &Ipv6Addr) -> bool {
+        let host = u128::from(*ip) & self.host_mask;
+        if host == 0 {
+            // the Subnet-Router anycast address
+            return false;
+        }
+        if self.config.host_bits() == 64 && host >= self.host_mask - 127 {
+            // reserved subnet anycast addresses, see RFC 2526
+            return false;
+        }
+        !self.config.exclude.contains(ip) && !state.recent_set.contains(ip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::Yaml;
+
+    fn build_pool(prefix: &str) -> Ipv6RotatePool {
+        let config = Ipv6RotateConfig::parse_yaml(&Yaml::String(prefix.to_string())).unwrap();
+        Ipv6RotatePool::new(&config)
+    }
+
+    #[test]
+    fn alloc_in_prefix() {
+        let pool = build_pool("2001:db8:1:2::/64");
+        let mut state = PoolState::default();
+        for _ in 0..100 {
+            let ip = pool.alloc(&mut state);
+            assert!(pool.config.prefix.contains(ip));
+            assert_ne!(u128::from(ip) & pool.host_mask, 0);
+        }
+        assert_eq!(state.recent.len(), 100);
+        assert_eq!(state.recent_set.len(), 100);
+    }
+
+    #[test]
+    fn avoid_collision() {
+        let pool = build_pool("2001:db8::/120");
+        let mut state = PoolState::default();
+        let mut used = AHashSet::new();
+        for _ in 0..16 {
+            let ip = pool.alloc(&mut state);
+            assert!(pool.config.prefix.contains(ip));
+            used.insert(ip);
+        }
+        // collisions are still possible after the max tries, but should be rare
+        assert!(used.len() >= 15);
+    }
+}
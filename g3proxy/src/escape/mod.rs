@@ -51,14 +51,26 @@ pub(crate) use registry::{foreach as foreach_escaper, get_names, get_or_insert_d
 mod stats;
 pub(crate) use stats::{
     ArcEscaperInternalStats, ArcEscaperStats, EscaperForbiddenSnapshot, EscaperForbiddenStats,
-    EscaperInterfaceStats, EscaperInternalStats, EscaperStats, EscaperTcpConnectSnapshot,
-    EscaperTcpStats, EscaperTlsSnapshot, EscaperTlsStats, EscaperUdpStats, RouteEscaperSnapshot,
-    RouteEscaperStats,
+    EscaperInterfaceStats, EscaperInternalStats, EscaperKeepaliveSnapshot, EscaperKeepaliveStats,
+    EscaperStats, EscaperTcpConnectSnapshot, EscaperTcpStats, EscaperTlsSnapshot, EscaperTlsStats,
+    EscaperUdpStats, RouteEscaperSnapshot, RouteEscaperStats,
+};
+
+mod bandwidth;
+pub(crate) use bandwidth::{
+    EscaperBandwidthLimiter, EscaperBandwidthSnapshot, EscaperStreamLimitSnapshot,
 };
 
 mod egress_path;
 pub(crate) use egress_path::EgressPathSelection;
 
+mod route_path;
+pub(crate) use route_path::{foreach_route_edge, EscaperRoutePath};
+
+mod egress_nat;
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod ipv6_rotate;
+
 mod comply_audit;
 mod direct_fixed;
 mod direct_float;
@@ -76,13 +88,14 @@ mod route_mapping;
 mod route_query;
 mod route_resolved;
 mod route_select;
+mod route_sni;
 mod route_upstream;
 mod trick_float;
 
 mod ops;
 pub use ops::load_all;
 pub(crate) use ops::{
-    get_escaper, reload, update_dependency_to_auditor, update_dependency_to_resolver,
+    get_escaper, rebuild, reload, update_dependency_to_auditor, update_dependency_to_resolver,
 };
 
 /// Functions in this trait should only be called from registry module,
@@ -159,7 +172,6 @@ pub(crate) trait EscaperInternal {
 #[async_trait]
 pub(crate) trait Escaper: EscaperInternal {
     fn name(&self) -> &NodeName;
-    #[allow(unused)]
     fn escaper_type(&self) -> &str;
     fn get_escape_stats(&self) -> Option<ArcEscaperStats> {
         None
@@ -167,6 +179,10 @@ pub(crate) trait Escaper: EscaperInternal {
     fn ref_route_stats(&self) -> Option<&Arc<RouteEscaperStats>> {
         None
     }
+    /// get the local ip to public egress ip mapping, if egress nat is set
+    fn egress_nat_snapshot(&self) -> Option<Vec<(IpAddr, IpAddr)>> {
+        None
+    }
 
     async fn publish(&self, data: String) -> anyhow::Result<()>;
 
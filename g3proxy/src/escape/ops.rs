@@ -45,6 +45,7 @@ use super::route_mapping::RouteMappingEscaper;
 use super::route_query::RouteQueryEscaper;
 use super::route_resolved::RouteResolvedEscaper;
 use super::route_select::RouteSelectEscaper;
+use super::route_sni::RouteSniEscaper;
 use super::route_upstream::RouteUpstreamEscaper;
 use super::trick_float::TrickFloatEscaper;
 
@@ -133,6 +134,23 @@ pub(crate) async fn reload(
     Ok(())
 }
 
+/// Rebuild the escaper in place with the loaded config, the config file will not be read again.
+///
+/// The runtime states, such as the fetched peers and the resolved next proxy addresses,
+/// will be dropped, and the servers and escapers depending on it will be notified.
+pub(crate) async fn rebuild(name: &NodeName) -> anyhow::Result<()> {
+    let _guard = ESCAPER_OPS_LOCK.lock().await;
+
+    if registry::get_config(name).is_none() {
+        return Err(anyhow!("no escaper with name {name} found"));
+    }
+
+    debug!("rebuilding escaper {name}");
+    reload_existed_unlocked(name, None).await?;
+    debug!("escaper {name} rebuild OK");
+    Ok(())
+}
+
 pub(crate) async fn update_dependency_to_resolver(resolver: &NodeName, status: &str) {
     let _guard = ESCAPER_OPS_LOCK.lock().await;
 
@@ -274,6 +292,7 @@ async fn spawn_new_unlocked(config: AnyEscaperConfig) -> anyhow::Result<()> {
         AnyEscaperConfig::RouteMapping(c) => RouteMappingEscaper::prepare_initial(c)?,
         AnyEscaperConfig::RouteQuery(c) => RouteQueryEscaper::prepare_initial(c).await?,
         AnyEscaperConfig::RouteSelect(c) => RouteSelectEscaper::prepare_initial(c)?,
+        AnyEscaperConfig::RouteSni(c) => RouteSniEscaper::prepare_initial(c)?,
         AnyEscaperConfig::RouteUpstream(c) => RouteUpstreamEscaper::prepare_initial(c)?,
         AnyEscaperConfig::RouteClient(c) => RouteClientEscaper::prepare_initial(c)?,
         AnyEscaperConfig::TrickFloat(c) => TrickFloatEscaper::prepare_initial(c)?,
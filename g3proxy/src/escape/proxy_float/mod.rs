@@ -213,7 +213,7 @@ impl Escaper for ProxyFloatEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tcp_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         let peer = self
             .select_peer(task_notes)
             .map_err(TcpConnectError::EscaperNotUsable)?;
@@ -230,7 +230,7 @@ impl Escaper for ProxyFloatEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tls_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         let peer = self
             .select_peer(task_notes)
             .map_err(TcpConnectError::EscaperNotUsable)?;
@@ -246,7 +246,7 @@ impl Escaper for ProxyFloatEscaper {
         task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
         self.stats.interface.add_udp_connect_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         let peer = self
             .select_peer(task_notes)
             .map_err(UdpConnectError::EscaperNotUsable)?;
@@ -262,7 +262,7 @@ impl Escaper for ProxyFloatEscaper {
         task_stats: ArcUdpRelayTaskRemoteStats,
     ) -> UdpRelaySetupResult {
         self.stats.interface.add_udp_relay_session_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         let peer = self
             .select_peer(task_notes)
             .map_err(UdpRelaySetupError::EscaperNotUsable)?;
@@ -330,7 +330,7 @@ impl EscaperInternal for ProxyFloatEscaper {
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.stats.interface.add_http_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         let peer = self
             .select_peer(task_notes)
             .map_err(TcpConnectError::EscaperNotUsable)?;
@@ -348,7 +348,7 @@ impl EscaperInternal for ProxyFloatEscaper {
         self.stats
             .interface
             .add_https_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         let peer = self
             .select_peer(task_notes)
             .map_err(TcpConnectError::EscaperNotUsable)?;
@@ -365,7 +365,7 @@ impl EscaperInternal for ProxyFloatEscaper {
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_over_http_request_attempted();
         self.stats.interface.add_ftp_control_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -379,7 +379,7 @@ impl EscaperInternal for ProxyFloatEscaper {
         _ftp_server: &UpstreamAddr,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_transfer_connection_attempted();
-        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        transfer_tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
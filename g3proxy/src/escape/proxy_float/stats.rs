@@ -24,8 +24,9 @@ use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::stats::{StatId, TcpIoSnapshot, UdpIoSnapshot};
 
 use crate::escape::{
-    EscaperInterfaceStats, EscaperInternalStats, EscaperStats, EscaperTcpConnectSnapshot,
-    EscaperTcpStats, EscaperTlsSnapshot, EscaperTlsStats, EscaperUdpStats,
+    EscaperInterfaceStats, EscaperInternalStats, EscaperKeepaliveStats, EscaperStats,
+    EscaperTcpConnectSnapshot, EscaperTcpStats, EscaperTlsSnapshot, EscaperTlsStats,
+    EscaperUdpStats,
 };
 use crate::module::http_forward::HttpForwardTaskRemoteStats;
 use crate::module::udp_connect::UdpConnectTaskRemoteStats;
@@ -69,6 +70,11 @@ impl EscaperInternalStats for ProxyFloatEscaperStats {
     fn add_https_forward_request_attempted(&self) {
         self.interface.add_https_forward_request_attempted();
     }
+
+    #[inline]
+    fn http_forward_keepalive(&self) -> &Arc<EscaperKeepaliveStats> {
+        &self.interface.http_forward_keepalive
+    }
 }
 
 impl EscaperStats for ProxyFloatEscaperStats {
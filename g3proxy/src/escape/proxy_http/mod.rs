@@ -178,7 +178,7 @@ impl Escaper for ProxyHttpEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tcp_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.http_connect_new_tcp_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -192,7 +192,7 @@ impl Escaper for ProxyHttpEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tls_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.http_connect_new_tls_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -205,7 +205,7 @@ impl Escaper for ProxyHttpEscaper {
         _task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
         self.stats.interface.add_udp_connect_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         Err(UdpConnectError::MethodUnavailable)
     }
 
@@ -217,7 +217,7 @@ impl Escaper for ProxyHttpEscaper {
         _task_stats: ArcUdpRelayTaskRemoteStats,
     ) -> UdpRelaySetupResult {
         self.stats.interface.add_udp_relay_session_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         Err(UdpRelaySetupError::MethodUnavailable)
     }
 
@@ -280,7 +280,7 @@ impl EscaperInternal for ProxyHttpEscaper {
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.stats.interface.add_http_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.http_forward_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -295,7 +295,7 @@ impl EscaperInternal for ProxyHttpEscaper {
         self.stats
             .interface
             .add_https_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.https_forward_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -309,7 +309,7 @@ impl EscaperInternal for ProxyHttpEscaper {
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_over_http_request_attempted();
         self.stats.interface.add_ftp_control_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -323,7 +323,7 @@ impl EscaperInternal for ProxyHttpEscaper {
         _ftp_server: &UpstreamAddr,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_transfer_connection_attempted();
-        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        transfer_tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 }
@@ -345,9 +345,20 @@ impl ProxyHttpEscaper {
 
         if let Some(version) = self.config.use_proxy_protocol {
             let mut encoder = ProxyProtocolEncoder::new(version);
-            let bytes = encoder
-                .encode_tcp(task_notes.client_addr(), task_notes.server_addr())
-                .map_err(TcpConnectError::ProxyProtocolEncodeError)?;
+            let tlv_config = &self.config.proxy_protocol_tlv;
+            let bytes = if tlv_config.is_empty() {
+                encoder.encode_tcp(task_notes.client_addr(), task_notes.server_addr())
+            } else {
+                let unique_id = tlv_config
+                    .unique_id()
+                    .then(|| task_notes.proxy_protocol_unique_id());
+                encoder.encode_tcp_with_tlvs(
+                    task_notes.client_addr(),
+                    task_notes.server_addr(),
+                    tlv_config.tlvs(task_notes.proxy_protocol_tlvs(), unique_id.as_ref()),
+                )
+            }
+            .map_err(TcpConnectError::ProxyProtocolEncodeError)?;
             stream
                 .write_all(bytes) // no need to flush data
                 .await
@@ -187,7 +187,7 @@ impl Escaper for ProxyHttpsEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tcp_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.http_connect_new_tcp_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -201,7 +201,7 @@ impl Escaper for ProxyHttpsEscaper {
         _audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
         self.stats.interface.add_tls_connect_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.http_connect_new_tls_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -214,7 +214,7 @@ impl Escaper for ProxyHttpsEscaper {
         _task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
         self.stats.interface.add_udp_connect_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         Err(UdpConnectError::MethodUnavailable)
     }
 
@@ -226,7 +226,7 @@ impl Escaper for ProxyHttpsEscaper {
         _task_stats: ArcUdpRelayTaskRemoteStats,
     ) -> UdpRelaySetupResult {
         self.stats.interface.add_udp_relay_session_attempted();
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         Err(UdpRelaySetupError::MethodUnavailable)
     }
 
@@ -289,7 +289,7 @@ impl EscaperInternal for ProxyHttpsEscaper {
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.stats.interface.add_http_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.http_forward_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -304,7 +304,7 @@ impl EscaperInternal for ProxyHttpsEscaper {
         self.stats
             .interface
             .add_https_forward_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.https_forward_new_connection(task_conf, tcp_notes, task_notes, task_stats)
             .await
     }
@@ -318,7 +318,7 @@ impl EscaperInternal for ProxyHttpsEscaper {
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_over_http_request_attempted();
         self.stats.interface.add_ftp_control_connection_attempted();
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -332,7 +332,7 @@ impl EscaperInternal for ProxyHttpsEscaper {
         _ftp_server: &UpstreamAddr,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
         self.stats.interface.add_ftp_transfer_connection_attempted();
-        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        transfer_tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 }
@@ -24,8 +24,8 @@ use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::stats::{StatId, TcpIoSnapshot};
 
 use crate::escape::{
-    EscaperInterfaceStats, EscaperInternalStats, EscaperStats, EscaperTcpConnectSnapshot,
-    EscaperTcpStats, EscaperTlsSnapshot, EscaperTlsStats,
+    EscaperInterfaceStats, EscaperInternalStats, EscaperKeepaliveStats, EscaperStats,
+    EscaperTcpConnectSnapshot, EscaperTcpStats, EscaperTlsSnapshot, EscaperTlsStats,
 };
 use crate::module::http_forward::HttpForwardTaskRemoteStats;
 
@@ -65,6 +65,11 @@ impl EscaperInternalStats for ProxyHttpsEscaperStats {
     fn add_https_forward_request_attempted(&self) {
         self.interface.add_https_forward_request_attempted();
     }
+
+    #[inline]
+    fn http_forward_keepalive(&self) -> &Arc<EscaperKeepaliveStats> {
+        &self.interface.http_forward_keepalive
+    }
 }
 
 impl EscaperStats for ProxyHttpsEscaperStats {
@@ -138,6 +138,56 @@ impl ProxySocks5Escaper {
         .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "peer negotiation timeout"))?
     }
 
+    /// setup udp associate with remote proxy, and the udp packets will be sent over the control connection
+    /// return (ctl_stream, local_addr, peer_addr)
+    async fn socks5_udp_over_tcp_associate(
+        &self,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(LimitedStream<TcpStream>, SocketAddr, SocketAddr), io::Error> {
+        let tcp_task_conf = TcpConnectTaskConf {
+            upstream: &UpstreamAddr::empty(),
+        };
+        let mut ctl_stream = self
+            .tcp_new_connection(&tcp_task_conf, tcp_notes, task_notes)
+            .await
+            .map_err(io::Error::other)?;
+        let local_tcp_addr = tcp_notes
+            .local
+            .ok_or_else(|| io::Error::other("no local tcp address"))?;
+        let peer_tcp_addr = tcp_notes
+            .next
+            .ok_or_else(|| io::Error::other("no peer tcp address"))?;
+
+        let send_udp_ip = match local_tcp_addr.ip() {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        };
+        // the returned udp relay address is not used
+        let _ = v5::client::socks5_udp_associate(
+            &mut ctl_stream,
+            &self.config.auth_info,
+            SocketAddr::new(send_udp_ip, 0),
+        )
+        .await
+        .map_err(io::Error::other)?;
+
+        Ok((ctl_stream, local_tcp_addr, peer_tcp_addr))
+    }
+
+    pub(super) async fn timed_socks5_udp_over_tcp_associate(
+        &self,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+    ) -> Result<(LimitedStream<TcpStream>, SocketAddr, SocketAddr), io::Error> {
+        tokio::time::timeout(
+            self.config.peer_negotiation_timeout,
+            self.socks5_udp_over_tcp_associate(tcp_notes, task_notes),
+        )
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "peer negotiation timeout"))?
+    }
+
     pub(super) async fn socks5_new_tcp_connection(
         &self,
         task_conf: &TcpConnectTaskConf<'_>,
@@ -175,7 +225,10 @@ impl ProxySocks5Escaper {
             .map_err(|e| TcpConnectError::InternalTlsClientError(anyhow::Error::new(e)))?;
 
         match tokio::time::timeout(task_conf.handshake_timeout(), connector.connect()).await {
-            Ok(Ok(stream)) => Ok(stream),
+            Ok(Ok(stream)) => {
+                tcp_notes.set_tls_alpn(stream.ssl());
+                Ok(stream)
+            }
             Ok(Err(e)) => {
                 let e = anyhow::Error::new(e);
                 EscapeLogForTlsHandshake {
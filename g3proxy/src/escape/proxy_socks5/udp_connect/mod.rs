@@ -16,7 +16,8 @@
 
 use std::sync::Arc;
 
-use g3_io_ext::{LimitedUdpRecv, LimitedUdpSend};
+use g3_io_ext::{AsyncStream, LimitedUdpRecv, LimitedUdpSend};
+use g3_socks::v5::{UdpOverTcpRecv, UdpOverTcpSend};
 
 use super::ProxySocks5Escaper;
 use crate::module::tcp_connect::TcpConnectTaskNotes;
@@ -40,6 +41,12 @@ impl ProxySocks5Escaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
+        if self.config.udp_over_tcp {
+            return self
+                .udp_over_tcp_connect_to(task_conf, udp_notes, task_notes, task_stats)
+                .await;
+        }
+
         let mut tcp_notes = TcpConnectTaskNotes::default();
         let (ctl_stream, udp_socket, udp_local_addr, udp_peer_addr) = self
             .timed_socks5_udp_associate(task_conf.sock_buf, &mut tcp_notes, task_notes)
@@ -78,4 +85,47 @@ impl ProxySocks5Escaper {
 
         Ok((Box::new(recv), Box::new(send), self.escape_logger.clone()))
     }
+
+    async fn udp_over_tcp_connect_to(
+        &self,
+        task_conf: &UdpConnectTaskConf<'_>,
+        udp_notes: &mut UdpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcUdpConnectTaskRemoteStats,
+    ) -> UdpConnectResult {
+        let mut tcp_notes = TcpConnectTaskNotes::default();
+        let (ctl_stream, tcp_local_addr, tcp_peer_addr) = self
+            .timed_socks5_udp_over_tcp_associate(&mut tcp_notes, task_notes)
+            .await
+            .map_err(UdpConnectError::SetupSocketFailed)?;
+
+        udp_notes.local = Some(tcp_local_addr);
+        udp_notes.next = Some(tcp_peer_addr);
+
+        let mut wrapper_stats = UdpConnectRemoteWrapperStats::new(&self.stats, task_stats);
+        wrapper_stats.push_user_io_stats(self.fetch_user_upstream_io_stats(task_notes));
+        let wrapper_stats = Arc::new(wrapper_stats);
+
+        let (ctl_r, ctl_w) = ctl_stream.into_split();
+        let recv = LimitedUdpRecv::local_limited(
+            UdpOverTcpRecv::new(ctl_r, tcp_peer_addr),
+            self.config.general.udp_sock_speed_limit.shift_millis,
+            self.config.general.udp_sock_speed_limit.max_south_packets,
+            self.config.general.udp_sock_speed_limit.max_south_bytes,
+            wrapper_stats.clone(),
+        );
+        let send = LimitedUdpSend::local_limited(
+            UdpOverTcpSend::new(ctl_w),
+            self.config.general.udp_sock_speed_limit.shift_millis,
+            self.config.general.udp_sock_speed_limit.max_north_packets,
+            self.config.general.udp_sock_speed_limit.max_north_bytes,
+            wrapper_stats,
+        );
+
+        // the control connection is used for data transfer, so there is no extra one to check
+        let recv = ProxySocks5UdpConnectRemoteRecv::new(recv, tokio::io::empty(), false);
+        let send = ProxySocks5UdpConnectRemoteSend::new(send, task_conf.upstream);
+
+        Ok((Box::new(recv), Box::new(send), self.escape_logger.clone()))
+    }
 }
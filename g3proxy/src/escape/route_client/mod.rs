@@ -161,7 +161,7 @@ impl Escaper for RouteClientEscaper {
         task_stats: ArcTcpConnectionTaskRemoteStats,
         audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         let escaper = self.select_next(task_notes.client_ip());
         self.stats.add_request_passed();
         escaper
@@ -177,7 +177,7 @@ impl Escaper for RouteClientEscaper {
         task_stats: ArcTcpConnectionTaskRemoteStats,
         audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         let escaper = self.select_next(task_notes.client_ip());
         self.stats.add_request_passed();
         escaper
@@ -192,7 +192,7 @@ impl Escaper for RouteClientEscaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         let escaper = self.select_next(task_notes.client_ip());
         self.stats.add_request_passed();
         escaper
@@ -207,7 +207,7 @@ impl Escaper for RouteClientEscaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcUdpRelayTaskRemoteStats,
     ) -> UdpRelaySetupResult {
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         let escaper = self.select_next(task_notes.client_ip());
         self.stats.add_request_passed();
         escaper
@@ -282,7 +282,7 @@ impl EscaperInternal for RouteClientEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -293,7 +293,7 @@ impl EscaperInternal for RouteClientEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -304,7 +304,7 @@ impl EscaperInternal for RouteClientEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcFtpTaskRemoteControlStats,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -317,7 +317,7 @@ impl EscaperInternal for RouteClientEscaper {
         _task_stats: ArcFtpTaskRemoteTransferStats,
         _ftp_server: &UpstreamAddr,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
-        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        transfer_tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 }
@@ -125,7 +125,7 @@ impl Escaper for RouteFailoverEscaper {
         task_stats: ArcTcpConnectionTaskRemoteStats,
         audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.tcp_setup_connection_with_failover(
             task_conf, tcp_notes, task_notes, task_stats, audit_ctx,
         )
@@ -140,7 +140,7 @@ impl Escaper for RouteFailoverEscaper {
         task_stats: ArcTcpConnectionTaskRemoteStats,
         audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         self.tls_setup_connection_with_failover(
             task_conf, tcp_notes, task_notes, task_stats, audit_ctx,
         )
@@ -154,7 +154,7 @@ impl Escaper for RouteFailoverEscaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         self.udp_setup_connection_with_failover(task_conf, udp_notes, task_notes, task_stats)
             .await
     }
@@ -166,7 +166,7 @@ impl Escaper for RouteFailoverEscaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcUdpRelayTaskRemoteStats,
     ) -> UdpRelaySetupResult {
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         self.udp_setup_relay_with_failover(task_conf, udp_notes, task_notes, task_stats)
             .await
     }
@@ -234,7 +234,7 @@ impl EscaperInternal for RouteFailoverEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -245,7 +245,7 @@ impl EscaperInternal for RouteFailoverEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -256,7 +256,7 @@ impl EscaperInternal for RouteFailoverEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcFtpTaskRemoteControlStats,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -269,7 +269,7 @@ impl EscaperInternal for RouteFailoverEscaper {
         _task_stats: ArcFtpTaskRemoteTransferStats,
         _ftp_server: &UpstreamAddr,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
-        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        transfer_tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 }
@@ -83,7 +83,7 @@ impl RouteFailoverEscaper {
         match tokio::time::timeout(self.config.fallback_delay, &mut primary_task).await {
             Ok(Ok(ctx)) => {
                 self.stats.add_request_passed();
-                udp_notes.clone_from(&ctx.udp_notes);
+                udp_notes.merge_child(&ctx.udp_notes);
                 return ctx.setup_result;
             }
             Ok(Err(_)) => {
@@ -112,12 +112,12 @@ impl RouteFailoverEscaper {
         match futures_util::future::select_ok([primary_task, standby_task]).await {
             Ok((ctx, _left)) => {
                 self.stats.add_request_passed();
-                udp_notes.clone_from(&ctx.udp_notes);
+                udp_notes.merge_child(&ctx.udp_notes);
                 ctx.setup_result
             }
             Err(ctx) => {
                 self.stats.add_request_failed();
-                udp_notes.clone_from(&ctx.udp_notes);
+                udp_notes.merge_child(&ctx.udp_notes);
                 ctx.setup_result
             }
         }
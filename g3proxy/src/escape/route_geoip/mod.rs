@@ -266,7 +266,7 @@ impl Escaper for RouteGeoIpEscaper {
         task_stats: ArcTcpConnectionTaskRemoteStats,
         audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         match self.select_next(task_conf.upstream).await {
             Ok(escaper) => {
                 self.stats.add_request_passed();
@@ -289,7 +289,7 @@ impl Escaper for RouteGeoIpEscaper {
         task_stats: ArcTcpConnectionTaskRemoteStats,
         audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         match self.select_next(task_conf.tcp.upstream).await {
             Ok(escaper) => {
                 self.stats.add_request_passed();
@@ -311,7 +311,7 @@ impl Escaper for RouteGeoIpEscaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         match self.select_next(task_conf.upstream).await {
             Ok(escaper) => {
                 self.stats.add_request_passed();
@@ -333,7 +333,7 @@ impl Escaper for RouteGeoIpEscaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcUdpRelayTaskRemoteStats,
     ) -> UdpRelaySetupResult {
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         match self.select_next(task_conf.initial_peer).await {
             Ok(escaper) => {
                 self.stats.add_request_passed();
@@ -429,7 +429,7 @@ impl EscaperInternal for RouteGeoIpEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -440,7 +440,7 @@ impl EscaperInternal for RouteGeoIpEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -451,7 +451,7 @@ impl EscaperInternal for RouteGeoIpEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcFtpTaskRemoteControlStats,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -464,7 +464,7 @@ impl EscaperInternal for RouteGeoIpEscaper {
         _task_stats: ArcFtpTaskRemoteTransferStats,
         _ftp_server: &UpstreamAddr,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
-        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        transfer_tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 }
@@ -0,0 +1,127 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, LazyLock, RwLock};
+
+use ahash::AHashMap;
+use slog::{Record, Serializer, Value};
+
+use g3_types::metrics::NodeName;
+
+type RouteEdgeKey = (NodeName, NodeName);
+
+static ROUTE_EDGE_STATS: LazyLock<RwLock<AHashMap<RouteEdgeKey, Arc<AtomicU64>>>> =
+    LazyLock::new(|| RwLock::new(AHashMap::new()));
+
+fn add_edge_selected(escaper: &NodeName, next: &NodeName) {
+    let key = (escaper.clone(), next.clone());
+    if let Some(c) = ROUTE_EDGE_STATS.read().unwrap().get(&key) {
+        c.fetch_add(1, Ordering::Relaxed);
+        return;
+    }
+    let mut map = ROUTE_EDGE_STATS.write().unwrap();
+    map.entry(key)
+        .or_insert_with(|| Arc::new(AtomicU64::new(0)))
+        .fetch_add(1, Ordering::Relaxed);
+}
+
+/// Visit the selected count of all route edges.
+///
+/// Edges whose source escaper is no longer present will be removed after visited.
+pub(crate) fn foreach_route_edge<F>(mut f: F)
+where
+    F: FnMut(&NodeName, &NodeName, u64),
+{
+    let names = super::get_names();
+    let mut map = ROUTE_EDGE_STATS.write().unwrap();
+    map.retain(|(escaper, next), c| {
+        f(escaper, next, c.load(Ordering::Relaxed));
+        names.contains(escaper)
+    });
+}
+
+/// The escapers that have been passed through before reaching the final escaper
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EscaperRoutePath(Vec<NodeName>);
+
+impl EscaperRoutePath {
+    pub(crate) fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Switch the current escaper to `next`, and record the route edge if changed
+    pub(crate) fn record_hop(&mut self, current: &mut NodeName, next: &NodeName) {
+        if current.is_empty() || current == next {
+            current.clone_from(next);
+            return;
+        }
+        add_edge_selected(current, next);
+        self.0.push(std::mem::replace(current, next.clone()));
+    }
+
+    /// Append the route path of a detached child task, which is started by the `current` escaper
+    pub(crate) fn append_child(
+        &mut self,
+        current: &NodeName,
+        child_path: &EscaperRoutePath,
+        child_escaper: &NodeName,
+    ) {
+        let child_first = child_path.0.first().unwrap_or(child_escaper);
+        if !current.is_empty() && !child_first.is_empty() && current != child_first {
+            add_edge_selected(current, child_first);
+            self.0.push(current.clone());
+        }
+        self.0.extend_from_slice(&child_path.0);
+    }
+
+    pub(crate) fn log_value<'a>(&'a self, last: &'a NodeName) -> Option<LtRoutePath<'a>> {
+        if self.0.is_empty() {
+            None
+        } else {
+            Some(LtRoutePath {
+                path: &self.0,
+                last,
+            })
+        }
+    }
+}
+
+pub(crate) struct LtRoutePath<'a> {
+    path: &'a [NodeName],
+    last: &'a NodeName,
+}
+
+impl fmt::Display for LtRoutePath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for name in self.path {
+            write!(f, "{name} -> ")?;
+        }
+        write!(f, "{}", self.last)
+    }
+}
+
+impl Value for LtRoutePath<'_> {
+    fn serialize(
+        &self,
+        _record: &Record,
+        key: slog::Key,
+        serializer: &mut dyn Serializer,
+    ) -> slog::Result {
+        serializer.emit_arguments(key, &format_args!("{self}"))
+    }
+}
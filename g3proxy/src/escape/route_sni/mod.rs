@@ -0,0 +1,309 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+
+use g3_daemon::stat::remote::ArcTcpConnectionTaskRemoteStats;
+use g3_types::metrics::NodeName;
+use g3_types::net::{Host, UpstreamAddr};
+
+use super::{ArcEscaper, Escaper, EscaperInternal, RouteEscaperStats};
+use crate::audit::AuditContext;
+use crate::config::escaper::route_sni::RouteSniEscaperConfig;
+use crate::config::escaper::{AnyEscaperConfig, EscaperConfig};
+use crate::module::ftp_over_http::{
+    ArcFtpTaskRemoteControlStats, ArcFtpTaskRemoteTransferStats, BoxFtpConnectContext,
+    BoxFtpRemoteConnection,
+};
+use crate::module::http_forward::{
+    ArcHttpForwardTaskRemoteStats, BoxHttpForwardConnection, BoxHttpForwardContext,
+    RouteHttpForwardContext,
+};
+use crate::module::tcp_connect::{
+    TcpConnectError, TcpConnectResult, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
+};
+use crate::module::udp_connect::{
+    ArcUdpConnectTaskRemoteStats, UdpConnectResult, UdpConnectTaskConf, UdpConnectTaskNotes,
+};
+use crate::module::udp_relay::{
+    ArcUdpRelayTaskRemoteStats, UdpRelaySetupResult, UdpRelayTaskConf, UdpRelayTaskNotes,
+};
+use crate::serve::ServerTaskNotes;
+
+mod table;
+use table::ServerNameTable;
+
+pub(super) struct RouteSniEscaper {
+    config: RouteSniEscaperConfig,
+    stats: Arc<RouteEscaperStats>,
+    next_table: BTreeMap<NodeName, ArcEscaper>,
+    server_name_table: ServerNameTable<ArcEscaper>,
+}
+
+impl RouteSniEscaper {
+    fn new_obj(
+        config: RouteSniEscaperConfig,
+        stats: Arc<RouteEscaperStats>,
+    ) -> anyhow::Result<ArcEscaper> {
+        let mut next_table = BTreeMap::new();
+        if let Some(escapers) = config.dependent_escaper() {
+            for escaper in escapers {
+                let next = super::registry::get_or_insert_default(&escaper);
+                next_table.insert(escaper, next);
+            }
+        }
+
+        let server_name_table = ServerNameTable::build(&config, &next_table);
+
+        let escaper = RouteSniEscaper {
+            config,
+            stats,
+            next_table,
+            server_name_table,
+        };
+
+        Ok(Arc::new(escaper))
+    }
+
+    pub(super) fn prepare_initial(config: RouteSniEscaperConfig) -> anyhow::Result<ArcEscaper> {
+        let stats = Arc::new(RouteEscaperStats::new(config.name()));
+        RouteSniEscaper::new_obj(config, stats)
+    }
+
+    fn prepare_reload(
+        config: AnyEscaperConfig,
+        stats: Arc<RouteEscaperStats>,
+    ) -> anyhow::Result<ArcEscaper> {
+        if let AnyEscaperConfig::RouteSni(config) = config {
+            RouteSniEscaper::new_obj(config, stats)
+        } else {
+            Err(anyhow!("invalid escaper config type"))
+        }
+    }
+
+    /// Select the next escaper by the server name in the following order:
+    ///  - the tls server name or http host detected by the server
+    ///  - the tls name used for the upstream tls handshake
+    ///  - the host of the upstream address
+    fn select_next(
+        &self,
+        task_notes: &ServerTaskNotes,
+        tls_name: Option<&Host>,
+        ups: &UpstreamAddr,
+    ) -> ArcEscaper {
+        let host = task_notes
+            .server_name
+            .as_ref()
+            .or(tls_name)
+            .unwrap_or_else(|| ups.host());
+        match host {
+            Host::Domain(domain) => Arc::clone(self.server_name_table.select(domain)),
+            Host::Ip(_) => Arc::clone(self.server_name_table.default_next()),
+        }
+    }
+}
+
+#[async_trait]
+impl Escaper for RouteSniEscaper {
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    fn escaper_type(&self) -> &str {
+        self.config.escaper_type()
+    }
+
+    fn ref_route_stats(&self) -> Option<&Arc<RouteEscaperStats>> {
+        Some(&self.stats)
+    }
+
+    async fn publish(&self, _data: String) -> anyhow::Result<()> {
+        Err(anyhow!("not implemented"))
+    }
+
+    async fn tcp_setup_connection(
+        &self,
+        task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+        audit_ctx: &mut AuditContext,
+    ) -> TcpConnectResult {
+        tcp_notes.set_escaper(&self.config.name);
+        let escaper = self.select_next(task_notes, None, task_conf.upstream);
+        self.stats.add_request_passed();
+        escaper
+            .tcp_setup_connection(task_conf, tcp_notes, task_notes, task_stats, audit_ctx)
+            .await
+    }
+
+    async fn tls_setup_connection(
+        &self,
+        task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcTcpConnectionTaskRemoteStats,
+        audit_ctx: &mut AuditContext,
+    ) -> TcpConnectResult {
+        tcp_notes.set_escaper(&self.config.name);
+        let escaper =
+            self.select_next(task_notes, Some(task_conf.tls_name), task_conf.tcp.upstream);
+        self.stats.add_request_passed();
+        escaper
+            .tls_setup_connection(task_conf, tcp_notes, task_notes, task_stats, audit_ctx)
+            .await
+    }
+
+    async fn udp_setup_connection(
+        &self,
+        task_conf: &UdpConnectTaskConf<'_>,
+        udp_notes: &mut UdpConnectTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcUdpConnectTaskRemoteStats,
+    ) -> UdpConnectResult {
+        udp_notes.set_escaper(&self.config.name);
+        let escaper = self.select_next(task_notes, None, task_conf.upstream);
+        self.stats.add_request_passed();
+        escaper
+            .udp_setup_connection(task_conf, udp_notes, task_notes, task_stats)
+            .await
+    }
+
+    async fn udp_setup_relay(
+        &self,
+        task_conf: &UdpRelayTaskConf<'_>,
+        udp_notes: &mut UdpRelayTaskNotes,
+        task_notes: &ServerTaskNotes,
+        task_stats: ArcUdpRelayTaskRemoteStats,
+    ) -> UdpRelaySetupResult {
+        udp_notes.set_escaper(&self.config.name);
+        let escaper = self.select_next(task_notes, None, task_conf.initial_peer);
+        self.stats.add_request_passed();
+        escaper
+            .udp_setup_relay(task_conf, udp_notes, task_notes, task_stats)
+            .await
+    }
+
+    fn new_http_forward_context(&self, escaper: ArcEscaper) -> BoxHttpForwardContext {
+        let ctx = RouteHttpForwardContext::new(escaper);
+        Box::new(ctx)
+    }
+
+    async fn new_ftp_connect_context(
+        &self,
+        _escaper: ArcEscaper,
+        task_conf: &TcpConnectTaskConf<'_>,
+        task_notes: &ServerTaskNotes,
+    ) -> BoxFtpConnectContext {
+        let escaper = self.select_next(task_notes, None, task_conf.upstream);
+        self.stats.add_request_passed();
+        escaper
+            .new_ftp_connect_context(Arc::clone(&escaper), task_conf, task_notes)
+            .await
+    }
+}
+
+#[async_trait]
+impl EscaperInternal for RouteSniEscaper {
+    fn _resolver(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn _dependent_escaper(&self) -> Option<BTreeSet<NodeName>> {
+        let mut set = BTreeSet::new();
+        for escaper in self.next_table.keys() {
+            set.insert(escaper.clone());
+        }
+        Some(set)
+    }
+
+    fn _clone_config(&self) -> AnyEscaperConfig {
+        AnyEscaperConfig::RouteSni(self.config.clone())
+    }
+
+    fn _update_config_in_place(
+        &self,
+        _flags: u64,
+        _config: AnyEscaperConfig,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    async fn _lock_safe_reload(&self, config: AnyEscaperConfig) -> anyhow::Result<ArcEscaper> {
+        let stats = Arc::clone(&self.stats);
+        RouteSniEscaper::prepare_reload(config, stats)
+    }
+
+    async fn _check_out_next_escaper(
+        &self,
+        task_notes: &ServerTaskNotes,
+        upstream: &UpstreamAddr,
+    ) -> Option<ArcEscaper> {
+        let escaper = self.select_next(task_notes, None, upstream);
+        self.stats.add_request_passed();
+        Some(escaper)
+    }
+
+    async fn _new_http_forward_connection(
+        &self,
+        _task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        _task_notes: &ServerTaskNotes,
+        _task_stats: ArcHttpForwardTaskRemoteStats,
+    ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
+        tcp_notes.set_escaper(&self.config.name);
+        Err(TcpConnectError::MethodUnavailable)
+    }
+
+    async fn _new_https_forward_connection(
+        &self,
+        _task_conf: &TlsConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        _task_notes: &ServerTaskNotes,
+        _task_stats: ArcHttpForwardTaskRemoteStats,
+    ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
+        tcp_notes.set_escaper(&self.config.name);
+        Err(TcpConnectError::MethodUnavailable)
+    }
+
+    async fn _new_ftp_control_connection(
+        &self,
+        _task_conf: &TcpConnectTaskConf<'_>,
+        tcp_notes: &mut TcpConnectTaskNotes,
+        _task_notes: &ServerTaskNotes,
+        _task_stats: ArcFtpTaskRemoteControlStats,
+    ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
+        tcp_notes.set_escaper(&self.config.name);
+        Err(TcpConnectError::MethodUnavailable)
+    }
+
+    async fn _new_ftp_transfer_connection(
+        &self,
+        _task_conf: &TcpConnectTaskConf<'_>,
+        transfer_tcp_notes: &mut TcpConnectTaskNotes,
+        _control_tcp_notes: &TcpConnectTaskNotes,
+        _task_notes: &ServerTaskNotes,
+        _task_stats: ArcFtpTaskRemoteTransferStats,
+        _ftp_server: &UpstreamAddr,
+    ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
+        transfer_tcp_notes.set_escaper(&self.config.name);
+        Err(TcpConnectError::MethodUnavailable)
+    }
+}
@@ -0,0 +1,179 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use ahash::AHashMap;
+use radix_trie::Trie;
+
+use g3_types::metrics::NodeName;
+use g3_types::route::HostPatternMatch;
+
+use crate::config::escaper::route_sni::RouteSniEscaperConfig;
+
+pub(super) struct ServerNameTable<T> {
+    exact_match_domain: AHashMap<Arc<str>, T>,
+    do_suffix_match: bool,
+    suffix_match_domain: Trie<String, T>,
+    regex_match_domain: HostPatternMatch<T>,
+    default_next: T,
+}
+
+impl<T: Clone> ServerNameTable<T> {
+    /// Build the table, all next escapers referenced by the config should be present in `next_table`
+    pub(super) fn build(
+        config: &RouteSniEscaperConfig,
+        next_table: &BTreeMap<NodeName, T>,
+    ) -> Self {
+        let default_next = next_table.get(&config.default_next).unwrap().clone();
+
+        let mut exact_match_domain = AHashMap::new();
+        for (escaper, domains) in &config.exact_match_domain {
+            let next = next_table.get(escaper).unwrap();
+            for domain in domains {
+                exact_match_domain.insert(domain.clone(), next.clone());
+            }
+        }
+
+        let do_suffix_match = !config.suffix_match_domain.is_empty();
+        let mut suffix_match_domain = Trie::new();
+        for (escaper, domains) in &config.suffix_match_domain {
+            let next = next_table.get(escaper).unwrap();
+            for domain in domains {
+                let reversed = g3_types::resolve::reverse_idna_domain(domain);
+                suffix_match_domain.insert(reversed, next.clone());
+            }
+        }
+
+        let mut regex_match_domain = HostPatternMatch::default();
+        for (escaper, patterns) in &config.regex_match_domain {
+            let next = next_table.get(escaper).unwrap();
+            for pattern in patterns {
+                regex_match_domain.add(pattern.clone(), next.clone());
+            }
+        }
+
+        ServerNameTable {
+            exact_match_domain,
+            do_suffix_match,
+            suffix_match_domain,
+            regex_match_domain,
+            default_next,
+        }
+    }
+
+    /// Select in the order of exact match, suffix match, regex match and then the default one
+    pub(super) fn select(&self, domain: &str) -> &T {
+        if !self.exact_match_domain.is_empty() {
+            if let Some(next) = self.exact_match_domain.get(domain) {
+                return next;
+            }
+        }
+
+        if self.do_suffix_match {
+            let key = g3_types::resolve::reverse_idna_domain(domain);
+            if let Some(next) = self.suffix_match_domain.get_ancestor_value(&key) {
+                return next;
+            }
+        }
+
+        if !self.regex_match_domain.is_empty() {
+            if let Some(next) = self.regex_match_domain.get(domain) {
+                return next;
+            }
+        }
+
+        &self.default_next
+    }
+
+    #[inline]
+    pub(super) fn default_next(&self) -> &T {
+        &self.default_next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::str::FromStr;
+
+    use g3_types::route::HostPattern;
+
+    fn name(s: &str) -> NodeName {
+        NodeName::from_str(s).unwrap()
+    }
+
+    fn build_table() -> ServerNameTable<&'static str> {
+        let mut config = RouteSniEscaperConfig::new(None);
+        config.default_next = name("default");
+
+        let mut exact = BTreeSet::new();
+        exact.insert(Arc::from("www.example.com"));
+        config.exact_match_domain.insert(name("exact"), exact);
+
+        let mut suffix = BTreeSet::new();
+        suffix.insert("example.com".to_string());
+        suffix.insert("example.net".to_string());
+        config.suffix_match_domain.insert(name("suffix"), suffix);
+
+        config.regex_match_domain.push((
+            name("regex"),
+            vec![
+                HostPattern::new_glob("*.example.net").unwrap(),
+                HostPattern::new_regex(r"api[0-9]+\.example\.org").unwrap(),
+            ],
+        ));
+
+        let mut next_table = BTreeMap::new();
+        for s in ["default", "exact", "suffix", "regex"] {
+            next_table.insert(name(s), s);
+        }
+        ServerNameTable::build(&config, &next_table)
+    }
+
+    #[test]
+    fn exact_before_suffix() {
+        let table = build_table();
+        assert_eq!(*table.select("www.example.com"), "exact");
+        assert_eq!(*table.select("example.com"), "suffix");
+        assert_eq!(*table.select("a.www.example.com"), "suffix");
+    }
+
+    #[test]
+    fn suffix_before_regex() {
+        let table = build_table();
+        assert_eq!(*table.select("www.example.net"), "suffix");
+        assert_eq!(*table.select("example.net"), "suffix");
+    }
+
+    #[test]
+    fn regex() {
+        let table = build_table();
+        assert_eq!(*table.select("api01.example.org"), "regex");
+        assert_eq!(*table.select("API01.Example.org"), "regex");
+        assert_eq!(*table.select("www.example.org"), "default");
+    }
+
+    #[test]
+    fn default_next() {
+        let table = build_table();
+        assert_eq!(*table.select("www.example.cn"), "default");
+        assert_eq!(*table.select("notexample.com"), "default");
+        assert_eq!(*table.default_next(), "default");
+    }
+}
@@ -27,6 +27,7 @@ use radix_trie::Trie;
 use g3_daemon::stat::remote::ArcTcpConnectionTaskRemoteStats;
 use g3_types::metrics::NodeName;
 use g3_types::net::{Host, UpstreamAddr};
+use g3_types::route::HostPatternMatch;
 
 use super::{ArcEscaper, Escaper, EscaperInternal, RouteEscaperStats};
 use crate::audit::AuditContext;
@@ -62,6 +63,7 @@ pub(super) struct RouteUpstreamEscaper {
     child_match_domain: Trie<String, ArcEscaper>,
     do_radix_match: bool,
     radix_match_domain: Trie<String, ArcEscaper>,
+    pattern_match_domain: HostPatternMatch<ArcEscaper>,
     default_next: ArcEscaper,
 }
 
@@ -123,6 +125,14 @@ impl RouteUpstreamEscaper {
             }
         }
 
+        let mut pattern_match_domain = HostPatternMatch::default();
+        for (escaper, patterns) in &config.pattern_match_domain {
+            for pattern in patterns {
+                let next = &next_table.get(escaper).unwrap();
+                pattern_match_domain.add(pattern.clone(), Arc::clone(next));
+            }
+        }
+
         let escaper = RouteUpstreamEscaper {
             config,
             stats,
@@ -134,6 +144,7 @@ impl RouteUpstreamEscaper {
             child_match_domain,
             do_radix_match,
             radix_match_domain,
+            pattern_match_domain,
             default_next,
         };
 
@@ -195,6 +206,12 @@ impl RouteUpstreamEscaper {
             }
         }
 
+        if !self.pattern_match_domain.is_empty() {
+            if let Some(escaper) = self.pattern_match_domain.get(host) {
+                return Arc::clone(escaper);
+            }
+        }
+
         Arc::clone(&self.default_next)
     }
 
@@ -232,7 +249,7 @@ impl Escaper for RouteUpstreamEscaper {
         task_stats: ArcTcpConnectionTaskRemoteStats,
         audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         let escaper = self.select_next(task_conf.upstream);
         self.stats.add_request_passed();
         escaper
@@ -248,7 +265,7 @@ impl Escaper for RouteUpstreamEscaper {
         task_stats: ArcTcpConnectionTaskRemoteStats,
         audit_ctx: &mut AuditContext,
     ) -> TcpConnectResult {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         let escaper = self.select_next(task_conf.tcp.upstream);
         self.stats.add_request_passed();
         escaper
@@ -263,7 +280,7 @@ impl Escaper for RouteUpstreamEscaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcUdpConnectTaskRemoteStats,
     ) -> UdpConnectResult {
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         let escaper = self.select_next(task_conf.upstream);
         self.stats.add_request_passed();
         escaper
@@ -278,7 +295,7 @@ impl Escaper for RouteUpstreamEscaper {
         task_notes: &ServerTaskNotes,
         task_stats: ArcUdpRelayTaskRemoteStats,
     ) -> UdpRelaySetupResult {
-        udp_notes.escaper.clone_from(&self.config.name);
+        udp_notes.set_escaper(&self.config.name);
         let escaper = self.select_next(task_conf.initial_peer);
         self.stats.add_request_passed();
         escaper
@@ -353,7 +370,7 @@ impl EscaperInternal for RouteUpstreamEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -364,7 +381,7 @@ impl EscaperInternal for RouteUpstreamEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -375,7 +392,7 @@ impl EscaperInternal for RouteUpstreamEscaper {
         _task_notes: &ServerTaskNotes,
         _task_stats: ArcFtpTaskRemoteControlStats,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
-        tcp_notes.escaper.clone_from(&self.config.name);
+        tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 
@@ -388,7 +405,7 @@ impl EscaperInternal for RouteUpstreamEscaper {
         _task_stats: ArcFtpTaskRemoteTransferStats,
         _ftp_server: &UpstreamAddr,
     ) -> Result<BoxFtpRemoteConnection, TcpConnectError> {
-        transfer_tcp_notes.escaper.clone_from(&self.config.name);
+        transfer_tcp_notes.set_escaper(&self.config.name);
         Err(TcpConnectError::MethodUnavailable)
     }
 }
@@ -22,9 +22,12 @@ use arc_swap::ArcSwapOption;
 use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::stats::{StatId, TcpIoSnapshot, TcpIoStats, UdpIoSnapshot, UdpIoStats};
 
+use super::EscaperBandwidthSnapshot;
+
 pub(crate) trait EscaperInternalStats {
     fn add_http_forward_request_attempted(&self);
     fn add_https_forward_request_attempted(&self);
+    fn http_forward_keepalive(&self) -> &Arc<EscaperKeepaliveStats>;
 }
 
 pub(crate) trait EscaperStats: EscaperInternalStats {
@@ -59,6 +62,14 @@ pub(crate) trait EscaperStats: EscaperInternalStats {
     fn forbidden_snapshot(&self) -> Option<EscaperForbiddenSnapshot> {
         None
     }
+
+    fn keepalive_snapshot(&self) -> Option<EscaperKeepaliveSnapshot> {
+        Some(self.http_forward_keepalive().snapshot())
+    }
+
+    fn bandwidth_snapshot(&self) -> Option<EscaperBandwidthSnapshot> {
+        None
+    }
 }
 
 pub(crate) type ArcEscaperInternalStats = Arc<dyn EscaperInternalStats + Send + Sync>;
@@ -86,6 +97,50 @@ impl EscaperForbiddenStats {
     }
 }
 
+#[derive(Default)]
+pub(crate) struct EscaperKeepaliveSnapshot {
+    pub(crate) reuse: u64,
+    pub(crate) reap_eof: u64,
+    pub(crate) reap_idle: u64,
+    pub(crate) reap_lifetime: u64,
+}
+
+/// Stats for the pooled http forward keepalive connections
+#[derive(Default)]
+pub(crate) struct EscaperKeepaliveStats {
+    reuse: AtomicU64,
+    reap_eof: AtomicU64,
+    reap_idle: AtomicU64,
+    reap_lifetime: AtomicU64,
+}
+
+impl EscaperKeepaliveStats {
+    pub(crate) fn add_reuse(&self) {
+        self.reuse.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_reap_eof(&self) {
+        self.reap_eof.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_reap_idle(&self) {
+        self.reap_idle.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_reap_lifetime(&self) {
+        self.reap_lifetime.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> EscaperKeepaliveSnapshot {
+        EscaperKeepaliveSnapshot {
+            reuse: self.reuse.load(Ordering::Relaxed),
+            reap_eof: self.reap_eof.load(Ordering::Relaxed),
+            reap_idle: self.reap_idle.load(Ordering::Relaxed),
+            reap_lifetime: self.reap_lifetime.load(Ordering::Relaxed),
+        }
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct EscaperInterfaceStats {
     tcp_connect_attempted: AtomicU64,
@@ -98,6 +153,7 @@ pub(crate) struct EscaperInterfaceStats {
     // for http forward keepalive
     http_forward_connection_attempted: AtomicU64,
     https_forward_connection_attempted: AtomicU64,
+    pub(crate) http_forward_keepalive: Arc<EscaperKeepaliveStats>,
     // for ftp connections
     ftp_control_connection_attempted: AtomicU64,
     ftp_transfer_connection_attempted: AtomicU64,
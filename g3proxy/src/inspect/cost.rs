@@ -0,0 +1,57 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use g3_types::ext::DurationExt;
+
+use crate::audit::AuditorStatsRecorder;
+
+/// Accumulated inspection cost of a single client connection,
+/// which will be recorded to the auditor stats when the connection ends
+pub(super) struct InspectionCost {
+    recorder: Arc<AuditorStatsRecorder>,
+    nanos: AtomicU64,
+    bytes: AtomicU64,
+}
+
+impl InspectionCost {
+    pub(super) fn new(recorder: Arc<AuditorStatsRecorder>) -> Self {
+        InspectionCost {
+            recorder,
+            nanos: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+        }
+    }
+
+    pub(super) fn add(&self, dur: Duration, bytes: usize) {
+        self.nanos.fetch_add(dur.as_nanos_u64(), Ordering::Relaxed);
+        self.bytes.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+impl Drop for InspectionCost {
+    fn drop(&mut self) {
+        let nanos = *self.nanos.get_mut();
+        let bytes = *self.bytes.get_mut();
+        if nanos > 0 || bytes > 0 {
+            self.recorder
+                .record_inspect_cost(Duration::from_nanos(nanos), bytes);
+        }
+    }
+}
@@ -0,0 +1,211 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use bytes::BytesMut;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use g3_dpi::parser::dns::{record_type, shannon_entropy, DnsQuestion, DnsTcpQueryObserver};
+use g3_io_ext::OnceBufReader;
+
+use super::{BoxAsyncRead, BoxAsyncWrite, StreamInspectContext};
+use crate::audit::AuditHandle;
+use crate::config::server::ServerConfig;
+use crate::log::inspect::dns::{DnsInspectLog, DnsQueryScore};
+use crate::serve::{ServerTaskError, ServerTaskForbiddenError, ServerTaskResult};
+
+const SCORE_HIGH_ENTROPY: u8 = 40;
+const SCORE_LONG_SUBDOMAIN: u8 = 20;
+const SCORE_HIGH_QUERY_RATE: u8 = 20;
+const SCORE_HIGH_TXT_VOLUME: u8 = 20;
+
+// the entropy of short labels is not meaningful
+const ENTROPY_CHECK_MIN_LENGTH: usize = 16;
+
+struct DnsInspectIo {
+    clt_r: BoxAsyncRead,
+    clt_r_buf: BytesMut,
+    clt_w: BoxAsyncWrite,
+    ups_r: BoxAsyncRead,
+    ups_w: BoxAsyncWrite,
+}
+
+pub(crate) struct DnsInspectObject<SC: ServerConfig> {
+    io: Option<DnsInspectIo>,
+    ctx: StreamInspectContext<SC>,
+}
+
+impl<SC> DnsInspectObject<SC>
+where
+    SC: ServerConfig + Send + Sync + 'static,
+{
+    pub(crate) fn new(ctx: StreamInspectContext<SC>) -> Self {
+        DnsInspectObject { io: None, ctx }
+    }
+
+    pub(crate) fn set_io(
+        &mut self,
+        clt_r: BoxAsyncRead,
+        clt_r_buf: BytesMut,
+        clt_w: BoxAsyncWrite,
+        ups_r: BoxAsyncRead,
+        ups_w: BoxAsyncWrite,
+    ) {
+        self.io = Some(DnsInspectIo {
+            clt_r,
+            clt_r_buf,
+            clt_w,
+            ups_r,
+            ups_w,
+        });
+    }
+
+    pub(crate) async fn intercept(mut self) -> ServerTaskResult<()> {
+        let DnsInspectIo {
+            clt_r,
+            clt_r_buf,
+            clt_w,
+            ups_r,
+            ups_w,
+        } = self.io.take().unwrap();
+
+        let blocked = Arc::new(AtomicBool::new(false));
+        let clt_r = DnsObserveReader {
+            inner: OnceBufReader::new(clt_r, clt_r_buf),
+            observer: DnsTcpQueryObserver::default(),
+            checker: DnsQueryChecker {
+                log: DnsInspectLog::new(&self.ctx),
+                audit_handle: self.ctx.audit_handle.clone(),
+                user_name: self.ctx.user().map(|user| user.name().clone()),
+                block_score: self
+                    .ctx
+                    .user()
+                    .and_then(|user| user.audit().dns_tunnel_block_score),
+            },
+            blocked: blocked.clone(),
+        };
+
+        let r = self
+            .ctx
+            .transit_transparent(clt_r, clt_w, ups_r, ups_w)
+            .await;
+        if blocked.load(Ordering::Relaxed) {
+            Err(ServerTaskError::ForbiddenByRule(
+                ServerTaskForbiddenError::ProtoBanned,
+            ))
+        } else {
+            r
+        }
+    }
+}
+
+struct DnsQueryChecker {
+    log: DnsInspectLog,
+    audit_handle: Arc<AuditHandle>,
+    user_name: Option<Arc<str>>,
+    block_score: Option<u8>,
+}
+
+impl DnsQueryChecker {
+    /// Check the query, return false if it should be blocked
+    fn check(&self, question: &DnsQuestion) -> bool {
+        let config = self.audit_handle.dns_interception();
+        let (subdomain, domain) = question.split_name(config.domain_labels);
+
+        let mut score = 0u8;
+        let labels: Vec<u8> = subdomain.bytes().filter(|c| *c != b'.').collect();
+        let entropy = shannon_entropy(&labels);
+        if labels.len() >= ENTROPY_CHECK_MIN_LENGTH && entropy >= config.entropy_threshold {
+            score += SCORE_HIGH_ENTROPY;
+        }
+        if subdomain.len() >= config.subdomain_length_threshold {
+            score += SCORE_LONG_SUBDOMAIN;
+        }
+
+        let is_txt = matches!(question.qtype, record_type::TXT | record_type::NULL);
+        let (queries, txt_queries) = self.audit_handle.dns_tunnel_tracker().record(
+            self.user_name.as_ref(),
+            domain,
+            is_txt,
+            config.stats_window,
+        );
+        if queries > config.query_rate_threshold {
+            score += SCORE_HIGH_QUERY_RATE;
+        }
+        if txt_queries > config.txt_volume_threshold {
+            score += SCORE_HIGH_TXT_VOLUME;
+        }
+
+        let blocked = self.block_score.map(|s| score >= s).unwrap_or(false);
+        if blocked || score >= config.log_score {
+            let score = DnsQueryScore {
+                domain,
+                entropy,
+                queries,
+                txt_queries,
+                score,
+            };
+            self.log.log(question, &score, blocked);
+        }
+        !blocked
+    }
+}
+
+struct DnsObserveReader<R> {
+    inner: R,
+    observer: DnsTcpQueryObserver,
+    checker: DnsQueryChecker,
+    blocked: Arc<AtomicBool>,
+}
+
+impl<R> AsyncRead for DnsObserveReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if self.blocked.load(Ordering::Relaxed) {
+            // stop forwarding any more queries to the upstream
+            return Poll::Ready(Ok(()));
+        }
+
+        let start = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        if self.observer.is_failed() {
+            return Poll::Ready(Ok(()));
+        }
+
+        let this = &mut *self;
+        let checker = &this.checker;
+        let r = this
+            .observer
+            .feed(&buf.filled()[start..], |question| checker.check(question));
+        if let Err(offset) = r {
+            // drop the blocked query, and the queries after it
+            buf.set_filled(start + offset.unwrap_or_default());
+            this.blocked.store(true, Ordering::Relaxed);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
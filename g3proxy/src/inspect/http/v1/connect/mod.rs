@@ -32,7 +32,7 @@ use g3_icap_client::reqmod::h1::{
 };
 use g3_icap_client::reqmod::IcapReqmodClient;
 use g3_io_ext::{LimitedCopy, LimitedCopyError, LimitedWriteExt};
-use g3_slog_types::{LtDateTime, LtDuration, LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtDateTime, LtDuration, LtMetricsTags, LtUpstreamAddr, LtUuid};
 use g3_types::net::UpstreamAddr;
 
 use super::{HttpRequest, HttpRequestIo, HttpResponseIo};
@@ -47,6 +47,7 @@ macro_rules! intercept_log {
             "intercept_type" => "HttpConnect",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "request_id" => $obj.req_id,
             "next_upstream" => $r.as_ref().map(LtUpstreamAddr),
             "received_at" => LtDateTime(&$obj.http_notes.receive_datetime),
@@ -36,13 +36,14 @@ use g3_icap_client::respmod::h1::{
     HttpResponseAdapter, RespmodAdaptationEndState, RespmodAdaptationRunState,
 };
 use g3_io_ext::{LimitedBufReadExt, LimitedCopy, LimitedCopyError, LimitedWriteExt};
-use g3_slog_types::{LtDateTime, LtDuration, LtHttpMethod, LtHttpUri, LtUuid};
+use g3_slog_types::{LtDateTime, LtDuration, LtHttpMethod, LtHttpUri, LtMetricsTags, LtUuid};
 use g3_types::net::HttpHeaderMap;
 
 use super::{HttpRequest, HttpRequestIo, HttpResponseIo};
 use crate::config::server::ServerConfig;
 use crate::inspect::StreamInspectContext;
 use crate::module::http_forward::HttpProxyClientResponse;
+use crate::module::http_header::HttpHeaderRewriteDirection;
 use crate::serve::{ServerIdleChecker, ServerTaskError, ServerTaskResult};
 
 mod adaptation;
@@ -54,6 +55,7 @@ macro_rules! intercept_log {
             "intercept_type" => "HttpForward",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "request_id" => $obj.req_id,
             "received_at" => LtDateTime(&$obj.http_notes.receive_datetime),
             "method" => LtHttpMethod(&$obj.req.method),
@@ -660,7 +662,7 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
     async fn send_response<CW, UR, UW>(
         &mut self,
         mut rsp: HttpTransparentResponse,
-        rsp_head: Bytes,
+        mut rsp_head: Bytes,
         rsp_io: &mut HttpResponseIo<CW, UR, UW>,
         adaptation_respond_shared_headers: Option<HttpHeaderMap>,
     ) -> ServerTaskResult<()>
@@ -679,6 +681,13 @@ impl<'a, SC: ServerConfig> H1ForwardTask<'a, SC> {
         self.http_notes.rsp_status = 0;
         self.http_notes.mark_rsp_recv_hdr();
 
+        if self.ctx.rewrite_http_header(
+            &mut rsp.end_to_end_headers,
+            HttpHeaderRewriteDirection::Response,
+        ) {
+            rsp_head = Bytes::from(rsp.serialize());
+        }
+
         if let Some(respmod) = self.ctx.audit_handle.icap_respmod_client() {
             match respmod
                 .h1_adapter(
@@ -23,13 +23,14 @@ use tokio::io::{AsyncRead, AsyncWrite};
 
 use g3_dpi::Protocol;
 use g3_io_ext::{FlexBufReader, LimitedBufReadExt};
-use g3_slog_types::LtUuid;
+use g3_slog_types::{LtMetricsTags, LtUuid};
 
 use crate::config::server::ServerConfig;
 use crate::inspect::{
     BoxAsyncRead, BoxAsyncWrite, InterceptionError, StreamInspectContext, StreamInspection,
 };
 use crate::module::http_forward::HttpProxyClientResponse;
+use crate::module::http_header::HttpHeaderRewriteDirection;
 use crate::serve::ServerTaskResult;
 
 mod error;
@@ -102,6 +103,7 @@ macro_rules! intercept_log {
             "intercept_type" => "H1Connection",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "current_req_id" => $obj.req_id,
         )
     };
@@ -174,7 +176,11 @@ where
                     }
                     return Err(e.into());
                 }
-                HttpRecvRequest::RequestWithoutIo(r) => {
+                HttpRecvRequest::RequestWithoutIo(mut r) => {
+                    self.ctx.rewrite_http_header(
+                        &mut r.inner.end_to_end_headers,
+                        HttpHeaderRewriteDirection::Request,
+                    );
                     let mut forward_task = H1ForwardTask::new(self.ctx.clone(), &r, self.req_id);
                     // not ICAP in this case
                     forward_task.forward_without_body(&mut rsp_io).await;
@@ -183,7 +189,7 @@ where
                         req_acceptor.close();
                     }
                 }
-                HttpRecvRequest::RequestWithIO(r, mut req_io, io_sender) => {
+                HttpRecvRequest::RequestWithIO(mut r, mut req_io, io_sender) => {
                     if r.inner.method == Method::CONNECT {
                         let mut connect_task = H1ConnectTask::new(self.ctx.clone(), r, self.req_id);
                         let r = if let Some(reqmod_client) =
@@ -228,6 +234,10 @@ where
                             pipeline_stats.del_task();
                         }
                     } else {
+                        self.ctx.rewrite_http_header(
+                            &mut r.inner.end_to_end_headers,
+                            HttpHeaderRewriteDirection::Request,
+                        );
                         let mut forward_task =
                             H1ForwardTask::new(self.ctx.clone(), &r, self.req_id);
                         if let Some(reqmod_client) = self.ctx.audit_handle.icap_reqmod_client() {
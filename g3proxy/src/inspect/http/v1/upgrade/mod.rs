@@ -34,7 +34,7 @@ use g3_icap_client::reqmod::h1::{
 };
 use g3_icap_client::reqmod::IcapReqmodClient;
 use g3_io_ext::{LimitedCopy, LimitedCopyError, LimitedWriteExt, OnceBufReader};
-use g3_slog_types::{LtDateTime, LtDuration, LtHttpUri, LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtDateTime, LtDuration, LtHttpUri, LtMetricsTags, LtUpstreamAddr, LtUuid};
 use g3_types::net::{HttpUpgradeToken, UpstreamAddr, WebSocketNotes};
 
 use super::{H1InterceptionError, HttpRequest, HttpRequestIo, HttpResponseIo};
@@ -51,6 +51,7 @@ macro_rules! intercept_log {
             "intercept_type" => "HttpUpgrade",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "request_id" => $obj.req_id,
             "next_protocol" => $r.as_ref().map(|v| v.0.to_string()),
             "next_upstream" => $r.as_ref().map(|v| LtUpstreamAddr(&v.1)),
@@ -168,10 +169,12 @@ where
                 let Some(http_host) = &req.host else {
                     return false;
                 };
-                return !self
-                    .ctx
-                    .websocket_inspect_action(http_host.host())
-                    .is_block();
+                let action = self.ctx.websocket_inspect_action(http_host.host());
+                if action.is_block() {
+                    self.ctx.record_inspect_action(Protocol::Websocket, action);
+                    return false;
+                }
+                return true;
             } else if matches!(p, HttpUpgradeToken::ConnectIp) {
                 return false;
             }
@@ -26,7 +26,7 @@ use slog::slog_info;
 use g3_dpi::Protocol;
 use g3_h2::{H2StreamReader, H2StreamWriter};
 use g3_http::server::UriExt;
-use g3_slog_types::{LtDateTime, LtDuration, LtH2StreamId, LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtDateTime, LtDuration, LtH2StreamId, LtMetricsTags, LtUpstreamAddr, LtUuid};
 use g3_types::net::{HttpUpgradeToken, UpstreamAddr, WebSocketNotes};
 
 use super::{ExchangeHead, H2StreamTransferError, HttpForwardTaskNotes};
@@ -40,6 +40,7 @@ macro_rules! intercept_log {
             "intercept_type" => "H2ExtendedConnect",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "clt_stream" => LtH2StreamId(&$obj.clt_stream_id),
             "ups_stream" => $obj.ups_stream_id.as_ref().map(LtH2StreamId),
             "next_protocol" => $obj.protocol.to_string(),
@@ -178,11 +179,10 @@ where
             }
         };
 
-        if self
-            .ctx
-            .websocket_inspect_action(upstream.host())
-            .is_block()
-        {
+        let ws_action = self.ctx.websocket_inspect_action(upstream.host());
+        if ws_action.is_block() {
+            self.ctx
+                .record_inspect_action(Protocol::Websocket, ws_action);
             self.reply_forbidden(clt_send_rsp);
             intercept_log!(self, "websocket blocked by inspection policy");
             return;
@@ -23,7 +23,7 @@ use slog::slog_info;
 
 use g3_h2::{H2StreamReader, H2StreamWriter};
 use g3_http::server::UriExt;
-use g3_slog_types::{LtDateTime, LtDuration, LtH2StreamId, LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtDateTime, LtDuration, LtH2StreamId, LtMetricsTags, LtUpstreamAddr, LtUuid};
 use g3_types::net::UpstreamAddr;
 
 use super::{ExchangeHead, HttpForwardTaskNotes};
@@ -36,6 +36,7 @@ macro_rules! intercept_log {
             "intercept_type" => "H2Connect",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "clt_stream" => LtH2StreamId(&$obj.clt_stream_id),
             "ups_stream" => $obj.ups_stream_id.as_ref().map(LtH2StreamId),
             "next_upstream" => $obj.upstream.as_ref().map(LtUpstreamAddr),
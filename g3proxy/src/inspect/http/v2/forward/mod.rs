@@ -35,7 +35,8 @@ use g3_icap_client::respmod::h2::{
     H2ResponseAdapter, RespmodAdaptationEndState, RespmodAdaptationRunState,
 };
 use g3_slog_types::{
-    LtDateTime, LtDuration, LtH2StreamId, LtHttpHeaderValue, LtHttpMethod, LtHttpUri, LtUuid,
+    LtDateTime, LtDuration, LtH2StreamId, LtHttpHeaderValue, LtHttpMethod, LtHttpUri,
+    LtMetricsTags, LtUuid,
 };
 use g3_types::net::HttpHeaderMap;
 
@@ -50,6 +51,7 @@ macro_rules! intercept_log {
             "intercept_type" => "H2StreamForward",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "clt_stream" => LtH2StreamId(&$obj.clt_stream_id),
             "ups_stream" => $obj.ups_stream_id.as_ref().map(LtH2StreamId),
             "started_at" => LtDateTime(&$obj.http_notes.started_datetime),
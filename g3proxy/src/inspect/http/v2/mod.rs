@@ -27,7 +27,7 @@ use tokio::time::Instant;
 use g3_dpi::{Protocol, ProtocolInspectAction};
 use g3_h2::H2BodyTransfer;
 use g3_io_ext::OnceBufReader;
-use g3_slog_types::{LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtMetricsTags, LtUpstreamAddr, LtUuid};
 use g3_types::net::UpstreamAddr;
 
 #[cfg(feature = "quic")]
@@ -98,6 +98,7 @@ macro_rules! intercept_log {
             "intercept_type" => "H2Connection",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "upstream" => LtUpstreamAddr(&$obj.upstream),
             "total_sub_task" => $obj.stats.get_total_task(),
             "alive_sub_task" => $obj.stats.get_alive_task(),
@@ -110,7 +111,9 @@ where
     SC: ServerConfig + Send + Sync + 'static,
 {
     pub(crate) async fn intercept(mut self) -> ServerTaskResult<()> {
-        let r = match self.ctx.h2_inspect_action(self.upstream.host()) {
+        let action = self.ctx.h2_inspect_action(self.upstream.host());
+        self.ctx.record_inspect_action(Protocol::Http2, action);
+        let r = match action {
             ProtocolInspectAction::Intercept => self
                 .do_intercept()
                 .await
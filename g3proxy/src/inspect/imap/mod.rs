@@ -18,11 +18,11 @@ use anyhow::anyhow;
 use slog::slog_info;
 use tokio::io::AsyncWriteExt;
 
-use g3_dpi::ProtocolInspectAction;
+use g3_dpi::{Protocol, ProtocolInspectAction};
 use g3_imap_proto::response::ByeResponse;
 use g3_imap_proto::CommandPipeline;
 use g3_io_ext::{LineRecvVec, OnceBufReader};
-use g3_slog_types::{LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtMetricsTags, LtUpstreamAddr, LtUuid};
 use g3_types::net::UpstreamAddr;
 
 use super::StartTlsProtocol;
@@ -63,6 +63,7 @@ macro_rules! intercept_log {
             "intercept_type" => "SmtpConnection",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "upstream" => LtUpstreamAddr(&$obj.upstream),
             "server_bye" => $obj.server_bye,
             "client_logout" => $obj.client_logout,
@@ -130,7 +131,9 @@ where
     }
 
     pub(crate) async fn intercept(mut self) -> ServerTaskResult<Option<StreamInspection<SC>>> {
-        let r = match self.ctx.imap_inspect_action(self.upstream.host()) {
+        let action = self.ctx.imap_inspect_action(self.upstream.host());
+        self.ctx.record_inspect_action(Protocol::Imap, action);
+        let r = match action {
             ProtocolInspectAction::Intercept => self.do_intercept().await,
             #[cfg(feature = "quic")]
             ProtocolInspectAction::Detour => self.do_detour().await.map(|_| None),
@@ -0,0 +1,158 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use bytes::BytesMut;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use g3_dpi::parser::kafka::{api_key, KafkaRequestObserver};
+use g3_dpi::KafkaInterceptionConfig;
+use g3_io_ext::OnceBufReader;
+
+use super::{BoxAsyncRead, BoxAsyncWrite, StreamInspectContext};
+use crate::config::server::ServerConfig;
+use crate::log::inspect::kafka::KafkaInspectLog;
+use crate::serve::{ServerTaskError, ServerTaskForbiddenError, ServerTaskResult};
+
+struct KafkaInterceptIo {
+    clt_r: BoxAsyncRead,
+    clt_r_buf: BytesMut,
+    clt_w: BoxAsyncWrite,
+    ups_r: BoxAsyncRead,
+    ups_w: BoxAsyncWrite,
+}
+
+pub(crate) struct KafkaInterceptObject<SC: ServerConfig> {
+    io: Option<KafkaInterceptIo>,
+    ctx: StreamInspectContext<SC>,
+}
+
+impl<SC> KafkaInterceptObject<SC>
+where
+    SC: ServerConfig + Send + Sync + 'static,
+{
+    pub(crate) fn new(ctx: StreamInspectContext<SC>) -> Self {
+        KafkaInterceptObject { io: None, ctx }
+    }
+
+    pub(crate) fn set_io(
+        &mut self,
+        clt_r: BoxAsyncRead,
+        clt_r_buf: BytesMut,
+        clt_w: BoxAsyncWrite,
+        ups_r: BoxAsyncRead,
+        ups_w: BoxAsyncWrite,
+    ) {
+        self.io = Some(KafkaInterceptIo {
+            clt_r,
+            clt_r_buf,
+            clt_w,
+            ups_r,
+            ups_w,
+        });
+    }
+
+    pub(crate) async fn intercept(mut self) -> ServerTaskResult<()> {
+        let KafkaInterceptIo {
+            clt_r,
+            clt_r_buf,
+            clt_w,
+            ups_r,
+            ups_w,
+        } = self.io.take().unwrap();
+
+        let config = self.ctx.kafka_interception().clone();
+        let blocked = Arc::new(AtomicBool::new(false));
+        let clt_r = KafkaObserveReader {
+            inner: OnceBufReader::new(clt_r, clt_r_buf),
+            observer: KafkaRequestObserver::new(
+                config.request_max_size,
+                config.request_buffer_size,
+            ),
+            log: KafkaInspectLog::new(&self.ctx),
+            config,
+            blocked: blocked.clone(),
+        };
+
+        let r = self
+            .ctx
+            .transit_transparent(clt_r, clt_w, ups_r, ups_w)
+            .await;
+        if blocked.load(Ordering::Relaxed) {
+            Err(ServerTaskError::ForbiddenByRule(
+                ServerTaskForbiddenError::ProtoBanned,
+            ))
+        } else {
+            r
+        }
+    }
+}
+
+struct KafkaObserveReader<R> {
+    inner: R,
+    observer: KafkaRequestObserver,
+    log: KafkaInspectLog,
+    config: KafkaInterceptionConfig,
+    blocked: Arc<AtomicBool>,
+}
+
+impl<R> AsyncRead for KafkaObserveReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        if self.blocked.load(Ordering::Relaxed) {
+            // stop forwarding any more requests to the upstream
+            return Poll::Ready(Ok(()));
+        }
+
+        let start = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+        if self.observer.is_failed() {
+            return Poll::Ready(Ok(()));
+        }
+
+        let this = &mut *self;
+        let config = &this.config;
+        let log = &this.log;
+        let r = this
+            .observer
+            .feed(&buf.filled()[start..], |header, topics| {
+                let blocked = match header.api_key {
+                    api_key::PRODUCE => config.block_produce,
+                    api_key::FETCH => config.block_fetch,
+                    _ => false,
+                };
+                log.log(header, topics, blocked);
+                !blocked
+            });
+        if let Err(offset) = r {
+            // drop the blocked request, and the requests after it
+            buf.set_filled(start + offset.unwrap_or_default());
+            this.blocked.store(true, Ordering::Relaxed);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
@@ -24,19 +24,28 @@ use uuid::Uuid;
 
 use g3_daemon::server::ServerQuitPolicy;
 use g3_dpi::{
-    H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig, MaybeProtocol,
-    ProtocolInspectAction, ProtocolInspector, SmtpInterceptionConfig,
+    DnsInterceptionConfig, H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig,
+    KafkaInterceptionConfig, MaybeProtocol, MysqlInterceptionConfig, PostgresInterceptionConfig,
+    Protocol, ProtocolInspectAction, ProtocolInspector, SmtpInterceptionConfig,
+    WebsocketInterceptionConfig,
 };
-use g3_types::net::{Host, OpensslClientConfig};
+use g3_types::metrics::StaticMetricsTags;
+use g3_types::net::{Host, HttpHeaderMap, OpensslClientConfig};
+use g3_udpdump::StreamDumpMetadata;
 
 use crate::audit::AuditHandle;
 use crate::auth::{User, UserForbiddenStats, UserSite};
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
 use crate::config::server::ServerConfig;
+use crate::module::http_header;
 use crate::serve::{ArcServerStats, ServerIdleChecker, ServerTaskNotes};
 
 mod error;
 pub(crate) use error::InterceptionError;
 
+mod cost;
+use cost::InspectionCost;
+
 pub(crate) mod stream;
 pub(crate) use stream::StreamTransitTask;
 
@@ -49,8 +58,13 @@ use start_tls::StartTlsProtocol;
 pub(crate) mod http;
 mod websocket;
 
+mod dns;
 pub(crate) mod imap;
+mod kafka;
+mod mysql;
+mod postgres;
 pub(crate) mod smtp;
+mod thrift;
 
 #[derive(Clone)]
 pub(super) struct StreamInspectUserContext {
@@ -89,6 +103,13 @@ impl StreamInspectTaskNotes {
             .and_then(|ctx| ctx.raw_user_name.as_ref())
     }
 
+    pub(crate) fn request_tags(&self) -> Option<&Arc<StaticMetricsTags>> {
+        self.user_ctx
+            .as_ref()
+            .and_then(|ctx| ctx.user_site.as_ref())
+            .and_then(|site| site.request_tags())
+    }
+
     #[inline]
     pub(crate) fn task_id(&self) -> &Uuid {
         &self.task_id
@@ -119,6 +140,8 @@ pub(crate) struct StreamInspectContext<SC: ServerConfig> {
     server_quit_policy: Arc<ServerQuitPolicy>,
     task_notes: StreamInspectTaskNotes,
     inspection_depth: usize,
+    inspection_cost: Arc<InspectionCost>,
+    user_header_rewrite: Option<Arc<HttpHeaderRewriteConfig>>,
 
     task_max_idle_count: i32,
 }
@@ -132,6 +155,8 @@ impl<SC: ServerConfig> Clone for StreamInspectContext<SC> {
             server_quit_policy: self.server_quit_policy.clone(),
             task_notes: self.task_notes.clone(),
             inspection_depth: self.inspection_depth,
+            inspection_cost: self.inspection_cost.clone(),
+            user_header_rewrite: self.user_header_rewrite.clone(),
             task_max_idle_count: self.task_max_idle_count,
         }
     }
@@ -146,10 +171,16 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         task_notes: &ServerTaskNotes,
     ) -> Self {
         let mut task_max_idle_count = server_config.task_max_idle_count();
+        let mut user_header_rewrite = None;
         if let Some(user_ctx) = task_notes.user_ctx() {
             task_max_idle_count = user_ctx.user().task_max_idle_count();
+            user_header_rewrite = server_config
+                .get_user_group()
+                .and_then(|g| g.http_header_rewrite().cloned());
         }
 
+        let inspection_cost = Arc::new(InspectionCost::new(audit_handle.stats_recorder().clone()));
+
         StreamInspectContext {
             audit_handle,
             server_config,
@@ -157,6 +188,8 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
             server_quit_policy,
             task_notes: StreamInspectTaskNotes::from(task_notes),
             inspection_depth: 0,
+            inspection_cost,
+            user_header_rewrite,
             task_max_idle_count,
         }
     }
@@ -196,6 +229,11 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         self.audit_handle.intercept_logger()
     }
 
+    #[inline]
+    fn flow_logger(&self) -> Option<&Logger> {
+        self.audit_handle.flow_logger()
+    }
+
     pub(crate) fn idle_checker(&self) -> ServerIdleChecker {
         ServerIdleChecker {
             idle_duration: self.server_config.task_idle_check_duration(),
@@ -224,6 +262,61 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         self.inspection_depth
     }
 
+    #[inline]
+    pub(crate) fn request_tags(&self) -> Option<&Arc<StaticMetricsTags>> {
+        self.task_notes.request_tags()
+    }
+
+    /// apply the server level and then the user group level header rewrite rules
+    ///
+    /// Returns true if any of the rules has been applied.
+    pub(crate) fn rewrite_http_header(
+        &self,
+        headers: &mut HttpHeaderMap,
+        direction: http_header::HttpHeaderRewriteDirection,
+    ) -> bool {
+        let rewrite_log = http_header::HttpHeaderRewriteLog {
+            logger: self.intercept_logger(),
+            task_id: self.server_task_id(),
+            user: self.raw_user_name().map(|s| s.as_ref()),
+            direction,
+        };
+        let mut changed = false;
+        for rewrite in [
+            self.server_config.http_header_rewrite(),
+            self.user_header_rewrite.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let rules = match direction {
+                http_header::HttpHeaderRewriteDirection::Request => &rewrite.request,
+                http_header::HttpHeaderRewriteDirection::Response => &rewrite.response,
+            };
+            if http_header::rewrite(headers, rules, &rewrite_log) {
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    #[inline]
+    fn add_inspection_cost(&self, dur: Duration, bytes: usize) {
+        self.inspection_cost.add(dur, bytes);
+    }
+
+    #[inline]
+    fn record_detected_protocol(&self, protocol: Protocol) {
+        self.audit_handle.stats_recorder().add_protocol(protocol);
+    }
+
+    #[inline]
+    fn record_inspect_action(&self, protocol: Protocol, action: ProtocolInspectAction) {
+        self.audit_handle
+            .stats_recorder()
+            .add_inspect_action(protocol, action);
+    }
+
     #[inline]
     fn increase_inspection_depth(&mut self) {
         self.inspection_depth += 1;
@@ -234,6 +327,19 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         self.audit_handle.tls_interception()
     }
 
+    fn stream_dump_metadata(&self, protocol: Protocol) -> StreamDumpMetadata {
+        let mut metadata = StreamDumpMetadata::default();
+        metadata.set_task_id(self.server_task_id().to_string());
+        metadata.set_server(self.server_config.name().as_str());
+        if let Some(user) = self.user() {
+            metadata.set_user(user.name());
+        }
+        metadata.set_escaper(self.server_config.escaper().as_str());
+        metadata.set_auditor(self.server_config.auditor().as_str());
+        metadata.set_protocol(protocol);
+        metadata
+    }
+
     pub(crate) fn user_site_tls_client(&self) -> Option<&OpensslClientConfig> {
         self.task_notes
             .user_ctx
@@ -292,6 +398,11 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         }
     }
 
+    #[inline]
+    fn websocket_interception(&self) -> &WebsocketInterceptionConfig {
+        self.audit_handle.websocket_interception()
+    }
+
     #[inline]
     fn smtp_inspect_action(&self, host: &Host) -> ProtocolInspectAction {
         match self.audit_handle.smtp_inspect_policy.check(host) {
@@ -318,6 +429,34 @@ impl<SC: ServerConfig> StreamInspectContext<SC> {
         self.audit_handle.imap_interception()
     }
 
+    #[inline]
+    fn kafka_interception(&self) -> &KafkaInterceptionConfig {
+        self.audit_handle.kafka_interception()
+    }
+
+    #[inline]
+    fn postgres_interception(&self) -> &PostgresInterceptionConfig {
+        self.audit_handle.postgres_interception()
+    }
+
+    #[inline]
+    fn mysql_inspect_action(&self, host: &Host) -> ProtocolInspectAction {
+        match self.audit_handle.mysql_inspect_policy.check(host) {
+            (true, policy_action) => policy_action,
+            (false, missing_policy_action) => missing_policy_action,
+        }
+    }
+
+    #[inline]
+    fn mysql_interception(&self) -> &MysqlInterceptionConfig {
+        self.audit_handle.mysql_interception()
+    }
+
+    #[inline]
+    fn dns_interception(&self) -> &DnsInterceptionConfig {
+        self.audit_handle.dns_interception()
+    }
+
     #[inline]
     fn task_max_idle_count(&self) -> i32 {
         self.task_max_idle_count
@@ -345,6 +484,11 @@ pub(crate) enum StreamInspection<SC: ServerConfig> {
     Websocket(websocket::H1WebsocketInterceptObject<SC>),
     Smtp(smtp::SmtpInterceptObject<SC>),
     Imap(imap::ImapInterceptObject<SC>),
+    Thrift(thrift::ThriftInspectObject<SC>),
+    Kafka(kafka::KafkaInterceptObject<SC>),
+    Postgres(postgres::PostgresInterceptObject<SC>),
+    Mysql(mysql::MysqlInterceptObject<SC>),
+    Dns(dns::DnsInspectObject<SC>),
 }
 
 type BoxAsyncRead = Box<dyn AsyncRead + Send + Unpin + 'static>;
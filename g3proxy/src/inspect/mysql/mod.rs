@@ -0,0 +1,323 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::anyhow;
+use bytes::{Buf, BytesMut};
+use slog::slog_info;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+
+use g3_dpi::parser::mysql::{
+    encode_err_packet, MysqlClientHandshake, MysqlParseError, MysqlServerHandshake,
+};
+use g3_dpi::{Protocol, ProtocolInspectAction};
+use g3_io_ext::OnceBufReader;
+use g3_slog_types::{LtMetricsTags, LtUpstreamAddr, LtUuid};
+use g3_types::net::UpstreamAddr;
+
+use super::{BoxAsyncRead, BoxAsyncWrite, StreamInspectContext};
+use crate::config::server::ServerConfig;
+use crate::serve::{ServerTaskError, ServerTaskForbiddenError, ServerTaskResult};
+
+/// ER_SECURE_TRANSPORT_REQUIRED
+const ERR_CODE_SECURE_TRANSPORT_REQUIRED: u16 = 3159;
+/// ER_HOST_NOT_PRIVILEGED
+const ERR_CODE_HOST_NOT_PRIVILEGED: u16 = 1130;
+
+macro_rules! intercept_log {
+    ($obj:tt, $($args:tt)+) => {
+        slog_info!($obj.ctx.intercept_logger(), $($args)+;
+            "intercept_type" => "MysqlConnection",
+            "task_id" => LtUuid($obj.ctx.server_task_id()),
+            "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
+            "upstream" => LtUpstreamAddr(&$obj.upstream),
+            "server_version" => $obj.server_version.as_deref(),
+            "connection_id" => $obj.connection_id,
+            "server_tls" => $obj.server_tls,
+            "tls" => $obj.tls,
+            "user" => $obj.user.as_deref(),
+            "database" => $obj.database.as_deref(),
+        )
+    };
+}
+
+struct MysqlIo {
+    clt_r: BoxAsyncRead,
+    clt_w: BoxAsyncWrite,
+    ups_r: BoxAsyncRead,
+    ups_r_buf: BytesMut,
+    ups_w: BoxAsyncWrite,
+}
+
+pub(crate) struct MysqlInterceptObject<SC: ServerConfig> {
+    io: Option<MysqlIo>,
+    ctx: StreamInspectContext<SC>,
+    upstream: UpstreamAddr,
+    server_version: Option<String>,
+    connection_id: Option<u32>,
+    server_tls: bool,
+    tls: bool,
+    user: Option<String>,
+    database: Option<String>,
+}
+
+impl<SC> MysqlInterceptObject<SC>
+where
+    SC: ServerConfig + Send + Sync + 'static,
+{
+    pub(crate) fn new(ctx: StreamInspectContext<SC>, upstream: UpstreamAddr) -> Self {
+        MysqlInterceptObject {
+            io: None,
+            ctx,
+            upstream,
+            server_version: None,
+            connection_id: None,
+            server_tls: false,
+            tls: false,
+            user: None,
+            database: None,
+        }
+    }
+
+    pub(crate) fn set_io(
+        &mut self,
+        clt_r: BoxAsyncRead,
+        clt_w: BoxAsyncWrite,
+        ups_r: BoxAsyncRead,
+        ups_r_buf: BytesMut,
+        ups_w: BoxAsyncWrite,
+    ) {
+        self.io = Some(MysqlIo {
+            clt_r,
+            clt_w,
+            ups_r,
+            ups_r_buf,
+            ups_w,
+        });
+    }
+
+    pub(crate) async fn intercept(mut self) -> ServerTaskResult<()> {
+        let action = self.ctx.mysql_inspect_action(self.upstream.host());
+        self.ctx.record_inspect_action(Protocol::Mysql, action);
+        let r = match action {
+            ProtocolInspectAction::Intercept => self.do_intercept().await,
+            // stream detour is not supported for mysql
+            #[cfg(feature = "quic")]
+            ProtocolInspectAction::Detour => self.do_bypass().await,
+            ProtocolInspectAction::Bypass => self.do_bypass().await,
+            ProtocolInspectAction::Block => self.do_block().await,
+        };
+        match r {
+            Ok(_) => {
+                intercept_log!(self, "finished");
+                Ok(())
+            }
+            Err(e) => {
+                intercept_log!(self, "{e}");
+                Err(e)
+            }
+        }
+    }
+
+    async fn do_bypass(&mut self) -> ServerTaskResult<()> {
+        let MysqlIo {
+            clt_r,
+            clt_w,
+            ups_r,
+            ups_r_buf,
+            ups_w,
+        } = self.io.take().unwrap();
+
+        self.ctx
+            .transit_transparent(clt_r, clt_w, OnceBufReader::new(ups_r, ups_r_buf), ups_w)
+            .await
+    }
+
+    async fn do_block(&mut self) -> ServerTaskResult<()> {
+        let MysqlIo {
+            clt_r: _,
+            mut clt_w,
+            ups_r: _,
+            ups_r_buf: _,
+            mut ups_w,
+        } = self.io.take().unwrap();
+
+        tokio::spawn(async move {
+            let _ = ups_w.shutdown().await;
+        });
+
+        // the server handshake has not been forwarded yet,
+        // so reply an ERR packet in place of it, like what a mysql server does
+        let packet = encode_err_packet(
+            0,
+            ERR_CODE_HOST_NOT_PRIVILEGED,
+            b"HY000",
+            "Connections to this server are blocked by proxy policy",
+        );
+        clt_w
+            .write_all(&packet)
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        let _ = clt_w.shutdown().await;
+        Err(ServerTaskError::InternalAdapterError(anyhow!(
+            "mysql blocked by inspection policy"
+        )))
+    }
+
+    async fn do_intercept(&mut self) -> ServerTaskResult<()> {
+        let MysqlIo {
+            mut clt_r,
+            mut clt_w,
+            mut ups_r,
+            mut ups_r_buf,
+            mut ups_w,
+        } = self.io.take().unwrap();
+
+        let handshake_timeout = self.ctx.mysql_interception().handshake_timeout;
+        let require_tls = self.ctx.mysql_interception().require_tls;
+
+        let len = tokio::time::timeout(
+            handshake_timeout,
+            self.read_server_handshake(&mut ups_r, &mut ups_r_buf),
+        )
+        .await
+        .map_err(|_| ServerTaskError::UpstreamAppTimeout("mysql server handshake timeout"))??;
+        clt_w
+            .write_all(&ups_r_buf[..len])
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        clt_w
+            .flush()
+            .await
+            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+        ups_r_buf.advance(len);
+
+        let mut clt_r_buf = BytesMut::with_capacity(1024);
+        let sequence_id = tokio::time::timeout(
+            handshake_timeout,
+            self.read_client_handshake(&mut clt_r, &mut clt_r_buf),
+        )
+        .await
+        .map_err(|_| ServerTaskError::ClientAppTimeout("mysql client handshake timeout"))??;
+
+        if self.tls && !self.server_tls {
+            return Err(ServerTaskError::ClientAppError(anyhow!(
+                "mysql client requested tls upgrade but the server does not support it"
+            )));
+        }
+
+        if !self.tls && require_tls {
+            let _ = ups_w.shutdown().await;
+            let packet = encode_err_packet(
+                sequence_id.wrapping_add(1),
+                ERR_CODE_SECURE_TRANSPORT_REQUIRED,
+                b"HY000",
+                "Connections using insecure transport are prohibited by proxy policy",
+            );
+            clt_w
+                .write_all(&packet)
+                .await
+                .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+            clt_w
+                .flush()
+                .await
+                .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+            return Err(ServerTaskError::ForbiddenByRule(
+                ServerTaskForbiddenError::ProtoBanned,
+            ));
+        }
+
+        self.ctx
+            .transit_transparent(
+                OnceBufReader::new(clt_r, clt_r_buf),
+                clt_w,
+                OnceBufReader::new(ups_r, ups_r_buf),
+                ups_w,
+            )
+            .await
+    }
+
+    async fn read_server_handshake<R>(
+        &mut self,
+        ups_r: &mut R,
+        buf: &mut BytesMut,
+    ) -> ServerTaskResult<usize>
+    where
+        R: AsyncRead + Unpin,
+    {
+        loop {
+            match MysqlServerHandshake::parse(buf.chunk()) {
+                Ok(handshake) => {
+                    self.server_version = Some(handshake.server_version.to_string());
+                    self.connection_id = Some(handshake.connection_id);
+                    self.server_tls = handshake.support_ssl();
+                    return Ok(handshake.encoded_len);
+                }
+                Err(MysqlParseError::NeedMoreData(n)) => {
+                    buf.reserve(n);
+                    let nr = ups_r
+                        .read_buf(buf)
+                        .await
+                        .map_err(ServerTaskError::UpstreamReadFailed)?;
+                    if nr == 0 {
+                        return Err(ServerTaskError::ClosedByUpstream);
+                    }
+                }
+                Err(e) => {
+                    return Err(ServerTaskError::UpstreamAppError(anyhow!(
+                        "invalid mysql server handshake: {e}"
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Read the client handshake response, the data will be kept in the buffer
+    async fn read_client_handshake<R>(
+        &mut self,
+        clt_r: &mut R,
+        buf: &mut BytesMut,
+    ) -> ServerTaskResult<u8>
+    where
+        R: AsyncRead + Unpin,
+    {
+        loop {
+            match MysqlClientHandshake::parse(buf.chunk()) {
+                Ok(handshake) => {
+                    self.tls = handshake.ssl_request;
+                    self.user = handshake.user.map(|s| s.to_string());
+                    self.database = handshake.database.map(|s| s.to_string());
+                    return Ok(handshake.sequence_id);
+                }
+                Err(MysqlParseError::NeedMoreData(n)) => {
+                    buf.reserve(n);
+                    let nr = clt_r
+                        .read_buf(buf)
+                        .await
+                        .map_err(ServerTaskError::ClientTcpReadFailed)?;
+                    if nr == 0 {
+                        return Err(ServerTaskError::ClosedByClient);
+                    }
+                }
+                Err(e) => {
+                    return Err(ServerTaskError::ClientAppError(anyhow!(
+                        "invalid mysql client handshake: {e}"
+                    )))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,265 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::anyhow;
+use bytes::{Buf, BytesMut};
+use slog::slog_info;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use g3_dpi::parser::postgres::{
+    encode_fatal_error_response, PostgresInitialMessage, PostgresParseError,
+};
+use g3_io_ext::OnceBufReader;
+use g3_slog_types::{LtMetricsTags, LtUpstreamAddr, LtUuid};
+use g3_types::net::UpstreamAddr;
+
+use super::{BoxAsyncRead, BoxAsyncWrite, StreamInspectContext};
+use crate::config::server::ServerConfig;
+use crate::serve::{ServerTaskError, ServerTaskForbiddenError, ServerTaskResult};
+
+macro_rules! intercept_log {
+    ($obj:tt, $($args:tt)+) => {
+        slog_info!($obj.ctx.intercept_logger(), $($args)+;
+            "intercept_type" => "PostgresConnection",
+            "task_id" => LtUuid($obj.ctx.server_task_id()),
+            "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
+            "upstream" => LtUpstreamAddr(&$obj.upstream),
+            "encryption" => $obj.encryption,
+            "user" => $obj.user.as_deref(),
+            "database" => $obj.database.as_deref(),
+            "application_name" => $obj.application_name.as_deref(),
+        )
+    };
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum InitialRequest {
+    Ssl,
+    GssEnc,
+    Cancel,
+    Startup,
+}
+
+struct PostgresIo {
+    clt_r: BoxAsyncRead,
+    clt_r_buf: BytesMut,
+    clt_w: BoxAsyncWrite,
+    ups_r: BoxAsyncRead,
+    ups_w: BoxAsyncWrite,
+}
+
+pub(crate) struct PostgresInterceptObject<SC: ServerConfig> {
+    io: Option<PostgresIo>,
+    ctx: StreamInspectContext<SC>,
+    upstream: UpstreamAddr,
+    encryption: Option<&'static str>,
+    user: Option<String>,
+    database: Option<String>,
+    application_name: Option<String>,
+}
+
+impl<SC> PostgresInterceptObject<SC>
+where
+    SC: ServerConfig + Send + Sync + 'static,
+{
+    pub(crate) fn new(ctx: StreamInspectContext<SC>, upstream: UpstreamAddr) -> Self {
+        PostgresInterceptObject {
+            io: None,
+            ctx,
+            upstream,
+            encryption: None,
+            user: None,
+            database: None,
+            application_name: None,
+        }
+    }
+
+    /// the connection is already wrapped in TLS by direct negotiation
+    pub(crate) fn set_direct_tls(&mut self) {
+        self.encryption = Some("tls");
+    }
+
+    pub(crate) fn set_io(
+        &mut self,
+        clt_r: BoxAsyncRead,
+        clt_r_buf: BytesMut,
+        clt_w: BoxAsyncWrite,
+        ups_r: BoxAsyncRead,
+        ups_w: BoxAsyncWrite,
+    ) {
+        self.io = Some(PostgresIo {
+            clt_r,
+            clt_r_buf,
+            clt_w,
+            ups_r,
+            ups_w,
+        });
+    }
+
+    pub(crate) async fn intercept(mut self) -> ServerTaskResult<()> {
+        match self.do_intercept().await {
+            Ok(_) => {
+                intercept_log!(self, "finished");
+                Ok(())
+            }
+            Err(e) => {
+                intercept_log!(self, "{e}");
+                Err(e)
+            }
+        }
+    }
+
+    async fn do_intercept(&mut self) -> ServerTaskResult<()> {
+        let PostgresIo {
+            mut clt_r,
+            mut clt_r_buf,
+            mut clt_w,
+            mut ups_r,
+            mut ups_w,
+        } = self.io.take().unwrap();
+
+        let startup_timeout = self.ctx.postgres_interception().startup_timeout;
+        let require_tls = self.ctx.postgres_interception().require_tls;
+
+        loop {
+            let (request, len) = tokio::time::timeout(
+                startup_timeout,
+                self.read_initial_message(&mut clt_r, &mut clt_r_buf),
+            )
+            .await
+            .map_err(|_| ServerTaskError::ClientAppTimeout("postgres startup timeout"))??;
+
+            match request {
+                InitialRequest::Ssl | InitialRequest::GssEnc => {
+                    if request == InitialRequest::GssEnc && require_tls {
+                        // reject GSSAPI encryption locally, so the client may try TLS instead
+                        clt_r_buf.advance(len);
+                        write_all_flush(&mut clt_w, b"N")
+                            .await
+                            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+                        continue;
+                    }
+
+                    write_all_flush(&mut ups_w, &clt_r_buf[..len])
+                        .await
+                        .map_err(ServerTaskError::UpstreamWriteFailed)?;
+                    clt_r_buf.advance(len);
+
+                    let mut rsp = [0u8; 1];
+                    tokio::time::timeout(startup_timeout, ups_r.read_exact(&mut rsp))
+                        .await
+                        .map_err(|_| {
+                            ServerTaskError::UpstreamAppTimeout(
+                                "postgres encryption response timeout",
+                            )
+                        })?
+                        .map_err(ServerTaskError::UpstreamReadFailed)?;
+                    write_all_flush(&mut clt_w, &rsp)
+                        .await
+                        .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+
+                    match (request, rsp[0]) {
+                        (InitialRequest::Ssl, b'S') => {
+                            self.encryption = Some("tls");
+                            break;
+                        }
+                        (InitialRequest::GssEnc, b'G') => {
+                            self.encryption = Some("gssapi");
+                            break;
+                        }
+                        (_, b'N') => {}
+                        // the server may send an ErrorResponse message if not supported
+                        _ => break,
+                    }
+                }
+                InitialRequest::Cancel => break,
+                InitialRequest::Startup => {
+                    if require_tls && self.encryption.is_none() {
+                        let _ = ups_w.shutdown().await;
+                        let msg = encode_fatal_error_response(
+                            "28000",
+                            "connection without TLS is not allowed by proxy policy",
+                        );
+                        write_all_flush(&mut clt_w, &msg)
+                            .await
+                            .map_err(ServerTaskError::ClientTcpWriteFailed)?;
+                        return Err(ServerTaskError::ForbiddenByRule(
+                            ServerTaskForbiddenError::ProtoBanned,
+                        ));
+                    }
+                    break;
+                }
+            }
+        }
+
+        self.ctx
+            .transit_transparent(OnceBufReader::new(clt_r, clt_r_buf), clt_w, ups_r, ups_w)
+            .await
+    }
+
+    async fn read_initial_message<R>(
+        &mut self,
+        clt_r: &mut R,
+        buf: &mut BytesMut,
+    ) -> ServerTaskResult<(InitialRequest, usize)>
+    where
+        R: AsyncRead + Unpin,
+    {
+        loop {
+            match PostgresInitialMessage::parse(buf.chunk()) {
+                Ok((PostgresInitialMessage::SslRequest, len)) => {
+                    return Ok((InitialRequest::Ssl, len))
+                }
+                Ok((PostgresInitialMessage::GssEncRequest, len)) => {
+                    return Ok((InitialRequest::GssEnc, len))
+                }
+                Ok((PostgresInitialMessage::CancelRequest, len)) => {
+                    return Ok((InitialRequest::Cancel, len))
+                }
+                Ok((PostgresInitialMessage::Startup(msg), len)) => {
+                    self.user = Some(msg.user.to_string());
+                    self.database = Some(msg.database().to_string());
+                    self.application_name = msg.application_name.map(|s| s.to_string());
+                    return Ok((InitialRequest::Startup, len));
+                }
+                Err(PostgresParseError::NeedMoreData(n)) => {
+                    buf.reserve(n);
+                    let nr = clt_r
+                        .read_buf(buf)
+                        .await
+                        .map_err(ServerTaskError::ClientTcpReadFailed)?;
+                    if nr == 0 {
+                        return Err(ServerTaskError::ClosedByClient);
+                    }
+                }
+                Err(e) => {
+                    return Err(ServerTaskError::ClientAppError(anyhow!(
+                        "invalid postgres initial message: {e}"
+                    )))
+                }
+            }
+        }
+    }
+}
+
+async fn write_all_flush<W>(writer: &mut W, data: &[u8]) -> std::io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    writer.write_all(data).await?;
+    writer.flush().await
+}
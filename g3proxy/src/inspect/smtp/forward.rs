@@ -102,6 +102,7 @@ impl<'a> Forward<'a> {
                     if !self.allow_starttls {
                         self.send_error_to_client(clt_w, ResponseEncoder::COMMAND_NOT_IMPLEMENTED)
                             .await?;
+                        continue;
                     }
                     self.send_cmd(ups_w, clt_w, cmd_line).await?;
                     let rsp = self.recv_relay_rsp(buf, ups_r, clt_w).await?;
@@ -113,6 +114,7 @@ impl<'a> Forward<'a> {
                     if self.auth_end {
                         self.send_error_to_client(clt_w, ResponseEncoder::BAD_SEQUENCE_OF_COMMANDS)
                             .await?;
+                        continue;
                     }
                     self.send_cmd(ups_w, clt_w, cmd_line).await?;
                     self.recv_relay_auth(buf, clt_r, clt_w, ups_r, ups_w)
@@ -122,10 +124,12 @@ impl<'a> Forward<'a> {
                     if !self.allow_odmr {
                         self.send_error_to_client(clt_w, ResponseEncoder::COMMAND_NOT_IMPLEMENTED)
                             .await?;
+                        continue;
                     }
                     if !self.auth_end {
                         self.send_error_to_client(clt_w, ResponseEncoder::AUTHENTICATION_REQUIRED)
                             .await?;
+                        continue;
                     }
                     self.send_cmd(ups_w, clt_w, cmd_line).await?;
                     // a max 10min timeout according to RFC2645
@@ -18,9 +18,9 @@ use anyhow::anyhow;
 use slog::slog_info;
 use tokio::io::AsyncWriteExt;
 
-use g3_dpi::ProtocolInspectAction;
+use g3_dpi::{Protocol, ProtocolInspectAction};
 use g3_io_ext::{LineRecvBuf, OnceBufReader};
-use g3_slog_types::{LtHost, LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtHost, LtMetricsTags, LtUpstreamAddr, LtUuid};
 use g3_smtp_proto::command::Command;
 use g3_smtp_proto::response::{ReplyCode, ResponseEncoder, ResponseParser};
 use g3_types::net::{Host, UpstreamAddr};
@@ -62,6 +62,7 @@ macro_rules! intercept_log {
             "intercept_type" => "SmtpConnection",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "upstream" => LtUpstreamAddr(&$obj.upstream),
             "client_host" => $obj.client_host.as_ref().map(LtHost),
             "transaction_count" => $obj.transaction_count,
@@ -121,7 +122,9 @@ where
     }
 
     pub(crate) async fn intercept(mut self) -> ServerTaskResult<Option<StreamInspection<SC>>> {
-        let r = match self.ctx.smtp_inspect_action(self.upstream.host()) {
+        let action = self.ctx.smtp_inspect_action(self.upstream.host());
+        self.ctx.record_inspect_action(Protocol::Smtp, action);
+        let r = match action {
             ProtocolInspectAction::Intercept => self.do_intercept().await,
             #[cfg(feature = "quic")]
             ProtocolInspectAction::Detour => self.do_detour().await.map(|_| None),
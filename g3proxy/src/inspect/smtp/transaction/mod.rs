@@ -26,7 +26,7 @@ use g3_dpi::SmtpInterceptionConfig;
 use g3_icap_client::reqmod::mail::{ReqmodAdaptationEndState, ReqmodAdaptationRunState};
 use g3_icap_client::reqmod::smtp::SmtpMessageAdapter;
 use g3_io_ext::{LimitedCopy, LimitedCopyError, LimitedWriteExt};
-use g3_slog_types::LtUuid;
+use g3_slog_types::{LtMetricsTags, LtUuid};
 use g3_smtp_proto::command::{Command, MailParam, RecipientParam};
 use g3_smtp_proto::io::TextDataReader;
 use g3_smtp_proto::response::{ReplyCode, ResponseEncoder, ResponseParser};
@@ -42,6 +42,7 @@ macro_rules! intercept_log {
             "intercept_type" => "SmtpTransaction",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "transaction_id" => $obj.transaction_id,
             "mail_from" => $obj.mail_from.reverse_path(),
         )
@@ -25,7 +25,7 @@ use tokio::io::{AsyncRead, AsyncWrite};
 use g3_dpi::Protocol;
 use g3_io_ext::{AsyncStream, OnceBufReader};
 use g3_openssl::{SslConnector, SslLazyAcceptor};
-use g3_slog_types::{LtUpstreamAddr, LtUuid, LtX509VerifyResult};
+use g3_slog_types::{LtMetricsTags, LtUpstreamAddr, LtUuid, LtX509VerifyResult};
 use g3_types::net::{Host, TlsCertUsage, TlsServiceType, UpstreamAddr};
 use g3_udpdump::ExportedPduDissectorHint;
 
@@ -50,6 +50,7 @@ macro_rules! intercept_log {
             "intercept_type" => "StartTlsHandshake",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "upstream" => LtUpstreamAddr(&$obj.upstream),
             "protocol" => Protocol::from($obj.protocol).as_str(),
             "tls_server_verify" => $obj.server_verify_result.map(LtX509VerifyResult),
@@ -284,11 +285,15 @@ where
             } else {
                 ExportedPduDissectorHint::TcpPort(self.upstream.port())
             };
+            let metadata = stream_dumper
+                .metadata_enabled()
+                .then(|| self.ctx.stream_dump_metadata(protocol));
             if stream_dumper.client_side() {
                 let (clt_r, clt_w) = stream_dumper.wrap_client_io(
                     self.ctx.task_notes.client_addr,
                     self.ctx.task_notes.server_addr,
                     dissector_hint,
+                    metadata.as_ref(),
                     clt_r,
                     clt_w,
                 );
@@ -298,6 +303,7 @@ where
                     self.ctx.task_notes.client_addr,
                     self.ctx.task_notes.server_addr,
                     dissector_hint,
+                    metadata.as_ref(),
                     ups_r,
                     ups_w,
                 );
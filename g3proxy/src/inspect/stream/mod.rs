@@ -26,8 +26,9 @@ use g3_types::net::UpstreamAddr;
 
 use super::{StreamInspectContext, StreamInspection};
 use crate::auth::User;
+use crate::config::idle::{TaskIdlePolicy, TaskIdleProtocol};
 use crate::config::server::ServerConfig;
-use crate::serve::{ServerTaskError, ServerTaskForbiddenError, ServerTaskResult};
+use crate::serve::{ServerTaskError, ServerTaskForbiddenError, ServerTaskResult, TaskIdleCounter};
 
 mod object;
 pub(crate) use object::StreamInspectObject;
@@ -36,6 +37,9 @@ pub(crate) trait StreamTransitTask {
     fn copy_config(&self) -> LimitedCopyConfig;
     fn idle_check_interval(&self) -> Duration;
     fn max_idle_count(&self) -> i32;
+    fn idle_policy(&self) -> TaskIdlePolicy {
+        TaskIdlePolicy::default()
+    }
     fn log_periodic(&self);
     fn log_flush_interval(&self) -> Option<Duration>;
     fn quit_policy(&self) -> &ServerQuitPolicy;
@@ -72,7 +76,14 @@ pub(crate) trait StreamTransitTask {
         UR: AsyncRead + Unpin,
         UW: AsyncWrite + Unpin,
     {
-        let idle_duration = self.idle_check_interval();
+        let mut idle_counter = TaskIdleCounter::new(
+            TaskIdleProtocol::Tunnel,
+            self.idle_check_interval(),
+            self.max_idle_count(),
+            self.idle_policy(),
+            self.user(),
+        );
+        let idle_duration = idle_counter.idle_duration();
         let mut idle_interval =
             tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
         let mut log_interval = self
@@ -83,7 +94,6 @@ pub(crate) trait StreamTransitTask {
                 OptionalInterval::with(interval)
             })
             .unwrap_or_default();
-        let mut idle_count = 0;
         loop {
             tokio::select! {
                 biased;
@@ -108,34 +118,16 @@ pub(crate) trait StreamTransitTask {
                     self.log_periodic();
                 }
                 _ = idle_interval.tick() => {
-                    if clt_to_ups.is_idle() && ups_to_clt.is_idle() {
-                        idle_count += 1;
-
-                        let quit = if let Some(user) = self.user() {
-                            if user.is_blocked() {
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
-                            }
-                            idle_count >= user.task_max_idle_count()
-                        } else {
-                            idle_count >= self.max_idle_count()
-                        };
-
-                        if quit {
-                            return Err(ServerTaskError::Idle(idle_duration, idle_count));
-                        }
-                    } else {
-                        idle_count = 0;
-
-                        clt_to_ups.reset_active();
-                        ups_to_clt.reset_active();
-                    }
-
                     if let Some(user) = self.user() {
                         if user.is_blocked() {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
                     }
 
+                    idle_counter.check(clt_to_ups.is_idle(), ups_to_clt.is_idle())?;
+                    clt_to_ups.reset_active();
+                    ups_to_clt.reset_active();
+
                     if self.quit_policy().force_quit() {
                         return Err(ServerTaskError::CanceledAsServerQuit)
                     }
@@ -241,6 +233,53 @@ where
 
     pub(crate) async fn transit_transparent<CR, CW, UR, UW>(
         &self,
+        clt_r: CR,
+        clt_w: CW,
+        ups_r: UR,
+        ups_w: UW,
+    ) -> ServerTaskResult<()>
+    where
+        CR: AsyncRead + Unpin,
+        CW: AsyncWrite + Unpin,
+        UR: AsyncRead + Unpin,
+        UW: AsyncWrite + Unpin,
+    {
+        self.transit_transparent_with_idle_protocol(
+            TaskIdleProtocol::Tunnel,
+            clt_r,
+            clt_w,
+            ups_r,
+            ups_w,
+        )
+        .await
+    }
+
+    pub(crate) async fn transit_websocket<CR, CW, UR, UW>(
+        &self,
+        clt_r: CR,
+        clt_w: CW,
+        ups_r: UR,
+        ups_w: UW,
+    ) -> ServerTaskResult<()>
+    where
+        CR: AsyncRead + Unpin,
+        CW: AsyncWrite + Unpin,
+        UR: AsyncRead + Unpin,
+        UW: AsyncWrite + Unpin,
+    {
+        self.transit_transparent_with_idle_protocol(
+            TaskIdleProtocol::Websocket,
+            clt_r,
+            clt_w,
+            ups_r,
+            ups_w,
+        )
+        .await
+    }
+
+    async fn transit_transparent_with_idle_protocol<CR, CW, UR, UW>(
+        &self,
+        idle_protocol: TaskIdleProtocol,
         mut clt_r: CR,
         mut clt_w: CW,
         mut ups_r: UR,
@@ -256,10 +295,16 @@ where
         let mut clt_to_ups = LimitedCopy::new(&mut clt_r, &mut ups_w, &copy_config);
         let mut ups_to_clt = LimitedCopy::new(&mut ups_r, &mut clt_w, &copy_config);
 
-        let idle_duration = self.server_config.task_idle_check_duration();
+        let mut idle_counter = TaskIdleCounter::new(
+            idle_protocol,
+            self.server_config.task_idle_check_duration(),
+            self.server_config.task_max_idle_count(),
+            self.server_config.task_idle_policy(),
+            self.user().map(|u| u.as_ref()),
+        );
+        let idle_duration = idle_counter.idle_duration();
         let mut idle_interval =
             tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
-        let mut idle_count = 0;
         loop {
             tokio::select! {
                 biased;
@@ -281,34 +326,16 @@ where
                     };
                 }
                 _ = idle_interval.tick() => {
-                    if clt_to_ups.is_idle() && ups_to_clt.is_idle() {
-                        idle_count += 1;
-
-                        let quit = if let Some(user) = self.user() {
-                            if user.is_blocked() {
-                                return Err(ServerTaskError::CanceledAsUserBlocked);
-                            }
-                            idle_count >= user.task_max_idle_count()
-                        } else {
-                            idle_count >= self.server_config.task_max_idle_count()
-                        };
-
-                        if quit {
-                            return Err(ServerTaskError::Idle(idle_duration, idle_count));
-                        }
-                    } else {
-                        idle_count = 0;
-
-                        clt_to_ups.reset_active();
-                        ups_to_clt.reset_active();
-                    }
-
                     if let Some(user) = self.user() {
                         if user.is_blocked() {
                             return Err(ServerTaskError::CanceledAsUserBlocked);
                         }
                     }
 
+                    idle_counter.check(clt_to_ups.is_idle(), ups_to_clt.is_idle())?;
+                    clt_to_ups.reset_active();
+                    ups_to_clt.reset_active();
+
                     if self.server_quit_policy.force_quit() {
                         return Err(ServerTaskError::CanceledAsServerQuit)
                     }
@@ -384,6 +411,21 @@ where
                     }
                     None => break,
                 },
+                StreamInspection::Thrift(thrift) => {
+                    return thrift.intercept().await;
+                }
+                StreamInspection::Kafka(kafka) => {
+                    return kafka.intercept().await;
+                }
+                StreamInspection::Postgres(postgres) => {
+                    return postgres.intercept().await;
+                }
+                StreamInspection::Mysql(mysql) => {
+                    return mysql.intercept().await;
+                }
+                StreamInspection::Dns(dns) => {
+                    return dns.intercept().await;
+                }
                 StreamInspection::End => break,
             }
         }
@@ -14,6 +14,9 @@
  * limitations under the License.
  */
 
+use std::time::Instant;
+
+use anyhow::anyhow;
 use bytes::{Buf, BytesMut};
 use tokio::io::{AsyncRead, AsyncReadExt};
 
@@ -133,7 +136,17 @@ where
         };
 
         self.ctx.increase_inspection_depth();
-        StreamInspectLog::new(&self.ctx).log(InspectSource::StreamInspection, protocol);
+        let custom_signature = if protocol == Protocol::Custom {
+            inspector.custom_protocol().cloned()
+        } else {
+            None
+        };
+        if let Some(s) = &custom_signature {
+            StreamInspectLog::new(&self.ctx).log_custom(InspectSource::StreamInspection, s.name());
+        } else {
+            StreamInspectLog::new(&self.ctx).log(InspectSource::StreamInspection, protocol);
+        }
+        self.ctx.record_detected_protocol(protocol);
         match protocol {
             Protocol::Unknown => {
                 self.ctx
@@ -208,6 +221,49 @@ where
                 imap_obj.set_io(clt_r, clt_w, OnceBufReader::new(ups_r, ups_r_buf), ups_w);
                 return Ok(StreamInspection::Imap(imap_obj));
             }
+            Protocol::Thrift => {
+                let mut thrift_obj = crate::inspect::thrift::ThriftInspectObject::new(self.ctx);
+                thrift_obj.set_io(clt_r, clt_r_buf, clt_w, ups_r, ups_w);
+                return Ok(StreamInspection::Thrift(thrift_obj));
+            }
+            Protocol::Kafka => {
+                let mut kafka_obj = crate::inspect::kafka::KafkaInterceptObject::new(self.ctx);
+                kafka_obj.set_io(clt_r, clt_r_buf, clt_w, ups_r, ups_w);
+                return Ok(StreamInspection::Kafka(kafka_obj));
+            }
+            Protocol::Postgres => {
+                let mut postgres_obj = crate::inspect::postgres::PostgresInterceptObject::new(
+                    self.ctx,
+                    self.upstream.clone(),
+                );
+                postgres_obj.set_io(clt_r, clt_r_buf, clt_w, ups_r, ups_w);
+                return Ok(StreamInspection::Postgres(postgres_obj));
+            }
+            Protocol::Mysql => {
+                let mut mysql_obj = crate::inspect::mysql::MysqlInterceptObject::new(
+                    self.ctx,
+                    self.upstream.clone(),
+                );
+                mysql_obj.set_io(clt_r, clt_w, ups_r, ups_r_buf, ups_w);
+                return Ok(StreamInspection::Mysql(mysql_obj));
+            }
+            Protocol::Dns => {
+                let mut dns_obj = crate::inspect::dns::DnsInspectObject::new(self.ctx);
+                dns_obj.set_io(clt_r, clt_r_buf, clt_w, ups_r, ups_w);
+                return Ok(StreamInspection::Dns(dns_obj));
+            }
+            Protocol::Custom => {
+                if let Some(s) = custom_signature {
+                    let action = s.inspect_action();
+                    self.ctx.record_inspect_action(Protocol::Custom, action);
+                    if action.is_block() {
+                        return Err(ServerTaskError::InternalAdapterError(anyhow!(
+                            "custom protocol {} blocked by inspection policy",
+                            s.name()
+                        )));
+                    }
+                }
+            }
             _ => {}
         }
 
@@ -276,11 +332,15 @@ where
         CR: AsyncRead + Unpin,
     {
         loop {
-            match inspector.check_client_initial_data(
+            let time_start = Instant::now();
+            let r = inspector.check_client_initial_data(
                 self.ctx.protocol_inspection(),
                 self.upstream.port(),
                 clt_r_buf.chunk(),
-            ) {
+            );
+            self.ctx
+                .add_inspection_cost(time_start.elapsed(), clt_r_buf.remaining());
+            match r {
                 Ok(p) => return Ok(p),
                 Err(ProtocolInspectError::NeedMoreData(_)) => {
                     if clt_r_buf.remaining() == 0 {
@@ -306,11 +366,15 @@ where
         UR: AsyncRead + Unpin,
     {
         loop {
-            match inspector.check_server_initial_data(
+            let time_start = Instant::now();
+            let r = inspector.check_server_initial_data(
                 self.ctx.protocol_inspection(),
                 self.upstream.port(),
                 ups_r_buf.chunk(),
-            ) {
+            );
+            self.ctx
+                .add_inspection_cost(time_start.elapsed(), ups_r_buf.remaining());
+            match r {
                 Ok(p) => return Ok(p),
                 Err(ProtocolInspectError::NeedMoreData(_)) => {
                     if ups_r_buf.remaining() == 0 {
@@ -0,0 +1,132 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use bytes::{Buf, BytesMut};
+use tokio::io::{AsyncRead, ReadBuf};
+
+use g3_dpi::parser::thrift::{ThriftFramedObserver, ThriftMessageHeader};
+use g3_io_ext::OnceBufReader;
+
+use super::{BoxAsyncRead, BoxAsyncWrite, StreamInspectContext};
+use crate::config::server::ServerConfig;
+use crate::log::inspect::thrift::ThriftInspectLog;
+use crate::serve::ServerTaskResult;
+
+struct ThriftInspectIo {
+    clt_r: BoxAsyncRead,
+    clt_r_buf: BytesMut,
+    clt_w: BoxAsyncWrite,
+    ups_r: BoxAsyncRead,
+    ups_w: BoxAsyncWrite,
+}
+
+pub(crate) struct ThriftInspectObject<SC: ServerConfig> {
+    io: Option<ThriftInspectIo>,
+    ctx: StreamInspectContext<SC>,
+}
+
+impl<SC> ThriftInspectObject<SC>
+where
+    SC: ServerConfig + Send + Sync + 'static,
+{
+    pub(crate) fn new(ctx: StreamInspectContext<SC>) -> Self {
+        ThriftInspectObject { io: None, ctx }
+    }
+
+    pub(crate) fn set_io(
+        &mut self,
+        clt_r: BoxAsyncRead,
+        clt_r_buf: BytesMut,
+        clt_w: BoxAsyncWrite,
+        ups_r: BoxAsyncRead,
+        ups_w: BoxAsyncWrite,
+    ) {
+        self.io = Some(ThriftInspectIo {
+            clt_r,
+            clt_r_buf,
+            clt_w,
+            ups_r,
+            ups_w,
+        });
+    }
+
+    pub(crate) async fn intercept(mut self) -> ServerTaskResult<()> {
+        let ThriftInspectIo {
+            clt_r,
+            clt_r_buf,
+            clt_w,
+            ups_r,
+            ups_w,
+        } = self.io.take().unwrap();
+
+        let max_name_len = self
+            .ctx
+            .audit_handle
+            .protocol_inspection()
+            .size_limit()
+            .thrift_method_name();
+        let log = ThriftInspectLog::new(&self.ctx);
+
+        if let Ok(header) = ThriftMessageHeader::parse(clt_r_buf.chunk(), max_name_len) {
+            // the message boundary is unknown for unframed transport,
+            // so only the first message will be logged
+            log.log(&header);
+            return self
+                .ctx
+                .transit_transparent(OnceBufReader::new(clt_r, clt_r_buf), clt_w, ups_r, ups_w)
+                .await;
+        }
+
+        let clt_r = ThriftObserveReader {
+            inner: OnceBufReader::new(clt_r, clt_r_buf),
+            observer: ThriftFramedObserver::new(max_name_len),
+            log,
+        };
+        self.ctx
+            .transit_transparent(clt_r, clt_w, ups_r, ups_w)
+            .await
+    }
+}
+
+struct ThriftObserveReader<R> {
+    inner: R,
+    observer: ThriftFramedObserver,
+    log: ThriftInspectLog,
+}
+
+impl<R> AsyncRead for ThriftObserveReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let start = buf.filled().len();
+        ready!(Pin::new(&mut self.inner).poll_read(cx, buf))?;
+
+        let this = &mut *self;
+        let log = &this.log;
+        this.observer
+            .feed(&buf.filled()[start..], |header| log.log(header));
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,375 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use bytes::BytesMut;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use g3_dpi::parser::tls::{
+    ClientHello, ExtensionType, HandshakeCoalescer, Record, RecordParseError,
+};
+use g3_io_ext::OnceBufReader;
+use g3_types::net::{Host, TlsServerName};
+
+use super::{TlsInterceptIo, TlsInterceptObject, TlsInterceptionError};
+use crate::config::audit::TlsInterceptionFallbackConfig;
+use crate::config::server::ServerConfig;
+use crate::inspect::StreamInspection;
+
+/// The client which the bypass cache entry is bound to
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(super) enum BypassClient {
+    User(Arc<str>),
+    Ip(IpAddr),
+}
+
+struct BypassEntry {
+    failures: usize,
+    bypass: bool,
+    expire: Instant,
+}
+
+#[derive(Default)]
+struct CertAgentFailureState {
+    failures: usize,
+    last_failure: Option<Instant>,
+    hold_until: Option<Instant>,
+}
+
+pub(super) struct TlsInterceptionFallback {
+    config: TlsInterceptionFallbackConfig,
+    bypass_cache: Mutex<AHashMap<(BypassClient, String), BypassEntry>>,
+    cert_agent_failure: Mutex<CertAgentFailureState>,
+}
+
+impl TlsInterceptionFallback {
+    pub(super) fn new(config: TlsInterceptionFallbackConfig) -> Self {
+        TlsInterceptionFallback {
+            config,
+            bypass_cache: Mutex::new(AHashMap::new()),
+            cert_agent_failure: Mutex::new(CertAgentFailureState::default()),
+        }
+    }
+
+    fn check_passthrough(&self, client: BypassClient, host: String) -> Option<&'static str> {
+        if self.config.on_cert_agent_failure {
+            let mut state = self.cert_agent_failure.lock().unwrap();
+            if let Some(until) = state.hold_until {
+                if until > Instant::now() {
+                    return Some("cert agent failure");
+                }
+                state.hold_until = None;
+            }
+        }
+
+        if self.config.bypass_cache_enabled() {
+            let key = (client, host);
+            let mut cache = self.bypass_cache.lock().unwrap();
+            if let Some(entry) = cache.get(&key) {
+                if entry.expire > Instant::now() {
+                    if entry.bypass {
+                        return Some("bypass cache");
+                    }
+                } else {
+                    cache.remove(&key);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Record a failure for the client and host,
+    /// return true if the entry has just been turned into bypass state
+    fn add_bypass_failure(&self, client: BypassClient, host: String) -> bool {
+        if !self.config.bypass_cache_enabled() {
+            return false;
+        }
+
+        let now = Instant::now();
+        let key = (client, host);
+        let mut cache = self.bypass_cache.lock().unwrap();
+        if let Some(entry) = cache.get_mut(&key) {
+            if entry.expire > now {
+                if entry.bypass {
+                    return false;
+                }
+                entry.failures += 1;
+                if entry.failures >= self.config.bypass_failure_threshold {
+                    entry.bypass = true;
+                    entry.expire = now + self.config.bypass_cache_ttl;
+                    return true;
+                }
+                return false;
+            }
+            cache.remove(&key);
+        }
+
+        if cache.len() >= self.config.bypass_cache_max_entries {
+            cache.retain(|_, entry| entry.expire > now);
+            if cache.len() >= self.config.bypass_cache_max_entries {
+                return false;
+            }
+        }
+        let bypass = self.config.bypass_failure_threshold <= 1;
+        cache.insert(
+            key,
+            BypassEntry {
+                failures: 1,
+                bypass,
+                expire: now + self.config.bypass_cache_ttl,
+            },
+        );
+        bypass
+    }
+
+    fn add_cert_agent_failure(&self) {
+        let now = Instant::now();
+        let mut state = self.cert_agent_failure.lock().unwrap();
+        match state.last_failure {
+            Some(time) if now.duration_since(time) < self.config.cert_agent_failure_hold => {
+                state.failures += 1;
+            }
+            _ => state.failures = 1,
+        }
+        state.last_failure = Some(now);
+        if state.failures >= self.config.bypass_failure_threshold {
+            state.failures = 0;
+            state.hold_until = Some(now + self.config.cert_agent_failure_hold);
+        }
+    }
+
+    /// Update the fallback state based on the interception error,
+    /// return true if the host has been added to the bypass cache for this client
+    pub(super) fn record_error(
+        &self,
+        client: BypassClient,
+        host: String,
+        e: &TlsInterceptionError,
+        client_cert_sent: bool,
+    ) -> bool {
+        match e {
+            TlsInterceptionError::NoFakeCertGenerated(_) if self.config.on_cert_agent_failure => {
+                self.add_cert_agent_failure();
+                self.add_bypass_failure(client, host)
+            }
+            TlsInterceptionError::ClientHandshakeFailed(_)
+                if client_cert_sent && self.config.on_client_reject =>
+            {
+                // the client rejected the fake cert, most likely because of cert pinning
+                self.add_bypass_failure(client, host)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<SC> TlsInterceptObject<SC>
+where
+    SC: ServerConfig + Send + Sync + 'static,
+{
+    pub(super) fn bypass_client(&self) -> BypassClient {
+        match self.ctx.user() {
+            Some(user) => BypassClient::User(user.name().clone()),
+            None => BypassClient::Ip(self.ctx.task_notes.client_addr.ip()),
+        }
+    }
+
+    /// Check if we should fallback to passthrough before any handshake is made.
+    /// The client hello message will be read in advance and be replayed later.
+    pub(super) async fn fallback_passthrough(
+        &mut self,
+        fallback: &TlsInterceptionFallback,
+    ) -> Result<Option<StreamInspection<SC>>, TlsInterceptionError> {
+        let TlsInterceptIo {
+            clt_r,
+            clt_w,
+            ups_r,
+            ups_w,
+        } = self.io.take().unwrap();
+
+        let (buf, mut clt_r) = clt_r.into_parts();
+        let mut clt_r_buf = match buf {
+            Some(b) => BytesMut::from(b.as_ref()),
+            None => BytesMut::with_capacity(4096),
+        };
+
+        let accept_timeout = self.tls_interception.server_config.accept_timeout;
+        let sni = tokio::time::timeout(
+            accept_timeout,
+            read_client_hello_sni(
+                &mut clt_r,
+                &mut clt_r_buf,
+                fallback.config.max_client_hello_size,
+            ),
+        )
+        .await
+        .map_err(|_| TlsInterceptionError::ClientHandshakeTimeout)??;
+        if let Some(name) = sni {
+            self.upstream.set_host(Host::from(name));
+        }
+
+        let clt_r = OnceBufReader::new(clt_r, clt_r_buf);
+        let host = self.upstream.host().to_string();
+        match fallback.check_passthrough(self.bypass_client(), host) {
+            Some(reason) => {
+                self.log_passthrough(reason);
+                let mut stream_obj = crate::inspect::stream::StreamInspectObject::new(
+                    self.ctx.clone(),
+                    self.upstream.clone(),
+                );
+                stream_obj.set_io(Box::new(clt_r), clt_w, ups_r, ups_w);
+                Ok(Some(StreamInspection::StreamUnknown(stream_obj)))
+            }
+            None => {
+                self.io = Some(TlsInterceptIo {
+                    clt_r,
+                    clt_w,
+                    ups_r,
+                    ups_w,
+                });
+                Ok(None)
+            }
+        }
+    }
+}
+
+async fn read_client_hello_sni<R>(
+    clt_r: &mut R,
+    clt_r_buf: &mut BytesMut,
+    max_client_hello_size: u32,
+) -> Result<Option<TlsServerName>, TlsInterceptionError>
+where
+    R: AsyncRead + Unpin,
+{
+    let mut handshake_coalescer = HandshakeCoalescer::new(max_client_hello_size);
+    let mut record_offset = 0;
+    loop {
+        let mut record = match Record::parse(&clt_r_buf[record_offset..]) {
+            Ok(r) => r,
+            Err(RecordParseError::NeedMoreData(_)) => match clt_r.read_buf(clt_r_buf).await {
+                Ok(0) => {
+                    return Err(TlsInterceptionError::ClientHandshakeFailed(anyhow!(
+                        "connection closed by client before client hello msg received"
+                    )));
+                }
+                Ok(_) => continue,
+                Err(e) => {
+                    return Err(TlsInterceptionError::ClientHandshakeFailed(anyhow!(
+                        "read client hello msg failed: {e}"
+                    )));
+                }
+            },
+            // leave the error to the real handshake
+            Err(_) => return Ok(None),
+        };
+        record_offset += record.encoded_len();
+
+        match record.consume_handshake(&mut handshake_coalescer) {
+            Ok(Some(handshake_msg)) => {
+                return Ok(handshake_msg.parse_client_hello().ok().and_then(get_sni));
+            }
+            Ok(None) => match handshake_coalescer.parse_client_hello() {
+                Ok(Some(ch)) => return Ok(get_sni(ch)),
+                Ok(None) => {
+                    if !record.consume_done() {
+                        return Ok(None);
+                    }
+                }
+                Err(_) => return Ok(None),
+            },
+            Err(_) => return Ok(None),
+        }
+    }
+}
+
+fn get_sni(ch: ClientHello) -> Option<TlsServerName> {
+    let data = ch.get_ext(ExtensionType::ServerName).ok()??;
+    TlsServerName::from_extension_value(data).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    fn client_reject() -> TlsInterceptionError {
+        TlsInterceptionError::ClientHandshakeFailed(anyhow!("rejected"))
+    }
+
+    #[test]
+    fn disabled_by_default() {
+        let fallback = TlsInterceptionFallback::new(TlsInterceptionFallbackConfig::default());
+        let client = BypassClient::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        for _ in 0..10 {
+            assert!(!fallback.record_error(
+                client.clone(),
+                "example.net".to_string(),
+                &client_reject(),
+                true
+            ));
+        }
+        assert!(fallback
+            .check_passthrough(client, "example.net".to_string())
+            .is_none());
+    }
+
+    #[test]
+    fn bypass_per_client() {
+        let config = TlsInterceptionFallbackConfig {
+            on_client_reject: true,
+            ..Default::default()
+        };
+        let fallback = TlsInterceptionFallback::new(config);
+        let client1 = BypassClient::User(Arc::from("user1"));
+        let client2 = BypassClient::User(Arc::from("user2"));
+        let host = "example.net".to_string();
+
+        assert!(!fallback.record_error(client1.clone(), host.clone(), &client_reject(), true));
+        assert!(!fallback.record_error(client1.clone(), host.clone(), &client_reject(), true));
+        assert!(fallback
+            .check_passthrough(client1.clone(), host.clone())
+            .is_none());
+        assert!(fallback.record_error(client1.clone(), host.clone(), &client_reject(), true));
+        assert_eq!(
+            fallback.check_passthrough(client1, host.clone()),
+            Some("bypass cache")
+        );
+        assert!(fallback.check_passthrough(client2, host).is_none());
+    }
+
+    #[test]
+    fn no_bypass_before_cert_sent() {
+        let config = TlsInterceptionFallbackConfig {
+            on_client_reject: true,
+            bypass_failure_threshold: 1,
+            ..Default::default()
+        };
+        let fallback = TlsInterceptionFallback::new(config);
+        let client = BypassClient::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert!(!fallback.record_error(
+            client.clone(),
+            "example.net".to_string(),
+            &client_reject(),
+            false
+        ));
+        assert!(fallback.record_error(client, "example.net".to_string(), &client_reject(), true));
+    }
+}
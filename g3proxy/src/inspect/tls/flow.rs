@@ -0,0 +1,135 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use slog::{slog_info, Logger};
+use uuid::Uuid;
+
+use g3_io_ext::{LimitedReaderStats, LimitedWriterStats};
+use g3_slog_types::{LtIpAddr, LtUuid};
+use g3_types::net::UpstreamAddr;
+
+#[derive(Clone, Copy)]
+pub(super) enum TlsFlowVerdict {
+    Unknown,
+    Intercept,
+    Passthrough,
+    Fail,
+}
+
+impl TlsFlowVerdict {
+    fn as_str(&self) -> &'static str {
+        match self {
+            TlsFlowVerdict::Unknown => "unknown",
+            TlsFlowVerdict::Intercept => "intercept",
+            TlsFlowVerdict::Passthrough => "passthrough",
+            TlsFlowVerdict::Fail => "fail",
+        }
+    }
+}
+
+struct TlsFlowState {
+    verdict: TlsFlowVerdict,
+    reason: Option<String>,
+    upstream: UpstreamAddr,
+}
+
+/// Record of a TLS connection, which will be sent to the flow log channel when dropped
+pub(super) struct TlsFlowRecord {
+    logger: Logger,
+    task_id: Uuid,
+    client_addr: SocketAddr,
+    user: Option<Arc<str>>,
+    state: Mutex<TlsFlowState>,
+    clt_read_bytes: AtomicU64,
+    clt_write_bytes: AtomicU64,
+}
+
+impl TlsFlowRecord {
+    pub(super) fn new(
+        logger: Logger,
+        task_id: Uuid,
+        client_addr: SocketAddr,
+        user: Option<Arc<str>>,
+        upstream: UpstreamAddr,
+    ) -> Self {
+        TlsFlowRecord {
+            logger,
+            task_id,
+            client_addr,
+            user,
+            state: Mutex::new(TlsFlowState {
+                verdict: TlsFlowVerdict::Unknown,
+                reason: None,
+                upstream,
+            }),
+            clt_read_bytes: AtomicU64::new(0),
+            clt_write_bytes: AtomicU64::new(0),
+        }
+    }
+
+    pub(super) fn set_verdict(
+        &self,
+        verdict: TlsFlowVerdict,
+        upstream: &UpstreamAddr,
+        reason: Option<String>,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        state.verdict = verdict;
+        state.reason = reason;
+        state.upstream = upstream.clone();
+    }
+}
+
+impl LimitedReaderStats for TlsFlowRecord {
+    fn add_read_bytes(&self, size: usize) {
+        self.clt_read_bytes
+            .fetch_add(size as u64, Ordering::Relaxed);
+    }
+}
+
+impl LimitedWriterStats for TlsFlowRecord {
+    fn add_write_bytes(&self, size: usize) {
+        self.clt_write_bytes
+            .fetch_add(size as u64, Ordering::Relaxed);
+    }
+}
+
+impl Drop for TlsFlowRecord {
+    fn drop(&mut self) {
+        if crate::log::flow::skip_log() {
+            return;
+        }
+
+        let state = self.state.get_mut().unwrap();
+        // use CEF extension key names, so it can be mapped directly by the SIEM
+        slog_info!(self.logger, "TlsFlow";
+            "externalId" => LtUuid(&self.task_id),
+            "src" => LtIpAddr(self.client_addr.ip()),
+            "spt" => self.client_addr.port(),
+            "dhost" => state.upstream.host().to_string(),
+            "dpt" => state.upstream.port(),
+            "suser" => self.user.as_deref(),
+            "act" => state.verdict.as_str(),
+            "reason" => state.reason.as_deref(),
+            "in" => *self.clt_read_bytes.get_mut(),
+            "out" => *self.clt_write_bytes.get_mut(),
+        );
+    }
+}
@@ -17,6 +17,7 @@
 use std::sync::Arc;
 
 use anyhow::anyhow;
+use bytes::BytesMut;
 use openssl::x509::X509VerifyResult;
 use slog::slog_info;
 use tokio::io::{AsyncRead, AsyncWrite};
@@ -24,20 +25,29 @@ use tokio::runtime::Handle;
 
 use g3_cert_agent::CertAgentHandle;
 use g3_dpi::Protocol;
-use g3_io_ext::{AsyncStream, FlexBufReader, OnceBufReader};
-use g3_slog_types::{LtUpstreamAddr, LtUuid, LtX509VerifyResult};
+use g3_io_ext::{
+    AsyncStream, FlexBufReader, LimitedReader, LimitedReaderStats, LimitedWriter, OnceBufReader,
+};
+use g3_slog_types::{LtMetricsTags, LtUpstreamAddr, LtUuid, LtX509VerifyResult};
 use g3_types::net::{
     AlpnProtocol, OpensslInterceptionClientConfig, OpensslInterceptionServerConfig, UpstreamAddr,
 };
 use g3_udpdump::{ExportedPduDissectorHint, StreamDumpConfig, StreamDumper};
 
 use super::{BoxAsyncRead, BoxAsyncWrite, StreamInspectContext, StreamInspection};
+use crate::config::audit::TlsInterceptionFallbackConfig;
 use crate::config::server::ServerConfig;
 use crate::log::inspect::{stream::StreamInspectLog, InspectSource};
 
 mod error;
 pub(crate) use error::TlsInterceptionError;
 
+mod fallback;
+use fallback::TlsInterceptionFallback;
+
+mod flow;
+use flow::{TlsFlowRecord, TlsFlowVerdict};
+
 mod modern;
 #[cfg(feature = "vendored-tongsuo")]
 mod tlcp;
@@ -48,6 +58,7 @@ pub(crate) struct TlsInterceptionContext {
     pub(super) client_config: Arc<OpensslInterceptionClientConfig>,
     pub(super) server_config: Arc<OpensslInterceptionServerConfig>,
     stream_dumper: Arc<Vec<StreamDumper>>,
+    fallback: Option<Arc<TlsInterceptionFallback>>,
 }
 
 impl TlsInterceptionContext {
@@ -56,6 +67,7 @@ impl TlsInterceptionContext {
         client_config: OpensslInterceptionClientConfig,
         server_config: OpensslInterceptionServerConfig,
         dump_config: Option<StreamDumpConfig>,
+        fallback_config: Option<TlsInterceptionFallbackConfig>,
     ) -> anyhow::Result<Self> {
         let mut stream_dumper = Vec::new();
         if let Some(dump) = dump_config {
@@ -85,6 +97,7 @@ impl TlsInterceptionContext {
             client_config: Arc::new(client_config),
             server_config: Arc::new(server_config),
             stream_dumper: Arc::new(stream_dumper),
+            fallback: fallback_config.map(|c| Arc::new(TlsInterceptionFallback::new(c))),
         })
     }
 
@@ -116,6 +129,7 @@ pub(crate) struct TlsInterceptObject<SC: ServerConfig> {
     upstream: UpstreamAddr,
     tls_interception: TlsInterceptionContext,
     server_verify_result: Option<X509VerifyResult>,
+    flow: Option<Arc<TlsFlowRecord>>,
 }
 
 macro_rules! intercept_log {
@@ -124,6 +138,7 @@ macro_rules! intercept_log {
             "intercept_type" => "TlsHandshake",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "upstream" => LtUpstreamAddr(&$obj.upstream),
             "tls_server_verify" => $obj.server_verify_result.map(LtX509VerifyResult),
         )
@@ -136,12 +151,24 @@ impl<SC: ServerConfig> TlsInterceptObject<SC> {
         upstream: UpstreamAddr,
         tls: TlsInterceptionContext,
     ) -> Self {
+        let flow = ctx.flow_logger().map(|logger| {
+            Arc::new(TlsFlowRecord::new(
+                logger.clone(),
+                *ctx.server_task_id(),
+                ctx.task_notes.client_addr,
+                ctx.raw_user_name()
+                    .or_else(|| ctx.user().map(|u| u.name()))
+                    .cloned(),
+                upstream.clone(),
+            ))
+        });
         TlsInterceptObject {
             io: None,
             ctx,
             upstream,
             tls_interception: tls,
             server_verify_result: None,
+            flow,
         }
     }
 
@@ -152,6 +179,26 @@ impl<SC: ServerConfig> TlsInterceptObject<SC> {
         ups_r: BoxAsyncRead,
         ups_w: BoxAsyncWrite,
     ) {
+        let (clt_r, clt_w) = match &self.flow {
+            Some(flow) => {
+                // count the client side bytes for the flow log
+                let (buf, clt_r) = clt_r.into_parts();
+                let buf = match buf {
+                    Some(b) => {
+                        flow.add_read_bytes(b.len());
+                        BytesMut::from(b.as_ref())
+                    }
+                    None => BytesMut::new(),
+                };
+                let clt_r = LimitedReader::new(clt_r, flow.clone());
+                let clt_w = LimitedWriter::new(clt_w, flow.clone());
+                (
+                    OnceBufReader::new(Box::new(clt_r) as BoxAsyncRead, buf),
+                    Box::new(clt_w) as BoxAsyncWrite,
+                )
+            }
+            None => (clt_r, clt_w),
+        };
         let io = TlsInterceptIo {
             clt_r,
             clt_w,
@@ -163,10 +210,31 @@ impl<SC: ServerConfig> TlsInterceptObject<SC> {
 
     fn log_ok(&self) {
         intercept_log!(self, "ok");
+        if let Some(flow) = &self.flow {
+            flow.set_verdict(TlsFlowVerdict::Intercept, &self.upstream, None);
+        }
     }
 
     fn log_err(&self, e: &TlsInterceptionError) {
         intercept_log!(self, "{e}");
+        if let Some(flow) = &self.flow {
+            flow.set_verdict(TlsFlowVerdict::Fail, &self.upstream, Some(e.to_string()));
+        }
+    }
+
+    fn log_passthrough(&self, reason: &str) {
+        intercept_log!(self, "passthrough: {reason}");
+        if let Some(flow) = &self.flow {
+            flow.set_verdict(
+                TlsFlowVerdict::Passthrough,
+                &self.upstream,
+                Some(reason.to_string()),
+            );
+        }
+    }
+
+    fn log_bypass_added(&self) {
+        intercept_log!(self, "added to bypass cache");
     }
 
     fn retain_alpn_protocol(&self, p: &[u8]) -> bool {
@@ -218,11 +286,15 @@ where
             } else {
                 ExportedPduDissectorHint::TlsPort(self.upstream.port())
             };
+            let metadata = stream_dumper
+                .metadata_enabled()
+                .then(|| self.ctx.stream_dump_metadata(protocol));
             if stream_dumper.client_side() {
                 let (clt_r, clt_w) = stream_dumper.wrap_client_io(
                     self.ctx.task_notes.client_addr,
                     self.ctx.task_notes.server_addr,
                     dissector_hint,
+                    metadata.as_ref(),
                     clt_r,
                     clt_w,
                 );
@@ -232,6 +304,7 @@ where
                     self.ctx.task_notes.client_addr,
                     self.ctx.task_notes.server_addr,
                     dissector_hint,
+                    metadata.as_ref(),
                     ups_r,
                     ups_w,
                 );
@@ -304,6 +377,22 @@ where
                 );
                 StreamInspection::Imap(imap_obj)
             }
+            Protocol::Postgres => {
+                // direct TLS negotiation, no SSLRequest will be sent inside
+                let mut postgres_obj = crate::inspect::postgres::PostgresInterceptObject::new(
+                    ctx,
+                    self.upstream.clone(),
+                );
+                postgres_obj.set_direct_tls();
+                postgres_obj.set_io(
+                    Box::new(clt_r),
+                    BytesMut::new(),
+                    Box::new(clt_w),
+                    Box::new(ups_r),
+                    Box::new(ups_w),
+                );
+                StreamInspection::Postgres(postgres_obj)
+            }
             _ => {
                 let mut stream_obj =
                     crate::inspect::stream::StreamInspectObject::new(ctx, self.upstream.clone());
@@ -41,6 +41,20 @@ where
         mut self,
         inspector: &mut ProtocolInspector,
     ) -> ServerTaskResult<StreamInspection<SC>> {
+        let fallback = self.tls_interception.fallback.clone();
+        if let Some(fallback) = &fallback {
+            match self.fallback_passthrough(fallback).await {
+                Ok(Some(obj)) => return Ok(obj),
+                Ok(None) => {}
+                Err(e) => {
+                    self.log_err(&e);
+                    return Err(
+                        InterceptionError::Tls(e).into_server_task_error(Protocol::TlsModern)
+                    );
+                }
+            }
+        }
+
         match self.do_intercept_modern(inspector).await {
             Ok(obj) => {
                 self.log_ok();
@@ -48,6 +62,18 @@ where
             }
             Err(e) => {
                 self.log_err(&e);
+                if let Some(fallback) = &fallback {
+                    // the server verify result will only be set after the fake cert is ready
+                    let client_cert_sent = self.server_verify_result.is_some();
+                    if fallback.record_error(
+                        self.bypass_client(),
+                        self.upstream.host().to_string(),
+                        &e,
+                        client_cert_sent,
+                    ) {
+                        self.log_bypass_added();
+                    }
+                }
                 Err(InterceptionError::Tls(e).into_server_task_error(Protocol::TlsModern))
             }
         }
@@ -18,12 +18,12 @@ use anyhow::anyhow;
 use slog::slog_info;
 use tokio::io::AsyncWriteExt;
 
-use g3_dpi::ProtocolInspectAction;
+use g3_dpi::{Protocol, ProtocolInspectAction};
 use g3_io_ext::LimitedWriteExt;
-use g3_slog_types::{LtHttpHeaderValue, LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtHttpHeaderValue, LtMetricsTags, LtUpstreamAddr, LtUuid};
 use g3_types::net::{UpstreamAddr, WebSocketNotes};
 
-use super::{ClientCloseFrame, ServerCloseFrame};
+use super::{ClientCloseFrame, ServerCloseFrame, WebsocketFrameRelay};
 #[cfg(feature = "quic")]
 use crate::audit::DetourAction;
 use crate::config::server::ServerConfig;
@@ -36,6 +36,7 @@ macro_rules! intercept_log {
             "intercept_type" => "H1Websocket",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "upstream" => LtUpstreamAddr(&$obj.upstream),
             "ws_resource_name" => $obj.ws_notes.resource_name(),
             "ws_origin" => $obj.ws_notes.origin().map(LtHttpHeaderValue),
@@ -90,7 +91,9 @@ impl<SC: ServerConfig> H1WebsocketInterceptObject<SC> {
     }
 
     pub(crate) async fn intercept(mut self) -> ServerTaskResult<()> {
-        let r = match self.ctx.websocket_inspect_action(self.upstream.host()) {
+        let action = self.ctx.websocket_inspect_action(self.upstream.host());
+        self.ctx.record_inspect_action(Protocol::Websocket, action);
+        let r = match action {
             ProtocolInspectAction::Intercept => self.do_intercept().await,
             #[cfg(feature = "quic")]
             ProtocolInspectAction::Detour => self.do_detour().await,
@@ -192,9 +195,7 @@ impl<SC: ServerConfig> H1WebsocketInterceptObject<SC> {
             ups_w,
         } = self.io.take().unwrap();
 
-        self.ctx
-            .transit_transparent(clt_r, clt_w, ups_r, ups_w)
-            .await
+        self.ctx.transit_websocket(clt_r, clt_w, ups_r, ups_w).await
     }
 
     async fn do_block(&mut self) -> ServerTaskResult<()> {
@@ -230,8 +231,13 @@ impl<SC: ServerConfig> H1WebsocketInterceptObject<SC> {
             ups_w,
         } = self.io.take().unwrap();
 
-        self.ctx
-            .transit_transparent(clt_r, clt_w, ups_r, ups_w)
-            .await
+        let config = self.ctx.websocket_interception();
+        if config.need_frame_relay() {
+            WebsocketFrameRelay::new(&self.ctx, &self.upstream, &self.ws_notes, config)
+                .relay(clt_r, clt_w, ups_r, ups_w)
+                .await
+        } else {
+            self.ctx.transit_websocket(clt_r, clt_w, ups_r, ups_w).await
+        }
     }
 }
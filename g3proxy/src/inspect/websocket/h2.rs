@@ -19,12 +19,12 @@ use bytes::Bytes;
 use h2::{RecvStream, SendStream};
 use slog::slog_info;
 
-use g3_dpi::ProtocolInspectAction;
+use g3_dpi::{Protocol, ProtocolInspectAction};
 use g3_h2::{H2StreamReader, H2StreamWriter};
-use g3_slog_types::{LtHttpHeaderValue, LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtHttpHeaderValue, LtMetricsTags, LtUpstreamAddr, LtUuid};
 use g3_types::net::{UpstreamAddr, WebSocketNotes};
 
-use super::{ClientCloseFrame, ServerCloseFrame};
+use super::{ClientCloseFrame, ServerCloseFrame, WebsocketFrameRelay};
 #[cfg(feature = "quic")]
 use crate::audit::DetourAction;
 use crate::config::server::ServerConfig;
@@ -37,6 +37,7 @@ macro_rules! intercept_log {
             "intercept_type" => "H2Websocket",
             "task_id" => LtUuid($obj.ctx.server_task_id()),
             "depth" => $obj.ctx.inspection_depth,
+            "request_tags" => $obj.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "upstream" => LtUpstreamAddr(&$obj.upstream),
             "ws_resource_name" => $obj.ws_notes.resource_name(),
             "ws_origin" => $obj.ws_notes.origin().map(LtHttpHeaderValue),
@@ -74,7 +75,9 @@ impl<SC: ServerConfig> H2WebsocketInterceptObject<SC> {
         ups_r: RecvStream,
         ups_w: SendStream<Bytes>,
     ) {
-        let r = match self.ctx.websocket_inspect_action(self.upstream.host()) {
+        let action = self.ctx.websocket_inspect_action(self.upstream.host());
+        self.ctx.record_inspect_action(Protocol::Websocket, action);
+        let r = match action {
             ProtocolInspectAction::Intercept => self.do_intercept(clt_r, clt_w, ups_r, ups_w).await,
             #[cfg(feature = "quic")]
             ProtocolInspectAction::Detour => self.do_detour(clt_r, clt_w, ups_r, ups_w).await,
@@ -172,9 +175,7 @@ impl<SC: ServerConfig> H2WebsocketInterceptObject<SC> {
         let ups_r = H2StreamReader::new(ups_r);
         let ups_w = H2StreamWriter::new(ups_w);
 
-        self.ctx
-            .transit_transparent(clt_r, clt_w, ups_r, ups_w)
-            .await
+        self.ctx.transit_websocket(clt_r, clt_w, ups_r, ups_w).await
     }
 
     async fn do_block(
@@ -204,8 +205,13 @@ impl<SC: ServerConfig> H2WebsocketInterceptObject<SC> {
         let ups_r = H2StreamReader::new(ups_r);
         let ups_w = H2StreamWriter::new(ups_w);
 
-        self.ctx
-            .transit_transparent(clt_r, clt_w, ups_r, ups_w)
-            .await
+        let config = self.ctx.websocket_interception();
+        if config.need_frame_relay() {
+            WebsocketFrameRelay::new(&self.ctx, &self.upstream, &self.ws_notes, config)
+                .relay(clt_r, clt_w, ups_r, ups_w)
+                .await
+        } else {
+            self.ctx.transit_websocket(clt_r, clt_w, ups_r, ups_w).await
+        }
     }
 }
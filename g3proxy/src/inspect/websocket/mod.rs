@@ -17,6 +17,9 @@
 mod close;
 use close::{ClientCloseFrame, ServerCloseFrame};
 
+mod relay;
+use relay::WebsocketFrameRelay;
+
 mod h1;
 pub(crate) use h1::H1WebsocketInterceptObject;
 
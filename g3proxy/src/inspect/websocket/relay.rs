@@ -0,0 +1,418 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::pin::pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::anyhow;
+use bytes::{Buf, BytesMut};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::time::Instant;
+
+use g3_dpi::parser::websocket::{WebsocketFrameHeader, WebsocketOpcode, WebsocketParseError};
+use g3_dpi::WebsocketInterceptionConfig;
+use g3_icap_client::reqmod::websocket::WebsocketAdaptationEndState;
+use g3_io_ext::LimitedWriteExt;
+use g3_types::net::{UpstreamAddr, WebSocketNotes};
+
+use super::{ClientCloseFrame, ServerCloseFrame};
+use crate::config::idle::TaskIdleProtocol;
+use crate::config::server::ServerConfig;
+use crate::inspect::StreamInspectContext;
+use crate::log::inspect::websocket::WebsocketInspectLog;
+use crate::serve::{ServerTaskError, ServerTaskResult, TaskIdleCounter};
+
+const RELAY_BUFFER_SIZE: usize = 16384;
+
+/// close code for message too big
+const CLOSE_CODE_TOO_BIG: u16 = 1009;
+/// close code for policy violation
+const CLOSE_CODE_POLICY: u16 = 1008;
+
+enum FrameRelayError {
+    Task(ServerTaskError),
+    Blocked(u16, &'static str),
+}
+
+impl From<ServerTaskError> for FrameRelayError {
+    fn from(e: ServerTaskError) -> Self {
+        FrameRelayError::Task(e)
+    }
+}
+
+struct ScanMessage {
+    text: bool,
+    frames: Vec<u8>,
+    payload: Vec<u8>,
+}
+
+/// Relay websocket data frame by frame, so we can log, limit or scan the messages
+pub(super) struct WebsocketFrameRelay<'a, SC: ServerConfig> {
+    ctx: &'a StreamInspectContext<SC>,
+    upstream: &'a UpstreamAddr,
+    ws_notes: &'a WebSocketNotes,
+    config: &'a WebsocketInterceptionConfig,
+    log: WebsocketInspectLog,
+}
+
+impl<'a, SC: ServerConfig> WebsocketFrameRelay<'a, SC> {
+    pub(super) fn new(
+        ctx: &'a StreamInspectContext<SC>,
+        upstream: &'a UpstreamAddr,
+        ws_notes: &'a WebSocketNotes,
+        config: &'a WebsocketInterceptionConfig,
+    ) -> Self {
+        WebsocketFrameRelay {
+            ctx,
+            upstream,
+            ws_notes,
+            config,
+            log: WebsocketInspectLog::new(ctx),
+        }
+    }
+
+    pub(super) async fn relay<CR, CW, UR, UW>(
+        &self,
+        mut clt_r: CR,
+        mut clt_w: CW,
+        mut ups_r: UR,
+        mut ups_w: UW,
+    ) -> ServerTaskResult<()>
+    where
+        CR: AsyncRead + Unpin,
+        CW: AsyncWrite + Unpin,
+        UR: AsyncRead + Unpin,
+        UW: AsyncWrite + Unpin,
+    {
+        let r = {
+            let clt_active = AtomicBool::new(false);
+            let ups_active = AtomicBool::new(false);
+            let mut clt_to_ups = pin!(self.relay_frames(true, &mut clt_r, &mut ups_w, &clt_active));
+            let mut ups_to_clt =
+                pin!(self.relay_frames(false, &mut ups_r, &mut clt_w, &ups_active));
+
+            let mut idle_counter = TaskIdleCounter::new(
+                TaskIdleProtocol::Websocket,
+                self.ctx.server_config.task_idle_check_duration(),
+                self.ctx.server_config.task_max_idle_count(),
+                self.ctx.server_config.task_idle_policy(),
+                self.ctx.user().map(|u| u.as_ref()),
+            );
+            let idle_duration = idle_counter.idle_duration();
+            let mut idle_interval =
+                tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
+            loop {
+                tokio::select! {
+                    biased;
+
+                    r = &mut clt_to_ups => break r,
+                    r = &mut ups_to_clt => break r,
+                    _ = idle_interval.tick() => {
+                        if let Some(user) = self.ctx.user() {
+                            if user.is_blocked() {
+                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            }
+                        }
+
+                        let clt_idle = !clt_active.swap(false, Ordering::Relaxed);
+                        let ups_idle = !ups_active.swap(false, Ordering::Relaxed);
+                        idle_counter.check(clt_idle, ups_idle)?;
+
+                        if self.ctx.server_quit_policy.force_quit() {
+                            return Err(ServerTaskError::CanceledAsServerQuit)
+                        }
+                    }
+                }
+            }
+        };
+
+        match r {
+            Ok(_) => Ok(()),
+            Err(FrameRelayError::Task(e)) => Err(e),
+            Err(FrameRelayError::Blocked(code, reason)) => {
+                let server_close = ServerCloseFrame::encode_with_status_code(code);
+                let client_close = ClientCloseFrame::encode_with_status_code(code);
+                if ups_w.write_all_flush(&client_close).await.is_ok() {
+                    let _ = ups_w.shutdown().await;
+                }
+                if clt_w.write_all_flush(&server_close).await.is_ok() {
+                    let _ = clt_w.shutdown().await;
+                }
+                Err(ServerTaskError::InternalAdapterError(anyhow!(
+                    "websocket message blocked: {reason}"
+                )))
+            }
+        }
+    }
+
+    fn read_error(from_client: bool, e: std::io::Error) -> FrameRelayError {
+        if from_client {
+            FrameRelayError::Task(ServerTaskError::ClientTcpReadFailed(e))
+        } else {
+            FrameRelayError::Task(ServerTaskError::UpstreamReadFailed(e))
+        }
+    }
+
+    fn write_error(from_client: bool, e: std::io::Error) -> FrameRelayError {
+        if from_client {
+            FrameRelayError::Task(ServerTaskError::UpstreamWriteFailed(e))
+        } else {
+            FrameRelayError::Task(ServerTaskError::ClientTcpWriteFailed(e))
+        }
+    }
+
+    fn closed_error(from_client: bool) -> FrameRelayError {
+        if from_client {
+            FrameRelayError::Task(ServerTaskError::ClosedByClient)
+        } else {
+            FrameRelayError::Task(ServerTaskError::ClosedByUpstream)
+        }
+    }
+
+    async fn fill_buf<R>(
+        from_client: bool,
+        reader: &mut R,
+        buf: &mut BytesMut,
+        active: &AtomicBool,
+    ) -> Result<(), FrameRelayError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        buf.reserve(RELAY_BUFFER_SIZE);
+        match reader.read_buf(buf).await {
+            Ok(0) => Err(Self::closed_error(from_client)),
+            Ok(_) => {
+                active.store(true, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(e) => Err(Self::read_error(from_client, e)),
+        }
+    }
+
+    async fn relay_frames<R, W>(
+        &self,
+        from_client: bool,
+        reader: &mut R,
+        writer: &mut W,
+        active: &AtomicBool,
+    ) -> Result<(), FrameRelayError>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let icap_scan =
+            self.config.icap_scan && self.ctx.audit_handle.icap_reqmod_client().is_some();
+        let mut buf = BytesMut::with_capacity(RELAY_BUFFER_SIZE);
+        let mut message_size: u64 = 0;
+        let mut scan_message: Option<ScanMessage> = None;
+
+        loop {
+            let header = loop {
+                match WebsocketFrameHeader::parse(&buf) {
+                    Ok(h) => break h,
+                    Err(WebsocketParseError::NeedMoreData(_)) => {
+                        Self::fill_buf(from_client, reader, &mut buf, active).await?;
+                    }
+                    Err(_) => {
+                        return Err(if from_client {
+                            FrameRelayError::Task(ServerTaskError::InvalidClientProtocol(
+                                "invalid websocket frame",
+                            ))
+                        } else {
+                            FrameRelayError::Task(ServerTaskError::InvalidUpstreamProtocol(
+                                "invalid websocket frame",
+                            ))
+                        });
+                    }
+                }
+            };
+            if self.config.log_frames {
+                self.log.log_frame(from_client, &header);
+            }
+
+            if !header.opcode.is_control() {
+                if header.opcode != WebsocketOpcode::Continuation {
+                    message_size = 0;
+                    if icap_scan {
+                        scan_message = Some(ScanMessage {
+                            text: header.opcode == WebsocketOpcode::Text,
+                            frames: Vec::new(),
+                            payload: Vec::new(),
+                        });
+                    }
+                }
+                message_size += header.payload_len;
+                if self.config.max_message_size > 0 && message_size > self.config.max_message_size {
+                    let reason = "message size exceeds limit";
+                    self.log.log_blocked(from_client, message_size, reason);
+                    return Err(FrameRelayError::Blocked(CLOSE_CODE_TOO_BIG, reason));
+                }
+
+                if let Some(s) = &scan_message {
+                    if message_size > self.config.icap_scan_max_size as u64 {
+                        // too large to scan, forward the buffered frames and skip the scan
+                        writer
+                            .write_all(&s.frames)
+                            .await
+                            .map_err(|e| Self::write_error(from_client, e))?;
+                        scan_message = None;
+                    }
+                }
+
+                if let Some(s) = &mut scan_message {
+                    self.buffer_frame(from_client, reader, &mut buf, &header, s, active)
+                        .await?;
+                    if header.fin {
+                        let s = scan_message.take().unwrap();
+                        self.scan_message(from_client, &s).await?;
+                        writer
+                            .write_all_flush(&s.frames)
+                            .await
+                            .map_err(|e| Self::write_error(from_client, e))?;
+                    }
+                    continue;
+                }
+            }
+
+            let frame_size = header.encoded_len as u64 + header.payload_len;
+            self.forward_frame(from_client, reader, writer, &mut buf, frame_size, active)
+                .await?;
+        }
+    }
+
+    async fn forward_frame<R, W>(
+        &self,
+        from_client: bool,
+        reader: &mut R,
+        writer: &mut W,
+        buf: &mut BytesMut,
+        mut frame_size: u64,
+        active: &AtomicBool,
+    ) -> Result<(), FrameRelayError>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        loop {
+            let len = frame_size.min(buf.len() as u64) as usize;
+            if len > 0 {
+                writer
+                    .write_all(&buf[..len])
+                    .await
+                    .map_err(|e| Self::write_error(from_client, e))?;
+                buf.advance(len);
+                frame_size -= len as u64;
+            }
+            if frame_size == 0 {
+                return writer
+                    .flush()
+                    .await
+                    .map_err(|e| Self::write_error(from_client, e));
+            }
+            Self::fill_buf(from_client, reader, buf, active).await?;
+        }
+    }
+
+    async fn buffer_frame<R>(
+        &self,
+        from_client: bool,
+        reader: &mut R,
+        buf: &mut BytesMut,
+        header: &WebsocketFrameHeader,
+        scan_message: &mut ScanMessage,
+        active: &AtomicBool,
+    ) -> Result<(), FrameRelayError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        scan_message
+            .frames
+            .extend_from_slice(&buf[..header.encoded_len]);
+        buf.advance(header.encoded_len);
+
+        let payload_start = scan_message.payload.len();
+        let mut left = header.payload_len;
+        loop {
+            let len = left.min(buf.len() as u64) as usize;
+            if len > 0 {
+                scan_message.frames.extend_from_slice(&buf[..len]);
+                scan_message.payload.extend_from_slice(&buf[..len]);
+                buf.advance(len);
+                left -= len as u64;
+            }
+            if left == 0 {
+                break;
+            }
+            Self::fill_buf(from_client, reader, buf, active).await?;
+        }
+        header.unmask(&mut scan_message.payload[payload_start..], 0);
+        Ok(())
+    }
+
+    async fn scan_message(
+        &self,
+        from_client: bool,
+        message: &ScanMessage,
+    ) -> Result<(), FrameRelayError> {
+        let Some(client) = self.ctx.audit_handle.icap_reqmod_client() else {
+            return Ok(());
+        };
+
+        let mut adapter = match client.websocket_message_adaptor().await {
+            Ok(adapter) => adapter,
+            Err(e) => {
+                return if client.bypass() {
+                    Ok(())
+                } else {
+                    Err(FrameRelayError::Task(
+                        ServerTaskError::InternalAdapterError(e),
+                    ))
+                };
+            }
+        };
+        adapter.set_client_addr(self.ctx.task_notes.client_addr);
+        if let Some(username) = self.ctx.raw_user_name() {
+            adapter.set_client_username(username.clone());
+        }
+
+        let http_header = adapter.build_http_header(
+            self.upstream,
+            self.ws_notes.resource_name(),
+            from_client,
+            message.text,
+            message.payload.len(),
+        );
+        match adapter.scan_message(&http_header, &message.payload).await {
+            Ok(WebsocketAdaptationEndState::Allowed) => Ok(()),
+            Ok(WebsocketAdaptationEndState::Blocked(_rsp)) => {
+                let reason = "message blocked by icap server";
+                self.log
+                    .log_blocked(from_client, message.payload.len() as u64, reason);
+                Err(FrameRelayError::Blocked(CLOSE_CODE_POLICY, reason))
+            }
+            Err(e) => {
+                if client.bypass() {
+                    Ok(())
+                } else {
+                    Err(FrameRelayError::Task(
+                        ServerTaskError::InternalAdapterError(anyhow!(
+                            "websocket message adaptation failed: {e}"
+                        )),
+                    ))
+                }
+            }
+        }
+    }
+}
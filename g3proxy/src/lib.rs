@@ -16,10 +16,12 @@
 
 pub mod audit;
 pub mod auth;
+pub mod capacity;
 pub mod config;
 pub mod control;
 pub mod escape;
 pub mod opts;
+pub mod preflight;
 pub mod resolve;
 pub mod serve;
 pub mod signal;
@@ -29,3 +31,4 @@ mod build;
 mod inspect;
 mod log;
 mod module;
+mod reload;
@@ -0,0 +1,51 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::IpAddr;
+
+use slog::{slog_info, Logger};
+use uuid::Uuid;
+
+use g3_geoip_types::IpLocation;
+use g3_slog_types::{LtIpAddr, LtUpstreamAddr, LtUuid};
+use g3_types::acl::AclAction;
+use g3_types::net::UpstreamAddr;
+
+pub(crate) struct EscapeLogForEgressLocation<'a> {
+    pub(crate) task_id: &'a Uuid,
+    pub(crate) user: &'a str,
+    pub(crate) upstream: &'a UpstreamAddr,
+    pub(crate) peer_ip: IpAddr,
+    pub(crate) location: &'a IpLocation,
+}
+
+impl EscapeLogForEgressLocation<'_> {
+    pub(crate) fn log(&self, logger: &Logger, action: AclAction) {
+        let country = self.location.country();
+        let continent = self.location.continent().or(country.map(|c| c.continent()));
+        slog_info!(logger, "egress location matched";
+            "escape_type" => "EgressLocation",
+            "task_id" => LtUuid(self.task_id),
+            "user" => self.user,
+            "upstream" => LtUpstreamAddr(self.upstream),
+            "next_peer_ip" => LtIpAddr(self.peer_ip),
+            "next_peer_country" => country.map(|c| c.alpha2_code()),
+            "next_peer_continent" => continent.map(|c| c.code()),
+            "next_peer_asn" => self.location.network_asn(),
+            "action" => action.to_string(),
+        )
+    }
+}
@@ -18,11 +18,13 @@ use slog::{slog_o, Logger};
 
 use g3_types::metrics::NodeName;
 
+pub(crate) mod egress_location;
 pub(crate) mod tcp_connect;
 pub(crate) mod tls_handshake;
 pub(crate) mod udp_sendto;
 
 use super::shared::SharedLoggerType;
+use crate::config::tenant::TenantMemberType;
 
 pub(crate) fn get_logger(escaper_type: &str, escaper_name: &NodeName) -> Logger {
     let config = crate::config::log::get_escape_default_config();
@@ -34,15 +36,18 @@ pub(crate) fn get_logger(escaper_type: &str, escaper_name: &NodeName) -> Logger
         "escaper_type" => escaper_type.to_string(),
         "escaper_name" => escaper_name.to_string(),
     );
-    config.build_logger(logger_name, super::LOG_TYPE_ESCAPE, common_values)
+    let logger = config.build_logger(logger_name, super::LOG_TYPE_ESCAPE, common_values);
+    super::add_tenant_value(logger, TenantMemberType::Escaper, escaper_name)
 }
 
 pub(crate) fn get_shared_logger(name: &str, escaper_type: &str, escaper_name: &NodeName) -> Logger {
     let logger_name = format!("le-{name}");
-    super::shared::get_shared_logger(SharedLoggerType::Escape, logger_name, |logger| {
-        logger.new(slog_o!(
-            "escaper_type" => escaper_type.to_string(),
-            "escaper_name" => escaper_name.to_string(),
-        ))
-    })
+    let logger =
+        super::shared::get_shared_logger(SharedLoggerType::Escape, logger_name, |logger| {
+            logger.new(slog_o!(
+                "escaper_type" => escaper_type.to_string(),
+                "escaper_name" => escaper_name.to_string(),
+            ))
+        });
+    super::add_tenant_value(logger, TenantMemberType::Escaper, escaper_name)
 }
@@ -36,10 +36,12 @@ impl EscapeLogForTcpConnect<'_> {
             "upstream" => LtUpstreamAddr(self.upstream),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
+            "next_egress_ip" => self.tcp_notes.egress.as_ref().and_then(|e| e.ip()).map(LtIpAddr),
             "next_peer_addr" => self.tcp_notes.next,
             "next_expire" => self.tcp_notes.expire.as_ref().map(LtDateTime),
             "tcp_connect_tries" => self.tcp_notes.tries,
             "tcp_connect_spend" => LtDuration(self.tcp_notes.duration),
+            "tcp_connect_timeout" => self.tcp_notes.connect_timeout.map(LtDuration),
             "reason" => e.brief(),
         )
     }
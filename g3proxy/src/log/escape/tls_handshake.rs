@@ -17,7 +17,7 @@
 use slog::{slog_info, Logger};
 use uuid::Uuid;
 
-use g3_slog_types::{LtDateTime, LtHost, LtIpAddr, LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtDateTime, LtDuration, LtHost, LtIpAddr, LtUpstreamAddr, LtUuid};
 use g3_types::net::{Host, UpstreamAddr};
 
 use crate::module::tcp_connect::TcpConnectTaskNotes;
@@ -55,11 +55,13 @@ impl EscapeLogForTlsHandshake<'_> {
             "upstream" => LtUpstreamAddr(self.upstream),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
+            "next_egress_ip" => self.tcp_notes.egress.as_ref().and_then(|e| e.ip()).map(LtIpAddr),
             "next_peer_addr" => self.tcp_notes.next,
             "next_expire" => self.tcp_notes.expire.as_ref().map(LtDateTime),
             "tls_name" => LtHost(self.tls_name),
             "tls_peer" => LtUpstreamAddr(self.tls_peer),
             "tls_application" => self.tls_application.as_str(),
+            "tls_handshake_timeout" => self.tcp_notes.tls_handshake_timeout.map(LtDuration),
         )
     }
 }
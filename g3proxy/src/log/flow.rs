@@ -0,0 +1,50 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::LazyLock;
+
+use governor::{clock::DefaultClock, state::InMemoryState, state::NotKeyed, RateLimiter};
+use slog::{slog_o, Logger};
+
+use g3_types::metrics::NodeName;
+
+static FLOW_LOG_RATE_LIMITER: LazyLock<Option<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>> =
+    LazyLock::new(|| {
+        crate::config::log::get_flow_rate_limit()
+            .map(|quota| RateLimiter::direct(quota.get_inner()))
+    });
+
+/// Get the flow logger, which will be None if the flow log channel is not configured
+pub(crate) fn get_logger(auditor_name: &NodeName) -> Option<Logger> {
+    let config = crate::config::log::get_flow_config()?;
+    let logger_name = format!("lf-{auditor_name}");
+    let common_values = slog_o!(
+        "daemon_name" => crate::opts::daemon_group(),
+        "log_type" => super::LOG_TYPE_FLOW,
+        "pid" => std::process::id(),
+        "auditor_name" => auditor_name.to_string(),
+    );
+    Some(config.build_logger(logger_name, super::LOG_TYPE_FLOW, common_values))
+}
+
+/// Check the flow log rate limit, which is shared by all auditors
+pub(crate) fn skip_log() -> bool {
+    if let Some(limiter) = &*FLOW_LOG_RATE_LIMITER {
+        limiter.check().is_err()
+    } else {
+        false
+    }
+}
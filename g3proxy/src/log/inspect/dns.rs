@@ -0,0 +1,70 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use slog::{slog_info, Logger};
+use uuid::Uuid;
+
+use g3_dpi::parser::dns::{record_type, DnsQuestion};
+use g3_slog_types::{LtMetricsTags, LtUuid};
+use g3_types::metrics::StaticMetricsTags;
+
+use crate::config::server::ServerConfig;
+use crate::inspect::StreamInspectContext;
+
+pub(crate) struct DnsQueryScore<'a> {
+    pub(crate) domain: &'a str,
+    pub(crate) entropy: f64,
+    pub(crate) queries: usize,
+    pub(crate) txt_queries: usize,
+    pub(crate) score: u8,
+}
+
+pub(crate) struct DnsInspectLog {
+    logger: Logger,
+    task_id: Uuid,
+    depth: usize,
+    request_tags: Option<Arc<StaticMetricsTags>>,
+}
+
+impl DnsInspectLog {
+    pub(crate) fn new<SC: ServerConfig>(ctx: &StreamInspectContext<SC>) -> Self {
+        DnsInspectLog {
+            logger: ctx.inspect_logger().clone(),
+            task_id: *ctx.server_task_id(),
+            depth: ctx.current_inspection_depth(),
+            request_tags: ctx.request_tags().cloned(),
+        }
+    }
+
+    pub(crate) fn log(&self, question: &DnsQuestion, score: &DnsQueryScore<'_>, blocked: bool) {
+        slog_info!(self.logger, "";
+            "task_id" => LtUuid(&self.task_id),
+            "depth" => self.depth,
+            "request_tags" => self.request_tags.as_deref().map(LtMetricsTags),
+            "protocol" => "dns",
+            "query_name" => &question.name,
+            "query_type" => record_type::name(question.qtype),
+            "domain" => score.domain,
+            "entropy" => format!("{:.2}", score.entropy),
+            "domain_queries" => score.queries,
+            "domain_txt_queries" => score.txt_queries,
+            "tunnel_score" => score.score,
+            "blocked" => blocked,
+        )
+    }
+}
@@ -18,7 +18,11 @@ use slog::Logger;
 
 use g3_types::metrics::NodeName;
 
+pub(crate) mod dns;
+pub(crate) mod kafka;
 pub(crate) mod stream;
+pub(crate) mod thrift;
+pub(crate) mod websocket;
 
 pub(crate) enum InspectSource {
     StreamInspection,
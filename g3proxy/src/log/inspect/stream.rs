@@ -17,7 +17,7 @@
 use slog::slog_info;
 
 use g3_dpi::Protocol;
-use g3_slog_types::LtUuid;
+use g3_slog_types::{LtMetricsTags, LtUuid};
 
 use super::InspectSource;
 use crate::config::server::ServerConfig;
@@ -36,8 +36,20 @@ impl<'a, SC: ServerConfig> StreamInspectLog<'a, SC> {
         slog_info!(self.ctx.inspect_logger(), "";
             "task_id" => LtUuid(self.ctx.server_task_id()),
             "depth" => self.ctx.current_inspection_depth(),
+            "request_tags" => self.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
             "source" => source.as_str(),
             "protocol" => protocol.as_str(),
         )
     }
+
+    pub(crate) fn log_custom(&self, source: InspectSource, name: &str) {
+        slog_info!(self.ctx.inspect_logger(), "";
+            "task_id" => LtUuid(self.ctx.server_task_id()),
+            "depth" => self.ctx.current_inspection_depth(),
+            "request_tags" => self.ctx.request_tags().map(|t| LtMetricsTags(t.as_ref())),
+            "source" => source.as_str(),
+            "protocol" => Protocol::Custom.as_str(),
+            "custom_protocol" => name,
+        )
+    }
 }
@@ -0,0 +1,78 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::Arc;
+
+use slog::{slog_info, Logger};
+use uuid::Uuid;
+
+use g3_dpi::parser::websocket::WebsocketFrameHeader;
+use g3_slog_types::{LtMetricsTags, LtUuid};
+use g3_types::metrics::StaticMetricsTags;
+
+use crate::config::server::ServerConfig;
+use crate::inspect::StreamInspectContext;
+
+pub(crate) struct WebsocketInspectLog {
+    logger: Logger,
+    task_id: Uuid,
+    depth: usize,
+    request_tags: Option<Arc<StaticMetricsTags>>,
+}
+
+fn direction(from_client: bool) -> &'static str {
+    if from_client {
+        "client_to_server"
+    } else {
+        "server_to_client"
+    }
+}
+
+impl WebsocketInspectLog {
+    pub(crate) fn new<SC: ServerConfig>(ctx: &StreamInspectContext<SC>) -> Self {
+        WebsocketInspectLog {
+            logger: ctx.inspect_logger().clone(),
+            task_id: *ctx.server_task_id(),
+            depth: ctx.current_inspection_depth(),
+            request_tags: ctx.request_tags().cloned(),
+        }
+    }
+
+    pub(crate) fn log_frame(&self, from_client: bool, header: &WebsocketFrameHeader) {
+        slog_info!(self.logger, "";
+            "task_id" => LtUuid(&self.task_id),
+            "depth" => self.depth,
+            "request_tags" => self.request_tags.as_deref().map(LtMetricsTags),
+            "protocol" => "websocket",
+            "direction" => direction(from_client),
+            "opcode" => header.opcode.as_str(),
+            "fin" => header.fin,
+            "masked" => header.mask_key.is_some(),
+            "payload_length" => header.payload_len,
+        )
+    }
+
+    pub(crate) fn log_blocked(&self, from_client: bool, message_size: u64, reason: &str) {
+        slog_info!(self.logger, "{}", reason;
+            "task_id" => LtUuid(&self.task_id),
+            "depth" => self.depth,
+            "request_tags" => self.request_tags.as_deref().map(LtMetricsTags),
+            "protocol" => "websocket",
+            "direction" => direction(from_client),
+            "message_size" => message_size,
+            "blocked" => true,
+        )
+    }
+}
@@ -14,10 +14,17 @@
  * limitations under the License.
  */
 
+use slog::{slog_o, Logger};
+
+use g3_types::metrics::NodeName;
+
+use crate::config::tenant::TenantMemberType;
+
 mod shared;
 
 pub(crate) mod audit;
 pub(crate) mod escape;
+pub(crate) mod flow;
 pub(crate) mod inspect;
 pub(crate) mod intercept;
 pub(crate) mod resolve;
@@ -28,3 +35,11 @@ const LOG_TYPE_ESCAPE: &str = "Escape";
 const LOG_TYPE_RESOLVE: &str = "Resolve";
 const LOG_TYPE_INSPECT: &str = "Inspect";
 const LOG_TYPE_INTERCEPT: &str = "Intercept";
+const LOG_TYPE_FLOW: &str = "Flow";
+
+fn add_tenant_value(logger: Logger, member_type: TenantMemberType, name: &NodeName) -> Logger {
+    match crate::config::tenant::get_tenant_of(member_type, name) {
+        Some(tenant) => logger.new(slog_o!("tenant" => tenant.to_string())),
+        None => logger,
+    }
+}
@@ -17,7 +17,8 @@
 use slog::{slog_info, Logger};
 
 use g3_slog_types::{
-    LtDateTime, LtDuration, LtHttpMethod, LtHttpUri, LtIpAddr, LtUpstreamAddr, LtUuid,
+    LtDateTime, LtDuration, LtHttpMethod, LtHttpUri, LtIpAddr, LtMetricsTags, LtUpstreamAddr,
+    LtUuid,
 };
 
 use super::TaskEvent;
@@ -51,6 +52,7 @@ impl TaskLogForFtpOverHttp<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.ftp_notes.upstream()),
@@ -75,10 +77,12 @@ impl TaskLogForFtpOverHttp<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.ftp_notes.upstream()),
             "escaper" => self.ftp_notes.control_tcp_notes.escaper.as_str(),
+            "route_path" => self.ftp_notes.control_tcp_notes.route_path.log_value(&self.ftp_notes.control_tcp_notes.escaper),
             "next_bind_ip" => self.ftp_notes.control_tcp_notes.bind.ip().map(LtIpAddr),
             "next_expire" => self.ftp_notes.control_tcp_notes.expire.as_ref().map(LtDateTime),
             "ftp_c_bound_addr" => self.ftp_notes.control_tcp_notes.local,
@@ -110,10 +114,12 @@ impl TaskLogForFtpOverHttp<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.ftp_notes.upstream()),
             "escaper" => self.ftp_notes.control_tcp_notes.escaper.as_str(),
+            "route_path" => self.ftp_notes.control_tcp_notes.route_path.log_value(&self.ftp_notes.control_tcp_notes.escaper),
             "next_bind_ip" => self.ftp_notes.control_tcp_notes.bind.ip().map(LtIpAddr),
             "next_expire" => self.ftp_notes.control_tcp_notes.expire.as_ref().map(LtDateTime),
             "ftp_c_bound_addr" => self.ftp_notes.control_tcp_notes.local,
@@ -154,10 +160,12 @@ impl TaskLogForFtpOverHttp<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.ftp_notes.upstream()),
             "escaper" => self.ftp_notes.control_tcp_notes.escaper.as_str(),
+            "route_path" => self.ftp_notes.control_tcp_notes.route_path.log_value(&self.ftp_notes.control_tcp_notes.escaper),
             "next_bind_ip" => self.ftp_notes.control_tcp_notes.bind.ip().map(LtIpAddr),
             "next_expire" => self.ftp_notes.control_tcp_notes.expire.as_ref().map(LtDateTime),
             "ftp_c_bound_addr" => self.ftp_notes.control_tcp_notes.local,
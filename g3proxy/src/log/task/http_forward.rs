@@ -17,9 +17,10 @@
 use slog::{slog_info, Logger};
 
 use g3_slog_types::{
-    LtDateTime, LtDuration, LtHttpMethod, LtHttpUri, LtIpAddr, LtUpstreamAddr, LtUuid,
+    LtDateTime, LtDuration, LtHttpMethod, LtHttpUri, LtIpAddr, LtMetricsTags, LtUpstreamAddr,
+    LtUuid,
 };
-use g3_types::net::UpstreamAddr;
+use g3_types::net::{OpensslCertIdentity, UpstreamAddr};
 
 use super::TaskEvent;
 use crate::module::http_forward::HttpForwardTaskNotes;
@@ -31,6 +32,7 @@ pub(crate) struct TaskLogForHttpForward<'a> {
     pub(crate) task_notes: &'a ServerTaskNotes,
     pub(crate) http_notes: &'a HttpForwardTaskNotes,
     pub(crate) http_user_agent: Option<&'a str>,
+    pub(crate) client_cert: Option<&'a OpensslCertIdentity>,
     pub(crate) tcp_notes: &'a TcpConnectTaskNotes,
     pub(crate) client_rd_bytes: u64,
     pub(crate) client_wr_bytes: u64,
@@ -53,8 +55,11 @@ impl TaskLogForHttpForward<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
+            "client_cert_subject" => self.client_cert.map(|c| c.subject()),
+            "client_cert_sha256" => self.client_cert.map(|c| c.fingerprint()),
             "upstream" => LtUpstreamAddr(self.upstream),
             "pipeline_wait" => LtDuration(self.http_notes.pipeline_wait),
             "method" => LtHttpMethod(&self.http_notes.method),
@@ -78,18 +83,24 @@ impl TaskLogForHttpForward<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
+            "client_cert_subject" => self.client_cert.map(|c| c.subject()),
+            "client_cert_sha256" => self.client_cert.map(|c| c.fingerprint()),
             "upstream" => LtUpstreamAddr(self.upstream),
             "escaper" => self.tcp_notes.escaper.as_str(),
+            "route_path" => self.tcp_notes.route_path.log_value(&self.tcp_notes.escaper),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
+            "next_egress_ip" => self.tcp_notes.egress.as_ref().and_then(|e| e.ip()).map(LtIpAddr),
             "next_peer_addr" => self.tcp_notes.next,
             "next_expire" => self.tcp_notes.expire.as_ref().map(LtDateTime),
             "tcp_connect_tries" => self.tcp_notes.tries,
             "tcp_connect_spend" => LtDuration(self.tcp_notes.duration),
             "pipeline_wait" => LtDuration(self.http_notes.pipeline_wait),
             "reuse_connection" => self.http_notes.reused_connection,
+            "retry_count" => self.http_notes.retry_count,
             "method" => LtHttpMethod(&self.http_notes.method),
             "uri" => LtHttpUri::new(&self.http_notes.uri, self.http_notes.uri_log_max_chars),
             "user_agent" => self.http_user_agent,
@@ -112,18 +123,24 @@ impl TaskLogForHttpForward<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
+            "client_cert_subject" => self.client_cert.map(|c| c.subject()),
+            "client_cert_sha256" => self.client_cert.map(|c| c.fingerprint()),
             "upstream" => LtUpstreamAddr(self.upstream),
             "escaper" => self.tcp_notes.escaper.as_str(),
+            "route_path" => self.tcp_notes.route_path.log_value(&self.tcp_notes.escaper),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
+            "next_egress_ip" => self.tcp_notes.egress.as_ref().and_then(|e| e.ip()).map(LtIpAddr),
             "next_peer_addr" => self.tcp_notes.next,
             "next_expire" => self.tcp_notes.expire.as_ref().map(LtDateTime),
             "tcp_connect_tries" => self.tcp_notes.tries,
             "tcp_connect_spend" => LtDuration(self.tcp_notes.duration),
             "pipeline_wait" => LtDuration(self.http_notes.pipeline_wait),
             "reuse_connection" => self.http_notes.reused_connection,
+            "retry_count" => self.http_notes.retry_count,
             "method" => LtHttpMethod(&self.http_notes.method),
             "uri" => LtHttpUri::new(&self.http_notes.uri, self.http_notes.uri_log_max_chars),
             "user_agent" => self.http_user_agent,
@@ -157,12 +174,17 @@ impl TaskLogForHttpForward<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
+            "client_cert_subject" => self.client_cert.map(|c| c.subject()),
+            "client_cert_sha256" => self.client_cert.map(|c| c.fingerprint()),
             "upstream" => LtUpstreamAddr(self.upstream),
             "escaper" => self.tcp_notes.escaper.as_str(),
+            "route_path" => self.tcp_notes.route_path.log_value(&self.tcp_notes.escaper),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
+            "next_egress_ip" => self.tcp_notes.egress.as_ref().and_then(|e| e.ip()).map(LtIpAddr),
             "next_peer_addr" => self.tcp_notes.next,
             "next_expire" => self.tcp_notes.expire.as_ref().map(LtDateTime),
             "tcp_connect_tries" => self.tcp_notes.tries,
@@ -170,6 +192,7 @@ impl TaskLogForHttpForward<'_> {
             "reason" => e.brief(),
             "pipeline_wait" => LtDuration(self.http_notes.pipeline_wait),
             "reuse_connection" => self.http_notes.reused_connection,
+            "retry_count" => self.http_notes.retry_count,
             "method" => LtHttpMethod(&self.http_notes.method),
             "uri" => LtHttpUri::new(&self.http_notes.uri, self.http_notes.uri_log_max_chars),
             "user_agent" => self.http_user_agent,
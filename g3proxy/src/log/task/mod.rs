@@ -25,6 +25,7 @@ pub(crate) mod udp_associate;
 pub(crate) mod udp_connect;
 
 use super::shared::SharedLoggerType;
+use crate::config::tenant::TenantMemberType;
 
 pub(crate) fn get_logger(server_type: &str, server_name: &NodeName) -> Logger {
     let config = crate::config::log::get_task_default_config();
@@ -36,17 +37,19 @@ pub(crate) fn get_logger(server_type: &str, server_name: &NodeName) -> Logger {
         "server_type" => server_type.to_string(),
         "server_name" => server_name.to_string(),
     );
-    config.build_logger(logger_name, super::LOG_TYPE_TASK, common_values)
+    let logger = config.build_logger(logger_name, super::LOG_TYPE_TASK, common_values);
+    super::add_tenant_value(logger, TenantMemberType::Server, server_name)
 }
 
 pub(crate) fn get_shared_logger(name: &str, server_type: &str, server_name: &NodeName) -> Logger {
     let logger_name = format!("lt-{name}");
-    super::shared::get_shared_logger(SharedLoggerType::Task, logger_name, |logger| {
+    let logger = super::shared::get_shared_logger(SharedLoggerType::Task, logger_name, |logger| {
         logger.new(slog_o!(
             "server_type" => server_type.to_string(),
             "server_name" => server_name.to_string(),
         ))
-    })
+    });
+    super::add_tenant_value(logger, TenantMemberType::Server, server_name)
 }
 
 enum TaskEvent {
@@ -16,7 +16,7 @@
 
 use slog::{slog_info, Logger};
 
-use g3_slog_types::{LtDateTime, LtDuration, LtIpAddr, LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtDateTime, LtDuration, LtIpAddr, LtMetricsTags, LtUpstreamAddr, LtUuid};
 use g3_types::net::UpstreamAddr;
 
 use super::TaskEvent;
@@ -48,6 +48,7 @@ impl TaskLogForTcpConnect<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
@@ -69,12 +70,15 @@ impl TaskLogForTcpConnect<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
             "escaper" => self.tcp_notes.escaper.as_str(),
+            "route_path" => self.tcp_notes.route_path.log_value(&self.tcp_notes.escaper),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
+            "next_egress_ip" => self.tcp_notes.egress.as_ref().and_then(|e| e.ip()).map(LtIpAddr),
             "next_peer_addr" => self.tcp_notes.next,
             "next_expire" => self.tcp_notes.expire.as_ref().map(LtDateTime),
             "tcp_connect_tries" => self.tcp_notes.tries,
@@ -98,12 +102,15 @@ impl TaskLogForTcpConnect<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
             "escaper" => self.tcp_notes.escaper.as_str(),
+            "route_path" => self.tcp_notes.route_path.log_value(&self.tcp_notes.escaper),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
+            "next_egress_ip" => self.tcp_notes.egress.as_ref().and_then(|e| e.ip()).map(LtIpAddr),
             "next_peer_addr" => self.tcp_notes.next,
             "next_expire" => self.tcp_notes.expire.as_ref().map(LtDateTime),
             "tcp_connect_tries" => self.tcp_notes.tries,
@@ -132,12 +139,15 @@ impl TaskLogForTcpConnect<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
             "upstream" => LtUpstreamAddr(self.upstream),
             "escaper" => self.tcp_notes.escaper.as_str(),
+            "route_path" => self.tcp_notes.route_path.log_value(&self.tcp_notes.escaper),
             "next_bind_ip" => self.tcp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.tcp_notes.local,
+            "next_egress_ip" => self.tcp_notes.egress.as_ref().and_then(|e| e.ip()).map(LtIpAddr),
             "next_peer_addr" => self.tcp_notes.next,
             "next_expire" => self.tcp_notes.expire.as_ref().map(LtDateTime),
             "tcp_connect_tries" => self.tcp_notes.tries,
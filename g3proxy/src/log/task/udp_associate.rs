@@ -18,7 +18,7 @@ use std::net::SocketAddr;
 
 use slog::{slog_info, Logger};
 
-use g3_slog_types::{LtDateTime, LtDuration, LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtDateTime, LtDuration, LtMetricsTags, LtUpstreamAddr, LtUuid};
 use g3_types::net::UpstreamAddr;
 
 use super::TaskEvent;
@@ -58,6 +58,7 @@ impl TaskLogForUdpAssociate<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "tcp_server_addr" => self.tcp_server_addr,
             "tcp_client_addr" => self.tcp_client_addr,
             "wait_time" => LtDuration(self.task_notes.wait_time),
@@ -78,12 +79,14 @@ impl TaskLogForUdpAssociate<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "tcp_server_addr" => self.tcp_server_addr,
             "tcp_client_addr" => self.tcp_client_addr,
             "udp_listen_addr" => self.udp_listen_addr,
             "udp_client_addr" => self.udp_client_addr,
             "initial_peer" => LtUpstreamAddr(self.initial_peer),
             "escaper" => self.udp_notes.escaper.as_str(),
+            "route_path" => self.udp_notes.route_path.log_value(&self.udp_notes.escaper),
             "wait_time" => LtDuration(self.task_notes.wait_time),
             "ready_time" => LtDuration(self.task_notes.ready_time),
             "c_rd_bytes" => self.client_rd_bytes,
@@ -105,12 +108,14 @@ impl TaskLogForUdpAssociate<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "tcp_server_addr" => self.tcp_server_addr,
             "tcp_client_addr" => self.tcp_client_addr,
             "udp_listen_addr" => self.udp_listen_addr,
             "udp_client_addr" => self.udp_client_addr,
             "initial_peer" => LtUpstreamAddr(self.initial_peer),
             "escaper" => self.udp_notes.escaper.as_str(),
+            "route_path" => self.udp_notes.route_path.log_value(&self.udp_notes.escaper),
             "wait_time" => LtDuration(self.task_notes.wait_time),
             "ready_time" => LtDuration(self.task_notes.ready_time),
             "total_time" => LtDuration(self.task_notes.time_elapsed()),
@@ -139,12 +144,14 @@ impl TaskLogForUdpAssociate<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "tcp_server_addr" => self.tcp_server_addr,
             "tcp_client_addr" => self.tcp_client_addr,
             "udp_listen_addr" => self.udp_listen_addr,
             "udp_client_addr" => self.udp_client_addr,
             "initial_peer" => LtUpstreamAddr(self.initial_peer),
             "escaper" => self.udp_notes.escaper.as_str(),
+            "route_path" => self.udp_notes.route_path.log_value(&self.udp_notes.escaper),
             "reason" => e.brief(),
             "wait_time" => LtDuration(self.task_notes.wait_time),
             "ready_time" => LtDuration(self.task_notes.ready_time),
@@ -18,7 +18,7 @@ use std::net::SocketAddr;
 
 use slog::{slog_info, Logger};
 
-use g3_slog_types::{LtDateTime, LtDuration, LtIpAddr, LtUpstreamAddr, LtUuid};
+use g3_slog_types::{LtDateTime, LtDuration, LtIpAddr, LtMetricsTags, LtUpstreamAddr, LtUuid};
 use g3_types::net::UpstreamAddr;
 
 use super::TaskEvent;
@@ -58,6 +58,7 @@ impl TaskLogForUdpConnect<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "tcp_server_addr" => self.tcp_server_addr,
             "tcp_client_addr" => self.tcp_client_addr,
             "wait_time" => LtDuration(self.task_notes.wait_time),
@@ -78,12 +79,14 @@ impl TaskLogForUdpConnect<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "tcp_server_addr" => self.tcp_server_addr,
             "tcp_client_addr" => self.tcp_client_addr,
             "udp_listen_addr" => self.udp_listen_addr,
             "udp_client_addr" => self.udp_client_addr,
             "upstream" => self.upstream.map(LtUpstreamAddr),
             "escaper" => self.udp_notes.escaper.as_str(),
+            "route_path" => self.udp_notes.route_path.log_value(&self.udp_notes.escaper),
             "next_bind_ip" => self.udp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.udp_notes.local,
             "next_peer_addr" => self.udp_notes.next,
@@ -109,12 +112,14 @@ impl TaskLogForUdpConnect<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "tcp_server_addr" => self.tcp_server_addr,
             "tcp_client_addr" => self.tcp_client_addr,
             "udp_listen_addr" => self.udp_listen_addr,
             "udp_client_addr" => self.udp_client_addr,
             "upstream" => self.upstream.map(LtUpstreamAddr),
             "escaper" => self.udp_notes.escaper.as_str(),
+            "route_path" => self.udp_notes.route_path.log_value(&self.udp_notes.escaper),
             "next_bind_ip" => self.udp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.udp_notes.local,
             "next_peer_addr" => self.udp_notes.next,
@@ -147,12 +152,14 @@ impl TaskLogForUdpConnect<'_> {
             "stage" => self.task_notes.stage.brief(),
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "user" => self.task_notes.raw_user_name(),
+            "request_tags" => self.task_notes.request_tags().map(LtMetricsTags),
             "tcp_server_addr" => self.tcp_server_addr,
             "tcp_client_addr" => self.tcp_client_addr,
             "udp_listen_addr" => self.udp_listen_addr,
             "udp_client_addr" => self.udp_client_addr,
             "upstream" => self.upstream.map(LtUpstreamAddr),
             "escaper" => self.udp_notes.escaper.as_str(),
+            "route_path" => self.udp_notes.route_path.log_value(&self.udp_notes.escaper),
             "next_bind_ip" => self.udp_notes.bind.ip().map(LtIpAddr),
             "next_bound_addr" => self.udp_notes.local,
             "next_peer_addr" => self.udp_notes.next,
@@ -138,11 +138,16 @@ fn tokio_run(args: &ProcArgs) -> anyhow::Result<()> {
             g3_daemon::runtime::metrics::add_tokio_stats(stats, "ip-locate".to_string());
         }
 
+        g3proxy::capacity::spawn_monitor();
+
         let _workers_guard = g3_daemon::runtime::worker::spawn_workers()
             .await
             .context("failed to spawn workers")?;
         match load_and_spawn().await {
-            Ok(_) => g3_daemon::control::upgrade::finish(),
+            Ok(_) => {
+                g3_daemon::control::upgrade::finish();
+                g3_daemon::schedule::spawn_all(g3proxy::control::run_scheduled_task);
+            }
             Err(e) => {
                 g3_daemon::control::upgrade::cancel_old_shutdown();
                 return Err(e);
@@ -176,6 +181,9 @@ async fn load_and_spawn() -> anyhow::Result<()> {
     g3proxy::audit::load_all()
         .await
         .context("failed to load all auditors")?;
+    g3proxy::preflight::run()
+        .await
+        .context("preflight check failed")?;
     g3proxy::serve::spawn_offline_clean();
     g3proxy::serve::spawn_all()
         .await
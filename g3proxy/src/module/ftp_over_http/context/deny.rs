@@ -55,7 +55,7 @@ impl FtpConnectContext for DenyFtpConnectContext {
     }
 
     fn fetch_control_tcp_notes(&self, tcp_notes: &mut TcpConnectTaskNotes) {
-        tcp_notes.escaper.clone_from(&self.escaper_name)
+        tcp_notes.set_escaper(&self.escaper_name)
     }
 
     async fn new_transfer_connection(
@@ -68,6 +68,6 @@ impl FtpConnectContext for DenyFtpConnectContext {
     }
 
     fn fetch_transfer_tcp_notes(&self, tcp_notes: &mut TcpConnectTaskNotes) {
-        tcp_notes.escaper.clone_from(&self.escaper_name)
+        tcp_notes.set_escaper(&self.escaper_name)
     }
 }
@@ -14,17 +14,26 @@
  * limitations under the License.
  */
 
+use std::sync::Arc;
+use std::time::Duration;
+
 use tokio::io::AsyncWriteExt;
 use tokio::sync::oneshot;
+use tokio::time::Instant;
 
 use g3_io_ext::LimitedBufReadExt;
+use g3_types::net::HttpKeepAliveConfig;
 
 use super::BoxHttpForwardConnection;
+use crate::escape::EscaperKeepaliveStats;
 
 struct HttpConnectionEofCheck {
     conn: BoxHttpForwardConnection,
     wait_channel: oneshot::Receiver<bool>,
     send_channel: oneshot::Sender<BoxHttpForwardConnection>,
+    expire: Duration,
+    expire_by_lifetime: bool,
+    stats: Option<Arc<EscaperKeepaliveStats>>,
 }
 
 impl HttpConnectionEofCheck {
@@ -33,6 +42,9 @@ impl HttpConnectionEofCheck {
             mut conn,
             mut wait_channel,
             send_channel,
+            expire,
+            expire_by_lifetime,
+            stats,
         } = self;
         tokio::select! {
             biased;
@@ -40,6 +52,9 @@ impl HttpConnectionEofCheck {
             _ = conn.1.fill_wait_data() => {
                 // close early when EOF or unexpected data, to avoid waiting at other side
                 wait_channel.close();
+                if let Some(stats) = &stats {
+                    stats.add_reap_eof();
+                }
                 // make sure we correctly shutdown tls connection
                 // FIXME use async drop at escaper side when supported
                 let _ = conn.0.shutdown().await;
@@ -53,6 +68,18 @@ impl HttpConnectionEofCheck {
                     let _ = conn.0.shutdown().await;
                 }
             }
+            _ = tokio::time::sleep(expire) => {
+                // reap the connection proactively, so we won't reuse a stale one
+                wait_channel.close();
+                if let Some(stats) = &stats {
+                    if expire_by_lifetime {
+                        stats.add_reap_lifetime();
+                    } else {
+                        stats.add_reap_idle();
+                    }
+                }
+                let _ = conn.0.shutdown().await;
+            }
         }
     }
 }
@@ -60,28 +87,60 @@ impl HttpConnectionEofCheck {
 pub(crate) struct HttpConnectionEofPoller {
     notify_channel: oneshot::Sender<bool>,
     recv_channel: oneshot::Receiver<BoxHttpForwardConnection>,
+    stats: Option<Arc<EscaperKeepaliveStats>>,
 }
 
 impl HttpConnectionEofPoller {
-    pub(crate) fn spawn(conn: BoxHttpForwardConnection) -> Self {
+    pub(crate) fn spawn(
+        conn: BoxHttpForwardConnection,
+        keepalive: &HttpKeepAliveConfig,
+        created: Instant,
+        stats: Option<Arc<EscaperKeepaliveStats>>,
+    ) -> Self {
+        let mut expire = keepalive.idle_expire();
+        let mut expire_by_lifetime = false;
+        if let Some(max_lifetime) = keepalive.max_lifetime() {
+            let left = max_lifetime.saturating_sub(created.elapsed());
+            if left < expire {
+                expire = left;
+                expire_by_lifetime = true;
+            }
+        }
+
         let (notify_sender, notify_receiver) = oneshot::channel();
         let (conn_sender, conn_receiver) = oneshot::channel();
         let runtime = HttpConnectionEofCheck {
             conn,
             wait_channel: notify_receiver,
             send_channel: conn_sender,
+            expire,
+            expire_by_lifetime,
+            stats: stats.clone(),
         };
         tokio::spawn(runtime.run());
         HttpConnectionEofPoller {
             notify_channel: notify_sender,
             recv_channel: conn_receiver,
+            stats,
+        }
+    }
+
+    /// Drop the connection as it has been idle for too long for the new task
+    pub(crate) fn reap_idle(self) {
+        if let Some(stats) = &self.stats {
+            stats.add_reap_idle();
         }
     }
 
     pub(crate) async fn recv_conn(self) -> Option<BoxHttpForwardConnection> {
         self.notify_channel.send(true).ok()?;
         match self.recv_channel.await {
-            Ok(conn) => Some(conn),
+            Ok(conn) => {
+                if let Some(stats) = &self.stats {
+                    stats.add_reuse();
+                }
+                Some(conn)
+            }
             Err(_) => None,
         }
     }
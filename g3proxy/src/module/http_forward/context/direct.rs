@@ -19,7 +19,7 @@ use std::time::Duration;
 use async_trait::async_trait;
 use tokio::time::Instant;
 
-use g3_types::net::{HttpForwardCapability, UpstreamAddr};
+use g3_types::net::{HttpForwardCapability, HttpKeepAliveConfig, UpstreamAddr};
 
 use super::{
     ArcHttpForwardTaskRemoteStats, BoxHttpForwardConnection, HttpConnectionEofPoller,
@@ -39,6 +39,7 @@ pub(crate) struct DirectHttpForwardContext {
     last_upstream: UpstreamAddr,
     last_is_tls: bool,
     last_connection: Option<(Instant, HttpConnectionEofPoller)>,
+    last_created: Instant,
 }
 
 impl DirectHttpForwardContext {
@@ -50,6 +51,7 @@ impl DirectHttpForwardContext {
             last_upstream: UpstreamAddr::empty(),
             last_is_tls: false,
             last_connection: None,
+            last_created: Instant::now(),
         }
     }
 }
@@ -109,6 +111,7 @@ impl HttpForwardContext for DirectHttpForwardContext {
             connection.1.update_stats(&task_stats, all_user_stats);
             Some(connection)
         } else {
+            eof_poller.reap_idle();
             None
         }
     }
@@ -120,6 +123,7 @@ impl HttpForwardContext for DirectHttpForwardContext {
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.last_is_tls = false;
+        self.last_created = Instant::now();
         self.escaper
             ._new_http_forward_connection(task_conf, &mut self.tcp_notes, task_notes, task_stats)
             .await
@@ -132,13 +136,19 @@ impl HttpForwardContext for DirectHttpForwardContext {
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.last_is_tls = true;
+        self.last_created = Instant::now();
         self.escaper
             ._new_https_forward_connection(task_conf, &mut self.tcp_notes, task_notes, task_stats)
             .await
     }
 
-    fn save_alive_connection(&mut self, c: BoxHttpForwardConnection) {
-        let eof_poller = HttpConnectionEofPoller::spawn(c);
+    fn save_alive_connection(
+        &mut self,
+        c: BoxHttpForwardConnection,
+        keepalive: &HttpKeepAliveConfig,
+    ) {
+        let stats = Some(self.stats.http_forward_keepalive().clone());
+        let eof_poller = HttpConnectionEofPoller::spawn(c, keepalive, self.last_created, stats);
         self.last_connection = Some((Instant::now(), eof_poller));
     }
 
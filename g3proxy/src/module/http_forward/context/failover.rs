@@ -22,14 +22,14 @@ use anyhow::anyhow;
 use async_trait::async_trait;
 use tokio::time::Instant;
 
-use g3_types::net::{HttpForwardCapability, UpstreamAddr};
+use g3_types::net::{HttpForwardCapability, HttpKeepAliveConfig, UpstreamAddr};
 
 use super::{
     ArcHttpForwardTaskRemoteStats, BoxHttpForwardConnection, HttpConnectionEofPoller,
     HttpForwardContext,
 };
 use crate::audit::AuditContext;
-use crate::escape::{ArcEscaper, RouteEscaperStats};
+use crate::escape::{ArcEscaper, EscaperRoutePath, RouteEscaperStats};
 use crate::module::tcp_connect::{
     TcpConnectError, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
 };
@@ -104,6 +104,8 @@ pub(crate) struct FailoverHttpForwardContext {
     standby_escaper: ArcEscaper,
     primary_final_escaper: ArcEscaper,
     standby_final_escaper: ArcEscaper,
+    primary_route_path: EscaperRoutePath,
+    standby_route_path: EscaperRoutePath,
     use_primary: bool,
     used_escaper: ArcEscaper,
     tcp_notes: TcpConnectTaskNotes,
@@ -111,6 +113,7 @@ pub(crate) struct FailoverHttpForwardContext {
     last_upstream: UpstreamAddr,
     last_is_tls: bool,
     last_connection: Option<(Instant, HttpConnectionEofPoller)>,
+    last_created: Instant,
 }
 
 impl FailoverHttpForwardContext {
@@ -127,6 +130,8 @@ impl FailoverHttpForwardContext {
             standby_escaper: Arc::clone(standby_escaper),
             primary_final_escaper: Arc::clone(primary_escaper),
             standby_final_escaper: Arc::clone(standby_escaper),
+            primary_route_path: EscaperRoutePath::default(),
+            standby_route_path: EscaperRoutePath::default(),
             use_primary: true,
             used_escaper: Arc::clone(primary_escaper),
             tcp_notes: TcpConnectTaskNotes::default(),
@@ -134,6 +139,7 @@ impl FailoverHttpForwardContext {
             last_upstream: UpstreamAddr::empty(),
             last_is_tls: false,
             last_connection: None,
+            last_created: Instant::now(),
         }
     }
 }
@@ -150,23 +156,33 @@ impl HttpForwardContext for FailoverHttpForwardContext {
             self.audit_ctx = audit_ctx.clone();
             // only use audit ctx of the primary escaper
 
+            let mut primary_route_path = EscaperRoutePath::default();
+            let mut route_escaper = self.route_stats.name().clone();
+            primary_route_path.record_hop(&mut route_escaper, self.primary_escaper.name());
             let mut primary_next_escaper = Arc::clone(&self.primary_escaper);
             primary_next_escaper._update_audit_context(&mut self.audit_ctx);
             while let Some(escaper) = primary_next_escaper
                 ._check_out_next_escaper(task_notes, upstream)
                 .await
             {
+                primary_route_path.record_hop(&mut route_escaper, escaper.name());
                 primary_next_escaper = escaper;
                 primary_next_escaper._update_audit_context(&mut self.audit_ctx);
             }
 
+            let mut standby_route_path = EscaperRoutePath::default();
+            let mut route_escaper = self.route_stats.name().clone();
+            standby_route_path.record_hop(&mut route_escaper, self.standby_escaper.name());
             let mut standby_next_escaper = Arc::clone(&self.standby_escaper);
             while let Some(escaper) = standby_next_escaper
                 ._check_out_next_escaper(task_notes, upstream)
                 .await
             {
+                standby_route_path.record_hop(&mut route_escaper, escaper.name());
                 standby_next_escaper = escaper;
             }
+            self.primary_route_path = primary_route_path;
+            self.standby_route_path = standby_route_path;
 
             if self.use_primary {
                 if !Arc::ptr_eq(&self.primary_final_escaper, &primary_next_escaper) {
@@ -231,6 +247,7 @@ impl HttpForwardContext for FailoverHttpForwardContext {
             connection.1.update_stats(&task_stats, all_user_stats);
             Some(connection)
         } else {
+            eof_poller.reap_idle();
             None
         }
     }
@@ -242,6 +259,7 @@ impl HttpForwardContext for FailoverHttpForwardContext {
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.last_is_tls = false;
+        self.last_created = Instant::now();
 
         let primary_context = HttpConnectFailoverContext::new(self.primary_final_escaper.clone());
         let mut primary_task =
@@ -257,6 +275,9 @@ impl HttpForwardContext for FailoverHttpForwardContext {
                 }
                 self.use_primary = true;
                 self.tcp_notes.clone_from(&ctx.tcp_notes);
+                self.tcp_notes
+                    .route_path
+                    .clone_from(&self.primary_route_path);
                 self.route_stats.add_request_passed();
                 return ctx.connect_result;
             }
@@ -268,6 +289,12 @@ impl HttpForwardContext for FailoverHttpForwardContext {
                     self.used_escaper = self.standby_final_escaper.clone();
                 }
                 self.use_primary = false;
+                self.tcp_notes
+                    .route_path
+                    .clone_from(&self.standby_route_path);
+                self.tcp_notes
+                    .escaper
+                    .clone_from(self.standby_final_escaper.name());
                 return match self
                     .used_escaper
                     ._new_http_forward_connection(
@@ -312,6 +339,15 @@ impl HttpForwardContext for FailoverHttpForwardContext {
         }
         self.use_primary = Arc::ptr_eq(&self.used_escaper, &self.primary_final_escaper);
         self.tcp_notes.clone_from(&ctx.tcp_notes);
+        if self.use_primary {
+            self.tcp_notes
+                .route_path
+                .clone_from(&self.primary_route_path);
+        } else {
+            self.tcp_notes
+                .route_path
+                .clone_from(&self.standby_route_path);
+        }
         ctx.connect_result
     }
 
@@ -322,6 +358,7 @@ impl HttpForwardContext for FailoverHttpForwardContext {
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.last_is_tls = true;
+        self.last_created = Instant::now();
 
         let primary_context = HttpConnectFailoverContext::new(self.primary_final_escaper.clone());
         let mut primary_task =
@@ -337,6 +374,9 @@ impl HttpForwardContext for FailoverHttpForwardContext {
                 }
                 self.use_primary = true;
                 self.tcp_notes.clone_from(&ctx.tcp_notes);
+                self.tcp_notes
+                    .route_path
+                    .clone_from(&self.primary_route_path);
                 self.route_stats.add_request_passed();
                 return ctx.connect_result;
             }
@@ -348,6 +388,12 @@ impl HttpForwardContext for FailoverHttpForwardContext {
                     self.used_escaper = self.standby_final_escaper.clone();
                 }
                 self.use_primary = false;
+                self.tcp_notes
+                    .route_path
+                    .clone_from(&self.standby_route_path);
+                self.tcp_notes
+                    .escaper
+                    .clone_from(self.standby_final_escaper.name());
                 return match self
                     .used_escaper
                     ._new_https_forward_connection(
@@ -392,11 +438,28 @@ impl HttpForwardContext for FailoverHttpForwardContext {
         }
         self.use_primary = Arc::ptr_eq(&self.used_escaper, &self.primary_final_escaper);
         self.tcp_notes.clone_from(&ctx.tcp_notes);
+        if self.use_primary {
+            self.tcp_notes
+                .route_path
+                .clone_from(&self.primary_route_path);
+        } else {
+            self.tcp_notes
+                .route_path
+                .clone_from(&self.standby_route_path);
+        }
         ctx.connect_result
     }
 
-    fn save_alive_connection(&mut self, c: BoxHttpForwardConnection) {
-        let eof_poller = HttpConnectionEofPoller::spawn(c);
+    fn save_alive_connection(
+        &mut self,
+        c: BoxHttpForwardConnection,
+        keepalive: &HttpKeepAliveConfig,
+    ) {
+        let stats = self
+            .used_escaper
+            .get_escape_stats()
+            .map(|s| s.http_forward_keepalive().clone());
+        let eof_poller = HttpConnectionEofPoller::spawn(c, keepalive, self.last_created, stats);
         self.last_connection = Some((Instant::now(), eof_poller));
     }
 
@@ -18,7 +18,7 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 
-use g3_types::net::{HttpForwardCapability, UpstreamAddr};
+use g3_types::net::{HttpForwardCapability, HttpKeepAliveConfig, UpstreamAddr};
 
 use super::{ArcHttpForwardTaskRemoteStats, BoxHttpForwardConnection, HttpConnectionEofPoller};
 use crate::audit::AuditContext;
@@ -69,6 +69,10 @@ pub(crate) trait HttpForwardContext {
         task_notes: &ServerTaskNotes,
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError>;
-    fn save_alive_connection(&mut self, c: BoxHttpForwardConnection);
+    fn save_alive_connection(
+        &mut self,
+        c: BoxHttpForwardConnection,
+        keepalive: &HttpKeepAliveConfig,
+    );
     fn fetch_tcp_notes(&self, tcp_notes: &mut TcpConnectTaskNotes);
 }
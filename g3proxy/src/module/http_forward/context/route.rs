@@ -20,14 +20,14 @@ use std::time::Duration;
 use async_trait::async_trait;
 use tokio::time::Instant;
 
-use g3_types::net::{HttpForwardCapability, UpstreamAddr};
+use g3_types::net::{HttpForwardCapability, HttpKeepAliveConfig, UpstreamAddr};
 
 use super::{
     ArcHttpForwardTaskRemoteStats, BoxHttpForwardConnection, HttpConnectionEofPoller,
     HttpForwardContext,
 };
 use crate::audit::AuditContext;
-use crate::escape::ArcEscaper;
+use crate::escape::{ArcEscaper, EscaperRoutePath};
 use crate::module::tcp_connect::{
     TcpConnectError, TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf,
 };
@@ -36,11 +36,13 @@ use crate::serve::ServerTaskNotes;
 pub(crate) struct RouteHttpForwardContext {
     escaper: ArcEscaper,
     final_escaper: ArcEscaper,
+    route_path: EscaperRoutePath,
     tcp_notes: TcpConnectTaskNotes,
     audit_ctx: AuditContext,
     last_upstream: UpstreamAddr,
     last_is_tls: bool,
     last_connection: Option<(Instant, HttpConnectionEofPoller)>,
+    last_created: Instant,
 }
 
 impl RouteHttpForwardContext {
@@ -49,11 +51,13 @@ impl RouteHttpForwardContext {
         RouteHttpForwardContext {
             escaper,
             final_escaper: fake_final_escaper,
+            route_path: EscaperRoutePath::default(),
             tcp_notes: TcpConnectTaskNotes::default(),
             audit_ctx: AuditContext::default(),
             last_upstream: UpstreamAddr::empty(),
             last_is_tls: false,
             last_connection: None,
+            last_created: Instant::now(),
         }
     }
 }
@@ -68,12 +72,15 @@ impl HttpForwardContext for RouteHttpForwardContext {
     ) -> HttpForwardCapability {
         if self.last_upstream.ne(upstream) {
             self.audit_ctx = audit_ctx.clone();
+            let mut route_path = EscaperRoutePath::default();
+            let mut route_escaper = self.escaper.name().clone();
             let mut next_escaper = Arc::clone(&self.escaper);
             next_escaper._update_audit_context(&mut self.audit_ctx);
             while let Some(escaper) = next_escaper
                 ._check_out_next_escaper(task_notes, upstream)
                 .await
             {
+                route_path.record_hop(&mut route_escaper, escaper.name());
                 next_escaper = escaper;
                 next_escaper._update_audit_context(&mut self.audit_ctx);
             }
@@ -82,6 +89,7 @@ impl HttpForwardContext for RouteHttpForwardContext {
                 // drop the old connection on old escaper
                 let _old_connection = self.last_connection.take();
             }
+            self.route_path = route_path;
         }
 
         *audit_ctx = self.audit_ctx.clone();
@@ -133,6 +141,7 @@ impl HttpForwardContext for RouteHttpForwardContext {
             connection.1.update_stats(&task_stats, all_user_stats);
             Some(connection)
         } else {
+            eof_poller.reap_idle();
             None
         }
     }
@@ -144,6 +153,9 @@ impl HttpForwardContext for RouteHttpForwardContext {
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.last_is_tls = false;
+        self.last_created = Instant::now();
+        self.tcp_notes.route_path.clone_from(&self.route_path);
+        self.tcp_notes.escaper.clone_from(self.final_escaper.name());
         self.final_escaper
             ._new_http_forward_connection(task_conf, &mut self.tcp_notes, task_notes, task_stats)
             .await
@@ -156,13 +168,24 @@ impl HttpForwardContext for RouteHttpForwardContext {
         task_stats: ArcHttpForwardTaskRemoteStats,
     ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.last_is_tls = true;
+        self.last_created = Instant::now();
+        self.tcp_notes.route_path.clone_from(&self.route_path);
+        self.tcp_notes.escaper.clone_from(self.final_escaper.name());
         self.final_escaper
             ._new_https_forward_connection(task_conf, &mut self.tcp_notes, task_notes, task_stats)
             .await
     }
 
-    fn save_alive_connection(&mut self, c: BoxHttpForwardConnection) {
-        let eof_poller = HttpConnectionEofPoller::spawn(c);
+    fn save_alive_connection(
+        &mut self,
+        c: BoxHttpForwardConnection,
+        keepalive: &HttpKeepAliveConfig,
+    ) {
+        let stats = self
+            .final_escaper
+            .get_escape_stats()
+            .map(|s| s.http_forward_keepalive().clone());
+        let eof_poller = HttpConnectionEofPoller::spawn(c, keepalive, self.last_created, stats);
         self.last_connection = Some((Instant::now(), eof_poller));
     }
 
@@ -0,0 +1,300 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use bytes::Bytes;
+use h2::client::SendRequest;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::time::Instant;
+
+use g3_types::net::{
+    AlpnProtocol, Host, OpensslClientConfig, OpensslClientConfigBuilder, UpstreamAddr,
+};
+
+use crate::config::server::http_forward_h2::HttpForwardUpstreamH2Config;
+use crate::module::tcp_connect::TcpConnectTaskNotes;
+
+const CONNECTION_CLOSED: usize = usize::MAX;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) struct HttpForwardH2PoolKey {
+    upstream: UpstreamAddr,
+    tls_name: Host,
+    user: Option<Arc<str>>,
+}
+
+impl HttpForwardH2PoolKey {
+    pub(crate) fn new(upstream: &UpstreamAddr, tls_name: &Host, user: Option<&Arc<str>>) -> Self {
+        HttpForwardH2PoolKey {
+            upstream: upstream.clone(),
+            tls_name: tls_name.clone(),
+            user: user.cloned(),
+        }
+    }
+}
+
+struct H2ConnectionShared {
+    active_streams: AtomicUsize,
+    used: AtomicBool,
+}
+
+impl H2ConnectionShared {
+    fn acquire(&self, max_streams: usize) -> bool {
+        let r = self
+            .active_streams
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                if n == CONNECTION_CLOSED || n >= max_streams {
+                    None
+                } else {
+                    Some(n + 1)
+                }
+            });
+        if r.is_ok() {
+            self.used.store(true, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn release(&self) {
+        let _ = self
+            .active_streams
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                if n == CONNECTION_CLOSED || n == 0 {
+                    None
+                } else {
+                    Some(n - 1)
+                }
+            });
+    }
+
+    fn is_closed(&self) -> bool {
+        self.active_streams.load(Ordering::Acquire) == CONNECTION_CLOSED
+    }
+
+    fn mark_closed(&self) {
+        self.active_streams
+            .store(CONNECTION_CLOSED, Ordering::Release);
+    }
+
+    /// close the connection if no stream has been opened since the last check
+    fn try_close_idle(&self) -> bool {
+        if self.used.swap(false, Ordering::Relaxed) {
+            return false;
+        }
+        self.active_streams
+            .compare_exchange(0, CONNECTION_CLOSED, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+}
+
+struct PooledH2Connection {
+    send_request: SendRequest<Bytes>,
+    tcp_notes: TcpConnectTaskNotes,
+    shared: Arc<H2ConnectionShared>,
+}
+
+/// a stream slot on a pooled h2 connection, released when dropped
+pub(crate) struct HttpForwardH2Stream {
+    pub(crate) send_request: SendRequest<Bytes>,
+    shared: Arc<H2ConnectionShared>,
+}
+
+impl HttpForwardH2Stream {
+    /// stop the underlying connection from being used by new requests
+    pub(crate) fn mark_broken(&self) {
+        self.shared.mark_closed();
+    }
+}
+
+impl Drop for HttpForwardH2Stream {
+    fn drop(&mut self) {
+        self.shared.release();
+    }
+}
+
+pub(crate) struct HttpForwardH2Pool {
+    config: HttpForwardUpstreamH2Config,
+    tls_client: OpensslClientConfig,
+    connections: Mutex<AHashMap<HttpForwardH2PoolKey, Vec<PooledH2Connection>>>,
+    h1_only: Mutex<AHashMap<HttpForwardH2PoolKey, Instant>>,
+}
+
+impl HttpForwardH2Pool {
+    pub(crate) fn new(
+        config: &HttpForwardUpstreamH2Config,
+        tls_builder: &OpensslClientConfigBuilder,
+    ) -> anyhow::Result<Self> {
+        let tls_client = tls_builder
+            .build_with_alpn_protocols(Some(vec![AlpnProtocol::Http2, AlpnProtocol::Http11]))?;
+        Ok(HttpForwardH2Pool {
+            config: config.clone(),
+            tls_client,
+            connections: Mutex::new(AHashMap::new()),
+            h1_only: Mutex::new(AHashMap::new()),
+        })
+    }
+
+    #[inline]
+    pub(crate) fn tls_client(&self) -> &OpensslClientConfig {
+        &self.tls_client
+    }
+
+    pub(crate) fn is_h1_only(&self, key: &HttpForwardH2PoolKey) -> bool {
+        let mut h1_only = self.h1_only.lock().unwrap();
+        match h1_only.get(key) {
+            Some(expire) => {
+                if expire.elapsed() < self.config.h1_only_cache_ttl {
+                    true
+                } else {
+                    h1_only.remove(key);
+                    false
+                }
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn set_h1_only(&self, key: HttpForwardH2PoolKey) {
+        let ttl = self.config.h1_only_cache_ttl;
+        let mut h1_only = self.h1_only.lock().unwrap();
+        h1_only.retain(|_, v| v.elapsed() < ttl);
+        h1_only.insert(key, Instant::now());
+    }
+
+    /// get a stream slot on an existing connection, the tcp notes of which will be copied out
+    pub(crate) fn get(
+        &self,
+        key: &HttpForwardH2PoolKey,
+        tcp_notes: &mut TcpConnectTaskNotes,
+    ) -> Option<HttpForwardH2Stream> {
+        let mut connections = self.connections.lock().unwrap();
+        let list = connections.get_mut(key)?;
+        list.retain(|c| !c.shared.is_closed());
+        for c in list.iter() {
+            if c.shared.acquire(self.config.max_concurrent_streams) {
+                tcp_notes.clone_from(&c.tcp_notes);
+                return Some(HttpForwardH2Stream {
+                    send_request: c.send_request.clone(),
+                    shared: c.shared.clone(),
+                });
+            }
+        }
+        if list.is_empty() {
+            connections.remove(key);
+        }
+        None
+    }
+
+    /// do h2 handshake on a new tls connection and add it to the pool
+    pub(crate) async fn handshake<R, W>(
+        self: &Arc<Self>,
+        key: HttpForwardH2PoolKey,
+        ups_r: R,
+        ups_w: W,
+        tcp_notes: &TcpConnectTaskNotes,
+    ) -> anyhow::Result<HttpForwardH2Stream>
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+        W: AsyncWrite + Send + Unpin + 'static,
+    {
+        let mut client_builder = h2::client::Builder::new();
+        client_builder
+            .enable_push(false)
+            .initial_window_size(self.config.stream_window_size)
+            .initial_connection_window_size(self.config.connection_window_size)
+            .max_frame_size(self.config.max_frame_size)
+            .max_send_buffer_size(self.config.max_send_buffer_size);
+
+        let (send_request, mut connection) = match tokio::time::timeout(
+            self.config.handshake_timeout,
+            client_builder.handshake(tokio::io::join(ups_r, ups_w)),
+        )
+        .await
+        {
+            Ok(Ok(d)) => d,
+            Ok(Err(e)) => return Err(anyhow!("h2 handshake failed: {e}")),
+            Err(_) => return Err(anyhow!("h2 handshake timed out")),
+        };
+
+        let shared = Arc::new(H2ConnectionShared {
+            active_streams: AtomicUsize::new(1),
+            used: AtomicBool::new(true),
+        });
+        let stream = HttpForwardH2Stream {
+            send_request: send_request.clone(),
+            shared: shared.clone(),
+        };
+
+        self.connections
+            .lock()
+            .unwrap()
+            .entry(key.clone())
+            .or_default()
+            .push(PooledH2Connection {
+                send_request,
+                tcp_notes: tcp_notes.clone(),
+                shared: shared.clone(),
+            });
+
+        let pool = Arc::downgrade(self);
+        let idle_timeout = self.config.idle_timeout;
+        tokio::spawn(async move {
+            let mut idle_interval =
+                tokio::time::interval_at(Instant::now() + idle_timeout, idle_timeout);
+            loop {
+                tokio::select! {
+                    biased;
+
+                    _ = &mut connection => break,
+                    _ = idle_interval.tick() => {
+                        if shared.try_close_idle() {
+                            break;
+                        }
+                    }
+                }
+            }
+            shared.mark_closed();
+            HttpForwardH2Pool::remove_closed(pool, &key);
+        });
+
+        Ok(stream)
+    }
+
+    fn remove_closed(pool: Weak<Self>, key: &HttpForwardH2PoolKey) {
+        let Some(pool) = pool.upgrade() else {
+            return;
+        };
+        let mut connections = pool.connections.lock().unwrap();
+        if let Some(list) = connections.get_mut(key) {
+            list.retain(|c| !c.shared.is_closed());
+            if list.is_empty() {
+                connections.remove(key);
+            }
+        }
+    }
+
+    /// drop all pooled connections, alive streams will not be affected
+    pub(crate) fn clear(&self) {
+        self.connections.lock().unwrap().clear();
+        self.h1_only.lock().unwrap().clear();
+    }
+}
@@ -16,6 +16,7 @@
 
 mod connection;
 mod context;
+mod h2_pool;
 mod response;
 mod stats;
 mod task;
@@ -29,6 +30,7 @@ pub(crate) use context::{
     BoxHttpForwardContext, DirectHttpForwardContext, FailoverHttpForwardContext,
     HttpForwardContext, ProxyHttpForwardContext, RouteHttpForwardContext,
 };
+pub(crate) use h2_pool::{HttpForwardH2Pool, HttpForwardH2PoolKey, HttpForwardH2Stream};
 pub(crate) use response::HttpProxyClientResponse;
 pub(crate) use stats::{
     ArcHttpForwardTaskRemoteStats, HttpForwardRemoteWrapperStats, HttpForwardTaskRemoteStats,
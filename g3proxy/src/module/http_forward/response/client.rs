@@ -27,6 +27,7 @@ use g3_http::server::HttpRequestParseError;
 use g3_io_ext::LimitedWriteExt;
 use g3_types::net::ConnectError;
 
+use crate::config::server::http_failover_hint::HttpFailoverHintConfig;
 use crate::module::http_header;
 use crate::module::tcp_connect::TcpConnectError;
 use crate::serve::ServerTaskError;
@@ -87,6 +88,20 @@ impl HttpProxyClientResponse {
         self.extra_headers.push(http_header::outgoing_ip(ip));
     }
 
+    pub(crate) fn add_failover_hint(&mut self, hint: &HttpFailoverHintConfig) {
+        self.extra_headers
+            .push(format!("Retry-After: {}\r\n", hint.retry_after_secs()));
+        if let Some(proxy) = &hint.secondary_proxy {
+            self.extra_headers
+                .push(format!("{}: {proxy}\r\n", hint.secondary_proxy_header));
+        }
+        if self.close {
+            // proxy-connection is not standard, but some legacy clients still rely on it
+            self.extra_headers
+                .push("Proxy-Connection: Close\r\n".to_string());
+        }
+    }
+
     #[inline]
     pub(crate) fn too_many_requests(version: Version) -> Self {
         HttpProxyClientResponse::from_standard(StatusCode::TOO_MANY_REQUESTS, version, true)
@@ -417,6 +432,8 @@ impl HttpProxyClientResponse {
             | ServerTaskError::ClosedByClient
             | ServerTaskError::ClosedEarlyByClient
             | ServerTaskError::Idle(_, _)
+            | ServerTaskError::ClientReadIdle(_, _)
+            | ServerTaskError::ClientWriteIdle(_, _)
             | ServerTaskError::InterceptionError(_, _)
             | ServerTaskError::Finished => return None,
         };
@@ -560,6 +577,8 @@ impl HttpProxyClientResponse {
         version: Version,
         writer: &mut W,
         realm: &AsciiStr,
+        negotiate: bool,
+        bearer: bool,
         close: bool,
     ) -> io::Result<()>
     where
@@ -570,6 +589,12 @@ impl HttpProxyClientResponse {
             version,
             close,
         );
+        if negotiate {
+            response.add_extra_header(g3_http::header::proxy_authenticate_negotiate());
+        }
+        if bearer {
+            response.add_extra_header(g3_http::header::proxy_authenticate_bearer(realm.as_str()));
+        }
         let auth_header = g3_http::header::proxy_authenticate_basic(realm.as_str());
         response.add_extra_header(auth_header);
         response.reply_err(writer).await
@@ -30,7 +30,9 @@ pub(crate) struct HttpForwardTaskNotes {
     pub(crate) dur_req_send_all: Duration,
     pub(crate) dur_rsp_recv_hdr: Duration,
     pub(crate) dur_rsp_recv_all: Duration,
+    pub(crate) dur_req_adaptation: Duration,
     pub(crate) retry_new_connection: bool,
+    pub(crate) retry_count: usize,
 }
 
 impl HttpForwardTaskNotes {
@@ -54,7 +56,9 @@ impl HttpForwardTaskNotes {
             dur_req_send_all: Duration::default(),
             dur_rsp_recv_hdr: Duration::default(),
             dur_rsp_recv_all: Duration::default(),
+            dur_req_adaptation: Duration::default(),
             retry_new_connection: false,
+            retry_count: 0,
         }
     }
 
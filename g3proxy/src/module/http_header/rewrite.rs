@@ -0,0 +1,130 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::FromStr;
+
+use slog::{slog_info, Logger};
+use uuid::Uuid;
+
+use g3_slog_types::LtUuid;
+use g3_types::net::{HttpHeaderMap, HttpHeaderValue};
+
+use crate::config::http_header_rewrite::{HttpHeaderRewriteAction, HttpHeaderRewriteRule};
+
+#[derive(Clone, Copy)]
+pub(crate) enum HttpHeaderRewriteDirection {
+    Request,
+    Response,
+}
+
+impl HttpHeaderRewriteDirection {
+    fn as_str(&self) -> &'static str {
+        match self {
+            HttpHeaderRewriteDirection::Request => "request",
+            HttpHeaderRewriteDirection::Response => "response",
+        }
+    }
+}
+
+pub(crate) struct HttpHeaderRewriteLog<'a> {
+    pub(crate) logger: &'a Logger,
+    pub(crate) task_id: &'a Uuid,
+    pub(crate) user: Option<&'a str>,
+    pub(crate) direction: HttpHeaderRewriteDirection,
+}
+
+impl HttpHeaderRewriteLog<'_> {
+    fn log(&self, rule: &HttpHeaderRewriteRule) {
+        slog_info!(self.logger, "";
+            "task_id" => LtUuid(self.task_id),
+            "user" => self.user,
+            "header_rewrite_direction" => self.direction.as_str(),
+            "header_rewrite_rule" => rule.id.as_str(),
+            "header_rewrite_action" => rule.action.as_str(),
+            "header_rewrite_header" => rule.header.as_str(),
+        )
+    }
+}
+
+/// Apply the rules in order, a log entry will be emitted for every rule that changed the headers.
+///
+/// Returns true if any of the rules has been applied.
+pub(crate) fn rewrite(
+    headers: &mut HttpHeaderMap,
+    rules: &[HttpHeaderRewriteRule],
+    log: &HttpHeaderRewriteLog<'_>,
+) -> bool {
+    let mut changed = false;
+    for rule in rules {
+        if apply_rule(headers, rule) {
+            log.log(rule);
+            changed = true;
+        }
+    }
+    changed
+}
+
+fn apply_rule(headers: &mut HttpHeaderMap, rule: &HttpHeaderRewriteRule) -> bool {
+    match &rule.action {
+        HttpHeaderRewriteAction::Add(value) => {
+            // the value has been checked when parsing config
+            let value = unsafe { HttpHeaderValue::from_string_unchecked(value.clone()) };
+            headers.append(rule.header.clone(), value);
+            true
+        }
+        HttpHeaderRewriteAction::Set(value) => {
+            let value = unsafe { HttpHeaderValue::from_string_unchecked(value.clone()) };
+            headers.insert(rule.header.clone(), value);
+            true
+        }
+        HttpHeaderRewriteAction::Remove => headers.remove(&rule.header).is_some(),
+        HttpHeaderRewriteAction::Replace(regex, replacement) => {
+            if !headers.contains_key(&rule.header) {
+                return false;
+            }
+
+            let mut matched = false;
+            let mut new_values = Vec::new();
+            for v in headers.get_all(&rule.header).iter() {
+                let s = v.to_str();
+                if !regex.is_match(s) {
+                    new_values.push(v.clone());
+                    continue;
+                }
+                let new = regex.replace_all(s, replacement.as_str());
+                match HttpHeaderValue::from_str(&new) {
+                    Ok(mut new_value) => {
+                        if let Some(name) = v.original_name() {
+                            new_value.set_original_name(name);
+                        }
+                        new_values.push(new_value);
+                        matched = true;
+                    }
+                    Err(_) => new_values.push(v.clone()),
+                }
+            }
+            if !matched {
+                return false;
+            }
+
+            headers.remove(&rule.header);
+            for v in new_values {
+                headers.append(rule.header.clone(), v);
+            }
+            true
+        }
+    }
+}
@@ -14,7 +14,15 @@
  * limitations under the License.
  */
 
+use std::fmt::Write;
+use std::time::Duration;
+
 use base64::prelude::*;
+use http::HeaderName;
+
+use g3_types::net::{HttpHeaderMap, HttpHeaderValue};
+
+const SERVER_TIMING: &str = "server-timing";
 
 pub(crate) fn proxy_authorization_basic_pass(userid: &str) -> String {
     format!(
@@ -22,3 +30,46 @@ pub(crate) fn proxy_authorization_basic_pass(userid: &str) -> String {
         BASE64_STANDARD.encode(format!("{userid}:{}", crate::build::PKG_NAME))
     )
 }
+
+/// Durations of the proxy side processing stages, a zero value means the stage was skipped
+#[derive(Default)]
+pub(crate) struct ServerTimingMetrics {
+    pub(crate) resolve: Duration,
+    pub(crate) connect: Duration,
+    pub(crate) tls: Duration,
+    pub(crate) icap: Duration,
+    pub(crate) total: Duration,
+}
+
+impl ServerTimingMetrics {
+    fn write_value(&self, v: &mut String) {
+        let mut add_metric = |name: &str, dur: Duration| {
+            if !v.is_empty() {
+                v.push_str(", ");
+            }
+            let _ = write!(v, "{name};dur={:.3}", dur.as_secs_f64() * 1000.0);
+        };
+
+        if !self.resolve.is_zero() {
+            add_metric("resolve", self.resolve);
+        }
+        if !self.connect.is_zero() {
+            add_metric("connect", self.connect);
+        }
+        if !self.tls.is_zero() {
+            add_metric("tls", self.tls);
+        }
+        if !self.icap.is_zero() {
+            add_metric("icap", self.icap);
+        }
+        add_metric("total", self.total);
+    }
+}
+
+pub(crate) fn set_server_timing(headers: &mut HttpHeaderMap, metrics: &ServerTimingMetrics) {
+    let mut value = String::with_capacity(128);
+    metrics.write_value(&mut value);
+    headers.append(HeaderName::from_static(SERVER_TIMING), unsafe {
+        HttpHeaderValue::from_string_unchecked(value)
+    });
+}
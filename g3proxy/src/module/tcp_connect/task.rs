@@ -18,13 +18,14 @@ use std::net::SocketAddr;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
-use openssl::ssl::Ssl;
+use openssl::ssl::{Ssl, SslRef};
 
 use g3_socket::BindAddr;
 use g3_types::metrics::NodeName;
-use g3_types::net::{EgressInfo, Host, OpensslClientConfig, UpstreamAddr};
+use g3_types::net::{AlpnProtocol, EgressInfo, Host, OpensslClientConfig, UpstreamAddr};
 
 use super::TcpConnectError;
+use crate::escape::EscaperRoutePath;
 
 pub(crate) struct TcpConnectTaskConf<'a> {
     pub(crate) upstream: &'a UpstreamAddr,
@@ -65,6 +66,7 @@ impl TcpConnectChainedNotes {
 #[derive(Debug, Default, Clone)]
 pub(crate) struct TcpConnectTaskNotes {
     pub(crate) escaper: NodeName,
+    pub(crate) route_path: EscaperRoutePath,
     pub(crate) bind: BindAddr,
     pub(crate) next: Option<SocketAddr>,
     pub(crate) tries: usize,
@@ -73,11 +75,17 @@ pub(crate) struct TcpConnectTaskNotes {
     pub(crate) egress: Option<EgressInfo>,
     pub(crate) chained: TcpConnectChainedNotes,
     pub(crate) duration: Duration,
+    pub(crate) resolve_duration: Duration,
+    pub(crate) tls_duration: Duration,
+    pub(crate) tls_alpn: Option<AlpnProtocol>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) tls_handshake_timeout: Option<Duration>,
 }
 
 impl TcpConnectTaskNotes {
     pub(crate) fn reset(&mut self) {
         self.escaper.clear();
+        self.route_path.clear();
         self.bind = BindAddr::None;
         self.next = None;
         self.tries = 0;
@@ -86,5 +94,28 @@ impl TcpConnectTaskNotes {
         self.egress = None;
         self.chained.reset();
         self.duration = Duration::ZERO;
+        self.resolve_duration = Duration::ZERO;
+        self.tls_duration = Duration::ZERO;
+        self.tls_alpn = None;
+        self.connect_timeout = None;
+        self.tls_handshake_timeout = None;
+    }
+
+    pub(crate) fn set_tls_alpn(&mut self, ssl: &SslRef) {
+        self.tls_alpn = ssl
+            .selected_alpn_protocol()
+            .and_then(AlpnProtocol::from_buf);
+    }
+
+    pub(crate) fn set_escaper(&mut self, escaper: &NodeName) {
+        self.route_path.record_hop(&mut self.escaper, escaper);
+    }
+
+    /// Take the notes of a detached child task, started by the current escaper
+    pub(crate) fn merge_child(&mut self, child: &Self) {
+        let mut route_path = std::mem::take(&mut self.route_path);
+        route_path.append_child(&self.escaper, &child.route_path, &child.escaper);
+        self.clone_from(child);
+        self.route_path = route_path;
     }
 }
@@ -22,6 +22,8 @@ use g3_socket::BindAddr;
 use g3_types::metrics::NodeName;
 use g3_types::net::{SocketBufferConfig, UpstreamAddr};
 
+use crate::escape::EscaperRoutePath;
+
 pub(crate) struct UdpConnectTaskConf<'a> {
     pub(crate) upstream: &'a UpstreamAddr,
     pub(crate) sock_buf: SocketBufferConfig,
@@ -30,8 +32,23 @@ pub(crate) struct UdpConnectTaskConf<'a> {
 #[derive(Clone, Debug, Default)]
 pub(crate) struct UdpConnectTaskNotes {
     pub(crate) escaper: NodeName,
+    pub(crate) route_path: EscaperRoutePath,
     pub(crate) bind: BindAddr,
     pub(crate) next: Option<SocketAddr>,
     pub(crate) local: Option<SocketAddr>,
     pub(crate) expire: Option<DateTime<Utc>>,
 }
+
+impl UdpConnectTaskNotes {
+    pub(crate) fn set_escaper(&mut self, escaper: &NodeName) {
+        self.route_path.record_hop(&mut self.escaper, escaper);
+    }
+
+    /// Take the notes of a detached child task, started by the current escaper
+    pub(crate) fn merge_child(&mut self, child: &Self) {
+        let mut route_path = std::mem::take(&mut self.route_path);
+        route_path.append_child(&self.escaper, &child.route_path, &child.escaper);
+        self.clone_from(child);
+        self.route_path = route_path;
+    }
+}
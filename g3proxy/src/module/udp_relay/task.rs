@@ -19,6 +19,8 @@ use chrono::{DateTime, Utc};
 use g3_types::metrics::NodeName;
 use g3_types::net::{SocketBufferConfig, UpstreamAddr};
 
+use crate::escape::EscaperRoutePath;
+
 pub(crate) struct UdpRelayTaskConf<'a> {
     pub(crate) initial_peer: &'a UpstreamAddr,
     pub(crate) sock_buf: SocketBufferConfig,
@@ -27,5 +29,20 @@ pub(crate) struct UdpRelayTaskConf<'a> {
 #[derive(Clone, Debug, Default)]
 pub(crate) struct UdpRelayTaskNotes {
     pub(crate) escaper: NodeName,
+    pub(crate) route_path: EscaperRoutePath,
     pub(crate) expire: Option<DateTime<Utc>>,
 }
+
+impl UdpRelayTaskNotes {
+    pub(crate) fn set_escaper(&mut self, escaper: &NodeName) {
+        self.route_path.record_hop(&mut self.escaper, escaper);
+    }
+
+    /// Take the notes of a detached child task, started by the current escaper
+    pub(crate) fn merge_child(&mut self, child: &Self) {
+        let mut route_path = std::mem::take(&mut self.route_path);
+        route_path.append_child(&self.escaper, &child.route_path, &child.escaper);
+        self.clone_from(child);
+        self.route_path = route_path;
+    }
+}
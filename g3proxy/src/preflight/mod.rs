@@ -0,0 +1,166 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::{Arc, Mutex};
+
+use anyhow::anyhow;
+use log::{info, warn};
+
+use g3_types::metrics::NodeName;
+
+use crate::config::preflight::{PreflightConfig, PreflightPolicy};
+
+static LAST_REPORT: Mutex<Option<Arc<PreflightReport>>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PreflightComponent {
+    Resolver,
+    CertAgent,
+    IcapReqmod,
+    IcapRespmod,
+    UserSource,
+}
+
+impl PreflightComponent {
+    pub(crate) const fn as_str(&self) -> &'static str {
+        match self {
+            PreflightComponent::Resolver => "resolver",
+            PreflightComponent::CertAgent => "cert_agent",
+            PreflightComponent::IcapReqmod => "icap_reqmod",
+            PreflightComponent::IcapRespmod => "icap_respmod",
+            PreflightComponent::UserSource => "user_source",
+        }
+    }
+}
+
+pub(crate) struct PreflightCheck {
+    pub(crate) component: PreflightComponent,
+    pub(crate) name: NodeName,
+    pub(crate) policy: PreflightPolicy,
+    pub(crate) error: Option<String>,
+}
+
+impl PreflightCheck {
+    #[inline]
+    pub(crate) fn passed(&self) -> bool {
+        self.error.is_none()
+    }
+
+    #[inline]
+    pub(crate) fn is_fatal(&self) -> bool {
+        self.error.is_some() && self.policy == PreflightPolicy::Fail
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct PreflightReport {
+    checks: Vec<PreflightCheck>,
+}
+
+impl PreflightReport {
+    pub(crate) fn add(
+        &mut self,
+        component: PreflightComponent,
+        name: &NodeName,
+        policy: PreflightPolicy,
+        r: anyhow::Result<()>,
+    ) {
+        let error = match r {
+            Ok(_) => {
+                info!("preflight check of {} {name} passed", component.as_str());
+                None
+            }
+            Err(e) => {
+                warn!(
+                    "preflight check of {} {name} failed (policy: {}): {e:?}",
+                    component.as_str(),
+                    policy.as_str()
+                );
+                Some(format!("{e:?}"))
+            }
+        };
+        self.checks.push(PreflightCheck {
+            component,
+            name: name.clone(),
+            policy,
+            error,
+        });
+    }
+
+    pub(crate) fn checks(&self) -> &[PreflightCheck] {
+        &self.checks
+    }
+
+    /// The daemon is ready if all checks with the fail policy have passed
+    pub(crate) fn is_ready(&self) -> bool {
+        !self.checks.iter().any(|c| c.is_fatal())
+    }
+
+    fn fatal_summary(&self) -> String {
+        let mut s = String::new();
+        for c in self.checks.iter().filter(|c| c.is_fatal()) {
+            if !s.is_empty() {
+                s.push_str(", ");
+            }
+            s.push_str(c.component.as_str());
+            s.push(' ');
+            s.push_str(c.name.as_str());
+        }
+        s
+    }
+}
+
+async fn run_all(config: &PreflightConfig) -> PreflightReport {
+    let mut report = PreflightReport::default();
+    if config.resolver != PreflightPolicy::Skip {
+        crate::resolve::preflight_check(config, &mut report).await;
+    }
+    if config.cert_agent != PreflightPolicy::Skip || config.icap != PreflightPolicy::Skip {
+        crate::audit::preflight_check(config, &mut report).await;
+    }
+    if config.user_source != PreflightPolicy::Skip {
+        crate::auth::preflight_check(config, &mut report).await;
+    }
+    report
+}
+
+/// Run all preflight checks if enabled. An error will be returned if any dependency with the
+/// fail policy is not ready.
+pub async fn run() -> anyhow::Result<()> {
+    let Some(config) = crate::config::preflight::get_config() else {
+        return Ok(());
+    };
+
+    let report = Arc::new(run_all(config).await);
+    *LAST_REPORT.lock().unwrap() = Some(report.clone());
+
+    if report.is_ready() {
+        info!(
+            "preflight finished, {} of {} checks passed",
+            report.checks.iter().filter(|c| c.passed()).count(),
+            report.checks.len()
+        );
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "required dependencies are not ready: {}",
+            report.fatal_summary()
+        ))
+    }
+}
+
+pub(crate) fn last_report() -> Option<Arc<PreflightReport>> {
+    LAST_REPORT.lock().unwrap().clone()
+}
@@ -0,0 +1,226 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::anyhow;
+use log::{error, info, warn};
+use tokio::sync::{Mutex, MutexGuard};
+
+use g3_types::metrics::NodeName;
+
+use crate::config::canary::CanaryConfig;
+use crate::config::ConfigGeneration;
+use crate::escape::ArcEscaperStats;
+
+static RELOAD_MUTEX: Mutex<()> = Mutex::const_new(());
+/// increased on each commit, so a canary check can detect newer reloads
+static RELOAD_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Reload all the reloadable configs.
+///
+/// The new config generation will only be used if all components are started successfully,
+/// and the canary check is passed, or the previous generation will be restored.
+pub(crate) async fn reload_all() {
+    let guard = RELOAD_MUTEX.lock().await;
+    info!("reloading config");
+
+    let old_generation = match crate::config::reload().await {
+        Ok(generation) => generation,
+        Err(e) => {
+            warn!("error reloading config: {e:?}");
+            warn!("reload aborted");
+            return;
+        }
+    };
+
+    match commit(guard, old_generation).await {
+        Ok(_) => info!("reload finished"),
+        Err(e) => warn!("reload rolled back: {e:?}"),
+    }
+}
+
+/// Reload the config of a single tenant, entities of other tenants will not be touched
+pub(crate) async fn reload_tenant(name: NodeName) -> anyhow::Result<()> {
+    let guard = RELOAD_MUTEX.lock().await;
+    info!("reloading tenant {name}");
+
+    let old_generation = tokio::task::spawn_blocking(move || crate::config::tenant::reload(&name))
+        .await
+        .map_err(|e| anyhow!("failed to join reload task: {e}"))??;
+
+    // only the changed entities will be reloaded
+    commit(guard, old_generation).await?;
+
+    info!("tenant reload finished");
+    Ok(())
+}
+
+async fn commit(
+    guard: MutexGuard<'static, ()>,
+    old_generation: ConfigGeneration,
+) -> anyhow::Result<()> {
+    let sequence = RELOAD_SEQUENCE.fetch_add(1, Ordering::Relaxed) + 1;
+    let changed_escapers = old_generation.changed_escapers();
+
+    if let Err(e) = apply_runtime().await {
+        rollback(old_generation).await;
+        return Err(e);
+    }
+
+    let Some(canary) = crate::config::canary::get_config() else {
+        return Ok(());
+    };
+    if canary.window.is_zero() {
+        return Ok(());
+    }
+
+    let counters = EscaperConnectionCounter::collect(&changed_escapers);
+    if counters.is_empty() {
+        return Ok(());
+    }
+    // do not block other reload requests while waiting
+    drop(guard);
+    info!(
+        "watching error ratio of {} changed escapers in canary window {:?}",
+        counters.len(),
+        canary.window
+    );
+    tokio::time::sleep(canary.window).await;
+    let r = check_canary(canary, counters);
+
+    let _guard = RELOAD_MUTEX.lock().await;
+    if let Err(e) = r {
+        if RELOAD_SEQUENCE.load(Ordering::Relaxed) != sequence {
+            // the previous generation is no longer the one before the running config
+            warn!("canary check failed, but skip rollback as newer reload happened: {e:?}");
+            return Ok(());
+        }
+        rollback(old_generation).await;
+        return Err(e);
+    }
+    Ok(())
+}
+
+async fn rollback(old_generation: ConfigGeneration) {
+    warn!("rolling back to the previous config generation");
+    old_generation.restore();
+    if let Err(e) = apply_runtime().await {
+        error!("failed to rollback to the previous config generation: {e:?}");
+    }
+}
+
+async fn apply_runtime() -> anyhow::Result<()> {
+    crate::resolve::spawn_all()
+        .await
+        .map_err(|e| anyhow!("failed to reload all resolvers: {e:?}"))?;
+    crate::escape::load_all()
+        .await
+        .map_err(|e| anyhow!("failed to reload all escapers: {e:?}"))?;
+    crate::auth::load_all()
+        .await
+        .map_err(|e| anyhow!("failed to reload all user groups: {e:?}"))?;
+    crate::audit::load_all()
+        .await
+        .map_err(|e| anyhow!("failed to reload all auditors: {e:?}"))?;
+    crate::serve::spawn_all()
+        .await
+        .map_err(|e| anyhow!("failed to reload all servers: {e:?}"))?;
+    Ok(())
+}
+
+struct EscaperConnectionCounter {
+    stats: ArcEscaperStats,
+    attempted: u64,
+    established: u64,
+}
+
+impl EscaperConnectionCounter {
+    fn collect(escapers: &HashSet<NodeName>) -> Vec<Self> {
+        let mut counters = Vec::new();
+        crate::escape::foreach_escaper(|name, escaper| {
+            if !escapers.contains(name) {
+                return;
+            }
+            if let Some(stats) = escaper.get_escape_stats() {
+                counters.push(EscaperConnectionCounter {
+                    attempted: stats.connection_attempted(),
+                    established: stats.connection_established(),
+                    stats,
+                });
+            }
+        });
+        counters
+    }
+}
+
+fn check_canary(
+    config: &CanaryConfig,
+    counters: Vec<EscaperConnectionCounter>,
+) -> anyhow::Result<()> {
+    let mut attempted = 0u64;
+    let mut established = 0u64;
+    for c in counters {
+        attempted += c.stats.connection_attempted().wrapping_sub(c.attempted);
+        established += c.stats.connection_established().wrapping_sub(c.established);
+    }
+    check_error_ratio(config, attempted, established)
+}
+
+fn check_error_ratio(
+    config: &CanaryConfig,
+    attempted: u64,
+    established: u64,
+) -> anyhow::Result<()> {
+    if attempted < config.min_attempts {
+        return Ok(());
+    }
+
+    let failed = attempted.saturating_sub(established);
+    let ratio = failed as f64 / attempted as f64;
+    if ratio > config.max_error_ratio {
+        Err(anyhow!(
+            "escaper connection error ratio {ratio:.3} exceeds {} in canary window",
+            config.max_error_ratio
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn error_ratio() {
+        let config = CanaryConfig {
+            window: Duration::from_secs(10),
+            max_error_ratio: 0.2,
+            min_attempts: 10,
+        };
+
+        // too few attempts
+        assert!(check_error_ratio(&config, 5, 0).is_ok());
+        assert!(check_error_ratio(&config, 10, 8).is_ok());
+        assert!(check_error_ratio(&config, 10, 7).is_err());
+        assert!(check_error_ratio(&config, 100, 0).is_err());
+        // more established than attempted in the window
+        assert!(check_error_ratio(&config, 10, 12).is_ok());
+    }
+}
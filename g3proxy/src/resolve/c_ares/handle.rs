@@ -27,12 +27,15 @@ use g3_types::metrics::NodeName;
 
 use crate::config::resolver::c_ares::CAresResolverConfig;
 use crate::config::resolver::ResolverConfig;
-use crate::resolve::{BoxLoggedResolveJob, IntegratedResolverHandle, LoggedResolveJob};
+use crate::resolve::{
+    BoxLoggedResolveJob, IntegratedResolverHandle, LoggedResolveJob, ResolveRebindFilter,
+};
 
 pub(crate) struct CAresResolverHandle {
     config: Arc<CAresResolverConfig>,
     inner: g3_resolver::ResolverHandle,
     logger: Arc<Logger>,
+    rebind_filter: Option<Arc<ResolveRebindFilter>>,
 }
 
 impl CAresResolverHandle {
@@ -40,11 +43,13 @@ impl CAresResolverHandle {
         config: &Arc<CAresResolverConfig>,
         inner: g3_resolver::ResolverHandle,
         logger: &Arc<Logger>,
+        rebind_filter: Option<Arc<ResolveRebindFilter>>,
     ) -> Self {
         CAresResolverHandle {
             config: Arc::clone(config),
             inner,
             logger: Arc::clone(logger),
+            rebind_filter,
         }
     }
 }
@@ -82,6 +87,10 @@ impl IntegratedResolverHandle for CAresResolverHandle {
         }))
     }
 
+    fn rebind_filter(&self) -> Option<&Arc<ResolveRebindFilter>> {
+        self.rebind_filter.as_ref()
+    }
+
     fn clone_inner(&self) -> Option<g3_resolver::ResolverHandle> {
         Some(self.inner.clone())
     }
@@ -26,7 +26,8 @@ use g3_types::metrics::NodeName;
 use crate::config::resolver::c_ares::CAresResolverConfig;
 use crate::config::resolver::{AnyResolverConfig, ResolverConfig};
 use crate::resolve::{
-    ArcIntegratedResolverHandle, BoxResolver, Resolver, ResolverInternal, ResolverStats,
+    ArcIntegratedResolverHandle, BoxResolver, ResolveRebindFilter, Resolver, ResolverInternal,
+    ResolverStats,
 };
 
 pub(crate) struct CAresResolver {
@@ -34,6 +35,7 @@ pub(crate) struct CAresResolver {
     inner: g3_resolver::Resolver,
     stats: Arc<ResolverStats>,
     logger: Arc<Logger>,
+    rebind_filter: Option<Arc<ResolveRebindFilter>>,
 }
 
 impl CAresResolver {
@@ -44,12 +46,19 @@ impl CAresResolver {
 
         let logger = crate::log::resolve::get_logger(config.resolver_type(), config.name());
         let stats = ResolverStats::new(config.name(), resolver.get_stats());
+        let logger = Arc::new(logger);
+        let rebind_filter = ResolveRebindFilter::new_optional(
+            config.name(),
+            config.rebind_protection.as_ref(),
+            &logger,
+        );
 
         Ok(Box::new(CAresResolver {
             config: Arc::new(config),
             inner: resolver,
             stats: Arc::new(stats),
-            logger: Arc::new(logger),
+            logger,
+            rebind_filter,
         }))
     }
 }
@@ -73,6 +82,11 @@ impl ResolverInternal for CAresResolver {
             self.inner
                 .update_config((&config).into())
                 .context("failed to update inner c_ares resolver config")?;
+            self.rebind_filter = ResolveRebindFilter::new_optional(
+                config.name(),
+                config.rebind_protection.as_ref(),
+                &self.logger,
+            );
             self.config = Arc::new(config);
             Ok(())
         } else {
@@ -100,6 +114,7 @@ impl Resolver for CAresResolver {
             &self.config,
             inner_context,
             &self.logger,
+            self.rebind_filter.clone(),
         ))
     }
 
@@ -27,12 +27,15 @@ use g3_types::metrics::NodeName;
 
 use crate::config::resolver::fail_over::FailOverResolverConfig;
 use crate::config::resolver::ResolverConfig;
-use crate::resolve::{BoxLoggedResolveJob, IntegratedResolverHandle, LoggedResolveJob};
+use crate::resolve::{
+    BoxLoggedResolveJob, IntegratedResolverHandle, LoggedResolveJob, ResolveRebindFilter,
+};
 
 pub(crate) struct FailOverResolverHandle {
     config: Arc<FailOverResolverConfig>,
     inner: g3_resolver::ResolverHandle,
     logger: Arc<Logger>,
+    rebind_filter: Option<Arc<ResolveRebindFilter>>,
 }
 
 impl FailOverResolverHandle {
@@ -40,11 +43,13 @@ impl FailOverResolverHandle {
         config: &Arc<FailOverResolverConfig>,
         inner: g3_resolver::ResolverHandle,
         logger: &Arc<Logger>,
+        rebind_filter: Option<Arc<ResolveRebindFilter>>,
     ) -> Self {
         FailOverResolverHandle {
             config: Arc::clone(config),
             inner,
             logger: Arc::clone(logger),
+            rebind_filter,
         }
     }
 }
@@ -82,6 +87,10 @@ impl IntegratedResolverHandle for FailOverResolverHandle {
         }))
     }
 
+    fn rebind_filter(&self) -> Option<&Arc<ResolveRebindFilter>> {
+        self.rebind_filter.as_ref()
+    }
+
     fn clone_inner(&self) -> Option<g3_resolver::ResolverHandle> {
         Some(self.inner.clone())
     }
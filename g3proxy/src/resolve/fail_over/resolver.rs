@@ -27,7 +27,8 @@ use g3_types::metrics::NodeName;
 use crate::config::resolver::fail_over::FailOverResolverConfig;
 use crate::config::resolver::{AnyResolverConfig, ResolverConfig};
 use crate::resolve::{
-    ArcIntegratedResolverHandle, BoxResolver, Resolver, ResolverInternal, ResolverStats,
+    ArcIntegratedResolverHandle, BoxResolver, ResolveRebindFilter, Resolver, ResolverInternal,
+    ResolverStats,
 };
 
 pub(crate) struct FailOverResolver {
@@ -36,6 +37,7 @@ pub(crate) struct FailOverResolver {
     inner: g3_resolver::Resolver,
     stats: Arc<ResolverStats>,
     logger: Arc<Logger>,
+    rebind_filter: Option<Arc<ResolveRebindFilter>>,
 }
 
 impl FailOverResolver {
@@ -61,13 +63,20 @@ impl FailOverResolver {
 
         let logger = crate::log::resolve::get_logger(config.resolver_type(), config.name());
         let stats = ResolverStats::new(config.name(), resolver.get_stats());
+        let logger = Arc::new(logger);
+        let rebind_filter = ResolveRebindFilter::new_optional(
+            config.name(),
+            config.rebind_protection.as_ref(),
+            &logger,
+        );
 
         Ok(Box::new(FailOverResolver {
             config: Arc::new(config),
             driver_config,
             inner: resolver,
             stats: Arc::new(stats),
-            logger: Arc::new(logger),
+            logger,
+            rebind_filter,
         }))
     }
 }
@@ -106,6 +115,11 @@ impl ResolverInternal for FailOverResolver {
                 .update_config(inner_config)
                 .context("failed to update inner fail_over resolver config")?;
             self.driver_config = driver_config;
+            self.rebind_filter = ResolveRebindFilter::new_optional(
+                config.name(),
+                config.rebind_protection.as_ref(),
+                &self.logger,
+            );
             self.config = Arc::new(config);
             Ok(())
         } else {
@@ -156,6 +170,7 @@ impl Resolver for FailOverResolver {
             &self.config,
             inner_context,
             &self.logger,
+            self.rebind_filter.clone(),
         ))
     }
 
@@ -24,6 +24,8 @@ use g3_resolver::{ResolveError, ResolvedRecordSource};
 use g3_types::metrics::NodeName;
 use g3_types::resolve::{QueryStrategy, ResolveRedirectionValue, ResolveStrategy};
 
+use super::{RebindFilteredResolveJob, ResolveRebindFilter};
+
 pub(crate) trait LoggedResolveJob {
     fn log_error(&self, _e: &ResolveError, _source: ResolvedRecordSource) {}
     fn poll_query(&mut self, cx: &mut Context<'_>) -> Poll<Result<Vec<IpAddr>, ResolveError>>;
@@ -53,6 +55,9 @@ pub(crate) trait IntegratedResolverHandle {
     fn is_closed(&self) -> bool;
     fn query_v4(&self, domain: Arc<str>) -> Result<BoxLoggedResolveJob, ResolveError>;
     fn query_v6(&self, domain: Arc<str>) -> Result<BoxLoggedResolveJob, ResolveError>;
+    fn rebind_filter(&self) -> Option<&Arc<ResolveRebindFilter>> {
+        None
+    }
 
     fn clone_inner(&self) -> Option<g3_resolver::ResolverHandle>;
 }
@@ -154,13 +159,45 @@ impl HappyEyeballsResolveJob {
         s: ResolveStrategy,
         h: &ArcIntegratedResolverHandle,
         domain: Arc<str>,
+    ) -> Result<Self, ResolveError> {
+        Self::new_dyn_filtered(s, h, domain, None)
+    }
+
+    /// Like `new_dyn`, but apply the DNS rebinding protection filter of the resolver
+    /// if it is enabled and the domain is not in its allow list
+    pub(crate) fn new_dyn_rebind_protected(
+        s: ResolveStrategy,
+        h: &ArcIntegratedResolverHandle,
+        domain: Arc<str>,
+    ) -> Result<Self, ResolveError> {
+        let filter = h.rebind_filter().filter(|f| !f.allow_domain(&domain));
+        Self::new_dyn_filtered(s, h, domain, filter)
+    }
+
+    fn new_dyn_filtered(
+        s: ResolveStrategy,
+        h: &ArcIntegratedResolverHandle,
+        domain: Arc<str>,
+        filter: Option<&Arc<ResolveRebindFilter>>,
     ) -> Result<Self, ResolveError> {
         if domain.is_empty() {
             return Err(ResolveError::EmptyDomain);
         }
+        let wrap = |job: BoxLoggedResolveJob, domain: &Arc<str>| match filter {
+            Some(f) => RebindFilteredResolveJob::wrap(job, f, domain),
+            None => job,
+        };
+        let query_v4 = |domain: Arc<str>| -> Result<BoxLoggedResolveJob, ResolveError> {
+            let job = h.query_v4(domain.clone())?;
+            Ok(wrap(job, &domain))
+        };
+        let query_v6 = |domain: Arc<str>| -> Result<BoxLoggedResolveJob, ResolveError> {
+            let job = h.query_v6(domain.clone())?;
+            Ok(wrap(job, &domain))
+        };
         match s.query {
             QueryStrategy::Ipv4Only => {
-                let h1 = h.query_v4(domain)?;
+                let h1 = query_v4(domain)?;
                 let h2 = Box::new(NeverResolveJob {});
                 Ok(HappyEyeballsResolveJob {
                     r1: None,
@@ -174,8 +211,8 @@ impl HappyEyeballsResolveJob {
                 })
             }
             QueryStrategy::Ipv4First => {
-                let h1 = h.query_v4(domain.clone())?;
-                let h2 = h.query_v6(domain)?;
+                let h1 = query_v4(domain.clone())?;
+                let h2 = query_v6(domain)?;
                 Ok(HappyEyeballsResolveJob {
                     r1: None,
                     r2: None,
@@ -188,7 +225,7 @@ impl HappyEyeballsResolveJob {
                 })
             }
             QueryStrategy::Ipv6Only => {
-                let h1 = h.query_v6(domain)?;
+                let h1 = query_v6(domain)?;
                 let h2 = Box::new(NeverResolveJob {});
                 Ok(HappyEyeballsResolveJob {
                     r1: None,
@@ -202,8 +239,8 @@ impl HappyEyeballsResolveJob {
                 })
             }
             QueryStrategy::Ipv6First => {
-                let h1 = h.query_v6(domain.clone())?;
-                let h2 = h.query_v4(domain)?;
+                let h1 = query_v6(domain.clone())?;
+                let h2 = query_v4(domain)?;
                 Ok(HappyEyeballsResolveJob {
                     r1: None,
                     r2: None,
@@ -345,6 +382,25 @@ impl HappyEyeballsResolveJob {
         self.r2_block = true;
         r
     }
+
+    /// Get the top address of each address family for a connect race,
+    /// or the top 2 addresses of the first family if the other one is not available in time
+    pub(crate) async fn get_race_pair(
+        &mut self,
+        resolution_delay: Duration,
+        second_resolution_delay: Duration,
+    ) -> Result<Vec<IpAddr>, ResolveError> {
+        let mut ips = self.get_r1_or_first(resolution_delay, 2).await?;
+        if let Ok(Ok(r2)) =
+            tokio::time::timeout(second_resolution_delay, self.get_r2_or_never(1)).await
+        {
+            if let Some(ip) = r2.into_iter().next() {
+                ips.truncate(1);
+                ips.push(ip);
+            }
+        }
+        Ok(ips)
+    }
 }
 
 enum ArriveFirstResolveJobInner {
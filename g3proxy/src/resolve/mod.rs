@@ -31,6 +31,9 @@ pub(crate) use handle::{
 };
 use handle::{BoxLoggedResolveJob, ErrorResolveJob, LoggedResolveJob};
 
+mod rebind;
+use rebind::{RebindFilteredResolveJob, ResolveRebindFilter};
+
 mod stats;
 pub(crate) use stats::ResolverStats;
 
@@ -44,11 +47,16 @@ mod hickory;
 
 mod deny_all;
 mod fail_over;
+#[cfg(feature = "hickory")]
+mod zone;
 
 mod ops;
 pub(crate) use ops::reload;
 pub use ops::spawn_all;
 
+mod preflight;
+pub(crate) use preflight::preflight_check;
+
 #[async_trait]
 pub(crate) trait ResolverInternal {
     fn _dependent_resolver(&self) -> Option<BTreeSet<NodeName>>;
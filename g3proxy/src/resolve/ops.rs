@@ -33,6 +33,8 @@ use super::hickory::HickoryResolver;
 
 use super::deny_all::DenyAllResolver;
 use super::fail_over::FailOverResolver;
+#[cfg(feature = "hickory")]
+use super::zone::ZoneResolver;
 
 use super::registry;
 
@@ -182,6 +184,8 @@ async fn spawn_new_unlocked(config: AnyResolverConfig) -> anyhow::Result<()> {
         AnyResolverConfig::Hickory(c) => HickoryResolver::new_obj(*c)?,
         AnyResolverConfig::DenyAll(c) => DenyAllResolver::new_obj(c)?,
         AnyResolverConfig::FailOver(c) => FailOverResolver::new_obj(c)?,
+        #[cfg(feature = "hickory")]
+        AnyResolverConfig::Zone(c) => ZoneResolver::new_obj(*c)?,
     };
     let old_resolver = registry::add(name.clone(), resolver);
     update_dependency_to_resolver_unlocked(&name, STATUS).await;
@@ -0,0 +1,65 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use anyhow::anyhow;
+
+use g3_resolver::ResolveError;
+use g3_types::resolve::ResolveStrategy;
+
+use super::ArriveFirstResolveJob;
+use crate::config::preflight::PreflightConfig;
+use crate::config::resolver::AnyResolverConfig;
+use crate::preflight::{PreflightComponent, PreflightReport};
+
+pub(crate) async fn preflight_check(config: &PreflightConfig, report: &mut PreflightReport) {
+    let mut all_handles = Vec::new();
+    super::registry::foreach(|name, resolver| {
+        // the DenyAll resolver will never answer
+        if matches!(resolver._clone_config(), AnyResolverConfig::DenyAll(_)) {
+            return;
+        }
+        all_handles.push((name.clone(), resolver.get_handle()));
+    });
+
+    for (name, handle) in all_handles {
+        let r = match ArriveFirstResolveJob::new(
+            &handle,
+            ResolveStrategy::default(),
+            config.resolve_test_domain.clone(),
+        ) {
+            Ok(mut job) => {
+                match tokio::time::timeout(
+                    config.check_timeout,
+                    std::future::poll_fn(|cx| job.poll_all_addrs(cx)),
+                )
+                .await
+                {
+                    // the resolver is working if the server answers, even if the domain not found
+                    Ok(Ok(_)) | Ok(Err(ResolveError::FromServer(_))) => Ok(()),
+                    Ok(Err(e)) => Err(anyhow!(
+                        "failed to resolve {}: {e}",
+                        config.resolve_test_domain
+                    )),
+                    Err(_) => Err(anyhow!(
+                        "timed out to resolve {}",
+                        config.resolve_test_domain
+                    )),
+                }
+            }
+            Err(e) => Err(anyhow!("failed to create resolve job: {e}")),
+        };
+        report.add(PreflightComponent::Resolver, &name, config.resolver, r);
+    }
+}
@@ -0,0 +1,190 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use slog::{slog_info, Logger};
+
+use g3_resolver::ResolveError;
+use g3_slog_types::LtIpAddr;
+use g3_types::acl::{AclAction, AclChildDomainRule};
+use g3_types::metrics::NodeName;
+
+use super::{BoxLoggedResolveJob, LoggedResolveJob};
+use crate::config::resolver::ResolveRebindProtectionConfig;
+
+pub(crate) struct ResolveRebindFilter {
+    resolver: NodeName,
+    filter_loopback: bool,
+    filter_private: bool,
+    filter_link_local: bool,
+    allow_domains: Option<AclChildDomainRule>,
+    logger: Arc<Logger>,
+}
+
+impl ResolveRebindFilter {
+    pub(crate) fn new(
+        resolver: &NodeName,
+        config: &ResolveRebindProtectionConfig,
+        logger: &Arc<Logger>,
+    ) -> Self {
+        ResolveRebindFilter {
+            resolver: resolver.clone(),
+            filter_loopback: config.filter_loopback,
+            filter_private: config.filter_private,
+            filter_link_local: config.filter_link_local,
+            allow_domains: config.allow_domains.as_ref().map(|b| b.build()),
+            logger: Arc::clone(logger),
+        }
+    }
+
+    pub(crate) fn new_optional(
+        resolver: &NodeName,
+        config: Option<&ResolveRebindProtectionConfig>,
+        logger: &Arc<Logger>,
+    ) -> Option<Arc<Self>> {
+        config.map(|config| Arc::new(ResolveRebindFilter::new(resolver, config, logger)))
+    }
+
+    pub(crate) fn allow_domain(&self, domain: &str) -> bool {
+        self.allow_domains
+            .as_ref()
+            .map(|rule| matches!(rule.check(domain), (true, AclAction::Permit)))
+            .unwrap_or(false)
+    }
+
+    fn drop_reason(&self, ip: IpAddr) -> Option<&'static str> {
+        match ip {
+            IpAddr::V4(ip4) => self.drop_reason_v4(ip4),
+            IpAddr::V6(ip6) => {
+                if let Some(ip4) = ip6.to_ipv4_mapped() {
+                    return self.drop_reason_v4(ip4);
+                }
+                self.drop_reason_v6(ip6)
+            }
+        }
+    }
+
+    fn drop_reason_v4(&self, ip: Ipv4Addr) -> Option<&'static str> {
+        if self.filter_loopback && (ip.is_loopback() || ip.is_unspecified()) {
+            return Some("loopback");
+        }
+        if self.filter_link_local && ip.is_link_local() {
+            return Some("link-local");
+        }
+        if self.filter_private {
+            let o = ip.octets();
+            // RFC 6598 shared address space is treated the same as RFC 1918
+            if ip.is_private() || (o[0] == 100 && (o[1] & 0b1100_0000) == 64) {
+                return Some("private");
+            }
+        }
+        None
+    }
+
+    fn drop_reason_v6(&self, ip: Ipv6Addr) -> Option<&'static str> {
+        if self.filter_loopback && (ip.is_loopback() || ip.is_unspecified()) {
+            return Some("loopback");
+        }
+        let first = ip.segments()[0];
+        if self.filter_link_local && (first & 0xffc0) == 0xfe80 {
+            return Some("link-local");
+        }
+        if self.filter_private && (first & 0xfe00) == 0xfc00 {
+            return Some("private");
+        }
+        None
+    }
+
+    fn filter(&self, domain: &str, ips: Vec<IpAddr>) -> Vec<IpAddr> {
+        let mut kept = Vec::with_capacity(ips.len());
+        for ip in ips {
+            match self.drop_reason(ip) {
+                Some(reason) => {
+                    slog_info!(&self.logger, "dropped {reason} answer for rebind protection";
+                        "resolver" => self.resolver.as_str(),
+                        "domain" => domain,
+                        "ip" => LtIpAddr(ip),
+                    );
+                }
+                None => kept.push(ip),
+            }
+        }
+        kept
+    }
+}
+
+pub(super) struct RebindFilteredResolveJob {
+    inner: BoxLoggedResolveJob,
+    filter: Arc<ResolveRebindFilter>,
+    domain: Arc<str>,
+}
+
+impl RebindFilteredResolveJob {
+    pub(super) fn wrap(
+        inner: BoxLoggedResolveJob,
+        filter: &Arc<ResolveRebindFilter>,
+        domain: &Arc<str>,
+    ) -> BoxLoggedResolveJob {
+        Box::new(RebindFilteredResolveJob {
+            inner,
+            filter: Arc::clone(filter),
+            domain: Arc::clone(domain),
+        })
+    }
+}
+
+impl LoggedResolveJob for RebindFilteredResolveJob {
+    fn poll_query(&mut self, cx: &mut Context<'_>) -> Poll<Result<Vec<IpAddr>, ResolveError>> {
+        let ips = ready!(self.inner.poll_query(cx))?;
+        Poll::Ready(Ok(self.filter.filter(&self.domain, ips)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter_all() -> ResolveRebindFilter {
+        let logger = Logger::root(slog::Discard, slog::o!());
+        ResolveRebindFilter::new(
+            &NodeName::default(),
+            &ResolveRebindProtectionConfig::default(),
+            &Arc::new(logger),
+        )
+    }
+
+    #[test]
+    fn drop_reason() {
+        let f = filter_all();
+        let check = |s: &str| f.drop_reason(s.parse().unwrap());
+        assert_eq!(check("127.0.0.1"), Some("loopback"));
+        assert_eq!(check("0.0.0.0"), Some("loopback"));
+        assert_eq!(check("::1"), Some("loopback"));
+        assert_eq!(check("::ffff:127.0.0.1"), Some("loopback"));
+        assert_eq!(check("169.254.169.254"), Some("link-local"));
+        assert_eq!(check("fe80::1"), Some("link-local"));
+        assert_eq!(check("10.1.2.3"), Some("private"));
+        assert_eq!(check("192.168.0.1"), Some("private"));
+        assert_eq!(check("100.64.0.1"), Some("private"));
+        assert_eq!(check("fd00::1"), Some("private"));
+        assert_eq!(check("100.128.0.1"), None);
+        assert_eq!(check("8.8.8.8"), None);
+        assert_eq!(check("2001:db8::1"), None);
+    }
+}
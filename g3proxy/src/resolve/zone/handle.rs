@@ -0,0 +1,122 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use slog::{slog_info, Logger};
+use tokio::time::Instant;
+
+use g3_resolver::{ResolveError, ResolveQueryType, ResolvedRecordSource};
+use g3_slog_types::LtDuration;
+use g3_types::metrics::NodeName;
+
+use crate::config::resolver::zone::ZoneResolverConfig;
+use crate::config::resolver::ResolverConfig;
+use crate::resolve::{
+    BoxLoggedResolveJob, IntegratedResolverHandle, LoggedResolveJob, ResolveRebindFilter,
+};
+
+pub(crate) struct ZoneResolverHandle {
+    config: Arc<ZoneResolverConfig>,
+    inner: g3_resolver::ResolverHandle,
+    logger: Arc<Logger>,
+    rebind_filter: Option<Arc<ResolveRebindFilter>>,
+}
+
+impl ZoneResolverHandle {
+    pub(crate) fn new(
+        config: &Arc<ZoneResolverConfig>,
+        inner: g3_resolver::ResolverHandle,
+        logger: &Arc<Logger>,
+        rebind_filter: Option<Arc<ResolveRebindFilter>>,
+    ) -> Self {
+        ZoneResolverHandle {
+            config: Arc::clone(config),
+            inner,
+            logger: Arc::clone(logger),
+            rebind_filter,
+        }
+    }
+}
+
+impl IntegratedResolverHandle for ZoneResolverHandle {
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+
+    fn query_v4(&self, domain: Arc<str>) -> Result<BoxLoggedResolveJob, ResolveError> {
+        let job = self.inner.get_v4(domain.clone())?;
+        Ok(Box::new(ZoneResolverJob {
+            config: Arc::clone(&self.config),
+            domain,
+            query_type: ResolveQueryType::A,
+            inner: job,
+            logger: Arc::clone(&self.logger),
+            create_ins: Instant::now(),
+        }))
+    }
+
+    fn query_v6(&self, domain: Arc<str>) -> Result<BoxLoggedResolveJob, ResolveError> {
+        let job = self.inner.get_v6(domain.clone())?;
+        Ok(Box::new(ZoneResolverJob {
+            config: Arc::clone(&self.config),
+            domain,
+            query_type: ResolveQueryType::Aaaa,
+            inner: job,
+            logger: Arc::clone(&self.logger),
+            create_ins: Instant::now(),
+        }))
+    }
+
+    fn rebind_filter(&self) -> Option<&Arc<ResolveRebindFilter>> {
+        self.rebind_filter.as_ref()
+    }
+
+    fn clone_inner(&self) -> Option<g3_resolver::ResolverHandle> {
+        Some(self.inner.clone())
+    }
+}
+
+struct ZoneResolverJob {
+    config: Arc<ZoneResolverConfig>,
+    domain: Arc<str>,
+    query_type: ResolveQueryType,
+    inner: g3_resolver::ResolveJob,
+    logger: Arc<Logger>,
+    create_ins: Instant,
+}
+
+impl LoggedResolveJob for ZoneResolverJob {
+    fn log_error(&self, e: &ResolveError, source: ResolvedRecordSource) {
+        slog_info!(&self.logger, "{}", e;
+            "next_fallback" => &self.config.fallback.as_str(),
+            "query_type" => self.query_type.as_str(),
+            "duration" => LtDuration(self.create_ins.elapsed()),
+            "rr_source" => source.as_str(),
+            "error_type" => e.get_type(),
+            "error_subtype" => e.get_subtype(),
+            "domain" => &self.domain,
+        );
+    }
+
+    impl_logged_poll_query!();
+}
@@ -0,0 +1,172 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use async_trait::async_trait;
+use slog::Logger;
+
+use g3_resolver::driver::zone::ZoneDriverConfig;
+use g3_types::metrics::NodeName;
+
+use crate::config::resolver::zone::ZoneResolverConfig;
+use crate::config::resolver::{AnyResolverConfig, ResolverConfig};
+use crate::resolve::{
+    ArcIntegratedResolverHandle, BoxResolver, ResolveRebindFilter, Resolver, ResolverInternal,
+    ResolverStats,
+};
+
+pub(crate) struct ZoneResolver {
+    config: Arc<ZoneResolverConfig>,
+    driver_config: ZoneDriverConfig,
+    inner: g3_resolver::Resolver,
+    stats: Arc<ResolverStats>,
+    logger: Arc<Logger>,
+    rebind_filter: Option<Arc<ResolveRebindFilter>>,
+}
+
+impl ZoneResolver {
+    pub(crate) fn new_obj(config: ZoneResolverConfig) -> anyhow::Result<BoxResolver> {
+        let mut driver_config = config.driver.clone();
+        if !config.fallback.is_empty() {
+            let fallback_handle = crate::resolve::get_handle(&config.fallback)
+                .context("failed to get fallback resolver handle")?;
+            driver_config.set_fallback_handle(fallback_handle.clone_inner());
+        }
+
+        let inner_config = g3_resolver::ResolverConfig {
+            name: config.name().to_string(),
+            runtime: config.runtime.clone(),
+            driver: g3_resolver::AnyResolveDriverConfig::Zone(Box::new(driver_config.clone())),
+        };
+        let mut builder = g3_resolver::ResolverBuilder::new(inner_config);
+        builder.thread_name(format!("res-{}", config.name()));
+        let resolver = builder.build()?;
+
+        let logger = crate::log::resolve::get_logger(config.resolver_type(), config.name());
+        let stats = ResolverStats::new(config.name(), resolver.get_stats());
+        let logger = Arc::new(logger);
+        let rebind_filter = ResolveRebindFilter::new_optional(
+            config.name(),
+            config.rebind_protection.as_ref(),
+            &logger,
+        );
+
+        Ok(Box::new(ZoneResolver {
+            config: Arc::new(config),
+            driver_config,
+            inner: resolver,
+            stats: Arc::new(stats),
+            logger,
+            rebind_filter,
+        }))
+    }
+}
+
+#[async_trait]
+impl ResolverInternal for ZoneResolver {
+    fn _dependent_resolver(&self) -> Option<BTreeSet<NodeName>> {
+        self.config.dependent_resolver()
+    }
+
+    fn _clone_config(&self) -> AnyResolverConfig {
+        AnyResolverConfig::Zone(Box::new(self.config.as_ref().clone()))
+    }
+
+    fn _update_config(
+        &mut self,
+        config: AnyResolverConfig,
+        dep_table: BTreeMap<NodeName, ArcIntegratedResolverHandle>,
+    ) -> anyhow::Result<()> {
+        if let AnyResolverConfig::Zone(config) = config {
+            let mut driver_config = config.driver.clone();
+            if let Some(fallback_handle) = dep_table.get(&config.fallback) {
+                driver_config.set_fallback_handle(fallback_handle.clone_inner());
+            }
+
+            let inner_config = g3_resolver::ResolverConfig {
+                name: config.name().to_string(),
+                runtime: config.runtime.clone(),
+                driver: g3_resolver::AnyResolveDriverConfig::Zone(Box::new(driver_config.clone())),
+            };
+
+            self.inner
+                .update_config(inner_config)
+                .context("failed to update inner zone resolver config")?;
+            self.driver_config = driver_config;
+            self.rebind_filter = ResolveRebindFilter::new_optional(
+                config.name(),
+                config.rebind_protection.as_ref(),
+                &self.logger,
+            );
+            self.config = Arc::new(*config);
+            Ok(())
+        } else {
+            Err(anyhow!("invalid config type for ZoneResolver"))
+        }
+    }
+
+    fn _update_dependent_handle(
+        &mut self,
+        target: &NodeName,
+        handle: ArcIntegratedResolverHandle,
+    ) -> anyhow::Result<()> {
+        let mut driver_config = self.driver_config.clone();
+        if self.config.fallback.eq(target) {
+            driver_config.set_fallback_handle(handle.clone_inner());
+        } else {
+            return Err(anyhow!(
+                "resolver {} doesn't depend on resolver {}",
+                self.config.name(),
+                target
+            ));
+        }
+
+        let inner_config = g3_resolver::ResolverConfig {
+            name: self.config.name().to_string(),
+            runtime: self.config.runtime.clone(),
+            driver: g3_resolver::AnyResolveDriverConfig::Zone(Box::new(driver_config.clone())),
+        };
+
+        self.inner
+            .update_config(inner_config)
+            .context("failed to update inner zone resolver config")?;
+        self.driver_config = driver_config;
+        Ok(())
+    }
+
+    async fn _shutdown(&mut self) {
+        self.inner.shutdown().await;
+    }
+}
+
+impl Resolver for ZoneResolver {
+    fn get_handle(&self) -> ArcIntegratedResolverHandle {
+        let inner_context = self.inner.get_handle();
+        Arc::new(super::ZoneResolverHandle::new(
+            &self.config,
+            inner_context,
+            &self.logger,
+            self.rebind_filter.clone(),
+        ))
+    }
+
+    fn get_stats(&self) -> Arc<ResolverStats> {
+        Arc::clone(&self.stats)
+    }
+}
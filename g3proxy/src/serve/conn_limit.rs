@@ -0,0 +1,270 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::sync::{Arc, Mutex};
+
+use ahash::AHashMap;
+
+use crate::config::server::client_conn_limit::ClientConnLimitConfig;
+
+struct ClientConnTable {
+    ip: AHashMap<IpAddr, usize>,
+    subnet: AHashMap<IpAddr, usize>,
+}
+
+impl ClientConnTable {
+    fn decrease(map: &mut AHashMap<IpAddr, usize>, key: &IpAddr) {
+        if let Some(count) = map.get_mut(key) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                map.remove(key);
+            }
+        }
+    }
+
+    fn top(map: &AHashMap<IpAddr, usize>, n: usize) -> Vec<(IpAddr, usize)> {
+        let mut all: Vec<(IpAddr, usize)> = map.iter().map(|(k, v)| (*k, *v)).collect();
+        all.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+        all.truncate(n);
+        all
+    }
+}
+
+/// Track alive connections per client ip and per client subnet.
+///
+/// Entries are removed only when there is no alive connection left. When there are already
+/// `max_tracked` different client ips or subnets, connections from new ones will be refused.
+pub(crate) struct ClientConnLimiter {
+    config: ClientConnLimitConfig,
+    table: Mutex<ClientConnTable>,
+}
+
+impl ClientConnLimiter {
+    pub(crate) fn new(config: &ClientConnLimitConfig) -> Self {
+        ClientConnLimiter {
+            config: config.clone(),
+            table: Mutex::new(ClientConnTable {
+                ip: AHashMap::new(),
+                subnet: AHashMap::new(),
+            }),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn config(&self) -> &ClientConnLimitConfig {
+        &self.config
+    }
+
+    fn subnet_of(&self, ip: IpAddr) -> IpAddr {
+        match ip {
+            IpAddr::V4(v4) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - self.config.ipv4_prefix as u32)
+                    .unwrap_or(0);
+                IpAddr::V4(Ipv4Addr::from(u32::from(v4) & mask))
+            }
+            IpAddr::V6(v6) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.config.ipv6_prefix as u32)
+                    .unwrap_or(0);
+                IpAddr::V6(Ipv6Addr::from(u128::from(v6) & mask))
+            }
+        }
+    }
+
+    fn subnet_prefix(&self, subnet: &IpAddr) -> u8 {
+        match subnet {
+            IpAddr::V4(_) => self.config.ipv4_prefix,
+            IpAddr::V6(_) => self.config.ipv6_prefix,
+        }
+    }
+
+    fn try_acquire(self: &Arc<Self>, ip: IpAddr) -> Option<ClientConnGuard> {
+        let ip = ip.to_canonical();
+        let subnet = self.subnet_of(ip);
+
+        let max_tracked = self.config.max_tracked.get();
+        let mut table = self.table.lock().unwrap();
+        let ip_count = match table.ip.get(&ip) {
+            Some(count) => *count,
+            None if table.ip.len() >= max_tracked => return None,
+            None => 0,
+        };
+        if self.config.max_per_ip > 0 && ip_count >= self.config.max_per_ip {
+            return None;
+        }
+        let subnet_count = match table.subnet.get(&subnet) {
+            Some(count) => *count,
+            None if table.subnet.len() >= max_tracked => return None,
+            None => 0,
+        };
+        if self.config.max_per_subnet > 0 && subnet_count >= self.config.max_per_subnet {
+            return None;
+        }
+        table.ip.insert(ip, ip_count + 1);
+        table.subnet.insert(subnet, subnet_count + 1);
+
+        Some(ClientConnGuard {
+            limiter: Some(Arc::clone(self)),
+            ip,
+            subnet,
+        })
+    }
+
+    fn release(&self, ip: &IpAddr, subnet: &IpAddr) {
+        let mut table = self.table.lock().unwrap();
+        ClientConnTable::decrease(&mut table.ip, ip);
+        ClientConnTable::decrease(&mut table.subnet, subnet);
+    }
+
+    /// get the top n client ip with the most alive connections
+    pub(crate) fn top_ip(&self, n: usize) -> Vec<(IpAddr, usize)> {
+        let table = self.table.lock().unwrap();
+        ClientConnTable::top(&table.ip, n)
+    }
+
+    /// get the top n client subnet with the most alive connections
+    pub(crate) fn top_subnet(&self, n: usize) -> Vec<(IpAddr, u8, usize)> {
+        let table = self.table.lock().unwrap();
+        ClientConnTable::top(&table.subnet, n)
+            .into_iter()
+            .map(|(subnet, count)| (subnet, self.subnet_prefix(&subnet), count))
+            .collect()
+    }
+}
+
+/// Hold a slot in the client connection table, which will be released on drop.
+pub(crate) struct ClientConnGuard {
+    limiter: Option<Arc<ClientConnLimiter>>,
+    ip: IpAddr,
+    subnet: IpAddr,
+}
+
+impl ClientConnGuard {
+    /// Acquire a guard for a new connection from `ip`.
+    ///
+    /// `None` will be returned if the connection should be dropped as over limit.
+    pub(crate) fn acquire(
+        limiter: Option<&Arc<ClientConnLimiter>>,
+        ip: IpAddr,
+    ) -> Option<ClientConnGuard> {
+        match limiter {
+            Some(limiter) => limiter.try_acquire(ip),
+            None => Some(ClientConnGuard {
+                limiter: None,
+                ip,
+                subnet: ip,
+            }),
+        }
+    }
+}
+
+impl Drop for ClientConnGuard {
+    fn drop(&mut self) {
+        if let Some(limiter) = self.limiter.take() {
+            limiter.release(&self.ip, &self.subnet);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::NonZeroUsize;
+
+    fn new_limiter(max_per_ip: usize, max_per_subnet: usize) -> Arc<ClientConnLimiter> {
+        new_limiter_tracked(max_per_ip, max_per_subnet, 16)
+    }
+
+    fn new_limiter_tracked(
+        max_per_ip: usize,
+        max_per_subnet: usize,
+        max_tracked: usize,
+    ) -> Arc<ClientConnLimiter> {
+        let config = ClientConnLimitConfig {
+            max_per_ip,
+            max_per_subnet,
+            ipv4_prefix: 24,
+            ipv6_prefix: 64,
+            max_tracked: NonZeroUsize::new(max_tracked).unwrap(),
+        };
+        Arc::new(ClientConnLimiter::new(&config))
+    }
+
+    #[test]
+    fn per_ip() {
+        let limiter = new_limiter(2, 0);
+        let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1));
+
+        let g1 = ClientConnGuard::acquire(Some(&limiter), ip).unwrap();
+        let _g2 = ClientConnGuard::acquire(Some(&limiter), ip).unwrap();
+        assert!(ClientConnGuard::acquire(Some(&limiter), ip).is_none());
+        assert_eq!(limiter.top_ip(1), vec![(ip, 2)]);
+
+        drop(g1);
+        assert!(ClientConnGuard::acquire(Some(&limiter), ip).is_some());
+    }
+
+    #[test]
+    fn per_subnet() {
+        let limiter = new_limiter(0, 2);
+        let ip1 = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1));
+        let ip2 = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 2));
+        let ip3 = IpAddr::V4(Ipv4Addr::new(192, 168, 2, 1));
+
+        let _g1 = ClientConnGuard::acquire(Some(&limiter), ip1).unwrap();
+        let _g2 = ClientConnGuard::acquire(Some(&limiter), ip2).unwrap();
+        assert!(ClientConnGuard::acquire(Some(&limiter), ip1).is_none());
+        assert!(ClientConnGuard::acquire(Some(&limiter), ip3).is_some());
+
+        let subnet = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 0));
+        assert_eq!(limiter.top_subnet(1), vec![(subnet, 24, 2)]);
+    }
+
+    #[test]
+    fn release_all() {
+        let limiter = new_limiter(1, 1);
+        let ip = IpAddr::V6(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1));
+
+        let g = ClientConnGuard::acquire(Some(&limiter), ip).unwrap();
+        drop(g);
+        assert!(limiter.top_ip(10).is_empty());
+        assert!(limiter.top_subnet(10).is_empty());
+    }
+
+    #[test]
+    fn table_full() {
+        let limiter = new_limiter_tracked(1, 0, 2);
+        let ip1 = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1));
+        let ip2 = IpAddr::V4(Ipv4Addr::new(192, 168, 2, 1));
+        let ip3 = IpAddr::V4(Ipv4Addr::new(192, 168, 3, 1));
+
+        let g1 = ClientConnGuard::acquire(Some(&limiter), ip1).unwrap();
+        let _g2 = ClientConnGuard::acquire(Some(&limiter), ip2).unwrap();
+        // new clients should be refused, and the tracked ones should not be evicted
+        assert!(ClientConnGuard::acquire(Some(&limiter), ip3).is_none());
+        assert!(ClientConnGuard::acquire(Some(&limiter), ip1).is_none());
+        assert_eq!(limiter.top_ip(10).len(), 2);
+
+        drop(g1);
+        let _g3 = ClientConnGuard::acquire(Some(&limiter), ip3).unwrap();
+        assert!(ClientConnGuard::acquire(Some(&limiter), ip1).is_none());
+        let mut top = limiter.top_ip(10);
+        top.sort();
+        assert_eq!(top, vec![(ip2, 1), (ip3, 1)]);
+    }
+}
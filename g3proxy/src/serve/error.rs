@@ -129,6 +129,10 @@ pub(crate) enum ServerTaskError {
     CanceledAsServerQuit,
     #[error("idle after {0:?} x {1}")]
     Idle(Duration, i32),
+    #[error("client read idle after {0:?} x {1}")]
+    ClientReadIdle(Duration, i32),
+    #[error("client write idle after {0:?} x {1}")]
+    ClientWriteIdle(Duration, i32),
     #[error("{0} interception error: {1}")]
     InterceptionError(Protocol, InterceptionError),
     #[error("finished")]
@@ -173,6 +177,8 @@ impl ServerTaskError {
             ServerTaskError::CanceledAsUserBlocked => "CanceledAsUserBlocked",
             ServerTaskError::CanceledAsServerQuit => "CanceledAsServerQuit",
             ServerTaskError::Idle(_, _) => "Idle",
+            ServerTaskError::ClientReadIdle(_, _) => "ClientReadIdle",
+            ServerTaskError::ClientWriteIdle(_, _) => "ClientWriteIdle",
             ServerTaskError::InterceptionError(_, _) => "InterceptionError",
             ServerTaskError::Finished => "Finished",
             ServerTaskError::UnclassifiedError(_) => "UnclassifiedError",
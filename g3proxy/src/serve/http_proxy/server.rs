@@ -27,9 +27,13 @@ use quinn::Connection;
 use slog::Logger;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::UnixStream;
 use tokio::sync::{broadcast, mpsc};
 use tokio_rustls::{server::TlsStream, TlsAcceptor};
 
+#[cfg(unix)]
+use g3_daemon::listen::ListenUnixRuntime;
 use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime};
 use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
 use g3_io_ext::AsyncStream;
@@ -38,11 +42,12 @@ use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::acl_set::AclDstHostRuleSet;
 use g3_types::metrics::NodeName;
 use g3_types::net::{
-    AlpnProtocol, OpensslClientConfig, OpensslTicketKey, RollingTicketer, RustlsServerConnectionExt,
+    AlpnProtocol, OpensslCertIdentity, OpensslClientConfig, OpensslTicketKey, ProxyRequestType,
+    RollingTicketer, RustlsServerConnectionExt,
 };
 
 use super::task::{
-    CommonTaskContext, HttpProxyPipelineReaderTask, HttpProxyPipelineStats,
+    CommonTaskContext, HttpProxyH2BridgeTask, HttpProxyPipelineReaderTask, HttpProxyPipelineStats,
     HttpProxyPipelineWriterTask,
 };
 use super::HttpProxyServerStats;
@@ -51,8 +56,11 @@ use crate::auth::UserGroup;
 use crate::config::server::http_proxy::HttpProxyServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
+use crate::module::http_forward::HttpForwardH2Pool;
 use crate::serve::{
-    ArcServer, ArcServerStats, Server, ServerInternal, ServerQuitPolicy, ServerStats, WrapArcServer,
+    ArcServer, ArcServerStats, ClientConnGuard, ClientConnLimiter, RouteTestContext,
+    RouteTestReport, RouteTestRequest, Server, ServerInternal, ServerQuitPolicy, ServerStats,
+    WrapArcServer,
 };
 
 pub(crate) struct HttpProxyServer {
@@ -63,7 +71,9 @@ pub(crate) struct HttpProxyServer {
     tls_acceptor: Option<TlsAcceptor>,
     tls_accept_timeout: Duration,
     tls_client_config: Arc<OpensslClientConfig>,
+    h2_pool: Option<Arc<HttpForwardH2Pool>>,
     ingress_net_filter: Option<AclNetworkRule>,
+    client_conn_limiter: Option<Arc<ClientConnLimiter>>,
     dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
     task_logger: Logger,
@@ -81,17 +91,24 @@ impl HttpProxyServer {
         server_stats: Arc<HttpProxyServerStats>,
         listen_stats: Arc<ListenStats>,
         tls_rolling_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
+        client_conn_limiter: Option<Arc<ClientConnLimiter>>,
         version: usize,
     ) -> anyhow::Result<HttpProxyServer> {
         let reload_sender = crate::serve::new_reload_notify_channel();
 
         let mut tls_accept_timeout = Duration::from_secs(10);
         let tls_acceptor = if let Some(tls_config_builder) = &config.server_tls_config {
+            let alpn_protocols = if config.http_forward_client_h2.is_some() {
+                vec![
+                    AlpnProtocol::Http2,
+                    AlpnProtocol::Http11,
+                    AlpnProtocol::Http10,
+                ]
+            } else {
+                vec![AlpnProtocol::Http11, AlpnProtocol::Http10]
+            };
             let tls_server_config = tls_config_builder
-                .build_with_alpn_protocols(
-                    Some(vec![AlpnProtocol::Http11, AlpnProtocol::Http10]),
-                    tls_rolling_ticketer.clone(),
-                )
+                .build_with_alpn_protocols(Some(alpn_protocols), tls_rolling_ticketer.clone())
                 .context("failed to build tls server config")?;
             tls_accept_timeout = tls_server_config.accept_timeout;
             Some(TlsAcceptor::from(tls_server_config.driver))
@@ -104,6 +121,14 @@ impl HttpProxyServer {
             .build()
             .context("failed to build tls client config")?;
 
+        let h2_pool = if let Some(h2_config) = &config.http_forward_upstream_h2 {
+            let pool = HttpForwardH2Pool::new(h2_config, &config.client_tls_config)
+                .context("failed to build upstream h2 connection pool")?;
+            Some(Arc::new(pool))
+        } else {
+            None
+        };
+
         let ingress_net_filter = config
             .ingress_net_filter
             .as_ref()
@@ -131,7 +156,9 @@ impl HttpProxyServer {
             tls_acceptor,
             tls_accept_timeout,
             tls_client_config: Arc::new(tls_client_config),
+            h2_pool,
             ingress_net_filter,
+            client_conn_limiter,
             dst_host_filter,
             reload_sender,
             task_logger,
@@ -152,15 +179,26 @@ impl HttpProxyServer {
 
         let tls_rolling_ticketer = if let Some(c) = &config.tls_ticketer {
             let ticketer = c
-                .build_and_spawn_updater()
+                .build_and_spawn_updater(config.name())
                 .context("failed to create tls rolling ticketer")?;
             Some(ticketer)
         } else {
             None
         };
 
-        let server =
-            HttpProxyServer::new(config, server_stats, listen_stats, tls_rolling_ticketer, 1)?;
+        let client_conn_limiter = config
+            .client_conn_limit
+            .as_ref()
+            .map(|c| Arc::new(ClientConnLimiter::new(c)));
+
+        let server = HttpProxyServer::new(
+            config,
+            server_stats,
+            listen_stats,
+            tls_rolling_ticketer,
+            client_conn_limiter,
+            1,
+        )?;
         Ok(Arc::new(server))
     }
 
@@ -174,18 +212,30 @@ impl HttpProxyServer {
                 self.tls_rolling_ticketer.clone()
             } else if let Some(c) = &config.tls_ticketer {
                 let ticketer = c
-                    .build_and_spawn_updater()
+                    .build_and_spawn_updater(config.name())
                     .context("failed to create tls rolling ticketer")?;
                 Some(ticketer)
             } else {
                 None
             };
 
+            // keep the old limiter if possible, as there may be alive connections
+            let client_conn_limiter = if self.config.client_conn_limit.eq(&config.client_conn_limit)
+            {
+                self.client_conn_limiter.clone()
+            } else {
+                config
+                    .client_conn_limit
+                    .as_ref()
+                    .map(|c| Arc::new(ClientConnLimiter::new(c)))
+            };
+
             let server = HttpProxyServer::new(
                 config,
                 server_stats,
                 listen_stats,
                 tls_rolling_ticketer,
+                client_conn_limiter,
                 self.reload_version + 1,
             )?;
             Ok(server)
@@ -198,14 +248,20 @@ impl HttpProxyServer {
         }
     }
 
-    fn get_common_task_context(&self, cc_info: ClientConnectionInfo) -> Arc<CommonTaskContext> {
+    fn get_common_task_context(
+        &self,
+        cc_info: ClientConnectionInfo,
+        client_cert: Option<Arc<OpensslCertIdentity>>,
+    ) -> Arc<CommonTaskContext> {
         Arc::new(CommonTaskContext {
             server_config: Arc::clone(&self.config),
             server_stats: Arc::clone(&self.server_stats),
             server_quit_policy: Arc::clone(&self.quit_policy),
             escaper: self.escaper.load().as_ref().clone(),
             cc_info,
+            client_cert,
             tls_client_config: self.tls_client_config.clone(),
+            h2_pool: self.h2_pool.clone(),
             task_logger: self.task_logger.clone(),
             dst_host_filter: self.dst_host_filter.clone(),
         })
@@ -228,17 +284,29 @@ impl HttpProxyServer {
         false
     }
 
+    fn acquire_client_conn(&self, client_addr: SocketAddr) -> Option<ClientConnGuard> {
+        let guard = ClientConnGuard::acquire(self.client_conn_limiter.as_ref(), client_addr.ip());
+        if guard.is_none() {
+            self.listen_stats.add_dropped();
+        }
+        guard
+    }
+
     fn audit_context(&self) -> AuditContext {
         AuditContext::new(self.audit_handle.load_full())
     }
 
-    async fn spawn_stream_task<T>(&self, stream: T, cc_info: ClientConnectionInfo)
-    where
+    async fn spawn_stream_task<T>(
+        &self,
+        stream: T,
+        cc_info: ClientConnectionInfo,
+        client_cert: Option<Arc<OpensslCertIdentity>>,
+    ) where
         T: AsyncStream,
         T::R: AsyncRead + Send + Sync + Unpin + 'static,
         T::W: AsyncWrite + Send + Sync + Unpin + 'static,
     {
-        let ctx = self.get_common_task_context(cc_info);
+        let ctx = self.get_common_task_context(cc_info, client_cert);
         let pipeline_stats = Arc::new(HttpProxyPipelineStats::default());
         let (task_sender, task_receiver) = mpsc::channel(ctx.server_config.pipeline_size.get());
 
@@ -259,6 +327,76 @@ impl HttpProxyServer {
         w_task.into_running().await
     }
 
+    fn is_h2_alpn(&self, protocol: Option<&[u8]>) -> bool {
+        self.config.http_forward_client_h2.is_some()
+            && protocol == Some(AlpnProtocol::Http2.identification_sequence())
+    }
+
+    async fn spawn_h2_bridge_task<S>(
+        &self,
+        stream: S,
+        cc_info: ClientConnectionInfo,
+        client_cert: Option<Arc<OpensslCertIdentity>>,
+    ) where
+        S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    {
+        let ctx = self.get_common_task_context(cc_info, client_cert);
+        HttpProxyH2BridgeTask::new(
+            ctx,
+            self.audit_handle.load_full(),
+            self.user_group.load_full(),
+        )
+        .into_running(stream)
+        .await
+    }
+
+    async fn run_maybe_tls_task<S>(&self, stream: S, cc_info: ClientConnectionInfo)
+    where
+        S: AsyncStream + AsyncRead + AsyncWrite + Send + Sync + Unpin + 'static,
+        S::R: AsyncRead + Send + Sync + Unpin + 'static,
+        S::W: AsyncWrite + Send + Sync + Unpin + 'static,
+    {
+        if let Some(tls_acceptor) = &self.tls_acceptor {
+            match tokio::time::timeout(self.tls_accept_timeout, tls_acceptor.accept(stream)).await {
+                Ok(Ok(tls_stream)) => {
+                    if tls_stream.get_ref().1.session_reused() {
+                        // Quick ACK is needed with session resumption
+                        cc_info.tcp_sock_try_quick_ack();
+                    }
+                    let client_cert =
+                        crate::serve::client_cert::from_rustls(tls_stream.get_ref().1);
+                    if self.is_h2_alpn(tls_stream.get_ref().1.alpn_protocol()) {
+                        self.spawn_h2_bridge_task(tls_stream, cc_info, client_cert)
+                            .await
+                    } else {
+                        self.spawn_stream_task(tls_stream, cc_info, client_cert)
+                            .await
+                    }
+                }
+                Ok(Err(e)) => {
+                    self.listen_stats.add_failed();
+                    debug!(
+                        "{} - {} tls error: {e:?}",
+                        cc_info.sock_local_addr(),
+                        cc_info.sock_peer_addr()
+                    );
+                    // TODO record tls failure and add some sec policy
+                }
+                Err(_) => {
+                    self.listen_stats.add_timeout();
+                    debug!(
+                        "{} - {} tls timeout",
+                        cc_info.sock_local_addr(),
+                        cc_info.sock_peer_addr()
+                    );
+                    // TODO record tls failure and add some sec policy
+                }
+            }
+        } else {
+            self.spawn_stream_task(stream, cc_info, None).await;
+        }
+    }
+
     #[cfg(feature = "quic")]
     fn spawn_quic_stream_task(
         &self,
@@ -266,7 +404,7 @@ impl HttpProxyServer {
         recv_stream: quinn::RecvStream,
         cc_info: ClientConnectionInfo,
     ) {
-        let ctx = self.get_common_task_context(cc_info);
+        let ctx = self.get_common_task_context(cc_info, None);
         let pipeline_stats = Arc::new(HttpProxyPipelineStats::default());
         let (task_sender, task_receiver) = mpsc::channel(ctx.server_config.pipeline_size.get());
 
@@ -309,6 +447,10 @@ impl ServerInternal for HttpProxyServer {
     fn _update_escaper_in_place(&self) {
         let escaper = crate::escape::get_or_insert_default(self.config.escaper());
         self.escaper.store(Arc::new(escaper));
+        if let Some(pool) = &self.h2_pool {
+            // pooled connections are established through the old escaper
+            pool.clear();
+        }
     }
 
     fn _update_user_group_in_place(&self) {
@@ -333,6 +475,18 @@ impl ServerInternal for HttpProxyServer {
     }
 
     fn _start_runtime(&self, server: &ArcServer) -> anyhow::Result<()> {
+        #[cfg(unix)]
+        if let Some(listen_config) = &self.config.listen_unix {
+            let runtime =
+                ListenUnixRuntime::new(WrapArcServer(server.clone()), server.get_listen_stats());
+            runtime.into_running(
+                listen_config,
+                self.config.listen_in_worker,
+                &self.reload_sender,
+            )?;
+            self.server_stats.set_online();
+        }
+
         let Some(listen_config) = &self.config.listen else {
             return Ok(());
         };
@@ -378,38 +532,11 @@ impl AcceptTcpServer for HttpProxyServer {
         if self.drop_early(client_addr) {
             return;
         }
+        let Some(_conn_guard) = self.acquire_client_conn(client_addr) else {
+            return;
+        };
 
-        if let Some(tls_acceptor) = &self.tls_acceptor {
-            match tokio::time::timeout(self.tls_accept_timeout, tls_acceptor.accept(stream)).await {
-                Ok(Ok(tls_stream)) => {
-                    if tls_stream.get_ref().1.session_reused() {
-                        // Quick ACK is needed with session resumption
-                        cc_info.tcp_sock_try_quick_ack();
-                    }
-                    self.spawn_stream_task(tls_stream, cc_info).await
-                }
-                Ok(Err(e)) => {
-                    self.listen_stats.add_failed();
-                    debug!(
-                        "{} - {} tls error: {e:?}",
-                        cc_info.sock_local_addr(),
-                        cc_info.sock_peer_addr()
-                    );
-                    // TODO record tls failure and add some sec policy
-                }
-                Err(_) => {
-                    self.listen_stats.add_timeout();
-                    debug!(
-                        "{} - {} tls timeout",
-                        cc_info.sock_local_addr(),
-                        cc_info.sock_peer_addr()
-                    );
-                    // TODO record tls failure and add some sec policy
-                }
-            }
-        } else {
-            self.spawn_stream_task(stream, cc_info).await;
-        }
+        self.run_maybe_tls_task(stream, cc_info).await
     }
 }
 
@@ -422,6 +549,9 @@ impl AcceptQuicServer for HttpProxyServer {
         if self.drop_early(client_addr) {
             return;
         }
+        let Some(_conn_guard) = self.acquire_client_conn(client_addr) else {
+            return;
+        };
 
         loop {
             // TODO update ctx and quit gracefully
@@ -464,6 +594,29 @@ impl Server for HttpProxyServer {
         Arc::clone(&self.listen_stats)
     }
 
+    fn client_conn_limiter(&self) -> Option<&Arc<ClientConnLimiter>> {
+        self.client_conn_limiter.as_ref()
+    }
+
+    async fn test_route(&self, req: &RouteTestRequest) -> Option<RouteTestReport> {
+        let ctx = RouteTestContext {
+            server: self.config.name(),
+            server_extra_tags: self.server_stats.share_extra_tags(),
+            allowed_requests: &[
+                ProxyRequestType::HttpForward,
+                ProxyRequestType::HttpsForward,
+                ProxyRequestType::FtpOverHttp,
+                ProxyRequestType::HttpConnect,
+            ],
+            ingress_net_filter: self.ingress_net_filter.as_ref(),
+            dst_port_filter: self.config.dst_port_filter.as_ref(),
+            dst_host_filter: self.dst_host_filter.as_deref(),
+            user_group: self.user_group.load_full(),
+            escaper: self.escaper.load().as_ref().clone(),
+        };
+        Some(ctx.run(req).await)
+    }
+
     fn alive_count(&self) -> i32 {
         self.server_stats.get_alive_count()
     }
@@ -479,8 +632,17 @@ impl Server for HttpProxyServer {
         if self.drop_early(client_addr) {
             return;
         }
+        let Some(_conn_guard) = self.acquire_client_conn(client_addr) else {
+            return;
+        };
 
-        self.spawn_stream_task(stream, cc_info).await;
+        let client_cert = crate::serve::client_cert::from_rustls(stream.get_ref().1);
+        if self.is_h2_alpn(stream.get_ref().1.alpn_protocol()) {
+            self.spawn_h2_bridge_task(stream, cc_info, client_cert)
+                .await;
+        } else {
+            self.spawn_stream_task(stream, cc_info, client_cert).await;
+        }
     }
 
     async fn run_openssl_task(&self, stream: SslStream<TcpStream>, cc_info: ClientConnectionInfo) {
@@ -489,7 +651,30 @@ impl Server for HttpProxyServer {
         if self.drop_early(client_addr) {
             return;
         }
+        let Some(_conn_guard) = self.acquire_client_conn(client_addr) else {
+            return;
+        };
+
+        let client_cert = crate::serve::client_cert::from_openssl(stream.ssl());
+        if self.is_h2_alpn(stream.ssl().selected_alpn_protocol()) {
+            self.spawn_h2_bridge_task(stream, cc_info, client_cert)
+                .await;
+        } else {
+            self.spawn_stream_task(stream, cc_info, client_cert).await;
+        }
+    }
+
+    #[cfg(unix)]
+    async fn run_unix_task(&self, stream: UnixStream, cc_info: ClientConnectionInfo) {
+        let client_addr = cc_info.client_addr();
+        self.server_stats.add_conn(client_addr);
+        if self.drop_early(client_addr) {
+            return;
+        }
+        let Some(_conn_guard) = self.acquire_client_conn(client_addr) else {
+            return;
+        };
 
-        self.spawn_stream_task(stream, cc_info).await;
+        self.run_maybe_tls_task(stream, cc_info).await
     }
 }
@@ -23,11 +23,11 @@ use g3_daemon::server::ClientConnectionInfo;
 use g3_icap_client::reqmod::h1::HttpAdapterErrorResponse;
 use g3_types::acl::AclAction;
 use g3_types::acl_set::AclDstHostRuleSet;
-use g3_types::net::{OpensslClientConfig, UpstreamAddr};
+use g3_types::net::{OpensslCertIdentity, OpensslClientConfig, UpstreamAddr};
 
 use super::{HttpProxyServerConfig, HttpProxyServerStats};
 use crate::escape::ArcEscaper;
-use crate::module::http_forward::HttpProxyClientResponse;
+use crate::module::http_forward::{HttpForwardH2Pool, HttpProxyClientResponse};
 use crate::module::http_header;
 use crate::module::tcp_connect::TcpConnectTaskNotes;
 use crate::serve::{ServerIdleChecker, ServerQuitPolicy, ServerTaskNotes};
@@ -39,7 +39,9 @@ pub(crate) struct CommonTaskContext {
     pub(crate) server_quit_policy: Arc<ServerQuitPolicy>,
     pub(crate) escaper: ArcEscaper,
     pub(crate) cc_info: ClientConnectionInfo,
+    pub(crate) client_cert: Option<Arc<OpensslCertIdentity>>,
     pub(crate) tls_client_config: Arc<OpensslClientConfig>,
+    pub(crate) h2_pool: Option<Arc<HttpForwardH2Pool>>,
     pub(crate) task_logger: Logger,
 
     pub(crate) dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
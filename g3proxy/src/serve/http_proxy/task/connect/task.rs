@@ -30,6 +30,7 @@ use super::protocol::{HttpClientWriter, HttpProxyRequest};
 use super::{CommonTaskContext, TcpConnectTaskCltWrapperStats};
 use crate::audit::AuditContext;
 use crate::auth::User;
+use crate::config::idle::TaskIdlePolicy;
 use crate::config::server::ServerConfig;
 use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
@@ -78,8 +79,10 @@ impl HttpProxyConnectTask {
     where
         W: AsyncWrite + Unpin,
     {
-        let rsp = HttpProxyClientResponse::too_many_requests(self.http_version);
-        // no custom header is set
+        let mut rsp = HttpProxyClientResponse::too_many_requests(self.http_version);
+        if let Some(hint) = &self.ctx.server_config.failover_hint {
+            rsp.add_failover_hint(hint);
+        }
         let _ = rsp.reply_err_to_request(clt_w).await;
         self.back_to_http = false;
     }
@@ -545,6 +548,10 @@ impl StreamTransitTask for HttpProxyConnectTask {
         self.ctx.server_config.task_idle_max_count
     }
 
+    fn idle_policy(&self) -> TaskIdlePolicy {
+        self.ctx.server_config.task_idle_policy
+    }
+
     fn log_periodic(&self) {
         self.get_log_context().log_periodic(&self.ctx.task_logger);
     }
@@ -14,9 +14,10 @@
  * limitations under the License.
  */
 
-use super::{protocol, CommonTaskContext, HttpProxyServerStats};
+use super::{h2_error_to_io, protocol, CommonTaskContext, HttpProxyServerStats};
 
 mod task;
+mod upstream_h2;
 pub(super) use task::HttpProxyForwardTask;
 
 mod stats;
@@ -18,11 +18,14 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::anyhow;
+use bytes::Bytes;
 use futures_util::FutureExt;
-use http::header;
+use h2::RecvStream;
+use http::{header, Method};
 use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt};
 use tokio::time::Instant;
 
+use g3_daemon::stat::task::TcpStreamTaskStats;
 use g3_http::client::HttpForwardRemoteResponse;
 use g3_http::server::HttpProxyClientRequest;
 use g3_http::{HttpBodyReader, HttpBodyType};
@@ -38,19 +41,22 @@ use g3_io_ext::{
     LimitedWriteExt, OptionalInterval,
 };
 use g3_types::acl::AclAction;
-use g3_types::net::{HttpHeaderMap, ProxyRequestType, UpstreamAddr};
+use g3_types::net::{AlpnProtocol, HttpHeaderMap, ProxyRequestType, UpstreamAddr};
 
 use super::protocol::{HttpClientReader, HttpClientWriter, HttpProxyRequest};
+use super::upstream_h2::{self, H2RequestBodyTransfer, H2ResponseBodyTransfer};
 use super::{
-    CommonTaskContext, HttpForwardTaskCltWrapperStats, HttpForwardTaskStats,
+    h2_error_to_io, CommonTaskContext, HttpForwardTaskCltWrapperStats, HttpForwardTaskStats,
     HttpsForwardTaskCltWrapperStats,
 };
 use crate::audit::AuditContext;
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
 use crate::config::server::ServerConfig;
 use crate::log::task::http_forward::TaskLogForHttpForward;
 use crate::module::http_forward::{
     BoxHttpForwardConnection, BoxHttpForwardContext, BoxHttpForwardReader, BoxHttpForwardWriter,
-    HttpForwardTaskNotes, HttpProxyClientResponse,
+    HttpForwardH2Pool, HttpForwardH2PoolKey, HttpForwardH2Stream, HttpForwardTaskNotes,
+    HttpProxyClientResponse,
 };
 use crate::module::http_header;
 use crate::module::tcp_connect::{
@@ -67,6 +73,8 @@ pub(crate) struct HttpProxyForwardTask<'a> {
     upstream: UpstreamAddr,
     req: &'a HttpProxyClientRequest,
     is_https: bool,
+    server_timing: bool,
+    user_header_rewrite: Option<Arc<HttpHeaderRewriteConfig>>,
     should_close: bool,
     send_error_response: bool,
     task_notes: ServerTaskNotes,
@@ -81,6 +89,8 @@ impl<'a> HttpProxyForwardTask<'a> {
         audit_ctx: AuditContext,
         req: &'a HttpProxyRequest<impl AsyncRead>,
         is_https: bool,
+        server_timing: bool,
+        user_header_rewrite: Option<Arc<HttpHeaderRewriteConfig>>,
         task_notes: ServerTaskNotes,
     ) -> Self {
         let uri_log_max_chars = task_notes
@@ -100,6 +110,8 @@ impl<'a> HttpProxyForwardTask<'a> {
             upstream: req.upstream.clone(),
             req: &req.inner,
             is_https,
+            server_timing,
+            user_header_rewrite,
             should_close: !req.inner.keep_alive(),
             send_error_response: true,
             task_notes,
@@ -118,8 +130,10 @@ impl<'a> HttpProxyForwardTask<'a> {
     where
         W: AsyncWrite + Unpin,
     {
-        let rsp = HttpProxyClientResponse::too_many_requests(self.req.version);
-        // no custom header is set
+        let mut rsp = HttpProxyClientResponse::too_many_requests(self.req.version);
+        if let Some(hint) = &self.ctx.server_config.failover_hint {
+            rsp.add_failover_hint(hint);
+        }
         if rsp.reply_err_to_request(clt_w).await.is_ok() {
             self.http_notes.rsp_status = rsp.status();
         }
@@ -214,6 +228,7 @@ impl<'a> HttpProxyForwardTask<'a> {
             task_notes: &self.task_notes,
             http_notes: &self.http_notes,
             http_user_agent,
+            client_cert: self.ctx.client_cert.as_deref(),
             tcp_notes: &self.tcp_notes,
             client_rd_bytes: self.task_stats.clt.read.get_bytes(),
             client_wr_bytes: self.task_stats.clt.write.get_bytes(),
@@ -601,6 +616,14 @@ impl<'a> HttpProxyForwardTask<'a> {
 
         self.setup_clt_limit_and_stats(clt_r, clt_w);
 
+        if !audit_task {
+            if let Some(pool) = self.ctx.h2_pool.clone() {
+                if let Some(r) = self.run_forward_h2(&pool, clt_r, clt_w).await {
+                    return r;
+                }
+            }
+        }
+
         fwd_ctx.prepare_connection(&self.upstream, self.is_https);
 
         if let Some(mut connection) = fwd_ctx
@@ -632,7 +655,7 @@ impl<'a> HttpProxyForwardTask<'a> {
             match r {
                 Ok(r) => {
                     if let Some(connection) = r {
-                        fwd_ctx.save_alive_connection(connection);
+                        fwd_ctx.save_alive_connection(connection, &upstream_keepalive);
                     }
                     return Ok(());
                 }
@@ -656,27 +679,67 @@ impl<'a> HttpProxyForwardTask<'a> {
             }
         }
 
-        let connection = self.get_new_connection(fwd_ctx, clt_w).await?;
-        match self
-            .run_with_connection(fwd_ctx, clt_r, clt_w, connection, audit_task)
-            .await
-        {
-            Ok(r) => {
-                if let Some(connection) = r {
-                    fwd_ctx.save_alive_connection(connection);
+        let clt_wr_bytes = self.task_stats.clt.write.get_bytes();
+        loop {
+            let connection = match self.try_new_connection(fwd_ctx).await {
+                Ok(connection) => connection,
+                Err(e) => {
+                    if let Some(backoff) = self.next_retry_backoff() {
+                        self.get_log_context()
+                            .log(&self.ctx.task_logger, &ServerTaskError::from(e));
+                        tokio::time::sleep(backoff).await;
+                        continue;
+                    }
+                    self.should_close = true;
+                    self.reply_connect_err(&e, clt_w).await;
+                    return Err(e.into());
                 }
-                Ok(())
-            }
-            Err(e) => {
-                self.should_close = true;
-                if self.send_error_response {
-                    self.reply_task_err(&e, clt_w).await;
+            };
+
+            self.http_notes.retry_new_connection = false;
+            match self
+                .run_with_connection(fwd_ctx, clt_r, clt_w, connection, audit_task)
+                .await
+            {
+                Ok(r) => {
+                    if let Some(connection) = r {
+                        fwd_ctx.save_alive_connection(connection, &upstream_keepalive);
+                    }
+                    return Ok(());
+                }
+                Err(e) => {
+                    // only retry if nothing has been sent to the client yet
+                    if self.http_notes.retry_new_connection
+                        && self.task_stats.clt.write.get_bytes() == clt_wr_bytes
+                    {
+                        if let Some(backoff) = self.next_retry_backoff() {
+                            self.get_log_context().log(&self.ctx.task_logger, &e);
+                            self.task_stats.ups.reset();
+                            tokio::time::sleep(backoff).await;
+                            continue;
+                        }
+                    }
+                    self.should_close = true;
+                    if self.send_error_response {
+                        self.reply_task_err(&e, clt_w).await;
+                    }
+                    return Err(e);
                 }
-                Err(e)
             }
         }
     }
 
+    /// get the backoff time if the request should be retried on a new connection
+    fn next_retry_backoff(&mut self) -> Option<Duration> {
+        let retry_config = self.ctx.server_config.http_forward_retry.as_ref()?;
+        if !matches!(self.http_notes.method, Method::GET | Method::HEAD) {
+            return None;
+        }
+        let backoff = retry_config.backoff(self.http_notes.retry_count + 1)?;
+        self.http_notes.retry_count += 1;
+        Some(backoff)
+    }
+
     async fn get_new_connection<CDW>(
         &mut self,
         fwd_ctx: &mut BoxHttpForwardContext,
@@ -685,6 +748,20 @@ impl<'a> HttpProxyForwardTask<'a> {
     where
         CDW: AsyncWrite + Send + Unpin,
     {
+        match self.try_new_connection(fwd_ctx).await {
+            Ok(connection) => Ok(connection),
+            Err(e) => {
+                self.should_close = true;
+                self.reply_connect_err(&e, clt_w).await;
+                Err(e.into())
+            }
+        }
+    }
+
+    async fn try_new_connection(
+        &mut self,
+        fwd_ctx: &mut BoxHttpForwardContext,
+    ) -> Result<BoxHttpForwardConnection, TcpConnectError> {
         self.task_notes.stage = ServerTaskStage::Connecting;
         self.http_notes.reused_connection = false;
 
@@ -703,9 +780,7 @@ impl<'a> HttpProxyForwardTask<'a> {
             }
             Err(e) => {
                 fwd_ctx.fetch_tcp_notes(&mut self.tcp_notes);
-                self.should_close = true;
-                self.reply_connect_err(&e, clt_w).await;
-                Err(e.into())
+                Err(e)
             }
         }
     }
@@ -744,6 +819,411 @@ impl<'a> HttpProxyForwardTask<'a> {
         }
     }
 
+    fn h2_pool_key(&self) -> Option<HttpForwardH2PoolKey> {
+        if !self.is_https
+            || self.task_notes.egress_path_selection.is_some()
+            || self.req.end_to_end_headers.contains_key(header::EXPECT)
+        {
+            return None;
+        }
+        if self
+            .task_notes
+            .user_ctx()
+            .and_then(|ctx| ctx.user_site())
+            .and_then(|site| site.tls_client())
+            .is_some()
+        {
+            // the user site tls client config doesn't negotiate h2
+            return None;
+        }
+
+        let tls_name = self.req.host.as_ref().unwrap_or(&self.upstream).host();
+        Some(HttpForwardH2PoolKey::new(
+            &self.upstream,
+            tls_name,
+            self.task_notes.raw_user_name(),
+        ))
+    }
+
+    /// forward the request over a pooled h2 connection,
+    /// return None if the upstream has no h2 support and h1 should be used
+    async fn run_forward_h2<CDR, CDW>(
+        &mut self,
+        pool: &Arc<HttpForwardH2Pool>,
+        clt_r: &mut Option<HttpClientReader<CDR>>,
+        clt_w: &mut HttpClientWriter<CDW>,
+    ) -> Option<ServerTaskResult<()>>
+    where
+        CDR: AsyncRead + Send + Unpin,
+        CDW: AsyncWrite + Send + Unpin,
+    {
+        let key = self.h2_pool_key()?;
+
+        loop {
+            let h2_stream = match self.get_h2_stream(pool, &key, clt_w).await {
+                Ok(Some(s)) => s,
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            };
+
+            self.http_notes.retry_new_connection = false;
+            match self.run_with_h2_stream(&h2_stream, clt_r, clt_w).await {
+                Ok(()) => return Some(Ok(())),
+                Err(e) => {
+                    if self.http_notes.reused_connection && self.http_notes.retry_new_connection {
+                        self.get_log_context().log(&self.ctx.task_logger, &e);
+                        self.task_stats.ups.reset();
+                        if let Some(user_ctx) = self.task_notes.user_ctx() {
+                            user_ctx.foreach_req_stats(|s| s.req_renew.add_http_forward(true));
+                        }
+                        continue;
+                    }
+                    self.should_close = true;
+                    if self.send_error_response {
+                        self.reply_task_err(&e, clt_w).await;
+                    }
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+
+    async fn get_h2_stream<W>(
+        &mut self,
+        pool: &Arc<HttpForwardH2Pool>,
+        key: &HttpForwardH2PoolKey,
+        clt_w: &mut W,
+    ) -> ServerTaskResult<Option<HttpForwardH2Stream>>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        if let Some(h2_stream) = pool.get(key, &mut self.tcp_notes) {
+            self.task_notes.stage = ServerTaskStage::Connected;
+            self.http_notes.reused_connection = true;
+            if let Some(user_ctx) = self.task_notes.user_ctx() {
+                user_ctx.foreach_req_stats(|s| s.req_reuse.add_http_forward(true));
+            }
+
+            if self.ctx.server_config.flush_task_log_on_connected {
+                self.get_log_context().log_connected(&self.ctx.task_logger);
+            }
+            self.mark_relaying();
+            return Ok(Some(h2_stream));
+        }
+        if pool.is_h1_only(key) {
+            return Ok(None);
+        }
+
+        self.task_notes.stage = ServerTaskStage::Connecting;
+        self.http_notes.reused_connection = false;
+        self.tcp_notes.reset();
+
+        let tls_name = self.req.host.as_ref().unwrap_or(&self.upstream).host();
+        let task_conf = TlsConnectTaskConf {
+            tcp: TcpConnectTaskConf {
+                upstream: &self.upstream,
+            },
+            tls_config: pool.tls_client(),
+            tls_name,
+        };
+        // the connection will be shared by many tasks, so use standalone stats
+        let (ups_r, ups_w) = match self
+            .ctx
+            .escaper
+            .tls_setup_connection(
+                &task_conf,
+                &mut self.tcp_notes,
+                &self.task_notes,
+                Arc::new(TcpStreamTaskStats::default()),
+                &mut self.audit_ctx,
+            )
+            .await
+        {
+            Ok(c) => c,
+            Err(e) => {
+                self.should_close = true;
+                self.reply_connect_err(&e, clt_w).await;
+                return Err(e.into());
+            }
+        };
+
+        if self.tcp_notes.tls_alpn != Some(AlpnProtocol::Http2) {
+            pool.set_h1_only(key.clone());
+            self.tcp_notes.reset();
+            return Ok(None);
+        }
+
+        let h2_stream = match pool
+            .handshake(key.clone(), ups_r, ups_w, &self.tcp_notes)
+            .await
+        {
+            Ok(s) => s,
+            Err(e) => {
+                let e = ServerTaskError::UpstreamAppError(e);
+                self.should_close = true;
+                self.reply_task_err(&e, clt_w).await;
+                return Err(e);
+            }
+        };
+
+        self.task_notes.stage = ServerTaskStage::Connected;
+        if self.ctx.server_config.flush_task_log_on_connected {
+            self.get_log_context().log_connected(&self.ctx.task_logger);
+        }
+        self.mark_relaying();
+        Ok(Some(h2_stream))
+    }
+
+    async fn run_with_h2_stream<CDR, CDW>(
+        &mut self,
+        h2_stream: &HttpForwardH2Stream,
+        clt_r: &mut Option<HttpClientReader<CDR>>,
+        clt_w: &mut HttpClientWriter<CDW>,
+    ) -> ServerTaskResult<()>
+    where
+        CDR: AsyncRead + Send + Unpin,
+        CDW: AsyncWrite + Send + Unpin,
+    {
+        let request = upstream_h2::build_request(self.req, &self.upstream)
+            .map_err(|_| ServerTaskError::InternalServerError("failed to convert request to h2"))?;
+
+        self.http_notes.retry_new_connection = true;
+        let mut send_request = match h2_stream.send_request.clone().ready().await {
+            Ok(s) => s,
+            Err(e) => {
+                h2_stream.mark_broken();
+                return Err(ServerTaskError::UpstreamWriteFailed(h2_error_to_io(e)));
+            }
+        };
+
+        let body_type = self.req.body_type();
+        let (mut rsp_fut, mut send_stream) =
+            match send_request.send_request(request, body_type.is_none()) {
+                Ok(v) => v,
+                Err(e) => {
+                    h2_stream.mark_broken();
+                    return Err(ServerTaskError::UpstreamWriteFailed(h2_error_to_io(e)));
+                }
+            };
+        self.http_notes.mark_req_send_hdr();
+        self.http_notes.retry_new_connection = false;
+
+        let mut rsp = None;
+        if let Some(body_type) = body_type {
+            let Some(clt_r) = clt_r else {
+                return Err(ServerTaskError::InternalServerError(
+                    "http body is expected but no body reader supplied",
+                ));
+            };
+
+            let mut clt_to_ups = H2RequestBodyTransfer::new(
+                clt_r,
+                body_type,
+                &mut send_stream,
+                &self.ctx.server_config.tcp_copy,
+                self.ctx.server_config.body_line_max_len,
+            );
+
+            let idle_duration = self.ctx.server_config.task_idle_check_duration;
+            let mut idle_interval =
+                tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
+            let mut log_interval = self.get_log_interval();
+            let mut idle_count = 0;
+            loop {
+                tokio::select! {
+                    biased;
+
+                    r = &mut rsp_fut => {
+                        let r = r.map_err(|e| ServerTaskError::UpstreamReadFailed(h2_error_to_io(e)))?;
+                        // not all client data read in, drop the client connection
+                        self.should_close = true;
+                        rsp = Some(r);
+                        break;
+                    }
+                    r = &mut clt_to_ups => {
+                        r?;
+                        break;
+                    }
+                    _ = log_interval.tick() => {
+                        self.get_log_context().log_periodic(&self.ctx.task_logger);
+                    }
+                    _ = idle_interval.tick() => {
+                        if clt_to_ups.is_idle() {
+                            idle_count += 1;
+
+                            let quit = if let Some(user_ctx) = self.task_notes.user_ctx() {
+                                let user = user_ctx.user();
+                                if user.is_blocked() {
+                                    return Err(ServerTaskError::CanceledAsUserBlocked);
+                                }
+                                idle_count >= user.task_max_idle_count()
+                            } else {
+                                idle_count >= self.ctx.server_config.task_idle_max_count
+                            };
+
+                            if quit {
+                                return if clt_to_ups.no_cached_data() {
+                                    Err(ServerTaskError::ClientAppTimeout("idle while reading request body"))
+                                } else {
+                                    Err(ServerTaskError::UpstreamAppTimeout("idle while sending request body"))
+                                };
+                            }
+                        } else {
+                            idle_count = 0;
+
+                            clt_to_ups.reset_active();
+                        }
+
+                        if let Some(user_ctx) = self.task_notes.user_ctx() {
+                            if user_ctx.user().is_blocked() {
+                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            }
+                        }
+
+                        if self.ctx.server_quit_policy.force_quit() {
+                            return Err(ServerTaskError::CanceledAsServerQuit)
+                        }
+                    }
+                }
+            }
+
+            let end_stream_sent = clt_to_ups.end_stream_sent();
+            drop(clt_to_ups);
+            if rsp.is_none() {
+                if !end_stream_sent {
+                    send_stream
+                        .send_data(Bytes::new(), true)
+                        .map_err(|e| ServerTaskError::UpstreamWriteFailed(h2_error_to_io(e)))?;
+                }
+                self.http_notes.mark_req_send_all();
+            }
+        } else {
+            self.http_notes.mark_req_send_all();
+        }
+
+        let rsp = match rsp {
+            Some(rsp) => rsp,
+            None => match tokio::time::timeout(self.rsp_hdr_recv_timeout(), rsp_fut).await {
+                Ok(Ok(rsp)) => rsp,
+                Ok(Err(e)) => return Err(ServerTaskError::UpstreamReadFailed(h2_error_to_io(e))),
+                Err(_) => {
+                    return Err(ServerTaskError::UpstreamAppTimeout(
+                        "timeout to receive response header",
+                    ))
+                }
+            },
+        };
+        self.http_notes.mark_rsp_recv_hdr();
+
+        let (parts, recv_stream) = rsp.into_parts();
+        let mut rsp_header = HttpForwardRemoteResponse::from_standard(
+            self.req.version,
+            parts.status,
+            &parts.headers,
+            &self.req.method,
+            !self.should_close,
+        )?;
+        if !rsp_header.keep_alive() {
+            self.should_close = true;
+        }
+        self.http_notes.origin_status = rsp_header.code;
+        self.http_notes.rsp_status = 0;
+        self.update_response_header(&mut rsp_header);
+
+        self.send_error_response = false;
+        self.send_response_header(clt_w, &rsp_header).await?;
+        self.http_notes.rsp_status = rsp_header.code;
+
+        match rsp_header.body_type(&self.req.method) {
+            Some(body_type) => {
+                self.send_h2_response_body(clt_w, recv_stream, body_type)
+                    .await?
+            }
+            None => self.http_notes.mark_rsp_no_body(),
+        }
+
+        self.task_notes.stage = ServerTaskStage::Finished;
+        Ok(())
+    }
+
+    async fn send_h2_response_body<W>(
+        &mut self,
+        clt_w: &mut W,
+        mut recv_stream: RecvStream,
+        body_type: HttpBodyType,
+    ) -> ServerTaskResult<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut ups_to_clt = if body_type == HttpBodyType::Chunked {
+            H2ResponseBodyTransfer::chunked(
+                &mut recv_stream,
+                clt_w,
+                &self.ctx.server_config.tcp_copy,
+            )
+        } else {
+            H2ResponseBodyTransfer::plain(recv_stream, clt_w, &self.ctx.server_config.tcp_copy)
+        };
+
+        let idle_duration = self.ctx.server_config.task_idle_check_duration;
+        let mut idle_interval =
+            tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
+        let mut log_interval = self.get_log_interval();
+        let mut idle_count = 0;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut ups_to_clt => {
+                    let size = r?;
+                    self.task_stats.ups.read.add_bytes(size);
+                    self.http_notes.mark_rsp_recv_all();
+                    return Ok(());
+                }
+                _ = log_interval.tick() => {
+                    self.get_log_context().log_periodic(&self.ctx.task_logger);
+                }
+                _ = idle_interval.tick() => {
+                    if ups_to_clt.is_idle() {
+                        idle_count += 1;
+
+                        let quit = if let Some(user_ctx) = self.task_notes.user_ctx() {
+                            let user = user_ctx.user();
+                            if user.is_blocked() {
+                                return Err(ServerTaskError::CanceledAsUserBlocked);
+                            }
+                            idle_count >= user.task_max_idle_count()
+                        } else {
+                            idle_count >= self.ctx.server_config.task_idle_max_count
+                        };
+
+                        if quit {
+                            return if ups_to_clt.no_cached_data() {
+                                Err(ServerTaskError::UpstreamAppTimeout("idle while reading response body"))
+                            } else {
+                                Err(ServerTaskError::ClientAppTimeout("idle while sending response with body"))
+                            };
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        ups_to_clt.reset_active();
+                    }
+
+                    if let Some(user_ctx) = self.task_notes.user_ctx() {
+                        if user_ctx.user().is_blocked() {
+                            return Err(ServerTaskError::CanceledAsUserBlocked);
+                        }
+                    }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+
     fn mark_relaying(&mut self) {
         self.task_notes.mark_relaying();
         if let Some(user_ctx) = self.task_notes.user_ctx() {
@@ -852,6 +1332,7 @@ impl<'a> HttpProxyForwardTask<'a> {
         let ups_w = &mut ups_c.0;
         let ups_r = &mut ups_c.1;
 
+        let adaptation_start = self.task_notes.task_created_instant().elapsed();
         let mut ups_w_adaptation = HttpForwardWriterForAdaptation { inner: ups_w };
         let mut adaptation_fut = icap_adapter
             .xfer(
@@ -930,6 +1411,9 @@ impl<'a> HttpProxyForwardTask<'a> {
             }
         }
         drop(adaptation_fut);
+        if let Some(dur) = adaptation_state.dur_ups_send_header {
+            self.http_notes.dur_req_adaptation = dur.saturating_sub(adaptation_start);
+        }
 
         let mut close_remote = false;
         let mut rsp_header = match rsp_header {
@@ -1740,6 +2224,19 @@ impl<'a> HttpProxyForwardTask<'a> {
     }
 
     fn update_response_header(&self, rsp: &mut HttpForwardRemoteResponse) {
+        let rewrite_log = http_header::HttpHeaderRewriteLog {
+            logger: &self.ctx.task_logger,
+            task_id: &self.task_notes.id,
+            user: self.task_notes.raw_user_name().map(|s| s.as_ref()),
+            direction: http_header::HttpHeaderRewriteDirection::Response,
+        };
+        if let Some(rewrite) = &self.ctx.server_config.http_header_rewrite {
+            http_header::rewrite(&mut rsp.end_to_end_headers, &rewrite.response, &rewrite_log);
+        }
+        if let Some(rewrite) = &self.user_header_rewrite {
+            http_header::rewrite(&mut rsp.end_to_end_headers, &rewrite.response, &rewrite_log);
+        }
+
         // append headers to hop-by-hop headers, so they will pass to client without adaptation
         if let Some(server_id) = &self.ctx.server_config.server_id {
             if self.ctx.server_config.http_forward_mark_upstream {
@@ -1773,6 +2270,20 @@ impl<'a> HttpProxyForwardTask<'a> {
                 http_header::set_outgoing_ip(&mut rsp.hop_by_hop_headers, addr);
             }
         }
+
+        if self.server_timing {
+            let mut metrics = http_header::ServerTimingMetrics {
+                icap: self.http_notes.dur_req_adaptation,
+                total: self.task_notes.task_created_instant().elapsed(),
+                ..Default::default()
+            };
+            if !self.http_notes.reused_connection {
+                metrics.resolve = self.tcp_notes.resolve_duration;
+                metrics.connect = self.tcp_notes.duration;
+                metrics.tls = self.tcp_notes.tls_duration;
+            }
+            http_header::set_server_timing(&mut rsp.hop_by_hop_headers, &metrics);
+        }
     }
 
     async fn send_response_header<W>(
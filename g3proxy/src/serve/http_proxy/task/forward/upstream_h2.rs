@@ -0,0 +1,260 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use h2::{RecvStream, SendStream};
+use http::uri::{PathAndQuery, Scheme};
+use http::{header, HeaderMap, HeaderValue, Request, Uri, Version};
+use tokio::io::{AsyncBufRead, AsyncWrite};
+
+use g3_h2::{
+    H2StreamBodyEncodeTransferError, H2StreamFromChunkedTransfer, H2StreamFromChunkedTransferError,
+    H2StreamReader, H2StreamToChunkedTransfer, H2StreamToChunkedTransferError,
+    ROwnedH2BodyEncodeTransfer,
+};
+use g3_http::server::HttpProxyClientRequest;
+use g3_http::{HttpBodyReader, HttpBodyType};
+use g3_io_ext::{LimitedCopyConfig, LimitedCopyError, ROwnedLimitedCopy};
+use g3_types::net::UpstreamAddr;
+
+use super::h2_error_to_io;
+use crate::serve::ServerTaskError;
+
+/// convert the h1 request header to a h2 one, hop-by-hop headers will be dropped
+pub(super) fn build_request(
+    req: &HttpProxyClientRequest,
+    upstream: &UpstreamAddr,
+) -> anyhow::Result<Request<()>> {
+    let authority = match req.end_to_end_headers.get(header::HOST) {
+        Some(v) => v.to_str().to_string(),
+        None => upstream.to_string(),
+    };
+    let path_and_query = req
+        .uri
+        .path_and_query()
+        .cloned()
+        .unwrap_or_else(|| PathAndQuery::from_static("/"));
+    let uri = Uri::builder()
+        .scheme(Scheme::HTTPS)
+        .authority(authority)
+        .path_and_query(path_and_query)
+        .build()
+        .map_err(|e| anyhow!("failed to build h2 request uri: {e}"))?;
+
+    let mut headers = HeaderMap::from(&req.end_to_end_headers);
+    headers.remove(header::HOST);
+    for name in req.extra_connection_headers() {
+        headers.remove(name);
+    }
+    if let Some(v) = req.hop_by_hop_headers.get(header::TE) {
+        if v.to_str().to_lowercase().contains("trailers") {
+            headers.insert(header::TE, HeaderValue::from_static("trailers"));
+        }
+    }
+
+    let mut request = Request::new(());
+    *request.method_mut() = req.method.clone();
+    *request.uri_mut() = uri;
+    *request.version_mut() = Version::HTTP_2;
+    *request.headers_mut() = headers;
+    Ok(request)
+}
+
+pub(super) enum H2RequestBodyTransfer<'a, R> {
+    Chunked(H2StreamFromChunkedTransfer<'a, R>),
+    Plain(ROwnedH2BodyEncodeTransfer<'a, HttpBodyReader<'a, R>>),
+}
+
+impl<'a, R> H2RequestBodyTransfer<'a, R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    pub(super) fn new(
+        clt_r: &'a mut R,
+        body_type: HttpBodyType,
+        send_stream: &'a mut SendStream<Bytes>,
+        copy_config: &LimitedCopyConfig,
+        body_line_max_len: usize,
+    ) -> Self {
+        match body_type {
+            HttpBodyType::Chunked => {
+                H2RequestBodyTransfer::Chunked(H2StreamFromChunkedTransfer::new(
+                    clt_r,
+                    send_stream,
+                    copy_config,
+                    body_line_max_len,
+                    body_line_max_len,
+                ))
+            }
+            _ => {
+                let body_reader = HttpBodyReader::new(clt_r, body_type, body_line_max_len);
+                H2RequestBodyTransfer::Plain(ROwnedH2BodyEncodeTransfer::new(
+                    body_reader,
+                    send_stream,
+                    copy_config,
+                ))
+            }
+        }
+    }
+
+    /// the end of stream flag should be sent by the caller if not
+    pub(super) fn end_stream_sent(&self) -> bool {
+        matches!(self, H2RequestBodyTransfer::Chunked(_))
+    }
+
+    pub(super) fn is_idle(&self) -> bool {
+        match self {
+            H2RequestBodyTransfer::Chunked(t) => t.is_idle(),
+            H2RequestBodyTransfer::Plain(t) => t.is_idle(),
+        }
+    }
+
+    pub(super) fn reset_active(&mut self) {
+        match self {
+            H2RequestBodyTransfer::Chunked(t) => t.reset_active(),
+            H2RequestBodyTransfer::Plain(t) => t.reset_active(),
+        }
+    }
+
+    pub(super) fn no_cached_data(&self) -> bool {
+        match self {
+            H2RequestBodyTransfer::Chunked(t) => t.no_cached_data(),
+            H2RequestBodyTransfer::Plain(t) => t.no_cached_data(),
+        }
+    }
+}
+
+impl<R> Future for H2RequestBodyTransfer<'_, R>
+where
+    R: AsyncBufRead + Unpin,
+{
+    type Output = Result<(), ServerTaskError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match &mut *self {
+            H2RequestBodyTransfer::Chunked(t) => Pin::new(t).poll(cx).map_err(|e| match e {
+                H2StreamFromChunkedTransferError::ReadError(e) => {
+                    ServerTaskError::ClientTcpReadFailed(e)
+                }
+                H2StreamFromChunkedTransferError::SendDataFailed(e)
+                | H2StreamFromChunkedTransferError::SendTrailerFailed(e) => {
+                    ServerTaskError::UpstreamWriteFailed(h2_error_to_io(e))
+                }
+                H2StreamFromChunkedTransferError::SenderNotInSendState => {
+                    ServerTaskError::UpstreamAppError(anyhow!(
+                        "upstream h2 stream is not in send state"
+                    ))
+                }
+            }),
+            H2RequestBodyTransfer::Plain(t) => Pin::new(t).poll(cx).map_err(|e| match e {
+                H2StreamBodyEncodeTransferError::ReadError(e) => {
+                    ServerTaskError::ClientTcpReadFailed(e)
+                }
+                H2StreamBodyEncodeTransferError::SendDataFailed(e) => {
+                    ServerTaskError::UpstreamWriteFailed(h2_error_to_io(e))
+                }
+                H2StreamBodyEncodeTransferError::SenderNotInSendState => {
+                    ServerTaskError::UpstreamAppError(anyhow!(
+                        "upstream h2 stream is not in send state"
+                    ))
+                }
+            }),
+        }
+    }
+}
+
+pub(super) enum H2ResponseBodyTransfer<'a, W> {
+    Chunked(H2StreamToChunkedTransfer<'a, W>),
+    Plain(ROwnedLimitedCopy<'a, H2StreamReader, W>),
+}
+
+impl<'a, W> H2ResponseBodyTransfer<'a, W>
+where
+    W: AsyncWrite + Unpin,
+{
+    pub(super) fn chunked(
+        recv_stream: &'a mut RecvStream,
+        clt_w: &'a mut W,
+        copy_config: &LimitedCopyConfig,
+    ) -> Self {
+        H2ResponseBodyTransfer::Chunked(H2StreamToChunkedTransfer::new(
+            recv_stream,
+            clt_w,
+            copy_config.yield_size(),
+        ))
+    }
+
+    pub(super) fn plain(
+        recv_stream: RecvStream,
+        clt_w: &'a mut W,
+        copy_config: &LimitedCopyConfig,
+    ) -> Self {
+        let reader = H2StreamReader::new(recv_stream);
+        H2ResponseBodyTransfer::Plain(ROwnedLimitedCopy::new(reader, clt_w, *copy_config))
+    }
+
+    pub(super) fn is_idle(&self) -> bool {
+        match self {
+            H2ResponseBodyTransfer::Chunked(t) => t.is_idle(),
+            H2ResponseBodyTransfer::Plain(t) => t.is_idle(),
+        }
+    }
+
+    pub(super) fn reset_active(&mut self) {
+        match self {
+            H2ResponseBodyTransfer::Chunked(t) => t.reset_active(),
+            H2ResponseBodyTransfer::Plain(t) => t.reset_active(),
+        }
+    }
+
+    pub(super) fn no_cached_data(&self) -> bool {
+        match self {
+            H2ResponseBodyTransfer::Chunked(t) => t.no_cached_data(),
+            H2ResponseBodyTransfer::Plain(t) => t.no_cached_data(),
+        }
+    }
+}
+
+impl<W> Future for H2ResponseBodyTransfer<'_, W>
+where
+    W: AsyncWrite + Unpin,
+{
+    type Output = Result<u64, ServerTaskError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match &mut *self {
+            H2ResponseBodyTransfer::Chunked(t) => Pin::new(t).poll(cx).map_err(|e| match e {
+                H2StreamToChunkedTransferError::WriteError(e) => {
+                    ServerTaskError::ClientTcpWriteFailed(e)
+                }
+                H2StreamToChunkedTransferError::RecvDataFailed(e)
+                | H2StreamToChunkedTransferError::RecvTrailerFailed(e) => {
+                    ServerTaskError::UpstreamReadFailed(h2_error_to_io(e))
+                }
+            }),
+            H2ResponseBodyTransfer::Plain(t) => Pin::new(t).poll(cx).map_err(|e| match e {
+                LimitedCopyError::ReadFailed(e) => ServerTaskError::UpstreamReadFailed(e),
+                LimitedCopyError::WriteFailed(e) => ServerTaskError::ClientTcpWriteFailed(e),
+            }),
+        }
+    }
+}
@@ -178,8 +178,10 @@ impl<'a> FtpOverHttpTask<'a> {
     where
         W: AsyncWrite + Unpin,
     {
-        let rsp = HttpProxyClientResponse::too_many_requests(self.req.version);
-        // no custom header is set
+        let mut rsp = HttpProxyClientResponse::too_many_requests(self.req.version);
+        if let Some(hint) = &self.ctx.server_config.failover_hint {
+            rsp.add_failover_hint(hint);
+        }
         if rsp.reply_err_to_request(clt_w).await.is_ok() {
             self.ftp_notes.rsp_status = rsp.status();
         }
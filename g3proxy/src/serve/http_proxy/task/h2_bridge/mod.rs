@@ -0,0 +1,184 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use bytes::Bytes;
+use h2::Reason;
+use log::debug;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use super::{
+    h2_error_to_io, CommonTaskContext, HttpProxyPipelineReaderTask, HttpProxyPipelineStats,
+    HttpProxyPipelineWriterTask,
+};
+use crate::audit::{AuditContext, AuditHandle};
+use crate::auth::UserGroup;
+
+mod stream;
+use stream::H2StreamBridge;
+
+/// Accept h2 clients, and bridge each stream to a HTTP/1.x proxy pipeline.
+///
+/// All the proxy logic, including auth, audit and the h2 upstream pool, is shared with
+/// HTTP/1.x clients as each h2 stream is converted to a HTTP/1.1 request.
+pub(crate) struct HttpProxyH2BridgeTask {
+    ctx: Arc<CommonTaskContext>,
+    audit_handle: Option<Arc<AuditHandle>>,
+    user_group: Option<Arc<UserGroup>>,
+    alive_streams: Arc<AtomicUsize>,
+}
+
+impl HttpProxyH2BridgeTask {
+    pub(crate) fn new(
+        ctx: Arc<CommonTaskContext>,
+        audit_handle: Option<Arc<AuditHandle>>,
+        user_group: Option<Arc<UserGroup>>,
+    ) -> Self {
+        HttpProxyH2BridgeTask {
+            ctx,
+            audit_handle,
+            user_group,
+            alive_streams: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    fn spawn_pipeline<S>(&self, io: S)
+    where
+        S: AsyncRead + AsyncWrite + Send + Sync + Unpin + 'static,
+    {
+        let pipeline_stats = Arc::new(HttpProxyPipelineStats::default());
+        // there will be only one request on each pipeline
+        let (task_sender, task_receiver) = mpsc::channel(1);
+
+        let (clt_r, clt_w) = tokio::io::split(io);
+        let r_task =
+            HttpProxyPipelineReaderTask::new(&self.ctx, task_sender, clt_r, &pipeline_stats);
+        let w_task = HttpProxyPipelineWriterTask::new(
+            &self.ctx,
+            AuditContext::new(self.audit_handle.clone()),
+            self.user_group.clone(),
+            task_receiver,
+            clt_w,
+            &pipeline_stats,
+        );
+
+        tokio::spawn(r_task.into_running());
+        tokio::spawn(w_task.into_running());
+    }
+
+    pub(crate) async fn into_running<S>(self, stream: S)
+    where
+        S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+    {
+        let Some(h2_config) = &self.ctx.server_config.http_forward_client_h2 else {
+            return;
+        };
+
+        let mut builder = h2::server::Builder::new();
+        builder
+            .initial_window_size(h2_config.stream_window_size)
+            .initial_connection_window_size(h2_config.connection_window_size)
+            .max_concurrent_streams(h2_config.max_concurrent_streams)
+            .max_frame_size(h2_config.max_frame_size)
+            .max_header_list_size(h2_config.max_header_list_size)
+            .max_send_buffer_size(h2_config.max_send_buffer_size);
+
+        let mut h2c = match tokio::time::timeout(
+            h2_config.handshake_timeout,
+            builder.handshake::<_, Bytes>(stream),
+        )
+        .await
+        {
+            Ok(Ok(h2c)) => h2c,
+            Ok(Err(e)) => {
+                debug!(
+                    "{} - {} h2 handshake error: {e}",
+                    self.ctx.cc_info.sock_local_addr(),
+                    self.ctx.cc_info.sock_peer_addr()
+                );
+                return;
+            }
+            Err(_) => {
+                debug!(
+                    "{} - {} h2 handshake timeout",
+                    self.ctx.cc_info.sock_local_addr(),
+                    self.ctx.cc_info.sock_peer_addr()
+                );
+                return;
+            }
+        };
+
+        let idle_duration = self.ctx.server_config.task_idle_check_duration;
+        let mut idle_interval =
+            tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
+        let mut idle_count = 0;
+        let mut shutdown = false;
+
+        loop {
+            tokio::select! {
+                biased;
+
+                r = h2c.accept() => {
+                    match r {
+                        Some(Ok((req, send_rsp))) => {
+                            let (bridge_io, pipeline_io) =
+                                tokio::io::duplex(h2_config.bridge_buffer_size);
+                            self.spawn_pipeline(pipeline_io);
+
+                            let bridge = H2StreamBridge::new(self.ctx.clone());
+                            let alive_streams = self.alive_streams.clone();
+                            alive_streams.fetch_add(1, Ordering::Relaxed);
+                            tokio::spawn(async move {
+                                bridge.run(req, send_rsp, bridge_io).await;
+                                alive_streams.fetch_sub(1, Ordering::Relaxed);
+                            });
+                        }
+                        Some(Err(e)) => {
+                            debug!(
+                                "{} - {} h2 connection error: {e}",
+                                self.ctx.cc_info.sock_local_addr(),
+                                self.ctx.cc_info.sock_peer_addr()
+                            );
+                            break;
+                        }
+                        None => break,
+                    }
+                }
+                _ = idle_interval.tick() => {
+                    if self.ctx.server_quit_policy.force_quit() {
+                        h2c.abrupt_shutdown(Reason::CANCEL);
+                        shutdown = true;
+                        continue;
+                    }
+
+                    if self.alive_streams.load(Ordering::Relaxed) == 0 {
+                        idle_count += 1;
+                        if idle_count > self.ctx.server_config.task_idle_max_count && !shutdown {
+                            h2c.graceful_shutdown();
+                            shutdown = true;
+                        }
+                    } else {
+                        idle_count = 0;
+                    }
+                }
+            }
+        }
+    }
+}
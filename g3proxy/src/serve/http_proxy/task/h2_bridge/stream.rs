@@ -0,0 +1,464 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::{self, Write};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use bytes::Bytes;
+use h2::server::SendResponse;
+use h2::{Reason, RecvStream};
+use http::request::Parts;
+use http::{header, HeaderMap, HeaderName, Method, Request, Response, StatusCode, Version};
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+use g3_h2::{
+    H2StreamBodyEncodeTransferError, H2StreamFromChunkedTransfer, H2StreamFromChunkedTransferError,
+    H2StreamReader, H2StreamToChunkedTransfer, H2StreamToChunkedTransferError, H2StreamWriter,
+    ROwnedH2BodyEncodeTransfer,
+};
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_http::{HttpBodyReader, HttpBodyType};
+use g3_io_ext::{LimitedCopyError, ROwnedLimitedCopy};
+
+use super::{h2_error_to_io, CommonTaskContext};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RequestBody {
+    None,
+    Plain,
+    Chunked,
+}
+
+/// errors on the pipeline side, the pipeline may have responded without reading all the body
+fn pipeline_write_error(e: io::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::BrokenPipe, e)
+}
+
+fn is_connection_header(name: &HeaderName) -> bool {
+    matches!(
+        name.as_str(),
+        "connection" | "keep-alive" | "proxy-connection" | "transfer-encoding" | "upgrade" | "te"
+    )
+}
+
+/// convert the h2 request header to a HTTP/1.1 proxy request,
+/// connection specific headers and the expect header will be dropped
+fn build_request_head(parts: &Parts, body: RequestBody) -> anyhow::Result<Vec<u8>> {
+    let authority = parts
+        .uri
+        .authority()
+        .ok_or_else(|| anyhow!("no authority found in h2 request"))?;
+
+    let mut buf = Vec::<u8>::with_capacity(1024);
+    if parts.method == Method::CONNECT {
+        let _ = write!(buf, "CONNECT {authority} HTTP/1.1\r\n");
+    } else {
+        let scheme = parts
+            .uri
+            .scheme_str()
+            .ok_or_else(|| anyhow!("no scheme found in h2 request"))?;
+        let path_and_query = parts
+            .uri
+            .path_and_query()
+            .map(|pa| pa.as_str())
+            .unwrap_or("/");
+        let _ = write!(
+            buf,
+            "{} {scheme}://{authority}{path_and_query} HTTP/1.1\r\n",
+            parts.method
+        );
+    }
+
+    if !parts.headers.contains_key(header::HOST) {
+        let _ = write!(buf, "Host: {authority}\r\n");
+    }
+    for (name, value) in &parts.headers {
+        if is_connection_header(name) || name == header::EXPECT {
+            continue;
+        }
+        buf.extend_from_slice(name.as_ref());
+        buf.extend_from_slice(b": ");
+        buf.extend_from_slice(value.as_bytes());
+        buf.extend_from_slice(b"\r\n");
+    }
+    if body == RequestBody::Chunked {
+        buf.extend_from_slice(b"Transfer-Encoding: chunked\r\n");
+    }
+    buf.extend_from_slice(b"\r\n");
+    Ok(buf)
+}
+
+/// convert the HTTP/1.x response header to a h2 one, hop-by-hop headers will be dropped
+fn build_response(rsp: &HttpForwardRemoteResponse) -> anyhow::Result<Response<()>> {
+    let status = StatusCode::from_u16(rsp.code)
+        .map_err(|e| anyhow!("invalid response status code {}: {e}", rsp.code))?;
+    let mut headers = HeaderMap::from(&rsp.end_to_end_headers);
+    for name in rsp.extra_connection_headers() {
+        headers.remove(name);
+    }
+
+    let mut response = Response::new(());
+    *response.status_mut() = status;
+    *response.version_mut() = Version::HTTP_2;
+    *response.headers_mut() = headers;
+    Ok(response)
+}
+
+pub(super) struct H2StreamBridge {
+    ctx: Arc<CommonTaskContext>,
+}
+
+impl H2StreamBridge {
+    pub(super) fn new(ctx: Arc<CommonTaskContext>) -> Self {
+        H2StreamBridge { ctx }
+    }
+
+    /// bridge the h2 stream to the HTTP/1.x stream of the proxy pipeline
+    pub(super) async fn run<S>(
+        self,
+        req: Request<RecvStream>,
+        mut send_rsp: SendResponse<Bytes>,
+        pipeline_io: S,
+    ) where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let (parts, recv_stream) = req.into_parts();
+
+        let body = if parts.method == Method::CONNECT || recv_stream.is_end_stream() {
+            RequestBody::None
+        } else if parts.headers.contains_key(header::CONTENT_LENGTH) {
+            RequestBody::Plain
+        } else {
+            RequestBody::Chunked
+        };
+        let Ok(head) = build_request_head(&parts, body) else {
+            let mut rsp = Response::new(());
+            *rsp.status_mut() = StatusCode::BAD_REQUEST;
+            let _ = send_rsp.send_response(rsp, true);
+            return;
+        };
+
+        let (pipe_r, mut pipe_w) = tokio::io::split(pipeline_io);
+        let mut pipe_r = BufReader::new(pipe_r);
+        if pipe_w.write_all(&head).await.is_err() {
+            send_rsp.send_reset(Reason::INTERNAL_ERROR);
+            return;
+        }
+
+        let r = if parts.method == Method::CONNECT {
+            self.run_connect(recv_stream, &mut send_rsp, pipe_r, pipe_w)
+                .await
+        } else {
+            let mut req_body_done = body == RequestBody::None;
+            let send_req_body = self.send_request_body(recv_stream, &mut pipe_w, body);
+            tokio::pin!(send_req_body);
+            let recv_rsp = self.recv_response(&parts.method, &mut pipe_r, &mut send_rsp);
+            tokio::pin!(recv_rsp);
+
+            loop {
+                tokio::select! {
+                    biased;
+
+                    r = &mut send_req_body, if !req_body_done => {
+                        req_body_done = true;
+                        if let Err(e) = r {
+                            if e.kind() != io::ErrorKind::BrokenPipe {
+                                // the client stream is broken, do not wait for the response
+                                break Err(e);
+                            }
+                        }
+                    }
+                    r = &mut recv_rsp => break r,
+                }
+            }
+        };
+        if r.is_err() {
+            send_rsp.send_reset(Reason::INTERNAL_ERROR);
+        }
+    }
+
+    async fn send_request_body<W>(
+        &self,
+        mut recv_stream: RecvStream,
+        pipe_w: &mut W,
+        body: RequestBody,
+    ) -> io::Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let copy_config = self.ctx.server_config.tcp_copy;
+        match body {
+            RequestBody::None => return Ok(()),
+            RequestBody::Plain => {
+                let reader = H2StreamReader::new(recv_stream);
+                ROwnedLimitedCopy::new(reader, &mut *pipe_w, copy_config)
+                    .await
+                    .map_err(|e| match e {
+                        LimitedCopyError::ReadFailed(e) => e,
+                        LimitedCopyError::WriteFailed(e) => pipeline_write_error(e),
+                    })?;
+            }
+            RequestBody::Chunked => {
+                H2StreamToChunkedTransfer::new(
+                    &mut recv_stream,
+                    &mut *pipe_w,
+                    copy_config.yield_size(),
+                )
+                .await
+                .map_err(|e| match e {
+                    H2StreamToChunkedTransferError::WriteError(e) => pipeline_write_error(e),
+                    H2StreamToChunkedTransferError::RecvDataFailed(e)
+                    | H2StreamToChunkedTransferError::RecvTrailerFailed(e) => h2_error_to_io(e),
+                })?;
+            }
+        }
+        pipe_w.flush().await.map_err(pipeline_write_error)
+    }
+
+    async fn recv_response_header<R>(
+        &self,
+        method: &Method,
+        pipe_r: &mut R,
+    ) -> anyhow::Result<HttpForwardRemoteResponse>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        let max_header_size = self.ctx.server_config.rsp_hdr_max_size;
+        loop {
+            let rsp = HttpForwardRemoteResponse::parse(pipe_r, method, true, max_header_size)
+                .await
+                .map_err(|e| anyhow!("invalid response from pipeline: {e}"))?;
+            // informational responses can not be sent to the client by h2 crate
+            if (100..200).contains(&rsp.code) {
+                continue;
+            }
+            return Ok(rsp);
+        }
+    }
+
+    async fn recv_response<R>(
+        &self,
+        method: &Method,
+        pipe_r: &mut R,
+        send_rsp: &mut SendResponse<Bytes>,
+    ) -> io::Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        let rsp = self
+            .recv_response_header(method, pipe_r)
+            .await
+            .map_err(io::Error::other)?;
+        let response = build_response(&rsp).map_err(io::Error::other)?;
+
+        let Some(body_type) = rsp.body_type(method) else {
+            send_rsp
+                .send_response(response, true)
+                .map_err(h2_error_to_io)?;
+            return Ok(());
+        };
+        let mut send_stream = send_rsp
+            .send_response(response, false)
+            .map_err(h2_error_to_io)?;
+
+        let copy_config = self.ctx.server_config.tcp_copy;
+        let body_line_max_len = self.ctx.server_config.body_line_max_len;
+        match body_type {
+            HttpBodyType::Chunked => {
+                H2StreamFromChunkedTransfer::new(
+                    pipe_r,
+                    &mut send_stream,
+                    &copy_config,
+                    body_line_max_len,
+                    body_line_max_len,
+                )
+                .await
+                .map_err(|e| match e {
+                    H2StreamFromChunkedTransferError::ReadError(e) => e,
+                    H2StreamFromChunkedTransferError::SendDataFailed(e)
+                    | H2StreamFromChunkedTransferError::SendTrailerFailed(e) => h2_error_to_io(e),
+                    H2StreamFromChunkedTransferError::SenderNotInSendState => {
+                        io::Error::other("client h2 stream is not in send state")
+                    }
+                })?;
+            }
+            _ => {
+                let body_reader = HttpBodyReader::new(pipe_r, body_type, body_line_max_len);
+                ROwnedH2BodyEncodeTransfer::new(body_reader, &mut send_stream, &copy_config)
+                    .await
+                    .map_err(|e| match e {
+                        H2StreamBodyEncodeTransferError::ReadError(e) => e,
+                        H2StreamBodyEncodeTransferError::SendDataFailed(e) => h2_error_to_io(e),
+                        H2StreamBodyEncodeTransferError::SenderNotInSendState => {
+                            io::Error::other("client h2 stream is not in send state")
+                        }
+                    })?;
+                send_stream
+                    .send_data(Bytes::new(), true)
+                    .map_err(h2_error_to_io)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn run_connect<R, W>(
+        &self,
+        recv_stream: RecvStream,
+        send_rsp: &mut SendResponse<Bytes>,
+        mut pipe_r: R,
+        mut pipe_w: W,
+    ) -> io::Result<()>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let method = Method::CONNECT;
+        let rsp = self
+            .recv_response_header(&method, &mut pipe_r)
+            .await
+            .map_err(io::Error::other)?;
+        if !(200..300).contains(&rsp.code) {
+            // let the normal path to send the error response
+            return self.recv_response(&method, &mut pipe_r, send_rsp).await;
+        }
+
+        let response = build_response(&rsp).map_err(io::Error::other)?;
+        let send_stream = send_rsp
+            .send_response(response, false)
+            .map_err(h2_error_to_io)?;
+
+        let copy_config = self.ctx.server_config.tcp_copy;
+        let mut clt_r = H2StreamReader::new(recv_stream);
+        let mut clt_w = H2StreamWriter::new(send_stream);
+        let clt_to_pipe = async {
+            ROwnedLimitedCopy::new(&mut clt_r, &mut pipe_w, copy_config)
+                .await
+                .map_err(|e| match e {
+                    LimitedCopyError::ReadFailed(e) | LimitedCopyError::WriteFailed(e) => e,
+                })?;
+            pipe_w.shutdown().await
+        };
+        let pipe_to_clt = async {
+            ROwnedLimitedCopy::new(&mut pipe_r, &mut clt_w, copy_config)
+                .await
+                .map_err(|e| match e {
+                    LimitedCopyError::ReadFailed(e) | LimitedCopyError::WriteFailed(e) => e,
+                })?;
+            clt_w.shutdown().await
+        };
+        tokio::try_join!(clt_to_pipe, pipe_to_clt)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_parts(req: Request<()>) -> Parts {
+        req.into_parts().0
+    }
+
+    #[test]
+    fn forward_request_head() {
+        let parts = request_parts(
+            Request::builder()
+                .method(Method::POST)
+                .uri("https://example.com/a?b=c")
+                .version(Version::HTTP_2)
+                .header("proxy-authorization", "Basic dGVzdDp0ZXN0")
+                .header(header::TE, "trailers")
+                .header(header::EXPECT, "100-continue")
+                .header(header::ACCEPT, "*/*")
+                .body(())
+                .unwrap(),
+        );
+
+        let head = build_request_head(&parts, RequestBody::Chunked).unwrap();
+        let head = String::from_utf8(head).unwrap();
+        assert!(head.starts_with("POST https://example.com/a?b=c HTTP/1.1\r\n"));
+        assert!(head.contains("Host: example.com\r\n"));
+        assert!(head.contains("proxy-authorization: Basic dGVzdDp0ZXN0\r\n"));
+        assert!(head.contains("accept: */*\r\n"));
+        assert!(head.contains("Transfer-Encoding: chunked\r\n"));
+        assert!(!head.contains("te:"));
+        assert!(!head.contains("expect:"));
+        assert!(head.ends_with("\r\n\r\n"));
+
+        let parts = request_parts(
+            Request::builder()
+                .method(Method::GET)
+                .uri("http://example.com:8080")
+                .header(header::HOST, "example.net")
+                .body(())
+                .unwrap(),
+        );
+        let head = build_request_head(&parts, RequestBody::None).unwrap();
+        let head = String::from_utf8(head).unwrap();
+        assert_eq!(
+            head,
+            "GET http://example.com:8080/ HTTP/1.1\r\nhost: example.net\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn connect_request_head() {
+        let parts = request_parts(
+            Request::builder()
+                .method(Method::CONNECT)
+                .uri("example.com:443")
+                .body(())
+                .unwrap(),
+        );
+        let head = build_request_head(&parts, RequestBody::None).unwrap();
+        let head = String::from_utf8(head).unwrap();
+        assert_eq!(
+            head,
+            "CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\r\n"
+        );
+
+        let parts = request_parts(Request::builder().uri("/a").body(()).unwrap());
+        assert!(build_request_head(&parts, RequestBody::None).is_err());
+    }
+
+    #[tokio::test]
+    async fn response_header() {
+        let data = b"HTTP/1.1 200 OK\r\n\
+            Connection: keep-alive, x-custom\r\n\
+            X-Custom: 1\r\n\
+            Keep-Alive: timeout=5\r\n\
+            Transfer-Encoding: chunked\r\n\
+            Set-Cookie: a=1\r\n\
+            Set-Cookie: b=2\r\n\
+            Server: test\r\n\r\n";
+        let mut reader = BufReader::new(&data[..]);
+        let rsp = HttpForwardRemoteResponse::parse(&mut reader, &Method::GET, true, 4096)
+            .await
+            .unwrap();
+        assert_eq!(rsp.body_type(&Method::GET), Some(HttpBodyType::Chunked));
+
+        let response = build_response(&rsp).unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let headers = response.headers();
+        assert!(!headers.contains_key(header::CONNECTION));
+        assert!(!headers.contains_key("keep-alive"));
+        assert!(!headers.contains_key(header::TRANSFER_ENCODING));
+        assert!(!headers.contains_key("x-custom"));
+        assert_eq!(headers.get_all(header::SET_COOKIE).iter().count(), 2);
+        assert_eq!(headers.get(header::SERVER).unwrap(), "test");
+    }
+}
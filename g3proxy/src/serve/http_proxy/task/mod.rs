@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+use std::io;
+
 use super::HttpProxyServerStats;
 use crate::config::server::http_proxy::HttpProxyServerConfig;
 
@@ -25,13 +27,23 @@ mod protocol;
 mod connect;
 mod forward;
 mod ftp;
+mod h2_bridge;
 mod pipeline;
 mod untrusted;
 
 use connect::HttpProxyConnectTask;
 use forward::HttpProxyForwardTask;
 use ftp::FtpOverHttpTask;
+pub(super) use h2_bridge::HttpProxyH2BridgeTask;
 pub(super) use pipeline::{
     HttpProxyPipelineReaderTask, HttpProxyPipelineStats, HttpProxyPipelineWriterTask,
 };
 use untrusted::HttpProxyUntrustedTask;
+
+fn h2_error_to_io(e: h2::Error) -> io::Error {
+    if e.is_io() {
+        e.into_io().unwrap()
+    } else {
+        io::Error::other(e)
+    }
+}
@@ -74,18 +74,26 @@ where
         }
     }
 
+    fn update_client_cert_header(&self, req: &mut HttpProxyRequest<CDR>) {
+        let element = self.ctx.client_cert.as_ref().map(|c| c.xfcc_element());
+        self.ctx
+            .server_config
+            .forward_client_cert
+            .update_headers(&mut req.inner.end_to_end_headers, element.as_deref());
+    }
+
     async fn run(&mut self) {
         let (stream_sender, mut stream_receiver) = mpsc::channel(1);
         loop {
             if let Some(mut reader) = self.stream_reader.take() {
                 let quit_after_timeout = self.pipeline_stats.get_alive_task() <= 0;
+                let read_idle_timeout = if quit_after_timeout {
+                    self.ctx.server_config.keepalive_idle_timeout()
+                } else {
+                    self.ctx.server_config.pipeline_read_idle_timeout
+                };
 
-                match tokio::time::timeout(
-                    self.ctx.server_config.pipeline_read_idle_timeout,
-                    reader.fill_wait_data(),
-                )
-                .await
-                {
+                match tokio::time::timeout(read_idle_timeout, reader.fill_wait_data()).await {
                     Ok(Ok(true)) => {}
                     Ok(Ok(false)) => {
                         trace!("client {} closed", self.ctx.client_addr());
@@ -121,6 +129,8 @@ where
                 .await
                 {
                     Ok(Ok((mut req, send_reader))) => {
+                        self.update_client_cert_header(&mut req);
+
                         if send_reader {
                             req.body_reader = Some(reader);
                         } else {
@@ -24,7 +24,7 @@ use tokio::sync::mpsc;
 
 use g3_io_ext::{ArcLimitedWriterStats, LimitedWriter};
 use g3_types::auth::UserAuthError;
-use g3_types::net::{HttpAuth, HttpBasicAuth, HttpHeaderMap};
+use g3_types::net::{HttpAuth, HttpBasicAuth, HttpBearerAuth, HttpHeaderMap, HttpNegotiateAuth};
 
 use super::protocol::{HttpClientReader, HttpClientWriter, HttpProxyRequest, HttpProxySubProtocol};
 use super::{
@@ -32,12 +32,16 @@ use super::{
     HttpProxyForwardTask, HttpProxyPipelineStats, HttpProxyUntrustedTask,
 };
 use crate::audit::AuditContext;
-use crate::auth::{UserContext, UserGroup, UserRequestStats};
+use crate::auth::{User, UserContext, UserGroup, UserRequestStats, UserType};
+use crate::config::http_header_rewrite::HttpHeaderRewriteConfig;
 use crate::config::server::ServerConfig;
 use crate::escape::EgressPathSelection;
 use crate::module::http_forward::{BoxHttpForwardContext, HttpProxyClientResponse};
+use crate::module::http_header;
 use crate::serve::{ServerStats, ServerTaskNotes};
 
+type FixedUser = (Arc<str>, Arc<User>, UserType);
+
 struct UserData {
     req_stats: Arc<UserRequestStats>,
     site_req_stats: Option<Arc<UserRequestStats>>,
@@ -83,6 +87,8 @@ pub(crate) struct HttpProxyPipelineWriterTask<CDR, CDW> {
     wrapper_stats: ArcLimitedWriterStats,
     pipeline_stats: Arc<HttpProxyPipelineStats>,
     req_count: RequestCount,
+    local_peer_user: Option<Option<FixedUser>>,
+    negotiate_user: Option<FixedUser>,
 }
 
 enum LoopAction {
@@ -124,17 +130,64 @@ where
             wrapper_stats: clt_w_stats,
             pipeline_stats: Arc::clone(pipeline_stats),
             req_count: RequestCount::default(),
+            local_peer_user: None,
+            negotiate_user: None,
         }
     }
 
-    fn do_auth(
+    fn offer_negotiate(&self) -> bool {
+        self.user_group
+            .as_ref()
+            .map(|user_group| user_group.negotiate_enabled())
+            .unwrap_or(false)
+    }
+
+    fn offer_bearer(&self) -> bool {
+        self.user_group
+            .as_ref()
+            .map(|user_group| user_group.bearer_enabled())
+            .unwrap_or(false)
+    }
+
+    async fn do_auth(
         &mut self,
         req: &HttpProxyRequest<CDR>,
     ) -> Result<Option<UserContext>, UserAuthError> {
+        if matches!(req.inner.auth_info, HttpAuth::None) && self.local_peer_user.is_none() {
+            // only lookup once for each connection
+            let local_peer_user = match &self.user_group {
+                Some(user_group) => user_group.get_local_peer_user(&self.ctx.cc_info).await,
+                None => None,
+            };
+            self.local_peer_user = Some(local_peer_user);
+        }
+
         if let Some(user_group) = &self.user_group {
             let mut user_ctx = match &req.inner.auth_info {
                 HttpAuth::None => {
-                    if let Some((user, user_type)) = user_group.get_anonymous_user() {
+                    if let Some((username, user, user_type)) = &self.negotiate_user {
+                        // negotiate auth is connection based, reuse the authenticated user
+                        let user_ctx = UserContext::new(
+                            Some(username.clone()),
+                            user.clone(),
+                            *user_type,
+                            self.ctx.server_config.name(),
+                            self.ctx.server_stats.share_extra_tags(),
+                        );
+                        user_ctx.check_usable()?;
+                        user_ctx
+                    } else if let Some(Some((username, user, user_type))) = &self.local_peer_user {
+                        let user_ctx = UserContext::new(
+                            Some(username.clone()),
+                            user.clone(),
+                            *user_type,
+                            self.ctx.server_config.name(),
+                            self.ctx.server_stats.share_extra_tags(),
+                        );
+                        user_ctx.check_client_addr(self.ctx.client_addr())?;
+                        user_ctx.check_local_peer()?;
+                        user_ctx
+                    } else if let Some((user, user_type)) = user_group.get_anonymous_user() {
                         let user_ctx = UserContext::new(
                             None,
                             user,
@@ -160,11 +213,40 @@ where
                             self.ctx.server_stats.share_extra_tags(),
                         );
                         user_ctx.check_client_addr(self.ctx.client_addr())?;
-                        user_ctx.check_password(password.as_original())?;
+                        user_group
+                            .check_user_password(&user_ctx, password.as_original())
+                            .await?;
                         user_ctx
                     }
                     None => return Err(UserAuthError::NoSuchUser),
                 },
+                HttpAuth::Negotiate(HttpNegotiateAuth { token }) => {
+                    let (username, user, user_type) = user_group.get_negotiate_user(token).await?;
+                    let user_ctx = UserContext::new(
+                        Some(username.clone()),
+                        user.clone(),
+                        user_type,
+                        self.ctx.server_config.name(),
+                        self.ctx.server_stats.share_extra_tags(),
+                    );
+                    user_ctx.check_client_addr(self.ctx.client_addr())?;
+                    user_ctx.check_usable()?;
+                    self.negotiate_user = Some((username, user, user_type));
+                    user_ctx
+                }
+                HttpAuth::Bearer(HttpBearerAuth { token }) => {
+                    let (username, user, user_type) = user_group.get_bearer_user(token)?;
+                    let user_ctx = UserContext::new(
+                        Some(username),
+                        user,
+                        user_type,
+                        self.ctx.server_config.name(),
+                        self.ctx.server_stats.share_extra_tags(),
+                    );
+                    user_ctx.check_client_addr(self.ctx.client_addr())?;
+                    user_ctx.check_usable()?;
+                    user_ctx
+                }
             };
 
             user_ctx.check_in_site(
@@ -207,15 +289,19 @@ where
         loop {
             let res = match self.task_queue.recv().await {
                 Some(Ok(req)) => {
-                    let res = match self.do_auth(&req) {
-                        Ok(user_ctx) => {
-                            self.req_count.consequent_auth_failed = 0;
-                            self.run(req, user_ctx).await
-                        }
-                        Err(e) => {
-                            self.req_count.consequent_auth_failed += 1;
-                            self.req_count.auth_failed += 1;
-                            self.run_untrusted(req, e.blocked_delay()).await
+                    let res = if self.should_reject_as_offline() {
+                        self.reply_offline(&req).await
+                    } else {
+                        match self.do_auth(&req).await {
+                            Ok(user_ctx) => {
+                                self.req_count.consequent_auth_failed = 0;
+                                self.run(req, user_ctx).await
+                            }
+                            Err(e) => {
+                                self.req_count.consequent_auth_failed += 1;
+                                self.req_count.auth_failed += 1;
+                                self.run_untrusted(req, e.blocked_delay()).await
+                            }
                         }
                     };
                     self.pipeline_stats.del_task();
@@ -244,6 +330,27 @@ where
         }
     }
 
+    fn should_reject_as_offline(&self) -> bool {
+        let Some(hint) = &self.ctx.server_config.failover_hint else {
+            return false;
+        };
+        hint.reject_when_offline && !self.ctx.server_stats.is_online()
+    }
+
+    async fn reply_offline(&mut self, req: &HttpProxyRequest<CDR>) -> LoopAction {
+        if let (Some(clt_w), Some(hint)) = (
+            &mut self.stream_writer,
+            &self.ctx.server_config.failover_hint,
+        ) {
+            let mut rsp = HttpProxyClientResponse::service_unavailable(req.inner.version);
+            rsp.add_failover_hint(hint);
+            let _ = rsp.reply_err_to_request(clt_w).await;
+        }
+
+        self.notify_reader_to_close();
+        LoopAction::Break
+    }
+
     fn get_egress_path_selection(
         &self,
         headers: &mut HttpHeaderMap,
@@ -265,12 +372,13 @@ where
         user_ctx: Option<UserContext>,
     ) -> LoopAction {
         let path_selection = self.get_egress_path_selection(&mut req.inner.end_to_end_headers);
-        let task_notes = ServerTaskNotes::with_path_selection(
+        let mut task_notes = ServerTaskNotes::with_path_selection(
             self.ctx.cc_info.clone(),
             user_ctx,
             req.time_accepted.elapsed(),
             path_selection,
         );
+        task_notes.server_name = req.inner.host.as_ref().map(|h| h.host().clone());
 
         let mut audit_ctx = self.audit_ctx.clone();
         let remote_protocol = match req.client_protocol {
@@ -383,6 +491,8 @@ where
         mut req: HttpProxyRequest<CDR>,
         blocked_delay: Option<Duration>,
     ) -> LoopAction {
+        let offer_negotiate = self.offer_negotiate();
+        let offer_bearer = self.offer_bearer();
         if self.ctx.server_config.no_early_error_reply {
             if let Some(duration) = blocked_delay {
                 self.ctx.server_stats.forbidden.add_user_blocked();
@@ -420,6 +530,8 @@ where
                     req.inner.version,
                     clt_w,
                     &self.ctx.server_config.auth_realm,
+                    offer_negotiate,
+                    offer_bearer,
                     true,
                 )
                 .await;
@@ -432,7 +544,8 @@ where
 
             match req.body_reader.take() {
                 Some(stream_r) => {
-                    let mut untrusted_task = HttpProxyUntrustedTask::new(&self.ctx, &req);
+                    let mut untrusted_task =
+                        HttpProxyUntrustedTask::new(&self.ctx, &req, offer_negotiate, offer_bearer);
                     let mut clt_r = Some(stream_r);
                     untrusted_task.run(&mut clt_r, clt_w).await;
                     if untrusted_task.should_close() {
@@ -450,7 +563,8 @@ where
                     }
                 }
                 None => {
-                    let mut untrusted_task = HttpProxyUntrustedTask::new(&self.ctx, &req);
+                    let mut untrusted_task =
+                        HttpProxyUntrustedTask::new(&self.ctx, &req, offer_negotiate, offer_bearer);
                     let mut clt_r = None;
                     untrusted_task.run::<CDR, CDW>(&mut clt_r, clt_w).await;
                     if untrusted_task.should_close() {
@@ -471,6 +585,40 @@ where
         }
     }
 
+    /// apply request header rewrite rules, and return the user group ones for use with response
+    fn rewrite_request_header(
+        &self,
+        req: &mut HttpProxyRequest<CDR>,
+        task_notes: &ServerTaskNotes,
+    ) -> Option<Arc<HttpHeaderRewriteConfig>> {
+        let user_header_rewrite = self
+            .user_group
+            .as_ref()
+            .filter(|_| task_notes.user_ctx().is_some())
+            .and_then(|g| g.http_header_rewrite().cloned());
+        let rewrite_log = http_header::HttpHeaderRewriteLog {
+            logger: &self.ctx.task_logger,
+            task_id: &task_notes.id,
+            user: task_notes.raw_user_name().map(|s| s.as_ref()),
+            direction: http_header::HttpHeaderRewriteDirection::Request,
+        };
+        if let Some(rewrite) = &self.ctx.server_config.http_header_rewrite {
+            http_header::rewrite(
+                &mut req.inner.end_to_end_headers,
+                &rewrite.request,
+                &rewrite_log,
+            );
+        }
+        if let Some(rewrite) = &user_header_rewrite {
+            http_header::rewrite(
+                &mut req.inner.end_to_end_headers,
+                &rewrite.request,
+                &rewrite_log,
+            );
+        }
+        user_header_rewrite
+    }
+
     async fn run_forward(
         &mut self,
         clt_w: &mut HttpClientWriter<CDW>,
@@ -484,13 +632,26 @@ where
             HttpProxySubProtocol::HttpsForward => true,
             _ => unreachable!(),
         };
+        let server_timing = self
+            .user_group
+            .as_ref()
+            .map(|g| g.server_timing_header())
+            .unwrap_or(false);
+        let user_header_rewrite = self.rewrite_request_header(&mut req, &task_notes);
 
         match req.body_reader.take() {
             Some(stream_r) => {
                 // we have a body, or we need to close the connection
                 // we may need to send stream_r back if we have a body
-                let mut forward_task =
-                    HttpProxyForwardTask::new(&self.ctx, audit_ctx, &req, is_https, task_notes);
+                let mut forward_task = HttpProxyForwardTask::new(
+                    &self.ctx,
+                    audit_ctx,
+                    &req,
+                    is_https,
+                    server_timing,
+                    user_header_rewrite,
+                    task_notes,
+                );
                 let mut clt_r = Some(stream_r);
                 forward_task
                     .run(&mut clt_r, clt_w, &mut self.forward_context)
@@ -511,8 +672,15 @@ where
             }
             None => {
                 // no body, and the connection is expected to keep alive from the client side
-                let mut forward_task =
-                    HttpProxyForwardTask::new(&self.ctx, audit_ctx, &req, is_https, task_notes);
+                let mut forward_task = HttpProxyForwardTask::new(
+                    &self.ctx,
+                    audit_ctx,
+                    &req,
+                    is_https,
+                    server_timing,
+                    user_header_rewrite,
+                    task_notes,
+                );
                 let mut clt_r = None;
                 forward_task
                     .run::<CDR, CDW>(&mut clt_r, clt_w, &mut self.forward_context)
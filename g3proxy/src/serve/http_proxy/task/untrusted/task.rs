@@ -33,6 +33,8 @@ use crate::serve::{ServerTaskError, ServerTaskResult};
 pub(crate) struct HttpProxyUntrustedTask<'a> {
     ctx: Arc<CommonTaskContext>,
     req: &'a HttpProxyClientRequest,
+    offer_negotiate: bool,
+    offer_bearer: bool,
     should_close: bool,
 }
 
@@ -40,10 +42,14 @@ impl<'a> HttpProxyUntrustedTask<'a> {
     pub(crate) fn new(
         ctx: &Arc<CommonTaskContext>,
         req: &'a HttpProxyRequest<impl AsyncRead>,
+        offer_negotiate: bool,
+        offer_bearer: bool,
     ) -> Self {
         HttpProxyUntrustedTask {
             ctx: Arc::clone(ctx),
             req: &req.inner,
+            offer_negotiate,
+            offer_bearer,
             should_close: !req.inner.keep_alive(),
         }
     }
@@ -116,6 +122,8 @@ impl<'a> HttpProxyUntrustedTask<'a> {
             self.req.version,
             clt_w,
             &self.ctx.server_config.auth_realm,
+            self.offer_negotiate,
+            self.offer_bearer,
             self.should_close,
         )
         .await;
@@ -38,8 +38,8 @@ use g3_openssl::SslStream;
 use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::metrics::NodeName;
 use g3_types::net::{
-    AlpnProtocol, OpensslTicketKey, RollingTicketer, RustlsServerConfig, RustlsServerConnectionExt,
-    UpstreamAddr,
+    AlpnProtocol, OpensslCertIdentity, OpensslTicketKey, RollingTicketer, RustlsServerConfig,
+    RustlsServerConnectionExt, UpstreamAddr,
 };
 use g3_types::route::HostMatch;
 
@@ -136,7 +136,7 @@ impl HttpRProxyServer {
 
         let tls_rolling_ticketer = if let Some(c) = &config.tls_ticketer {
             let ticketer = c
-                .build_and_spawn_updater()
+                .build_and_spawn_updater(config.name())
                 .context("failed to create tls rolling ticketer")?;
             Some(ticketer)
         } else {
@@ -167,7 +167,7 @@ impl HttpRProxyServer {
                 self.tls_rolling_ticketer.clone()
             } else if let Some(c) = &config.tls_ticketer {
                 let ticketer = c
-                    .build_and_spawn_updater()
+                    .build_and_spawn_updater(config.name())
                     .context("failed to create tls rolling ticketer")?;
                 Some(ticketer)
             } else {
@@ -196,13 +196,18 @@ impl HttpRProxyServer {
         }
     }
 
-    fn get_common_task_context(&self, cc_info: ClientConnectionInfo) -> Arc<CommonTaskContext> {
+    fn get_common_task_context(
+        &self,
+        cc_info: ClientConnectionInfo,
+        client_cert: Option<Arc<OpensslCertIdentity>>,
+    ) -> Arc<CommonTaskContext> {
         Arc::new(CommonTaskContext {
             server_config: Arc::clone(&self.config),
             server_stats: Arc::clone(&self.server_stats),
             server_quit_policy: Arc::clone(&self.quit_policy),
             escaper: self.escaper.load().as_ref().clone(),
             cc_info,
+            client_cert,
             task_logger: self.task_logger.clone(),
         })
     }
@@ -224,13 +229,17 @@ impl HttpRProxyServer {
         false
     }
 
-    async fn spawn_stream_task<T>(&self, stream: T, cc_info: ClientConnectionInfo)
-    where
+    async fn spawn_stream_task<T>(
+        &self,
+        stream: T,
+        cc_info: ClientConnectionInfo,
+        client_cert: Option<Arc<OpensslCertIdentity>>,
+    ) where
         T: AsyncStream,
         T::R: AsyncRead + Send + Sync + Unpin + 'static,
         T::W: AsyncWrite + Send + Sync + Unpin + 'static,
     {
-        let ctx = self.get_common_task_context(cc_info);
+        let ctx = self.get_common_task_context(cc_info, client_cert);
         let pipeline_stats = Arc::new(HttpRProxyPipelineStats::default());
         let (task_sender, task_receiver) = mpsc::channel(ctx.server_config.pipeline_size.get());
 
@@ -371,7 +380,9 @@ impl AcceptTcpServer for HttpRProxyServer {
                                         // Quick ACK is needed with session resumption
                                         cc_info.tcp_sock_try_quick_ack();
                                     }
-                                    self.spawn_stream_task(stream, cc_info).await
+                                    let client_cert =
+                                        crate::serve::client_cert::from_rustls(stream.get_ref().1);
+                                    self.spawn_stream_task(stream, cc_info, client_cert).await
                                 }
                                 Ok(Err(e)) => {
                                     self.listen_stats.add_failed();
@@ -424,7 +435,7 @@ impl AcceptTcpServer for HttpRProxyServer {
                 }
             }
         } else {
-            self.spawn_stream_task(stream, cc_info).await;
+            self.spawn_stream_task(stream, cc_info, None).await;
         }
     }
 }
@@ -473,7 +484,8 @@ impl Server for HttpRProxyServer {
             return;
         }
 
-        self.spawn_stream_task(stream, cc_info).await;
+        let client_cert = crate::serve::client_cert::from_rustls(stream.get_ref().1);
+        self.spawn_stream_task(stream, cc_info, client_cert).await;
     }
 
     async fn run_openssl_task(&self, stream: SslStream<TcpStream>, cc_info: ClientConnectionInfo) {
@@ -483,6 +495,7 @@ impl Server for HttpRProxyServer {
             return;
         }
 
-        self.spawn_stream_task(stream, cc_info).await;
+        let client_cert = crate::serve::client_cert::from_openssl(stream.ssl());
+        self.spawn_stream_task(stream, cc_info, client_cert).await;
     }
 }
@@ -194,6 +194,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
             task_notes: &self.task_notes,
             http_notes: &self.http_notes,
             http_user_agent,
+            client_cert: self.ctx.client_cert.as_deref(),
             tcp_notes: &self.tcp_notes,
             client_rd_bytes: self.task_stats.clt.read.get_bytes(),
             client_wr_bytes: self.task_stats.clt.write.get_bytes(),
@@ -526,7 +527,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
             match r {
                 Ok(r) => {
                     if let Some(connection) = r {
-                        fwd_ctx.save_alive_connection(connection);
+                        fwd_ctx.save_alive_connection(connection, &upstream_keepalive);
                     }
                     return Ok(());
                 }
@@ -557,7 +558,7 @@ impl<'a> HttpRProxyForwardTask<'a> {
         {
             Ok(r) => {
                 if let Some(connection) = r {
-                    fwd_ctx.save_alive_connection(connection);
+                    fwd_ctx.save_alive_connection(connection, &upstream_keepalive);
                 }
                 Ok(())
             }
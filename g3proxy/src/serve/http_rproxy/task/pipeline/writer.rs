@@ -124,13 +124,13 @@ where
         }
     }
 
-    fn do_auth(
+    async fn do_auth(
         &mut self,
         req: &HttpRProxyRequest<CDR>,
     ) -> Result<Option<UserContext>, UserAuthError> {
         if let Some(user_group) = &self.user_group {
             let mut user_ctx = match &req.inner.auth_info {
-                HttpAuth::None => {
+                HttpAuth::None | HttpAuth::Negotiate(_) | HttpAuth::Bearer(_) => {
                     if let Some((user, user_type)) = user_group.get_anonymous_user() {
                         let user_ctx = UserContext::new(
                             None,
@@ -157,7 +157,9 @@ where
                             self.ctx.server_stats.share_extra_tags(),
                         );
                         user_ctx.check_client_addr(self.ctx.client_addr())?;
-                        user_ctx.check_password(password.as_original())?;
+                        user_group
+                            .check_user_password(&user_ctx, password.as_original())
+                            .await?;
                         user_ctx
                     }
                     None => return Err(UserAuthError::NoSuchUser),
@@ -205,7 +207,7 @@ where
         loop {
             let res = match self.task_queue.recv().await {
                 Some(Ok(req)) => {
-                    let res = match self.do_auth(&req) {
+                    let res = match self.do_auth(&req).await {
                         Ok(user_ctx) => {
                             self.req_count.consequent_auth_failed = 0;
 
@@ -267,11 +269,12 @@ where
         user_ctx: Option<UserContext>,
         host: Arc<HttpHost>,
     ) -> LoopAction {
-        let task_notes = ServerTaskNotes::new(
+        let mut task_notes = ServerTaskNotes::new(
             self.ctx.cc_info.clone(),
             user_ctx,
             req.time_accepted.elapsed(),
         );
+        task_notes.server_name = req.inner.host.as_ref().map(|h| h.host().clone());
 
         if let Some(mut stream_w) = self.stream_writer.take() {
             let mut audit_ctx = AuditContext::default();
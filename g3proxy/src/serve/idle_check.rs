@@ -19,8 +19,9 @@ use std::time::Duration;
 
 use g3_io_ext::{IdleCheck, IdleForceQuitReason};
 
-use super::ServerQuitPolicy;
+use super::{ServerQuitPolicy, ServerTaskError};
 use crate::auth::User;
+use crate::config::idle::{TaskIdlePolicy, TaskIdleProtocol};
 
 pub(crate) struct ServerIdleChecker {
     pub(crate) idle_duration: Duration,
@@ -56,3 +57,174 @@ impl IdleCheck for ServerIdleChecker {
         None
     }
 }
+
+/// Idle counter for stream relay tasks, with protocol aware idle budgets.
+pub(crate) struct TaskIdleCounter {
+    idle_duration: Duration,
+    max_idle_count: i32,
+    max_read_idle_count: i32,
+    max_write_idle_count: i32,
+    idle_count: i32,
+    read_idle_count: i32,
+    write_idle_count: i32,
+}
+
+impl TaskIdleCounter {
+    pub(crate) fn new(
+        protocol: TaskIdleProtocol,
+        idle_duration: Duration,
+        server_max_idle_count: i32,
+        server_policy: TaskIdlePolicy,
+        user: Option<&User>,
+    ) -> Self {
+        let (common_max_idle_count, user_policy) = match user {
+            Some(user) => (user.task_max_idle_count(), *user.task_idle_policy()),
+            None => (server_max_idle_count, TaskIdlePolicy::default()),
+        };
+
+        let max_idle_count = user_policy
+            .protocol_max_idle_count(protocol)
+            .or(server_policy.protocol_max_idle_count(protocol))
+            .unwrap_or(common_max_idle_count);
+        let max_read_idle_count = user_policy.read.or(server_policy.read).unwrap_or(0);
+        let max_write_idle_count = user_policy.write.or(server_policy.write).unwrap_or(0);
+
+        TaskIdleCounter {
+            idle_duration,
+            max_idle_count,
+            max_read_idle_count,
+            max_write_idle_count,
+            idle_count: 0,
+            read_idle_count: 0,
+            write_idle_count: 0,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn idle_duration(&self) -> Duration {
+        self.idle_duration
+    }
+
+    /// Check the idle status of the last interval.
+    ///
+    /// `read_idle` should be true if no data received from the client,
+    /// `write_idle` should be true if no data sent to the client.
+    pub(crate) fn check(
+        &mut self,
+        read_idle: bool,
+        write_idle: bool,
+    ) -> Result<(), ServerTaskError> {
+        if read_idle && write_idle {
+            self.idle_count += 1;
+            if self.idle_count >= self.max_idle_count {
+                return Err(ServerTaskError::Idle(self.idle_duration, self.idle_count));
+            }
+        } else {
+            self.idle_count = 0;
+        }
+
+        if read_idle {
+            self.read_idle_count += 1;
+            if self.max_read_idle_count > 0 && self.read_idle_count >= self.max_read_idle_count {
+                return Err(ServerTaskError::ClientReadIdle(
+                    self.idle_duration,
+                    self.read_idle_count,
+                ));
+            }
+        } else {
+            self.read_idle_count = 0;
+        }
+
+        if write_idle {
+            self.write_idle_count += 1;
+            if self.max_write_idle_count > 0 && self.write_idle_count >= self.max_write_idle_count {
+                return Err(ServerTaskError::ClientWriteIdle(
+                    self.idle_duration,
+                    self.write_idle_count,
+                ));
+            }
+        } else {
+            self.write_idle_count = 0;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHECK_DURATION: Duration = Duration::from_secs(1);
+
+    #[test]
+    fn server_common_count() {
+        let mut counter = TaskIdleCounter::new(
+            TaskIdleProtocol::Tunnel,
+            CHECK_DURATION,
+            2,
+            TaskIdlePolicy::default(),
+            None,
+        );
+        assert!(counter.check(true, true).is_ok());
+        assert!(matches!(
+            counter.check(true, true),
+            Err(ServerTaskError::Idle(_, 2))
+        ));
+    }
+
+    #[test]
+    fn protocol_budget() {
+        let policy = TaskIdlePolicy {
+            tunnel: Some(3),
+            websocket: Some(1),
+            ..Default::default()
+        };
+
+        let mut counter =
+            TaskIdleCounter::new(TaskIdleProtocol::Websocket, CHECK_DURATION, 2, policy, None);
+        assert!(matches!(
+            counter.check(true, true),
+            Err(ServerTaskError::Idle(_, 1))
+        ));
+
+        let mut counter =
+            TaskIdleCounter::new(TaskIdleProtocol::Tunnel, CHECK_DURATION, 2, policy, None);
+        assert!(counter.check(true, true).is_ok());
+        assert!(counter.check(true, true).is_ok());
+        assert!(counter.check(true, true).is_err());
+    }
+
+    #[test]
+    fn read_write_budget() {
+        let policy = TaskIdlePolicy {
+            read: Some(2),
+            write: Some(3),
+            ..Default::default()
+        };
+
+        let mut counter =
+            TaskIdleCounter::new(TaskIdleProtocol::Tunnel, CHECK_DURATION, 10, policy, None);
+        assert!(counter.check(true, false).is_ok());
+        assert!(matches!(
+            counter.check(true, false),
+            Err(ServerTaskError::ClientReadIdle(_, 2))
+        ));
+
+        let mut counter =
+            TaskIdleCounter::new(TaskIdleProtocol::Tunnel, CHECK_DURATION, 10, policy, None);
+        assert!(counter.check(false, true).is_ok());
+        assert!(counter.check(false, true).is_ok());
+        assert!(matches!(
+            counter.check(false, true),
+            Err(ServerTaskError::ClientWriteIdle(_, 3))
+        ));
+
+        // reset on activity
+        let mut counter =
+            TaskIdleCounter::new(TaskIdleProtocol::Tunnel, CHECK_DURATION, 10, policy, None);
+        assert!(counter.check(true, false).is_ok());
+        assert!(counter.check(false, false).is_ok());
+        assert!(counter.check(true, false).is_ok());
+    }
+}
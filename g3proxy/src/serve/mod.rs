@@ -20,12 +20,16 @@ use async_trait::async_trait;
 #[cfg(feature = "quic")]
 use quinn::Connection;
 use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::UnixStream;
 use tokio::sync::broadcast;
 use tokio_rustls::server::TlsStream;
 
 use g3_daemon::listen::{
     AcceptQuicServer, AcceptTcpServer, ListenStats, ReloadQuicServer, ReloadTcpServer,
 };
+#[cfg(unix)]
+use g3_daemon::listen::{AcceptUnixServer, ReloadUnixServer};
 use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerQuitPolicy, ServerReloadCommand};
 use g3_openssl::SslStream;
 use g3_types::metrics::NodeName;
@@ -36,7 +40,15 @@ mod registry;
 pub(crate) use registry::{foreach_online as foreach_server, get_names, get_or_insert_default};
 
 mod idle_check;
-pub(crate) use idle_check::ServerIdleChecker;
+pub(crate) use idle_check::{ServerIdleChecker, TaskIdleCounter};
+
+mod conn_limit;
+pub(crate) use conn_limit::{ClientConnGuard, ClientConnLimiter};
+
+mod client_cert;
+
+mod route_test;
+pub(crate) use route_test::{RouteTestContext, RouteTestReport, RouteTestRequest};
 
 mod dummy_close;
 mod intelli_proxy;
@@ -50,6 +62,7 @@ mod http_proxy;
 mod http_rproxy;
 mod sni_proxy;
 mod socks_proxy;
+mod speed_test;
 mod tcp_stream;
 #[cfg(any(
     target_os = "linux",
@@ -59,6 +72,8 @@ mod tcp_stream;
 ))]
 mod tcp_tproxy;
 mod tls_stream;
+#[cfg(target_os = "linux")]
+mod udp_tproxy;
 
 mod error;
 mod task;
@@ -110,12 +125,26 @@ pub(crate) trait Server:
     }
     fn get_listen_stats(&self) -> Arc<ListenStats>;
 
+    fn client_conn_limiter(&self) -> Option<&Arc<ClientConnLimiter>> {
+        None
+    }
+
+    /// check the route of the request without sending any traffic,
+    /// None will be returned if not supported on this server
+    async fn test_route(&self, _req: &RouteTestRequest) -> Option<RouteTestReport> {
+        None
+    }
+
     fn alive_count(&self) -> i32;
     fn quit_policy(&self) -> &Arc<ServerQuitPolicy>;
 
     async fn run_rustls_task(&self, stream: TlsStream<TcpStream>, cc_info: ClientConnectionInfo);
 
     async fn run_openssl_task(&self, stream: SslStream<TcpStream>, cc_info: ClientConnectionInfo);
+
+    /// only servers that support unix socket listen need to implement this
+    #[cfg(unix)]
+    async fn run_unix_task(&self, _stream: UnixStream, _cc_info: ClientConnectionInfo) {}
 }
 
 pub(crate) type ArcServer = Arc<dyn Server + Send + Sync>;
@@ -140,6 +169,9 @@ impl BaseServer for WrapArcServer {
 #[async_trait]
 impl AcceptTcpServer for WrapArcServer {
     async fn run_tcp_task(&self, stream: TcpStream, cc_info: ClientConnectionInfo) {
+        if crate::capacity::check_shed_connection() {
+            return;
+        }
         self.0.run_tcp_task(stream, cc_info).await
     }
 }
@@ -150,6 +182,24 @@ impl ReloadTcpServer for WrapArcServer {
     }
 }
 
+#[cfg(unix)]
+#[async_trait]
+impl AcceptUnixServer for WrapArcServer {
+    async fn run_unix_task(&self, stream: UnixStream, cc_info: ClientConnectionInfo) {
+        if crate::capacity::check_shed_connection() {
+            return;
+        }
+        self.0.run_unix_task(stream, cc_info).await
+    }
+}
+
+#[cfg(unix)]
+impl ReloadUnixServer for WrapArcServer {
+    fn get_reloaded(&self) -> Self {
+        WrapArcServer(get_or_insert_default(self.name()))
+    }
+}
+
 #[async_trait]
 impl AcceptQuicServer for WrapArcServer {
     #[cfg(feature = "quic")]
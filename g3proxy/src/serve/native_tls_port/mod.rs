@@ -95,7 +95,7 @@ impl NativeTlsPort {
 
         let tls_rolling_ticketer = if let Some(c) = &config.tls_ticketer {
             let ticketer = c
-                .build_and_spawn_updater()
+                .build_and_spawn_updater(config.name())
                 .context("failed to create tls rolling ticketer")?;
             Some(ticketer)
         } else {
@@ -114,7 +114,7 @@ impl NativeTlsPort {
                 self.tls_rolling_ticketer.clone()
             } else if let Some(c) = &config.tls_ticketer {
                 let ticketer = c
-                    .build_and_spawn_updater()
+                    .build_and_spawn_updater(config.name())
                     .context("failed to create tls rolling ticketer")?;
                 Some(ticketer)
             } else {
@@ -41,6 +41,7 @@ use super::http_proxy::HttpProxyServer;
 use super::http_rproxy::HttpRProxyServer;
 use super::sni_proxy::SniProxyServer;
 use super::socks_proxy::SocksProxyServer;
+use super::speed_test::SpeedTestServer;
 use super::tcp_stream::TcpStreamServer;
 #[cfg(any(
     target_os = "linux",
@@ -50,6 +51,8 @@ use super::tcp_stream::TcpStreamServer;
 ))]
 use super::tcp_tproxy::TcpTProxyServer;
 use super::tls_stream::TlsStreamServer;
+#[cfg(target_os = "linux")]
+use super::udp_tproxy::UdpTProxyServer;
 
 static SERVER_OPS_LOCK: Mutex<()> = Mutex::const_new(());
 
@@ -308,11 +311,14 @@ fn spawn_new_unlocked(config: AnyServerConfig) -> anyhow::Result<()> {
             target_os = "openbsd"
         ))]
         AnyServerConfig::TcpTProxy(c) => TcpTProxyServer::prepare_initial(c)?,
+        #[cfg(target_os = "linux")]
+        AnyServerConfig::UdpTProxy(c) => UdpTProxyServer::prepare_initial(c)?,
         AnyServerConfig::TlsStream(c) => TlsStreamServer::prepare_initial(*c)?,
         AnyServerConfig::SniProxy(c) => SniProxyServer::prepare_initial(*c)?,
         AnyServerConfig::SocksProxy(c) => SocksProxyServer::prepare_initial(*c)?,
         AnyServerConfig::HttpProxy(c) => HttpProxyServer::prepare_initial(*c)?,
         AnyServerConfig::HttpRProxy(c) => HttpRProxyServer::prepare_initial(*c)?,
+        AnyServerConfig::SpeedTest(c) => SpeedTestServer::prepare_initial(c)?,
     };
     registry::add(name.clone(), server)?;
     update_dependency_to_server_unlocked(&name, "spawned");
@@ -45,6 +45,7 @@ struct PlainQuicPortAuxConfig {
     quinn_config: Option<quinn::ServerConfig>,
     accept_timeout: Duration,
     offline_rebind_port: Option<u16>,
+    migration_watch_interval: Option<Duration>,
 }
 
 impl ListenQuicConf for PlainQuicPortAuxConfig {
@@ -72,6 +73,11 @@ impl ListenQuicConf for PlainQuicPortAuxConfig {
     fn accept_timeout(&self) -> Duration {
         self.accept_timeout
     }
+
+    #[inline]
+    fn migration_watch_interval(&self) -> Option<Duration> {
+        self.migration_watch_interval
+    }
 }
 
 pub(crate) struct PlainQuicPort {
@@ -115,14 +121,18 @@ impl PlainQuicPort {
             quinn_config: None,
             accept_timeout: quic_server.accept_timeout,
             offline_rebind_port: config.offline_rebind_port,
+            migration_watch_interval: config.migration.watch_interval(),
         };
         let (cfg_sender, _cfg_receiver) = watch::channel(aux_config);
 
+        let mut quinn_config = quinn::ServerConfig::with_crypto(quic_server.driver);
+        config.migration.apply_to_server_config(&mut quinn_config);
+
         Ok(PlainQuicPort {
             name: config.name().clone(),
             config: ArcSwap::new(config),
             tls_rolling_ticketer,
-            quinn_config: quinn::ServerConfig::with_crypto(quic_server.driver),
+            quinn_config,
             listen_stats,
             reload_sender,
             cfg_sender,
@@ -137,7 +147,7 @@ impl PlainQuicPort {
 
         let tls_rolling_ticketer = if let Some(c) = &config.tls_ticketer {
             let ticketer = c
-                .build_and_spawn_updater()
+                .build_and_spawn_updater(config.name())
                 .context("failed to create tls rolling ticketer")?;
             Some(ticketer)
         } else {
@@ -157,7 +167,7 @@ impl PlainQuicPort {
                 self.tls_rolling_ticketer.clone()
             } else if let Some(c) = &config.tls_ticketer {
                 let ticketer = c
-                    .build_and_spawn_updater()
+                    .build_and_spawn_updater(config.name())
                     .context("failed to create tls rolling ticketer")?;
                 Some(ticketer)
             } else {
@@ -196,7 +206,9 @@ impl ServerInternal for PlainQuicPort {
 
             let quinn_config = if flags.contains(PlainQuicPortUpdateFlags::QUINN) {
                 let quic_config = config.tls_server.build_quic()?;
-                Some(quinn::ServerConfig::with_crypto(quic_config.driver))
+                let mut quinn_config = quinn::ServerConfig::with_crypto(quic_config.driver);
+                config.migration.apply_to_server_config(&mut quinn_config);
+                Some(quinn_config)
             } else {
                 None
             };
@@ -215,6 +227,7 @@ impl ServerInternal for PlainQuicPort {
                 quinn_config,
                 accept_timeout: config.tls_server.accept_timeout(),
                 offline_rebind_port: config.offline_rebind_port,
+                migration_watch_interval: config.migration.watch_interval(),
             };
             self.cfg_sender.send_replace(aux_config);
             self.config.store(config);
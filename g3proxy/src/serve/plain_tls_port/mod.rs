@@ -99,7 +99,7 @@ impl PlainTlsPort {
 
         let tls_rolling_ticketer = if let Some(c) = &config.tls_ticketer {
             let ticketer = c
-                .build_and_spawn_updater()
+                .build_and_spawn_updater(config.name())
                 .context("failed to create tls rolling ticketer")?;
             Some(ticketer)
         } else {
@@ -118,7 +118,7 @@ impl PlainTlsPort {
                 self.tls_rolling_ticketer.clone()
             } else if let Some(c) = &config.tls_ticketer {
                 let ticketer = c
-                    .build_and_spawn_updater()
+                    .build_and_spawn_updater(config.name())
                     .context("failed to create tls rolling ticketer")?;
                 Some(ticketer)
             } else {
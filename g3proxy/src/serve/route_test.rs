@@ -0,0 +1,230 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use arc_swap::ArcSwapOption;
+
+use g3_daemon::server::ClientConnectionInfo;
+use g3_types::acl::{AclAction, AclExactPortRule, AclNetworkRule};
+use g3_types::acl_set::AclDstHostRuleSet;
+use g3_types::metrics::{NodeName, StaticMetricsTags};
+use g3_types::net::{ProxyRequestType, UpstreamAddr};
+
+use super::ServerTaskNotes;
+use crate::auth::{UserContext, UserGroup};
+use crate::escape::ArcEscaper;
+
+const MAX_ESCAPER_CHAIN_DEPTH: usize = 16;
+
+pub(crate) struct RouteTestRequest {
+    pub(crate) user: Option<String>,
+    pub(crate) upstream: UpstreamAddr,
+    pub(crate) client_addr: SocketAddr,
+    pub(crate) request_type: ProxyRequestType,
+}
+
+#[derive(Default)]
+pub(crate) struct RouteTestReport {
+    pub(crate) steps: Vec<String>,
+    pub(crate) escapers: Vec<NodeName>,
+    pub(crate) permitted: bool,
+}
+
+impl RouteTestReport {
+    fn step(&mut self, msg: String) {
+        self.steps.push(msg);
+    }
+
+    fn deny(mut self, msg: String) -> Self {
+        self.steps.push(msg);
+        self.permitted = false;
+        self
+    }
+}
+
+/// The server side rules used to test the route for a request,
+/// no connections will be made and no stats will be recorded.
+pub(crate) struct RouteTestContext<'a> {
+    pub(crate) server: &'a NodeName,
+    pub(crate) server_extra_tags: &'a Arc<ArcSwapOption<StaticMetricsTags>>,
+    pub(crate) allowed_requests: &'a [ProxyRequestType],
+    pub(crate) ingress_net_filter: Option<&'a AclNetworkRule>,
+    pub(crate) dst_port_filter: Option<&'a AclExactPortRule>,
+    pub(crate) dst_host_filter: Option<&'a AclDstHostRuleSet>,
+    pub(crate) user_group: Option<Arc<UserGroup>>,
+    pub(crate) escaper: ArcEscaper,
+}
+
+impl RouteTestContext<'_> {
+    pub(crate) async fn run(self, req: &RouteTestRequest) -> RouteTestReport {
+        let mut report = RouteTestReport::default();
+
+        if !self.allowed_requests.contains(&req.request_type) {
+            return report.deny(format!(
+                "server {}: request type {:?} is not supported",
+                self.server, req.request_type
+            ));
+        }
+
+        if let Some(filter) = self.ingress_net_filter {
+            let (found, action) = filter.check(req.client_addr.ip());
+            if action.forbid_early() {
+                return report.deny(format!(
+                    "server ingress_network_filter: client {} {} (matched: {found})",
+                    req.client_addr.ip(),
+                    action
+                ));
+            }
+            report.step(format!(
+                "server ingress_network_filter: client {} {} (matched: {found})",
+                req.client_addr.ip(),
+                action
+            ));
+        }
+
+        let action = self.check_upstream(&req.upstream);
+        if action.forbid_early() {
+            return report.deny(format!(
+                "server dst filter: upstream {} {}",
+                req.upstream, action
+            ));
+        }
+        report.step(format!(
+            "server dst filter: upstream {} {}",
+            req.upstream, action
+        ));
+
+        let user_ctx = match self.user_group.as_ref() {
+            Some(user_group) => {
+                let (user, user_type) = match req.user.as_deref() {
+                    Some(name) => match user_group.get_user(name) {
+                        Some(v) => v,
+                        None => {
+                            return report.deny(format!("user {name} not found"));
+                        }
+                    },
+                    None => match user_group.get_anonymous_user() {
+                        Some(v) => v,
+                        None => {
+                            return report.deny(
+                                "no user specified and anonymous user is not enabled".to_string(),
+                            );
+                        }
+                    },
+                };
+                let user_ctx = UserContext::new_detached(
+                    req.user.as_deref().map(Arc::from),
+                    user,
+                    user_type,
+                    self.server,
+                    self.server_extra_tags,
+                );
+                match Self::check_user(&user_ctx, req) {
+                    Ok(msg) => report.step(msg),
+                    Err(msg) => return report.deny(msg),
+                }
+                Some(user_ctx)
+            }
+            None => {
+                report.step("no user group, auth skipped".to_string());
+                None
+            }
+        };
+
+        let cc_info = ClientConnectionInfo::new(req.client_addr, req.client_addr);
+        let task_notes = ServerTaskNotes::new(cc_info, user_ctx, Duration::ZERO);
+
+        let mut escaper = self.escaper;
+        loop {
+            report.step(format!(
+                "escaper {} (type {})",
+                escaper.name(),
+                escaper.escaper_type()
+            ));
+            report.escapers.push(escaper.name().clone());
+            if report.escapers.len() >= MAX_ESCAPER_CHAIN_DEPTH {
+                return report.deny(format!(
+                    "escaper chain is longer than {MAX_ESCAPER_CHAIN_DEPTH}, stopped"
+                ));
+            }
+            match escaper
+                ._check_out_next_escaper(&task_notes, &req.upstream)
+                .await
+            {
+                Some(next) => escaper = next,
+                None => break,
+            }
+        }
+
+        report.permitted = true;
+        report
+    }
+
+    fn check_upstream(&self, upstream: &UpstreamAddr) -> AclAction {
+        let mut default_action = if upstream.is_empty() {
+            AclAction::Forbid
+        } else {
+            AclAction::Permit
+        };
+
+        if let Some(filter) = self.dst_port_filter {
+            let port = upstream.port();
+            let (found, action) = filter.check_port(&port);
+            if found && action.forbid_early() {
+                return action;
+            };
+            default_action = default_action.restrict(action);
+        }
+
+        if let Some(filter) = self.dst_host_filter {
+            let (found, action) = filter.check(upstream.host());
+            if found && action.forbid_early() {
+                return action;
+            }
+            default_action = default_action.restrict(action);
+        }
+
+        default_action
+    }
+
+    fn check_user(user_ctx: &UserContext, req: &RouteTestRequest) -> Result<String, String> {
+        let user = user_ctx.user_name();
+        if user_ctx.check_client_addr(req.client_addr).is_err() {
+            return Err(format!(
+                "user {user}: client {} is blocked",
+                req.client_addr.ip()
+            ));
+        }
+        if user_ctx.user().is_blocked() {
+            return Err(format!("user {user}: blocked"));
+        }
+        let action = user_ctx.check_proxy_request(req.request_type);
+        if action.forbid_early() {
+            return Err(format!(
+                "user {user}: request type {:?} {}",
+                req.request_type, action
+            ));
+        }
+        let action = user_ctx.check_upstream(&req.upstream);
+        if action.forbid_early() {
+            return Err(format!("user {user}: upstream {} {}", req.upstream, action));
+        }
+        Ok(format!("user {user}: permitted"))
+    }
+}
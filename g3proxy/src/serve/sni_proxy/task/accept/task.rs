@@ -134,6 +134,7 @@ impl ClientHelloAcceptTask {
                     self.audit_ctx,
                     protocol,
                     final_upstream,
+                    upstream.host().clone(),
                     self.time_accepted.elapsed(),
                     self.pre_handshake_stats.as_ref().clone(),
                 )
@@ -147,11 +148,13 @@ impl ClientHelloAcceptTask {
                 ))
             }
         } else {
+            let server_name = upstream.host().clone();
             TcpStreamTask::new(
                 self.ctx,
                 self.audit_ctx,
                 protocol,
                 upstream,
+                server_name,
                 self.time_accepted.elapsed(),
                 self.pre_handshake_stats.as_ref().clone(),
             )
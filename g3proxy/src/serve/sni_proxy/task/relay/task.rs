@@ -26,11 +26,12 @@ use g3_dpi::Protocol;
 use g3_io_ext::{
     FlexBufReader, LimitedCopy, LimitedCopyConfig, LimitedReader, LimitedWriter, OnceBufReader,
 };
-use g3_types::net::UpstreamAddr;
+use g3_types::net::{Host, UpstreamAddr};
 
 use super::CommonTaskContext;
 use crate::audit::AuditContext;
 use crate::auth::User;
+use crate::config::idle::TaskIdlePolicy;
 use crate::inspect::{StreamInspectContext, StreamInspection, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes};
@@ -53,10 +54,12 @@ impl TcpStreamTask {
         audit_ctx: AuditContext,
         protocol: Protocol,
         upstream: UpstreamAddr,
+        server_name: Host,
         wait_time: Duration,
         pre_handshake_stats: TcpStreamConnectionStats,
     ) -> Self {
-        let task_notes = ServerTaskNotes::new(ctx.cc_info.clone(), None, wait_time);
+        let mut task_notes = ServerTaskNotes::new(ctx.cc_info.clone(), None, wait_time);
+        task_notes.server_name = Some(server_name);
         TcpStreamTask {
             ctx,
             upstream,
@@ -281,6 +284,10 @@ impl StreamTransitTask for TcpStreamTask {
         self.ctx.server_config.task_idle_max_count
     }
 
+    fn idle_policy(&self) -> TaskIdlePolicy {
+        self.ctx.server_config.task_idle_policy
+    }
+
     fn log_periodic(&self) {
         self.get_log_context().log_periodic(&self.ctx.task_logger);
     }
@@ -25,9 +25,13 @@ use quinn::Connection;
 use slog::Logger;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::TcpStream;
+#[cfg(unix)]
+use tokio::net::UnixStream;
 use tokio::sync::broadcast;
 use tokio_rustls::server::TlsStream;
 
+#[cfg(unix)]
+use g3_daemon::listen::ListenUnixRuntime;
 use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime};
 use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
 use g3_io_ext::AsyncStream;
@@ -35,6 +39,7 @@ use g3_openssl::SslStream;
 use g3_types::acl::{AclAction, AclNetworkRule};
 use g3_types::acl_set::AclDstHostRuleSet;
 use g3_types::metrics::NodeName;
+use g3_types::net::ProxyRequestType;
 
 use super::task::{CommonTaskContext, SocksProxyNegotiationTask};
 use super::SocksProxyServerStats;
@@ -44,7 +49,9 @@ use crate::config::server::socks_proxy::SocksProxyServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
 use crate::serve::{
-    ArcServer, ArcServerStats, Server, ServerInternal, ServerQuitPolicy, ServerStats, WrapArcServer,
+    ArcServer, ArcServerStats, ClientConnGuard, ClientConnLimiter, RouteTestContext,
+    RouteTestReport, RouteTestRequest, Server, ServerInternal, ServerQuitPolicy, ServerStats,
+    WrapArcServer,
 };
 
 pub(crate) struct SocksProxyServer {
@@ -52,6 +59,7 @@ pub(crate) struct SocksProxyServer {
     server_stats: Arc<SocksProxyServerStats>,
     listen_stats: Arc<ListenStats>,
     ingress_net_filter: Option<Arc<AclNetworkRule>>,
+    client_conn_limiter: Option<Arc<ClientConnLimiter>>,
     dst_host_filter: Option<Arc<AclDstHostRuleSet>>,
     reload_sender: broadcast::Sender<ServerReloadCommand>,
     task_logger: Logger,
@@ -68,6 +76,7 @@ impl SocksProxyServer {
         config: Arc<SocksProxyServerConfig>,
         server_stats: Arc<SocksProxyServerStats>,
         listen_stats: Arc<ListenStats>,
+        client_conn_limiter: Option<Arc<ClientConnLimiter>>,
         version: usize,
     ) -> anyhow::Result<SocksProxyServer> {
         let reload_sender = crate::serve::new_reload_notify_channel();
@@ -95,6 +104,7 @@ impl SocksProxyServer {
             server_stats,
             listen_stats,
             ingress_net_filter,
+            client_conn_limiter,
             dst_host_filter,
             reload_sender,
             task_logger,
@@ -113,7 +123,13 @@ impl SocksProxyServer {
         let server_stats = Arc::new(SocksProxyServerStats::new(config.name()));
         let listen_stats = Arc::new(ListenStats::new(config.name()));
 
-        let server = SocksProxyServer::new(config, server_stats, listen_stats, 1)?;
+        let client_conn_limiter = config
+            .client_conn_limit
+            .as_ref()
+            .map(|c| Arc::new(ClientConnLimiter::new(c)));
+
+        let server =
+            SocksProxyServer::new(config, server_stats, listen_stats, client_conn_limiter, 1)?;
         Ok(Arc::new(server))
     }
 
@@ -123,8 +139,24 @@ impl SocksProxyServer {
             let server_stats = Arc::clone(&self.server_stats);
             let listen_stats = Arc::clone(&self.listen_stats);
 
-            let server =
-                SocksProxyServer::new(config, server_stats, listen_stats, self.reload_version + 1)?;
+            // keep the old limiter if possible, as there may be alive connections
+            let client_conn_limiter = if self.config.client_conn_limit.eq(&config.client_conn_limit)
+            {
+                self.client_conn_limiter.clone()
+            } else {
+                config
+                    .client_conn_limit
+                    .as_ref()
+                    .map(|c| Arc::new(ClientConnLimiter::new(c)))
+            };
+
+            let server = SocksProxyServer::new(
+                config,
+                server_stats,
+                listen_stats,
+                client_conn_limiter,
+                self.reload_version + 1,
+            )?;
             Ok(server)
         } else {
             Err(anyhow!(
@@ -167,6 +199,12 @@ impl SocksProxyServer {
         if self.drop_early(client_addr) {
             return;
         }
+        let Some(_conn_guard) =
+            ClientConnGuard::acquire(self.client_conn_limiter.as_ref(), client_addr.ip())
+        else {
+            self.listen_stats.add_dropped();
+            return;
+        };
 
         let ctx = CommonTaskContext {
             server_config: Arc::clone(&self.config),
@@ -231,6 +269,18 @@ impl ServerInternal for SocksProxyServer {
     }
 
     fn _start_runtime(&self, server: &ArcServer) -> anyhow::Result<()> {
+        #[cfg(unix)]
+        if let Some(listen_config) = &self.config.listen_unix {
+            let runtime =
+                ListenUnixRuntime::new(WrapArcServer(server.clone()), server.get_listen_stats());
+            runtime.into_running(
+                listen_config,
+                self.config.listen_in_worker,
+                &self.reload_sender,
+            )?;
+            self.server_stats.set_online();
+        }
+
         let Some(listen_config) = &self.config.listen else {
             return Ok(());
         };
@@ -303,6 +353,27 @@ impl Server for SocksProxyServer {
         Arc::clone(&self.listen_stats)
     }
 
+    fn client_conn_limiter(&self) -> Option<&Arc<ClientConnLimiter>> {
+        self.client_conn_limiter.as_ref()
+    }
+
+    async fn test_route(&self, req: &RouteTestRequest) -> Option<RouteTestReport> {
+        let ctx = RouteTestContext {
+            server: self.config.name(),
+            server_extra_tags: self.server_stats.share_extra_tags(),
+            allowed_requests: &[
+                ProxyRequestType::SocksTcpConnect,
+                ProxyRequestType::SocksUdpAssociate,
+            ],
+            ingress_net_filter: self.ingress_net_filter.as_deref(),
+            dst_port_filter: self.config.dst_port_filter.as_ref(),
+            dst_host_filter: self.dst_host_filter.as_deref(),
+            user_group: self.user_group.load_full(),
+            escaper: self.escaper.load().as_ref().clone(),
+        };
+        Some(ctx.run(req).await)
+    }
+
     fn alive_count(&self) -> i32 {
         self.server_stats.get_alive_count()
     }
@@ -319,4 +390,9 @@ impl Server for SocksProxyServer {
     async fn run_openssl_task(&self, stream: SslStream<TcpStream>, cc_info: ClientConnectionInfo) {
         self.run_task(stream, cc_info).await
     }
+
+    #[cfg(unix)]
+    async fn run_unix_task(&self, stream: UnixStream, cc_info: ClientConnectionInfo) {
+        self.run_task(stream, cc_info).await
+    }
 }
@@ -126,6 +126,29 @@ impl SocksProxyNegotiationTask {
         }
     }
 
+    async fn get_local_peer_user_ctx(&self, user_group: &UserGroup) -> Option<UserContext> {
+        let (username, user, user_type) = user_group.get_local_peer_user(&self.ctx.cc_info).await?;
+        let user_ctx = UserContext::new(
+            Some(username),
+            user,
+            user_type,
+            self.ctx.server_config.name(),
+            self.ctx.server_stats.share_extra_tags(),
+        );
+        if let Err(e) = user_ctx
+            .check_client_addr(self.ctx.client_addr())
+            .and_then(|_| user_ctx.check_local_peer())
+        {
+            debug!(
+                "local peer user {} is not usable: {e}",
+                user_ctx.user_name()
+            );
+            return None;
+        }
+        user_ctx.req_stats().conn_total.add_socks();
+        Some(user_ctx)
+    }
+
     async fn run_v4<CDR, CDW>(
         self,
         mut clt_r: BufReader<LimitedReader<CDR>>,
@@ -135,8 +158,11 @@ impl SocksProxyNegotiationTask {
         CDR: AsyncRead + Send + Sync + Unpin + 'static,
         CDW: AsyncWrite + Send + Sync + Unpin + 'static,
     {
+        let mut local_peer_user_ctx = None;
         if let Some(user_group) = &self.user_group {
-            if !user_group.allow_anonymous(self.ctx.client_addr()) {
+            if let Some(user_ctx) = self.get_local_peer_user_ctx(user_group).await {
+                local_peer_user_ctx = Some(user_ctx);
+            } else if !user_group.allow_anonymous(self.ctx.client_addr()) {
                 // socks4(a) doesn't support auth
                 self.ctx.server_stats.forbidden.add_auth_failed();
                 return Err(ServerTaskError::InvalidClientProtocol(
@@ -147,19 +173,23 @@ impl SocksProxyNegotiationTask {
 
         let req = v4a::SocksV4aRequest::recv(&mut clt_r).await?;
 
-        let user_ctx = self.user_group.map(|user_group| {
-            let (user, user_type) = user_group.get_anonymous_user().unwrap();
-            let user_ctx = UserContext::new(
-                None,
-                user,
-                user_type,
-                self.ctx.server_config.name(),
-                self.ctx.server_stats.share_extra_tags(),
-            );
-            // no need to check user level client addr ACL again here
-            user_ctx.req_stats().conn_total.add_socks();
-            user_ctx
-        });
+        let user_ctx = if local_peer_user_ctx.is_some() {
+            local_peer_user_ctx
+        } else {
+            self.user_group.map(|user_group| {
+                let (user, user_type) = user_group.get_anonymous_user().unwrap();
+                let user_ctx = UserContext::new(
+                    None,
+                    user,
+                    user_type,
+                    self.ctx.server_config.name(),
+                    self.ctx.server_stats.share_extra_tags(),
+                );
+                // no need to check user level client addr ACL again here
+                user_ctx.req_stats().conn_total.add_socks();
+                user_ctx
+            })
+        };
 
         let task_notes = ServerTaskNotes::new(
             self.ctx.cc_info.clone(),
@@ -200,9 +230,13 @@ impl SocksProxyNegotiationTask {
         CDW: AsyncWrite + Send + Sync + Unpin + 'static,
     {
         let client_methods = v5::auth::recv_methods_from_client(&mut clt_r).await?;
+        let mut local_peer_user_ctx = None;
         let auth_method = if let Some(user_group) = &self.user_group {
             if client_methods.contains(&SocksAuthMethod::User) {
                 SocksAuthMethod::User
+            } else if let Some(user_ctx) = self.get_local_peer_user_ctx(user_group).await {
+                local_peer_user_ctx = Some(user_ctx);
+                SocksAuthMethod::None
             } else if user_group.allow_anonymous(self.ctx.client_addr()) {
                 SocksAuthMethod::None
             } else {
@@ -224,7 +258,9 @@ impl SocksProxyNegotiationTask {
 
         let user_ctx = match auth_method {
             SocksAuthMethod::None => {
-                if let Some(user_group) = &self.user_group {
+                if local_peer_user_ctx.is_some() {
+                    local_peer_user_ctx
+                } else if let Some(user_group) = &self.user_group {
                     let (user, user_type) = user_group.get_anonymous_user().unwrap();
                     let user_ctx = UserContext::new(
                         None,
@@ -256,7 +292,10 @@ impl SocksProxyNegotiationTask {
                             let _ = v5::auth::send_user_auth_failure(&mut clt_w).await;
                             return Err(ServerTaskError::ClientAuthFailed);
                         }
-                        match user_ctx.check_password(password.as_original()) {
+                        match user_group
+                            .check_user_password(&user_ctx, password.as_original())
+                            .await
+                        {
                             Ok(_) => {
                                 user_ctx.req_stats().conn_total.add_socks();
                                 v5::auth::send_user_auth_success(&mut clt_w)
@@ -30,6 +30,7 @@ use g3_types::net::{ProxyRequestType, UpstreamAddr};
 use super::{CommonTaskContext, TcpConnectTaskCltWrapperStats};
 use crate::audit::AuditContext;
 use crate::auth::User;
+use crate::config::idle::TaskIdlePolicy;
 use crate::config::server::ServerConfig;
 use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
@@ -468,6 +469,10 @@ impl StreamTransitTask for SocksProxyTcpConnectTask {
         self.ctx.server_config.task_idle_max_count
     }
 
+    fn idle_policy(&self) -> TaskIdlePolicy {
+        self.ctx.server_config.task_idle_policy
+    }
+
     fn log_periodic(&self) {
         self.get_log_context().log_periodic(&self.ctx.task_logger);
     }
@@ -0,0 +1,295 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use async_trait::async_trait;
+#[cfg(feature = "quic")]
+use quinn::Connection;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio::sync::broadcast;
+use tokio_rustls::server::TlsStream;
+
+use g3_daemon::listen::{AcceptQuicServer, AcceptTcpServer, ListenStats, ListenTcpRuntime};
+use g3_daemon::server::{BaseServer, ClientConnectionInfo, ServerExt, ServerReloadCommand};
+use g3_io_ext::AsyncStream;
+use g3_openssl::SslStream;
+use g3_types::acl::{AclAction, AclNetworkRule};
+use g3_types::metrics::NodeName;
+
+use super::task::{SpeedTestTask, SpeedTestTaskContext};
+use crate::config::server::speed_test::SpeedTestServerConfig;
+use crate::config::server::{AnyServerConfig, ServerConfig};
+use crate::serve::{ArcServer, Server, ServerInternal, ServerQuitPolicy, WrapArcServer};
+
+pub(crate) struct SpeedTestServer {
+    config: Arc<SpeedTestServerConfig>,
+    listen_stats: Arc<ListenStats>,
+    ingress_net_filter: Option<AclNetworkRule>,
+    reload_sender: broadcast::Sender<ServerReloadCommand>,
+    alive_count: Arc<AtomicI32>,
+
+    quit_policy: Arc<ServerQuitPolicy>,
+    reload_version: usize,
+}
+
+impl SpeedTestServer {
+    fn new(
+        config: Arc<SpeedTestServerConfig>,
+        listen_stats: Arc<ListenStats>,
+        version: usize,
+    ) -> SpeedTestServer {
+        let reload_sender = crate::serve::new_reload_notify_channel();
+
+        let ingress_net_filter = config
+            .ingress_net_filter
+            .as_ref()
+            .map(|builder| builder.build());
+
+        SpeedTestServer {
+            config,
+            listen_stats,
+            ingress_net_filter,
+            reload_sender,
+            alive_count: Arc::new(AtomicI32::new(0)),
+            quit_policy: Arc::new(ServerQuitPolicy::default()),
+            reload_version: version,
+        }
+    }
+
+    pub(crate) fn prepare_initial(config: SpeedTestServerConfig) -> anyhow::Result<ArcServer> {
+        let config = Arc::new(config);
+        let listen_stats = Arc::new(ListenStats::new(config.name()));
+
+        let server = SpeedTestServer::new(config, listen_stats, 1);
+        Ok(Arc::new(server))
+    }
+
+    fn prepare_reload(&self, config: AnyServerConfig) -> anyhow::Result<SpeedTestServer> {
+        if let AnyServerConfig::SpeedTest(config) = config {
+            let config = Arc::new(config);
+            let listen_stats = Arc::clone(&self.listen_stats);
+
+            let server = SpeedTestServer::new(config, listen_stats, self.reload_version + 1);
+            Ok(server)
+        } else {
+            Err(anyhow!(
+                "config type mismatch: expect {}, actual {}",
+                self.config.server_type(),
+                config.server_type()
+            ))
+        }
+    }
+
+    fn drop_early(&self, client_addr: SocketAddr) -> bool {
+        if let Some(ingress_net_filter) = &self.ingress_net_filter {
+            let (_, action) = ingress_net_filter.check(client_addr.ip());
+            match action {
+                AclAction::Permit | AclAction::PermitAndLog => {}
+                AclAction::Forbid | AclAction::ForbidAndLog => {
+                    self.listen_stats.add_dropped();
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn task_context(&self, cc_info: ClientConnectionInfo) -> SpeedTestTaskContext {
+        SpeedTestTaskContext {
+            server_config: Arc::clone(&self.config),
+            server_quit_policy: Arc::clone(&self.quit_policy),
+            alive_count: Arc::clone(&self.alive_count),
+            cc_info,
+        }
+    }
+
+    async fn run_task_with_stream<T>(&self, stream: T, cc_info: ClientConnectionInfo)
+    where
+        T: AsyncStream,
+        T::R: AsyncRead + Send + Sync + Unpin + 'static,
+        T::W: AsyncWrite + Send + Sync + Unpin + 'static,
+    {
+        let (clt_r, clt_w) = stream.into_split();
+        SpeedTestTask::new(self.task_context(cc_info))
+            .into_running(clt_r, clt_w)
+            .await;
+    }
+}
+
+impl ServerInternal for SpeedTestServer {
+    fn _clone_config(&self) -> AnyServerConfig {
+        AnyServerConfig::SpeedTest(self.config.as_ref().clone())
+    }
+
+    fn _update_config_in_place(&self, _flags: u64, _config: AnyServerConfig) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn _depend_on_server(&self, _name: &NodeName) -> bool {
+        false
+    }
+
+    fn _reload_config_notify_runtime(&self) {
+        let cmd = ServerReloadCommand::ReloadVersion(self.reload_version);
+        let _ = self.reload_sender.send(cmd);
+    }
+
+    fn _update_next_servers_in_place(&self) {}
+
+    fn _update_escaper_in_place(&self) {}
+
+    fn _update_user_group_in_place(&self) {}
+
+    fn _update_audit_handle_in_place(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn _reload_with_old_notifier(&self, config: AnyServerConfig) -> anyhow::Result<ArcServer> {
+        let mut server = self.prepare_reload(config)?;
+        server.reload_sender = self.reload_sender.clone();
+        Ok(Arc::new(server))
+    }
+
+    fn _reload_with_new_notifier(&self, config: AnyServerConfig) -> anyhow::Result<ArcServer> {
+        let server = self.prepare_reload(config)?;
+        Ok(Arc::new(server))
+    }
+
+    fn _start_runtime(&self, server: &ArcServer) -> anyhow::Result<()> {
+        let Some(listen_config) = &self.config.listen else {
+            return Ok(());
+        };
+        let runtime =
+            ListenTcpRuntime::new(WrapArcServer(server.clone()), server.get_listen_stats());
+        runtime.run_all_instances(
+            listen_config,
+            self.config.listen_in_worker,
+            &self.reload_sender,
+        )
+    }
+
+    fn _abort_runtime(&self) {
+        let _ = self.reload_sender.send(ServerReloadCommand::QuitRuntime);
+    }
+}
+
+impl BaseServer for SpeedTestServer {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        self.config.name()
+    }
+
+    #[inline]
+    fn server_type(&self) -> &'static str {
+        self.config.server_type()
+    }
+
+    #[inline]
+    fn version(&self) -> usize {
+        self.reload_version
+    }
+}
+
+impl ServerExt for SpeedTestServer {}
+
+#[async_trait]
+impl AcceptTcpServer for SpeedTestServer {
+    async fn run_tcp_task(&self, stream: TcpStream, cc_info: ClientConnectionInfo) {
+        if self.drop_early(cc_info.client_addr()) {
+            return;
+        }
+
+        self.run_task_with_stream(stream, cc_info).await
+    }
+}
+
+#[async_trait]
+impl AcceptQuicServer for SpeedTestServer {
+    #[cfg(feature = "quic")]
+    async fn run_quic_task(&self, connection: Connection, cc_info: ClientConnectionInfo) {
+        use log::debug;
+
+        if self.drop_early(cc_info.client_addr()) {
+            return;
+        }
+
+        loop {
+            match connection.accept_bi().await {
+                Ok((send_stream, recv_stream)) => {
+                    let task = SpeedTestTask::new(self.task_context(cc_info.clone()));
+                    tokio::spawn(task.into_running(recv_stream, send_stream));
+                }
+                Err(e) => {
+                    debug!(
+                        "{} - {} quic connection error: {e:?}",
+                        cc_info.sock_local_addr(),
+                        cc_info.sock_peer_addr()
+                    );
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Server for SpeedTestServer {
+    fn escaper(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn user_group(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn auditor(&self) -> &NodeName {
+        Default::default()
+    }
+
+    fn get_listen_stats(&self) -> Arc<ListenStats> {
+        Arc::clone(&self.listen_stats)
+    }
+
+    fn alive_count(&self) -> i32 {
+        self.alive_count.load(Ordering::Relaxed)
+    }
+
+    #[inline]
+    fn quit_policy(&self) -> &Arc<ServerQuitPolicy> {
+        &self.quit_policy
+    }
+
+    async fn run_rustls_task(&self, stream: TlsStream<TcpStream>, cc_info: ClientConnectionInfo) {
+        if self.drop_early(cc_info.client_addr()) {
+            return;
+        }
+
+        self.run_task_with_stream(stream, cc_info).await
+    }
+
+    async fn run_openssl_task(&self, stream: SslStream<TcpStream>, cc_info: ClientConnectionInfo) {
+        if self.drop_early(cc_info.client_addr()) {
+            return;
+        }
+
+        self.run_task_with_stream(stream, cc_info).await
+    }
+}
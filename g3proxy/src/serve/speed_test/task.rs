@@ -0,0 +1,373 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use http::{Method, StatusCode};
+use log::debug;
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::time::Instant;
+
+use g3_daemon::server::ClientConnectionInfo;
+use g3_http::server::{HttpRequestParseError, HttpTransparentRequest};
+use g3_http::HttpBodyReader;
+
+use crate::config::server::speed_test::SpeedTestServerConfig;
+use crate::serve::ServerQuitPolicy;
+
+const IO_BUFFER_SIZE: usize = 64 * 1024;
+
+pub(super) struct SpeedTestTaskContext {
+    pub(super) server_config: Arc<SpeedTestServerConfig>,
+    pub(super) server_quit_policy: Arc<ServerQuitPolicy>,
+    pub(super) alive_count: Arc<AtomicI32>,
+    pub(super) cc_info: ClientConnectionInfo,
+}
+
+struct AliveGuard(Arc<AtomicI32>);
+
+impl AliveGuard {
+    fn new(count: &Arc<AtomicI32>) -> Self {
+        count.fetch_add(1, Ordering::Relaxed);
+        AliveGuard(Arc::clone(count))
+    }
+}
+
+impl Drop for AliveGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+pub(super) struct SpeedTestTask {
+    ctx: SpeedTestTaskContext,
+}
+
+impl SpeedTestTask {
+    pub(super) fn new(ctx: SpeedTestTaskContext) -> Self {
+        SpeedTestTask { ctx }
+    }
+
+    pub(super) async fn into_running<R, W>(self, clt_r: R, clt_w: W)
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let _alive_guard = AliveGuard::new(&self.ctx.alive_count);
+
+        if let Err(e) = self.run(clt_r, clt_w).await {
+            debug!(
+                "{} - {} speed test task error: {e:?}",
+                self.ctx.cc_info.server_addr(),
+                self.ctx.cc_info.client_addr()
+            );
+        }
+    }
+
+    async fn run<R, W>(&self, clt_r: R, mut clt_w: W) -> anyhow::Result<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let config = &self.ctx.server_config;
+        let mut clt_r = BufReader::new(clt_r);
+
+        loop {
+            let req = match tokio::time::timeout(
+                config.req_hdr_recv_timeout,
+                HttpTransparentRequest::parse(&mut clt_r, config.req_hdr_max_size, false),
+            )
+            .await
+            {
+                Ok(Ok((req, _))) => req,
+                Ok(Err(HttpRequestParseError::ClientClosed)) => return Ok(()),
+                Ok(Err(e)) => {
+                    if let Some(status) = e.status_code() {
+                        let _ = send_text(&mut clt_w, status, &e.to_string(), false).await;
+                    }
+                    return Err(anyhow!("invalid request: {e}"));
+                }
+                Err(_) => return Ok(()),
+            };
+
+            let keep_alive = req.keep_alive() && !self.ctx.server_quit_policy.force_quit();
+            let keep_alive = match req.uri.path() {
+                "/download" => {
+                    self.handle_download(&req, &mut clt_r, &mut clt_w, keep_alive)
+                        .await?
+                }
+                "/upload" => {
+                    self.handle_upload(&req, &mut clt_r, &mut clt_w, keep_alive)
+                        .await?
+                }
+                "/echo" | "/" => {
+                    self.handle_echo(&req, &mut clt_r, &mut clt_w, keep_alive)
+                        .await?
+                }
+                _ => {
+                    self.handle_not_found(&req, &mut clt_r, &mut clt_w, keep_alive)
+                        .await?
+                }
+            };
+            if !keep_alive {
+                return Ok(());
+            }
+        }
+    }
+
+    /// read and drop the request body, `None` will be returned if it's too large
+    async fn drain_body<R>(
+        &self,
+        req: &HttpTransparentRequest,
+        clt_r: &mut R,
+    ) -> anyhow::Result<Option<(u64, Duration)>>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        let Some(body_type) = req.body_type() else {
+            return Ok(Some((0, Duration::ZERO)));
+        };
+        let config = &self.ctx.server_config;
+
+        let mut body_reader = HttpBodyReader::new(clt_r, body_type, config.body_line_max_len);
+        let mut buf = vec![0u8; IO_BUFFER_SIZE];
+        let mut total = 0u64;
+        let time_start = Instant::now();
+        loop {
+            let nr = body_reader
+                .read(&mut buf)
+                .await
+                .map_err(|e| anyhow!("failed to read request body: {e}"))?;
+            if nr == 0 {
+                return Ok(Some((total, time_start.elapsed())));
+            }
+            total += nr as u64;
+            if total > config.upload_max_size {
+                return Ok(None);
+            }
+        }
+    }
+
+    async fn handle_download<R, W>(
+        &self,
+        req: &HttpTransparentRequest,
+        clt_r: &mut R,
+        clt_w: &mut W,
+        keep_alive: bool,
+    ) -> anyhow::Result<bool>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        if self.drain_body(req, clt_r).await?.is_none() {
+            send_text(
+                clt_w,
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "too large body",
+                false,
+            )
+            .await?;
+            return Ok(false);
+        }
+
+        let config = &self.ctx.server_config;
+        let size = match query_value(req, "size") {
+            Some(s) => match s.parse::<u64>() {
+                Ok(size) if size <= config.download_max_size => size,
+                Ok(_) => {
+                    let msg = format!("size should be less than {}", config.download_max_size);
+                    send_text(clt_w, StatusCode::BAD_REQUEST, &msg, keep_alive).await?;
+                    return Ok(keep_alive);
+                }
+                Err(_) => {
+                    send_text(clt_w, StatusCode::BAD_REQUEST, "invalid size", keep_alive).await?;
+                    return Ok(keep_alive);
+                }
+            },
+            None => config.download_default_size,
+        };
+
+        let header = response_header(StatusCode::OK, "application/octet-stream", size, keep_alive);
+        clt_w.write_all(header.as_bytes()).await?;
+        if req.method != Method::HEAD {
+            let buf = vec![0u8; IO_BUFFER_SIZE];
+            let mut left = size;
+            while left > 0 {
+                let to_write = left.min(IO_BUFFER_SIZE as u64) as usize;
+                clt_w.write_all(&buf[..to_write]).await?;
+                left -= to_write as u64;
+            }
+        }
+        clt_w.flush().await?;
+        Ok(keep_alive)
+    }
+
+    async fn handle_upload<R, W>(
+        &self,
+        req: &HttpTransparentRequest,
+        clt_r: &mut R,
+        clt_w: &mut W,
+        keep_alive: bool,
+    ) -> anyhow::Result<bool>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let Some((size, duration)) = self.drain_body(req, clt_r).await? else {
+            send_text(
+                clt_w,
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "too large body",
+                false,
+            )
+            .await?;
+            return Ok(false);
+        };
+
+        let secs = duration.as_secs_f64();
+        let rate_bps = if secs > 0.0 {
+            (size as f64 * 8.0 / secs) as u64
+        } else {
+            0
+        };
+        let body = serde_json::json!({
+            "size": size,
+            "duration_ms": duration.as_millis() as u64,
+            "rate_bps": rate_bps,
+        });
+        send_json(clt_w, &body, keep_alive).await?;
+        Ok(keep_alive)
+    }
+
+    async fn handle_echo<R, W>(
+        &self,
+        req: &HttpTransparentRequest,
+        clt_r: &mut R,
+        clt_w: &mut W,
+        keep_alive: bool,
+    ) -> anyhow::Result<bool>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let Some((body_size, _)) = self.drain_body(req, clt_r).await? else {
+            send_text(
+                clt_w,
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "too large body",
+                false,
+            )
+            .await?;
+            return Ok(false);
+        };
+
+        let mut headers = Vec::new();
+        req.end_to_end_headers.for_each(|name, value| {
+            let name = value.original_name().unwrap_or(name.as_str());
+            headers.push(format!("{name}: {}", value.to_str()));
+        });
+        req.hop_by_hop_headers.for_each(|name, value| {
+            let name = value.original_name().unwrap_or(name.as_str());
+            headers.push(format!("{name}: {}", value.to_str()));
+        });
+
+        let cc_info = &self.ctx.cc_info;
+        let body = serde_json::json!({
+            "client_addr": cc_info.client_addr().to_string(),
+            "server_addr": cc_info.server_addr().to_string(),
+            "method": req.method.as_str(),
+            "uri": req.uri.to_string(),
+            "version": format!("{:?}", req.version),
+            "headers": headers,
+            "body_size": body_size,
+        });
+        send_json(clt_w, &body, keep_alive).await?;
+        Ok(keep_alive)
+    }
+
+    async fn handle_not_found<R, W>(
+        &self,
+        req: &HttpTransparentRequest,
+        clt_r: &mut R,
+        clt_w: &mut W,
+        keep_alive: bool,
+    ) -> anyhow::Result<bool>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let keep_alive = keep_alive && self.drain_body(req, clt_r).await?.is_some();
+        send_text(clt_w, StatusCode::NOT_FOUND, "not found", keep_alive).await?;
+        Ok(keep_alive)
+    }
+}
+
+fn query_value<'a>(req: &'a HttpTransparentRequest, key: &str) -> Option<&'a str> {
+    req.uri.query()?.split('&').find_map(|kv| {
+        let (k, v) = kv.split_once('=')?;
+        (k == key).then_some(v)
+    })
+}
+
+fn response_header(status: StatusCode, content_type: &str, len: u64, keep_alive: bool) -> String {
+    let connection = if keep_alive { "keep-alive" } else { "close" };
+    format!(
+        "HTTP/1.1 {} {}\r\n\
+         Content-Type: {content_type}\r\n\
+         Content-Length: {len}\r\n\
+         Cache-Control: no-store\r\n\
+         Connection: {connection}\r\n\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or_default(),
+    )
+}
+
+async fn send_text<W>(
+    clt_w: &mut W,
+    status: StatusCode,
+    msg: &str,
+    keep_alive: bool,
+) -> std::io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let header = response_header(status, "text/plain", msg.len() as u64, keep_alive);
+    clt_w.write_all(header.as_bytes()).await?;
+    clt_w.write_all(msg.as_bytes()).await?;
+    clt_w.flush().await
+}
+
+async fn send_json<W>(
+    clt_w: &mut W,
+    body: &serde_json::Value,
+    keep_alive: bool,
+) -> std::io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let body = body.to_string();
+    let header = response_header(
+        StatusCode::OK,
+        "application/json",
+        body.len() as u64,
+        keep_alive,
+    );
+    clt_w.write_all(header.as_bytes()).await?;
+    clt_w.write_all(body.as_bytes()).await?;
+    clt_w.flush().await
+}
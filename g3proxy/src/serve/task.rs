@@ -24,6 +24,8 @@ use uuid::Uuid;
 
 use g3_daemon::server::ClientConnectionInfo;
 use g3_types::limit::GaugeSemaphorePermit;
+use g3_types::metrics::StaticMetricsTags;
+use g3_types::net::{Host, ProxyProtocolTlv, PP2_TYPE_UNIQUE_ID};
 
 use crate::auth::UserContext;
 use crate::escape::EgressPathSelection;
@@ -68,6 +70,8 @@ pub(crate) struct ServerTaskNotes {
     pub(crate) wait_time: Duration,
     pub(crate) ready_time: Duration,
     pub(crate) egress_path_selection: Option<EgressPathSelection>,
+    /// the tls server name or http host detected in the client request
+    pub(crate) server_name: Option<Host>,
     /// the following fields should not be cloned
     pub(crate) user_req_alive_permit: Option<GaugeSemaphorePermit>,
 }
@@ -99,6 +103,7 @@ impl ServerTaskNotes {
             wait_time,
             ready_time: Duration::default(),
             egress_path_selection,
+            server_name: None,
             user_req_alive_permit: None,
         }
     }
@@ -118,6 +123,16 @@ impl ServerTaskNotes {
         self.cc_info.server_addr()
     }
 
+    #[inline]
+    pub(crate) fn proxy_protocol_tlvs(&self) -> &[ProxyProtocolTlv] {
+        self.cc_info.proxy_protocol_tlvs()
+    }
+
+    /// Get the PROXY protocol v2 unique id TLV for this task
+    pub(crate) fn proxy_protocol_unique_id(&self) -> ProxyProtocolTlv {
+        ProxyProtocolTlv::new(PP2_TYPE_UNIQUE_ID, self.id.as_bytes().to_vec())
+    }
+
     #[inline]
     pub(crate) fn worker_id(&self) -> Option<usize> {
         self.cc_info.worker_id()
@@ -137,6 +152,13 @@ impl ServerTaskNotes {
         self.user_ctx.as_ref().and_then(|c| c.raw_user_name())
     }
 
+    pub(crate) fn request_tags(&self) -> Option<&StaticMetricsTags> {
+        self.user_ctx
+            .as_ref()
+            .and_then(|c| c.request_tags())
+            .map(Arc::as_ref)
+    }
+
     pub(crate) fn egress_path(&self) -> Option<&EgressPathSelection> {
         self.user_ctx
             .as_ref()
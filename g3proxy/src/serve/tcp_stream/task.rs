@@ -28,6 +28,7 @@ use super::common::CommonTaskContext;
 use super::stats::TcpStreamTaskCltWrapperStats;
 use crate::audit::AuditContext;
 use crate::auth::User;
+use crate::config::idle::TaskIdlePolicy;
 use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf};
@@ -256,6 +257,10 @@ impl StreamTransitTask for TcpStreamTask {
         self.ctx.server_config.task_idle_max_count
     }
 
+    fn idle_policy(&self) -> TaskIdlePolicy {
+        self.ctx.server_config.task_idle_policy
+    }
+
     fn log_periodic(&self) {
         self.get_log_context().log_periodic(&self.ctx.task_logger);
     }
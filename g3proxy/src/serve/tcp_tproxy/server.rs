@@ -36,6 +36,7 @@ use g3_types::metrics::NodeName;
 use super::common::CommonTaskContext;
 use super::task::TProxyStreamTask;
 use crate::audit::{AuditContext, AuditHandle};
+use crate::auth::UserGroup;
 use crate::config::server::tcp_tproxy::TcpTProxyServerConfig;
 use crate::config::server::{AnyServerConfig, ServerConfig};
 use crate::escape::ArcEscaper;
@@ -53,6 +54,7 @@ pub(crate) struct TcpTProxyServer {
     task_logger: Logger,
 
     escaper: ArcSwap<ArcEscaper>,
+    user_group: ArcSwapOption<UserGroup>,
     audit_handle: ArcSwapOption<AuditHandle>,
     quit_policy: Arc<ServerQuitPolicy>,
     reload_version: usize,
@@ -77,6 +79,7 @@ impl TcpTProxyServer {
         server_stats.set_extra_tags(config.extra_metrics_tags.clone());
 
         let escaper = Arc::new(crate::escape::get_or_insert_default(config.escaper()));
+        let user_group = config.get_user_group();
         let audit_handle = config.get_audit_handle()?;
 
         let server = TcpTProxyServer {
@@ -87,6 +90,7 @@ impl TcpTProxyServer {
             reload_sender,
             task_logger,
             escaper: ArcSwap::new(escaper),
+            user_group: ArcSwapOption::new(user_group),
             audit_handle: ArcSwapOption::new(audit_handle),
             quit_policy: Arc::new(ServerQuitPolicy::default()),
             reload_version: version,
@@ -153,7 +157,7 @@ impl TcpTProxyServer {
             task_logger: self.task_logger.clone(),
         };
 
-        TProxyStreamTask::new(ctx, self.audit_context())
+        TProxyStreamTask::new(ctx, self.audit_context(), self.user_group.load_full())
             .into_running(stream)
             .await;
     }
@@ -184,7 +188,9 @@ impl ServerInternal for TcpTProxyServer {
         self.escaper.store(Arc::new(escaper));
     }
 
-    fn _update_user_group_in_place(&self) {}
+    fn _update_user_group_in_place(&self) {
+        self.user_group.store(self.config.get_user_group());
+    }
 
     fn _update_audit_handle_in_place(&self) -> anyhow::Result<()> {
         let audit_handle = self.config.get_audit_handle()?;
@@ -264,7 +270,7 @@ impl Server for TcpTProxyServer {
     }
 
     fn user_group(&self) -> &NodeName {
-        Default::default()
+        self.config.user_group()
     }
 
     fn auditor(&self) -> &NodeName {
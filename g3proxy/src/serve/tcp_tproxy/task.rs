@@ -23,16 +23,23 @@ use tokio::net::TcpStream;
 use g3_daemon::server::ServerQuitPolicy;
 use g3_daemon::stat::task::TcpStreamTaskStats;
 use g3_io_ext::{LimitedCopyConfig, LimitedReader, LimitedWriter};
+use g3_types::acl::AclAction;
+use g3_types::auth::UserAuthError;
 use g3_types::net::UpstreamAddr;
 
 use super::common::CommonTaskContext;
 use crate::audit::AuditContext;
-use crate::auth::User;
+use crate::auth::{User, UserContext, UserGroup};
+use crate::config::idle::TaskIdlePolicy;
+use crate::config::server::ServerConfig;
 use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes};
 use crate::serve::tcp_stream::TcpStreamTaskCltWrapperStats;
-use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage};
+use crate::serve::{
+    ServerStats, ServerTaskError, ServerTaskForbiddenError, ServerTaskNotes, ServerTaskResult,
+    ServerTaskStage,
+};
 
 pub(super) struct TProxyStreamTask {
     ctx: CommonTaskContext,
@@ -44,9 +51,14 @@ pub(super) struct TProxyStreamTask {
 }
 
 impl TProxyStreamTask {
-    pub(super) fn new(ctx: CommonTaskContext, audit_ctx: AuditContext) -> Self {
+    pub(super) fn new(
+        ctx: CommonTaskContext,
+        audit_ctx: AuditContext,
+        user_group: Option<Arc<UserGroup>>,
+    ) -> Self {
         let target = ctx.target_addr();
-        let task_notes = ServerTaskNotes::new(ctx.cc_info.clone(), None, Duration::ZERO);
+        let user_ctx = user_group.and_then(|user_group| Self::classify_user(&ctx, &user_group));
+        let task_notes = ServerTaskNotes::new(ctx.cc_info.clone(), user_ctx, Duration::ZERO);
         TProxyStreamTask {
             ctx,
             upstream: UpstreamAddr::from(target),
@@ -57,6 +69,22 @@ impl TProxyStreamTask {
         }
     }
 
+    /// map the client connection to a user if no explicit auth is present
+    fn classify_user(ctx: &CommonTaskContext, user_group: &UserGroup) -> Option<UserContext> {
+        let classify_config = ctx.server_config.user_classify.as_ref()?;
+        let (raw_user_name, (user, user_type)) = match classify_config.classify(&ctx.cc_info) {
+            Some(name) => (Some(name.clone()), user_group.get_user(name)?),
+            None => (None, user_group.get_anonymous_user()?),
+        };
+        Some(UserContext::new(
+            raw_user_name,
+            user,
+            user_type,
+            ctx.server_config.name(),
+            ctx.server_stats.share_extra_tags(),
+        ))
+    }
+
     fn get_log_context(&self) -> TaskLogForTcpConnect {
         TaskLogForTcpConnect {
             upstream: &self.upstream,
@@ -92,11 +120,67 @@ impl TProxyStreamTask {
         self.ctx.server_stats.dec_alive_task();
     }
 
+    fn check_user(&mut self) -> ServerTaskResult<()> {
+        let Some(user_ctx) = self.task_notes.user_ctx() else {
+            return Ok(());
+        };
+        let user_ctx = user_ctx.clone();
+
+        if let Err(e) = user_ctx
+            .check_client_addr(self.ctx.cc_info.client_addr())
+            .and_then(|_| user_ctx.check_usable())
+        {
+            return match e {
+                UserAuthError::BlockedUser(_) => Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::UserBlocked,
+                )),
+                UserAuthError::BlockedSrcIp(_) => Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::ClientIpBlocked,
+                )),
+                _ => Err(ServerTaskError::ClientAuthFailed),
+            };
+        }
+
+        if user_ctx.check_rate_limit().is_err() {
+            return Err(ServerTaskError::ForbiddenByRule(
+                ServerTaskForbiddenError::RateLimited,
+            ));
+        }
+
+        match user_ctx.acquire_request_semaphore() {
+            Ok(permit) => self.task_notes.user_req_alive_permit = Some(permit),
+            Err(_) => {
+                return Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::FullyLoaded,
+                ));
+            }
+        }
+
+        match user_ctx.check_upstream(&self.upstream) {
+            AclAction::Permit | AclAction::PermitAndLog => Ok(()),
+            AclAction::Forbid | AclAction::ForbidAndLog => {
+                user_ctx.add_dest_denied();
+                Err(ServerTaskError::ForbiddenByRule(
+                    ServerTaskForbiddenError::DestDenied,
+                ))
+            }
+        }
+    }
+
     async fn run(&mut self, clt_stream: TcpStream) -> ServerTaskResult<()> {
+        self.check_user()?;
+
+        let tcp_client_misc_opts = match self.task_notes.user_ctx() {
+            Some(user_ctx) => user_ctx
+                .user_config()
+                .tcp_client_misc_opts(&self.ctx.server_config.tcp_misc_opts),
+            None => self.ctx.server_config.tcp_misc_opts,
+        };
+
         // set client side socket options
         self.ctx
             .cc_info
-            .tcp_sock_set_raw_opts(&self.ctx.server_config.tcp_misc_opts, true)
+            .tcp_sock_set_raw_opts(&tcp_client_misc_opts, true)
             .map_err(|_| {
                 ServerTaskError::InternalServerError("failed to set client socket options")
             })?;
@@ -185,21 +269,37 @@ impl TProxyStreamTask {
 
         let (clt_r_stats, clt_w_stats) =
             TcpStreamTaskCltWrapperStats::new_pair(&self.ctx.server_stats, &self.task_stats);
-        let clt_speed_limit = &self.ctx.server_config.tcp_sock_speed_limit;
+        let mut clt_speed_limit = self.ctx.server_config.tcp_sock_speed_limit;
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            clt_speed_limit = user_ctx
+                .user_config()
+                .tcp_sock_speed_limit
+                .shrink_as_smaller(&clt_speed_limit);
+        }
 
-        let clt_r = LimitedReader::local_limited(
+        let mut clt_r = LimitedReader::local_limited(
             clt_r,
             clt_speed_limit.shift_millis,
             clt_speed_limit.max_north,
             clt_r_stats,
         );
-        let clt_w = LimitedWriter::local_limited(
+        let mut clt_w = LimitedWriter::local_limited(
             clt_w,
             clt_speed_limit.shift_millis,
             clt_speed_limit.max_south,
             clt_w_stats,
         );
 
+        if let Some(user_ctx) = self.task_notes.user_ctx() {
+            let user = user_ctx.user();
+            if let Some(limiter) = user.tcp_all_upload_speed_limit() {
+                clt_r.add_global_limiter(limiter.clone());
+            }
+            if let Some(limiter) = user.tcp_all_download_speed_limit() {
+                clt_w.add_global_limiter(limiter.clone());
+            }
+        }
+
         (clt_r, clt_w)
     }
 }
@@ -217,6 +317,10 @@ impl StreamTransitTask for TProxyStreamTask {
         self.ctx.server_config.task_idle_max_count
     }
 
+    fn idle_policy(&self) -> TaskIdlePolicy {
+        self.ctx.server_config.task_idle_policy
+    }
+
     fn log_periodic(&self) {
         self.get_log_context().log_periodic(&self.ctx.task_logger);
     }
@@ -230,6 +334,6 @@ impl StreamTransitTask for TProxyStreamTask {
     }
 
     fn user(&self) -> Option<&User> {
-        None
+        self.task_notes.user_ctx().map(|ctx| ctx.user().as_ref())
     }
 }
@@ -29,6 +29,7 @@ use g3_types::net::UpstreamAddr;
 use super::common::CommonTaskContext;
 use crate::audit::AuditContext;
 use crate::auth::User;
+use crate::config::idle::TaskIdlePolicy;
 use crate::inspect::{StreamInspectContext, StreamTransitTask};
 use crate::log::task::tcp_connect::TaskLogForTcpConnect;
 use crate::module::tcp_connect::{TcpConnectTaskConf, TcpConnectTaskNotes, TlsConnectTaskConf};
@@ -245,6 +246,10 @@ impl StreamTransitTask for TlsStreamTask {
         self.ctx.server_config.task_idle_max_count
     }
 
+    fn idle_policy(&self) -> TaskIdlePolicy {
+        self.ctx.server_config.task_idle_policy
+    }
+
     fn log_periodic(&self) {
         self.get_log_context().log_periodic(&self.ctx.task_logger);
     }
@@ -0,0 +1,50 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use slog::Logger;
+
+use g3_types::acl::{AclAction, AclNetworkRule};
+
+use super::stats::UdpTProxyServerStats;
+use crate::config::server::udp_tproxy::UdpTProxyServerConfig;
+use crate::escape::ArcEscaper;
+use crate::serve::ServerQuitPolicy;
+
+pub(super) struct CommonTaskContext {
+    pub(super) server_config: Arc<UdpTProxyServerConfig>,
+    pub(super) server_stats: Arc<UdpTProxyServerStats>,
+    pub(super) server_quit_policy: Arc<ServerQuitPolicy>,
+    pub(super) ingress_net_filter: Option<AclNetworkRule>,
+    pub(super) escaper: ArcEscaper,
+    pub(super) task_logger: Logger,
+}
+
+impl CommonTaskContext {
+    pub(super) fn drop_early(&self, client_addr: SocketAddr) -> bool {
+        if let Some(ingress_net_filter) = &self.ingress_net_filter {
+            let (_, action) = ingress_net_filter.check(client_addr.ip());
+            match action {
+                AclAction::Permit | AclAction::PermitAndLog => {}
+                AclAction::Forbid | AclAction::ForbidAndLog => return true,
+            }
+        }
+
+        false
+    }
+}
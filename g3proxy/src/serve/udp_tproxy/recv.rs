@@ -0,0 +1,116 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::{self, IoSliceMut};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use tokio::sync::mpsc;
+
+use g3_io_ext::{
+    LimitedRecvStats, UdpCopyClientError, UdpCopyClientRecv, UdpCopyPacket, UdpCopyPacketMeta,
+};
+
+use super::stats::UdpTProxyTaskCltWrapperStats;
+
+/// receive the client packets dispatched by the listen runtime
+pub(super) struct TProxyUdpClientRecv {
+    receiver: mpsc::Receiver<Vec<u8>>,
+    stats: Arc<UdpTProxyTaskCltWrapperStats>,
+}
+
+impl TProxyUdpClientRecv {
+    pub(super) fn new(
+        receiver: mpsc::Receiver<Vec<u8>>,
+        stats: Arc<UdpTProxyTaskCltWrapperStats>,
+    ) -> Self {
+        TProxyUdpClientRecv { receiver, stats }
+    }
+
+    /// copy the packet to the buffer, or return None if it's too large and should be dropped
+    fn copy_packet(&self, data: &[u8], buf: &mut [u8]) -> Option<usize> {
+        let len = data.len();
+        if len > buf.len() {
+            // the packet size config may have been reduced after the flow task is created
+            return None;
+        }
+        buf[..len].copy_from_slice(data);
+        self.stats.add_recv_bytes(len);
+        self.stats.add_recv_packet();
+        Some(len)
+    }
+}
+
+fn flow_closed() -> UdpCopyClientError {
+    UdpCopyClientError::RecvFailed(io::Error::new(
+        io::ErrorKind::ConnectionAborted,
+        "udp flow closed by listen runtime",
+    ))
+}
+
+impl UdpCopyClientRecv for TProxyUdpClientRecv {
+    fn max_hdr_len(&self) -> usize {
+        0
+    }
+
+    fn poll_recv_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<Result<(usize, usize), UdpCopyClientError>> {
+        loop {
+            match ready!(self.receiver.poll_recv(cx)) {
+                Some(data) => {
+                    if let Some(nr) = self.copy_packet(&data, buf) {
+                        return Poll::Ready(Ok((0, nr)));
+                    }
+                }
+                None => return Poll::Ready(Err(flow_closed())),
+            }
+        }
+    }
+
+    fn poll_recv_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &mut [UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        let mut count = 0;
+        while count < packets.len() {
+            let data = if count == 0 {
+                match ready!(self.receiver.poll_recv(cx)) {
+                    Some(data) => data,
+                    None => return Poll::Ready(Err(flow_closed())),
+                }
+            } else {
+                match self.receiver.try_recv() {
+                    Ok(data) => data,
+                    Err(_) => break,
+                }
+            };
+
+            let p = &mut packets[count];
+            let mut iov = IoSliceMut::new(p.buf_mut());
+            let Some(nr) = self.copy_packet(&data, &mut iov) else {
+                continue;
+            };
+            let meta = UdpCopyPacketMeta::new(&iov, 0, nr);
+            meta.set_packet(p);
+            count += 1;
+        }
+        Poll::Ready(Ok(count))
+    }
+}
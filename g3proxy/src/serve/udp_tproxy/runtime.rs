@@ -0,0 +1,252 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::future::poll_fn;
+use std::io::IoSliceMut;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use ahash::AHashMap;
+use arc_swap::ArcSwap;
+use log::{info, warn};
+use tokio::net::UdpSocket;
+use tokio::sync::{broadcast, mpsc};
+
+use g3_daemon::listen::ListenStats;
+use g3_daemon::server::{ClientConnectionInfo, ServerReloadCommand};
+use g3_io_ext::UdpSocketExt;
+use g3_types::metrics::NodeName;
+
+use super::common::CommonTaskContext;
+use super::task::UdpTProxyTask;
+use crate::serve::ServerStats;
+
+const FLOW_TABLE_CLEAN_INTERVAL: Duration = Duration::from_secs(10);
+
+type FlowKey = (SocketAddr, SocketAddr);
+
+pub(super) struct UdpTProxyRuntime {
+    server_name: NodeName,
+    server_version: usize,
+    instance_id: usize,
+    listen_stats: Arc<ListenStats>,
+    task_ctx: Arc<ArcSwap<CommonTaskContext>>,
+    /// the NAT table, keyed by (client address, original destination address)
+    flows: AHashMap<FlowKey, mpsc::Sender<Vec<u8>>>,
+}
+
+impl UdpTProxyRuntime {
+    pub(super) fn new(
+        server_name: NodeName,
+        server_version: usize,
+        listen_stats: Arc<ListenStats>,
+        task_ctx: Arc<ArcSwap<CommonTaskContext>>,
+    ) -> Self {
+        UdpTProxyRuntime {
+            server_name,
+            server_version,
+            instance_id: 0,
+            listen_stats,
+            task_ctx,
+            flows: AHashMap::new(),
+        }
+    }
+
+    fn pre_start(&self) {
+        info!(
+            "started UdpTProxy SRT[{}_v{}#{}]",
+            self.server_name, self.server_version, self.instance_id,
+        );
+        self.listen_stats.add_running_runtime();
+    }
+
+    fn post_stop(&self) {
+        info!(
+            "stopped UdpTProxy SRT[{}_v{}#{}]",
+            self.server_name, self.server_version, self.instance_id,
+        );
+        self.listen_stats.del_running_runtime();
+    }
+
+    async fn run(
+        mut self,
+        socket: UdpSocket,
+        mut server_reload_channel: broadcast::Receiver<ServerReloadCommand>,
+    ) {
+        use broadcast::error::RecvError;
+
+        let mut packet_size = self.task_ctx.load().server_config.udp_relay.packet_size();
+        // use 1 more byte to detect the packets that are too large and will be truncated
+        let mut buf = vec![0u8; packet_size + 1];
+        let mut clean_interval = tokio::time::interval(FLOW_TABLE_CLEAN_INTERVAL);
+
+        loop {
+            tokio::select! {
+                biased;
+
+                ev = server_reload_channel.recv() => {
+                    match ev {
+                        Ok(ServerReloadCommand::ReloadVersion(version)) => {
+                            // the task context has already been updated by the new server
+                            info!("SRT[{}_v{}#{}] received reload request from v{version}",
+                                self.server_name, self.server_version, self.instance_id);
+                            packet_size = self.task_ctx.load().server_config.udp_relay.packet_size();
+                            buf.resize(packet_size + 1, 0);
+                            continue;
+                        }
+                        Ok(ServerReloadCommand::QuitRuntime) => {}
+                        Err(RecvError::Closed) => {}
+                        Err(RecvError::Lagged(dropped)) => {
+                            warn!("SRT[{}_v{}#{}] server {} reload notify channel overflowed, {dropped} msg dropped",
+                                self.server_name, self.server_version, self.instance_id, self.server_name);
+                            continue;
+                        }
+                    }
+
+                    info!("SRT[{}_v{}#{}] will go offline",
+                        self.server_name, self.server_version, self.instance_id);
+                    break;
+                }
+                _ = clean_interval.tick() => {
+                    self.flows.retain(|_, sender| !sender.is_closed());
+                }
+                r = poll_fn(|cx| socket.poll_recvmsg_with_orig_dst(cx, &mut [IoSliceMut::new(&mut buf)])) => {
+                    match r {
+                        Ok((nr, _, _)) if nr > packet_size => {
+                            // truncated, drop it instead of forwarding a corrupted payload
+                            self.listen_stats.add_dropped();
+                        }
+                        Ok((nr, Some(client_addr), Some(orig_dst_addr))) => {
+                            self.dispatch(&buf[..nr], client_addr, orig_dst_addr);
+                        }
+                        Ok(_) => {
+                            self.listen_stats.add_dropped();
+                        }
+                        Err(e) => {
+                            self.listen_stats.add_failed();
+                            warn!("SRT[{}_v{}#{}] recv: {e:?}",
+                                self.server_name, self.server_version, self.instance_id);
+                        }
+                    }
+                }
+            }
+        }
+
+        // drop all senders so the flow tasks will quit
+        self.flows.clear();
+        self.post_stop();
+    }
+
+    fn dispatch(&mut self, packet: &[u8], client_addr: SocketAddr, orig_dst_addr: SocketAddr) {
+        use mpsc::error::TrySendError;
+
+        let key = (client_addr, orig_dst_addr);
+        if let Some(sender) = self.flows.get(&key) {
+            match sender.try_send(packet.to_vec()) {
+                Ok(_) => return,
+                Err(TrySendError::Full(_)) => {
+                    // just drop it as the flow task is too busy
+                    self.listen_stats.add_dropped();
+                    return;
+                }
+                Err(TrySendError::Closed(_)) => {
+                    self.flows.remove(&key);
+                }
+            }
+        }
+
+        let ctx = self.task_ctx.load_full();
+        ctx.server_stats.add_conn(client_addr);
+        if ctx.drop_early(client_addr) {
+            self.listen_stats.add_dropped();
+            return;
+        }
+        let alive_flows = usize::try_from(ctx.server_stats.get_alive_count()).unwrap_or(0);
+        if alive_flows >= ctx.server_config.max_flows {
+            // drop new flows to avoid running out of file descriptors and memory
+            self.listen_stats.add_dropped();
+            return;
+        }
+        self.listen_stats.add_accepted();
+        // count the flow as alive before the task is spawned, so the limit is accurate
+        ctx.server_stats.inc_alive_task();
+
+        let (sender, receiver) = mpsc::channel(ctx.server_config.flow_queue_size);
+        let _ = sender.try_send(packet.to_vec());
+        self.flows.insert(key, sender);
+
+        let mut cc_info = ClientConnectionInfo::new(client_addr, orig_dst_addr);
+        if let Some(rt) = g3_daemon::runtime::worker::select_handle() {
+            cc_info.set_worker_id(Some(rt.id));
+            rt.handle.spawn(async move {
+                UdpTProxyTask::new(ctx, cc_info)
+                    .into_running(receiver)
+                    .await;
+            });
+        } else {
+            tokio::spawn(async move {
+                UdpTProxyTask::new(ctx, cc_info)
+                    .into_running(receiver)
+                    .await;
+            });
+        }
+    }
+
+    fn into_running(
+        self,
+        socket: std::net::UdpSocket,
+        server_reload_channel: broadcast::Receiver<ServerReloadCommand>,
+    ) {
+        tokio::spawn(async move {
+            // make sure the listen socket associated with the correct reactor
+            match UdpSocket::from_std(socket) {
+                Ok(socket) => {
+                    self.pre_start();
+                    self.run(socket, server_reload_channel).await;
+                }
+                Err(e) => {
+                    warn!(
+                        "SRT[{}_v{}#{}] listen async: {e:?}",
+                        self.server_name, self.server_version, self.instance_id
+                    );
+                }
+            }
+        });
+    }
+
+    pub(super) fn run_all_instances(
+        &self,
+        server_reload_sender: &broadcast::Sender<ServerReloadCommand>,
+    ) -> anyhow::Result<()> {
+        let listen_config = self.task_ctx.load().server_config.listen.clone();
+
+        for i in 0..listen_config.instance() {
+            let runtime = UdpTProxyRuntime {
+                server_name: self.server_name.clone(),
+                server_version: self.server_version,
+                instance_id: i,
+                listen_stats: self.listen_stats.clone(),
+                task_ctx: self.task_ctx.clone(),
+                flows: AHashMap::new(),
+            };
+
+            let socket = g3_socket::udp::new_std_bind_listen(&listen_config)?;
+            runtime.into_running(socket, server_reload_sender.subscribe());
+        }
+        Ok(())
+    }
+}
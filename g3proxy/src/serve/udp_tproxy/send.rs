@@ -0,0 +1,77 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::{self, IoSlice};
+use std::task::{ready, Context, Poll};
+
+use g3_io_ext::{AsyncUdpSend, SendMsgHdr, UdpCopyClientError, UdpCopyClientSend, UdpCopyPacket};
+
+/// send packets to the client from the original destination address
+pub(super) struct TProxyUdpClientSend<T> {
+    inner: T,
+}
+
+impl<T> TProxyUdpClientSend<T>
+where
+    T: AsyncUdpSend,
+{
+    pub(super) fn new(inner: T) -> Self {
+        TProxyUdpClientSend { inner }
+    }
+}
+
+impl<T> UdpCopyClientSend for TProxyUdpClientSend<T>
+where
+    T: AsyncUdpSend + Send,
+{
+    fn poll_send_packet(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        let nw = ready!(self.inner.poll_send(cx, buf)).map_err(UdpCopyClientError::SendFailed)?;
+        if nw == 0 {
+            Poll::Ready(Err(UdpCopyClientError::SendFailed(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "write zero byte into sender",
+            ))))
+        } else {
+            Poll::Ready(Ok(nw))
+        }
+    }
+
+    fn poll_send_packets(
+        &mut self,
+        cx: &mut Context<'_>,
+        packets: &[UdpCopyPacket],
+    ) -> Poll<Result<usize, UdpCopyClientError>> {
+        let mut msgs: Vec<SendMsgHdr<1>> = packets
+            .iter()
+            .map(|p| SendMsgHdr::new([IoSlice::new(p.payload())], None))
+            .collect();
+
+        let count = ready!(self.inner.poll_batch_sendmsg(cx, &mut msgs))
+            .map_err(UdpCopyClientError::SendFailed)?;
+        if count == 0 {
+            Poll::Ready(Err(UdpCopyClientError::SendFailed(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "write zero packet into sender",
+            ))))
+        } else {
+            Poll::Ready(Ok(count))
+        }
+    }
+}
@@ -0,0 +1,62 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use g3_io_ext::{LimitedRecvStats, LimitedSendStats};
+
+use super::{UdpTProxyServerStats, UdpTProxyTaskStats};
+
+#[derive(Clone)]
+pub(crate) struct UdpTProxyTaskCltWrapperStats {
+    server: Arc<UdpTProxyServerStats>,
+    task: Arc<UdpTProxyTaskStats>,
+}
+
+impl UdpTProxyTaskCltWrapperStats {
+    pub(crate) fn new(server: &Arc<UdpTProxyServerStats>, task: &Arc<UdpTProxyTaskStats>) -> Self {
+        UdpTProxyTaskCltWrapperStats {
+            server: Arc::clone(server),
+            task: Arc::clone(task),
+        }
+    }
+}
+
+impl LimitedRecvStats for UdpTProxyTaskCltWrapperStats {
+    fn add_recv_bytes(&self, size: usize) {
+        let size = size as u64;
+        self.server.udp.add_in_bytes(size);
+        self.task.clt.recv.add_bytes(size);
+    }
+
+    fn add_recv_packets(&self, n: usize) {
+        self.server.udp.add_in_packets(n);
+        self.task.clt.recv.add_packets(n);
+    }
+}
+
+impl LimitedSendStats for UdpTProxyTaskCltWrapperStats {
+    fn add_send_bytes(&self, size: usize) {
+        let size = size as u64;
+        self.server.udp.add_out_bytes(size);
+        self.task.clt.send.add_bytes(size);
+    }
+
+    fn add_send_packets(&self, n: usize) {
+        self.server.udp.add_out_packets(n);
+        self.task.clt.send.add_packets(n);
+    }
+}
@@ -0,0 +1,242 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use slog::Logger;
+use tokio::net::UdpSocket;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use g3_daemon::server::ClientConnectionInfo;
+use g3_io_ext::{
+    LimitedUdpSend, OptionalInterval, UdpCopyClientToRemote, UdpCopyError, UdpCopyRemoteRecv,
+    UdpCopyRemoteSend, UdpCopyRemoteToClient, UdpSendHalf,
+};
+use g3_types::net::UpstreamAddr;
+
+use super::common::CommonTaskContext;
+use super::recv::TProxyUdpClientRecv;
+use super::send::TProxyUdpClientSend;
+use super::stats::{UdpTProxyTaskCltWrapperStats, UdpTProxyTaskStats};
+use crate::log::escape::udp_sendto::EscapeLogForUdpConnectSendTo;
+use crate::log::task::udp_connect::TaskLogForUdpConnect;
+use crate::module::udp_connect::{UdpConnectTaskConf, UdpConnectTaskNotes};
+use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage};
+
+pub(super) struct UdpTProxyTask {
+    ctx: Arc<CommonTaskContext>,
+    upstream: UpstreamAddr,
+    udp_notes: UdpConnectTaskNotes,
+    task_notes: ServerTaskNotes,
+    task_stats: Arc<UdpTProxyTaskStats>,
+}
+
+impl UdpTProxyTask {
+    pub(super) fn new(ctx: Arc<CommonTaskContext>, cc_info: ClientConnectionInfo) -> Self {
+        let upstream = UpstreamAddr::from(cc_info.server_addr());
+        let task_notes = ServerTaskNotes::new(cc_info, None, Duration::ZERO);
+        UdpTProxyTask {
+            ctx,
+            upstream,
+            udp_notes: UdpConnectTaskNotes::default(),
+            task_notes,
+            task_stats: Arc::new(UdpTProxyTaskStats::default()),
+        }
+    }
+
+    fn get_log_context(&self) -> TaskLogForUdpConnect {
+        TaskLogForUdpConnect {
+            task_notes: &self.task_notes,
+            tcp_server_addr: self.task_notes.server_addr(),
+            tcp_client_addr: self.task_notes.client_addr(),
+            udp_listen_addr: Some(self.task_notes.server_addr()),
+            udp_client_addr: Some(self.task_notes.client_addr()),
+            upstream: Some(&self.upstream),
+            udp_notes: &self.udp_notes,
+            client_rd_bytes: self.task_stats.clt.recv.get_bytes(),
+            client_rd_packets: self.task_stats.clt.recv.get_packets(),
+            client_wr_bytes: self.task_stats.clt.send.get_bytes(),
+            client_wr_packets: self.task_stats.clt.send.get_packets(),
+            remote_rd_bytes: self.task_stats.ups.recv.get_bytes(),
+            remote_rd_packets: self.task_stats.ups.recv.get_packets(),
+            remote_wr_bytes: self.task_stats.ups.send.get_bytes(),
+            remote_wr_packets: self.task_stats.ups.send.get_packets(),
+        }
+    }
+
+    pub(super) async fn into_running(mut self, receiver: mpsc::Receiver<Vec<u8>>) {
+        self.pre_start();
+        match self.run(receiver).await {
+            Ok(_) => self
+                .get_log_context()
+                .log(&self.ctx.task_logger, &ServerTaskError::Finished),
+            Err(e) => self.get_log_context().log(&self.ctx.task_logger, &e),
+        };
+        self.pre_stop();
+    }
+
+    fn pre_start(&self) {
+        // the alive task count has been increased by the listen runtime
+        self.ctx.server_stats.add_task();
+        if self.ctx.server_config.flush_task_log_on_created {
+            self.get_log_context().log_created(&self.ctx.task_logger);
+        }
+    }
+
+    fn pre_stop(&self) {
+        self.ctx.server_stats.dec_alive_task();
+    }
+
+    async fn run(&mut self, receiver: mpsc::Receiver<Vec<u8>>) -> ServerTaskResult<()> {
+        let wrapper_stats = Arc::new(UdpTProxyTaskCltWrapperStats::new(
+            &self.ctx.server_stats,
+            &self.task_stats,
+        ));
+
+        let reply_socket = g3_socket::udp::new_std_transparent_reply(
+            self.task_notes.server_addr(),
+            self.task_notes.client_addr(),
+            self.ctx.server_config.udp_socket_buffer,
+            self.ctx.server_config.udp_misc_opts,
+        )
+        .map_err(|_| {
+            ServerTaskError::InternalServerError("failed to setup udp reply socket to client")
+        })?;
+        let reply_socket = UdpSocket::from_std(reply_socket).map_err(|_| {
+            ServerTaskError::InternalServerError("failed to setup udp reply socket to client")
+        })?;
+        let (_, clt_w) = g3_io_ext::split_udp(reply_socket);
+        let clt_w = LimitedUdpSend::local_limited(clt_w, 0, 0, 0, wrapper_stats.clone());
+
+        let clt_r = TProxyUdpClientRecv::new(receiver, wrapper_stats);
+        let clt_w = TProxyUdpClientSend::new(clt_w);
+
+        self.task_notes.stage = ServerTaskStage::Connecting;
+        let task_conf = UdpConnectTaskConf {
+            upstream: &self.upstream,
+            sock_buf: self.ctx.server_config.udp_socket_buffer,
+        };
+        let (ups_r, ups_w, escape_logger) = self
+            .ctx
+            .escaper
+            .udp_setup_connection(
+                &task_conf,
+                &mut self.udp_notes,
+                &self.task_notes,
+                self.task_stats.clone(),
+            )
+            .await?;
+        self.task_notes.stage = ServerTaskStage::Connected;
+
+        if self.ctx.server_config.flush_task_log_on_connected {
+            self.get_log_context().log_connected(&self.ctx.task_logger);
+        }
+
+        self.task_notes.mark_relaying();
+        self.run_relay(clt_r, clt_w, ups_r, ups_w, &escape_logger)
+            .await
+    }
+
+    async fn run_relay(
+        &mut self,
+        mut clt_r: TProxyUdpClientRecv,
+        mut clt_w: TProxyUdpClientSend<LimitedUdpSend<UdpSendHalf>>,
+        mut ups_r: Box<dyn UdpCopyRemoteRecv + Unpin + Send + Sync>,
+        mut ups_w: Box<dyn UdpCopyRemoteSend + Unpin + Send + Sync>,
+        escape_logger: &Logger,
+    ) -> ServerTaskResult<()> {
+        let task_id = &self.task_notes.id;
+
+        let mut c_to_r =
+            UdpCopyClientToRemote::new(&mut clt_r, &mut *ups_w, self.ctx.server_config.udp_relay);
+        let mut r_to_c =
+            UdpCopyRemoteToClient::new(&mut clt_w, &mut *ups_r, self.ctx.server_config.udp_relay);
+
+        let idle_duration = self.ctx.server_config.task_idle_check_duration;
+        let mut idle_interval =
+            tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
+        let mut log_interval = self
+            .ctx
+            .server_config
+            .task_log_flush_interval
+            .map(|log_interval| {
+                let interval =
+                    tokio::time::interval_at(Instant::now() + log_interval, log_interval);
+                OptionalInterval::with(interval)
+            })
+            .unwrap_or_default();
+        let mut idle_count = 0;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut c_to_r => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(UdpCopyError::RemoteError(e)) => {
+                            EscapeLogForUdpConnectSendTo {
+                                task_id,
+                                upstream: Some(&self.upstream),
+                                udp_notes: &self.udp_notes,
+                            }
+                            .log(escape_logger, &e);
+                            Err(e.into())
+                        }
+                        Err(UdpCopyError::ClientError(e)) => Err(e.into()),
+                    };
+                }
+                r = &mut r_to_c => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(UdpCopyError::RemoteError(e)) => {
+                            EscapeLogForUdpConnectSendTo {
+                                task_id,
+                                upstream: Some(&self.upstream),
+                                udp_notes: &self.udp_notes,
+                            }
+                            .log(escape_logger, &e);
+                            Err(e.into())
+                        }
+                        Err(UdpCopyError::ClientError(e)) => Err(e.into()),
+                    };
+                }
+                _ = log_interval.tick() => {
+                    self.get_log_context().log_periodic(&self.ctx.task_logger);
+                }
+                _ = idle_interval.tick() => {
+                    if c_to_r.is_idle() && r_to_c.is_idle() {
+                        idle_count += 1;
+
+                        if idle_count >= self.ctx.server_config.task_idle_max_count {
+                            return Err(ServerTaskError::Idle(idle_duration, idle_count));
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        c_to_r.reset_active();
+                        r_to_c.reset_active();
+                    }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            }
+        }
+    }
+}
@@ -14,41 +14,8 @@
  * limitations under the License.
  */
 
-use log::{error, info, warn};
-use tokio::sync::Mutex;
-
 use g3_daemon::signal::AsyncSignalAction;
 
-static RELOAD_MUTEX: Mutex<()> = Mutex::const_new(());
-
-async fn do_reload() {
-    let _guard = RELOAD_MUTEX.lock().await;
-    info!("reloading config");
-
-    if let Err(e) = crate::config::reload().await {
-        warn!("error reloading config: {e:?}");
-        warn!("reload aborted");
-    }
-
-    if let Err(e) = crate::resolve::spawn_all().await {
-        error!("failed to reload all resolvers: {e:?}");
-    }
-    if let Err(e) = crate::escape::load_all().await {
-        error!("failed to reload all escapers: {e:?}");
-    }
-    if let Err(e) = crate::auth::load_all().await {
-        error!("failed to reload all user groups: {e:?}");
-    }
-    if let Err(e) = crate::audit::load_all().await {
-        error!("failed to reload all auditors: {e:?}");
-    }
-    if let Err(e) = crate::serve::spawn_all().await {
-        error!("failed to reload all servers: {e:?}");
-    }
-
-    info!("reload finished");
-}
-
 #[derive(Clone, Copy)]
 struct QuitAction {}
 
@@ -58,7 +25,6 @@ impl AsyncSignalAction for QuitAction {
     }
 }
 
-#[allow(unused)]
 #[derive(Clone, Copy)]
 struct OfflineAction {}
 
@@ -74,7 +40,7 @@ struct ReloadAction {}
 
 impl AsyncSignalAction for ReloadAction {
     async fn run(&self) {
-        do_reload().await
+        crate::reload::reload_all().await
     }
 }
 
@@ -85,5 +51,5 @@ pub fn register() -> anyhow::Result<()> {
 
 #[cfg(windows)]
 pub fn register() -> anyhow::Result<()> {
-    g3_daemon::signal::register(QuitAction {})
+    g3_daemon::signal::register(QuitAction {}, OfflineAction {})
 }
@@ -0,0 +1,134 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::{Arc, LazyLock, Mutex};
+
+use ahash::AHashMap;
+
+use g3_daemon::metrics::{TAG_KEY_BUCKET_LE, TAG_KEY_QUANTILE, TAG_KEY_STAT_ID};
+use g3_statsd_client::{StatsdClient, StatsdTagGroup};
+use g3_types::metrics::NodeName;
+use g3_types::stats::StatId;
+
+use super::TenantMetricExt;
+use crate::audit::{AuditorSnapshot, AuditorStats};
+use crate::config::tenant::TenantMemberType;
+
+const TAG_KEY_AUDITOR: &str = "auditor";
+const TAG_KEY_PROTOCOL: &str = "protocol";
+const TAG_KEY_ACTION: &str = "action";
+
+const METRIC_NAME_INSPECT_PROTOCOL: &str = "auditor.inspect.protocol";
+const METRIC_NAME_INSPECT_ACTION: &str = "auditor.inspect.action";
+const METRIC_NAME_INSPECT_COST_DURATION: &str = "auditor.inspect.cost.duration";
+const METRIC_NAME_INSPECT_COST_DURATION_BUCKET: &str = "auditor.inspect.cost.duration.bucket";
+const METRIC_NAME_INSPECT_COST_BYTES: &str = "auditor.inspect.cost.bytes";
+const METRIC_NAME_INSPECT_COST_BYTES_BUCKET: &str = "auditor.inspect.cost.bytes.bucket";
+
+type AuditorStatsValue = (Arc<AuditorStats>, AuditorSnapshot);
+
+static AUDITOR_STATS_MAP: LazyLock<Mutex<AHashMap<StatId, AuditorStatsValue>>> =
+    LazyLock::new(|| Mutex::new(AHashMap::new()));
+
+trait AuditorMetricExt {
+    fn add_auditor_tags(&mut self, auditor: &NodeName, stat_id: StatId);
+}
+
+impl AuditorMetricExt for StatsdTagGroup {
+    fn add_auditor_tags(&mut self, auditor: &NodeName, stat_id: StatId) {
+        let mut buffer = itoa::Buffer::new();
+        let stat_id = buffer.format(stat_id.as_u64());
+        self.add_tag(TAG_KEY_AUDITOR, auditor);
+        self.add_tag(TAG_KEY_STAT_ID, stat_id);
+        self.add_tenant_tag(TenantMemberType::Auditor, auditor);
+    }
+}
+
+pub(in crate::stat) fn sync_stats() {
+    let mut stats_map = AUDITOR_STATS_MAP.lock().unwrap();
+    crate::audit::foreach(|_, auditor| {
+        let stats = auditor.get_stats();
+        let stat_id = stats.stat_id();
+        stats_map
+            .entry(stat_id)
+            .or_insert_with(|| (stats, AuditorSnapshot::default()));
+    });
+}
+
+pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
+    let mut stats_map = AUDITOR_STATS_MAP.lock().unwrap();
+    stats_map.retain(|_, (stats, snap)| {
+        emit_to_statsd(client, stats, snap);
+        // use Arc instead of Weak here, as we should emit the final metrics before drop it
+        Arc::strong_count(stats) > 1
+    });
+}
+
+fn emit_to_statsd(client: &mut StatsdClient, stats: &AuditorStats, snap: &mut AuditorSnapshot) {
+    let mut common_tags = StatsdTagGroup::default();
+    common_tags.add_auditor_tags(stats.name(), stats.stat_id());
+
+    for (protocol, new_value) in stats.protocol_snapshot() {
+        let old_value = snap.protocol.entry(protocol).or_default();
+        let diff_value = new_value.wrapping_sub(*old_value);
+        client
+            .count_with_tags(METRIC_NAME_INSPECT_PROTOCOL, diff_value, &common_tags)
+            .with_tag(TAG_KEY_PROTOCOL, protocol)
+            .send();
+        *old_value = new_value;
+    }
+
+    for ((protocol, action), new_value) in stats.inspect_action_snapshot() {
+        let old_value = snap.inspect_action.entry((protocol, action)).or_default();
+        let diff_value = new_value.wrapping_sub(*old_value);
+        client
+            .count_with_tags(METRIC_NAME_INSPECT_ACTION, diff_value, &common_tags)
+            .with_tag(TAG_KEY_PROTOCOL, protocol)
+            .with_tag(TAG_KEY_ACTION, action)
+            .send();
+        *old_value = new_value;
+    }
+
+    stats.inspect_duration.foreach_stat(|_, quantile, v| {
+        client
+            .gauge_float_with_tags(METRIC_NAME_INSPECT_COST_DURATION, v, &common_tags)
+            .with_tag(TAG_KEY_QUANTILE, quantile)
+            .send();
+    });
+    stats.inspect_duration.foreach_bucket(|le, count| {
+        client
+            .gauge_with_tags(
+                METRIC_NAME_INSPECT_COST_DURATION_BUCKET,
+                count,
+                &common_tags,
+            )
+            .with_tag(TAG_KEY_BUCKET_LE, le)
+            .send();
+    });
+
+    stats.inspect_bytes.foreach_stat(|_, quantile, v| {
+        client
+            .gauge_float_with_tags(METRIC_NAME_INSPECT_COST_BYTES, v, &common_tags)
+            .with_tag(TAG_KEY_QUANTILE, quantile)
+            .send();
+    });
+    stats.inspect_bytes.foreach_bucket(|le, count| {
+        client
+            .gauge_with_tags(METRIC_NAME_INSPECT_COST_BYTES_BUCKET, count, &common_tags)
+            .with_tag(TAG_KEY_BUCKET_LE, le)
+            .send();
+    });
+}
@@ -0,0 +1,67 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Mutex;
+
+use g3_statsd_client::StatsdClient;
+
+const TAG_KEY_BIND_IP: &str = "bind_ip";
+
+const METRIC_NAME_FD_USED: &str = "capacity.fd.used";
+const METRIC_NAME_FD_LIMIT: &str = "capacity.fd.limit";
+const METRIC_NAME_PORT_USED: &str = "capacity.port.used";
+const METRIC_NAME_PORT_TOTAL: &str = "capacity.port.total";
+const METRIC_NAME_FLOW_COUNT: &str = "capacity.flow.count";
+const METRIC_NAME_FLOW_MAX: &str = "capacity.flow.max";
+const METRIC_NAME_SHED_CONNECTION: &str = "capacity.shed.connection";
+
+static SHED_CONNECTION_SNAPSHOT: Mutex<u64> = Mutex::new(0);
+
+pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
+    let Some(snapshot) = crate::capacity::snapshot() else {
+        return;
+    };
+
+    client.gauge(METRIC_NAME_FD_USED, snapshot.fd_used).send();
+    client.gauge(METRIC_NAME_FD_LIMIT, snapshot.fd_limit).send();
+
+    if snapshot.port_range_size > 0 {
+        client
+            .gauge(METRIC_NAME_PORT_TOTAL, snapshot.port_range_size)
+            .send();
+        for (ip, used) in &snapshot.port_used {
+            client
+                .gauge(METRIC_NAME_PORT_USED, *used)
+                .with_tag(TAG_KEY_BIND_IP, ip.to_string())
+                .send();
+        }
+    }
+
+    if let Some(count) = snapshot.flow_count {
+        client.gauge(METRIC_NAME_FLOW_COUNT, count).send();
+    }
+    if let Some(max) = snapshot.flow_max {
+        client.gauge(METRIC_NAME_FLOW_MAX, max).send();
+    }
+
+    let mut shed_snap = SHED_CONNECTION_SNAPSHOT.lock().unwrap();
+    let new_value = crate::capacity::shed_connection_total();
+    if new_value != 0 || *shed_snap != 0 {
+        let diff_value = new_value.wrapping_sub(*shed_snap);
+        client.count(METRIC_NAME_SHED_CONNECTION, diff_value).send();
+        *shed_snap = new_value;
+    }
+}
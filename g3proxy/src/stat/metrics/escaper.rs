@@ -15,6 +15,7 @@
  */
 
 use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Instant;
 
 use ahash::AHashMap;
 
@@ -25,9 +26,11 @@ use g3_statsd_client::{StatsdClient, StatsdTagGroup};
 use g3_types::metrics::NodeName;
 use g3_types::stats::{StatId, TcpIoSnapshot, UdpIoSnapshot};
 
-use super::TAG_KEY_ESCAPER;
+use super::{TenantMetricExt, TAG_KEY_ESCAPER};
+use crate::config::tenant::TenantMemberType;
 use crate::escape::{
-    ArcEscaperStats, EscaperForbiddenSnapshot, EscaperTcpConnectSnapshot, EscaperTlsSnapshot,
+    ArcEscaperStats, EscaperBandwidthSnapshot, EscaperForbiddenSnapshot, EscaperKeepaliveSnapshot,
+    EscaperStreamLimitSnapshot, EscaperTcpConnectSnapshot, EscaperTlsSnapshot,
     RouteEscaperSnapshot, RouteEscaperStats,
 };
 
@@ -47,9 +50,18 @@ const METRIC_NAME_ESCAPER_IO_IN_PACKETS: &str = "escaper.traffic.in.packets";
 const METRIC_NAME_ESCAPER_IO_OUT_BYTES: &str = "escaper.traffic.out.bytes";
 const METRIC_NAME_ESCAPER_IO_OUT_PACKETS: &str = "escaper.traffic.out.packets";
 const METRIC_NAME_ESCAPER_FORBIDDEN_IP_BLOCKED: &str = "escaper.forbidden.ip_blocked";
+const METRIC_NAME_ESCAPER_KEEPALIVE_REUSE: &str = "escaper.keepalive.reuse";
+const METRIC_NAME_ESCAPER_KEEPALIVE_REAP_EOF: &str = "escaper.keepalive.reap.eof";
+const METRIC_NAME_ESCAPER_KEEPALIVE_REAP_IDLE: &str = "escaper.keepalive.reap.idle";
+const METRIC_NAME_ESCAPER_KEEPALIVE_REAP_LIFETIME: &str = "escaper.keepalive.reap.lifetime";
+const METRIC_NAME_ESCAPER_BANDWIDTH_UTILIZATION: &str = "escaper.bandwidth.utilization";
 
 const METRIC_NAME_ROUTE_REQUEST_PASSED: &str = "route.request.passed";
 const METRIC_NAME_ROUTE_REQUEST_FAILED: &str = "route.request.failed";
+const METRIC_NAME_ROUTE_EDGE_SELECTED: &str = "route.edge.selected";
+
+const TAG_KEY_NEXT_ESCAPER: &str = "next_escaper";
+const TAG_KEY_DIRECTION: &str = "direction";
 
 type EscaperStatsValue = (ArcEscaperStats, EscaperSnapshot);
 type RouterStatsValue = (Arc<RouteEscaperStats>, RouteEscaperSnapshot);
@@ -58,6 +70,8 @@ static ESCAPER_STATS_MAP: LazyLock<Mutex<AHashMap<StatId, EscaperStatsValue>>> =
     LazyLock::new(|| Mutex::new(AHashMap::new()));
 static ROUTE_STATS_MAP: LazyLock<Mutex<AHashMap<StatId, RouterStatsValue>>> =
     LazyLock::new(|| Mutex::new(AHashMap::new()));
+static ROUTE_EDGE_SNAPSHOT_MAP: LazyLock<Mutex<AHashMap<(NodeName, NodeName), u64>>> =
+    LazyLock::new(|| Mutex::new(AHashMap::new()));
 
 trait EscaperMetricExt {
     fn add_escaper_tags(&mut self, escaper: &NodeName, stat_id: StatId);
@@ -69,6 +83,7 @@ impl EscaperMetricExt for StatsdTagGroup {
         let stat_id = buffer.format(stat_id.as_u64());
         self.add_tag(TAG_KEY_ESCAPER, escaper);
         self.add_tag(TAG_KEY_STAT_ID, stat_id);
+        self.add_tenant_tag(TenantMemberType::Escaper, escaper);
     }
 }
 
@@ -82,6 +97,15 @@ struct EscaperSnapshot {
     tcp: TcpIoSnapshot,
     udp: UdpIoSnapshot,
     forbidden: EscaperForbiddenSnapshot,
+    keepalive: EscaperKeepaliveSnapshot,
+    bandwidth: BandwidthSnapshot,
+}
+
+#[derive(Default)]
+struct BandwidthSnapshot {
+    time: Option<Instant>,
+    upload: u64,
+    download: u64,
 }
 
 pub(in crate::stat) fn sync_stats() {
@@ -124,6 +148,8 @@ pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
         Arc::strong_count(stats) > 1
     });
     drop(route_stats_map);
+
+    emit_route_edge_stats(client);
 }
 
 fn emit_escaper_stats(
@@ -170,6 +196,10 @@ fn emit_escaper_stats(
         emit_forbidden_stats(client, forbidden_stats, &mut snap.forbidden, &common_tags);
     }
 
+    if let Some(keepalive_stats) = stats.keepalive_snapshot() {
+        emit_keepalive_stats(client, keepalive_stats, &mut snap.keepalive, &common_tags);
+    }
+
     if let Some(tcp_io_stats) = stats.tcp_io_snapshot() {
         emit_tcp_io_to_statsd(client, tcp_io_stats, &mut snap.tcp, &common_tags);
     }
@@ -177,6 +207,53 @@ fn emit_escaper_stats(
     if let Some(udp_io_stats) = stats.udp_io_snapshot() {
         emit_udp_io_to_statsd(client, udp_io_stats, &mut snap.udp, &common_tags);
     }
+
+    if let Some(bandwidth_stats) = stats.bandwidth_snapshot() {
+        emit_bandwidth_stats(client, bandwidth_stats, &mut snap.bandwidth, &common_tags);
+    }
+}
+
+fn emit_bandwidth_stats(
+    client: &mut StatsdClient,
+    stats: EscaperBandwidthSnapshot,
+    snap: &mut BandwidthSnapshot,
+    common_tags: &StatsdTagGroup,
+) {
+    let now = Instant::now();
+    let Some(last_time) = snap.time.replace(now) else {
+        // no utilization for the first time, just save the snapshot
+        snap.upload = stats.upload.map(|s| s.passed_bytes).unwrap_or_default();
+        snap.download = stats.download.map(|s| s.passed_bytes).unwrap_or_default();
+        return;
+    };
+    let elapsed = now.duration_since(last_time).as_secs_f64();
+
+    let mut emit_direction =
+        |stats: Option<EscaperStreamLimitSnapshot>, snap_value: &mut u64, direction: &str| {
+            let Some(stats) = stats else {
+                *snap_value = 0;
+                return;
+            };
+            let diff_value = stats.passed_bytes.wrapping_sub(*snap_value);
+            *snap_value = stats.passed_bytes;
+
+            let capacity = stats.bytes_per_second * elapsed;
+            if capacity <= 0.0 {
+                return;
+            }
+            let utilization = (diff_value as f64 * 100.0 / capacity).min(100.0);
+            client
+                .gauge_float_with_tags(
+                    METRIC_NAME_ESCAPER_BANDWIDTH_UTILIZATION,
+                    utilization,
+                    common_tags,
+                )
+                .with_tag(TAG_KEY_DIRECTION, direction)
+                .send();
+        };
+
+    emit_direction(stats.upload, &mut snap.upload, "upload");
+    emit_direction(stats.download, &mut snap.download, "download");
 }
 
 fn emit_tcp_connect_stats(
@@ -249,6 +326,31 @@ fn emit_forbidden_stats(
     }
 }
 
+fn emit_keepalive_stats(
+    client: &mut StatsdClient,
+    stats: EscaperKeepaliveSnapshot,
+    snap: &mut EscaperKeepaliveSnapshot,
+    common_tags: &StatsdTagGroup,
+) {
+    macro_rules! emit_optional_field {
+        ($field:ident, $name:expr) => {
+            let new_value = stats.$field;
+            if new_value != 0 || snap.$field != 0 {
+                let diff_value = new_value.wrapping_sub(snap.$field);
+                client
+                    .count_with_tags($name, diff_value, common_tags)
+                    .send();
+                snap.$field = new_value;
+            }
+        };
+    }
+
+    emit_optional_field!(reuse, METRIC_NAME_ESCAPER_KEEPALIVE_REUSE);
+    emit_optional_field!(reap_eof, METRIC_NAME_ESCAPER_KEEPALIVE_REAP_EOF);
+    emit_optional_field!(reap_idle, METRIC_NAME_ESCAPER_KEEPALIVE_REAP_IDLE);
+    emit_optional_field!(reap_lifetime, METRIC_NAME_ESCAPER_KEEPALIVE_REAP_LIFETIME);
+}
+
 fn emit_tcp_io_to_statsd(
     client: &mut StatsdClient,
     stats: TcpIoSnapshot,
@@ -329,3 +431,24 @@ fn emit_route_stats(
         snap.request_failed = new_value;
     }
 }
+
+fn emit_route_edge_stats(client: &mut StatsdClient) {
+    let mut old_snapshot_map = ROUTE_EDGE_SNAPSHOT_MAP.lock().unwrap();
+    let mut new_snapshot_map = AHashMap::with_capacity(old_snapshot_map.len());
+    crate::escape::foreach_route_edge(|escaper, next, new_value| {
+        let key = (escaper.clone(), next.clone());
+        let old_value = old_snapshot_map.remove(&key).unwrap_or_default();
+        let diff_value = new_value.wrapping_sub(old_value);
+
+        let mut tags = StatsdTagGroup::default();
+        tags.add_tag(TAG_KEY_ESCAPER, escaper);
+        tags.add_tag(TAG_KEY_NEXT_ESCAPER, next);
+        tags.add_tenant_tag(TenantMemberType::Escaper, escaper);
+        client
+            .count_with_tags(METRIC_NAME_ROUTE_EDGE_SELECTED, diff_value, &tags)
+            .send();
+
+        new_snapshot_map.insert(key, new_value);
+    });
+    *old_snapshot_map = new_snapshot_map;
+}
@@ -14,6 +14,13 @@
  * limitations under the License.
  */
 
+use g3_statsd_client::StatsdTagGroup;
+use g3_types::metrics::NodeName;
+
+use crate::config::tenant::TenantMemberType;
+
+pub(super) mod auditor;
+pub(super) mod capacity;
 pub(super) mod escaper;
 pub(super) mod resolver;
 pub(super) mod server;
@@ -24,6 +31,19 @@ use user::{RequestStatsNamesRef, TrafficStatsNamesRef, UserMetricExt};
 pub(crate) mod user_site;
 
 const TAG_KEY_ESCAPER: &str = "escaper";
+const TAG_KEY_TENANT: &str = "tenant";
+
+trait TenantMetricExt {
+    fn add_tenant_tag(&mut self, member_type: TenantMemberType, name: &NodeName);
+}
+
+impl TenantMetricExt for StatsdTagGroup {
+    fn add_tenant_tag(&mut self, member_type: TenantMemberType, name: &NodeName) {
+        if let Some(tenant) = crate::config::tenant::get_tenant_of(member_type, name) {
+            self.add_tag(TAG_KEY_TENANT, tenant);
+        }
+    }
+}
 
 #[derive(Copy, Clone)]
 enum MetricUserConnectionType {
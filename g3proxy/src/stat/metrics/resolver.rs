@@ -26,6 +26,8 @@ use g3_statsd_client::{StatsdClient, StatsdTagGroup};
 use g3_types::metrics::NodeName;
 use g3_types::stats::StatId;
 
+use super::TenantMetricExt;
+use crate::config::tenant::TenantMemberType;
 use crate::resolve::ResolverStats;
 
 const TAG_KEY_RESOLVER: &str = "resolver";
@@ -34,6 +36,8 @@ const TAG_KEY_RR_TYPE: &str = "rr_type";
 const METRIC_NAME_QUERY_TOTAL: &str = "resolver.query.total";
 const METRIC_NAME_QUERY_CACHED: &str = "resolver.query.cached";
 const METRIC_NAME_QUERY_DRIVER: &str = "resolver.query.driver.total";
+const METRIC_NAME_QUERY_DRIVER_RESPONDED: &str = "resolver.query.driver.responded";
+const METRIC_NAME_QUERY_DRIVER_LATENCY: &str = "resolver.query.driver.latency";
 const METRIC_NAME_QUERY_DRIVER_TIMEOUT: &str = "resolver.query.driver.timeout";
 const METRIC_NAME_QUERY_DRIVER_REFUSED: &str = "resolver.query.driver.refused";
 const METRIC_NAME_QUERY_DRIVER_MALFORMED: &str = "resolver.query.driver.malformed";
@@ -61,6 +65,7 @@ impl ResolverMetricExt for StatsdTagGroup {
         let stat_id = buffer.format(stat_id.as_u64());
         self.add_tag(TAG_KEY_RESOLVER, resolver);
         self.add_tag(TAG_KEY_STAT_ID, stat_id);
+        self.add_tenant_tag(TenantMemberType::Resolver, resolver);
     }
 }
 
@@ -158,6 +163,26 @@ fn emit_query_stats_to_statsd(
 
     emit_query_stats_u64!(cached, METRIC_NAME_QUERY_CACHED);
     emit_query_stats_u64!(driver, METRIC_NAME_QUERY_DRIVER);
+
+    let new_value = stats.driver_responded;
+    if new_value != 0 || snap.driver_responded != 0 {
+        let diff_value = new_value.wrapping_sub(snap.driver_responded);
+        client
+            .count_with_tags(METRIC_NAME_QUERY_DRIVER_RESPONDED, diff_value, common_tags)
+            .with_tag(TAG_KEY_RR_TYPE, rr_type)
+            .send();
+        if diff_value > 0 {
+            // the average latency in microseconds for this emit interval
+            let latency = stats.driver_latency_us.wrapping_sub(snap.driver_latency_us) / diff_value;
+            client
+                .gauge_with_tags(METRIC_NAME_QUERY_DRIVER_LATENCY, latency, common_tags)
+                .with_tag(TAG_KEY_RR_TYPE, rr_type)
+                .send();
+        }
+        snap.driver_responded = new_value;
+        snap.driver_latency_us = stats.driver_latency_us;
+    }
+
     emit_query_stats_u64!(driver_timeout, METRIC_NAME_QUERY_DRIVER_TIMEOUT);
     emit_query_stats_u64!(driver_refused, METRIC_NAME_QUERY_DRIVER_REFUSED);
     emit_query_stats_u64!(driver_malformed, METRIC_NAME_QUERY_DRIVER_MALFORMED);
@@ -25,6 +25,8 @@ use g3_daemon::metrics::{
 use g3_statsd_client::{StatsdClient, StatsdTagGroup};
 use g3_types::stats::{StatId, TcpIoSnapshot, UdpIoSnapshot};
 
+use super::TenantMetricExt;
+use crate::config::tenant::TenantMemberType;
 use crate::serve::{ArcServerStats, ServerForbiddenSnapshot};
 use crate::stat::types::UntrustedTaskStatsSnapshot;
 
@@ -103,6 +105,7 @@ pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
 fn emit_server_stats(client: &mut StatsdClient, stats: &ArcServerStats, snap: &mut ServerSnapshot) {
     let mut common_tags = StatsdTagGroup::default();
     common_tags.add_server_tags(stats.name(), stats.is_online(), stats.stat_id());
+    common_tags.add_tenant_tag(TenantMemberType::Server, stats.name());
     if let Some(tags) = stats.load_extra_tags() {
         common_tags.add_static_tags(&tags);
     }
@@ -23,15 +23,16 @@ use g3_daemon::metrics::{
     TAG_KEY_TRANSPORT,
 };
 use g3_statsd_client::{StatsdClient, StatsdTagGroup};
-use g3_types::metrics::NodeName;
+use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::stats::{StatId, TcpIoSnapshot, UdpIoSnapshot};
 
 use super::TAG_KEY_ESCAPER;
-use super::{MetricUserConnectionType, MetricUserRequestType};
+use super::{MetricUserConnectionType, MetricUserRequestType, TenantMetricExt};
 use crate::auth::{
     User, UserForbiddenSnapshot, UserForbiddenStats, UserRequestSnapshot, UserRequestStats,
     UserTrafficSnapshot, UserTrafficStats, UserUpstreamTrafficSnapshot, UserUpstreamTrafficStats,
 };
+use crate::config::tenant::TenantMemberType;
 use crate::stat::types::{
     ConnectionSnapshot, ConnectionStats, KeepaliveRequestSnapshot, KeepaliveRequestStats,
     L7ConnectionAliveStats, RequestAliveStats, RequestSnapshot, RequestStats, TrafficSnapshot,
@@ -145,6 +146,7 @@ impl UserMetricExt for StatsdTagGroup {
         self.add_tag(TAG_KEY_USER_TYPE, user_type);
         self.add_tag(TAG_KEY_STAT_ID, stat_id);
         self.add_tag(TAG_KEY_SERVER, server);
+        self.add_tenant_tag(TenantMemberType::UserGroup, user_group);
     }
 
     fn add_user_upstream_traffic_tags(
@@ -162,6 +164,7 @@ impl UserMetricExt for StatsdTagGroup {
         self.add_tag(TAG_KEY_USER_TYPE, user_type);
         self.add_tag(TAG_KEY_STAT_ID, stat_id);
         self.add_tag(TAG_KEY_ESCAPER, escaper);
+        self.add_tenant_tag(TenantMemberType::UserGroup, user_group);
     }
 }
 
@@ -236,7 +239,7 @@ pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
 
     let mut req_stats_map = USER_REQUEST_STATS_MAP.lock().unwrap();
     req_stats_map.retain(|_, (stats, snap)| {
-        emit_user_request_stats(client, stats, snap, &REQUEST_STATS_NAMES);
+        emit_user_request_stats(client, stats, snap, &REQUEST_STATS_NAMES, None);
         // use Arc instead of Weak here, as we should emit the final metrics before drop it
         Arc::strong_count(stats) > 1
     });
@@ -244,7 +247,7 @@ pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
 
     let mut io_stats_map = USER_TRAFFIC_STATS_MAP.lock().unwrap();
     io_stats_map.retain(|_, (stats, snap)| {
-        emit_user_traffic_stats(client, stats, snap, &TRAFFIC_STATS_NAMES);
+        emit_user_traffic_stats(client, stats, snap, &TRAFFIC_STATS_NAMES, None);
         // use Arc instead of Weak here, as we should emit the final metrics before drop it
         Arc::strong_count(stats) > 1
     });
@@ -252,7 +255,7 @@ pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
 
     let mut upstream_io_stats_map = USER_UPSTREAM_TRAFFIC_STATS_MAP.lock().unwrap();
     upstream_io_stats_map.retain(|_, (stats, snap)| {
-        emit_user_upstream_traffic_stats(client, stats, snap, &UPSTREAM_TRAFFIC_STATS_NAMES);
+        emit_user_upstream_traffic_stats(client, stats, snap, &UPSTREAM_TRAFFIC_STATS_NAMES, None);
         // use Arc instead of Weak here, as we should emit the final metrics before drop it
         Arc::strong_count(stats) > 1
     });
@@ -309,6 +312,7 @@ pub(super) fn emit_user_request_stats<'a>(
     stats: &'a UserRequestStats,
     snap: &'a mut UserRequestSnapshot,
     names: &'a RequestStatsNamesRef<'a>,
+    request_tags: Option<&'a StaticMetricsTags>,
 ) {
     let mut common_tags = StatsdTagGroup::default();
     common_tags.add_user_request_tags(
@@ -321,6 +325,9 @@ pub(super) fn emit_user_request_stats<'a>(
     if let Some(server_extra_tags) = stats.server_extra_tags() {
         common_tags.add_static_tags(&server_extra_tags);
     }
+    if let Some(request_tags) = request_tags {
+        common_tags.add_static_tags(request_tags);
+    }
 
     find_conn_stat(
         &stats.conn_total,
@@ -381,6 +388,7 @@ pub(super) fn emit_user_traffic_stats<'a>(
     stats: &'a UserTrafficStats,
     snap: &'a mut UserTrafficSnapshot,
     names: &'a TrafficStatsNamesRef<'a>,
+    request_tags: Option<&'a StaticMetricsTags>,
 ) {
     let mut common_tags = StatsdTagGroup::default();
     common_tags.add_user_request_tags(
@@ -393,6 +401,9 @@ pub(super) fn emit_user_traffic_stats<'a>(
     if let Some(server_extra_tags) = stats.server_extra_tags() {
         common_tags.add_static_tags(&server_extra_tags);
     }
+    if let Some(request_tags) = request_tags {
+        common_tags.add_static_tags(request_tags);
+    }
 
     find_io_stat(&stats.io, &mut snap.io, names, |key, value, req_type| {
         client
@@ -407,6 +418,7 @@ pub(super) fn emit_user_upstream_traffic_stats<'a>(
     stats: &'a UserUpstreamTrafficStats,
     snap: &'a mut UserUpstreamTrafficSnapshot,
     names: &'a TrafficStatsNamesRef<'a>,
+    request_tags: Option<&'a StaticMetricsTags>,
 ) {
     let mut common_tags = StatsdTagGroup::default();
     common_tags.add_user_upstream_traffic_tags(
@@ -419,6 +431,9 @@ pub(super) fn emit_user_upstream_traffic_stats<'a>(
     if let Some(escaper_extra_tags) = stats.escaper_extra_tags() {
         common_tags.add_static_tags(&escaper_extra_tags);
     }
+    if let Some(request_tags) = request_tags {
+        common_tags.add_static_tags(request_tags);
+    }
 
     find_ups_io_stat(&stats.io, &mut snap.io, names, |key, value, trans_type| {
         client
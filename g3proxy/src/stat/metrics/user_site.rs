@@ -17,10 +17,11 @@
 use std::sync::{Arc, LazyLock, Mutex};
 
 use ahash::AHashMap;
+use arc_swap::ArcSwapOption;
 
-use g3_daemon::metrics::TAG_KEY_QUANTILE;
+use g3_daemon::metrics::{TAG_KEY_BUCKET_LE, TAG_KEY_QUANTILE};
 use g3_statsd_client::{StatsdClient, StatsdTagGroup};
-use g3_types::metrics::NodeName;
+use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::stats::StatId;
 
 use super::{RequestStatsNamesRef, TrafficStatsNamesRef, UserMetricExt};
@@ -102,12 +103,14 @@ impl TrafficStatsNames {
 
 struct DurationStatsNames {
     task_ready: String,
+    task_ready_bucket: String,
 }
 
 impl DurationStatsNames {
     fn new_for_client(site_id: &NodeName) -> Self {
         DurationStatsNames {
             task_ready: format!("user.site.{site_id}.task.ready.duration"),
+            task_ready_bucket: format!("user.site.{site_id}.task.ready.duration.bucket"),
         }
     }
 }
@@ -116,14 +119,20 @@ struct RequestStatsValue {
     stats: Arc<UserRequestStats>,
     snap: UserRequestSnapshot,
     names: RequestStatsNames,
+    request_tags: Arc<ArcSwapOption<StaticMetricsTags>>,
 }
 
 impl RequestStatsValue {
-    fn new(stats: Arc<UserRequestStats>, site_id: &NodeName) -> Self {
+    fn new(
+        stats: Arc<UserRequestStats>,
+        site_id: &NodeName,
+        request_tags: &Arc<ArcSwapOption<StaticMetricsTags>>,
+    ) -> Self {
         RequestStatsValue {
             stats,
             snap: Default::default(),
             names: RequestStatsNames::new(site_id),
+            request_tags: request_tags.clone(),
         }
     }
 }
@@ -132,14 +141,20 @@ struct TrafficStatsValue {
     stats: Arc<UserTrafficStats>,
     snap: UserTrafficSnapshot,
     names: TrafficStatsNames,
+    request_tags: Arc<ArcSwapOption<StaticMetricsTags>>,
 }
 
 impl TrafficStatsValue {
-    fn new(stats: Arc<UserTrafficStats>, site_id: &NodeName) -> Self {
+    fn new(
+        stats: Arc<UserTrafficStats>,
+        site_id: &NodeName,
+        request_tags: &Arc<ArcSwapOption<StaticMetricsTags>>,
+    ) -> Self {
         TrafficStatsValue {
             stats,
             snap: Default::default(),
             names: TrafficStatsNames::new_for_client(site_id),
+            request_tags: request_tags.clone(),
         }
     }
 }
@@ -147,13 +162,19 @@ impl TrafficStatsValue {
 struct DurationStatsValue {
     stats: Arc<UserSiteDurationStats>,
     names: DurationStatsNames,
+    request_tags: Arc<ArcSwapOption<StaticMetricsTags>>,
 }
 
 impl DurationStatsValue {
-    fn new(stats: Arc<UserSiteDurationStats>, site_id: &NodeName) -> Self {
+    fn new(
+        stats: Arc<UserSiteDurationStats>,
+        site_id: &NodeName,
+        request_tags: &Arc<ArcSwapOption<StaticMetricsTags>>,
+    ) -> Self {
         DurationStatsValue {
             stats,
             names: DurationStatsNames::new_for_client(site_id),
+            request_tags: request_tags.clone(),
         }
     }
 }
@@ -162,35 +183,53 @@ struct UpstreamTrafficStatsValue {
     stats: Arc<UserUpstreamTrafficStats>,
     snap: UserUpstreamTrafficSnapshot,
     tags: TrafficStatsNames,
+    request_tags: Arc<ArcSwapOption<StaticMetricsTags>>,
 }
 
 impl UpstreamTrafficStatsValue {
-    fn new(stats: Arc<UserUpstreamTrafficStats>, site_id: &NodeName) -> Self {
+    fn new(
+        stats: Arc<UserUpstreamTrafficStats>,
+        site_id: &NodeName,
+        request_tags: &Arc<ArcSwapOption<StaticMetricsTags>>,
+    ) -> Self {
         UpstreamTrafficStatsValue {
             stats,
             snap: Default::default(),
             tags: TrafficStatsNames::new_for_upstream(site_id),
+            request_tags: request_tags.clone(),
         }
     }
 }
 
-pub(crate) fn push_request_stats(stats: Arc<UserRequestStats>, site_id: &NodeName) {
+pub(crate) fn push_request_stats(
+    stats: Arc<UserRequestStats>,
+    site_id: &NodeName,
+    request_tags: &Arc<ArcSwapOption<StaticMetricsTags>>,
+) {
     let k = stats.stat_id();
-    let v = RequestStatsValue::new(stats, site_id);
+    let v = RequestStatsValue::new(stats, site_id, request_tags);
     let mut ht = STORE_REQUEST_STATS_MAP.lock().unwrap();
     ht.insert(k, v);
 }
 
-pub(crate) fn push_traffic_stats(stats: Arc<UserTrafficStats>, site_id: &NodeName) {
+pub(crate) fn push_traffic_stats(
+    stats: Arc<UserTrafficStats>,
+    site_id: &NodeName,
+    request_tags: &Arc<ArcSwapOption<StaticMetricsTags>>,
+) {
     let k = stats.stat_id();
-    let v = TrafficStatsValue::new(stats, site_id);
+    let v = TrafficStatsValue::new(stats, site_id, request_tags);
     let mut ht = STORE_TRAFFIC_STATS_MAP.lock().unwrap();
     ht.insert(k, v);
 }
 
-pub(crate) fn push_duration_stats(stats: Arc<UserSiteDurationStats>, site_id: &NodeName) {
+pub(crate) fn push_duration_stats(
+    stats: Arc<UserSiteDurationStats>,
+    site_id: &NodeName,
+    request_tags: &Arc<ArcSwapOption<StaticMetricsTags>>,
+) {
     let k = stats.stat_id();
-    let v = DurationStatsValue::new(stats, site_id);
+    let v = DurationStatsValue::new(stats, site_id, request_tags);
     let mut ht = STORE_DURATION_STATS_MAP.lock().unwrap();
     ht.insert(k, v);
 }
@@ -198,9 +237,10 @@ pub(crate) fn push_duration_stats(stats: Arc<UserSiteDurationStats>, site_id: &N
 pub(crate) fn push_upstream_traffic_stats(
     stats: Arc<UserUpstreamTrafficStats>,
     site_id: &NodeName,
+    request_tags: &Arc<ArcSwapOption<StaticMetricsTags>>,
 ) {
     let k = stats.stat_id();
-    let v = UpstreamTrafficStatsValue::new(stats, site_id);
+    let v = UpstreamTrafficStatsValue::new(stats, site_id, request_tags);
     let mut ht = STORE_UPSTREAM_TRAFFIC_STATS_MAP.lock().unwrap();
     ht.insert(k, v);
 }
@@ -229,7 +269,14 @@ pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
             request_renew: &v.names.request_renew,
             l7_connection_alive: &v.names.l7_connection_alive,
         };
-        super::user::emit_user_request_stats(client, &v.stats, &mut v.snap, &names);
+        let request_tags = v.request_tags.load();
+        super::user::emit_user_request_stats(
+            client,
+            &v.stats,
+            &mut v.snap,
+            &names,
+            request_tags.as_deref(),
+        );
         // use Arc instead of Weak here, as we should emit the final metrics before drop it
         Arc::strong_count(&v.stats) > 1
     });
@@ -243,7 +290,14 @@ pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
             out_bytes: &v.names.out_bytes,
             out_packets: &v.names.out_packets,
         };
-        super::user::emit_user_traffic_stats(client, &v.stats, &mut v.snap, &names);
+        let request_tags = v.request_tags.load();
+        super::user::emit_user_traffic_stats(
+            client,
+            &v.stats,
+            &mut v.snap,
+            &names,
+            request_tags.as_deref(),
+        );
         // use Arc instead of Weak here, as we should emit the final metrics before drop it
         Arc::strong_count(&v.stats) > 1
     });
@@ -251,7 +305,8 @@ pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
 
     let mut dur_stats_map = USER_SITE_DURATION_STATS_MAP.lock().unwrap();
     dur_stats_map.retain(|_, v| {
-        emit_site_duration_stats(client, &v.stats, &v.names);
+        let request_tags = v.request_tags.load();
+        emit_site_duration_stats(client, &v.stats, &v.names, request_tags.as_deref());
         // use Arc instead of Weak here, as we should emit the final metrics before drop it
         Arc::strong_count(&v.stats) > 1
     });
@@ -265,7 +320,14 @@ pub(in crate::stat) fn emit_stats(client: &mut StatsdClient) {
             out_bytes: &v.tags.out_bytes,
             out_packets: &v.tags.out_packets,
         };
-        super::user::emit_user_upstream_traffic_stats(client, &v.stats, &mut v.snap, &names);
+        let request_tags = v.request_tags.load();
+        super::user::emit_user_upstream_traffic_stats(
+            client,
+            &v.stats,
+            &mut v.snap,
+            &names,
+            request_tags.as_deref(),
+        );
         // use Arc instead of Weak here, as we should emit the final metrics before drop it
         Arc::strong_count(&v.stats) > 1
     });
@@ -276,6 +338,7 @@ fn emit_site_duration_stats<'a>(
     client: &'a mut StatsdClient,
     stats: &'a UserSiteDurationStats,
     names: &'a DurationStatsNames,
+    request_tags: Option<&'a StaticMetricsTags>,
 ) {
     let mut common_tags = StatsdTagGroup::default();
     common_tags.add_user_request_tags(
@@ -288,6 +351,9 @@ fn emit_site_duration_stats<'a>(
     if let Some(server_extra_tags) = stats.server_extra_tags() {
         common_tags.add_static_tags(&server_extra_tags);
     }
+    if let Some(request_tags) = request_tags {
+        common_tags.add_static_tags(request_tags);
+    }
 
     stats.task_ready.foreach_stat(|_, quantile, v| {
         client
@@ -295,4 +361,10 @@ fn emit_site_duration_stats<'a>(
             .with_tag(TAG_KEY_QUANTILE, quantile)
             .send();
     });
+    stats.task_ready.foreach_bucket(|le, count| {
+        client
+            .gauge_with_tags(&names.task_ready_bucket, count, &common_tags)
+            .with_tag(TAG_KEY_BUCKET_LE, le)
+            .send();
+    });
 }
@@ -221,4 +221,14 @@ impl RequestAliveStats {
     pub(crate) fn socks_udp_associate(&self) -> i32 {
         self.socks_udp_associate.load(Ordering::Relaxed)
     }
+
+    pub(crate) fn total(&self) -> i32 {
+        self.http_forward()
+            + self.https_forward()
+            + self.http_connect()
+            + self.ftp_over_http()
+            + self.socks_tcp_connect()
+            + self.socks_udp_connect()
+            + self.socks_udp_associate()
+    }
 }
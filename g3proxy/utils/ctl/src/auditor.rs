@@ -0,0 +1,143 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::{Arg, ArgMatches, Command};
+use futures_util::future::TryFutureExt;
+use serde_json::json;
+
+use g3_ctl::CommandResult;
+
+use g3proxy_proto::auditor_capnp::auditor_control;
+use g3proxy_proto::proc_capnp::proc_control;
+
+use crate::common::{parse_fetch_result, parse_operation_result};
+
+pub const COMMAND: &str = "auditor";
+
+const COMMAND_ARG_NAME: &str = "name";
+
+const SUBCOMMAND_FLUSH_ICAP_VERDICT_CACHE: &str = "flush-icap-verdict-cache";
+const SUBCOMMAND_ICAP_VERDICT_CACHE_STATS: &str = "icap-verdict-cache-stats";
+const SUBCOMMAND_ICAP_POOL_STATS: &str = "icap-pool-stats";
+
+const SUBCOMMAND_ARG_SERVICE: &str = "service";
+
+pub fn command() -> Command {
+    Command::new(COMMAND)
+        .arg(Arg::new(COMMAND_ARG_NAME).required(true).num_args(1))
+        .subcommand_required(true)
+        .subcommand(
+            Command::new(SUBCOMMAND_FLUSH_ICAP_VERDICT_CACHE)
+                .about("Drop all cached icap respmod verdicts"),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_ICAP_VERDICT_CACHE_STATS)
+                .about("Show stats of the icap respmod verdict cache"),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_ICAP_POOL_STATS)
+                .about("Show stats of the icap connection pool")
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_SERVICE)
+                        .help("The icap service")
+                        .required(true)
+                        .num_args(1)
+                        .value_parser(["reqmod", "respmod"]),
+                ),
+        )
+}
+
+async fn flush_icap_verdict_cache(client: &auditor_control::Client) -> CommandResult<()> {
+    let req = client.flush_icap_verdict_cache_request();
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
+async fn icap_verdict_cache_stats(client: &auditor_control::Client) -> CommandResult<()> {
+    let req = client.get_icap_verdict_cache_stats_request();
+    let rsp = req.send().promise.await?;
+    let stats = parse_fetch_result(rsp.get()?.get_stats()?)?;
+    if g3_ctl::is_json_output() {
+        g3_ctl::print_json(&json!({
+            "entries": stats.get_entries(),
+            "hit_clean": stats.get_hit_clean(),
+            "hit_blocked": stats.get_hit_blocked(),
+            "miss": stats.get_miss(),
+            "insert": stats.get_insert(),
+            "expire": stats.get_expire(),
+        }));
+    } else {
+        println!("entries: {}", stats.get_entries());
+        println!("hit clean: {}", stats.get_hit_clean());
+        println!("hit blocked: {}", stats.get_hit_blocked());
+        println!("miss: {}", stats.get_miss());
+        println!("insert: {}", stats.get_insert());
+        println!("expire: {}", stats.get_expire());
+    }
+    Ok(())
+}
+
+async fn icap_pool_stats(client: &auditor_control::Client, service: &str) -> CommandResult<()> {
+    let mut req = client.get_icap_connection_pool_stats_request();
+    req.get().set_service(service);
+    let rsp = req.send().promise.await?;
+    let stats = parse_fetch_result(rsp.get()?.get_stats()?)?;
+    if g3_ctl::is_json_output() {
+        g3_ctl::print_json(&json!({
+            "idle": stats.get_idle(),
+            "created": stats.get_created(),
+            "reused": stats.get_reused(),
+            "lifetime_expired": stats.get_lifetime_expired(),
+            "health_check_passed": stats.get_health_check_passed(),
+            "health_check_failed": stats.get_health_check_failed(),
+            "shared": stats.get_shared(),
+        }));
+    } else {
+        println!("idle: {}", stats.get_idle());
+        println!("created: {}", stats.get_created());
+        println!("reused: {}", stats.get_reused());
+        println!("lifetime expired: {}", stats.get_lifetime_expired());
+        println!("health check passed: {}", stats.get_health_check_passed());
+        println!("health check failed: {}", stats.get_health_check_failed());
+        println!("shared: {}", stats.get_shared());
+    }
+    Ok(())
+}
+
+pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let name = args.get_one::<String>(COMMAND_ARG_NAME).unwrap();
+
+    let (subcommand, sub_args) = args.subcommand().unwrap();
+    match subcommand {
+        SUBCOMMAND_FLUSH_ICAP_VERDICT_CACHE => {
+            super::proc::get_auditor(client, name)
+                .and_then(|auditor| async move { flush_icap_verdict_cache(&auditor).await })
+                .await
+        }
+        SUBCOMMAND_ICAP_VERDICT_CACHE_STATS => {
+            super::proc::get_auditor(client, name)
+                .and_then(|auditor| async move { icap_verdict_cache_stats(&auditor).await })
+                .await
+        }
+        SUBCOMMAND_ICAP_POOL_STATS => {
+            let service = sub_args.get_one::<String>(SUBCOMMAND_ARG_SERVICE).unwrap();
+            super::proc::get_auditor(client, name)
+                .and_then(|auditor| async move { icap_pool_stats(&auditor, service).await })
+                .await
+        }
+        _ => unreachable!(),
+    }
+}
@@ -36,6 +36,8 @@ const SUBCOMMAND_PUBLISH: &str = "publish";
 const SUBCOMMAND_PUBLISH_ARG_FILE: &str = "file";
 const SUBCOMMAND_PUBLISH_ARG_DATA: &str = "data";
 
+const SUBCOMMAND_EGRESS_NAT: &str = "egress-nat";
+
 pub fn command() -> Command {
     Command::new(COMMAND)
         .arg(Arg::new(COMMAND_ARG_NAME).required(true).num_args(1))
@@ -61,6 +63,7 @@ pub fn command() -> Command {
                         .conflicts_with(SUBCOMMAND_PUBLISH_ARG_FILE),
                 ),
         )
+        .subcommand(Command::new(SUBCOMMAND_EGRESS_NAT).about("List egress nat ip mapping"))
 }
 
 async fn publish(client: &escaper_control::Client, args: &ArgMatches) -> CommandResult<()> {
@@ -89,6 +92,12 @@ async fn publish(client: &escaper_control::Client, args: &ArgMatches) -> Command
     parse_operation_result(rsp.get()?.get_result()?)
 }
 
+async fn list_egress_nat(client: &escaper_control::Client) -> CommandResult<()> {
+    let req = client.list_egress_nat_request();
+    let rsp = req.send().promise.await?;
+    g3_ctl::print_result_list(rsp.get()?.get_result()?)
+}
+
 pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
     let name = args.get_one::<String>(COMMAND_ARG_NAME).unwrap();
 
@@ -99,6 +108,11 @@ pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandRes
                 .and_then(|escaper| async move { publish(&escaper, args).await })
                 .await
         }
+        SUBCOMMAND_EGRESS_NAT => {
+            super::proc::get_escaper(client, name)
+                .and_then(|escaper| async move { list_egress_nat(&escaper).await })
+                .await
+        }
         _ => unreachable!(),
     }
 }
@@ -14,19 +14,23 @@
  * limitations under the License.
  */
 
+use std::process::ExitCode;
+
 use anyhow::anyhow;
-use clap::Command;
+use clap::{ArgMatches, Command};
 
-use g3_ctl::{CommandError, DaemonCtlArgs, DaemonCtlArgsExt};
+use g3_ctl::{CommandError, CommandResult, DaemonCtlArgs, DaemonCtlArgsExt};
 
 use g3proxy_proto::proc_capnp::proc_control;
 
 mod common;
 mod proc;
 
+mod auditor;
 mod escaper;
 mod resolver;
 mod server;
+mod tenant;
 mod user_group;
 
 fn build_cli_args() -> Command {
@@ -38,29 +42,38 @@ fn build_cli_args() -> Command {
         .subcommand(proc::commands::force_quit())
         .subcommand(proc::commands::force_quit_all())
         .subcommand(proc::commands::list())
+        .subcommand(proc::commands::preflight())
         .subcommand(proc::commands::reload_user_group())
         .subcommand(proc::commands::reload_resolver())
         .subcommand(proc::commands::reload_auditor())
         .subcommand(proc::commands::reload_escaper())
         .subcommand(proc::commands::reload_server())
+        .subcommand(proc::commands::reload_tenant())
         .subcommand(user_group::command())
         .subcommand(resolver::command())
+        .subcommand(auditor::command())
         .subcommand(escaper::command())
         .subcommand(server::command())
+        .subcommand(tenant::command())
 }
 
 #[tokio::main(flavor = "current_thread")]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> ExitCode {
     let args = build_cli_args().get_matches();
 
     let mut ctl_opts = DaemonCtlArgs::parse_clap(&args);
     if ctl_opts.generate_shell_completion(build_cli_args) {
-        return Ok(());
+        return ExitCode::SUCCESS;
     }
 
+    g3_ctl::exit_with_result(run(&args, &ctl_opts).await)
+}
+
+async fn run(args: &ArgMatches, ctl_opts: &DaemonCtlArgs) -> CommandResult<()> {
     let (rpc_system, proc_control) = ctl_opts
         .connect_rpc::<proc_control::Client>("g3proxy")
-        .await?;
+        .await
+        .map_err(CommandError::NotRunning)?;
 
     tokio::task::LocalSet::new()
         .run_until(async move {
@@ -78,6 +91,7 @@ async fn main() -> anyhow::Result<()> {
                 proc::COMMAND_FORCE_QUIT => proc::force_quit(&proc_control, args).await,
                 proc::COMMAND_FORCE_QUIT_ALL => proc::force_quit_all(&proc_control).await,
                 proc::COMMAND_LIST => proc::list(&proc_control, args).await,
+                proc::COMMAND_PREFLIGHT => proc::preflight(&proc_control).await,
                 proc::COMMAND_RELOAD_USER_GROUP => {
                     proc::reload_user_group(&proc_control, args).await
                 }
@@ -85,15 +99,17 @@ async fn main() -> anyhow::Result<()> {
                 proc::COMMAND_RELOAD_AUDITOR => proc::reload_auditor(&proc_control, args).await,
                 proc::COMMAND_RELOAD_ESCAPER => proc::reload_escaper(&proc_control, args).await,
                 proc::COMMAND_RELOAD_SERVER => proc::reload_server(&proc_control, args).await,
+                proc::COMMAND_RELOAD_TENANT => proc::reload_tenant(&proc_control, args).await,
                 user_group::COMMAND => user_group::run(&proc_control, args).await,
                 resolver::COMMAND => resolver::run(&proc_control, args).await,
+                auditor::COMMAND => auditor::run(&proc_control, args).await,
                 escaper::COMMAND => escaper::run(&proc_control, args).await,
                 server::COMMAND => server::run(&proc_control, args).await,
+                tenant::COMMAND => tenant::run(&proc_control, args).await,
                 _ => Err(CommandError::Cli(anyhow!(
                     "unsupported command {subcommand}"
                 ))),
             }
         })
         .await
-        .map_err(anyhow::Error::new)
 }
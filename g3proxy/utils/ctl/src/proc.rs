@@ -15,13 +15,16 @@
  */
 
 use clap::ArgMatches;
+use serde_json::json;
 
-use g3_ctl::CommandResult;
+use g3_ctl::{CommandError, CommandResult};
 
+use g3proxy_proto::auditor_capnp::auditor_control;
 use g3proxy_proto::escaper_capnp::escaper_control;
 use g3proxy_proto::proc_capnp::proc_control;
 use g3proxy_proto::resolver_capnp::resolver_control;
 use g3proxy_proto::server_capnp::server_control;
+use g3proxy_proto::tenant_capnp::tenant_control;
 use g3proxy_proto::user_group_capnp::user_group_control;
 
 use crate::common::{parse_fetch_result, parse_operation_result};
@@ -35,18 +38,22 @@ pub const COMMAND_FORCE_QUIT_ALL: &str = "force-quit-all";
 
 pub const COMMAND_LIST: &str = "list";
 
-const COMMAND_LIST_ARG_RESOURCE: &str = "resource";
-const RESOURCE_VALUE_USER_GROUP: &str = "user-group";
-const RESOURCE_VALUE_RESOLVER: &str = "resolver";
-const RESOURCE_VALUE_AUDITOR: &str = "auditor";
-const RESOURCE_VALUE_ESCAPER: &str = "escaper";
-const RESOURCE_VALUE_SERVER: &str = "server";
+pub const COMMAND_PREFLIGHT: &str = "preflight";
+
+pub(crate) const COMMAND_LIST_ARG_RESOURCE: &str = "resource";
+pub(crate) const RESOURCE_VALUE_USER_GROUP: &str = "user-group";
+pub(crate) const RESOURCE_VALUE_RESOLVER: &str = "resolver";
+pub(crate) const RESOURCE_VALUE_AUDITOR: &str = "auditor";
+pub(crate) const RESOURCE_VALUE_ESCAPER: &str = "escaper";
+pub(crate) const RESOURCE_VALUE_SERVER: &str = "server";
+const RESOURCE_VALUE_TENANT: &str = "tenant";
 
 pub const COMMAND_RELOAD_USER_GROUP: &str = "reload-user-group";
 pub const COMMAND_RELOAD_RESOLVER: &str = "reload-resolver";
 pub const COMMAND_RELOAD_AUDITOR: &str = "reload-auditor";
 pub const COMMAND_RELOAD_ESCAPER: &str = "reload-escaper";
 pub const COMMAND_RELOAD_SERVER: &str = "reload-server";
+pub const COMMAND_RELOAD_TENANT: &str = "reload-tenant";
 
 const SUBCOMMAND_ARG_NAME: &str = "name";
 
@@ -88,11 +95,17 @@ pub mod commands {
                     RESOURCE_VALUE_AUDITOR,
                     RESOURCE_VALUE_ESCAPER,
                     RESOURCE_VALUE_SERVER,
+                    RESOURCE_VALUE_TENANT,
                 ])
                 .ignore_case(true),
         )
     }
 
+    pub fn preflight() -> Command {
+        Command::new(COMMAND_PREFLIGHT)
+            .about("Show the preflight check report, exit with error if not ready")
+    }
+
     pub fn reload_user_group() -> Command {
         Command::new(COMMAND_RELOAD_USER_GROUP)
             .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
@@ -117,6 +130,12 @@ pub mod commands {
         Command::new(COMMAND_RELOAD_SERVER)
             .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
     }
+
+    pub fn reload_tenant() -> Command {
+        Command::new(COMMAND_RELOAD_TENANT)
+            .about("Reload all entities of the tenant, other tenants will not be affected")
+            .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
+    }
 }
 
 pub async fn version(client: &proc_control::Client) -> CommandResult<()> {
@@ -162,6 +181,7 @@ pub async fn list(client: &proc_control::Client, args: &ArgMatches) -> CommandRe
         RESOURCE_VALUE_AUDITOR => list_auditor(client).await,
         RESOURCE_VALUE_ESCAPER => list_escaper(client).await,
         RESOURCE_VALUE_SERVER => list_server(client).await,
+        RESOURCE_VALUE_TENANT => list_tenant(client).await,
         _ => unreachable!(),
     }
 }
@@ -196,6 +216,60 @@ async fn list_server(client: &proc_control::Client) -> CommandResult<()> {
     g3_ctl::print_result_list(rsp.get()?.get_result()?)
 }
 
+async fn list_tenant(client: &proc_control::Client) -> CommandResult<()> {
+    let req = client.list_tenant_request();
+    let rsp = req.send().promise.await?;
+    g3_ctl::print_result_list(rsp.get()?.get_result()?)
+}
+
+fn text_field<'a>(field: &'static str, reader: capnp::text::Reader<'a>) -> CommandResult<&'a str> {
+    reader
+        .to_str()
+        .map_err(|reason| CommandError::Utf8 { field, reason })
+}
+
+pub async fn preflight(client: &proc_control::Client) -> CommandResult<()> {
+    let req = client.get_preflight_report_request();
+    let rsp = req.send().promise.await?;
+    let report = parse_fetch_result(rsp.get()?.get_report()?)?;
+
+    let mut checks = Vec::new();
+    for check in report.get_checks()?.iter() {
+        let component = text_field("component", check.get_component()?)?;
+        let name = text_field("name", check.get_name()?)?;
+        let policy = text_field("policy", check.get_policy()?)?;
+        let error = text_field("error", check.get_error()?)?;
+        if g3_ctl::is_json_output() {
+            checks.push(json!({
+                "component": component,
+                "name": name,
+                "policy": policy,
+                "passed": check.get_passed(),
+                "error": error,
+            }));
+        } else if check.get_passed() {
+            println!("[ok] {component} {name}");
+        } else {
+            println!("[failed] {component} {name} (policy: {policy}): {error}");
+        }
+    }
+    if g3_ctl::is_json_output() {
+        g3_ctl::print_json(&json!({
+            "ready": report.get_ready(),
+            "checks": checks,
+        }));
+    }
+
+    if report.get_ready() {
+        Ok(())
+    } else {
+        Err(CommandError::Api {
+            code: 1,
+            reason: "some required dependencies are not ready".to_string(),
+        })
+    }
+}
+
 pub async fn reload_user_group(
     client: &proc_control::Client,
     args: &ArgMatches,
@@ -242,6 +316,14 @@ pub async fn reload_server(client: &proc_control::Client, args: &ArgMatches) ->
     parse_operation_result(rsp.get()?.get_result()?)
 }
 
+pub async fn reload_tenant(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let name = args.get_one::<String>(SUBCOMMAND_ARG_NAME).unwrap();
+    let mut req = client.reload_tenant_request();
+    req.get().set_name(name);
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
 pub(crate) async fn get_user_group(
     client: &proc_control::Client,
     name: &str,
@@ -262,6 +344,16 @@ pub(crate) async fn get_resolver(
     parse_fetch_result(rsp.get()?.get_resolver()?)
 }
 
+pub(crate) async fn get_auditor(
+    client: &proc_control::Client,
+    name: &str,
+) -> CommandResult<auditor_control::Client> {
+    let mut req = client.get_auditor_request();
+    req.get().set_name(name);
+    let rsp = req.send().promise.await?;
+    parse_fetch_result(rsp.get()?.get_auditor()?)
+}
+
 pub(crate) async fn get_escaper(
     client: &proc_control::Client,
     name: &str,
@@ -281,3 +373,13 @@ pub(crate) async fn get_server(
     let rsp = req.send().promise.await?;
     parse_fetch_result(rsp.get()?.get_server()?)
 }
+
+pub(crate) async fn get_tenant(
+    client: &proc_control::Client,
+    name: &str,
+) -> CommandResult<tenant_control::Client> {
+    let mut req = client.get_tenant_request();
+    req.get().set_name(name);
+    let rsp = req.send().promise.await?;
+    parse_fetch_result(rsp.get()?.get_tenant()?)
+}
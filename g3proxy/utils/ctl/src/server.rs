@@ -14,48 +14,217 @@
  * limitations under the License.
  */
 
-use clap::{Arg, ArgMatches, Command};
+use clap::{value_parser, Arg, ArgMatches, Command};
 use futures_util::future::TryFutureExt;
+use serde_json::{json, Value};
 
-use g3_ctl::CommandResult;
+use g3_ctl::{CommandError, CommandResult};
 
 use g3proxy_proto::proc_capnp::proc_control;
-use g3proxy_proto::server_capnp::server_control;
+use g3proxy_proto::server_capnp::{client_conn_count, server_control};
 
 pub const COMMAND: &str = "server";
 
 const COMMAND_ARG_NAME: &str = "name";
 
 const SUBCOMMAND_STATUS: &str = "status";
+const SUBCOMMAND_CLIENT_CONN_TOP: &str = "client-conn-top";
+const SUBCOMMAND_TEST_ROUTE: &str = "test-route";
+
+const SUBCOMMAND_ARG_COUNT: &str = "count";
+const SUBCOMMAND_ARG_UPSTREAM: &str = "upstream";
+const SUBCOMMAND_ARG_REQUEST_TYPE: &str = "request-type";
+const SUBCOMMAND_ARG_USER: &str = "user";
+const SUBCOMMAND_ARG_CLIENT_ADDR: &str = "client-addr";
 
 pub fn command() -> Command {
     Command::new(COMMAND)
         .arg(Arg::new(COMMAND_ARG_NAME).required(true).num_args(1))
         .subcommand_required(true)
         .subcommand(Command::new(SUBCOMMAND_STATUS))
+        .subcommand(
+            Command::new(SUBCOMMAND_CLIENT_CONN_TOP).arg(
+                Arg::new(SUBCOMMAND_ARG_COUNT)
+                    .help("Show how many top entries")
+                    .value_parser(value_parser!(u32))
+                    .default_value("10")
+                    .num_args(1),
+            ),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_TEST_ROUTE)
+                .about("Show how a request would be handled without sending any traffic")
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_UPSTREAM)
+                        .help("Upstream address, in host:port format")
+                        .required(true)
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_REQUEST_TYPE)
+                        .help("Proxy request type")
+                        .long(SUBCOMMAND_ARG_REQUEST_TYPE)
+                        .short('t')
+                        .value_parser([
+                            "http_forward",
+                            "https_forward",
+                            "ftp_over_http",
+                            "http_connect",
+                            "socks_tcp_connect",
+                            "socks_udp_associate",
+                        ])
+                        .default_value("http_connect")
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_USER)
+                        .help("User name, the anonymous user will be used if not set")
+                        .long(SUBCOMMAND_ARG_USER)
+                        .short('u')
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_CLIENT_ADDR)
+                        .help("Client socket address")
+                        .long(SUBCOMMAND_ARG_CLIENT_ADDR)
+                        .short('c')
+                        .default_value("127.0.0.1:0")
+                        .num_args(1),
+                ),
+        )
 }
 
 async fn status(client: &server_control::Client) -> CommandResult<()> {
     let req = client.status_request();
     let rsp = req.send().promise.await?;
     let stats = rsp.get()?.get_status()?;
-    println!("online: {}", stats.get_online());
-    println!("alive tasks: {}", stats.get_alive_task_count());
-    println!("total conn: {}", stats.get_total_conn_count());
-    println!("total task: {}", stats.get_total_task_count());
+    if g3_ctl::is_json_output() {
+        g3_ctl::print_json(&json!({
+            "online": stats.get_online(),
+            "alive_task_count": stats.get_alive_task_count(),
+            "total_conn_count": stats.get_total_conn_count(),
+            "total_task_count": stats.get_total_task_count(),
+        }));
+    } else {
+        println!("online: {}", stats.get_online());
+        println!("alive tasks: {}", stats.get_alive_task_count());
+        println!("total conn: {}", stats.get_total_conn_count());
+        println!("total task: {}", stats.get_total_task_count());
+    }
     Ok(())
 }
 
+async fn client_conn_top(client: &server_control::Client, count: u32) -> CommandResult<()> {
+    let mut req = client.client_conn_top_request();
+    req.get().set_count(count);
+    let rsp = req.send().promise.await?;
+    let top = rsp.get()?.get_top()?;
+    let ip = parse_client_conn_count_list(top.get_ip()?)?;
+    let subnet = parse_client_conn_count_list(top.get_subnet()?)?;
+    if g3_ctl::is_json_output() {
+        let to_json = |list: Vec<(String, u64)>| -> Vec<Value> {
+            list.into_iter()
+                .map(|(addr, count)| json!({"addr": addr, "count": count}))
+                .collect()
+        };
+        g3_ctl::print_json(&json!({
+            "ip": to_json(ip),
+            "subnet": to_json(subnet),
+        }));
+    } else {
+        println!("# client ip");
+        for (addr, count) in ip {
+            println!("{addr}\t{count}");
+        }
+        println!("# client subnet");
+        for (addr, count) in subnet {
+            println!("{addr}\t{count}");
+        }
+    }
+    Ok(())
+}
+
+async fn test_route(client: &server_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let mut req = client.test_route_request();
+    let mut params = req.get();
+    params.set_upstream(args.get_one::<String>(SUBCOMMAND_ARG_UPSTREAM).unwrap());
+    params.set_request_type(args.get_one::<String>(SUBCOMMAND_ARG_REQUEST_TYPE).unwrap());
+    if let Some(user) = args.get_one::<String>(SUBCOMMAND_ARG_USER) {
+        params.set_user(user);
+    }
+    params.set_client_addr(args.get_one::<String>(SUBCOMMAND_ARG_CLIENT_ADDR).unwrap());
+    let rsp = req.send().promise.await?;
+    let result = rsp.get()?.get_result()?;
+    let steps = parse_text_list(result.get_steps()?, "steps")?;
+    let escapers = parse_text_list(result.get_escapers()?, "escapers")?;
+    if g3_ctl::is_json_output() {
+        g3_ctl::print_json(&json!({
+            "permitted": result.get_permitted(),
+            "steps": steps,
+            "escapers": escapers,
+        }));
+    } else {
+        for step in steps {
+            println!("{step}");
+        }
+        if result.get_permitted() {
+            println!("result: permitted, escaper path: {}", escapers.join(" -> "));
+        } else {
+            println!("result: forbidden");
+        }
+    }
+    Ok(())
+}
+
+fn parse_text_list(
+    list: capnp::text_list::Reader<'_>,
+    field: &'static str,
+) -> CommandResult<Vec<String>> {
+    let mut r = Vec::with_capacity(list.len() as usize);
+    for t in list.iter() {
+        let t = t?
+            .to_str()
+            .map_err(|e| CommandError::Utf8 { field, reason: e })?;
+        r.push(t.to_string());
+    }
+    Ok(r)
+}
+
+fn parse_client_conn_count_list(
+    list: capnp::struct_list::Reader<'_, client_conn_count::Owned>,
+) -> CommandResult<Vec<(String, u64)>> {
+    let mut r = Vec::with_capacity(list.len() as usize);
+    for c in list.iter() {
+        let addr = c.get_addr()?.to_str().map_err(|e| CommandError::Utf8 {
+            field: "addr",
+            reason: e,
+        })?;
+        r.push((addr.to_string(), c.get_count()));
+    }
+    Ok(r)
+}
+
 pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
     let name = args.get_one::<String>(COMMAND_ARG_NAME).unwrap();
 
-    let (subcommand, _) = args.subcommand().unwrap();
+    let (subcommand, args) = args.subcommand().unwrap();
     match subcommand {
         SUBCOMMAND_STATUS => {
             super::proc::get_server(client, name)
                 .and_then(|server| async move { status(&server).await })
                 .await
         }
+        SUBCOMMAND_CLIENT_CONN_TOP => {
+            let count = args.get_one::<u32>(SUBCOMMAND_ARG_COUNT).copied().unwrap();
+            super::proc::get_server(client, name)
+                .and_then(|server| async move { client_conn_top(&server, count).await })
+                .await
+        }
+        SUBCOMMAND_TEST_ROUTE => {
+            super::proc::get_server(client, name)
+                .and_then(|server| async move { test_route(&server, args).await })
+                .await
+        }
         _ => unreachable!(),
     }
 }
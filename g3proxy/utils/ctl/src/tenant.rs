@@ -0,0 +1,118 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use clap::{Arg, ArgMatches, Command};
+use futures_util::future::TryFutureExt;
+
+use g3_ctl::CommandResult;
+
+use g3proxy_proto::proc_capnp::proc_control;
+use g3proxy_proto::tenant_capnp::tenant_control;
+
+use crate::common::parse_operation_result;
+use crate::proc::{
+    COMMAND_LIST_ARG_RESOURCE, RESOURCE_VALUE_AUDITOR, RESOURCE_VALUE_ESCAPER,
+    RESOURCE_VALUE_RESOLVER, RESOURCE_VALUE_SERVER, RESOURCE_VALUE_USER_GROUP,
+};
+
+pub const COMMAND: &str = "tenant";
+
+const COMMAND_ARG_NAME: &str = "name";
+
+const SUBCOMMAND_RELOAD: &str = "reload";
+const SUBCOMMAND_LIST: &str = "list";
+
+pub fn command() -> Command {
+    Command::new(COMMAND)
+        .arg(Arg::new(COMMAND_ARG_NAME).required(true).num_args(1))
+        .subcommand_required(true)
+        .subcommand(
+            Command::new(SUBCOMMAND_RELOAD)
+                .about("Reload all entities of this tenant, other tenants will not be affected"),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_LIST)
+                .about("List entities that belong to this tenant")
+                .arg(
+                    Arg::new(COMMAND_LIST_ARG_RESOURCE)
+                        .required(true)
+                        .num_args(1)
+                        .value_parser([
+                            RESOURCE_VALUE_USER_GROUP,
+                            RESOURCE_VALUE_RESOLVER,
+                            RESOURCE_VALUE_AUDITOR,
+                            RESOURCE_VALUE_ESCAPER,
+                            RESOURCE_VALUE_SERVER,
+                        ])
+                        .ignore_case(true),
+                ),
+        )
+}
+
+async fn reload(client: &tenant_control::Client) -> CommandResult<()> {
+    let req = client.reload_request();
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
+async fn list(client: &tenant_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    match args
+        .get_one::<String>(COMMAND_LIST_ARG_RESOURCE)
+        .unwrap()
+        .as_str()
+    {
+        RESOURCE_VALUE_USER_GROUP => {
+            let rsp = client.list_user_group_request().send().promise.await?;
+            g3_ctl::print_result_list(rsp.get()?.get_result()?)
+        }
+        RESOURCE_VALUE_RESOLVER => {
+            let rsp = client.list_resolver_request().send().promise.await?;
+            g3_ctl::print_result_list(rsp.get()?.get_result()?)
+        }
+        RESOURCE_VALUE_AUDITOR => {
+            let rsp = client.list_auditor_request().send().promise.await?;
+            g3_ctl::print_result_list(rsp.get()?.get_result()?)
+        }
+        RESOURCE_VALUE_ESCAPER => {
+            let rsp = client.list_escaper_request().send().promise.await?;
+            g3_ctl::print_result_list(rsp.get()?.get_result()?)
+        }
+        RESOURCE_VALUE_SERVER => {
+            let rsp = client.list_server_request().send().promise.await?;
+            g3_ctl::print_result_list(rsp.get()?.get_result()?)
+        }
+        _ => unreachable!(),
+    }
+}
+
+pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let name = args.get_one::<String>(COMMAND_ARG_NAME).unwrap();
+
+    let (subcommand, args) = args.subcommand().unwrap();
+    match subcommand {
+        SUBCOMMAND_RELOAD => {
+            super::proc::get_tenant(client, name)
+                .and_then(|tenant| async move { reload(&tenant).await })
+                .await
+        }
+        SUBCOMMAND_LIST => {
+            super::proc::get_tenant(client, name)
+                .and_then(|tenant| async move { list(&tenant, args).await })
+                .await
+        }
+        _ => unreachable!(),
+    }
+}
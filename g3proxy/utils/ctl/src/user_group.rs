@@ -19,11 +19,12 @@ use std::str::FromStr;
 
 use anyhow::anyhow;
 use clap::{value_parser, Arg, ArgMatches, Command, ValueHint};
+use serde_json::{json, Value};
 
 use g3_ctl::{CommandError, CommandResult};
 
 use g3proxy_proto::proc_capnp::proc_control;
-use g3proxy_proto::user_group_capnp::user_group_control;
+use g3proxy_proto::user_group_capnp::{user_group_control, user_quota_state, user_traffic_summary};
 
 use super::common::parse_operation_result;
 
@@ -35,6 +36,13 @@ const COMMAND_ARG_FILE: &str = "file";
 const SUBCOMMAND_LIST_STATIC_USER: &str = "list-static-user";
 const SUBCOMMAND_LIST_DYNAMIC_USER: &str = "list-dynamic-user";
 const SUBCOMMAND_PUBLISH_USER: &str = "publish-user";
+const SUBCOMMAND_TRAFFIC_TOP: &str = "traffic-top";
+const SUBCOMMAND_QUOTA: &str = "quota";
+const SUBCOMMAND_RESET_QUOTA: &str = "reset-quota";
+
+const SUBCOMMAND_ARG_USER: &str = "user";
+const SUBCOMMAND_ARG_INTERVAL: &str = "interval";
+const SUBCOMMAND_ARG_COUNT: &str = "count";
 
 pub fn command() -> Command {
     Command::new(COMMAND)
@@ -54,6 +62,49 @@ pub fn command() -> Command {
                         .value_hint(ValueHint::FilePath),
                 ),
         )
+        .subcommand(
+            Command::new(SUBCOMMAND_TRAFFIC_TOP)
+                .about("Show the busiest users in the last few seconds")
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_USER)
+                        .help("Only show the specified user")
+                        .long(SUBCOMMAND_ARG_USER)
+                        .short('u')
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_INTERVAL)
+                        .help("Sample interval in seconds, at most 60")
+                        .long(SUBCOMMAND_ARG_INTERVAL)
+                        .short('i')
+                        .value_parser(value_parser!(u32).range(1..=60))
+                        .default_value("5")
+                        .num_args(1),
+                )
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_COUNT)
+                        .help("Show how many top users and sites")
+                        .value_parser(value_parser!(u32))
+                        .default_value("10")
+                        .num_args(1),
+                ),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_QUOTA)
+                .about("Show the quota state of users")
+                .arg(
+                    Arg::new(SUBCOMMAND_ARG_USER)
+                        .help("Only show the specified user")
+                        .long(SUBCOMMAND_ARG_USER)
+                        .short('u')
+                        .num_args(1),
+                ),
+        )
+        .subcommand(
+            Command::new(SUBCOMMAND_RESET_QUOTA)
+                .about("Reset the traffic quota usage of the specified user")
+                .arg(Arg::new(SUBCOMMAND_ARG_USER).required(true).num_args(1)),
+        )
 }
 
 pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandResult<()> {
@@ -66,6 +117,9 @@ pub async fn run(client: &proc_control::Client, args: &ArgMatches) -> CommandRes
         SUBCOMMAND_LIST_STATIC_USER => list_static_user(&user_group).await,
         SUBCOMMAND_LIST_DYNAMIC_USER => list_dynamic_user(&user_group).await,
         SUBCOMMAND_PUBLISH_USER => publish_dynamic_user(&user_group, args).await,
+        SUBCOMMAND_TRAFFIC_TOP => traffic_top(&user_group, args).await,
+        SUBCOMMAND_QUOTA => query_quota(&user_group, args).await,
+        SUBCOMMAND_RESET_QUOTA => reset_quota(&user_group, args).await,
         _ => unreachable!(),
     }
 }
@@ -108,3 +162,139 @@ async fn publish_dynamic_user(
     let rsp = req.send().promise.await?;
     parse_operation_result(rsp.get()?.get_result()?)
 }
+
+async fn traffic_top(client: &user_group_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let mut req = client.traffic_top_request();
+    let mut params = req.get();
+    if let Some(user) = args.get_one::<String>(SUBCOMMAND_ARG_USER) {
+        params.set_user(user);
+    }
+    params.set_interval(
+        args.get_one::<u32>(SUBCOMMAND_ARG_INTERVAL)
+            .copied()
+            .unwrap(),
+    );
+    params.set_count(args.get_one::<u32>(SUBCOMMAND_ARG_COUNT).copied().unwrap());
+    let rsp = req.send().promise.await?;
+    let list = rsp.get()?.get_result()?;
+
+    let mut all = Vec::with_capacity(list.len() as usize);
+    for summary in list.iter() {
+        all.push(parse_user_traffic_summary(summary)?);
+    }
+
+    if g3_ctl::is_json_output() {
+        g3_ctl::print_json(&Value::Array(all));
+    } else {
+        for v in all {
+            println!(
+                "{}\talive: {}\tin: {}\tout: {}",
+                v["user"].as_str().unwrap_or_default(),
+                v["alive_task_count"],
+                v["in_bytes"],
+                v["out_bytes"],
+            );
+            if let Some(sites) = v["top_sites"].as_array() {
+                for site in sites {
+                    println!(
+                        "  site {}\tin: {}\tout: {}",
+                        site["site"].as_str().unwrap_or_default(),
+                        site["in_bytes"],
+                        site["out_bytes"],
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn query_quota(client: &user_group_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let mut req = client.query_quota_request();
+    if let Some(user) = args.get_one::<String>(SUBCOMMAND_ARG_USER) {
+        req.get().set_user(user);
+    }
+    let rsp = req.send().promise.await?;
+    let list = rsp.get()?.get_result()?;
+
+    let mut all = Vec::with_capacity(list.len() as usize);
+    for state in list.iter() {
+        all.push(parse_user_quota_state(state)?);
+    }
+
+    if g3_ctl::is_json_output() {
+        g3_ctl::print_json(&Value::Array(all));
+    } else {
+        for v in all {
+            println!(
+                "{}\tperiod: {}\tused: {}\tlimit: {}\texceeded: {}\tin_time_window: {}",
+                v["user"].as_str().unwrap_or_default(),
+                v["period"].as_str().unwrap_or_default(),
+                v["used_bytes"],
+                v["limit_bytes"],
+                v["traffic_exceeded"],
+                v["in_time_window"],
+            );
+        }
+    }
+    Ok(())
+}
+
+async fn reset_quota(client: &user_group_control::Client, args: &ArgMatches) -> CommandResult<()> {
+    let user = args.get_one::<String>(SUBCOMMAND_ARG_USER).unwrap();
+    let mut req = client.reset_quota_request();
+    req.get().set_user(user);
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
+fn parse_user_quota_state(state: user_quota_state::Reader<'_>) -> CommandResult<Value> {
+    let user = state.get_user()?.to_str().map_err(|e| CommandError::Utf8 {
+        field: "user",
+        reason: e,
+    })?;
+    let period = state
+        .get_period()?
+        .to_str()
+        .map_err(|e| CommandError::Utf8 {
+            field: "period",
+            reason: e,
+        })?;
+    Ok(json!({
+        "user": user,
+        "period": period,
+        "used_bytes": state.get_used_bytes(),
+        "limit_bytes": state.get_limit_bytes(),
+        "traffic_exceeded": state.get_traffic_exceeded(),
+        "in_time_window": state.get_in_time_window(),
+    }))
+}
+
+fn parse_user_traffic_summary(summary: user_traffic_summary::Reader<'_>) -> CommandResult<Value> {
+    let user = summary
+        .get_user()?
+        .to_str()
+        .map_err(|e| CommandError::Utf8 {
+            field: "user",
+            reason: e,
+        })?;
+    let mut sites = Vec::new();
+    for site in summary.get_top_sites()?.iter() {
+        let name = site.get_site()?.to_str().map_err(|e| CommandError::Utf8 {
+            field: "site",
+            reason: e,
+        })?;
+        sites.push(json!({
+            "site": name,
+            "in_bytes": site.get_in_bytes(),
+            "out_bytes": site.get_out_bytes(),
+        }));
+    }
+    Ok(json!({
+        "user": user,
+        "alive_task_count": summary.get_alive_task_count(),
+        "in_bytes": summary.get_in_bytes(),
+        "out_bytes": summary.get_out_bytes(),
+        "top_sites": sites,
+    }))
+}
@@ -0,0 +1,189 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+
+use anyhow::{anyhow, Context};
+use bytes::{BufMut, Bytes, BytesMut};
+use http::{header, Method, Request, StatusCode, Version};
+
+const GRPC_HEALTH_CHECK_PATH: &str = "/grpc.health.v1.Health/Check";
+
+/// The `ServingStatus` enum defined in grpc.health.v1
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum GrpcServingStatus {
+    Unknown,
+    Serving,
+    NotServing,
+    ServiceUnknown,
+}
+
+impl GrpcServingStatus {
+    fn from_value(v: u64) -> Self {
+        match v {
+            1 => GrpcServingStatus::Serving,
+            2 => GrpcServingStatus::NotServing,
+            3 => GrpcServingStatus::ServiceUnknown,
+            _ => GrpcServingStatus::Unknown,
+        }
+    }
+}
+
+pub(super) async fn check(addr: SocketAddr, service: &str) -> anyhow::Result<()> {
+    let stream = tokio::net::TcpStream::connect(addr)
+        .await
+        .map_err(|e| anyhow!("failed to connect to peer: {e}"))?;
+    let (send_request, connection) = h2::client::handshake(stream)
+        .await
+        .map_err(|e| anyhow!("h2 handshake failed: {e}"))?;
+    let conn_task = tokio::spawn(async move {
+        let _ = connection.await;
+    });
+
+    let r = send_check_request(addr, service, send_request).await;
+    conn_task.abort();
+    match r? {
+        GrpcServingStatus::Serving => Ok(()),
+        status => Err(anyhow!("peer reported status {status:?}")),
+    }
+}
+
+async fn send_check_request(
+    addr: SocketAddr,
+    service: &str,
+    send_request: h2::client::SendRequest<Bytes>,
+) -> anyhow::Result<GrpcServingStatus> {
+    let req = Request::builder()
+        .method(Method::POST)
+        .version(Version::HTTP_2)
+        .uri(format!("http://{addr}{GRPC_HEALTH_CHECK_PATH}"))
+        .header(header::CONTENT_TYPE, "application/grpc")
+        .header(header::TE, "trailers")
+        .body(())
+        .map_err(|e| anyhow!("failed to build request: {e}"))?;
+
+    let mut send_request = send_request
+        .ready()
+        .await
+        .map_err(|e| anyhow!("h2 connection not ready: {e}"))?;
+    let (rsp_fut, mut send_stream) = send_request
+        .send_request(req, false)
+        .map_err(|e| anyhow!("failed to send request header: {e}"))?;
+    send_stream
+        .send_data(encode_check_request(service), true)
+        .map_err(|e| anyhow!("failed to send request body: {e}"))?;
+
+    let rsp = rsp_fut
+        .await
+        .map_err(|e| anyhow!("failed to recv response header: {e}"))?;
+    if rsp.status() != StatusCode::OK {
+        return Err(anyhow!("unexpected response status {}", rsp.status()));
+    }
+    if let Some(status) = rsp.headers().get("grpc-status") {
+        // trailers-only response
+        return Err(anyhow!("grpc error status {status:?}"));
+    }
+
+    let mut recv_stream = rsp.into_body();
+    let mut body = BytesMut::new();
+    while let Some(r) = recv_stream.data().await {
+        let data = r.map_err(|e| anyhow!("failed to recv response body: {e}"))?;
+        let _ = recv_stream.flow_control().release_capacity(data.len());
+        body.extend_from_slice(&data);
+    }
+    let trailers = recv_stream
+        .trailers()
+        .await
+        .map_err(|e| anyhow!("failed to recv response trailers: {e}"))?;
+    if let Some(status) = trailers.as_ref().and_then(|t| t.get("grpc-status")) {
+        if status.as_bytes() != b"0" {
+            return Err(anyhow!("grpc error status {status:?}"));
+        }
+    }
+
+    decode_check_response(&body).context("invalid health check response")
+}
+
+fn encode_check_request(service: &str) -> Bytes {
+    let mut msg = BytesMut::with_capacity(service.len() + 16);
+    if !service.is_empty() {
+        // field 1, wire type 2 (length-delimited)
+        msg.put_u8(0x0a);
+        put_varint(&mut msg, service.len() as u64);
+        msg.put_slice(service.as_bytes());
+    }
+
+    let mut buf = BytesMut::with_capacity(msg.len() + 5);
+    buf.put_u8(0); // not compressed
+    buf.put_u32(msg.len() as u32);
+    buf.put_slice(&msg);
+    buf.freeze()
+}
+
+fn decode_check_response(buf: &[u8]) -> anyhow::Result<GrpcServingStatus> {
+    if buf.len() < 5 {
+        return Err(anyhow!("too short grpc message"));
+    }
+    if buf[0] != 0 {
+        return Err(anyhow!("compressed grpc message is not supported"));
+    }
+    let len = u32::from_be_bytes([buf[1], buf[2], buf[3], buf[4]]) as usize;
+    let Some(mut msg) = buf.get(5..5 + len) else {
+        return Err(anyhow!("incomplete grpc message"));
+    };
+
+    let mut status = GrpcServingStatus::Unknown;
+    while !msg.is_empty() {
+        let key = get_varint(&mut msg)?;
+        match (key >> 3, key & 0x07) {
+            (1, 0) => status = GrpcServingStatus::from_value(get_varint(&mut msg)?),
+            (_, 0) => {
+                get_varint(&mut msg)?;
+            }
+            (_, 1) => msg = msg.get(8..).ok_or_else(|| anyhow!("truncated fixed64"))?,
+            (_, 2) => {
+                let len = get_varint(&mut msg)? as usize;
+                msg = msg.get(len..).ok_or_else(|| anyhow!("truncated field"))?;
+            }
+            (_, 5) => msg = msg.get(4..).ok_or_else(|| anyhow!("truncated fixed32"))?,
+            (_, t) => return Err(anyhow!("unsupported wire type {t}")),
+        }
+    }
+    Ok(status)
+}
+
+fn put_varint(buf: &mut BytesMut, mut v: u64) {
+    while v >= 0x80 {
+        buf.put_u8((v as u8) | 0x80);
+        v >>= 7;
+    }
+    buf.put_u8(v as u8);
+}
+
+fn get_varint(buf: &mut &[u8]) -> anyhow::Result<u64> {
+    let mut v = 0u64;
+    for i in 0..10 {
+        let Some((b, left)) = buf.split_first() else {
+            return Err(anyhow!("truncated varint"));
+        };
+        *buf = left;
+        v |= ((b & 0x7f) as u64) << (i * 7);
+        if b & 0x80 == 0 {
+            return Ok(v);
+        }
+    }
+    Err(anyhow!("invalid varint"))
+}
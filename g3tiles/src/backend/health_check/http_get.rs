@@ -0,0 +1,98 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+
+use anyhow::anyhow;
+use http::StatusCode;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::config::backend::health_check::HttpHealthCheckProbe;
+
+const MAX_RESPONSE_SIZE: usize = 64 * 1024;
+
+pub(super) async fn check<S>(
+    mut stream: S,
+    addr: SocketAddr,
+    probe: &HttpHealthCheckProbe,
+) -> anyhow::Result<()>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let host = match &probe.host {
+        Some(host) => host.clone(),
+        None => addr.to_string(),
+    };
+    let req = format!(
+        "GET {} HTTP/1.1\r\nHost: {host}\r\nUser-Agent: g3tiles-health-check\r\nConnection: close\r\n\r\n",
+        probe.path
+    );
+    stream
+        .write_all(req.as_bytes())
+        .await
+        .map_err(|e| anyhow!("failed to send request: {e}"))?;
+    stream
+        .flush()
+        .await
+        .map_err(|e| anyhow!("failed to send request: {e}"))?;
+
+    let mut buf = Vec::with_capacity(4096);
+    let mut limited = stream.take(MAX_RESPONSE_SIZE as u64);
+    limited
+        .read_to_end(&mut buf)
+        .await
+        .map_err(|e| anyhow!("failed to read response: {e}"))?;
+
+    let status = parse_status_line(&buf)?;
+    if !probe.status_is_expected(status) {
+        return Err(anyhow!("unexpected response status {status}"));
+    }
+
+    if let Some(expected) = &probe.expected_body {
+        let Some(hdr_end) = find_bytes(&buf, b"\r\n\r\n") else {
+            return Err(anyhow!("incomplete response header"));
+        };
+        let body = &buf[hdr_end + 4..];
+        if find_bytes(body, expected.as_bytes()).is_none() {
+            return Err(anyhow!("expected content not found in response body"));
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_status_line(buf: &[u8]) -> anyhow::Result<StatusCode> {
+    let Some(line_end) = buf.iter().position(|c| *c == b'\n') else {
+        return Err(anyhow!("no status line found in response"));
+    };
+    let line = &buf[..line_end];
+    let mut parts = line.split(|c| *c == b' ');
+    match parts.next() {
+        Some(version) if version.starts_with(b"HTTP/1.") => {}
+        _ => return Err(anyhow!("invalid http version in status line")),
+    }
+    let Some(code) = parts.next() else {
+        return Err(anyhow!("no status code in status line"));
+    };
+    StatusCode::from_bytes(code).map_err(|e| anyhow!("invalid status code: {e}"))
+}
+
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+    haystack.windows(needle.len()).position(|w| w == needle)
+}
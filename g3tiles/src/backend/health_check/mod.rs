@@ -0,0 +1,244 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Instant;
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use arc_swap::{ArcSwap, ArcSwapOption};
+use log::{info, warn};
+use rustls_pki_types::ServerName;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::TlsConnector;
+
+use g3_types::collection::{SelectiveVec, SelectiveVecBuilder, WeightedValue};
+use g3_types::metrics::NodeName;
+use g3_types::net::RustlsClientConfig;
+
+use crate::config::backend::health_check::{HealthCheckConfig, HealthCheckProbe};
+use crate::module::stream::StreamBackendStats;
+
+mod grpc;
+mod http_get;
+
+#[derive(Default)]
+struct PeerHealthState {
+    unhealthy: bool,
+    success_count: usize,
+    failure_count: usize,
+    recovered_at: Option<Instant>,
+}
+
+impl PeerHealthState {
+    /// Get the weight factor of this peer in the slow start window
+    fn slow_start_factor(&mut self, config: &HealthCheckConfig) -> f64 {
+        let (Some(window), Some(recovered_at)) = (config.slow_start, self.recovered_at) else {
+            return 1.0;
+        };
+        let elapsed = recovered_at.elapsed();
+        if elapsed >= window {
+            self.recovered_at = None;
+            return 1.0;
+        }
+        (elapsed.as_secs_f64() / window.as_secs_f64()).max(SLOW_START_MIN_FACTOR)
+    }
+}
+
+const SLOW_START_MIN_FACTOR: f64 = 0.1;
+
+pub(super) struct HealthChecker {
+    backend: NodeName,
+    config: HealthCheckConfig,
+    tls_client: Option<RustlsClientConfig>,
+    stats: Arc<StreamBackendStats>,
+    peers: ArcSwap<Vec<WeightedValue<SocketAddr>>>,
+    healthy_peers: ArcSwapOption<SelectiveVec<WeightedValue<SocketAddr>>>,
+}
+
+impl HealthChecker {
+    pub(super) fn new(
+        backend: &NodeName,
+        config: HealthCheckConfig,
+        stats: Arc<StreamBackendStats>,
+    ) -> anyhow::Result<Self> {
+        let tls_client = match &config.probe {
+            HealthCheckProbe::TlsHandshake(builder, _) => Some(builder.build()?),
+            HealthCheckProbe::HttpGet(http) => match &http.tls_client {
+                Some(builder) => Some(builder.build()?),
+                None => None,
+            },
+            _ => None,
+        };
+        Ok(HealthChecker {
+            backend: backend.clone(),
+            config,
+            tls_client,
+            stats,
+            peers: ArcSwap::new(Arc::new(Vec::new())),
+            healthy_peers: ArcSwapOption::new(None),
+        })
+    }
+
+    pub(super) fn update_peers(&self, peers: Vec<WeightedValue<SocketAddr>>) {
+        if let Some(old_healthy) = self.healthy_peers.load_full() {
+            // drop the removed peers now, don't wait for the next check round
+            let mut builder = SelectiveVecBuilder::with_capacity(old_healthy.len());
+            for v in old_healthy.iter() {
+                if peers.iter().any(|p| p.inner() == v.inner()) {
+                    builder.insert(*v);
+                }
+            }
+            self.healthy_peers.store(builder.build().map(Arc::new));
+        }
+        self.peers.store(Arc::new(peers));
+    }
+
+    /// Get the peers that are considered healthy.
+    ///
+    /// None will be returned if no health check has been finished, or no peer is healthy.
+    pub(super) fn healthy_peers(&self) -> Option<Arc<SelectiveVec<WeightedValue<SocketAddr>>>> {
+        self.healthy_peers.load_full()
+    }
+
+    pub(super) async fn run(self: Arc<Self>) {
+        let mut peer_states: AHashMap<SocketAddr, PeerHealthState> = AHashMap::new();
+        let mut interval = tokio::time::interval(self.config.interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            let peers = self.peers.load_full();
+            let results =
+                futures_util::future::join_all(peers.iter().map(|v| self.check_peer(*v.inner())))
+                    .await;
+
+            let mut new_states = AHashMap::with_capacity(peers.len());
+            let mut builder = SelectiveVecBuilder::with_capacity(peers.len());
+            let mut unhealthy_count = 0;
+            for (peer, r) in peers.iter().zip(results) {
+                let addr = *peer.inner();
+                let mut state = peer_states.remove(&addr).unwrap_or_default();
+                self.update_state(addr, &mut state, r);
+                if state.unhealthy {
+                    unhealthy_count += 1;
+                } else {
+                    let factor = state.slow_start_factor(&self.config);
+                    if factor < 1.0 {
+                        builder.insert(WeightedValue::with_weight(addr, peer.weight() * factor));
+                    } else {
+                        builder.insert(*peer);
+                    }
+                }
+                new_states.insert(addr, state);
+            }
+            peer_states = new_states;
+            self.stats
+                .set_health_peers(peers.len() - unhealthy_count, unhealthy_count);
+            self.healthy_peers.store(builder.build().map(Arc::new));
+        }
+    }
+
+    fn update_state(&self, addr: SocketAddr, state: &mut PeerHealthState, r: anyhow::Result<()>) {
+        match r {
+            Ok(_) => {
+                state.failure_count = 0;
+                state.success_count += 1;
+                if state.unhealthy && state.success_count >= self.config.rise.get() {
+                    state.unhealthy = false;
+                    state.recovered_at = Some(Instant::now());
+                    self.stats.add_health_state_change();
+                    info!(
+                        "backend {}: {} health check to peer {addr} passed, mark it healthy",
+                        self.backend,
+                        self.config.probe.as_str()
+                    );
+                }
+            }
+            Err(e) => {
+                state.success_count = 0;
+                state.failure_count += 1;
+                if !state.unhealthy && state.failure_count >= self.config.fall.get() {
+                    state.unhealthy = true;
+                    self.stats.add_health_state_change();
+                    warn!(
+                        "backend {}: {} health check to peer {addr} failed: {e:?}, mark it unhealthy",
+                        self.backend,
+                        self.config.probe.as_str()
+                    );
+                }
+            }
+        }
+    }
+
+    async fn check_peer(&self, addr: SocketAddr) -> anyhow::Result<()> {
+        match tokio::time::timeout(self.config.timeout, self.do_check_peer(addr)).await {
+            Ok(r) => r,
+            Err(_) => Err(anyhow!("timed out")),
+        }
+    }
+
+    async fn do_check_peer(&self, addr: SocketAddr) -> anyhow::Result<()> {
+        match &self.config.probe {
+            HealthCheckProbe::TcpConnect => {
+                TcpStream::connect(addr)
+                    .await
+                    .map_err(|e| anyhow!("failed to connect to peer: {e}"))?;
+                Ok(())
+            }
+            HealthCheckProbe::TlsHandshake(_, tls_name) => {
+                self.tls_connect(addr, tls_name.as_ref()).await?;
+                Ok(())
+            }
+            HealthCheckProbe::HttpGet(probe) => {
+                if self.tls_client.is_some() {
+                    let stream = self.tls_connect(addr, probe.tls_name.as_ref()).await?;
+                    http_get::check(stream, addr, probe).await
+                } else {
+                    let stream = TcpStream::connect(addr)
+                        .await
+                        .map_err(|e| anyhow!("failed to connect to peer: {e}"))?;
+                    http_get::check(stream, addr, probe).await
+                }
+            }
+            HealthCheckProbe::Grpc(service) => grpc::check(addr, service).await,
+        }
+    }
+
+    async fn tls_connect(
+        &self,
+        addr: SocketAddr,
+        tls_name: Option<&ServerName<'static>>,
+    ) -> anyhow::Result<TlsStream<TcpStream>> {
+        let Some(tls_client) = &self.tls_client else {
+            return Err(anyhow!("no tls client config set"));
+        };
+        let tcp_stream = TcpStream::connect(addr)
+            .await
+            .map_err(|e| anyhow!("failed to connect to peer: {e}"))?;
+        let tls_name = tls_name
+            .cloned()
+            .unwrap_or_else(|| ServerName::IpAddress(addr.ip().into()));
+        let tls_connector = TlsConnector::from(tls_client.driver.clone());
+        tls_connector
+            .connect(tls_name, tcp_stream)
+            .await
+            .map_err(|e| anyhow!("tls handshake failed: {e}"))
+    }
+}
@@ -14,9 +14,10 @@
  * limitations under the License.
  */
 
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::sync::Arc;
 
+use anyhow::anyhow;
 use async_trait::async_trait;
 
 use g3_types::collection::{SelectiveItem, SelectivePickPolicy, SelectiveVec};
@@ -28,6 +29,7 @@ use crate::module::stream::{StreamConnectError, StreamConnectResult};
 use crate::serve::ServerTaskNotes;
 
 mod dummy_close;
+mod health_check;
 #[cfg(feature = "quic")]
 mod keyless_quic;
 mod keyless_tcp;
@@ -35,7 +37,7 @@ mod stream_tcp;
 
 mod ops;
 pub use ops::load_all;
-pub(crate) use ops::{reload, update_dependency_to_discover};
+pub(crate) use ops::{drain_peer, reload, update_dependency_to_discover};
 
 mod registry;
 pub(crate) use registry::{get_names, get_or_insert_default};
@@ -53,6 +55,11 @@ pub(crate) trait Backend {
     fn discover(&self) -> &NodeName;
     fn update_discover(&self) -> anyhow::Result<()>;
 
+    /// stop sending new connections to the peer, or resume it
+    fn drain_peer(&self, _peer: SocketAddr, _drain: bool) -> anyhow::Result<()> {
+        Err(anyhow!("peer drain is not supported by this backend"))
+    }
+
     async fn stream_connect(&self, _task_notes: &ServerTaskNotes) -> StreamConnectResult {
         Err(StreamConnectError::UpstreamNotResolved) // TODO
     }
@@ -15,6 +15,7 @@
  */
 
 use std::collections::HashSet;
+use std::net::SocketAddr;
 
 use anyhow::{anyhow, Context};
 use log::{debug, warn};
@@ -139,6 +140,19 @@ pub(crate) async fn update_dependency_to_discover(discover: &NodeName, status: &
     }
 }
 
+pub(crate) async fn drain_peer(
+    name: &NodeName,
+    peer: SocketAddr,
+    drain: bool,
+) -> anyhow::Result<()> {
+    let _guard = BACKEND_OPS_LOCK.lock().await;
+
+    let Some(backend) = registry::get(name) else {
+        return Err(anyhow!("no backend with name {name} found"));
+    };
+    backend.drain_peer(peer, drain)
+}
+
 async fn reload_unlocked(old: AnyBackendConfig, new: AnyBackendConfig) -> anyhow::Result<()> {
     let name = old.name();
     match old.diff_action(&new) {
@@ -17,16 +17,20 @@
 use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
 
+use ahash::AHashSet;
 use anyhow::{anyhow, Context};
-use arc_swap::ArcSwapOption;
+use arc_swap::{ArcSwap, ArcSwapOption};
 use async_trait::async_trait;
 use futures_util::future::{AbortHandle, Abortable};
+use log::info;
+use tokio::io::AsyncWriteExt;
 use tokio::time::Instant;
 
 use g3_types::collection::{SelectiveVec, SelectiveVecBuilder, WeightedValue};
 use g3_types::metrics::NodeName;
-use g3_types::net::ConnectError;
+use g3_types::net::{ConnectError, ProxyProtocolEncoder};
 
+use super::health_check::HealthChecker;
 use super::{ArcBackend, Backend, BackendExt};
 use crate::config::backend::stream_tcp::StreamTcpBackendConfig;
 use crate::config::backend::{AnyBackendConfig, BackendConfig};
@@ -36,13 +40,80 @@ use crate::module::stream::{
 };
 use crate::serve::ServerTaskNotes;
 
+struct StreamTcpPeers {
+    discovered: ArcSwap<Vec<WeightedValue<SocketAddr>>>,
+    /// the drained peers, which will be kept after reload
+    drained: Arc<Mutex<AHashSet<SocketAddr>>>,
+    selective: ArcSwapOption<SelectiveVec<WeightedValue<SocketAddr>>>,
+    health_checker: Option<Arc<HealthChecker>>,
+}
+
+impl StreamTcpPeers {
+    fn new(
+        drained: Arc<Mutex<AHashSet<SocketAddr>>>,
+        health_checker: Option<Arc<HealthChecker>>,
+    ) -> Self {
+        StreamTcpPeers {
+            discovered: ArcSwap::new(Arc::new(Vec::new())),
+            drained,
+            selective: ArcSwapOption::new(None),
+            health_checker,
+        }
+    }
+
+    fn set_discovered(&self, peers: Vec<WeightedValue<SocketAddr>>) {
+        self.discovered.store(Arc::new(peers));
+        self.refresh(&self.drained.lock().unwrap());
+    }
+
+    fn set_drain(&self, peer: SocketAddr, drain: bool) -> bool {
+        let mut drained = self.drained.lock().unwrap();
+        let changed = if drain {
+            drained.insert(peer)
+        } else {
+            drained.remove(&peer)
+        };
+        if changed {
+            self.refresh(&drained);
+        }
+        changed
+    }
+
+    fn refresh(&self, drained: &AHashSet<SocketAddr>) {
+        let discovered = self.discovered.load();
+        let peers: Vec<WeightedValue<SocketAddr>> = discovered
+            .iter()
+            .filter(|v| !drained.contains(v.inner()))
+            .copied()
+            .collect();
+
+        let mut builder = SelectiveVecBuilder::with_capacity(peers.len());
+        for v in &peers {
+            builder.insert(*v);
+        }
+        self.selective.store(builder.build().map(Arc::new));
+        if let Some(checker) = &self.health_checker {
+            checker.update_peers(peers);
+        }
+    }
+}
+
 pub(crate) struct StreamTcpBackend {
     config: Arc<StreamTcpBackendConfig>,
     stats: Arc<StreamBackendStats>,
     duration_recorder: Arc<StreamBackendDurationRecorder>,
     duration_stats: Arc<StreamBackendDurationStats>,
-    peer_addrs: Arc<ArcSwapOption<SelectiveVec<WeightedValue<SocketAddr>>>>,
+    peers: Arc<StreamTcpPeers>,
     discover_handle: Mutex<Option<AbortHandle>>,
+    health_check_handle: Option<AbortHandle>,
+}
+
+impl Drop for StreamTcpBackend {
+    fn drop(&mut self) {
+        if let Some(handle) = self.health_check_handle.take() {
+            handle.abort();
+        }
+    }
 }
 
 impl StreamTcpBackend {
@@ -51,20 +122,35 @@ impl StreamTcpBackend {
         stats: Arc<StreamBackendStats>,
         duration_recorder: Arc<StreamBackendDurationRecorder>,
         duration_stats: Arc<StreamBackendDurationStats>,
+        drained_peers: Arc<Mutex<AHashSet<SocketAddr>>>,
     ) -> anyhow::Result<ArcBackend> {
-        let peer_addrs = Arc::new(ArcSwapOption::new(None));
-
         // always update extra metrics tags
         stats.set_extra_tags(config.extra_metrics_tags.clone());
         duration_stats.set_extra_tags(config.extra_metrics_tags.clone());
 
+        let (health_checker, health_check_handle) = match &config.health_check {
+            Some(c) => {
+                let checker = HealthChecker::new(config.name(), c.clone(), stats.clone())
+                    .context("failed to create health checker")?;
+                let checker = Arc::new(checker);
+                let (abort_handle, abort_reg) = AbortHandle::new_pair();
+                tokio::spawn(Abortable::new(checker.clone().run(), abort_reg));
+                (Some(checker), Some(abort_handle))
+            }
+            None => {
+                stats.clear_health_peers();
+                (None, None)
+            }
+        };
+
         let backend = Arc::new(StreamTcpBackend {
             config,
             stats,
             duration_recorder,
             duration_stats,
-            peer_addrs,
+            peers: Arc::new(StreamTcpPeers::new(drained_peers, health_checker)),
             discover_handle: Mutex::new(None),
+            health_check_handle,
         });
         backend.update_discover()?;
 
@@ -85,6 +171,7 @@ impl StreamTcpBackend {
             stats,
             Arc::new(duration_recorder),
             duration_stats,
+            Arc::new(Mutex::new(AHashSet::new())),
         )
     }
 
@@ -96,11 +183,24 @@ impl StreamTcpBackend {
             stats,
             self.duration_recorder.clone(),
             self.duration_stats.clone(),
+            self.peers.drained.clone(),
         )
     }
 
     fn select_peer(&self, task_notes: &ServerTaskNotes) -> Option<SocketAddr> {
-        let guard = self.peer_addrs.load();
+        if let Some(peers) = self
+            .peers
+            .health_checker
+            .as_ref()
+            .and_then(|checker| checker.healthy_peers())
+        {
+            let v =
+                self.select_consistent(peers.as_ref(), self.config.peer_pick_policy, task_notes);
+            return Some(*v.inner());
+        }
+
+        // fallback to all peers if no one is known to be healthy
+        let guard = self.peers.selective.load();
         let peers = (*guard).as_ref()?;
 
         let v = self.select_consistent(peers.as_ref(), self.config.peer_pick_policy, task_notes);
@@ -151,17 +251,13 @@ impl Backend for StreamTcpBackend {
                     discover.name()
                 ))?;
 
-        let peer_addrs_container = self.peer_addrs.clone();
+        let peers = self.peers.clone();
         let (abort_handle, abort_reg) = AbortHandle::new_pair();
         let abort_fut = Abortable::new(
             async move {
                 while discover_receiver.changed().await.is_ok() {
                     if let Ok(data) = discover_receiver.borrow().as_ref() {
-                        let mut builder = SelectiveVecBuilder::new();
-                        for v in data {
-                            builder.insert(*v);
-                        }
-                        peer_addrs_container.store(builder.build().map(Arc::new));
+                        peers.set_discovered(data.clone());
                     }
                 }
             },
@@ -179,6 +275,17 @@ impl Backend for StreamTcpBackend {
         Ok(())
     }
 
+    fn drain_peer(&self, peer: SocketAddr, drain: bool) -> anyhow::Result<()> {
+        if self.peers.set_drain(peer, drain) {
+            if drain {
+                info!("backend {}: peer {peer} drained", self.name());
+            } else {
+                info!("backend {}: peer {peer} undrained", self.name());
+            }
+        }
+        Ok(())
+    }
+
     async fn stream_connect(&self, task_notes: &ServerTaskNotes) -> StreamConnectResult {
         let Some(next_addr) = self.select_peer(task_notes) else {
             return Err(StreamConnectError::UpstreamNotResolved);
@@ -195,14 +302,31 @@ impl Backend for StreamTcpBackend {
         .map_err(StreamConnectError::SetupSocketFailed)?;
 
         let time_now = Instant::now();
-        let stream = socket
-            .connect(next_addr)
-            .await
-            .map_err(ConnectError::from)?;
+        let mut stream = match tokio::time::timeout(
+            self.config.connect_timeout,
+            socket.connect(next_addr),
+        )
+        .await
+        {
+            Ok(Ok(stream)) => stream,
+            Ok(Err(e)) => return Err(ConnectError::from(e).into()),
+            Err(_) => return Err(ConnectError::TimedOut.into()),
+        };
         let connect_dur = time_now.elapsed();
         self.stats.add_conn_established();
         self.duration_recorder.record_connect_time(connect_dur);
 
+        if let Some(version) = self.config.proxy_protocol {
+            let mut encoder = ProxyProtocolEncoder::new(version);
+            let bytes = encoder
+                .encode_tcp(task_notes.client_addr(), task_notes.server_addr())
+                .map_err(StreamConnectError::ProxyProtocolEncodeError)?;
+            stream
+                .write_all(bytes) // no need to flush data
+                .await
+                .map_err(StreamConnectError::ProxyProtocolWriteFailed)?;
+        }
+
         let (ups_r, ups_w) = stream.into_split();
         Ok((Box::new(ups_r), Box::new(ups_w)))
     }
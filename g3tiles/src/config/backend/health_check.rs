@@ -0,0 +1,246 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use http::StatusCode;
+use rustls_pki_types::ServerName;
+use yaml_rust::{yaml, Yaml};
+
+use g3_types::net::RustlsClientConfigBuilder;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct HttpHealthCheckProbe {
+    pub(crate) path: String,
+    pub(crate) host: Option<String>,
+    pub(crate) expected_status: Vec<StatusCode>,
+    pub(crate) expected_body: Option<String>,
+    pub(crate) tls_client: Option<RustlsClientConfigBuilder>,
+    pub(crate) tls_name: Option<ServerName<'static>>,
+}
+
+impl Default for HttpHealthCheckProbe {
+    fn default() -> Self {
+        HttpHealthCheckProbe {
+            path: "/".to_string(),
+            host: None,
+            expected_status: Vec::new(),
+            expected_body: None,
+            tls_client: None,
+            tls_name: None,
+        }
+    }
+}
+
+impl HttpHealthCheckProbe {
+    pub(crate) fn status_is_expected(&self, status: StatusCode) -> bool {
+        if self.expected_status.is_empty() {
+            status.is_success()
+        } else {
+            self.expected_status.contains(&status)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum HealthCheckProbe {
+    TcpConnect,
+    TlsHandshake(RustlsClientConfigBuilder, Option<ServerName<'static>>),
+    HttpGet(Box<HttpHealthCheckProbe>),
+    Grpc(String),
+}
+
+impl HealthCheckProbe {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            HealthCheckProbe::TcpConnect => "tcp",
+            HealthCheckProbe::TlsHandshake(_, _) => "tls",
+            HealthCheckProbe::HttpGet(_) => "http",
+            HealthCheckProbe::Grpc(_) => "grpc",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct HealthCheckConfig {
+    pub(crate) probe: HealthCheckProbe,
+    pub(crate) interval: Duration,
+    pub(crate) timeout: Duration,
+    pub(crate) rise: NonZeroUsize,
+    pub(crate) fall: NonZeroUsize,
+    pub(crate) slow_start: Option<Duration>,
+}
+
+impl HealthCheckConfig {
+    fn new(probe: HealthCheckProbe) -> Self {
+        HealthCheckConfig {
+            probe,
+            interval: Duration::from_secs(10),
+            timeout: Duration::from_secs(3),
+            rise: NonZeroUsize::new(2).unwrap(),
+            fall: NonZeroUsize::new(3).unwrap(),
+            slow_start: None,
+        }
+    }
+
+    pub(crate) fn parse(value: &Yaml, lookup_dir: &Path) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => Self::parse_map(map, lookup_dir),
+            Yaml::String(s) => {
+                let probe = match g3_yaml::key::normalize(s).as_str() {
+                    "tcp" | "tcp_connect" => HealthCheckProbe::TcpConnect,
+                    "tls" | "tls_handshake" => {
+                        HealthCheckProbe::TlsHandshake(RustlsClientConfigBuilder::default(), None)
+                    }
+                    "http" | "http_get" => HealthCheckProbe::HttpGet(Box::default()),
+                    "grpc" => HealthCheckProbe::Grpc(String::new()),
+                    _ => return Err(anyhow!("unsupported health check type {s}")),
+                };
+                Ok(HealthCheckConfig::new(probe))
+            }
+            _ => Err(anyhow!("invalid yaml value type for health check config")),
+        }
+    }
+
+    fn parse_map(map: &yaml::Hash, lookup_dir: &Path) -> anyhow::Result<Self> {
+        let probe_type = g3_yaml::hash_get_required_str(map, "type")?;
+        let mut tls_client: Option<RustlsClientConfigBuilder> = None;
+        let mut tls_name: Option<ServerName<'static>> = None;
+        let mut http = HttpHealthCheckProbe::default();
+        let mut grpc_service = String::new();
+        let mut config = HealthCheckConfig::new(HealthCheckProbe::TcpConnect);
+
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "type" => Ok(()),
+            "interval" => {
+                config.interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "timeout" => {
+                config.timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "rise" | "healthy_threshold" => {
+                config.rise = g3_yaml::value::as_nonzero_usize(v)
+                    .context(format!("invalid nonzero usize value for key {k}"))?;
+                Ok(())
+            }
+            "fall" | "unhealthy_threshold" => {
+                config.fall = g3_yaml::value::as_nonzero_usize(v)
+                    .context(format!("invalid nonzero usize value for key {k}"))?;
+                Ok(())
+            }
+            "slow_start" => {
+                let window = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                config.slow_start = if window.is_zero() { None } else { Some(window) };
+                Ok(())
+            }
+            "tls_client" => {
+                let builder = g3_yaml::value::as_rustls_client_config_builder(v, Some(lookup_dir))
+                    .context(format!(
+                        "invalid rustls tls client config value for key {k}"
+                    ))?;
+                tls_client = Some(builder);
+                Ok(())
+            }
+            "tls_name" => {
+                let name = g3_yaml::value::as_rustls_server_name(v)
+                    .context(format!("invalid tls server name value for key {k}"))?;
+                tls_name = Some(name);
+                Ok(())
+            }
+            "path" => {
+                http.path = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                if !http.path.starts_with('/') {
+                    return Err(anyhow!("the path should start with '/'"));
+                }
+                Ok(())
+            }
+            "host" => {
+                let host = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                http.host = Some(host);
+                Ok(())
+            }
+            "expected_status" | "expect_status" => {
+                http.expected_status = parse_status_list(v)
+                    .context(format!("invalid http status code list value for key {k}"))?;
+                Ok(())
+            }
+            "expected_body" | "expect_body" => {
+                let body = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                http.expected_body = Some(body);
+                Ok(())
+            }
+            "service" => {
+                grpc_service = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        config.probe = match g3_yaml::key::normalize(probe_type).as_str() {
+            "tcp" | "tcp_connect" => HealthCheckProbe::TcpConnect,
+            "tls" | "tls_handshake" => {
+                let tls_client = tls_client.unwrap_or_default();
+                HealthCheckProbe::TlsHandshake(tls_client, tls_name)
+            }
+            "http" | "http_get" => {
+                http.tls_client = tls_client;
+                http.tls_name = tls_name;
+                HealthCheckProbe::HttpGet(Box::new(http))
+            }
+            "grpc" => HealthCheckProbe::Grpc(grpc_service),
+            _ => return Err(anyhow!("unsupported health check type {probe_type}")),
+        };
+
+        if config.interval.is_zero() {
+            return Err(anyhow!("interval should not be zero"));
+        }
+        if config.timeout.is_zero() {
+            return Err(anyhow!("timeout should not be zero"));
+        }
+        Ok(config)
+    }
+}
+
+fn parse_status_list(v: &Yaml) -> anyhow::Result<Vec<StatusCode>> {
+    let parse_one = |v: &Yaml| -> anyhow::Result<StatusCode> {
+        let code = g3_yaml::value::as_u16(v)?;
+        StatusCode::from_u16(code).map_err(|e| anyhow!("invalid status code {code}: {e}"))
+    };
+
+    match v {
+        Yaml::Array(seq) => {
+            let mut list = Vec::with_capacity(seq.len());
+            for (i, v) in seq.iter().enumerate() {
+                let code = parse_one(v).context(format!("invalid status code value for #{i}"))?;
+                list.push(code);
+            }
+            Ok(list)
+        }
+        _ => Ok(vec![parse_one(v)?]),
+    }
+}
@@ -23,6 +23,7 @@ use g3_types::metrics::NodeName;
 use g3_yaml::{HybridParser, YamlDocPosition};
 
 pub(crate) mod dummy_close;
+pub(crate) mod health_check;
 #[cfg(feature = "quic")]
 pub(crate) mod keyless_quic;
 pub(crate) mod keyless_tcp;
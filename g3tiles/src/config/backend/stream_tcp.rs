@@ -15,6 +15,7 @@
  */
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::{anyhow, Context};
 use yaml_rust::{yaml, Yaml};
@@ -22,8 +23,10 @@ use yaml_rust::{yaml, Yaml};
 use g3_histogram::HistogramMetricsConfig;
 use g3_types::collection::SelectivePickPolicy;
 use g3_types::metrics::{NodeName, StaticMetricsTags};
+use g3_types::net::ProxyProtocolVersion;
 use g3_yaml::YamlDocPosition;
 
+use super::health_check::HealthCheckConfig;
 use super::{AnyBackendConfig, BackendConfig, BackendConfigDiffAction};
 use crate::config::discover::DiscoverRegisterData;
 
@@ -38,6 +41,9 @@ pub(crate) struct StreamTcpBackendConfig {
     pub(crate) peer_pick_policy: SelectivePickPolicy,
     pub(crate) extra_metrics_tags: Option<Arc<StaticMetricsTags>>,
     pub(crate) duration_stats: HistogramMetricsConfig,
+    pub(crate) connect_timeout: Duration,
+    pub(crate) proxy_protocol: Option<ProxyProtocolVersion>,
+    pub(crate) health_check: Option<HealthCheckConfig>,
 }
 
 impl StreamTcpBackendConfig {
@@ -50,6 +56,9 @@ impl StreamTcpBackendConfig {
             peer_pick_policy: SelectivePickPolicy::Random,
             extra_metrics_tags: None,
             duration_stats: HistogramMetricsConfig::default(),
+            connect_timeout: Duration::from_secs(30),
+            proxy_protocol: None,
+            health_check: None,
         }
     }
 
@@ -107,6 +116,24 @@ impl StreamTcpBackendConfig {
                 )?;
                 Ok(())
             }
+            "connect_timeout" => {
+                self.connect_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "proxy_protocol" => {
+                let version = g3_yaml::value::as_proxy_protocol_version(v)
+                    .context(format!("invalid ProxyProtocolVersion value for key {k}"))?;
+                self.proxy_protocol = Some(version);
+                Ok(())
+            }
+            "health_check" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(self.position.as_ref())?;
+                let config = HealthCheckConfig::parse(v, lookup_dir)
+                    .context(format!("invalid health check config value for key {k}"))?;
+                self.health_check = Some(config);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
@@ -52,6 +52,12 @@ pub(crate) fn load(v: &Yaml, conf_dir: &Path) -> anyhow::Result<()> {
                     default_log_config = Some(config);
                     Ok(())
                 }
+                "kafka" => {
+                    let config = LogConfig::parse_kafka_yaml(v, crate::build::PKG_NAME)
+                        .context(format!("invalid kafka config value for key {k}"))?;
+                    default_log_config = Some(config);
+                    Ok(())
+                }
                 "task" => {
                     let config = LogConfig::parse_yaml(v, conf_dir, crate::build::PKG_NAME)
                         .context(format!("invalid value for key {k}"))?;
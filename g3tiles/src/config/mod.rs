@@ -75,6 +75,17 @@ fn reload_doc(map: &yaml::Hash) -> anyhow::Result<()> {
     Ok(())
 }
 
+const MAIN_CONF_KEYS: &[&str] = &[
+    "runtime",
+    "worker",
+    "log",
+    "stat",
+    "controller",
+    "server",
+    "discover",
+    "backend",
+];
+
 fn load_doc(map: &yaml::Hash) -> anyhow::Result<()> {
     let conf_dir =
         g3_daemon::opts::config_dir().ok_or_else(|| anyhow!("no valid config dir has been set"))?;
@@ -87,7 +98,10 @@ fn load_doc(map: &yaml::Hash) -> anyhow::Result<()> {
         "server" => server::load_all(v, conf_dir),
         "discover" => discover::load_all(v, conf_dir),
         "backend" => backend::load_all(v, conf_dir),
-        _ => Err(anyhow!("invalid key {k} in main conf")),
+        _ => match g3_yaml::key::suggest(k, MAIN_CONF_KEYS) {
+            Some(s) => Err(anyhow!("invalid key {k} in main conf, did you mean {s}?")),
+            None => Err(anyhow!("invalid key {k} in main conf")),
+        },
     })?;
     Ok(())
 }
@@ -0,0 +1,216 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use ascii::AsciiString;
+use yaml_rust::{yaml, Yaml};
+
+use g3_types::acl::AclNetworkRuleBuilder;
+use g3_types::metrics::{NodeName, StaticMetricsTags};
+use g3_yaml::YamlDocPosition;
+
+use super::{ServerConfig, IDLE_CHECK_DEFAULT_DURATION, IDLE_CHECK_MAXIMUM_DURATION};
+use crate::config::server::{AnyServerConfig, ServerConfigDiffAction};
+
+const SERVER_CONFIG_TYPE: &str = "H3Proxy";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum H3BackendProtocol {
+    #[default]
+    Http1,
+    Http2,
+}
+
+impl H3BackendProtocol {
+    fn parse_yaml(v: &Yaml) -> anyhow::Result<Self> {
+        let s = g3_yaml::value::as_string(v)?;
+        match g3_yaml::key::normalize(&s).as_str() {
+            "http1" | "http/1.1" | "http_1_1" | "h1" => Ok(H3BackendProtocol::Http1),
+            "http2" | "h2" => Ok(H3BackendProtocol::Http2),
+            _ => Err(anyhow!("unsupported backend protocol {s}")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct H3ProxyServerConfig {
+    name: NodeName,
+    position: Option<YamlDocPosition>,
+    pub(crate) shared_logger: Option<AsciiString>,
+    pub(crate) ingress_net_filter: Option<AclNetworkRuleBuilder>,
+    pub(crate) extra_metrics_tags: Option<Arc<StaticMetricsTags>>,
+    pub(crate) task_idle_check_duration: Duration,
+    pub(crate) task_idle_max_count: i32,
+    pub(crate) spawn_task_unconstrained: bool,
+    pub(crate) backend: NodeName,
+    pub(crate) backend_protocol: H3BackendProtocol,
+    pub(crate) rsp_head_recv_timeout: Duration,
+    pub(crate) rsp_head_max_size: usize,
+    pub(crate) body_line_max_len: usize,
+    pub(crate) log_uri_max_chars: usize,
+}
+
+impl H3ProxyServerConfig {
+    pub(crate) fn new(position: Option<YamlDocPosition>) -> Self {
+        H3ProxyServerConfig {
+            name: NodeName::default(),
+            position,
+            shared_logger: None,
+            ingress_net_filter: None,
+            extra_metrics_tags: None,
+            task_idle_check_duration: IDLE_CHECK_DEFAULT_DURATION,
+            task_idle_max_count: 1,
+            spawn_task_unconstrained: false,
+            backend: NodeName::default(),
+            backend_protocol: H3BackendProtocol::default(),
+            rsp_head_recv_timeout: Duration::from_secs(60),
+            rsp_head_max_size: 64 * 1024,
+            body_line_max_len: 8192,
+            log_uri_max_chars: 1024,
+        }
+    }
+
+    pub(super) fn parse(
+        map: &yaml::Hash,
+        position: Option<YamlDocPosition>,
+    ) -> anyhow::Result<Self> {
+        let mut server = H3ProxyServerConfig::new(position);
+
+        g3_yaml::foreach_kv(map, |k, v| server.set(k, v))?;
+
+        server.check()?;
+        Ok(server)
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.name.is_empty() {
+            return Err(anyhow!("name is not set"));
+        }
+        if self.backend.is_empty() {
+            return Err(anyhow!("no backend is set"));
+        }
+        if self.task_idle_check_duration > IDLE_CHECK_MAXIMUM_DURATION {
+            self.task_idle_check_duration = IDLE_CHECK_MAXIMUM_DURATION;
+        }
+        Ok(())
+    }
+
+    fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
+        match g3_yaml::key::normalize(k).as_str() {
+            super::CONFIG_KEY_SERVER_TYPE => Ok(()),
+            super::CONFIG_KEY_SERVER_NAME => {
+                self.name = g3_yaml::value::as_metrics_name(v)?;
+                Ok(())
+            }
+            "shared_logger" => {
+                let name = g3_yaml::value::as_ascii(v)?;
+                self.shared_logger = Some(name);
+                Ok(())
+            }
+            "extra_metrics_tags" => {
+                let tags = g3_yaml::value::as_static_metrics_tags(v)
+                    .context(format!("invalid static metrics tags value for key {k}"))?;
+                self.extra_metrics_tags = Some(Arc::new(tags));
+                Ok(())
+            }
+            "ingress_network_filter" | "ingress_net_filter" => {
+                let filter = g3_yaml::value::acl::as_ingress_network_rule_builder(v).context(
+                    format!("invalid ingress network acl rule value for key {k}"),
+                )?;
+                self.ingress_net_filter = Some(filter);
+                Ok(())
+            }
+            "task_idle_check_duration" => {
+                self.task_idle_check_duration = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "task_idle_max_count" => {
+                self.task_idle_max_count =
+                    g3_yaml::value::as_i32(v).context(format!("invalid i32 value for key {k}"))?;
+                Ok(())
+            }
+            "spawn_task_unconstrained" | "task_unconstrained" => {
+                self.spawn_task_unconstrained = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
+            "backend" => {
+                self.backend = g3_yaml::value::as_metrics_name(v)?;
+                Ok(())
+            }
+            "backend_protocol" => {
+                self.backend_protocol = H3BackendProtocol::parse_yaml(v)
+                    .context(format!("invalid backend protocol value for key {k}"))?;
+                Ok(())
+            }
+            "rsp_head_recv_timeout" | "response_head_recv_timeout" => {
+                self.rsp_head_recv_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "rsp_head_max_size" | "response_head_max_size" => {
+                self.rsp_head_max_size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            "body_line_max_length" | "body_line_max_len" => {
+                self.body_line_max_len = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            "log_uri_max_chars" | "uri_log_max_chars" => {
+                self.log_uri_max_chars = g3_yaml::value::as_usize(v)
+                    .context(format!("invalid usize value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        }
+    }
+}
+
+impl ServerConfig for H3ProxyServerConfig {
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    fn position(&self) -> Option<YamlDocPosition> {
+        self.position.clone()
+    }
+
+    fn server_type(&self) -> &'static str {
+        SERVER_CONFIG_TYPE
+    }
+
+    fn diff_action(&self, new: &AnyServerConfig) -> ServerConfigDiffAction {
+        let new = match new {
+            AnyServerConfig::H3Proxy(config) => config,
+            _ => return ServerConfigDiffAction::SpawnNew,
+        };
+
+        if self.eq(new) {
+            return ServerConfigDiffAction::NoAction;
+        }
+
+        ServerConfigDiffAction::ReloadOnlyConfig
+    }
+
+    fn shared_logger(&self) -> Option<&str> {
+        self.shared_logger.as_ref().map(|s| s.as_str())
+    }
+}
@@ -32,9 +32,12 @@ pub(crate) mod dummy_close;
 pub(crate) mod plain_quic_port;
 pub(crate) mod plain_tcp_port;
 
+#[cfg(feature = "quic")]
+pub(crate) mod h3_proxy;
 pub(crate) mod keyless_proxy;
 pub(crate) mod openssl_proxy;
 pub(crate) mod rustls_proxy;
+pub(crate) mod tcp_stream;
 
 mod registry;
 
@@ -86,6 +89,9 @@ pub(crate) enum AnyServerConfig {
     OpensslProxy(openssl_proxy::OpensslProxyServerConfig),
     RustlsProxy(rustls_proxy::RustlsProxyServerConfig),
     KeylessProxy(keyless_proxy::KeylessProxyServerConfig),
+    #[cfg(feature = "quic")]
+    H3Proxy(h3_proxy::H3ProxyServerConfig),
+    TcpStream(tcp_stream::TcpStreamServerConfig),
 }
 
 macro_rules! impl_transparent0 {
@@ -99,6 +105,9 @@ macro_rules! impl_transparent0 {
                 AnyServerConfig::OpensslProxy(s) => s.$f(),
                 AnyServerConfig::RustlsProxy(s) => s.$f(),
                 AnyServerConfig::KeylessProxy(s) => s.$f(),
+                #[cfg(feature = "quic")]
+                AnyServerConfig::H3Proxy(s) => s.$f(),
+                AnyServerConfig::TcpStream(s) => s.$f(),
             }
         }
     };
@@ -115,6 +124,9 @@ macro_rules! impl_transparent1 {
                 AnyServerConfig::OpensslProxy(s) => s.$f(p),
                 AnyServerConfig::RustlsProxy(s) => s.$f(p),
                 AnyServerConfig::KeylessProxy(s) => s.$f(p),
+                #[cfg(feature = "quic")]
+                AnyServerConfig::H3Proxy(s) => s.$f(p),
+                AnyServerConfig::TcpStream(s) => s.$f(p),
             }
         }
     };
@@ -205,6 +217,17 @@ fn load_server(
                 .context("failed to load this KeylessProxy server")?;
             Ok(AnyServerConfig::KeylessProxy(server))
         }
+        #[cfg(feature = "quic")]
+        "h3_proxy" | "h3proxy" | "http3_proxy" | "http3proxy" => {
+            let server = h3_proxy::H3ProxyServerConfig::parse(map, position)
+                .context("failed to load this H3Proxy server")?;
+            Ok(AnyServerConfig::H3Proxy(server))
+        }
+        "tcp_stream" | "tcpstream" => {
+            let server = tcp_stream::TcpStreamServerConfig::parse(map, position)
+                .context("failed to load this TcpStream server")?;
+            Ok(AnyServerConfig::TcpStream(server))
+        }
         _ => Err(anyhow!("unsupported server type {}", server_type)),
     }
 }
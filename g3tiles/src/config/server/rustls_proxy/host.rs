@@ -14,6 +14,7 @@
  * limitations under the License.
  */
 
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -33,10 +34,18 @@ use g3_types::net::{
 use g3_types::route::AlpnMatch;
 use g3_yaml::{YamlDocPosition, YamlMapCallback};
 
+use crate::module::acme::{AcmeChallengeType, AcmeConfig, ACME_TLS_ALPN_PROTOCOL};
+use crate::module::cert_dir::CertDirResolver;
+
+const CERT_DIR_CHECK_INTERVAL_MIN: Duration = Duration::from_secs(1);
+
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct RustlsHostConfig {
     name: String,
     cert_pairs: Vec<RustlsCertificatePair>,
+    cert_dir: Option<PathBuf>,
+    cert_dir_check_interval: Duration,
+    acme: Option<AcmeConfig>,
     client_auth: bool,
     client_auth_certs: Vec<CertificateDer<'static>>,
     use_session_ticket: bool,
@@ -54,6 +63,9 @@ impl Default for RustlsHostConfig {
         RustlsHostConfig {
             name: String::new(),
             cert_pairs: Vec::with_capacity(1),
+            cert_dir: None,
+            cert_dir_check_interval: Duration::from_secs(60),
+            acme: None,
             client_auth: false,
             client_auth_certs: Vec::new(),
             use_session_ticket: true,
@@ -82,10 +94,12 @@ impl NamedValue for RustlsHostConfig {
 }
 
 impl RustlsHostConfig {
+    /// Build the server tls config, and also the one for TLS-ALPN-01 validation
+    /// connections if acme is enabled with that challenge type.
     pub(crate) fn build_tls_config(
         &self,
         tls_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
-    ) -> anyhow::Result<Arc<ServerConfig>> {
+    ) -> anyhow::Result<(Arc<ServerConfig>, Option<Arc<ServerConfig>>)> {
         let config_builder = ServerConfig::builder();
         let config_builder = if self.client_auth {
             let mut root_store = RootCertStore::empty();
@@ -117,7 +131,28 @@ impl RustlsHostConfig {
                 .push_cert_pair(pair)
                 .context(format!("failed to add cert pair {i}"))?;
         }
-        let mut config = config_builder.with_cert_resolver(Arc::new(cert_resolver));
+        let mut acme_tls_config = None;
+        let mut config = if let Some(dir) = &self.cert_dir {
+            let dir_resolver =
+                CertDirResolver::new_spawned(dir, self.cert_dir_check_interval, cert_resolver)
+                    .context(format!(
+                        "failed to load certificates from {}",
+                        dir.display()
+                    ))?;
+            if let Some(acme) = &self.acme {
+                let tls_alpn_resolver = crate::module::acme::spawn(acme, dir, &dir_resolver);
+                if acme.challenge == AcmeChallengeType::TlsAlpn01 {
+                    let mut acme_config = ServerConfig::builder()
+                        .with_no_client_auth()
+                        .with_cert_resolver(tls_alpn_resolver);
+                    acme_config.alpn_protocols = vec![ACME_TLS_ALPN_PROTOCOL.to_vec()];
+                    acme_tls_config = Some(Arc::new(acme_config));
+                }
+            }
+            config_builder.with_cert_resolver(dir_resolver)
+        } else {
+            config_builder.with_cert_resolver(Arc::new(cert_resolver))
+        };
 
         config.set_session_cache(self.no_session_cache);
         config.set_session_ticketer(self.use_session_ticket, tls_ticketer)?;
@@ -128,7 +163,7 @@ impl RustlsHostConfig {
             }
         }
 
-        Ok(Arc::new(config))
+        Ok((Arc::new(config), acme_tls_config))
     }
 }
 
@@ -156,6 +191,25 @@ impl YamlMapCallback for RustlsHostConfig {
                 .context(format!("invalid rustls cert pair list value for key {key}"))?;
                 Ok(())
             }
+            "cert_dir" | "cert_directory" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
+                let dir = g3_yaml::value::as_dir_path(value, lookup_dir, false)
+                    .context(format!("invalid directory path value for key {key}"))?;
+                self.cert_dir = Some(dir);
+                Ok(())
+            }
+            "cert_dir_check_interval" => {
+                self.cert_dir_check_interval = g3_yaml::humanize::as_duration(value)
+                    .context(format!("invalid humanize duration value for key {key}"))?;
+                Ok(())
+            }
+            "acme" => {
+                let lookup_dir = g3_daemon::config::get_lookup_dir(doc)?;
+                let acme = AcmeConfig::parse_yaml(value, lookup_dir)
+                    .context(format!("invalid acme config value for key {key}"))?;
+                self.acme = Some(acme);
+                Ok(())
+            }
             "enable_client_auth" => {
                 self.client_auth = g3_yaml::value::as_bool(value)?;
                 Ok(())
@@ -224,9 +278,17 @@ impl YamlMapCallback for RustlsHostConfig {
         if self.name.is_empty() {
             return Err(anyhow!("no name set"));
         }
-        if self.cert_pairs.is_empty() {
+        if self.cert_pairs.is_empty() && self.cert_dir.is_none() {
             return Err(anyhow!("no certificate set"));
         }
+        if self.acme.is_some() && self.cert_dir.is_none() {
+            return Err(anyhow!(
+                "cert_dir is required to store the acme certificates"
+            ));
+        }
+        if self.cert_dir_check_interval < CERT_DIR_CHECK_INTERVAL_MIN {
+            self.cert_dir_check_interval = CERT_DIR_CHECK_INTERVAL_MIN;
+        }
         if self.backends.is_empty() {
             return Err(anyhow!("no backend service set"));
         }
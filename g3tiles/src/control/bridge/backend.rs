@@ -0,0 +1,34 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+
+use anyhow::anyhow;
+
+use g3_types::metrics::NodeName;
+
+pub(in crate::control) async fn drain_backend_peer(
+    name: String,
+    peer: SocketAddr,
+    drain: bool,
+) -> anyhow::Result<()> {
+    let name = unsafe { NodeName::new_unchecked(name) };
+    g3_daemon::runtime::main_handle()
+        .ok_or(anyhow!("unable to get main runtime handle"))?
+        .spawn(async move { crate::backend::drain_peer(&name, peer, drain).await })
+        .await
+        .map_err(|e| anyhow!("failed to spawn drain task: {e}"))?
+}
@@ -16,3 +16,6 @@
 
 mod reload;
 pub(super) use reload::{reload_backend, reload_discover, reload_server};
+
+mod backend;
+pub(super) use backend::drain_backend_peer;
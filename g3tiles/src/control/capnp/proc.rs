@@ -14,9 +14,13 @@
  * limitations under the License.
  */
 
+use std::net::SocketAddr;
+use std::str::FromStr;
+
 use capnp::capability::Promise;
 use capnp_rpc::pry;
 
+use g3_daemon::control::CtlAccessLevel;
 use g3_types::metrics::NodeName;
 
 use g3tiles_proto::proc_capnp::proc_control;
@@ -25,7 +29,15 @@ use g3tiles_proto::types_capnp::fetch_result;
 
 use super::set_operation_result;
 
-pub(super) struct ProcControlImpl;
+pub(super) struct ProcControlImpl {
+    access: CtlAccessLevel,
+}
+
+impl ProcControlImpl {
+    pub(super) fn new(access: CtlAccessLevel) -> Self {
+        ProcControlImpl { access }
+    }
+}
 
 impl proc_control::Server for ProcControlImpl {
     fn version(
@@ -42,6 +54,7 @@ impl proc_control::Server for ProcControlImpl {
         _params: proc_control::OfflineParams,
         mut results: proc_control::OfflineResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         Promise::from_future(async move {
             g3_daemon::control::quit::start_graceful_shutdown().await;
             set_operation_result(results.get().init_result(), Ok(()));
@@ -54,6 +67,7 @@ impl proc_control::Server for ProcControlImpl {
         _params: proc_control::CancelShutdownParams,
         mut results: proc_control::CancelShutdownResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         Promise::from_future(async move {
             let r = g3_daemon::control::quit::cancel_graceful_shutdown().await;
             set_operation_result(results.get().init_result(), r);
@@ -66,6 +80,7 @@ impl proc_control::Server for ProcControlImpl {
         _params: proc_control::ReleaseControllerParams,
         mut results: proc_control::ReleaseControllerResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         Promise::from_future(async move {
             let r = g3_daemon::control::quit::release_controller().await;
             set_operation_result(results.get().init_result(), r);
@@ -91,6 +106,7 @@ impl proc_control::Server for ProcControlImpl {
         params: proc_control::ReloadServerParams,
         mut results: proc_control::ReloadServerResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let server = pry!(pry!(pry!(params.get()).get_name()).to_string());
         Promise::from_future(async move {
             let r = crate::control::bridge::reload_server(server, None).await;
@@ -117,6 +133,7 @@ impl proc_control::Server for ProcControlImpl {
         _params: proc_control::ForceQuitOfflineServersParams,
         mut results: proc_control::ForceQuitOfflineServersResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         crate::serve::force_quit_offline_servers();
         results.get().init_result().set_ok("success");
         Promise::ok(())
@@ -127,6 +144,7 @@ impl proc_control::Server for ProcControlImpl {
         params: proc_control::ForceQuitOfflineServerParams,
         mut results: proc_control::ForceQuitOfflineServerResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let server = pry!(pry!(pry!(params.get()).get_name()).to_str());
         let server = unsafe { NodeName::new_unchecked(server) };
         crate::serve::force_quit_offline_server(&server);
@@ -139,6 +157,7 @@ impl proc_control::Server for ProcControlImpl {
         params: proc_control::ReloadDiscoverParams,
         mut results: proc_control::ReloadDiscoverResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let discover = pry!(pry!(pry!(params.get()).get_name()).to_string());
         Promise::from_future(async move {
             let r = crate::control::bridge::reload_discover(discover, None).await;
@@ -165,6 +184,7 @@ impl proc_control::Server for ProcControlImpl {
         params: proc_control::ReloadBackendParams,
         mut results: proc_control::ReloadBackendResults,
     ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
         let connector = pry!(pry!(pry!(params.get()).get_name()).to_string());
         Promise::from_future(async move {
             let r = crate::control::bridge::reload_backend(connector, None).await;
@@ -185,6 +205,58 @@ impl proc_control::Server for ProcControlImpl {
         }
         Promise::ok(())
     }
+
+    fn drain_backend_peer(
+        &mut self,
+        params: proc_control::DrainBackendPeerParams,
+        mut results: proc_control::DrainBackendPeerResults,
+    ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
+        let params = pry!(params.get());
+        let backend = pry!(pry!(params.get_name()).to_string());
+        let peer = pry!(pry!(params.get_peer()).to_str());
+        let peer = match SocketAddr::from_str(peer) {
+            Ok(addr) => addr,
+            Err(e) => {
+                set_operation_result(
+                    results.get().init_result(),
+                    Err(anyhow::anyhow!("invalid peer address {peer}: {e}")),
+                );
+                return Promise::ok(());
+            }
+        };
+        Promise::from_future(async move {
+            let r = crate::control::bridge::drain_backend_peer(backend, peer, true).await;
+            set_operation_result(results.get().init_result(), r);
+            Ok(())
+        })
+    }
+
+    fn undrain_backend_peer(
+        &mut self,
+        params: proc_control::UndrainBackendPeerParams,
+        mut results: proc_control::UndrainBackendPeerResults,
+    ) -> Promise<(), capnp::Error> {
+        pry!(self.access.check_admin());
+        let params = pry!(params.get());
+        let backend = pry!(pry!(params.get_name()).to_string());
+        let peer = pry!(pry!(params.get_peer()).to_str());
+        let peer = match SocketAddr::from_str(peer) {
+            Ok(addr) => addr,
+            Err(e) => {
+                set_operation_result(
+                    results.get().init_result(),
+                    Err(anyhow::anyhow!("invalid peer address {peer}: {e}")),
+                );
+                return Promise::ok(());
+            }
+        };
+        Promise::from_future(async move {
+            let r = crate::control::bridge::drain_backend_peer(backend, peer, false).await;
+            set_operation_result(results.get().init_result(), r);
+            Ok(())
+        })
+    }
 }
 
 fn set_fetch_result<'a, T>(
@@ -24,6 +24,9 @@ pub(crate) mod tcp_connect;
 
 pub(crate) mod keyless;
 
+#[cfg(feature = "quic")]
+pub(crate) mod h3_stream;
+
 pub(crate) fn get_logger(server_type: &str, server_name: &NodeName) -> Logger {
     let config = crate::config::log::get_task_default_config();
     let logger_name = format!("lt-{server_name}");
@@ -17,11 +17,13 @@
 use slog::{slog_info, Logger};
 
 use g3_slog_types::{LtDateTime, LtDuration, LtUuid};
+use g3_types::net::OpensslCertIdentity;
 
 use crate::serve::{ServerTaskError, ServerTaskNotes};
 
 pub(crate) struct TaskLogForTcpConnect<'a> {
     pub(crate) task_notes: &'a ServerTaskNotes,
+    pub(crate) client_cert: Option<&'a OpensslCertIdentity>,
     pub(crate) client_rd_bytes: u64,
     pub(crate) client_wr_bytes: u64,
     pub(crate) remote_rd_bytes: u64,
@@ -37,6 +39,8 @@ impl TaskLogForTcpConnect<'_> {
             "start_at" => LtDateTime(&self.task_notes.start_at),
             "server_addr" => self.task_notes.server_addr(),
             "client_addr" => self.task_notes.client_addr(),
+            "client_cert_subject" => self.client_cert.map(|c| c.subject()),
+            "client_cert_sha256" => self.client_cert.map(|c| c.fingerprint()),
             "reason" => e.brief(),
             "wait_time" => LtDuration(self.task_notes.wait_time),
             "ready_time" => LtDuration(self.task_notes.ready_time),
@@ -0,0 +1,279 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use arc_swap::ArcSwap;
+use log::debug;
+use openssl::asn1::{Asn1Object, Asn1OctetString, Asn1Time};
+use openssl::bn::{BigNum, MsbOption};
+use openssl::ec::{EcGroup, EcKey};
+use openssl::hash::{hash, MessageDigest};
+use openssl::nid::Nid;
+use openssl::pkey::PKey;
+use openssl::x509::extension::SubjectAlternativeName;
+use openssl::x509::{X509Builder, X509Extension, X509NameBuilder};
+use rustls::crypto::CryptoProvider;
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use g3_http::HttpMethodLine;
+
+use super::AcmeChallengeType;
+
+/// The ALPN protocol used by the TLS-ALPN-01 challenge, see RFC 8737.
+pub(crate) const ACME_TLS_ALPN_PROTOCOL: &[u8] = b"acme-tls/1";
+
+const ACME_IDENTIFIER_OID: &str = "1.3.6.1.5.5.7.1.31";
+const HTTP01_PATH_PREFIX: &str = "/.well-known/acme-challenge/";
+const HTTP01_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Resolve the validation certificates for the TLS-ALPN-01 challenge by SNI.
+#[derive(Debug, Default)]
+pub(crate) struct AcmeTlsAlpnResolver {
+    keys: ArcSwap<AHashMap<String, Arc<CertifiedKey>>>,
+}
+
+impl AcmeTlsAlpnResolver {
+    fn set(&self, name: &str, key: Option<Arc<CertifiedKey>>) {
+        let mut keys = self.keys.load().as_ref().clone();
+        match key {
+            Some(key) => keys.insert(name.to_string(), key),
+            None => keys.remove(name),
+        };
+        self.keys.store(Arc::new(keys));
+    }
+}
+
+impl ResolvesServerCert for AcmeTlsAlpnResolver {
+    fn resolve(&self, client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+        let sni = client_hello.server_name()?;
+        self.keys.load().get(&sni.to_ascii_lowercase()).cloned()
+    }
+}
+
+pub(super) struct ChallengeSolver {
+    challenge: AcmeChallengeType,
+    http01_listen: SocketAddr,
+    http01_tokens: Arc<Mutex<AHashMap<String, String>>>,
+    tls_alpn_resolver: Arc<AcmeTlsAlpnResolver>,
+}
+
+impl ChallengeSolver {
+    pub(super) fn new(
+        challenge: AcmeChallengeType,
+        http01_listen: SocketAddr,
+        tls_alpn_resolver: Arc<AcmeTlsAlpnResolver>,
+    ) -> Self {
+        ChallengeSolver {
+            challenge,
+            http01_listen,
+            http01_tokens: Arc::new(Mutex::new(AHashMap::new())),
+            tls_alpn_resolver,
+        }
+    }
+
+    #[inline]
+    pub(super) fn challenge_type(&self) -> AcmeChallengeType {
+        self.challenge
+    }
+
+    /// Start the challenge responder if needed, it will be stopped when the returned value is dropped.
+    pub(super) async fn start(&self) -> anyhow::Result<Option<Http01Responder>> {
+        match self.challenge {
+            AcmeChallengeType::Http01 => {
+                let listener = TcpListener::bind(self.http01_listen).await.map_err(|e| {
+                    anyhow!(
+                        "failed to listen on {} for http-01 challenge: {e}",
+                        self.http01_listen
+                    )
+                })?;
+                let tokens = self.http01_tokens.clone();
+                let handle = tokio::spawn(async move { run_http01(listener, tokens).await });
+                Ok(Some(Http01Responder { handle }))
+            }
+            AcmeChallengeType::TlsAlpn01 => Ok(None),
+        }
+    }
+
+    pub(super) fn add(
+        &self,
+        name: &str,
+        token: &str,
+        key_authorization: &str,
+    ) -> anyhow::Result<()> {
+        match self.challenge {
+            AcmeChallengeType::Http01 => {
+                let mut tokens = self.http01_tokens.lock().unwrap();
+                tokens.insert(token.to_string(), key_authorization.to_string());
+            }
+            AcmeChallengeType::TlsAlpn01 => {
+                let key = build_tls_alpn_cert(name, key_authorization)?;
+                self.tls_alpn_resolver.set(name, Some(Arc::new(key)));
+            }
+        }
+        Ok(())
+    }
+
+    pub(super) fn remove(&self, name: &str, token: &str) {
+        match self.challenge {
+            AcmeChallengeType::Http01 => {
+                let mut tokens = self.http01_tokens.lock().unwrap();
+                tokens.remove(token);
+            }
+            AcmeChallengeType::TlsAlpn01 => self.tls_alpn_resolver.set(name, None),
+        }
+    }
+}
+
+pub(super) struct Http01Responder {
+    handle: JoinHandle<()>,
+}
+
+impl Drop for Http01Responder {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+async fn run_http01(listener: TcpListener, tokens: Arc<Mutex<AHashMap<String, String>>>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, peer)) => {
+                let tokens = tokens.clone();
+                tokio::spawn(async move {
+                    let r =
+                        tokio::time::timeout(HTTP01_REQUEST_TIMEOUT, serve_http01(stream, tokens))
+                            .await;
+                    match r {
+                        Ok(Ok(_)) => {}
+                        Ok(Err(e)) => debug!("failed to serve http-01 request from {peer}: {e}"),
+                        Err(_) => debug!("timeout to serve http-01 request from {peer}"),
+                    }
+                });
+            }
+            Err(e) => debug!("failed to accept http-01 connection: {e}"),
+        }
+    }
+}
+
+async fn serve_http01(
+    stream: TcpStream,
+    tokens: Arc<Mutex<AHashMap<String, String>>>,
+) -> anyhow::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut line = Vec::with_capacity(256);
+    reader
+        .read_until(b'\n', &mut line)
+        .await
+        .map_err(|e| anyhow!("failed to read request line: {e}"))?;
+    let method_line =
+        HttpMethodLine::parse(&line).map_err(|e| anyhow!("invalid request line: {e}"))?;
+    let key_authorization = method_line
+        .uri
+        .strip_prefix(HTTP01_PATH_PREFIX)
+        .filter(|_| method_line.method == "GET")
+        .and_then(|token| tokens.lock().unwrap().get(token).cloned());
+
+    // drain the request header
+    loop {
+        line.clear();
+        let nr = reader
+            .read_until(b'\n', &mut line)
+            .await
+            .map_err(|e| anyhow!("failed to read request header: {e}"))?;
+        if nr == 0 || line == b"\r\n" || line == b"\n" {
+            break;
+        }
+    }
+
+    let rsp = match key_authorization {
+        Some(body) => format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/octet-stream\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        ),
+        None => "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+            .to_string(),
+    };
+    let mut stream = reader.into_inner();
+    stream
+        .write_all(rsp.as_bytes())
+        .await
+        .map_err(|e| anyhow!("failed to send response: {e}"))?;
+    let _ = stream.shutdown().await;
+    Ok(())
+}
+
+/// Build the self-signed validation certificate for the TLS-ALPN-01 challenge, see RFC 8737 3.
+fn build_tls_alpn_cert(name: &str, key_authorization: &str) -> anyhow::Result<CertifiedKey> {
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
+    let pkey = PKey::from_ec_key(EcKey::generate(&group)?)?;
+
+    let mut builder = X509Builder::new()?;
+    builder.set_version(2)?;
+    let mut serial = BigNum::new()?;
+    serial.rand(128, MsbOption::MAYBE_ZERO, false)?;
+    let serial = serial.to_asn1_integer()?;
+    builder.set_serial_number(&serial)?;
+    let mut subject = X509NameBuilder::new()?;
+    subject.append_entry_by_nid(Nid::COMMONNAME, name)?;
+    let subject = subject.build();
+    builder.set_subject_name(&subject)?;
+    builder.set_issuer_name(&subject)?;
+    builder.set_pubkey(&pkey)?;
+    let not_before = Asn1Time::days_from_now(0)?;
+    builder.set_not_before(&not_before)?;
+    let not_after = Asn1Time::days_from_now(7)?;
+    builder.set_not_after(&not_after)?;
+
+    let san = SubjectAlternativeName::new()
+        .dns(name)
+        .build(&builder.x509v3_context(None, None))?;
+    builder.append_extension(san)?;
+
+    // the extension value is an OCTET STRING of the SHA-256 digest of the key authorization
+    let digest = hash(MessageDigest::sha256(), key_authorization.as_bytes())?;
+    let mut der = Vec::with_capacity(digest.len() + 2);
+    der.push(0x04);
+    der.push(digest.len() as u8);
+    der.extend_from_slice(&digest);
+    let oid = Asn1Object::from_str(ACME_IDENTIFIER_OID)?;
+    let value = Asn1OctetString::new_from_bytes(&der)?;
+    let ext = X509Extension::new_from_der(&oid, true, &value)?;
+    builder.append_extension(ext)?;
+
+    builder.sign(&pkey, MessageDigest::sha256())?;
+    let cert = builder.build();
+
+    let cert = CertificateDer::from(cert.to_der()?);
+    let key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(pkey.private_key_to_pkcs8()?));
+    let Some(provider) = CryptoProvider::get_default() else {
+        return Err(anyhow!("no rustls provider registered"));
+    };
+    let signing_key = provider
+        .key_provider
+        .load_private_key(key)
+        .map_err(|e| anyhow!("failed to load private key: {e}"))?;
+    Ok(CertifiedKey::new(vec![cert], signing_key))
+}
@@ -0,0 +1,310 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use http::Method;
+use rustls_pki_types::ServerName;
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+use url::Url;
+
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_http::HttpBodyReader;
+use g3_types::net::{AlpnProtocol, RustlsClientConfig};
+
+use super::{AccountKey, AcmeConfig};
+
+const RESPONSE_HEADER_MAX_SIZE: usize = 64 * 1024;
+const RESPONSE_BODY_MAX_SIZE: u64 = 1024 * 1024;
+const BAD_NONCE_ERROR_TYPE: &str = "urn:ietf:params:acme:error:badNonce";
+
+pub(super) struct AcmeResponse {
+    pub(super) code: u16,
+    pub(super) location: Option<String>,
+    nonce: Option<String>,
+    pub(super) body: Vec<u8>,
+}
+
+impl AcmeResponse {
+    pub(super) fn json(&self) -> anyhow::Result<Value> {
+        serde_json::from_slice(&self.body).map_err(|e| anyhow!("invalid json response body: {e}"))
+    }
+
+    pub(super) fn location_url(&self) -> anyhow::Result<Url> {
+        let Some(location) = &self.location else {
+            return Err(anyhow!("no Location header found in response"));
+        };
+        Url::parse(location).map_err(|e| anyhow!("invalid Location url {location}: {e}"))
+    }
+
+    fn problem(&self) -> (String, String) {
+        let Ok(v) = self.json() else {
+            return (String::new(), String::new());
+        };
+        let ty = v.get("type").and_then(|v| v.as_str()).unwrap_or_default();
+        let detail = v.get("detail").and_then(|v| v.as_str()).unwrap_or_default();
+        (ty.to_string(), detail.to_string())
+    }
+}
+
+struct AcmeDirectory {
+    new_nonce: Url,
+    new_account: Url,
+    new_order: Url,
+}
+
+impl AcmeDirectory {
+    fn parse_json(v: &Value) -> anyhow::Result<Self> {
+        let get_url = |key: &str| -> anyhow::Result<Url> {
+            let s = v
+                .get(key)
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("no {key} found in acme directory"))?;
+            Url::parse(s).map_err(|e| anyhow!("invalid {key} url {s}: {e}"))
+        };
+        Ok(AcmeDirectory {
+            new_nonce: get_url("newNonce")?,
+            new_account: get_url("newAccount")?,
+            new_order: get_url("newOrder")?,
+        })
+    }
+}
+
+/// A minimal ACME client, see RFC 8555.
+pub(super) struct AcmeClient {
+    tls_client: RustlsClientConfig,
+    request_timeout: Duration,
+    account_key: AccountKey,
+    directory: AcmeDirectory,
+    nonce: Option<String>,
+    kid: Option<String>,
+}
+
+impl AcmeClient {
+    pub(super) async fn new(config: &AcmeConfig) -> anyhow::Result<Self> {
+        let account_key = AccountKey::load_or_create(&config.account_key)?;
+        let tls_client = config
+            .tls_client
+            .build_with_alpn_protocols(Some(vec![AlpnProtocol::Http11]))?;
+
+        let rsp = send_request(
+            &tls_client,
+            config.request_timeout,
+            Method::GET,
+            &config.directory_url,
+            None,
+        )
+        .await?;
+        if rsp.code != 200 {
+            return Err(anyhow!(
+                "failed to get acme directory: status code {}",
+                rsp.code
+            ));
+        }
+        let directory = AcmeDirectory::parse_json(&rsp.json()?)?;
+
+        Ok(AcmeClient {
+            tls_client,
+            request_timeout: config.request_timeout,
+            account_key,
+            directory,
+            nonce: None,
+            kid: None,
+        })
+    }
+
+    pub(super) fn key_authorization(&self, token: &str) -> String {
+        self.account_key.key_authorization(token)
+    }
+
+    #[inline]
+    pub(super) fn new_order_url(&self) -> &Url {
+        &self.directory.new_order
+    }
+
+    /// Find or register the account bound to the account key.
+    pub(super) async fn login(&mut self, contact: &[String]) -> anyhow::Result<()> {
+        if self.kid.is_some() {
+            return Ok(());
+        }
+        let payload = serde_json::json!({
+            "termsOfServiceAgreed": true,
+            "contact": contact,
+        });
+        let url = self.directory.new_account.clone();
+        let rsp = self.post(&url, Some(&payload)).await?;
+        let Some(kid) = rsp.location else {
+            return Err(anyhow!("no account url returned"));
+        };
+        self.kid = Some(kid);
+        Ok(())
+    }
+
+    /// Send a signed POST request, or a POST-as-GET request if no payload is given.
+    ///
+    /// Error will be returned if the server responded with an error status code.
+    pub(super) async fn post(
+        &mut self,
+        url: &Url,
+        payload: Option<&Value>,
+    ) -> anyhow::Result<AcmeResponse> {
+        let mut retry_bad_nonce = true;
+        loop {
+            let nonce = match self.nonce.take() {
+                Some(nonce) => nonce,
+                None => self.fetch_nonce().await?,
+            };
+            let body = self
+                .account_key
+                .sign(url.as_str(), &nonce, self.kid.as_deref(), payload)?;
+            let rsp = send_request(
+                &self.tls_client,
+                self.request_timeout,
+                Method::POST,
+                url,
+                Some(body),
+            )
+            .await?;
+            self.nonce = rsp.nonce.clone();
+            if rsp.code < 400 {
+                return Ok(rsp);
+            }
+
+            let (ty, detail) = rsp.problem();
+            if ty == BAD_NONCE_ERROR_TYPE && retry_bad_nonce {
+                retry_bad_nonce = false;
+                continue;
+            }
+            return Err(anyhow!(
+                "acme server responded {} for {url}: {ty} {detail}",
+                rsp.code
+            ));
+        }
+    }
+
+    async fn fetch_nonce(&self) -> anyhow::Result<String> {
+        let rsp = send_request(
+            &self.tls_client,
+            self.request_timeout,
+            Method::HEAD,
+            &self.directory.new_nonce,
+            None,
+        )
+        .await?;
+        rsp.nonce
+            .ok_or_else(|| anyhow!("no Replay-Nonce header found in newNonce response"))
+    }
+}
+
+async fn send_request(
+    tls_client: &RustlsClientConfig,
+    timeout: Duration,
+    method: Method,
+    url: &Url,
+    body: Option<Vec<u8>>,
+) -> anyhow::Result<AcmeResponse> {
+    tokio::time::timeout(timeout, do_send_request(tls_client, method, url, body))
+        .await
+        .map_err(|_| anyhow!("timeout to send request to {url}"))?
+}
+
+async fn do_send_request(
+    tls_client: &RustlsClientConfig,
+    method: Method,
+    url: &Url,
+    body: Option<Vec<u8>>,
+) -> anyhow::Result<AcmeResponse> {
+    let Some(host) = url.host_str() else {
+        return Err(anyhow!("no host found in url {url}"));
+    };
+    let port = url.port_or_known_default().unwrap_or(443);
+    let tls_name = ServerName::try_from(host.trim_matches(['[', ']']).to_string())
+        .map_err(|e| anyhow!("invalid tls server name {host}: {e}"))?;
+
+    let tcp_stream = TcpStream::connect((host.trim_matches(['[', ']']), port))
+        .await
+        .map_err(|e| anyhow!("failed to connect to {host}:{port}: {e}"))?;
+    let tls_connector = TlsConnector::from(tls_client.driver.clone());
+    let mut tls_stream = tokio::time::timeout(
+        tls_client.handshake_timeout,
+        tls_connector.connect(tls_name, tcp_stream),
+    )
+    .await
+    .map_err(|_| anyhow!("tls handshake with {host}:{port} timed out"))?
+    .map_err(|e| anyhow!("tls handshake with {host}:{port} failed: {e}"))?;
+
+    let mut path = url.path().to_string();
+    if let Some(query) = url.query() {
+        path.push('?');
+        path.push_str(query);
+    }
+    let host_header = match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    };
+    let mut req = format!(
+        "{method} {path} HTTP/1.1\r\nHost: {host_header}\r\nUser-Agent: g3tiles-acme\r\nAccept: application/json\r\nConnection: close\r\n"
+    );
+    if let Some(body) = &body {
+        req.push_str("Content-Type: application/jose+json\r\n");
+        req.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    req.push_str("\r\n");
+    let mut req = req.into_bytes();
+    if let Some(body) = body {
+        req.extend(body);
+    }
+    tls_stream
+        .write_all(&req)
+        .await
+        .map_err(|e| anyhow!("failed to send request: {e}"))?;
+    tls_stream
+        .flush()
+        .await
+        .map_err(|e| anyhow!("failed to send request: {e}"))?;
+
+    let mut reader = BufReader::new(tls_stream);
+    let rsp =
+        HttpForwardRemoteResponse::parse(&mut reader, &method, false, RESPONSE_HEADER_MAX_SIZE)
+            .await
+            .map_err(|e| anyhow!("failed to read response header: {e}"))?;
+
+    let mut body = Vec::new();
+    if let Some(body_type) = rsp.body_type(&method) {
+        let body_reader = HttpBodyReader::new(&mut reader, body_type, 1024);
+        body_reader
+            .take(RESPONSE_BODY_MAX_SIZE)
+            .read_to_end(&mut body)
+            .await
+            .map_err(|e| anyhow!("failed to read response body: {e}"))?;
+    }
+
+    let get_header = |name: &str| {
+        rsp.end_to_end_headers
+            .get(name)
+            .map(|v| v.to_str().to_string())
+    };
+    Ok(AcmeResponse {
+        code: rsp.code,
+        location: get_header("location"),
+        nonce: get_header("replay-nonce"),
+        body,
+    })
+}
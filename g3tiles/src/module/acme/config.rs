@@ -0,0 +1,183 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use url::Url;
+use yaml_rust::Yaml;
+
+use g3_types::net::RustlsClientConfigBuilder;
+
+const LETS_ENCRYPT_DIRECTORY_URL: &str = "https://acme-v02.api.letsencrypt.org/directory";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum AcmeChallengeType {
+    Http01,
+    TlsAlpn01,
+}
+
+impl AcmeChallengeType {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            AcmeChallengeType::Http01 => "http-01",
+            AcmeChallengeType::TlsAlpn01 => "tls-alpn-01",
+        }
+    }
+}
+
+impl FromStr for AcmeChallengeType {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match g3_yaml::key::normalize(s).as_str() {
+            "http_01" | "http01" | "http" => Ok(AcmeChallengeType::Http01),
+            "tls_alpn_01" | "tls_alpn01" | "tls_alpn" => Ok(AcmeChallengeType::TlsAlpn01),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct AcmeConfig {
+    pub(crate) directory_url: Url,
+    pub(crate) contact: Vec<String>,
+    pub(crate) account_key: PathBuf,
+    pub(crate) names: Vec<String>,
+    pub(crate) challenge: AcmeChallengeType,
+    pub(crate) http01_listen: SocketAddr,
+    pub(crate) tls_client: RustlsClientConfigBuilder,
+    pub(crate) request_timeout: Duration,
+    pub(crate) validation_timeout: Duration,
+    pub(crate) renew_before: Duration,
+    pub(crate) check_interval: Duration,
+}
+
+impl AcmeConfig {
+    fn new(account_key: PathBuf) -> Self {
+        AcmeConfig {
+            directory_url: Url::parse(LETS_ENCRYPT_DIRECTORY_URL).unwrap(),
+            contact: Vec::new(),
+            account_key,
+            names: Vec::new(),
+            challenge: AcmeChallengeType::TlsAlpn01,
+            http01_listen: SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 80),
+            tls_client: RustlsClientConfigBuilder::default(),
+            request_timeout: Duration::from_secs(30),
+            validation_timeout: Duration::from_secs(120),
+            renew_before: Duration::from_secs(30 * 86400),
+            check_interval: Duration::from_secs(12 * 3600),
+        }
+    }
+
+    pub(crate) fn parse_yaml(value: &Yaml, lookup_dir: &Path) -> anyhow::Result<Self> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!("yaml value type for 'acme' should be 'map'"));
+        };
+
+        let v = g3_yaml::hash_get_required(map, "account_key")?;
+        let account_key = g3_yaml::value::as_file_path(v, lookup_dir, true)
+            .context("invalid file path value for key account_key")?;
+        let mut config = AcmeConfig::new(account_key);
+
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "account_key" => Ok(()),
+            "directory" | "directory_url" => {
+                config.directory_url =
+                    g3_yaml::value::as_url(v).context(format!("invalid url value for key {k}"))?;
+                Ok(())
+            }
+            "contact" | "contacts" => {
+                config.contact = g3_yaml::value::as_list(v, g3_yaml::value::as_string)
+                    .context(format!("invalid string list value for key {k}"))?;
+                Ok(())
+            }
+            "names" | "domains" => {
+                config.names = g3_yaml::value::as_list(v, g3_yaml::value::as_domain)
+                    .context(format!("invalid domain list value for key {k}"))?;
+                Ok(())
+            }
+            "challenge" | "challenge_type" => {
+                let s = g3_yaml::value::as_string(v)?;
+                config.challenge = AcmeChallengeType::from_str(&s)
+                    .map_err(|_| anyhow!("invalid acme challenge type {s}"))?;
+                Ok(())
+            }
+            "http01_listen" | "http_01_listen" => {
+                config.http01_listen = g3_yaml::value::as_sockaddr(v)
+                    .context(format!("invalid socket address value for key {k}"))?;
+                Ok(())
+            }
+            "tls_client" => {
+                config.tls_client =
+                    g3_yaml::value::as_rustls_client_config_builder(v, Some(lookup_dir)).context(
+                        format!("invalid rustls tls client config value for key {k}"),
+                    )?;
+                Ok(())
+            }
+            "request_timeout" => {
+                config.request_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "validation_timeout" => {
+                config.validation_timeout = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "renew_before" => {
+                config.renew_before = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            "check_interval" => {
+                config.check_interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        config.check()?;
+        Ok(config)
+    }
+
+    fn check(&mut self) -> anyhow::Result<()> {
+        if self.names.is_empty() {
+            return Err(anyhow!("no domain names set"));
+        }
+        for name in &self.names {
+            if name.starts_with("*.") {
+                return Err(anyhow!(
+                    "wildcard name {name} is not supported by {} challenge",
+                    self.challenge.as_str()
+                ));
+            }
+        }
+        match self.directory_url.scheme() {
+            "https" => {}
+            s => return Err(anyhow!("unsupported acme directory url scheme {s}")),
+        }
+        self.tls_client.check()?;
+        if self.check_interval < Duration::from_secs(60) {
+            self.check_interval = Duration::from_secs(60);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,312 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::anyhow;
+use base64::prelude::*;
+use openssl::bn::{BigNum, BigNumContext};
+use openssl::ec::{EcGroup, EcKey};
+use openssl::ecdsa::EcdsaSig;
+use openssl::hash::{hash, MessageDigest};
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Private};
+use serde_json::{json, Value};
+
+const P256_COORDINATE_SIZE: i32 = 32;
+
+/// Get the JWK thumbprint, see RFC 7638.
+///
+/// The required members should be in lexicographic order, without any whitespace.
+fn jwk_thumbprint(canonical_jwk: &str) -> anyhow::Result<String> {
+    let digest = hash(MessageDigest::sha256(), canonical_jwk.as_bytes())?;
+    Ok(BASE64_URL_SAFE_NO_PAD.encode(digest))
+}
+
+/// The ACME account key, only ECDSA P-256 keys (ES256) are supported.
+pub(super) struct AccountKey {
+    key: EcKey<Private>,
+    jwk: Value,
+    thumbprint: String,
+}
+
+impl AccountKey {
+    /// Load the account key from the pem file, a new key will be generated and
+    /// saved to the file if it's empty.
+    pub(super) fn load_or_create(path: &Path) -> anyhow::Result<Self> {
+        let content = fs::read(path)
+            .map_err(|e| anyhow!("failed to read account key file {}: {e}", path.display()))?;
+        let key = if content.is_empty() {
+            let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)
+                .map_err(|e| anyhow!("failed to get P-256 curve: {e}"))?;
+            let key = EcKey::generate(&group)
+                .map_err(|e| anyhow!("failed to generate account key: {e}"))?;
+            let pkey = PKey::from_ec_key(key.clone())
+                .map_err(|e| anyhow!("failed to convert account key: {e}"))?;
+            let pem = pkey
+                .private_key_to_pem_pkcs8()
+                .map_err(|e| anyhow!("failed to encode account key: {e}"))?;
+            super::write_private_file(path, &pem)
+                .map_err(|e| anyhow!("failed to save account key to {}: {e}", path.display()))?;
+            key
+        } else {
+            let pkey = PKey::private_key_from_pem(&content)
+                .map_err(|e| anyhow!("invalid account key file {}: {e}", path.display()))?;
+            pkey.ec_key()
+                .map_err(|_| anyhow!("the account key should be an ECDSA P-256 key"))?
+        };
+        if key.group().curve_name() != Some(Nid::X9_62_PRIME256V1) {
+            return Err(anyhow!("the account key should be an ECDSA P-256 key"));
+        }
+
+        let mut ctx = BigNumContext::new()?;
+        let mut x = BigNum::new()?;
+        let mut y = BigNum::new()?;
+        key.public_key()
+            .affine_coordinates_gfp(key.group(), &mut x, &mut y, &mut ctx)
+            .map_err(|e| anyhow!("failed to get public key coordinates: {e}"))?;
+        let x = BASE64_URL_SAFE_NO_PAD.encode(x.to_vec_padded(P256_COORDINATE_SIZE)?);
+        let y = BASE64_URL_SAFE_NO_PAD.encode(y.to_vec_padded(P256_COORDINATE_SIZE)?);
+
+        let thumbprint = jwk_thumbprint(&format!(
+            r#"{{"crv":"P-256","kty":"EC","x":"{x}","y":"{y}"}}"#
+        ))?;
+        let jwk = json!({
+            "crv": "P-256",
+            "kty": "EC",
+            "x": x,
+            "y": y,
+        });
+
+        Ok(AccountKey {
+            key,
+            jwk,
+            thumbprint,
+        })
+    }
+
+    /// Get the key authorization string for the challenge token, see RFC 8555 8.1.
+    pub(super) fn key_authorization(&self, token: &str) -> String {
+        format!("{token}.{}", self.thumbprint)
+    }
+
+    /// Build the JWS request body in flattened JSON serialization.
+    ///
+    /// The `jwk` will be embedded if no `kid` is given, and an empty payload
+    /// will be used for POST-as-GET requests if no `payload` is given.
+    pub(super) fn sign(
+        &self,
+        url: &str,
+        nonce: &str,
+        kid: Option<&str>,
+        payload: Option<&Value>,
+    ) -> anyhow::Result<Vec<u8>> {
+        let protected = match kid {
+            Some(kid) => json!({
+                "alg": "ES256",
+                "kid": kid,
+                "nonce": nonce,
+                "url": url,
+            }),
+            None => json!({
+                "alg": "ES256",
+                "jwk": self.jwk,
+                "nonce": nonce,
+                "url": url,
+            }),
+        };
+        let protected = BASE64_URL_SAFE_NO_PAD.encode(protected.to_string());
+        let payload = match payload {
+            Some(v) => BASE64_URL_SAFE_NO_PAD.encode(v.to_string()),
+            None => String::new(),
+        };
+
+        let signing_input = format!("{protected}.{payload}");
+        let digest = hash(MessageDigest::sha256(), signing_input.as_bytes())?;
+        let sig = EcdsaSig::sign(&digest, &self.key)
+            .map_err(|e| anyhow!("failed to sign the request: {e}"))?;
+        // the signature should be R || S in fixed length, see RFC 7518 3.4
+        let mut raw = sig.r().to_vec_padded(P256_COORDINATE_SIZE)?;
+        raw.extend(sig.s().to_vec_padded(P256_COORDINATE_SIZE)?);
+
+        let body = json!({
+            "protected": protected,
+            "payload": payload,
+            "signature": BASE64_URL_SAFE_NO_PAD.encode(raw),
+        });
+        Ok(body.to_string().into_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::bn::BigNumRef;
+    use std::path::PathBuf;
+
+    fn test_key_file(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("g3tiles-acme-{name}-{}.key", std::process::id()));
+        fs::write(&path, b"").unwrap();
+        path
+    }
+
+    fn decode_json(s: &str) -> Value {
+        let data = BASE64_URL_SAFE_NO_PAD.decode(s).unwrap();
+        serde_json::from_slice(&data).unwrap()
+    }
+
+    fn coordinate(v: &Value) -> BigNum {
+        let data = BASE64_URL_SAFE_NO_PAD.decode(v.as_str().unwrap()).unwrap();
+        assert_eq!(data.len(), P256_COORDINATE_SIZE as usize);
+        BigNum::from_slice(&data).unwrap()
+    }
+
+    fn verify(key: &AccountKey, body: &Value) {
+        let signing_input = format!(
+            "{}.{}",
+            body["protected"].as_str().unwrap(),
+            body["payload"].as_str().unwrap()
+        );
+        let digest = hash(MessageDigest::sha256(), signing_input.as_bytes()).unwrap();
+
+        let raw = BASE64_URL_SAFE_NO_PAD
+            .decode(body["signature"].as_str().unwrap())
+            .unwrap();
+        assert_eq!(raw.len(), 2 * P256_COORDINATE_SIZE as usize);
+        let (r, s) = raw.split_at(P256_COORDINATE_SIZE as usize);
+        let sig = EcdsaSig::from_private_components(
+            BigNum::from_slice(r).unwrap(),
+            BigNum::from_slice(s).unwrap(),
+        )
+        .unwrap();
+        assert!(sig.verify(&digest, &key.key).unwrap());
+    }
+
+    #[test]
+    fn thumbprint_rfc7638() {
+        // the example in RFC 7638 section 3.1
+        let canonical = concat!(
+            r#"{"e":"AQAB","kty":"RSA","n":"0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4"#,
+            r#"cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9"#,
+            r#"yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHz"#,
+            r#"u6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_x"#,
+            r#"BniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw"}"#
+        );
+        assert_eq!(
+            jwk_thumbprint(canonical).unwrap(),
+            "NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs"
+        );
+    }
+
+    #[test]
+    fn load_or_create() {
+        let path = test_key_file("create");
+        let key = AccountKey::load_or_create(&path).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        // the saved key should be loaded next time
+        let loaded = AccountKey::load_or_create(&path).unwrap();
+        assert_eq!(loaded.jwk, key.jwk);
+        assert_eq!(loaded.thumbprint, key.thumbprint);
+        assert_eq!(
+            key.key_authorization("token"),
+            format!("token.{}", key.thumbprint)
+        );
+
+        // the jwk should match the public key
+        let mut ctx = BigNumContext::new().unwrap();
+        let mut x = BigNum::new().unwrap();
+        let mut y = BigNum::new().unwrap();
+        key.key
+            .public_key()
+            .affine_coordinates_gfp(key.key.group(), &mut x, &mut y, &mut ctx)
+            .unwrap();
+        let eq = |a: &BigNumRef, b: &BigNum| a.ucmp(b).is_eq();
+        assert!(eq(&x, &coordinate(&key.jwk["x"])));
+        assert!(eq(&y, &coordinate(&key.jwk["y"])));
+        let canonical = format!(
+            r#"{{"crv":"P-256","kty":"EC","x":"{}","y":"{}"}}"#,
+            key.jwk["x"].as_str().unwrap(),
+            key.jwk["y"].as_str().unwrap()
+        );
+        assert_eq!(jwk_thumbprint(&canonical).unwrap(), key.thumbprint);
+
+        fs::write(&path, b"invalid").unwrap();
+        assert!(AccountKey::load_or_create(&path).is_err());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn sign_with_jwk() {
+        let path = test_key_file("sign-jwk");
+        let key = AccountKey::load_or_create(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        let payload = json!({"termsOfServiceAgreed": true});
+        let body = key
+            .sign(
+                "https://acme.test/new-account",
+                "nonce1",
+                None,
+                Some(&payload),
+            )
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        let protected = decode_json(body["protected"].as_str().unwrap());
+        assert_eq!(protected["alg"], "ES256");
+        assert_eq!(protected["nonce"], "nonce1");
+        assert_eq!(protected["url"], "https://acme.test/new-account");
+        assert_eq!(protected["jwk"], key.jwk);
+        assert!(protected.get("kid").is_none());
+        assert_eq!(decode_json(body["payload"].as_str().unwrap()), payload);
+        verify(&key, &body);
+    }
+
+    #[test]
+    fn sign_with_kid() {
+        let path = test_key_file("sign-kid");
+        let key = AccountKey::load_or_create(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        // POST-as-GET
+        let body = key
+            .sign(
+                "https://acme.test/order/1",
+                "nonce2",
+                Some("https://acme.test/acct/1"),
+                None,
+            )
+            .unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+
+        let protected = decode_json(body["protected"].as_str().unwrap());
+        assert_eq!(protected["alg"], "ES256");
+        assert_eq!(protected["kid"], "https://acme.test/acct/1");
+        assert_eq!(protected["nonce"], "nonce2");
+        assert_eq!(protected["url"], "https://acme.test/order/1");
+        assert!(protected.get("jwk").is_none());
+        assert_eq!(body["payload"], "");
+        verify(&key, &body);
+    }
+}
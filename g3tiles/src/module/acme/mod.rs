@@ -0,0 +1,83 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::module::cert_dir::CertDirResolver;
+
+mod config;
+pub(crate) use config::{AcmeChallengeType, AcmeConfig};
+
+mod jws;
+use jws::AccountKey;
+
+mod client;
+use client::AcmeClient;
+
+mod challenge;
+use challenge::ChallengeSolver;
+pub(crate) use challenge::{AcmeTlsAlpnResolver, ACME_TLS_ALPN_PROTOCOL};
+
+mod order;
+
+mod task;
+use task::AcmeRenewTask;
+
+/// Spawn the task to obtain and renew certificates into the cert dir.
+///
+/// The task will stop when the cert dir resolver is dropped.
+/// The returned resolver should be used for TLS-ALPN-01 validation connections.
+pub(crate) fn spawn(
+    config: &AcmeConfig,
+    cert_dir: &Path,
+    resolver: &Arc<CertDirResolver>,
+) -> Arc<AcmeTlsAlpnResolver> {
+    let tls_alpn_resolver = Arc::new(AcmeTlsAlpnResolver::default());
+    let solver = ChallengeSolver::new(
+        config.challenge,
+        config.http01_listen,
+        tls_alpn_resolver.clone(),
+    );
+    let task = AcmeRenewTask::new(config.clone(), cert_dir.to_path_buf(), solver);
+    let weak = Arc::downgrade(resolver);
+    tokio::spawn(async move { task.run(weak).await });
+    tls_alpn_resolver
+}
+
+/// Write the private key file, which should only be readable by the owner.
+///
+/// The permission is set before any content is written, even if the file already exists.
+fn write_private_file(path: &Path, content: &[u8]) -> io::Result<()> {
+    let mut options = File::options();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        // the mode is only used when creating new files
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(content)?;
+    file.sync_all()
+}
@@ -0,0 +1,220 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use base64::prelude::*;
+use log::{debug, info};
+use openssl::ec::{EcGroup, EcKey};
+use openssl::hash::MessageDigest;
+use openssl::nid::Nid;
+use openssl::pkey::{PKey, Private};
+use openssl::stack::Stack;
+use openssl::x509::extension::SubjectAlternativeName;
+use openssl::x509::{X509NameBuilder, X509ReqBuilder};
+use serde_json::{json, Value};
+use tokio::time::Instant;
+use url::Url;
+
+use super::{AcmeClient, AcmeConfig, ChallengeSolver};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+pub(super) struct IssuedCert {
+    pub(super) key_pem: Vec<u8>,
+    pub(super) cert_pem: Vec<u8>,
+}
+
+/// Run a new order for the name, see RFC 8555 7.4.
+pub(super) async fn issue(
+    client: &mut AcmeClient,
+    config: &AcmeConfig,
+    solver: &ChallengeSolver,
+    name: &str,
+) -> anyhow::Result<IssuedCert> {
+    client.login(&config.contact).await?;
+
+    let payload = json!({
+        "identifiers": [{"type": "dns", "value": name}],
+    });
+    let new_order_url = client.new_order_url().clone();
+    let rsp = client.post(&new_order_url, Some(&payload)).await?;
+    let order_url = rsp.location_url()?;
+    let order = rsp.json()?;
+
+    let authorizations = order
+        .get("authorizations")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| anyhow!("no authorizations found in order"))?;
+    if !authorizations.is_empty() {
+        let _responder = solver.start().await?;
+        for authz in authorizations {
+            let authz_url = parse_url(authz)?;
+            authorize(client, config, solver, &authz_url).await?;
+        }
+    }
+
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1)?;
+    let key = PKey::from_ec_key(EcKey::generate(&group)?)?;
+    let csr = build_csr(name, &key)?;
+    let finalize_url = parse_url(
+        order
+            .get("finalize")
+            .ok_or_else(|| anyhow!("no finalize url found in order"))?,
+    )?;
+    let payload = json!({ "csr": BASE64_URL_SAFE_NO_PAD.encode(csr) });
+    client.post(&finalize_url, Some(&payload)).await?;
+
+    let order = poll_status(client, &order_url, config.validation_timeout, "order").await?;
+    let cert_url = parse_url(
+        order
+            .get("certificate")
+            .ok_or_else(|| anyhow!("no certificate url found in order"))?,
+    )?;
+    let rsp = client.post(&cert_url, None).await?;
+    if rsp.body.is_empty() {
+        return Err(anyhow!("empty certificate chain downloaded"));
+    }
+
+    Ok(IssuedCert {
+        key_pem: key.private_key_to_pem_pkcs8()?,
+        cert_pem: rsp.body,
+    })
+}
+
+async fn authorize(
+    client: &mut AcmeClient,
+    config: &AcmeConfig,
+    solver: &ChallengeSolver,
+    authz_url: &Url,
+) -> anyhow::Result<()> {
+    let authz = client.post(authz_url, None).await?.json()?;
+    if get_status(&authz)? == "valid" {
+        return Ok(());
+    }
+    let name = authz
+        .get("identifier")
+        .and_then(|v| v.get("value"))
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("no identifier found in authorization {authz_url}"))?
+        .to_string();
+
+    let challenge_type = solver.challenge_type().as_str();
+    let challenge = authz
+        .get("challenges")
+        .and_then(|v| v.as_array())
+        .and_then(|challenges| {
+            challenges
+                .iter()
+                .find(|c| c.get("type").and_then(|v| v.as_str()) == Some(challenge_type))
+        })
+        .ok_or_else(|| anyhow!("no {challenge_type} challenge offered for {name}"))?;
+    let token = challenge
+        .get("token")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("no token found in {challenge_type} challenge for {name}"))?
+        .to_string();
+    let challenge_url = parse_url(
+        challenge
+            .get("url")
+            .ok_or_else(|| anyhow!("no url found in {challenge_type} challenge for {name}"))?,
+    )?;
+
+    let key_authorization = client.key_authorization(&token);
+    solver.add(&name, &token, &key_authorization)?;
+    debug!("acme {challenge_type} challenge for {name} is ready");
+    let r = async {
+        client.post(&challenge_url, Some(&json!({}))).await?;
+        poll_status(
+            client,
+            authz_url,
+            config.validation_timeout,
+            "authorization",
+        )
+        .await
+    }
+    .await;
+    solver.remove(&name, &token);
+    r?;
+    info!("acme authorization for {name} is valid");
+    Ok(())
+}
+
+/// Poll the order or authorization object until it's valid.
+async fn poll_status(
+    client: &mut AcmeClient,
+    url: &Url,
+    timeout: Duration,
+    object: &str,
+) -> anyhow::Result<Value> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let v = client.post(url, None).await?.json()?;
+        match get_status(&v)? {
+            "valid" => return Ok(v),
+            "invalid" => {
+                let detail = v
+                    .get("error")
+                    .or_else(|| {
+                        v.get("challenges")
+                            .and_then(|c| c.as_array())
+                            .and_then(|c| c.iter().find_map(|c| c.get("error")))
+                    })
+                    .map(|e| e.to_string())
+                    .unwrap_or_default();
+                return Err(anyhow!("acme {object} {url} is invalid: {detail}"));
+            }
+            _ => {}
+        }
+        if Instant::now() >= deadline {
+            return Err(anyhow!("timeout to wait acme {object} {url} to be valid"));
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+fn get_status(v: &Value) -> anyhow::Result<&str> {
+    v.get("status")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| anyhow!("no status found in acme object"))
+}
+
+fn parse_url(v: &Value) -> anyhow::Result<Url> {
+    let s = v
+        .as_str()
+        .ok_or_else(|| anyhow!("url value should be a string"))?;
+    Url::parse(s).map_err(|e| anyhow!("invalid url {s}: {e}"))
+}
+
+fn build_csr(name: &str, key: &PKey<Private>) -> anyhow::Result<Vec<u8>> {
+    let mut builder = X509ReqBuilder::new()?;
+    let mut subject = X509NameBuilder::new()?;
+    subject.append_entry_by_nid(Nid::COMMONNAME, name)?;
+    builder.set_subject_name(&subject.build())?;
+    builder.set_pubkey(key)?;
+
+    let san = SubjectAlternativeName::new()
+        .dns(name)
+        .build(&builder.x509v3_context(None))?;
+    let mut extensions = Stack::new()?;
+    extensions.push(san)?;
+    builder.add_extensions(&extensions)?;
+
+    builder.sign(key, MessageDigest::sha256())?;
+    let csr = builder.build().to_der()?;
+    Ok(csr)
+}
@@ -0,0 +1,216 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use log::{info, warn};
+use openssl::asn1::Asn1Time;
+use openssl::x509::X509;
+
+use super::{AcmeClient, AcmeConfig, ChallengeSolver};
+use crate::module::cert_dir::{self, CertDirResolver};
+
+const RETRY_INTERVAL: Duration = Duration::from_secs(3600);
+
+pub(super) struct AcmeRenewTask {
+    config: AcmeConfig,
+    cert_dir: PathBuf,
+    solver: ChallengeSolver,
+    client: Option<AcmeClient>,
+}
+
+impl AcmeRenewTask {
+    pub(super) fn new(config: AcmeConfig, cert_dir: PathBuf, solver: ChallengeSolver) -> Self {
+        AcmeRenewTask {
+            config,
+            cert_dir,
+            solver,
+            client: None,
+        }
+    }
+
+    pub(super) async fn run(mut self, resolver: Weak<CertDirResolver>) {
+        loop {
+            if resolver.strong_count() == 0 {
+                break;
+            }
+
+            let mut all_ok = true;
+            for name in self.config.names.clone() {
+                match self.check_and_renew(&name, &resolver).await {
+                    Ok(true) => info!("acme certificate for {name} has been renewed"),
+                    Ok(false) => {}
+                    Err(e) => {
+                        warn!("failed to renew acme certificate for {name}: {e:?}");
+                        all_ok = false;
+                    }
+                }
+            }
+
+            let wait = if all_ok {
+                self.config.check_interval
+            } else {
+                // drop the client to refresh the directory and nonce at next run
+                self.client = None;
+                self.config.check_interval.min(RETRY_INTERVAL)
+            };
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    async fn check_and_renew(
+        &mut self,
+        name: &str,
+        resolver: &Weak<CertDirResolver>,
+    ) -> anyhow::Result<bool> {
+        let cert_path = self.cert_dir.join(format!("{name}.crt"));
+        let key_path = self.cert_dir.join(format!("{name}.key"));
+        if !need_renew(&cert_path, self.config.renew_before) {
+            return Ok(false);
+        }
+
+        let client = match &mut self.client {
+            Some(client) => client,
+            None => self.client.insert(AcmeClient::new(&self.config).await?),
+        };
+        let issued = super::order::issue(client, &self.config, &self.solver, name).await?;
+
+        save_file(&key_path, &issued.key_pem, true)?;
+        save_file(&cert_path, &issued.cert_pem, false)?;
+
+        let Some(resolver) = resolver.upgrade() else {
+            return Ok(true);
+        };
+        let key = cert_dir::load_certified_key(&cert_path, &key_path)?;
+        resolver.update(name, Arc::new(key));
+        Ok(true)
+    }
+}
+
+fn need_renew(cert_path: &Path, renew_before: Duration) -> bool {
+    let Ok(content) = fs::read(cert_path) else {
+        return true;
+    };
+    let Ok(cert) = X509::from_pem(&content) else {
+        return true;
+    };
+    let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) else {
+        return true;
+    };
+    let Ok(threshold) = Asn1Time::from_unix((now + renew_before).as_secs() as i64) else {
+        return true;
+    };
+    match cert.not_after().compare(&threshold) {
+        Ok(ordering) => ordering.is_lt(),
+        Err(_) => true,
+    }
+}
+
+/// Write to a temp file and then rename, so the cert dir scanner never sees a partial file.
+fn save_file(path: &Path, content: &[u8], private: bool) -> anyhow::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    if private {
+        super::write_private_file(&tmp_path, content)
+    } else {
+        fs::write(&tmp_path, content)
+    }
+    .map_err(|e| anyhow!("failed to write {}: {e}", tmp_path.display()))?;
+    fs::rename(&tmp_path, path).map_err(|e| {
+        anyhow!(
+            "failed to rename {} to {}: {e}",
+            tmp_path.display(),
+            path.display()
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::hash::MessageDigest;
+    use openssl::nid::Nid;
+    use openssl::pkey::PKey;
+    use openssl::x509::X509NameBuilder;
+
+    const DAY: Duration = Duration::from_secs(86400);
+
+    fn test_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("g3tiles-acme-{name}-{}", std::process::id()))
+    }
+
+    fn write_cert(path: &Path, valid_days: u32) {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_nid(Nid::COMMONNAME, "www.example.com")
+            .unwrap();
+        let name = name.build();
+
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(valid_days).unwrap())
+            .unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        fs::write(path, builder.build().to_pem().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn renew_check() {
+        let path = test_path("renew.crt");
+        let _ = fs::remove_file(&path);
+        assert!(need_renew(&path, DAY));
+
+        fs::write(&path, b"invalid").unwrap();
+        assert!(need_renew(&path, DAY));
+
+        write_cert(&path, 10);
+        assert!(!need_renew(&path, DAY));
+        assert!(!need_renew(&path, 9 * DAY));
+        assert!(need_renew(&path, 11 * DAY));
+        assert!(need_renew(&path, 30 * DAY));
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn save_private() {
+        let path = test_path("save.key");
+        // an existing file with loose permission should be fixed
+        fs::write(&path, b"old").unwrap();
+        save_file(&path, b"new", true).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        assert!(!path.with_extension("tmp").exists());
+        let _ = fs::remove_file(&path);
+    }
+}
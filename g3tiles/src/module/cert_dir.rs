@@ -0,0 +1,384 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime};
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use arc_swap::ArcSwap;
+use log::{debug, warn};
+use rustls::crypto::CryptoProvider;
+use rustls::server::{ClientHello, ResolvesServerCert};
+use rustls::sign::CertifiedKey;
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+
+use g3_types::net::MultipleCertResolver;
+
+const CERT_FILE_EXTENSIONS: &[&str] = &["crt", "pem"];
+const KEY_FILE_EXTENSION: &str = "key";
+const WILDCARD_FILE_PREFIX: &str = "_.";
+
+/// Resolve server certificates by SNI from cert/key files in a directory.
+///
+/// The file stem of each certificate file is used as the SNI name,
+/// and a leading `_.` in the stem stands for a `*.` wildcard name.
+/// The static resolver will be used if no certificate matches the SNI.
+#[derive(Debug)]
+pub(crate) struct CertDirResolver {
+    dir: PathBuf,
+    keys: ArcSwap<AHashMap<String, Arc<CertifiedKey>>>,
+    fallback: MultipleCertResolver,
+}
+
+impl CertDirResolver {
+    pub(crate) fn new_spawned(
+        dir: &Path,
+        check_interval: Duration,
+        fallback: MultipleCertResolver,
+    ) -> anyhow::Result<Arc<Self>> {
+        let mut scanner = CertDirScanner::default();
+        let keys = scanner.scan(dir)?;
+        let resolver = Arc::new(CertDirResolver {
+            dir: dir.to_path_buf(),
+            keys: ArcSwap::new(Arc::new(keys)),
+            fallback,
+        });
+        let weak = Arc::downgrade(&resolver);
+        tokio::spawn(async move { scanner.run(weak, check_interval).await });
+        Ok(resolver)
+    }
+
+    /// Replace the certificate for the name immediately, without waiting for the next scan.
+    pub(crate) fn update(&self, name: &str, key: Arc<CertifiedKey>) {
+        let mut keys = self.keys.load().as_ref().clone();
+        keys.insert(name.to_ascii_lowercase(), key);
+        self.keys.store(Arc::new(keys));
+    }
+
+    fn get(&self, name: &str) -> Option<Arc<CertifiedKey>> {
+        let keys = self.keys.load();
+        if let Some(ck) = keys.get(name) {
+            return Some(ck.clone());
+        }
+        let (_, domain) = name.split_once('.')?;
+        keys.get(&format!("*.{domain}")).cloned()
+    }
+}
+
+impl ResolvesServerCert for CertDirResolver {
+    fn resolve(&self, client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+        if let Some(sni) = client_hello.server_name() {
+            if let Some(ck) = self.get(&sni.to_ascii_lowercase()) {
+                return Some(ck);
+            }
+        }
+        self.fallback.resolve(client_hello)
+    }
+}
+
+struct LoadedCert {
+    cert_mtime: SystemTime,
+    key_mtime: SystemTime,
+    key: Arc<CertifiedKey>,
+}
+
+#[derive(Default)]
+struct CertDirScanner {
+    loaded: HashMap<String, LoadedCert>,
+}
+
+impl CertDirScanner {
+    async fn run(mut self, resolver: Weak<CertDirResolver>, check_interval: Duration) {
+        let mut interval = tokio::time::interval(check_interval);
+        interval.tick().await; // the first tick completes immediately
+        loop {
+            interval.tick().await;
+            let Some(resolver) = resolver.upgrade() else {
+                break;
+            };
+            let dir = resolver.dir.clone();
+            let r = tokio::task::spawn_blocking(move || {
+                let r = self.scan(&dir);
+                (self, r)
+            })
+            .await;
+            match r {
+                Ok((scanner, Ok(keys))) => {
+                    self = scanner;
+                    resolver.keys.store(Arc::new(keys));
+                }
+                Ok((scanner, Err(e))) => {
+                    self = scanner;
+                    warn!("failed to scan cert dir {}: {e:?}", resolver.dir.display());
+                }
+                Err(e) => {
+                    warn!("cert dir scan task failed: {e}");
+                    break;
+                }
+            }
+        }
+    }
+
+    fn scan(&mut self, dir: &Path) -> anyhow::Result<AHashMap<String, Arc<CertifiedKey>>> {
+        let entries =
+            fs::read_dir(dir).map_err(|e| anyhow!("failed to read dir {}: {e}", dir.display()))?;
+
+        let mut found = HashMap::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| anyhow!("failed to read dir entry: {e}"))?;
+            let cert_path = entry.path();
+            let Some(ext) = cert_path.extension().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if !CERT_FILE_EXTENSIONS.contains(&ext) {
+                continue;
+            }
+            let Some(stem) = cert_path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let key_path = cert_path.with_extension(KEY_FILE_EXTENSION);
+            if !key_path.is_file() {
+                continue;
+            }
+
+            let name = match stem.strip_prefix(WILDCARD_FILE_PREFIX) {
+                Some(domain) => format!("*.{domain}"),
+                None => stem.to_string(),
+            };
+            found.insert(name.to_ascii_lowercase(), (cert_path, key_path));
+        }
+
+        let mut keys = AHashMap::with_capacity(found.len());
+        let mut loaded = HashMap::with_capacity(found.len());
+        for (name, (cert_path, key_path)) in found {
+            let (Ok(cert_mtime), Ok(key_mtime)) =
+                (modified_time(&cert_path), modified_time(&key_path))
+            else {
+                continue;
+            };
+
+            if let Some(old) = self.loaded.remove(&name) {
+                if old.cert_mtime == cert_mtime && old.key_mtime == key_mtime {
+                    keys.insert(name.clone(), old.key.clone());
+                    loaded.insert(name, old);
+                    continue;
+                }
+                match load_certified_key(&cert_path, &key_path) {
+                    Ok(key) => {
+                        debug!(
+                            "reloaded certificate for {name} from {}",
+                            cert_path.display()
+                        );
+                        let key = Arc::new(key);
+                        keys.insert(name.clone(), key.clone());
+                        loaded.insert(
+                            name,
+                            LoadedCert {
+                                cert_mtime,
+                                key_mtime,
+                                key,
+                            },
+                        );
+                    }
+                    Err(e) => {
+                        // keep the old one, and retry at next scan
+                        warn!("failed to reload certificate for {name}: {e:?}");
+                        keys.insert(name.clone(), old.key.clone());
+                        loaded.insert(name, old);
+                    }
+                }
+            } else {
+                match load_certified_key(&cert_path, &key_path) {
+                    Ok(key) => {
+                        debug!("loaded certificate for {name} from {}", cert_path.display());
+                        let key = Arc::new(key);
+                        keys.insert(name.clone(), key.clone());
+                        loaded.insert(
+                            name,
+                            LoadedCert {
+                                cert_mtime,
+                                key_mtime,
+                                key,
+                            },
+                        );
+                    }
+                    Err(e) => warn!("failed to load certificate for {name}: {e:?}"),
+                }
+            }
+        }
+
+        self.loaded = loaded;
+        Ok(keys)
+    }
+}
+
+fn modified_time(path: &Path) -> anyhow::Result<SystemTime> {
+    let meta = fs::metadata(path)
+        .map_err(|e| anyhow!("failed to get metadata of {}: {e}", path.display()))?;
+    meta.modified()
+        .map_err(|e| anyhow!("failed to get mtime of {}: {e}", path.display()))
+}
+
+pub(crate) fn load_certified_key(
+    cert_path: &Path,
+    key_path: &Path,
+) -> anyhow::Result<CertifiedKey> {
+    let mut certs = Vec::new();
+    let iter = CertificateDer::pem_file_iter(cert_path)
+        .map_err(|e| anyhow!("failed to open {}: {e:?}", cert_path.display()))?;
+    for (i, r) in iter.enumerate() {
+        let cert =
+            r.map_err(|e| anyhow!("invalid certificate {}#{i}: {e:?}", cert_path.display()))?;
+        certs.push(cert);
+    }
+    if certs.is_empty() {
+        return Err(anyhow!(
+            "no valid certificate found in {}",
+            cert_path.display()
+        ));
+    }
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .map_err(|e| anyhow!("invalid private key file {}: {e:?}", key_path.display()))?;
+
+    let Some(provider) = CryptoProvider::get_default() else {
+        return Err(anyhow!("no rustls provider registered"));
+    };
+    let signing_key = provider
+        .key_provider
+        .load_private_key(key)
+        .map_err(|e| anyhow!("failed to load private key: {e}"))?;
+    Ok(CertifiedKey::new(certs, signing_key))
+}
+
+#[cfg(all(test, feature = "rustls-ring"))]
+mod tests {
+    use super::*;
+    use openssl::asn1::Asn1Time;
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::hash::MessageDigest;
+    use openssl::nid::Nid;
+    use openssl::pkey::PKey;
+    use openssl::x509::{X509NameBuilder, X509};
+
+    fn test_dir(name: &str) -> PathBuf {
+        let _ = rustls::crypto::ring::default_provider().install_default();
+        let dir =
+            std::env::temp_dir().join(format!("g3tiles-cert-dir-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_pair(dir: &Path, stem: &str, mtime: SystemTime) {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_nid(Nid::COMMONNAME, stem).unwrap();
+        let name = name.build();
+
+        let mut builder = X509::builder().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        let cert = builder.build();
+
+        let cert_path = dir.join(format!("{stem}.crt"));
+        let key_path = dir.join(format!("{stem}.key"));
+        fs::write(&cert_path, cert.to_pem().unwrap()).unwrap();
+        fs::write(&key_path, key.private_key_to_pem_pkcs8().unwrap()).unwrap();
+        // make sure the change can be detected even if done in the same mtime tick
+        for path in [cert_path, key_path] {
+            let f = fs::File::options().write(true).open(path).unwrap();
+            f.set_modified(mtime).unwrap();
+        }
+    }
+
+    #[test]
+    fn scan_load() {
+        let dir = test_dir("load");
+        let mtime = SystemTime::now();
+        write_pair(&dir, "www.example.com", mtime);
+        write_pair(&dir, "_.example.net", mtime);
+        fs::write(dir.join("no-key.example.org.crt"), b"").unwrap();
+        fs::write(dir.join("README.txt"), b"").unwrap();
+
+        let mut scanner = CertDirScanner::default();
+        let keys = scanner.scan(&dir).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert!(keys.contains_key("www.example.com"));
+        assert!(keys.contains_key("*.example.net"));
+
+        let resolver = CertDirResolver {
+            dir: dir.clone(),
+            keys: ArcSwap::new(Arc::new(keys)),
+            fallback: MultipleCertResolver::default(),
+        };
+        assert!(resolver.get("www.example.com").is_some());
+        assert!(resolver.get("a.example.net").is_some());
+        assert!(resolver.get("example.net").is_none());
+        assert!(resolver.get("a.b.example.net").is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn scan_reload() {
+        let dir = test_dir("reload");
+        let mtime = SystemTime::now();
+        write_pair(&dir, "a.example.com", mtime);
+        write_pair(&dir, "b.example.com", mtime);
+
+        let mut scanner = CertDirScanner::default();
+        let keys = scanner.scan(&dir).unwrap();
+        let old_a = keys.get("a.example.com").unwrap().clone();
+        let old_b = keys.get("b.example.com").unwrap().clone();
+
+        // unchanged files should not be reloaded
+        let keys = scanner.scan(&dir).unwrap();
+        assert!(Arc::ptr_eq(keys.get("a.example.com").unwrap(), &old_a));
+        assert!(Arc::ptr_eq(keys.get("b.example.com").unwrap(), &old_b));
+
+        // changed pair should be reloaded, and removed pair should be dropped
+        write_pair(&dir, "a.example.com", mtime + Duration::from_secs(10));
+        fs::remove_file(dir.join("b.example.com.crt")).unwrap();
+        let keys = scanner.scan(&dir).unwrap();
+        assert_eq!(keys.len(), 1);
+        let new_a = keys.get("a.example.com").unwrap();
+        assert!(!Arc::ptr_eq(new_a, &old_a));
+        assert_ne!(new_a.cert, old_a.cert);
+
+        // the old one should be kept if the new files are invalid
+        fs::write(dir.join("a.example.com.crt"), b"invalid").unwrap();
+        let keys = scanner.scan(&dir).unwrap();
+        assert!(Arc::ptr_eq(keys.get("a.example.com").unwrap(), new_a));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
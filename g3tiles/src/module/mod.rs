@@ -17,3 +17,7 @@
 pub(crate) mod stream;
 
 pub(crate) mod keyless;
+
+pub(crate) mod cert_dir;
+
+pub(crate) mod acme;
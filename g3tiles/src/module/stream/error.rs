@@ -18,7 +18,7 @@ use std::io;
 
 use thiserror::Error;
 
-use g3_types::net::ConnectError;
+use g3_types::net::{ConnectError, ProxyProtocolEncodeError};
 
 #[derive(Debug, Error)]
 pub(crate) enum StreamConnectError {
@@ -28,4 +28,8 @@ pub(crate) enum StreamConnectError {
     SetupSocketFailed(io::Error),
     #[error("connect failed: {0}")]
     ConnectFailed(#[from] ConnectError),
+    #[error("proxy protocol encode error: {0}")]
+    ProxyProtocolEncodeError(ProxyProtocolEncodeError),
+    #[error("proxy protocol write failed: {0:?}")]
+    ProxyProtocolWriteFailed(io::Error),
 }
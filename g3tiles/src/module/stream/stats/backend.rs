@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -32,6 +32,11 @@ pub(crate) struct StreamBackendStats {
 
     conn_attempt: AtomicU64,
     conn_established: AtomicU64,
+
+    health_checked: AtomicBool,
+    health_peer_healthy: AtomicU64,
+    health_peer_unhealthy: AtomicU64,
+    health_state_change: AtomicU64,
 }
 
 impl StreamBackendStats {
@@ -42,6 +47,10 @@ impl StreamBackendStats {
             extra_metrics_tags: Arc::new(ArcSwapOption::new(None)),
             conn_attempt: AtomicU64::new(0),
             conn_established: AtomicU64::new(0),
+            health_checked: AtomicBool::new(false),
+            health_peer_healthy: AtomicU64::new(0),
+            health_peer_unhealthy: AtomicU64::new(0),
+            health_state_change: AtomicU64::new(0),
         }
     }
 
@@ -78,6 +87,37 @@ impl StreamBackendStats {
     pub(crate) fn conn_established(&self) -> u64 {
         self.conn_established.load(Ordering::Relaxed)
     }
+
+    pub(crate) fn set_health_peers(&self, healthy: usize, unhealthy: usize) {
+        self.health_peer_healthy
+            .store(healthy as u64, Ordering::Relaxed);
+        self.health_peer_unhealthy
+            .store(unhealthy as u64, Ordering::Relaxed);
+        self.health_checked.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn clear_health_peers(&self) {
+        self.health_checked.store(false, Ordering::Relaxed);
+    }
+
+    pub(crate) fn health_peers(&self) -> Option<(u64, u64)> {
+        if self.health_checked.load(Ordering::Relaxed) {
+            Some((
+                self.health_peer_healthy.load(Ordering::Relaxed),
+                self.health_peer_unhealthy.load(Ordering::Relaxed),
+            ))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) fn add_health_state_change(&self) {
+        self.health_state_change.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn health_state_change(&self) -> u64 {
+        self.health_state_change.load(Ordering::Relaxed)
+    }
 }
 
 pub(crate) struct StreamBackendDurationStats {
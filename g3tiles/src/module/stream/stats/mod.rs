@@ -18,7 +18,9 @@ mod server;
 pub(crate) use server::StreamServerStats;
 
 mod task;
-pub(crate) use task::{StreamAcceptTaskCltWrapperStats, StreamRelayTaskCltWrapperStats};
+pub(crate) use task::{
+    StreamAcceptTaskCltWrapperStats, StreamRelayTaskCltWrapperStats, StreamRelayTaskUpsWrapperStats,
+};
 
 mod backend;
 pub(crate) use backend::{
@@ -85,3 +85,28 @@ impl LimitedWriterStats for StreamRelayTaskCltWrapperStats {
         self.server.add_write(size);
     }
 }
+
+#[derive(Clone)]
+pub(crate) struct StreamRelayTaskUpsWrapperStats {
+    task: Arc<TcpStreamTaskStats>,
+}
+
+impl StreamRelayTaskUpsWrapperStats {
+    pub(crate) fn new(task: &Arc<TcpStreamTaskStats>) -> Self {
+        StreamRelayTaskUpsWrapperStats {
+            task: Arc::clone(task),
+        }
+    }
+}
+
+impl LimitedReaderStats for StreamRelayTaskUpsWrapperStats {
+    fn add_read_bytes(&self, size: usize) {
+        self.task.ups.read.add_bytes(size as u64);
+    }
+}
+
+impl LimitedWriterStats for StreamRelayTaskUpsWrapperStats {
+    fn add_write_bytes(&self, size: usize) {
+        self.task.ups.write.add_bytes(size as u64);
+    }
+}
@@ -0,0 +1,40 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use openssl::ssl::SslRef;
+use openssl::x509::X509VerifyResult;
+use rustls::ServerConnection;
+
+use g3_types::net::OpensslCertIdentity;
+
+/// Get the identity of the verified client certificate on a rustls server connection
+pub(crate) fn from_rustls(conn: &ServerConnection) -> Option<Arc<OpensslCertIdentity>> {
+    let cert = conn.peer_certificates()?.first()?;
+    OpensslCertIdentity::from_der(cert.as_ref())
+        .ok()
+        .map(Arc::new)
+}
+
+/// Get the identity of the verified client certificate on an openssl server connection
+pub(crate) fn from_openssl(ssl: &SslRef) -> Option<Arc<OpensslCertIdentity>> {
+    if ssl.verify_result() != X509VerifyResult::OK {
+        return None;
+    }
+    let cert = ssl.peer_certificate()?;
+    OpensslCertIdentity::from_x509(&cert).ok().map(Arc::new)
+}
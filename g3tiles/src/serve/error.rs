@@ -19,6 +19,8 @@ use std::time::Duration;
 
 use thiserror::Error;
 
+#[cfg(feature = "quic")]
+use g3_http::client::HttpResponseParseError;
 use g3_types::net::ConnectError;
 
 use crate::module::stream::StreamConnectError;
@@ -33,6 +35,9 @@ pub(crate) enum ServerTaskError {
     ClientTcpWriteFailed(io::Error),
     #[error("invalid client protocol: {0}")]
     InvalidClientProtocol(&'static str),
+    #[cfg(feature = "quic")]
+    #[error("h3 with client: {0}")]
+    ClientH3Failed(h3::Error),
     #[error("upstream not resolved")]
     UpstreamNotResolved,
     #[error("upstream not connected: {0}")]
@@ -41,6 +46,15 @@ pub(crate) enum ServerTaskError {
     UpstreamReadFailed(io::Error),
     #[error("write to upstream: {0:?}")]
     UpstreamWriteFailed(io::Error),
+    #[cfg(feature = "quic")]
+    #[error("upstream app timeout: {0}")]
+    UpstreamAppTimeout(&'static str),
+    #[cfg(feature = "quic")]
+    #[error("invalid upstream response: {0}")]
+    UpstreamResponseParseFailed(HttpResponseParseError),
+    #[cfg(feature = "quic")]
+    #[error("h2 with upstream: {0}")]
+    UpstreamH2Failed(h2::Error),
     #[error("closed by upstream")]
     ClosedByUpstream,
     #[error("closed by client")]
@@ -63,10 +77,18 @@ impl ServerTaskError {
             ServerTaskError::ClientTcpReadFailed(_) => "ClientTcpReadFailed",
             ServerTaskError::ClientTcpWriteFailed(_) => "ClientTcpWriteFailed",
             ServerTaskError::InvalidClientProtocol(_) => "InvalidClientProtocol",
+            #[cfg(feature = "quic")]
+            ServerTaskError::ClientH3Failed(_) => "ClientH3Failed",
             ServerTaskError::UpstreamNotResolved => "UpstreamNotResolved",
             ServerTaskError::UpstreamNotConnected(_) => "UpstreamNotConnected",
             ServerTaskError::UpstreamReadFailed(_) => "UpstreamReadFailed",
             ServerTaskError::UpstreamWriteFailed(_) => "UpstreamWriteFailed",
+            #[cfg(feature = "quic")]
+            ServerTaskError::UpstreamAppTimeout(_) => "UpstreamAppTimeout",
+            #[cfg(feature = "quic")]
+            ServerTaskError::UpstreamResponseParseFailed(_) => "UpstreamResponseParseFailed",
+            #[cfg(feature = "quic")]
+            ServerTaskError::UpstreamH2Failed(_) => "UpstreamH2Failed",
             ServerTaskError::ClosedByUpstream => "ClosedByUpstream",
             ServerTaskError::ClosedByClient => "ClosedByClient",
             ServerTaskError::CanceledAsServerQuit => "CanceledAsServerQuit",
@@ -87,6 +109,12 @@ impl From<StreamConnectError> for ServerTaskError {
                 "failed to setup local socket for remote connection",
             ),
             StreamConnectError::ConnectFailed(e) => ServerTaskError::UpstreamNotConnected(e),
+            StreamConnectError::ProxyProtocolEncodeError(_) => {
+                ServerTaskError::InternalServerError("failed to encode proxy protocol header")
+            }
+            StreamConnectError::ProxyProtocolWriteFailed(e) => {
+                ServerTaskError::UpstreamWriteFailed(e)
+            }
         }
     }
 }
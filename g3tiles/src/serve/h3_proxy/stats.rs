@@ -0,0 +1,161 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicI32, AtomicIsize, AtomicU64, Ordering};
+use std::sync::Arc;
+
+use arc_swap::ArcSwapOption;
+
+use g3_types::metrics::{NodeName, StaticMetricsTags};
+use g3_types::stats::StatId;
+
+use crate::serve::{ServerStats, ServerStreamSnapshot};
+
+pub(crate) struct H3ProxyServerStats {
+    name: NodeName,
+    id: StatId,
+
+    extra_metrics_tags: Arc<ArcSwapOption<StaticMetricsTags>>,
+
+    online: AtomicIsize,
+    conn_total: AtomicU64,
+
+    task_total: AtomicU64,
+    task_alive_count: AtomicI32,
+
+    stream_total: AtomicU64,
+    stream_alive_count: AtomicI32,
+    stream_failed: AtomicU64,
+    stream_in_bytes: AtomicU64,
+    stream_out_bytes: AtomicU64,
+}
+
+impl H3ProxyServerStats {
+    pub(crate) fn new(name: &NodeName) -> Self {
+        H3ProxyServerStats {
+            name: name.clone(),
+            id: StatId::new(),
+            extra_metrics_tags: Arc::new(ArcSwapOption::new(None)),
+            online: AtomicIsize::new(0),
+            conn_total: AtomicU64::new(0),
+            task_total: AtomicU64::new(0),
+            task_alive_count: AtomicI32::new(0),
+            stream_total: AtomicU64::new(0),
+            stream_alive_count: AtomicI32::new(0),
+            stream_failed: AtomicU64::new(0),
+            stream_in_bytes: AtomicU64::new(0),
+            stream_out_bytes: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn set_online(&self) {
+        self.online.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_offline(&self) {
+        self.online.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_extra_tags(&self, tags: Option<Arc<StaticMetricsTags>>) {
+        self.extra_metrics_tags.store(tags);
+    }
+
+    pub(crate) fn add_conn(&self, _addr: SocketAddr) {
+        self.conn_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_task(&self) {
+        self.task_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn inc_alive_task(&self) {
+        self.task_alive_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn dec_alive_task(&self) {
+        self.task_alive_count.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_stream(&self) {
+        self.stream_total.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn inc_alive_stream(&self) {
+        self.stream_alive_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn dec_alive_stream(&self) {
+        self.stream_alive_count.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn add_stream_failed(&self) {
+        self.stream_failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn add_stream_in_bytes(&self, size: u64) {
+        self.stream_in_bytes.fetch_add(size, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn add_stream_out_bytes(&self, size: u64) {
+        self.stream_out_bytes.fetch_add(size, Ordering::Relaxed);
+    }
+}
+
+impl ServerStats for H3ProxyServerStats {
+    #[inline]
+    fn name(&self) -> &NodeName {
+        &self.name
+    }
+
+    #[inline]
+    fn stat_id(&self) -> StatId {
+        self.id
+    }
+
+    #[inline]
+    fn load_extra_tags(&self) -> Option<Arc<StaticMetricsTags>> {
+        self.extra_metrics_tags.load_full()
+    }
+
+    fn is_online(&self) -> bool {
+        self.online.load(Ordering::Relaxed) > 0
+    }
+
+    fn conn_total(&self) -> u64 {
+        self.conn_total.load(Ordering::Relaxed)
+    }
+
+    fn task_total(&self) -> u64 {
+        self.task_total.load(Ordering::Relaxed)
+    }
+
+    fn alive_count(&self) -> i32 {
+        self.task_alive_count.load(Ordering::Relaxed)
+    }
+
+    fn stream_snapshot(&self) -> Option<ServerStreamSnapshot> {
+        Some(ServerStreamSnapshot {
+            total: self.stream_total.load(Ordering::Relaxed),
+            alive: self.stream_alive_count.load(Ordering::Relaxed),
+            failed: self.stream_failed.load(Ordering::Relaxed),
+            in_bytes: self.stream_in_bytes.load(Ordering::Relaxed),
+            out_bytes: self.stream_out_bytes.load(Ordering::Relaxed),
+        })
+    }
+}
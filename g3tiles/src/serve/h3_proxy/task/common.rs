@@ -0,0 +1,56 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use arc_swap::ArcSwap;
+use slog::Logger;
+
+use g3_daemon::server::ClientConnectionInfo;
+
+use crate::backend::ArcBackend;
+use crate::config::server::h3_proxy::H3ProxyServerConfig;
+use crate::serve::h3_proxy::H3ProxyServerStats;
+use crate::serve::ServerQuitPolicy;
+
+#[derive(Clone)]
+pub(crate) struct CommonTaskContext {
+    pub server_config: Arc<H3ProxyServerConfig>,
+    pub server_stats: Arc<H3ProxyServerStats>,
+    pub server_quit_policy: Arc<ServerQuitPolicy>,
+    pub cc_info: ClientConnectionInfo,
+    pub task_logger: Logger,
+    pub backend_selector: Arc<ArcSwap<ArcBackend>>,
+}
+
+impl CommonTaskContext {
+    pub(super) fn select_backend(&self) -> ArcBackend {
+        self.backend_selector.load().as_ref().clone()
+    }
+
+    #[inline]
+    pub(super) fn client_addr(&self) -> SocketAddr {
+        self.cc_info.client_addr()
+    }
+
+    /// the max time to wait for data on a single stream
+    pub(super) fn stream_idle_timeout(&self) -> Duration {
+        let count = self.server_config.task_idle_max_count.max(1) as u32;
+        self.server_config.task_idle_check_duration * count
+    }
+}
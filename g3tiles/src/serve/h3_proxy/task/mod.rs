@@ -0,0 +1,153 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use http::Request;
+use log::debug;
+use quinn::Connection;
+use tokio::time::Instant;
+
+use crate::config::server::h3_proxy::H3BackendProtocol;
+use crate::config::server::ServerConfig;
+use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult};
+
+mod common;
+pub(super) use common::CommonTaskContext;
+
+mod stream;
+use stream::{H2BackendSession, H3RequestStream, H3StreamTask};
+
+pub(super) struct H3ProxyTask {
+    ctx: Arc<CommonTaskContext>,
+    task_notes: ServerTaskNotes,
+    alive_streams: Arc<AtomicI32>,
+    h2_session: Option<Arc<H2BackendSession>>,
+}
+
+impl H3ProxyTask {
+    pub(super) fn new(ctx: CommonTaskContext) -> Self {
+        let task_notes = ServerTaskNotes::new(ctx.cc_info.clone(), Duration::ZERO);
+        let h2_session = match ctx.server_config.backend_protocol {
+            H3BackendProtocol::Http1 => None,
+            H3BackendProtocol::Http2 => Some(Arc::new(H2BackendSession::default())),
+        };
+        H3ProxyTask {
+            ctx: Arc::new(ctx),
+            task_notes,
+            alive_streams: Arc::new(AtomicI32::new(0)),
+            h2_session,
+        }
+    }
+
+    pub(super) async fn into_running(mut self, connection: Connection) {
+        self.pre_start();
+        if let Err(e) = self.run(connection).await {
+            debug!(
+                "H3Proxy: connection from {} closed: {e}",
+                self.ctx.client_addr()
+            );
+        }
+        self.pre_stop();
+    }
+
+    fn pre_start(&self) {
+        debug!(
+            "H3Proxy: new client from {} to {} server {}",
+            self.ctx.client_addr(),
+            self.ctx.server_config.server_type(),
+            self.ctx.server_config.name(),
+        );
+        self.ctx.server_stats.add_task();
+        self.ctx.server_stats.inc_alive_task();
+    }
+
+    fn pre_stop(&self) {
+        self.ctx.server_stats.dec_alive_task();
+    }
+
+    async fn run(&mut self, connection: Connection) -> ServerTaskResult<()> {
+        let mut h3_conn = h3::server::builder()
+            .build::<_, Bytes>(h3_quinn::Connection::new(connection))
+            .await
+            .map_err(ServerTaskError::ClientH3Failed)?;
+
+        self.task_notes.mark_relaying();
+
+        let idle_duration = self.ctx.server_config.task_idle_check_duration;
+        let mut idle_interval =
+            tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
+        let mut idle_count = 0;
+
+        loop {
+            // keep the accept future alive across idle checks, as it is not cancel safe
+            let accept = h3_conn.accept();
+            tokio::pin!(accept);
+
+            let r = loop {
+                tokio::select! {
+                    biased;
+
+                    r = &mut accept => break r,
+                    _ = idle_interval.tick() => {
+                        if self.alive_streams.load(Ordering::Relaxed) > 0 {
+                            idle_count = 0;
+                        } else {
+                            idle_count += 1;
+
+                            if idle_count >= self.ctx.server_config.task_idle_max_count {
+                                return Err(ServerTaskError::Idle(idle_duration, idle_count));
+                            }
+                        }
+
+                        if self.ctx.server_quit_policy.force_quit() {
+                            return Err(ServerTaskError::CanceledAsServerQuit);
+                        }
+                    }
+                }
+            };
+
+            match r {
+                Ok(Some((req, stream))) => {
+                    idle_count = 0;
+                    self.spawn_stream(req, stream);
+                }
+                Ok(None) => return Err(ServerTaskError::ClosedByClient),
+                Err(e) => return Err(ServerTaskError::ClientH3Failed(e)),
+            }
+        }
+    }
+
+    fn spawn_stream(&self, req: Request<()>, stream: H3RequestStream) {
+        let task = H3StreamTask::new(
+            self.ctx.clone(),
+            req,
+            stream.id(),
+            self.alive_streams.clone(),
+            self.h2_session.clone(),
+        );
+        if self.ctx.server_config.spawn_task_unconstrained {
+            tokio::spawn(
+                async move { tokio::task::unconstrained(task.into_running(stream)).await },
+            );
+        } else {
+            tokio::spawn(async move { task.into_running(stream).await });
+        }
+    }
+}
@@ -0,0 +1,240 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::{self, Write};
+
+use bytes::{BufMut, Bytes};
+use http::{header, HeaderMap, Response, StatusCode};
+use tokio::io::{AsyncBufRead, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+
+use g3_http::client::HttpForwardRemoteResponse;
+use g3_http::{HttpBodyDecodeReader, HttpBodyType};
+
+use super::{H3RequestStream, H3StreamTask};
+use crate::serve::{ServerTaskError, ServerTaskResult};
+
+const BODY_COPY_BUFFER_SIZE: usize = 16 * 1024;
+
+impl H3StreamTask {
+    pub(super) async fn forward_h1<R, W>(
+        &mut self,
+        clt_stream: &mut H3RequestStream,
+        ups_r: R,
+        mut ups_w: W,
+    ) -> ServerTaskResult<()>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        // the request body is only known to be absent after the client has finished the stream
+        let first_data = self.recv_clt_data(clt_stream).await?;
+        let chunked =
+            first_data.is_some() && !self.req.headers().contains_key(header::CONTENT_LENGTH);
+
+        let head = self.build_h1_req_head(chunked);
+        ups_w
+            .write_all(&head)
+            .await
+            .map_err(ServerTaskError::UpstreamWriteFailed)?;
+        self.task_notes.mark_relaying();
+
+        if let Some(data) = first_data {
+            send_h1_body_data(&mut ups_w, &data, chunked).await?;
+            while let Some(data) = self.recv_clt_data(clt_stream).await? {
+                send_h1_body_data(&mut ups_w, &data, chunked).await?;
+            }
+            if chunked {
+                let trailers = clt_stream
+                    .recv_trailers()
+                    .await
+                    .map_err(ServerTaskError::ClientH3Failed)?;
+                send_h1_body_end(&mut ups_w, trailers).await?;
+            }
+        }
+        ups_w
+            .flush()
+            .await
+            .map_err(ServerTaskError::UpstreamWriteFailed)?;
+
+        let mut ups_r = BufReader::new(ups_r);
+        let ups_rsp = self.recv_h1_rsp_head(&mut ups_r).await?;
+
+        let status = StatusCode::from_u16(ups_rsp.code).map_err(|_| {
+            ServerTaskError::InternalServerError("unsupported upstream response status code")
+        })?;
+        let mut rsp = Response::new(());
+        *rsp.status_mut() = status;
+        *rsp.headers_mut() = HeaderMap::from(&ups_rsp.end_to_end_headers);
+        self.send_rsp_head(clt_stream, rsp).await?;
+
+        let Some(body_type) = ups_rsp.body_type(self.req.method()) else {
+            return clt_stream
+                .finish()
+                .await
+                .map_err(ServerTaskError::ClientH3Failed);
+        };
+        let mut body_reader = match body_type {
+            HttpBodyType::ContentLength(size) => {
+                HttpBodyDecodeReader::new_fixed_length(&mut ups_r, size)
+            }
+            HttpBodyType::Chunked => HttpBodyDecodeReader::new_chunked(
+                &mut ups_r,
+                self.ctx.server_config.body_line_max_len,
+            ),
+            HttpBodyType::ReadUntilEnd => HttpBodyDecodeReader::new_read_until_end(&mut ups_r),
+        };
+
+        let mut buf = vec![0u8; BODY_COPY_BUFFER_SIZE];
+        loop {
+            let nr = match tokio::time::timeout(self.idle_timeout, body_reader.read(&mut buf)).await
+            {
+                Ok(Ok(nr)) => nr,
+                Ok(Err(e)) => return Err(ServerTaskError::UpstreamReadFailed(e)),
+                Err(_) => return Err(self.idle_error()),
+            };
+            if nr == 0 {
+                break;
+            }
+            self.send_clt_data(clt_stream, Bytes::copy_from_slice(&buf[..nr]))
+                .await?;
+        }
+
+        let trailers = body_reader
+            .trailer(self.ctx.server_config.rsp_head_max_size)
+            .await
+            .map_err(|e| ServerTaskError::UpstreamReadFailed(io::Error::other(e)))?;
+        match trailers {
+            Some(trailers) => clt_stream
+                .send_trailers(HeaderMap::from(&trailers))
+                .await
+                .map_err(ServerTaskError::ClientH3Failed),
+            None => clt_stream
+                .finish()
+                .await
+                .map_err(ServerTaskError::ClientH3Failed),
+        }
+    }
+
+    fn build_h1_req_head(&self, chunked: bool) -> Vec<u8> {
+        let mut buf = Vec::<u8>::with_capacity(1024);
+        let method = self.req.method();
+        let uri = self.req.uri();
+        let path = uri.path_and_query().map(|pa| pa.as_str()).unwrap_or("/");
+        let _ = write!(buf, "{method} {path} HTTP/1.1\r\n");
+
+        let headers = self.req.headers();
+        if !headers.contains_key(header::HOST) {
+            if let Some(authority) = uri.authority() {
+                let _ = write!(buf, "Host: {authority}\r\n");
+            }
+        }
+        for (name, value) in headers {
+            if super::is_hop_by_hop_header(name) {
+                continue;
+            }
+            buf.put_slice(name.as_ref());
+            buf.put_slice(b": ");
+            buf.put_slice(value.as_bytes());
+            buf.put_slice(b"\r\n");
+        }
+        if chunked {
+            buf.put_slice(b"Transfer-Encoding: chunked\r\n");
+        }
+        // use a new connection for each request
+        buf.put_slice(b"Connection: close\r\n\r\n");
+        buf
+    }
+
+    async fn recv_h1_rsp_head<R>(
+        &mut self,
+        ups_r: &mut R,
+    ) -> ServerTaskResult<HttpForwardRemoteResponse>
+    where
+        R: AsyncBufRead + Unpin,
+    {
+        let max_header_size = self.ctx.server_config.rsp_head_max_size;
+        let recv_rsp = async {
+            loop {
+                let rsp = HttpForwardRemoteResponse::parse(
+                    ups_r,
+                    self.req.method(),
+                    false,
+                    max_header_size,
+                )
+                .await
+                .map_err(ServerTaskError::UpstreamResponseParseFailed)?;
+                // skip all informational responses
+                if rsp.code >= 200 {
+                    return Ok(rsp);
+                }
+            }
+        };
+
+        match tokio::time::timeout(self.ctx.server_config.rsp_head_recv_timeout, recv_rsp).await {
+            Ok(r) => r,
+            Err(_) => Err(ServerTaskError::UpstreamAppTimeout(
+                "timeout to receive response header",
+            )),
+        }
+    }
+}
+
+async fn send_h1_body_data<W>(ups_w: &mut W, data: &[u8], chunked: bool) -> ServerTaskResult<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    if chunked {
+        let head = format!("{:x}\r\n", data.len());
+        ups_w
+            .write_all(head.as_bytes())
+            .await
+            .map_err(ServerTaskError::UpstreamWriteFailed)?;
+        ups_w
+            .write_all(data)
+            .await
+            .map_err(ServerTaskError::UpstreamWriteFailed)?;
+        ups_w
+            .write_all(b"\r\n")
+            .await
+            .map_err(ServerTaskError::UpstreamWriteFailed)
+    } else {
+        ups_w
+            .write_all(data)
+            .await
+            .map_err(ServerTaskError::UpstreamWriteFailed)
+    }
+}
+
+async fn send_h1_body_end<W>(ups_w: &mut W, trailers: Option<HeaderMap>) -> ServerTaskResult<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let mut buf = Vec::<u8>::with_capacity(64);
+    buf.put_slice(b"0\r\n");
+    if let Some(trailers) = trailers {
+        for (name, value) in &trailers {
+            buf.put_slice(name.as_ref());
+            buf.put_slice(b": ");
+            buf.put_slice(value.as_bytes());
+            buf.put_slice(b"\r\n");
+        }
+    }
+    buf.put_slice(b"\r\n");
+    ups_w
+        .write_all(&buf)
+        .await
+        .map_err(ServerTaskError::UpstreamWriteFailed)
+}
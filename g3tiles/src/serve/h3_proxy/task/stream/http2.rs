@@ -0,0 +1,182 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::future::poll_fn;
+use std::io;
+use std::task::Poll;
+
+use bytes::Bytes;
+use h2::client::SendRequest;
+use h2::SendStream;
+use http::{Request, Response, Version};
+use tokio::sync::Mutex;
+
+use super::{H3RequestStream, H3StreamTask};
+use crate::backend::ArcBackend;
+use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult};
+
+/// The HTTP/2 connection to backend, shared by all streams on the same client connection
+#[derive(Default)]
+pub(crate) struct H2BackendSession {
+    send_request: Mutex<Option<SendRequest<Bytes>>>,
+}
+
+impl H2BackendSession {
+    pub(super) async fn get_send_request(
+        &self,
+        backend: &ArcBackend,
+        task_notes: &ServerTaskNotes,
+    ) -> ServerTaskResult<SendRequest<Bytes>> {
+        let mut send_request = self.send_request.lock().await;
+        if let Some(s) = send_request.as_mut() {
+            // only check if the connection is still usable, the stream limit will be waited later
+            let r = poll_fn(|cx| match s.poll_ready(cx) {
+                Poll::Pending => Poll::Ready(Ok(())),
+                Poll::Ready(r) => Poll::Ready(r),
+            })
+            .await;
+            if r.is_ok() {
+                return Ok(s.clone());
+            }
+        }
+
+        let (ups_r, ups_w) = backend.stream_connect(task_notes).await?;
+        let (new_send_request, connection) = h2::client::handshake(tokio::io::join(ups_r, ups_w))
+            .await
+            .map_err(ServerTaskError::UpstreamH2Failed)?;
+        tokio::spawn(async move {
+            let _ = connection.await;
+        });
+        *send_request = Some(new_send_request.clone());
+        Ok(new_send_request)
+    }
+}
+
+impl H3StreamTask {
+    pub(super) async fn forward_h2(
+        &mut self,
+        clt_stream: &mut H3RequestStream,
+        send_request: SendRequest<Bytes>,
+    ) -> ServerTaskResult<()> {
+        let first_data = self.recv_clt_data(clt_stream).await?;
+
+        let mut send_request = send_request
+            .ready()
+            .await
+            .map_err(ServerTaskError::UpstreamH2Failed)?;
+        let (rsp_fut, mut ups_send_stream) = send_request
+            .send_request(self.build_h2_req(), first_data.is_none())
+            .map_err(ServerTaskError::UpstreamH2Failed)?;
+        self.task_notes.mark_relaying();
+
+        if let Some(data) = first_data {
+            send_h2_body_data(&mut ups_send_stream, data).await?;
+            while let Some(data) = self.recv_clt_data(clt_stream).await? {
+                send_h2_body_data(&mut ups_send_stream, data).await?;
+            }
+            let trailers = clt_stream
+                .recv_trailers()
+                .await
+                .map_err(ServerTaskError::ClientH3Failed)?;
+            let r = match trailers {
+                Some(trailers) => ups_send_stream.send_trailers(trailers),
+                None => ups_send_stream.send_data(Bytes::new(), true),
+            };
+            r.map_err(ServerTaskError::UpstreamH2Failed)?;
+        }
+
+        let ups_rsp =
+            match tokio::time::timeout(self.ctx.server_config.rsp_head_recv_timeout, rsp_fut).await
+            {
+                Ok(Ok(rsp)) => rsp,
+                Ok(Err(e)) => return Err(ServerTaskError::UpstreamH2Failed(e)),
+                Err(_) => {
+                    return Err(ServerTaskError::UpstreamAppTimeout(
+                        "timeout to receive response header",
+                    ))
+                }
+            };
+        let (parts, mut ups_recv_stream) = ups_rsp.into_parts();
+        self.send_rsp_head(clt_stream, Response::from_parts(parts, ()))
+            .await?;
+
+        loop {
+            let data = match tokio::time::timeout(self.idle_timeout, ups_recv_stream.data()).await {
+                Ok(Some(Ok(data))) => data,
+                Ok(Some(Err(e))) => return Err(ServerTaskError::UpstreamH2Failed(e)),
+                Ok(None) => break,
+                Err(_) => return Err(self.idle_error()),
+            };
+            let _ = ups_recv_stream.flow_control().release_capacity(data.len());
+            self.send_clt_data(clt_stream, data).await?;
+        }
+
+        let trailers = ups_recv_stream
+            .trailers()
+            .await
+            .map_err(ServerTaskError::UpstreamH2Failed)?;
+        match trailers {
+            Some(trailers) => clt_stream
+                .send_trailers(trailers)
+                .await
+                .map_err(ServerTaskError::ClientH3Failed),
+            None => clt_stream
+                .finish()
+                .await
+                .map_err(ServerTaskError::ClientH3Failed),
+        }
+    }
+
+    fn build_h2_req(&self) -> Request<()> {
+        let mut req = Request::new(());
+        *req.method_mut() = self.req.method().clone();
+        *req.uri_mut() = self.req.uri().clone();
+        *req.version_mut() = Version::HTTP_2;
+        let headers = req.headers_mut();
+        for (name, value) in self.req.headers() {
+            if super::is_hop_by_hop_header(name) {
+                continue;
+            }
+            headers.append(name, value.clone());
+        }
+        req
+    }
+}
+
+async fn send_h2_body_data(
+    send_stream: &mut SendStream<Bytes>,
+    mut data: Bytes,
+) -> ServerTaskResult<()> {
+    while !data.is_empty() {
+        send_stream.reserve_capacity(data.len());
+        match poll_fn(|cx| send_stream.poll_capacity(cx)).await {
+            Some(Ok(n)) => {
+                let chunk = data.split_to(n.min(data.len()));
+                send_stream
+                    .send_data(chunk, false)
+                    .map_err(ServerTaskError::UpstreamH2Failed)?;
+            }
+            Some(Err(e)) => return Err(ServerTaskError::UpstreamH2Failed(e)),
+            None => {
+                return Err(ServerTaskError::UpstreamWriteFailed(io::Error::new(
+                    io::ErrorKind::BrokenPipe,
+                    "h2 stream closed",
+                )))
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,218 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::{Buf, Bytes};
+use h3::quic::StreamId;
+use h3::server::RequestStream;
+use http::{HeaderName, Method, Request, Response, StatusCode};
+
+use super::CommonTaskContext;
+use crate::log::task::h3_stream::TaskLogForH3Stream;
+use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage};
+
+mod http1;
+
+mod http2;
+pub(super) use http2::H2BackendSession;
+
+pub(super) type H3RequestStream = RequestStream<h3_quinn::BidiStream<Bytes>, Bytes>;
+
+pub(super) struct H3StreamTask {
+    ctx: Arc<CommonTaskContext>,
+    task_notes: ServerTaskNotes,
+    req: Request<()>,
+    stream_id: StreamId,
+    alive_streams: Arc<AtomicI32>,
+    h2_session: Option<Arc<H2BackendSession>>,
+    idle_timeout: Duration,
+    rsp_status: u16,
+    clt_rd_bytes: u64,
+    clt_wr_bytes: u64,
+}
+
+impl H3StreamTask {
+    pub(super) fn new(
+        ctx: Arc<CommonTaskContext>,
+        req: Request<()>,
+        stream_id: StreamId,
+        alive_streams: Arc<AtomicI32>,
+        h2_session: Option<Arc<H2BackendSession>>,
+    ) -> Self {
+        let task_notes = ServerTaskNotes::new(ctx.cc_info.clone(), Duration::ZERO);
+        let idle_timeout = ctx.stream_idle_timeout();
+        H3StreamTask {
+            ctx,
+            task_notes,
+            req,
+            stream_id,
+            alive_streams,
+            h2_session,
+            idle_timeout,
+            rsp_status: 0,
+            clt_rd_bytes: 0,
+            clt_wr_bytes: 0,
+        }
+    }
+
+    fn get_log_context(&self) -> TaskLogForH3Stream {
+        TaskLogForH3Stream {
+            task_notes: &self.task_notes,
+            stream_id: self.stream_id,
+            method: self.req.method(),
+            uri: self.req.uri(),
+            uri_log_max_chars: self.ctx.server_config.log_uri_max_chars,
+            rsp_status: self.rsp_status,
+            client_rd_bytes: self.clt_rd_bytes,
+            client_wr_bytes: self.clt_wr_bytes,
+        }
+    }
+
+    pub(super) async fn into_running(mut self, mut clt_stream: H3RequestStream) {
+        self.pre_start();
+        let e = match self.run(&mut clt_stream).await {
+            Ok(_) => ServerTaskError::Finished,
+            Err(e) => {
+                self.ctx.server_stats.add_stream_failed();
+                if self.rsp_status == 0 {
+                    self.reply_error(&mut clt_stream, &e).await;
+                }
+                e
+            }
+        };
+        self.get_log_context().log(&self.ctx.task_logger, &e);
+        self.pre_stop();
+    }
+
+    fn pre_start(&self) {
+        self.alive_streams.fetch_add(1, Ordering::Relaxed);
+        self.ctx.server_stats.add_stream();
+        self.ctx.server_stats.inc_alive_stream();
+    }
+
+    fn pre_stop(&self) {
+        self.alive_streams.fetch_sub(1, Ordering::Relaxed);
+        self.ctx.server_stats.dec_alive_stream();
+    }
+
+    async fn run(&mut self, clt_stream: &mut H3RequestStream) -> ServerTaskResult<()> {
+        self.task_notes.stage = ServerTaskStage::Preparing;
+
+        if self.req.method() == Method::CONNECT {
+            return Err(ServerTaskError::InvalidClientProtocol(
+                "CONNECT method is not supported",
+            ));
+        }
+
+        self.task_notes.stage = ServerTaskStage::Connecting;
+
+        let backend = self.ctx.select_backend();
+        match self.h2_session.clone() {
+            Some(session) => {
+                let send_request = session.get_send_request(&backend, &self.task_notes).await?;
+                self.task_notes.stage = ServerTaskStage::Connected;
+                self.forward_h2(clt_stream, send_request).await
+            }
+            None => {
+                let (ups_r, ups_w) = backend.stream_connect(&self.task_notes).await?;
+                self.task_notes.stage = ServerTaskStage::Connected;
+                self.forward_h1(clt_stream, ups_r, ups_w).await
+            }
+        }
+    }
+
+    async fn reply_error(&mut self, clt_stream: &mut H3RequestStream, e: &ServerTaskError) {
+        let status = match e {
+            ServerTaskError::ClientH3Failed(_)
+            | ServerTaskError::ClosedByClient
+            | ServerTaskError::CanceledAsServerQuit => return,
+            ServerTaskError::InvalidClientProtocol(_) => StatusCode::BAD_REQUEST,
+            ServerTaskError::UpstreamAppTimeout(_) | ServerTaskError::Idle(_, _) => {
+                StatusCode::GATEWAY_TIMEOUT
+            }
+            _ => StatusCode::BAD_GATEWAY,
+        };
+        let mut rsp = Response::new(());
+        *rsp.status_mut() = status;
+        if clt_stream.send_response(rsp).await.is_ok() {
+            self.rsp_status = status.as_u16();
+            let _ = clt_stream.finish().await;
+        }
+    }
+
+    async fn recv_clt_data(
+        &mut self,
+        clt_stream: &mut H3RequestStream,
+    ) -> ServerTaskResult<Option<Bytes>> {
+        match tokio::time::timeout(self.idle_timeout, clt_stream.recv_data()).await {
+            Ok(Ok(Some(mut buf))) => {
+                let data = buf.copy_to_bytes(buf.remaining());
+                self.clt_rd_bytes += data.len() as u64;
+                self.ctx.server_stats.add_stream_in_bytes(data.len() as u64);
+                Ok(Some(data))
+            }
+            Ok(Ok(None)) => Ok(None),
+            Ok(Err(e)) => Err(ServerTaskError::ClientH3Failed(e)),
+            Err(_) => Err(self.idle_error()),
+        }
+    }
+
+    async fn send_rsp_head(
+        &mut self,
+        clt_stream: &mut H3RequestStream,
+        rsp: Response<()>,
+    ) -> ServerTaskResult<()> {
+        let status = rsp.status().as_u16();
+        clt_stream
+            .send_response(rsp)
+            .await
+            .map_err(ServerTaskError::ClientH3Failed)?;
+        self.rsp_status = status;
+        Ok(())
+    }
+
+    async fn send_clt_data(
+        &mut self,
+        clt_stream: &mut H3RequestStream,
+        data: Bytes,
+    ) -> ServerTaskResult<()> {
+        let len = data.len() as u64;
+        clt_stream
+            .send_data(data)
+            .await
+            .map_err(ServerTaskError::ClientH3Failed)?;
+        self.clt_wr_bytes += len;
+        self.ctx.server_stats.add_stream_out_bytes(len);
+        Ok(())
+    }
+
+    fn idle_error(&self) -> ServerTaskError {
+        ServerTaskError::Idle(
+            self.ctx.server_config.task_idle_check_duration,
+            self.ctx.server_config.task_idle_max_count,
+        )
+    }
+}
+
+fn is_hop_by_hop_header(name: &HeaderName) -> bool {
+    matches!(
+        name.as_str(),
+        "connection" | "keep-alive" | "proxy-connection" | "te" | "transfer-encoding" | "upgrade"
+    )
+}
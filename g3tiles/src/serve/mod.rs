@@ -36,14 +36,18 @@ pub(crate) use registry::{foreach_online as foreach_server, get_names, get_or_in
 mod error;
 pub(crate) use error::{ServerTaskError, ServerTaskResult};
 
+mod client_cert;
 mod dummy_close;
 #[cfg(feature = "quic")]
 mod plain_quic_port;
 mod plain_tcp_port;
 
+#[cfg(feature = "quic")]
+mod h3_proxy;
 mod keyless_proxy;
 mod openssl_proxy;
 mod rustls_proxy;
+mod tcp_stream;
 
 mod ops;
 pub(crate) use ops::{
@@ -56,7 +60,7 @@ mod task;
 pub(crate) use task::{ServerTaskNotes, ServerTaskStage};
 
 mod stats;
-pub(crate) use stats::{ArcServerStats, ServerStats};
+pub(crate) use stats::{ArcServerStats, ServerStats, ServerStreamSnapshot};
 
 pub(crate) trait ServerInternal {
     fn _clone_config(&self) -> AnyServerConfig;
@@ -37,6 +37,7 @@ use g3_types::route::HostMatch;
 
 use super::{CommonTaskContext, OpensslRelayTask};
 use crate::module::stream::StreamAcceptTaskCltWrapperStats;
+use crate::serve::client_cert;
 use crate::serve::openssl_proxy::OpensslHost;
 
 pub(crate) struct OpensslAcceptTask {
@@ -100,10 +101,13 @@ impl OpensslAcceptTask {
                     return;
                 };
 
+                let client_cert = client_cert::from_openssl(ssl_stream.ssl());
+
                 OpensslRelayTask::new(
                     self.ctx,
                     host,
                     backend,
+                    client_cert,
                     time_accepted.elapsed(),
                     pre_handshake_stats,
                     self.alive_permit,
@@ -27,6 +27,7 @@ use g3_io_ext::{
 };
 use g3_openssl::SslStream;
 use g3_types::limit::GaugeSemaphorePermit;
+use g3_types::net::OpensslCertIdentity;
 
 use super::CommonTaskContext;
 use crate::backend::ArcBackend;
@@ -40,6 +41,7 @@ pub(crate) struct OpensslRelayTask {
     ctx: CommonTaskContext,
     host: Arc<OpensslHost>,
     backend: ArcBackend,
+    client_cert: Option<Arc<OpensslCertIdentity>>,
     task_notes: ServerTaskNotes,
     task_stats: Arc<TcpStreamTaskStats>,
     alive_permit: Option<GaugeSemaphorePermit>,
@@ -50,6 +52,7 @@ impl OpensslRelayTask {
         ctx: CommonTaskContext,
         host: Arc<OpensslHost>,
         backend: ArcBackend,
+        client_cert: Option<Arc<OpensslCertIdentity>>,
         wait_time: Duration,
         pre_handshake_stats: Arc<TcpStreamConnectionStats>,
         alive_permit: Option<GaugeSemaphorePermit>,
@@ -59,6 +62,7 @@ impl OpensslRelayTask {
             ctx,
             host,
             backend,
+            client_cert,
             task_notes,
             task_stats: Arc::new(TcpStreamTaskStats::with_clt_stats(
                 pre_handshake_stats.as_ref().clone(),
@@ -70,6 +74,7 @@ impl OpensslRelayTask {
     fn get_log_context(&self) -> TaskLogForTcpConnect {
         TaskLogForTcpConnect {
             task_notes: &self.task_notes,
+            client_cert: self.client_cert.as_deref(),
             client_rd_bytes: self.task_stats.clt.read.get_bytes(),
             client_wr_bytes: self.task_stats.clt.write.get_bytes(),
             remote_rd_bytes: self.task_stats.ups.read.get_bytes(),
@@ -34,9 +34,12 @@ use super::dummy_close::DummyCloseServer;
 use super::plain_quic_port::PlainQuicPort;
 use super::plain_tcp_port::PlainTcpPort;
 
+#[cfg(feature = "quic")]
+use super::h3_proxy::H3ProxyServer;
 use super::keyless_proxy::KeylessProxyServer;
 use super::openssl_proxy::OpensslProxyServer;
 use super::rustls_proxy::RustlsProxyServer;
+use super::tcp_stream::TcpStreamServer;
 
 static SERVER_OPS_LOCK: Mutex<()> = Mutex::const_new(());
 
@@ -228,6 +231,9 @@ fn spawn_new_unlocked(config: AnyServerConfig) -> anyhow::Result<()> {
         AnyServerConfig::OpensslProxy(c) => OpensslProxyServer::prepare_initial(c)?,
         AnyServerConfig::RustlsProxy(c) => RustlsProxyServer::prepare_initial(c)?,
         AnyServerConfig::KeylessProxy(c) => KeylessProxyServer::prepare_initial(c)?,
+        #[cfg(feature = "quic")]
+        AnyServerConfig::H3Proxy(c) => H3ProxyServer::prepare_initial(c)?,
+        AnyServerConfig::TcpStream(c) => TcpStreamServer::prepare_initial(c)?,
     };
     registry::add(name.clone(), server)?;
     update_dependency_to_server_unlocked(&name, "spawned");
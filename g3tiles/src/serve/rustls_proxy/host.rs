@@ -32,6 +32,7 @@ use crate::config::server::rustls_proxy::RustlsHostConfig;
 pub(crate) struct RustlsHost {
     pub(super) config: Arc<RustlsHostConfig>,
     pub(super) tls_config: Arc<ServerConfig>,
+    pub(super) acme_tls_config: Option<Arc<ServerConfig>>,
     req_alive_sem: Option<GaugeSemaphore>,
     request_rate_limit: Option<Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>>,
     pub(crate) backends: Arc<ArcSwap<AlpnMatch<ArcBackend>>>,
@@ -42,7 +43,7 @@ impl RustlsHost {
         config: &Arc<RustlsHostConfig>,
         tls_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
     ) -> anyhow::Result<Self> {
-        let tls_config = config.build_tls_config(tls_ticketer)?;
+        let (tls_config, acme_tls_config) = config.build_tls_config(tls_ticketer)?;
 
         let backends = config.backends.build(crate::backend::get_or_insert_default);
 
@@ -55,6 +56,7 @@ impl RustlsHost {
         Ok(RustlsHost {
             config: config.clone(),
             tls_config,
+            acme_tls_config,
             req_alive_sem,
             request_rate_limit,
             backends: Arc::new(ArcSwap::new(Arc::new(backends))),
@@ -66,7 +68,7 @@ impl RustlsHost {
         config: Arc<RustlsHostConfig>,
         tls_ticketer: Option<Arc<RollingTicketer<OpensslTicketKey>>>,
     ) -> anyhow::Result<Self> {
-        let tls_config = config.build_tls_config(tls_ticketer)?;
+        let (tls_config, acme_tls_config) = config.build_tls_config(tls_ticketer)?;
 
         let request_rate_limit = if let Some(quota) = &config.request_rate_limit {
             if let Some(old_limiter) = &self.request_rate_limit {
@@ -100,6 +102,7 @@ impl RustlsHost {
         let new_host = RustlsHost {
             config,
             tls_config,
+            acme_tls_config,
             req_alive_sem,
             request_rate_limit,
             backends: self.backends.clone(), // use the old container
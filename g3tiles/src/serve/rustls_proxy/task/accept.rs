@@ -32,7 +32,9 @@ use g3_types::net::{Host, RustlsServerConnectionExt};
 use g3_types::route::HostMatch;
 
 use super::{CommonTaskContext, RustlsRelayTask};
+use crate::module::acme::ACME_TLS_ALPN_PROTOCOL;
 use crate::module::stream::StreamAcceptTaskCltWrapperStats;
+use crate::serve::client_cert;
 use crate::serve::rustls_proxy::RustlsHost;
 
 pub(crate) struct RustlsAcceptTask {
@@ -69,6 +71,11 @@ impl RustlsAcceptTask {
         );
 
         if let Some((mut tls_stream, host)) = self.handshake(stream, hosts).await {
+            if tls_stream.get_ref().1.alpn_protocol() == Some(ACME_TLS_ALPN_PROTOCOL) {
+                // the TLS-ALPN-01 validation is done after the handshake
+                let _ = tls_stream.shutdown().await;
+                return;
+            }
             if tls_stream.get_ref().1.session_reused() {
                 // Quick ACK is needed with session resumption
                 self.ctx.cc_info.tcp_sock_try_quick_ack();
@@ -86,10 +93,13 @@ impl RustlsAcceptTask {
                 return;
             };
 
+            let client_cert = client_cert::from_rustls(tls_stream.get_ref().1);
+
             RustlsRelayTask::new(
                 self.ctx,
                 host,
                 backend.clone(),
+                client_cert,
                 time_accepted.elapsed(),
                 pre_handshake_stats,
                 self.alive_permit,
@@ -128,7 +138,11 @@ impl RustlsAcceptTask {
                 };
                 self.alive_permit = sema;
 
-                let accept = d.into_stream(host.tls_config.clone());
+                let tls_config = match &host.acme_tls_config {
+                    Some(acme_config) if is_acme_tls_alpn(&client_hello) => acme_config.clone(),
+                    _ => host.tls_config.clone(),
+                };
+                let accept = d.into_stream(tls_config);
                 match tokio::time::timeout(host.config.accept_timeout, accept).await {
                     Ok(Ok(s)) => Some((s, host)),
                     Ok(Err(e)) => {
@@ -174,3 +188,10 @@ impl RustlsAcceptTask {
         hosts.get_default().cloned()
     }
 }
+
+fn is_acme_tls_alpn(client_hello: &ClientHello) -> bool {
+    client_hello
+        .alpn()
+        .map(|mut iter| iter.any(|p| p == ACME_TLS_ALPN_PROTOCOL))
+        .unwrap_or(false)
+}
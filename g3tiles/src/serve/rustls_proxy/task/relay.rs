@@ -25,6 +25,7 @@ use tokio_rustls::server::TlsStream;
 use g3_daemon::stat::task::{TcpStreamConnectionStats, TcpStreamTaskStats};
 use g3_io_ext::{AsyncStream, LimitedCopy, LimitedCopyConfig, LimitedCopyError, LimitedStream};
 use g3_types::limit::GaugeSemaphorePermit;
+use g3_types::net::OpensslCertIdentity;
 
 use super::CommonTaskContext;
 use crate::backend::ArcBackend;
@@ -38,6 +39,7 @@ pub(crate) struct RustlsRelayTask {
     ctx: CommonTaskContext,
     host: Arc<RustlsHost>,
     backend: ArcBackend,
+    client_cert: Option<Arc<OpensslCertIdentity>>,
     task_notes: ServerTaskNotes,
     task_stats: Arc<TcpStreamTaskStats>,
     alive_permit: Option<GaugeSemaphorePermit>,
@@ -48,6 +50,7 @@ impl RustlsRelayTask {
         ctx: CommonTaskContext,
         host: Arc<RustlsHost>,
         backend: ArcBackend,
+        client_cert: Option<Arc<OpensslCertIdentity>>,
         wait_time: Duration,
         pre_handshake_stats: Arc<TcpStreamConnectionStats>,
         alive_permit: Option<GaugeSemaphorePermit>,
@@ -57,6 +60,7 @@ impl RustlsRelayTask {
             ctx,
             host,
             backend,
+            client_cert,
             task_notes,
             task_stats: Arc::new(TcpStreamTaskStats::with_clt_stats(
                 pre_handshake_stats.as_ref().clone(),
@@ -68,6 +72,7 @@ impl RustlsRelayTask {
     fn get_log_context(&self) -> TaskLogForTcpConnect {
         TaskLogForTcpConnect {
             task_notes: &self.task_notes,
+            client_cert: self.client_cert.as_deref(),
             client_rd_bytes: self.task_stats.clt.read.get_bytes(),
             client_wr_bytes: self.task_stats.clt.write.get_bytes(),
             remote_rd_bytes: self.task_stats.ups.read.get_bytes(),
@@ -19,6 +19,15 @@ use std::sync::Arc;
 use g3_types::metrics::{NodeName, StaticMetricsTags};
 use g3_types::stats::{StatId, TcpIoSnapshot, UdpIoSnapshot};
 
+#[derive(Default)]
+pub(crate) struct ServerStreamSnapshot {
+    pub(crate) total: u64,
+    pub(crate) alive: i32,
+    pub(crate) failed: u64,
+    pub(crate) in_bytes: u64,
+    pub(crate) out_bytes: u64,
+}
+
 pub(crate) trait ServerStats {
     fn name(&self) -> &NodeName;
     fn stat_id(&self) -> StatId;
@@ -39,6 +48,10 @@ pub(crate) trait ServerStats {
     fn udp_io_snapshot(&self) -> Option<UdpIoSnapshot> {
         None
     }
+    /// stats for multiplexed streams, such as HTTP/3 requests
+    fn stream_snapshot(&self) -> Option<ServerStreamSnapshot> {
+        None
+    }
 }
 
 pub(crate) type ArcServerStats = Arc<dyn ServerStats + Send + Sync>;
@@ -0,0 +1,211 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use log::debug;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::time::Instant;
+
+use g3_daemon::stat::task::TcpStreamTaskStats;
+use g3_io_ext::{LimitedCopy, LimitedCopyError, LimitedReader, LimitedWriter};
+
+use super::CommonTaskContext;
+use crate::config::server::ServerConfig;
+use crate::log::task::tcp_connect::TaskLogForTcpConnect;
+use crate::module::stream::{StreamRelayTaskCltWrapperStats, StreamRelayTaskUpsWrapperStats};
+use crate::serve::{ServerTaskError, ServerTaskNotes, ServerTaskResult, ServerTaskStage};
+
+pub(crate) struct TcpStreamTask {
+    ctx: CommonTaskContext,
+    task_notes: ServerTaskNotes,
+    task_stats: Arc<TcpStreamTaskStats>,
+}
+
+impl TcpStreamTask {
+    pub(crate) fn new(ctx: CommonTaskContext) -> Self {
+        let task_notes = ServerTaskNotes::new(ctx.cc_info.clone(), Duration::ZERO);
+        TcpStreamTask {
+            ctx,
+            task_notes,
+            task_stats: Arc::new(TcpStreamTaskStats::default()),
+        }
+    }
+
+    fn get_log_context(&self) -> TaskLogForTcpConnect {
+        TaskLogForTcpConnect {
+            task_notes: &self.task_notes,
+            client_cert: None,
+            client_rd_bytes: self.task_stats.clt.read.get_bytes(),
+            client_wr_bytes: self.task_stats.clt.write.get_bytes(),
+            remote_rd_bytes: self.task_stats.ups.read.get_bytes(),
+            remote_wr_bytes: self.task_stats.ups.write.get_bytes(),
+        }
+    }
+
+    pub(crate) async fn into_running<CR, CW>(mut self, clt_r: CR, clt_w: CW)
+    where
+        CR: AsyncRead + Unpin,
+        CW: AsyncWrite + Unpin,
+    {
+        self.pre_start();
+        let (clt_r, clt_w) = self.setup_limit_and_stats(clt_r, clt_w);
+        if let Err(e) = self.run(clt_r, clt_w).await {
+            self.get_log_context().log(&self.ctx.task_logger, &e)
+        }
+        self.pre_stop();
+    }
+
+    fn pre_start(&self) {
+        debug!(
+            "TcpStream: new client from {} to {} server {}",
+            self.ctx.client_addr(),
+            self.ctx.server_config.server_type(),
+            self.ctx.server_config.name(),
+        );
+        self.ctx.server_stats.add_task();
+        self.ctx.server_stats.inc_alive_task();
+    }
+
+    fn pre_stop(&self) {
+        self.ctx.server_stats.dec_alive_task();
+    }
+
+    async fn run<CR, CW>(&mut self, clt_r: CR, clt_w: CW) -> ServerTaskResult<()>
+    where
+        CR: AsyncRead + Unpin,
+        CW: AsyncWrite + Unpin,
+    {
+        self.task_notes.stage = ServerTaskStage::Preparing;
+
+        // set client side socket options
+        self.ctx
+            .cc_info
+            .tcp_sock_set_raw_opts(&self.ctx.server_config.tcp_misc_opts, true)
+            .map_err(|_| {
+                ServerTaskError::InternalServerError("failed to set client socket options")
+            })?;
+
+        self.task_notes.stage = ServerTaskStage::Connecting;
+
+        let backend = self.ctx.select_backend();
+        let (ups_r, ups_w) = backend.stream_connect(&self.task_notes).await?;
+        let ups_stats = Arc::new(StreamRelayTaskUpsWrapperStats::new(&self.task_stats));
+        let ups_r = LimitedReader::new(ups_r, ups_stats.clone());
+        let ups_w = LimitedWriter::new(ups_w, ups_stats);
+
+        self.task_notes.stage = ServerTaskStage::Connected;
+
+        self.task_notes.mark_relaying();
+        self.relay(clt_r, clt_w, ups_r, ups_w).await
+    }
+
+    async fn relay<CR, CW, UR, UW>(
+        &mut self,
+        mut clt_r: CR,
+        mut clt_w: CW,
+        mut ups_r: UR,
+        mut ups_w: UW,
+    ) -> ServerTaskResult<()>
+    where
+        CR: AsyncRead + Unpin,
+        CW: AsyncWrite + Unpin,
+        UR: AsyncRead + Unpin,
+        UW: AsyncWrite + Unpin,
+    {
+        let copy_config = self.ctx.server_config.tcp_copy;
+        let mut clt_to_ups = LimitedCopy::new(&mut clt_r, &mut ups_w, &copy_config);
+        let mut ups_to_clt = LimitedCopy::new(&mut ups_r, &mut clt_w, &copy_config);
+
+        let idle_duration = self.ctx.server_config.task_idle_check_duration;
+        let task_idle_max_count = self.ctx.server_config.task_idle_max_count;
+        let mut idle_interval =
+            tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
+        let mut idle_count = 0;
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut clt_to_ups => {
+                    let _ = ups_to_clt.write_flush().await;
+                    return match r {
+                        Ok(_) => Err(ServerTaskError::ClosedByClient),
+                        Err(LimitedCopyError::ReadFailed(e)) => Err(ServerTaskError::ClientTcpReadFailed(e)),
+                        Err(LimitedCopyError::WriteFailed(e)) => Err(ServerTaskError::UpstreamWriteFailed(e)),
+                    };
+                }
+                r = &mut ups_to_clt => {
+                    let _ = clt_to_ups.write_flush().await;
+                    return match r {
+                        Ok(_) => Err(ServerTaskError::ClosedByUpstream),
+                        Err(LimitedCopyError::ReadFailed(e)) => Err(ServerTaskError::UpstreamReadFailed(e)),
+                        Err(LimitedCopyError::WriteFailed(e)) => Err(ServerTaskError::ClientTcpWriteFailed(e)),
+                    };
+                }
+                _ = idle_interval.tick() => {
+                    if clt_to_ups.is_idle() && ups_to_clt.is_idle() {
+                        idle_count += 1;
+
+                        if idle_count >= task_idle_max_count {
+                            return Err(ServerTaskError::Idle(idle_duration, idle_count));
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        clt_to_ups.reset_active();
+                        ups_to_clt.reset_active();
+                    }
+
+                    if self.ctx.server_quit_policy.force_quit() {
+                        return Err(ServerTaskError::CanceledAsServerQuit)
+                    }
+                }
+            };
+        }
+    }
+
+    fn setup_limit_and_stats<CR, CW>(
+        &self,
+        clt_r: CR,
+        clt_w: CW,
+    ) -> (LimitedReader<CR>, LimitedWriter<CW>)
+    where
+        CR: AsyncRead,
+        CW: AsyncWrite,
+    {
+        let wrapper_stats = Arc::new(StreamRelayTaskCltWrapperStats::new(
+            &self.ctx.server_stats,
+            &self.task_stats,
+        ));
+        let limit_config = &self.ctx.server_config.tcp_sock_speed_limit;
+
+        let clt_r = LimitedReader::local_limited(
+            clt_r,
+            limit_config.shift_millis,
+            limit_config.max_north,
+            wrapper_stats.clone(),
+        );
+        let clt_w = LimitedWriter::local_limited(
+            clt_w,
+            limit_config.shift_millis,
+            limit_config.max_south,
+            wrapper_stats,
+        );
+
+        (clt_r, clt_w)
+    }
+}
@@ -18,7 +18,7 @@ use std::sync::{Arc, LazyLock, Mutex};
 
 use ahash::AHashMap;
 
-use g3_daemon::metrics::TAG_KEY_QUANTILE;
+use g3_daemon::metrics::{TAG_KEY_BUCKET_LE, TAG_KEY_QUANTILE};
 use g3_statsd_client::{StatsdClient, StatsdTagGroup};
 use g3_types::stats::StatId;
 
@@ -39,6 +39,10 @@ const METRIC_NAME_KEYLESS_RESPONSE_DROP: &str = "backend.keyless.response.drop";
 const METRIC_NAME_KEYLESS_CONNECT_DURATION: &str = "backend.keyless.connect.duration";
 const METRIC_NAME_KEYLESS_WAIT_DURATION: &str = "backend.keyless.wait.duration";
 const METRIC_NAME_KEYLESS_RESPONSE_DURATION: &str = "backend.keyless.response.duration";
+const METRIC_NAME_KEYLESS_CONNECT_DURATION_BUCKET: &str = "backend.keyless.connect.duration.bucket";
+const METRIC_NAME_KEYLESS_WAIT_DURATION_BUCKET: &str = "backend.keyless.wait.duration.bucket";
+const METRIC_NAME_KEYLESS_RESPONSE_DURATION_BUCKET: &str =
+    "backend.keyless.response.duration.bucket";
 
 type KeylessBackendStatsValue = (Arc<KeylessBackendStats>, KeylessBackendSnapshot);
 
@@ -161,7 +165,7 @@ fn emit_keyless_duration_stats(
     }
 
     macro_rules! emit_duration {
-        ($field:ident, $name:expr) => {
+        ($field:ident, $name:expr, $bucket_name:expr) => {
             stats.$field.foreach_stat(|_, qs, v| {
                 if v > 0_f64 {
                     client
@@ -169,11 +173,29 @@ fn emit_keyless_duration_stats(
                         .with_tag(TAG_KEY_QUANTILE, qs)
                         .send();
                 }
+            });
+            stats.$field.foreach_bucket(|le, count| {
+                client
+                    .gauge_with_tags($bucket_name, count, &common_tags)
+                    .with_tag(TAG_KEY_BUCKET_LE, le)
+                    .send();
             })
         };
     }
 
-    emit_duration!(connect, METRIC_NAME_KEYLESS_CONNECT_DURATION);
-    emit_duration!(wait, METRIC_NAME_KEYLESS_WAIT_DURATION);
-    emit_duration!(response, METRIC_NAME_KEYLESS_RESPONSE_DURATION);
+    emit_duration!(
+        connect,
+        METRIC_NAME_KEYLESS_CONNECT_DURATION,
+        METRIC_NAME_KEYLESS_CONNECT_DURATION_BUCKET
+    );
+    emit_duration!(
+        wait,
+        METRIC_NAME_KEYLESS_WAIT_DURATION,
+        METRIC_NAME_KEYLESS_WAIT_DURATION_BUCKET
+    );
+    emit_duration!(
+        response,
+        METRIC_NAME_KEYLESS_RESPONSE_DURATION,
+        METRIC_NAME_KEYLESS_RESPONSE_DURATION_BUCKET
+    );
 }
@@ -18,7 +18,7 @@ use std::sync::{Arc, LazyLock, Mutex};
 
 use ahash::AHashMap;
 
-use g3_daemon::metrics::TAG_KEY_QUANTILE;
+use g3_daemon::metrics::{TAG_KEY_BUCKET_LE, TAG_KEY_QUANTILE};
 use g3_histogram::HistogramStats;
 use g3_statsd_client::{StatsdClient, StatsdTagGroup};
 use g3_types::stats::StatId;
@@ -28,8 +28,12 @@ use crate::module::stream::{StreamBackendDurationStats, StreamBackendStats};
 
 const METRIC_NAME_STREAM_CONN_ATTEMPT: &str = "backend.stream.connection.attempt";
 const METRIC_NAME_STREAM_CONN_ESTABLISHED: &str = "backend.stream.connection.established";
+const METRIC_NAME_STREAM_HEALTH_PEER_HEALTHY: &str = "backend.stream.health.peer.healthy";
+const METRIC_NAME_STREAM_HEALTH_PEER_UNHEALTHY: &str = "backend.stream.health.peer.unhealthy";
+const METRIC_NAME_STREAM_HEALTH_STATE_CHANGE: &str = "backend.stream.health.state_change";
 
 const METRIC_NAME_STREAM_CONNECT_DURATION: &str = "backend.stream.connect.duration";
+const METRIC_NAME_STREAM_CONNECT_DURATION_BUCKET: &str = "backend.stream.connect.duration.bucket";
 
 type StreamBackendStatsValue = (Arc<StreamBackendStats>, StreamBackendSnapshot);
 
@@ -48,6 +52,7 @@ static STREAM_DURATION_STATS_MAP: LazyLock<
 struct StreamBackendSnapshot {
     conn_attempt: u64,
     conn_established: u64,
+    health_state_change: u64,
 }
 
 pub(crate) fn push_stream_stats(stats: Arc<StreamBackendStats>) {
@@ -111,6 +116,24 @@ fn emit_stream_stats(
 
     emit_count!(conn_attempt, METRIC_NAME_STREAM_CONN_ATTEMPT);
     emit_count!(conn_established, METRIC_NAME_STREAM_CONN_ESTABLISHED);
+
+    if let Some((healthy, unhealthy)) = stats.health_peers() {
+        client
+            .gauge_with_tags(
+                METRIC_NAME_STREAM_HEALTH_PEER_HEALTHY,
+                healthy,
+                &common_tags,
+            )
+            .send();
+        client
+            .gauge_with_tags(
+                METRIC_NAME_STREAM_HEALTH_PEER_UNHEALTHY,
+                unhealthy,
+                &common_tags,
+            )
+            .send();
+        emit_count!(health_state_change, METRIC_NAME_STREAM_HEALTH_STATE_CHANGE);
+    }
 }
 
 fn emit_stream_duration_stats(client: &mut StatsdClient, stats: &Arc<StreamBackendDurationStats>) {
@@ -135,5 +158,15 @@ fn emit_stream_connect_duration_stats(
                 .with_tag(TAG_KEY_QUANTILE, qs)
                 .send();
         }
-    })
+    });
+    stats.foreach_bucket(|le, count| {
+        client
+            .gauge_with_tags(
+                METRIC_NAME_STREAM_CONNECT_DURATION_BUCKET,
+                count,
+                common_tags,
+            )
+            .with_tag(TAG_KEY_BUCKET_LE, le)
+            .send();
+    });
 }
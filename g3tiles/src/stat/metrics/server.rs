@@ -25,7 +25,7 @@ use g3_daemon::metrics::{
 use g3_statsd_client::{StatsdClient, StatsdTagGroup};
 use g3_types::stats::{StatId, TcpIoSnapshot, UdpIoSnapshot};
 
-use crate::serve::ArcServerStats;
+use crate::serve::{ArcServerStats, ServerStreamSnapshot};
 
 const METRIC_NAME_SERVER_CONN_TOTAL: &str = "server.connection.total";
 const METRIC_NAME_SERVER_TASK_TOTAL: &str = "server.task.total";
@@ -34,6 +34,11 @@ const METRIC_NAME_SERVER_IO_IN_BYTES: &str = "server.traffic.in.bytes";
 const METRIC_NAME_SERVER_IO_IN_PACKETS: &str = "server.traffic.in.packets";
 const METRIC_NAME_SERVER_IO_OUT_BYTES: &str = "server.traffic.out.bytes";
 const METRIC_NAME_SERVER_IO_OUT_PACKETS: &str = "server.traffic.out.packets";
+const METRIC_NAME_SERVER_STREAM_TOTAL: &str = "server.stream.total";
+const METRIC_NAME_SERVER_STREAM_ALIVE: &str = "server.stream.alive";
+const METRIC_NAME_SERVER_STREAM_FAILED: &str = "server.stream.failed";
+const METRIC_NAME_SERVER_STREAM_IN_BYTES: &str = "server.stream.in.bytes";
+const METRIC_NAME_SERVER_STREAM_OUT_BYTES: &str = "server.stream.out.bytes";
 
 type ServerStatsValue = (ArcServerStats, ServerSnapshot);
 type ListenStatsValue = (Arc<ListenStats>, ListenSnapshot);
@@ -49,6 +54,7 @@ struct ServerSnapshot {
     task_total: u64,
     tcp: TcpIoSnapshot,
     udp: UdpIoSnapshot,
+    stream: ServerStreamSnapshot,
 }
 
 pub(in crate::stat) fn sync_stats() {
@@ -127,6 +133,10 @@ fn emit_server_stats(client: &mut StatsdClient, stats: &ArcServerStats, snap: &m
     if let Some(udp_io_stats) = stats.udp_io_snapshot() {
         emit_udp_io_to_statsd(client, udp_io_stats, &mut snap.udp, &common_tags);
     }
+
+    if let Some(stream_stats) = stats.stream_snapshot() {
+        emit_stream_to_statsd(client, stream_stats, &mut snap.stream, &common_tags);
+    }
 }
 
 fn emit_tcp_io_to_statsd(
@@ -182,3 +192,30 @@ fn emit_udp_io_to_statsd(
     emit_field!(out_packets, METRIC_NAME_SERVER_IO_OUT_PACKETS);
     emit_field!(out_bytes, METRIC_NAME_SERVER_IO_OUT_BYTES);
 }
+
+fn emit_stream_to_statsd(
+    client: &mut StatsdClient,
+    stats: ServerStreamSnapshot,
+    snap: &mut ServerStreamSnapshot,
+    common_tags: &StatsdTagGroup,
+) {
+    client
+        .gauge_with_tags(METRIC_NAME_SERVER_STREAM_ALIVE, stats.alive, common_tags)
+        .send();
+
+    macro_rules! emit_field {
+        ($field:ident, $name:expr) => {
+            let new_value = stats.$field;
+            let diff_value = new_value.wrapping_sub(snap.$field);
+            client
+                .count_with_tags($name, diff_value, common_tags)
+                .send();
+            snap.$field = new_value;
+        };
+    }
+
+    emit_field!(total, METRIC_NAME_SERVER_STREAM_TOTAL);
+    emit_field!(failed, METRIC_NAME_SERVER_STREAM_FAILED);
+    emit_field!(in_bytes, METRIC_NAME_SERVER_STREAM_IN_BYTES);
+    emit_field!(out_bytes, METRIC_NAME_SERVER_STREAM_OUT_BYTES);
+}
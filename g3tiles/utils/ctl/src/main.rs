@@ -38,6 +38,8 @@ fn build_cli_args() -> Command {
         .subcommand(proc::commands::reload_server())
         .subcommand(proc::commands::reload_discover())
         .subcommand(proc::commands::reload_backend())
+        .subcommand(proc::commands::drain_backend_peer())
+        .subcommand(proc::commands::undrain_backend_peer())
         .subcommand(server::command())
 }
 
@@ -73,6 +75,12 @@ async fn main() -> anyhow::Result<()> {
                 proc::COMMAND_RELOAD_SERVER => proc::reload_server(&proc_control, args).await,
                 proc::COMMAND_RELOAD_DISCOVER => proc::reload_discover(&proc_control, args).await,
                 proc::COMMAND_RELOAD_BACKEND => proc::reload_backend(&proc_control, args).await,
+                proc::COMMAND_DRAIN_BACKEND_PEER => {
+                    proc::drain_backend_peer(&proc_control, args).await
+                }
+                proc::COMMAND_UNDRAIN_BACKEND_PEER => {
+                    proc::undrain_backend_peer(&proc_control, args).await
+                }
                 server::COMMAND => server::run(&proc_control, args).await,
                 _ => Err(CommandError::Cli(anyhow!(
                     "unsupported command {subcommand}"
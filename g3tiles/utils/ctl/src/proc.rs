@@ -41,7 +41,11 @@ pub const COMMAND_RELOAD_SERVER: &str = "reload-server";
 pub const COMMAND_RELOAD_DISCOVER: &str = "reload-discover";
 pub const COMMAND_RELOAD_BACKEND: &str = "reload-backend";
 
+pub const COMMAND_DRAIN_BACKEND_PEER: &str = "drain-backend-peer";
+pub const COMMAND_UNDRAIN_BACKEND_PEER: &str = "undrain-backend-peer";
+
 const SUBCOMMAND_ARG_NAME: &str = "name";
+const SUBCOMMAND_ARG_PEER: &str = "peer";
 
 pub mod commands {
     use super::*;
@@ -98,6 +102,20 @@ pub mod commands {
         Command::new(COMMAND_RELOAD_BACKEND)
             .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
     }
+
+    pub fn drain_backend_peer() -> Command {
+        Command::new(COMMAND_DRAIN_BACKEND_PEER)
+            .about("Stop sending new connections to the peer of the backend")
+            .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
+            .arg(Arg::new(SUBCOMMAND_ARG_PEER).required(true).num_args(1))
+    }
+
+    pub fn undrain_backend_peer() -> Command {
+        Command::new(COMMAND_UNDRAIN_BACKEND_PEER)
+            .about("Resume sending new connections to the drained peer of the backend")
+            .arg(Arg::new(SUBCOMMAND_ARG_NAME).required(true).num_args(1))
+            .arg(Arg::new(SUBCOMMAND_ARG_PEER).required(true).num_args(1))
+    }
 }
 
 pub async fn version(client: &proc_control::Client) -> CommandResult<()> {
@@ -190,6 +208,32 @@ pub async fn reload_backend(client: &proc_control::Client, args: &ArgMatches) ->
     parse_operation_result(rsp.get()?.get_result()?)
 }
 
+pub async fn drain_backend_peer(
+    client: &proc_control::Client,
+    args: &ArgMatches,
+) -> CommandResult<()> {
+    let name = args.get_one::<String>(SUBCOMMAND_ARG_NAME).unwrap();
+    let peer = args.get_one::<String>(SUBCOMMAND_ARG_PEER).unwrap();
+    let mut req = client.drain_backend_peer_request();
+    req.get().set_name(name);
+    req.get().set_peer(peer);
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
+pub async fn undrain_backend_peer(
+    client: &proc_control::Client,
+    args: &ArgMatches,
+) -> CommandResult<()> {
+    let name = args.get_one::<String>(SUBCOMMAND_ARG_NAME).unwrap();
+    let peer = args.get_one::<String>(SUBCOMMAND_ARG_PEER).unwrap();
+    let mut req = client.undrain_backend_peer_request();
+    req.get().set_name(name);
+    req.get().set_peer(peer);
+    let rsp = req.send().promise.await?;
+    parse_operation_result(rsp.get()?.get_result()?)
+}
+
 pub(crate) async fn get_server(
     client: &proc_control::Client,
     name: &str,
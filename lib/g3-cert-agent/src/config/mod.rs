@@ -20,7 +20,9 @@ use std::time::Duration;
 use anyhow::{anyhow, Context};
 use tokio::net::UdpSocket;
 
-use g3_types::net::SocketBufferConfig;
+use g3_types::net::{
+    Host, OpensslClientConfig, OpensslClientConfigBuilder, OpensslSpkiPinSet, SocketBufferConfig,
+};
 
 use super::{CertAgentHandle, QueryRuntime};
 
@@ -35,6 +37,9 @@ pub struct CertAgentConfig {
     pub(crate) query_peer_addr: SocketAddr,
     pub(crate) query_socket_buffer: SocketBufferConfig,
     pub(crate) query_wait_timeout: Duration,
+    pub(crate) query_tls_client: Option<OpensslClientConfigBuilder>,
+    pub(crate) query_tls_name: Option<Host>,
+    pub(crate) query_tls_pin: OpensslSpkiPinSet,
     pub(crate) protective_cache_ttl: u32,
     pub(crate) maximum_cache_ttl: u32,
 }
@@ -48,6 +53,9 @@ impl Default for CertAgentConfig {
             query_peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 2999),
             query_socket_buffer: SocketBufferConfig::default(),
             query_wait_timeout: Duration::from_secs(4),
+            query_tls_client: None,
+            query_tls_name: None,
+            query_tls_pin: OpensslSpkiPinSet::default(),
             protective_cache_ttl: 10,
             maximum_cache_ttl: 300,
         }
@@ -79,6 +87,18 @@ impl CertAgentConfig {
         self.query_wait_timeout = time;
     }
 
+    pub fn set_query_tls_client(&mut self, builder: OpensslClientConfigBuilder) {
+        self.query_tls_client = Some(builder);
+    }
+
+    pub fn set_query_tls_name(&mut self, name: Host) {
+        self.query_tls_name = Some(name);
+    }
+
+    pub fn set_query_tls_pin(&mut self, pin_set: OpensslSpkiPinSet) {
+        self.query_tls_pin = pin_set;
+    }
+
     pub fn set_protective_cache_ttl(&mut self, ttl: u32) {
         self.protective_cache_ttl = ttl;
     }
@@ -88,6 +108,13 @@ impl CertAgentConfig {
     }
 
     pub fn spawn_cert_agent(&self) -> anyhow::Result<CertAgentHandle> {
+        if let Some(builder) = &self.query_tls_client {
+            let tls_client = builder
+                .build()
+                .context("failed to build tls client config")?;
+            return Ok(self.spawn_tls_cert_agent(tls_client));
+        }
+
         let socket = g3_socket::udp::new_std_socket_to(
             self.query_peer_addr,
             &Default::default(),
@@ -124,4 +151,20 @@ impl CertAgentConfig {
             self.cache_request_timeout,
         ))
     }
+
+    fn spawn_tls_cert_agent(&self, tls_client: OpensslClientConfig) -> CertAgentHandle {
+        let (cache_runtime, cache_handle, query_handle) =
+            g3_io_ext::create_effective_cache(self.cache_request_batch_count);
+
+        let query_runtime = super::query::run_with_tls(self.clone(), tls_client, query_handle);
+        if let Some(rt) = crate::get_cert_generate_rt_handle() {
+            rt.spawn(query_runtime);
+            rt.spawn(cache_runtime);
+        } else {
+            tokio::spawn(query_runtime);
+            tokio::spawn(cache_runtime);
+        }
+
+        CertAgentHandle::new(cache_handle, self.cache_request_timeout)
+    }
 }
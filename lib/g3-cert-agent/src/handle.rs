@@ -67,4 +67,14 @@ impl CertAgentHandle {
             .await
             .and_then(|r| r.inner().cloned())
     }
+
+    /// Send a query without mimic cert to the cert generator, which can be used to check
+    /// whether the cert generator is reachable
+    pub async fn probe(&self, host: Arc<str>) -> Option<FakeCertPair> {
+        let query_key = CacheQueryKey::new(TlsServiceType::Http, TlsCertUsage::TlsServer, host);
+        self.inner
+            .fetch(Arc::new(query_key), self.request_timeout)
+            .await
+            .and_then(|r| r.inner().cloned())
+    }
 }
@@ -45,6 +45,16 @@ pub use handle::CertAgentHandle;
 mod runtime;
 pub use runtime::*;
 
+/// Parse the response data in the same way as the query runtime, used as a fuzzing entry point.
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub fn parse_response(mut data: &[u8]) -> anyhow::Result<()> {
+    let v = rmpv::decode::read_value_ref(&mut data)
+        .map_err(|e| anyhow!("invalid msgpack response data: {e}"))?;
+    Response::parse(v, 10)?.into_parts()?;
+    Ok(())
+}
+
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
 struct CacheIndexKey {
     service: TlsServiceType,
@@ -129,11 +139,16 @@ impl Eq for CacheQueryKey {}
 pub struct FakeCertPair {
     certs: Vec<X509>,
     key: PKey<Private>,
+    ocsp_stapling: Option<Vec<u8>>,
 }
 
 impl FakeCertPair {
     pub fn add_to_ssl(self, ssl: &mut SslRef) -> anyhow::Result<()> {
-        let FakeCertPair { certs, key } = self;
+        let FakeCertPair {
+            certs,
+            key,
+            ocsp_stapling,
+        } = self;
         let mut certs_iter = certs.into_iter();
         let Some(leaf_cert) = certs_iter.next() else {
             return Err(anyhow!("no certificate found"));
@@ -146,12 +161,16 @@ impl FakeCertPair {
         }
         ssl.set_private_key(&key)
             .map_err(|e| anyhow!("failed to set private key: {e}"))?;
+        if let Some(ocsp) = ocsp_stapling {
+            ssl.set_ocsp_status(&ocsp)
+                .map_err(|e| anyhow!("failed to set ocsp stapling response: {e}"))?;
+        }
         Ok(())
     }
 
     #[cfg(feature = "tongsuo")]
     pub fn add_enc_to_tlcp(self, ssl: &mut SslRef) -> anyhow::Result<()> {
-        let FakeCertPair { certs, key } = self;
+        let FakeCertPair { certs, key, .. } = self;
         let mut certs_iter = certs.into_iter();
         let Some(leaf_cert) = certs_iter.next() else {
             return Err(anyhow!("no certificate found"));
@@ -169,7 +188,7 @@ impl FakeCertPair {
 
     #[cfg(feature = "tongsuo")]
     pub fn add_sign_to_tlcp(self, ssl: &mut SslRef) -> anyhow::Result<()> {
-        let FakeCertPair { certs, key } = self;
+        let FakeCertPair { certs, key, .. } = self;
         let mut certs_iter = certs.into_iter();
         let Some(leaf_cert) = certs_iter.next() else {
             return Err(anyhow!("no certificate found"));
@@ -35,6 +35,7 @@ pub mod response_key {
     pub const PRIVATE_KEY: &str = "key";
     pub const TTL: &str = "ttl";
     pub const USAGE: &str = "usage";
+    pub const OCSP_STAPLING: &str = "ocsp_stapling";
 }
 
 pub mod response_key_id {
@@ -44,4 +45,5 @@ pub mod response_key_id {
     pub const PRIVATE_KEY: u64 = 4;
     pub const TTL: u64 = 5;
     pub const USAGE: u64 = 6;
+    pub const OCSP_STAPLING: u64 = 7;
 }
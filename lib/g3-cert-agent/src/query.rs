@@ -15,7 +15,7 @@
  */
 
 use std::collections::VecDeque;
-use std::future::Future;
+use std::future::{poll_fn, Future};
 use std::io;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -24,15 +24,14 @@ use std::time::Duration;
 
 use anyhow::anyhow;
 use log::{debug, warn};
-use tokio::io::ReadBuf;
-use tokio::net::UdpSocket;
 
-use g3_io_ext::{EffectiveCacheData, EffectiveQueryHandle};
+use g3_io_ext::{AsyncMessageIo, EffectiveCacheData, EffectiveQueryHandle, LengthPrefixedStream};
+use g3_types::net::{Host, OpensslClientConfig};
 
 use super::{CacheQueryKey, CertAgentConfig, FakeCertPair, Response};
 
-pub(super) struct QueryRuntime {
-    socket: UdpSocket,
+pub(super) struct QueryRuntime<T> {
+    io: T,
     query_handle: EffectiveQueryHandle<CacheQueryKey, FakeCertPair>,
     read_buffer: Box<[u8]>,
     write_queue: VecDeque<(Arc<CacheQueryKey>, Vec<u8>)>,
@@ -42,14 +41,14 @@ pub(super) struct QueryRuntime {
     query_wait: Duration,
 }
 
-impl QueryRuntime {
+impl<T: AsyncMessageIo> QueryRuntime<T> {
     pub(super) fn new(
         config: &CertAgentConfig,
-        socket: UdpSocket,
+        io: T,
         query_handle: EffectiveQueryHandle<CacheQueryKey, FakeCertPair>,
     ) -> Self {
         QueryRuntime {
-            socket,
+            io,
             query_handle,
             read_buffer: vec![0u8; 16384].into_boxed_slice(),
             write_queue: VecDeque::new(),
@@ -60,6 +59,10 @@ impl QueryRuntime {
         }
     }
 
+    pub(super) fn into_query_handle(self) -> EffectiveQueryHandle<CacheQueryKey, FakeCertPair> {
+        self.query_handle
+    }
+
     fn send_empty_result(&mut self, req: Arc<CacheQueryKey>, expired: bool) {
         let result = EffectiveCacheData::empty(self.protective_ttl, self.vanish_wait);
         self.query_handle.send_rsp_data(req, result, expired);
@@ -104,18 +107,16 @@ impl QueryRuntime {
         }
     }
 
-    fn poll_loop(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    pub(super) fn poll_loop(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         loop {
             // handle rsp
-            let mut buf = ReadBuf::new(&mut self.read_buffer);
-            match self.socket.poll_recv(cx, &mut buf) {
+            match self.io.poll_recv_msg(cx, &mut self.read_buffer) {
                 Poll::Pending => {}
                 Poll::Ready(Err(e)) => {
                     warn!("socket recv error: {e:?}");
                     return Poll::Ready(Err(e));
                 }
-                Poll::Ready(Ok(_)) => {
-                    let len = buf.filled().len();
+                Poll::Ready(Ok(len)) => {
                     if len > 0 {
                         self.handle_rsp(len);
                     }
@@ -125,7 +126,7 @@ impl QueryRuntime {
 
             // send req from write queue
             while let Some((req_key, v)) = self.write_queue.pop_front() {
-                match self.socket.poll_send(cx, v.as_slice()) {
+                match self.io.poll_send_msg(cx, v.as_slice()) {
                     Poll::Pending => {
                         self.write_queue.push_front((req_key, v));
                         break;
@@ -138,6 +139,12 @@ impl QueryRuntime {
                 }
             }
 
+            // flush the buffered req
+            if let Poll::Ready(Err(e)) = self.io.poll_flush_msg(cx) {
+                warn!("socket send error: {e:?}");
+                return Poll::Ready(Err(e));
+            }
+
             // handle timeout
             loop {
                 match self.query_handle.poll_query_expired(cx) {
@@ -160,10 +167,44 @@ impl QueryRuntime {
     }
 }
 
-impl Future for QueryRuntime {
+impl<T: AsyncMessageIo + Unpin> Future for QueryRuntime<T> {
     type Output = io::Result<()>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         (*self).poll_loop(cx)
     }
 }
+
+const TLS_RECONNECT_WAIT: Duration = Duration::from_secs(1);
+
+/// Run the query runtime over a TLS connection, and reconnect if it's broken
+pub(super) async fn run_with_tls(
+    config: CertAgentConfig,
+    tls_client: OpensslClientConfig,
+    mut query_handle: EffectiveQueryHandle<CacheQueryKey, FakeCertPair>,
+) {
+    let peer = config.query_peer_addr;
+    let tls_name = config.query_tls_name.clone().unwrap_or(Host::Ip(peer.ip()));
+    loop {
+        match g3_io_ext::openssl::connect_with_pin(
+            peer,
+            &tls_name,
+            &tls_client,
+            &config.query_tls_pin,
+        )
+        .await
+        {
+            Ok(stream) => {
+                let io = LengthPrefixedStream::new(stream);
+                let mut runtime = QueryRuntime::new(&config, io, query_handle);
+                if poll_fn(|cx| runtime.poll_loop(cx)).await.is_ok() {
+                    // all cache handles have been dropped
+                    return;
+                }
+                query_handle = runtime.into_query_handle();
+            }
+            Err(e) => warn!("failed to setup tls connection to cert generator: {e}"),
+        }
+        tokio::time::sleep(TLS_RECONNECT_WAIT).await;
+    }
+}
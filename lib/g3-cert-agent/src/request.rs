@@ -154,8 +154,14 @@ impl Request {
         Ok(request)
     }
 
-    pub fn encode_rsp(&self, pem_cert: &str, der_key: &[u8], ttl: u32) -> anyhow::Result<Vec<u8>> {
-        let map = vec![
+    pub fn encode_rsp(
+        &self,
+        pem_cert: &str,
+        der_key: &[u8],
+        ocsp_stapling: Option<&[u8]>,
+        ttl: u32,
+    ) -> anyhow::Result<Vec<u8>> {
+        let mut map = vec![
             (
                 ValueRef::Integer(response_key_id::HOST.into()),
                 ValueRef::String(self.host.as_ref().into()),
@@ -181,6 +187,12 @@ impl Request {
                 ValueRef::Integer(ttl.into()),
             ),
         ];
+        if let Some(ocsp) = ocsp_stapling {
+            map.push((
+                ValueRef::Integer(response_key_id::OCSP_STAPLING.into()),
+                ValueRef::Binary(ocsp),
+            ));
+        }
         let mut buf = Vec::with_capacity(4096);
         let v = ValueRef::Map(map);
         rmpv::encode::write_value_ref(&mut buf, &v)
@@ -25,12 +25,28 @@ use g3_types::net::{TlsCertUsage, TlsServiceType};
 
 use super::{response_key, response_key_id, CacheQueryKey, FakeCertPair};
 
+fn as_ocsp_response(v: &ValueRef) -> anyhow::Result<Vec<u8>> {
+    match v {
+        ValueRef::Binary(b) => {
+            if b.is_empty() {
+                Err(anyhow!("empty ocsp response"))
+            } else {
+                Ok(b.to_vec())
+            }
+        }
+        _ => Err(anyhow!(
+            "msgpack value type for 'ocsp response' should be 'binary'"
+        )),
+    }
+}
+
 pub(super) struct Response {
     host: String,
     service: TlsServiceType,
     usage: TlsCertUsage,
     certs: Vec<X509>,
     key: Option<PKey<Private>>,
+    ocsp_stapling: Option<Vec<u8>>,
     ttl: u32,
 }
 
@@ -42,6 +58,7 @@ impl Response {
             usage: TlsCertUsage::TlsServer,
             certs: Vec::new(),
             key: None,
+            ocsp_stapling: None,
             ttl: protective_ttl,
         }
     }
@@ -78,6 +95,11 @@ impl Response {
                         self.ttl = g3_msgpack::value::as_u32(&v)
                             .context(format!("invalid u32 value for key {key}"))?;
                     }
+                    response_key::OCSP_STAPLING => {
+                        let data = as_ocsp_response(&v)
+                            .context(format!("invalid ocsp response value for key {key}"))?;
+                        self.ocsp_stapling = Some(data);
+                    }
                     _ => {} // ignore unknown keys
                 }
             }
@@ -112,6 +134,11 @@ impl Response {
                         self.ttl = g3_msgpack::value::as_u32(&v)
                             .context(format!("invalid u32 value for key id {key_id}"))?;
                     }
+                    response_key_id::OCSP_STAPLING => {
+                        let data = as_ocsp_response(&v)
+                            .context(format!("invalid ocsp response value for key id {key_id}"))?;
+                        self.ocsp_stapling = Some(data);
+                    }
                     _ => {} // ignore unknown keys
                 }
             }
@@ -142,6 +169,7 @@ impl Response {
             FakeCertPair {
                 certs: self.certs,
                 key,
+                ocsp_stapling: self.ocsp_stapling,
             },
             self.ttl,
         ))
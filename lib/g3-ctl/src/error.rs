@@ -14,14 +14,18 @@
  * limitations under the License.
  */
 
+use std::process::ExitCode;
 use std::str::Utf8Error;
 
+use serde_json::json;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum CommandError {
     #[error("cli error ({0:?})")]
     Cli(#[from] anyhow::Error),
+    #[error("daemon not running ({0:?})")]
+    NotRunning(anyhow::Error),
     #[error("rpc error ({0:?})")]
     Rpc(#[from] capnp::Error),
     #[error("api error (code: {code:?}, reason: {reason:?})")]
@@ -48,4 +52,54 @@ impl CommandError {
     }
 }
 
+impl CommandError {
+    /// The stable exit code for each kind of error
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CommandError::Cli(_) => 2,
+            CommandError::NotRunning(_) => 3,
+            CommandError::Rpc(_) | CommandError::Utf8 { .. } => 4,
+            CommandError::Api { .. } => 5,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            CommandError::Cli(_) => "invalid_argument",
+            CommandError::NotRunning(_) => "not_running",
+            CommandError::Rpc(_) | CommandError::Utf8 { .. } => "rpc_error",
+            CommandError::Api { .. } => "api_error",
+        }
+    }
+
+    fn report(&self) {
+        if crate::is_json_output() {
+            let code = match self {
+                CommandError::Api { code, .. } => Some(*code),
+                _ => None,
+            };
+            crate::print_json(&json!({
+                "error": {
+                    "kind": self.kind(),
+                    "code": code,
+                    "message": self.to_string(),
+                }
+            }));
+        } else {
+            eprintln!("Error: {self}");
+        }
+    }
+}
+
 pub type CommandResult<T> = Result<T, CommandError>;
+
+/// Report the error if any, and get the exit code for the process
+pub fn exit_with_result(r: CommandResult<()>) -> ExitCode {
+    match r {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            e.report();
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
@@ -14,12 +14,18 @@
  * limitations under the License.
  */
 
+use serde_json::{json, Value};
+
 use super::{CommandError, CommandResult};
 
 pub fn print_ok_notice(notice_reader: capnp::text::Reader<'_>) -> CommandResult<()> {
     match notice_reader.to_str() {
         Ok(notice) => {
-            println!("notice: {notice}");
+            if crate::is_json_output() {
+                crate::print_json(&json!({"ok": notice}));
+            } else {
+                println!("notice: {notice}");
+            }
             Ok(())
         }
         Err(e) => Err(CommandError::Utf8 {
@@ -32,7 +38,11 @@ pub fn print_ok_notice(notice_reader: capnp::text::Reader<'_>) -> CommandResult<
 pub fn print_text(field: &'static str, text_reader: capnp::text::Reader<'_>) -> CommandResult<()> {
     match text_reader.to_str() {
         Ok(text) => {
-            println!("{text}");
+            if crate::is_json_output() {
+                crate::print_json(&json!({field: text}));
+            } else {
+                println!("{text}");
+            }
             Ok(())
         }
         Err(e) => Err(CommandError::Utf8 { field, reason: e }),
@@ -48,8 +58,19 @@ pub fn print_text_list(
     field: &'static str,
     list: capnp::text_list::Reader<'_>,
 ) -> CommandResult<()> {
-    for text in list.iter() {
-        print_text(field, text?)?;
+    if crate::is_json_output() {
+        let mut values = Vec::with_capacity(list.len() as usize);
+        for text in list.iter() {
+            let text = text?
+                .to_str()
+                .map_err(|e| CommandError::Utf8 { field, reason: e })?;
+            values.push(Value::String(text.to_string()));
+        }
+        crate::print_json(&json!({field: values}));
+    } else {
+        for text in list.iter() {
+            print_text(field, text?)?;
+        }
     }
     Ok(())
 }
@@ -60,12 +81,24 @@ pub fn print_result_list(result_list_reader: capnp::text_list::Reader<'_>) -> Co
 }
 
 pub fn print_data(data_reader: capnp::data::Reader<'_>) {
-    println!("{}", hex::encode(data_reader));
+    if crate::is_json_output() {
+        crate::print_json(&json!({"data": hex::encode(data_reader)}));
+    } else {
+        println!("{}", hex::encode(data_reader));
+    }
 }
 
 pub fn print_data_list(list: capnp::data_list::Reader<'_>) -> CommandResult<()> {
-    for data in list.iter() {
-        print_data(data?);
+    if crate::is_json_output() {
+        let mut values = Vec::with_capacity(list.len() as usize);
+        for data in list.iter() {
+            values.push(Value::String(hex::encode(data?)));
+        }
+        crate::print_json(&json!({"data": values}));
+    } else {
+        for data in list.iter() {
+            print_data(data?);
+        }
     }
     Ok(())
 }
@@ -15,10 +15,15 @@
  */
 
 mod opts;
+#[cfg(feature = "tls")]
+mod tls;
 pub use opts::{DaemonCtlArgs, DaemonCtlArgsExt};
 
 mod error;
-pub use error::{CommandError, CommandResult};
+pub use error::{exit_with_result, CommandError, CommandResult};
 
 mod io;
 pub use io::*;
+
+mod output;
+pub use output::{is_json_output, output_format, print_json, OutputFormat};
@@ -16,6 +16,7 @@
 
 use std::io;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use anyhow::anyhow;
 use capnp_rpc::{rpc_twoparty_capnp, twoparty, RpcSystem};
@@ -23,6 +24,10 @@ use clap::{value_parser, Arg, ArgMatches, Command, ValueHint};
 use clap_complete::Shell;
 use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 
+#[cfg(feature = "tls")]
+use crate::tls::TlsConnectArgs;
+use crate::OutputFormat;
+
 #[cfg(unix)]
 const DEFAULT_TMP_CONTROL_DIR: &str = "/tmp/g3";
 
@@ -30,6 +35,17 @@ const GLOBAL_ARG_COMPLETION: &str = "completion";
 const GLOBAL_ARG_CONTROL_DIR: &str = "control-dir";
 const GLOBAL_ARG_GROUP: &str = "daemon-group";
 const GLOBAL_ARG_PID: &str = "pid";
+const GLOBAL_ARG_OUTPUT: &str = "output";
+#[cfg(feature = "tls")]
+const GLOBAL_ARG_TLS_CONNECT: &str = "tls-connect";
+#[cfg(feature = "tls")]
+const GLOBAL_ARG_TLS_NAME: &str = "tls-name";
+#[cfg(feature = "tls")]
+const GLOBAL_ARG_TLS_CA_CERT: &str = "tls-ca-cert";
+#[cfg(feature = "tls")]
+const GLOBAL_ARG_TLS_CERT: &str = "tls-cert";
+#[cfg(feature = "tls")]
+const GLOBAL_ARG_TLS_KEY: &str = "tls-key";
 
 pub trait DaemonCtlArgsExt {
     fn append_daemon_ctl_args(self) -> Self;
@@ -42,6 +58,8 @@ pub struct DaemonCtlArgs {
     control_dir: Option<PathBuf>,
     daemon_group: String,
     pid: usize,
+    #[cfg(feature = "tls")]
+    tls_connect: Option<TlsConnectArgs>,
 }
 
 impl DaemonCtlArgs {
@@ -66,6 +84,30 @@ impl DaemonCtlArgs {
             config.pid = *pid;
         }
 
+        #[cfg(feature = "tls")]
+        if let Some(addr) = args.get_one::<String>(GLOBAL_ARG_TLS_CONNECT) {
+            // cert and key are required by clap
+            if let (Some(cert), Some(key)) = (
+                args.get_one::<PathBuf>(GLOBAL_ARG_TLS_CERT),
+                args.get_one::<PathBuf>(GLOBAL_ARG_TLS_KEY),
+            ) {
+                config.tls_connect = Some(TlsConnectArgs {
+                    addr: addr.clone(),
+                    server_name: args.get_one::<String>(GLOBAL_ARG_TLS_NAME).cloned(),
+                    ca_cert: args.get_one::<PathBuf>(GLOBAL_ARG_TLS_CA_CERT).cloned(),
+                    cert: cert.clone(),
+                    key: key.clone(),
+                });
+            }
+        }
+
+        if let Some(output) = args.get_one::<String>(GLOBAL_ARG_OUTPUT) {
+            // the value has been checked by clap
+            if let Ok(format) = OutputFormat::from_str(output) {
+                crate::output::set_output_format(format);
+            }
+        }
+
         config
     }
 
@@ -89,20 +131,15 @@ impl DaemonCtlArgs {
     where
         T: capnp::capability::FromClientHook,
     {
-        let stream = self.connect_to_daemon(daemon_name).await?;
+        #[cfg(feature = "tls")]
+        if let Some(tls_connect) = &self.tls_connect {
+            let mut stream = tls_connect.connect().await?;
+            self.enter_rpc_mode(&mut stream).await?;
+            return Ok(build_rpc_client(stream));
+        }
 
-        let (reader, writer) = tokio::io::split(stream);
-        let reader = tokio_util::compat::TokioAsyncReadCompatExt::compat(reader);
-        let writer = tokio_util::compat::TokioAsyncWriteCompatExt::compat_write(writer);
-        let rpc_network = Box::new(twoparty::VatNetwork::new(
-            reader,
-            writer,
-            rpc_twoparty_capnp::Side::Client,
-            Default::default(),
-        ));
-        let mut rpc_system = RpcSystem::new(rpc_network, None);
-        let client: T = rpc_system.bootstrap(rpc_twoparty_capnp::Side::Server);
-        Ok((rpc_system, client))
+        let stream = self.connect_to_daemon(daemon_name).await?;
+        Ok(build_rpc_client(stream))
     }
 
     #[cfg(unix)]
@@ -111,7 +148,10 @@ impl DaemonCtlArgs {
         daemon_name: &'static str,
     ) -> anyhow::Result<impl AsyncRead + AsyncWrite> {
         let control_dir = self.control_dir.clone().unwrap_or_else(|| {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
             let mut sys_ctl_dir = PathBuf::from("/run");
+            #[cfg(not(any(target_os = "linux", target_os = "android")))]
+            let mut sys_ctl_dir = PathBuf::from("/var/run");
             sys_ctl_dir.push(daemon_name);
 
             if sys_ctl_dir.is_dir() {
@@ -149,9 +189,9 @@ impl DaemonCtlArgs {
             format!(r"\\.\pipe\{daemon_name}@{}", self.daemon_group)
         };
 
-        let mut stream = tokio::net::windows::named_pipe::ClientOptions::new()
-            .open(&pipe_name)
-            .map_err(|e| anyhow!("failed to open connection to pipe {}: {e:?}", pipe_name))?;
+        let mut stream = g3_socket::named_pipe::open_client(&pipe_name)
+            .await
+            .map_err(|e| anyhow!("failed to open connection to pipe {pipe_name}: {e:?}"))?;
         self.enter_rpc_mode(&mut stream).await?;
         Ok(stream)
     }
@@ -172,6 +212,25 @@ impl DaemonCtlArgs {
     }
 }
 
+fn build_rpc_client<S, T>(stream: S) -> (RpcSystem<rpc_twoparty_capnp::Side>, T)
+where
+    S: AsyncRead + AsyncWrite + 'static,
+    T: capnp::capability::FromClientHook,
+{
+    let (reader, writer) = tokio::io::split(stream);
+    let reader = tokio_util::compat::TokioAsyncReadCompatExt::compat(reader);
+    let writer = tokio_util::compat::TokioAsyncWriteCompatExt::compat_write(writer);
+    let rpc_network = Box::new(twoparty::VatNetwork::new(
+        reader,
+        writer,
+        rpc_twoparty_capnp::Side::Client,
+        Default::default(),
+    ));
+    let mut rpc_system = RpcSystem::new(rpc_network, None);
+    let client: T = rpc_system.bootstrap(rpc_twoparty_capnp::Side::Server);
+    (rpc_system, client)
+}
+
 impl DaemonCtlArgsExt for Command {
     fn append_daemon_ctl_args(self) -> Self {
         self.arg(
@@ -210,5 +269,85 @@ impl DaemonCtlArgsExt for Command {
                 .short('p')
                 .long("daemon-pid"),
         )
+        .arg(
+            Arg::new(GLOBAL_ARG_OUTPUT)
+                .help("Output format")
+                .num_args(1)
+                .value_name("FORMAT")
+                .value_parser(["text", "json"])
+                .default_value("text")
+                .global(true)
+                .long("output"),
+        )
+        .append_tls_connect_args()
+    }
+}
+
+trait TlsConnectArgsExt {
+    fn append_tls_connect_args(self) -> Self;
+}
+
+impl TlsConnectArgsExt for Command {
+    #[cfg(feature = "tls")]
+    fn append_tls_connect_args(self) -> Self {
+        // the daemon group or pid is not needed if connect via tls
+        self.mut_arg(GLOBAL_ARG_GROUP, |a| {
+            a.required_unless_present(GLOBAL_ARG_TLS_CONNECT)
+        })
+        .mut_arg(GLOBAL_ARG_PID, |a| {
+            a.required_unless_present(GLOBAL_ARG_TLS_CONNECT)
+        })
+        .arg(
+            Arg::new(GLOBAL_ARG_TLS_CONNECT)
+                .help("Connect to the tls control listener of the daemon")
+                .num_args(1)
+                .value_name("ADDRESS")
+                .requires_all([GLOBAL_ARG_TLS_CERT, GLOBAL_ARG_TLS_KEY])
+                .conflicts_with_all([GLOBAL_ARG_GROUP, GLOBAL_ARG_PID])
+                .long("tls-connect"),
+        )
+        .arg(
+            Arg::new(GLOBAL_ARG_TLS_NAME)
+                .help("Server name to verify for the tls control listener")
+                .num_args(1)
+                .value_name("SERVER NAME")
+                .requires(GLOBAL_ARG_TLS_CONNECT)
+                .long("tls-name"),
+        )
+        .arg(
+            Arg::new(GLOBAL_ARG_TLS_CA_CERT)
+                .help("CA certificate file to verify the tls control listener")
+                .num_args(1)
+                .value_name("CA CERT FILE")
+                .value_hint(ValueHint::FilePath)
+                .value_parser(value_parser!(PathBuf))
+                .requires(GLOBAL_ARG_TLS_CONNECT)
+                .long("tls-ca-cert"),
+        )
+        .arg(
+            Arg::new(GLOBAL_ARG_TLS_CERT)
+                .help("Client certificate file for the tls control listener")
+                .num_args(1)
+                .value_name("CERT FILE")
+                .value_hint(ValueHint::FilePath)
+                .value_parser(value_parser!(PathBuf))
+                .requires(GLOBAL_ARG_TLS_CONNECT)
+                .long("tls-cert"),
+        )
+        .arg(
+            Arg::new(GLOBAL_ARG_TLS_KEY)
+                .help("Client private key file for the tls control listener")
+                .num_args(1)
+                .value_name("KEY FILE")
+                .value_hint(ValueHint::FilePath)
+                .value_parser(value_parser!(PathBuf))
+                .requires(GLOBAL_ARG_TLS_CONNECT)
+                .long("tls-key"),
+        )
+    }
+
+    #[cfg(not(feature = "tls"))]
+    fn append_tls_connect_args(self) -> Self {
+        self
     }
 }
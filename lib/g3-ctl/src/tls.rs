@@ -0,0 +1,83 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use openssl::ssl::{SslConnector, SslFiletype, SslMethod};
+use tokio::net::TcpStream;
+
+use g3_openssl::SslStream;
+
+/// Connect to the tcp control listener of the daemon, which requires mTLS
+#[derive(Debug)]
+pub(crate) struct TlsConnectArgs {
+    pub(crate) addr: String,
+    pub(crate) server_name: Option<String>,
+    pub(crate) ca_cert: Option<PathBuf>,
+    pub(crate) cert: PathBuf,
+    pub(crate) key: PathBuf,
+}
+
+impl TlsConnectArgs {
+    fn server_name(&self) -> String {
+        if let Some(name) = &self.server_name {
+            return name.clone();
+        }
+        if let Ok(addr) = self.addr.parse::<SocketAddr>() {
+            // the ip address will be verified instead of the domain name
+            return addr.ip().to_string();
+        }
+        self.addr
+            .rsplit_once(':')
+            .map(|v| v.0)
+            .unwrap_or(&self.addr)
+            .to_string()
+    }
+
+    pub(crate) async fn connect(&self) -> anyhow::Result<SslStream<TcpStream>> {
+        let mut builder = SslConnector::builder(SslMethod::tls_client())
+            .map_err(|e| anyhow!("failed to create ssl connector builder: {e}"))?;
+        if let Some(ca_cert) = &self.ca_cert {
+            builder
+                .set_ca_file(ca_cert)
+                .map_err(|e| anyhow!("failed to load ca cert {}: {e}", ca_cert.display()))?;
+        }
+        builder
+            .set_certificate_chain_file(&self.cert)
+            .map_err(|e| anyhow!("failed to load cert {}: {e}", self.cert.display()))?;
+        builder
+            .set_private_key_file(&self.key, SslFiletype::PEM)
+            .map_err(|e| anyhow!("failed to load private key {}: {e}", self.key.display()))?;
+        let connector = builder.build();
+
+        let ssl = connector
+            .configure()
+            .and_then(|c| c.into_ssl(&self.server_name()))
+            .map_err(|e| anyhow!("failed to create ssl: {e}"))?;
+
+        let stream = TcpStream::connect(&self.addr)
+            .await
+            .map_err(|e| anyhow!("failed to connect to {}: {e:?}", self.addr))?;
+        let connector = g3_openssl::SslConnector::new(ssl, stream)
+            .map_err(|e| anyhow!("failed to get ssl stream: {e}"))?;
+        connector
+            .connect()
+            .await
+            .map_err(|e| anyhow!("tls handshake with {} failed: {e}", self.addr))
+    }
+}
@@ -0,0 +1,36 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// The access level of a control connection
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CtlAccessLevel {
+    /// Only methods that fetch status or info are allowed
+    ReadOnly,
+    /// All methods are allowed
+    Admin,
+}
+
+impl CtlAccessLevel {
+    /// Check the permission for methods that will change the daemon state
+    pub fn check_admin(&self) -> Result<(), capnp::Error> {
+        match self {
+            CtlAccessLevel::Admin => Ok(()),
+            CtlAccessLevel::ReadOnly => Err(capnp::Error::failed(
+                "permission denied: admin access level is required".to_string(),
+            )),
+        }
+    }
+}
@@ -22,14 +22,21 @@ use log::{trace, warn};
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::sync::{mpsc, oneshot};
 
+use super::CtlAccessLevel;
+
 static CAPNP_MESSAGE_SENDER: Mutex<Option<mpsc::UnboundedSender<CapnpMessage>>> = Mutex::new(None);
 
 struct CapnpMessage {
     reader: Box<dyn AsyncRead + Send + Unpin>,
     writer: Box<dyn AsyncWrite + Send + Unpin>,
+    access: CtlAccessLevel,
 }
 
-pub fn handle_capnp_connection<R, W>(reader: R, writer: W) -> anyhow::Result<()>
+pub fn handle_capnp_connection<R, W>(
+    reader: R,
+    writer: W,
+    access: CtlAccessLevel,
+) -> anyhow::Result<()>
 where
     R: AsyncRead + Send + Unpin + 'static,
     W: AsyncWrite + Send + Unpin + 'static,
@@ -37,6 +44,7 @@ where
     let msg = CapnpMessage {
         reader: Box::new(reader),
         writer: Box::new(writer),
+        access,
     };
     let value = CAPNP_MESSAGE_SENDER.lock().unwrap();
     if let Some(sender) = &*value {
@@ -63,7 +71,7 @@ pub async fn spawn_working_thread<F>(
     build_client: &'static F,
 ) -> anyhow::Result<std::thread::JoinHandle<()>>
 where
-    F: Fn() -> capnp::capability::Client + Sync,
+    F: Fn(CtlAccessLevel) -> capnp::capability::Client + Sync,
 {
     let (sender, receiver) = mpsc::unbounded_channel::<CapnpMessage>();
     let (ready_notifier, ready) = oneshot::channel::<bool>();
@@ -80,7 +88,10 @@ where
                 set_capnp_message_sender(sender);
                 ready_notifier.send(true).unwrap();
                 while let Some(msg) = receiver.recv().await {
-                    trace!("received new capnp rpc connection");
+                    trace!(
+                        "received new capnp rpc connection with {:?} access",
+                        msg.access
+                    );
                     let reader = msg.reader;
                     let writer = msg.writer;
                     let reader = tokio_util::compat::TokioAsyncReadCompatExt::compat(reader);
@@ -93,7 +104,7 @@ where
                         Default::default(),
                     );
 
-                    let client = build_client();
+                    let client = build_client(msg.access);
                     let rpc_system = RpcSystem::new(Box::new(network), Some(client));
                     tokio::task::spawn_local(async move {
                         trace!("handling capnp rpc connection ...");
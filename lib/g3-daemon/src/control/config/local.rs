@@ -14,20 +14,75 @@
  * limitations under the License.
  */
 
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
 use g3_types::sync::GlobalInit;
 
 use super::GeneralControllerConfig;
+#[cfg(unix)]
+use crate::control::CtlAccessLevel;
+
+/// The uid / gid of the peer process connected to the local control socket
+#[cfg(unix)]
+#[derive(Clone)]
+pub(crate) struct LocalPeerCredConfig {
+    allowed_uid: Vec<u32>,
+    allowed_gid: Vec<u32>,
+    admin_uid: Vec<u32>,
+    admin_gid: Vec<u32>,
+}
+
+#[cfg(unix)]
+impl LocalPeerCredConfig {
+    const fn new() -> Self {
+        LocalPeerCredConfig {
+            allowed_uid: Vec::new(),
+            allowed_gid: Vec::new(),
+            admin_uid: Vec::new(),
+            admin_gid: Vec::new(),
+        }
+    }
+
+    fn match_admin(&self, uid: u32, gid: u32) -> bool {
+        self.admin_uid.contains(&uid) || self.admin_gid.contains(&gid)
+    }
+
+    /// Get the access level for the peer, `None` means the peer is not allowed
+    pub(crate) fn check(&self, uid: u32, gid: u32) -> Option<CtlAccessLevel> {
+        // the user of the daemon itself is always needed for reload / upgrade
+        if uid == rustix::process::geteuid().as_raw() {
+            return Some(CtlAccessLevel::Admin);
+        }
+
+        if self.match_admin(uid, gid) {
+            return Some(CtlAccessLevel::Admin);
+        }
+
+        let restricted = !self.allowed_uid.is_empty() || !self.allowed_gid.is_empty();
+        if restricted && !self.allowed_uid.contains(&uid) && !self.allowed_gid.contains(&gid) {
+            return None;
+        }
+
+        if self.admin_uid.is_empty() && self.admin_gid.is_empty() {
+            Some(CtlAccessLevel::Admin)
+        } else {
+            Some(CtlAccessLevel::ReadOnly)
+        }
+    }
+}
 
 pub(crate) struct LocalControllerConfig {
     general: GeneralControllerConfig,
+    #[cfg(unix)]
+    peer_cred: LocalPeerCredConfig,
 }
 
 static LOCAL_CONTROLLER_CONFIG: GlobalInit<LocalControllerConfig> =
     GlobalInit::new(LocalControllerConfig {
         general: GeneralControllerConfig::new(),
+        #[cfg(unix)]
+        peer_cred: LocalPeerCredConfig::new(),
     });
 
 impl LocalControllerConfig {
@@ -35,6 +90,11 @@ impl LocalControllerConfig {
         LOCAL_CONTROLLER_CONFIG.as_ref().general.clone()
     }
 
+    #[cfg(unix)]
+    pub(crate) fn get_peer_cred() -> LocalPeerCredConfig {
+        LOCAL_CONTROLLER_CONFIG.as_ref().peer_cred.clone()
+    }
+
     pub(crate) fn set_default(v: &Yaml) -> anyhow::Result<()> {
         match v {
             Yaml::Hash(map) => {
@@ -51,7 +111,72 @@ impl LocalControllerConfig {
     fn set(&mut self, k: &str, v: &Yaml) -> anyhow::Result<()> {
         match g3_yaml::key::normalize(k).as_str() {
             "recv_timeout" | "send_timeout" => self.general.set(k, v),
+            #[cfg(unix)]
+            "allowed_uid" => {
+                self.peer_cred.allowed_uid =
+                    as_id_list(v).context(format!("invalid uid list value for key {k}"))?;
+                Ok(())
+            }
+            #[cfg(unix)]
+            "allowed_gid" => {
+                self.peer_cred.allowed_gid =
+                    as_id_list(v).context(format!("invalid gid list value for key {k}"))?;
+                Ok(())
+            }
+            #[cfg(unix)]
+            "admin_uid" => {
+                self.peer_cred.admin_uid =
+                    as_id_list(v).context(format!("invalid uid list value for key {k}"))?;
+                Ok(())
+            }
+            #[cfg(unix)]
+            "admin_gid" => {
+                self.peer_cred.admin_gid =
+                    as_id_list(v).context(format!("invalid gid list value for key {k}"))?;
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         }
     }
 }
+
+#[cfg(unix)]
+fn as_id_list(v: &Yaml) -> anyhow::Result<Vec<u32>> {
+    let mut ids = Vec::new();
+    if let Yaml::Array(seq) = v {
+        for (i, v) in seq.iter().enumerate() {
+            let id = g3_yaml::value::as_u32(v).context(format!("invalid u32 value for #{i}"))?;
+            ids.push(id);
+        }
+    } else {
+        let id = g3_yaml::value::as_u32(v)?;
+        ids.push(id);
+    }
+    Ok(ids)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_peer_cred() {
+        let own_uid = rustix::process::geteuid().as_raw();
+        let other_uid = own_uid.wrapping_add(1000);
+
+        let mut config = LocalPeerCredConfig::new();
+        assert_eq!(config.check(other_uid, 0), Some(CtlAccessLevel::Admin));
+
+        config.allowed_gid.push(100);
+        assert_eq!(config.check(other_uid, 0), None);
+        assert_eq!(config.check(other_uid, 100), Some(CtlAccessLevel::Admin));
+        assert_eq!(config.check(own_uid, 0), Some(CtlAccessLevel::Admin));
+
+        config.admin_uid.push(other_uid.wrapping_add(1));
+        assert_eq!(config.check(other_uid, 100), Some(CtlAccessLevel::ReadOnly));
+        assert_eq!(
+            config.check(other_uid.wrapping_add(1), 0),
+            Some(CtlAccessLevel::Admin)
+        );
+    }
+}
@@ -18,6 +18,8 @@ use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
 mod local;
+#[cfg(feature = "control-tls")]
+mod tls;
 
 const DEFAULT_RECV_TIMEOUT: u64 = 30;
 const DEFAULT_SEND_TIMEOUT: u64 = 1;
@@ -62,12 +64,16 @@ impl GeneralControllerConfig {
 }
 
 pub(crate) use local::LocalControllerConfig;
+#[cfg(feature = "control-tls")]
+pub(crate) use tls::TlsControllerConfig;
 
 pub fn load(v: &Yaml) -> anyhow::Result<()> {
     match v {
         Yaml::Hash(map) => {
             g3_yaml::foreach_kv(map, |k, v| match k {
                 "local" => LocalControllerConfig::set_default(v),
+                #[cfg(feature = "control-tls")]
+                "tls" => TlsControllerConfig::set_default(v),
                 _ => Err(anyhow!("invalid key '{k}'")),
             })?;
             Ok(())
@@ -25,7 +25,7 @@ use tokio::sync::oneshot;
 
 use g3_io_ext::LimitedWriteExt;
 
-use super::{CtlProtoCtx, CtlProtoType, LocalControllerConfig};
+use super::{CtlAccessLevel, CtlProtoCtx, CtlProtoType, LocalControllerConfig};
 
 #[cfg(unix)]
 mod unix;
@@ -44,7 +44,7 @@ static DAEMON_CONTROLLER_ABORT_CHANNEL: Mutex<
     Option<oneshot::Sender<oneshot::Sender<LocalControllerImpl>>>,
 > = Mutex::new(None);
 
-fn ctl_handle<R, W>(r: R, w: W)
+fn ctl_handle<R, W>(r: R, w: W, access: CtlAccessLevel)
 where
     R: AsyncRead + Send + Unpin + 'static,
     W: AsyncWrite + Send + Unpin + 'static,
@@ -54,6 +54,7 @@ where
         w,
         LocalControllerConfig::get_general(),
         CtlProtoType::Text,
+        access,
     );
     tokio::spawn(async move {
         if let Err(e) = ctx.run().await {
@@ -122,8 +123,22 @@ impl LocalController {
     }
 
     pub fn start_as_daemon(self) -> anyhow::Result<impl Future> {
+        #[cfg(feature = "control-tls")]
+        let tls_controller = super::tls::TlsController::create()?;
         let fut = self.start(&DAEMON_CONTROLLER_ABORT_CHANNEL)?;
         debug!("daemon controller started");
+        #[cfg(feature = "control-tls")]
+        let fut = async move {
+            if let Some(tls_controller) = tls_controller {
+                // drop the output of the local controller, so the joined future is Send
+                let local_fut = async move {
+                    fut.await;
+                };
+                tokio::join!(local_fut, tls_controller.start());
+            } else {
+                fut.await;
+            }
+        };
         Ok(fut)
     }
 
@@ -133,6 +148,8 @@ impl LocalController {
 
     pub async fn abort_daemon() {
         LocalController::abort(&DAEMON_CONTROLLER_ABORT_CHANNEL).await;
+        #[cfg(feature = "control-tls")]
+        super::tls::TlsController::abort().await;
     }
 
     pub async fn send_release_controller_command(
@@ -24,6 +24,8 @@ use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::UnixListener;
 use tokio::sync::oneshot;
 
+use crate::control::LocalControllerConfig;
+
 pub(super) struct LocalControllerImpl {
     listen_path: PathBuf,
     listener: UnixListener,
@@ -102,6 +104,7 @@ impl LocalControllerImpl {
         self,
         mut quit_receiver: oneshot::Receiver<oneshot::Sender<Self>>,
     ) {
+        let peer_cred = LocalControllerConfig::get_peer_cred();
         loop {
             tokio::select! {
                 biased;
@@ -109,27 +112,38 @@ impl LocalControllerImpl {
                 r = self.listener.accept() => {
                     match r {
                         Ok((stream, addr)) => {
-                            if let Ok(ucred) = stream.peer_cred() {
-                                if let Some(addr) = addr.as_pathname() {
-                                    debug!(
-                                        "new ctl client from {} uid {} pid {}",
-                                        addr.display(),
-                                        ucred.uid(),
-                                        ucred.gid(),
-                                    );
-                                } else {
-                                    debug!(
-                                        "new ctl client from uid {} pid {}",
-                                        ucred.uid(),
-                                        ucred.gid()
-                                    );
+                            let ucred = match stream.peer_cred() {
+                                Ok(ucred) => ucred,
+                                Err(e) => {
+                                    warn!("failed to get peer cred of ctl client: {e}");
+                                    continue;
                                 }
+                            };
+                            if let Some(addr) = addr.as_pathname() {
+                                debug!(
+                                    "new ctl client from {} uid {} gid {}",
+                                    addr.display(),
+                                    ucred.uid(),
+                                    ucred.gid(),
+                                );
                             } else {
-                                debug!("new ctl local control client");
+                                debug!(
+                                    "new ctl client from uid {} gid {}",
+                                    ucred.uid(),
+                                    ucred.gid()
+                                );
                             }
+                            let Some(access) = peer_cred.check(ucred.uid(), ucred.gid()) else {
+                                warn!(
+                                    "ctl client from uid {} gid {} is not allowed",
+                                    ucred.uid(),
+                                    ucred.gid()
+                                );
+                                continue;
+                            };
 
                             let (r, w) = stream.into_split();
-                            super::ctl_handle(r, w);
+                            super::ctl_handle(r, w, access);
                         }
                         Err(e) => {
                             warn!("controller {} accept: {e}", self.listen_path.display());
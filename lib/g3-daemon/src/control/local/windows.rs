@@ -20,6 +20,8 @@ use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::net::windows::named_pipe::{NamedPipeServer, ServerOptions};
 use tokio::sync::oneshot;
 
+use crate::control::CtlAccessLevel;
+
 pub(super) struct LocalControllerImpl {
     pipe_name: String,
     server: NamedPipeServer,
@@ -29,7 +31,6 @@ impl LocalControllerImpl {
     fn create(pipe_name: String) -> anyhow::Result<Self> {
         let server = ServerOptions::new()
             .first_pipe_instance(true)
-            .max_instances(2)
             .create(&pipe_name)?;
         Ok(LocalControllerImpl { pipe_name, server })
     }
@@ -57,8 +58,8 @@ impl LocalControllerImpl {
     ) -> anyhow::Result<impl AsyncRead + AsyncWrite> {
         let pipe_name = format!(r"\\.\pipe\{daemon_name}@{}", daemon_group);
 
-        tokio::net::windows::named_pipe::ClientOptions::new()
-            .open(&pipe_name)
+        g3_socket::named_pipe::open_client(&pipe_name)
+            .await
             .map_err(|e| anyhow!("failed to open connection to pipe {}: {e:?}", pipe_name))
     }
 
@@ -78,7 +79,7 @@ impl LocalControllerImpl {
                                 Ok(new_server) => {
                                     let server = std::mem::replace(&mut self.server, new_server);
                                     let (r, w) = tokio::io::split(server);
-                                    super::ctl_handle(r, w);
+                                    super::ctl_handle(r, w, CtlAccessLevel::Admin);
                                 }
                                 Err(e) => {
                                     warn!("failed to re-open controller pipe {}: {e}", self.pipe_name);
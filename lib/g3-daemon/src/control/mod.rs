@@ -17,14 +17,23 @@
 use log::warn;
 use tokio::io::{AsyncBufRead, AsyncWrite};
 
+mod access;
+pub use access::CtlAccessLevel;
+
 mod local;
 pub use local::LocalController;
 
+#[cfg(feature = "control-tls")]
+mod tls;
+
 pub mod quit;
 pub use quit::QuitAction;
 pub mod upgrade;
 pub use upgrade::UpgradeAction;
 
+mod reload;
+pub use reload::set_reload_action;
+
 pub mod capnp;
 
 pub mod config;
@@ -48,6 +57,7 @@ where
     writer: W,
     config: GeneralControllerConfig,
     protocol_type: CtlProtoType,
+    access: CtlAccessLevel,
 }
 
 impl<R, W> CtlProtoCtx<R, W>
@@ -60,12 +70,14 @@ where
         writer: W,
         config: GeneralControllerConfig,
         protocol_type: CtlProtoType,
+        access: CtlAccessLevel,
     ) -> Self {
         CtlProtoCtx {
             reader,
             writer,
             config,
             protocol_type,
+            access,
         }
     }
 
@@ -75,12 +87,18 @@ where
             match self.protocol_type {
                 CtlProtoType::End => break,
                 CtlProtoType::Text => {
-                    let mut ctx =
-                        text::TextCtlCtx::new(&mut self.reader, &mut self.writer, &mut self.config);
+                    let mut ctx = text::TextCtlCtx::new(
+                        &mut self.reader,
+                        &mut self.writer,
+                        &mut self.config,
+                        self.access,
+                    );
                     self.protocol_type = ctx.run().await?;
                 }
                 CtlProtoType::CapnP => {
-                    if let Err(e) = capnp::handle_capnp_connection(self.reader, self.writer) {
+                    if let Err(e) =
+                        capnp::handle_capnp_connection(self.reader, self.writer, self.access)
+                    {
                         warn!("upgrade to capnp failed: {e:?}");
                     }
                     break;
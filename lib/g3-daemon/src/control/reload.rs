@@ -0,0 +1,39 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::OnceLock;
+
+use anyhow::anyhow;
+use log::{info, warn};
+
+static RELOAD_ACTION: OnceLock<fn() -> anyhow::Result<()>> = OnceLock::new();
+
+/// Set the action of the `reload` text control command.
+///
+/// This is intended for daemons without capnp rpc control, the action will be run in place.
+pub fn set_reload_action(action: fn() -> anyhow::Result<()>) {
+    if RELOAD_ACTION.set(action).is_err() {
+        warn!("reload action has already been set");
+    }
+}
+
+pub(super) fn run() -> anyhow::Result<()> {
+    let Some(action) = RELOAD_ACTION.get() else {
+        return Err(anyhow!("reload is not supported"));
+    };
+    info!("reloading by control command");
+    action()
+}
@@ -23,7 +23,7 @@ use yaml_rust::Yaml;
 
 use g3_io_ext::{LimitedBufReadExt, LimitedWriteExt};
 
-use super::{CtlProtoType, GeneralControllerConfig};
+use super::{CtlAccessLevel, CtlProtoType, GeneralControllerConfig};
 
 const TEXT_COMMAND_MAX_LEN: usize = 1024;
 
@@ -35,6 +35,7 @@ where
     reader: &'a mut R,
     writer: &'a mut W,
     config: &'a mut GeneralControllerConfig,
+    access: CtlAccessLevel,
     buf: Vec<u8>,
 }
 
@@ -47,11 +48,13 @@ where
         reader: &'a mut R,
         writer: &'a mut W,
         config: &'a mut GeneralControllerConfig,
+        access: CtlAccessLevel,
     ) -> Self {
         TextCtlCtx {
             reader,
             writer,
             config,
+            access,
             buf: Vec::with_capacity(TEXT_COMMAND_MAX_LEN),
         }
     }
@@ -138,6 +141,7 @@ where
             }
             Some("set") => self.set(iter),
             Some("pid") => Ok(std::process::id().to_string()),
+            Some("reload") => self.reload(),
             Some(k) => Err(anyhow!("unknown command {k}")),
             None => Ok(String::new()),
         };
@@ -147,6 +151,14 @@ where
         }
     }
 
+    fn reload(&self) -> anyhow::Result<String> {
+        if self.access != CtlAccessLevel::Admin {
+            return Err(anyhow!("permission denied: admin access level is required"));
+        }
+        super::reload::run()?;
+        Ok("ok".to_string())
+    }
+
     fn set(&mut self, mut iter: SplitWhitespace) -> anyhow::Result<String> {
         if let Some(key) = iter.next() {
             if let Some(value) = iter.next() {
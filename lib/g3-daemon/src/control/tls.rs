@@ -0,0 +1,165 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use log::{debug, warn};
+use openssl::ssl::Ssl;
+use tokio::io::BufReader;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::oneshot;
+
+use g3_openssl::SslAcceptor;
+use g3_types::net::OpensslServerConfig;
+
+use super::config::TlsControllerConfig;
+use super::{CtlAccessLevel, CtlProtoCtx, CtlProtoType};
+
+static TLS_CONTROLLER_ABORT_CHANNEL: Mutex<
+    Option<oneshot::Sender<oneshot::Sender<TlsController>>>,
+> = Mutex::new(None);
+
+/// The optional tcp control listener, which runs along with the daemon controller
+pub(crate) struct TlsController {
+    listener: TcpListener,
+    tls_server: OpensslServerConfig,
+    config: Arc<TlsControllerConfig>,
+}
+
+impl TlsController {
+    pub(crate) fn create() -> anyhow::Result<Option<Self>> {
+        let Some(config) = TlsControllerConfig::get() else {
+            return Ok(None);
+        };
+        let listener = g3_socket::tcp::new_listen_to(&config.listen).map_err(|e| {
+            anyhow!(
+                "failed to listen on tcp address {}: {e}",
+                config.listen.address()
+            )
+        })?;
+        let tls_server = config
+            .tls_server
+            .build()
+            .context("failed to build tls server config")?;
+        Ok(Some(TlsController {
+            listener,
+            tls_server,
+            config,
+        }))
+    }
+
+    pub(crate) fn start(self) -> impl Future<Output = ()> {
+        let (sender, receiver) = oneshot::channel();
+        *TLS_CONTROLLER_ABORT_CHANNEL.lock().unwrap() = Some(sender);
+        debug!("tls controller started");
+        self.into_running(receiver)
+    }
+
+    pub(crate) async fn abort() {
+        let (sender, receiver) = oneshot::channel();
+
+        let abort_channel = TLS_CONTROLLER_ABORT_CHANNEL.lock().unwrap().take();
+        if let Some(quit_sender) = abort_channel {
+            if quit_sender.send(sender).is_ok() {
+                let _ = receiver.await;
+            }
+        }
+    }
+
+    async fn into_running(self, mut quit_receiver: oneshot::Receiver<oneshot::Sender<Self>>) {
+        loop {
+            tokio::select! {
+                biased;
+
+                r = self.listener.accept() => {
+                    match r {
+                        Ok((stream, peer)) => {
+                            debug!("new tls ctl client from {peer}");
+                            self.spawn_client(stream, peer);
+                        }
+                        Err(e) => {
+                            warn!("controller {} accept: {e}", self.config.listen.address());
+                        }
+                    }
+                }
+                r = &mut quit_receiver => {
+                    if let Ok(v) = r {
+                        let _ = v.send(self);
+                    }
+                    break;
+                }
+            }
+        }
+    }
+
+    fn spawn_client(&self, stream: TcpStream, peer: SocketAddr) {
+        let ssl = match Ssl::new(&self.tls_server.ssl_context) {
+            Ok(ssl) => ssl,
+            Err(e) => {
+                warn!("failed to get new Ssl state: {e}");
+                return;
+            }
+        };
+        let accept_timeout = self.tls_server.accept_timeout;
+        let config = self.config.clone();
+        tokio::spawn(async move {
+            if let Err(e) = serve_client(ssl, stream, accept_timeout, config).await {
+                warn!("error handle tls ctl client {peer}: {e}");
+            }
+        });
+    }
+}
+
+async fn serve_client(
+    ssl: Ssl,
+    stream: TcpStream,
+    accept_timeout: Duration,
+    config: Arc<TlsControllerConfig>,
+) -> anyhow::Result<()> {
+    let acceptor = SslAcceptor::new(ssl, stream, accept_timeout)
+        .map_err(|e| anyhow!("failed to get ssl stream: {e}"))?;
+    let ssl_stream = acceptor
+        .accept()
+        .await
+        .map_err(|e| anyhow!("tls handshake failed: {e}"))?;
+
+    let peer_cert = ssl_stream.ssl().peer_certificate();
+    config
+        .client_pin
+        .verify(peer_cert.as_deref())
+        .context("client pin check failed")?;
+    // admin access is only granted to explicitly pinned clients
+    let access =
+        if !config.admin_pin.is_empty() && config.admin_pin.verify(peer_cert.as_deref()).is_ok() {
+            CtlAccessLevel::Admin
+        } else {
+            CtlAccessLevel::ReadOnly
+        };
+
+    let (r, w) = tokio::io::split(ssl_stream);
+    let ctx = CtlProtoCtx::new(
+        BufReader::new(r),
+        w,
+        config.general.clone(),
+        CtlProtoType::Text,
+        access,
+    );
+    ctx.run().await
+}
@@ -0,0 +1,90 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use g3_types::net::{OpensslServerConfigBuilder, OpensslSpkiPinSet, TcpListenConfig};
+
+static TLS_FRONTEND_CONFIG: OnceLock<Arc<TlsFrontendConfig>> = OnceLock::new();
+
+pub struct TlsFrontendConfig {
+    pub listen: TcpListenConfig,
+    pub tls_server: OpensslServerConfigBuilder,
+    pub client_pin: OpensslSpkiPinSet,
+}
+
+impl TlsFrontendConfig {
+    pub fn get() -> Option<Arc<TlsFrontendConfig>> {
+        TLS_FRONTEND_CONFIG.get().cloned()
+    }
+
+    pub fn load(value: &Yaml) -> anyhow::Result<()> {
+        let Yaml::Hash(map) = value else {
+            return Err(anyhow!(
+                "yaml value type for the tls frontend config should be 'map'"
+            ));
+        };
+
+        let mut listen: Option<TcpListenConfig> = None;
+        let mut tls_server: Option<OpensslServerConfigBuilder> = None;
+        let mut client_pin = OpensslSpkiPinSet::default();
+        let lookup_dir = crate::config::get_lookup_dir(None)?;
+
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "listen" => {
+                let config = g3_yaml::value::as_tcp_listen_config(v)
+                    .context(format!("invalid tcp listen config value for key {k}"))?;
+                listen = Some(config);
+                Ok(())
+            }
+            "tls_server" => {
+                let builder =
+                    g3_yaml::value::as_openssl_tls_server_config_builder(v, Some(lookup_dir))
+                        .context(format!("invalid tls server config value for key {k}"))?;
+                tls_server = Some(builder);
+                Ok(())
+            }
+            "client_pin" => {
+                client_pin = g3_yaml::value::as_openssl_spki_pin_set(v)
+                    .context(format!("invalid spki pin value for key {k}"))?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        let Some(listen) = listen else {
+            return Err(anyhow!("no listen address set"));
+        };
+        let Some(mut tls_server) = tls_server else {
+            return Err(anyhow!("no tls server config set"));
+        };
+        // the client should always present its certificate
+        tls_server.enable_client_auth();
+        tls_server.check()?;
+
+        TLS_FRONTEND_CONFIG
+            .set(Arc::new(TlsFrontendConfig {
+                listen,
+                tls_server,
+                client_pin,
+            }))
+            .map_err(|_| anyhow!("duplicate tls frontend config"))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::future::Future;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context};
+use openssl::ssl::Ssl;
+use tokio::net::{TcpListener, TcpStream};
+
+use g3_io_ext::LengthPrefixedStream;
+use g3_openssl::{SslAcceptor, SslStream};
+use g3_types::net::{OpensslServerConfig, OpensslSpkiPinSet};
+
+use super::TlsFrontendConfig;
+
+pub type TlsFrontendStream = LengthPrefixedStream<SslStream<TcpStream>>;
+
+/// The mutual TLS listener for the message stream frontend of helper daemons
+pub struct TlsFrontendListener {
+    listener: TcpListener,
+    tls_server: OpensslServerConfig,
+    client_pin: Arc<OpensslSpkiPinSet>,
+}
+
+impl TlsFrontendListener {
+    pub fn new(config: &TlsFrontendConfig) -> anyhow::Result<Self> {
+        let listener = g3_socket::tcp::new_listen_to(&config.listen).map_err(|e| {
+            anyhow!(
+                "failed to listen on tcp address {}: {e}",
+                config.listen.address()
+            )
+        })?;
+        let tls_server = config
+            .tls_server
+            .build()
+            .context("failed to build tls server config")?;
+        Ok(TlsFrontendListener {
+            listener,
+            tls_server,
+            client_pin: Arc::new(config.client_pin.clone()),
+        })
+    }
+
+    pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
+        self.listener.accept().await
+    }
+
+    /// Get the future to do tls handshake and client pin check on the accepted stream,
+    /// which should be spawned to run in a separate task
+    pub fn handshake(
+        &self,
+        stream: TcpStream,
+    ) -> impl Future<Output = anyhow::Result<TlsFrontendStream>> + Send {
+        let ssl = Ssl::new(&self.tls_server.ssl_context);
+        let accept_timeout = self.tls_server.accept_timeout;
+        let client_pin = self.client_pin.clone();
+        async move {
+            let ssl = ssl.map_err(|e| anyhow!("failed to get new Ssl state: {e}"))?;
+            let acceptor = SslAcceptor::new(ssl, stream, accept_timeout)
+                .map_err(|e| anyhow!("failed to get ssl stream: {e}"))?;
+            let ssl_stream = acceptor
+                .accept()
+                .await
+                .map_err(|e| anyhow!("tls handshake failed: {e}"))?;
+            client_pin
+                .verify(ssl_stream.ssl().peer_certificate().as_deref())
+                .context("client pin check failed")?;
+            Ok(LengthPrefixedStream::new(ssl_stream))
+        }
+    }
+}
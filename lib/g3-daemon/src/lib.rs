@@ -21,6 +21,7 @@ pub mod log;
 pub mod metrics;
 pub mod opts;
 pub mod runtime;
+pub mod schedule;
 pub mod server;
 pub mod signal;
 pub mod stat;
@@ -30,3 +31,6 @@ pub mod daemonize;
 
 #[cfg(feature = "register")]
 pub mod register;
+
+#[cfg(feature = "tls-frontend")]
+pub mod frontend;
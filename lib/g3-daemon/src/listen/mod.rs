@@ -20,6 +20,11 @@ pub use stats::{ListenSnapshot, ListenStats};
 mod tcp;
 pub use tcp::{AcceptTcpServer, ListenTcpRuntime, ReloadTcpServer};
 
+#[cfg(unix)]
+mod unix;
+#[cfg(unix)]
+pub use unix::{AcceptUnixServer, ListenUnixRuntime, ReloadUnixServer};
+
 #[cfg_attr(feature = "quic", path = "quic.rs")]
 #[cfg_attr(not(feature = "quic"), path = "no_quic.rs")]
 mod quic;
@@ -20,7 +20,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use log::{info, warn};
+use log::{debug, info, warn};
 use quinn::{Connection, Endpoint, Incoming};
 use tokio::runtime::Handle;
 use tokio::sync::{broadcast, watch};
@@ -52,6 +52,11 @@ pub trait ListenQuicConf {
     fn ingress_network_acl(&self) -> Option<&AclNetworkRule>;
 
     fn accept_timeout(&self) -> Duration;
+
+    /// the interval to check the client address change of accepted connections
+    fn migration_watch_interval(&self) -> Option<Duration> {
+        None
+    }
 }
 
 #[derive(Clone)]
@@ -221,6 +226,7 @@ where
         let server = self.server.clone();
         let listen_stats = self.listen_stats.clone();
         let accept_timeout = aux_config.accept_timeout();
+        let migration_watch_interval = aux_config.migration_watch_interval();
         if let Some(worker_id) = self.worker_id {
             cc_info.set_worker_id(Some(worker_id));
             tokio::spawn(async move {
@@ -229,6 +235,7 @@ where
                     incoming,
                     cc_info,
                     accept_timeout,
+                    migration_watch_interval,
                     listen_stats,
                 )
                 .await
@@ -241,6 +248,7 @@ where
                     incoming,
                     cc_info,
                     accept_timeout,
+                    migration_watch_interval,
                     listen_stats,
                 )
                 .await
@@ -252,6 +260,7 @@ where
                     incoming,
                     cc_info,
                     accept_timeout,
+                    migration_watch_interval,
                     listen_stats,
                 )
                 .await
@@ -264,6 +273,7 @@ where
         incoming: Incoming,
         cc_info: ClientConnectionInfo,
         timeout: Duration,
+        migration_watch_interval: Option<Duration>,
         listen_stats: Arc<ListenStats>,
     ) {
         let connecting = match incoming.accept() {
@@ -277,6 +287,17 @@ where
         match tokio::time::timeout(timeout, connecting).await {
             Ok(Ok(c)) => {
                 listen_stats.add_accepted();
+                if let Some(interval) = migration_watch_interval {
+                    let watch_conn = c.clone();
+                    let client_addr = cc_info.client_addr();
+                    tokio::spawn(async move {
+                        g3_io_ext::watch_connection_migration(watch_conn, interval, |old, new| {
+                            listen_stats.add_migrated();
+                            debug!("quic connection from {client_addr} migrated: {old} -> {new}");
+                        })
+                        .await
+                    });
+                }
                 server.run_quic_task(c, cc_info).await;
             }
             Ok(Err(_e)) => {
@@ -26,6 +26,7 @@ pub struct ListenSnapshot {
     pub dropped: u64,
     pub timeout: u64,
     pub failed: u64,
+    pub migrated: u64,
 }
 
 #[derive(Debug)]
@@ -38,6 +39,7 @@ pub struct ListenStats {
     dropped: AtomicU64,
     timeout: AtomicU64,
     failed: AtomicU64,
+    migrated: AtomicU64,
 }
 
 impl ListenStats {
@@ -50,6 +52,7 @@ impl ListenStats {
             dropped: AtomicU64::new(0),
             timeout: AtomicU64::new(0),
             failed: AtomicU64::new(0),
+            migrated: AtomicU64::new(0),
         }
     }
 
@@ -105,6 +108,13 @@ impl ListenStats {
         self.failed.load(Ordering::Relaxed)
     }
 
+    pub fn add_migrated(&self) {
+        self.migrated.fetch_add(1, Ordering::Relaxed);
+    }
+    pub fn migrated(&self) -> u64 {
+        self.migrated.load(Ordering::Relaxed)
+    }
+
     pub fn add_by_proxy_protocol_error(&self, e: ProxyProtocolReadError) {
         match e {
             ProxyProtocolReadError::ReadTimeout => self.add_timeout(),
@@ -118,7 +128,8 @@ impl ListenStats {
             | ProxyProtocolReadError::InvalidFamily(_)
             | ProxyProtocolReadError::InvalidProtocol(_)
             | ProxyProtocolReadError::InvalidSrcAddr
-            | ProxyProtocolReadError::InvalidDstAddr => self.add_dropped(),
+            | ProxyProtocolReadError::InvalidDstAddr
+            | ProxyProtocolReadError::InvalidTlv => self.add_dropped(),
         }
     }
 }
@@ -0,0 +1,209 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use log::{info, warn};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::runtime::Handle;
+use tokio::sync::broadcast;
+
+use g3_types::net::UnixListenConfig;
+
+use crate::listen::ListenStats;
+use crate::server::{BaseServer, ClientConnectionInfo, ServerReloadCommand};
+
+#[async_trait]
+pub trait AcceptUnixServer: BaseServer {
+    async fn run_unix_task(&self, stream: UnixStream, cc_info: ClientConnectionInfo);
+}
+
+pub trait ReloadUnixServer: AcceptUnixServer {
+    fn get_reloaded(&self) -> Self;
+}
+
+#[derive(Clone)]
+pub struct ListenUnixRuntime<S> {
+    server: S,
+    server_type: &'static str,
+    server_version: usize,
+    worker_id: Option<usize>,
+    listen_stats: Arc<ListenStats>,
+}
+
+impl<S> ListenUnixRuntime<S>
+where
+    S: ReloadUnixServer + Clone + Send + Sync + 'static,
+{
+    pub fn new(server: S, listen_stats: Arc<ListenStats>) -> Self {
+        let server_type = server.server_type();
+        let server_version = server.version();
+        ListenUnixRuntime {
+            server,
+            server_type,
+            server_version,
+            worker_id: None,
+            listen_stats,
+        }
+    }
+
+    fn pre_start(&self) {
+        info!(
+            "started {} SRT[{}_v{}#unix]",
+            self.server_type,
+            self.server.name(),
+            self.server_version,
+        );
+        self.listen_stats.add_running_runtime();
+    }
+
+    fn pre_stop(&self) {
+        info!(
+            "stopping {} SRT[{}_v{}#unix]",
+            self.server_type,
+            self.server.name(),
+            self.server_version,
+        );
+    }
+
+    fn post_stop(&self) {
+        info!(
+            "stopped {} SRT[{}_v{}#unix]",
+            self.server_type,
+            self.server.name(),
+            self.server_version,
+        );
+        self.listen_stats.del_running_runtime();
+    }
+
+    async fn run(
+        mut self,
+        listener: UnixListener,
+        mut server_reload_channel: broadcast::Receiver<ServerReloadCommand>,
+    ) {
+        use broadcast::error::RecvError;
+
+        loop {
+            tokio::select! {
+                biased;
+
+                ev = server_reload_channel.recv() => {
+                    match ev {
+                        Ok(ServerReloadCommand::ReloadVersion(version)) => {
+                            info!("SRT[{}_v{}#unix] received reload request from v{version}",
+                                self.server.name(), self.server_version);
+                            let new_server = self.server.get_reloaded();
+                            self.server_version = new_server.version();
+                            self.server = new_server;
+                            continue;
+                        }
+                        Ok(ServerReloadCommand::QuitRuntime) => {},
+                        Err(RecvError::Closed) => {},
+                        Err(RecvError::Lagged(dropped)) => {
+                            warn!("SRT[{}_v{}#unix] server {} reload notify channel overflowed, {dropped} msg dropped",
+                                self.server.name(), self.server_version, self.server.name());
+                            continue;
+                        },
+                    }
+
+                    info!("SRT[{}_v{}#unix] will go offline",
+                        self.server.name(), self.server_version);
+                    self.pre_stop();
+                    break;
+                }
+                result = listener.accept() => {
+                    match result {
+                        Ok((stream, _addr)) => {
+                            self.listen_stats.add_accepted();
+                            self.run_task(stream);
+                        }
+                        Err(e) => {
+                            self.listen_stats.add_failed();
+                            warn!("SRT[{}_v{}#unix] accept: {e:?}",
+                                self.server.name(), self.server_version);
+                        }
+                    }
+                }
+            }
+        }
+        self.post_stop();
+    }
+
+    fn run_task(&self, stream: UnixStream) {
+        let server = self.server.clone();
+
+        let mut cc_info = ClientConnectionInfo::new_unix();
+        #[cfg(target_os = "linux")]
+        if let Ok(cred) = stream.peer_cred() {
+            cc_info.set_local_peer_cred(cred.into());
+        }
+        if let Some(worker_id) = self.worker_id {
+            cc_info.set_worker_id(Some(worker_id));
+            tokio::spawn(async move {
+                server.run_unix_task(stream, cc_info).await;
+            });
+        } else if let Some(rt) = crate::runtime::worker::select_handle() {
+            cc_info.set_worker_id(Some(rt.id));
+            rt.handle.spawn(async move {
+                server.run_unix_task(stream, cc_info).await;
+            });
+        } else {
+            tokio::spawn(async move {
+                server.run_unix_task(stream, cc_info).await;
+            });
+        }
+    }
+
+    fn get_rt_handle(&mut self, listen_in_worker: bool) -> Handle {
+        if listen_in_worker {
+            if let Some(rt) = crate::runtime::worker::select_listen_handle() {
+                self.worker_id = Some(rt.id);
+                return rt.handle;
+            }
+        }
+        Handle::current()
+    }
+
+    pub fn into_running(
+        mut self,
+        listen_config: &UnixListenConfig,
+        listen_in_worker: bool,
+        server_reload_sender: &broadcast::Sender<ServerReloadCommand>,
+    ) -> anyhow::Result<()> {
+        let listener = g3_socket::unix::new_std_listener(listen_config)?;
+        let server_reload_channel = server_reload_sender.subscribe();
+
+        let handle = self.get_rt_handle(listen_in_worker);
+        handle.spawn(async move {
+            // make sure the listen socket associated with the correct reactor
+            match UnixListener::from_std(listener) {
+                Ok(listener) => {
+                    self.pre_start();
+                    self.run(listener, server_reload_channel).await;
+                }
+                Err(e) => {
+                    warn!(
+                        "SRT[{}_v{}#unix] listen async: {e:?}",
+                        self.server.name(),
+                        self.server_version,
+                    );
+                }
+            }
+        });
+        Ok(())
+    }
+}
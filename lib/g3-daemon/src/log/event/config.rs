@@ -14,20 +14,25 @@
  * limitations under the License.
  */
 
+use std::panic::UnwindSafe;
 use std::path::Path;
 use std::sync::Arc;
 
 use anyhow::{anyhow, Context};
-use slog::{slog_o, Logger, OwnedKV, SendSyncRefUnwindSafeKV};
+use slog::{slog_o, Logger, Never, OwnedKV, SendSyncRefUnwindSafeDrain, SendSyncRefUnwindSafeKV};
 use yaml_rust::Yaml;
 
 use g3_fluentd::FluentdClientConfig;
 #[cfg(target_os = "linux")]
 use g3_journal::JournalConfig;
+use g3_kafka_log::KafkaProducerConfig;
 use g3_syslog::SyslogBuilder;
 use g3_types::log::AsyncLogConfig;
 
-use super::{LoggerStats, ReportLogIoError};
+use super::{
+    FieldSelectDrain, LogFieldSelection, LogSinkConfig, LoggerStats, MultipleSinkDrain,
+    ReportLogIoError,
+};
 
 const DEFAULT_CHANNEL_SIZE: usize = 4096;
 const IO_ERROR_SAMPLING_OFFSET_MAX: usize = 16;
@@ -40,7 +45,9 @@ pub enum LogConfigDriver {
     Journal(JournalConfig),
     Syslog(SyslogBuilder),
     Fluentd(Arc<FluentdClientConfig>),
+    Kafka(Arc<KafkaProducerConfig>),
     Stdout,
+    Multiple(Vec<LogSinkConfig>),
 }
 
 #[derive(Clone)]
@@ -49,6 +56,7 @@ pub struct LogConfig {
     pub(crate) async_channel_size: usize,
     pub(crate) async_thread_number: usize,
     pub(crate) io_err_sampling_mask: usize,
+    pub(crate) field_selection: Option<Arc<LogFieldSelection>>,
     pub(crate) program_name: &'static str,
 }
 
@@ -59,6 +67,7 @@ impl LogConfig {
             async_channel_size: DEFAULT_CHANNEL_SIZE,
             async_thread_number: 1,
             io_err_sampling_mask: (1 << IO_ERROR_SAMPLING_OFFSET_DEFAULT) - 1,
+            field_selection: None,
             program_name,
         }
     }
@@ -70,6 +79,7 @@ impl LogConfig {
             "journal" => Ok(LogConfig::new_journal(program_name)),
             "syslog" => Ok(LogConfig::new_syslog(program_name)),
             "fluentd" => Ok(LogConfig::new_fluentd(program_name)),
+            "kafka" => Ok(LogConfig::new_kafka(program_name)),
             "stdout" => Ok(LogConfig::new_stdout(program_name)),
             _ => Err(anyhow!("invalid default log config")),
         }
@@ -101,6 +111,13 @@ impl LogConfig {
         )
     }
 
+    pub fn new_kafka(program_name: &'static str) -> Self {
+        Self::with_driver(
+            LogConfigDriver::Kafka(Arc::new(KafkaProducerConfig::default())),
+            program_name,
+        )
+    }
+
     pub fn new_stdout(program_name: &'static str) -> Self {
         Self::with_driver(LogConfigDriver::Stdout, program_name)
     }
@@ -117,11 +134,13 @@ impl LogConfig {
                 "journal" => Ok(LogConfig::new_journal(program_name)),
                 "syslog" => Ok(LogConfig::new_syslog(program_name)),
                 "fluentd" => Ok(LogConfig::new_fluentd(program_name)),
+                "kafka" => Ok(LogConfig::new_kafka(program_name)),
                 "stdout" => Ok(LogConfig::new_stdout(program_name)),
                 _ => Err(anyhow!("invalid log config")),
             },
             Yaml::Hash(map) => {
                 let mut config = LogConfig::new_discard(program_name);
+                let mut field_selection = LogFieldSelection::default();
                 g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
                     #[cfg(target_os = "linux")]
                     "journal" => {
@@ -141,6 +160,23 @@ impl LogConfig {
                         config.driver = LogConfigDriver::Fluentd(Arc::new(client));
                         Ok(())
                     }
+                    "kafka" => {
+                        let producer =
+                            KafkaProducerConfig::parse_yaml(v).context("invalid kafka config")?;
+                        config.driver = LogConfigDriver::Kafka(Arc::new(producer));
+                        Ok(())
+                    }
+                    "sinks" => {
+                        let sinks = g3_yaml::value::as_list(v, |v| {
+                            LogSinkConfig::parse_yaml(v, conf_dir, program_name)
+                        })
+                        .context(format!("invalid log sink list value for key {k}"))?;
+                        if sinks.is_empty() {
+                            return Err(anyhow!("no log sink set"));
+                        }
+                        config.driver = LogConfigDriver::Multiple(sinks);
+                        Ok(())
+                    }
                     "async_channel_size" | "channel_size" => {
                         let channel_size = g3_yaml::value::as_usize(v)
                             .context(format!("invalid usize value for key {k}"))?;
@@ -165,8 +201,20 @@ impl LogConfig {
                             Ok(())
                         }
                     }
+                    "include_fields" => field_selection
+                        .set_include_yaml(v)
+                        .context(format!("invalid string list value for key {k}")),
+                    "exclude_fields" => field_selection
+                        .set_exclude_yaml(v)
+                        .context(format!("invalid string list value for key {k}")),
+                    "rename_fields" => field_selection
+                        .set_rename_yaml(v)
+                        .context(format!("invalid log fields rename value for key {k}")),
                     _ => Err(anyhow!("invalid key {k}")),
                 })?;
+                if !field_selection.is_empty() {
+                    config.field_selection = Some(Arc::new(field_selection));
+                }
                 Ok(config)
             }
             _ => Err(anyhow!("invalid value type")),
@@ -194,6 +242,14 @@ impl LogConfig {
         ))
     }
 
+    pub fn parse_kafka_yaml(v: &Yaml, program_name: &'static str) -> anyhow::Result<LogConfig> {
+        let driver = KafkaProducerConfig::parse_yaml(v).context("invalid kafka config")?;
+        Ok(LogConfig::with_driver(
+            LogConfigDriver::Kafka(Arc::new(driver)),
+            program_name,
+        ))
+    }
+
     pub fn build_shared_logger(
         self,
         logger_name: String,
@@ -223,6 +279,7 @@ impl LogConfig {
             thread_name: logger_name.clone(),
         };
 
+        let field_selection = self.field_selection;
         match self.driver {
             LogConfigDriver::Discard => {
                 let drain = slog::Discard {};
@@ -234,14 +291,14 @@ impl LogConfig {
                 let logger_stats = LoggerStats::new(&logger_name, drain.get_stats());
                 super::registry::add(logger_name.clone(), Arc::new(logger_stats));
                 let drain = ReportLogIoError::new(drain, &logger_name, self.io_err_sampling_mask);
-                Logger::root(drain, common_values)
+                new_root_logger(drain, field_selection, common_values)
             }
             LogConfigDriver::Syslog(builder) => {
                 let drain = builder.start_async(&async_conf);
                 let logger_stats = LoggerStats::new(&logger_name, drain.get_stats());
                 super::registry::add(logger_name.clone(), Arc::new(logger_stats));
                 let drain = ReportLogIoError::new(drain, &logger_name, self.io_err_sampling_mask);
-                Logger::root(drain, common_values)
+                new_root_logger(drain, field_selection, common_values)
             }
             LogConfigDriver::Fluentd(fluentd_conf) => {
                 let drain = g3_fluentd::new_async_logger(
@@ -252,19 +309,61 @@ impl LogConfig {
                 let logger_stats = LoggerStats::new(&logger_name, drain.get_stats());
                 super::registry::add(logger_name.clone(), Arc::new(logger_stats));
                 let drain = ReportLogIoError::new(drain, &logger_name, self.io_err_sampling_mask);
-                Logger::root(drain, common_values)
+                new_root_logger(drain, field_selection, common_values)
+            }
+            LogConfigDriver::Kafka(kafka_conf) => {
+                let drain = g3_kafka_log::new_async_logger(
+                    &async_conf,
+                    &kafka_conf,
+                    self.program_name,
+                    log_type,
+                );
+                let logger_stats = LoggerStats::new(&logger_name, drain.get_stats());
+                super::registry::add(logger_name.clone(), Arc::new(logger_stats));
+                let drain = ReportLogIoError::new(drain, &logger_name, self.io_err_sampling_mask);
+                new_root_logger(drain, field_selection, common_values)
             }
             LogConfigDriver::Stdout => {
                 let drain = g3_stdlog::new_async_logger(&async_conf, false, true);
                 let logger_stats = LoggerStats::new(&logger_name, drain.get_stats());
                 super::registry::add(logger_name.clone(), Arc::new(logger_stats));
                 let drain = slog::IgnoreResult::new(drain);
-                Logger::root(drain, common_values)
+                new_root_logger(drain, field_selection, common_values)
+            }
+            LogConfigDriver::Multiple(sinks) => {
+                let mut loggers = Vec::with_capacity(sinks.len());
+                for (i, sink) in sinks.into_iter().enumerate() {
+                    let sink_logger_name = match &sink.name {
+                        Some(name) => format!("{logger_name}.{name}"),
+                        None => format!("{logger_name}.{i}"),
+                    };
+                    let logger = sink
+                        .config
+                        .build_logger(sink_logger_name, log_type, slog_o!());
+                    loggers.push((sink.filter, logger));
+                }
+                let drain = MultipleSinkDrain::new(loggers);
+                new_root_logger(drain, field_selection, common_values)
             }
         }
     }
 }
 
+fn new_root_logger<D, T>(
+    drain: D,
+    field_selection: Option<Arc<LogFieldSelection>>,
+    values: OwnedKV<T>,
+) -> Logger
+where
+    D: SendSyncRefUnwindSafeDrain<Ok = (), Err = Never> + UnwindSafe + 'static,
+    T: SendSyncRefUnwindSafeKV + 'static,
+{
+    match field_selection {
+        Some(selection) => Logger::root(FieldSelectDrain::new(drain, selection), values),
+        None => Logger::root(drain, values),
+    }
+}
+
 pub struct LogConfigContainer {
     inner: Option<LogConfig>,
 }
@@ -0,0 +1,298 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use ahash::{AHashMap, AHashSet};
+use anyhow::{anyhow, Context};
+use slog::{
+    slog_o, BorrowedKV, Drain, Key, Level, OwnedKVList, Record, RecordStatic, SerdeValue,
+    Serializer, KV,
+};
+use yaml_rust::Yaml;
+
+/// get a static key for the renamed field, as slog only accepts static keys
+///
+/// The keys are leaked, but they are deduplicated so reloading the config won't leak more.
+fn static_key(name: String) -> &'static str {
+    static KEYS: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+    let mut keys = KEYS.lock().unwrap();
+    if let Some(key) = keys.get(name.as_str()) {
+        return key;
+    }
+    let key: &'static str = Box::leak(name.into_boxed_str());
+    keys.insert(key);
+    key
+}
+
+/// select and rename the fields of the records sent to a logger
+#[derive(Clone, Default)]
+pub struct LogFieldSelection {
+    include: Option<AHashSet<String>>,
+    exclude: AHashSet<String>,
+    rename: AHashMap<String, &'static str>,
+}
+
+impl LogFieldSelection {
+    pub(super) fn set_include_yaml(&mut self, v: &Yaml) -> anyhow::Result<()> {
+        let fields = g3_yaml::value::as_list(v, g3_yaml::value::as_string)?;
+        self.include = Some(fields.into_iter().collect());
+        Ok(())
+    }
+
+    pub(super) fn set_exclude_yaml(&mut self, v: &Yaml) -> anyhow::Result<()> {
+        let fields = g3_yaml::value::as_list(v, g3_yaml::value::as_string)?;
+        self.exclude = fields.into_iter().collect();
+        Ok(())
+    }
+
+    pub(super) fn set_rename_yaml(&mut self, v: &Yaml) -> anyhow::Result<()> {
+        if let Yaml::Hash(map) = v {
+            g3_yaml::foreach_kv(map, |k, v| {
+                let name = g3_yaml::value::as_string(v)
+                    .context(format!("invalid string value for field {k}"))?;
+                if name.is_empty() {
+                    return Err(anyhow!("empty new name for field {k}"));
+                }
+                self.rename.insert(k.to_string(), static_key(name));
+                Ok(())
+            })
+        } else {
+            Err(anyhow!(
+                "yaml value type for 'log fields rename' should be 'map'"
+            ))
+        }
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.include.is_none() && self.exclude.is_empty() && self.rename.is_empty()
+    }
+
+    /// get the key that should be emitted for the field, or None if it should be dropped
+    fn select_key(&self, key: Key) -> Option<Key> {
+        if self.exclude.contains(key) {
+            return None;
+        }
+        if let Some(include) = &self.include {
+            if !include.contains(key) {
+                return None;
+            }
+        }
+        Some(self.rename.get(key).copied().unwrap_or(key))
+    }
+}
+
+macro_rules! impl_select_emit {
+    ($t:ty => $f:ident) => {
+        fn $f(&mut self, key: Key, val: $t) -> slog::Result {
+            match self.selection.select_key(key) {
+                Some(key) => self.inner.$f(key, val),
+                None => Ok(()),
+            }
+        }
+    };
+}
+
+struct SelectSerializer<'a> {
+    selection: &'a LogFieldSelection,
+    inner: &'a mut dyn Serializer,
+}
+
+impl Serializer for SelectSerializer<'_> {
+    impl_select_emit!(usize => emit_usize);
+    impl_select_emit!(isize => emit_isize);
+    impl_select_emit!(bool => emit_bool);
+    impl_select_emit!(char => emit_char);
+    impl_select_emit!(u8 => emit_u8);
+    impl_select_emit!(i8 => emit_i8);
+    impl_select_emit!(u16 => emit_u16);
+    impl_select_emit!(i16 => emit_i16);
+    impl_select_emit!(u32 => emit_u32);
+    impl_select_emit!(i32 => emit_i32);
+    impl_select_emit!(f32 => emit_f32);
+    impl_select_emit!(u64 => emit_u64);
+    impl_select_emit!(i64 => emit_i64);
+    impl_select_emit!(f64 => emit_f64);
+    impl_select_emit!(&str => emit_str);
+    impl_select_emit!(&fmt::Arguments => emit_arguments);
+    impl_select_emit!(&dyn SerdeValue => emit_serde);
+    impl_select_emit!(&(dyn std::error::Error + 'static) => emit_error);
+
+    fn emit_unit(&mut self, key: Key) -> slog::Result {
+        match self.selection.select_key(key) {
+            Some(key) => self.inner.emit_unit(key),
+            None => Ok(()),
+        }
+    }
+
+    fn emit_none(&mut self, key: Key) -> slog::Result {
+        match self.selection.select_key(key) {
+            Some(key) => self.inner.emit_none(key),
+            None => Ok(()),
+        }
+    }
+}
+
+/// all the fields of a record, including the logger values, with the selection applied
+struct SelectedKV<'a> {
+    selection: &'a LogFieldSelection,
+    logger_values: &'a OwnedKVList,
+    record_kv: BorrowedKV<'a>,
+}
+
+impl KV for SelectedKV<'_> {
+    fn serialize(&self, record: &Record, serializer: &mut dyn Serializer) -> slog::Result {
+        let mut s = SelectSerializer {
+            selection: self.selection,
+            inner: serializer,
+        };
+        // keep the same order as the formatters in log drivers
+        self.logger_values.serialize(record, &mut s)?;
+        self.record_kv.serialize(record, &mut s)
+    }
+}
+
+/// apply the field selection to records before sending them to the real drain
+pub(super) struct FieldSelectDrain<D> {
+    drain: D,
+    selection: Arc<LogFieldSelection>,
+    empty_values: OwnedKVList,
+}
+
+impl<D> FieldSelectDrain<D> {
+    pub(super) fn new(drain: D, selection: Arc<LogFieldSelection>) -> Self {
+        FieldSelectDrain {
+            drain,
+            selection,
+            empty_values: OwnedKVList::from(slog_o!()),
+        }
+    }
+}
+
+impl<D: Drain> Drain for FieldSelectDrain<D> {
+    type Ok = D::Ok;
+    type Err = D::Err;
+
+    fn log(&self, record: &Record, logger_values: &OwnedKVList) -> Result<D::Ok, D::Err> {
+        let kv = SelectedKV {
+            selection: &self.selection,
+            logger_values,
+            record_kv: record.kv(),
+        };
+        let record_static = RecordStatic {
+            location: record.location(),
+            tag: record.tag(),
+            level: record.level(),
+        };
+        let record = Record::new(&record_static, record.msg(), BorrowedKV(&kv));
+        // the logger values have been merged into the record values
+        self.drain.log(&record, &self.empty_values)
+    }
+
+    fn is_enabled(&self, level: Level) -> bool {
+        self.drain.is_enabled(level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_key() {
+        let yaml = yaml_rust::YamlLoader::load_from_str(
+            "include: [a, b, c]\nexclude: [c]\nrename: {b: bb}",
+        )
+        .unwrap();
+        let map = yaml[0].as_hash().unwrap();
+
+        let mut selection = LogFieldSelection::default();
+        assert!(selection.is_empty());
+        selection
+            .set_include_yaml(map.get(&Yaml::from_str("include")).unwrap())
+            .unwrap();
+        selection
+            .set_exclude_yaml(map.get(&Yaml::from_str("exclude")).unwrap())
+            .unwrap();
+        selection
+            .set_rename_yaml(map.get(&Yaml::from_str("rename")).unwrap())
+            .unwrap();
+        assert!(!selection.is_empty());
+
+        assert_eq!(selection.select_key("a"), Some("a"));
+        assert_eq!(selection.select_key("b"), Some("bb"));
+        assert_eq!(selection.select_key("c"), None);
+        assert_eq!(selection.select_key("d"), None);
+    }
+
+    struct CollectSerializer<'a>(&'a mut Vec<(String, String)>);
+
+    impl Serializer for CollectSerializer<'_> {
+        fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments) -> slog::Result {
+            self.0.push((key.to_string(), val.to_string()));
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct CollectDrain {
+        fields: Arc<Mutex<Vec<(String, String)>>>,
+    }
+
+    impl Drain for CollectDrain {
+        type Ok = ();
+        type Err = slog::Never;
+
+        fn log(&self, record: &Record, logger_values: &OwnedKVList) -> Result<(), slog::Never> {
+            let mut fields = self.fields.lock().unwrap();
+            let mut s = CollectSerializer(&mut fields);
+            logger_values.serialize(record, &mut s).unwrap();
+            record.kv().serialize(record, &mut s).unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn select_drain() {
+        let mut selection = LogFieldSelection::default();
+        selection.exclude.insert("drop".to_string());
+        selection
+            .rename
+            .insert("old".to_string(), static_key("new".to_string()));
+
+        let collect = CollectDrain::default();
+        let drain = FieldSelectDrain::new(collect.clone(), Arc::new(selection));
+        let logger = slog::Logger::root(drain, slog_o!("daemon" => "test", "drop" => 1));
+        slog::info!(logger, "msg"; "old" => "v", "keep" => 2, "drop" => 3);
+
+        let fields = collect.fields.lock().unwrap();
+        let mut keys = fields.iter().map(|(k, _)| k.as_str()).collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(keys, ["daemon", "keep", "new"]);
+        assert!(fields.contains(&("new".to_string(), "v".to_string())));
+        assert!(fields.contains(&("daemon".to_string(), "test".to_string())));
+    }
+
+    #[test]
+    fn static_key_dedup() {
+        let k1 = static_key("renamed_key".to_string());
+        let k2 = static_key("renamed_key".to_string());
+        assert!(std::ptr::eq(k1, k2));
+    }
+}
@@ -24,5 +24,13 @@ pub mod metrics;
 
 mod registry;
 
+mod fields;
+use fields::FieldSelectDrain;
+pub use fields::LogFieldSelection;
+
+mod sink;
+use sink::MultipleSinkDrain;
+pub use sink::{LogSinkConfig, LogSinkFilter};
+
 mod config;
 pub use config::{LogConfig, LogConfigContainer, LogConfigDriver};
@@ -0,0 +1,199 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use slog::{Drain, Key, Level, Logger, Never, OwnedKVList, Record, Serializer, KV};
+use yaml_rust::{yaml, Yaml};
+
+use super::LogConfig;
+
+#[derive(Clone)]
+struct LogFieldMatch {
+    key: String,
+    values: Vec<String>,
+}
+
+/// filter the records that will be sent to a sink
+#[derive(Clone, Default)]
+pub struct LogSinkFilter {
+    min_level: Option<Level>,
+    fields: Vec<LogFieldMatch>,
+}
+
+impl LogSinkFilter {
+    fn parse_fields_yaml(&mut self, v: &Yaml) -> anyhow::Result<()> {
+        if let Yaml::Hash(map) = v {
+            g3_yaml::foreach_kv(map, |k, v| {
+                let values = g3_yaml::value::as_list(v, g3_yaml::value::as_string)
+                    .context(format!("invalid string list value for field {k}"))?;
+                self.fields.push(LogFieldMatch {
+                    key: k.to_string(),
+                    values,
+                });
+                Ok(())
+            })
+        } else {
+            Err(anyhow!(
+                "yaml value type for 'log fields filter' should be 'map'"
+            ))
+        }
+    }
+
+    fn matches(&self, record: &Record, logger_values: &OwnedKVList) -> bool {
+        if let Some(min_level) = self.min_level {
+            if !record.level().is_at_least(min_level) {
+                return false;
+            }
+        }
+
+        for field in &self.fields {
+            let mut s = FieldValueSerializer {
+                key: &field.key,
+                value: None,
+            };
+            // the record values take precedence over the logger values
+            let _ = record.kv().serialize(record, &mut s);
+            if s.value.is_none() {
+                let _ = logger_values.serialize(record, &mut s);
+            }
+            match s.value {
+                Some(v) => {
+                    if !field.values.iter().any(|s| s.eq(&v)) {
+                        return false;
+                    }
+                }
+                None => return false,
+            }
+        }
+        true
+    }
+}
+
+struct FieldValueSerializer<'a> {
+    key: &'a str,
+    value: Option<String>,
+}
+
+impl Serializer for FieldValueSerializer<'_> {
+    fn emit_arguments(&mut self, key: Key, val: &fmt::Arguments) -> slog::Result {
+        if self.value.is_none() && key == self.key {
+            self.value = Some(val.to_string());
+        }
+        Ok(())
+    }
+}
+
+/// a single sink in a multiple sinks log config
+#[derive(Clone)]
+pub struct LogSinkConfig {
+    pub(super) name: Option<String>,
+    pub(super) config: LogConfig,
+    pub(super) filter: LogSinkFilter,
+}
+
+impl LogSinkConfig {
+    pub(super) fn parse_yaml(
+        v: &Yaml,
+        conf_dir: &Path,
+        program_name: &'static str,
+    ) -> anyhow::Result<Self> {
+        match v {
+            Yaml::String(_) => Ok(LogSinkConfig {
+                name: None,
+                config: LogConfig::parse_yaml(v, conf_dir, program_name)?,
+                filter: LogSinkFilter::default(),
+            }),
+            Yaml::Hash(map) => {
+                let mut name = None;
+                let mut filter = LogSinkFilter::default();
+                let mut driver_map = yaml::Hash::new();
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "name" => {
+                        let s = g3_yaml::value::as_string(v)
+                            .context(format!("invalid string value for key {k}"))?;
+                        name = Some(s);
+                        Ok(())
+                    }
+                    "level" | "min_level" => {
+                        let s = g3_yaml::value::as_string(v)
+                            .context(format!("invalid string value for key {k}"))?;
+                        let level = Level::from_str(&s)
+                            .map_err(|_| anyhow!("invalid log level {s} for key {k}"))?;
+                        filter.min_level = Some(level);
+                        Ok(())
+                    }
+                    "fields" | "match_fields" => filter
+                        .parse_fields_yaml(v)
+                        .context(format!("invalid log fields filter value for key {k}")),
+                    "sinks" => Err(anyhow!("nested sinks is not allowed")),
+                    _ => {
+                        driver_map.insert(Yaml::String(k.to_string()), v.clone());
+                        Ok(())
+                    }
+                })?;
+                let config =
+                    LogConfig::parse_yaml(&Yaml::Hash(driver_map), conf_dir, program_name)?;
+                Ok(LogSinkConfig {
+                    name,
+                    config,
+                    filter,
+                })
+            }
+            _ => Err(anyhow!("invalid yaml value type for log sink")),
+        }
+    }
+}
+
+/// send each record to all the sinks whose filter matches
+///
+/// Each sink has its own async channel and threads, so a blocked or broken sink won't affect the others.
+pub(super) struct MultipleSinkDrain {
+    sinks: Vec<(LogSinkFilter, Logger)>,
+}
+
+impl MultipleSinkDrain {
+    pub(super) fn new(sinks: Vec<(LogSinkFilter, Logger)>) -> Self {
+        MultipleSinkDrain { sinks }
+    }
+}
+
+impl Drain for MultipleSinkDrain {
+    type Ok = ();
+    type Err = Never;
+
+    fn log(&self, record: &Record, logger_values: &OwnedKVList) -> Result<(), Never> {
+        for (filter, logger) in &self.sinks {
+            if filter.matches(record, logger_values) {
+                let _ = Drain::log(logger, record, logger_values);
+            }
+        }
+        Ok(())
+    }
+
+    fn is_enabled(&self, level: Level) -> bool {
+        self.sinks.iter().any(|(filter, logger)| {
+            filter
+                .min_level
+                .map(|l| level.is_at_least(l))
+                .unwrap_or(true)
+                && Drain::is_enabled(logger, level)
+        })
+    }
+}
@@ -26,6 +26,7 @@ const METRIC_NAME_LISTEN_ACCEPTED: &str = "listen.accepted";
 const METRIC_NAME_LISTEN_DROPPED: &str = "listen.dropped";
 const METRIC_NAME_LISTEN_TIMEOUT: &str = "listen.timeout";
 const METRIC_NAME_LISTEN_FAILED: &str = "listen.failed";
+const METRIC_NAME_LISTEN_MIGRATED: &str = "listen.migrated";
 
 pub fn emit_listen_stats(
     client: &mut StatsdClient,
@@ -60,4 +61,5 @@ pub fn emit_listen_stats(
     emit_field!(dropped, METRIC_NAME_LISTEN_DROPPED);
     emit_field!(timeout, METRIC_NAME_LISTEN_TIMEOUT);
     emit_field!(failed, METRIC_NAME_LISTEN_FAILED);
+    emit_field!(migrated, METRIC_NAME_LISTEN_MIGRATED);
 }
@@ -0,0 +1,261 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use chrono::{Local, NaiveTime, TimeDelta};
+use yaml_rust::{yaml, Yaml};
+
+use g3_types::metrics::NodeName;
+
+/// A schedule action supported by the daemon
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ScheduleAction {
+    name: &'static str,
+    need_target: bool,
+}
+
+impl ScheduleAction {
+    /// an action that should be run on the entry set by `target`
+    pub const fn with_target(name: &'static str) -> Self {
+        ScheduleAction {
+            name,
+            need_target: true,
+        }
+    }
+
+    /// an action that should be run without any target
+    pub const fn without_target(name: &'static str) -> Self {
+        ScheduleAction {
+            name,
+            need_target: false,
+        }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ScheduleTrigger {
+    /// run at every interval
+    Interval(Duration),
+    /// run once every day at the specified local time
+    DailyAt(NaiveTime),
+}
+
+impl ScheduleTrigger {
+    pub(crate) fn next_delay(&self) -> Duration {
+        match self {
+            ScheduleTrigger::Interval(interval) => *interval,
+            ScheduleTrigger::DailyAt(time) => {
+                let now = Local::now();
+                let mut next = now.date_naive().and_time(*time);
+                if next <= now.naive_local() {
+                    next += TimeDelta::days(1);
+                }
+                (next - now.naive_local())
+                    .to_std()
+                    .unwrap_or(Duration::from_secs(1))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ScheduledTaskConfig {
+    name: String,
+    action: String,
+    target: Option<NodeName>,
+    trigger: Option<ScheduleTrigger>,
+}
+
+impl ScheduledTaskConfig {
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    #[inline]
+    pub fn target(&self) -> Option<&NodeName> {
+        self.target.as_ref()
+    }
+
+    #[inline]
+    pub(crate) fn trigger(&self) -> &ScheduleTrigger {
+        // this is always set after check
+        self.trigger.as_ref().unwrap()
+    }
+
+    pub(crate) fn parse_yaml(v: &Yaml, valid_actions: &[ScheduleAction]) -> anyhow::Result<Self> {
+        if let Yaml::Hash(map) = v {
+            let mut config = ScheduledTaskConfig {
+                name: String::new(),
+                action: String::new(),
+                target: None,
+                trigger: None,
+            };
+            config.parse_map(map)?;
+            config.check(valid_actions)?;
+            Ok(config)
+        } else {
+            Err(anyhow!(
+                "yaml value type for 'scheduled task' should be 'map'"
+            ))
+        }
+    }
+
+    fn parse_map(&mut self, map: &yaml::Hash) -> anyhow::Result<()> {
+        g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+            "name" => {
+                self.name = g3_yaml::value::as_string(v)?;
+                Ok(())
+            }
+            "action" => {
+                self.action = g3_yaml::key::normalize(&g3_yaml::value::as_string(v)?);
+                Ok(())
+            }
+            "target" => {
+                let target = g3_yaml::value::as_metrics_name(v)
+                    .context(format!("invalid metrics name value for key {k}"))?;
+                self.target = Some(target);
+                Ok(())
+            }
+            "interval" | "every" => {
+                let interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                if interval.is_zero() {
+                    return Err(anyhow!("zero interval is not allowed"));
+                }
+                self.trigger = Some(ScheduleTrigger::Interval(interval));
+                Ok(())
+            }
+            "daily_at" | "at" => {
+                let s = g3_yaml::value::as_string(v)?;
+                let time = NaiveTime::parse_from_str(&s, "%H:%M")
+                    .or_else(|_| NaiveTime::parse_from_str(&s, "%H:%M:%S"))
+                    .map_err(|e| anyhow!("invalid time value {s} for key {k}: {e}"))?;
+                self.trigger = Some(ScheduleTrigger::DailyAt(time));
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })
+    }
+
+    fn check(&mut self, valid_actions: &[ScheduleAction]) -> anyhow::Result<()> {
+        if self.action.is_empty() {
+            return Err(anyhow!("no action set"));
+        }
+        let Some(action) = valid_actions.iter().find(|a| a.name == self.action) else {
+            return Err(anyhow!("unsupported action {}", self.action));
+        };
+        match (action.need_target, &self.target) {
+            (true, None) => {
+                return Err(anyhow!("no target set for action {}", self.action));
+            }
+            (false, Some(_)) => {
+                return Err(anyhow!("no target is allowed for action {}", self.action));
+            }
+            _ => {}
+        }
+        if self.trigger.is_none() {
+            return Err(anyhow!("neither interval nor daily_at is set"));
+        }
+        if self.name.is_empty() {
+            self.name = match &self.target {
+                Some(target) => format!("{}:{target}", self.action),
+                None => self.action.clone(),
+            };
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    const ACTIONS: &[ScheduleAction] = &[
+        ScheduleAction::with_target("reload_server"),
+        ScheduleAction::without_target("reload_config"),
+    ];
+
+    fn parse(s: &str) -> anyhow::Result<ScheduledTaskConfig> {
+        let docs = YamlLoader::load_from_str(s).unwrap();
+        ScheduledTaskConfig::parse_yaml(&docs[0], ACTIONS)
+    }
+
+    #[test]
+    fn parse_interval() {
+        let task = parse("{action: Reload-Server, target: https, interval: 5m}").unwrap();
+        assert_eq!(task.action(), "reload_server");
+        assert_eq!(task.target().unwrap().as_str(), "https");
+        assert_eq!(task.name(), "reload_server:https");
+        assert_eq!(
+            task.trigger(),
+            &ScheduleTrigger::Interval(Duration::from_secs(300))
+        );
+        assert_eq!(task.trigger().next_delay(), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn parse_daily_at() {
+        let task = parse("{name: reload, action: reload_config, daily_at: '03:30'}").unwrap();
+        assert_eq!(task.name(), "reload");
+        assert!(task.target().is_none());
+        assert_eq!(
+            task.trigger(),
+            &ScheduleTrigger::DailyAt(NaiveTime::from_hms_opt(3, 30, 0).unwrap())
+        );
+        assert!(task.trigger().next_delay() <= Duration::from_secs(86400));
+
+        let task = parse("{action: reload_config, at: '23:59:59'}").unwrap();
+        assert_eq!(
+            task.trigger(),
+            &ScheduleTrigger::DailyAt(NaiveTime::from_hms_opt(23, 59, 59).unwrap())
+        );
+    }
+
+    #[test]
+    fn invalid_action() {
+        assert!(parse("{interval: 1m}").is_err());
+        assert!(parse("{action: reload_escaper, target: e, interval: 1m}").is_err());
+    }
+
+    #[test]
+    fn invalid_target() {
+        assert!(parse("{action: reload_server, interval: 1m}").is_err());
+        assert!(parse("{action: reload_config, target: s, interval: 1m}").is_err());
+        assert!(parse("{action: reload_server, target: 'a b', interval: 1m}").is_err());
+    }
+
+    #[test]
+    fn invalid_trigger() {
+        assert!(parse("{action: reload_config}").is_err());
+        assert!(parse("{action: reload_config, interval: 0s}").is_err());
+        assert!(parse("{action: reload_config, daily_at: '25:00'}").is_err());
+        assert!(parse("{action: reload_config, every: 1m, unknown: 1}").is_err());
+    }
+}
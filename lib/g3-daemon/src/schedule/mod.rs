@@ -0,0 +1,77 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{anyhow, Context};
+use log::{info, warn};
+use yaml_rust::Yaml;
+
+mod config;
+pub use config::{ScheduleAction, ScheduleTrigger, ScheduledTaskConfig};
+
+static GLOBAL_SCHEDULE_CONFIG: OnceLock<Vec<Arc<ScheduledTaskConfig>>> = OnceLock::new();
+
+/// Load the scheduled tasks. The `action` of each task should be in `valid_actions`.
+pub fn load(v: &Yaml, valid_actions: &[ScheduleAction]) -> anyhow::Result<()> {
+    let tasks = match v {
+        Yaml::Array(seq) => {
+            let mut tasks = Vec::with_capacity(seq.len());
+            for (i, v) in seq.iter().enumerate() {
+                let task = ScheduledTaskConfig::parse_yaml(v, valid_actions)
+                    .context(format!("invalid scheduled task config #{i}"))?;
+                tasks.push(Arc::new(task));
+            }
+            tasks
+        }
+        Yaml::Null => Vec::new(),
+        _ => return Err(anyhow!("yaml value type for 'schedule' should be 'seq'")),
+    };
+    if GLOBAL_SCHEDULE_CONFIG.set(tasks).is_err() {
+        warn!("global schedule config has already been set");
+    }
+    Ok(())
+}
+
+/// Spawn all scheduled tasks in the current runtime.
+///
+/// `run` will be called each time a task is triggered.
+pub fn spawn_all<F, Fut>(run: F)
+where
+    F: Fn(Arc<ScheduledTaskConfig>) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = anyhow::Result<()>> + Send,
+{
+    let Some(tasks) = GLOBAL_SCHEDULE_CONFIG.get() else {
+        return;
+    };
+
+    for task in tasks {
+        let task = task.clone();
+        let run = run.clone();
+        tokio::spawn(async move {
+            loop {
+                let delay = task.trigger().next_delay();
+                tokio::time::sleep(delay).await;
+
+                match run(task.clone()).await {
+                    Ok(_) => info!("scheduled task {} finished", task.name()),
+                    Err(e) => warn!("scheduled task {} failed: {e:?}", task.name()),
+                }
+            }
+        });
+    }
+}
@@ -15,11 +15,14 @@
  */
 
 use std::io;
-use std::net::{IpAddr, SocketAddr};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
 
 use g3_io_ext::haproxy::ProxyAddr;
+#[cfg(target_os = "linux")]
+use g3_socket::local_peer::LocalPeerCred;
 use g3_socket::RawSocket;
-use g3_types::net::TcpMiscSockOpts;
+use g3_types::net::{ProxyProtocolTlv, TcpMiscSockOpts};
 
 #[derive(Clone, Debug)]
 pub struct ClientConnectionInfo {
@@ -30,6 +33,9 @@ pub struct ClientConnectionInfo {
     #[allow(unused)]
     sock_local_addr: SocketAddr,
     tcp_raw_socket: Option<RawSocket>,
+    proxy_protocol_tlvs: Option<Arc<[ProxyProtocolTlv]>>,
+    #[cfg(target_os = "linux")]
+    local_peer_cred: Option<LocalPeerCred>,
 }
 
 impl ClientConnectionInfo {
@@ -41,9 +47,30 @@ impl ClientConnectionInfo {
             sock_peer_addr: peer_addr,
             sock_local_addr: local_addr,
             tcp_raw_socket: None,
+            proxy_protocol_tlvs: None,
+            #[cfg(target_os = "linux")]
+            local_peer_cred: None,
         }
     }
 
+    /// unix socket connections have no ip address, loopback ip with port 0 will be used
+    pub fn new_unix() -> Self {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0);
+        ClientConnectionInfo::new(addr, addr)
+    }
+
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn set_local_peer_cred(&mut self, cred: LocalPeerCred) {
+        self.local_peer_cred = Some(cred);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn local_peer_cred(&self) -> Option<&LocalPeerCred> {
+        self.local_peer_cred.as_ref()
+    }
+
     #[inline]
     pub fn set_tcp_raw_socket(&mut self, raw_fd: RawSocket) {
         self.tcp_raw_socket = Some(raw_fd);
@@ -53,6 +80,15 @@ impl ClientConnectionInfo {
     pub fn set_proxy_addr(&mut self, addr: ProxyAddr) {
         self.client_addr = addr.src_addr;
         self.server_addr = addr.dst_addr;
+        if !addr.tlvs.is_empty() {
+            self.proxy_protocol_tlvs = Some(Arc::from(addr.tlvs));
+        }
+    }
+
+    /// Get the TLVs received in the PROXY protocol v2 header
+    #[inline]
+    pub fn proxy_protocol_tlvs(&self) -> &[ProxyProtocolTlv] {
+        self.proxy_protocol_tlvs.as_deref().unwrap_or_default()
     }
 
     #[inline]
@@ -121,4 +157,16 @@ impl ClientConnectionInfo {
 
     #[cfg(not(any(target_os = "linux", target_os = "android")))]
     pub fn tcp_sock_try_quick_ack(&self) {}
+
+    /// get the DSCP value of the client tcp connection
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn tcp_sock_dscp(&self) -> Option<u8> {
+        let raw_socket = self.tcp_raw_socket.as_ref()?;
+        raw_socket.tcp_tos().ok().map(|tos| tos >> 2)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    pub fn tcp_sock_dscp(&self) -> Option<u8> {
+        None
+    }
 }
@@ -19,12 +19,12 @@ use std::future::Future;
 #[cfg(unix)]
 mod unix;
 #[cfg(unix)]
-pub use unix::register;
+pub use unix::{register, register_reload};
 
 #[cfg(windows)]
 mod windows;
 #[cfg(windows)]
-pub use windows::register;
+pub use windows::{register, register_reload};
 
 pub trait AsyncSignalAction: Copy {
     fn run(&self) -> impl Future<Output = ()> + Send;
@@ -16,13 +16,14 @@
 
 use anyhow::anyhow;
 use log::info;
-use tokio::signal::windows::ctrl_c;
+use tokio::signal::windows::{ctrl_break, ctrl_c, ctrl_shutdown};
 
 use super::AsyncSignalAction;
 
-pub fn register<QUIT>(do_quit: QUIT) -> anyhow::Result<()>
+pub fn register<QUIT, OFFLINE>(do_quit: QUIT, do_offline: OFFLINE) -> anyhow::Result<()>
 where
     QUIT: AsyncSignalAction + Send + 'static,
+    OFFLINE: AsyncSignalAction + Send + 'static,
 {
     let mut quit_sig = ctrl_c().map_err(|e| anyhow!("failed to create Ctrl-C listener: {e}"))?;
     tokio::spawn(async move {
@@ -32,5 +33,30 @@ where
         }
     });
 
+    // Ctrl-Break and system shutdown are the closest to SIGTERM we can get on windows
+    let mut break_sig =
+        ctrl_break().map_err(|e| anyhow!("failed to create Ctrl-Break listener: {e}"))?;
+    let mut shutdown_sig =
+        ctrl_shutdown().map_err(|e| anyhow!("failed to create shutdown listener: {e}"))?;
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = break_sig.recv() => {
+                info!("got offline signal");
+            }
+            _ = shutdown_sig.recv() => {
+                info!("got system shutdown signal");
+            }
+        }
+        do_offline.run().await;
+    });
+
+    Ok(())
+}
+
+/// There is no SIGHUP on windows, so nothing will be registered.
+pub fn register_reload<RELOAD>(_call_reload: RELOAD) -> anyhow::Result<()>
+where
+    RELOAD: AsyncSignalAction + Send + 'static,
+{
     Ok(())
 }
@@ -0,0 +1,177 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::ProtocolInspectAction;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomProtocolPattern {
+    offset: usize,
+    value: Vec<u8>,
+    mask: Option<Vec<u8>>,
+}
+
+impl CustomProtocolPattern {
+    pub fn new(offset: usize, value: Vec<u8>, mask: Option<Vec<u8>>) -> Result<Self, &'static str> {
+        if value.is_empty() {
+            return Err("empty pattern value");
+        }
+        if let Some(mask) = &mask {
+            if mask.len() != value.len() {
+                return Err("the length of mask and value should be the same");
+            }
+        }
+        Ok(CustomProtocolPattern {
+            offset,
+            value,
+            mask,
+        })
+    }
+
+    #[inline]
+    fn required_size(&self) -> usize {
+        self.offset + self.value.len()
+    }
+
+    /// Check the available data, return None if more data is needed
+    fn check(&self, data: &[u8]) -> Option<bool> {
+        let left = data.get(self.offset..)?;
+        for (i, b) in left.iter().take(self.value.len()).enumerate() {
+            let v = match &self.mask {
+                Some(mask) => b & mask[i],
+                None => *b,
+            };
+            if v != self.value[i] {
+                return Some(false);
+            }
+        }
+        if left.len() < self.value.len() {
+            None
+        } else {
+            Some(true)
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CustomProtocolSignature {
+    name: String,
+    server_first: bool,
+    ports: Vec<u16>,
+    patterns: Vec<CustomProtocolPattern>,
+    inspect_action: ProtocolInspectAction,
+}
+
+impl CustomProtocolSignature {
+    pub fn new(name: String) -> Self {
+        CustomProtocolSignature {
+            name,
+            server_first: false,
+            ports: Vec::new(),
+            patterns: Vec::new(),
+            inspect_action: ProtocolInspectAction::Bypass,
+        }
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_server_first(&mut self, server_first: bool) {
+        self.server_first = server_first;
+    }
+
+    #[inline]
+    pub fn server_first(&self) -> bool {
+        self.server_first
+    }
+
+    pub fn add_port(&mut self, port: u16) {
+        if !self.ports.contains(&port) {
+            self.ports.push(port);
+        }
+    }
+
+    pub fn add_pattern(&mut self, pattern: CustomProtocolPattern) {
+        self.patterns.push(pattern);
+    }
+
+    #[inline]
+    pub fn has_pattern(&self) -> bool {
+        !self.patterns.is_empty()
+    }
+
+    pub fn set_inspect_action(&mut self, action: ProtocolInspectAction) {
+        self.inspect_action = action;
+    }
+
+    #[inline]
+    pub fn inspect_action(&self) -> ProtocolInspectAction {
+        self.inspect_action
+    }
+
+    pub(crate) fn match_port(&self, port: u16) -> bool {
+        self.ports.is_empty() || self.ports.contains(&port)
+    }
+
+    /// Check the initial data.
+    /// Return the needed data size if more data is needed.
+    pub(crate) fn check_data(&self, data: &[u8]) -> Result<bool, usize> {
+        let mut required_size = 0;
+        for p in &self.patterns {
+            match p.check(data) {
+                Some(true) => {}
+                Some(false) => return Ok(false),
+                None => required_size = required_size.max(p.required_size()),
+            }
+        }
+        if required_size > 0 {
+            Err(required_size - data.len())
+        } else {
+            Ok(true)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_pattern() {
+        let p = CustomProtocolPattern::new(2, vec![0x12, 0x30], Some(vec![0xff, 0xf0])).unwrap();
+        assert_eq!(p.check(b"\x00"), None);
+        assert_eq!(p.check(b"\x00\x00\x12"), None);
+        assert_eq!(p.check(b"\x00\x00\x13"), Some(false));
+        assert_eq!(p.check(b"\x00\x00\x12\x3f"), Some(true));
+        assert_eq!(p.check(b"\x00\x00\x12\x40\x00"), Some(false));
+    }
+
+    #[test]
+    fn check_signature() {
+        let mut s = CustomProtocolSignature::new("test".to_string());
+        s.add_pattern(CustomProtocolPattern::new(0, b"AB".to_vec(), None).unwrap());
+        s.add_pattern(CustomProtocolPattern::new(4, b"\x01".to_vec(), None).unwrap());
+        assert_eq!(s.check_data(b"AB"), Err(3));
+        assert_eq!(s.check_data(b"AC"), Ok(false));
+        assert_eq!(s.check_data(b"AB\x00\x00\x01"), Ok(true));
+        assert_eq!(s.check_data(b"AB\x00\x00\x02"), Ok(false));
+
+        s.add_port(8000);
+        assert!(s.match_port(8000));
+        assert!(!s.match_port(8001));
+    }
+}
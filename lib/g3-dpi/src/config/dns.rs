@@ -0,0 +1,44 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+/// Config for the DNS tunneling detection on relayed DNS queries
+#[derive(Clone, Debug, PartialEq)]
+pub struct DnsInterceptionConfig {
+    /// the number of the trailing labels that will be treated as the registered domain
+    pub domain_labels: usize,
+    pub entropy_threshold: f64,
+    pub subdomain_length_threshold: usize,
+    pub stats_window: Duration,
+    pub query_rate_threshold: usize,
+    pub txt_volume_threshold: usize,
+    pub log_score: u8,
+}
+
+impl Default for DnsInterceptionConfig {
+    fn default() -> Self {
+        DnsInterceptionConfig {
+            domain_labels: 2,
+            entropy_threshold: 3.5,
+            subdomain_length_threshold: 48,
+            stats_window: Duration::from_secs(60),
+            query_rate_threshold: 120,
+            txt_volume_threshold: 30,
+            log_score: 40,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KafkaInterceptionConfig {
+    pub request_max_size: usize,
+    pub request_buffer_size: usize,
+    pub block_produce: bool,
+    pub block_fetch: bool,
+}
+
+impl Default for KafkaInterceptionConfig {
+    fn default() -> Self {
+        KafkaInterceptionConfig {
+            request_max_size: 100 * 1024 * 1024,
+            request_buffer_size: 4096,
+            block_produce: false,
+            block_fetch: false,
+        }
+    }
+}
@@ -16,6 +16,7 @@
 
 use std::fmt;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
 use g3_types::acl::{
@@ -37,6 +38,24 @@ pub use smtp::SmtpInterceptionConfig;
 mod imap;
 pub use imap::ImapInterceptionConfig;
 
+mod kafka;
+pub use kafka::KafkaInterceptionConfig;
+
+mod postgres;
+pub use postgres::PostgresInterceptionConfig;
+
+mod mysql;
+pub use mysql::MysqlInterceptionConfig;
+
+mod dns;
+pub use dns::DnsInterceptionConfig;
+
+mod websocket;
+pub use websocket::WebsocketInterceptionConfig;
+
+mod custom;
+pub use custom::{CustomProtocolPattern, CustomProtocolSignature};
+
 #[derive(Clone)]
 pub struct ProtocolInspectPolicyBuilder {
     missed_action: ProtocolInspectAction,
@@ -121,7 +140,7 @@ impl ProtocolInspectPolicy {
     }
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum ProtocolInspectAction {
     Block,
     #[default]
@@ -132,7 +151,7 @@ pub enum ProtocolInspectAction {
 }
 
 impl ProtocolInspectAction {
-    fn as_str(&self) -> &'static str {
+    pub fn as_str(&self) -> &'static str {
         match self {
             Self::Block => "block",
             Self::Intercept => "intercept",
@@ -177,6 +196,7 @@ pub struct ProtocolInspectionConfig {
     data0_wait_timeout: Duration,
     data0_read_timeout: Duration,
     data0_size_limit: ProtocolInspectionSizeLimit,
+    custom_signatures: Vec<Arc<CustomProtocolSignature>>,
 }
 
 impl Default for ProtocolInspectionConfig {
@@ -187,6 +207,7 @@ impl Default for ProtocolInspectionConfig {
             data0_wait_timeout: Duration::from_secs(60),
             data0_read_timeout: Duration::from_secs(4),
             data0_size_limit: Default::default(),
+            custom_signatures: Vec::new(),
         }
     }
 }
@@ -239,4 +260,13 @@ impl ProtocolInspectionConfig {
     pub fn size_limit_mut(&mut self) -> &mut ProtocolInspectionSizeLimit {
         &mut self.data0_size_limit
     }
+
+    pub fn add_custom_signature(&mut self, signature: CustomProtocolSignature) {
+        self.custom_signatures.push(Arc::new(signature));
+    }
+
+    #[inline]
+    pub fn custom_signatures(&self) -> &[Arc<CustomProtocolSignature>] {
+        &self.custom_signatures
+    }
 }
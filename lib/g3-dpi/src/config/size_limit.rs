@@ -20,6 +20,7 @@ pub struct ProtocolInspectionSizeLimit {
     pub(crate) http_client_request_uri: usize,
     pub(crate) imap_server_greeting_msg: usize,
     pub(crate) nats_server_info_line: usize,
+    pub(crate) thrift_method_name: usize,
 }
 
 impl Default for ProtocolInspectionSizeLimit {
@@ -29,6 +30,7 @@ impl Default for ProtocolInspectionSizeLimit {
             http_client_request_uri: 4096,
             imap_server_greeting_msg: 512,
             nats_server_info_line: 1024,
+            thrift_method_name: 256,
         }
     }
 }
@@ -49,4 +51,13 @@ impl ProtocolInspectionSizeLimit {
     pub fn set_nats_server_info_line(&mut self, size: usize) {
         self.nats_server_info_line = size;
     }
+
+    pub fn set_thrift_method_name(&mut self, size: usize) {
+        self.thrift_method_name = size;
+    }
+
+    #[inline]
+    pub fn thrift_method_name(&self) -> usize {
+        self.thrift_method_name
+    }
 }
@@ -0,0 +1,42 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebsocketInterceptionConfig {
+    /// the max size of a (fragmented) data message, 0 means no limit
+    pub max_message_size: u64,
+    pub log_frames: bool,
+    pub icap_scan: bool,
+    /// the max size of a data message that will be sent to ICAP server for scanning
+    pub icap_scan_max_size: usize,
+}
+
+impl Default for WebsocketInterceptionConfig {
+    fn default() -> Self {
+        WebsocketInterceptionConfig {
+            max_message_size: 0,
+            log_frames: false,
+            icap_scan: false,
+            icap_scan_max_size: 1024 * 1024,
+        }
+    }
+}
+
+impl WebsocketInterceptionConfig {
+    /// Check if the frames need to be parsed, or we can just relay the data transparently
+    pub fn need_frame_relay(&self) -> bool {
+        self.max_message_size > 0 || self.log_frames || self.icap_scan
+    }
+}
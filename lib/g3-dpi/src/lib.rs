@@ -25,9 +25,11 @@ pub use protocol::{
 
 mod config;
 pub use config::{
-    H1InterceptionConfig, H2InterceptionConfig, ImapInterceptionConfig, ProtocolInspectAction,
-    ProtocolInspectPolicy, ProtocolInspectPolicyBuilder, ProtocolInspectionConfig,
-    ProtocolInspectionSizeLimit, SmtpInterceptionConfig,
+    CustomProtocolPattern, CustomProtocolSignature, DnsInterceptionConfig, H1InterceptionConfig,
+    H2InterceptionConfig, ImapInterceptionConfig, KafkaInterceptionConfig, MysqlInterceptionConfig,
+    PostgresInterceptionConfig, ProtocolInspectAction, ProtocolInspectPolicy,
+    ProtocolInspectPolicyBuilder, ProtocolInspectionConfig, ProtocolInspectionSizeLimit,
+    SmtpInterceptionConfig, WebsocketInterceptionConfig,
 };
 
 pub mod parser;
@@ -0,0 +1,373 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use thiserror::Error;
+
+use super::{DerHeader, DerHeaderParseError};
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DerDecodeError {
+    #[error("need {0} bytes more data")]
+    NeedMoreData(usize),
+    #[error("invalid header: {0}")]
+    InvalidHeader(DerHeaderParseError),
+    #[error("max depth {0} exceeded")]
+    DepthExceeded(usize),
+    #[error("element size {0} exceeds the limit")]
+    ElementTooLarge(usize),
+    #[error("child element overflows its parent")]
+    ChildOverflow,
+}
+
+impl From<DerHeaderParseError> for DerDecodeError {
+    fn from(value: DerHeaderParseError) -> Self {
+        match value {
+            DerHeaderParseError::NeedMoreData(n) => DerDecodeError::NeedMoreData(n),
+            e => DerDecodeError::InvalidHeader(e),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DerDecoderLimits {
+    /// max nesting depth of constructed elements
+    pub max_depth: usize,
+    /// max encoded size of a top level element
+    pub max_element_size: usize,
+    /// primitive elements larger than this will be returned in chunks
+    pub max_primitive_size: usize,
+}
+
+impl Default for DerDecoderLimits {
+    fn default() -> Self {
+        DerDecoderLimits {
+            max_depth: 32,
+            max_element_size: 1 << 24,
+            max_primitive_size: 1 << 16,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DerEvent<'a> {
+    /// start of a constructed element, the children will follow
+    ConstructedStart(DerHeader),
+    /// end of a constructed element
+    ConstructedEnd(DerHeader),
+    /// a primitive element with all its content
+    Primitive(DerHeader, &'a [u8]),
+    /// part of the content of a large primitive element
+    PrimitiveChunk {
+        header: DerHeader,
+        chunk: &'a [u8],
+        last: bool,
+    },
+}
+
+struct PendingPrimitive {
+    header: DerHeader,
+    end: usize,
+}
+
+/// A streaming DER decoder which doesn't need to buffer whole messages
+///
+/// The caller should feed the unconsumed data to `next_event` repeatedly,
+/// and append more data from the network when `NeedMoreData` is returned.
+pub struct DerStreamDecoder {
+    limits: DerDecoderLimits,
+    offset: usize,
+    stack: Vec<(DerHeader, usize)>,
+    primitive: Option<PendingPrimitive>,
+}
+
+impl DerStreamDecoder {
+    pub fn new(limits: DerDecoderLimits) -> Self {
+        DerStreamDecoder {
+            limits,
+            offset: 0,
+            stack: Vec::with_capacity(limits.max_depth.min(8)),
+            primitive: None,
+        }
+    }
+
+    /// Current depth of constructed elements
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Check if a top level element has been fully decoded
+    pub fn is_idle(&self) -> bool {
+        self.stack.is_empty() && self.primitive.is_none()
+    }
+
+    /// Reset the state to decode a new top level element
+    pub fn reset(&mut self) {
+        self.offset = 0;
+        self.stack.clear();
+        self.primitive = None;
+    }
+
+    /// Decode the next event from the data
+    ///
+    /// The number of bytes consumed will be returned along with the event.
+    pub fn next_event<'a>(
+        &mut self,
+        data: &'a [u8],
+    ) -> Result<(DerEvent<'a>, usize), DerDecodeError> {
+        if let Some((header, end)) = self.stack.last() {
+            if *end == self.offset {
+                let header = *header;
+                self.stack.pop();
+                if self.stack.is_empty() {
+                    self.offset = 0;
+                }
+                return Ok((DerEvent::ConstructedEnd(header), 0));
+            }
+        }
+
+        if let Some(p) = &self.primitive {
+            if data.is_empty() {
+                return Err(DerDecodeError::NeedMoreData(p.end - self.offset));
+            }
+            let header = p.header;
+            let left = p.end - self.offset;
+            let len = left.min(data.len());
+            let last = len == left;
+            if last {
+                self.primitive = None;
+            }
+            self.advance(len);
+            return Ok((
+                DerEvent::PrimitiveChunk {
+                    header,
+                    chunk: &data[..len],
+                    last,
+                },
+                len,
+            ));
+        }
+
+        let header = DerHeader::parse(data)?;
+        let element_end = self.offset + header.encoded_len();
+        match self.stack.last() {
+            Some((_, parent_end)) => {
+                if element_end > *parent_end {
+                    return Err(DerDecodeError::ChildOverflow);
+                }
+            }
+            None => {
+                if header.encoded_len() > self.limits.max_element_size {
+                    return Err(DerDecodeError::ElementTooLarge(header.encoded_len()));
+                }
+            }
+        }
+
+        if header.constructed {
+            if self.stack.len() >= self.limits.max_depth {
+                return Err(DerDecodeError::DepthExceeded(self.limits.max_depth));
+            }
+            self.stack.push((header, element_end));
+            self.advance(header.header_len);
+            return Ok((DerEvent::ConstructedStart(header), header.header_len));
+        }
+
+        let total = header.encoded_len();
+        if data.len() >= total {
+            self.advance(total);
+            return Ok((
+                DerEvent::Primitive(header, &data[header.header_len..total]),
+                total,
+            ));
+        }
+        if header.content_len <= self.limits.max_primitive_size {
+            return Err(DerDecodeError::NeedMoreData(total - data.len()));
+        }
+
+        // too large to be buffered, return the content in chunks
+        self.primitive = Some(PendingPrimitive {
+            header,
+            end: element_end,
+        });
+        self.advance(data.len());
+        Ok((
+            DerEvent::PrimitiveChunk {
+                header,
+                chunk: &data[header.header_len..],
+                last: false,
+            },
+            data.len(),
+        ))
+    }
+
+    fn advance(&mut self, len: usize) {
+        self.offset += len;
+        if self.stack.is_empty() && self.primitive.is_none() {
+            self.offset = 0;
+        }
+    }
+}
+
+impl Default for DerStreamDecoder {
+    fn default() -> Self {
+        DerStreamDecoder::new(DerDecoderLimits::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::der::tag;
+
+    // LDAP BindRequest, messageID 1, version 3, name "cn=a", simple auth "pw"
+    const LDAP_BIND: &[u8] = &[
+        0x30, 0x12, 0x02, 0x01, 0x01, 0x60, 0x0d, 0x02, 0x01, 0x03, 0x04, 0x04, b'c', b'n', b'=',
+        b'a', 0x80, 0x02, b'p', b'w',
+    ];
+
+    fn collect(decoder: &mut DerStreamDecoder, mut data: &[u8]) -> Vec<String> {
+        let mut events = Vec::new();
+        loop {
+            let (event, len) = decoder.next_event(data).unwrap();
+            events.push(match event {
+                DerEvent::ConstructedStart(h) => format!("start {}", h.tag),
+                DerEvent::ConstructedEnd(h) => format!("end {}", h.tag),
+                DerEvent::Primitive(h, v) => format!("{} {v:?}", h.tag),
+                DerEvent::PrimitiveChunk {
+                    header,
+                    chunk,
+                    last,
+                } => {
+                    format!("chunk {} {} {last}", header.tag, chunk.len())
+                }
+            });
+            data = &data[len..];
+            if decoder.is_idle() {
+                break;
+            }
+        }
+        events
+    }
+
+    #[test]
+    fn ldap_bind() {
+        let mut decoder = DerStreamDecoder::default();
+        let events = collect(&mut decoder, LDAP_BIND);
+        assert_eq!(
+            events,
+            vec![
+                "start 16".to_string(),
+                format!("{} [1]", tag::INTEGER),
+                "start 0".to_string(),
+                format!("{} [3]", tag::INTEGER),
+                format!("{} [99, 110, 61, 97]", tag::OCTET_STRING),
+                "0 [112, 119]".to_string(),
+                "end 0".to_string(),
+                "end 16".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        let mut decoder = DerStreamDecoder::default();
+        let mut buf = Vec::new();
+        let mut events = 0;
+        for b in LDAP_BIND {
+            buf.push(*b);
+            loop {
+                match decoder.next_event(&buf) {
+                    Ok((_, len)) => {
+                        events += 1;
+                        buf.drain(..len);
+                    }
+                    Err(DerDecodeError::NeedMoreData(_)) => break,
+                    Err(e) => panic!("unexpected error: {e}"),
+                }
+            }
+        }
+        assert!(decoder.is_idle());
+        assert_eq!(events, 8);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn large_primitive() {
+        let limits = DerDecoderLimits {
+            max_primitive_size: 4,
+            ..Default::default()
+        };
+        let mut decoder = DerStreamDecoder::new(limits);
+        let data = [0x04, 0x06, 1, 2, 3];
+        let (event, len) = decoder.next_event(&data).unwrap();
+        assert_eq!(len, 5);
+        assert!(matches!(
+            event,
+            DerEvent::PrimitiveChunk {
+                last: false,
+                chunk: [1, 2, 3],
+                ..
+            }
+        ));
+        let (event, len) = decoder.next_event(&[4, 5, 6, 7]).unwrap();
+        assert_eq!(len, 3);
+        assert!(matches!(
+            event,
+            DerEvent::PrimitiveChunk {
+                last: true,
+                chunk: [4, 5, 6],
+                ..
+            }
+        ));
+        assert!(decoder.is_idle());
+    }
+
+    #[test]
+    fn limits() {
+        let limits = DerDecoderLimits {
+            max_depth: 2,
+            ..Default::default()
+        };
+        let mut decoder = DerStreamDecoder::new(limits);
+        let data = [0x30, 0x06, 0x30, 0x04, 0x30, 0x02, 0x05, 0x00];
+        let mut offset = 0;
+        for _ in 0..2 {
+            let (_, len) = decoder.next_event(&data[offset..]).unwrap();
+            offset += len;
+        }
+        assert_eq!(
+            decoder.next_event(&data[offset..]).unwrap_err(),
+            DerDecodeError::DepthExceeded(2)
+        );
+
+        let limits = DerDecoderLimits {
+            max_element_size: 4,
+            ..Default::default()
+        };
+        let mut decoder = DerStreamDecoder::new(limits);
+        assert_eq!(
+            decoder.next_event(&data).unwrap_err(),
+            DerDecodeError::ElementTooLarge(8)
+        );
+
+        let mut decoder = DerStreamDecoder::default();
+        let data = [0x30, 0x03, 0x04, 0x02, 0x00, 0x00];
+        decoder.next_event(&data).unwrap();
+        assert_eq!(
+            decoder.next_event(&data[2..]).unwrap_err(),
+            DerDecodeError::ChildOverflow
+        );
+    }
+}
@@ -0,0 +1,215 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use thiserror::Error;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DerClass {
+    Universal,
+    Application,
+    ContextSpecific,
+    Private,
+}
+
+impl From<u8> for DerClass {
+    fn from(value: u8) -> Self {
+        match value >> 6 {
+            0 => DerClass::Universal,
+            1 => DerClass::Application,
+            2 => DerClass::ContextSpecific,
+            _ => DerClass::Private,
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DerHeaderParseError {
+    #[error("need {0} bytes more data")]
+    NeedMoreData(usize),
+    #[error("tag number too large")]
+    TagNumberTooLarge,
+    #[error("non-minimal tag number encoding")]
+    NonMinimalTagNumber,
+    #[error("indefinite length is not allowed")]
+    IndefiniteLength,
+    #[error("length field too large")]
+    LengthTooLarge,
+    #[error("non-minimal length encoding")]
+    NonMinimalLength,
+}
+
+/// The identifier and length octets of a DER encoded element
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DerHeader {
+    pub class: DerClass,
+    pub constructed: bool,
+    pub tag: u32,
+    pub content_len: usize,
+    pub header_len: usize,
+}
+
+impl DerHeader {
+    const MAX_TAG_OCTETS: usize = 4;
+    const MAX_LENGTH_OCTETS: usize = 4;
+
+    /// Parse the identifier and length octets
+    ///
+    /// According to https://www.itu.int/rec/T-REC-X.690 section 8.1 and 10.1
+    pub fn parse(data: &[u8]) -> Result<Self, DerHeaderParseError> {
+        if data.len() < 2 {
+            return Err(DerHeaderParseError::NeedMoreData(2 - data.len()));
+        }
+
+        let class = DerClass::from(data[0]);
+        let constructed = data[0] & 0x20 != 0;
+        let mut tag = (data[0] & 0x1f) as u32;
+        let mut offset = 1;
+        if tag == 0x1f {
+            // high tag number form
+            tag = 0;
+            loop {
+                if offset > Self::MAX_TAG_OCTETS {
+                    return Err(DerHeaderParseError::TagNumberTooLarge);
+                }
+                let Some(b) = data.get(offset) else {
+                    return Err(DerHeaderParseError::NeedMoreData(2));
+                };
+                if tag == 0 && *b == 0x80 {
+                    return Err(DerHeaderParseError::NonMinimalTagNumber);
+                }
+                tag = (tag << 7) | (*b & 0x7f) as u32;
+                offset += 1;
+                if *b & 0x80 == 0 {
+                    break;
+                }
+            }
+            if tag < 0x1f {
+                return Err(DerHeaderParseError::NonMinimalTagNumber);
+            }
+        }
+
+        let Some(b) = data.get(offset) else {
+            return Err(DerHeaderParseError::NeedMoreData(1));
+        };
+        offset += 1;
+        let content_len = if *b < 0x80 {
+            *b as usize
+        } else if *b == 0x80 {
+            return Err(DerHeaderParseError::IndefiniteLength);
+        } else {
+            let n = (*b & 0x7f) as usize;
+            if n > Self::MAX_LENGTH_OCTETS {
+                return Err(DerHeaderParseError::LengthTooLarge);
+            }
+            let end = offset + n;
+            if data.len() < end {
+                return Err(DerHeaderParseError::NeedMoreData(end - data.len()));
+            }
+            let len_data = &data[offset..end];
+            if len_data[0] == 0 {
+                return Err(DerHeaderParseError::NonMinimalLength);
+            }
+            offset = end;
+            let len = len_data
+                .iter()
+                .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+            if len < 0x80 {
+                return Err(DerHeaderParseError::NonMinimalLength);
+            }
+            len
+        };
+
+        Ok(DerHeader {
+            class,
+            constructed,
+            tag,
+            content_len,
+            header_len: offset,
+        })
+    }
+
+    /// Get the total length of this element on the wire
+    pub fn encoded_len(&self) -> usize {
+        self.header_len + self.content_len
+    }
+
+    pub fn is_universal(&self, tag: u32) -> bool {
+        self.class == DerClass::Universal && self.tag == tag
+    }
+
+    pub fn is_application(&self, tag: u32) -> bool {
+        self.class == DerClass::Application && self.tag == tag
+    }
+
+    pub fn is_context_specific(&self, tag: u32) -> bool {
+        self.class == DerClass::ContextSpecific && self.tag == tag
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_form() {
+        let h = DerHeader::parse(&[0x30, 0x03, 0x02, 0x01, 0x01]).unwrap();
+        assert_eq!(h.class, DerClass::Universal);
+        assert!(h.constructed);
+        assert_eq!(h.tag, 16);
+        assert_eq!(h.content_len, 3);
+        assert_eq!(h.header_len, 2);
+        assert_eq!(h.encoded_len(), 5);
+    }
+
+    #[test]
+    fn long_form() {
+        let h = DerHeader::parse(&[0x30, 0x82, 0x01, 0x00]).unwrap();
+        assert_eq!(h.content_len, 256);
+        assert_eq!(h.header_len, 4);
+
+        assert_eq!(
+            DerHeader::parse(&[0x30, 0x82, 0x01]).unwrap_err(),
+            DerHeaderParseError::NeedMoreData(1)
+        );
+        assert_eq!(
+            DerHeader::parse(&[0x30, 0x81, 0x10]).unwrap_err(),
+            DerHeaderParseError::NonMinimalLength
+        );
+        assert_eq!(
+            DerHeader::parse(&[0x30, 0x82, 0x00, 0x80]).unwrap_err(),
+            DerHeaderParseError::NonMinimalLength
+        );
+        assert_eq!(
+            DerHeader::parse(&[0x30, 0x80]).unwrap_err(),
+            DerHeaderParseError::IndefiniteLength
+        );
+    }
+
+    #[test]
+    fn high_tag_number() {
+        let h = DerHeader::parse(&[0x9f, 0x81, 0x00, 0x01, 0xff]).unwrap();
+        assert_eq!(h.class, DerClass::ContextSpecific);
+        assert!(!h.constructed);
+        assert_eq!(h.tag, 128);
+        assert_eq!(h.content_len, 1);
+        assert_eq!(h.header_len, 4);
+
+        assert_eq!(
+            DerHeader::parse(&[0x9f, 0x1e, 0x00]).unwrap_err(),
+            DerHeaderParseError::NonMinimalTagNumber
+        );
+    }
+}
@@ -0,0 +1,41 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+mod header;
+pub use header::{DerClass, DerHeader, DerHeaderParseError};
+
+mod decoder;
+pub use decoder::{DerDecodeError, DerDecoderLimits, DerEvent, DerStreamDecoder};
+
+/// Universal tag numbers
+///
+/// See https://www.itu.int/rec/T-REC-X.680
+pub mod tag {
+    pub const BOOLEAN: u32 = 1;
+    pub const INTEGER: u32 = 2;
+    pub const BIT_STRING: u32 = 3;
+    pub const OCTET_STRING: u32 = 4;
+    pub const NULL: u32 = 5;
+    pub const OBJECT_IDENTIFIER: u32 = 6;
+    pub const ENUMERATED: u32 = 10;
+    pub const UTF8_STRING: u32 = 12;
+    pub const SEQUENCE: u32 = 16;
+    pub const SET: u32 = 17;
+    pub const PRINTABLE_STRING: u32 = 19;
+    pub const IA5_STRING: u32 = 22;
+    pub const UTC_TIME: u32 = 23;
+    pub const GENERALIZED_TIME: u32 = 24;
+}
@@ -0,0 +1,211 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use thiserror::Error;
+
+mod observer;
+pub use observer::DnsTcpQueryObserver;
+
+pub mod record_type {
+    pub const A: u16 = 1;
+    pub const NS: u16 = 2;
+    pub const CNAME: u16 = 5;
+    pub const NULL: u16 = 10;
+    pub const MX: u16 = 15;
+    pub const TXT: u16 = 16;
+    pub const AAAA: u16 = 28;
+    pub const SRV: u16 = 33;
+    pub const HTTPS: u16 = 65;
+    pub const ANY: u16 = 255;
+
+    pub fn name(rtype: u16) -> &'static str {
+        match rtype {
+            A => "A",
+            NS => "NS",
+            CNAME => "CNAME",
+            NULL => "NULL",
+            MX => "MX",
+            TXT => "TXT",
+            AAAA => "AAAA",
+            SRV => "SRV",
+            HTTPS => "HTTPS",
+            ANY => "ANY",
+            _ => "Other",
+        }
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum DnsParseError {
+    #[error("message too short")]
+    MessageTooShort,
+    #[error("not a query message")]
+    NotQuery,
+    #[error("no question found")]
+    NoQuestion,
+    #[error("invalid domain name")]
+    InvalidName,
+}
+
+/// The first question in a DNS query message
+#[derive(Debug, PartialEq, Eq)]
+pub struct DnsQuestion {
+    pub id: u16,
+    /// the query name in lower case, without the trailing dot
+    pub name: String,
+    pub qtype: u16,
+}
+
+impl DnsQuestion {
+    const HEADER_LEN: usize = 12;
+    const MAX_NAME_LEN: usize = 255;
+
+    /// Parse the first question in the DNS query message
+    pub fn parse(msg: &[u8]) -> Result<Self, DnsParseError> {
+        if msg.len() < Self::HEADER_LEN {
+            return Err(DnsParseError::MessageTooShort);
+        }
+        if msg[2] & 0b1000_0000 != 0 {
+            return Err(DnsParseError::NotQuery);
+        }
+        let id = u16::from_be_bytes([msg[0], msg[1]]);
+        let qd_count = u16::from_be_bytes([msg[4], msg[5]]);
+        if qd_count == 0 {
+            return Err(DnsParseError::NoQuestion);
+        }
+
+        let mut name = String::with_capacity(64);
+        let mut offset = Self::HEADER_LEN;
+        loop {
+            let Some(len) = msg.get(offset) else {
+                return Err(DnsParseError::MessageTooShort);
+            };
+            let len = *len as usize;
+            offset += 1;
+            if len == 0 {
+                break;
+            }
+            if len > 63 {
+                // compression pointer is not expected in the first question
+                return Err(DnsParseError::InvalidName);
+            }
+            let Some(label) = msg.get(offset..offset + len) else {
+                return Err(DnsParseError::MessageTooShort);
+            };
+            if !name.is_empty() {
+                name.push('.');
+            }
+            if name.len() + len > Self::MAX_NAME_LEN {
+                return Err(DnsParseError::InvalidName);
+            }
+            for c in label {
+                if !c.is_ascii_graphic() || *c == b'.' {
+                    return Err(DnsParseError::InvalidName);
+                }
+                name.push(c.to_ascii_lowercase() as char);
+            }
+            offset += len;
+        }
+
+        let Some(qtype) = msg.get(offset..offset + 2) else {
+            return Err(DnsParseError::MessageTooShort);
+        };
+        Ok(DnsQuestion {
+            id,
+            name,
+            qtype: u16::from_be_bytes([qtype[0], qtype[1]]),
+        })
+    }
+
+    /// Split the name into the subdomain part and the registered domain part
+    ///
+    /// The last `domain_labels` labels will be used as the registered domain.
+    pub fn split_name(&self, domain_labels: usize) -> (&str, &str) {
+        let mut split = self.name.len();
+        for _ in 0..domain_labels.max(1) {
+            match self.name[..split].rfind('.') {
+                Some(p) => split = p,
+                None => return ("", &self.name),
+            }
+        }
+        (&self.name[..split], &self.name[split + 1..])
+    }
+}
+
+/// Calculate the Shannon entropy of the data, in bits per byte
+pub fn shannon_entropy(data: &[u8]) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+
+    let mut counts = [0usize; 256];
+    for b in data {
+        counts[*b as usize] += 1;
+    }
+    let total = data.len() as f64;
+    counts
+        .iter()
+        .filter(|n| **n > 0)
+        .map(|n| {
+            let p = *n as f64 / total;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const TXT_QUERY: &[u8] = &[
+        0x12, 0x34, 0x01, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10, b'm', b'z',
+        b'x', b'w', b'g', b'z', b'l', b'n', b'o', b'j', b'q', b'x', b'a', b'2', b'b', b'q', 0x07,
+        b'E', b'x', b'a', b'm', b'p', b'l', b'e', 0x03, b'c', b'o', b'm', 0x00, 0x00, 0x10, 0x00,
+        0x01,
+    ];
+
+    #[test]
+    fn parse_question() {
+        let question = DnsQuestion::parse(TXT_QUERY).unwrap();
+        assert_eq!(question.id, 0x1234);
+        assert_eq!(question.name, "mzxwgzlnojqxa2bq.example.com");
+        assert_eq!(question.qtype, record_type::TXT);
+
+        let (sub, domain) = question.split_name(2);
+        assert_eq!(sub, "mzxwgzlnojqxa2bq");
+        assert_eq!(domain, "example.com");
+        let (sub, domain) = question.split_name(3);
+        assert_eq!(sub, "");
+        assert_eq!(domain, "mzxwgzlnojqxa2bq.example.com");
+    }
+
+    #[test]
+    fn parse_truncated() {
+        assert_eq!(
+            DnsQuestion::parse(&TXT_QUERY[..20]).unwrap_err(),
+            DnsParseError::MessageTooShort
+        );
+    }
+
+    #[test]
+    fn entropy() {
+        assert_eq!(shannon_entropy(b""), 0.0);
+        assert_eq!(shannon_entropy(b"aaaa"), 0.0);
+        assert_eq!(shannon_entropy(b"abab"), 1.0);
+        assert!(shannon_entropy(b"mzxwgzlnojqxa2bq") > 3.5);
+        assert!(shannon_entropy(b"www") < 1.0);
+    }
+}
@@ -0,0 +1,174 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::DnsQuestion;
+
+enum ObserveState {
+    Buffer,
+    Skip(usize),
+    Failed,
+}
+
+/// Observe the DNS queries in a DNS over TCP client data stream
+///
+/// Only the beginning part of each message will be buffered for parsing the question,
+/// and the remaining data will be skipped.
+pub struct DnsTcpQueryObserver {
+    state: ObserveState,
+    buf: Vec<u8>,
+}
+
+impl Default for DnsTcpQueryObserver {
+    fn default() -> Self {
+        DnsTcpQueryObserver {
+            state: ObserveState::Buffer,
+            buf: Vec::with_capacity(Self::MAX_BUFFER_SIZE),
+        }
+    }
+}
+
+impl DnsTcpQueryObserver {
+    // length(2) + header(12) + question name(max 255) + type(2) + class(2)
+    const MAX_BUFFER_SIZE: usize = 2 + 12 + 255 + 4;
+
+    /// Check if the stream is not a valid DNS over TCP query stream
+    pub fn is_failed(&self) -> bool {
+        matches!(self.state, ObserveState::Failed)
+    }
+
+    /// Feed the data of the stream
+    ///
+    /// The callback will be called for each query found. If the callback returns false,
+    /// the feed will stop and return the offset of the data where that query message starts,
+    /// if the message starts in this data.
+    pub fn feed<F>(&mut self, mut data: &[u8], mut f: F) -> Result<(), Option<usize>>
+    where
+        F: FnMut(&DnsQuestion) -> bool,
+    {
+        let total_len = data.len();
+        while !data.is_empty() {
+            match &mut self.state {
+                ObserveState::Buffer => {
+                    let message_start = if self.buf.is_empty() {
+                        Some(total_len - data.len())
+                    } else {
+                        None
+                    };
+
+                    if self.buf.len() < 2 {
+                        let to_copy = (2 - self.buf.len()).min(data.len());
+                        self.buf.extend_from_slice(&data[..to_copy]);
+                        data = &data[to_copy..];
+                        if self.buf.len() < 2 {
+                            break;
+                        }
+                    }
+
+                    let total = u16::from_be_bytes([self.buf[0], self.buf[1]]) as usize + 2;
+                    let buffer_size = total.min(Self::MAX_BUFFER_SIZE);
+                    let to_copy = (buffer_size - self.buf.len()).min(data.len());
+                    self.buf.extend_from_slice(&data[..to_copy]);
+                    data = &data[to_copy..];
+
+                    match DnsQuestion::parse(&self.buf[2..]) {
+                        Ok(question) => {
+                            if !f(&question) {
+                                return Err(message_start);
+                            }
+                            let left = total - self.buf.len();
+                            self.buf.clear();
+                            if left > 0 {
+                                self.state = ObserveState::Skip(left);
+                            }
+                        }
+                        Err(_) if self.buf.len() < buffer_size => {}
+                        Err(_) => self.state = ObserveState::Failed,
+                    }
+                }
+                ObserveState::Skip(left) => {
+                    let to_skip = (*left).min(data.len());
+                    *left -= to_skip;
+                    data = &data[to_skip..];
+                    if *left == 0 {
+                        self.state = ObserveState::Buffer;
+                    }
+                }
+                ObserveState::Failed => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::dns::tests::TXT_QUERY;
+
+    fn tcp_message(msg: &[u8]) -> Vec<u8> {
+        let mut data = (msg.len() as u16).to_be_bytes().to_vec();
+        data.extend_from_slice(msg);
+        data
+    }
+
+    #[test]
+    fn multiple_queries() {
+        let mut data = tcp_message(TXT_QUERY);
+        // with additional EDNS data
+        let mut msg = TXT_QUERY.to_vec();
+        msg.extend_from_slice(&[0u8; 300]);
+        data.extend_from_slice(&tcp_message(&msg));
+        data.extend_from_slice(&tcp_message(TXT_QUERY));
+
+        for chunk_size in [1, 3, 7, 64, data.len()] {
+            let mut observer = DnsTcpQueryObserver::default();
+            let mut names = Vec::new();
+            for chunk in data.chunks(chunk_size) {
+                observer
+                    .feed(chunk, |q| {
+                        names.push(q.name.clone());
+                        true
+                    })
+                    .unwrap();
+            }
+            assert!(!observer.is_failed());
+            assert_eq!(names.len(), 3);
+        }
+    }
+
+    #[test]
+    fn block() {
+        let mut data = tcp_message(TXT_QUERY);
+        data.extend_from_slice(&tcp_message(TXT_QUERY));
+
+        let mut observer = DnsTcpQueryObserver::default();
+        let mut count = 0;
+        let r = observer.feed(&data, |_| {
+            count += 1;
+            count < 2
+        });
+        assert_eq!(r, Err(Some(TXT_QUERY.len() + 2)));
+    }
+
+    #[test]
+    fn invalid() {
+        let mut observer = DnsTcpQueryObserver::default();
+        observer
+            .feed(&[0x00, 0x04, 0x00, 0x00, 0x00, 0x00], |_| true)
+            .unwrap();
+        assert!(observer.is_failed());
+    }
+}
@@ -0,0 +1,351 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::Utf8Error;
+
+use thiserror::Error;
+
+mod observer;
+pub use observer::KafkaRequestObserver;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum KafkaParseError {
+    #[error("need {0} bytes more data")]
+    NeedMoreData(usize),
+    #[error("invalid request size {0}")]
+    InvalidRequestSize(i32),
+    #[error("invalid api key {0}")]
+    InvalidApiKey(i16),
+    #[error("invalid api version {0}")]
+    InvalidApiVersion(i16),
+    #[error("invalid string length {0}")]
+    InvalidStringLength(i16),
+    #[error("invalid array length {0}")]
+    InvalidArrayLength(i32),
+    #[error("invalid string: {0}")]
+    InvalidString(Utf8Error),
+}
+
+pub mod api_key {
+    pub const PRODUCE: i16 = 0;
+    pub const FETCH: i16 = 1;
+    pub const METADATA: i16 = 3;
+    pub const SASL_HANDSHAKE: i16 = 17;
+    pub const API_VERSIONS: i16 = 18;
+    pub const SASL_AUTHENTICATE: i16 = 36;
+
+    /// the max api key value that we know of
+    pub const MAX: i16 = 74;
+
+    pub fn name(key: i16) -> &'static str {
+        match key {
+            PRODUCE => "Produce",
+            FETCH => "Fetch",
+            2 => "ListOffsets",
+            METADATA => "Metadata",
+            8 => "OffsetCommit",
+            9 => "OffsetFetch",
+            10 => "FindCoordinator",
+            11 => "JoinGroup",
+            12 => "Heartbeat",
+            13 => "LeaveGroup",
+            14 => "SyncGroup",
+            SASL_HANDSHAKE => "SaslHandshake",
+            API_VERSIONS => "ApiVersions",
+            19 => "CreateTopics",
+            20 => "DeleteTopics",
+            22 => "InitProducerId",
+            SASL_AUTHENTICATE => "SaslAuthenticate",
+            _ => "Other",
+        }
+    }
+}
+
+/// The header of a Kafka request, including the size field
+///
+/// See https://kafka.apache.org/protocol.html#protocol_messages
+#[derive(Debug, PartialEq, Eq)]
+pub struct KafkaRequestHeader<'a> {
+    /// the size of the request, not including the size field
+    pub size: usize,
+    pub api_key: i16,
+    pub api_version: i16,
+    pub correlation_id: i32,
+    pub client_id: Option<&'a str>,
+    /// the length of the encoded header, including the size field,
+    /// but not including the tagged fields for flexible versions
+    pub encoded_len: usize,
+}
+
+impl<'a> KafkaRequestHeader<'a> {
+    // size(4) + api key(2) + api version(2) + correlation id(4) + client id length(2)
+    pub const MIN_SIZE: usize = 14;
+
+    pub fn parse(data: &'a [u8], max_size: usize) -> Result<Self, KafkaParseError> {
+        if data.len() < Self::MIN_SIZE {
+            return Err(KafkaParseError::NeedMoreData(Self::MIN_SIZE - data.len()));
+        }
+
+        let size = i32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+        if size < (Self::MIN_SIZE - 4) as i32 || size as usize > max_size {
+            return Err(KafkaParseError::InvalidRequestSize(size));
+        }
+        let api_key = i16::from_be_bytes([data[4], data[5]]);
+        if !(0..=api_key::MAX).contains(&api_key) {
+            return Err(KafkaParseError::InvalidApiKey(api_key));
+        }
+        let api_version = i16::from_be_bytes([data[6], data[7]]);
+        if !(0..=32).contains(&api_version) {
+            return Err(KafkaParseError::InvalidApiVersion(api_version));
+        }
+        let correlation_id = i32::from_be_bytes([data[8], data[9], data[10], data[11]]);
+
+        let mut offset = 12;
+        let client_id = read_nullable_string(data, &mut offset)?;
+        if offset > size as usize + 4 {
+            return Err(KafkaParseError::InvalidRequestSize(size));
+        }
+
+        Ok(KafkaRequestHeader {
+            size: size as usize,
+            api_key,
+            api_version,
+            correlation_id,
+            client_id,
+            encoded_len: offset,
+        })
+    }
+
+    /// Check if the request is using flexible versions, which use the compact encodings
+    pub fn is_flexible(&self) -> bool {
+        match self.api_key {
+            api_key::PRODUCE => self.api_version >= 9,
+            api_key::FETCH => self.api_version >= 12,
+            api_key::METADATA => self.api_version >= 9,
+            api_key::API_VERSIONS => self.api_version >= 3,
+            _ => false,
+        }
+    }
+
+    /// Get the topic names in the request body
+    ///
+    /// Only Produce, Fetch and Metadata requests of non-flexible versions are supported.
+    /// The topics that can be found in the data will be returned.
+    pub fn topics(&self, data: &'a [u8]) -> Vec<&'a str> {
+        let mut topics = Vec::new();
+        if self.is_flexible() {
+            return topics;
+        }
+        let data = &data[..data.len().min(self.size + 4)];
+        let mut offset = self.encoded_len;
+        let _ = match self.api_key {
+            api_key::PRODUCE => self.parse_produce_topics(data, &mut offset, &mut topics),
+            api_key::FETCH => self.parse_fetch_topics(data, &mut offset, &mut topics),
+            api_key::METADATA => parse_metadata_topics(data, &mut offset, &mut topics),
+            _ => Ok(()),
+        };
+        topics
+    }
+
+    fn parse_produce_topics(
+        &self,
+        data: &'a [u8],
+        offset: &mut usize,
+        topics: &mut Vec<&'a str>,
+    ) -> Result<(), KafkaParseError> {
+        if self.api_version >= 3 {
+            // transactional id
+            read_nullable_string(data, offset)?;
+        }
+        // acks(2) + timeout(4)
+        skip(data, offset, 6)?;
+        let topic_count = read_array_len(data, offset)?;
+        for _ in 0..topic_count {
+            if let Some(name) = read_nullable_string(data, offset)? {
+                topics.push(name);
+            }
+            let partition_count = read_array_len(data, offset)?;
+            for _ in 0..partition_count {
+                // partition index
+                skip(data, offset, 4)?;
+                // records
+                let len = read_i32(data, offset)?;
+                if len > 0 {
+                    skip(data, offset, len as usize)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_fetch_topics(
+        &self,
+        data: &'a [u8],
+        offset: &mut usize,
+        topics: &mut Vec<&'a str>,
+    ) -> Result<(), KafkaParseError> {
+        // replica id(4) + max wait ms(4) + min bytes(4)
+        let mut fixed_len = 12;
+        if self.api_version >= 3 {
+            // max bytes
+            fixed_len += 4;
+        }
+        if self.api_version >= 4 {
+            // isolation level
+            fixed_len += 1;
+        }
+        if self.api_version >= 7 {
+            // session id + session epoch
+            fixed_len += 8;
+        }
+        skip(data, offset, fixed_len)?;
+
+        // partition(4) + fetch offset(8) + partition max bytes(4)
+        let mut partition_len = 16;
+        if self.api_version >= 9 {
+            // current leader epoch
+            partition_len += 4;
+        }
+        if self.api_version >= 5 {
+            // log start offset
+            partition_len += 8;
+        }
+
+        let topic_count = read_array_len(data, offset)?;
+        for _ in 0..topic_count {
+            if let Some(name) = read_nullable_string(data, offset)? {
+                topics.push(name);
+            }
+            let partition_count = read_array_len(data, offset)?;
+            skip(data, offset, partition_count * partition_len)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_metadata_topics<'a>(
+    data: &'a [u8],
+    offset: &mut usize,
+    topics: &mut Vec<&'a str>,
+) -> Result<(), KafkaParseError> {
+    let topic_count = read_array_len(data, offset)?;
+    for _ in 0..topic_count {
+        if let Some(name) = read_nullable_string(data, offset)? {
+            topics.push(name);
+        }
+    }
+    Ok(())
+}
+
+fn skip(data: &[u8], offset: &mut usize, len: usize) -> Result<(), KafkaParseError> {
+    let end = *offset + len;
+    if data.len() < end {
+        return Err(KafkaParseError::NeedMoreData(end - data.len()));
+    }
+    *offset = end;
+    Ok(())
+}
+
+fn read_i32(data: &[u8], offset: &mut usize) -> Result<i32, KafkaParseError> {
+    let start = *offset;
+    skip(data, offset, 4)?;
+    Ok(i32::from_be_bytes([
+        data[start],
+        data[start + 1],
+        data[start + 2],
+        data[start + 3],
+    ]))
+}
+
+fn read_array_len(data: &[u8], offset: &mut usize) -> Result<usize, KafkaParseError> {
+    let len = read_i32(data, offset)?;
+    if len < -1 {
+        return Err(KafkaParseError::InvalidArrayLength(len));
+    }
+    Ok(len.max(0) as usize)
+}
+
+fn read_nullable_string<'a>(
+    data: &'a [u8],
+    offset: &mut usize,
+) -> Result<Option<&'a str>, KafkaParseError> {
+    let start = *offset;
+    skip(data, offset, 2)?;
+    let len = i16::from_be_bytes([data[start], data[start + 1]]);
+    match len {
+        -1 => Ok(None),
+        0.. => {
+            let start = *offset;
+            skip(data, offset, len as usize)?;
+            let s = std::str::from_utf8(&data[start..*offset])
+                .map_err(KafkaParseError::InvalidString)?;
+            Ok(Some(s))
+        }
+        _ => Err(KafkaParseError::InvalidStringLength(len)),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    // Metadata v1 request for topic "test", client id "cli"
+    pub(crate) const METADATA_REQUEST: &[u8] = &[
+        0x00, 0x00, 0x00, 0x17, 0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x05, 0x00, 0x03, b'c',
+        b'l', b'i', 0x00, 0x00, 0x00, 0x01, 0x00, 0x04, b't', b'e', b's', b't',
+    ];
+
+    // Produce v2 request for topic "abc" with 3 bytes records
+    pub(crate) const PRODUCE_REQUEST: &[u8] = &[
+        0x00, 0x00, 0x00, 0x2b, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x06, 0x00, 0x03, b'c',
+        b'l', b'i', 0x00, 0x01, 0x00, 0x00, 0x03, 0xe8, 0x00, 0x00, 0x00, 0x01, 0x00, 0x03, b'a',
+        b'b', b'c', 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x01,
+        0x02, 0x03,
+    ];
+
+    #[test]
+    fn metadata() {
+        let h = KafkaRequestHeader::parse(METADATA_REQUEST, 1024).unwrap();
+        assert_eq!(h.size, METADATA_REQUEST.len() - 4);
+        assert_eq!(h.api_key, api_key::METADATA);
+        assert_eq!(h.api_version, 1);
+        assert_eq!(h.correlation_id, 5);
+        assert_eq!(h.client_id, Some("cli"));
+        assert_eq!(h.topics(METADATA_REQUEST), vec!["test"]);
+    }
+
+    #[test]
+    fn produce() {
+        let h = KafkaRequestHeader::parse(PRODUCE_REQUEST, 1024).unwrap();
+        assert_eq!(h.size, PRODUCE_REQUEST.len() - 4);
+        assert_eq!(h.api_key, api_key::PRODUCE);
+        assert_eq!(h.topics(PRODUCE_REQUEST), vec!["abc"]);
+        assert_eq!(h.topics(&PRODUCE_REQUEST[..30]), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            KafkaRequestHeader::parse(METADATA_REQUEST, 16).unwrap_err(),
+            KafkaParseError::InvalidRequestSize(0x17)
+        );
+        assert_eq!(
+            KafkaRequestHeader::parse(&METADATA_REQUEST[..10], 1024).unwrap_err(),
+            KafkaParseError::NeedMoreData(4)
+        );
+        assert!(KafkaRequestHeader::parse(b"GET / HTTP/1.1\r\n", 1024).is_err());
+    }
+}
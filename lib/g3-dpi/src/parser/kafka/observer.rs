@@ -0,0 +1,192 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{KafkaParseError, KafkaRequestHeader};
+
+enum ObserveState {
+    Buffer,
+    Skip(usize),
+    Failed,
+}
+
+/// Observe the Kafka requests in a client data stream
+///
+/// Only the beginning part of each request will be buffered for parsing,
+/// and the remaining data will be skipped.
+pub struct KafkaRequestObserver {
+    max_request_size: usize,
+    max_buffer_size: usize,
+    state: ObserveState,
+    buf: Vec<u8>,
+}
+
+impl KafkaRequestObserver {
+    pub fn new(max_request_size: usize, max_buffer_size: usize) -> Self {
+        KafkaRequestObserver {
+            max_request_size,
+            max_buffer_size: max_buffer_size.max(KafkaRequestHeader::MIN_SIZE),
+            state: ObserveState::Buffer,
+            buf: Vec::with_capacity(256),
+        }
+    }
+
+    /// Check if the stream is not a valid Kafka request stream
+    pub fn is_failed(&self) -> bool {
+        matches!(self.state, ObserveState::Failed)
+    }
+
+    /// Feed the data of the stream
+    ///
+    /// The callback will be called for each request found, with the header and topic names in
+    /// the buffered data. If the callback returns false, the feed will stop and return the
+    /// offset of the data where that request starts, if the request starts in this data.
+    pub fn feed<F>(&mut self, mut data: &[u8], mut f: F) -> Result<(), Option<usize>>
+    where
+        F: FnMut(&KafkaRequestHeader<'_>, &[&str]) -> bool,
+    {
+        let total_len = data.len();
+        while !data.is_empty() {
+            match &mut self.state {
+                ObserveState::Buffer => {
+                    let request_start = if self.buf.is_empty() {
+                        Some(total_len - data.len())
+                    } else {
+                        None
+                    };
+
+                    let mut to_copy = (self.max_buffer_size - self.buf.len()).min(data.len());
+                    if self.buf.len() >= 4 {
+                        let size = u32::from_be_bytes([
+                            self.buf[0],
+                            self.buf[1],
+                            self.buf[2],
+                            self.buf[3],
+                        ]) as usize;
+                        to_copy = to_copy.min(size + 4 - self.buf.len());
+                    } else if self.buf.len() + to_copy >= 4 {
+                        let mut size_data = [0u8; 4];
+                        let n = self.buf.len();
+                        size_data[..n].copy_from_slice(&self.buf);
+                        size_data[n..].copy_from_slice(&data[..4 - n]);
+                        let size = u32::from_be_bytes(size_data) as usize;
+                        to_copy = to_copy.min(size.saturating_add(4) - n);
+                    }
+                    self.buf.extend_from_slice(&data[..to_copy]);
+                    data = &data[to_copy..];
+
+                    match KafkaRequestHeader::parse(&self.buf, self.max_request_size) {
+                        Ok(header) => {
+                            let total = header.size + 4;
+                            if self.buf.len() < total && self.buf.len() < self.max_buffer_size {
+                                // buffer more data to get the topics
+                                continue;
+                            }
+                            let topics = header.topics(&self.buf);
+                            if !f(&header, &topics) {
+                                return Err(request_start);
+                            }
+                            let left = total - self.buf.len();
+                            self.buf.clear();
+                            if left > 0 {
+                                self.state = ObserveState::Skip(left);
+                            }
+                        }
+                        Err(KafkaParseError::NeedMoreData(_)) => {
+                            if self.buf.len() >= self.max_buffer_size {
+                                self.state = ObserveState::Failed;
+                            }
+                        }
+                        Err(_) => self.state = ObserveState::Failed,
+                    }
+                }
+                ObserveState::Skip(left) => {
+                    let to_skip = (*left).min(data.len());
+                    *left -= to_skip;
+                    data = &data[to_skip..];
+                    if *left == 0 {
+                        self.state = ObserveState::Buffer;
+                    }
+                }
+                ObserveState::Failed => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::kafka::api_key;
+    use crate::parser::kafka::tests::{METADATA_REQUEST, PRODUCE_REQUEST};
+
+    #[test]
+    fn multiple_requests() {
+        let mut data = METADATA_REQUEST.to_vec();
+        data.extend_from_slice(PRODUCE_REQUEST);
+        data.extend_from_slice(METADATA_REQUEST);
+
+        for chunk_size in [1, 3, 5, 16, data.len()] {
+            let mut observer = KafkaRequestObserver::new(1024, 1024);
+            let mut requests = Vec::new();
+            for chunk in data.chunks(chunk_size) {
+                observer
+                    .feed(chunk, |h, topics| {
+                        requests.push((h.api_key, topics.join(",")));
+                        true
+                    })
+                    .unwrap();
+            }
+            assert!(!observer.is_failed());
+            assert_eq!(
+                requests,
+                vec![
+                    (api_key::METADATA, "test".to_string()),
+                    (api_key::PRODUCE, "abc".to_string()),
+                    (api_key::METADATA, "test".to_string()),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn small_buffer() {
+        let mut observer = KafkaRequestObserver::new(1024, 20);
+        let mut data = PRODUCE_REQUEST.to_vec();
+        data.extend_from_slice(METADATA_REQUEST);
+        let mut requests = Vec::new();
+        observer
+            .feed(&data, |h, topics| {
+                requests.push((h.api_key, topics.len()));
+                true
+            })
+            .unwrap();
+        assert_eq!(
+            requests,
+            vec![(api_key::PRODUCE, 0), (api_key::METADATA, 0)]
+        );
+    }
+
+    #[test]
+    fn block() {
+        let mut data = METADATA_REQUEST.to_vec();
+        data.extend_from_slice(PRODUCE_REQUEST);
+
+        let mut observer = KafkaRequestObserver::new(1024, 1024);
+        let r = observer.feed(&data, |h, _| h.api_key != api_key::PRODUCE);
+        assert_eq!(r, Err(Some(METADATA_REQUEST.len())));
+    }
+}
@@ -14,7 +14,14 @@
  * limitations under the License.
  */
 
+pub mod der;
+pub mod dns;
+pub mod kafka;
+pub mod mysql;
+pub mod postgres;
+pub mod thrift;
 pub mod tls;
+pub mod websocket;
 
 #[cfg(feature = "quic")]
 pub mod quic;
@@ -0,0 +1,332 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::Utf8Error;
+
+use thiserror::Error;
+
+pub mod capability {
+    pub const CLIENT_CONNECT_WITH_DB: u32 = 0x0000_0008;
+    pub const CLIENT_PROTOCOL_41: u32 = 0x0000_0200;
+    pub const CLIENT_SSL: u32 = 0x0000_0800;
+    pub const CLIENT_SECURE_CONNECTION: u32 = 0x0000_8000;
+    pub const CLIENT_PLUGIN_AUTH: u32 = 0x0008_0000;
+    pub const CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA: u32 = 0x0020_0000;
+}
+
+/// The max payload size of the handshake packets that we will accept
+pub const MAX_HANDSHAKE_PAYLOAD_SIZE: usize = 4096;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum MysqlParseError {
+    #[error("need {0} bytes more data")]
+    NeedMoreData(usize),
+    #[error("invalid payload length {0}")]
+    InvalidPayloadLength(usize),
+    #[error("invalid sequence id {0}")]
+    InvalidSequenceId(u8),
+    #[error("unsupported protocol version {0}")]
+    UnsupportedProtocolVersion(u8),
+    #[error("unsupported client capabilities {0:#x}")]
+    UnsupportedCapabilities(u32),
+    #[error("invalid packet payload")]
+    InvalidPayload,
+    #[error("invalid string: {0}")]
+    InvalidString(Utf8Error),
+}
+
+/// The header of a MySQL packet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MysqlPacketHeader {
+    pub payload_len: usize,
+    pub sequence_id: u8,
+}
+
+impl MysqlPacketHeader {
+    pub const SIZE: usize = 4;
+
+    pub fn parse(data: &[u8]) -> Result<Self, MysqlParseError> {
+        if data.len() < Self::SIZE {
+            return Err(MysqlParseError::NeedMoreData(Self::SIZE - data.len()));
+        }
+        let payload_len = u32::from_le_bytes([data[0], data[1], data[2], 0]) as usize;
+        Ok(MysqlPacketHeader {
+            payload_len,
+            sequence_id: data[3],
+        })
+    }
+
+    /// Get the payload of a complete handshake packet, the size limit will be checked
+    fn handshake_payload(data: &[u8], sequence_id: u8) -> Result<&[u8], MysqlParseError> {
+        let header = MysqlPacketHeader::parse(data)?;
+        if header.sequence_id != sequence_id {
+            return Err(MysqlParseError::InvalidSequenceId(header.sequence_id));
+        }
+        if header.payload_len == 0 || header.payload_len > MAX_HANDSHAKE_PAYLOAD_SIZE {
+            return Err(MysqlParseError::InvalidPayloadLength(header.payload_len));
+        }
+        let total = Self::SIZE + header.payload_len;
+        if data.len() < total {
+            return Err(MysqlParseError::NeedMoreData(total - data.len()));
+        }
+        Ok(&data[Self::SIZE..total])
+    }
+}
+
+/// The Protocol::HandshakeV10 packet sent by the server
+///
+/// See https://dev.mysql.com/doc/dev/mysql-server/latest/page_protocol_connection_phase_packets_protocol_handshake_v10.html
+#[derive(Debug, PartialEq, Eq)]
+pub struct MysqlServerHandshake<'a> {
+    pub server_version: &'a str,
+    pub connection_id: u32,
+    pub capabilities: u32,
+    /// the encoded length of the packet, including the packet header
+    pub encoded_len: usize,
+}
+
+impl<'a> MysqlServerHandshake<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, MysqlParseError> {
+        let payload = MysqlPacketHeader::handshake_payload(data, 0)?;
+        if payload[0] != 10 {
+            return Err(MysqlParseError::UnsupportedProtocolVersion(payload[0]));
+        }
+
+        let mut left = &payload[1..];
+        let server_version = read_cstr(&mut left)?;
+        // connection id(4) + auth plugin data part 1(8) + filler(1) + capability flags lower(2)
+        if left.len() < 15 {
+            return Err(MysqlParseError::InvalidPayload);
+        }
+        let connection_id = u32::from_le_bytes([left[0], left[1], left[2], left[3]]);
+        if left[12] != 0 {
+            return Err(MysqlParseError::InvalidPayload);
+        }
+        let mut capabilities = u16::from_le_bytes([left[13], left[14]]) as u32;
+        // character set(1) + status flags(2) + capability flags upper(2)
+        if left.len() >= 20 {
+            let upper = u16::from_le_bytes([left[18], left[19]]) as u32;
+            capabilities |= upper << 16;
+        }
+
+        Ok(MysqlServerHandshake {
+            server_version,
+            connection_id,
+            capabilities,
+            encoded_len: MysqlPacketHeader::SIZE + payload.len(),
+        })
+    }
+
+    pub fn support_ssl(&self) -> bool {
+        self.capabilities & capability::CLIENT_SSL != 0
+    }
+}
+
+/// The SSLRequest or Protocol::HandshakeResponse41 packet sent by the client
+///
+/// See https://dev.mysql.com/doc/dev/mysql-server/latest/page_protocol_connection_phase_packets_protocol_handshake_response.html
+#[derive(Debug, PartialEq, Eq)]
+pub struct MysqlClientHandshake<'a> {
+    pub sequence_id: u8,
+    pub capabilities: u32,
+    /// set if this is a SSLRequest packet, the real response will be sent after the TLS handshake
+    pub ssl_request: bool,
+    pub user: Option<&'a str>,
+    pub database: Option<&'a str>,
+    /// the encoded length of the packet, including the packet header
+    pub encoded_len: usize,
+}
+
+impl<'a> MysqlClientHandshake<'a> {
+    // capability flags(4) + max packet size(4) + character set(1) + filler(23)
+    const FIXED_SIZE: usize = 32;
+
+    pub fn parse(data: &'a [u8]) -> Result<Self, MysqlParseError> {
+        let payload = MysqlPacketHeader::handshake_payload(data, 1)?;
+        if payload.len() < Self::FIXED_SIZE {
+            return Err(MysqlParseError::InvalidPayloadLength(payload.len()));
+        }
+        let capabilities = u32::from_le_bytes([payload[0], payload[1], payload[2], payload[3]]);
+        if capabilities & capability::CLIENT_PROTOCOL_41 == 0 {
+            return Err(MysqlParseError::UnsupportedCapabilities(capabilities));
+        }
+        let encoded_len = MysqlPacketHeader::SIZE + payload.len();
+
+        if payload.len() == Self::FIXED_SIZE {
+            if capabilities & capability::CLIENT_SSL == 0 {
+                return Err(MysqlParseError::InvalidPayload);
+            }
+            return Ok(MysqlClientHandshake {
+                sequence_id: 1,
+                capabilities,
+                ssl_request: true,
+                user: None,
+                database: None,
+                encoded_len,
+            });
+        }
+
+        let mut left = &payload[Self::FIXED_SIZE..];
+        let user = read_cstr(&mut left)?;
+        if capabilities & capability::CLIENT_PLUGIN_AUTH_LENENC_CLIENT_DATA != 0 {
+            let len = read_lenenc_int(&mut left)?;
+            skip(&mut left, len)?;
+        } else if capabilities & capability::CLIENT_SECURE_CONNECTION != 0 {
+            let Some((len, next)) = left.split_first() else {
+                return Err(MysqlParseError::InvalidPayload);
+            };
+            left = next;
+            skip(&mut left, *len as usize)?;
+        } else {
+            read_cstr(&mut left)?;
+        }
+        let database = if capabilities & capability::CLIENT_CONNECT_WITH_DB != 0 {
+            Some(read_cstr(&mut left)?).filter(|s| !s.is_empty())
+        } else {
+            None
+        };
+
+        Ok(MysqlClientHandshake {
+            sequence_id: 1,
+            capabilities,
+            ssl_request: false,
+            user: Some(user),
+            database,
+            encoded_len,
+        })
+    }
+}
+
+fn read_cstr<'a>(data: &mut &'a [u8]) -> Result<&'a str, MysqlParseError> {
+    let Some(p) = memchr::memchr(b'\0', data) else {
+        return Err(MysqlParseError::InvalidPayload);
+    };
+    let s = std::str::from_utf8(&data[..p]).map_err(MysqlParseError::InvalidString)?;
+    *data = &data[p + 1..];
+    Ok(s)
+}
+
+fn read_lenenc_int(data: &mut &[u8]) -> Result<usize, MysqlParseError> {
+    let Some((first, left)) = data.split_first() else {
+        return Err(MysqlParseError::InvalidPayload);
+    };
+    let int_len = match *first {
+        0..=0xFA => {
+            *data = left;
+            return Ok(*first as usize);
+        }
+        0xFC => 2,
+        0xFD => 3,
+        0xFE => 8,
+        _ => return Err(MysqlParseError::InvalidPayload),
+    };
+    if left.len() < int_len {
+        return Err(MysqlParseError::InvalidPayload);
+    }
+    let mut bytes = [0u8; 8];
+    bytes[..int_len].copy_from_slice(&left[..int_len]);
+    *data = &left[int_len..];
+    Ok(u64::from_le_bytes(bytes) as usize)
+}
+
+fn skip(data: &mut &[u8], len: usize) -> Result<(), MysqlParseError> {
+    if data.len() < len {
+        return Err(MysqlParseError::InvalidPayload);
+    }
+    *data = &data[len..];
+    Ok(())
+}
+
+/// Encode an ERR_Packet
+pub fn encode_err_packet(sequence_id: u8, code: u16, sqlstate: &[u8; 5], message: &str) -> Vec<u8> {
+    let payload_len = 1 + 2 + 1 + 5 + message.len();
+    let mut buf = Vec::with_capacity(MysqlPacketHeader::SIZE + payload_len);
+    buf.extend_from_slice(&(payload_len as u32).to_le_bytes()[..3]);
+    buf.push(sequence_id);
+    buf.push(0xFF);
+    buf.extend_from_slice(&code.to_le_bytes());
+    buf.push(b'#');
+    buf.extend_from_slice(sqlstate);
+    buf.extend_from_slice(message.as_bytes());
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SERVER_HANDSHAKE: &[u8] = &[
+        0x4a, 0x00, 0x00, 0x00, 0x0a, b'8', b'.', b'0', b'.', b'3', b'6', 0x00, 0x0b, 0x00, 0x00,
+        0x00, 0x3d, 0x1a, 0x3f, 0x51, 0x0c, 0x5e, 0x2b, 0x18, 0x00, 0xff, 0xff, 0xff, 0x02, 0x00,
+        0xff, 0xdf, 0x15, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x2d, 0x5c,
+        0x3e, 0x27, 0x3b, 0x0b, 0x41, 0x67, 0x62, 0x40, 0x1f, 0x2a, 0x00, b'c', b'a', b'c', b'h',
+        b'i', b'n', b'g', b'_', b's', b'h', b'a', b'2', b'_', b'p', b'a', b's', b's', b'w', b'o',
+        b'r', b'd', 0x00,
+    ];
+
+    const SSL_REQUEST: &[u8] = &[
+        0x20, 0x00, 0x00, 0x01, 0x0d, 0xaa, 0xbf, 0x01, 0x00, 0x00, 0x00, 0x01, 0xff, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    const HANDSHAKE_RESPONSE: &[u8] = &[
+        0x31, 0x00, 0x00, 0x01, 0x0d, 0xa2, 0x0a, 0x00, 0x00, 0x00, 0x00, 0x01, 0xff, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, b'r', b'o', b'o', b't', 0x00, 0x04, 0x01, 0x02, 0x03,
+        0x04, b'o', b'r', b'd', b'e', b'r', b's', 0x00,
+    ];
+
+    #[test]
+    fn server_handshake() {
+        let handshake = MysqlServerHandshake::parse(SERVER_HANDSHAKE).unwrap();
+        assert_eq!(handshake.server_version, "8.0.36");
+        assert_eq!(handshake.connection_id, 11);
+        assert!(handshake.support_ssl());
+        assert_eq!(handshake.encoded_len, SERVER_HANDSHAKE.len());
+
+        assert_eq!(
+            MysqlServerHandshake::parse(&SERVER_HANDSHAKE[..10]).unwrap_err(),
+            MysqlParseError::NeedMoreData(SERVER_HANDSHAKE.len() - 10)
+        );
+    }
+
+    #[test]
+    fn ssl_request() {
+        let handshake = MysqlClientHandshake::parse(SSL_REQUEST).unwrap();
+        assert!(handshake.ssl_request);
+        assert!(handshake.user.is_none());
+        assert_eq!(handshake.encoded_len, SSL_REQUEST.len());
+    }
+
+    #[test]
+    fn handshake_response() {
+        let handshake = MysqlClientHandshake::parse(HANDSHAKE_RESPONSE).unwrap();
+        assert!(!handshake.ssl_request);
+        assert_eq!(handshake.user, Some("root"));
+        assert_eq!(handshake.database, Some("orders"));
+        assert_eq!(handshake.encoded_len, HANDSHAKE_RESPONSE.len());
+    }
+
+    #[test]
+    fn err_packet() {
+        let buf = encode_err_packet(2, 3159, b"HY000", "denied");
+        assert_eq!(buf.len(), 4 + 9 + 6);
+        assert_eq!(buf[0] as usize, 9 + 6);
+        assert_eq!(buf[3], 2);
+        assert_eq!(buf[4], 0xFF);
+    }
+}
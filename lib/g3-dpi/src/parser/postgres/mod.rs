@@ -0,0 +1,251 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::Utf8Error;
+
+use thiserror::Error;
+
+pub const SSL_REQUEST_CODE: u32 = 80877103;
+pub const GSSENC_REQUEST_CODE: u32 = 80877104;
+pub const CANCEL_REQUEST_CODE: u32 = 80877102;
+
+/// The max length of the startup packet that the server will accept
+pub const MAX_STARTUP_PACKET_LENGTH: usize = 10000;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PostgresParseError {
+    #[error("need {0} bytes more data")]
+    NeedMoreData(usize),
+    #[error("invalid message length {0}")]
+    InvalidMessageLength(u32),
+    #[error("unsupported protocol version {0:#x}")]
+    UnsupportedProtocolVersion(u32),
+    #[error("invalid startup parameter")]
+    InvalidParameter,
+    #[error("invalid string: {0}")]
+    InvalidString(Utf8Error),
+    #[error("no user parameter found")]
+    NoUser,
+}
+
+/// The StartupMessage sent by the client
+///
+/// See https://www.postgresql.org/docs/current/protocol-message-formats.html
+#[derive(Debug, PartialEq, Eq)]
+pub struct PostgresStartupMessage<'a> {
+    pub protocol_version: u32,
+    pub user: &'a str,
+    pub database: Option<&'a str>,
+    pub application_name: Option<&'a str>,
+}
+
+impl PostgresStartupMessage<'_> {
+    pub fn protocol_major_version(&self) -> u16 {
+        (self.protocol_version >> 16) as u16
+    }
+
+    pub fn protocol_minor_version(&self) -> u16 {
+        (self.protocol_version & 0xFFFF) as u16
+    }
+
+    /// The database name will be the same as the user name if not set
+    pub fn database(&self) -> &str {
+        self.database.unwrap_or(self.user)
+    }
+}
+
+/// The first message sent by the client, which has no message type byte
+#[derive(Debug, PartialEq, Eq)]
+pub enum PostgresInitialMessage<'a> {
+    SslRequest,
+    GssEncRequest,
+    CancelRequest,
+    Startup(PostgresStartupMessage<'a>),
+}
+
+impl<'a> PostgresInitialMessage<'a> {
+    /// Parse the initial message, return the message and the encoded length of it
+    pub fn parse(data: &'a [u8]) -> Result<(Self, usize), PostgresParseError> {
+        if data.len() < 8 {
+            return Err(PostgresParseError::NeedMoreData(8 - data.len()));
+        }
+
+        let len = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+        if len < 8 || len as usize > MAX_STARTUP_PACKET_LENGTH {
+            return Err(PostgresParseError::InvalidMessageLength(len));
+        }
+        let code = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+        let len = len as usize;
+
+        match code {
+            SSL_REQUEST_CODE | GSSENC_REQUEST_CODE => {
+                if len != 8 {
+                    return Err(PostgresParseError::InvalidMessageLength(len as u32));
+                }
+                let msg = if code == SSL_REQUEST_CODE {
+                    PostgresInitialMessage::SslRequest
+                } else {
+                    PostgresInitialMessage::GssEncRequest
+                };
+                Ok((msg, 8))
+            }
+            CANCEL_REQUEST_CODE => {
+                if len != 16 {
+                    return Err(PostgresParseError::InvalidMessageLength(len as u32));
+                }
+                if data.len() < 16 {
+                    return Err(PostgresParseError::NeedMoreData(16 - data.len()));
+                }
+                Ok((PostgresInitialMessage::CancelRequest, 16))
+            }
+            _ => {
+                if code >> 16 != 3 {
+                    return Err(PostgresParseError::UnsupportedProtocolVersion(code));
+                }
+                if data.len() < len {
+                    return Err(PostgresParseError::NeedMoreData(len - data.len()));
+                }
+                let msg = parse_startup_parameters(code, &data[8..len])?;
+                Ok((PostgresInitialMessage::Startup(msg), len))
+            }
+        }
+    }
+}
+
+fn parse_startup_parameters(
+    protocol_version: u32,
+    mut data: &[u8],
+) -> Result<PostgresStartupMessage<'_>, PostgresParseError> {
+    let mut user = None;
+    let mut database = None;
+    let mut application_name = None;
+
+    loop {
+        let name = read_cstr(&mut data)?;
+        if name.is_empty() {
+            if !data.is_empty() {
+                return Err(PostgresParseError::InvalidParameter);
+            }
+            break;
+        }
+        let value = read_cstr(&mut data)?;
+        match name {
+            "user" => user = Some(value),
+            "database" => database = Some(value),
+            "application_name" => application_name = Some(value),
+            _ => {}
+        }
+    }
+
+    let Some(user) = user else {
+        return Err(PostgresParseError::NoUser);
+    };
+    Ok(PostgresStartupMessage {
+        protocol_version,
+        user,
+        database: database.filter(|s| !s.is_empty()),
+        application_name: application_name.filter(|s| !s.is_empty()),
+    })
+}
+
+fn read_cstr<'a>(data: &mut &'a [u8]) -> Result<&'a str, PostgresParseError> {
+    let Some(p) = memchr::memchr(b'\0', data) else {
+        return Err(PostgresParseError::InvalidParameter);
+    };
+    let s = std::str::from_utf8(&data[..p]).map_err(PostgresParseError::InvalidString)?;
+    *data = &data[p + 1..];
+    Ok(s)
+}
+
+/// Encode a FATAL level ErrorResponse message
+pub fn encode_fatal_error_response(sqlstate: &str, message: &str) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(32 + sqlstate.len() + message.len());
+    buf.push(b'E');
+    buf.extend_from_slice(&[0u8; 4]);
+    buf.extend_from_slice(b"SFATAL\0");
+    buf.extend_from_slice(b"VFATAL\0");
+    buf.push(b'C');
+    buf.extend_from_slice(sqlstate.as_bytes());
+    buf.push(b'\0');
+    buf.push(b'M');
+    buf.extend_from_slice(message.as_bytes());
+    buf.push(b'\0');
+    buf.push(b'\0');
+    let len = (buf.len() - 1) as u32;
+    buf[1..5].copy_from_slice(&len.to_be_bytes());
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SSL_REQUEST: &[u8] = &[0x00, 0x00, 0x00, 0x08, 0x04, 0xd2, 0x16, 0x2f];
+
+    const STARTUP_MESSAGE: &[u8] = &[
+        0x00, 0x00, 0x00, 0x38, 0x00, 0x03, 0x00, 0x00, b'u', b's', b'e', b'r', 0x00, b'a', b'p',
+        b'p', 0x00, b'd', b'a', b't', b'a', b'b', b'a', b's', b'e', 0x00, b'o', b'r', b'd', b'e',
+        b'r', b's', 0x00, b'a', b'p', b'p', b'l', b'i', b'c', b'a', b't', b'i', b'o', b'n', b'_',
+        b'n', b'a', b'm', b'e', 0x00, b'p', b's', b'q', b'l', 0x00, 0x00,
+    ];
+
+    #[test]
+    fn ssl_request() {
+        let (msg, len) = PostgresInitialMessage::parse(SSL_REQUEST).unwrap();
+        assert_eq!(msg, PostgresInitialMessage::SslRequest);
+        assert_eq!(len, 8);
+    }
+
+    #[test]
+    fn startup() {
+        let (msg, len) = PostgresInitialMessage::parse(STARTUP_MESSAGE).unwrap();
+        assert_eq!(len, STARTUP_MESSAGE.len());
+        let PostgresInitialMessage::Startup(msg) = msg else {
+            panic!("not startup message");
+        };
+        assert_eq!(msg.protocol_major_version(), 3);
+        assert_eq!(msg.protocol_minor_version(), 0);
+        assert_eq!(msg.user, "app");
+        assert_eq!(msg.database(), "orders");
+        assert_eq!(msg.application_name, Some("psql"));
+    }
+
+    #[test]
+    fn startup_partial() {
+        assert_eq!(
+            PostgresInitialMessage::parse(&STARTUP_MESSAGE[..20]).unwrap_err(),
+            PostgresParseError::NeedMoreData(STARTUP_MESSAGE.len() - 20)
+        );
+    }
+
+    #[test]
+    fn invalid_version() {
+        let data = [0x00, 0x00, 0x00, 0x08, 0x00, 0x02, 0x00, 0x00];
+        assert_eq!(
+            PostgresInitialMessage::parse(&data).unwrap_err(),
+            PostgresParseError::UnsupportedProtocolVersion(0x20000)
+        );
+    }
+
+    #[test]
+    fn error_response() {
+        let buf = encode_fatal_error_response("28000", "denied");
+        assert_eq!(buf[0], b'E');
+        let len = u32::from_be_bytes([buf[1], buf[2], buf[3], buf[4]]) as usize;
+        assert_eq!(len + 1, buf.len());
+        assert_eq!(buf.last(), Some(&0));
+    }
+}
@@ -0,0 +1,270 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::Utf8Error;
+
+use thiserror::Error;
+
+mod observer;
+pub use observer::ThriftFramedObserver;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ThriftParseError {
+    #[error("need {0} bytes more data")]
+    NeedMoreData(usize),
+    #[error("unsupported protocol")]
+    UnsupportedProtocol,
+    #[error("invalid message type {0}")]
+    InvalidMessageType(u8),
+    #[error("invalid method name length")]
+    InvalidNameLength,
+    #[error("invalid method name: {0}")]
+    InvalidName(Utf8Error),
+    #[error("invalid varint")]
+    InvalidVarint,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThriftProtocol {
+    Binary,
+    Compact,
+}
+
+impl ThriftProtocol {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ThriftProtocol::Binary => "binary",
+            ThriftProtocol::Compact => "compact",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThriftMessageType {
+    Call,
+    Reply,
+    Exception,
+    Oneway,
+}
+
+impl ThriftMessageType {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ThriftMessageType::Call => "call",
+            ThriftMessageType::Reply => "reply",
+            ThriftMessageType::Exception => "exception",
+            ThriftMessageType::Oneway => "oneway",
+        }
+    }
+}
+
+impl TryFrom<u8> for ThriftMessageType {
+    type Error = ThriftParseError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(ThriftMessageType::Call),
+            2 => Ok(ThriftMessageType::Reply),
+            3 => Ok(ThriftMessageType::Exception),
+            4 => Ok(ThriftMessageType::Oneway),
+            _ => Err(ThriftParseError::InvalidMessageType(value)),
+        }
+    }
+}
+
+/// The header of a Thrift message
+///
+/// See https://github.com/apache/thrift/blob/master/doc/specs/thrift-binary-protocol.md
+/// and https://github.com/apache/thrift/blob/master/doc/specs/thrift-compact-protocol.md
+#[derive(Debug, PartialEq, Eq)]
+pub struct ThriftMessageHeader<'a> {
+    pub protocol: ThriftProtocol,
+    pub framed: bool,
+    pub message_type: ThriftMessageType,
+    pub name: &'a str,
+    pub seq_id: i32,
+    /// the length of the frame, not including the frame size field
+    pub frame_size: Option<u32>,
+}
+
+impl<'a> ThriftMessageHeader<'a> {
+    const BINARY_VERSION_1: u16 = 0x8001;
+    const COMPACT_PROTOCOL_ID: u8 = 0x82;
+    const COMPACT_VERSION: u8 = 1;
+
+    /// Parse the message header of the unframed transport
+    pub fn parse(data: &'a [u8], max_name_len: usize) -> Result<Self, ThriftParseError> {
+        let Some(b) = data.first() else {
+            return Err(ThriftParseError::NeedMoreData(1));
+        };
+        match *b {
+            0x80 => Self::parse_binary(data, max_name_len),
+            Self::COMPACT_PROTOCOL_ID => Self::parse_compact(data, max_name_len),
+            _ => Err(ThriftParseError::UnsupportedProtocol),
+        }
+    }
+
+    /// Parse the message header of the framed transport
+    pub fn parse_framed(data: &'a [u8], max_name_len: usize) -> Result<Self, ThriftParseError> {
+        if data.len() < 4 {
+            return Err(ThriftParseError::NeedMoreData(4 - data.len()));
+        }
+        let frame_size = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+        let mut header = Self::parse(&data[4..], max_name_len)?;
+        header.framed = true;
+        header.frame_size = Some(frame_size);
+        Ok(header)
+    }
+
+    fn parse_binary(data: &'a [u8], max_name_len: usize) -> Result<Self, ThriftParseError> {
+        // version(2) + unused(1) + type(1) + name length(4)
+        const FIXED_LEN: usize = 8;
+
+        if data.len() < FIXED_LEN {
+            return Err(ThriftParseError::NeedMoreData(FIXED_LEN - data.len()));
+        }
+        if u16::from_be_bytes([data[0], data[1]]) != Self::BINARY_VERSION_1 {
+            return Err(ThriftParseError::UnsupportedProtocol);
+        }
+        let message_type = ThriftMessageType::try_from(data[3] & 0x07)?;
+        let name_len = i32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+        if name_len < 0 || name_len as usize > max_name_len {
+            return Err(ThriftParseError::InvalidNameLength);
+        }
+        let name_end = FIXED_LEN + name_len as usize;
+        let seq_end = name_end + 4;
+        if data.len() < seq_end {
+            return Err(ThriftParseError::NeedMoreData(seq_end - data.len()));
+        }
+        let name = std::str::from_utf8(&data[FIXED_LEN..name_end])
+            .map_err(ThriftParseError::InvalidName)?;
+        let seq_id = i32::from_be_bytes([
+            data[name_end],
+            data[name_end + 1],
+            data[name_end + 2],
+            data[name_end + 3],
+        ]);
+        Ok(ThriftMessageHeader {
+            protocol: ThriftProtocol::Binary,
+            framed: false,
+            message_type,
+            name,
+            seq_id,
+            frame_size: None,
+        })
+    }
+
+    fn parse_compact(data: &'a [u8], max_name_len: usize) -> Result<Self, ThriftParseError> {
+        if data.len() < 2 {
+            return Err(ThriftParseError::NeedMoreData(2 - data.len()));
+        }
+        if data[1] & 0x1f != Self::COMPACT_VERSION {
+            return Err(ThriftParseError::UnsupportedProtocol);
+        }
+        let message_type = ThriftMessageType::try_from(data[1] >> 5)?;
+
+        let mut offset = 2;
+        let (seq_id, len) = read_varint32(&data[offset..])?;
+        offset += len;
+        let (name_len, len) = read_varint32(&data[offset..])?;
+        offset += len;
+        if name_len as usize > max_name_len {
+            return Err(ThriftParseError::InvalidNameLength);
+        }
+        let name_end = offset + name_len as usize;
+        if data.len() < name_end {
+            return Err(ThriftParseError::NeedMoreData(name_end - data.len()));
+        }
+        let name =
+            std::str::from_utf8(&data[offset..name_end]).map_err(ThriftParseError::InvalidName)?;
+        Ok(ThriftMessageHeader {
+            protocol: ThriftProtocol::Compact,
+            framed: false,
+            message_type,
+            name,
+            seq_id: seq_id as i32,
+            frame_size: None,
+        })
+    }
+}
+
+fn read_varint32(data: &[u8]) -> Result<(u32, usize), ThriftParseError> {
+    let mut v: u32 = 0;
+    for (i, b) in data.iter().enumerate() {
+        if i >= 5 {
+            return Err(ThriftParseError::InvalidVarint);
+        }
+        v |= ((*b & 0x7f) as u32) << (7 * i);
+        if *b & 0x80 == 0 {
+            return Ok((v, i + 1));
+        }
+    }
+    Err(ThriftParseError::NeedMoreData(1))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) const BINARY_CALL: &[u8] = &[
+        0x80, 0x01, 0x00, 0x01, 0x00, 0x00, 0x00, 0x04, b'p', b'i', b'n', b'g', 0x00, 0x00, 0x00,
+        0x07, 0x00,
+    ];
+
+    #[test]
+    fn binary() {
+        let h = ThriftMessageHeader::parse(BINARY_CALL, 64).unwrap();
+        assert_eq!(h.protocol, ThriftProtocol::Binary);
+        assert_eq!(h.message_type, ThriftMessageType::Call);
+        assert_eq!(h.name, "ping");
+        assert_eq!(h.seq_id, 7);
+        assert!(!h.framed);
+
+        assert_eq!(
+            ThriftMessageHeader::parse(&BINARY_CALL[..10], 64).unwrap_err(),
+            ThriftParseError::NeedMoreData(6)
+        );
+        assert_eq!(
+            ThriftMessageHeader::parse(BINARY_CALL, 3).unwrap_err(),
+            ThriftParseError::InvalidNameLength
+        );
+    }
+
+    #[test]
+    fn compact() {
+        let data = [0x82, 0x21, 0x96, 0x01, 0x03, b'a', b'd', b'd', 0x00];
+        let h = ThriftMessageHeader::parse(&data, 64).unwrap();
+        assert_eq!(h.protocol, ThriftProtocol::Compact);
+        assert_eq!(h.message_type, ThriftMessageType::Call);
+        assert_eq!(h.name, "add");
+        assert_eq!(h.seq_id, 150);
+    }
+
+    #[test]
+    fn framed() {
+        let mut data = (BINARY_CALL.len() as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(BINARY_CALL);
+        let h = ThriftMessageHeader::parse_framed(&data, 64).unwrap();
+        assert!(h.framed);
+        assert_eq!(h.frame_size, Some(BINARY_CALL.len() as u32));
+        assert_eq!(h.name, "ping");
+
+        assert_eq!(
+            ThriftMessageHeader::parse_framed(b"GET / HTTP/1.1\r\n", 64).unwrap_err(),
+            ThriftParseError::UnsupportedProtocol
+        );
+    }
+}
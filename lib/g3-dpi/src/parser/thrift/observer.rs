@@ -0,0 +1,166 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{ThriftMessageHeader, ThriftParseError};
+
+enum ObserveState {
+    FrameSize { buf: [u8; 4], len: usize },
+    Header { frame_size: u32, frame_left: usize },
+    Skip(usize),
+    Failed,
+}
+
+/// Observe the Thrift messages in a framed transport data stream
+///
+/// Only the message header will be buffered, the remaining data in the frame will be skipped.
+pub struct ThriftFramedObserver {
+    max_name_len: usize,
+    state: ObserveState,
+    header_buf: Vec<u8>,
+}
+
+impl ThriftFramedObserver {
+    pub fn new(max_name_len: usize) -> Self {
+        ThriftFramedObserver {
+            max_name_len,
+            state: ObserveState::FrameSize {
+                buf: [0u8; 4],
+                len: 0,
+            },
+            header_buf: Vec::with_capacity(64),
+        }
+    }
+
+    /// Check if the stream is not valid for framed transport
+    pub fn is_failed(&self) -> bool {
+        matches!(self.state, ObserveState::Failed)
+    }
+
+    fn max_header_len(&self) -> usize {
+        // name length field, version/type, and sequence id field with the max varint length
+        self.max_name_len + 16
+    }
+
+    /// Feed the data of the stream, and the callback will be called for each message header found
+    pub fn feed<F>(&mut self, mut data: &[u8], mut f: F)
+    where
+        F: FnMut(&ThriftMessageHeader<'_>),
+    {
+        let max_header_len = self.max_header_len();
+        while !data.is_empty() {
+            match &mut self.state {
+                ObserveState::FrameSize { buf, len } => {
+                    let to_copy = (4 - *len).min(data.len());
+                    buf[*len..*len + to_copy].copy_from_slice(&data[..to_copy]);
+                    *len += to_copy;
+                    data = &data[to_copy..];
+                    if *len == 4 {
+                        let frame_size = u32::from_be_bytes(*buf);
+                        self.header_buf.clear();
+                        self.state = ObserveState::Header {
+                            frame_size,
+                            frame_left: frame_size as usize,
+                        };
+                        self.check_empty_frame();
+                    }
+                }
+                ObserveState::Header {
+                    frame_size,
+                    frame_left,
+                } => {
+                    let frame_size = *frame_size;
+                    let buf_left = max_header_len - self.header_buf.len();
+                    let to_copy = (*frame_left).min(buf_left).min(data.len());
+                    self.header_buf.extend_from_slice(&data[..to_copy]);
+                    *frame_left -= to_copy;
+                    let frame_left = *frame_left;
+                    data = &data[to_copy..];
+
+                    match ThriftMessageHeader::parse(&self.header_buf, self.max_name_len) {
+                        Ok(mut header) => {
+                            header.framed = true;
+                            header.frame_size = Some(frame_size);
+                            f(&header);
+                            self.state = ObserveState::Skip(frame_left);
+                            self.check_empty_frame();
+                        }
+                        Err(ThriftParseError::NeedMoreData(_)) => {
+                            if frame_left == 0 || self.header_buf.len() >= max_header_len {
+                                self.state = ObserveState::Failed;
+                            }
+                        }
+                        Err(_) => self.state = ObserveState::Failed,
+                    }
+                }
+                ObserveState::Skip(left) => {
+                    let to_skip = (*left).min(data.len());
+                    *left -= to_skip;
+                    data = &data[to_skip..];
+                    self.check_empty_frame();
+                }
+                ObserveState::Failed => return,
+            }
+        }
+    }
+
+    fn check_empty_frame(&mut self) {
+        match self.state {
+            ObserveState::Header { frame_left: 0, .. } => self.state = ObserveState::Failed,
+            ObserveState::Skip(0) => {
+                self.state = ObserveState::FrameSize {
+                    buf: [0u8; 4],
+                    len: 0,
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::thrift::tests::BINARY_CALL;
+
+    #[test]
+    fn multiple_frames() {
+        let mut data = Vec::new();
+        for _ in 0..3 {
+            data.extend_from_slice(&(BINARY_CALL.len() as u32).to_be_bytes());
+            data.extend_from_slice(BINARY_CALL);
+        }
+
+        for chunk_size in [1, 3, 7, data.len()] {
+            let mut observer = ThriftFramedObserver::new(64);
+            let mut names = Vec::new();
+            for chunk in data.chunks(chunk_size) {
+                observer.feed(chunk, |h| {
+                    assert_eq!(h.frame_size, Some(BINARY_CALL.len() as u32));
+                    names.push(h.name.to_string());
+                });
+            }
+            assert!(!observer.is_failed());
+            assert_eq!(names, vec!["ping", "ping", "ping"]);
+        }
+    }
+
+    #[test]
+    fn not_framed() {
+        let mut observer = ThriftFramedObserver::new(64);
+        observer.feed(BINARY_CALL, |_| panic!("should not be called"));
+        assert!(observer.is_failed());
+    }
+}
@@ -0,0 +1,254 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WebsocketParseError {
+    #[error("need {0} bytes more data")]
+    NeedMoreData(usize),
+    #[error("reserved bits set")]
+    ReservedBitsSet,
+    #[error("invalid opcode {0}")]
+    InvalidOpcode(u8),
+    #[error("invalid control frame")]
+    InvalidControlFrame,
+    #[error("invalid payload length")]
+    InvalidPayloadLength,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebsocketOpcode {
+    Continuation,
+    Text,
+    Binary,
+    Close,
+    Ping,
+    Pong,
+}
+
+impl WebsocketOpcode {
+    fn from_u8(v: u8) -> Option<Self> {
+        match v {
+            0x0 => Some(WebsocketOpcode::Continuation),
+            0x1 => Some(WebsocketOpcode::Text),
+            0x2 => Some(WebsocketOpcode::Binary),
+            0x8 => Some(WebsocketOpcode::Close),
+            0x9 => Some(WebsocketOpcode::Ping),
+            0xA => Some(WebsocketOpcode::Pong),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebsocketOpcode::Continuation => "continuation",
+            WebsocketOpcode::Text => "text",
+            WebsocketOpcode::Binary => "binary",
+            WebsocketOpcode::Close => "close",
+            WebsocketOpcode::Ping => "ping",
+            WebsocketOpcode::Pong => "pong",
+        }
+    }
+
+    pub fn is_control(&self) -> bool {
+        matches!(
+            self,
+            WebsocketOpcode::Close | WebsocketOpcode::Ping | WebsocketOpcode::Pong
+        )
+    }
+}
+
+/// The header of a WebSocket frame
+///
+/// See https://datatracker.ietf.org/doc/html/rfc6455#section-5.2
+#[derive(Debug, PartialEq, Eq)]
+pub struct WebsocketFrameHeader {
+    pub fin: bool,
+    pub opcode: WebsocketOpcode,
+    pub mask_key: Option<[u8; 4]>,
+    pub payload_len: u64,
+    /// the length of the encoded header, including the extended payload length and the mask key
+    pub encoded_len: usize,
+}
+
+impl WebsocketFrameHeader {
+    pub const MIN_SIZE: usize = 2;
+    pub const MAX_SIZE: usize = 14;
+
+    pub fn parse(data: &[u8]) -> Result<Self, WebsocketParseError> {
+        if data.len() < Self::MIN_SIZE {
+            return Err(WebsocketParseError::NeedMoreData(
+                Self::MIN_SIZE - data.len(),
+            ));
+        }
+
+        let b0 = data[0];
+        let b1 = data[1];
+        if b0 & 0x70 != 0 {
+            // no extensions that use the reserved bits are supported
+            return Err(WebsocketParseError::ReservedBitsSet);
+        }
+        let fin = b0 & 0x80 != 0;
+        let opcode = WebsocketOpcode::from_u8(b0 & 0x0F)
+            .ok_or(WebsocketParseError::InvalidOpcode(b0 & 0x0F))?;
+        let masked = b1 & 0x80 != 0;
+
+        let mut offset = 2;
+        let payload_len = match b1 & 0x7F {
+            126 => {
+                if data.len() < offset + 2 {
+                    return Err(WebsocketParseError::NeedMoreData(offset + 2 - data.len()));
+                }
+                let len = u16::from_be_bytes([data[2], data[3]]);
+                offset += 2;
+                len as u64
+            }
+            127 => {
+                if data.len() < offset + 8 {
+                    return Err(WebsocketParseError::NeedMoreData(offset + 8 - data.len()));
+                }
+                let mut len_buf = [0u8; 8];
+                len_buf.copy_from_slice(&data[2..10]);
+                let len = u64::from_be_bytes(len_buf);
+                if len & (1 << 63) != 0 {
+                    return Err(WebsocketParseError::InvalidPayloadLength);
+                }
+                offset += 8;
+                len
+            }
+            n => n as u64,
+        };
+
+        if opcode.is_control() && (!fin || payload_len > 125) {
+            return Err(WebsocketParseError::InvalidControlFrame);
+        }
+
+        let mask_key = if masked {
+            if data.len() < offset + 4 {
+                return Err(WebsocketParseError::NeedMoreData(offset + 4 - data.len()));
+            }
+            let mut key = [0u8; 4];
+            key.copy_from_slice(&data[offset..offset + 4]);
+            offset += 4;
+            Some(key)
+        } else {
+            None
+        };
+
+        Ok(WebsocketFrameHeader {
+            fin,
+            opcode,
+            mask_key,
+            payload_len,
+            encoded_len: offset,
+        })
+    }
+
+    /// Unmask the payload data in place
+    ///
+    /// `offset` is the offset of the data in the payload of this frame.
+    pub fn unmask(&self, data: &mut [u8], offset: u64) {
+        if let Some(key) = self.mask_key {
+            let start = (offset % 4) as usize;
+            for (i, b) in data.iter_mut().enumerate() {
+                *b ^= key[(start + i) % 4];
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unmasked_text() {
+        let data = [0x81, 0x05, b'H', b'e', b'l', b'l', b'o'];
+        let h = WebsocketFrameHeader::parse(&data).unwrap();
+        assert!(h.fin);
+        assert_eq!(h.opcode, WebsocketOpcode::Text);
+        assert_eq!(h.mask_key, None);
+        assert_eq!(h.payload_len, 5);
+        assert_eq!(h.encoded_len, 2);
+    }
+
+    #[test]
+    fn masked_text() {
+        let data = [
+            0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58,
+        ];
+        let h = WebsocketFrameHeader::parse(&data).unwrap();
+        assert_eq!(h.mask_key, Some([0x37, 0xfa, 0x21, 0x3d]));
+        assert_eq!(h.payload_len, 5);
+        assert_eq!(h.encoded_len, 6);
+
+        let mut payload = data[6..].to_vec();
+        h.unmask(&mut payload[..2], 0);
+        h.unmask(&mut payload[2..], 2);
+        assert_eq!(payload, b"Hello");
+    }
+
+    #[test]
+    fn extended_length() {
+        let data = [0x82, 0x7E, 0x01, 0x00];
+        let h = WebsocketFrameHeader::parse(&data).unwrap();
+        assert_eq!(h.opcode, WebsocketOpcode::Binary);
+        assert_eq!(h.payload_len, 256);
+        assert_eq!(h.encoded_len, 4);
+
+        let data = [0x02, 0x7F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00];
+        let h = WebsocketFrameHeader::parse(&data).unwrap();
+        assert!(!h.fin);
+        assert_eq!(h.payload_len, 65536);
+        assert_eq!(h.encoded_len, 10);
+    }
+
+    #[test]
+    fn partial() {
+        assert_eq!(
+            WebsocketFrameHeader::parse(&[0x81]),
+            Err(WebsocketParseError::NeedMoreData(1))
+        );
+        assert_eq!(
+            WebsocketFrameHeader::parse(&[0x82, 0x7E, 0x01]),
+            Err(WebsocketParseError::NeedMoreData(1))
+        );
+        assert_eq!(
+            WebsocketFrameHeader::parse(&[0x81, 0x85, 0x37]),
+            Err(WebsocketParseError::NeedMoreData(3))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            WebsocketFrameHeader::parse(&[0xC1, 0x00]),
+            Err(WebsocketParseError::ReservedBitsSet)
+        );
+        assert_eq!(
+            WebsocketFrameHeader::parse(&[0x83, 0x00]),
+            Err(WebsocketParseError::InvalidOpcode(3))
+        );
+        assert_eq!(
+            WebsocketFrameHeader::parse(&[0x09, 0x00]),
+            Err(WebsocketParseError::InvalidControlFrame)
+        );
+        assert_eq!(
+            WebsocketFrameHeader::parse(&[0x89, 0x7E, 0x00, 0x80]),
+            Err(WebsocketParseError::InvalidControlFrame)
+        );
+    }
+}
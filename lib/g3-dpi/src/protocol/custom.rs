@@ -0,0 +1,51 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::Arc;
+
+use super::{Protocol, ProtocolInspectError, ProtocolInspectState};
+use crate::CustomProtocolSignature;
+
+impl ProtocolInspectState {
+    pub(crate) fn check_custom_protocol(
+        &mut self,
+        data: &[u8],
+        signatures: &[Arc<CustomProtocolSignature>],
+        server_port: u16,
+        server_first: bool,
+    ) -> Result<Option<Protocol>, ProtocolInspectError> {
+        let mut need_more_size = usize::MAX;
+        for s in signatures {
+            if s.server_first() != server_first || !s.match_port(server_port) {
+                continue;
+            }
+            match s.check_data(data) {
+                Ok(true) => {
+                    self.custom_matched = Some(s.clone());
+                    return Ok(Some(Protocol::Custom));
+                }
+                Ok(false) => {}
+                Err(size) => need_more_size = need_more_size.min(size),
+            }
+        }
+
+        if need_more_size != usize::MAX {
+            return Err(ProtocolInspectError::NeedMoreData(need_more_size));
+        }
+        self.exclude_current();
+        Ok(None)
+    }
+}
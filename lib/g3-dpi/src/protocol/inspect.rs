@@ -22,7 +22,7 @@ use fixedbitset::FixedBitSet;
 use g3_types::net::AlpnProtocol;
 
 use super::{MaybeProtocol, Protocol, ProtocolPortMap};
-use crate::{ProtocolInspectionConfig, ProtocolInspectionSizeLimit};
+use crate::{CustomProtocolSignature, ProtocolInspectionConfig};
 
 const GUESS_PROTOCOL_FOR_CLIENT_INITIAL_DATA: &[MaybeProtocol] = &[
     MaybeProtocol::Ssl,
@@ -30,11 +30,15 @@ const GUESS_PROTOCOL_FOR_CLIENT_INITIAL_DATA: &[MaybeProtocol] = &[
     MaybeProtocol::Ssh,
     MaybeProtocol::Smpp,
     MaybeProtocol::BitTorrent,
+    MaybeProtocol::Postgres,
+    MaybeProtocol::Kafka,
+    MaybeProtocol::Thrift,
 ];
 const GUESS_PROTOCOL_FOR_SERVER_INITIAL_DATA: &[MaybeProtocol] = &[
     MaybeProtocol::Ssh,
     MaybeProtocol::Ftp,
     MaybeProtocol::Nats,
+    MaybeProtocol::Mysql,
     MaybeProtocol::BitTorrent,
 ];
 
@@ -46,6 +50,7 @@ pub enum ProtocolInspectError {
 pub(crate) struct ProtocolInspectState {
     current: Option<MaybeProtocol>,
     excluded: FixedBitSet,
+    pub(super) custom_matched: Option<Arc<CustomProtocolSignature>>,
 }
 
 impl Default for ProtocolInspectState {
@@ -53,6 +58,7 @@ impl Default for ProtocolInspectState {
         ProtocolInspectState {
             current: None,
             excluded: FixedBitSet::with_capacity(MaybeProtocol::_MaxSize as usize),
+            custom_matched: None,
         }
     }
 }
@@ -85,14 +91,17 @@ impl ProtocolInspectState {
     fn reset_state(&mut self) {
         self.current = None;
         self.excluded.clear();
+        self.custom_matched = None;
     }
 
     fn check_client_initial_data_for_protocol(
         &mut self,
         proto: MaybeProtocol,
         data: &[u8],
-        size_limit: &ProtocolInspectionSizeLimit,
+        config: &ProtocolInspectionConfig,
+        server_port: u16,
     ) -> Result<Option<Protocol>, ProtocolInspectError> {
+        let size_limit = config.size_limit();
         if self.excluded(proto) {
             return Ok(None);
         }
@@ -108,6 +117,12 @@ impl ProtocolInspectState {
             MaybeProtocol::Smpp => self.check_smpp_session_request(data),
             MaybeProtocol::Rtmp => self.check_rtmp_tcp_client_handshake(data),
             MaybeProtocol::BitTorrent => self.check_bittorrent_tcp_handshake(data),
+            MaybeProtocol::Thrift => self.check_thrift_client_request(data, size_limit),
+            MaybeProtocol::Kafka => self.check_kafka_client_request(data),
+            MaybeProtocol::Postgres => self.check_postgres_client_startup(data),
+            MaybeProtocol::Custom => {
+                self.check_custom_protocol(data, config.custom_signatures(), server_port, false)
+            }
             MaybeProtocol::Ftp
             | MaybeProtocol::Smtp
             | MaybeProtocol::Odmr
@@ -115,7 +130,8 @@ impl ProtocolInspectState {
             | MaybeProtocol::Nntp
             | MaybeProtocol::Nnsp
             | MaybeProtocol::Imap
-            | MaybeProtocol::Nats => {
+            | MaybeProtocol::Nats
+            | MaybeProtocol::Mysql => {
                 self.exclude_current();
                 Ok(None)
             }
@@ -139,8 +155,10 @@ impl ProtocolInspectState {
         &mut self,
         proto: MaybeProtocol,
         data: &[u8],
-        size_limit: &ProtocolInspectionSizeLimit,
+        config: &ProtocolInspectionConfig,
+        server_port: u16,
     ) -> Result<Option<Protocol>, ProtocolInspectError> {
+        let size_limit = config.size_limit();
         if self.excluded(proto) {
             return Ok(None);
         }
@@ -154,6 +172,10 @@ impl ProtocolInspectState {
             MaybeProtocol::Imap => self.check_imap_server_greeting(data, size_limit),
             MaybeProtocol::Nats => self.check_nats_server_info_msg(data, size_limit),
             MaybeProtocol::BitTorrent => self.check_bittorrent_tcp_handshake(data),
+            MaybeProtocol::Mysql => self.check_mysql_server_handshake(data),
+            MaybeProtocol::Custom => {
+                self.check_custom_protocol(data, config.custom_signatures(), server_port, true)
+            }
             MaybeProtocol::Dns
             | MaybeProtocol::Ssl
             | MaybeProtocol::Http
@@ -161,7 +183,10 @@ impl ProtocolInspectState {
             | MaybeProtocol::Mqtt
             | MaybeProtocol::Stomp
             | MaybeProtocol::Smpp
-            | MaybeProtocol::Rtmp => {
+            | MaybeProtocol::Rtmp
+            | MaybeProtocol::Thrift
+            | MaybeProtocol::Kafka
+            | MaybeProtocol::Postgres => {
                 self.exclude_current();
                 Ok(None)
             }
@@ -237,6 +262,11 @@ impl ProtocolInspector {
         self.guess_protocols = true;
     }
 
+    /// Get the matched custom protocol signature if `Protocol::Custom` is returned
+    pub fn custom_protocol(&self) -> Option<&Arc<CustomProtocolSignature>> {
+        self.state.custom_matched.as_ref()
+    }
+
     pub fn set_no_explicit_ssl(&mut self) {
         self.no_explicit_ssl = true;
     }
@@ -256,7 +286,8 @@ impl ProtocolInspector {
                 match self.state.check_client_initial_data_for_protocol(
                     $p,
                     data,
-                    config.size_limit(),
+                    config,
+                    server_port,
                 ) {
                     Ok(Some(p)) => return Ok(p),
                     Ok(None) => {}
@@ -279,6 +310,10 @@ impl ProtocolInspector {
         }
 
         if self.guess_protocols {
+            if !config.custom_signatures().is_empty() {
+                check_protocol!(MaybeProtocol::Custom);
+            }
+
             if let Some(v) = self.server_portmap.get(server_port) {
                 if !self.no_explicit_ssl && v.check_ssl() {
                     check_protocol!(MaybeProtocol::Ssl);
@@ -311,7 +346,8 @@ impl ProtocolInspector {
                 match self.state.check_server_initial_data_for_protocol(
                     $p,
                     data,
-                    config.size_limit(),
+                    config,
+                    server_port,
                 ) {
                     Ok(Some(p)) => return Ok(p),
                     Ok(None) => {}
@@ -334,6 +370,10 @@ impl ProtocolInspector {
         }
 
         if self.guess_protocols {
+            if !config.custom_signatures().is_empty() {
+                check_protocol!(MaybeProtocol::Custom);
+            }
+
             if let Some(v) = self.server_portmap.get(server_port) {
                 for proto in v.protocols() {
                     check_protocol!(*proto);
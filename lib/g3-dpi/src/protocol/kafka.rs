@@ -0,0 +1,67 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{MaybeProtocol, Protocol, ProtocolInspectError, ProtocolInspectState};
+use crate::parser::kafka::{api_key, KafkaParseError, KafkaRequestHeader};
+
+impl ProtocolInspectState {
+    pub(crate) fn check_kafka_client_request(
+        &mut self,
+        data: &[u8],
+    ) -> Result<Option<Protocol>, ProtocolInspectError> {
+        // the initial request should be small
+        const MAX_INITIAL_REQUEST_SIZE: usize = 4096;
+
+        let data_len = data.len();
+        if data_len < KafkaRequestHeader::MIN_SIZE {
+            return Err(ProtocolInspectError::NeedMoreData(
+                KafkaRequestHeader::MIN_SIZE - data_len,
+            ));
+        }
+
+        if data[0] != 0 || data[1] != 0 {
+            self.exclude_current();
+            return Ok(None);
+        }
+
+        match KafkaRequestHeader::parse(data, MAX_INITIAL_REQUEST_SIZE) {
+            Ok(header) => {
+                // clients should always negotiate versions, query metadata or authenticate first
+                match header.api_key {
+                    api_key::API_VERSIONS | api_key::METADATA | api_key::SASL_HANDSHAKE => {}
+                    _ => {
+                        self.exclude_current();
+                        return Ok(None);
+                    }
+                }
+
+                // exclude impossible protocols
+                self.exclude_other(MaybeProtocol::Ssl);
+                self.exclude_other(MaybeProtocol::Http);
+                self.exclude_other(MaybeProtocol::Ssh);
+                self.exclude_other(MaybeProtocol::Smpp);
+                self.exclude_other(MaybeProtocol::BitTorrent);
+                self.exclude_other(MaybeProtocol::Thrift);
+                Ok(Some(Protocol::Kafka))
+            }
+            Err(KafkaParseError::NeedMoreData(n)) => Err(ProtocolInspectError::NeedMoreData(n)),
+            Err(_) => {
+                self.exclude_current();
+                Ok(None)
+            }
+        }
+    }
+}
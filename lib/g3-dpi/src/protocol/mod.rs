@@ -46,6 +46,11 @@ pub enum MaybeProtocol {
     Rtmp,
     Nats,
     BitTorrent,
+    Thrift,
+    Kafka,
+    Postgres,
+    Mysql,
+    Custom,
 
     Https,
     Submissions,
@@ -103,6 +108,10 @@ impl FromStr for MaybeProtocol {
             "rtmp" => Ok(MaybeProtocol::Rtmp),
             "nats" => Ok(MaybeProtocol::Nats),
             "bittorrent" | "bt" => Ok(MaybeProtocol::BitTorrent),
+            "thrift" => Ok(MaybeProtocol::Thrift),
+            "kafka" => Ok(MaybeProtocol::Kafka),
+            "postgres" | "postgresql" => Ok(MaybeProtocol::Postgres),
+            "mysql" => Ok(MaybeProtocol::Mysql),
             "https" | "http+tls" => Ok(MaybeProtocol::Https),
             "submissions" | "smtps" => Ok(MaybeProtocol::Submissions),
             "pop3s" | "pop3+tls" => Ok(MaybeProtocol::Pop3s),
@@ -135,6 +144,7 @@ impl From<AlpnProtocol> for MaybeProtocol {
             AlpnProtocol::Mqtt => MaybeProtocol::Mqtt,
             AlpnProtocol::DnsOverTls => MaybeProtocol::Dns,
             AlpnProtocol::DnsOverQuic => MaybeProtocol::Dns,
+            AlpnProtocol::Postgresql => MaybeProtocol::Postgres,
         }
     }
 }
@@ -180,6 +190,11 @@ pub enum Protocol {
     BitTorrentOverUtp,
     Websocket,
     Dns,
+    Thrift,
+    Kafka,
+    Postgres,
+    Mysql,
+    Custom,
 }
 
 impl Protocol {
@@ -211,6 +226,11 @@ impl Protocol {
             Protocol::BitTorrentOverTcp | Protocol::BitTorrentOverUtp => "bittorrent",
             Protocol::Websocket => "websocket",
             Protocol::Dns => "dns",
+            Protocol::Thrift => "thrift",
+            Protocol::Kafka => "kafka",
+            Protocol::Postgres => "postgres",
+            Protocol::Mysql => "mysql",
+            Protocol::Custom => "custom",
         }
     }
 
@@ -239,6 +259,11 @@ impl Protocol {
             Protocol::BitTorrentOverUtp => "bittorrent.utp",
             Protocol::Websocket => "websocket",
             Protocol::Dns => "dns",
+            Protocol::Thrift => "thrift",
+            Protocol::Kafka => "kafka",
+            Protocol::Postgres => "pgsql",
+            Protocol::Mysql => "mysql",
+            Protocol::Custom => "",
         }
     }
 
@@ -265,6 +290,11 @@ impl Protocol {
             Protocol::BitTorrentOverTcp | Protocol::BitTorrentOverUtp => "bittorrent",
             Protocol::Websocket => "websocket",
             Protocol::Dns => "dns",
+            Protocol::Thrift => "thrift",
+            Protocol::Kafka => "kafka",
+            Protocol::Postgres => "pgsql",
+            Protocol::Mysql => "mysql",
+            Protocol::Custom => "",
         }
     }
 }
@@ -284,6 +314,7 @@ impl From<AlpnProtocol> for Protocol {
             AlpnProtocol::Mqtt => Protocol::Mqtt,
             AlpnProtocol::DnsOverTls => Protocol::Dns,
             AlpnProtocol::DnsOverQuic => Protocol::Dns,
+            AlpnProtocol::Postgresql => Protocol::Postgres,
         }
     }
 }
@@ -295,14 +326,18 @@ impl fmt::Display for Protocol {
 }
 
 mod bittorrent;
+mod custom;
 mod dns;
 mod ftp;
 mod http;
 mod imap;
+mod kafka;
 mod mqtt;
+mod mysql;
 mod nats;
 mod nntp;
 mod pop3;
+mod postgres;
 mod rtmp;
 mod rtsp;
 mod smpp;
@@ -310,3 +345,4 @@ mod smtp;
 mod ssh;
 mod ssl;
 mod stomp;
+mod thrift;
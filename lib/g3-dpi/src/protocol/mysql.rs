@@ -0,0 +1,62 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{MaybeProtocol, Protocol, ProtocolInspectError, ProtocolInspectState};
+use crate::parser::mysql::{MysqlParseError, MysqlServerHandshake};
+
+impl ProtocolInspectState {
+    pub(crate) fn check_mysql_server_handshake(
+        &mut self,
+        data: &[u8],
+    ) -> Result<Option<Protocol>, ProtocolInspectError> {
+        // packet header(4) + protocol version(1) + server version(at least 1)
+        const MINIMUM_DATA_LEN: usize = 6;
+
+        let data_len = data.len();
+        if data_len < MINIMUM_DATA_LEN {
+            return Err(ProtocolInspectError::NeedMoreData(
+                MINIMUM_DATA_LEN - data_len,
+            ));
+        }
+
+        // sequence id should be 0 and protocol version should be 10
+        if data[2] != 0 || data[3] != 0 || data[4] != 10 {
+            self.exclude_current();
+            return Ok(None);
+        }
+
+        // exclude impossible protocols
+        self.exclude_other(MaybeProtocol::Ftp);
+        self.exclude_other(MaybeProtocol::Ssh);
+        self.exclude_other(MaybeProtocol::Smtp);
+        self.exclude_other(MaybeProtocol::Odmr);
+        self.exclude_other(MaybeProtocol::Pop3);
+        self.exclude_other(MaybeProtocol::Nntp);
+        self.exclude_other(MaybeProtocol::Nnsp);
+        self.exclude_other(MaybeProtocol::Imap);
+        self.exclude_other(MaybeProtocol::Nats);
+        self.exclude_other(MaybeProtocol::BitTorrent);
+
+        match MysqlServerHandshake::parse(data) {
+            Ok(_) => Ok(Some(Protocol::Mysql)),
+            Err(MysqlParseError::NeedMoreData(n)) => Err(ProtocolInspectError::NeedMoreData(n)),
+            Err(_) => {
+                self.exclude_current();
+                Ok(None)
+            }
+        }
+    }
+}
@@ -141,12 +141,15 @@ impl ProtocolPortMap {
         map.insert(1883, MaybeProtocol::Mqtt);
         map.insert(1935, MaybeProtocol::Rtmp);
         map.insert(2775, MaybeProtocol::Smpp);
+        map.insert(3306, MaybeProtocol::Mysql);
         map.insert(3550, MaybeProtocol::Ssmpp);
         map.insert(4222, MaybeProtocol::Nats);
+        map.insert(5432, MaybeProtocol::Postgres);
         map.insert(6881, MaybeProtocol::BitTorrent);
         map.insert(8080, MaybeProtocol::Http);
         map.insert(8554, MaybeProtocol::Rtsp);
         map.insert(8883, MaybeProtocol::SecureMqtt);
+        map.insert(9092, MaybeProtocol::Kafka);
         map
     }
 
@@ -0,0 +1,65 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{MaybeProtocol, Protocol, ProtocolInspectError, ProtocolInspectState};
+use crate::parser::postgres::{PostgresInitialMessage, PostgresParseError};
+
+impl ProtocolInspectState {
+    pub(crate) fn check_postgres_client_startup(
+        &mut self,
+        data: &[u8],
+    ) -> Result<Option<Protocol>, ProtocolInspectError> {
+        // length(4) + protocol version or request code(4)
+        const MINIMUM_DATA_LEN: usize = 8;
+
+        let data_len = data.len();
+        if data_len < MINIMUM_DATA_LEN {
+            return Err(ProtocolInspectError::NeedMoreData(
+                MINIMUM_DATA_LEN - data_len,
+            ));
+        }
+
+        // the length of the startup message should be less than 10000
+        if data[0] != 0 || data[1] != 0 {
+            self.exclude_current();
+            return Ok(None);
+        }
+
+        match PostgresInitialMessage::parse(data) {
+            Ok((PostgresInitialMessage::CancelRequest, _)) => {
+                // this is sent in a separate connection, and no response will be sent
+                self.exclude_current();
+                Ok(None)
+            }
+            Ok(_) => {
+                // exclude impossible protocols
+                self.exclude_other(MaybeProtocol::Ssl);
+                self.exclude_other(MaybeProtocol::Http);
+                self.exclude_other(MaybeProtocol::Ssh);
+                self.exclude_other(MaybeProtocol::Smpp);
+                self.exclude_other(MaybeProtocol::BitTorrent);
+                self.exclude_other(MaybeProtocol::Kafka);
+                self.exclude_other(MaybeProtocol::Thrift);
+                Ok(Some(Protocol::Postgres))
+            }
+            Err(PostgresParseError::NeedMoreData(n)) => Err(ProtocolInspectError::NeedMoreData(n)),
+            Err(_) => {
+                self.exclude_current();
+                Ok(None)
+            }
+        }
+    }
+}
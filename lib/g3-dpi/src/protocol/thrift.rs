@@ -0,0 +1,66 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use super::{MaybeProtocol, Protocol, ProtocolInspectError, ProtocolInspectState};
+use crate::parser::thrift::{ThriftMessageHeader, ThriftParseError};
+use crate::ProtocolInspectionSizeLimit;
+
+impl ProtocolInspectState {
+    pub(crate) fn check_thrift_client_request(
+        &mut self,
+        data: &[u8],
+        size_limit: &ProtocolInspectionSizeLimit,
+    ) -> Result<Option<Protocol>, ProtocolInspectError> {
+        // at least the binary protocol version and message type, or the frame size
+        const MINIMUM_DATA_LEN: usize = 4;
+
+        let data_len = data.len();
+        if data_len < MINIMUM_DATA_LEN {
+            return Err(ProtocolInspectError::NeedMoreData(
+                MINIMUM_DATA_LEN - data_len,
+            ));
+        }
+
+        let r = match data[0] {
+            0x80 | 0x82 => ThriftMessageHeader::parse(data, size_limit.thrift_method_name),
+            0x00 => {
+                // framed transport, with frame size less than 16MB
+                ThriftMessageHeader::parse_framed(data, size_limit.thrift_method_name)
+            }
+            _ => {
+                self.exclude_current();
+                return Ok(None);
+            }
+        };
+
+        match r {
+            Ok(_) => {
+                // exclude impossible protocols
+                self.exclude_other(MaybeProtocol::Ssl);
+                self.exclude_other(MaybeProtocol::Http);
+                self.exclude_other(MaybeProtocol::Ssh);
+                self.exclude_other(MaybeProtocol::Smpp);
+                self.exclude_other(MaybeProtocol::BitTorrent);
+                Ok(Some(Protocol::Thrift))
+            }
+            Err(ThriftParseError::NeedMoreData(n)) => Err(ProtocolInspectError::NeedMoreData(n)),
+            Err(_) => {
+                self.exclude_current();
+                Ok(None)
+            }
+        }
+    }
+}
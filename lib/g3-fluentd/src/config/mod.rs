@@ -25,6 +25,7 @@ use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 
 use g3_openssl::SslConnector;
 use g3_socket::BindAddr;
+use g3_types::collection::SelectivePickPolicy;
 use g3_types::net::{Host, OpensslClientConfig, OpensslClientConfigBuilder, TcpKeepAliveConfig};
 
 use super::FluentdConnection;
@@ -32,12 +33,17 @@ use super::FluentdConnection;
 #[cfg(feature = "yaml")]
 mod yaml;
 
+mod server;
+pub use server::FluentdServerPeer;
+pub(crate) use server::FluentdServerPicker;
+
 const FLUENTD_DEFAULT_PORT: u16 = 24224;
 const FLUENTD_HASH_SIZE: usize = 64;
 
 #[derive(Clone)]
 pub struct FluentdClientConfig {
-    server_addr: SocketAddr,
+    servers: Vec<FluentdServerPeer>,
+    server_pick_policy: SelectivePickPolicy,
     bind: BindAddr,
     shared_key: String,
     username: String,
@@ -66,7 +72,8 @@ impl FluentdClientConfig {
     pub fn new(server: SocketAddr) -> Self {
         let hostname = g3_compat::hostname().to_string_lossy().to_string();
         FluentdClientConfig {
-            server_addr: server,
+            servers: vec![FluentdServerPeer::new(server)],
+            server_pick_policy: SelectivePickPolicy::Serial,
             bind: BindAddr::None,
             shared_key: String::new(),
             username: String::new(),
@@ -84,7 +91,25 @@ impl FluentdClientConfig {
     }
 
     pub fn set_server_addr(&mut self, addr: SocketAddr) {
-        self.server_addr = addr;
+        self.servers = vec![FluentdServerPeer::new(addr)];
+    }
+
+    pub fn set_servers(&mut self, servers: Vec<FluentdServerPeer>) -> anyhow::Result<()> {
+        if servers.is_empty() {
+            return Err(anyhow!("no fluentd server set"));
+        }
+        self.servers = servers;
+        Ok(())
+    }
+
+    pub fn set_server_pick_policy(&mut self, policy: SelectivePickPolicy) -> anyhow::Result<()> {
+        server::check_pick_policy(policy)?;
+        self.server_pick_policy = policy;
+        Ok(())
+    }
+
+    pub(super) fn build_server_picker(&self) -> FluentdServerPicker {
+        FluentdServerPicker::new(&self.servers, self.server_pick_policy)
     }
 
     pub fn set_bind_ip(&mut self, ip: IpAddr) {
@@ -143,9 +168,12 @@ impl FluentdClientConfig {
         self.retry_queue_len = len;
     }
 
-    pub(super) async fn new_connection(&self) -> anyhow::Result<FluentdConnection> {
+    pub(super) async fn new_connection(
+        &self,
+        server_addr: SocketAddr,
+    ) -> anyhow::Result<FluentdConnection> {
         let socket = g3_socket::tcp::new_socket_to(
-            self.server_addr.ip(),
+            server_addr.ip(),
             &self.bind,
             &self.tcp_keepalive,
             &Default::default(),
@@ -153,15 +181,15 @@ impl FluentdClientConfig {
         )
         .map_err(|e| anyhow!("failed to setup socket: {e:?}"))?;
         let tcp_stream = socket
-            .connect(self.server_addr)
+            .connect(server_addr)
             .await
-            .map_err(|e| anyhow!("failed to tcp connect to peer {}: {e:?}", self.server_addr))?;
+            .map_err(|e| anyhow!("failed to tcp connect to peer {server_addr}: {e:?}"))?;
 
         if let Some(tls_client) = &self.tls_client {
-            let default_tls_name = Host::Ip(self.server_addr.ip());
+            let default_tls_name = Host::Ip(server_addr.ip());
             let tls_name = self.tls_name.as_ref().unwrap_or(&default_tls_name);
             let ssl = tls_client
-                .build_ssl(tls_name, self.server_addr.port())
+                .build_ssl(tls_name, server_addr.port())
                 .map_err(|e| anyhow!("failed to prepare ssl: {e}"))?;
             let tls_connect = SslConnector::new(ssl, tcp_stream)
                 .map_err(|e| anyhow!("failed to create TLS connector: {e}"))?;
@@ -0,0 +1,156 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use anyhow::anyhow;
+
+use g3_types::collection::{SelectivePickPolicy, SelectiveVec, SelectiveVecBuilder, WeightedValue};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FluentdServerPeer {
+    addr: SocketAddr,
+    priority: u8,
+    weight: f64,
+}
+
+impl FluentdServerPeer {
+    pub fn new(addr: SocketAddr) -> Self {
+        FluentdServerPeer {
+            addr,
+            priority: 0,
+            weight: 1.0,
+        }
+    }
+
+    #[inline]
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// servers with smaller priority value will be tried first
+    pub fn set_priority(&mut self, priority: u8) {
+        self.priority = priority;
+    }
+
+    pub fn set_weight(&mut self, weight: f64) -> anyhow::Result<()> {
+        if weight.is_sign_negative() || weight.is_nan() {
+            return Err(anyhow!("invalid weight value {weight}"));
+        }
+        self.weight = weight;
+        Ok(())
+    }
+}
+
+pub(crate) fn check_pick_policy(policy: SelectivePickPolicy) -> anyhow::Result<()> {
+    match policy {
+        SelectivePickPolicy::Serial
+        | SelectivePickPolicy::RoundRobin
+        | SelectivePickPolicy::Random => Ok(()),
+        _ => Err(anyhow!("unsupported pick policy {policy:?}")),
+    }
+}
+
+/// Shared by all IO threads of the same logger, so the round-robin state is global.
+pub(crate) struct FluentdServerPicker {
+    levels: Vec<(SelectiveVec<WeightedValue<SocketAddr>>, AtomicUsize)>,
+    pick_policy: SelectivePickPolicy,
+}
+
+impl FluentdServerPicker {
+    pub(crate) fn new(servers: &[FluentdServerPeer], pick_policy: SelectivePickPolicy) -> Self {
+        let mut level_map: BTreeMap<u8, SelectiveVecBuilder<WeightedValue<SocketAddr>>> =
+            BTreeMap::new();
+        for peer in servers {
+            level_map
+                .entry(peer.priority)
+                .or_default()
+                .insert(WeightedValue::with_weight(peer.addr, peer.weight));
+        }
+        let levels = level_map
+            .into_values()
+            .filter_map(|b| b.build())
+            .map(|v| (v, AtomicUsize::new(0)))
+            .collect();
+        FluentdServerPicker {
+            levels,
+            pick_policy,
+        }
+    }
+
+    /// Get all servers in the order they should be tried
+    pub(crate) fn candidates(&self) -> Vec<SocketAddr> {
+        let mut addrs = Vec::new();
+        for (level, rr_id) in &self.levels {
+            let picked = match self.pick_policy {
+                SelectivePickPolicy::RoundRobin => {
+                    let mut picked = level.pick_serial_n(level.len());
+                    let start = rr_id.fetch_add(1, Ordering::Relaxed) % picked.len();
+                    picked.rotate_left(start);
+                    picked
+                }
+                SelectivePickPolicy::Random => level.pick_random_n(level.len()),
+                _ => level.pick_serial_n(level.len()),
+            };
+            addrs.extend(picked.into_iter().map(|v| *v.inner()));
+        }
+        addrs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn peer(addr: &str, priority: u8) -> FluentdServerPeer {
+        let mut peer = FluentdServerPeer::new(SocketAddr::from_str(addr).unwrap());
+        peer.set_priority(priority);
+        peer
+    }
+
+    #[test]
+    fn serial_by_priority() {
+        let servers = [
+            peer("127.0.0.2:24224", 1),
+            peer("127.0.0.1:24224", 0),
+            peer("127.0.0.3:24224", 1),
+        ];
+        let picker = FluentdServerPicker::new(&servers, SelectivePickPolicy::Serial);
+        let addrs = picker.candidates();
+        assert_eq!(addrs.len(), 3);
+        assert_eq!(addrs[0], servers[1].addr());
+        assert_eq!(addrs[1], servers[0].addr());
+        assert_eq!(addrs[2], servers[2].addr());
+    }
+
+    #[test]
+    fn round_robin_in_level() {
+        let servers = [
+            peer("127.0.0.1:24224", 0),
+            peer("127.0.0.2:24224", 0),
+            peer("127.0.0.3:24224", 1),
+        ];
+        let picker = FluentdServerPicker::new(&servers, SelectivePickPolicy::RoundRobin);
+        let first = picker.candidates();
+        let second = picker.candidates();
+        assert_ne!(first[0], second[0]);
+        assert_eq!(first[2], servers[2].addr());
+        assert_eq!(second[2], servers[2].addr());
+    }
+}
@@ -19,7 +19,56 @@ use std::path::Path;
 use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
-use super::FluentdClientConfig;
+use super::{FluentdClientConfig, FluentdServerPeer};
+
+impl FluentdServerPeer {
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => {
+                let mut addr = None;
+                let mut priority = 0;
+                let mut weight = None;
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "address" | "addr" => {
+                        let sock_addr = g3_yaml::value::as_env_sockaddr(v)
+                            .context(format!("invalid sockaddr value for key {k}"))?;
+                        addr = Some(sock_addr);
+                        Ok(())
+                    }
+                    "priority" => {
+                        priority = g3_yaml::value::as_u8(v)
+                            .context(format!("invalid u8 value for key {k}"))?;
+                        Ok(())
+                    }
+                    "weight" => {
+                        let f = g3_yaml::value::as_f64(v)
+                            .context(format!("invalid f64 value for key {k}"))?;
+                        weight = Some(f);
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+
+                let Some(addr) = addr else {
+                    return Err(anyhow!("no address set"));
+                };
+                let mut peer = FluentdServerPeer::new(addr);
+                peer.set_priority(priority);
+                if let Some(weight) = weight {
+                    peer.set_weight(weight)?;
+                }
+                Ok(peer)
+            }
+            Yaml::String(_) => {
+                let addr = g3_yaml::value::as_env_sockaddr(value)?;
+                Ok(FluentdServerPeer::new(addr))
+            }
+            _ => Err(anyhow!(
+                "yaml value type for 'FluentdServerPeer' should be 'map' or 'string'"
+            )),
+        }
+    }
+}
 
 impl FluentdClientConfig {
     pub fn parse_yaml(value: &Yaml, lookup_dir: Option<&Path>) -> anyhow::Result<Self> {
@@ -33,6 +82,16 @@ impl FluentdClientConfig {
                         config.set_server_addr(addr);
                         Ok(())
                     }
+                    "servers" => {
+                        let servers = g3_yaml::value::as_list(v, FluentdServerPeer::parse_yaml)
+                            .context(format!("invalid fluentd server list value for key {k}"))?;
+                        config.set_servers(servers)
+                    }
+                    "server_pick_policy" | "pick_policy" => {
+                        let policy = g3_yaml::value::as_selective_pick_policy(v)
+                            .context(format!("invalid selective pick policy value for key {k}"))?;
+                        config.set_server_pick_policy(policy)
+                    }
                     "bind_ip" | "bind" => {
                         let ip = g3_yaml::value::as_ipaddr(v)?;
                         config.set_bind_ip(ip);
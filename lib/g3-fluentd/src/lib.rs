@@ -28,7 +28,8 @@ use g3_openssl::SslStream;
 use g3_types::log::{AsyncLogConfig, AsyncLogger, LogStats};
 
 mod config;
-pub use config::FluentdClientConfig;
+use config::FluentdServerPicker;
+pub use config::{FluentdClientConfig, FluentdServerPeer};
 
 mod handshake;
 
@@ -46,10 +47,12 @@ pub fn new_async_logger(
     let (sender, receiver) = flume::bounded::<Vec<u8>>(async_conf.channel_capacity);
 
     let stats = Arc::new(LogStats::default());
+    let server_picker = Arc::new(fluent_conf.build_server_picker());
 
     for i in 0..async_conf.thread_number {
         let io_thread = AsyncIoThread {
             config: Arc::clone(fluent_conf),
+            server_picker: Arc::clone(&server_picker),
             receiver: receiver.clone(),
             stats: Arc::clone(&stats),
             retry_queue: VecDeque::with_capacity(fluent_conf.retry_queue_len),
@@ -76,18 +79,33 @@ enum FluentdConnection {
 
 struct AsyncIoThread {
     config: Arc<FluentdClientConfig>,
+    server_picker: Arc<FluentdServerPicker>,
     receiver: Receiver<Vec<u8>>,
     stats: Arc<LogStats>,
     retry_queue: VecDeque<Vec<u8>>,
 }
 
 impl AsyncIoThread {
+    async fn connect(&self) -> Option<FluentdConnection> {
+        for addr in self.server_picker.candidates() {
+            match tokio::time::timeout(
+                self.config.connect_timeout,
+                self.config.new_connection(addr),
+            )
+            .await
+            {
+                Ok(Ok(connection)) => return Some(connection),
+                Ok(Err(e)) => warn!("failed to connect to fluentd server {addr}: {e:?}"),
+                Err(_) => warn!("timed out to connect to fluentd server {addr}"),
+            }
+        }
+        None
+    }
+
     async fn run_to_end(mut self) {
         loop {
-            match tokio::time::timeout(self.config.connect_timeout, self.config.new_connection())
-                .await
-            {
-                Ok(Ok(connection)) => {
+            match self.connect().await {
+                Some(connection) => {
                     let r = match connection {
                         FluentdConnection::Tcp(tcp_stream) => {
                             self.run_with_connection(tcp_stream).await
@@ -101,15 +119,8 @@ impl AsyncIoThread {
                         Err(e) => warn!("lost connection to fluentd: {e:?}"),
                     }
                 }
-                Ok(Err(e)) => {
-                    warn!("failed to connect to fluentd server: {e:?}");
-                    match self.run_without_connection().await {
-                        Ok(_) => break,
-                        Err(e) => warn!("{e:?}"),
-                    }
-                }
-                Err(_) => {
-                    warn!("timed out to connect to fluentd server");
+                None => {
+                    warn!("no fluentd server available");
                     match self.run_without_connection().await {
                         Ok(_) => break,
                         Err(e) => warn!("{e:?}"),
@@ -17,5 +17,8 @@
 mod record;
 pub use record::{GeoIpAsnRecord, GeoIpCountryRecord};
 
+mod mmdb;
+pub use mmdb::{MaxmindAsnRecord, MaxmindCountryRecord, MaxmindDb};
+
 pub mod store;
 pub mod vendor;
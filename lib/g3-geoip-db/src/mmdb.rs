@@ -0,0 +1,82 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::IpAddr;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use ip_network::IpNetwork;
+use maxminddb::{geoip2, Mmap, Reader};
+
+use g3_geoip_types::{ContinentCode, IsoCountryCode};
+
+/// A memory mapped MaxMind GeoLite2 / GeoIP2 database
+pub struct MaxmindDb {
+    reader: Reader<Mmap>,
+}
+
+impl MaxmindDb {
+    pub fn open(file: &Path) -> anyhow::Result<Self> {
+        let reader = Reader::open_mmap(file)
+            .map_err(|e| anyhow!("failed to open mmdb file {}: {e}", file.display()))?;
+        Ok(MaxmindDb { reader })
+    }
+
+    pub fn database_type(&self) -> &str {
+        &self.reader.metadata.database_type
+    }
+
+    /// Lookup country and continent, which works with both City and Country databases
+    pub fn lookup_country(&self, ip: IpAddr) -> Option<(IpNetwork, MaxmindCountryRecord)> {
+        let (city, prefix) = self.reader.lookup_prefix::<geoip2::City>(ip).ok()?;
+        let net = IpNetwork::new_truncate(ip, prefix as u8).ok()?;
+        let country = city
+            .country
+            .or(city.registered_country)
+            .and_then(|c| c.iso_code)
+            .and_then(|s| IsoCountryCode::from_str(s).ok());
+        let continent = city
+            .continent
+            .and_then(|c| c.code)
+            .and_then(|s| ContinentCode::from_str(s).ok())
+            .or_else(|| country.map(|c| c.continent()));
+        Some((net, MaxmindCountryRecord { country, continent }))
+    }
+
+    pub fn lookup_asn(&self, ip: IpAddr) -> Option<(IpNetwork, MaxmindAsnRecord<'_>)> {
+        let (asn, prefix) = self.reader.lookup_prefix::<geoip2::Asn>(ip).ok()?;
+        let net = IpNetwork::new_truncate(ip, prefix as u8).ok()?;
+        let number = asn.autonomous_system_number?;
+        Some((
+            net,
+            MaxmindAsnRecord {
+                number,
+                name: asn.autonomous_system_organization,
+            },
+        ))
+    }
+}
+
+pub struct MaxmindCountryRecord {
+    pub country: Option<IsoCountryCode>,
+    pub continent: Option<ContinentCode>,
+}
+
+pub struct MaxmindAsnRecord<'a> {
+    pub number: u32,
+    pub name: Option<&'a str>,
+}
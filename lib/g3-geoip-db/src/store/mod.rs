@@ -19,12 +19,15 @@ use std::sync::{Arc, LazyLock};
 use arc_swap::ArcSwapOption;
 use ip_network_table::IpNetworkTable;
 
-use crate::{GeoIpAsnRecord, GeoIpCountryRecord};
+use crate::{GeoIpAsnRecord, GeoIpCountryRecord, MaxmindDb};
 
 static GEO_COUNTRY_DB: LazyLock<ArcSwapOption<IpNetworkTable<GeoIpCountryRecord>>> =
     LazyLock::new(|| ArcSwapOption::new(None));
 static GEO_ASN_DB: LazyLock<ArcSwapOption<IpNetworkTable<GeoIpAsnRecord>>> =
     LazyLock::new(|| ArcSwapOption::new(None));
+static MMDB_CITY_DB: LazyLock<ArcSwapOption<MaxmindDb>> =
+    LazyLock::new(|| ArcSwapOption::new(None));
+static MMDB_ASN_DB: LazyLock<ArcSwapOption<MaxmindDb>> = LazyLock::new(|| ArcSwapOption::new(None));
 
 pub fn load_country() -> Option<Arc<IpNetworkTable<GeoIpCountryRecord>>> {
     GEO_COUNTRY_DB.load_full()
@@ -41,3 +44,19 @@ pub fn load_asn() -> Option<Arc<IpNetworkTable<GeoIpAsnRecord>>> {
 pub fn store_asn(db: Arc<IpNetworkTable<GeoIpAsnRecord>>) {
     GEO_ASN_DB.store(Some(db));
 }
+
+pub fn load_mmdb_city() -> Option<Arc<MaxmindDb>> {
+    MMDB_CITY_DB.load_full()
+}
+
+pub fn store_mmdb_city(db: Arc<MaxmindDb>) {
+    MMDB_CITY_DB.store(Some(db));
+}
+
+pub fn load_mmdb_asn() -> Option<Arc<MaxmindDb>> {
+    MMDB_ASN_DB.load_full()
+}
+
+pub fn store_mmdb_asn(db: Arc<MaxmindDb>) {
+    MMDB_ASN_DB.store(Some(db));
+}
@@ -19,7 +19,7 @@ use std::sync::Arc;
 
 use hickory_proto::ProtoError;
 use quinn::crypto::rustls::QuicClientConfig;
-use quinn::{Connection, Endpoint, EndpointConfig, TokioRuntime};
+use quinn::{Connection, Endpoint, EndpointConfig, TokioRuntime, TransportConfig};
 use rustls::ClientConfig;
 
 pub(crate) async fn quic_connect(
@@ -28,6 +28,8 @@ pub(crate) async fn quic_connect(
     mut tls_config: ClientConfig,
     tls_name: &str,
     alpn_protocol: &'static [u8],
+    transport: Option<TransportConfig>,
+    enable_0rtt: bool,
 ) -> Result<Connection, ProtoError> {
     let bind_addr = bind_addr.unwrap_or_else(|| match name_server {
         SocketAddr::V4(_) => SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0),
@@ -42,16 +44,33 @@ pub(crate) async fn quic_connect(
     if tls_config.alpn_protocols.is_empty() {
         tls_config.alpn_protocols = vec![alpn_protocol.to_vec()];
     }
+    if enable_0rtt {
+        tls_config.enable_early_data = true;
+    }
     let quic_config = QuicClientConfig::try_from(tls_config)
         .map_err(|e| format!("invalid quic tls config: {e}"))?;
-    let client_config = quinn::ClientConfig::new(Arc::new(quic_config));
-    // TODO set transport config
+    let mut client_config = quinn::ClientConfig::new(Arc::new(quic_config));
+    if let Some(transport) = transport {
+        client_config.transport_config(Arc::new(transport));
+    }
     endpoint.set_default_client_config(client_config);
 
-    let connection = endpoint
+    let connecting = endpoint
         .connect(name_server, tls_name)
-        .map_err(|e| format!("quinn endpoint create error: {e}"))?
-        .await
-        .map_err(|e| format!("quinn endpoint connect error: {e}"))?;
+        .map_err(|e| format!("quinn endpoint create error: {e}"))?;
+    let connection = if enable_0rtt {
+        // the session ticket is stored in the shared rustls resumption store,
+        // so 0-RTT is only possible after a previous full handshake
+        match connecting.into_0rtt() {
+            Ok((connection, _accepted)) => connection,
+            Err(connecting) => connecting
+                .await
+                .map_err(|e| format!("quinn endpoint connect error: {e}"))?,
+        }
+    } else {
+        connecting
+            .await
+            .map_err(|e| format!("quinn endpoint connect error: {e}"))?
+    };
     Ok(connection)
 }
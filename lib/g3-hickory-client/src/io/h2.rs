@@ -37,6 +37,7 @@ pub async fn connect(
     bind_addr: Option<SocketAddr>,
     tls_config: ClientConfig,
     tls_name: ServerName<'static>,
+    query_path: String,
     connect_timeout: Duration,
     request_timeout: Duration,
 ) -> Result<HttpsClientStream, ProtoError> {
@@ -70,7 +71,7 @@ pub async fn connect(
         let _ = connection.await;
     });
 
-    HttpsClientStream::new(&server_name, send_request, request_timeout)
+    HttpsClientStream::new(&server_name, &query_path, send_request, request_timeout)
 }
 
 /// A DNS client connection for DNS-over-HTTPS
@@ -86,10 +87,12 @@ pub struct HttpsClientStream {
 impl HttpsClientStream {
     pub fn new(
         name_server_name: &str,
+        query_path: &str,
         h2: SendRequest<Bytes>,
         request_timeout: Duration,
     ) -> Result<Self, ProtoError> {
-        let request_builder = HttpDnsRequestBuilder::new(Version::HTTP_2, name_server_name)?;
+        let request_builder =
+            HttpDnsRequestBuilder::new(Version::HTTP_2, name_server_name, query_path)?;
         Ok(HttpsClientStream {
             request_builder: Arc::new(request_builder),
             request_timeout,
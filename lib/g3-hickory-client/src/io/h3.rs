@@ -31,17 +31,28 @@ use rustls::ClientConfig;
 use super::http::request::HttpDnsRequestBuilder;
 use super::http::response::HttpDnsResponse;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn connect(
     name_server: SocketAddr,
     bind_addr: Option<SocketAddr>,
     tls_config: ClientConfig,
     tls_name: String,
+    query_path: String,
+    enable_0rtt: bool,
     connect_timeout: Duration,
     request_timeout: Duration,
 ) -> Result<H3ClientStream, ProtoError> {
     let connection = tokio::time::timeout(
         connect_timeout,
-        crate::connect::quinn::quic_connect(name_server, bind_addr, tls_config, &tls_name, b"h3"),
+        crate::connect::quinn::quic_connect(
+            name_server,
+            bind_addr,
+            tls_config,
+            &tls_name,
+            b"h3",
+            None,
+            enable_0rtt,
+        ),
     )
     .await
     .map_err(|_| ProtoError::from("quic connect timed out"))??;
@@ -51,7 +62,13 @@ pub async fn connect(
         .await
         .map_err(|e| format!("h3 connection failed: {e}"))?;
 
-    H3ClientStream::new(&tls_name, driver, send_request, request_timeout)
+    H3ClientStream::new(
+        &tls_name,
+        &query_path,
+        driver,
+        send_request,
+        request_timeout,
+    )
 }
 
 /// A DNS client connection for DNS-over-HTTP/3
@@ -68,11 +85,13 @@ pub struct H3ClientStream {
 impl H3ClientStream {
     pub fn new(
         name_server_name: &str,
+        query_path: &str,
         connection: Connection<h3_quinn::Connection, Bytes>,
         send_request: SendRequest<h3_quinn::OpenStreams, Bytes>,
         request_timeout: Duration,
     ) -> Result<Self, ProtoError> {
-        let request_builder = HttpDnsRequestBuilder::new(Version::HTTP_3, name_server_name)?;
+        let request_builder =
+            HttpDnsRequestBuilder::new(Version::HTTP_3, name_server_name, query_path)?;
         Ok(H3ClientStream {
             request_builder: Arc::new(request_builder),
             request_timeout,
@@ -15,7 +15,7 @@
  */
 
 const MIME_APPLICATION_DNS: &str = "application/dns-message";
-const DNS_QUERY_PATH: &str = "/dns-query";
+pub const DEFAULT_DNS_QUERY_PATH: &str = "/dns-query";
 
 pub mod request;
 pub mod response;
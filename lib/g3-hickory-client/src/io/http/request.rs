@@ -25,14 +25,17 @@ pub struct HttpDnsRequestBuilder {
 }
 
 impl HttpDnsRequestBuilder {
-    pub fn new(version: Version, host: &str) -> Result<Self, ProtoError> {
+    pub fn new(version: Version, host: &str, path: &str) -> Result<Self, ProtoError> {
         let mut parts = Parts::default();
         parts.scheme = Some(Scheme::HTTPS);
         parts.authority = Some(
             Authority::from_str(host)
                 .map_err(|e| ProtoError::from(format!("invalid authority: {e}")))?,
         );
-        parts.path_and_query = Some(PathAndQuery::from_static(super::DNS_QUERY_PATH));
+        parts.path_and_query = Some(
+            PathAndQuery::from_str(path)
+                .map_err(|e| ProtoError::from(format!("invalid query path: {e}")))?,
+        );
 
         let url = Uri::from_parts(parts)
             .map_err(|e| ProtoError::from(format!("uri parse error: {e}")))?;
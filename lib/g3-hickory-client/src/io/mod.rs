@@ -22,3 +22,4 @@ pub mod quic;
 pub mod tls;
 
 mod http;
+pub use http::DEFAULT_DNS_QUERY_PATH;
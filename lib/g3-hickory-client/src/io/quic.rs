@@ -23,20 +23,31 @@ use bytes::Bytes;
 use futures_util::Stream;
 use hickory_proto::xfer::{DnsRequest, DnsRequestSender, DnsResponse, DnsResponseStream};
 use hickory_proto::{ProtoError, ProtoErrorKind};
-use quinn::{Connection, RecvStream, VarInt};
+use quinn::{Connection, RecvStream, TransportConfig, VarInt};
 use rustls::ClientConfig;
 
+#[allow(clippy::too_many_arguments)]
 pub async fn connect(
     name_server: SocketAddr,
     bind_addr: Option<SocketAddr>,
     tls_config: ClientConfig,
     tls_name: String,
+    transport: Option<TransportConfig>,
+    enable_0rtt: bool,
     connect_timeout: Duration,
     request_timeout: Duration,
 ) -> Result<QuicClientStream, ProtoError> {
     let connection = tokio::time::timeout(
         connect_timeout,
-        crate::connect::quinn::quic_connect(name_server, bind_addr, tls_config, &tls_name, b"doq"),
+        crate::connect::quinn::quic_connect(
+            name_server,
+            bind_addr,
+            tls_config,
+            &tls_name,
+            b"doq",
+            transport,
+            enable_0rtt,
+        ),
     )
     .await
     .map_err(|_| ProtoError::from("quic connect timed out"))??;
@@ -0,0 +1,235 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::BTreeSet;
+
+use hdrhistogram::{Counter, Histogram};
+use thiserror::Error;
+
+const MAX_BUCKET_COUNT: usize = 1024;
+const EXPONENTIAL_MIN_SCALE: i8 = -10;
+const EXPONENTIAL_MAX_SCALE: i8 = 20;
+
+#[derive(Debug, Error)]
+pub enum InvalidHistogramBuckets {
+    #[error("no bucket bound set")]
+    Empty,
+    #[error("too many buckets, the max allowed is {MAX_BUCKET_COUNT}")]
+    TooMany,
+    #[error("invalid range [{0}, {1}]")]
+    InvalidRange(u64, u64),
+    #[error("out of range(1-100) steps {0}")]
+    InvalidSteps(u16),
+    #[error("out of range({EXPONENTIAL_MIN_SCALE}-{EXPONENTIAL_MAX_SCALE}) scale {0}")]
+    InvalidScale(i8),
+    #[error("the max size should not be zero")]
+    ZeroMaxSize,
+}
+
+/// Bucket layout of the histogram
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HistogramBuckets {
+    /// Buckets with the explicit upper bounds
+    Explicit(Vec<u64>),
+    /// Linear spaced buckets in each power of 10, the upper bounds are pre-computed
+    LogLinear(Vec<u64>),
+    /// Base 2 exponential buckets, the same as the OpenTelemetry exponential histogram.
+    /// The scale will be reduced if there are more buckets than `max_size`.
+    Exponential { scale: i8, max_size: u16 },
+}
+
+impl HistogramBuckets {
+    pub fn explicit<T>(bounds: T) -> Result<Self, InvalidHistogramBuckets>
+    where
+        T: IntoIterator<Item = u64>,
+    {
+        let bounds: BTreeSet<u64> = bounds.into_iter().collect();
+        if bounds.is_empty() {
+            return Err(InvalidHistogramBuckets::Empty);
+        }
+        if bounds.len() > MAX_BUCKET_COUNT {
+            return Err(InvalidHistogramBuckets::TooMany);
+        }
+        Ok(HistogramBuckets::Explicit(bounds.into_iter().collect()))
+    }
+
+    /// Split each power of 10 in [min, max] into `steps` linear spaced buckets
+    pub fn log_linear(min: u64, max: u64, steps: u16) -> Result<Self, InvalidHistogramBuckets> {
+        if min == 0 || min >= max {
+            return Err(InvalidHistogramBuckets::InvalidRange(min, max));
+        }
+        if !(1..=100).contains(&steps) {
+            return Err(InvalidHistogramBuckets::InvalidSteps(steps));
+        }
+
+        let mut bounds = BTreeSet::new();
+        let mut decade = 10_u64.pow(min.ilog10());
+        'outer: loop {
+            let width = decade.saturating_mul(9);
+            for i in 1..=steps as u64 {
+                let bound = decade.saturating_add(width / steps as u64 * i);
+                if bound >= max {
+                    break 'outer;
+                }
+                if bound > min {
+                    bounds.insert(bound);
+                }
+            }
+            match decade.checked_mul(10) {
+                Some(v) => decade = v,
+                None => break,
+            }
+        }
+        bounds.insert(min);
+        bounds.insert(max);
+        if bounds.len() > MAX_BUCKET_COUNT {
+            return Err(InvalidHistogramBuckets::TooMany);
+        }
+        Ok(HistogramBuckets::LogLinear(bounds.into_iter().collect()))
+    }
+
+    pub fn exponential(scale: i8, max_size: u16) -> Result<Self, InvalidHistogramBuckets> {
+        if !(EXPONENTIAL_MIN_SCALE..=EXPONENTIAL_MAX_SCALE).contains(&scale) {
+            return Err(InvalidHistogramBuckets::InvalidScale(scale));
+        }
+        if max_size == 0 {
+            return Err(InvalidHistogramBuckets::ZeroMaxSize);
+        }
+        Ok(HistogramBuckets::Exponential { scale, max_size })
+    }
+
+    fn bounds<T: Counter>(&self, histogram: &Histogram<T>) -> Vec<u64> {
+        match self {
+            HistogramBuckets::Explicit(bounds) | HistogramBuckets::LogLinear(bounds) => {
+                bounds.clone()
+            }
+            HistogramBuckets::Exponential { scale, max_size } => {
+                exponential_bounds(*scale, *max_size, histogram.min(), histogram.max())
+            }
+        }
+    }
+
+    /// Get the cumulative count for each bucket, in the form of (upper bound, count)
+    pub(crate) fn cumulative_counts<T: Counter>(
+        &self,
+        histogram: &Histogram<T>,
+    ) -> Vec<(u64, u64)> {
+        let bounds = self.bounds(histogram);
+        let mut counts = vec![0_u64; bounds.len()];
+        for v in histogram.iter_recorded() {
+            let value = histogram.highest_equivalent(v.value_iterated_to());
+            let i = bounds.partition_point(|b| *b < value);
+            if i < counts.len() {
+                counts[i] += v.count_at_value().as_u64();
+            }
+        }
+
+        let mut total = 0_u64;
+        bounds
+            .into_iter()
+            .zip(counts)
+            .map(|(bound, count)| {
+                total += count;
+                (bound, total)
+            })
+            .collect()
+    }
+}
+
+/// Get the index of the bucket which contains the value,
+/// bucket i covers range (base^i, base^(i+1)], with base = 2^(2^-scale)
+fn exponential_index(value: u64, scale: i8) -> i64 {
+    let log2 = (value as f64).log2();
+    (log2 * 2_f64.powi(scale as i32)).ceil() as i64 - 1
+}
+
+fn exponential_bound(index: i64, scale: i8) -> u64 {
+    2_f64.powf((index + 1) as f64 / 2_f64.powi(scale as i32)) as u64
+}
+
+fn exponential_bounds(mut scale: i8, max_size: u16, min: u64, max: u64) -> Vec<u64> {
+    let mut bounds = BTreeSet::new();
+    if min == 0 {
+        // the zero bucket
+        bounds.insert(0);
+    }
+    let min = min.max(1);
+    let max = max.max(1);
+
+    let (low, high) = loop {
+        let low = exponential_index(min, scale);
+        let high = exponential_index(max, scale);
+        if high - low < max_size as i64 || scale <= EXPONENTIAL_MIN_SCALE {
+            break (low, high);
+        }
+        scale -= 1;
+    };
+    for index in low..=high {
+        bounds.insert(exponential_bound(index, scale));
+    }
+    bounds.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_linear() {
+        let buckets = HistogramBuckets::log_linear(1, 100, 3).unwrap();
+        assert_eq!(
+            buckets,
+            HistogramBuckets::LogLinear(vec![1, 4, 7, 10, 40, 70, 100])
+        );
+
+        let buckets = HistogramBuckets::log_linear(15, 200, 1).unwrap();
+        assert_eq!(buckets, HistogramBuckets::LogLinear(vec![15, 100, 200]));
+
+        assert!(HistogramBuckets::log_linear(0, 100, 3).is_err());
+        assert!(HistogramBuckets::log_linear(100, 100, 3).is_err());
+        assert!(HistogramBuckets::log_linear(1, 100, 0).is_err());
+    }
+
+    #[test]
+    fn exponential() {
+        assert_eq!(exponential_index(1, 0), -1);
+        assert_eq!(exponential_index(2, 0), 0);
+        assert_eq!(exponential_index(3, 0), 1);
+        assert_eq!(exponential_index(4, 0), 1);
+        assert_eq!(exponential_bound(1, 0), 4);
+
+        let bounds = exponential_bounds(0, 160, 1, 1000);
+        assert_eq!(bounds.first(), Some(&1));
+        assert_eq!(bounds.last(), Some(&1024));
+
+        let bounds = exponential_bounds(3, 4, 1, 1000);
+        assert!(bounds.len() <= 5);
+        assert!(*bounds.last().unwrap() >= 1000);
+    }
+
+    #[test]
+    fn cumulative_counts() {
+        let mut histogram = Histogram::<u64>::new(3).unwrap();
+        for v in [1, 5, 5, 50, 500] {
+            histogram.record(v).unwrap();
+        }
+        let buckets = HistogramBuckets::explicit([10, 100]).unwrap();
+        assert_eq!(
+            buckets.cumulative_counts(&histogram),
+            vec![(10, 3), (100, 4)]
+        );
+    }
+}
@@ -21,12 +21,13 @@ use std::time::Duration;
 use hdrhistogram::Counter;
 use tokio::runtime::Handle;
 
-use crate::{HistogramRecorder, HistogramStats, Quantile, RotatingHistogram};
+use crate::{HistogramBuckets, HistogramRecorder, HistogramStats, Quantile, RotatingHistogram};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HistogramMetricsConfig {
     quantile_list: BTreeSet<Quantile>,
     rotate_interval: Duration,
+    buckets: Option<HistogramBuckets>,
 }
 
 impl HistogramMetricsConfig {
@@ -34,6 +35,7 @@ impl HistogramMetricsConfig {
         HistogramMetricsConfig {
             quantile_list: BTreeSet::new(),
             rotate_interval: dur,
+            buckets: None,
         }
     }
 
@@ -47,6 +49,11 @@ impl HistogramMetricsConfig {
         self.rotate_interval = dur;
     }
 
+    #[inline]
+    pub fn set_buckets(&mut self, buckets: HistogramBuckets) {
+        self.buckets = Some(buckets);
+    }
+
     #[inline]
     pub fn rotate_interval(&self) -> Duration {
         self.rotate_interval
@@ -60,11 +67,15 @@ impl HistogramMetricsConfig {
         T: Counter + Send + 'static,
     {
         let (h, r) = RotatingHistogram::new(self.rotate_interval);
-        let stats = if self.quantile_list.is_empty() {
-            Arc::new(HistogramStats::default())
+        let mut stats = if self.quantile_list.is_empty() {
+            HistogramStats::default()
         } else {
-            Arc::new(HistogramStats::with_quantiles(&self.quantile_list))
+            HistogramStats::with_quantiles(&self.quantile_list)
         };
+        if let Some(buckets) = &self.buckets {
+            stats = stats.with_buckets(buckets.clone());
+        }
+        let stats = Arc::new(stats);
         h.spawn_refresh(Arc::clone(&stats), handle);
         (r, stats)
     }
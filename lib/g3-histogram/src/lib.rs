@@ -29,5 +29,8 @@ pub use stats::HistogramStats;
 mod quantile;
 pub use quantile::Quantile;
 
+mod bucket;
+pub use bucket::{HistogramBuckets, InvalidHistogramBuckets};
+
 mod config;
 pub use config::HistogramMetricsConfig;
@@ -15,11 +15,12 @@
  */
 
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
 
 use hdrhistogram::{Counter, Histogram};
 use portable_atomic::AtomicF64;
 
-use super::Quantile;
+use super::{HistogramBuckets, Quantile};
 
 pub struct HistogramQuantileStats {
     quantile: Quantile,
@@ -35,11 +36,29 @@ impl HistogramQuantileStats {
     }
 }
 
+struct HistogramBucketStats {
+    layout: HistogramBuckets,
+    /// cumulative counts in the form of (upper bound, count)
+    counts: Mutex<Vec<(u64, u64)>>,
+    total: AtomicU64,
+}
+
+impl HistogramBucketStats {
+    fn new(layout: HistogramBuckets) -> Self {
+        HistogramBucketStats {
+            layout,
+            counts: Mutex::new(Vec::new()),
+            total: AtomicU64::new(0),
+        }
+    }
+}
+
 pub struct HistogramStats {
     min: AtomicU64,
     max: AtomicU64,
     mean: AtomicF64,
     quantile: Vec<HistogramQuantileStats>,
+    buckets: Option<HistogramBucketStats>,
 }
 
 impl HistogramStats {
@@ -49,6 +68,7 @@ impl HistogramStats {
             max: AtomicU64::new(0),
             mean: AtomicF64::new(0.0_f64),
             quantile: Vec::with_capacity(8),
+            buckets: None,
         }
     }
 
@@ -68,6 +88,11 @@ impl HistogramStats {
         self
     }
 
+    pub fn with_buckets(mut self, layout: HistogramBuckets) -> Self {
+        self.buckets = Some(HistogramBucketStats::new(layout));
+        self
+    }
+
     pub fn update<T: Counter>(&self, histogram: &Histogram<T>) {
         self.min.store(histogram.min(), Ordering::Relaxed);
         self.max.store(histogram.max(), Ordering::Relaxed);
@@ -78,6 +103,11 @@ impl HistogramStats {
                 Ordering::Relaxed,
             );
         }
+        if let Some(buckets) = &self.buckets {
+            let counts = buckets.layout.cumulative_counts(histogram);
+            *buckets.counts.lock().unwrap() = counts;
+            buckets.total.store(histogram.len(), Ordering::Relaxed);
+        }
     }
 
     pub fn foreach_stat<F>(&self, mut call: F)
@@ -95,6 +125,24 @@ impl HistogramStats {
             call(Some(q.quantile.value()), q.quantile.as_str(), v as f64);
         }
     }
+
+    /// Iterate the cumulative count of each bucket, with the upper bound as the first argument.
+    /// The last bucket will have the upper bound "+Inf".
+    pub fn foreach_bucket<F>(&self, mut call: F)
+    where
+        F: FnMut(&str, u64),
+    {
+        let Some(buckets) = &self.buckets else {
+            return;
+        };
+        let mut buffer = itoa::Buffer::new();
+        let counts = buckets.counts.lock().unwrap();
+        for (bound, count) in counts.iter() {
+            call(buffer.format(*bound), *count);
+        }
+        drop(counts);
+        call("+Inf", buckets.total.load(Ordering::Relaxed));
+    }
 }
 
 impl Default for HistogramStats {
@@ -0,0 +1,281 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::{self, Write};
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{ready, Context, Poll};
+
+use http::header;
+use tokio::io::{AsyncBufRead, AsyncRead, ReadBuf};
+
+use g3_types::net::HttpHeaderMap;
+
+const DECODE_INPUT_BUFFER_SIZE: usize = 4096;
+const BROTLI_DECODE_BUFFER_SIZE: usize = 4096;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HttpContentEncoding {
+    Brotli,
+    Zstd,
+}
+
+impl HttpContentEncoding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpContentEncoding::Brotli => "br",
+            HttpContentEncoding::Zstd => "zstd",
+        }
+    }
+}
+
+/// Get the single supported content encoding set in the headers
+pub(crate) fn get_content_encoding(headers: &HttpHeaderMap) -> Option<HttpContentEncoding> {
+    let mut values = headers.get_all(header::CONTENT_ENCODING).iter();
+    let value = values.next()?;
+    if values.next().is_some() {
+        return None;
+    }
+    HttpContentEncoding::from_str(value.to_str()).ok()
+}
+
+impl FromStr for HttpContentEncoding {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "br" => Ok(HttpContentEncoding::Brotli),
+            "zstd" => Ok(HttpContentEncoding::Zstd),
+            _ => Err(()),
+        }
+    }
+}
+
+enum ContentDecoder {
+    Brotli(Box<brotli_decompressor::DecompressorWriter<Vec<u8>>>),
+    Zstd(Box<zstd::stream::write::Decoder<'static, Vec<u8>>>),
+}
+
+impl ContentDecoder {
+    fn new(encoding: HttpContentEncoding) -> io::Result<Self> {
+        match encoding {
+            HttpContentEncoding::Brotli => {
+                let decoder = brotli_decompressor::DecompressorWriter::new(
+                    Vec::new(),
+                    BROTLI_DECODE_BUFFER_SIZE,
+                );
+                Ok(ContentDecoder::Brotli(Box::new(decoder)))
+            }
+            HttpContentEncoding::Zstd => {
+                let decoder = zstd::stream::write::Decoder::new(Vec::new())?;
+                Ok(ContentDecoder::Zstd(Box::new(decoder)))
+            }
+        }
+    }
+
+    fn decode(&mut self, input: &[u8]) -> io::Result<()> {
+        match self {
+            ContentDecoder::Brotli(d) => {
+                d.write_all(input)?;
+                d.flush()
+            }
+            ContentDecoder::Zstd(d) => {
+                d.write_all(input)?;
+                d.flush()
+            }
+        }
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        match self {
+            ContentDecoder::Brotli(d) => d.close(),
+            ContentDecoder::Zstd(d) => d.flush(),
+        }
+    }
+
+    fn output_mut(&mut self) -> &mut Vec<u8> {
+        match self {
+            ContentDecoder::Brotli(d) => d.get_mut(),
+            ContentDecoder::Zstd(d) => d.get_mut(),
+        }
+    }
+}
+
+/// Decode the content encoded HTTP body, the input should be the already de-chunked body data
+pub struct HttpContentDecodeReader<R> {
+    inner: R,
+    decoder: ContentDecoder,
+    in_buf: Box<[u8]>,
+    out_buf: Vec<u8>,
+    out_offset: usize,
+    read_done: bool,
+    finished: bool,
+}
+
+impl<R> HttpContentDecodeReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    pub fn new(inner: R, encoding: HttpContentEncoding) -> io::Result<Self> {
+        let decoder = ContentDecoder::new(encoding)?;
+        Ok(HttpContentDecodeReader {
+            inner,
+            decoder,
+            in_buf: vec![0u8; DECODE_INPUT_BUFFER_SIZE].into_boxed_slice(),
+            out_buf: Vec::new(),
+            out_offset: 0,
+            read_done: false,
+            finished: false,
+        })
+    }
+
+    pub fn finished(&self) -> bool {
+        self.finished && self.out_offset >= self.out_buf.len()
+    }
+
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn take_output(&mut self) {
+        // the drained buffer will be reused by the decoder
+        self.out_buf.clear();
+        std::mem::swap(self.decoder.output_mut(), &mut self.out_buf);
+        self.out_offset = 0;
+    }
+}
+
+impl<R> AsyncBufRead for HttpContentDecodeReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.out_offset < this.out_buf.len() {
+                return Poll::Ready(Ok(&this.out_buf[this.out_offset..]));
+            }
+            if this.finished {
+                return Poll::Ready(Ok(&[]));
+            }
+
+            if this.read_done {
+                this.decoder.finish()?;
+                this.finished = true;
+            } else {
+                let mut buf = ReadBuf::new(&mut this.in_buf);
+                ready!(Pin::new(&mut this.inner).poll_read(cx, &mut buf))?;
+                let nr = buf.filled().len();
+                if nr == 0 {
+                    this.read_done = true;
+                    continue;
+                }
+                this.decoder.decode(&this.in_buf[..nr])?;
+            }
+            this.take_output();
+        }
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.out_offset = (this.out_offset + amt).min(this.out_buf.len());
+    }
+}
+
+impl<R> AsyncRead for HttpContentDecodeReader<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let data = ready!(self.as_mut().poll_fill_buf(cx))?;
+        let len = data.len().min(buf.remaining());
+        buf.put_slice(&data[..len]);
+        self.consume(len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+
+    const BROTLI_DATA: &[u8] = &[
+        27, 24, 0, 0, 196, 113, 108, 221, 252, 238, 234, 69, 68, 234, 65, 22, 161, 68, 22, 217, 76,
+        30, 79, 139, 138, 48, 173, 143, 1,
+    ];
+    const ZSTD_DATA: &[u8] = &[
+        40, 181, 47, 253, 4, 88, 185, 0, 0, 104, 101, 108, 108, 111, 32, 119, 111, 114, 108, 100,
+        44, 32, 104, 101, 108, 108, 111, 32, 122, 115, 116, 100, 104, 218, 58, 218,
+    ];
+
+    #[test]
+    fn parse_encoding() {
+        assert_eq!(
+            HttpContentEncoding::from_str("br").unwrap(),
+            HttpContentEncoding::Brotli
+        );
+        assert_eq!(
+            HttpContentEncoding::from_str(" ZSTD ").unwrap(),
+            HttpContentEncoding::Zstd
+        );
+        assert!(HttpContentEncoding::from_str("gzip").is_err());
+    }
+
+    #[tokio::test]
+    async fn decode_brotli() {
+        let stream = tokio_test::io::Builder::new()
+            .read(&BROTLI_DATA[..10])
+            .read(&BROTLI_DATA[10..])
+            .build();
+        let mut reader = HttpContentDecodeReader::new(stream, HttpContentEncoding::Brotli).unwrap();
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf.as_slice(), b"hello world, hello brotli");
+        assert!(reader.finished());
+    }
+
+    #[tokio::test]
+    async fn decode_brotli_truncated() {
+        let stream = tokio_test::io::Builder::new()
+            .read(&BROTLI_DATA[..10])
+            .build();
+        let mut reader = HttpContentDecodeReader::new(stream, HttpContentEncoding::Brotli).unwrap();
+
+        let mut buf = Vec::new();
+        assert!(reader.read_to_end(&mut buf).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn decode_zstd() {
+        let stream = tokio_test::io::Builder::new()
+            .read(&ZSTD_DATA[..10])
+            .read(&ZSTD_DATA[10..])
+            .build();
+        let mut reader = HttpContentDecodeReader::new(stream, HttpContentEncoding::Zstd).unwrap();
+
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).await.unwrap();
+        assert_eq!(buf.as_slice(), b"hello world, hello zstd");
+        assert!(reader.finished());
+    }
+}
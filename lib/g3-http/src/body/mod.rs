@@ -41,3 +41,10 @@ pub use chunked_decoder::ChunkedDataDecodeReader;
 
 mod trailer_reader;
 pub use trailer_reader::{TrailerReadError, TrailerReader};
+
+#[cfg(feature = "decompress")]
+mod content_decoder;
+#[cfg(feature = "decompress")]
+pub(crate) use content_decoder::get_content_encoding;
+#[cfg(feature = "decompress")]
+pub use content_decoder::{HttpContentDecodeReader, HttpContentEncoding};
@@ -25,6 +25,7 @@ use g3_types::net::{HttpHeaderMap, HttpHeaderValue};
 use super::HttpResponseParseError;
 use crate::{HttpHeaderLine, HttpLineParseError, HttpStatusLine};
 
+#[derive(Clone)]
 pub struct HttpAdaptedResponse {
     pub version: Version,
     pub status: StatusCode,
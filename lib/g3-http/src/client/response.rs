@@ -18,7 +18,7 @@ use std::io::Write;
 use std::str::FromStr;
 
 use bytes::BufMut;
-use http::{header, HeaderName, Method, Version};
+use http::{header, HeaderMap, HeaderName, Method, StatusCode, Version};
 use tokio::io::AsyncBufRead;
 
 use g3_io_ext::LimitedBufReadExt;
@@ -26,6 +26,8 @@ use g3_types::net::{HttpHeaderMap, HttpHeaderValue};
 
 use super::{HttpAdaptedResponse, HttpResponseParseError};
 use crate::header::Connection;
+#[cfg(feature = "decompress")]
+use crate::HttpContentEncoding;
 use crate::{HttpBodyType, HttpHeaderLine, HttpLineParseError, HttpStatusLine};
 
 pub struct HttpForwardRemoteResponse {
@@ -147,6 +149,97 @@ impl HttpForwardRemoteResponse {
         }
     }
 
+    #[cfg(feature = "decompress")]
+    pub fn content_encoding(&self) -> Option<HttpContentEncoding> {
+        crate::body::get_content_encoding(&self.end_to_end_headers)
+    }
+
+    /// Get a copy of this response to be used for the decoded body,
+    /// with Content-Encoding and Content-Length headers removed
+    #[cfg(feature = "decompress")]
+    pub fn strip_content_encoding(&self) -> Self {
+        let mut end_to_end_headers = self.end_to_end_headers.clone();
+        end_to_end_headers.remove(header::CONTENT_ENCODING);
+        end_to_end_headers.remove(header::CONTENT_LENGTH);
+        HttpForwardRemoteResponse {
+            version: self.version,
+            code: self.code,
+            reason: self.reason.clone(),
+            end_to_end_headers,
+            hop_by_hop_headers: self.hop_by_hop_headers.clone(),
+            original_connection_name: self.original_connection_name.clone(),
+            extra_connection_headers: self.extra_connection_headers.clone(),
+            origin_header_size: self.origin_header_size,
+            keep_alive: self.keep_alive,
+            content_length: 0,
+            chunked_transfer: self.chunked_transfer,
+            has_transfer_encoding: self.has_transfer_encoding,
+            has_content_length: false,
+            has_keep_alive: self.has_keep_alive,
+        }
+    }
+
+    /// build a HTTP/1.x response from a standard response header, such as the one received in h2
+    pub fn from_standard(
+        version: Version,
+        status: StatusCode,
+        headers: &HeaderMap,
+        method: &Method,
+        keep_alive: bool,
+    ) -> Result<Self, HttpResponseParseError> {
+        let reason = status.canonical_reason().unwrap_or_default().to_string();
+        let mut rsp = HttpForwardRemoteResponse::new(version, status.as_u16(), reason);
+        rsp.keep_alive = keep_alive;
+
+        let mut header_size = 0;
+        for (name, value) in headers {
+            match name.as_str() {
+                // connection specific headers, should not be present in h2 but let's drop them
+                "connection" | "keep-alive" | "proxy-connection" | "transfer-encoding"
+                | "upgrade" | "te" => continue,
+                "content-length" => {
+                    let content_length = value
+                        .to_str()
+                        .ok()
+                        .and_then(|v| u64::from_str(v).ok())
+                        .ok_or(HttpResponseParseError::InvalidContentLength)?;
+                    if rsp.has_content_length && rsp.content_length != content_length {
+                        return Err(HttpResponseParseError::InvalidContentLength);
+                    }
+                    rsp.has_content_length = true;
+                    rsp.content_length = content_length;
+                }
+                _ => {}
+            }
+
+            let value = value
+                .to_str()
+                .ok()
+                .and_then(|v| HttpHeaderValue::from_str(v).ok())
+                .ok_or(HttpResponseParseError::InvalidHeaderLine(
+                    HttpLineParseError::InvalidHeaderValue,
+                ))?;
+            header_size += name.as_str().len() + value.as_bytes().len() + 4;
+            rsp.end_to_end_headers.append(name.clone(), value);
+        }
+        rsp.origin_header_size = header_size;
+
+        if !rsp.expect_no_body(method) && !rsp.has_content_length {
+            if version == Version::HTTP_11 {
+                rsp.hop_by_hop_headers.insert(
+                    header::TRANSFER_ENCODING,
+                    HttpHeaderValue::from_static("chunked"),
+                );
+                rsp.chunked_transfer = true;
+                rsp.has_transfer_encoding = true;
+            } else {
+                // read to end and close the connection
+                rsp.keep_alive = false;
+            }
+        }
+        Ok(rsp)
+    }
+
     pub fn origin_header_size(&self) -> usize {
         self.origin_header_size
     }
@@ -155,6 +248,12 @@ impl HttpForwardRemoteResponse {
         self.keep_alive
     }
 
+    /// get the extra header names listed in the Connection header
+    #[inline]
+    pub fn extra_connection_headers(&self) -> &[HeaderName] {
+        &self.extra_connection_headers
+    }
+
     pub fn set_no_keep_alive(&mut self) {
         if self.has_keep_alive {
             self.hop_by_hop_headers
@@ -463,4 +562,210 @@ mod tests {
         assert!(!rsp.keep_alive());
         assert_eq!(rsp.body_type(&method), Some(HttpBodyType::ReadUntilEnd));
     }
+
+    #[test]
+    fn from_standard() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_TYPE, "text/plain".parse().unwrap());
+        let method = Method::GET;
+
+        let rsp = HttpForwardRemoteResponse::from_standard(
+            Version::HTTP_11,
+            StatusCode::OK,
+            &headers,
+            &method,
+            true,
+        )
+        .unwrap();
+        assert_eq!(rsp.code, 200);
+        assert!(rsp.keep_alive());
+        assert_eq!(rsp.body_type(&method), Some(HttpBodyType::Chunked));
+
+        let rsp = HttpForwardRemoteResponse::from_standard(
+            Version::HTTP_10,
+            StatusCode::OK,
+            &headers,
+            &method,
+            true,
+        )
+        .unwrap();
+        assert!(!rsp.keep_alive());
+        assert_eq!(rsp.body_type(&method), Some(HttpBodyType::ReadUntilEnd));
+
+        headers.insert(header::CONTENT_LENGTH, "4".parse().unwrap());
+        headers.insert(header::CONNECTION, "close".parse().unwrap());
+        let rsp = HttpForwardRemoteResponse::from_standard(
+            Version::HTTP_11,
+            StatusCode::OK,
+            &headers,
+            &method,
+            true,
+        )
+        .unwrap();
+        assert!(rsp.keep_alive());
+        assert!(!rsp.end_to_end_headers.contains_key(header::CONNECTION));
+        assert_eq!(rsp.body_type(&method), Some(HttpBodyType::ContentLength(4)));
+    }
+
+    #[test]
+    fn from_standard_no_body() {
+        let headers = HeaderMap::new();
+
+        let method = Method::HEAD;
+        let rsp = HttpForwardRemoteResponse::from_standard(
+            Version::HTTP_11,
+            StatusCode::OK,
+            &headers,
+            &method,
+            true,
+        )
+        .unwrap();
+        assert!(rsp.keep_alive());
+        assert!(!rsp
+            .hop_by_hop_headers
+            .contains_key(header::TRANSFER_ENCODING));
+        assert_eq!(rsp.body_type(&method), None);
+
+        let method = Method::GET;
+        for status in [StatusCode::NO_CONTENT, StatusCode::NOT_MODIFIED] {
+            let rsp = HttpForwardRemoteResponse::from_standard(
+                Version::HTTP_10,
+                status,
+                &headers,
+                &method,
+                true,
+            )
+            .unwrap();
+            assert!(rsp.keep_alive());
+            assert_eq!(rsp.body_type(&method), None);
+        }
+    }
+
+    #[test]
+    fn from_standard_strip_connection_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::TRANSFER_ENCODING, "chunked".parse().unwrap());
+        headers.insert(header::UPGRADE, "websocket".parse().unwrap());
+        headers.insert(header::TE, "trailers".parse().unwrap());
+        headers.insert("keep-alive", "timeout=5".parse().unwrap());
+        headers.insert("proxy-connection", "keep-alive".parse().unwrap());
+        headers.insert(header::SERVER, "test".parse().unwrap());
+        headers.append(header::SET_COOKIE, "a=1".parse().unwrap());
+        headers.append(header::SET_COOKIE, "b=2".parse().unwrap());
+        let method = Method::GET;
+
+        let rsp = HttpForwardRemoteResponse::from_standard(
+            Version::HTTP_11,
+            StatusCode::NOT_FOUND,
+            &headers,
+            &method,
+            true,
+        )
+        .unwrap();
+        assert_eq!(rsp.code, 404);
+        assert_eq!(rsp.reason, "Not Found");
+        assert!(!rsp
+            .end_to_end_headers
+            .contains_key(header::TRANSFER_ENCODING));
+        assert!(!rsp.end_to_end_headers.contains_key(header::UPGRADE));
+        assert!(!rsp.end_to_end_headers.contains_key(header::TE));
+        assert!(!rsp.end_to_end_headers.contains_key("keep-alive"));
+        assert!(!rsp.end_to_end_headers.contains_key("proxy-connection"));
+        assert!(rsp.end_to_end_headers.contains_key(header::SERVER));
+        assert_eq!(
+            rsp.end_to_end_headers
+                .get_all(header::SET_COOKIE)
+                .iter()
+                .count(),
+            2
+        );
+        // the chunked encoding is added by us as no content-length is present
+        assert_eq!(rsp.body_type(&method), Some(HttpBodyType::Chunked));
+
+        let data = String::from_utf8(rsp.serialize()).unwrap().to_lowercase();
+        assert!(data.starts_with("http/1.1 404 not found\r\n"));
+        assert!(data.contains("transfer-encoding: chunked\r\n"));
+        assert!(data.contains("set-cookie: a=1\r\n"));
+        assert!(data.contains("set-cookie: b=2\r\n"));
+        assert!(!data.contains("upgrade"));
+        assert!(data.ends_with("\r\n\r\n"));
+    }
+
+    #[test]
+    fn from_standard_content_length() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_LENGTH, "10".parse().unwrap());
+        let method = Method::GET;
+
+        let rsp = HttpForwardRemoteResponse::from_standard(
+            Version::HTTP_10,
+            StatusCode::OK,
+            &headers,
+            &method,
+            true,
+        )
+        .unwrap();
+        assert!(rsp.keep_alive());
+        assert_eq!(
+            rsp.body_type(&method),
+            Some(HttpBodyType::ContentLength(10))
+        );
+
+        let data = String::from_utf8(rsp.serialize()).unwrap().to_lowercase();
+        assert!(data.contains("content-length: 10\r\n"));
+        assert!(!data.contains("transfer-encoding"));
+
+        headers.append(header::CONTENT_LENGTH, "10".parse().unwrap());
+        let rsp = HttpForwardRemoteResponse::from_standard(
+            Version::HTTP_11,
+            StatusCode::OK,
+            &headers,
+            &method,
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            rsp.body_type(&method),
+            Some(HttpBodyType::ContentLength(10))
+        );
+
+        headers.append(header::CONTENT_LENGTH, "11".parse().unwrap());
+        assert!(HttpForwardRemoteResponse::from_standard(
+            Version::HTTP_11,
+            StatusCode::OK,
+            &headers,
+            &method,
+            true,
+        )
+        .is_err());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_LENGTH, "abc".parse().unwrap());
+        assert!(HttpForwardRemoteResponse::from_standard(
+            Version::HTTP_11,
+            StatusCode::OK,
+            &headers,
+            &method,
+            true,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn from_standard_no_keep_alive() {
+        let headers = HeaderMap::new();
+        let method = Method::GET;
+
+        let rsp = HttpForwardRemoteResponse::from_standard(
+            Version::HTTP_11,
+            StatusCode::OK,
+            &headers,
+            &method,
+            false,
+        )
+        .unwrap();
+        assert!(!rsp.keep_alive());
+        let data = String::from_utf8(rsp.serialize()).unwrap().to_lowercase();
+        assert!(data.contains("connection: close\r\n"));
+    }
 }
@@ -26,6 +26,8 @@ use g3_types::net::{HttpHeaderMap, HttpHeaderValue, HttpUpgradeToken};
 
 use super::{HttpAdaptedResponse, HttpResponseParseError};
 use crate::header::Connection;
+#[cfg(feature = "decompress")]
+use crate::HttpContentEncoding;
 use crate::{HttpBodyType, HttpHeaderLine, HttpLineParseError, HttpStatusLine};
 
 pub struct HttpTransparentResponse {
@@ -157,6 +159,38 @@ impl HttpTransparentResponse {
         }
     }
 
+    #[cfg(feature = "decompress")]
+    pub fn content_encoding(&self) -> Option<HttpContentEncoding> {
+        crate::body::get_content_encoding(&self.end_to_end_headers)
+    }
+
+    /// Get a copy of this response to be used for the decoded body,
+    /// with Content-Encoding and Content-Length headers removed
+    #[cfg(feature = "decompress")]
+    pub fn strip_content_encoding(&self) -> Self {
+        let mut end_to_end_headers = self.end_to_end_headers.clone();
+        end_to_end_headers.remove(header::CONTENT_ENCODING);
+        end_to_end_headers.remove(header::CONTENT_LENGTH);
+        HttpTransparentResponse {
+            version: self.version,
+            code: self.code,
+            reason: self.reason.clone(),
+            end_to_end_headers,
+            hop_by_hop_headers: self.hop_by_hop_headers.clone(),
+            original_connection_name: self.original_connection_name.clone(),
+            extra_connection_headers: self.extra_connection_headers.clone(),
+            origin_header_size: self.origin_header_size,
+            keep_alive: self.keep_alive,
+            connection_upgrade: self.connection_upgrade,
+            upgrade: self.upgrade.clone(),
+            content_length: 0,
+            chunked_transfer: self.chunked_transfer,
+            has_transfer_encoding: self.has_transfer_encoding,
+            has_content_length: false,
+            has_keep_alive: self.has_keep_alive,
+        }
+    }
+
     pub fn keep_alive(&self) -> bool {
         self.keep_alive
     }
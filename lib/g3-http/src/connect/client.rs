@@ -31,7 +31,7 @@ where
     let mut req = HttpConnectRequest::new(addr, &[]);
 
     match auth {
-        HttpAuth::None => {}
+        HttpAuth::None | HttpAuth::Negotiate(_) | HttpAuth::Bearer(_) => {}
         HttpAuth::Basic(a) => {
             let line = crate::header::proxy_authorization_basic(&a.username, &a.password);
             req.append_dyn_header(line);
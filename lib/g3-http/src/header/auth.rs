@@ -33,6 +33,14 @@ pub fn proxy_authenticate_basic(realm: &str) -> String {
     format!("Proxy-Authenticate: Basic realm=\"{realm}\"\r\n")
 }
 
+pub fn proxy_authenticate_negotiate() -> String {
+    "Proxy-Authenticate: Negotiate\r\n".to_string()
+}
+
+pub fn proxy_authenticate_bearer(realm: &str) -> String {
+    format!("Proxy-Authenticate: Bearer realm=\"{realm}\"\r\n")
+}
+
 pub fn www_authenticate_basic(realm: &str) -> String {
     format!("WWW-Authenticate: Basic realm=\"{realm}\"\r\n")
 }
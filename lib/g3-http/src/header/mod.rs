@@ -15,7 +15,10 @@
  */
 
 mod auth;
-pub use auth::{proxy_authenticate_basic, proxy_authorization_basic, www_authenticate_basic};
+pub use auth::{
+    proxy_authenticate_basic, proxy_authenticate_bearer, proxy_authenticate_negotiate,
+    proxy_authorization_basic, www_authenticate_basic,
+};
 
 mod connection;
 pub use connection::{connection_as_bytes, Connection};
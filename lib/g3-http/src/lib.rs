@@ -25,6 +25,8 @@ pub use body::{
     HttpBodyType, PreviewData, PreviewDataState, PreviewError, StreamToChunkedTransfer,
     TrailerReadError, TrailerReader,
 };
+#[cfg(feature = "decompress")]
+pub use body::{HttpContentDecodeReader, HttpContentEncoding};
 
 pub mod client;
 pub mod connect;
@@ -161,6 +161,12 @@ impl HttpProxyClientRequest {
         self.keep_alive
     }
 
+    /// get the extra header names listed in the Connection header
+    #[inline]
+    pub fn extra_connection_headers(&self) -> &[HeaderName] {
+        &self.extra_connection_headers
+    }
+
     pub fn body_type(&self) -> Option<HttpBodyType> {
         if self.chunked_transfer {
             Some(HttpBodyType::Chunked)
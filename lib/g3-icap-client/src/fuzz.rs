@@ -0,0 +1,50 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Entry points for fuzzing the ICAP response parsers.
+
+use std::collections::BTreeSet;
+
+use tokio::io::AsyncBufRead;
+
+use crate::reqmod::ReqmodResponse;
+use crate::respmod::RespmodResponse;
+use crate::{IcapMethod, IcapServiceOptions};
+
+pub async fn parse_options_response<R>(reader: &mut R, max_header_size: usize) -> anyhow::Result<()>
+where
+    R: AsyncBufRead + Unpin,
+{
+    IcapServiceOptions::parse(reader, IcapMethod::Reqmod, max_header_size).await?;
+    Ok(())
+}
+
+pub async fn parse_reqmod_response<R>(reader: &mut R, max_header_size: usize) -> anyhow::Result<()>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let shared_names = BTreeSet::from(["x-client-ip".to_string()]);
+    ReqmodResponse::parse(reader, max_header_size, &shared_names).await?;
+    Ok(())
+}
+
+pub async fn parse_respmod_response<R>(reader: &mut R, max_header_size: usize) -> anyhow::Result<()>
+where
+    R: AsyncBufRead + Unpin,
+{
+    RespmodResponse::parse(reader, max_header_size).await?;
+    Ok(())
+}
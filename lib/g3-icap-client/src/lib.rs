@@ -27,5 +27,12 @@ pub use options::IcapServiceOptions;
 
 mod service;
 
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub mod fuzz;
+
 use service::{IcapClientConnection, IcapClientReader, IcapClientWriter};
-pub use service::{IcapMethod, IcapServiceClient, IcapServiceConfig};
+pub use service::{
+    IcapConnectionPoolSnapshot, IcapMethod, IcapRespmodSpoolConfig, IcapServiceClient,
+    IcapServiceConfig, IcapVerdictCacheConfig,
+};
@@ -35,6 +35,7 @@ pub(super) struct BidirectionalRecvIcapResponse<'a, I: IdleCheck> {
     pub(super) icap_client: &'a Arc<IcapServiceClient>,
     pub(super) icap_reader: &'a mut IcapClientReader,
     pub(super) idle_checker: &'a I,
+    pub(super) allow_204: bool,
 }
 
 impl<I: IdleCheck> BidirectionalRecvIcapResponse<'_, I> {
@@ -105,6 +106,7 @@ impl<I: IdleCheck> BidirectionalRecvIcapResponse<'_, I> {
         .await?;
 
         match rsp.code {
+            204 if self.allow_204 => Ok(rsp),
             204 | 206 => Err(H1ReqmodAdaptationError::IcapServerErrorResponse(
                 IcapErrorReason::InvalidResponseAfterContinue,
                 rsp.code,
@@ -0,0 +1,281 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::{IoSlice, Write};
+
+use bytes::BufMut;
+use tokio::io::{AsyncBufRead, AsyncWriteExt};
+use tokio::time::Instant;
+
+use g3_http::{H1BodyToChunkedTransfer, HttpBodyDecodeReader, HttpBodyType};
+use g3_io_ext::{IdleCheck, LimitedCopy, LimitedCopyError, LimitedWriteExt};
+
+use super::{
+    BidirectionalRecvHttpRequest, BidirectionalRecvIcapResponse, H1ReqmodAdaptationError,
+    HttpRequestAdapter, HttpRequestForAdaptation, HttpRequestUpstreamWriter,
+    ReqmodAdaptationEndState, ReqmodAdaptationRunState,
+};
+use crate::reqmod::response::ReqmodResponse;
+use crate::reqmod::IcapReqmodResponsePayload;
+
+impl<I: IdleCheck> HttpRequestAdapter<I> {
+    fn build_buffered_body_request(&self, http_header_len: usize) -> Vec<u8> {
+        let mut header = Vec::with_capacity(self.icap_client.partial_request_header.len() + 128);
+        header.extend_from_slice(&self.icap_client.partial_request_header);
+        self.push_extended_headers(&mut header);
+        // the original body can be sent again from the buffer, so 204 is acceptable here
+        header.put_slice(b"Allow: 204\r\n");
+        let _ = write!(
+            header,
+            "Encapsulated: req-hdr=0, req-body={http_header_len}\r\n",
+        );
+        header.put_slice(b"\r\n");
+        header
+    }
+
+    pub(super) async fn xfer_with_buffer<H, CR, UW>(
+        mut self,
+        state: &mut ReqmodAdaptationRunState,
+        http_request: &H,
+        clt_body_io: &mut CR,
+        body_size: usize,
+        ups_writer: &mut UW,
+    ) -> Result<ReqmodAdaptationEndState<H>, H1ReqmodAdaptationError>
+    where
+        H: HttpRequestForAdaptation,
+        CR: AsyncBufRead + Unpin,
+        UW: HttpRequestUpstreamWriter<H> + Unpin,
+    {
+        let body = self.buffer_client_body(clt_body_io, body_size).await?;
+        state.clt_read_finished = true;
+
+        let http_header = http_request.serialize_for_adapter();
+        let icap_header = self.build_buffered_body_request(http_header.len());
+
+        let icap_w = &mut self.icap_connection.writer;
+        icap_w
+            .write_all_vectored([IoSlice::new(&icap_header), IoSlice::new(&http_header)])
+            .await
+            .map_err(H1ReqmodAdaptationError::IcapServerWriteFailed)?;
+
+        let mut body_reader = body.as_slice();
+        let mut body_transfer = H1BodyToChunkedTransfer::new(
+            &mut body_reader,
+            &mut self.icap_connection.writer,
+            HttpBodyType::ContentLength(body_size as u64),
+            self.http_body_line_max_size,
+            self.copy_config,
+        );
+        let bidirectional_transfer = BidirectionalRecvIcapResponse {
+            icap_client: &self.icap_client,
+            icap_reader: &mut self.icap_connection.reader,
+            idle_checker: &self.idle_checker,
+            allow_204: true,
+        };
+        let mut rsp = bidirectional_transfer
+            .transfer_and_recv(&mut body_transfer)
+            .await?;
+        let shared_headers = rsp.take_shared_headers();
+        if !shared_headers.is_empty() {
+            state.respond_shared_headers = Some(shared_headers);
+        }
+        let body_finished = body_transfer.finished();
+
+        if rsp.code == 204 {
+            if body_finished {
+                self.icap_connection.mark_writer_finished();
+            }
+            if rsp.payload == IcapReqmodResponsePayload::NoPayload {
+                self.icap_connection.mark_reader_finished();
+            }
+            return self
+                .handle_original_http_request_with_buffered_body(
+                    state,
+                    rsp,
+                    http_request,
+                    &body,
+                    ups_writer,
+                )
+                .await;
+        }
+
+        match rsp.payload {
+            IcapReqmodResponsePayload::NoPayload => {
+                if body_finished {
+                    self.icap_connection.mark_writer_finished();
+                }
+                self.icap_connection.mark_reader_finished();
+                self.handle_icap_ok_without_payload(rsp).await
+            }
+            IcapReqmodResponsePayload::HttpRequestWithoutBody(header_size) => {
+                if body_finished {
+                    self.icap_connection.mark_writer_finished();
+                }
+                self.handle_icap_http_request_without_body(
+                    state,
+                    rsp,
+                    header_size,
+                    http_request,
+                    ups_writer,
+                )
+                .await
+            }
+            IcapReqmodResponsePayload::HttpRequestWithBody(header_size) => {
+                if body_finished {
+                    self.icap_connection.mark_writer_finished();
+                    self.handle_icap_http_request_with_body_after_transfer(
+                        state,
+                        rsp,
+                        header_size,
+                        http_request,
+                        ups_writer,
+                    )
+                    .await
+                } else {
+                    let mut bidirectional_transfer = BidirectionalRecvHttpRequest {
+                        http_body_line_max_size: self.http_body_line_max_size,
+                        http_req_add_no_via_header: self.http_req_add_no_via_header,
+                        copy_config: self.copy_config,
+                        idle_checker: &self.idle_checker,
+                        http_header_size: header_size,
+                        icap_read_finished: false,
+                    };
+                    let r = bidirectional_transfer
+                        .transfer(
+                            state,
+                            &mut body_transfer,
+                            http_request,
+                            &mut self.icap_connection.reader,
+                            ups_writer,
+                        )
+                        .await?;
+                    if body_transfer.finished() {
+                        self.icap_connection.mark_writer_finished();
+                        if bidirectional_transfer.icap_read_finished {
+                            self.icap_connection.mark_reader_finished();
+                            if rsp.keep_alive {
+                                self.icap_client.save_connection(self.icap_connection);
+                            }
+                        }
+                    }
+                    Ok(r)
+                }
+            }
+            IcapReqmodResponsePayload::HttpResponseWithoutBody(header_size) => {
+                if body_finished {
+                    self.icap_connection.mark_writer_finished();
+                }
+                self.handle_icap_http_response_without_body(rsp, header_size)
+                    .await
+                    .map(|rsp| ReqmodAdaptationEndState::HttpErrResponse(rsp, None))
+            }
+            IcapReqmodResponsePayload::HttpResponseWithBody(header_size) => {
+                if body_finished {
+                    self.icap_connection.mark_writer_finished();
+                }
+                self.handle_icap_http_response_with_body(rsp, header_size)
+                    .await
+                    .map(|(rsp, body)| ReqmodAdaptationEndState::HttpErrResponse(rsp, Some(body)))
+            }
+        }
+    }
+
+    async fn buffer_client_body<CR>(
+        &self,
+        clt_body_io: &mut CR,
+        body_size: usize,
+    ) -> Result<Vec<u8>, H1ReqmodAdaptationError>
+    where
+        CR: AsyncBufRead + Unpin,
+    {
+        let mut body = Vec::with_capacity(body_size);
+        let mut body_reader = HttpBodyDecodeReader::new_fixed_length(clt_body_io, body_size as u64);
+        let mut body_copy = LimitedCopy::new(&mut body_reader, &mut body, &self.copy_config);
+
+        let idle_duration = self.idle_checker.idle_duration();
+        let mut idle_interval =
+            tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
+        let mut idle_count = 0;
+
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut body_copy => {
+                    match r {
+                        Ok(_) => break,
+                        Err(LimitedCopyError::ReadFailed(e)) => return Err(H1ReqmodAdaptationError::HttpClientReadFailed(e)),
+                        Err(LimitedCopyError::WriteFailed(_)) => return Err(H1ReqmodAdaptationError::InternalServerError("failed to buffer http client body")),
+                    }
+                }
+                _ = idle_interval.tick() => {
+                    if body_copy.is_idle() {
+                        idle_count += 1;
+
+                        let quit = self.idle_checker.check_quit(idle_count);
+                        if quit {
+                            return Err(H1ReqmodAdaptationError::HttpClientReadIdle);
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        body_copy.reset_active();
+                    }
+
+                    if let Some(reason) = self.idle_checker.check_force_quit() {
+                        return Err(H1ReqmodAdaptationError::IdleForceQuit(reason));
+                    }
+                }
+            }
+        }
+
+        Ok(body)
+    }
+
+    async fn handle_original_http_request_with_buffered_body<H, UW>(
+        self,
+        state: &mut ReqmodAdaptationRunState,
+        icap_rsp: ReqmodResponse,
+        http_request: &H,
+        body: &[u8],
+        ups_writer: &mut UW,
+    ) -> Result<ReqmodAdaptationEndState<H>, H1ReqmodAdaptationError>
+    where
+        H: HttpRequestForAdaptation,
+        UW: HttpRequestUpstreamWriter<H> + Unpin,
+    {
+        if icap_rsp.keep_alive {
+            self.icap_client.save_connection(self.icap_connection);
+        }
+
+        ups_writer
+            .send_request_header(http_request)
+            .await
+            .map_err(H1ReqmodAdaptationError::HttpUpstreamWriteFailed)?;
+        state.mark_ups_send_header();
+        ups_writer
+            .write_all(body)
+            .await
+            .map_err(H1ReqmodAdaptationError::HttpUpstreamWriteFailed)?;
+        ups_writer
+            .flush()
+            .await
+            .map_err(H1ReqmodAdaptationError::HttpUpstreamWriteFailed)?;
+        state.mark_ups_send_all();
+
+        Ok(ReqmodAdaptationEndState::OriginalTransferred)
+    }
+}
@@ -43,6 +43,7 @@ mod recv_response;
 mod http_response;
 pub use http_response::HttpAdapterErrorResponse;
 
+mod buffered;
 mod forward_body;
 mod forward_header;
 mod preview;
@@ -175,6 +176,13 @@ impl<I: IdleCheck> HttpRequestAdapter<I> {
                     "no client http body io supplied while body type is not none",
                 ));
             };
+            if self.icap_options.support_204 {
+                if let Some(body_size) = self.icap_client.config.buffered_body_size(body_type) {
+                    return self
+                        .xfer_with_buffer(state, http_request, clt_body_io, body_size, ups_writer)
+                        .await;
+                }
+            }
             if let Some(preview_size) = self.icap_options.preview_size {
                 self.xfer_with_preview(
                     state,
@@ -25,6 +25,8 @@ mod payload;
 use payload::IcapReqmodResponsePayload;
 
 mod response;
+#[cfg(feature = "fuzzing")]
+pub(crate) use response::ReqmodResponse;
 
 pub mod h1;
 pub mod h2;
@@ -34,6 +36,8 @@ pub mod mail;
 pub mod imap;
 pub mod smtp;
 
+pub mod websocket;
+
 #[derive(Clone)]
 pub struct IcapReqmodClient {
     inner: Arc<IcapServiceClient>,
@@ -0,0 +1,224 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::io::{IoSlice, Write};
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use bytes::BufMut;
+use tokio::io::AsyncWriteExt;
+
+use g3_io_ext::LimitedWriteExt;
+use g3_types::net::UpstreamAddr;
+
+use super::IcapReqmodClient;
+use crate::reqmod::response::ReqmodResponse;
+use crate::reqmod::IcapReqmodResponsePayload;
+use crate::{IcapClientConnection, IcapServiceClient, IcapServiceOptions};
+
+pub use crate::reqmod::h1::HttpAdapterErrorResponse;
+
+mod error;
+pub use error::WebsocketAdaptationError;
+
+impl IcapReqmodClient {
+    pub async fn websocket_message_adaptor(&self) -> anyhow::Result<WebsocketMessageAdapter> {
+        let icap_client = self.inner.clone();
+        let (icap_connection, icap_options) = icap_client.fetch_connection().await?;
+        Ok(WebsocketMessageAdapter {
+            icap_client,
+            icap_connection,
+            icap_options,
+            client_addr: None,
+            client_username: None,
+        })
+    }
+}
+
+pub enum WebsocketAdaptationEndState {
+    Allowed,
+    Blocked(HttpAdapterErrorResponse),
+}
+
+/// Send a complete WebSocket data message to the ICAP server for scanning
+///
+/// The message will be encapsulated in a HTTP POST request. Modification of the message is not
+/// supported, the message will be forwarded as is unless a HTTP error response is returned.
+pub struct WebsocketMessageAdapter {
+    icap_client: Arc<IcapServiceClient>,
+    icap_connection: IcapClientConnection,
+    icap_options: Arc<IcapServiceOptions>,
+    client_addr: Option<SocketAddr>,
+    client_username: Option<Arc<str>>,
+}
+
+impl WebsocketMessageAdapter {
+    pub fn set_client_addr(&mut self, addr: SocketAddr) {
+        self.client_addr = Some(addr);
+    }
+
+    pub fn set_client_username(&mut self, user: Arc<str>) {
+        self.client_username = Some(user);
+    }
+
+    pub fn build_http_header(
+        &self,
+        upstream: &UpstreamAddr,
+        resource_name: &str,
+        from_client: bool,
+        text: bool,
+        size: usize,
+    ) -> Vec<u8> {
+        let mut header = Vec::with_capacity(256);
+        let _ = write!(header, "POST {resource_name} HTTP/1.1\r\n");
+        let _ = write!(header, "Host: {upstream}\r\n");
+        if text {
+            header.extend_from_slice(b"Content-Type: text/plain; charset=utf-8\r\n");
+        } else {
+            header.extend_from_slice(b"Content-Type: application/octet-stream\r\n");
+        }
+        if from_client {
+            header.extend_from_slice(b"X-WebSocket-Direction: client-to-server\r\n");
+        } else {
+            header.extend_from_slice(b"X-WebSocket-Direction: server-to-client\r\n");
+        }
+
+        let mut len_buf = itoa::Buffer::new();
+        let len_s = len_buf.format(size);
+
+        header.extend_from_slice(b"Content-Length: ");
+        header.extend_from_slice(len_s.as_bytes());
+        header.extend_from_slice(b"\r\n");
+
+        header.extend_from_slice(b"\r\n");
+        header
+    }
+
+    fn build_forward_all_request(&self, http_header_len: usize) -> Vec<u8> {
+        let mut header = Vec::with_capacity(self.icap_client.partial_request_header.len() + 64);
+        header.extend_from_slice(&self.icap_client.partial_request_header);
+        header.put_slice(b"X-Transformed-From: WebSocket\r\n");
+        if let Some(addr) = self.client_addr {
+            crate::serialize::add_client_addr(&mut header, addr);
+        }
+        if let Some(user) = &self.client_username {
+            crate::serialize::add_client_username(&mut header, user);
+        }
+        if self.icap_options.support_204 {
+            header.put_slice(b"Allow: 204\r\n");
+        }
+        let _ = write!(
+            header,
+            "Encapsulated: req-hdr=0, req-body={http_header_len}\r\n",
+        );
+        header.put_slice(b"\r\n");
+        header
+    }
+
+    pub async fn scan_message(
+        mut self,
+        http_header: &[u8],
+        message: &[u8],
+    ) -> Result<WebsocketAdaptationEndState, WebsocketAdaptationError> {
+        let icap_header = self.build_forward_all_request(http_header.len());
+        let chunked_header = format!("{:x}\r\n", message.len());
+
+        let icap_w = &mut self.icap_connection.writer;
+        icap_w
+            .write_all_vectored([
+                IoSlice::new(&icap_header),
+                IoSlice::new(http_header),
+                IoSlice::new(chunked_header.as_bytes()),
+                IoSlice::new(message),
+                IoSlice::new(b"\r\n0\r\n\r\n"),
+            ])
+            .await
+            .map_err(WebsocketAdaptationError::IcapServerWriteFailed)?;
+        icap_w
+            .flush()
+            .await
+            .map_err(WebsocketAdaptationError::IcapServerWriteFailed)?;
+        self.icap_connection.mark_writer_finished();
+
+        let rsp = ReqmodResponse::parse(
+            &mut self.icap_connection.reader,
+            self.icap_client.config.icap_max_header_size,
+            &self.icap_client.config.respond_shared_names,
+        )
+        .await?;
+
+        match rsp.code {
+            204 => {
+                self.icap_connection.mark_reader_finished();
+                if rsp.keep_alive {
+                    self.icap_client.save_connection(self.icap_connection);
+                }
+                Ok(WebsocketAdaptationEndState::Allowed)
+            }
+            206 => Err(WebsocketAdaptationError::IcapServerErrorResponse(
+                rsp.code, rsp.reason,
+            )),
+            n if (200..300).contains(&n) => match rsp.payload {
+                IcapReqmodResponsePayload::NoPayload => {
+                    self.icap_connection.mark_reader_finished();
+                    if rsp.keep_alive {
+                        self.icap_client.save_connection(self.icap_connection);
+                    }
+                    // there should be a payload
+                    Err(WebsocketAdaptationError::IcapServerErrorResponse(
+                        rsp.code, rsp.reason,
+                    ))
+                }
+                IcapReqmodResponsePayload::HttpRequestWithoutBody(_)
+                | IcapReqmodResponsePayload::HttpRequestWithBody(_) => {
+                    // modification is not supported, and the connection won't be reused
+                    Ok(WebsocketAdaptationEndState::Allowed)
+                }
+                IcapReqmodResponsePayload::HttpResponseWithoutBody(header_size) => {
+                    let http_rsp = HttpAdapterErrorResponse::parse(
+                        &mut self.icap_connection.reader,
+                        header_size,
+                    )
+                    .await?;
+                    self.icap_connection.mark_reader_finished();
+                    if rsp.keep_alive {
+                        self.icap_client.save_connection(self.icap_connection);
+                    }
+                    Ok(WebsocketAdaptationEndState::Blocked(http_rsp))
+                }
+                IcapReqmodResponsePayload::HttpResponseWithBody(header_size) => {
+                    let http_rsp = HttpAdapterErrorResponse::parse(
+                        &mut self.icap_connection.reader,
+                        header_size,
+                    )
+                    .await?;
+                    // the body is not needed, and the connection won't be reused
+                    Ok(WebsocketAdaptationEndState::Blocked(http_rsp))
+                }
+            },
+            _ => {
+                if rsp.payload == IcapReqmodResponsePayload::NoPayload {
+                    self.icap_connection.mark_reader_finished();
+                    if rsp.keep_alive {
+                        self.icap_client.save_connection(self.icap_connection);
+                    }
+                }
+                Err(WebsocketAdaptationError::IcapServerErrorResponse(
+                    rsp.code, rsp.reason,
+                ))
+            }
+        }
+    }
+}
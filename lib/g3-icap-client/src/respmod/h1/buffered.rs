@@ -0,0 +1,276 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::{IoSlice, Write};
+
+use bytes::BufMut;
+use tokio::io::{AsyncBufRead, AsyncWriteExt};
+use tokio::time::Instant;
+
+use g3_http::{H1BodyToChunkedTransfer, HttpBodyDecodeReader, HttpBodyType};
+use g3_io_ext::{IdleCheck, LimitedCopy, LimitedCopyError, LimitedWriteExt};
+
+use super::{
+    BidirectionalRecvHttpResponse, BidirectionalRecvIcapResponse, H1RespmodAdaptationError,
+    HttpResponseAdapter, HttpResponseClientWriter, HttpResponseForAdaptation,
+    RespmodAdaptationEndState, RespmodAdaptationRunState,
+};
+use crate::reqmod::h1::HttpRequestForAdaptation;
+use crate::respmod::response::RespmodResponse;
+use crate::respmod::IcapRespmodResponsePayload;
+
+impl<I: IdleCheck> HttpResponseAdapter<I> {
+    fn build_buffered_body_request(
+        &self,
+        http_req_hdr_len: usize,
+        http_rsp_hdr_len: usize,
+    ) -> Vec<u8> {
+        let mut header = Vec::with_capacity(self.icap_client.partial_request_header.len() + 128);
+        header.extend_from_slice(&self.icap_client.partial_request_header);
+        self.push_extended_headers(&mut header);
+        // the original body can be sent again from the buffer, so 204 is acceptable here
+        header.put_slice(b"Allow: 204\r\n");
+        let _ = write!(
+            header,
+            "Encapsulated: req-hdr=0, res-hdr={http_req_hdr_len}, res-body={}\r\n",
+            http_req_hdr_len + http_rsp_hdr_len
+        );
+        header.put_slice(b"\r\n");
+        header
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn xfer_with_buffer<R, H, UR, CW>(
+        mut self,
+        state: &mut RespmodAdaptationRunState,
+        http_request: &R,
+        http_response: &H,
+        ups_body_io: &mut UR,
+        body_size: usize,
+        clt_writer: &mut CW,
+    ) -> Result<RespmodAdaptationEndState<H>, H1RespmodAdaptationError>
+    where
+        R: HttpRequestForAdaptation,
+        H: HttpResponseForAdaptation,
+        UR: AsyncBufRead + Unpin,
+        CW: HttpResponseClientWriter<H> + Unpin,
+    {
+        let body = self.buffer_upstream_body(ups_body_io, body_size).await?;
+        state.mark_ups_recv_all();
+
+        let http_req_header = http_request.serialize_for_adapter();
+        let http_rsp_header = http_response.serialize_for_adapter();
+        let icap_header =
+            self.build_buffered_body_request(http_req_header.len(), http_rsp_header.len());
+
+        let icap_w = &mut self.icap_connection.writer;
+        icap_w
+            .write_all_vectored([
+                IoSlice::new(&icap_header),
+                IoSlice::new(&http_req_header),
+                IoSlice::new(&http_rsp_header),
+            ])
+            .await
+            .map_err(H1RespmodAdaptationError::IcapServerWriteFailed)?;
+
+        let mut body_reader = body.as_slice();
+        let mut body_transfer = H1BodyToChunkedTransfer::new(
+            &mut body_reader,
+            &mut self.icap_connection.writer,
+            HttpBodyType::ContentLength(body_size as u64),
+            self.http_body_line_max_size,
+            self.copy_config,
+        );
+        let bidirectional_transfer = BidirectionalRecvIcapResponse {
+            icap_client: &self.icap_client,
+            icap_reader: &mut self.icap_connection.reader,
+            idle_checker: &self.idle_checker,
+            allow_204: true,
+        };
+        let rsp = bidirectional_transfer
+            .transfer_and_recv(&mut body_transfer)
+            .await?;
+        let body_finished = body_transfer.finished();
+
+        if rsp.code == 204 {
+            if body_finished {
+                self.icap_connection.mark_writer_finished();
+            }
+            if rsp.payload == IcapRespmodResponsePayload::NoPayload {
+                self.icap_connection.mark_reader_finished();
+            }
+            return self
+                .handle_original_http_response_with_buffered_body(
+                    state,
+                    rsp,
+                    http_response,
+                    &body,
+                    clt_writer,
+                )
+                .await;
+        }
+
+        match rsp.payload {
+            IcapRespmodResponsePayload::NoPayload => {
+                if body_finished {
+                    self.icap_connection.mark_writer_finished();
+                }
+                self.icap_connection.mark_reader_finished();
+                self.handle_icap_ok_without_payload(rsp).await
+            }
+            IcapRespmodResponsePayload::HttpResponseWithoutBody(header_size) => {
+                if body_finished {
+                    self.icap_connection.mark_writer_finished();
+                }
+                self.handle_icap_http_response_without_body(
+                    state,
+                    rsp,
+                    header_size,
+                    http_response,
+                    clt_writer,
+                )
+                .await
+            }
+            IcapRespmodResponsePayload::HttpResponseWithBody(header_size) => {
+                if body_finished {
+                    self.icap_connection.mark_writer_finished();
+                    self.handle_icap_http_response_with_body_after_transfer(
+                        state,
+                        rsp,
+                        header_size,
+                        http_response,
+                        clt_writer,
+                    )
+                    .await
+                } else {
+                    let mut bidirectional_transfer = BidirectionalRecvHttpResponse {
+                        http_body_line_max_size: self.http_body_line_max_size,
+                        copy_config: self.copy_config,
+                        idle_checker: &self.idle_checker,
+                        http_header_size: header_size,
+                        icap_read_finished: false,
+                    };
+                    let r = bidirectional_transfer
+                        .transfer(
+                            state,
+                            &mut body_transfer,
+                            http_response,
+                            &mut self.icap_connection.reader,
+                            clt_writer,
+                        )
+                        .await?;
+                    if body_transfer.finished() {
+                        self.icap_connection.mark_writer_finished();
+                        if bidirectional_transfer.icap_read_finished {
+                            self.icap_connection.mark_reader_finished();
+                            if rsp.keep_alive {
+                                self.icap_client.save_connection(self.icap_connection);
+                            }
+                        }
+                    }
+                    Ok(r)
+                }
+            }
+        }
+    }
+
+    async fn buffer_upstream_body<UR>(
+        &self,
+        ups_body_io: &mut UR,
+        body_size: usize,
+    ) -> Result<Vec<u8>, H1RespmodAdaptationError>
+    where
+        UR: AsyncBufRead + Unpin,
+    {
+        let mut body = Vec::with_capacity(body_size);
+        let mut body_reader = HttpBodyDecodeReader::new_fixed_length(ups_body_io, body_size as u64);
+        let mut body_copy = LimitedCopy::new(&mut body_reader, &mut body, &self.copy_config);
+
+        let idle_duration = self.idle_checker.idle_duration();
+        let mut idle_interval =
+            tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
+        let mut idle_count = 0;
+
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut body_copy => {
+                    match r {
+                        Ok(_) => break,
+                        Err(LimitedCopyError::ReadFailed(e)) => return Err(H1RespmodAdaptationError::HttpUpstreamReadFailed(e)),
+                        Err(LimitedCopyError::WriteFailed(_)) => return Err(H1RespmodAdaptationError::InternalServerError("failed to buffer http upstream body")),
+                    }
+                }
+                _ = idle_interval.tick() => {
+                    if body_copy.is_idle() {
+                        idle_count += 1;
+
+                        let quit = self.idle_checker.check_quit(idle_count);
+                        if quit {
+                            return Err(H1RespmodAdaptationError::HttpUpstreamReadIdle);
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        body_copy.reset_active();
+                    }
+
+                    if let Some(reason) = self.idle_checker.check_force_quit() {
+                        return Err(H1RespmodAdaptationError::IdleForceQuit(reason));
+                    }
+                }
+            }
+        }
+
+        Ok(body)
+    }
+
+    async fn handle_original_http_response_with_buffered_body<H, CW>(
+        self,
+        state: &mut RespmodAdaptationRunState,
+        icap_rsp: RespmodResponse,
+        http_response: &H,
+        body: &[u8],
+        clt_writer: &mut CW,
+    ) -> Result<RespmodAdaptationEndState<H>, H1RespmodAdaptationError>
+    where
+        H: HttpResponseForAdaptation,
+        CW: HttpResponseClientWriter<H> + Unpin,
+    {
+        if icap_rsp.keep_alive {
+            self.icap_client.save_connection(self.icap_connection);
+        }
+
+        state.mark_clt_send_start();
+        clt_writer
+            .send_response_header(http_response)
+            .await
+            .map_err(H1RespmodAdaptationError::HttpClientWriteFailed)?;
+        state.mark_clt_send_header();
+        clt_writer
+            .write_all(body)
+            .await
+            .map_err(H1RespmodAdaptationError::HttpClientWriteFailed)?;
+        clt_writer
+            .flush()
+            .await
+            .map_err(H1RespmodAdaptationError::HttpClientWriteFailed)?;
+        state.mark_clt_send_all();
+
+        Ok(RespmodAdaptationEndState::OriginalTransferred)
+    }
+}
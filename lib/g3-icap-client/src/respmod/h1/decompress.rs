@@ -0,0 +1,86 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use tokio::io::AsyncBufRead;
+
+use g3_http::{HttpBodyDecodeReader, HttpBodyType, HttpContentDecodeReader, HttpContentEncoding};
+use g3_io_ext::IdleCheck;
+
+use super::{
+    H1RespmodAdaptationError, HttpResponseAdapter, HttpResponseClientWriter,
+    HttpResponseForAdaptation, RespmodAdaptationEndState, RespmodAdaptationRunState,
+};
+use crate::reqmod::h1::HttpRequestForAdaptation;
+
+impl<I: IdleCheck> HttpResponseAdapter<I> {
+    /// Send the decoded body to the ICAP server.
+    ///
+    /// The Content-Encoding header will be stripped in the response header sent to the ICAP server,
+    /// so the adapted response, which will be sent to the client, will contain no encoding.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn xfer_with_decompress<R, H, UR, CW>(
+        self,
+        state: &mut RespmodAdaptationRunState,
+        http_request: &R,
+        http_response: &H,
+        content_encoding: HttpContentEncoding,
+        ups_body_type: HttpBodyType,
+        ups_body_io: &mut UR,
+        clt_writer: &mut CW,
+    ) -> Result<RespmodAdaptationEndState<H>, H1RespmodAdaptationError>
+    where
+        R: HttpRequestForAdaptation,
+        H: HttpResponseForAdaptation,
+        UR: AsyncBufRead + Unpin,
+        CW: HttpResponseClientWriter<H> + Unpin,
+    {
+        let http_body_line_max_size = self.http_body_line_max_size;
+        let body_reader = match ups_body_type {
+            HttpBodyType::ContentLength(size) => {
+                HttpBodyDecodeReader::new_fixed_length(ups_body_io, size)
+            }
+            HttpBodyType::Chunked => {
+                HttpBodyDecodeReader::new_chunked(ups_body_io, http_body_line_max_size)
+            }
+            HttpBodyType::ReadUntilEnd => HttpBodyDecodeReader::new_read_until_end(ups_body_io),
+        };
+        let mut decode_reader = HttpContentDecodeReader::new(body_reader, content_encoding)
+            .map_err(|_| {
+                H1RespmodAdaptationError::InternalServerError("failed to create content decoder")
+            })?;
+
+        let decoded_response = http_response.strip_content_encoding();
+        let r = self
+            .xfer_without_preview(
+                state,
+                http_request,
+                &decoded_response,
+                HttpBodyType::ReadUntilEnd,
+                &mut decode_reader,
+                clt_writer,
+            )
+            .await?;
+
+        if state.ups_read_finished {
+            // trailer fields are not sent to the ICAP server
+            let mut body_reader = decode_reader.into_inner();
+            if body_reader.trailer(http_body_line_max_size).await.is_err() {
+                state.ups_read_finished = false;
+            }
+        }
+        Ok(r)
+    }
+}
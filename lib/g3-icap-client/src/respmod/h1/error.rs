@@ -59,6 +59,8 @@ pub enum H1RespmodAdaptationError {
     IcapServerReadIdle,
     #[error("idle while writing to icap server")]
     IcapServerWriteIdle,
+    #[error("spool file error: {0:?}")]
+    SpoolFileError(io::Error),
     #[error("not implemented feature: {0}")]
     NotImplemented(&'static str),
 }
@@ -89,6 +89,7 @@ impl<I: IdleCheck> HttpResponseAdapter<I> {
             icap_client: &self.icap_client,
             icap_reader: &mut self.icap_connection.reader,
             idle_checker: &self.idle_checker,
+            allow_204: false,
         };
         let rsp = bidirectional_transfer
             .transfer_and_recv(&mut body_transfer)
@@ -20,7 +20,7 @@ use http::Method;
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use g3_http::client::{HttpForwardRemoteResponse, HttpTransparentResponse};
-use g3_http::HttpBodyType;
+use g3_http::{HttpBodyType, HttpContentEncoding};
 
 use super::{HttpAdaptedResponse, HttpResponseClientWriter, HttpResponseForAdaptation};
 
@@ -44,6 +44,14 @@ impl HttpResponseForAdaptation for HttpForwardRemoteResponse {
     fn adapt_without_body(&self, other: HttpAdaptedResponse) -> Self {
         self.adapt_without_body(other)
     }
+
+    fn content_encoding(&self) -> Option<HttpContentEncoding> {
+        self.content_encoding()
+    }
+
+    fn strip_content_encoding(&self) -> Self {
+        self.strip_content_encoding()
+    }
 }
 
 impl HttpResponseForAdaptation for HttpTransparentResponse {
@@ -66,6 +74,14 @@ impl HttpResponseForAdaptation for HttpTransparentResponse {
     fn adapt_without_body(&self, other: HttpAdaptedResponse) -> Self {
         self.adapt_without_body(other)
     }
+
+    fn content_encoding(&self) -> Option<HttpContentEncoding> {
+        self.content_encoding()
+    }
+
+    fn strip_content_encoding(&self) -> Self {
+        self.strip_content_encoding()
+    }
 }
 
 impl<W, H> HttpResponseClientWriter<H> for W
@@ -24,7 +24,7 @@ use tokio::io::{AsyncBufRead, AsyncWrite};
 use tokio::time::Instant;
 
 use g3_http::client::HttpAdaptedResponse;
-use g3_http::HttpBodyType;
+use g3_http::{HttpBodyType, HttpContentEncoding};
 use g3_io_ext::{IdleCheck, LimitedCopyConfig};
 use g3_types::net::HttpHeaderMap;
 
@@ -40,9 +40,12 @@ use bidirectional::{BidirectionalRecvHttpResponse, BidirectionalRecvIcapResponse
 
 mod recv_response;
 
+mod buffered;
+mod decompress;
 mod forward_body;
 mod forward_header;
 mod preview;
+mod spool;
 
 mod impl_trait;
 
@@ -52,6 +55,8 @@ pub trait HttpResponseForAdaptation {
     fn serialize_for_adapter(&self) -> Vec<u8>;
     fn adapt_with_body(&self, other: HttpAdaptedResponse) -> Self;
     fn adapt_without_body(&self, other: HttpAdaptedResponse) -> Self;
+    fn content_encoding(&self) -> Option<HttpContentEncoding>;
+    fn strip_content_encoding(&self) -> Self;
 }
 
 #[allow(async_fn_in_trait)]
@@ -188,6 +193,59 @@ impl<I: IdleCheck> HttpResponseAdapter<I> {
         CW: HttpResponseClientWriter<H> + Unpin,
     {
         if let Some(body_type) = http_response.body_type(http_request.method()) {
+            let icap_client = self.icap_client.clone();
+            if icap_client.config.respmod_decompress && body_type != HttpBodyType::ContentLength(0)
+            {
+                if let Some(content_encoding) = http_response.content_encoding() {
+                    return self
+                        .xfer_with_decompress(
+                            state,
+                            http_request,
+                            http_response,
+                            content_encoding,
+                            body_type,
+                            ups_body_io,
+                            clt_writer,
+                        )
+                        .await;
+                }
+            }
+
+            if let Some(spool_config) = &icap_client.config.respmod_spool {
+                let body_size = match body_type {
+                    HttpBodyType::ContentLength(size) => Some(size),
+                    _ => None,
+                };
+                if spool_config.need_spool(body_size) {
+                    return self
+                        .xfer_with_spool(
+                            state,
+                            spool_config,
+                            http_request,
+                            http_response,
+                            body_type,
+                            ups_body_io,
+                            clt_writer,
+                        )
+                        .await;
+                }
+            }
+
+            if self.icap_options.support_204 {
+                if let Some(body_size) = self.icap_client.config.buffered_body_size(body_type) {
+                    return self
+                        .xfer_with_buffer(
+                            state,
+                            http_request,
+                            http_response,
+                            ups_body_io,
+                            body_size,
+                            clt_writer,
+                        )
+                        .await;
+                }
+            }
+
             if let Some(preview_size) = self.icap_options.preview_size {
                 self.xfer_with_preview(
                     state,
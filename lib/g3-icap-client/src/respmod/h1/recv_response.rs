@@ -166,6 +166,28 @@ impl<I: IdleCheck> HttpResponseAdapter<I> {
     {
         let http_rsp =
             HttpAdaptedResponse::parse(&mut self.icap_connection.reader, http_header_size).await?;
+        self.send_icap_http_response_without_body(
+            state,
+            icap_rsp,
+            http_rsp,
+            orig_http_response,
+            clt_writer,
+        )
+        .await
+    }
+
+    pub(super) async fn send_icap_http_response_without_body<H, CW>(
+        mut self,
+        state: &mut RespmodAdaptationRunState,
+        icap_rsp: RespmodResponse,
+        http_rsp: HttpAdaptedResponse,
+        orig_http_response: &H,
+        clt_writer: &mut CW,
+    ) -> Result<RespmodAdaptationEndState<H>, H1RespmodAdaptationError>
+    where
+        H: HttpResponseForAdaptation,
+        CW: HttpResponseClientWriter<H> + Unpin,
+    {
         self.icap_connection.mark_reader_finished();
         if icap_rsp.keep_alive {
             self.icap_client.save_connection(self.icap_connection);
@@ -201,6 +223,28 @@ impl<I: IdleCheck> HttpResponseAdapter<I> {
     {
         let http_rsp =
             HttpAdaptedResponse::parse(&mut self.icap_connection.reader, http_header_size).await?;
+        self.send_icap_http_response_with_body_after_transfer(
+            state,
+            icap_rsp,
+            http_rsp,
+            orig_http_response,
+            clt_writer,
+        )
+        .await
+    }
+
+    pub(super) async fn send_icap_http_response_with_body_after_transfer<H, CW>(
+        mut self,
+        state: &mut RespmodAdaptationRunState,
+        icap_rsp: RespmodResponse,
+        http_rsp: HttpAdaptedResponse,
+        orig_http_response: &H,
+        clt_writer: &mut CW,
+    ) -> Result<RespmodAdaptationEndState<H>, H1RespmodAdaptationError>
+    where
+        H: HttpResponseForAdaptation,
+        CW: HttpResponseClientWriter<H> + Unpin,
+    {
         let body_content_length = http_rsp.content_length;
 
         let final_rsp = orig_http_response.adapt_with_body(http_rsp);
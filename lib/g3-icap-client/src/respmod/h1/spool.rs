@@ -0,0 +1,519 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::io::{IoSlice, Write};
+use std::sync::Arc;
+
+use bytes::BufMut;
+use tokio::io::{AsyncBufRead, AsyncWriteExt, BufReader};
+use tokio::time::Instant;
+
+use g3_http::client::HttpAdaptedResponse;
+use g3_http::{
+    H1BodyToChunkedTransfer, HttpBodyDecodeReader, HttpBodyType, StreamToChunkedTransfer,
+};
+use g3_io_ext::{IdleCheck, LimitedCopy, LimitedCopyError, LimitedWriteExt};
+
+use super::{
+    BidirectionalRecvHttpResponse, BidirectionalRecvIcapResponse, H1RespmodAdaptationError,
+    HttpResponseAdapter, HttpResponseClientWriter, HttpResponseForAdaptation,
+    RespmodAdaptationEndState, RespmodAdaptationRunState,
+};
+use crate::reqmod::h1::HttpRequestForAdaptation;
+use crate::respmod::spool::{SpoolFile, SpoolFileReader};
+use crate::respmod::verdict::{IcapVerdict, VerdictHash};
+use crate::respmod::{IcapRespmodResponsePayload, IcapVerdictCache};
+use crate::IcapRespmodSpoolConfig;
+
+fn is_blocked_status(code: u16) -> bool {
+    matches!(code, 403 | 451)
+}
+
+impl<I: IdleCheck> HttpResponseAdapter<I> {
+    fn build_spooled_body_request(
+        &self,
+        http_req_hdr_len: usize,
+        http_rsp_hdr_len: usize,
+    ) -> Vec<u8> {
+        let mut header = Vec::with_capacity(self.icap_client.partial_request_header.len() + 128);
+        header.extend_from_slice(&self.icap_client.partial_request_header);
+        self.push_extended_headers(&mut header);
+        // the original body can be sent again from the spool file, so 204 is acceptable here
+        if self.icap_options.support_204 {
+            header.put_slice(b"Allow: 204\r\n");
+        }
+        let _ = write!(
+            header,
+            "Encapsulated: req-hdr=0, res-hdr={http_req_hdr_len}, res-body={}\r\n",
+            http_req_hdr_len + http_rsp_hdr_len
+        );
+        header.put_slice(b"\r\n");
+        header
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn xfer_with_spool<R, H, UR, CW>(
+        mut self,
+        state: &mut RespmodAdaptationRunState,
+        spool_config: &IcapRespmodSpoolConfig,
+        http_request: &R,
+        http_response: &H,
+        ups_body_type: HttpBodyType,
+        ups_body_io: &mut UR,
+        clt_writer: &mut CW,
+    ) -> Result<RespmodAdaptationEndState<H>, H1RespmodAdaptationError>
+    where
+        R: HttpRequestForAdaptation,
+        H: HttpResponseForAdaptation,
+        UR: AsyncBufRead + Unpin,
+        CW: HttpResponseClientWriter<H> + Unpin,
+    {
+        let verdict_cache = self.icap_client.verdict_cache.clone();
+        let mut spool = SpoolFile::create(spool_config, verdict_cache.is_some())
+            .map_err(H1RespmodAdaptationError::SpoolFileError)?;
+        self.spool_upstream_body(&mut spool, ups_body_type, ups_body_io)
+            .await?;
+        state.mark_ups_recv_all();
+
+        let verdict_key = spool.finish_hash();
+        let spool_reader = spool
+            .into_reader()
+            .await
+            .map_err(H1RespmodAdaptationError::SpoolFileError)?;
+
+        let verdict_cache = verdict_cache.zip(verdict_key);
+        if let Some((cache, hash)) = &verdict_cache {
+            if let Some(verdict) = cache.get(hash) {
+                let r = match verdict {
+                    IcapVerdict::Clean => {
+                        self.send_spooled_original(
+                            state,
+                            http_response,
+                            ups_body_type,
+                            spool_reader,
+                            clt_writer,
+                        )
+                        .await
+                    }
+                    IcapVerdict::Blocked(http_rsp) => {
+                        Self::send_cached_blocked(state, http_rsp, http_response, clt_writer).await
+                    }
+                };
+                // the ICAP connection is not used
+                self.icap_connection.mark_reader_finished();
+                self.icap_connection.mark_writer_finished();
+                self.icap_client.save_connection(self.icap_connection);
+                return r;
+            }
+        }
+
+        self.xfer_spooled_body(
+            state,
+            http_request,
+            http_response,
+            ups_body_type,
+            spool_reader,
+            verdict_cache,
+            clt_writer,
+        )
+        .await
+    }
+
+    async fn spool_upstream_body<UR>(
+        &self,
+        spool: &mut SpoolFile,
+        ups_body_type: HttpBodyType,
+        ups_body_io: &mut UR,
+    ) -> Result<(), H1RespmodAdaptationError>
+    where
+        UR: AsyncBufRead + Unpin,
+    {
+        let mut body_reader = match ups_body_type {
+            HttpBodyType::ContentLength(size) => {
+                HttpBodyDecodeReader::new_fixed_length(ups_body_io, size)
+            }
+            HttpBodyType::Chunked => {
+                HttpBodyDecodeReader::new_chunked(ups_body_io, self.http_body_line_max_size)
+            }
+            HttpBodyType::ReadUntilEnd => HttpBodyDecodeReader::new_read_until_end(ups_body_io),
+        };
+        let mut body_copy = LimitedCopy::new(&mut body_reader, spool, &self.copy_config);
+
+        let idle_duration = self.idle_checker.idle_duration();
+        let mut idle_interval =
+            tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
+        let mut idle_count = 0;
+
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut body_copy => {
+                    match r {
+                        Ok(_) => break,
+                        Err(LimitedCopyError::ReadFailed(e)) => return Err(H1RespmodAdaptationError::HttpUpstreamReadFailed(e)),
+                        Err(LimitedCopyError::WriteFailed(e)) => return Err(H1RespmodAdaptationError::SpoolFileError(e)),
+                    }
+                }
+                _ = idle_interval.tick() => {
+                    if body_copy.is_idle() {
+                        idle_count += 1;
+
+                        let quit = self.idle_checker.check_quit(idle_count);
+                        if quit {
+                            return Err(H1RespmodAdaptationError::HttpUpstreamReadIdle);
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        body_copy.reset_active();
+                    }
+
+                    if let Some(reason) = self.idle_checker.check_force_quit() {
+                        return Err(H1RespmodAdaptationError::IdleForceQuit(reason));
+                    }
+                }
+            }
+        }
+
+        // trailer fields are not kept in the spool file
+        body_reader
+            .trailer(self.http_body_line_max_size)
+            .await
+            .map_err(|_| H1RespmodAdaptationError::InvalidHttpUpstreamResponseBody)?;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn xfer_spooled_body<R, H, CW>(
+        mut self,
+        state: &mut RespmodAdaptationRunState,
+        http_request: &R,
+        http_response: &H,
+        ups_body_type: HttpBodyType,
+        mut spool_reader: SpoolFileReader,
+        verdict_cache: Option<(Arc<IcapVerdictCache>, VerdictHash)>,
+        clt_writer: &mut CW,
+    ) -> Result<RespmodAdaptationEndState<H>, H1RespmodAdaptationError>
+    where
+        R: HttpRequestForAdaptation,
+        H: HttpResponseForAdaptation,
+        CW: HttpResponseClientWriter<H> + Unpin,
+    {
+        let http_req_header = http_request.serialize_for_adapter();
+        let http_rsp_header = http_response.serialize_for_adapter();
+        let icap_header =
+            self.build_spooled_body_request(http_req_header.len(), http_rsp_header.len());
+
+        let icap_w = &mut self.icap_connection.writer;
+        icap_w
+            .write_all_vectored([
+                IoSlice::new(&icap_header),
+                IoSlice::new(&http_req_header),
+                IoSlice::new(&http_rsp_header),
+            ])
+            .await
+            .map_err(H1RespmodAdaptationError::IcapServerWriteFailed)?;
+
+        let spool_size = spool_reader.size();
+        let mut body_reader =
+            BufReader::with_capacity(self.copy_config.buffer_size(), &mut spool_reader);
+        let mut body_transfer = H1BodyToChunkedTransfer::new(
+            &mut body_reader,
+            &mut self.icap_connection.writer,
+            HttpBodyType::ContentLength(spool_size),
+            self.http_body_line_max_size,
+            self.copy_config,
+        );
+        let bidirectional_transfer = BidirectionalRecvIcapResponse {
+            icap_client: &self.icap_client,
+            icap_reader: &mut self.icap_connection.reader,
+            idle_checker: &self.idle_checker,
+            allow_204: self.icap_options.support_204,
+        };
+        let rsp = bidirectional_transfer
+            .transfer_and_recv(&mut body_transfer)
+            .await?;
+        let body_finished = body_transfer.finished();
+        // only save the verdict if the ICAP server has received the whole body
+        let verdict_cache = verdict_cache.filter(|_| body_finished);
+
+        if rsp.code == 204 {
+            if body_finished {
+                self.icap_connection.mark_writer_finished();
+            }
+            if rsp.payload == IcapRespmodResponsePayload::NoPayload {
+                self.icap_connection.mark_reader_finished();
+            }
+            if let Some((cache, hash)) = verdict_cache {
+                cache.put(hash, IcapVerdict::Clean);
+            }
+
+            spool_reader
+                .rewind()
+                .await
+                .map_err(H1RespmodAdaptationError::SpoolFileError)?;
+            let r = self
+                .send_spooled_original(
+                    state,
+                    http_response,
+                    ups_body_type,
+                    spool_reader,
+                    clt_writer,
+                )
+                .await;
+            if rsp.keep_alive {
+                self.icap_client.save_connection(self.icap_connection);
+            }
+            return r;
+        }
+
+        match rsp.payload {
+            IcapRespmodResponsePayload::NoPayload => {
+                if body_finished {
+                    self.icap_connection.mark_writer_finished();
+                }
+                self.icap_connection.mark_reader_finished();
+                self.handle_icap_ok_without_payload(rsp).await
+            }
+            IcapRespmodResponsePayload::HttpResponseWithoutBody(header_size) => {
+                if body_finished {
+                    self.icap_connection.mark_writer_finished();
+                }
+                let http_rsp =
+                    HttpAdaptedResponse::parse(&mut self.icap_connection.reader, header_size)
+                        .await?;
+                Self::save_blocked_verdict(verdict_cache, &http_rsp);
+                self.send_icap_http_response_without_body(
+                    state,
+                    rsp,
+                    http_rsp,
+                    http_response,
+                    clt_writer,
+                )
+                .await
+            }
+            IcapRespmodResponsePayload::HttpResponseWithBody(header_size) => {
+                if body_finished {
+                    self.icap_connection.mark_writer_finished();
+                    let http_rsp =
+                        HttpAdaptedResponse::parse(&mut self.icap_connection.reader, header_size)
+                            .await?;
+                    Self::save_blocked_verdict(verdict_cache, &http_rsp);
+                    self.send_icap_http_response_with_body_after_transfer(
+                        state,
+                        rsp,
+                        http_rsp,
+                        http_response,
+                        clt_writer,
+                    )
+                    .await
+                } else {
+                    let mut bidirectional_transfer = BidirectionalRecvHttpResponse {
+                        http_body_line_max_size: self.http_body_line_max_size,
+                        copy_config: self.copy_config,
+                        idle_checker: &self.idle_checker,
+                        http_header_size: header_size,
+                        icap_read_finished: false,
+                    };
+                    let r = bidirectional_transfer
+                        .transfer(
+                            state,
+                            &mut body_transfer,
+                            http_response,
+                            &mut self.icap_connection.reader,
+                            clt_writer,
+                        )
+                        .await?;
+                    if body_transfer.finished() {
+                        self.icap_connection.mark_writer_finished();
+                        if bidirectional_transfer.icap_read_finished {
+                            self.icap_connection.mark_reader_finished();
+                            if rsp.keep_alive {
+                                self.icap_client.save_connection(self.icap_connection);
+                            }
+                        }
+                    }
+                    Ok(r)
+                }
+            }
+        }
+    }
+
+    fn save_blocked_verdict(
+        verdict_cache: Option<(Arc<IcapVerdictCache>, VerdictHash)>,
+        http_rsp: &HttpAdaptedResponse,
+    ) {
+        if let Some((cache, hash)) = verdict_cache {
+            if is_blocked_status(http_rsp.status.as_u16()) {
+                cache.put(hash, IcapVerdict::Blocked(http_rsp.clone()));
+            }
+        }
+    }
+
+    async fn send_cached_blocked<H, CW>(
+        state: &mut RespmodAdaptationRunState,
+        http_rsp: HttpAdaptedResponse,
+        orig_http_response: &H,
+        clt_writer: &mut CW,
+    ) -> Result<RespmodAdaptationEndState<H>, H1RespmodAdaptationError>
+    where
+        H: HttpResponseForAdaptation,
+        CW: HttpResponseClientWriter<H> + Unpin,
+    {
+        let final_rsp = orig_http_response.adapt_without_body(http_rsp);
+        state.mark_clt_send_start();
+        clt_writer
+            .send_response_header(&final_rsp)
+            .await
+            .map_err(H1RespmodAdaptationError::HttpClientWriteFailed)?;
+        clt_writer
+            .flush()
+            .await
+            .map_err(H1RespmodAdaptationError::HttpClientWriteFailed)?;
+        state.mark_clt_send_header();
+        state.mark_clt_send_no_body();
+
+        Ok(RespmodAdaptationEndState::AdaptedTransferred(final_rsp))
+    }
+
+    async fn send_spooled_original<H, CW>(
+        &self,
+        state: &mut RespmodAdaptationRunState,
+        http_response: &H,
+        ups_body_type: HttpBodyType,
+        spool_reader: SpoolFileReader,
+        clt_writer: &mut CW,
+    ) -> Result<RespmodAdaptationEndState<H>, H1RespmodAdaptationError>
+    where
+        H: HttpResponseForAdaptation,
+        CW: HttpResponseClientWriter<H> + Unpin,
+    {
+        state.mark_clt_send_start();
+        clt_writer
+            .send_response_header(http_response)
+            .await
+            .map_err(H1RespmodAdaptationError::HttpClientWriteFailed)?;
+        state.mark_clt_send_header();
+
+        let mut body_reader =
+            BufReader::with_capacity(self.copy_config.buffer_size(), spool_reader);
+        if matches!(ups_body_type, HttpBodyType::Chunked) {
+            // the trailer fields are dropped while spooling
+            let body_transfer = StreamToChunkedTransfer::new_with_no_trailer(
+                &mut body_reader,
+                clt_writer,
+                self.copy_config.yield_size(),
+            );
+            self.send_spooled_chunked_body(body_transfer).await?;
+        } else {
+            let body_copy = LimitedCopy::new(&mut body_reader, clt_writer, &self.copy_config);
+            self.send_spooled_plain_body(body_copy).await?;
+        }
+
+        state.mark_clt_send_all();
+        Ok(RespmodAdaptationEndState::OriginalTransferred)
+    }
+
+    async fn send_spooled_plain_body<W>(
+        &self,
+        mut body_copy: LimitedCopy<'_, BufReader<SpoolFileReader>, W>,
+    ) -> Result<(), H1RespmodAdaptationError>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        let idle_duration = self.idle_checker.idle_duration();
+        let mut idle_interval =
+            tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
+        let mut idle_count = 0;
+
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut body_copy => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(LimitedCopyError::ReadFailed(e)) => Err(H1RespmodAdaptationError::SpoolFileError(e)),
+                        Err(LimitedCopyError::WriteFailed(e)) => Err(H1RespmodAdaptationError::HttpClientWriteFailed(e)),
+                    };
+                }
+                _ = idle_interval.tick() => {
+                    if body_copy.is_idle() {
+                        idle_count += 1;
+
+                        let quit = self.idle_checker.check_quit(idle_count);
+                        if quit {
+                            return Err(H1RespmodAdaptationError::HttpClientWriteIdle);
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        body_copy.reset_active();
+                    }
+
+                    if let Some(reason) = self.idle_checker.check_force_quit() {
+                        return Err(H1RespmodAdaptationError::IdleForceQuit(reason));
+                    }
+                }
+            }
+        }
+    }
+
+    async fn send_spooled_chunked_body<W>(
+        &self,
+        mut body_transfer: StreamToChunkedTransfer<'_, BufReader<SpoolFileReader>, W>,
+    ) -> Result<(), H1RespmodAdaptationError>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        let idle_duration = self.idle_checker.idle_duration();
+        let mut idle_interval =
+            tokio::time::interval_at(Instant::now() + idle_duration, idle_duration);
+        let mut idle_count = 0;
+
+        loop {
+            tokio::select! {
+                biased;
+
+                r = &mut body_transfer => {
+                    return match r {
+                        Ok(_) => Ok(()),
+                        Err(LimitedCopyError::ReadFailed(e)) => Err(H1RespmodAdaptationError::SpoolFileError(e)),
+                        Err(LimitedCopyError::WriteFailed(e)) => Err(H1RespmodAdaptationError::HttpClientWriteFailed(e)),
+                    };
+                }
+                _ = idle_interval.tick() => {
+                    if body_transfer.is_idle() {
+                        idle_count += 1;
+
+                        let quit = self.idle_checker.check_quit(idle_count);
+                        if quit {
+                            return Err(H1RespmodAdaptationError::HttpClientWriteIdle);
+                        }
+                    } else {
+                        idle_count = 0;
+
+                        body_transfer.reset_active();
+                    }
+
+                    if let Some(reason) = self.idle_checker.check_force_quit() {
+                        return Err(H1RespmodAdaptationError::IdleForceQuit(reason));
+                    }
+                }
+            }
+        }
+    }
+}
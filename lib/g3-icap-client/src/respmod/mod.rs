@@ -25,6 +25,13 @@ mod payload;
 pub use payload::IcapRespmodResponsePayload;
 
 mod response;
+#[cfg(feature = "fuzzing")]
+pub(crate) use response::RespmodResponse;
+
+mod spool;
+
+mod verdict;
+pub use verdict::{IcapVerdictCache, IcapVerdictCacheSnapshot};
 
 pub mod h1;
 pub mod h2;
@@ -0,0 +1,249 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::fs::OpenOptions;
+use std::io;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{ready, Context, Poll};
+
+use openssl::rand;
+use openssl::sha::Sha256;
+use openssl::symm::{Cipher, Crypter, Mode};
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncSeekExt, AsyncWrite, ReadBuf};
+
+use super::verdict::VerdictHash;
+use crate::service::IcapRespmodSpoolConfig;
+
+const SPOOL_AES_KEY_LENGTH: usize = 32;
+const SPOOL_AES_IV_LENGTH: usize = 16;
+
+static SPOOL_FILE_ID: AtomicU64 = AtomicU64::new(0);
+
+struct SpoolKey {
+    key: [u8; SPOOL_AES_KEY_LENGTH],
+    iv: [u8; SPOOL_AES_IV_LENGTH],
+}
+
+impl SpoolKey {
+    fn new() -> io::Result<Self> {
+        let mut key = [0u8; SPOOL_AES_KEY_LENGTH];
+        rand::rand_bytes(&mut key).map_err(io::Error::other)?;
+        let mut iv = [0u8; SPOOL_AES_IV_LENGTH];
+        rand::rand_bytes(&mut iv).map_err(io::Error::other)?;
+        Ok(SpoolKey { key, iv })
+    }
+
+    fn crypter(&self, mode: Mode) -> io::Result<Crypter> {
+        Crypter::new(Cipher::aes_256_ctr(), mode, &self.key, Some(&self.iv))
+            .map_err(io::Error::other)
+    }
+}
+
+/// A temporary file used to hold the http body before sending it to the ICAP server.
+///
+/// The file will be removed when dropped, and on unix it will be unlinked just after
+/// its creation, so nothing would be left even if the process crashed.
+/// The content will be encrypted by a random key, which is only kept in memory, if enabled.
+pub(crate) struct SpoolFile {
+    path: Option<PathBuf>,
+    file: File,
+    key: Option<SpoolKey>,
+    crypter: Option<Crypter>,
+    hasher: Option<Sha256>,
+    buf: Vec<u8>,
+    buf_offset: usize,
+    size: u64,
+}
+
+impl SpoolFile {
+    pub(crate) fn create(config: &IcapRespmodSpoolConfig, with_hash: bool) -> io::Result<Self> {
+        let id = SPOOL_FILE_ID.fetch_add(1, Ordering::Relaxed);
+        let path = config
+            .directory
+            .join(format!("g3-icap-spool-{}-{id}", std::process::id()));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(&path)?;
+        #[cfg(unix)]
+        let path = match std::fs::remove_file(&path) {
+            Ok(_) => None,
+            Err(_) => Some(path),
+        };
+        #[cfg(not(unix))]
+        let path = Some(path);
+
+        let (key, crypter) = if config.encrypt {
+            let key = SpoolKey::new()?;
+            let crypter = key.crypter(Mode::Encrypt)?;
+            (Some(key), Some(crypter))
+        } else {
+            (None, None)
+        };
+
+        Ok(SpoolFile {
+            path,
+            file: File::from_std(file),
+            key,
+            crypter,
+            hasher: with_hash.then(Sha256::new),
+            buf: Vec::new(),
+            buf_offset: 0,
+            size: 0,
+        })
+    }
+
+    #[inline]
+    pub(crate) fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Get the SHA-256 hash of the plain content, should be called after all data written
+    pub(crate) fn finish_hash(&mut self) -> Option<VerdictHash> {
+        self.hasher.take().map(|h| h.finish())
+    }
+
+    fn poll_write_pending(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.buf_offset < self.buf.len() {
+            let nw = ready!(Pin::new(&mut self.file).poll_write(cx, &self.buf[self.buf_offset..]))?;
+            if nw == 0 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "write zero byte into spool file",
+                )));
+            }
+            self.buf_offset += nw;
+        }
+        self.buf.clear();
+        self.buf_offset = 0;
+        Poll::Ready(Ok(()))
+    }
+
+    /// Rewind the file and convert it to a reader of the plain content.
+    pub(crate) async fn into_reader(mut self) -> io::Result<SpoolFileReader> {
+        self.file.rewind().await?;
+        let crypter = match &self.key {
+            Some(key) => Some(key.crypter(Mode::Decrypt)?),
+            None => None,
+        };
+        Ok(SpoolFileReader {
+            spool: self,
+            crypter,
+        })
+    }
+}
+
+impl Drop for SpoolFile {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+impl AsyncWrite for SpoolFile {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        // the disk may be slower than the network, so we only accept new data when the
+        // previous one has been written out, to apply backpressure to the reader side
+        ready!(self.poll_write_pending(cx))?;
+
+        let this = &mut *self;
+        if let Some(hasher) = &mut this.hasher {
+            hasher.update(buf);
+        }
+        if let Some(crypter) = &mut this.crypter {
+            this.buf.resize(buf.len(), 0);
+            let len = crypter
+                .update(buf, &mut this.buf)
+                .map_err(io::Error::other)?;
+            this.buf.truncate(len);
+        } else {
+            this.buf.extend_from_slice(buf);
+        }
+        this.size += buf.len() as u64;
+
+        match this.poll_write_pending(cx) {
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            _ => Poll::Ready(Ok(buf.len())),
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_pending(cx))?;
+        Pin::new(&mut self.file).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+pub(crate) struct SpoolFileReader {
+    spool: SpoolFile,
+    crypter: Option<Crypter>,
+}
+
+impl AsyncRead for SpoolFileReader {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+        let Some(crypter) = &mut this.crypter else {
+            return Pin::new(&mut this.spool.file).poll_read(cx, buf);
+        };
+
+        let spool = &mut this.spool;
+        spool.buf.resize(buf.remaining(), 0);
+        let mut cipher_buf = ReadBuf::new(&mut spool.buf);
+        ready!(Pin::new(&mut spool.file).poll_read(cx, &mut cipher_buf))?;
+        let nr = cipher_buf.filled().len();
+        if nr == 0 {
+            return Poll::Ready(Ok(()));
+        }
+
+        let dst = buf.initialize_unfilled_to(nr);
+        let len = crypter
+            .update(&spool.buf[..nr], dst)
+            .map_err(io::Error::other)?;
+        buf.advance(len);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl SpoolFileReader {
+    #[inline]
+    pub(crate) fn size(&self) -> u64 {
+        self.spool.size()
+    }
+
+    /// Rewind to read the content again from the start.
+    pub(crate) async fn rewind(&mut self) -> io::Result<()> {
+        self.spool.file.rewind().await?;
+        if let Some(key) = &self.spool.key {
+            self.crypter = Some(key.crypter(Mode::Decrypt)?);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use lru::LruCache;
+use tokio::time::Instant;
+
+use g3_http::client::HttpAdaptedResponse;
+
+use crate::IcapVerdictCacheConfig;
+
+pub(crate) const VERDICT_HASH_LENGTH: usize = 32;
+
+pub(crate) type VerdictHash = [u8; VERDICT_HASH_LENGTH];
+
+#[derive(Clone)]
+pub(crate) enum IcapVerdict {
+    /// the ICAP server returned 204 for this content
+    Clean,
+    /// the ICAP server returned an error http response for this content,
+    /// only the header will be kept
+    Blocked(HttpAdaptedResponse),
+}
+
+struct VerdictCacheEntry {
+    verdict: IcapVerdict,
+    expire: Instant,
+}
+
+#[derive(Default)]
+struct IcapVerdictCacheStats {
+    hit_clean: AtomicU64,
+    hit_blocked: AtomicU64,
+    miss: AtomicU64,
+    insert: AtomicU64,
+    expire: AtomicU64,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IcapVerdictCacheSnapshot {
+    pub entries: usize,
+    pub hit_clean: u64,
+    pub hit_blocked: u64,
+    pub miss: u64,
+    pub insert: u64,
+    pub expire: u64,
+}
+
+/// Cache of the ICAP RESPMOD verdicts, keyed by the SHA-256 hash of the http body
+pub struct IcapVerdictCache {
+    config: IcapVerdictCacheConfig,
+    inner: Mutex<LruCache<VerdictHash, VerdictCacheEntry, ahash::RandomState>>,
+    stats: IcapVerdictCacheStats,
+}
+
+impl IcapVerdictCache {
+    pub fn new(config: &IcapVerdictCacheConfig) -> Self {
+        let size = NonZeroUsize::new(config.max_entries)
+            .unwrap_or_else(|| unsafe { NonZeroUsize::new_unchecked(1) });
+        IcapVerdictCache {
+            config: config.clone(),
+            inner: Mutex::new(LruCache::with_hasher(size, ahash::RandomState::new())),
+            stats: IcapVerdictCacheStats::default(),
+        }
+    }
+
+    pub(crate) fn config(&self) -> &IcapVerdictCacheConfig {
+        &self.config
+    }
+
+    pub(crate) fn get(&self, hash: &VerdictHash) -> Option<IcapVerdict> {
+        let mut cache = self.inner.lock().unwrap();
+        let verdict = match cache.get(hash) {
+            Some(entry) if entry.expire > Instant::now() => Some(entry.verdict.clone()),
+            Some(_) => {
+                cache.pop(hash);
+                self.stats.expire.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => None,
+        };
+        drop(cache);
+
+        match &verdict {
+            Some(IcapVerdict::Clean) => self.stats.hit_clean.fetch_add(1, Ordering::Relaxed),
+            Some(IcapVerdict::Blocked(_)) => self.stats.hit_blocked.fetch_add(1, Ordering::Relaxed),
+            None => self.stats.miss.fetch_add(1, Ordering::Relaxed),
+        };
+        verdict
+    }
+
+    pub(crate) fn put(&self, hash: VerdictHash, verdict: IcapVerdict) {
+        let entry = VerdictCacheEntry {
+            verdict,
+            expire: Instant::now() + self.config.ttl,
+        };
+        let mut cache = self.inner.lock().unwrap();
+        cache.put(hash, entry);
+        drop(cache);
+        self.stats.insert.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Remove all cached verdicts, and return the count of the removed ones
+    pub fn flush(&self) -> usize {
+        let mut cache = self.inner.lock().unwrap();
+        let len = cache.len();
+        cache.clear();
+        len
+    }
+
+    pub fn snapshot(&self) -> IcapVerdictCacheSnapshot {
+        let entries = self.inner.lock().unwrap().len();
+        IcapVerdictCacheSnapshot {
+            entries,
+            hit_clean: self.stats.hit_clean.load(Ordering::Relaxed),
+            hit_blocked: self.stats.hit_blocked.load(Ordering::Relaxed),
+            miss: self.stats.miss.load(Ordering::Relaxed),
+            insert: self.stats.insert.load(Ordering::Relaxed),
+            expire: self.stats.expire.load(Ordering::Relaxed),
+        }
+    }
+}
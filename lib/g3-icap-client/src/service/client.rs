@@ -20,34 +20,97 @@ use anyhow::anyhow;
 use tokio::sync::oneshot;
 
 use super::{
-    IcapClientConnection, IcapConnector, IcapServiceClientCommand, IcapServiceConfig,
-    IcapServicePool,
+    IcapClientConnection, IcapConnectionPoolSnapshot, IcapConnector, IcapIdleConnectionPool,
+    IcapServiceClientCommand, IcapServiceConfig, IcapServicePool,
 };
 use crate::options::{IcapOptionsRequest, IcapServiceOptions};
+use crate::respmod::IcapVerdictCache;
 
 pub struct IcapServiceClient {
     pub(crate) config: Arc<IcapServiceConfig>,
     pub(crate) partial_request_header: Vec<u8>,
     cmd_sender: flume::Sender<IcapServiceClientCommand>,
     conn_creator: Arc<IcapConnector>,
+    idle_pool: Arc<IcapIdleConnectionPool>,
+    pub(crate) verdict_cache: Option<Arc<IcapVerdictCache>>,
 }
 
 impl IcapServiceClient {
     pub fn new(config: Arc<IcapServiceConfig>) -> anyhow::Result<Self> {
+        let idle_pool = Arc::new(IcapIdleConnectionPool::new(&config));
+        Self::with_idle_pool(config, idle_pool)
+    }
+
+    /// Create a new client which will share the idle connections with the other client,
+    /// if both of them use the same ICAP server and connection settings
+    pub fn new_sharing_pool(
+        config: Arc<IcapServiceConfig>,
+        other: &IcapServiceClient,
+    ) -> anyhow::Result<Self> {
+        if other.config.same_connection_target(&config) {
+            Self::with_idle_pool(config, other.idle_pool.clone())
+        } else {
+            Self::new(config)
+        }
+    }
+
+    fn with_idle_pool(
+        config: Arc<IcapServiceConfig>,
+        idle_pool: Arc<IcapIdleConnectionPool>,
+    ) -> anyhow::Result<Self> {
+        config.check()?;
         let (cmd_sender, cmd_receiver) = flume::unbounded();
-        let conn_creator = IcapConnector::new(config.clone())?;
+        let conn_creator = IcapConnector::new(config.clone(), idle_pool.stats().clone())?;
         let conn_creator = Arc::new(conn_creator);
-        let pool = IcapServicePool::new(config.clone(), cmd_receiver, conn_creator.clone());
+        let pool = IcapServicePool::new(
+            config.clone(),
+            cmd_receiver,
+            conn_creator.clone(),
+            idle_pool.clone(),
+        );
         tokio::spawn(pool.into_running());
         let partial_request_header = config.build_request_header();
+        let verdict_cache = config
+            .respmod_verdict_cache
+            .as_ref()
+            .map(|c| Arc::new(IcapVerdictCache::new(c)));
         Ok(IcapServiceClient {
             config,
             partial_request_header,
             cmd_sender,
             conn_creator,
+            idle_pool,
+            verdict_cache,
         })
     }
 
+    pub fn pool_snapshot(&self) -> IcapConnectionPoolSnapshot {
+        self.idle_pool.stats().snapshot()
+    }
+
+    /// Check if the idle connection pool is shared with the other client
+    pub fn pool_shared_with(&self, other: &IcapServiceClient) -> bool {
+        Arc::ptr_eq(&self.idle_pool, &other.idle_pool)
+    }
+
+    pub fn verdict_cache(&self) -> Option<&Arc<IcapVerdictCache>> {
+        self.verdict_cache.as_ref()
+    }
+
+    /// Keep the cached verdicts of the old client if both the ICAP server and the cache config
+    /// are not changed
+    pub fn inherit_verdict_cache(&mut self, old: &IcapServiceClient) {
+        let Some(old_cache) = &old.verdict_cache else {
+            return;
+        };
+        if self.verdict_cache.is_none() || self.config.upstream != old.config.upstream {
+            return;
+        }
+        if self.config.respmod_verdict_cache.as_ref() == Some(old_cache.config()) {
+            self.verdict_cache = Some(old_cache.clone());
+        }
+    }
+
     async fn fetch_from_pool(&self) -> Option<(IcapClientConnection, Arc<IcapServiceOptions>)> {
         let (rsp_sender, rsp_receiver) = oneshot::channel();
         let cmd = IcapServiceClientCommand::FetchConnection(rsp_sender);
@@ -82,6 +145,21 @@ impl IcapServiceClient {
         Ok((conn, Arc::new(options)))
     }
 
+    /// Create a new connection and send an OPTIONS request, the connection will be dropped
+    /// and the pool will not be touched
+    pub async fn probe_options(&self) -> anyhow::Result<IcapServiceOptions> {
+        let mut conn = self
+            .conn_creator
+            .create()
+            .await
+            .map_err(|e| anyhow!("create new connection failed: {e:?}"))?;
+        let options_req = IcapOptionsRequest::new(self.config.as_ref());
+        options_req
+            .get_options(&mut conn, self.config.icap_max_header_size)
+            .await
+            .map_err(|e| anyhow!("failed to get icap service options: {e}"))
+    }
+
     pub fn save_connection(&self, conn: IcapClientConnection) {
         if conn.reusable() {
             let _ = self
@@ -23,6 +23,7 @@ use http::HeaderName;
 use rustls_pki_types::ServerName;
 use url::Url;
 
+use g3_http::HttpBodyType;
 use g3_types::net::{
     ConnectionPoolConfig, HttpAuth, RustlsClientConfigBuilder, TcpKeepAliveConfig, UpstreamAddr,
 };
@@ -30,6 +31,12 @@ use g3_types::net::{
 #[cfg(feature = "yaml")]
 mod yaml;
 
+mod spool;
+pub use spool::IcapRespmodSpoolConfig;
+
+mod verdict;
+pub use verdict::IcapVerdictCacheConfig;
+
 use super::IcapMethod;
 
 pub struct IcapServiceConfig {
@@ -42,11 +49,16 @@ pub struct IcapServiceConfig {
     pub(crate) tls_name: ServerName<'static>,
     pub connection_pool: ConnectionPoolConfig,
     pub(crate) tcp_keepalive: TcpKeepAliveConfig,
+    pub(crate) pool_health_check_interval: Option<Duration>,
     pub(crate) icap_206_enable: bool,
     pub(crate) icap_max_header_size: usize,
     pub(crate) preview_data_read_timeout: Duration,
+    pub(crate) allow_204_buffer_size: usize,
     pub(crate) respond_shared_names: BTreeSet<String>,
     pub(crate) bypass: bool,
+    pub(crate) respmod_spool: Option<IcapRespmodSpoolConfig>,
+    pub(crate) respmod_verdict_cache: Option<IcapVerdictCacheConfig>,
+    pub(crate) respmod_decompress: bool,
 }
 
 impl IcapServiceConfig {
@@ -80,11 +92,16 @@ impl IcapServiceConfig {
             tls_name,
             connection_pool: ConnectionPoolConfig::default(),
             tcp_keepalive: TcpKeepAliveConfig::default_enabled(),
+            pool_health_check_interval: None,
             icap_206_enable: false,
             icap_max_header_size: 8192,
             preview_data_read_timeout: Duration::from_secs(4),
+            allow_204_buffer_size: 0,
             respond_shared_names: BTreeSet::new(),
             bypass: false,
+            respmod_spool: None,
+            respmod_verdict_cache: None,
+            respmod_decompress: false,
         })
     }
 
@@ -92,6 +109,10 @@ impl IcapServiceConfig {
         self.tcp_keepalive = config;
     }
 
+    pub fn set_pool_health_check_interval(&mut self, interval: Duration) {
+        self.pool_health_check_interval = Some(interval);
+    }
+
     pub fn set_tls_client(&mut self, config: RustlsClientConfigBuilder) {
         self.tls_client = Some(config);
     }
@@ -108,10 +129,73 @@ impl IcapServiceConfig {
         self.preview_data_read_timeout = time;
     }
 
+    pub fn set_allow_204_buffer_size(&mut self, size: usize) {
+        self.allow_204_buffer_size = size;
+    }
+
+    /// Get the body size if the body can be buffered in memory,
+    /// so `Allow: 204` can be sent outside of preview
+    pub(crate) fn buffered_body_size(&self, body_type: HttpBodyType) -> Option<usize> {
+        match body_type {
+            HttpBodyType::ContentLength(size)
+                if size > 0 && size <= self.allow_204_buffer_size as u64 =>
+            {
+                Some(size as usize)
+            }
+            _ => None,
+        }
+    }
+
     pub fn set_bypass(&mut self, bypass: bool) {
         self.bypass = bypass;
     }
 
+    pub fn set_respmod_spool(&mut self, config: IcapRespmodSpoolConfig) -> anyhow::Result<()> {
+        if self.method != IcapMethod::Respmod {
+            return Err(anyhow!("spool is only supported for RESPMOD service"));
+        }
+        self.respmod_spool = Some(config);
+        Ok(())
+    }
+
+    pub fn set_respmod_verdict_cache(
+        &mut self,
+        config: IcapVerdictCacheConfig,
+    ) -> anyhow::Result<()> {
+        if self.method != IcapMethod::Respmod {
+            return Err(anyhow!(
+                "verdict cache is only supported for RESPMOD service"
+            ));
+        }
+        self.respmod_verdict_cache = Some(config);
+        Ok(())
+    }
+
+    pub fn set_respmod_decompress(&mut self, enable: bool) -> anyhow::Result<()> {
+        if self.method != IcapMethod::Respmod {
+            return Err(anyhow!("decompress is only supported for RESPMOD service"));
+        }
+        self.respmod_decompress = enable;
+        Ok(())
+    }
+
+    pub(crate) fn check(&self) -> anyhow::Result<()> {
+        if self.respmod_verdict_cache.is_some() && self.respmod_spool.is_none() {
+            return Err(anyhow!(
+                "verdict cache can only be used with respmod spool enabled"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check if connections created by this config can be used by the other one
+    pub(crate) fn same_connection_target(&self, other: &IcapServiceConfig) -> bool {
+        self.upstream == other.upstream
+            && self.tls_client == other.tls_client
+            && self.tls_name == other.tls_name
+            && self.tcp_keepalive == other.tcp_keepalive
+    }
+
     pub fn add_respond_shared_name(&mut self, name: HeaderName) {
         self.respond_shared_names.insert(name.as_str().to_string());
     }
@@ -137,7 +221,7 @@ impl IcapServiceConfig {
             let _ = write!(header, "User-Agent: {user_agent}\r\n");
         }
         match &self.auth {
-            HttpAuth::None => {}
+            HttpAuth::None | HttpAuth::Negotiate(_) | HttpAuth::Bearer(_) => {}
             HttpAuth::Basic(basic_auth) => {
                 let _ = write!(
                     header,
@@ -0,0 +1,51 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::path::PathBuf;
+
+const DEFAULT_SPOOL_THRESHOLD: u64 = 4 * 1024 * 1024;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IcapRespmodSpoolConfig {
+    pub(crate) directory: PathBuf,
+    pub(crate) threshold: u64,
+    pub(crate) encrypt: bool,
+}
+
+impl IcapRespmodSpoolConfig {
+    pub fn new(directory: PathBuf) -> Self {
+        IcapRespmodSpoolConfig {
+            directory,
+            threshold: DEFAULT_SPOOL_THRESHOLD,
+            encrypt: true,
+        }
+    }
+
+    pub fn set_threshold(&mut self, threshold: u64) {
+        self.threshold = threshold;
+    }
+
+    pub fn set_encrypt(&mut self, encrypt: bool) {
+        self.encrypt = encrypt;
+    }
+
+    /// body with unknown size will always be spooled
+    pub(crate) fn need_spool(&self, body_size: Option<u64>) -> bool {
+        match body_size {
+            Some(size) => size >= self.threshold,
+            None => true,
+        }
+    }
+}
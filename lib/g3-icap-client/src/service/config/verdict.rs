@@ -0,0 +1,41 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IcapVerdictCacheConfig {
+    pub(crate) ttl: Duration,
+    pub(crate) max_entries: usize,
+}
+
+impl Default for IcapVerdictCacheConfig {
+    fn default() -> Self {
+        IcapVerdictCacheConfig {
+            ttl: Duration::from_secs(3600),
+            max_entries: 4096,
+        }
+    }
+}
+
+impl IcapVerdictCacheConfig {
+    pub fn set_ttl(&mut self, ttl: Duration) {
+        self.ttl = ttl;
+    }
+
+    pub fn set_max_entries(&mut self, max_entries: usize) {
+        self.max_entries = max_entries;
+    }
+}
@@ -14,14 +14,96 @@
  * limitations under the License.
  */
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use anyhow::{anyhow, Context};
 use url::Url;
 use yaml_rust::{yaml, Yaml};
 
-use super::{IcapMethod, IcapServiceConfig};
+use super::{IcapMethod, IcapRespmodSpoolConfig, IcapServiceConfig, IcapVerdictCacheConfig};
+
+fn parse_spool_directory(value: &Yaml, lookup_dir: Option<&Path>) -> anyhow::Result<PathBuf> {
+    match lookup_dir {
+        Some(dir) => g3_yaml::value::as_dir_path(value, dir, true),
+        None => g3_yaml::value::as_absolute_path(value),
+    }
+}
+
+impl IcapRespmodSpoolConfig {
+    fn parse_yaml(value: &Yaml, lookup_dir: Option<&Path>) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => {
+                const KEY_DIRECTORY: &str = "directory";
+                let v = g3_yaml::hash_get_required(map, KEY_DIRECTORY)?;
+                let directory = parse_spool_directory(v, lookup_dir).context(format!(
+                    "invalid directory path value for key {KEY_DIRECTORY}"
+                ))?;
+                let mut config = IcapRespmodSpoolConfig::new(directory);
+
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    KEY_DIRECTORY => Ok(()),
+                    "threshold" | "size_threshold" => {
+                        let threshold = g3_yaml::humanize::as_usize(v)
+                            .context(format!("invalid humanize usize value for key {k}"))?;
+                        config.set_threshold(threshold as u64);
+                        Ok(())
+                    }
+                    "encrypt" | "encryption" => {
+                        let encrypt = g3_yaml::value::as_bool(v)
+                            .context(format!("invalid bool value for key {k}"))?;
+                        config.set_encrypt(encrypt);
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+
+                Ok(config)
+            }
+            Yaml::String(_) => {
+                let directory = parse_spool_directory(value, lookup_dir)?;
+                Ok(IcapRespmodSpoolConfig::new(directory))
+            }
+            _ => Err(anyhow!(
+                "yaml value type for 'icap respmod spool config' should be 'map' or 'dir path'"
+            )),
+        }
+    }
+}
+
+impl IcapVerdictCacheConfig {
+    fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        let mut config = IcapVerdictCacheConfig::default();
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "ttl" => {
+                        let ttl = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_ttl(ttl);
+                        Ok(())
+                    }
+                    "max_entries" | "capacity" => {
+                        let max_entries = g3_yaml::value::as_usize(v)
+                            .context(format!("invalid usize value for key {k}"))?;
+                        config.set_max_entries(max_entries);
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                Ok(config)
+            }
+            Yaml::Integer(_) => {
+                let max_entries = g3_yaml::value::as_usize(value)?;
+                config.set_max_entries(max_entries);
+                Ok(config)
+            }
+            _ => Err(anyhow!(
+                "yaml value type for 'icap verdict cache config' should be 'map' or 'usize'"
+            )),
+        }
+    }
+}
 
 impl IcapServiceConfig {
     fn parse_yaml(
@@ -62,6 +144,12 @@ impl IcapServiceConfig {
                     .context(format!("invalid connection pool config value for key {k}"))?;
                 Ok(())
             }
+            "pool_health_check_interval" | "health_check_interval" => {
+                let interval = g3_yaml::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                config.set_pool_health_check_interval(interval);
+                Ok(())
+            }
             "icap_max_header_size" => {
                 let size = g3_yaml::humanize::as_usize(v)
                     .context(format!("invalid humanize usize value for key {k}"))?;
@@ -74,6 +162,12 @@ impl IcapServiceConfig {
                 config.set_preview_data_read_timeout(time);
                 Ok(())
             }
+            "allow_204_buffer_size" => {
+                let size = g3_yaml::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                config.set_allow_204_buffer_size(size);
+                Ok(())
+            }
             "respond_shared_names" => {
                 if let Yaml::Array(seq) = v {
                     for (i, v) in seq.iter().enumerate() {
@@ -88,6 +182,25 @@ impl IcapServiceConfig {
                 }
                 Ok(())
             }
+            "respmod_spool" | "spool" => {
+                let spool = IcapRespmodSpoolConfig::parse_yaml(v, lookup_dir).context(format!(
+                    "invalid icap respmod spool config value for key {k}"
+                ))?;
+                config.set_respmod_spool(spool)?;
+                Ok(())
+            }
+            "respmod_verdict_cache" | "verdict_cache" => {
+                let cache = IcapVerdictCacheConfig::parse_yaml(v).context(format!(
+                    "invalid icap verdict cache config value for key {k}"
+                ))?;
+                config.set_respmod_verdict_cache(cache)?;
+                Ok(())
+            }
+            "respmod_decompress" | "decompress" => {
+                let enable = g3_yaml::value::as_bool(v)?;
+                config.set_respmod_decompress(enable)?;
+                Ok(())
+            }
             "bypass" => {
                 let bypass = g3_yaml::value::as_bool(v)?;
                 config.set_bypass(bypass);
@@ -96,6 +209,7 @@ impl IcapServiceConfig {
             _ => Err(anyhow!("invalid key {k}")),
         })?;
 
+        config.check()?;
         Ok(config)
     }
 
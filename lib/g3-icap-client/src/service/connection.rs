@@ -23,13 +23,15 @@ use anyhow::Context;
 use tokio::io::BufReader;
 use tokio::net::TcpStream;
 use tokio::sync::oneshot;
+use tokio::time::Instant;
 use tokio_rustls::TlsConnector;
 
 use g3_io_ext::rustls::{MaybeTlsStreamReadHalf, MaybeTlsStreamWriteHalf};
 use g3_io_ext::{AsyncStream, LimitedBufReadExt};
 use g3_types::net::{Host, RustlsClientConfig};
 
-use super::IcapServiceConfig;
+use super::{IcapConnectionPoolStats, IcapServiceConfig};
+use crate::options::IcapOptionsRequest;
 use crate::IcapServiceOptions;
 
 pub type IcapClientWriter = MaybeTlsStreamWriteHalf<TcpStream>;
@@ -41,6 +43,7 @@ pub struct IcapClientConnection {
     reader_clean: bool,
     writer_clean: bool,
     reused_connection: bool,
+    created: Instant,
 }
 
 impl IcapClientConnection {
@@ -51,6 +54,7 @@ impl IcapClientConnection {
             reader_clean: true,
             writer_clean: true,
             reused_connection: false,
+            created: Instant::now(),
         }
     }
 
@@ -74,15 +78,29 @@ impl IcapClientConnection {
     pub(super) fn reusable(&self) -> bool {
         self.reader_clean && self.writer_clean
     }
+
+    fn expire_at(&self, max_lifetime: Option<Duration>) -> Option<Instant> {
+        max_lifetime.map(|lifetime| self.created + lifetime)
+    }
+
+    pub(super) fn expired(&self, max_lifetime: Option<Duration>) -> bool {
+        self.expire_at(max_lifetime)
+            .map(|expire| expire <= Instant::now())
+            .unwrap_or(false)
+    }
 }
 
 pub(super) struct IcapConnector {
     config: Arc<IcapServiceConfig>,
     tls_client: Option<RustlsClientConfig>,
+    stats: Arc<IcapConnectionPoolStats>,
 }
 
 impl IcapConnector {
-    pub(super) fn new(config: Arc<IcapServiceConfig>) -> anyhow::Result<Self> {
+    pub(super) fn new(
+        config: Arc<IcapServiceConfig>,
+        stats: Arc<IcapConnectionPoolStats>,
+    ) -> anyhow::Result<Self> {
         let tls_client = match &config.tls_client {
             Some(builder) => {
                 let client = builder
@@ -92,7 +110,11 @@ impl IcapConnector {
             }
             None => None,
         };
-        Ok(IcapConnector { config, tls_client })
+        Ok(IcapConnector {
+            config,
+            tls_client,
+            stats,
+        })
     }
 
     async fn select_peer_addr(&self) -> io::Result<SocketAddr> {
@@ -118,6 +140,7 @@ impl IcapConnector {
             true,
         )?;
         let stream = socket.connect(peer).await?;
+        self.stats.add_created();
 
         if let Some(client) = &self.tls_client {
             let tls_connector = TlsConnector::from(client.driver.clone());
@@ -187,22 +210,73 @@ impl IcapConnectionEofPoller {
         }
     }
 
-    pub(super) async fn into_running(mut self, idle_timeout: Duration) {
-        let idle_sleep = tokio::time::sleep(idle_timeout);
-
-        tokio::select! {
-            _ = self.conn.reader.fill_wait_data() => {}
-            _ = idle_sleep => {}
-            r = self.req_receiver.recv_async() => {
-                if let Ok(req) = r {
-                    let IcapConnectionPollRequest {
-                        client_sender,
-                        options,
-                    } = req;
-                    self.conn.reused_connection = true;
-                    let _ = client_sender.send((self.conn, options));
+    pub(super) async fn into_running(
+        mut self,
+        config: Arc<IcapServiceConfig>,
+        stats: Arc<IcapConnectionPoolStats>,
+    ) {
+        let idle_expire = Instant::now() + config.connection_pool.idle_timeout();
+        let (deadline, lifetime_expired) =
+            match self.conn.expire_at(config.connection_pool.max_lifetime()) {
+                Some(expire) if expire < idle_expire => (expire, true),
+                _ => (idle_expire, false),
+            };
+        let idle_sleep = tokio::time::sleep_until(deadline);
+        tokio::pin!(idle_sleep);
+
+        let mut health_check = config
+            .pool_health_check_interval
+            .map(|d| (d, tokio::time::interval_at(Instant::now() + d, d)));
+
+        loop {
+            tokio::select! {
+                _ = self.conn.reader.fill_wait_data() => return,
+                _ = &mut idle_sleep => {
+                    if lifetime_expired {
+                        stats.add_lifetime_expired();
+                    }
+                    return;
+                }
+                r = self.req_receiver.recv_async() => {
+                    if let Ok(req) = r {
+                        let IcapConnectionPollRequest {
+                            client_sender,
+                            options,
+                        } = req;
+                        self.conn.reused_connection = true;
+                        if client_sender.send((self.conn, options)).is_ok() {
+                            stats.add_reused();
+                        }
+                    }
+                    return;
+                }
+                timeout = async {
+                    let (timeout, interval) = health_check.as_mut().unwrap();
+                    interval.tick().await;
+                    *timeout
+                }, if health_check.is_some() => {
+                    let passed = self.health_check(&config, timeout).await;
+                    stats.add_health_check(passed);
+                    if !passed {
+                        return;
+                    }
                 }
             }
         }
     }
+
+    /// Send an OPTIONS request on the idle connection, and check if we can get a valid response
+    async fn health_check(&mut self, config: &IcapServiceConfig, timeout: Duration) -> bool {
+        let req = IcapOptionsRequest::new(config);
+        self.conn.mark_io_inuse();
+        match tokio::time::timeout(
+            timeout,
+            req.get_options(&mut self.conn, config.icap_max_header_size),
+        )
+        .await
+        {
+            Ok(Ok(_)) => self.conn.reusable(),
+            _ => false,
+        }
+    }
 }
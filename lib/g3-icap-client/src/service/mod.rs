@@ -15,7 +15,7 @@
  */
 
 mod config;
-pub use config::IcapServiceConfig;
+pub use config::{IcapRespmodSpoolConfig, IcapServiceConfig, IcapVerdictCacheConfig};
 
 mod connection;
 pub(super) use connection::{IcapClientConnection, IcapClientReader, IcapClientWriter};
@@ -25,7 +25,11 @@ mod client;
 pub use client::IcapServiceClient;
 
 mod pool;
-use pool::{IcapServiceClientCommand, IcapServicePool};
+use pool::{IcapIdleConnectionPool, IcapServiceClientCommand, IcapServicePool};
+
+mod stats;
+pub use stats::IcapConnectionPoolSnapshot;
+use stats::IcapConnectionPoolStats;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum IcapMethod {
@@ -14,15 +14,14 @@
  * limitations under the License.
  */
 
-use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use tokio::sync::{mpsc, oneshot};
 use tokio::time::Interval;
 
 use super::{
-    IcapClientConnection, IcapConnectionEofPoller, IcapConnectionPollRequest, IcapConnector,
-    IcapServiceConfig,
+    IcapClientConnection, IcapConnectionEofPoller, IcapConnectionPollRequest,
+    IcapConnectionPoolStats, IcapConnector, IcapServiceConfig,
 };
 use crate::options::{IcapOptionsRequest, IcapServiceOptions};
 
@@ -38,6 +37,30 @@ enum IcapServicePoolCommand {
     SaveConnection(IcapClientConnection),
 }
 
+/// The idle connections, which may be shared by services on the same ICAP server
+pub(super) struct IcapIdleConnectionPool {
+    conn_req_sender: flume::Sender<IcapConnectionPollRequest>,
+    conn_req_receiver: flume::Receiver<IcapConnectionPollRequest>,
+    stats: Arc<IcapConnectionPoolStats>,
+}
+
+impl IcapIdleConnectionPool {
+    pub(super) fn new(config: &IcapServiceConfig) -> Self {
+        let (conn_req_sender, conn_req_receiver) =
+            flume::bounded(config.connection_pool.max_idle_count());
+        IcapIdleConnectionPool {
+            conn_req_sender,
+            conn_req_receiver,
+            stats: Arc::new(IcapConnectionPoolStats::default()),
+        }
+    }
+
+    #[inline]
+    pub(super) fn stats(&self) -> &Arc<IcapConnectionPoolStats> {
+        &self.stats
+    }
+}
+
 pub(super) struct IcapServicePool {
     config: Arc<IcapServiceConfig>,
     options: Arc<IcapServiceOptions>,
@@ -46,9 +69,7 @@ pub(super) struct IcapServicePool {
     client_cmd_receiver: flume::Receiver<IcapServiceClientCommand>,
     pool_cmd_sender: mpsc::Sender<IcapServicePoolCommand>,
     pool_cmd_receiver: mpsc::Receiver<IcapServicePoolCommand>,
-    conn_req_sender: flume::Sender<IcapConnectionPollRequest>,
-    conn_req_receiver: flume::Receiver<IcapConnectionPollRequest>,
-    idle_conn_count: Arc<AtomicUsize>,
+    idle_pool: Arc<IcapIdleConnectionPool>,
 }
 
 impl IcapServicePool {
@@ -56,12 +77,11 @@ impl IcapServicePool {
         config: Arc<IcapServiceConfig>,
         client_cmd_receiver: flume::Receiver<IcapServiceClientCommand>,
         connector: Arc<IcapConnector>,
+        idle_pool: Arc<IcapIdleConnectionPool>,
     ) -> Self {
         let options = Arc::new(IcapServiceOptions::new_expired(config.method));
         let check_interval = tokio::time::interval(config.connection_pool.check_interval());
         let (pool_cmd_sender, pool_cmd_receiver) = mpsc::channel(POOL_CMD_CHANNEL_SIZE);
-        let (conn_req_sender, conn_req_receiver) =
-            flume::bounded(config.connection_pool.max_idle_count());
         IcapServicePool {
             config,
             options,
@@ -70,14 +90,12 @@ impl IcapServicePool {
             client_cmd_receiver,
             pool_cmd_sender,
             pool_cmd_receiver,
-            conn_req_sender,
-            conn_req_receiver,
-            idle_conn_count: Arc::new(AtomicUsize::new(0)),
+            idle_pool,
         }
     }
 
     fn idle_conn_count(&self) -> usize {
-        self.idle_conn_count.load(Ordering::Relaxed)
+        self.idle_pool.stats.idle_count()
     }
 
     pub(super) async fn into_running(mut self) {
@@ -150,7 +168,7 @@ impl IcapServicePool {
             IcapServiceClientCommand::FetchConnection(sender) => {
                 if self.idle_conn_count() > 0 {
                     // there maybe race condition, so we have fallback at client side
-                    let req_sender = self.conn_req_sender.clone();
+                    let req_sender = self.idle_pool.conn_req_sender.clone();
                     let options = self.options.clone();
                     tokio::spawn(async move {
                         let _ = req_sender
@@ -179,19 +197,24 @@ impl IcapServicePool {
     }
 
     fn save_connection(&mut self, conn: IcapClientConnection) {
-        // it's ok to skip compare_swap as we only increase the idle count in the same future context
+        if conn.expired(self.config.connection_pool.max_lifetime()) {
+            self.idle_pool.stats.add_lifetime_expired();
+            return;
+        }
+        // the idle count may be increased by another service sharing the same idle pool,
+        // so the max idle count is not strictly enforced in this case
         if self.idle_conn_count() < self.config.connection_pool.max_idle_count() {
-            let Some(eof_poller) = IcapConnectionEofPoller::new(conn, &self.conn_req_receiver)
+            let Some(eof_poller) =
+                IcapConnectionEofPoller::new(conn, &self.idle_pool.conn_req_receiver)
             else {
                 return;
             };
-            let idle_count = self.idle_conn_count.clone();
-            // relaxed is fine as we only increase it here in the same future context
-            idle_count.fetch_add(1, Ordering::Relaxed);
-            let idle_timeout = self.config.connection_pool.idle_timeout();
+            let stats = self.idle_pool.stats.clone();
+            stats.add_idle();
+            let config = self.config.clone();
             tokio::spawn(async move {
-                eof_poller.into_running(idle_timeout).await;
-                idle_count.fetch_sub(1, Ordering::Relaxed);
+                eof_poller.into_running(config, stats.clone()).await;
+                stats.del_idle();
             });
         }
     }
@@ -0,0 +1,83 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+#[derive(Default)]
+pub(crate) struct IcapConnectionPoolStats {
+    idle: AtomicUsize,
+    created: AtomicU64,
+    reused: AtomicU64,
+    lifetime_expired: AtomicU64,
+    health_check_passed: AtomicU64,
+    health_check_failed: AtomicU64,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IcapConnectionPoolSnapshot {
+    pub idle: usize,
+    pub created: u64,
+    pub reused: u64,
+    pub lifetime_expired: u64,
+    pub health_check_passed: u64,
+    pub health_check_failed: u64,
+}
+
+impl IcapConnectionPoolStats {
+    #[inline]
+    pub(super) fn idle_count(&self) -> usize {
+        self.idle.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn add_idle(&self) {
+        self.idle.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn del_idle(&self) {
+        self.idle.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_created(&self) {
+        self.created.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_reused(&self) {
+        self.reused.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_lifetime_expired(&self) {
+        self.lifetime_expired.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(super) fn add_health_check(&self, passed: bool) {
+        if passed {
+            self.health_check_passed.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.health_check_failed.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(super) fn snapshot(&self) -> IcapConnectionPoolSnapshot {
+        IcapConnectionPoolSnapshot {
+            idle: self.idle.load(Ordering::Relaxed),
+            created: self.created.load(Ordering::Relaxed),
+            reused: self.reused.load(Ordering::Relaxed),
+            lifetime_expired: self.lifetime_expired.load(Ordering::Relaxed),
+            health_check_passed: self.health_check_passed.load(Ordering::Relaxed),
+            health_check_failed: self.health_check_failed.load(Ordering::Relaxed),
+        }
+    }
+}
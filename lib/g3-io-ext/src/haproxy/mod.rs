@@ -19,6 +19,8 @@ use std::net::SocketAddr;
 
 use thiserror::Error;
 
+use g3_types::net::ProxyProtocolTlv;
+
 mod v1;
 pub use v1::ProxyProtocolV1Reader;
 
@@ -28,6 +30,8 @@ pub use v2::ProxyProtocolV2Reader;
 pub struct ProxyAddr {
     pub src_addr: SocketAddr,
     pub dst_addr: SocketAddr,
+    /// the TLVs found in PROXY protocol v2 header
+    pub tlvs: Vec<ProxyProtocolTlv>,
 }
 
 #[derive(Debug, Error)]
@@ -54,4 +58,6 @@ pub enum ProxyProtocolReadError {
     InvalidSrcAddr,
     #[error("invalid dst address")]
     InvalidDstAddr,
+    #[error("invalid tlv")]
+    InvalidTlv,
 }
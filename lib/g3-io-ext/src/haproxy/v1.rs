@@ -118,6 +118,7 @@ impl ProxyProtocolV1Reader {
         Ok(Some(ProxyAddr {
             src_addr: SocketAddr::new(src_ip, src_port),
             dst_addr: SocketAddr::new(dst_ip, dst_port),
+            tlvs: Vec::new(),
         }))
     }
 
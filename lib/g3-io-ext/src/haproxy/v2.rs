@@ -19,6 +19,8 @@ use std::time::Duration;
 
 use tokio::io::{AsyncRead, AsyncReadExt};
 
+use g3_types::net::ProxyProtocolTlv;
+
 use super::{ProxyAddr, ProxyProtocolReadError};
 
 const PROXY_HDR_V2_LEN: usize = 16;
@@ -99,6 +101,7 @@ impl ProxyProtocolV2Reader {
             return Err(ProxyProtocolReadError::InvalidDataLength(data_len));
         }
 
+        let tlvs = self.get_tlvs(12, data_len)?;
         let b = &self.data_buf[0..12];
         let src_addr = Ipv4Addr::from([b[0], b[1], b[2], b[3]]);
         let dst_addr = Ipv4Addr::from([b[4], b[5], b[6], b[7]]);
@@ -108,6 +111,7 @@ impl ProxyProtocolV2Reader {
         Ok(ProxyAddr {
             src_addr: SocketAddr::new(IpAddr::V4(src_addr), src_port),
             dst_addr: SocketAddr::new(IpAddr::V4(dst_addr), dst_port),
+            tlvs,
         })
     }
 
@@ -116,6 +120,7 @@ impl ProxyProtocolV2Reader {
             return Err(ProxyProtocolReadError::InvalidDataLength(data_len));
         }
 
+        let tlvs = self.get_tlvs(36, data_len)?;
         let b = &self.data_buf[0..36];
         let src_addr = Ipv6Addr::from([
             b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7], b[8], b[9], b[10], b[11], b[12], b[13],
@@ -131,9 +136,32 @@ impl ProxyProtocolV2Reader {
         Ok(ProxyAddr {
             src_addr: SocketAddr::new(IpAddr::V6(src_addr), src_port),
             dst_addr: SocketAddr::new(IpAddr::V6(dst_addr), dst_port),
+            tlvs,
         })
     }
 
+    fn get_tlvs(
+        &self,
+        offset: usize,
+        data_len: usize,
+    ) -> Result<Vec<ProxyProtocolTlv>, ProxyProtocolReadError> {
+        let mut tlvs = Vec::new();
+        let mut left = &self.data_buf[offset..data_len];
+        while !left.is_empty() {
+            if left.len() < 3 {
+                return Err(ProxyProtocolReadError::InvalidTlv);
+            }
+            let kind = left[0];
+            let len = u16::from_be_bytes([left[1], left[2]]) as usize;
+            let Some(value) = left.get(3..3 + len) else {
+                return Err(ProxyProtocolReadError::InvalidTlv);
+            };
+            tlvs.push(ProxyProtocolTlv::new(kind, value.to_vec()));
+            left = &left[3 + len..];
+        }
+        Ok(tlvs)
+    }
+
     async fn read_in_data<R>(&mut self, reader: &mut R) -> Result<usize, ProxyProtocolReadError>
     where
         R: AsyncRead + Unpin,
@@ -216,6 +244,30 @@ mod tests {
         run_t(client, server).await;
     }
 
+    #[tokio::test]
+    async fn t_tcp4_tlv() {
+        let client = SocketAddr::from_str("192.168.0.1:56324").unwrap();
+        let server = SocketAddr::from_str("192.168.0.11:443").unwrap();
+        let tlv = ProxyProtocolTlv::new(0x02, b"example.net".to_vec());
+
+        let mut encoder = ProxyProtocolEncoder::new(ProxyProtocolVersion::V2);
+        let encoded = encoder
+            .encode_tcp_with_tlvs(client, server, [&tlv])
+            .unwrap();
+
+        let mut stream = tokio_test::io::Builder::new().read(encoded).build();
+
+        let mut reader = ProxyProtocolV2Reader::new(Duration::from_secs(1));
+        let addr = reader
+            .read_proxy_protocol_v2_for_tcp(&mut stream)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(addr.src_addr, client);
+        assert_eq!(addr.dst_addr, server);
+        assert_eq!(addr.tlvs, vec![tlv]);
+    }
+
     #[tokio::test]
     async fn t_tcp6() {
         let client = SocketAddr::from_str("[2001:db8::1]:56324").unwrap();
@@ -0,0 +1,231 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::future::poll_fn;
+use std::io;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::UdpSocket;
+
+/// IO that works on whole messages, a datagram socket or a framed stream
+pub trait AsyncMessageIo {
+    /// Receive a message into `buf` and return its length.
+    ///
+    /// The same buffer should be used for retry until a message is returned.
+    fn poll_recv_msg(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>>;
+
+    /// Send out a message, it may be buffered and sent out later in `poll_flush_msg`.
+    fn poll_send_msg(&mut self, cx: &mut Context<'_>, msg: &[u8]) -> Poll<io::Result<()>>;
+
+    /// Flush all buffered messages.
+    fn poll_flush_msg(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>>;
+}
+
+/// The socket should be connected to the peer address
+impl AsyncMessageIo for UdpSocket {
+    fn poll_recv_msg(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let mut buf = ReadBuf::new(buf);
+        ready!(self.poll_recv(cx, &mut buf))?;
+        Poll::Ready(Ok(buf.filled().len()))
+    }
+
+    fn poll_send_msg(&mut self, cx: &mut Context<'_>, msg: &[u8]) -> Poll<io::Result<()>> {
+        ready!(self.poll_send(cx, msg))?;
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_flush_msg(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+const LENGTH_PREFIX_SIZE: usize = 2;
+
+/// Messages on a stream, each one is prefixed by its length in a 2 bytes big endian integer
+pub struct LengthPrefixedStream<S> {
+    stream: S,
+    rd_hdr: [u8; LENGTH_PREFIX_SIZE],
+    rd_hdr_len: usize,
+    rd_msg_len: usize,
+    rd_msg_off: usize,
+    wr_buf: Vec<u8>,
+    wr_off: usize,
+}
+
+impl<S> LengthPrefixedStream<S> {
+    pub fn new(stream: S) -> Self {
+        LengthPrefixedStream {
+            stream,
+            rd_hdr: [0u8; LENGTH_PREFIX_SIZE],
+            rd_hdr_len: 0,
+            rd_msg_len: 0,
+            rd_msg_off: 0,
+            wr_buf: Vec::new(),
+            wr_off: 0,
+        }
+    }
+
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl<S> LengthPrefixedStream<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    fn poll_write_buf(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.wr_off < self.wr_buf.len() {
+            let nw =
+                ready!(Pin::new(&mut self.stream).poll_write(cx, &self.wr_buf[self.wr_off..]))?;
+            if nw == 0 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "write zero byte into writer",
+                )));
+            }
+            self.wr_off += nw;
+        }
+        self.wr_buf.clear();
+        self.wr_off = 0;
+        Poll::Ready(Ok(()))
+    }
+
+    pub async fn recv_msg(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        poll_fn(|cx| self.poll_recv_msg(cx, buf)).await
+    }
+
+    pub async fn send_msg(&mut self, msg: &[u8]) -> io::Result<()> {
+        poll_fn(|cx| self.poll_send_msg(cx, msg)).await?;
+        poll_fn(|cx| self.poll_flush_msg(cx)).await
+    }
+}
+
+impl<S> AsyncMessageIo for LengthPrefixedStream<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    fn poll_recv_msg(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        while self.rd_hdr_len < LENGTH_PREFIX_SIZE {
+            let mut hdr_buf = ReadBuf::new(&mut self.rd_hdr[self.rd_hdr_len..]);
+            ready!(Pin::new(&mut self.stream).poll_read(cx, &mut hdr_buf))?;
+            let nr = hdr_buf.filled().len();
+            if nr == 0 {
+                return Poll::Ready(Err(io::Error::from(io::ErrorKind::UnexpectedEof)));
+            }
+            self.rd_hdr_len += nr;
+            if self.rd_hdr_len == LENGTH_PREFIX_SIZE {
+                let len = u16::from_be_bytes(self.rd_hdr) as usize;
+                if len > buf.len() {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("too large message size {len}"),
+                    )));
+                }
+                self.rd_msg_len = len;
+                self.rd_msg_off = 0;
+            }
+        }
+
+        while self.rd_msg_off < self.rd_msg_len {
+            let mut msg_buf = ReadBuf::new(&mut buf[self.rd_msg_off..self.rd_msg_len]);
+            ready!(Pin::new(&mut self.stream).poll_read(cx, &mut msg_buf))?;
+            let nr = msg_buf.filled().len();
+            if nr == 0 {
+                return Poll::Ready(Err(io::Error::from(io::ErrorKind::UnexpectedEof)));
+            }
+            self.rd_msg_off += nr;
+        }
+
+        self.rd_hdr_len = 0;
+        Poll::Ready(Ok(self.rd_msg_len))
+    }
+
+    fn poll_send_msg(&mut self, cx: &mut Context<'_>, msg: &[u8]) -> Poll<io::Result<()>> {
+        let Ok(len) = u16::try_from(msg.len()) else {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("too large message size {}", msg.len()),
+            )));
+        };
+        ready!(self.poll_write_buf(cx))?;
+
+        self.wr_buf.extend_from_slice(&len.to_be_bytes());
+        self.wr_buf.extend_from_slice(msg);
+        // the message is buffered now, it's fine if we can't write it out right now
+        if let Poll::Ready(Err(e)) = self.poll_write_buf(cx) {
+            return Poll::Ready(Err(e));
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_flush_msg(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        ready!(self.poll_write_buf(cx))?;
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn length_prefixed() {
+        let (client, server) = tokio::io::duplex(8);
+        let mut client = LengthPrefixedStream::new(client);
+        let mut server = LengthPrefixedStream::new(server);
+
+        let send_task = tokio::spawn(async move {
+            client.send_msg(b"hello").await.unwrap();
+            client.send_msg(b"").await.unwrap();
+            client.send_msg(&[1u8; 100]).await.unwrap();
+            client
+        });
+
+        let mut buf = [0u8; 128];
+        let len = server.recv_msg(&mut buf).await.unwrap();
+        assert_eq!(&buf[..len], b"hello");
+        let len = server.recv_msg(&mut buf).await.unwrap();
+        assert_eq!(len, 0);
+        let len = server.recv_msg(&mut buf).await.unwrap();
+        assert_eq!(&buf[..len], &[1u8; 100]);
+
+        let client = send_task.await.unwrap();
+        drop(client);
+        let err = server.recv_msg(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn too_large() {
+        let (client, server) = tokio::io::duplex(1024);
+        let mut client = LengthPrefixedStream::new(client);
+        let mut server = LengthPrefixedStream::new(server);
+
+        client.send_msg(&[0u8; 64]).await.unwrap();
+        let mut buf = [0u8; 16];
+        let err = server.recv_msg(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -43,5 +43,8 @@ pub use ext::{LimitedBufReadExt, LimitedReadExt, LimitedWriteExt};
 mod idle;
 pub use idle::{IdleCheck, IdleForceQuitReason};
 
+mod message;
+pub use message::{AsyncMessageIo, LengthPrefixedStream};
+
 pub(super) mod stream;
 pub use stream::AsyncStream;
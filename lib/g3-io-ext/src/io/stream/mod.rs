@@ -33,6 +33,16 @@ impl AsyncStream for TcpStream {
     }
 }
 
+#[cfg(unix)]
+impl AsyncStream for tokio::net::UnixStream {
+    type R = tokio::net::unix::OwnedReadHalf;
+    type W = tokio::net::unix::OwnedWriteHalf;
+
+    fn into_split(self) -> (Self::R, Self::W) {
+        self.into_split()
+    }
+}
+
 impl<R, W> AsyncStream for Join<R, W>
 where
     R: AsyncRead,
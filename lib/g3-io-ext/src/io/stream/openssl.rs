@@ -14,9 +14,14 @@
  * limitations under the License.
  */
 
+use std::io;
+use std::net::SocketAddr;
+
 use tokio::io::{AsyncRead, AsyncWrite, ReadHalf, WriteHalf};
+use tokio::net::TcpStream;
 
-use g3_openssl::SslStream;
+use g3_openssl::{SslConnector, SslStream};
+use g3_types::net::{Host, OpensslClientConfig, OpensslSpkiPinSet};
 
 use super::AsyncStream;
 
@@ -73,3 +78,49 @@ where
         }
     }
 }
+
+/// Connect to the peer with TLS, and check its certificate against the pin set if not empty
+pub async fn connect_with_pin(
+    peer: SocketAddr,
+    tls_name: &Host,
+    tls_client: &OpensslClientConfig,
+    pin_set: &OpensslSpkiPinSet,
+) -> io::Result<SslStream<TcpStream>> {
+    let stream = TcpStream::connect(peer)
+        .await
+        .map_err(|e| io::Error::new(e.kind(), format!("failed to connect to {peer}: {e}")))?;
+
+    let ssl = tls_client
+        .build_ssl(tls_name, peer.port())
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("failed to build ssl: {e}")))?;
+    let connector = SslConnector::new(ssl, stream).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::Other,
+            format!("failed to get ssl stream: {e}"),
+        )
+    })?;
+    let stream = tokio::time::timeout(tls_client.handshake_timeout, connector.connect())
+        .await
+        .map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("tls handshake with {peer} timed out"),
+            )
+        })?
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                format!("tls handshake with {peer} failed: {e}"),
+            )
+        })?;
+
+    pin_set
+        .verify(stream.ssl().peer_certificate().as_deref())
+        .map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::Other,
+                format!("pin check failed for peer {peer}: {e}"),
+            )
+        })?;
+    Ok(stream)
+}
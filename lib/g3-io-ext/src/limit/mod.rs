@@ -29,6 +29,7 @@ pub enum GlobalLimitGroup {
     Server,
     User,
     UserSite,
+    Escaper,
 }
 
 mod datagram;
@@ -45,6 +45,11 @@ impl GlobalStreamLimiter {
         self.config.store(Arc::new(config));
     }
 
+    #[inline]
+    pub fn config(&self) -> GlobalStreamSpeedLimitConfig {
+        *self.config.load().as_ref()
+    }
+
     pub fn tokio_spawn_replenish(self: Arc<Self>) {
         let fut = async move {
             loop {
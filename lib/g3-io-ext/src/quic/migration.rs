@@ -0,0 +1,46 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use quinn::Connection;
+
+/// Watch the remote address change of a server side quic connection.
+///
+/// The callback will be called with the old and new remote address each time
+/// a connection migration is detected. This future will return after the
+/// connection is found to be closed.
+pub async fn watch_connection_migration<F>(connection: Connection, interval: Duration, mut f: F)
+where
+    F: FnMut(SocketAddr, SocketAddr),
+{
+    let mut remote_addr = connection.remote_address();
+    let mut interval = tokio::time::interval(interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        if connection.close_reason().is_some() {
+            return;
+        }
+
+        let new_addr = connection.remote_address();
+        if new_addr != remote_addr {
+            f(remote_addr, new_addr);
+            remote_addr = new_addr;
+        }
+    }
+}
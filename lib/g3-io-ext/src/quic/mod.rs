@@ -19,3 +19,6 @@ pub use udp_poller::QuinnUdpPollHelper;
 
 mod limited_socket;
 pub use limited_socket::{LimitedTokioRuntime, LimitedUdpSocket};
+
+mod migration;
+pub use migration::watch_connection_migration;
@@ -47,6 +47,17 @@ pub trait UdpSocketExt {
         iov: &mut [IoSliceMut<'_>],
     ) -> Poll<io::Result<(usize, Option<SocketAddr>)>>;
 
+    /// recvmsg with the original destination address set by TPROXY,
+    /// IP_RECVORIGDSTADDR / IPV6_RECVORIGDSTADDR should be enabled on the socket.
+    /// return `(nr, peer_addr, orig_dst_addr)`
+    #[cfg(target_os = "linux")]
+    #[allow(clippy::type_complexity)]
+    fn poll_recvmsg_with_orig_dst(
+        &self,
+        cx: &mut Context<'_>,
+        iov: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<(usize, Option<SocketAddr>, Option<SocketAddr>)>>;
+
     #[cfg(any(
         target_os = "linux",
         target_os = "android",
@@ -30,7 +30,9 @@ use tokio::net::UdpSocket;
 
 use super::UdpSocketExt;
 
+// aligned so that the buffer can be used as sockaddr_in / sockaddr_in6
 #[derive(Default)]
+#[repr(C, align(8))]
 struct RawSocketAddr {
     buf: [u8; mem::size_of::<libc::sockaddr_in6>()],
 }
@@ -104,6 +106,31 @@ impl From<SocketAddr> for RawSocketAddr {
     }
 }
 
+#[cfg(target_os = "linux")]
+fn sockaddr_storage_to_std(addr: &libc::sockaddr_storage) -> Option<SocketAddr> {
+    match addr.ss_family as libc::c_int {
+        libc::AF_INET => {
+            let v4 =
+                unsafe { &*(addr as *const libc::sockaddr_storage as *const libc::sockaddr_in) };
+            Some(SocketAddr::V4(SocketAddrV4::new(
+                Ipv4Addr::from(u32::from_be(v4.sin_addr.s_addr)),
+                u16::from_be(v4.sin_port),
+            )))
+        }
+        libc::AF_INET6 => {
+            let v6 =
+                unsafe { &*(addr as *const libc::sockaddr_storage as *const libc::sockaddr_in6) };
+            Some(SocketAddr::V6(SocketAddrV6::new(
+                Ipv6Addr::from(v6.sin6_addr.s6_addr),
+                u16::from_be(v6.sin6_port),
+                u32::from_be(v6.sin6_flowinfo),
+                v6.sin6_scope_id,
+            )))
+        }
+        _ => None,
+    }
+}
+
 pub struct SendMsgHdr<'a, const C: usize> {
     pub(crate) iov: [IoSlice<'a>; C],
     c_addr: Option<UnsafeCell<RawSocketAddr>>,
@@ -312,6 +339,80 @@ impl UdpSocketExt for UdpSocket {
         }
     }
 
+    #[cfg(target_os = "linux")]
+    fn poll_recvmsg_with_orig_dst(
+        &self,
+        cx: &mut Context<'_>,
+        iov: &mut [IoSliceMut<'_>],
+    ) -> Poll<io::Result<(usize, Option<SocketAddr>, Option<SocketAddr>)>> {
+        use std::os::fd::AsRawFd;
+
+        let raw_fd = self.as_raw_fd();
+
+        let mut recvmsg = || {
+            let mut c_addr = unsafe { mem::zeroed::<libc::sockaddr_storage>() };
+            let mut orig_dst = unsafe { mem::zeroed::<libc::sockaddr_storage>() };
+            // large enough for a single IP(V6)_ORIGDSTADDR cmsg, and aligned for cmsghdr
+            let mut control = [0u64; 8];
+
+            let mut h = unsafe { mem::zeroed::<libc::msghdr>() };
+            h.msg_name = ptr::addr_of_mut!(c_addr) as _;
+            h.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as _;
+            h.msg_iov = iov.as_mut_ptr() as _;
+            h.msg_iovlen = iov.len() as _;
+            h.msg_control = control.as_mut_ptr() as _;
+            h.msg_controllen = mem::size_of_val(&control) as _;
+
+            let r = unsafe { libc::recvmsg(raw_fd, &mut h, libc::MSG_DONTWAIT) };
+            if r < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(&h) };
+            while !cmsg.is_null() {
+                let hdr = unsafe { &*cmsg };
+                if (hdr.cmsg_level == libc::SOL_IP && hdr.cmsg_type == libc::IP_ORIGDSTADDR)
+                    || (hdr.cmsg_level == libc::SOL_IPV6 && hdr.cmsg_type == libc::IPV6_ORIGDSTADDR)
+                {
+                    let data = unsafe { libc::CMSG_DATA(cmsg) };
+                    let hdr_len = data as usize - cmsg as usize;
+                    let data_len = (hdr.cmsg_len as usize)
+                        .saturating_sub(hdr_len)
+                        .min(mem::size_of::<libc::sockaddr_storage>());
+                    // the cmsg data may be unaligned, copy it out as bytes
+                    unsafe {
+                        ptr::copy_nonoverlapping(
+                            data,
+                            ptr::addr_of_mut!(orig_dst) as *mut u8,
+                            data_len,
+                        )
+                    };
+                    break;
+                }
+                cmsg = unsafe { libc::CMSG_NXTHDR(&h, cmsg) };
+            }
+
+            Ok((
+                r as usize,
+                sockaddr_storage_to_std(&c_addr),
+                sockaddr_storage_to_std(&orig_dst),
+            ))
+        };
+
+        loop {
+            ready!(self.poll_recv_ready(cx))?;
+            match self.try_io(Interest::READABLE, &mut recvmsg) {
+                Ok(r) => return Poll::Ready(Ok(r)),
+                Err(e) => {
+                    if e.kind() == io::ErrorKind::WouldBlock {
+                        continue;
+                    }
+                    return Poll::Ready(Err(e));
+                }
+            }
+        }
+    }
+
     #[cfg(any(
         target_os = "linux",
         target_os = "android",
@@ -639,4 +740,40 @@ mod tests {
         assert_eq!(addr, Some(c_addr));
         assert_eq!(&recv_iov[0][..len], msg_2);
     }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn recvmsg_with_orig_dst() {
+        use std::os::fd::AsRawFd;
+
+        let s_sock = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let s_addr = s_sock.local_addr().unwrap();
+        let enable: libc::c_int = 1;
+        let r = unsafe {
+            libc::setsockopt(
+                s_sock.as_raw_fd(),
+                libc::SOL_IP,
+                libc::IP_RECVORIGDSTADDR,
+                ptr::addr_of!(enable) as _,
+                mem::size_of::<libc::c_int>() as _,
+            )
+        };
+        assert_eq!(r, 0);
+
+        let c_sock = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let c_addr = c_sock.local_addr().unwrap();
+
+        let msg = b"abcd";
+        c_sock.send_to(msg, s_addr).await.unwrap();
+
+        let mut buf = [0u8; 16];
+        let (nr, peer_addr, orig_dst) =
+            poll_fn(|cx| s_sock.poll_recvmsg_with_orig_dst(cx, &mut [IoSliceMut::new(&mut buf)]))
+                .await
+                .unwrap();
+        assert_eq!(nr, msg.len());
+        assert_eq!(&buf[..nr], msg);
+        assert_eq!(peer_addr, Some(c_addr));
+        assert_eq!(orig_dst, Some(s_addr));
+    }
 }
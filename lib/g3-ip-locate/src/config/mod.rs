@@ -20,7 +20,9 @@ use std::time::Duration;
 use anyhow::anyhow;
 use tokio::net::UdpSocket;
 
-use g3_types::net::SocketBufferConfig;
+use g3_types::net::{
+    Host, OpensslClientConfig, OpensslClientConfigBuilder, OpensslSpkiPinSet, SocketBufferConfig,
+};
 
 use super::{IpLocationQueryRuntime, IpLocationServiceHandle};
 
@@ -34,6 +36,9 @@ pub struct IpLocateServiceConfig {
     pub(crate) query_peer_addr: SocketAddr,
     pub(crate) query_socket_buffer: SocketBufferConfig,
     pub(crate) query_wait_timeout: Duration,
+    pub(crate) query_tls_client: Option<OpensslClientConfigBuilder>,
+    pub(crate) query_tls_name: Option<Host>,
+    pub(crate) query_tls_pin: OpensslSpkiPinSet,
     pub(crate) default_expire_ttl: u32,
     pub(crate) maximum_expire_ttl: u32,
 }
@@ -46,6 +51,9 @@ impl Default for IpLocateServiceConfig {
             query_peer_addr: SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 2888),
             query_socket_buffer: SocketBufferConfig::default(),
             query_wait_timeout: Duration::from_secs(1),
+            query_tls_client: None,
+            query_tls_name: None,
+            query_tls_pin: OpensslSpkiPinSet::default(),
             default_expire_ttl: 10,
             maximum_expire_ttl: 300,
         }
@@ -73,6 +81,18 @@ impl IpLocateServiceConfig {
         self.query_wait_timeout = time;
     }
 
+    pub fn set_query_tls_client(&mut self, builder: OpensslClientConfigBuilder) {
+        self.query_tls_client = Some(builder);
+    }
+
+    pub fn set_query_tls_name(&mut self, name: Host) {
+        self.query_tls_name = Some(name);
+    }
+
+    pub fn set_query_tls_pin(&mut self, pin_set: OpensslSpkiPinSet) {
+        self.query_tls_pin = pin_set;
+    }
+
     pub fn set_default_expire_ttl(&mut self, ttl: u32) {
         self.default_expire_ttl = ttl;
     }
@@ -84,6 +104,13 @@ impl IpLocateServiceConfig {
     pub fn spawn_ip_locate_agent(&self) -> anyhow::Result<IpLocationServiceHandle> {
         use anyhow::Context;
 
+        if let Some(builder) = &self.query_tls_client {
+            let tls_client = builder
+                .build()
+                .context("failed to build tls client config")?;
+            return Ok(self.spawn_tls_ip_locate_agent(tls_client));
+        }
+
         let socket = g3_socket::udp::new_std_socket_to(
             self.query_peer_addr,
             &Default::default(),
@@ -118,4 +145,22 @@ impl IpLocateServiceConfig {
             self.cache_request_timeout,
         ))
     }
+
+    fn spawn_tls_ip_locate_agent(
+        &self,
+        tls_client: OpensslClientConfig,
+    ) -> IpLocationServiceHandle {
+        let (cache_runtime, cache_handle, query_handle) = super::crate_ip_location_cache(self);
+
+        let query_runtime = super::query::run_with_tls(self.clone(), tls_client, query_handle);
+        if let Some(rt) = crate::get_ip_locate_rt_handle() {
+            rt.spawn(query_runtime);
+            rt.spawn(cache_runtime);
+        } else {
+            tokio::spawn(query_runtime);
+            tokio::spawn(cache_runtime);
+        }
+
+        IpLocationServiceHandle::new(cache_handle, self.cache_request_timeout)
+    }
 }
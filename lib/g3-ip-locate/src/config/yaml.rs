@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+use std::path::Path;
+
 use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
@@ -26,7 +28,7 @@ impl IpLocateServiceConfig {
         Ok(())
     }
 
-    pub fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+    pub fn parse_yaml(value: &Yaml, lookup_dir: Option<&Path>) -> anyhow::Result<Self> {
         match value {
             Yaml::Hash(map) => {
                 let mut config = IpLocateServiceConfig::default();
@@ -61,6 +63,26 @@ impl IpLocateServiceConfig {
                         config.set_query_wait_timeout(time);
                         Ok(())
                     }
+                    "query_tls_client" => {
+                        let builder = g3_yaml::value::as_to_one_openssl_tls_client_config_builder(
+                            v, lookup_dir,
+                        )
+                        .context(format!("invalid tls client config value for key {k}"))?;
+                        config.set_query_tls_client(builder);
+                        Ok(())
+                    }
+                    "query_tls_name" => {
+                        let name = g3_yaml::value::as_host(v)
+                            .context(format!("invalid tls server name value for key {k}"))?;
+                        config.set_query_tls_name(name);
+                        Ok(())
+                    }
+                    "query_tls_pin" => {
+                        let pin_set = g3_yaml::value::as_openssl_spki_pin_set(v)
+                            .context(format!("invalid spki pin value for key {k}"))?;
+                        config.set_query_tls_pin(pin_set);
+                        Ok(())
+                    }
                     "default_expire_ttl" => {
                         let ttl = g3_yaml::value::as_u32(v)?;
                         config.set_default_expire_ttl(ttl);
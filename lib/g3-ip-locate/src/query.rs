@@ -15,7 +15,7 @@
  */
 
 use std::collections::VecDeque;
-use std::future::Future;
+use std::future::{poll_fn, Future};
 use std::io;
 use std::net::IpAddr;
 use std::pin::Pin;
@@ -24,15 +24,16 @@ use std::time::Duration;
 
 use anyhow::anyhow;
 use log::warn;
-use tokio::io::ReadBuf;
-use tokio::net::UdpSocket;
+
+use g3_io_ext::{AsyncMessageIo, LengthPrefixedStream};
+use g3_types::net::{Host, OpensslClientConfig};
 
 use super::{
     IpLocateServiceConfig, IpLocationCacheResponse, IpLocationQueryHandle, Request, Response,
 };
 
-pub(crate) struct IpLocationQueryRuntime {
-    socket: UdpSocket,
+pub(crate) struct IpLocationQueryRuntime<T> {
+    io: T,
     query_handle: IpLocationQueryHandle,
     read_buffer: Box<[u8]>,
     write_queue: VecDeque<(IpAddr, Vec<u8>)>,
@@ -41,14 +42,14 @@ pub(crate) struct IpLocationQueryRuntime {
     query_wait: Duration,
 }
 
-impl IpLocationQueryRuntime {
+impl<T: AsyncMessageIo> IpLocationQueryRuntime<T> {
     pub(crate) fn new(
         config: &IpLocateServiceConfig,
-        socket: UdpSocket,
+        io: T,
         query_handle: IpLocationQueryHandle,
     ) -> Self {
         IpLocationQueryRuntime {
-            socket,
+            io,
             query_handle,
             read_buffer: vec![0u8; 16384].into_boxed_slice(),
             write_queue: VecDeque::new(),
@@ -58,6 +59,10 @@ impl IpLocationQueryRuntime {
         }
     }
 
+    pub(crate) fn into_query_handle(self) -> IpLocationQueryHandle {
+        self.query_handle
+    }
+
     fn send_empty_result(&mut self, ip: IpAddr, ttl: u32, expired: bool) {
         let result = IpLocationCacheResponse::empty(ttl);
         self.query_handle.send_rsp_data(Some(ip), result, expired);
@@ -104,18 +109,16 @@ impl IpLocationQueryRuntime {
         }
     }
 
-    fn poll_loop(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    pub(crate) fn poll_loop(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         loop {
             // handle rsp
-            let mut buf = ReadBuf::new(&mut self.read_buffer);
-            match self.socket.poll_recv(cx, &mut buf) {
+            match self.io.poll_recv_msg(cx, &mut self.read_buffer) {
                 Poll::Pending => {}
                 Poll::Ready(Err(e)) => {
                     warn!("socket recv error: {e:?}");
                     return Poll::Ready(Err(e));
                 }
-                Poll::Ready(Ok(_)) => {
-                    let len = buf.filled().len();
+                Poll::Ready(Ok(len)) => {
                     if len > 0 {
                         self.handle_rsp(len);
                     }
@@ -125,7 +128,7 @@ impl IpLocationQueryRuntime {
 
             // send req from write queue
             while let Some((ip, buf)) = self.write_queue.pop_front() {
-                match self.socket.poll_send(cx, &buf) {
+                match self.io.poll_send_msg(cx, &buf) {
                     Poll::Pending => {
                         self.write_queue.push_front((ip, buf));
                         break;
@@ -137,6 +140,12 @@ impl IpLocationQueryRuntime {
                 }
             }
 
+            // flush the buffered req
+            if let Poll::Ready(Err(e)) = self.io.poll_flush_msg(cx) {
+                warn!("socket send error: {e:?}");
+                return Poll::Ready(Err(e));
+            }
+
             // handle timeout
             loop {
                 match self.query_handle.poll_query_expired(cx) {
@@ -158,10 +167,44 @@ impl IpLocationQueryRuntime {
     }
 }
 
-impl Future for IpLocationQueryRuntime {
+impl<T: AsyncMessageIo + Unpin> Future for IpLocationQueryRuntime<T> {
     type Output = io::Result<()>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         (*self).poll_loop(cx)
     }
 }
+
+const TLS_RECONNECT_WAIT: Duration = Duration::from_secs(1);
+
+/// Run the query runtime over a TLS connection, and reconnect if it's broken
+pub(crate) async fn run_with_tls(
+    config: IpLocateServiceConfig,
+    tls_client: OpensslClientConfig,
+    mut query_handle: IpLocationQueryHandle,
+) {
+    let peer = config.query_peer_addr;
+    let tls_name = config.query_tls_name.clone().unwrap_or(Host::Ip(peer.ip()));
+    loop {
+        match g3_io_ext::openssl::connect_with_pin(
+            peer,
+            &tls_name,
+            &tls_client,
+            &config.query_tls_pin,
+        )
+        .await
+        {
+            Ok(stream) => {
+                let io = LengthPrefixedStream::new(stream);
+                let mut runtime = IpLocationQueryRuntime::new(&config, io, query_handle);
+                if poll_fn(|cx| runtime.poll_loop(cx)).await.is_ok() {
+                    // all cache handles have been dropped
+                    return;
+                }
+                query_handle = runtime.into_query_handle();
+            }
+            Err(e) => warn!("failed to setup tls connection to ip locate service: {e}"),
+        }
+        tokio::time::sleep(TLS_RECONNECT_WAIT).await;
+    }
+}
@@ -18,6 +18,32 @@ pub fn normalize(raw: &str) -> String {
     raw.to_lowercase().replace('-', "_")
 }
 
+fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Find the closest one in the normalized `candidates` for a mistyped key.
+pub fn suggest<'a>(raw: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let key = normalize(raw);
+    let max_distance = (key.len() / 3).max(1);
+    candidates
+        .iter()
+        .map(|c| (edit_distance(key.as_bytes(), c.as_bytes()), *c))
+        .filter(|(d, _)| *d <= max_distance)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, c)| c)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -29,4 +55,14 @@ mod tests {
         assert_eq!(normalize("A-B-C"), "a_b_c");
         assert_eq!(normalize("A-B_C"), "a_b_c");
     }
+
+    #[test]
+    fn suggestion() {
+        let candidates = ["server", "escaper", "resolver", "user_group"];
+        assert_eq!(suggest("sever", &candidates), Some("server"));
+        assert_eq!(suggest("Escapers", &candidates), Some("escaper"));
+        assert_eq!(suggest("user-groups", &candidates), Some("user_group"));
+        assert_eq!(suggest("runtime", &candidates), None);
+        assert_eq!(suggest("a", &candidates), None);
+    }
 }
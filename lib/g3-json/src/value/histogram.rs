@@ -20,7 +20,7 @@ use std::str::FromStr;
 use anyhow::{anyhow, Context};
 use serde_json::Value;
 
-use g3_histogram::{HistogramMetricsConfig, Quantile};
+use g3_histogram::{HistogramBuckets, HistogramMetricsConfig, Quantile};
 
 pub fn as_quantile(value: &Value) -> anyhow::Result<Quantile> {
     match value {
@@ -66,6 +66,99 @@ pub fn as_quantile_list(value: &Value) -> anyhow::Result<BTreeSet<Quantile>> {
     Ok(set)
 }
 
+fn as_bucket_bounds(value: &Value) -> anyhow::Result<Vec<u64>> {
+    match value {
+        Value::String(s) => {
+            let mut bounds = Vec::new();
+            for v in s.split(',') {
+                let bound =
+                    u64::from_str(v.trim()).map_err(|e| anyhow!("invalid bound {v}: {e}"))?;
+                bounds.push(bound);
+            }
+            Ok(bounds)
+        }
+        Value::Array(seq) => {
+            let mut bounds = Vec::with_capacity(seq.len());
+            for (i, v) in seq.iter().enumerate() {
+                let bound = crate::value::as_u64(v)
+                    .context(format!("invalid u64 value for element #{i}"))?;
+                bounds.push(bound);
+            }
+            Ok(bounds)
+        }
+        _ => Err(anyhow!(
+            "the json value type for 'histogram bucket bounds' should be 'array' or 'string'"
+        )),
+    }
+}
+
+pub fn as_histogram_buckets(value: &Value) -> anyhow::Result<HistogramBuckets> {
+    let Value::Object(map) = value else {
+        let bounds = as_bucket_bounds(value)?;
+        return HistogramBuckets::explicit(bounds)
+            .map_err(|e| anyhow!("invalid explicit bucket bounds: {e}"));
+    };
+
+    let layout = crate::get_required_str(map, "type")?;
+    match crate::key::normalize(layout).as_str() {
+        "explicit" => {
+            let v = crate::map_get_required(map, "bounds")?;
+            let bounds =
+                as_bucket_bounds(v).context("invalid bucket bounds value for key bounds")?;
+            HistogramBuckets::explicit(bounds)
+                .map_err(|e| anyhow!("invalid explicit bucket bounds: {e}"))
+        }
+        "log_linear" | "loglinear" => {
+            let mut min = 1;
+            let mut max = 0;
+            let mut steps = 9;
+            for (k, v) in map {
+                match crate::key::normalize(k).as_str() {
+                    "type" => {}
+                    "min" => {
+                        min = crate::value::as_u64(v)
+                            .context(format!("invalid u64 value for key {k}"))?;
+                    }
+                    "max" => {
+                        max = crate::value::as_u64(v)
+                            .context(format!("invalid u64 value for key {k}"))?;
+                    }
+                    "steps" => {
+                        steps = crate::value::as_u16(v)
+                            .context(format!("invalid u16 value for key {k}"))?;
+                    }
+                    _ => return Err(anyhow!("invalid key {k}")),
+                }
+            }
+            HistogramBuckets::log_linear(min, max, steps)
+                .map_err(|e| anyhow!("invalid log linear buckets: {e}"))
+        }
+        "exponential" | "base2_exponential" => {
+            let mut scale = 20;
+            let mut max_size = 160;
+            for (k, v) in map {
+                match crate::key::normalize(k).as_str() {
+                    "type" => {}
+                    "scale" => {
+                        let v = crate::value::as_i32(v)
+                            .context(format!("invalid i32 value for key {k}"))?;
+                        scale = i8::try_from(v)
+                            .map_err(|_| anyhow!("out of range scale value {v} for key {k}"))?;
+                    }
+                    "max_size" => {
+                        max_size = crate::value::as_u16(v)
+                            .context(format!("invalid u16 value for key {k}"))?;
+                    }
+                    _ => return Err(anyhow!("invalid key {k}")),
+                }
+            }
+            HistogramBuckets::exponential(scale, max_size)
+                .map_err(|e| anyhow!("invalid exponential buckets: {e}"))
+        }
+        _ => Err(anyhow!("unsupported histogram bucket type {layout}")),
+    }
+}
+
 pub fn as_histogram_metrics_config(value: &Value) -> anyhow::Result<HistogramMetricsConfig> {
     if let Value::Object(map) = value {
         let mut config = HistogramMetricsConfig::default();
@@ -81,6 +174,11 @@ pub fn as_histogram_metrics_config(value: &Value) -> anyhow::Result<HistogramMet
                         .context(format!("invalid humanize duration value for key {k}"))?;
                     config.set_rotate_interval(rotate);
                 }
+                "bucket" | "buckets" => {
+                    let buckets = as_histogram_buckets(v)
+                        .context(format!("invalid histogram buckets value for key {k}"))?;
+                    config.set_buckets(buckets);
+                }
                 _ => return Err(anyhow!("invalid key {k}")),
             }
         }
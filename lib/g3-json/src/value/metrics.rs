@@ -14,13 +14,14 @@
  * limitations under the License.
  */
 
+use std::collections::BTreeMap;
 use std::str::FromStr;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
 use serde_json::Value;
 
 use g3_types::collection::WeightedValue;
-use g3_types::metrics::NodeName;
+use g3_types::metrics::{MetricTagName, MetricTagValue, NodeName, StaticMetricsTags};
 
 pub fn as_metrics_name(v: &Value) -> anyhow::Result<NodeName> {
     if let Value::String(s) = v {
@@ -33,6 +34,23 @@ pub fn as_metrics_name(v: &Value) -> anyhow::Result<NodeName> {
     }
 }
 
+pub fn as_static_metrics_tags(v: &Value) -> anyhow::Result<StaticMetricsTags> {
+    if let Value::Object(map) = v {
+        let mut tags = BTreeMap::new();
+        for (k, v) in map {
+            let name = MetricTagName::from_str(k).context("invalid metrics tag name")?;
+            let value_s = crate::value::as_string(v).context("invalid metrics tag json value")?;
+            let value = MetricTagValue::from_str(&value_s).context("invalid metrics tag value")?;
+            tags.insert(name, value);
+        }
+        Ok(tags)
+    } else {
+        Err(anyhow!(
+            "the json value type for 'static metric tags' should be 'map'"
+        ))
+    }
+}
+
 pub fn as_weighted_metrics_name(value: &Value) -> anyhow::Result<WeightedValue<NodeName>> {
     if let Value::Object(map) = value {
         let mut name = NodeName::default();
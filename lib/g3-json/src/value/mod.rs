@@ -25,11 +25,11 @@ mod speed_limit;
 
 pub use auth::{as_password, as_username};
 pub use datetime::as_rfc3339_datetime;
-pub use metrics::{as_metrics_name, as_weighted_metrics_name};
+pub use metrics::{as_metrics_name, as_static_metrics_tags, as_weighted_metrics_name};
 pub use net::*;
 pub use primary::{
     as_ascii, as_bool, as_bytes, as_f64, as_hashmap, as_i32, as_list, as_nonzero_u32, as_string,
-    as_u16, as_u32, as_u8, as_usize,
+    as_u16, as_u32, as_u64, as_u8, as_usize,
 };
 pub use random::as_random_ratio;
 pub use rate_limit::as_rate_limit_quota;
@@ -73,4 +73,6 @@ pub use route::*;
 #[cfg(feature = "histogram")]
 mod histogram;
 #[cfg(feature = "histogram")]
-pub use histogram::{as_histogram_metrics_config, as_quantile, as_quantile_list};
+pub use histogram::{
+    as_histogram_buckets, as_histogram_metrics_config, as_quantile, as_quantile_list,
+};
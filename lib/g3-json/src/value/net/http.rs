@@ -36,6 +36,11 @@ pub fn as_http_keepalive_config(v: &Value) -> anyhow::Result<HttpKeepAliveConfig
                             .context(format!("invalid humanize duration value for key {k}"))?;
                         config.set_idle_expire(idle_expire);
                     }
+                    "max_lifetime" => {
+                        let max_lifetime = crate::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_max_lifetime(max_lifetime);
+                    }
                     _ => return Err(anyhow!("invalid key {k}")),
                 }
             }
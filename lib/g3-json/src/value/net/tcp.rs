@@ -17,7 +17,7 @@
 use anyhow::{anyhow, Context};
 use serde_json::Value;
 
-use g3_types::net::{TcpConnectConfig, TcpKeepAliveConfig, TcpMiscSockOpts};
+use g3_types::net::{TcpConnectConfig, TcpConnectRaceConfig, TcpKeepAliveConfig, TcpMiscSockOpts};
 
 pub fn as_tcp_connect_config(v: &Value) -> anyhow::Result<TcpConnectConfig> {
     if let Value::Object(map) = v {
@@ -47,6 +47,46 @@ pub fn as_tcp_connect_config(v: &Value) -> anyhow::Result<TcpConnectConfig> {
     }
 }
 
+pub fn as_tcp_connect_race_config(v: &Value) -> anyhow::Result<TcpConnectRaceConfig> {
+    let mut config = TcpConnectRaceConfig::default();
+
+    match v {
+        Value::Object(map) => {
+            config.set_enable(true);
+            for (k, v) in map {
+                match crate::key::normalize(k).as_str() {
+                    "enable" => {
+                        let enable = crate::value::as_bool(v)
+                            .context(format!("invalid boolean value for key {k}"))?;
+                        config.set_enable(enable);
+                    }
+                    "resolution_delay" => {
+                        let delay = crate::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_resolution_delay(delay);
+                    }
+                    "stagger" | "stagger_delay" => {
+                        let stagger = crate::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_stagger(stagger);
+                    }
+                    _ => return Err(anyhow!("invalid key {k}")),
+                }
+            }
+        }
+        Value::Bool(enable) => {
+            config.set_enable(*enable);
+        }
+        _ => {
+            return Err(anyhow!(
+                "json value type for 'TcpConnectRaceConfig' should be 'map' or 'bool'"
+            ));
+        }
+    }
+
+    Ok(config)
+}
+
 pub fn as_tcp_keepalive_config(v: &Value) -> anyhow::Result<TcpKeepAliveConfig> {
     let mut config = TcpKeepAliveConfig::default();
 
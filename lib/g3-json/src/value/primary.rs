@@ -87,6 +87,18 @@ pub fn as_u32(v: &Value) -> anyhow::Result<u32> {
     }
 }
 
+pub fn as_u64(v: &Value) -> anyhow::Result<u64> {
+    match v {
+        Value::String(s) => Ok(u64::from_str(s)?),
+        Value::Number(n) => n
+            .as_u64()
+            .ok_or_else(|| anyhow!("out of range json value for u64")),
+        _ => Err(anyhow!(
+            "json value type for 'u64' should be 'string' or 'positive integer'"
+        )),
+    }
+}
+
 pub fn as_nonzero_u32(v: &Value) -> anyhow::Result<NonZeroU32> {
     match v {
         Value::String(s) => Ok(NonZeroU32::from_str(s)?),
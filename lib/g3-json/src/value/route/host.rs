@@ -20,7 +20,7 @@ use anyhow::{anyhow, Context};
 use serde_json::Value;
 
 use g3_types::net::Host;
-use g3_types::route::HostMatch;
+use g3_types::route::{HostMatch, HostPattern};
 
 use crate::JsonMapCallback;
 
@@ -147,3 +147,23 @@ where
 
     Ok(obj)
 }
+
+pub fn as_host_glob_pattern(value: &Value) -> anyhow::Result<HostPattern> {
+    if let Value::String(s) = value {
+        HostPattern::new_glob(s)
+    } else {
+        Err(anyhow!(
+            "json value type for 'host glob pattern' should be 'string'"
+        ))
+    }
+}
+
+pub fn as_host_regex_pattern(value: &Value) -> anyhow::Result<HostPattern> {
+    if let Value::String(s) = value {
+        HostPattern::new_regex(s)
+    } else {
+        Err(anyhow!(
+            "json value type for 'host regex pattern' should be 'string'"
+        ))
+    }
+}
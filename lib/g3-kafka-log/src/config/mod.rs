@@ -0,0 +1,205 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::time::Duration;
+
+use anyhow::anyhow;
+use rskafka::client::partition::{Compression, PartitionClient, UnknownTopicHandling};
+use rskafka::client::ClientBuilder;
+
+#[cfg(feature = "yaml")]
+mod yaml;
+
+const KAFKA_DEFAULT_BROKER: &str = "127.0.0.1:9092";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KafkaCompression {
+    #[default]
+    None,
+    Gzip,
+    Lz4,
+    Snappy,
+    Zstd,
+}
+
+impl KafkaCompression {
+    pub(crate) fn codec(&self) -> Compression {
+        match self {
+            KafkaCompression::None => Compression::NoCompression,
+            KafkaCompression::Gzip => Compression::Gzip,
+            KafkaCompression::Lz4 => Compression::Lz4,
+            KafkaCompression::Snappy => Compression::Snappy,
+            KafkaCompression::Zstd => Compression::Zstd,
+        }
+    }
+}
+
+impl std::str::FromStr for KafkaCompression {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" | "no" => Ok(KafkaCompression::None),
+            "gzip" => Ok(KafkaCompression::Gzip),
+            "lz4" => Ok(KafkaCompression::Lz4),
+            "snappy" => Ok(KafkaCompression::Snappy),
+            "zstd" => Ok(KafkaCompression::Zstd),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum KafkaPartitioner {
+    /// spread records to all partitions in turn
+    #[default]
+    RoundRobin,
+    /// select a random partition for each record
+    Random,
+    /// send all records to the specified partition
+    Fixed(i32),
+    /// use the value of the log field as record key, and select partition by the hash of it,
+    /// the same way as the default partitioner in the java client
+    KeyField(String),
+}
+
+#[derive(Clone)]
+pub struct KafkaProducerConfig {
+    brokers: Vec<String>,
+    topic: Option<String>,
+    pub(crate) partitioner: KafkaPartitioner,
+    pub(crate) compression: KafkaCompression,
+    pub(crate) batch_size: usize,
+    pub(crate) linger: Duration,
+    pub(crate) connect_timeout: Duration,
+    pub(crate) connect_delay: Duration,
+    pub(crate) produce_timeout: Duration,
+    pub(crate) retry_queue_len: usize,
+}
+
+impl Default for KafkaProducerConfig {
+    fn default() -> Self {
+        KafkaProducerConfig::new(vec![KAFKA_DEFAULT_BROKER.to_string()])
+    }
+}
+
+impl KafkaProducerConfig {
+    pub fn new(brokers: Vec<String>) -> Self {
+        KafkaProducerConfig {
+            brokers,
+            topic: None,
+            partitioner: KafkaPartitioner::default(),
+            compression: KafkaCompression::default(),
+            batch_size: 128,
+            linger: Duration::from_millis(100),
+            connect_timeout: Duration::from_secs(10),
+            connect_delay: Duration::from_secs(10),
+            produce_timeout: Duration::from_secs(5),
+            retry_queue_len: 1024,
+        }
+    }
+
+    pub fn set_brokers(&mut self, brokers: Vec<String>) {
+        self.brokers = brokers;
+    }
+
+    pub fn set_topic(&mut self, topic: String) {
+        self.topic = Some(topic);
+    }
+
+    pub fn set_partitioner(&mut self, partitioner: KafkaPartitioner) {
+        self.partitioner = partitioner;
+    }
+
+    pub fn set_compression(&mut self, compression: KafkaCompression) {
+        self.compression = compression;
+    }
+
+    pub fn set_batch_size(&mut self, size: usize) {
+        self.batch_size = size.max(1);
+    }
+
+    pub fn set_linger(&mut self, linger: Duration) {
+        self.linger = linger.max(Duration::from_millis(1));
+    }
+
+    pub fn set_connect_timeout(&mut self, timeout: Duration) {
+        self.connect_timeout = timeout;
+    }
+
+    pub fn set_connect_delay(&mut self, delay: Duration) {
+        self.connect_delay = delay;
+    }
+
+    pub fn set_produce_timeout(&mut self, timeout: Duration) {
+        self.produce_timeout = timeout;
+    }
+
+    pub fn set_retry_queue_len(&mut self, len: usize) {
+        self.retry_queue_len = len;
+    }
+
+    pub(crate) fn key_field(&self) -> Option<&str> {
+        match &self.partitioner {
+            KafkaPartitioner::KeyField(field) => Some(field.as_str()),
+            _ => None,
+        }
+    }
+
+    /// get the topic name, fallback to `<default_prefix>.<log_type>` if not set
+    pub(crate) fn topic(&self, default_prefix: &str, log_type: &str) -> String {
+        match &self.topic {
+            Some(topic) => topic.clone(),
+            None => format!("{default_prefix}.{log_type}"),
+        }
+    }
+
+    pub(crate) async fn new_partition_clients(
+        &self,
+        topic: &str,
+    ) -> anyhow::Result<Vec<PartitionClient>> {
+        let client = ClientBuilder::new(self.brokers.clone())
+            .build()
+            .await
+            .map_err(|e| anyhow!("failed to connect to kafka brokers: {e}"))?;
+
+        let topics = client
+            .list_topics()
+            .await
+            .map_err(|e| anyhow!("failed to list kafka topics: {e}"))?;
+        let Some(topic_meta) = topics.into_iter().find(|t| t.name == topic) else {
+            return Err(anyhow!("kafka topic {topic} not found"));
+        };
+        if topic_meta.partitions.is_empty() {
+            return Err(anyhow!("no partition found for kafka topic {topic}"));
+        }
+        if let KafkaPartitioner::Fixed(p) = &self.partitioner {
+            if !topic_meta.partitions.contains(p) {
+                return Err(anyhow!("partition {p} not found for kafka topic {topic}"));
+            }
+        }
+
+        let mut partition_clients = Vec::with_capacity(topic_meta.partitions.len());
+        for p in topic_meta.partitions {
+            let partition_client = client
+                .partition_client(topic, p, UnknownTopicHandling::Error)
+                .await
+                .map_err(|e| anyhow!("failed to create client for partition {p}: {e}"))?;
+            partition_clients.push(partition_client);
+        }
+        Ok(partition_clients)
+    }
+}
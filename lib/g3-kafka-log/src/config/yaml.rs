@@ -0,0 +1,153 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use super::{KafkaCompression, KafkaPartitioner, KafkaProducerConfig};
+
+impl KafkaProducerConfig {
+    pub fn parse_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => {
+                let mut config = KafkaProducerConfig::default();
+
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "brokers" | "broker" | "bootstrap_servers" => {
+                        let brokers = g3_yaml::value::as_list(v, g3_yaml::value::as_string)
+                            .context(format!("invalid string list value for key {k}"))?;
+                        if brokers.is_empty() {
+                            return Err(anyhow!("no broker set for key {k}"));
+                        }
+                        config.set_brokers(brokers);
+                        Ok(())
+                    }
+                    "topic" => {
+                        let topic = g3_yaml::value::as_string(v)?;
+                        config.set_topic(topic);
+                        Ok(())
+                    }
+                    "partitioner" | "partition" => {
+                        let partitioner = as_kafka_partitioner(v)
+                            .context(format!("invalid kafka partitioner value for key {k}"))?;
+                        config.set_partitioner(partitioner);
+                        Ok(())
+                    }
+                    "compression" => {
+                        let s = g3_yaml::value::as_string(v)?;
+                        let compression = KafkaCompression::from_str(&s)
+                            .map_err(|_| anyhow!("invalid kafka compression type {s}"))?;
+                        config.set_compression(compression);
+                        Ok(())
+                    }
+                    "batch_size" => {
+                        let size = g3_yaml::value::as_usize(v)
+                            .context(format!("invalid usize value for key {k}"))?;
+                        config.set_batch_size(size);
+                        Ok(())
+                    }
+                    "linger" | "linger_time" => {
+                        let linger = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_linger(linger);
+                        Ok(())
+                    }
+                    "connect_timeout" => {
+                        let timeout = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_connect_timeout(timeout);
+                        Ok(())
+                    }
+                    "connect_delay" => {
+                        let delay = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_connect_delay(delay);
+                        Ok(())
+                    }
+                    "produce_timeout" => {
+                        let timeout = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.set_produce_timeout(timeout);
+                        Ok(())
+                    }
+                    "retry_queue_len" => {
+                        let len = g3_yaml::value::as_usize(v)
+                            .context(format!("invalid usize value for key {k}"))?;
+                        config.set_retry_queue_len(len);
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+
+                Ok(config)
+            }
+            Yaml::String(s) => {
+                let config = KafkaProducerConfig::new(vec![s.to_string()]);
+                Ok(config)
+            }
+            Yaml::Null => {
+                let config = KafkaProducerConfig::default();
+                Ok(config)
+            }
+            _ => Err(anyhow!(
+                "yaml value type for 'KafkaProducerConfig' should be 'map'"
+            )),
+        }
+    }
+}
+
+fn as_kafka_partitioner(v: &Yaml) -> anyhow::Result<KafkaPartitioner> {
+    match v {
+        Yaml::String(s) => match g3_yaml::key::normalize(s).as_str() {
+            "round_robin" | "roundrobin" | "rr" => Ok(KafkaPartitioner::RoundRobin),
+            "random" => Ok(KafkaPartitioner::Random),
+            _ => Err(anyhow!("unsupported kafka partitioner {s}")),
+        },
+        Yaml::Integer(_) => {
+            let p = g3_yaml::value::as_i32(v)?;
+            if p < 0 {
+                return Err(anyhow!("invalid kafka partition id {p}"));
+            }
+            Ok(KafkaPartitioner::Fixed(p))
+        }
+        Yaml::Hash(map) => {
+            let mut partitioner = None;
+            g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                "fixed" | "partition" => {
+                    let p = g3_yaml::value::as_i32(v)
+                        .context(format!("invalid i32 value for key {k}"))?;
+                    if p < 0 {
+                        return Err(anyhow!("invalid kafka partition id {p}"));
+                    }
+                    partitioner = Some(KafkaPartitioner::Fixed(p));
+                    Ok(())
+                }
+                "key_field" | "key" => {
+                    let field = g3_yaml::value::as_string(v)?;
+                    partitioner = Some(KafkaPartitioner::KeyField(field));
+                    Ok(())
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+            partitioner.ok_or_else(|| anyhow!("no kafka partitioner set"))
+        }
+        _ => Err(anyhow!(
+            "yaml value type for 'kafka partitioner' should be 'string', 'integer' or 'map'"
+        )),
+    }
+}
@@ -0,0 +1,266 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use flume::Receiver;
+use log::warn;
+use rskafka::client::partition::PartitionClient;
+use rskafka::record::Record;
+
+use g3_types::log::{AsyncLogConfig, AsyncLogger, LogStats};
+
+mod config;
+pub use config::{KafkaCompression, KafkaPartitioner, KafkaProducerConfig};
+
+mod format;
+pub use format::{KafkaFormatter, KafkaLogRecord};
+
+mod partition;
+
+pub fn new_async_logger(
+    async_conf: &AsyncLogConfig,
+    kafka_conf: &Arc<KafkaProducerConfig>,
+    default_topic_prefix: &str,
+    log_type: &str,
+) -> AsyncLogger<KafkaLogRecord, KafkaFormatter> {
+    let (sender, receiver) = flume::bounded::<KafkaLogRecord>(async_conf.channel_capacity);
+
+    let stats = Arc::new(LogStats::default());
+    let topic = kafka_conf.topic(default_topic_prefix, log_type);
+
+    for i in 0..async_conf.thread_number {
+        let io_thread = AsyncIoThread {
+            config: Arc::clone(kafka_conf),
+            topic: topic.clone(),
+            receiver: receiver.clone(),
+            stats: Arc::clone(&stats),
+            retry_queue: VecDeque::with_capacity(kafka_conf.retry_queue_len),
+            next_partition: i,
+        };
+
+        let _detached_thread = std::thread::Builder::new()
+            .name(format!("{}#{i}", async_conf.thread_name))
+            .spawn(move || {
+                let rt = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .unwrap();
+                rt.block_on(io_thread.run_to_end());
+            });
+    }
+
+    let key_field = kafka_conf.key_field().map(|s| s.to_string());
+    AsyncLogger::new(sender, KafkaFormatter::new(key_field), stats)
+}
+
+struct AsyncIoThread {
+    config: Arc<KafkaProducerConfig>,
+    topic: String,
+    receiver: Receiver<KafkaLogRecord>,
+    stats: Arc<LogStats>,
+    retry_queue: VecDeque<KafkaLogRecord>,
+    next_partition: usize,
+}
+
+impl AsyncIoThread {
+    async fn run_to_end(mut self) {
+        loop {
+            match tokio::time::timeout(
+                self.config.connect_timeout,
+                self.config.new_partition_clients(&self.topic),
+            )
+            .await
+            {
+                Ok(Ok(clients)) => match self.run_with_clients(&clients).await {
+                    Ok(_) => break,
+                    Err(e) => warn!("lost connection to kafka for topic {}: {e:?}", self.topic),
+                },
+                Ok(Err(e)) => {
+                    warn!("failed to connect to kafka for topic {}: {e:?}", self.topic);
+                    match self.run_without_connection().await {
+                        Ok(_) => break,
+                        Err(e) => warn!("{e:?}"),
+                    }
+                }
+                Err(_) => {
+                    warn!("timed out to connect to kafka for topic {}", self.topic);
+                    match self.run_without_connection().await {
+                        Ok(_) => break,
+                        Err(e) => warn!("{e:?}"),
+                    }
+                }
+            }
+        }
+    }
+
+    async fn run_without_connection(&mut self) -> anyhow::Result<()> {
+        let drop_count = Arc::new(AtomicUsize::new(0));
+        let drop_count_i = drop_count.clone();
+        match tokio::time::timeout(self.config.connect_delay, async {
+            while let Ok(record) = self.receiver.recv_async().await {
+                if self.push_to_retry(record).is_some() {
+                    drop_count_i.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        })
+        .await
+        {
+            Ok(_) => Ok(()),
+            Err(_) => Err(anyhow!(
+                "will retry connect again. {} logs dropped during this period",
+                drop_count.load(Ordering::Relaxed)
+            )),
+        }
+    }
+
+    async fn run_with_clients(&mut self, clients: &[PartitionClient]) -> anyhow::Result<()> {
+        let batch_size = self.config.batch_size;
+        let mut batch = Vec::with_capacity(batch_size);
+        let mut linger_interval = tokio::time::interval(self.config.linger);
+
+        while !self.retry_queue.is_empty() {
+            let end = self.retry_queue.len().min(batch_size);
+            batch.extend(self.retry_queue.drain(..end));
+            self.send_batch(clients, &mut batch).await?;
+        }
+
+        loop {
+            tokio::select! {
+                biased;
+
+                r = self.receiver.recv_async() => {
+                    match r {
+                        Ok(record) => {
+                            batch.push(record);
+                            if batch.len() >= batch_size {
+                                self.send_batch(clients, &mut batch).await?;
+                            }
+                        }
+                        Err(_) => {
+                            // the channel is closed, send all pending records and quit
+                            return self.send_batch(clients, &mut batch).await;
+                        }
+                    }
+                }
+                _ = linger_interval.tick() => {
+                    self.send_batch(clients, &mut batch).await?;
+                }
+            }
+        }
+    }
+
+    async fn send_batch(
+        &mut self,
+        clients: &[PartitionClient],
+        batch: &mut Vec<KafkaLogRecord>,
+    ) -> anyhow::Result<()> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let mut partitioned_records = BTreeMap::<usize, (Vec<Record>, usize)>::new();
+        for record in batch.drain(..) {
+            let index = self.select_partition(clients, &record);
+            let (records, size) = partitioned_records.entry(index).or_default();
+            *size += record.value.len();
+            records.push(Record {
+                key: record.key,
+                value: Some(record.value),
+                headers: BTreeMap::new(),
+                timestamp: record.timestamp,
+            });
+        }
+
+        let compression = self.config.compression.codec();
+        let mut iter = partitioned_records.into_iter();
+        while let Some((index, (records, size))) = iter.next() {
+            let count = records.len();
+            let client = &clients[index];
+            match tokio::time::timeout(
+                self.config.produce_timeout,
+                client.produce(records, compression),
+            )
+            .await
+            {
+                Ok(Ok(_)) => {
+                    for _ in 0..count {
+                        self.stats.io.add_passed();
+                    }
+                    self.stats.io.add_size(size);
+                }
+                Ok(Err(e)) => {
+                    self.drop_records(count);
+                    iter.for_each(|(_, (records, _))| self.drop_records(records.len()));
+                    return Err(anyhow!(
+                        "failed to produce to partition {}: {e}",
+                        client.partition()
+                    ));
+                }
+                Err(_) => {
+                    self.drop_records(count);
+                    iter.for_each(|(_, (records, _))| self.drop_records(records.len()));
+                    return Err(anyhow!(
+                        "timed out to produce to partition {}",
+                        client.partition()
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn select_partition(&mut self, clients: &[PartitionClient], record: &KafkaLogRecord) -> usize {
+        match &self.config.partitioner {
+            KafkaPartitioner::Fixed(p) => clients
+                .iter()
+                .position(|c| c.partition() == *p)
+                .unwrap_or_default(),
+            KafkaPartitioner::Random => fastrand::usize(0..clients.len()),
+            KafkaPartitioner::KeyField(_) => match &record.key {
+                Some(key) => partition::hash_key(key, clients.len()),
+                None => self.round_robin(clients.len()),
+            },
+            KafkaPartitioner::RoundRobin => self.round_robin(clients.len()),
+        }
+    }
+
+    fn round_robin(&mut self, total: usize) -> usize {
+        let index = self.next_partition % total;
+        self.next_partition = self.next_partition.wrapping_add(1);
+        index
+    }
+
+    fn drop_records(&self, count: usize) {
+        for _ in 0..count {
+            self.stats.drop.add_peer_unreachable();
+        }
+    }
+
+    fn push_to_retry(&mut self, record: KafkaLogRecord) -> Option<KafkaLogRecord> {
+        self.retry_queue.push_back(record);
+        if self.retry_queue.len() > self.config.retry_queue_len {
+            self.stats.drop.add_peer_unreachable();
+            self.retry_queue.pop_front()
+        } else {
+            None
+        }
+    }
+}
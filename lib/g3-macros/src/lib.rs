@@ -0,0 +1,54 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod stats;
+
+/// Generate the snapshot struct, the snapshot method and the statsd emit code
+/// for stats structs with atomic integer fields.
+///
+/// ```ignore
+/// #[derive(Default, StatsSnapshot)]
+/// #[stats_snapshot(RequestSnapshot)]
+/// pub(crate) struct RequestStats {
+///     #[stats(count = "request.total")]
+///     total: AtomicU64,
+///     #[stats(gauge = "request.alive")]
+///     alive_count: AtomicI32,
+///     #[stats(count = "request.failed", tag(reason = "timeout"), skip_zero)]
+///     timeout: AtomicU64,
+/// }
+/// ```
+///
+/// The following code will be generated:
+///
+/// - a `RequestSnapshot` struct with the plain integer value of each field
+/// - `RequestStats::snapshot()` to load all values
+/// - `RequestSnapshot::emit_diff(client, tags, last)` to emit the diff of the
+///   count fields and the value of the gauge fields, `last` will be updated
+///
+/// Fields without `count` or `gauge` will be in the snapshot but not emitted.
+/// With `skip_zero`, the metric won't be emitted if both the new value and the
+/// last value are zero.
+#[proc_macro_derive(StatsSnapshot, attributes(stats_snapshot, stats))]
+pub fn derive_stats_snapshot(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    stats::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
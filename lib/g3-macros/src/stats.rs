@@ -0,0 +1,207 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, LitStr, Type};
+
+enum MetricKind {
+    None,
+    Count(LitStr),
+    Gauge(LitStr),
+}
+
+struct StatsField {
+    ident: Ident,
+    value_type: Ident,
+    kind: MetricKind,
+    tags: Vec<(LitStr, LitStr)>,
+    skip_zero: bool,
+}
+
+fn atomic_value_type(ty: &Type) -> syn::Result<Ident> {
+    let Type::Path(p) = ty else {
+        return Err(syn::Error::new_spanned(ty, "atomic integer type expected"));
+    };
+    let Some(seg) = p.path.segments.last() else {
+        return Err(syn::Error::new_spanned(ty, "atomic integer type expected"));
+    };
+    let value_type = match seg.ident.to_string().as_str() {
+        "AtomicU64" => "u64",
+        "AtomicI64" => "i64",
+        "AtomicU32" => "u32",
+        "AtomicI32" => "i32",
+        "AtomicUsize" => "usize",
+        "AtomicIsize" => "isize",
+        _ => {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "unsupported type, only atomic integer types are allowed",
+            ))
+        }
+    };
+    Ok(Ident::new(value_type, seg.ident.span()))
+}
+
+fn parse_field(field: &syn::Field) -> syn::Result<StatsField> {
+    let ident = field.ident.clone().unwrap();
+    let value_type = atomic_value_type(&field.ty)?;
+    let mut stats_field = StatsField {
+        ident,
+        value_type,
+        kind: MetricKind::None,
+        tags: Vec::new(),
+        skip_zero: false,
+    };
+
+    for attr in &field.attrs {
+        if !attr.path().is_ident("stats") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("count") {
+                stats_field.kind = MetricKind::Count(meta.value()?.parse()?);
+            } else if meta.path.is_ident("gauge") {
+                stats_field.kind = MetricKind::Gauge(meta.value()?.parse()?);
+            } else if meta.path.is_ident("skip_zero") {
+                stats_field.skip_zero = true;
+            } else if meta.path.is_ident("tag") {
+                meta.parse_nested_meta(|tag| {
+                    let Some(key) = tag.path.get_ident() else {
+                        return Err(tag.error("invalid tag key"));
+                    };
+                    let key = LitStr::new(&key.to_string(), key.span());
+                    stats_field.tags.push((key, tag.value()?.parse()?));
+                    Ok(())
+                })?;
+            } else {
+                return Err(meta.error("unsupported stats attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(stats_field)
+}
+
+pub(super) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let stats_name = &input.ident;
+    let vis = &input.vis;
+
+    let mut snapshot_name: Option<Ident> = None;
+    for attr in &input.attrs {
+        if attr.path().is_ident("stats_snapshot") {
+            snapshot_name = Some(attr.parse_args()?);
+        }
+    }
+    let Some(snapshot_name) = snapshot_name else {
+        return Err(syn::Error::new_spanned(
+            stats_name,
+            "#[stats_snapshot(Name)] is required to set the snapshot struct name",
+        ));
+    };
+
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            stats_name,
+            "StatsSnapshot can only be derived for structs",
+        ));
+    };
+    let Fields::Named(named) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            stats_name,
+            "StatsSnapshot can only be derived for structs with named fields",
+        ));
+    };
+    let fields = named
+        .named
+        .iter()
+        .map(parse_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let snapshot_fields = fields.iter().map(|f| {
+        let ident = &f.ident;
+        let value_type = &f.value_type;
+        quote! { #vis #ident: #value_type, }
+    });
+    let load_fields = fields.iter().map(|f| {
+        let ident = &f.ident;
+        quote! { #ident: self.#ident.load(::std::sync::atomic::Ordering::Relaxed), }
+    });
+    let emit_fields = fields.iter().map(|f| {
+        let ident = &f.ident;
+        let tags = f.tags.iter().map(|(k, v)| quote! { .with_tag(#k, #v) });
+        match &f.kind {
+            MetricKind::None => quote! {},
+            MetricKind::Count(name) => {
+                let skip = if f.skip_zero {
+                    quote! { if self.#ident != 0 || last.#ident != 0 }
+                } else {
+                    quote! {}
+                };
+                quote! {
+                    #skip {
+                        let diff_value = self.#ident.wrapping_sub(last.#ident);
+                        client.count_with_tags(#name, diff_value, tags)
+                            #(#tags)*
+                            .send();
+                        last.#ident = self.#ident;
+                    }
+                }
+            }
+            MetricKind::Gauge(name) => {
+                let skip = if f.skip_zero {
+                    quote! { if self.#ident != 0 || last.#ident != 0 }
+                } else {
+                    quote! {}
+                };
+                quote! {
+                    #skip {
+                        client.gauge_with_tags(#name, self.#ident, tags)
+                            #(#tags)*
+                            .send();
+                        last.#ident = self.#ident;
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #[derive(Clone, Copy, Debug, Default)]
+        #vis struct #snapshot_name {
+            #(#snapshot_fields)*
+        }
+
+        impl #stats_name {
+            #vis fn snapshot(&self) -> #snapshot_name {
+                #snapshot_name {
+                    #(#load_fields)*
+                }
+            }
+        }
+
+        impl #snapshot_name {
+            #vis fn emit_diff(
+                &self,
+                client: &mut ::g3_statsd_client::StatsdClient,
+                tags: &::g3_statsd_client::StatsdTagGroup,
+                last: &mut Self,
+            ) {
+                #(#emit_fields)*
+            }
+        }
+    })
+}
@@ -25,6 +25,8 @@ use hickory_client::client::{Client, ClientHandle};
 use hickory_proto::rr::{DNSClass, Name, RData, RecordType};
 use hickory_proto::runtime::iocompat::AsyncIoTokioAsStd;
 use hickory_proto::runtime::TokioRuntimeProvider;
+#[cfg(feature = "quic")]
+use quinn::TransportConfig;
 use rustls::ClientConfig;
 use rustls_pki_types::ServerName;
 use tokio::net::TcpStream;
@@ -254,17 +256,33 @@ impl HickoryClientConfig {
                         .await
                 }
                 DnsEncryptionProtocol::Https => {
-                    self.new_dns_over_h2_client(tls_client, ec.tls_name().clone())
-                        .await
+                    self.new_dns_over_h2_client(
+                        tls_client,
+                        ec.tls_name().clone(),
+                        ec.query_path().to_string(),
+                    )
+                    .await
                 }
                 #[cfg(feature = "quic")]
                 DnsEncryptionProtocol::Quic => {
-                    self.new_dns_over_quic_client(tls_client, ec.tls_name())
-                        .await
+                    let transport = ec.quic_transport().map(|c| c.build_for_client());
+                    self.new_dns_over_quic_client(
+                        tls_client,
+                        ec.tls_name(),
+                        transport,
+                        ec.enable_0rtt(),
+                    )
+                    .await
                 }
                 #[cfg(feature = "quic")]
                 DnsEncryptionProtocol::H3 => {
-                    self.new_dns_over_h3_client(tls_client, ec.tls_name()).await
+                    self.new_dns_over_h3_client(
+                        tls_client,
+                        ec.tls_name(),
+                        ec.query_path().to_string(),
+                        ec.enable_0rtt(),
+                    )
+                    .await
                 }
             }
         } else {
@@ -337,12 +355,14 @@ impl HickoryClientConfig {
         &self,
         tls_client: ClientConfig,
         tls_name: ServerName<'static>,
+        query_path: String,
     ) -> anyhow::Result<Client> {
         let client_connect = g3_hickory_client::io::h2::connect(
             self.target,
             self.bind,
             tls_client,
             tls_name,
+            query_path,
             self.connect_timeout,
             self.request_timeout,
         );
@@ -359,6 +379,8 @@ impl HickoryClientConfig {
         &self,
         tls_client: ClientConfig,
         tls_name: &ServerName<'static>,
+        transport: Option<TransportConfig>,
+        enable_0rtt: bool,
     ) -> anyhow::Result<Client> {
         let tls_name = match tls_name {
             ServerName::DnsName(domain) => domain.as_ref().to_string(),
@@ -371,6 +393,8 @@ impl HickoryClientConfig {
             self.bind,
             tls_client,
             tls_name,
+            transport,
+            enable_0rtt,
             self.connect_timeout,
             self.request_timeout,
         );
@@ -387,6 +411,8 @@ impl HickoryClientConfig {
         &self,
         tls_client: ClientConfig,
         tls_name: &ServerName<'static>,
+        query_path: String,
+        enable_0rtt: bool,
     ) -> anyhow::Result<Client> {
         let tls_name = match tls_name {
             ServerName::DnsName(domain) => domain.as_ref().to_string(),
@@ -399,6 +425,8 @@ impl HickoryClientConfig {
             self.bind,
             tls_client,
             tls_name,
+            query_path,
+            enable_0rtt,
             self.connect_timeout,
             self.request_timeout,
         );
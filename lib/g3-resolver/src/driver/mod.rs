@@ -29,6 +29,9 @@ pub mod c_ares;
 #[cfg(feature = "hickory")]
 pub mod hickory;
 
+#[cfg(feature = "hickory")]
+pub mod zone;
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum AnyResolveDriverConfig {
     FailOver(fail_over::FailOverDriverConfig),
@@ -36,6 +39,8 @@ pub enum AnyResolveDriverConfig {
     CAres(c_ares::CAresDriverConfig),
     #[cfg(feature = "hickory")]
     Hickory(Box<hickory::HickoryDriverConfig>),
+    #[cfg(feature = "hickory")]
+    Zone(Box<zone::ZoneDriverConfig>),
 }
 
 impl AnyResolveDriverConfig {
@@ -46,6 +51,8 @@ impl AnyResolveDriverConfig {
             AnyResolveDriverConfig::CAres(c) => c.spawn_resolver_driver(),
             #[cfg(feature = "hickory")]
             AnyResolveDriverConfig::Hickory(c) => c.spawn_resolver_driver(),
+            #[cfg(feature = "hickory")]
+            AnyResolveDriverConfig::Zone(c) => c.spawn_resolver_driver(),
         }
     }
 }
@@ -0,0 +1,146 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+use hickory_proto::rr::Name;
+
+use super::{ZoneData, ZoneResolver};
+use crate::{BoxResolverDriver, ResolverHandle};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ZoneTransferConfig {
+    pub(crate) master: SocketAddr,
+    pub(crate) bind: Option<SocketAddr>,
+    pub(crate) refresh_interval: Duration,
+    pub(crate) retry_interval: Duration,
+    pub(crate) timeout: Duration,
+}
+
+impl ZoneTransferConfig {
+    pub fn new(master: SocketAddr) -> Self {
+        ZoneTransferConfig {
+            master,
+            bind: None,
+            refresh_interval: Duration::from_secs(3600),
+            retry_interval: Duration::from_secs(60),
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    pub fn set_bind(&mut self, bind: SocketAddr) {
+        self.bind = Some(bind);
+    }
+
+    pub fn set_refresh_interval(&mut self, interval: Duration) {
+        self.refresh_interval = interval;
+    }
+
+    pub fn set_retry_interval(&mut self, interval: Duration) {
+        self.retry_interval = interval;
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZoneDriverConfig {
+    origin: Option<Name>,
+    zone_file: Option<PathBuf>,
+    transfer: Option<ZoneTransferConfig>,
+    fallback_handle: Option<ResolverHandle>,
+    negative_ttl: u32,
+}
+
+impl Default for ZoneDriverConfig {
+    fn default() -> Self {
+        ZoneDriverConfig {
+            origin: None,
+            zone_file: None,
+            transfer: None,
+            fallback_handle: None,
+            negative_ttl: crate::config::RESOLVER_MINIMUM_CACHE_TTL,
+        }
+    }
+}
+
+impl ZoneDriverConfig {
+    pub fn set_origin(&mut self, origin: &str) -> anyhow::Result<()> {
+        let mut name =
+            Name::from_str(origin).map_err(|e| anyhow!("invalid zone origin {origin}: {e}"))?;
+        name.set_fqdn(true);
+        self.origin = Some(name);
+        Ok(())
+    }
+
+    pub fn set_zone_file(&mut self, path: PathBuf) {
+        self.zone_file = Some(path);
+    }
+
+    pub fn set_transfer(&mut self, config: ZoneTransferConfig) {
+        self.transfer = Some(config);
+    }
+
+    pub fn set_fallback_handle(&mut self, handle: Option<ResolverHandle>) {
+        self.fallback_handle = handle;
+    }
+
+    pub fn set_negative_ttl(&mut self, ttl: u32) {
+        self.negative_ttl = ttl;
+    }
+
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.zone_file.is_none() && self.transfer.is_none() {
+            return Err(anyhow!("neither zone file nor zone transfer is set"));
+        }
+        if self.transfer.is_some() && self.origin.is_none() {
+            return Err(anyhow!("zone origin is required for zone transfer"));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn spawn_resolver_driver(&self) -> anyhow::Result<BoxResolverDriver> {
+        let data = match &self.zone_file {
+            Some(path) => ZoneData::load_file(path, self.origin.clone())
+                .context(format!("failed to load zone file {}", path.display()))?,
+            None => ZoneData::default(),
+        };
+        let data = Arc::new(RwLock::new(Arc::new(data)));
+
+        let transfer_handle = match (&self.transfer, &self.origin) {
+            (Some(transfer), Some(origin)) => Some(super::transfer::spawn(
+                transfer.clone(),
+                origin.clone(),
+                data.clone(),
+            )),
+            _ => None,
+        };
+
+        Ok(Box::new(ZoneResolver {
+            data,
+            fallback: self.fallback_handle.clone(),
+            negative_ttl: self.negative_ttl,
+            transfer_handle,
+        }))
+    }
+}
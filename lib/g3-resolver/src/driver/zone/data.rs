@@ -0,0 +1,118 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::IpAddr;
+use std::path::Path;
+
+use ahash::AHashMap;
+use anyhow::anyhow;
+use hickory_proto::rr::{Name, RData, Record};
+use hickory_proto::serialize::txt::Parser;
+
+const MAX_CNAME_DEPTH: usize = 8;
+
+#[derive(Default)]
+pub(super) struct ZoneData {
+    v4: AHashMap<String, (u32, Vec<IpAddr>)>,
+    v6: AHashMap<String, (u32, Vec<IpAddr>)>,
+    cname: AHashMap<String, (u32, String)>,
+}
+
+fn normalize_name(name: &Name) -> String {
+    let s = name.to_lowercase().to_ascii();
+    match s.strip_suffix('.') {
+        Some(s) => s.to_string(),
+        None => s,
+    }
+}
+
+fn normalize_domain(domain: &str) -> String {
+    domain.trim_end_matches('.').to_ascii_lowercase()
+}
+
+impl ZoneData {
+    pub(super) fn load_file(path: &Path, origin: Option<Name>) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let (_, record_sets) = Parser::new(content, Some(path.to_path_buf()), origin)
+            .parse()
+            .map_err(|e| anyhow!("parse failed: {e}"))?;
+
+        let mut data = ZoneData::default();
+        for set in record_sets.values() {
+            for r in set.records_without_rrsigs() {
+                data.add_record(r);
+            }
+        }
+        Ok(data)
+    }
+
+    pub(super) fn add_record(&mut self, record: &Record) {
+        let ttl = record.ttl();
+        match record.data() {
+            RData::A(v) => {
+                let entry = self
+                    .v4
+                    .entry(normalize_name(record.name()))
+                    .or_insert_with(|| (ttl, Vec::new()));
+                entry.0 = entry.0.min(ttl);
+                entry.1.push(IpAddr::V4(v.0));
+            }
+            RData::AAAA(v) => {
+                let entry = self
+                    .v6
+                    .entry(normalize_name(record.name()))
+                    .or_insert_with(|| (ttl, Vec::new()));
+                entry.0 = entry.0.min(ttl);
+                entry.1.push(IpAddr::V6(v.0));
+            }
+            RData::CNAME(v) => {
+                self.cname
+                    .insert(normalize_name(record.name()), (ttl, normalize_name(&v.0)));
+            }
+            _ => {}
+        }
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.v4.is_empty() && self.v6.is_empty() && self.cname.is_empty()
+    }
+
+    pub(super) fn query_v4(&self, domain: &str) -> Option<(u32, Vec<IpAddr>)> {
+        self.query(&self.v4, domain)
+    }
+
+    pub(super) fn query_v6(&self, domain: &str) -> Option<(u32, Vec<IpAddr>)> {
+        self.query(&self.v6, domain)
+    }
+
+    fn query(
+        &self,
+        map: &AHashMap<String, (u32, Vec<IpAddr>)>,
+        domain: &str,
+    ) -> Option<(u32, Vec<IpAddr>)> {
+        let mut name = normalize_domain(domain);
+        let mut ttl = u32::MAX;
+        for _ in 0..MAX_CNAME_DEPTH {
+            if let Some((t, ips)) = map.get(&name) {
+                return Some((ttl.min(*t), ips.clone()));
+            }
+            let (t, target) = self.cname.get(&name)?;
+            ttl = ttl.min(*t);
+            name.clone_from(target);
+        }
+        None
+    }
+}
@@ -0,0 +1,138 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::{Arc, RwLock};
+
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use super::ZoneData;
+use crate::config::ResolverRuntimeConfig;
+use crate::message::ResolveDriverResponse;
+use crate::{
+    ResolveDriver, ResolveJob, ResolveLocalError, ResolveServerError, ResolvedRecord,
+    ResolverHandle,
+};
+
+pub(super) struct ZoneResolver {
+    pub(super) data: Arc<RwLock<Arc<ZoneData>>>,
+    pub(super) fallback: Option<ResolverHandle>,
+    pub(super) negative_ttl: u32,
+    pub(super) transfer_handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for ZoneResolver {
+    fn drop(&mut self) {
+        if let Some(handle) = self.transfer_handle.take() {
+            handle.abort();
+        }
+    }
+}
+
+impl ZoneResolver {
+    fn load_data(&self) -> Arc<ZoneData> {
+        self.data.read().unwrap().clone()
+    }
+}
+
+struct FallbackJob {
+    domain: Arc<str>,
+    job: Option<Result<ResolveJob, ResolveLocalError>>,
+    negative_ttl: u32,
+}
+
+impl FallbackJob {
+    async fn resolve(self) -> ResolvedRecord {
+        match self.job {
+            Some(Ok(mut job)) => match job.recv().await {
+                Ok((r, _)) => r.as_ref().clone(),
+                Err(e) => ResolvedRecord::failed(self.domain, self.negative_ttl, e.into()),
+            },
+            Some(Err(e)) => ResolvedRecord::failed(self.domain, self.negative_ttl, e.into()),
+            None => ResolvedRecord::failed(
+                self.domain,
+                self.negative_ttl,
+                ResolveServerError::NotFound.into(),
+            ),
+        }
+    }
+
+    async fn resolve_protective(self, timeout: std::time::Duration) -> ResolvedRecord {
+        let domain = self.domain.clone();
+        let negative_ttl = self.negative_ttl;
+        tokio::time::timeout(timeout, self.resolve())
+            .await
+            .unwrap_or_else(|_| ResolvedRecord::timed_out(domain, negative_ttl))
+    }
+}
+
+impl ResolveDriver for ZoneResolver {
+    fn query_v4(
+        &self,
+        domain: Arc<str>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        if let Some((ttl, ips)) = self.load_data().query_v4(&domain) {
+            let record = ResolvedRecord::resolved(domain, ttl, ips);
+            let _ = sender.send(ResolveDriverResponse::V4(record));
+            return;
+        }
+
+        let job = self
+            .fallback
+            .as_ref()
+            .map(|handle| handle.get_v4(domain.clone()));
+        let job = FallbackJob {
+            domain,
+            job,
+            negative_ttl: self.negative_ttl,
+        };
+        let timeout = config.protective_query_timeout;
+        tokio::spawn(async move {
+            let record = job.resolve_protective(timeout).await;
+            let _ = sender.send(ResolveDriverResponse::V4(record));
+        });
+    }
+
+    fn query_v6(
+        &self,
+        domain: Arc<str>,
+        config: &ResolverRuntimeConfig,
+        sender: mpsc::UnboundedSender<ResolveDriverResponse>,
+    ) {
+        if let Some((ttl, ips)) = self.load_data().query_v6(&domain) {
+            let record = ResolvedRecord::resolved(domain, ttl, ips);
+            let _ = sender.send(ResolveDriverResponse::V6(record));
+            return;
+        }
+
+        let job = self
+            .fallback
+            .as_ref()
+            .map(|handle| handle.get_v6(domain.clone()));
+        let job = FallbackJob {
+            domain,
+            job,
+            negative_ttl: self.negative_ttl,
+        };
+        let timeout = config.protective_query_timeout;
+        tokio::spawn(async move {
+            let record = job.resolve_protective(timeout).await;
+            let _ = sender.send(ResolveDriverResponse::V6(record));
+        });
+    }
+}
@@ -0,0 +1,103 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::{Arc, RwLock};
+
+use anyhow::anyhow;
+use futures_util::StreamExt;
+use hickory_client::client::{Client, ClientHandle};
+use hickory_proto::rr::{Name, RData};
+use hickory_proto::runtime::iocompat::AsyncIoTokioAsStd;
+use hickory_proto::runtime::TokioRuntimeProvider;
+use log::{debug, warn};
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+
+use super::{ZoneData, ZoneTransferConfig};
+
+pub(super) fn spawn(
+    config: ZoneTransferConfig,
+    origin: Name,
+    data: Arc<RwLock<Arc<ZoneData>>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match tokio::time::timeout(config.timeout, transfer(&config, &origin)).await {
+                Ok(Ok(new_data)) => {
+                    debug!("zone {origin} transferred from {}", config.master);
+                    {
+                        let mut guard = data.write().unwrap();
+                        *guard = Arc::new(new_data);
+                    }
+                    tokio::time::sleep(config.refresh_interval).await;
+                }
+                Ok(Err(e)) => {
+                    warn!(
+                        "zone {origin} transfer from {} failed: {e:?}",
+                        config.master
+                    );
+                    tokio::time::sleep(config.retry_interval).await;
+                }
+                Err(_) => {
+                    warn!("zone {origin} transfer from {} timed out", config.master);
+                    tokio::time::sleep(config.retry_interval).await;
+                }
+            }
+        }
+    })
+}
+
+async fn transfer(config: &ZoneTransferConfig, origin: &Name) -> anyhow::Result<ZoneData> {
+    let (stream, sender) = hickory_proto::tcp::TcpClientStream::<AsyncIoTokioAsStd<TcpStream>>::new(
+        config.master,
+        config.bind,
+        Some(config.timeout),
+        TokioRuntimeProvider::new(),
+    );
+    let (mut client, bg) = Client::with_timeout(stream, sender, config.timeout, None)
+        .await
+        .map_err(|e| anyhow!("failed to create tcp client: {e}"))?;
+    let bg_task = tokio::spawn(bg);
+
+    let mut data = ZoneData::default();
+    let mut soa_count = 0;
+    let mut xfr_stream = client.zone_transfer(origin.clone(), None);
+    while let Some(r) = xfr_stream.next().await {
+        let rsp = match r {
+            Ok(rsp) => rsp,
+            Err(e) => {
+                bg_task.abort();
+                return Err(anyhow!("zone transfer failed: {e}"));
+            }
+        };
+        for record in rsp.answers() {
+            if matches!(record.data(), RData::SOA(_)) {
+                soa_count += 1;
+            } else {
+                data.add_record(record);
+            }
+        }
+    }
+    bg_task.abort();
+
+    if soa_count < 2 {
+        return Err(anyhow!("incomplete zone transfer response"));
+    }
+    if data.is_empty() {
+        return Err(anyhow!("no usable record found in the zone"));
+    }
+    Ok(data)
+}
@@ -36,6 +36,20 @@ struct CachedRecord {
     expire_key: Option<delay_queue::Key>,
 }
 
+struct DoingQuery {
+    start: Instant,
+    senders: Vec<oneshot::Sender<(ArcResolvedRecord, ResolvedRecordSource)>>,
+}
+
+impl DoingQuery {
+    fn new(sender: oneshot::Sender<(ArcResolvedRecord, ResolvedRecordSource)>) -> Self {
+        DoingQuery {
+            start: Instant::now(),
+            senders: vec![sender],
+        }
+    }
+}
+
 pub(crate) struct ResolverRuntime {
     config: ResolverConfig,
     stats: Arc<ResolverStats>,
@@ -47,8 +61,8 @@ pub(crate) struct ResolverRuntime {
     expired_v6: DelayQueue<Arc<str>>,
     cache_v4: AHashMap<Arc<str>, CachedRecord>,
     cache_v6: AHashMap<Arc<str>, CachedRecord>,
-    doing_v4: AHashMap<Arc<str>, Vec<oneshot::Sender<(ArcResolvedRecord, ResolvedRecordSource)>>>,
-    doing_v6: AHashMap<Arc<str>, Vec<oneshot::Sender<(ArcResolvedRecord, ResolvedRecordSource)>>>,
+    doing_v4: AHashMap<Arc<str>, DoingQuery>,
+    doing_v6: AHashMap<Arc<str>, DoingQuery>,
     driver: Option<BoxResolverDriver>,
 }
 
@@ -136,7 +150,11 @@ impl ResolverRuntime {
             ResolveDriverResponse::V4(record) => {
                 self.stats.query_a.add_record(&record);
                 let record = Arc::new(record);
-                if let Some(mut vec) = self.doing_v4.remove(&record.domain) {
+                if let Some(doing) = self.doing_v4.remove(&record.domain) {
+                    self.stats
+                        .query_a
+                        .add_driver_latency(record.created.saturating_duration_since(doing.start));
+                    let mut vec = doing.senders;
                     if let Some(sender) = vec.pop() {
                         let _ = sender.send((Arc::clone(&record), ResolvedRecordSource::Query));
                         self.stats.query_a.add_query_cached_n(vec.len());
@@ -152,7 +170,11 @@ impl ResolverRuntime {
             ResolveDriverResponse::V6(record) => {
                 self.stats.query_aaaa.add_record(&record);
                 let record = Arc::new(record);
-                if let Some(mut vec) = self.doing_v6.remove(&record.domain) {
+                if let Some(doing) = self.doing_v6.remove(&record.domain) {
+                    self.stats
+                        .query_aaaa
+                        .add_driver_latency(record.created.saturating_duration_since(doing.start));
+                    let mut vec = doing.senders;
                     if let Some(sender) = vec.pop() {
                         let _ = sender.send((Arc::clone(&record), ResolvedRecordSource::Query));
                         self.stats.query_aaaa.add_query_cached_n(vec.len());
@@ -189,10 +211,10 @@ impl ResolverRuntime {
                     None => match self.doing_v4.entry(domain.to_owned()) {
                         hash_map::Entry::Occupied(mut o) => {
                             // there is a query already
-                            o.get_mut().push(sender);
+                            o.get_mut().senders.push(sender);
                         }
                         hash_map::Entry::Vacant(v) => {
-                            v.insert(vec![sender]);
+                            v.insert(DoingQuery::new(sender));
                             if let Some(driver) = &self.driver {
                                 self.stats.query_a.add_query_driver();
                                 driver.query_v4(
@@ -217,10 +239,10 @@ impl ResolverRuntime {
                     None => match self.doing_v6.entry(domain.to_owned()) {
                         hash_map::Entry::Occupied(mut o) => {
                             // there is a query already
-                            o.get_mut().push(sender);
+                            o.get_mut().senders.push(sender);
                         }
                         hash_map::Entry::Vacant(v) => {
-                            v.insert(vec![sender]);
+                            v.insert(DoingQuery::new(sender));
                             if let Some(driver) = &self.driver {
                                 self.stats.query_aaaa.add_query_driver();
                                 driver.query_v6(
@@ -15,6 +15,7 @@
  */
 
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
 
 use super::{
     ResolveDriverError, ResolveError, ResolveLocalError, ResolveServerError, ResolvedRecord,
@@ -25,6 +26,8 @@ pub struct ResolverQueryStats {
     query_total: AtomicU64,
     query_cached: AtomicU64,
     query_driver: AtomicU64,
+    driver_responded: AtomicU64,
+    driver_latency_us: AtomicU64,
     driver_timeout: AtomicU64,
     driver_refused: AtomicU64,
     driver_malformed: AtomicU64,
@@ -39,6 +42,9 @@ pub struct ResolverQuerySnapshot {
     pub total: u64,
     pub cached: u64,
     pub driver: u64,
+    pub driver_responded: u64,
+    /// the sum of the latency of all driver responses, in microseconds
+    pub driver_latency_us: u64,
     pub driver_timeout: u64,
     pub driver_refused: u64,
     pub driver_malformed: u64,
@@ -54,6 +60,8 @@ impl ResolverQueryStats {
             total: self.query_total.load(Ordering::Relaxed),
             cached: self.query_cached.load(Ordering::Relaxed),
             driver: self.query_driver.load(Ordering::Relaxed),
+            driver_responded: self.driver_responded.load(Ordering::Relaxed),
+            driver_latency_us: self.driver_latency_us.load(Ordering::Relaxed),
             driver_timeout: self.driver_timeout.load(Ordering::Relaxed),
             driver_refused: self.driver_refused.load(Ordering::Relaxed),
             driver_malformed: self.driver_malformed.load(Ordering::Relaxed),
@@ -82,6 +90,12 @@ impl ResolverQueryStats {
         self.query_driver.fetch_add(1, Ordering::Relaxed);
     }
 
+    pub(crate) fn add_driver_latency(&self, latency: Duration) {
+        self.driver_responded.fetch_add(1, Ordering::Relaxed);
+        self.driver_latency_us
+            .fetch_add(latency.as_micros() as u64, Ordering::Relaxed);
+    }
+
     #[inline]
     fn add_driver_timeout(&self) {
         self.driver_timeout.fetch_add(1, Ordering::Relaxed);
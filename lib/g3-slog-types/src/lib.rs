@@ -20,6 +20,9 @@ pub use datetime::LtDateTime;
 mod duration;
 pub use duration::LtDuration;
 
+mod metrics;
+pub use metrics::LtMetricsTags;
+
 mod net;
 pub use net::{LtHost, LtIpAddr, LtUpstreamAddr};
 
@@ -0,0 +1,47 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt;
+
+use slog::{Record, Serializer, Value};
+
+use g3_types::metrics::StaticMetricsTags;
+
+pub struct LtMetricsTags<'a>(pub &'a StaticMetricsTags);
+
+impl fmt::Display for LtMetricsTags<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut iter = self.0.iter();
+        if let Some((k, v)) = iter.next() {
+            write!(f, "{k}={v}")?;
+            for (k, v) in iter {
+                write!(f, ",{k}={v}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Value for LtMetricsTags<'_> {
+    fn serialize(
+        &self,
+        _record: &Record,
+        key: slog::Key,
+        serializer: &mut dyn Serializer,
+    ) -> slog::Result {
+        serializer.emit_arguments(key, &format_args!("{self}"))
+    }
+}
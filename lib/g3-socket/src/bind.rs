@@ -35,6 +35,9 @@ pub enum BindAddr {
     Ip(IpAddr),
     #[cfg(any(target_os = "linux", target_os = "android"))]
     Interface(InterfaceName),
+    /// bind to an ip which may be not assigned to any local interface, by using IP_FREEBIND
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    FreeIp(IpAddr),
 }
 
 impl BindAddr {
@@ -43,10 +46,11 @@ impl BindAddr {
     }
 
     pub fn ip(&self) -> Option<IpAddr> {
-        if let BindAddr::Ip(ip) = self {
-            Some(*ip)
-        } else {
-            None
+        match self {
+            BindAddr::Ip(ip) => Some(*ip),
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            BindAddr::FreeIp(ip) => Some(*ip),
+            _ => None,
         }
     }
 
@@ -76,6 +80,19 @@ impl BindAddr {
                 set_bind_address_no_port(socket, true)?;
                 socket.bind_device(Some(name.as_bytes()))
             }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            BindAddr::FreeIp(ip) => {
+                if AddressFamily::from(ip) != peer_family {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "bind_ip should be of the same family with peer ip",
+                    ));
+                }
+                set_freebind(socket, ip)?;
+                set_bind_address_no_port(socket, true)?;
+                let addr: SockAddr = SocketAddr::new(*ip, 0).into();
+                socket.bind(&addr)
+            }
         }
     }
 
@@ -94,8 +111,21 @@ impl BindAddr {
                     AddressFamily::Ipv6 => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
                 }
             }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            BindAddr::FreeIp(ip) => {
+                set_freebind(socket, ip)?;
+                *ip
+            }
         };
         let bind_addr = SockAddr::from(SocketAddr::new(bind_ip, 0));
         socket.bind(&bind_addr)
     }
 }
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn set_freebind(socket: &Socket, ip: &IpAddr) -> io::Result<()> {
+    match ip {
+        IpAddr::V4(_) => socket.set_freebind(true),
+        IpAddr::V6(_) => socket.set_freebind_ipv6(true),
+    }
+}
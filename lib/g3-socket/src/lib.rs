@@ -25,5 +25,14 @@ pub mod tcp;
 pub mod udp;
 pub mod util;
 
+#[cfg(unix)]
+pub mod unix;
+
+#[cfg(windows)]
+pub mod named_pipe;
+
+#[cfg(target_os = "linux")]
+pub mod local_peer;
+
 mod bind;
 pub use bind::BindAddr;
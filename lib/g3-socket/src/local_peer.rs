@@ -0,0 +1,347 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+
+use tokio::net::unix::UCred;
+
+use crate::util::native_socket_addr;
+
+const TCP_STATE_ESTABLISHED: &str = "01";
+
+/// Credential of the process that owns the peer end of a local connection
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LocalPeerCred {
+    uid: u32,
+    pid: Option<i32>,
+    inode: Option<u64>,
+}
+
+impl LocalPeerCred {
+    pub fn new(uid: u32, pid: Option<i32>) -> Self {
+        LocalPeerCred {
+            uid,
+            pid,
+            inode: None,
+        }
+    }
+
+    #[inline]
+    pub fn uid(&self) -> u32 {
+        self.uid
+    }
+
+    #[inline]
+    pub fn pid(&self) -> Option<i32> {
+        self.pid
+    }
+
+    /// find the pid of the peer process if it is not known yet.
+    ///
+    /// this will scan all fds of all processes, so it may be slow on a busy host
+    pub fn find_pid(&mut self) -> io::Result<Option<i32>> {
+        if self.pid.is_none() {
+            if let Some(inode) = self.inode {
+                self.pid = find_socket_pid(inode)?;
+            }
+        }
+        Ok(self.pid)
+    }
+
+    /// get the cgroup (v2 unified path, or the first v1 path) of the peer process
+    pub fn cgroup(&mut self) -> io::Result<Option<String>> {
+        match self.find_pid()? {
+            Some(pid) => read_cgroup(pid).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+impl From<UCred> for LocalPeerCred {
+    fn from(cred: UCred) -> Self {
+        LocalPeerCred::new(cred.uid(), cred.pid())
+    }
+}
+
+/// Find the owner of the peer socket for a TCP connection over loopback
+///
+/// `client` and `server` should be the peer and local address of the accepted socket.
+/// Ok(None) will be returned if the client end socket is not found on this host.
+pub fn find_tcp_peer(client: SocketAddr, server: SocketAddr) -> io::Result<Option<LocalPeerCred>> {
+    let client = native_socket_addr(client);
+    let server = native_socket_addr(server);
+    if !client.ip().is_loopback() {
+        return Ok(None);
+    }
+
+    match (client, server) {
+        (SocketAddr::V4(_), SocketAddr::V4(_)) => {
+            if let Some(cred) = search_tcp_table("/proc/net/tcp", client, server)? {
+                return Ok(Some(cred));
+            }
+            // the client may also use a v4-mapped address on an ipv6 socket
+            search_tcp_table("/proc/net/tcp6", client, server)
+        }
+        (SocketAddr::V6(_), SocketAddr::V6(_)) => {
+            search_tcp_table("/proc/net/tcp6", client, server)
+        }
+        _ => Ok(None),
+    }
+}
+
+fn search_tcp_table<P: AsRef<Path>>(
+    path: P,
+    client: SocketAddr,
+    server: SocketAddr,
+) -> io::Result<Option<LocalPeerCred>> {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    let reader = BufReader::new(file);
+    for line in reader.lines().skip(1) {
+        let line = line?;
+        if let Some(cred) = parse_tcp_table_line(&line, client, server) {
+            return Ok(Some(cred));
+        }
+    }
+    Ok(None)
+}
+
+fn parse_tcp_table_line(
+    line: &str,
+    client: SocketAddr,
+    server: SocketAddr,
+) -> Option<LocalPeerCred> {
+    // sl local_address rem_address st tx_queue:rx_queue tr:tm->when retrnsmt uid timeout inode
+    let mut iter = line.split_ascii_whitespace();
+    let _sl = iter.next()?;
+    let local = parse_table_addr(iter.next()?)?;
+    let remote = parse_table_addr(iter.next()?)?;
+    let state = iter.next()?;
+    if state != TCP_STATE_ESTABLISHED || local != client || remote != server {
+        return None;
+    }
+    let _queue = iter.next()?;
+    let _timer = iter.next()?;
+    let _retransmit = iter.next()?;
+    let uid = iter.next()?.parse::<u32>().ok()?;
+    let _timeout = iter.next()?;
+    let inode = iter.next()?.parse::<u64>().ok()?;
+    Some(LocalPeerCred {
+        uid,
+        pid: None,
+        inode: Some(inode),
+    })
+}
+
+fn parse_table_addr(s: &str) -> Option<SocketAddr> {
+    let (ip, port) = s.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    // the kernel prints each 32bit word in host byte order
+    let ip = match ip.len() {
+        8 => {
+            let v = u32::from_str_radix(ip, 16).ok()?;
+            IpAddr::V4(Ipv4Addr::from(v.to_ne_bytes()))
+        }
+        32 => {
+            let mut bytes = [0u8; 16];
+            for i in 0..4 {
+                let v = u32::from_str_radix(&ip[i * 8..i * 8 + 8], 16).ok()?;
+                bytes[i * 4..i * 4 + 4].copy_from_slice(&v.to_ne_bytes());
+            }
+            IpAddr::V6(Ipv6Addr::from(bytes))
+        }
+        _ => return None,
+    };
+    Some(native_socket_addr(SocketAddr::new(ip, port)))
+}
+
+fn find_socket_pid(inode: u64) -> io::Result<Option<i32>> {
+    let target = format!("socket:[{inode}]");
+    for entry in fs::read_dir("/proc")? {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|s| s.parse::<i32>().ok())
+        else {
+            continue;
+        };
+        // the process may exit or we may have no permission, just skip it
+        let Ok(fds) = fs::read_dir(entry.path().join("fd")) else {
+            continue;
+        };
+        for fd in fds.flatten() {
+            if let Ok(link) = fs::read_link(fd.path()) {
+                if link.as_os_str() == target.as_str() {
+                    return Ok(Some(pid));
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn read_cgroup(pid: i32) -> io::Result<String> {
+    let content = fs::read_to_string(format!("/proc/{pid}/cgroup"))?;
+    parse_cgroup(&content)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no valid cgroup path found"))
+}
+
+fn parse_cgroup(content: &str) -> Option<String> {
+    let mut first = None;
+    for line in content.lines() {
+        // hierarchy-ID:controller-list:cgroup-path
+        let mut iter = line.splitn(3, ':');
+        let id = iter.next()?;
+        let _controllers = iter.next()?;
+        let path = iter.next()?;
+        if id == "0" {
+            return Some(path.to_string());
+        }
+        if first.is_none() {
+            first = Some(path.to_string());
+        }
+    }
+    first
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn tcp_table_addr() {
+        #[cfg(target_endian = "little")]
+        {
+            let addr = parse_table_addr("0100007F:1F90").unwrap();
+            assert_eq!(addr, SocketAddr::from_str("127.0.0.1:8080").unwrap());
+
+            let addr = parse_table_addr("00000000000000000000000001000000:0050").unwrap();
+            assert_eq!(addr, SocketAddr::from_str("[::1]:80").unwrap());
+
+            let addr = parse_table_addr("0000000000000000FFFF00000100007F:0050").unwrap();
+            assert_eq!(addr, SocketAddr::from_str("127.0.0.1:80").unwrap());
+        }
+        assert!(parse_table_addr("0100007F").is_none());
+    }
+
+    #[test]
+    fn tcp_table_line() {
+        #[cfg(target_endian = "little")]
+        {
+            let line = "   1: 0100007F:C350 0100007F:1F90 01 00000000:00000000 00:00000000 00000000  1000        0 123456 1 0000000000000000 20 4 30 10 -1";
+            let client = SocketAddr::from_str("127.0.0.1:50000").unwrap();
+            let server = SocketAddr::from_str("127.0.0.1:8080").unwrap();
+            let cred = parse_tcp_table_line(line, client, server).unwrap();
+            assert_eq!(cred.uid(), 1000);
+            assert_eq!(cred.inode, Some(123456));
+
+            assert!(parse_tcp_table_line(line, server, client).is_none());
+        }
+    }
+
+    #[test]
+    fn tcp_table_line_v6() {
+        #[cfg(target_endian = "little")]
+        {
+            let line = "   0: 00000000000000000000000001000000:C350 00000000000000000000000001000000:0050 01 00000000:00000000 00:00000000 00000000     0        0 654321 1 0000000000000000 20 4 30 10 -1";
+            let client = SocketAddr::from_str("[::1]:50000").unwrap();
+            let server = SocketAddr::from_str("[::1]:80").unwrap();
+            let cred = parse_tcp_table_line(line, client, server).unwrap();
+            assert_eq!(cred.uid(), 0);
+            assert_eq!(cred.pid(), None);
+            assert_eq!(cred.inode, Some(654321));
+        }
+    }
+
+    #[test]
+    fn tcp_table_line_invalid() {
+        #[cfg(target_endian = "little")]
+        {
+            let client = SocketAddr::from_str("127.0.0.1:50000").unwrap();
+            let server = SocketAddr::from_str("127.0.0.1:8080").unwrap();
+
+            // TIME_WAIT
+            let line = "   1: 0100007F:C350 0100007F:1F90 06 00000000:00000000 03:00000000 00000000     0        0 0 3 0000000000000000";
+            assert!(parse_tcp_table_line(line, client, server).is_none());
+
+            // truncated
+            let line =
+                "   1: 0100007F:C350 0100007F:1F90 01 00000000:00000000 00:00000000 00000000  1000";
+            assert!(parse_tcp_table_line(line, client, server).is_none());
+
+            // invalid uid
+            let line = "   1: 0100007F:C350 0100007F:1F90 01 00000000:00000000 00:00000000 00000000  abc        0 123456";
+            assert!(parse_tcp_table_line(line, client, server).is_none());
+
+            let line = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode";
+            assert!(parse_tcp_table_line(line, client, server).is_none());
+        }
+    }
+
+    #[test]
+    fn tcp_table_search() {
+        #[cfg(target_endian = "little")]
+        {
+            let path = std::env::temp_dir().join(format!("g3-socket-tcp-{}", std::process::id()));
+            let content = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 111 1 0000000000000000 100 0 0 10 0
+   1: 0100007F:1F90 0100007F:C350 01 00000000:00000000 00:00000000 00000000     0        0 222 1 0000000000000000 20 4 30 10 -1
+   2: 0100007F:C350 0100007F:1F90 01 00000000:00000000 00:00000000 00000000  1000        0 333 1 0000000000000000 20 4 30 10 -1
+";
+            fs::write(&path, content).unwrap();
+
+            let client = SocketAddr::from_str("127.0.0.1:50000").unwrap();
+            let server = SocketAddr::from_str("127.0.0.1:8080").unwrap();
+            let cred = search_tcp_table(&path, client, server).unwrap().unwrap();
+            assert_eq!(cred.uid(), 1000);
+            assert_eq!(cred.inode, Some(333));
+
+            let client = SocketAddr::from_str("127.0.0.1:50001").unwrap();
+            assert!(search_tcp_table(&path, client, server).unwrap().is_none());
+
+            fs::remove_file(&path).unwrap();
+            assert!(search_tcp_table(&path, client, server).unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn tcp_peer_not_loopback() {
+        let client = SocketAddr::from_str("192.0.2.1:50000").unwrap();
+        let server = SocketAddr::from_str("192.0.2.2:8080").unwrap();
+        assert!(find_tcp_peer(client, server).unwrap().is_none());
+    }
+
+    #[test]
+    fn cgroup_path() {
+        let v2 = "0::/system.slice/app.service\n";
+        assert_eq!(parse_cgroup(v2).unwrap(), "/system.slice/app.service");
+
+        let v1 = "12:pids:/user.slice\n11:memory:/user.slice/user-1000.slice\n";
+        assert_eq!(parse_cgroup(v1).unwrap(), "/user.slice");
+
+        assert!(parse_cgroup("").is_none());
+    }
+}
@@ -0,0 +1,40 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io;
+use std::time::Duration;
+
+use tokio::net::windows::named_pipe::{ClientOptions, NamedPipeClient};
+
+// all pipe instances may be busy if other clients are connected at the same time
+const ERROR_PIPE_BUSY: i32 = 231;
+const PIPE_BUSY_MAX_RETRY: usize = 10;
+const PIPE_BUSY_RETRY_WAIT: Duration = Duration::from_millis(50);
+
+/// Open the named pipe, and retry for a while if all pipe instances are busy
+pub async fn open_client(pipe_name: &str) -> io::Result<NamedPipeClient> {
+    let mut retry = 0;
+    loop {
+        match ClientOptions::new().open(pipe_name) {
+            Ok(client) => return Ok(client),
+            Err(e) if e.raw_os_error() == Some(ERROR_PIPE_BUSY) && retry < PIPE_BUSY_MAX_RETRY => {
+                retry += 1;
+                tokio::time::sleep(PIPE_BUSY_RETRY_WAIT).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
@@ -82,6 +82,25 @@ impl RawSocket {
         socket.set_quickack(true)
     }
 
+    /// get the TOS / Traffic Class value of the accepted tcp socket
+    ///
+    /// It will be the value of the client SYN packet if `net.ipv4.tcp_reflect_tos` is enabled.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn tcp_tos(&self) -> io::Result<u8> {
+        let socket = self.get_inner()?;
+        let is_ipv6 = socket
+            .local_addr()?
+            .as_socket_ipv6()
+            .map(|a| a.ip().to_ipv4_mapped().is_none())
+            .unwrap_or(false);
+        let v = if is_ipv6 {
+            socket.tclass_v6()?
+        } else {
+            socket.tos()?
+        };
+        Ok(v as u8)
+    }
+
     pub fn set_udp_misc_opts(&self, misc_opts: UdpMiscSockOpts) -> io::Result<()> {
         let socket = self.get_inner()?;
         if let Some(ttl) = misc_opts.time_to_live {
@@ -14,10 +14,14 @@
  * limitations under the License.
  */
 
-#[cfg(any(target_os = "linux", target_os = "android"))]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
 mod unix;
+#[cfg(target_os = "freebsd")]
+pub(crate) use unix::set_accept_filter;
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub(crate) use unix::set_bind_address_no_port;
+#[cfg(target_os = "linux")]
+pub(crate) use unix::set_recv_orig_dst_addr;
 
 #[cfg(windows)]
 mod windows;
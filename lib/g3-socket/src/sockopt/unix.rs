@@ -31,6 +31,7 @@ where
     Ok(())
 }
 
+#[cfg(any(target_os = "linux", target_os = "android"))]
 pub(crate) fn set_bind_address_no_port<T: AsRawFd>(fd: &T, enable: bool) -> io::Result<()> {
     unsafe {
         setsockopt(
@@ -42,3 +43,53 @@ pub(crate) fn set_bind_address_no_port<T: AsRawFd>(fd: &T, enable: bool) -> io::
         Ok(())
     }
 }
+
+#[cfg(target_os = "linux")]
+pub(crate) fn set_recv_orig_dst_addr<T: AsRawFd>(fd: &T, ipv6: bool) -> io::Result<()> {
+    unsafe {
+        setsockopt(
+            fd.as_raw_fd(),
+            libc::IPPROTO_IP,
+            libc::IP_RECVORIGDSTADDR,
+            1 as c_int,
+        )?;
+        if ipv6 {
+            setsockopt(
+                fd.as_raw_fd(),
+                libc::IPPROTO_IPV6,
+                libc::IPV6_RECVORIGDSTADDR,
+                1 as c_int,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "freebsd")]
+#[derive(Clone, Copy)]
+#[repr(C)]
+struct AcceptFilterArg {
+    af_name: [libc::c_char; 16],
+    af_arg: [libc::c_char; 256 - 16],
+}
+
+#[cfg(target_os = "freebsd")]
+pub(crate) fn set_accept_filter<T: AsRawFd>(fd: &T, name: &str) -> io::Result<()> {
+    let mut arg = AcceptFilterArg {
+        af_name: [0; 16],
+        af_arg: [0; 256 - 16],
+    };
+    if name.len() >= arg.af_name.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "too long accept filter name",
+        ));
+    }
+    for (i, b) in name.bytes().enumerate() {
+        arg.af_name[i] = b as libc::c_char;
+    }
+    unsafe {
+        setsockopt(fd.as_raw_fd(), libc::SOL_SOCKET, libc::SO_ACCEPTFILTER, arg)?;
+        Ok(())
+    }
+}
@@ -43,6 +43,11 @@ pub fn new_std_listener(config: &TcpListenConfig) -> io::Result<std::net::TcpLis
     let bind_addr: SockAddr = addr.into();
     socket.bind(&bind_addr)?;
     socket.listen(config.backlog() as i32)?;
+    #[cfg(target_os = "freebsd")]
+    if let Some(name) = config.accept_filter() {
+        // accept filters can only be installed on listening sockets
+        super::sockopt::set_accept_filter(&socket, name)?;
+    }
     Ok(std::net::TcpListener::from(socket))
 }
 
@@ -116,6 +116,11 @@ pub fn new_std_bind_listen(config: &UdpListenConfig) -> io::Result<UdpSocket> {
     if config.is_ipv6only() {
         socket.set_only_v6(true)?;
     }
+    #[cfg(target_os = "linux")]
+    if config.transparent() {
+        socket.set_ip_transparent(true)?;
+        super::sockopt::set_recv_orig_dst_addr(&socket, addr.is_ipv6())?;
+    }
     let bind_addr = SockAddr::from(addr);
     socket.bind(&bind_addr)?;
     RawSocket::from(&socket).set_udp_misc_opts(config.socket_misc_opts())?;
@@ -128,12 +133,34 @@ pub fn new_std_rebind_listen(config: &UdpListenConfig, addr: SocketAddr) -> io::
     if config.is_ipv6only() {
         socket.set_only_v6(true)?;
     }
+    #[cfg(target_os = "linux")]
+    if config.transparent() {
+        socket.set_ip_transparent(true)?;
+        super::sockopt::set_recv_orig_dst_addr(&socket, addr.is_ipv6())?;
+    }
     let bind_addr = SockAddr::from(addr);
     socket.bind(&bind_addr)?;
     RawSocket::from(&socket).set_udp_misc_opts(config.socket_misc_opts())?;
     Ok(UdpSocket::from(socket))
 }
 
+/// Create a socket that sends replies to a TPROXY client from the original destination address
+#[cfg(target_os = "linux")]
+pub fn new_std_transparent_reply(
+    orig_dst_addr: SocketAddr,
+    peer_addr: SocketAddr,
+    buf_conf: SocketBufferConfig,
+    misc_opts: UdpMiscSockOpts,
+) -> io::Result<UdpSocket> {
+    let socket = new_udp_socket(AddressFamily::from(&orig_dst_addr), buf_conf)?;
+    socket.set_reuse_address(true)?;
+    socket.set_ip_transparent(true)?;
+    RawSocket::from(&socket).set_udp_misc_opts(misc_opts)?;
+    socket.bind(&SockAddr::from(orig_dst_addr))?;
+    socket.connect(&SockAddr::from(peer_addr))?;
+    Ok(UdpSocket::from(socket))
+}
+
 fn new_udp_socket(family: AddressFamily, buf_conf: SocketBufferConfig) -> io::Result<Socket> {
     let socket = new_nonblocking_udp_socket(family)?;
     RawSocket::from(&socket).set_buf_opts(buf_conf)?;
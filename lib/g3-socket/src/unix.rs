@@ -0,0 +1,60 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fs::{self, Permissions};
+use std::io;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::net::UnixListener;
+use std::path::Path;
+
+use socket2::{Domain, SockAddr, Socket, Type};
+
+use g3_types::net::UnixListenConfig;
+
+pub fn new_std_listener(config: &UnixListenConfig) -> io::Result<UnixListener> {
+    let path = config.path();
+    remove_stale_socket(path)?;
+
+    let socket = Socket::new(Domain::UNIX, Type::STREAM, None)?;
+    socket.set_nonblocking(true)?;
+    let bind_addr = SockAddr::unix(path)?;
+    socket.bind(&bind_addr)?;
+    if let Some(mode) = config.mode() {
+        fs::set_permissions(path, Permissions::from_mode(mode))?;
+    }
+    if config.owner().is_some() || config.group().is_some() {
+        std::os::unix::fs::chown(path, config.owner(), config.group())?;
+    }
+    socket.listen(config.backlog() as i32)?;
+    Ok(UnixListener::from(socket))
+}
+
+fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    match fs::symlink_metadata(path) {
+        Ok(meta) => {
+            if meta.file_type().is_socket() {
+                fs::remove_file(path)
+            } else {
+                Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("{} exists and is not a socket file", path.display()),
+                ))
+            }
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
@@ -19,10 +19,12 @@ use super::types::*;
 mod reply;
 mod request;
 mod udp_io;
+mod udp_over_tcp;
 
 pub use reply::Socks5Reply;
 pub use request::Socks5Request;
 pub use udp_io::{SocksUdpHeader, UdpInput, UdpOutput};
+pub use udp_over_tcp::{UdpOverTcpRecv, UdpOverTcpSend};
 
 pub mod auth;
 pub mod client;
@@ -0,0 +1,294 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! UDP over TCP for SOCKS5 UDP Associate
+//!
+//! Each UDP packet, with the SOCKS5 UDP request header, is sent over the TCP control connection,
+//! prefixed with a 2-byte big-endian length field.
+
+use std::io::{self, IoSlice};
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+))]
+use g3_io_ext::RecvMsgHdr;
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "macos",
+))]
+use g3_io_ext::SendMsgHdr;
+use g3_io_ext::{AsyncUdpRecv, AsyncUdpSend};
+
+const FRAME_LEN_SIZE: usize = 2;
+const FRAME_MAX_DATA_SIZE: usize = u16::MAX as usize;
+
+pub struct UdpOverTcpRecv<R> {
+    inner: R,
+    peer_addr: SocketAddr,
+    len_buf: [u8; FRAME_LEN_SIZE],
+    len_read: usize,
+    data_buf: Box<[u8]>,
+    data_len: usize,
+    data_read: usize,
+}
+
+impl<R> UdpOverTcpRecv<R> {
+    pub fn new(inner: R, peer_addr: SocketAddr) -> Self {
+        UdpOverTcpRecv {
+            inner,
+            peer_addr,
+            len_buf: [0u8; FRAME_LEN_SIZE],
+            len_read: 0,
+            data_buf: vec![0u8; FRAME_MAX_DATA_SIZE].into_boxed_slice(),
+            data_len: 0,
+            data_read: 0,
+        }
+    }
+}
+
+impl<R> UdpOverTcpRecv<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_read_some(
+        &mut self,
+        cx: &mut Context<'_>,
+        in_len_field: bool,
+    ) -> Poll<io::Result<usize>> {
+        let buf = if in_len_field {
+            &mut self.len_buf[self.len_read..]
+        } else {
+            &mut self.data_buf[self.data_read..self.data_len]
+        };
+        let mut read_buf = ReadBuf::new(buf);
+        ready!(Pin::new(&mut self.inner).poll_read(cx, &mut read_buf))?;
+        let nr = read_buf.filled().len();
+        if nr == 0 {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "tcp connection closed by peer",
+            )));
+        }
+        Poll::Ready(Ok(nr))
+    }
+
+    fn poll_recv_frame(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        while self.len_read < FRAME_LEN_SIZE {
+            let nr = ready!(self.poll_read_some(cx, true))?;
+            self.len_read += nr;
+            if self.len_read == FRAME_LEN_SIZE {
+                self.data_len = u16::from_be_bytes(self.len_buf) as usize;
+                self.data_read = 0;
+            }
+        }
+
+        while self.data_read < self.data_len {
+            let nr = ready!(self.poll_read_some(cx, false))?;
+            self.data_read += nr;
+        }
+
+        self.len_read = 0;
+        let len = self.data_len;
+        if len > buf.len() {
+            return Poll::Ready(Err(io::Error::other(format!(
+                "too large udp packet: {len} > {}",
+                buf.len()
+            ))));
+        }
+        buf[..len].copy_from_slice(&self.data_buf[..len]);
+        Poll::Ready(Ok(len))
+    }
+}
+
+impl<R> AsyncUdpRecv for UdpOverTcpRecv<R>
+where
+    R: AsyncRead + Unpin,
+{
+    fn poll_recv_from(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<(usize, SocketAddr)>> {
+        let nr = ready!(self.poll_recv_frame(cx, buf))?;
+        Poll::Ready(Ok((nr, self.peer_addr)))
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        self.poll_recv_frame(cx, buf)
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "macos",
+    ))]
+    fn poll_batch_recvmsg<const C: usize>(
+        &mut self,
+        cx: &mut Context<'_>,
+        hdr_v: &mut [RecvMsgHdr<'_, C>],
+    ) -> Poll<io::Result<usize>> {
+        // only one packet will be received each time, as there is no message boundary in tcp
+        let Some(h) = hdr_v.first_mut() else {
+            return Poll::Ready(Ok(0));
+        };
+        let Some(iov) = h.iov.first_mut() else {
+            return Poll::Ready(Err(io::Error::other("no iov buffer set")));
+        };
+        let nr = ready!(self.poll_recv_frame(cx, iov))?;
+        h.n_recv = nr;
+        Poll::Ready(Ok(1))
+    }
+}
+
+pub struct UdpOverTcpSend<W> {
+    inner: W,
+    frame_buf: Vec<u8>,
+    frame_sent: usize,
+}
+
+impl<W> UdpOverTcpSend<W> {
+    pub fn new(inner: W) -> Self {
+        UdpOverTcpSend {
+            inner,
+            frame_buf: Vec::with_capacity(FRAME_LEN_SIZE + FRAME_MAX_DATA_SIZE),
+            frame_sent: 0,
+        }
+    }
+}
+
+impl<W> UdpOverTcpSend<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    /// The caller should retry with the same packet if `Pending` is returned
+    fn poll_send_frame(
+        &mut self,
+        cx: &mut Context<'_>,
+        iov: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        if self.frame_buf.is_empty() {
+            let data_len: usize = iov.iter().map(|v| v.len()).sum();
+            if data_len > FRAME_MAX_DATA_SIZE {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("too large udp packet: {data_len} > {FRAME_MAX_DATA_SIZE}"),
+                )));
+            }
+            self.frame_buf
+                .extend_from_slice(&(data_len as u16).to_be_bytes());
+            for v in iov {
+                self.frame_buf.extend_from_slice(v);
+            }
+            self.frame_sent = 0;
+        }
+
+        while self.frame_sent < self.frame_buf.len() {
+            let nw = ready!(
+                Pin::new(&mut self.inner).poll_write(cx, &self.frame_buf[self.frame_sent..])
+            )?;
+            if nw == 0 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "write zero byte into tcp connection",
+                )));
+            }
+            self.frame_sent += nw;
+        }
+
+        let data_len = self.frame_buf.len() - FRAME_LEN_SIZE;
+        self.frame_buf.clear();
+        self.frame_sent = 0;
+        Poll::Ready(Ok(data_len))
+    }
+}
+
+impl<W> AsyncUdpSend for UdpOverTcpSend<W>
+where
+    W: AsyncWrite + Unpin,
+{
+    fn poll_send_to(
+        &mut self,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+        _target: SocketAddr,
+    ) -> Poll<io::Result<usize>> {
+        self.poll_send_frame(cx, &[IoSlice::new(buf)])
+    }
+
+    fn poll_send(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.poll_send_frame(cx, &[IoSlice::new(buf)])
+    }
+
+    fn poll_sendmsg(
+        &mut self,
+        cx: &mut Context<'_>,
+        iov: &[IoSlice<'_>],
+        _target: Option<SocketAddr>,
+    ) -> Poll<io::Result<usize>> {
+        self.poll_send_frame(cx, iov)
+    }
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd",
+    ))]
+    fn poll_batch_sendmsg<const C: usize>(
+        &mut self,
+        cx: &mut Context<'_>,
+        msgs: &mut [SendMsgHdr<'_, C>],
+    ) -> Poll<io::Result<usize>> {
+        let Some(m) = msgs.first_mut() else {
+            return Poll::Ready(Ok(0));
+        };
+        let nw = ready!(self.poll_send_frame(cx, m.as_ref()))?;
+        m.n_send = nw;
+        Poll::Ready(Ok(1))
+    }
+
+    #[cfg(target_os = "macos")]
+    fn poll_batch_sendmsg_x<const C: usize>(
+        &mut self,
+        cx: &mut Context<'_>,
+        msgs: &mut [SendMsgHdr<'_, C>],
+    ) -> Poll<io::Result<usize>> {
+        let Some(m) = msgs.first_mut() else {
+            return Poll::Ready(Ok(0));
+        };
+        let nw = ready!(self.poll_send_frame(cx, m.as_ref()))?;
+        m.n_send = nw;
+        Poll::Ready(Ok(1))
+    }
+}
@@ -158,7 +158,7 @@ impl AsyncIoThread {
 
     fn send_data(&self, data: String, backend: &mut SyslogBackend) -> io::Result<()> {
         let size = data.len();
-        backend.write_all(data.as_bytes())?;
+        backend.send_msg(data.as_bytes())?;
         backend.flush()?;
         self.stats.io.add_passed();
         self.stats.io.add_size(size);
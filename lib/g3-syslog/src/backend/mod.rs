@@ -14,8 +14,8 @@
  * limitations under the License.
  */
 
-use std::io;
-use std::net::{IpAddr, SocketAddr, UdpSocket};
+use std::io::{self, Write};
+use std::net::{IpAddr, SocketAddr, TcpStream, UdpSocket};
 #[cfg(unix)]
 use std::os::unix::net::UnixDatagram;
 #[cfg(unix)]
@@ -24,33 +24,82 @@ use std::path::PathBuf;
 #[cfg(feature = "yaml")]
 mod yaml;
 
+mod tcp;
 mod udp;
 #[cfg(unix)]
 mod unix_datagram;
 
+#[cfg(feature = "openssl")]
+mod tls;
+#[cfg(feature = "openssl")]
+pub use tls::SyslogTlsTarget;
+
 pub(super) enum SyslogBackend {
     Udp(UdpSocket),
     #[cfg(unix)]
     Unix(UnixDatagram),
+    Tcp(TcpStream),
+    #[cfg(feature = "openssl")]
+    Tls(openssl::ssl::SslStream<TcpStream>),
 }
 
 impl SyslogBackend {
     pub(super) fn need_reconnect(&self) -> bool {
-        false
+        match self {
+            SyslogBackend::Udp(_) => false,
+            #[cfg(unix)]
+            SyslogBackend::Unix(_) => false,
+            SyslogBackend::Tcp(_) => true,
+            #[cfg(feature = "openssl")]
+            SyslogBackend::Tls(_) => true,
+        }
+    }
+
+    /// Send a whole syslog message.
+    /// Octet counting framing (rfc6587 / rfc5425) will be used for stream backends.
+    pub(super) fn send_msg(&mut self, msg: &[u8]) -> io::Result<()> {
+        match self {
+            SyslogBackend::Udp(_) => self.write_all(msg),
+            #[cfg(unix)]
+            SyslogBackend::Unix(_) => self.write_all(msg),
+            SyslogBackend::Tcp(s) => write_framed(s, msg),
+            #[cfg(feature = "openssl")]
+            SyslogBackend::Tls(s) => write_framed(s, msg),
+        }
     }
 }
 
+fn write_framed<W: Write>(w: &mut W, msg: &[u8]) -> io::Result<()> {
+    let mut buffer = itoa::Buffer::new();
+    let len_s = buffer.format(msg.len());
+    let mut data = Vec::with_capacity(len_s.len() + 1 + msg.len());
+    data.extend_from_slice(len_s.as_bytes());
+    data.push(b' ');
+    data.extend_from_slice(msg);
+    w.write_all(&data)
+}
+
 impl io::Write for SyslogBackend {
     fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
         match self {
             SyslogBackend::Udp(s) => s.send(buf),
             #[cfg(unix)]
             SyslogBackend::Unix(s) => s.send(buf),
+            SyslogBackend::Tcp(s) => s.write(buf),
+            #[cfg(feature = "openssl")]
+            SyslogBackend::Tls(s) => s.write(buf),
         }
     }
 
     fn flush(&mut self) -> io::Result<()> {
-        Ok(())
+        match self {
+            SyslogBackend::Udp(_) => Ok(()),
+            #[cfg(unix)]
+            SyslogBackend::Unix(_) => Ok(()),
+            SyslogBackend::Tcp(s) => s.flush(),
+            #[cfg(feature = "openssl")]
+            SyslogBackend::Tls(s) => s.flush(),
+        }
     }
 }
 
@@ -61,6 +110,11 @@ pub enum SyslogBackendBuilder {
     Unix(Option<PathBuf>),
     /// udp socket with optional bind ip and remote address
     Udp(Option<IpAddr>, SocketAddr),
+    /// tcp stream with remote address
+    Tcp(SocketAddr),
+    /// tls over tcp stream
+    #[cfg(feature = "openssl")]
+    Tls(SyslogTlsTarget),
 }
 
 #[cfg(unix)]
@@ -95,6 +149,15 @@ impl SyslogBackendBuilder {
                 let socket = udp::udp(*bind_ip, *server)?;
                 Ok(SyslogBackend::Udp(socket))
             }
+            SyslogBackendBuilder::Tcp(server) => {
+                let stream = tcp::tcp(*server, tcp::DEFAULT_CONNECT_TIMEOUT)?;
+                Ok(SyslogBackend::Tcp(stream))
+            }
+            #[cfg(feature = "openssl")]
+            SyslogBackendBuilder::Tls(target) => {
+                let stream = target.connect()?;
+                Ok(SyslogBackend::Tls(stream))
+            }
         }
     }
 }
@@ -0,0 +1,65 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt;
+use std::io;
+use std::net::{SocketAddr, TcpStream};
+
+use openssl::ssl::SslStream;
+
+use g3_types::net::{Host, OpensslClientConfig};
+
+#[derive(Clone)]
+pub struct SyslogTlsTarget {
+    pub(super) server: SocketAddr,
+    pub(super) tls_name: Host,
+    pub(super) tls_client: OpensslClientConfig,
+}
+
+impl SyslogTlsTarget {
+    pub fn new(server: SocketAddr, tls_name: Host, tls_client: OpensslClientConfig) -> Self {
+        SyslogTlsTarget {
+            server,
+            tls_name,
+            tls_client,
+        }
+    }
+
+    pub(super) fn connect(&self) -> io::Result<SslStream<TcpStream>> {
+        let timeout = self.tls_client.handshake_timeout;
+        let stream = super::tcp::tcp(self.server, timeout)?;
+        stream.set_read_timeout(Some(timeout))?;
+
+        let ssl = self
+            .tls_client
+            .build_ssl(&self.tls_name, self.server.port())
+            .map_err(io::Error::other)?;
+        let tls_stream = ssl
+            .connect(stream)
+            .map_err(|e| io::Error::other(format!("tls handshake failed: {e}")))?;
+        tls_stream.get_ref().set_read_timeout(None)?;
+        Ok(tls_stream)
+    }
+}
+
+impl fmt::Debug for SyslogTlsTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyslogTlsTarget")
+            .field("server", &self.server)
+            .field("tls_name", &self.tls_name)
+            .finish()
+    }
+}
@@ -93,3 +93,85 @@ impl SyslogBackendBuilder {
         }
     }
 }
+
+impl SyslogBackendBuilder {
+    pub(crate) fn parse_tcp_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        match value {
+            Yaml::Hash(map) => {
+                let mut addr: Option<SocketAddr> = None;
+
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "address" | "addr" => {
+                        addr = Some(g3_yaml::value::as_env_sockaddr(v).context(format!(
+                            "invalid syslog tcp peer socket address value for key {k}"
+                        ))?);
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+
+                if let Some(addr) = addr.take() {
+                    Ok(SyslogBackendBuilder::Tcp(addr))
+                } else {
+                    Err(anyhow!("no target address has been set"))
+                }
+            }
+            Yaml::String(s) => {
+                let addr =
+                    SocketAddr::from_str(s).map_err(|e| anyhow!("invalid SocketAddr: {e}"))?;
+                Ok(SyslogBackendBuilder::Tcp(addr))
+            }
+            _ => Err(anyhow!("invalid yaml value for tcp syslog backend")),
+        }
+    }
+
+    #[cfg(feature = "openssl")]
+    pub(crate) fn parse_tls_yaml(value: &Yaml) -> anyhow::Result<Self> {
+        use g3_types::net::{Host, OpensslClientConfigBuilder};
+
+        use super::SyslogTlsTarget;
+
+        if let Yaml::Hash(map) = value {
+            let mut addr: Option<SocketAddr> = None;
+            let mut tls_name: Option<Host> = None;
+            let mut tls_client = OpensslClientConfigBuilder::with_cache_for_one_site();
+
+            g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                "address" | "addr" => {
+                    addr = Some(g3_yaml::value::as_env_sockaddr(v).context(format!(
+                        "invalid syslog tls peer socket address value for key {k}"
+                    ))?);
+                    Ok(())
+                }
+                "tls" | "tls_client" => {
+                    tls_client =
+                        g3_yaml::value::as_to_one_openssl_tls_client_config_builder(v, None)
+                            .context(format!(
+                                "invalid openssl tls client config value for key {k}"
+                            ))?;
+                    Ok(())
+                }
+                "tls_name" => {
+                    let name = g3_yaml::value::as_host(v)
+                        .context(format!("invalid tls server name value for key {k}"))?;
+                    tls_name = Some(name);
+                    Ok(())
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+
+            let Some(addr) = addr else {
+                return Err(anyhow!("no target address has been set"));
+            };
+            let tls_name = tls_name.unwrap_or_else(|| Host::Ip(addr.ip()));
+            let tls_client = tls_client
+                .build()
+                .context("failed to build tls client config")?;
+            Ok(SyslogBackendBuilder::Tls(SyslogTlsTarget::new(
+                addr, tls_name, tls_client,
+            )))
+        } else {
+            Err(anyhow!("invalid yaml value for tls syslog backend"))
+        }
+    }
+}
@@ -27,10 +27,13 @@ mod serde;
 mod cee;
 mod rfc3164;
 mod rfc5424;
+mod siem;
 
 pub(super) use cee::{FormatterRfc3164Cee, FormatterRfc5424Cee, CEE_EVENT_FLAG};
 pub(super) use rfc3164::FormatterRfc3164;
 pub(super) use rfc5424::FormatterRfc5424;
+pub use siem::SiemProductInfo;
+pub(super) use siem::{FormatterCef, FormatterLeef};
 
 #[cfg(feature = "yaml")]
 mod yaml;
@@ -58,4 +61,8 @@ pub enum SyslogFormatterKind {
     Rfc5424(i32, Option<String>),
     /// rfc5424 cee formatter with optional message id and event flag
     Rfc5424Cee(Option<String>, String),
+    /// rfc3164 formatter with ArcSight CEF content
+    Cef(SiemProductInfo),
+    /// rfc3164 formatter with QRadar LEEF content
+    Leef(SiemProductInfo),
 }
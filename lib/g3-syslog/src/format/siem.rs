@@ -0,0 +1,361 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::cell::RefCell;
+use std::fmt::{Arguments, Write};
+use std::io;
+
+use chrono::Local;
+use itoa::Integer;
+use ryu::Float;
+use slog::{Level, OwnedKVList, Record, Serializer, KV};
+
+use super::rfc3164::format_rfc3164_header;
+use super::{SyslogFormatter, SyslogHeader};
+
+thread_local! {
+    static TL_BUF: RefCell<String> = RefCell::new(String::with_capacity(128));
+    static TL_VBUF: RefCell<Vec<u8>> = RefCell::new(Vec::with_capacity(128));
+}
+
+/// The device info that will be set in the CEF / LEEF header
+#[derive(Clone, Debug)]
+pub struct SiemProductInfo {
+    pub vendor: String,
+    /// the syslog ident will be used if not set
+    pub product: Option<String>,
+    pub version: String,
+}
+
+impl Default for SiemProductInfo {
+    fn default() -> Self {
+        SiemProductInfo {
+            vendor: "G3".to_string(),
+            product: None,
+            version: "0".to_string(),
+        }
+    }
+}
+
+fn cef_severity(level: Level) -> u8 {
+    match level {
+        Level::Critical => 10,
+        Level::Error => 8,
+        Level::Warning => 6,
+        Level::Info => 3,
+        Level::Debug => 1,
+        Level::Trace => 0,
+    }
+}
+
+/// escape '\' and '|' in header fields, which is the same for CEF and LEEF
+fn push_header_field(w: &mut Vec<u8>, v: &str) {
+    for c in v.chars() {
+        match c {
+            '\\' => w.extend_from_slice(b"\\\\"),
+            '|' => w.extend_from_slice(b"\\|"),
+            '\r' | '\n' => w.push(b' '),
+            _ => w.extend_from_slice(c.encode_utf8(&mut [0u8; 4]).as_bytes()),
+        }
+    }
+    w.push(b'|');
+}
+
+fn push_product_header(w: &mut Vec<u8>, product: &SiemProductInfo, header: &SyslogHeader) {
+    push_header_field(w, &product.vendor);
+    push_header_field(w, product.product.as_deref().unwrap_or(header.process));
+    push_header_field(w, &product.version);
+}
+
+/// Formatter for ArcSight Common Event Format, with rfc3164 syslog header
+pub(crate) struct FormatterCef {
+    product: SiemProductInfo,
+    append_report_ts: bool,
+}
+
+impl FormatterCef {
+    pub(crate) fn new(product: SiemProductInfo) -> Self {
+        FormatterCef {
+            product,
+            append_report_ts: false,
+        }
+    }
+}
+
+impl SyslogFormatter for FormatterCef {
+    fn append_report_ts(&mut self, enable: bool) {
+        self.append_report_ts = enable;
+    }
+
+    fn format_slog(
+        &self,
+        w: &mut Vec<u8>,
+        header: &SyslogHeader,
+        record: &Record,
+        logger_values: &OwnedKVList,
+    ) -> Result<(), slog::Error> {
+        let datetime_now = Local::now();
+
+        format_rfc3164_header(w, header, record.level(), &datetime_now)?;
+
+        w.extend_from_slice(b"CEF:0|");
+        push_product_header(w, &self.product, header);
+        let msg = record.msg().to_string();
+        // use the log message as both the signature id and the name
+        push_header_field(w, &msg);
+        push_header_field(w, &msg);
+        let mut buffer = itoa::Buffer::new();
+        w.extend_from_slice(buffer.format(cef_severity(record.level())).as_bytes());
+        w.push(b'|');
+
+        let mut kv_formatter = FormatterExtension::new(w, ExtensionKind::Cef);
+        logger_values.serialize(record, &mut kv_formatter)?;
+        record.kv().serialize(record, &mut kv_formatter)?;
+        if self.append_report_ts {
+            kv_formatter.emit_integer("rt", datetime_now.timestamp_millis())?;
+        }
+        Ok(())
+    }
+}
+
+/// Formatter for IBM QRadar Log Event Extended Format, with rfc3164 syslog header
+pub(crate) struct FormatterLeef {
+    product: SiemProductInfo,
+    append_report_ts: bool,
+}
+
+impl FormatterLeef {
+    pub(crate) fn new(product: SiemProductInfo) -> Self {
+        FormatterLeef {
+            product,
+            append_report_ts: false,
+        }
+    }
+}
+
+impl SyslogFormatter for FormatterLeef {
+    fn append_report_ts(&mut self, enable: bool) {
+        self.append_report_ts = enable;
+    }
+
+    fn format_slog(
+        &self,
+        w: &mut Vec<u8>,
+        header: &SyslogHeader,
+        record: &Record,
+        logger_values: &OwnedKVList,
+    ) -> Result<(), slog::Error> {
+        let datetime_now = Local::now();
+
+        format_rfc3164_header(w, header, record.level(), &datetime_now)?;
+
+        w.extend_from_slice(b"LEEF:1.0|");
+        push_product_header(w, &self.product, header);
+        let msg = record.msg().to_string();
+        push_header_field(w, &msg);
+
+        let mut kv_formatter = FormatterExtension::new(w, ExtensionKind::Leef);
+        kv_formatter.emit_integer("sev", cef_severity(record.level()))?;
+        logger_values.serialize(record, &mut kv_formatter)?;
+        record.kv().serialize(record, &mut kv_formatter)?;
+        if self.append_report_ts {
+            kv_formatter.emit_integer("devTime", datetime_now.timestamp_millis())?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ExtensionKind {
+    Cef,
+    Leef,
+}
+
+struct FormatterExtension<'a> {
+    w: &'a mut Vec<u8>,
+    kind: ExtensionKind,
+    first: bool,
+}
+
+impl<'a> FormatterExtension<'a> {
+    fn new(w: &'a mut Vec<u8>, kind: ExtensionKind) -> Self {
+        FormatterExtension {
+            w,
+            kind,
+            first: true,
+        }
+    }
+
+    fn push_key(&mut self, key: &str) {
+        if self.first {
+            self.first = false;
+        } else {
+            match self.kind {
+                ExtensionKind::Cef => self.w.push(b' '),
+                ExtensionKind::Leef => self.w.push(b'\t'),
+            }
+        }
+        self.w.extend_from_slice(key.as_bytes());
+        self.w.push(b'=');
+    }
+
+    fn push_str_value(&mut self, v: &str) {
+        self.w.reserve(v.len());
+        for c in v.chars() {
+            match (self.kind, c) {
+                (ExtensionKind::Cef, '\\') => self.w.extend_from_slice(b"\\\\"),
+                (ExtensionKind::Cef, '=') => self.w.extend_from_slice(b"\\="),
+                (ExtensionKind::Leef, '\t') => self.w.extend_from_slice(b"\\t"),
+                (_, '\r') => self.w.extend_from_slice(b"\\r"),
+                (_, '\n') => self.w.extend_from_slice(b"\\n"),
+                _ => self
+                    .w
+                    .extend_from_slice(c.encode_utf8(&mut [0u8; 4]).as_bytes()),
+            }
+        }
+    }
+
+    fn emit_integer<T: Integer>(&mut self, key: &str, value: T) -> slog::Result {
+        self.push_key(key);
+
+        let mut buffer = itoa::Buffer::new();
+        let value_s = buffer.format(value);
+        self.w.extend_from_slice(value_s.as_bytes());
+        Ok(())
+    }
+
+    fn emit_float<T: Float>(&mut self, key: &str, value: T) -> slog::Result {
+        self.push_key(key);
+
+        let mut buffer = ryu::Buffer::new();
+        let value_s = buffer.format(value);
+        self.w.extend_from_slice(value_s.as_bytes());
+        Ok(())
+    }
+}
+
+impl Serializer for FormatterExtension<'_> {
+    impl_integer_by_itoa! {
+        /// Emit `usize`
+        usize => emit_usize
+    }
+    impl_integer_by_itoa! {
+        /// Emit `isize`
+        isize => emit_isize
+    }
+    impl_integer_by_itoa! {
+        /// Emit `u8`
+        u8 => emit_u8
+    }
+    impl_integer_by_itoa! {
+        /// Emit `i8`
+        i8 => emit_i8
+    }
+    impl_integer_by_itoa! {
+        /// Emit `u16`
+        u16 => emit_u16
+    }
+    impl_integer_by_itoa! {
+        /// Emit `i16`
+        i16 => emit_i16
+    }
+    impl_integer_by_itoa! {
+        /// Emit `u32`
+        u32 => emit_u32
+    }
+    impl_integer_by_itoa! {
+        /// Emit `i32`
+        i32 => emit_i32
+    }
+    impl_float_by_ryu! {
+        /// Emit `f32`
+        f32 => emit_f32
+    }
+    impl_integer_by_itoa! {
+        /// Emit `u64`
+        u64 => emit_u64
+    }
+    impl_integer_by_itoa! {
+        /// Emit `i64`
+        i64 => emit_i64
+    }
+    impl_float_by_ryu! {
+        /// Emit `f64`
+        f64 => emit_f64
+    }
+
+    fn emit_bool(&mut self, key: slog::Key, value: bool) -> slog::Result {
+        self.push_key(key);
+
+        if value {
+            self.w.extend_from_slice(b"true");
+        } else {
+            self.w.extend_from_slice(b"false");
+        }
+        Ok(())
+    }
+
+    fn emit_char(&mut self, key: slog::Key, value: char) -> slog::Result {
+        self.push_key(key);
+        self.push_str_value(value.encode_utf8(&mut [0u8; 4]));
+        Ok(())
+    }
+
+    fn emit_none(&mut self, _key: slog::Key) -> slog::Result {
+        Ok(())
+    }
+
+    fn emit_str(&mut self, key: slog::Key, value: &str) -> slog::Result {
+        self.push_key(key);
+        self.push_str_value(value);
+        Ok(())
+    }
+
+    impl_arguments_with_tls! {}
+    impl_serde_with_tls! {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_cef() {
+        let mut buf: Vec<u8> = Vec::new();
+        let mut f = FormatterExtension::new(&mut buf, ExtensionKind::Cef);
+        f.emit_str("dhost", "a=b\\c\n").unwrap();
+        f.emit_u64("in", 10).unwrap();
+        let s = std::str::from_utf8(&buf).unwrap();
+        assert_eq!(s, "dhost=a\\=b\\\\c\\n in=10");
+    }
+
+    #[test]
+    fn escape_leef() {
+        let mut buf: Vec<u8> = Vec::new();
+        let mut f = FormatterExtension::new(&mut buf, ExtensionKind::Leef);
+        f.emit_str("usrName", "a=b\tc").unwrap();
+        f.emit_bool("ok", true).unwrap();
+        let s = std::str::from_utf8(&buf).unwrap();
+        assert_eq!(s, "usrName=a=b\\tc\tok=true");
+    }
+
+    #[test]
+    fn escape_header() {
+        let mut buf: Vec<u8> = Vec::new();
+        push_header_field(&mut buf, "a|b\\c");
+        assert_eq!(buf.as_slice(), b"a\\|b\\\\c|");
+    }
+}
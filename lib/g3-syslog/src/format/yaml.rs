@@ -17,7 +17,7 @@
 use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
-use super::SyslogFormatterKind;
+use super::{SiemProductInfo, SyslogFormatterKind};
 
 impl SyslogFormatterKind {
     pub(crate) fn parse_rfc5424_yaml(value: &Yaml) -> anyhow::Result<Self> {
@@ -56,3 +56,36 @@ impl SyslogFormatterKind {
         }
     }
 }
+
+impl SyslogFormatterKind {
+    pub(crate) fn parse_siem_product_yaml(value: &Yaml) -> anyhow::Result<SiemProductInfo> {
+        let mut product = SiemProductInfo::default();
+
+        match value {
+            Yaml::Hash(map) => {
+                g3_yaml::foreach_kv(map, |k, v| match g3_yaml::key::normalize(k).as_str() {
+                    "vendor" | "device_vendor" => {
+                        product.vendor = g3_yaml::value::as_string(v)
+                            .context(format!("invalid string value for key {k}"))?;
+                        Ok(())
+                    }
+                    "product" | "device_product" => {
+                        let s = g3_yaml::value::as_string(v)
+                            .context(format!("invalid string value for key {k}"))?;
+                        product.product = Some(s);
+                        Ok(())
+                    }
+                    "version" | "device_version" => {
+                        product.version = g3_yaml::value::as_string(v)
+                            .context(format!("invalid string value for key {k}"))?;
+                        Ok(())
+                    }
+                    _ => Err(anyhow!("invalid key {k}")),
+                })?;
+                Ok(product)
+            }
+            Yaml::Null => Ok(product),
+            _ => Err(anyhow!("invalid yaml value for CEF / LEEF syslog format")),
+        }
+    }
+}
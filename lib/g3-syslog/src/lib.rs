@@ -31,9 +31,11 @@ pub use types::{Facility, Severity};
 use async_streamer::AsyncSyslogStreamer;
 
 pub use backend::SyslogBackendBuilder;
+#[cfg(feature = "openssl")]
+pub use backend::SyslogTlsTarget;
 
 use format::BoxSyslogFormatter;
-pub use format::SyslogFormatterKind;
+pub use format::{SiemProductInfo, SyslogFormatterKind};
 
 pub struct SyslogHeader {
     pub facility: Facility,
@@ -85,6 +87,8 @@ impl SyslogBuilder {
             SyslogFormatterKind::Rfc5424(_, mid) | SyslogFormatterKind::Rfc5424Cee(mid, _) => {
                 SyslogFormatterKind::Rfc5424Cee(mid.clone(), event_flag)
             }
+            // CEF and LEEF have their own structured syntax
+            SyslogFormatterKind::Cef(_) | SyslogFormatterKind::Leef(_) => return,
         };
     }
 
@@ -127,6 +131,14 @@ impl SyslogBuilder {
                 let formatter = format::FormatterRfc5424Cee::new(mid, event_flag);
                 Box::new(formatter) as BoxSyslogFormatter
             }
+            SyslogFormatterKind::Cef(product) => {
+                let formatter = format::FormatterCef::new(product);
+                Box::new(formatter) as BoxSyslogFormatter
+            }
+            SyslogFormatterKind::Leef(product) => {
+                let formatter = format::FormatterLeef::new(product);
+                Box::new(formatter) as BoxSyslogFormatter
+            }
         };
         formatter.append_report_ts(self.append_report_ts);
         AsyncSyslogStreamer::new(async_conf, header, formatter, &self.backend)
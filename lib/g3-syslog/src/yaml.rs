@@ -41,6 +41,19 @@ impl SyslogBuilder {
                         builder.set_backend(backend);
                         Ok(())
                     }
+                    "target_tcp" | "backend_tcp" => {
+                        let backend = SyslogBackendBuilder::parse_tcp_yaml(v)
+                            .context(format!("invalid value for key {k}"))?;
+                        builder.set_backend(backend);
+                        Ok(())
+                    }
+                    #[cfg(feature = "openssl")]
+                    "target_tls" | "backend_tls" => {
+                        let backend = SyslogBackendBuilder::parse_tls_yaml(v)
+                            .context(format!("invalid value for key {k}"))?;
+                        builder.set_backend(backend);
+                        Ok(())
+                    }
                     "target" | "backend" => {
                         if let Yaml::Hash(map) = v {
                             g3_yaml::foreach_kv(map, |k, v| {
@@ -51,6 +64,19 @@ impl SyslogBuilder {
                                         builder.set_backend(backend);
                                         Ok(())
                                     }
+                                    "tcp" => {
+                                        let backend = SyslogBackendBuilder::parse_tcp_yaml(v)
+                                            .context(format!("invalid value for key {k}"))?;
+                                        builder.set_backend(backend);
+                                        Ok(())
+                                    }
+                                    #[cfg(feature = "openssl")]
+                                    "tls" => {
+                                        let backend = SyslogBackendBuilder::parse_tls_yaml(v)
+                                            .context(format!("invalid value for key {k}"))?;
+                                        builder.set_backend(backend);
+                                        Ok(())
+                                    }
                                     #[cfg(unix)]
                                     "unix" => {
                                         let backend = SyslogBackendBuilder::parse_unix_yaml(v)
@@ -72,6 +98,18 @@ impl SyslogBuilder {
                         builder.set_format(format);
                         Ok(())
                     }
+                    "format_cef" => {
+                        let product = SyslogFormatterKind::parse_siem_product_yaml(v)
+                            .context(format!("invalid value for key {k}"))?;
+                        builder.set_format(SyslogFormatterKind::Cef(product));
+                        Ok(())
+                    }
+                    "format_leef" => {
+                        let product = SyslogFormatterKind::parse_siem_product_yaml(v)
+                            .context(format!("invalid value for key {k}"))?;
+                        builder.set_format(SyslogFormatterKind::Leef(product));
+                        Ok(())
+                    }
                     "use_cee_log_syntax" | "use_cls" => {
                         use_cee_log_syntax = g3_yaml::value::as_bool(v)
                             .context(format!("invalid boolean value for key {k}"))?;
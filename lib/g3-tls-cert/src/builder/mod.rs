@@ -44,3 +44,6 @@ pub use intermediate::IntermediateCertBuilder;
 
 mod mimic;
 pub use mimic::MimicCertBuilder;
+
+mod ocsp;
+pub use ocsp::OcspResponseBuilder;
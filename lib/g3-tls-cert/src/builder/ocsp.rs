@@ -0,0 +1,109 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::anyhow;
+use chrono::TimeDelta;
+use openssl::pkey::{PKeyRef, Private};
+use openssl::x509::X509Ref;
+
+/// Build a locally signed OCSP response, which can be stapled by the TLS server
+/// that uses the generated certificate.
+#[cfg_attr(feature = "boringssl", allow(dead_code))]
+pub struct OcspResponseBuilder {
+    this_update_skew: TimeDelta,
+}
+
+impl Default for OcspResponseBuilder {
+    fn default() -> Self {
+        OcspResponseBuilder {
+            this_update_skew: TimeDelta::hours(1),
+        }
+    }
+}
+
+impl OcspResponseBuilder {
+    /// Build a DER encoded OCSP response with status *good* for `cert`.
+    ///
+    /// The response will be signed directly by the issuer CA, and the next update time
+    /// will be set to `valid_seconds` later.
+    #[cfg(not(feature = "boringssl"))]
+    pub fn build_good(
+        &self,
+        cert: &X509Ref,
+        ca_cert: &X509Ref,
+        ca_key: &PKeyRef<Private>,
+        valid_seconds: u32,
+    ) -> anyhow::Result<Vec<u8>> {
+        use chrono::Utc;
+        use openssl::hash::MessageDigest;
+        use openssl::ocsp::{
+            OcspBasicResponse, OcspCertId, OcspCertStatus, OcspResponse, OcspResponseStatus,
+        };
+        use openssl::pkey::Id;
+
+        use super::asn1_time_from_chrono;
+        use crate::ext::OcspBasicResponseExt;
+
+        let time_now = Utc::now();
+        let this_update = asn1_time_from_chrono(&(time_now - self.this_update_skew))?;
+        let next_update =
+            asn1_time_from_chrono(&(time_now + TimeDelta::seconds(valid_seconds as i64)))?;
+
+        let cert_id = OcspCertId::from_cert(MessageDigest::sha1(), cert, ca_cert)
+            .map_err(|e| anyhow!("failed to create ocsp cert id: {e}"))?;
+
+        let mut basic = <OcspBasicResponse as OcspBasicResponseExt>::new()
+            .map_err(|e| anyhow!("failed to create ocsp basic response: {e}"))?;
+        basic
+            .add_status(&cert_id, OcspCertStatus::GOOD, &this_update, &next_update)
+            .map_err(|e| anyhow!("failed to add cert status: {e}"))?;
+
+        let digest = match ca_key.id() {
+            // see https://www.openssl.org/docs/manmaster/man3/EVP_DigestSign.html
+            Id::SM2 => MessageDigest::sm3(),
+            Id::ED25519 | Id::ED448 => MessageDigest::null(),
+            _ => MessageDigest::sha256(),
+        };
+        // the CA is the issuer, so there is no need to add extra certs
+        basic
+            .sign(
+                ca_cert,
+                ca_key,
+                digest,
+                openssl_sys::OCSP_NOCERTS | openssl_sys::OCSP_RESPID_KEY,
+            )
+            .map_err(|e| anyhow!("failed to sign ocsp basic response: {e}"))?;
+
+        let response = OcspResponse::create(OcspResponseStatus::SUCCESSFUL, Some(&basic))
+            .map_err(|e| anyhow!("failed to create ocsp response: {e}"))?;
+        response
+            .to_der()
+            .map_err(|e| anyhow!("failed to encode ocsp response: {e}"))
+    }
+
+    #[cfg(feature = "boringssl")]
+    pub fn build_good(
+        &self,
+        _cert: &X509Ref,
+        _ca_cert: &X509Ref,
+        _ca_key: &PKeyRef<Private>,
+        _valid_seconds: u32,
+    ) -> anyhow::Result<Vec<u8>> {
+        Err(anyhow!(
+            "ocsp response generation is not supported with BoringSSL"
+        ))
+    }
+}
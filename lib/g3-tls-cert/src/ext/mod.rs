@@ -24,3 +24,8 @@ pub use rsa::RsaExt;
 
 mod pkey;
 pub use pkey::PublicKeyExt;
+
+#[cfg(not(feature = "boringssl"))]
+mod ocsp;
+#[cfg(not(feature = "boringssl"))]
+pub use ocsp::OcspBasicResponseExt;
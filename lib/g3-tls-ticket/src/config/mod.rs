@@ -19,6 +19,7 @@ use std::time::Duration;
 
 use anyhow::Context;
 
+use g3_types::metrics::NodeName;
 use g3_types::net::{OpensslTicketKey, RollingTicketKey, RollingTicketer};
 
 use super::{TicketKeyUpdate, TicketSourceConfig};
@@ -46,11 +47,14 @@ impl Default for TlsTicketConfig {
 impl TlsTicketConfig {
     pub fn build_and_spawn_updater(
         &self,
+        owner: &NodeName,
     ) -> anyhow::Result<Arc<RollingTicketer<OpensslTicketKey>>> {
         let initial_key = OpensslTicketKey::new_random(self.local_lifetime)
             .context("failed to create initial random key")?;
         let ticketer = Arc::new(RollingTicketer::new(initial_key));
-        TicketKeyUpdate::new(self.clone(), ticketer.clone()).spawn_run();
+        crate::stats::register(owner, ticketer.stats().clone());
+        let rotate_notify = crate::rotate::register(owner);
+        TicketKeyUpdate::new(self.clone(), ticketer.clone(), rotate_notify).spawn_run();
         Ok(ticketer)
     }
 }
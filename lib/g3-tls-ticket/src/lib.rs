@@ -22,3 +22,9 @@ use source::TicketSourceConfig;
 
 mod update;
 use update::TicketKeyUpdate;
+
+mod stats;
+pub use stats::emit_stats;
+
+mod rotate;
+pub use rotate::rotate;
@@ -0,0 +1,55 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::{Arc, LazyLock, Mutex, Weak};
+
+use rustc_hash::FxHashMap;
+use tokio::sync::Notify;
+
+use g3_types::metrics::NodeName;
+
+static TICKETER_ROTATE_MAP: LazyLock<Mutex<FxHashMap<NodeName, Vec<Weak<Notify>>>>> =
+    LazyLock::new(|| Mutex::new(FxHashMap::default()));
+
+pub(crate) fn register(owner: &NodeName) -> Arc<Notify> {
+    let notify = Arc::new(Notify::new());
+    let mut map = TICKETER_ROTATE_MAP.lock().unwrap();
+    let list = map.entry(owner.clone()).or_default();
+    list.retain(|v| v.strong_count() > 0);
+    list.push(Arc::downgrade(&notify));
+    notify
+}
+
+/// Rotate the encrypt key of all the running ticketers of the `owner`.
+///
+/// The new key will be fetched from the remote source if configured, or generated locally.
+/// The number of ticketers that will be rotated is returned.
+pub fn rotate(owner: &NodeName) -> usize {
+    let mut map = TICKETER_ROTATE_MAP.lock().unwrap();
+    let Some(list) = map.get_mut(owner) else {
+        return 0;
+    };
+    list.retain(|v| v.strong_count() > 0);
+    let mut count = 0;
+    for notify in list.iter().filter_map(|v| v.upgrade()) {
+        notify.notify_one();
+        count += 1;
+    }
+    if list.is_empty() {
+        map.remove(owner);
+    }
+    count
+}
@@ -56,11 +56,11 @@ pub(crate) enum TicketSourceConfig {
 }
 
 impl TicketSourceConfig {
-    pub(crate) fn build(&self) -> anyhow::Result<TicketSource> {
+    pub(crate) fn build(&self, local_lifetime: u32) -> anyhow::Result<TicketSource> {
         match self {
             TicketSourceConfig::Redis(s) => {
                 let source = s
-                    .build()
+                    .build(local_lifetime)
                     .context("failed to build redis remote key source")?;
                 Ok(TicketSource::Redis(source))
             }
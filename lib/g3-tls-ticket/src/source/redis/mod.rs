@@ -15,12 +15,17 @@
  */
 
 use anyhow::{anyhow, Context};
-use redis::AsyncCommands;
+use chrono::Utc;
+use redis::{AsyncCommands, ExistenceCheck, SetExpiry, SetOptions};
+use serde_json::json;
 
 use g3_redis_client::{RedisClientConfig, RedisClientConfigBuilder};
+use g3_types::net::OpensslTicketKeyBuilder;
 
 use super::{RemoteDecryptKey, RemoteEncryptKey, RemoteKeys};
 
+const ROTATE_LOCK_EXPIRE_SECONDS: u64 = 30;
+
 #[cfg(feature = "yaml")]
 mod yaml;
 
@@ -29,15 +34,17 @@ pub(crate) struct RedisSourceConfig {
     redis: RedisClientConfigBuilder,
     enc_key_name: String,
     dec_set_name: String,
+    rotate: bool,
 }
 
 impl RedisSourceConfig {
-    pub(super) fn build(&self) -> anyhow::Result<RedisSource> {
+    pub(super) fn build(&self, lifetime: u32) -> anyhow::Result<RedisSource> {
         let redis = self.redis.build()?;
         Ok(RedisSource {
             redis,
             enc_key_name: self.enc_key_name.clone(),
             dec_set_name: self.dec_set_name.clone(),
+            rotate_lifetime: self.rotate.then_some(lifetime),
         })
     }
 
@@ -56,6 +63,7 @@ pub(crate) struct RedisSource {
     redis: RedisClientConfig,
     enc_key_name: String,
     dec_set_name: String,
+    rotate_lifetime: Option<u32>,
 }
 
 impl RedisSource {
@@ -66,10 +74,17 @@ impl RedisSource {
             .await
             .context("failed to connect to redis")?;
 
-        let enc_key = conn
+        let mut enc_key: redis::Value = conn
             .get(&self.enc_key_name)
             .await
             .map_err(|e| anyhow!("failed to get redis key {}: {e}", self.enc_key_name))?;
+        if let (redis::Value::Nil, Some(lifetime)) = (&enc_key, self.rotate_lifetime) {
+            self.rotate_keys(&mut conn, lifetime).await?;
+            enc_key = conn
+                .get(&self.enc_key_name)
+                .await
+                .map_err(|e| anyhow!("failed to get redis key {}: {e}", self.enc_key_name))?;
+        }
         let redis::Value::BulkString(b) = enc_key else {
             return Err(anyhow!(
                 "invalid data type for redis key {}",
@@ -90,7 +105,9 @@ impl RedisSource {
                 self.dec_set_name
             )
         })?;
+        let now = Utc::now();
         let mut dec_keys = Vec::with_capacity(members.len());
+        let mut expired_members = Vec::new();
         for (i, m) in members.into_iter().enumerate() {
             let redis::Value::BulkString(b) = m else {
                 return Err(anyhow!(
@@ -105,12 +122,95 @@ impl RedisSource {
                 )
             })?;
             let dec_key = RemoteDecryptKey::parse_json(&record).context("invalid decrypt key")?;
+            if dec_key.expire_duration(&now).is_none() {
+                expired_members.push(b);
+                continue;
+            }
             dec_keys.push(dec_key);
         }
 
+        if self.rotate_lifetime.is_some() && !expired_members.is_empty() {
+            let _: redis::Value = conn
+                .srem(&self.dec_set_name, expired_members)
+                .await
+                .map_err(|e| {
+                    anyhow!(
+                        "failed to remove expired members from set {}: {e}",
+                        self.dec_set_name
+                    )
+                })?;
+        }
+
         Ok(RemoteKeys {
             enc: enc_key,
             dec: dec_keys,
         })
     }
+
+    async fn rotate_keys<C: AsyncCommands>(
+        &self,
+        conn: &mut C,
+        lifetime: u32,
+    ) -> anyhow::Result<()> {
+        // only one instance in the cluster should generate the new key
+        let lock_name = format!("{}.lock", self.enc_key_name);
+        let lock_options = SetOptions::default()
+            .conditional_set(ExistenceCheck::NX)
+            .with_expiration(SetExpiry::EX(ROTATE_LOCK_EXPIRE_SECONDS));
+        let locked: redis::Value = conn
+            .set_options(&lock_name, std::process::id(), lock_options)
+            .await
+            .map_err(|e| anyhow!("failed to set redis lock key {lock_name}: {e}"))?;
+        if matches!(locked, redis::Value::Nil) {
+            return Err(anyhow!(
+                "redis key {} is being rotated by another instance",
+                self.enc_key_name
+            ));
+        }
+
+        let r = self.do_rotate_keys(conn, lifetime).await;
+        let _: Result<redis::Value, _> = conn.del(&lock_name).await;
+        r
+    }
+
+    async fn do_rotate_keys<C: AsyncCommands>(
+        &self,
+        conn: &mut C,
+        lifetime: u32,
+    ) -> anyhow::Result<()> {
+        let builder = OpensslTicketKeyBuilder::new_random(lifetime)?;
+        let name = hex::encode(builder.name);
+        let aes = hex::encode(builder.aes_key);
+        let hmac = hex::encode(builder.hmac_key);
+
+        // new tickets will be issued with this key for half of the lifetime,
+        // and they should be decryptable for the whole lifetime
+        let enc_expire = (lifetime >> 1).max(1) as u64;
+        let dec_expire =
+            Utc::now() + chrono::Duration::seconds(lifetime as i64 + enc_expire as i64);
+
+        let dec_record = json!({
+            "name": name,
+            "aes": aes,
+            "hmac": hmac,
+            "lifetime": lifetime,
+            "expire": dec_expire.to_rfc3339(),
+        });
+        let _: redis::Value = conn
+            .sadd(&self.dec_set_name, dec_record.to_string())
+            .await
+            .map_err(|e| anyhow!("failed to add new key to set {}: {e}", self.dec_set_name))?;
+
+        let enc_record = json!({
+            "name": name,
+            "aes": aes,
+            "hmac": hmac,
+            "lifetime": lifetime,
+        });
+        let _: redis::Value = conn
+            .set_ex(&self.enc_key_name, enc_record.to_string(), enc_expire)
+            .await
+            .map_err(|e| anyhow!("failed to set redis key {}: {e}", self.enc_key_name))?;
+        Ok(())
+    }
 }
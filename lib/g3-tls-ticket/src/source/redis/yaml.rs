@@ -38,6 +38,10 @@ impl RedisSourceConfig {
                 config.dec_set_name = g3_yaml::value::as_string(v)?;
                 Ok(())
             }
+            "rotate" => {
+                config.rotate = g3_yaml::value::as_bool(v)?;
+                Ok(())
+            }
             normalized_key => config.redis.set_yaml_kv(normalized_key, v, lookup_dir),
         })?;
 
@@ -0,0 +1,87 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::sync::{Arc, LazyLock, Mutex};
+
+use rustc_hash::FxHashMap;
+
+use g3_statsd_client::{StatsdClient, StatsdTagGroup};
+use g3_types::metrics::NodeName;
+use g3_types::net::RollingTicketerStats;
+use g3_types::stats::StatId;
+
+const TAG_KEY_OWNER: &str = "owner";
+const TAG_KEY_STAT_ID: &str = "stat_id";
+
+const METRIC_NAME_TICKET_DECRYPT_TOTAL: &str = "tls.ticket.decrypt.total";
+const METRIC_NAME_TICKET_DECRYPT_HIT: &str = "tls.ticket.decrypt.hit";
+
+#[derive(Default)]
+struct TicketerSnapshot {
+    decrypt_total: u64,
+    decrypt_hit: u64,
+}
+
+struct TicketerStatsValue {
+    owner: NodeName,
+    stats: Arc<RollingTicketerStats>,
+    snap: TicketerSnapshot,
+}
+
+static TICKETER_STATS_MAP: LazyLock<Mutex<FxHashMap<StatId, TicketerStatsValue>>> =
+    LazyLock::new(|| Mutex::new(FxHashMap::default()));
+
+pub(crate) fn register(owner: &NodeName, stats: Arc<RollingTicketerStats>) {
+    let value = TicketerStatsValue {
+        owner: owner.clone(),
+        stats,
+        snap: TicketerSnapshot::default(),
+    };
+    let mut stats_map = TICKETER_STATS_MAP.lock().unwrap();
+    stats_map.insert(StatId::new(), value);
+}
+
+pub fn emit_stats(client: &mut StatsdClient) {
+    let mut stats_map = TICKETER_STATS_MAP.lock().unwrap();
+    stats_map.retain(|stat_id, v| {
+        emit_to_statsd(client, *stat_id, v);
+        // use Arc instead of Weak here, as we should emit the final metrics before drop it
+        Arc::strong_count(&v.stats) > 1
+    });
+}
+
+fn emit_to_statsd(client: &mut StatsdClient, stat_id: StatId, v: &mut TicketerStatsValue) {
+    let mut buffer = itoa::Buffer::new();
+    let stat_id = buffer.format(stat_id.as_u64());
+
+    let mut common_tags = StatsdTagGroup::default();
+    common_tags.add_tag(TAG_KEY_OWNER, &v.owner);
+    common_tags.add_tag(TAG_KEY_STAT_ID, stat_id);
+
+    let new_value = v.stats.decrypt_total();
+    let diff_value = new_value.wrapping_sub(v.snap.decrypt_total);
+    client
+        .count_with_tags(METRIC_NAME_TICKET_DECRYPT_TOTAL, diff_value, &common_tags)
+        .send();
+    v.snap.decrypt_total = new_value;
+
+    let new_value = v.stats.decrypt_hit();
+    let diff_value = new_value.wrapping_sub(v.snap.decrypt_hit);
+    client
+        .count_with_tags(METRIC_NAME_TICKET_DECRYPT_HIT, diff_value, &common_tags)
+        .send();
+    v.snap.decrypt_hit = new_value;
+}
@@ -21,6 +21,7 @@ use std::time::Duration;
 use chrono::Utc;
 use log::warn;
 use rustc_hash::FxHashSet;
+use tokio::sync::Notify;
 use tokio::time::Instant;
 use tokio_util::time::DelayQueue;
 
@@ -32,6 +33,7 @@ use crate::source::TicketSource;
 pub(crate) struct TicketKeyUpdate {
     config: TlsTicketConfig,
     ticketer: Arc<RollingTicketer<OpensslTicketKey>>,
+    rotate_notify: Arc<Notify>,
     expire_set: FxHashSet<TicketKeyName>,
     expire_queue: DelayQueue<TicketKeyName>,
     local_roll_at: Instant,
@@ -41,12 +43,14 @@ impl TicketKeyUpdate {
     pub(crate) fn new(
         config: TlsTicketConfig,
         ticketer: Arc<RollingTicketer<OpensslTicketKey>>,
+        rotate_notify: Arc<Notify>,
     ) -> Self {
         let local_roll_time = Duration::from_secs((config.local_lifetime >> 1) as u64);
         let local_roll_at = Instant::now() + local_roll_time;
         TicketKeyUpdate {
             config,
             ticketer,
+            rotate_notify,
             expire_set: FxHashSet::default(),
             expire_queue: DelayQueue::new(),
             local_roll_at,
@@ -61,7 +65,7 @@ impl TicketKeyUpdate {
         let mut check_interval = tokio::time::interval(self.config.check_interval);
 
         let remote_source = match &self.config.remote_source {
-            Some(config) => match config.build() {
+            Some(config) => match config.build(self.config.local_lifetime) {
                 Ok(source) => Some(source),
                 Err(e) => {
                     warn!("remote source disabled, dur to: {e}");
@@ -71,14 +75,26 @@ impl TicketKeyUpdate {
             None => None,
         };
 
+        let rotate_notify = self.rotate_notify.clone();
         loop {
             if self.expire_set.is_empty() {
-                check_interval.tick().await;
-                self.check_roll_ticket(remote_source.as_ref()).await;
+                tokio::select! {
+                    biased;
+
+                    _ = rotate_notify.notified() => {
+                        self.force_roll_ticket(remote_source.as_ref()).await;
+                    }
+                    _ = check_interval.tick() => {
+                        self.check_roll_ticket(remote_source.as_ref()).await;
+                    }
+                }
             } else {
                 tokio::select! {
                     biased;
 
+                    _ = rotate_notify.notified() => {
+                        self.force_roll_ticket(remote_source.as_ref()).await;
+                    }
                     _ = check_interval.tick() => {
                         self.check_roll_ticket(remote_source.as_ref()).await;
                     }
@@ -100,27 +116,9 @@ impl TicketKeyUpdate {
 
     async fn check_roll_ticket(&mut self, remote_source: Option<&TicketSource>) {
         let mut roll_local = true;
-        if let Some(source) = &remote_source {
-            match source.fetch_remote_keys().await {
-                Ok(data) => {
-                    roll_local = false;
-                    self.update_encrypt_key(data.enc.key, Instant::now());
-                    let now = Utc::now();
-                    for dec_key in data.dec {
-                        if let Some(expire_dur) = dec_key.expire_duration(&now) {
-                            let key = dec_key.key;
-                            let key_name = key.name();
-                            if !self.expire_set.contains(&key_name) {
-                                self.ticketer.add_decrypt_key(Arc::new(key));
-                                self.expire_set.insert(key_name);
-                                self.expire_queue.insert(key_name, expire_dur);
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    warn!("failed to get keys from remote source: {e}")
-                }
+        if let Some(source) = remote_source {
+            if self.update_from_remote(source).await {
+                roll_local = false;
             }
         }
 
@@ -130,6 +128,40 @@ impl TicketKeyUpdate {
         }
     }
 
+    async fn force_roll_ticket(&mut self, remote_source: Option<&TicketSource>) {
+        if let Some(source) = remote_source {
+            if self.update_from_remote(source).await {
+                return;
+            }
+        }
+        self.new_local_key(Instant::now());
+    }
+
+    async fn update_from_remote(&mut self, source: &TicketSource) -> bool {
+        match source.fetch_remote_keys().await {
+            Ok(data) => {
+                self.update_encrypt_key(data.enc.key, Instant::now());
+                let now = Utc::now();
+                for dec_key in data.dec {
+                    if let Some(expire_dur) = dec_key.expire_duration(&now) {
+                        let key = dec_key.key;
+                        let key_name = key.name();
+                        if !self.expire_set.contains(&key_name) {
+                            self.ticketer.add_decrypt_key(Arc::new(key));
+                            self.expire_set.insert(key_name);
+                            self.expire_queue.insert(key_name, expire_dur);
+                        }
+                    }
+                }
+                true
+            }
+            Err(e) => {
+                warn!("failed to get keys from remote source: {e}");
+                false
+            }
+        }
+    }
+
     fn new_local_key(&mut self, now: Instant) {
         let local_lifetime = self.config.local_lifetime;
         match OpensslTicketKey::new_random(local_lifetime) {
@@ -0,0 +1,81 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+#![feature(test)]
+
+extern crate test;
+use test::Bencher;
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use ip_network::{IpNetwork, Ipv4Network, Ipv6Network};
+
+use g3_types::acl::{AclAction, AclNetworkRuleBuilder};
+
+const RULE_COUNT: u32 = 200_000;
+
+fn build_v4_builder() -> AclNetworkRuleBuilder {
+    let mut builder = AclNetworkRuleBuilder::new_egress(AclAction::Permit);
+    for i in 0..RULE_COUNT {
+        let ip = Ipv4Addr::from(0x0a00_0000 | (i << 8));
+        let net = Ipv4Network::new(ip, 24).unwrap();
+        builder.add_network(IpNetwork::V4(net), AclAction::Forbid);
+    }
+    builder
+}
+
+fn build_v6_builder() -> AclNetworkRuleBuilder {
+    let mut builder = AclNetworkRuleBuilder::new_egress(AclAction::Permit);
+    for i in 0..RULE_COUNT {
+        let ip = Ipv6Addr::new(0x2001, 0xdb8, (i >> 16) as u16, i as u16, 0, 0, 0, 0);
+        let net = Ipv6Network::new(ip, 64).unwrap();
+        builder.add_network(IpNetwork::V6(net), AclAction::Forbid);
+    }
+    builder
+}
+
+#[bench]
+fn load_v4(b: &mut Bencher) {
+    let builder = build_v4_builder();
+    b.iter(|| builder.build());
+}
+
+#[bench]
+fn load_v6(b: &mut Bencher) {
+    let builder = build_v6_builder();
+    b.iter(|| builder.build());
+}
+
+#[bench]
+fn lookup_v4(b: &mut Bencher) {
+    let rule = build_v4_builder().build();
+    let mut i = 0u32;
+    b.iter(|| {
+        i = i.wrapping_add(0x9e37);
+        let ip = IpAddr::V4(Ipv4Addr::from(0x0a00_0000 | (i & 0x00ff_ffff)));
+        rule.check(ip)
+    });
+}
+
+#[bench]
+fn lookup_v6(b: &mut Bencher) {
+    let rule = build_v6_builder().build();
+    let mut i = 0u32;
+    b.iter(|| {
+        i = i.wrapping_add(0x9e37);
+        let ip = Ipv6Addr::new(0x2001, 0xdb8, (i >> 16) as u16 & 0x3, i as u16, 0, 0, 0, 1);
+        rule.check(IpAddr::V6(ip))
+    });
+}
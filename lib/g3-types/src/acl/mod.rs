@@ -23,6 +23,7 @@ mod exact_host;
 mod exact_port;
 mod fx_hash;
 mod network;
+mod network_trie;
 mod proxy_request;
 mod radix_trie;
 mod regex_set;
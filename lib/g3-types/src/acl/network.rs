@@ -19,8 +19,8 @@ use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::sync::LazyLock;
 
 use ip_network::IpNetwork;
-use ip_network_table::IpNetworkTable;
 
+use super::network_trie::IpNetworkTrie;
 use super::{AclAction, ActionContract};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -53,10 +53,8 @@ impl<Action: ActionContract> AclNetworkRuleBuilder<Action> {
     }
 
     pub fn build(&self) -> AclNetworkRule<Action> {
-        let mut inner = IpNetworkTable::new();
-        for (net, action) in &self.inner {
-            inner.insert(*net, *action);
-        }
+        let inner =
+            IpNetworkTrie::bulk_load(self.inner.iter().map(|(net, action)| (*net, *action)));
         AclNetworkRule {
             inner,
             default_action: self.missed_action,
@@ -117,13 +115,13 @@ impl AclNetworkRuleBuilder<AclAction> {
 }
 
 pub struct AclNetworkRule<Action = AclAction> {
-    inner: IpNetworkTable<Action>,
+    inner: IpNetworkTrie<Action>,
     default_action: Action,
 }
 
 impl<Action: ActionContract> AclNetworkRule<Action> {
     pub fn check(&self, ip: IpAddr) -> (bool, Action) {
-        if let Some((_, action)) = self.inner.longest_match(ip) {
+        if let Some(action) = self.inner.longest_match(ip) {
             (true, *action)
         } else {
             (false, self.default_action)
@@ -0,0 +1,281 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+use std::net::IpAddr;
+
+use ip_network::IpNetwork;
+
+const NO_CHILD: u32 = 0;
+
+#[inline]
+fn prefix_mask(len: u8) -> u128 {
+    if len == 0 {
+        0
+    } else {
+        u128::MAX << (128 - len)
+    }
+}
+
+#[inline]
+fn bit_at(key: u128, pos: u8) -> usize {
+    ((key >> (127 - pos)) & 1) as usize
+}
+
+#[inline]
+fn common_prefix_len(a: u128, b: u128, max: u8) -> u8 {
+    let n = (a ^ b).leading_zeros() as u8;
+    n.min(max)
+}
+
+struct Node<T> {
+    key: u128,
+    len: u8,
+    value: Option<T>,
+    children: [u32; 2],
+}
+
+impl<T> Node<T> {
+    fn new(key: u128, len: u8, value: Option<T>) -> Self {
+        Node {
+            key: key & prefix_mask(len),
+            len,
+            value,
+            children: [NO_CHILD; 2],
+        }
+    }
+
+    #[inline]
+    fn contains(&self, key: u128) -> bool {
+        (key & prefix_mask(self.len)) == self.key
+    }
+}
+
+/// A path compressed binary radix trie for longest prefix match.
+///
+/// All nodes are stored in a single vec and linked by index, the root node is always at index 0,
+/// so 0 can be used to mark an empty child slot. IPv4 addresses are stored in the high 32 bits.
+struct PrefixTrie<T> {
+    nodes: Vec<Node<T>>,
+}
+
+impl<T> PrefixTrie<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        let mut nodes = Vec::with_capacity(capacity + 1);
+        nodes.push(Node::new(0, 0, None));
+        PrefixTrie { nodes }
+    }
+
+    fn push_node(&mut self, node: Node<T>) -> u32 {
+        let id = self.nodes.len() as u32;
+        self.nodes.push(node);
+        id
+    }
+
+    fn insert(&mut self, key: u128, len: u8, value: T) -> Option<T> {
+        let key = key & prefix_mask(len);
+        let mut cur = 0usize;
+        loop {
+            let node = &self.nodes[cur];
+            if node.len == len {
+                return self.nodes[cur].value.replace(value);
+            }
+
+            let bit = bit_at(key, node.len);
+            let child = node.children[bit];
+            if child == NO_CHILD {
+                let id = self.push_node(Node::new(key, len, Some(value)));
+                self.nodes[cur].children[bit] = id;
+                return None;
+            }
+
+            let child_node = &self.nodes[child as usize];
+            let common = common_prefix_len(key, child_node.key, len.min(child_node.len));
+            if common == child_node.len {
+                cur = child as usize;
+                continue;
+            }
+
+            let child_key = child_node.key;
+            let id = if common == len {
+                // the new node is the parent of the child node
+                let mut new = Node::new(key, len, Some(value));
+                new.children[bit_at(child_key, common)] = child;
+                self.push_node(new)
+            } else {
+                // add a branch node for both of them
+                let mut branch = Node::new(key, common, None);
+                branch.children[bit_at(child_key, common)] = child;
+                let leaf = self.push_node(Node::new(key, len, Some(value)));
+                branch.children[bit_at(key, common)] = leaf;
+                self.push_node(branch)
+            };
+            self.nodes[cur].children[bit] = id;
+            return None;
+        }
+    }
+
+    fn longest_match(&self, key: u128) -> Option<&T> {
+        let mut node = &self.nodes[0];
+        let mut found = node.value.as_ref();
+        while node.len < 128 {
+            let child = node.children[bit_at(key, node.len)];
+            if child == NO_CHILD {
+                break;
+            }
+            node = &self.nodes[child as usize];
+            if !node.contains(key) {
+                break;
+            }
+            if let Some(v) = &node.value {
+                found = Some(v);
+            }
+        }
+        found
+    }
+}
+
+/// Longest prefix match table for both IPv4 and IPv6 networks.
+pub(super) struct IpNetworkTrie<T> {
+    ipv4: PrefixTrie<T>,
+    ipv6: PrefixTrie<T>,
+}
+
+impl<T> Default for IpNetworkTrie<T> {
+    fn default() -> Self {
+        IpNetworkTrie {
+            ipv4: PrefixTrie::with_capacity(0),
+            ipv6: PrefixTrie::with_capacity(0),
+        }
+    }
+}
+
+impl<T> IpNetworkTrie<T> {
+    /// Build the table from a list of networks.
+    ///
+    /// The networks will be sorted by prefix length first, so that parent nodes are always
+    /// inserted before their children and no branch node split is needed for them.
+    pub(super) fn bulk_load<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (IpNetwork, T)>,
+    {
+        let mut all = iter.into_iter().collect::<Vec<_>>();
+        all.sort_unstable_by_key(|(net, _)| (net.netmask(), net.network_address()));
+        let v4_count = all
+            .iter()
+            .filter(|(net, _)| matches!(net, IpNetwork::V4(_)))
+            .count();
+
+        // each insert will add at most 2 nodes
+        let mut trie = IpNetworkTrie {
+            ipv4: PrefixTrie::with_capacity(v4_count * 2),
+            ipv6: PrefixTrie::with_capacity((all.len() - v4_count) * 2),
+        };
+        for (net, v) in all {
+            trie.insert(net, v);
+        }
+        trie.ipv4.nodes.shrink_to_fit();
+        trie.ipv6.nodes.shrink_to_fit();
+        trie
+    }
+
+    pub(super) fn insert(&mut self, net: IpNetwork, v: T) -> Option<T> {
+        match net {
+            IpNetwork::V4(n) => {
+                let key = (u32::from(n.network_address()) as u128) << 96;
+                self.ipv4.insert(key, n.netmask(), v)
+            }
+            IpNetwork::V6(n) => {
+                let key = u128::from(n.network_address());
+                self.ipv6.insert(key, n.netmask(), v)
+            }
+        }
+    }
+
+    pub(super) fn longest_match(&self, ip: IpAddr) -> Option<&T> {
+        match ip {
+            IpAddr::V4(ip4) => self.ipv4.longest_match((u32::from(ip4) as u128) << 96),
+            IpAddr::V6(ip6) => self.ipv6.longest_match(u128::from(ip6)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn net(s: &str) -> IpNetwork {
+        IpNetwork::from_str(s).unwrap()
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        IpAddr::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn longest_match() {
+        let mut trie = IpNetworkTrie::default();
+        assert!(trie.insert(net("10.0.0.0/8"), 1).is_none());
+        assert!(trie.insert(net("10.1.2.0/24"), 3).is_none());
+        assert!(trie.insert(net("10.1.0.0/16"), 2).is_none());
+        assert!(trie.insert(net("10.1.2.128/25"), 4).is_none());
+        assert!(trie.insert(net("10.1.3.1/32"), 5).is_none());
+        assert_eq!(trie.insert(net("10.1.0.0/16"), 6), Some(2));
+
+        assert_eq!(trie.longest_match(ip("10.2.0.1")), Some(&1));
+        assert_eq!(trie.longest_match(ip("10.1.1.1")), Some(&6));
+        assert_eq!(trie.longest_match(ip("10.1.2.1")), Some(&3));
+        assert_eq!(trie.longest_match(ip("10.1.2.200")), Some(&4));
+        assert_eq!(trie.longest_match(ip("10.1.3.1")), Some(&5));
+        assert_eq!(trie.longest_match(ip("10.1.3.2")), Some(&6));
+        assert_eq!(trie.longest_match(ip("11.0.0.1")), None);
+    }
+
+    #[test]
+    fn default_route() {
+        let trie = IpNetworkTrie::bulk_load([
+            (net("0.0.0.0/0"), 0),
+            (net("192.168.0.0/16"), 1),
+            (net("::/0"), 2),
+            (net("2001:db8::/32"), 3),
+            (net("2001:db8::1/128"), 4),
+        ]);
+
+        assert_eq!(trie.longest_match(ip("1.1.1.1")), Some(&0));
+        assert_eq!(trie.longest_match(ip("192.168.1.1")), Some(&1));
+        assert_eq!(trie.longest_match(ip("2001::1")), Some(&2));
+        assert_eq!(trie.longest_match(ip("2001:db8::2")), Some(&3));
+        assert_eq!(trie.longest_match(ip("2001:db8::1")), Some(&4));
+    }
+
+    #[test]
+    fn bulk_load() {
+        let mut all = Vec::new();
+        for i in 0..=255u8 {
+            for j in (0..=255u8).step_by(16) {
+                all.push((
+                    net(&format!("172.{i}.{j}.0/24")),
+                    (i as u32) << 8 | j as u32,
+                ));
+            }
+            all.push((net(&format!("172.{i}.0.0/16")), i as u32));
+        }
+        let trie = IpNetworkTrie::bulk_load(all);
+
+        assert_eq!(trie.longest_match(ip("172.3.16.1")), Some(&(3 << 8 | 16)));
+        assert_eq!(trie.longest_match(ip("172.3.17.1")), Some(&3));
+        assert_eq!(trie.longest_match(ip("173.0.0.1")), None);
+    }
+}
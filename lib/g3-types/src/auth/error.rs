@@ -63,4 +63,6 @@ pub enum AuthParseError {
     InvalidPassword,
     #[error("no delimiter found")]
     NoDelimiterFound,
+    #[error("invalid token")]
+    InvalidToken,
 }
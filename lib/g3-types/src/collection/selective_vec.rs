@@ -161,6 +161,21 @@ macro_rules! panic_on_empty {
 }
 
 impl<T: SelectiveItem> SelectiveVec<T> {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.inner.iter()
+    }
+
     pub fn pick_random(&self) -> &T {
         match self.inner.len() {
             0 => panic_on_empty!(),
@@ -19,10 +19,17 @@ use std::str::FromStr;
 use anyhow::anyhow;
 #[cfg(feature = "rustls")]
 use rustls_pki_types::ServerName;
+#[cfg(feature = "rustls")]
+use url::Url;
 
+#[cfg(feature = "quinn")]
+use crate::net::QuinnTransportConfigBuilder;
 #[cfg(feature = "rustls")]
 use crate::net::{RustlsClientConfig, RustlsClientConfigBuilder};
 
+#[cfg(feature = "rustls")]
+const DEFAULT_DNS_QUERY_PATH: &str = "/dns-query";
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum DnsEncryptionProtocol {
     Tls,
@@ -83,6 +90,11 @@ pub struct DnsEncryptionConfig {
     protocol: DnsEncryptionProtocol,
     tls_name: ServerName<'static>,
     tls_client: RustlsClientConfig,
+    query_path: Option<String>,
+    #[cfg(feature = "quinn")]
+    quic_transport: Option<QuinnTransportConfigBuilder>,
+    #[cfg(feature = "quic")]
+    enable_0rtt: bool,
 }
 
 #[cfg(feature = "rustls")]
@@ -101,6 +113,26 @@ impl DnsEncryptionConfig {
     pub fn tls_client(&self) -> &RustlsClientConfig {
         &self.tls_client
     }
+
+    /// the http path for DNS-over-HTTPS and DNS-over-HTTP/3 queries
+    #[inline]
+    pub fn query_path(&self) -> &str {
+        self.query_path.as_deref().unwrap_or(DEFAULT_DNS_QUERY_PATH)
+    }
+
+    /// the quic transport config for DNS-over-QUIC
+    #[cfg(feature = "quinn")]
+    #[inline]
+    pub fn quic_transport(&self) -> Option<&QuinnTransportConfigBuilder> {
+        self.quic_transport.as_ref()
+    }
+
+    /// whether to send queries in 0-RTT for DNS-over-QUIC and DNS-over-HTTP/3
+    #[cfg(feature = "quic")]
+    #[inline]
+    pub fn enable_0rtt(&self) -> bool {
+        self.enable_0rtt
+    }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -109,6 +141,11 @@ pub struct DnsEncryptionConfigBuilder {
     protocol: DnsEncryptionProtocol,
     tls_name: ServerName<'static>,
     tls_config: RustlsClientConfigBuilder,
+    query_path: Option<String>,
+    #[cfg(feature = "quinn")]
+    quic_transport: Option<QuinnTransportConfigBuilder>,
+    #[cfg(feature = "quic")]
+    enable_0rtt: bool,
 }
 
 #[cfg(feature = "rustls")]
@@ -118,7 +155,35 @@ impl DnsEncryptionConfigBuilder {
             protocol: DnsEncryptionProtocol::Tls,
             tls_name,
             tls_config: RustlsClientConfigBuilder::default(),
+            query_path: None,
+            #[cfg(feature = "quinn")]
+            quic_transport: None,
+            #[cfg(feature = "quic")]
+            enable_0rtt: false,
+        }
+    }
+
+    /// parse from url like `https://1.1.1.1/dns-query`,
+    /// the port in the url, if set, will also be returned
+    pub fn from_url(url: &Url) -> anyhow::Result<(Self, Option<u16>)> {
+        let protocol = DnsEncryptionProtocol::from_str(url.scheme())?;
+        let host = url
+            .host_str()
+            .ok_or_else(|| anyhow!("no host found in url"))?;
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        let tls_name = ServerName::try_from(host)
+            .map(|r| r.to_owned())
+            .map_err(|e| anyhow!("invalid tls server name {host}: {e}"))?;
+
+        let mut builder = DnsEncryptionConfigBuilder::new(tls_name);
+        builder.set_protocol(protocol);
+        match protocol {
+            DnsEncryptionProtocol::Https => builder.set_query_path(url.path())?,
+            #[cfg(feature = "quic")]
+            DnsEncryptionProtocol::H3 => builder.set_query_path(url.path())?,
+            _ => {}
         }
+        Ok((builder, url.port()))
     }
 
     pub fn set_protocol(&mut self, protocol: DnsEncryptionProtocol) {
@@ -143,6 +208,27 @@ impl DnsEncryptionConfigBuilder {
         self.tls_config = config_builder;
     }
 
+    pub fn set_query_path(&mut self, path: &str) -> anyhow::Result<()> {
+        if path.is_empty() || path == "/" {
+            self.query_path = None;
+        } else if path.starts_with('/') {
+            self.query_path = Some(path.to_string());
+        } else {
+            return Err(anyhow!("the query path should start with '/'"));
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "quinn")]
+    pub fn set_quic_transport(&mut self, config: QuinnTransportConfigBuilder) {
+        self.quic_transport = Some(config);
+    }
+
+    #[cfg(feature = "quic")]
+    pub fn set_enable_0rtt(&mut self, enable: bool) {
+        self.enable_0rtt = enable;
+    }
+
     pub fn summary(&self) -> String {
         match &self.tls_name {
             ServerName::DnsName(n) => format!("{}({})", self.protocol.as_str(), n.as_ref()),
@@ -167,6 +253,11 @@ impl DnsEncryptionConfigBuilder {
             protocol: self.protocol,
             tls_name: self.tls_name.clone(),
             tls_client,
+            query_path: self.query_path.clone(),
+            #[cfg(feature = "quinn")]
+            quic_transport: self.quic_transport.clone(),
+            #[cfg(feature = "quic")]
+            enable_0rtt: self.enable_0rtt,
         })
     }
 }
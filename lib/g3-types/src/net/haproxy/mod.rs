@@ -21,9 +21,14 @@ use std::str::FromStr;
 use anyhow::anyhow;
 use thiserror::Error;
 
+mod tlv;
 mod v1;
 mod v2;
 
+pub use tlv::{
+    ProxyProtocolTlv, ProxyProtocolTlvConfig, PP2_TYPE_ALPN, PP2_TYPE_AUTHORITY, PP2_TYPE_CRC32C,
+    PP2_TYPE_NETNS, PP2_TYPE_NOOP, PP2_TYPE_SSL, PP2_TYPE_UNIQUE_ID, PP2_UNIQUE_ID_MAX_LEN,
+};
 use v1::ProxyProtocolV1Encoder;
 pub use v2::ProxyProtocolV2Encoder;
 
@@ -81,4 +86,26 @@ impl ProxyProtocolEncoder {
             ProxyProtocolEncoder::V2(v2) => v2.encode_tcp(client_addr, server_addr),
         }
     }
+
+    /// Encode with extra TLVs, which will be ignored for PROXY protocol v1
+    pub fn encode_tcp_with_tlvs<'a, I>(
+        &mut self,
+        client_addr: SocketAddr,
+        server_addr: SocketAddr,
+        tlvs: I,
+    ) -> Result<&[u8], ProxyProtocolEncodeError>
+    where
+        I: IntoIterator<Item = &'a ProxyProtocolTlv>,
+    {
+        match self {
+            ProxyProtocolEncoder::V1(v1) => v1.encode_tcp(client_addr, server_addr),
+            ProxyProtocolEncoder::V2(v2) => {
+                v2.encode_tcp(client_addr, server_addr)?;
+                for tlv in tlvs {
+                    v2.push_tlv(tlv.kind(), tlv.value())?;
+                }
+                Ok(v2.finalize())
+            }
+        }
+    }
 }
@@ -0,0 +1,151 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+pub const PP2_TYPE_ALPN: u8 = 0x01;
+pub const PP2_TYPE_AUTHORITY: u8 = 0x02;
+pub const PP2_TYPE_CRC32C: u8 = 0x03;
+pub const PP2_TYPE_NOOP: u8 = 0x04;
+pub const PP2_TYPE_UNIQUE_ID: u8 = 0x05;
+pub const PP2_TYPE_SSL: u8 = 0x20;
+pub const PP2_TYPE_NETNS: u8 = 0x30;
+
+/// the max length of the value of PP2_TYPE_UNIQUE_ID
+pub const PP2_UNIQUE_ID_MAX_LEN: usize = 128;
+
+/// A Type-Length-Value entry in PROXY protocol v2 header
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProxyProtocolTlv {
+    kind: u8,
+    value: Vec<u8>,
+}
+
+impl ProxyProtocolTlv {
+    pub fn new(kind: u8, value: Vec<u8>) -> Self {
+        ProxyProtocolTlv { kind, value }
+    }
+
+    #[inline]
+    pub fn kind(&self) -> u8 {
+        self.kind
+    }
+
+    #[inline]
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+/// Config for the TLVs that should be added to the PROXY protocol v2 header sent to upstream
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProxyProtocolTlvConfig {
+    forward_types: Vec<u8>,
+    forward_all: bool,
+    custom: Vec<ProxyProtocolTlv>,
+    unique_id: bool,
+}
+
+impl ProxyProtocolTlvConfig {
+    /// forward all TLVs received from the client side
+    pub fn set_forward_all(&mut self) {
+        self.forward_all = true;
+        self.forward_types.clear();
+    }
+
+    /// forward the TLVs with the specified type received from the client side
+    pub fn add_forward_type(&mut self, kind: u8) {
+        if !self.forward_all && !self.forward_types.contains(&kind) {
+            self.forward_types.push(kind);
+        }
+    }
+
+    pub fn add_custom(&mut self, tlv: ProxyProtocolTlv) {
+        self.custom.push(tlv);
+    }
+
+    /// add a PP2_TYPE_UNIQUE_ID TLV for each new connection
+    pub fn set_unique_id(&mut self, enable: bool) {
+        self.unique_id = enable;
+    }
+
+    #[inline]
+    pub fn unique_id(&self) -> bool {
+        self.unique_id
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.forward_all
+            && self.forward_types.is_empty()
+            && self.custom.is_empty()
+            && !self.unique_id
+    }
+
+    #[inline]
+    pub fn custom_tlvs(&self) -> &[ProxyProtocolTlv] {
+        &self.custom
+    }
+
+    /// filter the TLVs received from the client side that should be forwarded
+    pub fn forward_tlvs<'a>(
+        &'a self,
+        received: &'a [ProxyProtocolTlv],
+    ) -> impl Iterator<Item = &'a ProxyProtocolTlv> {
+        received.iter().filter(|tlv| {
+            // these are hop-by-hop TLVs and should not be forwarded
+            if matches!(tlv.kind, PP2_TYPE_CRC32C | PP2_TYPE_NOOP) {
+                return false;
+            }
+            self.forward_all || self.forward_types.contains(&tlv.kind)
+        })
+    }
+
+    /// get all the TLVs that should be sent, the unique id TLV will be ignored if not enabled
+    pub fn tlvs<'a>(
+        &'a self,
+        received: &'a [ProxyProtocolTlv],
+        unique_id: Option<&'a ProxyProtocolTlv>,
+    ) -> impl Iterator<Item = &'a ProxyProtocolTlv> {
+        self.forward_tlvs(received)
+            .chain(self.custom.iter())
+            .chain(unique_id.filter(|_| self.unique_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forward_filter() {
+        let received = vec![
+            ProxyProtocolTlv::new(PP2_TYPE_AUTHORITY, b"example.net".to_vec()),
+            ProxyProtocolTlv::new(PP2_TYPE_NOOP, Vec::new()),
+            ProxyProtocolTlv::new(PP2_TYPE_UNIQUE_ID, b"1234".to_vec()),
+        ];
+
+        let config = ProxyProtocolTlvConfig::default();
+        assert!(config.is_empty());
+        assert_eq!(config.forward_tlvs(&received).count(), 0);
+
+        let mut config = ProxyProtocolTlvConfig::default();
+        config.add_forward_type(PP2_TYPE_AUTHORITY);
+        let forwarded: Vec<_> = config.forward_tlvs(&received).collect();
+        assert_eq!(forwarded, vec![&received[0]]);
+
+        config.set_forward_all();
+        let forwarded: Vec<_> = config.forward_tlvs(&received).collect();
+        assert_eq!(forwarded, vec![&received[0], &received[2]]);
+    }
+}
@@ -0,0 +1,43 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::FromStr;
+
+use crate::auth::AuthParseError;
+
+pub struct HttpBearerAuth {
+    pub token: String,
+}
+
+impl FromStr for HttpBearerAuth {
+    type Err = AuthParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let token = s.trim();
+        // token68 as described in RFC 7235
+        let data = token.trim_end_matches('=');
+        if data.is_empty()
+            || !data
+                .bytes()
+                .all(|c| c.is_ascii_alphanumeric() || b"-._~+/".contains(&c))
+        {
+            return Err(AuthParseError::InvalidToken);
+        }
+        Ok(HttpBearerAuth {
+            token: token.to_string(),
+        })
+    }
+}
@@ -24,9 +24,17 @@ use crate::net::HttpHeaderValue;
 mod basic;
 pub use basic::HttpBasicAuth;
 
+mod negotiate;
+pub use negotiate::HttpNegotiateAuth;
+
+mod bearer;
+pub use bearer::HttpBearerAuth;
+
 pub enum HttpAuth {
     None,
     Basic(HttpBasicAuth),
+    Negotiate(HttpNegotiateAuth),
+    Bearer(HttpBearerAuth),
 }
 
 impl HttpAuth {
@@ -37,6 +45,14 @@ impl HttpAuth {
                     let basic = HttpBasicAuth::from_str(&value[i + 1..])?;
                     Ok(HttpAuth::Basic(basic))
                 }
+                "negotiate" => {
+                    let negotiate = HttpNegotiateAuth::from_str(&value[i + 1..])?;
+                    Ok(HttpAuth::Negotiate(negotiate))
+                }
+                "bearer" => {
+                    let bearer = HttpBearerAuth::from_str(&value[i + 1..])?;
+                    Ok(HttpAuth::Bearer(bearer))
+                }
                 _ => Ok(HttpAuth::None),
             },
             None => Err(AuthParseError::UnsupportedAuthType),
@@ -98,6 +114,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_negotiate() {
+        let value = "Negotiate YIIBhgYGKwYBBQUC";
+        let info = HttpAuth::from_authorization(value).unwrap();
+        let HttpAuth::Negotiate(negotiate) = info else {
+            panic!("not negotiate auth");
+        };
+        assert_eq!(negotiate.token[0], 0x60);
+        assert_eq!(negotiate.token.len(), 12);
+    }
+
+    #[test]
+    fn parse_bearer() {
+        let value = "Bearer eyJhbGciOiJSUzI1NiJ9.eyJzdWIiOiJ0ZXN0In0.c2ln";
+        let info = HttpAuth::from_authorization(value).unwrap();
+        let HttpAuth::Bearer(bearer) = info else {
+            panic!("not bearer auth");
+        };
+        assert_eq!(
+            bearer.token,
+            "eyJhbGciOiJSUzI1NiJ9.eyJzdWIiOiJ0ZXN0In0.c2ln"
+        );
+
+        let value = "Bearer abc def";
+        assert!(HttpAuth::from_authorization(value).is_err());
+    }
+
     #[test]
     fn parse_scheme_only() {
         let value = "Basic ";
@@ -0,0 +1,39 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::FromStr;
+
+use base64::prelude::*;
+
+use crate::auth::AuthParseError;
+
+pub struct HttpNegotiateAuth {
+    pub token: Vec<u8>,
+}
+
+impl FromStr for HttpNegotiateAuth {
+    type Err = AuthParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let token = BASE64_STANDARD
+            .decode(s.trim())
+            .map_err(|_| AuthParseError::InvalidBase64Encoding)?;
+        if token.is_empty() {
+            return Err(AuthParseError::InvalidBase64Encoding);
+        }
+        Ok(HttpNegotiateAuth { token })
+    }
+}
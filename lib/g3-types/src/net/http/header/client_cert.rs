@@ -0,0 +1,155 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::str::FromStr;
+
+use http::HeaderName;
+
+use super::{HttpHeaderMap, HttpHeaderValue};
+
+pub const X_FORWARDED_CLIENT_CERT: HeaderName = HeaderName::from_static("x-forwarded-client-cert");
+
+/// How to handle the X-Forwarded-Client-Cert header in the requests sent to upstream
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq)]
+pub enum HttpForwardClientCertMode {
+    /// forward the header sent by the client without any change
+    ForwardOnly,
+    /// always remove the header
+    Sanitize,
+    /// remove the header sent by the client, and set it if a client cert is present
+    #[default]
+    SanitizeSet,
+    /// append to the header sent by the client if a client cert is present
+    AppendForward,
+}
+
+impl HttpForwardClientCertMode {
+    /// Update the headers with the XFCC element built from the verified client certificate
+    pub fn update_headers(&self, headers: &mut HttpHeaderMap, element: Option<&str>) {
+        match self {
+            HttpForwardClientCertMode::ForwardOnly => {}
+            HttpForwardClientCertMode::Sanitize => {
+                headers.remove(X_FORWARDED_CLIENT_CERT);
+            }
+            HttpForwardClientCertMode::SanitizeSet => {
+                headers.remove(X_FORWARDED_CLIENT_CERT);
+                if let Some(element) = element {
+                    if let Ok(value) = HttpHeaderValue::from_str(element) {
+                        headers.insert(X_FORWARDED_CLIENT_CERT, value);
+                    }
+                }
+            }
+            HttpForwardClientCertMode::AppendForward => {
+                let Some(element) = element else {
+                    return;
+                };
+                let mut s = String::new();
+                for v in headers.get_all(X_FORWARDED_CLIENT_CERT) {
+                    s.push_str(v.to_str());
+                    s.push(',');
+                }
+                s.push_str(element);
+                if let Ok(value) = HttpHeaderValue::from_str(&s) {
+                    headers.insert(X_FORWARDED_CLIENT_CERT, value);
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for HttpForwardClientCertMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "forward_only" | "forward" => Ok(HttpForwardClientCertMode::ForwardOnly),
+            "sanitize" | "remove" => Ok(HttpForwardClientCertMode::Sanitize),
+            "sanitize_set" | "set" => Ok(HttpForwardClientCertMode::SanitizeSet),
+            "append_forward" | "append" => Ok(HttpForwardClientCertMode::AppendForward),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(headers: &HttpHeaderMap) -> Vec<&str> {
+        headers
+            .get_all(X_FORWARDED_CLIENT_CERT)
+            .iter()
+            .map(|v| v.to_str())
+            .collect()
+    }
+
+    #[test]
+    fn update() {
+        let mut headers = HttpHeaderMap::default();
+        headers.append(
+            X_FORWARDED_CLIENT_CERT,
+            HttpHeaderValue::from_static("Hash=a"),
+        );
+
+        HttpForwardClientCertMode::ForwardOnly.update_headers(&mut headers, Some("Hash=b"));
+        assert_eq!(values(&headers), ["Hash=a"]);
+
+        HttpForwardClientCertMode::AppendForward.update_headers(&mut headers, None);
+        assert_eq!(values(&headers), ["Hash=a"]);
+        HttpForwardClientCertMode::AppendForward.update_headers(&mut headers, Some("Hash=b"));
+        assert_eq!(values(&headers), ["Hash=a,Hash=b"]);
+
+        HttpForwardClientCertMode::SanitizeSet.update_headers(&mut headers, Some("Hash=c"));
+        assert_eq!(values(&headers), ["Hash=c"]);
+
+        HttpForwardClientCertMode::Sanitize.update_headers(&mut headers, Some("Hash=d"));
+        assert!(values(&headers).is_empty());
+
+        HttpForwardClientCertMode::SanitizeSet.update_headers(&mut headers, None);
+        assert!(values(&headers).is_empty());
+    }
+
+    #[test]
+    fn strip_client_supplied() {
+        let mut headers = HttpHeaderMap::default();
+        headers.append(
+            X_FORWARDED_CLIENT_CERT,
+            HttpHeaderValue::from_static("Hash=forged"),
+        );
+        HttpForwardClientCertMode::default().update_headers(&mut headers, None);
+        assert!(values(&headers).is_empty());
+
+        headers.append(
+            X_FORWARDED_CLIENT_CERT,
+            HttpHeaderValue::from_static("Hash=forged"),
+        );
+        HttpForwardClientCertMode::default().update_headers(&mut headers, Some("Hash=verified"));
+        assert_eq!(values(&headers), ["Hash=verified"]);
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            HttpForwardClientCertMode::from_str("Sanitize_Set"),
+            Ok(HttpForwardClientCertMode::SanitizeSet)
+        );
+        assert_eq!(
+            HttpForwardClientCertMode::from_str("append"),
+            Ok(HttpForwardClientCertMode::AppendForward)
+        );
+        assert!(HttpForwardClientCertMode::from_str("always").is_err());
+    }
+}
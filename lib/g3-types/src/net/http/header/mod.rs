@@ -22,9 +22,12 @@ pub use map::HttpHeaderMap;
 pub use name::HttpOriginalHeaderName;
 pub use value::HttpHeaderValue;
 
+mod client_cert;
 mod forwarded;
 mod server_id;
 
+pub use client_cert::{HttpForwardClientCertMode, X_FORWARDED_CLIENT_CERT};
+
 pub use forwarded::{
     HttpForwardedHeaderType, HttpForwardedHeaderValue, HttpStandardForwardedHeaderValue,
 };
@@ -22,6 +22,7 @@ const DEFAULT_HTTP_KEEPALIVE_IDLE: u64 = 60;
 pub struct HttpKeepAliveConfig {
     enabled: bool,
     idle_expire: Duration,
+    max_lifetime: Option<Duration>,
 }
 
 impl Default for HttpKeepAliveConfig {
@@ -29,6 +30,7 @@ impl Default for HttpKeepAliveConfig {
         HttpKeepAliveConfig {
             enabled: true,
             idle_expire: Duration::from_secs(DEFAULT_HTTP_KEEPALIVE_IDLE),
+            max_lifetime: None,
         }
     }
 }
@@ -38,6 +40,7 @@ impl HttpKeepAliveConfig {
         HttpKeepAliveConfig {
             enabled: true,
             idle_expire,
+            max_lifetime: None,
         }
     }
 
@@ -63,13 +66,29 @@ impl HttpKeepAliveConfig {
         }
     }
 
+    pub fn set_max_lifetime(&mut self, max_lifetime: Duration) {
+        self.max_lifetime = Some(max_lifetime);
+    }
+
+    /// the max time a connection can be reused since its creation
+    #[inline]
+    pub fn max_lifetime(&self) -> Option<Duration> {
+        self.max_lifetime
+    }
+
     #[must_use]
     pub fn adjust_to(self, other: Self) -> Self {
         let idle_expire = self.idle_expire.min(other.idle_expire);
         let enabled = self.enabled && other.enabled; // only if both enabled
+        let max_lifetime = match (self.max_lifetime, other.max_lifetime) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (Some(a), None) => Some(a),
+            (None, b) => b,
+        };
         HttpKeepAliveConfig {
             enabled,
             idle_expire,
+            max_lifetime,
         }
     }
 }
@@ -20,7 +20,7 @@ mod header;
 mod keepalive;
 mod upgrade;
 
-pub use auth::{HttpAuth, HttpBasicAuth};
+pub use auth::{HttpAuth, HttpBasicAuth, HttpBearerAuth, HttpNegotiateAuth};
 pub use capability::*;
 pub use header::*;
 pub use keepalive::HttpKeepAliveConfig;
@@ -33,6 +33,8 @@ mod upstream;
 
 #[cfg(unix)]
 mod interface;
+#[cfg(unix)]
+mod unix;
 
 #[cfg(feature = "http")]
 mod http;
@@ -53,7 +55,10 @@ pub use dns::*;
 pub use egress::{EgressArea, EgressInfo};
 pub use error::ConnectError;
 pub use haproxy::{
-    ProxyProtocolEncodeError, ProxyProtocolEncoder, ProxyProtocolV2Encoder, ProxyProtocolVersion,
+    ProxyProtocolEncodeError, ProxyProtocolEncoder, ProxyProtocolTlv, ProxyProtocolTlvConfig,
+    ProxyProtocolV2Encoder, ProxyProtocolVersion, PP2_TYPE_ALPN, PP2_TYPE_AUTHORITY,
+    PP2_TYPE_CRC32C, PP2_TYPE_NETNS, PP2_TYPE_NOOP, PP2_TYPE_SSL, PP2_TYPE_UNIQUE_ID,
+    PP2_UNIQUE_ID_MAX_LEN,
 };
 pub use host::Host;
 pub use pool::ConnectionPoolConfig;
@@ -72,6 +77,8 @@ pub use upstream::{UpstreamAddr, UpstreamHostRef, WeightedUpstreamAddr};
 
 #[cfg(unix)]
 pub use interface::InterfaceName;
+#[cfg(unix)]
+pub use unix::UnixListenConfig;
 
 #[cfg(feature = "http")]
 pub use self::http::*;
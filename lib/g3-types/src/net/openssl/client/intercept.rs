@@ -23,8 +23,8 @@ use openssl::x509::store::X509StoreBuilder;
 use openssl::x509::X509;
 
 use super::{
-    OpensslClientSessionCache, OpensslSessionCacheConfig, DEFAULT_HANDSHAKE_TIMEOUT,
-    MINIMAL_HANDSHAKE_TIMEOUT,
+    OpensslClientSessionCache, OpensslSessionCacheConfig, OpensslSharedSessionCache,
+    DEFAULT_HANDSHAKE_TIMEOUT, MINIMAL_HANDSHAKE_TIMEOUT,
 };
 use crate::net::{TlsAlpn, TlsServerName, TlsVersion, UpstreamAddr};
 
@@ -292,7 +292,10 @@ impl OpensslInterceptionClientConfigBuilder {
     }
 
     #[cfg(feature = "boringssl")]
-    fn build_ssl_context(&self) -> anyhow::Result<ContextPair> {
+    fn build_ssl_context(
+        &self,
+        shared_session_cache: Option<&OpensslSharedSessionCache>,
+    ) -> anyhow::Result<ContextPair> {
         let mut ctx_builder = SslConnector::builder(SslMethod::tls_client())
             .map_err(|e| anyhow!("failed to create ssl context builder: {e}"))?;
 
@@ -327,7 +330,9 @@ impl OpensslInterceptionClientConfigBuilder {
 
         self.build_set_verify_cert_store(&mut ctx_builder)?;
 
-        let session_cache = self.session_cache.set_for_client(&mut ctx_builder)?;
+        let session_cache = self
+            .session_cache
+            .set_for_client(&mut ctx_builder, shared_session_cache)?;
 
         Ok(ContextPair {
             ssl_context: ctx_builder.build().into_context(),
@@ -336,7 +341,10 @@ impl OpensslInterceptionClientConfigBuilder {
     }
 
     #[cfg(not(feature = "boringssl"))]
-    fn build_ssl_context(&self) -> anyhow::Result<ContextPair> {
+    fn build_ssl_context(
+        &self,
+        shared_session_cache: Option<&OpensslSharedSessionCache>,
+    ) -> anyhow::Result<ContextPair> {
         use openssl::ssl::{SslCtValidationMode, StatusType};
 
         let mut ctx_builder = SslConnector::builder(SslMethod::tls_client())
@@ -370,7 +378,9 @@ impl OpensslInterceptionClientConfigBuilder {
 
         self.build_set_verify_cert_store(&mut ctx_builder)?;
 
-        let session_cache = self.session_cache.set_for_client(&mut ctx_builder)?;
+        let session_cache = self
+            .session_cache
+            .set_for_client(&mut ctx_builder, shared_session_cache)?;
 
         Ok(ContextPair {
             ssl_context: ctx_builder.build().into_context(),
@@ -410,7 +420,7 @@ impl OpensslInterceptionClientConfigBuilder {
 
         self.build_set_verify_cert_store(&mut ctx_builder)?;
 
-        let session_cache = self.session_cache.set_for_client(&mut ctx_builder)?;
+        let session_cache = self.session_cache.set_for_client(&mut ctx_builder, None)?;
 
         Ok(ContextPair {
             ssl_context: ctx_builder.build().into_context(),
@@ -418,9 +428,21 @@ impl OpensslInterceptionClientConfigBuilder {
         })
     }
 
+    /// Create a new session cache which can be shared between the built client configs
+    pub fn new_shared_session_cache(&self) -> Option<OpensslSharedSessionCache> {
+        self.session_cache.new_shared_cache()
+    }
+
     pub fn build(&self) -> anyhow::Result<OpensslInterceptionClientConfig> {
+        self.build_with_shared_session_cache(None)
+    }
+
+    pub fn build_with_shared_session_cache(
+        &self,
+        session_cache: Option<&OpensslSharedSessionCache>,
+    ) -> anyhow::Result<OpensslInterceptionClientConfig> {
         Ok(OpensslInterceptionClientConfig {
-            ssl_context_pair: self.build_ssl_context()?,
+            ssl_context_pair: self.build_ssl_context(session_cache)?,
             #[cfg(feature = "tongsuo")]
             tlcp_context_pair: self.build_tlcp_context()?,
             insecure: self.insecure,
@@ -39,6 +39,7 @@ mod intercept;
 pub use intercept::{OpensslInterceptionClientConfig, OpensslInterceptionClientConfigBuilder};
 
 mod session;
+pub use session::OpensslSharedSessionCache;
 use session::{OpensslClientSessionCache, OpensslSessionCacheConfig};
 
 const MINIMAL_HANDSHAKE_TIMEOUT: Duration = Duration::from_millis(100);
@@ -542,7 +543,7 @@ impl OpensslClientConfigBuilder {
             .set_verify_cert_store(store_builder.build())
             .map_err(|e| anyhow!("failed to set verify ca certs: {e}"))?;
 
-        let session_cache = self.session_cache.set_for_client(&mut ctx_builder)?;
+        let session_cache = self.session_cache.set_for_client(&mut ctx_builder, None)?;
 
         if let Some(protocols) = alpn_protocols {
             let mut len: usize = 0;
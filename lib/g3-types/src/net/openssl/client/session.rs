@@ -16,7 +16,7 @@
 
 use std::collections::VecDeque;
 use std::num::NonZeroUsize;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 use anyhow::anyhow;
 use lru::LruCache;
@@ -89,20 +89,43 @@ impl OpensslSessionCacheConfig {
         }
     }
 
+    pub(in crate::net::openssl) fn new_shared_cache(&self) -> Option<OpensslSharedSessionCache> {
+        let caches = match self.method {
+            OpensslSessionCacheMethod::ForMany => {
+                SessionCaches::for_many(self.sites_count, self.each_capacity.get())
+            }
+            OpensslSessionCacheMethod::ForOne => SessionCaches::for_one(self.each_capacity.get()),
+            OpensslSessionCacheMethod::Builtin | OpensslSessionCacheMethod::Off => return None,
+        };
+        Some(OpensslSharedSessionCache {
+            caches: Arc::new(caches),
+        })
+    }
+
     pub(in crate::net::openssl) fn set_for_client(
         &self,
         ctx_builder: &mut SslContextBuilder,
+        shared_cache: Option<&OpensslSharedSessionCache>,
     ) -> anyhow::Result<Option<OpensslClientSessionCache>> {
         match self.method {
             OpensslSessionCacheMethod::ForMany => {
                 let session_cache = OpensslClientSessionCache::new()?;
-                let caches = SessionCaches::for_many(self.sites_count, self.each_capacity.get());
+                let caches = match shared_cache {
+                    Some(shared) => shared.caches.clone(),
+                    None => Arc::new(SessionCaches::for_many(
+                        self.sites_count,
+                        self.each_capacity.get(),
+                    )),
+                };
                 session_cache.add_to_context(ctx_builder, caches);
                 Ok(Some(session_cache))
             }
             OpensslSessionCacheMethod::ForOne => {
                 let session_cache = OpensslClientSessionCache::new()?;
-                let caches = SessionCaches::for_one(self.each_capacity.get());
+                let caches = match shared_cache {
+                    Some(shared) => shared.caches.clone(),
+                    None => Arc::new(SessionCaches::for_one(self.each_capacity.get())),
+                };
                 session_cache.add_to_context(ctx_builder, caches);
                 Ok(Some(session_cache))
             }
@@ -181,9 +204,16 @@ impl SessionCaches {
     }
 }
 
+/// Client session caches that can be shared between multiple ssl contexts,
+/// so sessions will still be resumed after the contexts are rebuilt
+#[derive(Clone)]
+pub struct OpensslSharedSessionCache {
+    caches: Arc<SessionCaches>,
+}
+
 #[derive(Clone, Copy)]
 pub(in crate::net::openssl) struct OpensslClientSessionCache {
-    session_cache_index: Index<SslContext, SessionCaches>,
+    session_cache_index: Index<SslContext, Arc<SessionCaches>>,
     session_key_index: Index<Ssl, String>,
 }
 
@@ -197,14 +227,14 @@ impl OpensslClientSessionCache {
         })
     }
 
-    fn add_to_context(&self, ctx_builder: &mut SslContextBuilder, caches: SessionCaches) {
+    fn add_to_context(&self, ctx_builder: &mut SslContextBuilder, caches: Arc<SessionCaches>) {
         ctx_builder
             .set_session_cache_mode(SslSessionCacheMode::CLIENT | SslSessionCacheMode::NO_INTERNAL);
 
         let session_cache = *self;
         ctx_builder.set_new_session_callback(move |ssl, session| {
             if let Some(caches) = ssl.ssl_context().ex_data(session_cache.session_cache_index) {
-                match caches {
+                match caches.as_ref() {
                     SessionCaches::One(m) => m.lock().unwrap().push(session),
                     SessionCaches::Many(m) => {
                         if let Some(key) = ssl.ex_data(session_cache.session_key_index) {
@@ -228,7 +258,7 @@ impl OpensslClientSessionCache {
         port: u16,
     ) -> anyhow::Result<()> {
         if let Some(caches) = ssl.ssl_context().ex_data(self.session_cache_index) {
-            let session = match caches {
+            let session = match caches.as_ref() {
                 SessionCaches::One(m) => {
                     let mut o = m.lock().unwrap();
                     o.pop()
@@ -0,0 +1,225 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::fmt::Write;
+
+use anyhow::anyhow;
+use openssl::hash::MessageDigest;
+use openssl::x509::{X509NameRef, X509Ref, X509};
+
+/// The identity parsed from a verified peer certificate
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OpensslCertIdentity {
+    subject: String,
+    fingerprint: String,
+    dns_names: Vec<String>,
+    uris: Vec<String>,
+}
+
+impl OpensslCertIdentity {
+    pub fn from_der(der: &[u8]) -> anyhow::Result<Self> {
+        let cert =
+            X509::from_der(der).map_err(|e| anyhow!("invalid DER encoded certificate: {e}"))?;
+        Self::from_x509(&cert)
+    }
+
+    pub fn from_x509(cert: &X509Ref) -> anyhow::Result<Self> {
+        let digest = cert
+            .digest(MessageDigest::sha256())
+            .map_err(|e| anyhow!("failed to get sha256 digest of the certificate: {e}"))?;
+
+        let mut dns_names = Vec::new();
+        let mut uris = Vec::new();
+        if let Some(names) = cert.subject_alt_names() {
+            for name in names {
+                if let Some(dns) = name.dnsname() {
+                    dns_names.push(dns.to_string());
+                } else if let Some(uri) = name.uri() {
+                    uris.push(uri.to_string());
+                }
+            }
+        }
+
+        Ok(OpensslCertIdentity {
+            subject: rfc2253_name(cert.subject_name()),
+            fingerprint: hex::encode(digest),
+            dns_names,
+            uris,
+        })
+    }
+
+    /// The subject distinguished name in RFC 2253 format
+    #[inline]
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    /// The lower case hex encoded SHA-256 digest of the DER encoded certificate
+    #[inline]
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    #[inline]
+    pub fn dns_names(&self) -> &[String] {
+        &self.dns_names
+    }
+
+    #[inline]
+    pub fn uris(&self) -> &[String] {
+        &self.uris
+    }
+
+    /// Build an element for the X-Forwarded-Client-Cert header
+    pub fn xfcc_element(&self) -> String {
+        let mut s = String::with_capacity(128);
+        let _ = write!(s, "Hash={}", self.fingerprint);
+        s.push_str(";Subject=");
+        push_quoted(&mut s, &self.subject);
+        for uri in &self.uris {
+            s.push_str(";URI=");
+            push_xfcc_value(&mut s, uri);
+        }
+        for dns in &self.dns_names {
+            s.push_str(";DNS=");
+            push_xfcc_value(&mut s, dns);
+        }
+        s
+    }
+}
+
+fn rfc2253_name(name: &X509NameRef) -> String {
+    let mut s = String::new();
+    // RFC 2253 starts with the last RDN
+    let entries: Vec<_> = name.entries().collect();
+    for entry in entries.into_iter().rev() {
+        let Ok(value) = entry.data().as_utf8() else {
+            continue;
+        };
+        if !s.is_empty() {
+            s.push(',');
+        }
+        let nid = entry.object().nid();
+        match nid.short_name() {
+            Ok(n) => s.push_str(n),
+            Err(_) => {
+                let _ = write!(s, "{}", entry.object());
+            }
+        }
+        s.push('=');
+        let value: &str = &value;
+        let last = value.chars().count().saturating_sub(1);
+        for (i, c) in value.chars().enumerate() {
+            match c {
+                ',' | '+' | '"' | '\\' | '<' | '>' | ';' => s.push('\\'),
+                '#' if i == 0 => s.push('\\'),
+                ' ' if i == 0 || i == last => s.push('\\'),
+                _ => {}
+            }
+            s.push(c);
+        }
+    }
+    s
+}
+
+fn push_quoted(s: &mut String, value: &str) {
+    s.push('"');
+    for c in value.chars() {
+        if matches!(c, '"' | '\\') {
+            s.push('\\');
+        }
+        s.push(c);
+    }
+    s.push('"');
+}
+
+fn push_xfcc_value(s: &mut String, value: &str) {
+    if value.contains([',', ';', '=', '"']) {
+        push_quoted(s, value);
+    } else {
+        s.push_str(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::asn1::Asn1Time;
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::nid::Nid;
+    use openssl::pkey::PKey;
+    use openssl::x509::extension::SubjectAlternativeName;
+    use openssl::x509::{X509Builder, X509NameBuilder};
+
+    fn build_cert() -> X509 {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_nid(Nid::ORGANIZATIONNAME, "Example, Inc")
+            .unwrap();
+        name.append_entry_by_nid(Nid::COMMONNAME, "client").unwrap();
+        let name = name.build();
+
+        let mut builder = X509Builder::new().unwrap();
+        builder.set_version(2).unwrap();
+        builder.set_subject_name(&name).unwrap();
+        builder.set_issuer_name(&name).unwrap();
+        builder.set_pubkey(&key).unwrap();
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).unwrap())
+            .unwrap();
+        let san = SubjectAlternativeName::new()
+            .dns("client.example.net")
+            .uri("spiffe://example.net/client")
+            .build(&builder.x509v3_context(None, None))
+            .unwrap();
+        builder.append_extension(san).unwrap();
+        builder.sign(&key, MessageDigest::sha256()).unwrap();
+        builder.build()
+    }
+
+    #[test]
+    fn parse() {
+        let cert = build_cert();
+        let der = cert.to_der().unwrap();
+        let identity = OpensslCertIdentity::from_der(&der).unwrap();
+
+        assert_eq!(identity.subject(), "CN=client,O=Example\\, Inc");
+        assert_eq!(identity.dns_names(), &["client.example.net".to_string()]);
+        assert_eq!(
+            identity.uris(),
+            &["spiffe://example.net/client".to_string()]
+        );
+        let digest = openssl::hash::hash(MessageDigest::sha256(), &der).unwrap();
+        assert_eq!(identity.fingerprint(), hex::encode(digest));
+
+        assert!(OpensslCertIdentity::from_der(b"not a cert").is_err());
+    }
+
+    #[test]
+    fn xfcc() {
+        let identity = OpensslCertIdentity::from_x509(&build_cert()).unwrap();
+        let expected = format!(
+            "Hash={};Subject=\"CN=client,O=Example\\\\, Inc\";URI=spiffe://example.net/client;DNS=client.example.net",
+            identity.fingerprint()
+        );
+        assert_eq!(identity.xfcc_element(), expected);
+    }
+}
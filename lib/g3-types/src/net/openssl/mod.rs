@@ -17,7 +17,7 @@
 mod client;
 pub use client::{
     OpensslClientConfig, OpensslClientConfigBuilder, OpensslInterceptionClientConfig,
-    OpensslInterceptionClientConfigBuilder,
+    OpensslInterceptionClientConfigBuilder, OpensslSharedSessionCache,
 };
 
 mod server;
@@ -30,6 +30,12 @@ pub use server::{
 mod cert_pair;
 pub use cert_pair::OpensslCertificatePair;
 
+mod pin;
+pub use pin::OpensslSpkiPinSet;
+
+mod identity;
+pub use identity::OpensslCertIdentity;
+
 #[cfg(feature = "tongsuo")]
 mod tlcp_cert_pair;
 #[cfg(feature = "tongsuo")]
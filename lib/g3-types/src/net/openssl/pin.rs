@@ -0,0 +1,104 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::anyhow;
+use openssl::hash::MessageDigest;
+use openssl::x509::X509Ref;
+
+const SPKI_PIN_LEN: usize = 32;
+
+/// A set of SHA-256 digests of the DER encoded SubjectPublicKeyInfo of the allowed peer certificates
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct OpensslSpkiPinSet {
+    pins: Vec<[u8; SPKI_PIN_LEN]>,
+}
+
+impl OpensslSpkiPinSet {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pins.is_empty()
+    }
+
+    pub fn add_hex(&mut self, s: &str) -> anyhow::Result<()> {
+        let s = s.trim().replace(':', "");
+        let mut pin = [0u8; SPKI_PIN_LEN];
+        hex::decode_to_slice(&s, &mut pin)
+            .map_err(|e| anyhow!("invalid hex encoded sha256 digest {s}: {e}"))?;
+        if !self.pins.contains(&pin) {
+            self.pins.push(pin);
+        }
+        Ok(())
+    }
+
+    pub fn add_cert(&mut self, cert: &X509Ref) -> anyhow::Result<()> {
+        let pin = spki_sha256(cert)?;
+        if !self.pins.contains(&pin) {
+            self.pins.push(pin);
+        }
+        Ok(())
+    }
+
+    /// Check the peer certificate against the pins, always pass if no pin is set
+    pub fn verify(&self, cert: Option<&X509Ref>) -> anyhow::Result<()> {
+        if self.pins.is_empty() {
+            return Ok(());
+        }
+        let cert = cert.ok_or_else(|| anyhow!("no peer certificate found"))?;
+        let pin = spki_sha256(cert)?;
+        if self.pins.contains(&pin) {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "peer public key sha256 digest {} is not pinned",
+                hex::encode(pin)
+            ))
+        }
+    }
+}
+
+fn spki_sha256(cert: &X509Ref) -> anyhow::Result<[u8; SPKI_PIN_LEN]> {
+    let key = cert
+        .public_key()
+        .map_err(|e| anyhow!("failed to get public key from certificate: {e}"))?;
+    let der = key
+        .public_key_to_der()
+        .map_err(|e| anyhow!("failed to encode public key: {e}"))?;
+    let digest = openssl::hash::hash(MessageDigest::sha256(), &der)
+        .map_err(|e| anyhow!("failed to get sha256 digest of public key: {e}"))?;
+    let mut pin = [0u8; SPKI_PIN_LEN];
+    pin.copy_from_slice(&digest);
+    Ok(pin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_hex() {
+        let mut set = OpensslSpkiPinSet::default();
+        assert!(set.is_empty());
+        set.add_hex("5e8f16062ea3cd2c4a0d547876baa6f38cabf625a2b7a4bf63c6b8e2d4bc3d8a")
+            .unwrap();
+        set.add_hex("5E:8F:16:06:2E:A3:CD:2C:4A:0D:54:78:76:BA:A6:F3:8C:AB:F6:25:A2:B7:A4:BF:63:C6:B8:E2:D4:BC:3D:8A")
+            .unwrap();
+        assert_eq!(set.pins.len(), 1);
+
+        assert!(set.add_hex("5e8f16").is_err());
+        assert!(set.add_hex("not a hex string").is_err());
+        assert!(set.verify(None).is_err());
+    }
+}
@@ -141,6 +141,8 @@ fn build_tls_context(
     #[cfg(feature = "boringssl")]
     set_select_certificate_callback(&mut builder, retry_index, sni_index, alpn_index);
     set_alpn_select_callback(&mut builder, alpn_name_index);
+    #[cfg(not(feature = "boringssl"))]
+    set_status_callback(&mut builder)?;
 
     Ok(builder)
 }
@@ -157,6 +159,7 @@ fn build_tls_context(
 
     set_client_hello_callback(&mut builder, retry_index, sni_index, alpn_index);
     set_alpn_select_callback(&mut builder, alpn_name_index);
+    set_status_callback(&mut builder)?;
 
     Ok(builder)
 }
@@ -177,6 +180,15 @@ fn build_tlcp_context(
     Ok(builder)
 }
 
+/// Send the OCSP response if it has been set along with the fake certificate.
+/// This is not needed for BoringSSL, as the stapled response will always be sent.
+#[cfg(not(feature = "boringssl"))]
+fn set_status_callback(builder: &mut SslAcceptorBuilder) -> anyhow::Result<()> {
+    builder
+        .set_status_callback(|ssl| Ok(ssl.ocsp_status().is_some()))
+        .map_err(|e| anyhow!("failed to set ocsp status callback: {e}"))
+}
+
 #[cfg(not(feature = "boringssl"))]
 fn set_client_hello_callback(
     builder: &mut SslAcceptorBuilder,
@@ -68,6 +68,22 @@ impl OpensslTicketKeyBuilder {
         }
     }
 
+    pub fn new_random(lifetime: u32) -> anyhow::Result<Self> {
+        let mut builder = OpensslTicketKeyBuilder::new(lifetime);
+        rand::rand_bytes(&mut builder.name)
+            .map_err(|e| anyhow!("failed to generate random key name: {e}"))?;
+        rand::rand_bytes(&mut builder.aes_key)
+            .map_err(|e| anyhow!("failed to generate random AES key: {e}"))?;
+        rand::rand_bytes(&mut builder.hmac_key)
+            .map_err(|e| anyhow!("failed to generate random HMAC key: {e}"))?;
+        Ok(builder)
+    }
+
+    #[inline]
+    pub fn lifetime(&self) -> u32 {
+        self.lifetime
+    }
+
     #[inline]
     pub fn set_lifetime(&mut self, lifetime: u32) {
         self.lifetime = lifetime;
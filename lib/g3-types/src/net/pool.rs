@@ -22,6 +22,7 @@ pub struct ConnectionPoolConfig {
     max_idle_count: usize,
     min_idle_count: usize,
     idle_timeout: Duration,
+    max_lifetime: Option<Duration>,
 }
 
 impl Default for ConnectionPoolConfig {
@@ -37,6 +38,7 @@ impl ConnectionPoolConfig {
             max_idle_count: max_idle,
             min_idle_count: min_idle,
             idle_timeout: Duration::from_secs(300),
+            max_lifetime: None,
         }
     }
 
@@ -79,4 +81,14 @@ impl ConnectionPoolConfig {
     pub fn idle_timeout(&self) -> Duration {
         self.idle_timeout
     }
+
+    #[inline]
+    pub fn set_max_lifetime(&mut self, lifetime: Duration) {
+        self.max_lifetime = Some(lifetime);
+    }
+
+    #[inline]
+    pub fn max_lifetime(&self) -> Option<Duration> {
+        self.max_lifetime
+    }
 }
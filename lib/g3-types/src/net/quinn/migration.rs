@@ -0,0 +1,79 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::net::{SocketAddrV4, SocketAddrV6};
+use std::time::Duration;
+
+use quinn::ServerConfig;
+
+/// Server side connection migration config
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuinnMigrationConfig {
+    enable: bool,
+    preferred_address_v4: Option<SocketAddrV4>,
+    preferred_address_v6: Option<SocketAddrV6>,
+    watch_interval: Option<Duration>,
+}
+
+impl Default for QuinnMigrationConfig {
+    fn default() -> Self {
+        QuinnMigrationConfig {
+            enable: true,
+            preferred_address_v4: None,
+            preferred_address_v6: None,
+            watch_interval: Some(Duration::from_secs(1)),
+        }
+    }
+}
+
+impl QuinnMigrationConfig {
+    pub fn set_enable(&mut self, enable: bool) {
+        self.enable = enable;
+    }
+
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.enable
+    }
+
+    pub fn set_preferred_address_v4(&mut self, addr: SocketAddrV4) {
+        self.preferred_address_v4 = Some(addr);
+    }
+
+    pub fn set_preferred_address_v6(&mut self, addr: SocketAddrV6) {
+        self.preferred_address_v6 = Some(addr);
+    }
+
+    /// Set the interval to check the client address change, None to disable it
+    pub fn set_watch_interval(&mut self, interval: Option<Duration>) {
+        self.watch_interval = interval.filter(|v| !v.is_zero());
+    }
+
+    /// Get the interval to check the client address change
+    pub fn watch_interval(&self) -> Option<Duration> {
+        if self.enable {
+            self.watch_interval
+        } else {
+            None
+        }
+    }
+
+    pub fn apply_to_server_config(&self, config: &mut ServerConfig) {
+        config.migration(self.enable);
+        config.preferred_address_v4(self.preferred_address_v4);
+        config.preferred_address_v6(self.preferred_address_v6);
+    }
+}
@@ -15,4 +15,7 @@
  */
 
 mod transport;
-pub use transport::QuinnTransportConfigBuilder;
+pub use transport::{QuinnCongestionControl, QuinnTransportConfigBuilder};
+
+mod migration;
+pub use migration::QuinnMigrationConfig;
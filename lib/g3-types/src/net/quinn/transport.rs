@@ -14,10 +14,34 @@
  * limitations under the License.
  */
 
+use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 
+use anyhow::anyhow;
+use quinn::congestion::{BbrConfig, CubicConfig, NewRenoConfig};
 use quinn::{IdleTimeout, TransportConfig, VarInt};
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QuinnCongestionControl {
+    Cubic,
+    NewReno,
+    Bbr,
+}
+
+impl FromStr for QuinnCongestionControl {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "cubic" => Ok(QuinnCongestionControl::Cubic),
+            "new_reno" | "newreno" | "reno" => Ok(QuinnCongestionControl::NewReno),
+            "bbr" => Ok(QuinnCongestionControl::Bbr),
+            _ => Err(anyhow!("unsupported congestion control algorithm {s}")),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct QuinnTransportConfigBuilder {
     max_idle_timeout: IdleTimeout,
@@ -25,6 +49,7 @@ pub struct QuinnTransportConfigBuilder {
     stream_receive_window: Option<VarInt>,
     receive_window: Option<VarInt>,
     send_window: Option<u64>,
+    congestion_control: Option<QuinnCongestionControl>,
 }
 
 impl Default for QuinnTransportConfigBuilder {
@@ -35,6 +60,7 @@ impl Default for QuinnTransportConfigBuilder {
             stream_receive_window: None,
             receive_window: None,
             send_window: None,
+            congestion_control: None,
         }
     }
 }
@@ -61,6 +87,10 @@ impl QuinnTransportConfigBuilder {
         self.send_window = Some(size as u64);
     }
 
+    pub fn set_congestion_control(&mut self, cc: QuinnCongestionControl) {
+        self.congestion_control = Some(cc);
+    }
+
     pub fn build_for_client(&self) -> TransportConfig {
         let mut config = TransportConfig::default();
         config
@@ -77,6 +107,18 @@ impl QuinnTransportConfigBuilder {
         if let Some(v) = self.send_window {
             config.send_window(v);
         }
+        match self.congestion_control {
+            Some(QuinnCongestionControl::Cubic) => {
+                config.congestion_controller_factory(Arc::new(CubicConfig::default()));
+            }
+            Some(QuinnCongestionControl::NewReno) => {
+                config.congestion_controller_factory(Arc::new(NewRenoConfig::default()));
+            }
+            Some(QuinnCongestionControl::Bbr) => {
+                config.congestion_controller_factory(Arc::new(BbrConfig::default()));
+            }
+            None => {}
+        }
         config
     }
 }
@@ -48,6 +48,7 @@ pub struct RustlsQuicServerConfig {
 pub struct RustlsServerConfigBuilder {
     cert_pairs: Vec<RustlsCertificatePair>,
     client_auth: bool,
+    client_auth_optional: bool,
     client_auth_certs: Option<Vec<CertificateDer<'static>>>,
     use_session_ticket: bool,
     no_session_cache: bool,
@@ -59,6 +60,7 @@ impl RustlsServerConfigBuilder {
         RustlsServerConfigBuilder {
             cert_pairs: Vec::with_capacity(1),
             client_auth: false,
+            client_auth_optional: false,
             client_auth_certs: None,
             use_session_ticket: true,
             no_session_cache: false,
@@ -90,6 +92,11 @@ impl RustlsServerConfigBuilder {
         self.client_auth = true;
     }
 
+    /// Also accept the clients that present no certificate, only works if client auth is enabled
+    pub fn set_client_auth_optional(&mut self, optional: bool) {
+        self.client_auth_optional = optional;
+    }
+
     pub fn set_client_auth_certificates(&mut self, certs: Vec<CertificateDer<'static>>) {
         self.client_auth_certs = Some(certs);
     }
@@ -133,7 +140,11 @@ impl RustlsServerConfigBuilder {
                     })?;
                 }
             };
-            let client_verifier = WebPkiClientVerifier::builder(Arc::new(root_store))
+            let mut verifier_builder = WebPkiClientVerifier::builder(Arc::new(root_store));
+            if self.client_auth_optional {
+                verifier_builder = verifier_builder.allow_unauthenticated();
+            }
+            let client_verifier = verifier_builder
                 .build()
                 .map_err(|e| anyhow!("failed to build client cert verifier: {e}"))?;
             config_builder.with_client_cert_verifier(client_verifier)
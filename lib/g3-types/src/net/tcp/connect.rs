@@ -126,3 +126,52 @@ impl HappyEyeballsConfig {
         }
     }
 }
+
+/// Race the connection to the top 2 resolved addresses and use the first established one
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TcpConnectRaceConfig {
+    enable: bool,
+    resolution_delay: Duration,
+    stagger: Duration,
+}
+
+impl Default for TcpConnectRaceConfig {
+    fn default() -> Self {
+        TcpConnectRaceConfig {
+            enable: false,
+            resolution_delay: Duration::from_millis(50),
+            stagger: Duration::from_millis(20),
+        }
+    }
+}
+
+impl TcpConnectRaceConfig {
+    pub fn set_enable(&mut self, enable: bool) {
+        self.enable = enable;
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enable
+    }
+
+    /// Set the time to wait for the resolution of the other address family
+    pub fn set_resolution_delay(&mut self, delay: Duration) {
+        self.resolution_delay = delay.min(Duration::from_secs(1));
+    }
+
+    #[inline]
+    pub fn resolution_delay(&self) -> Duration {
+        self.resolution_delay
+    }
+
+    /// Set the delay before starting the second connection
+    pub fn set_stagger(&mut self, stagger: Duration) {
+        self.stagger = stagger.min(Duration::from_secs(1));
+    }
+
+    #[inline]
+    pub fn stagger(&self) -> Duration {
+        self.stagger
+    }
+}
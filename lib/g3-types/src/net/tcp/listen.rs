@@ -30,6 +30,8 @@ pub struct TcpListenConfig {
     transparent: bool,
     #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
     mark: Option<u32>,
+    #[cfg(target_os = "freebsd")]
+    accept_filter: Option<String>,
     backlog: u32,
     instance: usize,
     scale: usize,
@@ -50,6 +52,8 @@ impl TcpListenConfig {
             transparent: false,
             #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
             mark: None,
+            #[cfg(target_os = "freebsd")]
+            accept_filter: None,
             backlog: DEFAULT_LISTEN_BACKLOG,
             instance: 1,
             scale: 0,
@@ -86,6 +90,12 @@ impl TcpListenConfig {
         self.mark
     }
 
+    #[cfg(target_os = "freebsd")]
+    #[inline]
+    pub fn accept_filter(&self) -> Option<&str> {
+        self.accept_filter.as_deref()
+    }
+
     #[inline]
     pub fn backlog(&self) -> u32 {
         self.backlog
@@ -123,6 +133,16 @@ impl TcpListenConfig {
         self.mark = Some(mark);
     }
 
+    #[cfg(target_os = "freebsd")]
+    pub fn set_accept_filter(&mut self, name: &str) -> anyhow::Result<()> {
+        // the size of af_name in struct accept_filter_arg is 16, including the tailing NUL
+        if name.is_empty() || name.len() >= 16 {
+            return Err(anyhow!("invalid accept filter name {name}"));
+        }
+        self.accept_filter = Some(name.to_string());
+        Ok(())
+    }
+
     #[inline]
     pub fn set_backlog(&mut self, backlog: u32) {
         if backlog >= MINIMAL_LISTEN_BACKLOG {
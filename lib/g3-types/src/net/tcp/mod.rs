@@ -19,7 +19,7 @@ mod keepalive;
 mod listen;
 mod sockopt;
 
-pub use connect::{HappyEyeballsConfig, TcpConnectConfig};
+pub use connect::{HappyEyeballsConfig, TcpConnectConfig, TcpConnectRaceConfig};
 pub use listen::TcpListenConfig;
 
 pub use keepalive::TcpKeepAliveConfig;
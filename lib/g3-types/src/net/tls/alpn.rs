@@ -34,6 +34,7 @@ pub enum AlpnProtocol {
     Mqtt,
     DnsOverTls,
     DnsOverQuic,
+    Postgresql,
 }
 
 impl fmt::Display for AlpnProtocol {
@@ -58,6 +59,7 @@ impl AlpnProtocol {
             Self::Mqtt => "mqtt",
             Self::DnsOverTls => "dot",
             Self::DnsOverQuic => "doq",
+            Self::Postgresql => "postgresql",
         }
     }
 
@@ -76,6 +78,7 @@ impl AlpnProtocol {
             Self::Mqtt => b"\x04mqtt",
             Self::DnsOverTls => b"\x03dot",
             Self::DnsOverQuic => b"\x03doq",
+            Self::Postgresql => b"\x0apostgresql",
         }
     }
 
@@ -104,6 +107,7 @@ impl AlpnProtocol {
             b"mqtt" => Some(AlpnProtocol::Mqtt),
             b"dot" => Some(AlpnProtocol::DnsOverTls),
             b"doq" => Some(AlpnProtocol::DnsOverQuic),
+            b"postgresql" => Some(AlpnProtocol::Postgresql),
             _ => None,
         }
     }
@@ -209,4 +213,12 @@ mod tests {
 
         assert_eq!(filtered, alpn2);
     }
+
+    #[test]
+    fn identification_sequence() {
+        let p = AlpnProtocol::Postgresql;
+        let wired = p.wired_identification_sequence();
+        assert_eq!(wired[0] as usize, wired.len() - 1);
+        assert_eq!(AlpnProtocol::from_buf(p.identification_sequence()), Some(p));
+    }
 }
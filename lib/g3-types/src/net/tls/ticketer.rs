@@ -14,6 +14,7 @@
  * limitations under the License.
  */
 
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 
 use ahash::AHashMap;
@@ -31,9 +32,36 @@ pub trait RollingTicketKey: Sized {
     fn lifetime(&self) -> u32;
 }
 
+/// Stats for the tickets presented by clients for session resumption
+#[derive(Default)]
+pub struct RollingTicketerStats {
+    decrypt_total: AtomicU64,
+    decrypt_hit: AtomicU64,
+}
+
+impl RollingTicketerStats {
+    fn add_decrypt(&self, hit: bool) {
+        self.decrypt_total.fetch_add(1, Ordering::Relaxed);
+        if hit {
+            self.decrypt_hit.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// The number of tickets presented
+    pub fn decrypt_total(&self) -> u64 {
+        self.decrypt_total.load(Ordering::Relaxed)
+    }
+
+    /// The number of tickets that have a valid decrypt key
+    pub fn decrypt_hit(&self) -> u64 {
+        self.decrypt_hit.load(Ordering::Relaxed)
+    }
+}
+
 pub struct RollingTicketer<K: RollingTicketKey> {
     dec_keys: RwLock<AHashMap<TicketKeyName, Arc<K>>>,
     pub(crate) enc_key: ArcSwap<K>,
+    stats: Arc<RollingTicketerStats>,
 }
 
 impl<K: RollingTicketKey> RollingTicketer<K> {
@@ -43,6 +71,7 @@ impl<K: RollingTicketKey> RollingTicketer<K> {
         let ticketer = RollingTicketer {
             dec_keys,
             enc_key: ArcSwap::new(key.clone()),
+            stats: Arc::new(RollingTicketerStats::default()),
         };
         ticketer.add_decrypt_key(key);
         ticketer
@@ -50,9 +79,17 @@ impl<K: RollingTicketKey> RollingTicketer<K> {
 
     pub fn get_decrypt_key(&self, name: &[u8]) -> Option<Arc<K>> {
         let Ok(key_name) = TicketKeyName::try_from(name) else {
+            self.stats.add_decrypt(false);
             return None;
         };
-        self.dec_keys.read().unwrap().get(&key_name).cloned()
+        let key = self.dec_keys.read().unwrap().get(&key_name).cloned();
+        self.stats.add_decrypt(key.is_some());
+        key
+    }
+
+    #[inline]
+    pub fn stats(&self) -> &Arc<RollingTicketerStats> {
+        &self.stats
     }
 
     pub fn add_decrypt_key(&self, key: Arc<K>) {
@@ -25,6 +25,8 @@ use crate::net::{SocketBufferConfig, UdpMiscSockOpts};
 pub struct UdpListenConfig {
     address: SocketAddr,
     ipv6only: bool,
+    #[cfg(target_os = "linux")]
+    transparent: bool,
     buf_conf: SocketBufferConfig,
     misc_opts: UdpMiscSockOpts,
     instance: usize,
@@ -42,6 +44,8 @@ impl UdpListenConfig {
         UdpListenConfig {
             address,
             ipv6only: false,
+            #[cfg(target_os = "linux")]
+            transparent: false,
             buf_conf: SocketBufferConfig::default(),
             misc_opts: UdpMiscSockOpts::default(),
             instance: 1,
@@ -77,6 +81,12 @@ impl UdpListenConfig {
         self.ipv6only
     }
 
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn transparent(&self) -> bool {
+        self.transparent
+    }
+
     #[inline]
     pub fn instance(&self) -> usize {
         self.instance.max(self.scale)
@@ -107,6 +117,12 @@ impl UdpListenConfig {
         self.ipv6only = ipv6only;
     }
 
+    #[cfg(target_os = "linux")]
+    #[inline]
+    pub fn set_transparent(&mut self) {
+        self.transparent = true;
+    }
+
     pub fn set_instance(&mut self, instance: usize) {
         if instance == 0 {
             self.instance = 1;
@@ -0,0 +1,121 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::path::{Path, PathBuf};
+
+use anyhow::anyhow;
+
+const DEFAULT_LISTEN_BACKLOG: u32 = 4096;
+const MINIMAL_LISTEN_BACKLOG: u32 = 8;
+
+// the size of sun_path in struct sockaddr_un is 108 on linux and 104 on BSDs,
+// which includes the tailing NUL
+const MAX_SOCKET_PATH_LEN: usize = 103;
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnixListenConfig {
+    path: PathBuf,
+    mode: Option<u32>,
+    owner: Option<u32>,
+    group: Option<u32>,
+    backlog: u32,
+}
+
+impl Default for UnixListenConfig {
+    fn default() -> Self {
+        UnixListenConfig::new(PathBuf::new())
+    }
+}
+
+impl UnixListenConfig {
+    pub fn new(path: PathBuf) -> Self {
+        UnixListenConfig {
+            path,
+            mode: None,
+            owner: None,
+            group: None,
+            backlog: DEFAULT_LISTEN_BACKLOG,
+        }
+    }
+
+    pub fn check(&self) -> anyhow::Result<()> {
+        if self.path.as_os_str().is_empty() {
+            return Err(anyhow!("no listen path is set"));
+        }
+        if self.path.as_os_str().len() > MAX_SOCKET_PATH_LEN {
+            return Err(anyhow!(
+                "the listen path should not be longer than {MAX_SOCKET_PATH_LEN} bytes"
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[inline]
+    pub fn mode(&self) -> Option<u32> {
+        self.mode
+    }
+
+    #[inline]
+    pub fn owner(&self) -> Option<u32> {
+        self.owner
+    }
+
+    #[inline]
+    pub fn group(&self) -> Option<u32> {
+        self.group
+    }
+
+    #[inline]
+    pub fn backlog(&self) -> u32 {
+        self.backlog
+    }
+
+    #[inline]
+    pub fn set_path(&mut self, path: PathBuf) {
+        self.path = path;
+    }
+
+    pub fn set_mode(&mut self, mode: u32) -> anyhow::Result<()> {
+        if mode & !0o777 != 0 {
+            return Err(anyhow!("invalid file mode {mode:o}"));
+        }
+        self.mode = Some(mode);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_owner(&mut self, uid: u32) {
+        self.owner = Some(uid);
+    }
+
+    #[inline]
+    pub fn set_group(&mut self, gid: u32) {
+        self.group = Some(gid);
+    }
+
+    #[inline]
+    pub fn set_backlog(&mut self, backlog: u32) {
+        if backlog >= MINIMAL_LISTEN_BACKLOG {
+            self.backlog = backlog;
+        }
+    }
+}
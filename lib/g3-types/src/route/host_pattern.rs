@@ -0,0 +1,284 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+use anyhow::anyhow;
+use regex::{Regex, RegexBuilder};
+
+const GLOB_MAX_LENGTH: usize = 253;
+const GLOB_MAX_WILDCARDS: usize = 8;
+
+const REGEX_MAX_LENGTH: usize = 1024;
+const REGEX_MAX_NEST: u32 = 16;
+const REGEX_MAX_COMPILED_SIZE: usize = 64 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HostPatternKind {
+    Glob,
+    Regex,
+}
+
+impl HostPatternKind {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            HostPatternKind::Glob => "glob",
+            HostPatternKind::Regex => "regex",
+        }
+    }
+}
+
+/// An anchored, case-insensitive pattern that should match the whole domain.
+///
+/// The regex is compiled only once when the pattern is created, clone of it is cheap.
+/// Two patterns are considered the same if they are compiled from the same regex.
+#[derive(Clone, Debug)]
+pub struct HostPattern {
+    kind: HostPatternKind,
+    source: String,
+    regex: Regex,
+}
+
+impl HostPattern {
+    /// Create a glob pattern.
+    ///
+    /// `*` matches any characters within a single label, `**` matches any characters
+    /// including the dots, and `?` matches exactly one character within a label.
+    pub fn new_glob(glob: &str) -> anyhow::Result<Self> {
+        if glob.is_empty() {
+            return Err(anyhow!("empty glob pattern"));
+        }
+        if glob.len() > GLOB_MAX_LENGTH {
+            return Err(anyhow!(
+                "glob pattern is longer than {GLOB_MAX_LENGTH} characters"
+            ));
+        }
+
+        let mut re = String::with_capacity(glob.len() * 2);
+        let mut wildcards = 0usize;
+        let mut chars = glob.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' => {
+                    wildcards += 1;
+                    if chars.next_if_eq(&'*').is_some() {
+                        if chars.next_if_eq(&'*').is_some() {
+                            return Err(anyhow!("too many consecutive '*' in glob pattern"));
+                        }
+                        re.push_str(".*");
+                    } else {
+                        re.push_str("[^.]*");
+                    }
+                }
+                '?' => {
+                    wildcards += 1;
+                    re.push_str("[^.]");
+                }
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => re.push(c),
+                '.' => re.push_str("\\."),
+                _ => return Err(anyhow!("invalid character '{c}' in glob pattern")),
+            }
+        }
+        if wildcards > GLOB_MAX_WILDCARDS {
+            return Err(anyhow!(
+                "too many wildcards in glob pattern, the max allowed is {GLOB_MAX_WILDCARDS}"
+            ));
+        }
+
+        let regex = Self::build_regex(&re)?;
+        Ok(HostPattern {
+            kind: HostPatternKind::Glob,
+            source: glob.to_string(),
+            regex,
+        })
+    }
+
+    /// Create a regex pattern.
+    ///
+    /// The regex will always be anchored at both ends, so there is no need to add `^` and `$`.
+    pub fn new_regex(re: &str) -> anyhow::Result<Self> {
+        if re.is_empty() {
+            return Err(anyhow!("empty regex pattern"));
+        }
+        if re.len() > REGEX_MAX_LENGTH {
+            return Err(anyhow!(
+                "regex pattern is longer than {REGEX_MAX_LENGTH} characters"
+            ));
+        }
+
+        let regex = Self::build_regex(re)?;
+        Ok(HostPattern {
+            kind: HostPatternKind::Regex,
+            source: re.to_string(),
+            regex,
+        })
+    }
+
+    fn build_regex(re: &str) -> anyhow::Result<Regex> {
+        RegexBuilder::new(&format!("^(?:{re})$"))
+            .case_insensitive(true)
+            .nest_limit(REGEX_MAX_NEST)
+            .size_limit(REGEX_MAX_COMPILED_SIZE)
+            .build()
+            .map_err(|e| anyhow!("failed to compile {re}: {e}"))
+    }
+
+    #[inline]
+    pub fn kind(&self) -> HostPatternKind {
+        self.kind
+    }
+
+    #[inline]
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    #[inline]
+    pub fn is_match(&self, domain: &str) -> bool {
+        self.regex.is_match(domain)
+    }
+}
+
+impl PartialEq for HostPattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.regex.as_str() == other.regex.as_str()
+    }
+}
+
+impl Eq for HostPattern {}
+
+impl PartialOrd for HostPattern {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HostPattern {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.regex.as_str().cmp(other.regex.as_str())
+    }
+}
+
+impl Hash for HostPattern {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.regex.as_str().hash(state)
+    }
+}
+
+impl fmt::Display for HostPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.kind.as_str(), self.source)
+    }
+}
+
+/// Match domains against a list of patterns, in the order they are added.
+#[derive(Clone, Debug)]
+pub struct HostPatternMatch<T> {
+    rules: Vec<(HostPattern, T)>,
+}
+
+impl<T> Default for HostPatternMatch<T> {
+    fn default() -> Self {
+        HostPatternMatch { rules: Vec::new() }
+    }
+}
+
+impl<T> HostPatternMatch<T> {
+    /// Add a new pattern, the old value will be returned if the same pattern exists
+    pub fn add(&mut self, pattern: HostPattern, v: T) -> Option<T> {
+        if let Some((_, old)) = self.rules.iter_mut().find(|(p, _)| pattern.eq(p)) {
+            return Some(std::mem::replace(old, v));
+        }
+        self.rules.push((pattern, v));
+        None
+    }
+
+    pub fn get(&self, domain: &str) -> Option<&T> {
+        self.rules
+            .iter()
+            .find(|(p, _)| p.is_match(domain))
+            .map(|(_, v)| v)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob() {
+        let p = HostPattern::new_glob("*.example.com").unwrap();
+        assert!(p.is_match("www.example.com"));
+        assert!(p.is_match("WWW.Example.com"));
+        assert!(!p.is_match("a.www.example.com"));
+        assert!(!p.is_match("example.com"));
+        assert!(!p.is_match("www.example.com.cn"));
+
+        let p = HostPattern::new_glob("**.example.com").unwrap();
+        assert!(p.is_match("a.www.example.com"));
+
+        let p = HostPattern::new_glob("api-?.example.com").unwrap();
+        assert!(p.is_match("api-1.example.com"));
+        assert!(!p.is_match("api-12.example.com"));
+
+        assert!(HostPattern::new_glob("***.example.com").is_err());
+        assert!(HostPattern::new_glob("a/b.example.com").is_err());
+        assert!(HostPattern::new_glob("*.*.*.*.*.*.*.*.*.example.com").is_err());
+    }
+
+    #[test]
+    fn regex() {
+        let p = HostPattern::new_regex(r"api[0-9]+\.example\.(com|net)").unwrap();
+        assert!(p.is_match("api01.example.net"));
+        assert!(!p.is_match("xapi01.example.net"));
+        assert!(!p.is_match("api01.example.net.evil"));
+
+        assert!(HostPattern::new_regex("(").is_err());
+        assert!(HostPattern::new_regex("[a-z]{1000}{1000}").is_err());
+    }
+
+    #[test]
+    fn same_pattern() {
+        let glob = HostPattern::new_glob("*.example.com").unwrap();
+        let regex = HostPattern::new_regex(r"[^.]*\.example\.com").unwrap();
+        assert_eq!(glob, regex);
+    }
+
+    #[test]
+    fn ordered_match() {
+        let mut m = HostPatternMatch::default();
+        assert!(m
+            .add(HostPattern::new_glob("api.*.com").unwrap(), 1)
+            .is_none());
+        assert!(m
+            .add(HostPattern::new_glob("*.example.com").unwrap(), 2)
+            .is_none());
+        assert_eq!(m.get("api.example.com"), Some(&1));
+        assert_eq!(m.get("www.example.com"), Some(&2));
+        assert_eq!(m.get("www.example.net"), None);
+        assert_eq!(
+            m.add(HostPattern::new_regex(r"api\.[^.]*\.com").unwrap(), 3),
+            Some(1)
+        );
+    }
+}
@@ -17,6 +17,9 @@
 mod host;
 pub use host::HostMatch;
 
+mod host_pattern;
+pub use host_pattern::{HostPattern, HostPatternKind, HostPatternMatch};
+
 mod uri_path;
 pub use uri_path::UriPathMatch;
 
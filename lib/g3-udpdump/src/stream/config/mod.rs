@@ -15,6 +15,7 @@
  */
 
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
 
 use g3_types::net::{SocketBufferConfig, UdpMiscSockOpts};
 
@@ -28,6 +29,7 @@ pub struct StreamDumpConfig {
     pub opts: UdpMiscSockOpts,
     pub packet_size: usize,
     pub client_side: bool,
+    pub metadata_interval: Option<Duration>,
 }
 
 impl Default for StreamDumpConfig {
@@ -38,6 +40,7 @@ impl Default for StreamDumpConfig {
             opts: UdpMiscSockOpts::default(),
             packet_size: 1480,
             client_side: false,
+            metadata_interval: None,
         }
     }
 }
@@ -48,6 +48,12 @@ impl StreamDumpConfig {
                         config.client_side = g3_yaml::value::as_bool(v)?;
                         Ok(())
                     }
+                    "metadata_interval" => {
+                        let interval = g3_yaml::humanize::as_duration(v)
+                            .context(format!("invalid humanize duration value for key {k}"))?;
+                        config.metadata_interval = Some(interval);
+                        Ok(())
+                    }
                     _ => Err(anyhow!("invalid key {k}")),
                 })?;
 
@@ -18,14 +18,16 @@ use std::net::{IpAddr, SocketAddr};
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 
+use super::MetadataFrame;
 use crate::ExportedPduDissectorHint;
 
 pub(super) fn new_pair(
     client: SocketAddr,
     remote: SocketAddr,
     dissector_hint: ExportedPduDissectorHint,
+    metadata: Option<MetadataFrame>,
 ) -> (ToClientPduHeader, ToRemotePduHeader) {
-    let state = Arc::new(TcpDissectorState::new(dissector_hint));
+    let state = Arc::new(TcpDissectorState::new(dissector_hint, metadata));
     let to_client = ToClientPduHeader::new(client, remote, state.clone());
     let to_remote = ToRemotePduHeader::new(client, remote, state);
     (to_client, to_remote)
@@ -35,17 +37,23 @@ pub(super) struct TcpDissectorState {
     dissector_hint: ExportedPduDissectorHint,
     write_to_client: AtomicU32,
     write_to_remote: AtomicU32,
+    metadata: Option<MetadataFrame>,
 }
 
 impl TcpDissectorState {
-    fn new(dissector_hint: ExportedPduDissectorHint) -> Self {
+    fn new(dissector_hint: ExportedPduDissectorHint, metadata: Option<MetadataFrame>) -> Self {
         TcpDissectorState {
             dissector_hint,
             write_to_client: AtomicU32::new(1),
             write_to_remote: AtomicU32::new(1),
+            metadata,
         }
     }
 
+    fn metadata_frame(&self) -> Option<Vec<u8>> {
+        self.metadata.as_ref().and_then(|m| m.poll_frame())
+    }
+
     fn write_to_client(&self) -> u32 {
         self.write_to_client.load(Ordering::Relaxed)
     }
@@ -69,6 +77,7 @@ pub trait PduHeader {
     fn new_header(&mut self, pkt_size: usize) -> Vec<u8>;
     fn update_tcp_dissector_data(&self, hdr: &mut Vec<u8>, data_len: usize);
     fn record_written_data(&self, data_len: usize);
+    fn metadata_frame(&self) -> Option<Vec<u8>>;
 }
 
 pub struct ToClientPduHeader {
@@ -128,6 +137,10 @@ impl PduHeader for ToClientPduHeader {
     fn record_written_data(&self, data_len: usize) {
         self.tcp_dissector_state.add_write_to_client(data_len);
     }
+
+    fn metadata_frame(&self) -> Option<Vec<u8>> {
+        self.tcp_dissector_state.metadata_frame()
+    }
 }
 
 pub struct ToRemotePduHeader {
@@ -187,8 +200,14 @@ impl PduHeader for ToRemotePduHeader {
     fn record_written_data(&self, data_len: usize) {
         self.tcp_dissector_state.add_write_to_remote(data_len);
     }
+
+    fn metadata_frame(&self) -> Option<Vec<u8>> {
+        self.tcp_dissector_state.metadata_frame()
+    }
 }
 
+const EXP_PDU_TAG_DISSECTOR_NAME: u8 = 12;
+
 const EXP_PDU_TAG_IPV4_SRC: u8 = 20;
 const EXP_PDU_TAG_IPV4_DST: u8 = 21;
 const EXP_PDU_TAG_IPV6_SRC: u8 = 22;
@@ -212,7 +231,24 @@ fn new_fixed_header(
     let mut buf = Vec::with_capacity(pkt_size);
 
     dissector_hint.serialize(&mut buf);
+    push_addr_header(&mut buf, src_addr, dst_addr);
+
+    buf
+}
+
+const METADATA_DISSECTOR_NAME: &[u8] = b"json";
+
+pub(super) fn push_metadata_header(buf: &mut Vec<u8>, client: SocketAddr, remote: SocketAddr) {
+    buf.extend_from_slice(&[0x00, EXP_PDU_TAG_DISSECTOR_NAME, 0x00]);
+    buf.push(METADATA_DISSECTOR_NAME.len() as u8);
+    buf.extend_from_slice(METADATA_DISSECTOR_NAME);
+    push_addr_header(buf, client, remote);
 
+    // end of option
+    buf.extend_from_slice(&[0x00, EXP_PDU_TAG_END_OF_OPT, 0x00, 0x00]);
+}
+
+fn push_addr_header(buf: &mut Vec<u8>, src_addr: SocketAddr, dst_addr: SocketAddr) {
     // src ip
     match src_addr.ip() {
         IpAddr::V4(ip4) => {
@@ -272,8 +308,6 @@ fn new_fixed_header(
         dst_port[0],
         dst_port[1],
     ]);
-
-    buf
 }
 
 const EXP_PDU_TAG_TCP_INFO_DATA: u8 = 34;
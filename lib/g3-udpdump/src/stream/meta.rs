@@ -0,0 +1,162 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::io::Write;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use g3_dpi::Protocol;
+
+/// Context info for the dumped stream, which will be sent periodically in separate frames
+#[derive(Clone, Default)]
+pub struct StreamDumpMetadata {
+    task_id: Option<String>,
+    server: Option<String>,
+    user: Option<String>,
+    escaper: Option<String>,
+    auditor: Option<String>,
+    protocol: Option<Protocol>,
+}
+
+impl StreamDumpMetadata {
+    pub fn set_task_id(&mut self, id: String) {
+        self.task_id = Some(id);
+    }
+
+    pub fn set_server(&mut self, name: &str) {
+        self.server = Some(name.to_string());
+    }
+
+    pub fn set_user(&mut self, name: &str) {
+        self.user = Some(name.to_string());
+    }
+
+    pub fn set_escaper(&mut self, name: &str) {
+        self.escaper = Some(name.to_string());
+    }
+
+    pub fn set_auditor(&mut self, name: &str) {
+        self.auditor = Some(name.to_string());
+    }
+
+    pub fn set_protocol(&mut self, protocol: Protocol) {
+        self.protocol = Some(protocol);
+    }
+
+    fn encode_json(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(b"{\"type\":\"g3-stream-metadata\"");
+        if let Some(id) = &self.task_id {
+            push_json_field(buf, "task_id", id);
+        }
+        if let Some(name) = &self.server {
+            push_json_field(buf, "server", name);
+        }
+        if let Some(name) = &self.user {
+            push_json_field(buf, "user", name);
+        }
+        if let Some(name) = &self.escaper {
+            push_json_field(buf, "escaper", name);
+        }
+        if let Some(name) = &self.auditor {
+            push_json_field(buf, "auditor", name);
+        }
+        if let Some(protocol) = &self.protocol {
+            push_json_field(buf, "protocol", protocol.as_str());
+        }
+        buf.push(b'}');
+    }
+}
+
+fn push_json_field(buf: &mut Vec<u8>, key: &str, value: &str) {
+    buf.extend_from_slice(b",\"");
+    buf.extend_from_slice(key.as_bytes());
+    buf.extend_from_slice(b"\":\"");
+    for c in value.chars() {
+        match c {
+            '"' => buf.extend_from_slice(b"\\\""),
+            '\\' => buf.extend_from_slice(b"\\\\"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(buf, "\\u{:04x}", c as u32);
+            }
+            c => {
+                let mut tmp = [0u8; 4];
+                buf.extend_from_slice(c.encode_utf8(&mut tmp).as_bytes());
+            }
+        }
+    }
+    buf.push(b'"');
+}
+
+pub(super) struct MetadataFrame {
+    data: Vec<u8>,
+    interval: Duration,
+    created: Instant,
+    next_send: AtomicU64,
+}
+
+impl MetadataFrame {
+    pub(super) fn new(
+        client: SocketAddr,
+        remote: SocketAddr,
+        metadata: &StreamDumpMetadata,
+        interval: Duration,
+    ) -> Self {
+        let mut data = Vec::with_capacity(256);
+        super::header::push_metadata_header(&mut data, client, remote);
+        metadata.encode_json(&mut data);
+        MetadataFrame {
+            data,
+            interval,
+            created: Instant::now(),
+            next_send: AtomicU64::new(0),
+        }
+    }
+
+    /// Get the frame if it's time to send it again
+    pub(super) fn poll_frame(&self) -> Option<Vec<u8>> {
+        let now = self.created.elapsed().as_millis() as u64;
+        let next = self.next_send.load(Ordering::Relaxed);
+        if now < next {
+            return None;
+        }
+        let new_next = now + self.interval.as_millis() as u64;
+        self.next_send
+            .compare_exchange(next, new_next, Ordering::Relaxed, Ordering::Relaxed)
+            .ok()
+            .map(|_| self.data.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode() {
+        let mut metadata = StreamDumpMetadata::default();
+        metadata.set_server("test\"server");
+        metadata.set_user("user\n1");
+        metadata.set_protocol(Protocol::Http1);
+
+        let mut buf = Vec::new();
+        metadata.encode_json(&mut buf);
+        assert_eq!(
+            std::str::from_utf8(&buf).unwrap(),
+            r#"{"type":"g3-stream-metadata","server":"test\"server","user":"user\u000a1","protocol":"http_1"}"#
+        );
+    }
+}
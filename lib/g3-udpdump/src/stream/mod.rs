@@ -30,6 +30,10 @@ pub use config::StreamDumpConfig;
 mod sink;
 use sink::Sinker;
 
+mod meta;
+use meta::MetadataFrame;
+pub use meta::StreamDumpMetadata;
+
 mod header;
 use header::PduHeader;
 pub use header::{ToClientPduHeader, ToRemotePduHeader};
@@ -73,11 +77,32 @@ impl StreamDumper {
         self.config.client_side
     }
 
+    #[inline]
+    pub fn metadata_enabled(&self) -> bool {
+        self.config.metadata_interval.is_some()
+    }
+
+    fn new_header_pair(
+        &self,
+        client_addr: SocketAddr,
+        remote_addr: SocketAddr,
+        dissector_hint: ExportedPduDissectorHint,
+        metadata: Option<&StreamDumpMetadata>,
+    ) -> (ToClientPduHeader, ToRemotePduHeader) {
+        let metadata = self
+            .config
+            .metadata_interval
+            .zip(metadata)
+            .map(|(interval, m)| MetadataFrame::new(client_addr, remote_addr, m, interval));
+        header::new_pair(client_addr, remote_addr, dissector_hint, metadata)
+    }
+
     pub fn wrap_writer<CW, RW>(
         &self,
         client_addr: SocketAddr,
         remote_addr: SocketAddr,
         dissector_hint: ExportedPduDissectorHint,
+        metadata: Option<&StreamDumpMetadata>,
         client_writer: CW,
         remote_writer: RW,
     ) -> (ToClientStreamDumpWriter<CW>, ToRemoteStreamDumpWriter<RW>)
@@ -85,7 +110,7 @@ impl StreamDumper {
         CW: AsyncWrite,
         RW: AsyncWrite,
     {
-        let (to_c, to_r) = header::new_pair(client_addr, remote_addr, dissector_hint);
+        let (to_c, to_r) = self.new_header_pair(client_addr, remote_addr, dissector_hint, metadata);
         let cw = StreamDumpWriter::new(
             client_writer,
             to_c,
@@ -106,6 +131,7 @@ impl StreamDumper {
         client_addr: SocketAddr,
         remote_addr: SocketAddr,
         dissector_hint: ExportedPduDissectorHint,
+        metadata: Option<&StreamDumpMetadata>,
         remote_reader: R,
         remote_writer: W,
     ) -> (FromRemoteStreamDumpReader<R>, ToRemoteStreamDumpWriter<W>)
@@ -113,7 +139,7 @@ impl StreamDumper {
         R: AsyncRead,
         W: AsyncWrite,
     {
-        let (to_c, to_r) = header::new_pair(client_addr, remote_addr, dissector_hint);
+        let (to_c, to_r) = self.new_header_pair(client_addr, remote_addr, dissector_hint, metadata);
         let r = StreamDumpReader::new(
             remote_reader,
             to_c,
@@ -134,6 +160,7 @@ impl StreamDumper {
         client_addr: SocketAddr,
         remote_addr: SocketAddr,
         dissector_hint: ExportedPduDissectorHint,
+        metadata: Option<&StreamDumpMetadata>,
         client_reader: R,
         client_writer: W,
     ) -> (FromClientStreamDumpReader<R>, ToClientStreamDumpWriter<W>)
@@ -141,7 +168,7 @@ impl StreamDumper {
         R: AsyncRead,
         W: AsyncWrite,
     {
-        let (to_c, to_r) = header::new_pair(client_addr, remote_addr, dissector_hint);
+        let (to_c, to_r) = self.new_header_pair(client_addr, remote_addr, dissector_hint, metadata);
         let r = StreamDumpReader::new(
             client_reader,
             to_r,
@@ -69,6 +69,9 @@ impl<H: PduHeader> StreamDumpState<H> {
         let mut buf = mem::replace(&mut self.buf, new_buf);
         let data_len = buf.len() - self.hdr_len;
         self.header.update_tcp_dissector_data(&mut buf, data_len);
+        if let Some(frame) = self.header.metadata_frame() {
+            let _ = self.sender.send(frame);
+        }
         let _ = self.sender.send(buf);
         self.header.record_written_data(data_len);
     }
@@ -14,16 +14,48 @@
  * limitations under the License.
  */
 
-use anyhow::{anyhow, Context};
+use std::fmt;
+
+use anyhow::anyhow;
 use yaml_rust::{yaml, Yaml};
 
+use crate::YamlKeyPosition;
+
+/// Error context for the innermost key whose source position is known.
+#[derive(Debug)]
+struct KeyPositionContext {
+    key: String,
+    position: YamlKeyPosition,
+}
+
+impl fmt::Display for KeyPositionContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to parse value of key {} at {}",
+            self.key, self.position
+        )
+    }
+}
+
 pub fn foreach_kv<F>(table: &yaml::Hash, mut f: F) -> anyhow::Result<()>
 where
     F: FnMut(&str, &Yaml) -> anyhow::Result<()>,
 {
     for (k, v) in table.iter() {
         if let Yaml::String(key) = k {
-            f(key, v).context(format!("failed to parse value of key {key}"))?;
+            if let Err(e) = f(key, v) {
+                // only report the position of the innermost key
+                if e.downcast_ref::<KeyPositionContext>().is_none() {
+                    if let Some(position) = crate::key_position(k) {
+                        return Err(e.context(KeyPositionContext {
+                            key: key.to_string(),
+                            position,
+                        }));
+                    }
+                }
+                return Err(e.context(format!("failed to parse value of key {key}")));
+            }
         } else {
             return Err(anyhow!("key in hash should be string"));
         }
@@ -17,6 +17,7 @@
 mod callback;
 mod hash;
 mod hybrid;
+mod position;
 mod util;
 
 pub mod humanize;
@@ -28,4 +29,5 @@ pub use hash::{
     foreach_kv, get_required as hash_get_required, get_required_str as hash_get_required_str,
 };
 pub use hybrid::HybridParser;
+pub use position::{lookup as key_position, YamlKeyPosition};
 pub use util::{foreach_doc, load_doc, YamlDocPosition};
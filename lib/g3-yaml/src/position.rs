@@ -0,0 +1,250 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use yaml_rust::parser::{Event, MarkedEventReceiver, Parser};
+use yaml_rust::scanner::{Marker, ScanError};
+use yaml_rust::{Yaml, YamlLoader};
+
+/// Max count of loaded files whose key positions are kept.
+const MAX_TRACKED_FILES: usize = 256;
+
+thread_local! {
+    static KEY_POSITIONS: RefCell<VecDeque<FileKeyPositions>> = RefCell::new(VecDeque::new());
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct YamlKeyPosition {
+    pub path: Arc<Path>,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for YamlKeyPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.path.display(), self.line, self.column)
+    }
+}
+
+struct FileKeyPositions {
+    path: Arc<Path>,
+    keys: HashMap<usize, (String, usize, usize)>,
+}
+
+/// Get the source position of a mapping key.
+///
+/// The key should be a reference into a doc returned by [`load_docs`],
+/// cloned values are not tracked.
+pub fn lookup(key: &Yaml) -> Option<YamlKeyPosition> {
+    let Yaml::String(s) = key else {
+        return None;
+    };
+    let addr = key as *const Yaml as usize;
+    KEY_POSITIONS.with(|files| {
+        files.borrow().iter().rev().find_map(|file| {
+            let (k, line, col) = file.keys.get(&addr)?;
+            // the address may be reused by a newer value, so check the key string
+            if k != s {
+                return None;
+            }
+            Some(YamlKeyPosition {
+                path: file.path.clone(),
+                line: *line,
+                column: *col + 1,
+            })
+        })
+    })
+}
+
+enum MarkNode {
+    Leaf,
+    Seq(Vec<MarkNode>),
+    Map(Vec<(Marker, MarkNode)>),
+}
+
+struct MarkFrame {
+    node: MarkNode,
+    key: Option<Marker>,
+}
+
+#[derive(Default)]
+struct PositionReceiver {
+    loader: YamlLoader,
+    stack: Vec<MarkFrame>,
+    docs: Vec<MarkNode>,
+}
+
+impl PositionReceiver {
+    fn insert_node(&mut self, node: MarkNode, mark: Marker) {
+        let Some(parent) = self.stack.last_mut() else {
+            self.stack.push(MarkFrame { node, key: None });
+            return;
+        };
+        match &mut parent.node {
+            MarkNode::Seq(v) => v.push(node),
+            MarkNode::Map(v) => match parent.key.take() {
+                Some(key) => v.push((key, node)),
+                None => parent.key = Some(mark),
+            },
+            MarkNode::Leaf => {}
+        }
+    }
+}
+
+impl MarkedEventReceiver for PositionReceiver {
+    fn on_event(&mut self, ev: Event, mark: Marker) {
+        match &ev {
+            Event::DocumentEnd => {
+                let node = self.stack.pop().map(|f| f.node).unwrap_or(MarkNode::Leaf);
+                self.stack.clear();
+                self.docs.push(node);
+            }
+            Event::SequenceStart(..) => self.stack.push(MarkFrame {
+                node: MarkNode::Seq(Vec::new()),
+                key: None,
+            }),
+            Event::MappingStart(..) => self.stack.push(MarkFrame {
+                node: MarkNode::Map(Vec::new()),
+                key: None,
+            }),
+            Event::SequenceEnd | Event::MappingEnd => {
+                if let Some(frame) = self.stack.pop() {
+                    self.insert_node(frame.node, mark);
+                }
+            }
+            Event::Scalar(..) | Event::Alias(_) => self.insert_node(MarkNode::Leaf, mark),
+            _ => {}
+        }
+        self.loader.on_event(ev, mark);
+    }
+}
+
+fn record_keys(doc: &Yaml, node: &MarkNode, keys: &mut HashMap<usize, (String, usize, usize)>) {
+    match (doc, node) {
+        (Yaml::Array(values), MarkNode::Seq(nodes)) => {
+            for (v, n) in values.iter().zip(nodes) {
+                record_keys(v, n, keys);
+            }
+        }
+        (Yaml::Hash(map), MarkNode::Map(nodes)) => {
+            for ((k, v), (mark, n)) in map.iter().zip(nodes) {
+                if let Yaml::String(s) = k {
+                    keys.insert(
+                        k as *const Yaml as usize,
+                        (s.clone(), mark.line(), mark.col()),
+                    );
+                }
+                record_keys(v, n, keys);
+            }
+        }
+        // aliased nodes have no position info of their own
+        _ => {}
+    }
+}
+
+/// Load all yaml docs from the content of `path`, and record the position of
+/// all mapping keys in them.
+pub(crate) fn load_docs(path: &Path, content: &str) -> Result<Vec<Yaml>, ScanError> {
+    let mut receiver = PositionReceiver::default();
+    let mut parser = Parser::new_from_str(content);
+    parser.load(&mut receiver, true)?;
+
+    if receiver.loader.documents().len() != receiver.docs.len() {
+        // the loader stopped on a semantic error, like duplicated keys,
+        // which is not exposed to us, so load again to get the error
+        return YamlLoader::load_from_str(content);
+    }
+
+    let docs = receiver.loader.documents().to_vec();
+    let mut keys = HashMap::new();
+    for (doc, node) in docs.iter().zip(&receiver.docs) {
+        record_keys(doc, node, &mut keys);
+    }
+    KEY_POSITIONS.with(|files| {
+        let mut files = files.borrow_mut();
+        if files.len() >= MAX_TRACKED_FILES {
+            files.pop_front();
+        }
+        files.push_back(FileKeyPositions {
+            path: Arc::from(path),
+            keys,
+        });
+    });
+    Ok(docs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::anyhow;
+
+    #[test]
+    fn key_position() {
+        let content = "server:\n  - name: a\n    listen: 8080\n    bad-key: 1\n";
+        let docs = load_docs(Path::new("test.yaml"), content).unwrap();
+        let Yaml::Hash(map) = &docs[0] else {
+            panic!("not a hash");
+        };
+        let (k, v) = map.iter().next().unwrap();
+        let position = lookup(k).unwrap();
+        assert_eq!(position.to_string(), "test.yaml:1:1");
+
+        let Yaml::Array(seq) = v else {
+            panic!("not an array");
+        };
+        let Yaml::Hash(server) = &seq[0] else {
+            panic!("not a hash");
+        };
+        let keys: Vec<String> = server
+            .keys()
+            .map(|k| lookup(k).unwrap().to_string())
+            .collect();
+        assert_eq!(keys, ["test.yaml:2:5", "test.yaml:3:5", "test.yaml:4:5"]);
+
+        let cloned = k.clone();
+        assert!(lookup(&cloned).is_none());
+
+        let e = crate::foreach_kv(map, |_, v| {
+            let Yaml::Array(seq) = v else {
+                return Err(anyhow!("not an array"));
+            };
+            let Yaml::Hash(server) = &seq[0] else {
+                return Err(anyhow!("not a hash"));
+            };
+            crate::foreach_kv(server, |k, _| match k {
+                "bad-key" => Err(anyhow!("invalid key {k}")),
+                _ => Ok(()),
+            })
+        })
+        .unwrap_err();
+        assert_eq!(
+            format!("{e:#}"),
+            "failed to parse value of key server: \
+             failed to parse value of key bad-key at test.yaml:4:5: \
+             invalid key bad-key"
+        );
+    }
+
+    #[test]
+    fn duplicated_key() {
+        assert!(load_docs(Path::new("test.yaml"), "a: 1\na: 2\n").is_err());
+    }
+}
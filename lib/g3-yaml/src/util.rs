@@ -21,7 +21,7 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use anyhow::anyhow;
-use yaml_rust::{Yaml, YamlLoader};
+use yaml_rust::Yaml;
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct YamlDocPosition {
@@ -86,7 +86,7 @@ pub fn load_doc(position: &YamlDocPosition) -> anyhow::Result<Yaml> {
     let mut conf = String::new();
     File::open(&position.path)?.read_to_string(&mut conf)?;
 
-    let mut yaml_docs = YamlLoader::load_from_str(&conf)?;
+    let mut yaml_docs = crate::position::load_docs(&position.path, &conf)?;
     if yaml_docs.get(position.index).is_some() {
         Ok(yaml_docs.remove(position.index))
     } else {
@@ -101,7 +101,7 @@ where
     let mut conf = String::new();
     File::open(path)?.read_to_string(&mut conf)?;
 
-    let yaml_docs = YamlLoader::load_from_str(&conf)?;
+    let yaml_docs = crate::position::load_docs(path, &conf)?;
     for (i, doc) in yaml_docs.iter().enumerate() {
         f(i, doc)?;
     }
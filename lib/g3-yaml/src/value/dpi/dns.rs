@@ -0,0 +1,73 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use g3_dpi::DnsInterceptionConfig;
+
+pub fn as_dns_interception_config(value: &Yaml) -> anyhow::Result<DnsInterceptionConfig> {
+    if let Yaml::Hash(map) = value {
+        let mut config = DnsInterceptionConfig::default();
+
+        crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+            "domain_labels" => {
+                let labels = crate::value::as_usize(v)?;
+                if labels == 0 {
+                    return Err(anyhow!("domain labels should not be 0"));
+                }
+                config.domain_labels = labels;
+                Ok(())
+            }
+            "entropy_threshold" => {
+                config.entropy_threshold = crate::value::as_f64(v)?;
+                Ok(())
+            }
+            "subdomain_length_threshold" => {
+                config.subdomain_length_threshold = crate::value::as_usize(v)?;
+                Ok(())
+            }
+            "stats_window" => {
+                let window = crate::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                if window.is_zero() {
+                    return Err(anyhow!("stats window should not be zero"));
+                }
+                config.stats_window = window;
+                Ok(())
+            }
+            "query_rate_threshold" => {
+                config.query_rate_threshold = crate::value::as_usize(v)?;
+                Ok(())
+            }
+            "txt_volume_threshold" => {
+                config.txt_volume_threshold = crate::value::as_usize(v)?;
+                Ok(())
+            }
+            "log_score" => {
+                config.log_score = crate::value::as_u8(v)?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        Ok(config)
+    } else {
+        Err(anyhow!(
+            "yaml value type for 'dns interception config' should be 'map'"
+        ))
+    }
+}
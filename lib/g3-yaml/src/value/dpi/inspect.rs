@@ -17,7 +17,10 @@
 use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
-use g3_dpi::{ProtocolInspectionConfig, ProtocolInspectionSizeLimit};
+use g3_dpi::{
+    CustomProtocolPattern, CustomProtocolSignature, ProtocolInspectionConfig,
+    ProtocolInspectionSizeLimit,
+};
 
 pub fn parse_inspect_size_limit(
     config: &mut ProtocolInspectionSizeLimit,
@@ -49,6 +52,12 @@ pub fn parse_inspect_size_limit(
                 config.set_nats_server_info_line(size);
                 Ok(())
             }
+            "thrift_method_name" => {
+                let size = crate::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                config.set_thrift_method_name(size);
+                Ok(())
+            }
             "smtp_greeting_msg" | "smtp_server_greeting_msg" => Ok(()),
             _ => Err(anyhow!("invalid key {k}")),
         })
@@ -59,6 +68,102 @@ pub fn parse_inspect_size_limit(
     }
 }
 
+fn as_custom_protocol_pattern(value: &Yaml) -> anyhow::Result<CustomProtocolPattern> {
+    if let Yaml::Hash(map) = value {
+        let mut offset = 0usize;
+        let mut data: Option<Vec<u8>> = None;
+        let mut mask: Option<Vec<u8>> = None;
+
+        crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+            "offset" => {
+                offset = crate::value::as_usize(v)?;
+                Ok(())
+            }
+            "hex" | "bytes" => {
+                let s = crate::value::as_string(v)?;
+                let bytes =
+                    hex::decode(s).map_err(|e| anyhow!("invalid hex string for key {k}: {e}"))?;
+                data = Some(bytes);
+                Ok(())
+            }
+            "text" | "string" => {
+                let s = crate::value::as_string(v)?;
+                data = Some(s.into_bytes());
+                Ok(())
+            }
+            "mask" => {
+                let s = crate::value::as_string(v)?;
+                let bytes =
+                    hex::decode(s).map_err(|e| anyhow!("invalid hex string for key {k}: {e}"))?;
+                mask = Some(bytes);
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        let Some(data) = data else {
+            return Err(anyhow!("no pattern value set"));
+        };
+        CustomProtocolPattern::new(offset, data, mask).map_err(|e| anyhow!("{e}"))
+    } else {
+        Err(anyhow!(
+            "yaml value type for 'custom protocol pattern' should be 'map'"
+        ))
+    }
+}
+
+fn as_custom_protocol_signature(value: &Yaml) -> anyhow::Result<CustomProtocolSignature> {
+    if let Yaml::Hash(map) = value {
+        let v = crate::hash_get_required(map, "name")?;
+        let name = crate::value::as_string(v)?;
+        if name.is_empty() {
+            return Err(anyhow!("empty name"));
+        }
+        let mut signature = CustomProtocolSignature::new(name);
+
+        crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+            "name" => Ok(()),
+            "server_first" => {
+                let server_first = crate::value::as_bool(v)?;
+                signature.set_server_first(server_first);
+                Ok(())
+            }
+            "port" | "ports" => {
+                let ports = crate::value::as_list(v, crate::value::as_u16)
+                    .context(format!("invalid u16 list value for key {k}"))?;
+                for port in ports {
+                    signature.add_port(port);
+                }
+                Ok(())
+            }
+            "pattern" | "patterns" => {
+                let patterns = crate::value::as_list(v, as_custom_protocol_pattern)
+                    .context(format!("invalid custom protocol pattern value for key {k}"))?;
+                for pattern in patterns {
+                    signature.add_pattern(pattern);
+                }
+                Ok(())
+            }
+            "action" | "inspect_action" => {
+                let action = super::policy::as_protocol_inspect_action(v)
+                    .context(format!("invalid protocol inspect action value for key {k}"))?;
+                signature.set_inspect_action(action);
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        if !signature.has_pattern() {
+            return Err(anyhow!("no pattern set"));
+        }
+        Ok(signature)
+    } else {
+        Err(anyhow!(
+            "yaml value type for 'custom protocol signature' should be 'map'"
+        ))
+    }
+}
+
 pub fn as_protocol_inspection_config(value: &Yaml) -> anyhow::Result<ProtocolInspectionConfig> {
     if let Yaml::Hash(map) = value {
         let mut config = ProtocolInspectionConfig::default();
@@ -89,6 +194,15 @@ pub fn as_protocol_inspection_config(value: &Yaml) -> anyhow::Result<ProtocolIns
             }
             "data0_size_limit" => parse_inspect_size_limit(config.size_limit_mut(), v)
                 .context(format!("invalid inspect size limit value for key {k}")),
+            "custom_signature" | "custom_signatures" | "custom_protocol" | "custom_protocols" => {
+                let signatures = crate::value::as_list(v, as_custom_protocol_signature).context(
+                    format!("invalid custom protocol signature value for key {k}"),
+                )?;
+                for signature in signatures {
+                    config.add_custom_signature(signature);
+                }
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         })?;
 
@@ -0,0 +1,54 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use g3_dpi::KafkaInterceptionConfig;
+
+pub fn as_kafka_interception_config(value: &Yaml) -> anyhow::Result<KafkaInterceptionConfig> {
+    if let Yaml::Hash(map) = value {
+        let mut config = KafkaInterceptionConfig::default();
+
+        crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+            "request_max_size" => {
+                config.request_max_size = crate::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            "request_buffer_size" => {
+                config.request_buffer_size = crate::humanize::as_usize(v)
+                    .context(format!("invalid humanize usize value for key {k}"))?;
+                Ok(())
+            }
+            "block_produce" => {
+                config.block_produce = crate::value::as_bool(v)?;
+                Ok(())
+            }
+            "block_fetch" => {
+                config.block_fetch = crate::value::as_bool(v)?;
+                Ok(())
+            }
+            _ => Err(anyhow!("invalid key {k}")),
+        })?;
+
+        Ok(config)
+    } else {
+        Err(anyhow!(
+            "yaml value type for 'kafka interception config' should be 'map'"
+        ))
+    }
+}
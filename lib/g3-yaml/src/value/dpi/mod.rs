@@ -31,3 +31,18 @@ pub use smtp::as_smtp_interception_config;
 
 mod imap;
 pub use imap::as_imap_interception_config;
+
+mod kafka;
+pub use kafka::as_kafka_interception_config;
+
+mod postgres;
+pub use postgres::as_postgres_interception_config;
+
+mod mysql;
+pub use mysql::as_mysql_interception_config;
+
+mod dns;
+pub use dns::as_dns_interception_config;
+
+mod websocket;
+pub use websocket::as_websocket_interception_config;
@@ -54,7 +54,7 @@ trait InspectRuleYamlParser {
     }
 }
 
-fn as_protocol_inspect_action(value: &Yaml) -> anyhow::Result<ProtocolInspectAction> {
+pub(super) fn as_protocol_inspect_action(value: &Yaml) -> anyhow::Result<ProtocolInspectAction> {
     if let Yaml::String(s) = value {
         ProtocolInspectAction::from_str(s)
             .map_err(|_| anyhow!("invalid protocol inspect action '{s}'"))
@@ -20,7 +20,7 @@ use std::str::FromStr;
 use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
-use g3_histogram::{HistogramMetricsConfig, Quantile};
+use g3_histogram::{HistogramBuckets, HistogramMetricsConfig, Quantile};
 
 pub fn as_quantile(value: &Yaml) -> anyhow::Result<Quantile> {
     match value {
@@ -60,6 +60,100 @@ pub fn as_quantile_list(value: &Yaml) -> anyhow::Result<BTreeSet<Quantile>> {
     Ok(set)
 }
 
+fn as_bucket_bounds(value: &Yaml) -> anyhow::Result<Vec<u64>> {
+    match value {
+        Yaml::String(s) => {
+            let mut bounds = Vec::new();
+            for v in s.split(',') {
+                let bound =
+                    u64::from_str(v.trim()).map_err(|e| anyhow!("invalid bound {v}: {e}"))?;
+                bounds.push(bound);
+            }
+            Ok(bounds)
+        }
+        Yaml::Array(seq) => {
+            let mut bounds = Vec::with_capacity(seq.len());
+            for (i, v) in seq.iter().enumerate() {
+                let bound = crate::value::as_u64(v)
+                    .context(format!("invalid u64 value for element #{i}"))?;
+                bounds.push(bound);
+            }
+            Ok(bounds)
+        }
+        _ => Err(anyhow!(
+            "the yaml value type for 'histogram bucket bounds' should be 'seq' or 'str'"
+        )),
+    }
+}
+
+pub fn as_histogram_buckets(value: &Yaml) -> anyhow::Result<HistogramBuckets> {
+    let Yaml::Hash(map) = value else {
+        let bounds = as_bucket_bounds(value)?;
+        return HistogramBuckets::explicit(bounds)
+            .map_err(|e| anyhow!("invalid explicit bucket bounds: {e}"));
+    };
+
+    let layout = crate::hash_get_required_str(map, "type")?;
+    match crate::key::normalize(layout).as_str() {
+        "explicit" => {
+            let v = crate::hash_get_required(map, "bounds")?;
+            let bounds =
+                as_bucket_bounds(v).context("invalid bucket bounds value for key bounds")?;
+            HistogramBuckets::explicit(bounds)
+                .map_err(|e| anyhow!("invalid explicit bucket bounds: {e}"))
+        }
+        "log_linear" | "loglinear" => {
+            let mut min = 1;
+            let mut max = 0;
+            let mut steps = 9;
+            crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+                "type" => Ok(()),
+                "min" => {
+                    min = crate::value::as_u64(v)
+                        .context(format!("invalid u64 value for key {k}"))?;
+                    Ok(())
+                }
+                "max" => {
+                    max = crate::value::as_u64(v)
+                        .context(format!("invalid u64 value for key {k}"))?;
+                    Ok(())
+                }
+                "steps" => {
+                    steps = crate::value::as_u16(v)
+                        .context(format!("invalid u16 value for key {k}"))?;
+                    Ok(())
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+            HistogramBuckets::log_linear(min, max, steps)
+                .map_err(|e| anyhow!("invalid log linear buckets: {e}"))
+        }
+        "exponential" | "base2_exponential" => {
+            let mut scale = 20;
+            let mut max_size = 160;
+            crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+                "type" => Ok(()),
+                "scale" => {
+                    let v = crate::value::as_i32(v)
+                        .context(format!("invalid i32 value for key {k}"))?;
+                    scale = i8::try_from(v)
+                        .map_err(|_| anyhow!("out of range scale value {v} for key {k}"))?;
+                    Ok(())
+                }
+                "max_size" => {
+                    max_size = crate::value::as_u16(v)
+                        .context(format!("invalid u16 value for key {k}"))?;
+                    Ok(())
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+            HistogramBuckets::exponential(scale, max_size)
+                .map_err(|e| anyhow!("invalid exponential buckets: {e}"))
+        }
+        _ => Err(anyhow!("unsupported histogram bucket type {layout}")),
+    }
+}
+
 pub fn as_histogram_metrics_config(value: &Yaml) -> anyhow::Result<HistogramMetricsConfig> {
     if let Yaml::Hash(map) = value {
         let mut config = HistogramMetricsConfig::default();
@@ -76,6 +170,12 @@ pub fn as_histogram_metrics_config(value: &Yaml) -> anyhow::Result<HistogramMetr
                 config.set_rotate_interval(rotate);
                 Ok(())
             }
+            "bucket" | "buckets" => {
+                let buckets = as_histogram_buckets(v)
+                    .context(format!("invalid histogram buckets value for key {k}"))?;
+                config.set_buckets(buckets);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         })?;
         Ok(config)
@@ -51,7 +51,9 @@ pub mod acl_set;
 #[cfg(feature = "histogram")]
 mod histogram;
 #[cfg(feature = "histogram")]
-pub use histogram::{as_histogram_metrics_config, as_quantile, as_quantile_list};
+pub use histogram::{
+    as_histogram_buckets, as_histogram_metrics_config, as_quantile, as_quantile_list,
+};
 
 #[cfg(feature = "resolve")]
 mod resolve;
@@ -73,15 +75,15 @@ pub use self::openssl::as_openssl_tlcp_certificate_pair;
 #[cfg(feature = "openssl")]
 pub use self::openssl::{
     as_openssl_certificate_pair, as_openssl_certificates, as_openssl_private_key,
-    as_openssl_tls_server_config_builder, as_tls_interception_client_config_builder,
-    as_tls_interception_server_config_builder, as_to_many_openssl_tls_client_config_builder,
-    as_to_one_openssl_tls_client_config_builder,
+    as_openssl_spki_pin_set, as_openssl_tls_server_config_builder,
+    as_tls_interception_client_config_builder, as_tls_interception_server_config_builder,
+    as_to_many_openssl_tls_client_config_builder, as_to_one_openssl_tls_client_config_builder,
 };
 
 #[cfg(feature = "quinn")]
 mod quinn;
 #[cfg(feature = "quinn")]
-pub use quinn::as_quinn_transport_config;
+pub use quinn::{as_quinn_migration_config, as_quinn_transport_config};
 
 #[cfg(all(unix, not(target_os = "openbsd"), feature = "sched"))]
 mod sched;
@@ -18,6 +18,7 @@ use std::path::Path;
 use std::str::FromStr;
 
 use anyhow::{anyhow, Context};
+use url::Url;
 use yaml_rust::Yaml;
 
 use g3_types::net::{DnsEncryptionConfigBuilder, DnsEncryptionProtocol};
@@ -60,11 +61,40 @@ pub fn as_dns_encryption_protocol_builder(
                     config.set_tls_client_config(builder);
                     Ok(())
                 }
+                "query_path" | "path" => {
+                    let path = crate::value::as_string(v)?;
+                    config
+                        .set_query_path(&path)
+                        .context(format!("invalid query path value for key {k}"))
+                }
+                #[cfg(feature = "quinn")]
+                "quic_transport" => {
+                    let transport = crate::value::as_quinn_transport_config(v)
+                        .context(format!("invalid quinn transport config value for key {k}"))?;
+                    config.set_quic_transport(transport);
+                    Ok(())
+                }
+                #[cfg(feature = "quinn")]
+                "enable_0rtt" => {
+                    let enable = crate::value::as_bool(v)?;
+                    config.set_enable_0rtt(enable);
+                    Ok(())
+                }
                 _ => Err(anyhow!("invalid key {k}")),
             })?;
 
             Ok(config)
         }
+        Yaml::String(s) if s.contains("://") => {
+            let url = Url::parse(s).map_err(|e| anyhow!("invalid url string: {e}"))?;
+            let (config, port) = DnsEncryptionConfigBuilder::from_url(&url)?;
+            if port.is_some() {
+                return Err(anyhow!(
+                    "port is not allowed in the url, it should be set via server port"
+                ));
+            }
+            Ok(config)
+        }
         Yaml::String(_) => {
             let name = crate::value::as_rustls_server_name(value)
                 .context("the string type value should be valid tls server name")?;
@@ -14,10 +14,13 @@
  * limitations under the License.
  */
 
-use yaml_rust::Yaml;
+use yaml_rust::{yaml, Yaml};
 
 use anyhow::{anyhow, Context};
-use g3_types::net::ProxyProtocolVersion;
+use g3_types::net::{
+    ProxyProtocolTlv, ProxyProtocolTlvConfig, ProxyProtocolVersion, PP2_TYPE_ALPN,
+    PP2_TYPE_AUTHORITY, PP2_TYPE_NETNS, PP2_TYPE_SSL, PP2_TYPE_UNIQUE_ID,
+};
 
 pub fn as_proxy_protocol_version(value: &Yaml) -> anyhow::Result<ProxyProtocolVersion> {
     let v =
@@ -28,3 +31,149 @@ pub fn as_proxy_protocol_version(value: &Yaml) -> anyhow::Result<ProxyProtocolVe
         _ => Err(anyhow!("unsupported PROXY protocol version {v}")),
     }
 }
+
+fn as_proxy_protocol_tlv_type(value: &Yaml) -> anyhow::Result<u8> {
+    if let Yaml::String(s) = value {
+        match crate::key::normalize(s).as_str() {
+            "alpn" => return Ok(PP2_TYPE_ALPN),
+            "authority" => return Ok(PP2_TYPE_AUTHORITY),
+            "unique_id" => return Ok(PP2_TYPE_UNIQUE_ID),
+            "ssl" => return Ok(PP2_TYPE_SSL),
+            "netns" => return Ok(PP2_TYPE_NETNS),
+            _ => {}
+        }
+    }
+    crate::value::as_u8(value).context("TLV type should be a valid u8 value or a known type name")
+}
+
+fn as_proxy_protocol_tlv(value: &Yaml) -> anyhow::Result<ProxyProtocolTlv> {
+    let Yaml::Hash(map) = value else {
+        return Err(anyhow!(
+            "yaml value type for 'ProxyProtocolTlv' should be 'map'"
+        ));
+    };
+
+    let mut kind: Option<u8> = None;
+    let mut data = Vec::new();
+    crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+        "type" => {
+            kind =
+                Some(as_proxy_protocol_tlv_type(v).context(format!("invalid value for key {k}"))?);
+            Ok(())
+        }
+        "value" => {
+            let s =
+                crate::value::as_string(v).context(format!("invalid string value for key {k}"))?;
+            data = s.into_bytes();
+            Ok(())
+        }
+        _ => Err(anyhow!("invalid key {k}")),
+    })?;
+
+    let Some(kind) = kind else {
+        return Err(anyhow!("no TLV type set"));
+    };
+    if data.len() > u16::MAX as usize {
+        return Err(anyhow!("too long TLV value"));
+    }
+    Ok(ProxyProtocolTlv::new(kind, data))
+}
+
+fn set_forward_types(config: &mut ProxyProtocolTlvConfig, value: &Yaml) -> anyhow::Result<()> {
+    match value {
+        Yaml::Boolean(true) => config.set_forward_all(),
+        Yaml::Boolean(false) => {}
+        Yaml::String(s) if s.eq_ignore_ascii_case("all") => config.set_forward_all(),
+        Yaml::Array(seq) => {
+            for (i, v) in seq.iter().enumerate() {
+                let kind = as_proxy_protocol_tlv_type(v)
+                    .context(format!("invalid TLV type value for #{i}"))?;
+                config.add_forward_type(kind);
+            }
+        }
+        _ => {
+            let kind = as_proxy_protocol_tlv_type(value)?;
+            config.add_forward_type(kind);
+        }
+    }
+    Ok(())
+}
+
+fn as_proxy_protocol_tlv_config_map(map: &yaml::Hash) -> anyhow::Result<ProxyProtocolTlvConfig> {
+    let mut config = ProxyProtocolTlvConfig::default();
+    crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+        "forward" => set_forward_types(&mut config, v)
+            .context(format!("invalid forward TLV types value for key {k}")),
+        "unique_id" => {
+            let enable =
+                crate::value::as_bool(v).context(format!("invalid bool value for key {k}"))?;
+            config.set_unique_id(enable);
+            Ok(())
+        }
+        "custom" => {
+            if let Yaml::Array(seq) = v {
+                for (i, v) in seq.iter().enumerate() {
+                    let tlv = as_proxy_protocol_tlv(v)
+                        .context(format!("invalid TLV value for {k}#{i}"))?;
+                    config.add_custom(tlv);
+                }
+            } else {
+                let tlv =
+                    as_proxy_protocol_tlv(v).context(format!("invalid TLV value for key {k}"))?;
+                config.add_custom(tlv);
+            }
+            Ok(())
+        }
+        _ => Err(anyhow!("invalid key {k}")),
+    })?;
+    Ok(config)
+}
+
+/// Parse the config for the TLVs to send in PROXY protocol v2 header.
+///
+/// A map value with keys `forward`, `unique_id` and `custom` can be used,
+/// or set the TLV types to forward directly.
+pub fn as_proxy_protocol_tlv_config(value: &Yaml) -> anyhow::Result<ProxyProtocolTlvConfig> {
+    if let Yaml::Hash(map) = value {
+        as_proxy_protocol_tlv_config_map(map)
+    } else {
+        let mut config = ProxyProtocolTlvConfig::default();
+        set_forward_types(&mut config, value)?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn tlv_config() {
+        let yaml = YamlLoader::load_from_str(
+            r#"
+            forward: [authority, 0xE0]
+            unique_id: true
+            custom:
+              - type: 0xE1
+                value: "abc"
+            "#,
+        )
+        .unwrap();
+        let config = as_proxy_protocol_tlv_config(&yaml[0]).unwrap();
+        assert!(config.unique_id());
+        assert_eq!(
+            config.custom_tlvs(),
+            &[ProxyProtocolTlv::new(0xE1, b"abc".to_vec())]
+        );
+        let received = [
+            ProxyProtocolTlv::new(PP2_TYPE_AUTHORITY, b"example.net".to_vec()),
+            ProxyProtocolTlv::new(PP2_TYPE_ALPN, b"h2".to_vec()),
+        ];
+        assert_eq!(config.forward_tlvs(&received).count(), 1);
+
+        let yaml = Yaml::String("all".to_string());
+        let config = as_proxy_protocol_tlv_config(&yaml).unwrap();
+        assert_eq!(config.forward_tlvs(&received).count(), 2);
+    }
+}
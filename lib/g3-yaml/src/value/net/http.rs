@@ -22,7 +22,8 @@ use http::HeaderName;
 use yaml_rust::Yaml;
 
 use g3_types::net::{
-    HttpForwardCapability, HttpForwardedHeaderType, HttpKeepAliveConfig, HttpServerId,
+    HttpForwardCapability, HttpForwardClientCertMode, HttpForwardedHeaderType, HttpKeepAliveConfig,
+    HttpServerId,
 };
 
 pub fn as_http_keepalive_config(v: &Yaml) -> anyhow::Result<HttpKeepAliveConfig> {
@@ -41,6 +42,11 @@ pub fn as_http_keepalive_config(v: &Yaml) -> anyhow::Result<HttpKeepAliveConfig>
                     config.set_idle_expire(idle_expire);
                     Ok(())
                 }
+                "max_lifetime" => {
+                    let max_lifetime = crate::humanize::as_duration(v)?;
+                    config.set_max_lifetime(max_lifetime);
+                    Ok(())
+                }
                 _ => Err(anyhow!("invalid key {k}")),
             })?;
         }
@@ -75,6 +81,17 @@ pub fn as_http_forwarded_header_type(value: &Yaml) -> anyhow::Result<HttpForward
     }
 }
 
+pub fn as_http_forward_client_cert_mode(value: &Yaml) -> anyhow::Result<HttpForwardClientCertMode> {
+    if let Yaml::String(s) = value {
+        HttpForwardClientCertMode::from_str(s)
+            .map_err(|_| anyhow!("invalid string value for 'HttpForwardClientCertMode'"))
+    } else {
+        Err(anyhow!(
+            "yaml value type for 'HttpForwardClientCertMode' should be 'string'"
+        ))
+    }
+}
+
 pub fn as_http_forward_capability(value: &Yaml) -> anyhow::Result<HttpForwardCapability> {
     let mut cap = HttpForwardCapability::default();
 
@@ -26,6 +26,8 @@ mod udp;
 
 #[cfg(unix)]
 mod interface;
+#[cfg(unix)]
+mod unix;
 
 #[cfg(feature = "http")]
 mod http;
@@ -38,27 +40,29 @@ pub use base::{
     as_upstream_addr, as_url, as_weighted_sockaddr, as_weighted_upstream_addr,
 };
 pub use buf::as_socket_buffer_config;
-pub use haproxy::as_proxy_protocol_version;
+pub use haproxy::{as_proxy_protocol_tlv_config, as_proxy_protocol_version};
 pub use pool::as_connection_pool_config;
 pub use port::{as_port_range, as_ports};
 pub use proxy::as_proxy_request_type;
 pub use tcp::{
-    as_happy_eyeballs_config, as_tcp_connect_config, as_tcp_keepalive_config, as_tcp_listen_config,
-    as_tcp_misc_sock_opts,
+    as_happy_eyeballs_config, as_tcp_connect_config, as_tcp_connect_race_config,
+    as_tcp_keepalive_config, as_tcp_listen_config, as_tcp_misc_sock_opts,
 };
 pub use tls::as_tls_version;
 pub use udp::{as_udp_listen_config, as_udp_misc_sock_opts};
 
 #[cfg(unix)]
 pub use interface::as_interface_name;
+#[cfg(unix)]
+pub use unix::as_unix_listen_config;
 
 #[cfg(feature = "acl-rule")]
 pub use base::as_ip_network;
 
 #[cfg(feature = "http")]
 pub use self::http::{
-    as_http_forward_capability, as_http_forwarded_header_type, as_http_header_name,
-    as_http_keepalive_config, as_http_path_and_query, as_http_server_id,
+    as_http_forward_capability, as_http_forward_client_cert_mode, as_http_forwarded_header_type,
+    as_http_header_name, as_http_keepalive_config, as_http_path_and_query, as_http_server_id,
 };
 
 #[cfg(feature = "rustls")]
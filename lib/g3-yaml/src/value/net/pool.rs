@@ -45,6 +45,12 @@ pub fn as_connection_pool_config(value: &Yaml) -> anyhow::Result<ConnectionPoolC
                 config.set_idle_timeout(timeout);
                 Ok(())
             }
+            "max_lifetime" => {
+                let lifetime = crate::humanize::as_duration(v)
+                    .context(format!("invalid humanize duration value for key {k}"))?;
+                config.set_max_lifetime(lifetime);
+                Ok(())
+            }
             _ => Err(anyhow!("invalid key {k}")),
         })?;
         Ok(config)
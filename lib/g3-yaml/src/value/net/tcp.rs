@@ -20,7 +20,8 @@ use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
 use g3_types::net::{
-    HappyEyeballsConfig, TcpConnectConfig, TcpKeepAliveConfig, TcpListenConfig, TcpMiscSockOpts,
+    HappyEyeballsConfig, TcpConnectConfig, TcpConnectRaceConfig, TcpKeepAliveConfig,
+    TcpListenConfig, TcpMiscSockOpts,
 };
 
 fn set_tcp_listen_scale(config: &mut TcpListenConfig, v: &Yaml) -> anyhow::Result<()> {
@@ -115,6 +116,14 @@ pub fn as_tcp_listen_config(value: &Yaml) -> anyhow::Result<TcpListenConfig> {
                     config.set_mark(mark);
                     Ok(())
                 }
+                #[cfg(target_os = "freebsd")]
+                "accept_filter" => {
+                    let name = crate::value::as_string(v)
+                        .context(format!("invalid string value for key {k}"))?;
+                    config
+                        .set_accept_filter(&name)
+                        .context(format!("invalid accept filter value for key {k}"))
+                }
                 "scale" => set_tcp_listen_scale(&mut config, v)
                     .context(format!("invalid scale value for key {k}")),
                 _ => Err(anyhow!("invalid key {k}")),
@@ -189,6 +198,44 @@ pub fn as_happy_eyeballs_config(v: &Yaml) -> anyhow::Result<HappyEyeballsConfig>
     }
 }
 
+pub fn as_tcp_connect_race_config(v: &Yaml) -> anyhow::Result<TcpConnectRaceConfig> {
+    let mut config = TcpConnectRaceConfig::default();
+
+    match v {
+        Yaml::Hash(map) => {
+            config.set_enable(true);
+            crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+                "enable" => {
+                    let enable = crate::value::as_bool(v)?;
+                    config.set_enable(enable);
+                    Ok(())
+                }
+                "resolution_delay" => {
+                    let delay = crate::humanize::as_duration(v)?;
+                    config.set_resolution_delay(delay);
+                    Ok(())
+                }
+                "stagger" | "stagger_delay" => {
+                    let stagger = crate::humanize::as_duration(v)?;
+                    config.set_stagger(stagger);
+                    Ok(())
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+        }
+        Yaml::Boolean(enable) => {
+            config.set_enable(*enable);
+        }
+        _ => {
+            return Err(anyhow!(
+                "yaml value type for 'TcpConnectRaceConfig' should be 'map' or 'bool'"
+            ));
+        }
+    }
+
+    Ok(config)
+}
+
 pub fn as_tcp_keepalive_config(v: &Yaml) -> anyhow::Result<TcpKeepAliveConfig> {
     let mut config = TcpKeepAliveConfig::default();
 
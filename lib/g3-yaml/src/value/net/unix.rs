@@ -0,0 +1,123 @@
+/*
+ * Copyright 2025 ByteDance and/or its affiliates.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use anyhow::{anyhow, Context};
+use yaml_rust::Yaml;
+
+use g3_types::net::UnixListenConfig;
+
+fn as_file_mode(v: &Yaml) -> anyhow::Result<u32> {
+    match v {
+        Yaml::Integer(i) => u32::try_from(*i).map_err(|e| anyhow!("out of range u32 value: {e}")),
+        Yaml::String(s) => {
+            let s = s.strip_prefix("0o").unwrap_or(s);
+            u32::from_str_radix(s, 8).map_err(|e| anyhow!("invalid octal string: {e}"))
+        }
+        _ => Err(anyhow!(
+            "yaml value type for file mode should be 'integer' or 'octal string'"
+        )),
+    }
+}
+
+pub fn as_unix_listen_config(value: &Yaml) -> anyhow::Result<UnixListenConfig> {
+    let mut config = UnixListenConfig::default();
+
+    match value {
+        Yaml::String(_) => {
+            let path = crate::value::as_absolute_path(value)
+                .context("invalid unix listen socket path value")?;
+            config.set_path(path);
+        }
+        Yaml::Hash(map) => {
+            crate::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+                "path" => {
+                    let path = crate::value::as_absolute_path(v)
+                        .context(format!("invalid absolute path value for key {k}"))?;
+                    config.set_path(path);
+                    Ok(())
+                }
+                "mode" | "file_mode" => {
+                    let mode =
+                        as_file_mode(v).context(format!("invalid file mode value for key {k}"))?;
+                    config
+                        .set_mode(mode)
+                        .context(format!("unsupported file mode value for key {k}"))
+                }
+                "owner" | "uid" => {
+                    let uid = crate::value::as_u32(v)
+                        .context(format!("invalid u32 value for key {k}"))?;
+                    config.set_owner(uid);
+                    Ok(())
+                }
+                "group" | "gid" => {
+                    let gid = crate::value::as_u32(v)
+                        .context(format!("invalid u32 value for key {k}"))?;
+                    config.set_group(gid);
+                    Ok(())
+                }
+                "backlog" => {
+                    let backlog = crate::value::as_u32(v)
+                        .context(format!("invalid u32 value for key {k}"))?;
+                    config.set_backlog(backlog);
+                    Ok(())
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+        }
+        _ => return Err(anyhow!("invalid value type")),
+    }
+
+    config.check()?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn t_unix_listen() {
+        let yaml = YamlLoader::load_from_str("/run/g3proxy/http.sock").unwrap();
+        let config = as_unix_listen_config(&yaml[0]).unwrap();
+        assert_eq!(config.path().to_str(), Some("/run/g3proxy/http.sock"));
+        assert!(config.mode().is_none());
+
+        let yaml = YamlLoader::load_from_str(
+            r#"
+            path: /run/g3proxy/socks.sock
+            mode: "0660"
+            owner: 1000
+            group: 1000
+            "#,
+        )
+        .unwrap();
+        let config = as_unix_listen_config(&yaml[0]).unwrap();
+        assert_eq!(config.mode(), Some(0o660));
+        assert_eq!(config.owner(), Some(1000));
+        assert_eq!(config.group(), Some(1000));
+
+        let yaml = YamlLoader::load_from_str("mode: 0o600\npath: /tmp/a.sock").unwrap();
+        let config = as_unix_listen_config(&yaml[0]).unwrap();
+        assert_eq!(config.mode(), Some(0o600));
+
+        let yaml = YamlLoader::load_from_str("relative/a.sock").unwrap();
+        assert!(as_unix_listen_config(&yaml[0]).is_err());
+
+        let yaml = YamlLoader::load_from_str("path: /tmp/a.sock\nmode: \"1777\"").unwrap();
+        assert!(as_unix_listen_config(&yaml[0]).is_err());
+    }
+}
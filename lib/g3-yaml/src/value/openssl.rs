@@ -26,6 +26,7 @@ use yaml_rust::Yaml;
 use g3_types::net::{
     OpensslCertificatePair, OpensslClientConfigBuilder, OpensslInterceptionClientConfigBuilder,
     OpensslInterceptionServerConfigBuilder, OpensslProtocol, OpensslServerConfigBuilder,
+    OpensslSpkiPinSet,
 };
 
 #[cfg(feature = "tongsuo")]
@@ -139,6 +140,29 @@ pub fn as_openssl_certificate_pair(
     }
 }
 
+/// Parse the hex encoded sha256 digest of the peer public key, or a list of them
+pub fn as_openssl_spki_pin_set(value: &Yaml) -> anyhow::Result<OpensslSpkiPinSet> {
+    let mut pin_set = OpensslSpkiPinSet::default();
+    match value {
+        Yaml::String(s) => pin_set.add_hex(s)?,
+        Yaml::Array(seq) => {
+            for (i, v) in seq.iter().enumerate() {
+                let s = crate::value::as_string(v)
+                    .context(format!("invalid string value for element #{i}"))?;
+                pin_set
+                    .add_hex(&s)
+                    .context(format!("invalid pin value for element #{i}"))?;
+            }
+        }
+        _ => {
+            return Err(anyhow!(
+                "yaml value type for openssl spki pin set should be 'string' or 'array'"
+            ));
+        }
+    }
+    Ok(pin_set)
+}
+
 #[cfg(feature = "tongsuo")]
 pub fn as_openssl_tlcp_certificate_pair(
     value: &Yaml,
@@ -14,10 +14,23 @@
  * limitations under the License.
  */
 
+use std::net::SocketAddr;
+use std::str::FromStr;
+
 use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
-use g3_types::net::QuinnTransportConfigBuilder;
+use g3_types::net::{QuinnCongestionControl, QuinnMigrationConfig, QuinnTransportConfigBuilder};
+
+fn as_quinn_congestion_control(value: &Yaml) -> anyhow::Result<QuinnCongestionControl> {
+    if let Yaml::String(s) = value {
+        QuinnCongestionControl::from_str(s)
+    } else {
+        Err(anyhow!(
+            "yaml value type for quinn congestion control should be 'string'"
+        ))
+    }
+}
 
 pub fn as_quinn_transport_config(value: &Yaml) -> anyhow::Result<QuinnTransportConfigBuilder> {
     let Yaml::Hash(map) = value else {
@@ -59,7 +72,62 @@ pub fn as_quinn_transport_config(value: &Yaml) -> anyhow::Result<QuinnTransportC
             config.set_send_window(size);
             Ok(())
         }
+        "congestion_control" => {
+            let cc = as_quinn_congestion_control(v).context(format!(
+                "invalid quinn congestion control value for key {k}"
+            ))?;
+            config.set_congestion_control(cc);
+            Ok(())
+        }
         _ => Err(anyhow!("invalid key {k}")),
     })?;
     Ok(config)
 }
+
+pub fn as_quinn_migration_config(value: &Yaml) -> anyhow::Result<QuinnMigrationConfig> {
+    let mut config = QuinnMigrationConfig::default();
+    match value {
+        Yaml::Hash(map) => {
+            crate::hash::foreach_kv(map, |k, v| match crate::key::normalize(k).as_str() {
+                "enable" => {
+                    let enable = crate::value::as_bool(v)
+                        .context(format!("invalid bool value for key {k}"))?;
+                    config.set_enable(enable);
+                    Ok(())
+                }
+                "preferred_address_v4" => {
+                    let addr = crate::value::as_sockaddr(v)
+                        .context(format!("invalid socket address value for key {k}"))?;
+                    let SocketAddr::V4(addr) = addr else {
+                        return Err(anyhow!("the address for key {k} should be ipv4"));
+                    };
+                    config.set_preferred_address_v4(addr);
+                    Ok(())
+                }
+                "preferred_address_v6" => {
+                    let addr = crate::value::as_sockaddr(v)
+                        .context(format!("invalid socket address value for key {k}"))?;
+                    let SocketAddr::V6(addr) = addr else {
+                        return Err(anyhow!("the address for key {k} should be ipv6"));
+                    };
+                    config.set_preferred_address_v6(addr);
+                    Ok(())
+                }
+                "watch_interval" => {
+                    let interval = crate::humanize::as_duration(v)
+                        .context(format!("invalid humanize duration value for key {k}"))?;
+                    config.set_watch_interval(Some(interval));
+                    Ok(())
+                }
+                _ => Err(anyhow!("invalid key {k}")),
+            })?;
+        }
+        _ => {
+            let enable = crate::value::as_bool(value).context(
+                "the yaml value type for quinn migration config should be 'map' or 'bool'",
+            )?;
+            config.set_enable(enable);
+        }
+    }
+    Ok(config)
+}
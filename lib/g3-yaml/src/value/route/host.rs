@@ -20,7 +20,7 @@ use anyhow::{anyhow, Context};
 use yaml_rust::Yaml;
 
 use g3_types::net::Host;
-use g3_types::route::HostMatch;
+use g3_types::route::{HostMatch, HostPattern};
 
 use crate::{YamlDocPosition, YamlMapCallback};
 
@@ -152,3 +152,23 @@ where
 
     Ok(obj)
 }
+
+pub fn as_host_glob_pattern(value: &Yaml) -> anyhow::Result<HostPattern> {
+    if let Yaml::String(s) = value {
+        HostPattern::new_glob(s)
+    } else {
+        Err(anyhow!(
+            "yaml value type for 'host glob pattern' should be 'string'"
+        ))
+    }
+}
+
+pub fn as_host_regex_pattern(value: &Yaml) -> anyhow::Result<HostPattern> {
+    if let Yaml::String(s) = value {
+        HostPattern::new_regex(s)
+    } else {
+        Err(anyhow!(
+            "yaml value type for 'host regex pattern' should be 'string'"
+        ))
+    }
+}
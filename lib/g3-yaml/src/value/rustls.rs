@@ -268,6 +268,11 @@ pub fn as_rustls_server_config_builder(
                 }
                 Ok(())
             }
+            "client_auth_optional" => {
+                let optional = crate::value::as_bool(v)?;
+                builder.set_client_auth_optional(optional);
+                Ok(())
+            }
             "use_session_ticket" => {
                 let enable = crate::value::as_bool(v)?;
                 builder.set_use_session_ticket(enable);
@@ -0,0 +1,28 @@
+#!/bin/sh
+
+
+test_socks_conformance()
+{
+	python3 "${PROJECT_DIR}/g3proxy/ci/python3+socket/test_socks5.py" -x ${SOCKS_PROXY} -T httpbin.local:80
+}
+
+
+test_http_connect_conformance()
+{
+	python3 "${PROJECT_DIR}/g3proxy/ci/python3+socket/test_http_connect.py" -x ${HTTP_PROXY} -T httpbin.local:80
+}
+
+
+test_http_chunked_conformance()
+{
+	python3 "${PROJECT_DIR}/g3proxy/ci/python3+socket/test_chunked.py" -x ${HTTP_PROXY} -T http://httpbin.local/post
+}
+
+
+SOCKS_PROXY="127.0.0.1:1080"
+test_socks_conformance
+
+
+HTTP_PROXY="127.0.0.1:8080"
+test_http_connect_conformance
+test_http_chunked_conformance